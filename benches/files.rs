@@ -56,7 +56,9 @@ fn benchmark_files_no_partitions(c: &mut Criterion) {
                         path: output.try_into().unwrap(),
                         idle_timeout: Duration::from_secs(30),
                         encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+                        batch_encoding: None,
                         compression: sinks::file::Compression::None,
+                        rotation: None,
                         acknowledgements: Default::default(),
                         timezone: Default::default(),
                         internal_metrics: Default::default(),
@@ -106,6 +106,46 @@ fn git_short_hash() -> std::io::Result<String> {
     })
 }
 
+/// Compiles the eBPF programs of the `ebpf_flows` source with clang, along with the BTF the loader
+/// uses to relocate their accesses to the kernel structures.
+#[cfg(feature = "sources-ebpf_flows")]
+fn compile_ebpf_flows_programs(tracker: &mut TrackedEnv) {
+    let target_os = tracker.get_env_var("CARGO_CFG_TARGET_OS");
+    let target_arch = match tracker.get_env_var("CARGO_CFG_TARGET_ARCH").as_deref() {
+        Some("x86_64") => "x86",
+        Some("aarch64") => "arm64",
+        // The source isn't built for the other targets.
+        _ => return,
+    };
+    if target_os.as_deref() != Some("linux") {
+        return;
+    }
+
+    let clang = tracker
+        .get_env_var("CLANG")
+        .unwrap_or_else(|| "clang".to_string());
+    let output = Path::new(&env::var("OUT_DIR").expect("OUT_DIR environment variable not set"))
+        .join("ebpf_flows.bpf.o");
+
+    let mut command = Command::new(clang);
+    command
+        .args(["-target", "bpf", "-O2", "-g", "-Wall"])
+        .arg(format!("-D__TARGET_ARCH_{}", target_arch));
+    // The directory holding the `bpf` headers of libbpf, if they aren't installed system-wide.
+    if let Some(include_dir) = tracker.get_env_var("LIBBPF_INCLUDE_DIR") {
+        command.arg("-I").arg(include_dir);
+    }
+    let status = command
+        .args(["-c", "src/sources/ebpf_flows/programs/flows.bpf.c", "-o"])
+        .arg(output)
+        .status()
+        .expect("clang is required to build the eBPF programs of the `ebpf_flows` source");
+    assert!(
+        status.success(),
+        "Could not compile the eBPF programs of the `ebpf_flows` source"
+    );
+}
+
 fn main() {
     // Always rerun if the build script itself changes.
     println!("cargo:rerun-if-changed=build.rs");
@@ -117,6 +157,7 @@ fn main() {
     #[cfg(feature = "protobuf-build")]
     {
         println!("cargo:rerun-if-changed=proto/third-party/dnstap.proto");
+        println!("cargo:rerun-if-changed=proto/third-party/google/cloud/bigquery/storage/v1/storage.proto");
         println!("cargo:rerun-if-changed=proto/third-party/google/pubsub/v1/pubsub.proto");
         println!("cargo:rerun-if-changed=proto/third-party/google/rpc/status.proto");
         println!("cargo:rerun-if-changed=proto/vector/dd_metric.proto");
@@ -145,6 +186,7 @@ fn main() {
                 &[
                     "lib/vector-core/proto/event.proto",
                     "proto/third-party/dnstap.proto",
+                    "proto/third-party/google/cloud/bigquery/storage/v1/storage.proto",
                     "proto/vector/ddsketch_full.proto",
                     "proto/vector/dd_metric.proto",
                     "proto/vector/dd_trace.proto",
@@ -165,6 +207,13 @@ fn main() {
     // inform Cargo when it needs to rerun this build script.  This allows us to avoid rerunning it
     // every single time unless something _actually_ changes.
     let mut tracker = TrackedEnv::new();
+
+    #[cfg(feature = "sources-ebpf_flows")]
+    {
+        println!("cargo:rerun-if-changed=src/sources/ebpf_flows/programs/flows.bpf.c");
+        println!("cargo:rerun-if-changed=src/sources/ebpf_flows/programs/vmlinux.h");
+        compile_ebpf_flows_programs(&mut tracker);
+    }
     let pkg_name = tracker
         .get_env_var("CARGO_PKG_NAME")
         .expect("Cargo-provided environment variables should always exist!");
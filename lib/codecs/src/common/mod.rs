@@ -1,3 +1,4 @@
 //! A collection of common utility features used by both encoding and decoding logic.
 
 pub mod length_delimited;
+pub mod schema_registry;
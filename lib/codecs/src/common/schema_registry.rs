@@ -0,0 +1,51 @@
+//! The Confluent wire format, used by codecs reading and writing messages whose schemas are
+//! registered in the [Confluent Schema Registry][schema_registry], and a client of the registry,
+//! behind the `schema_registry` feature.
+//!
+//! [schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+
+use bytes::Bytes;
+
+#[cfg(feature = "schema_registry")]
+mod client;
+
+#[cfg(all(test, feature = "schema_registry"))]
+pub(crate) use client::test_util;
+#[cfg(feature = "schema_registry")]
+pub use client::{FailedRequest, SchemaRegistryClient, SchemaRegistryConfig};
+
+/// The magic byte starting messages in the Confluent wire format.
+pub const CONFLUENT_MAGIC_BYTE: u8 = 0;
+
+/// The length of the Confluent wire format prefix: the magic byte followed by the schema ID, as a
+/// big-endian 32-bit integer.
+pub const CONFLUENT_SCHEMA_PREFIX_LEN: usize = 5;
+
+/// Splits the Confluent wire format prefix off `bytes`, returning the schema ID and the message.
+pub fn split_schema_id(bytes: &Bytes) -> vector_common::Result<(u32, Bytes)> {
+    match bytes.get(..CONFLUENT_SCHEMA_PREFIX_LEN) {
+        Some([CONFLUENT_MAGIC_BYTE, id @ ..]) => {
+            let id = u32::from_be_bytes(id.try_into().expect("prefix is 5 bytes long"));
+            Ok((id, bytes.slice(CONFLUENT_SCHEMA_PREFIX_LEN..)))
+        }
+        _ => Err(vector_common::Error::from(
+            "Expected avro datum to be prefixed with schema id",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_schema_id() {
+        let bytes = Bytes::from_static(b"\0\0\0\x01\x2apayload");
+        let (id, message) = split_schema_id(&bytes).unwrap();
+        assert_eq!(id, 298);
+        assert_eq!(message, Bytes::from_static(b"payload"));
+
+        assert!(split_schema_id(&Bytes::from_static(b"\x01\0\0\0\x01")).is_err());
+        assert!(split_schema_id(&Bytes::from_static(b"\0\0")).is_err());
+    }
+}
@@ -0,0 +1,317 @@
+//! The client of the schema registry, behind the `schema_registry` feature.
+
+use std::time::{Duration, Instant};
+
+use reqwest::{header::ACCEPT, Url};
+use serde::{de::DeserializeOwned, Deserialize};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+const CONTENT_TYPE: &str = "application/vnd.schemaregistry.v1+json";
+
+/// The backoff before retrying a failed request for the first time.
+const MIN_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The maximum backoff between retries of a failed request.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+
+const fn default_timeout_secs() -> u64 {
+    10
+}
+
+/// Options for the [Confluent Schema Registry][schema_registry].
+///
+/// [schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct SchemaRegistryConfig {
+    /// The URL of the schema registry.
+    #[configurable(metadata(docs::examples = "http://localhost:8081"))]
+    pub url: String,
+
+    /// The subject the schema is registered under.
+    ///
+    /// Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+    /// this is `<topic>-value`.
+    #[configurable(metadata(docs::examples = "logs-value"))]
+    pub subject: Option<String>,
+
+    /// The username for HTTP basic authentication to the schema registry.
+    #[configurable(metadata(docs::examples = "${SCHEMA_REGISTRY_USERNAME}"))]
+    pub username: Option<String>,
+
+    /// The password for HTTP basic authentication to the schema registry.
+    #[configurable(metadata(docs::examples = "${SCHEMA_REGISTRY_PASSWORD}"))]
+    pub password: Option<SensitiveString>,
+
+    /// The timeout for requests to the schema registry, in seconds.
+    #[serde(default = "default_timeout_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub timeout_secs: u64,
+}
+
+#[derive(Deserialize)]
+struct SchemaResponse {
+    schema: String,
+}
+
+#[derive(Deserialize)]
+struct RegisterResponse {
+    id: u32,
+}
+
+/// A client of the schema registry, shared by the clones of a codec.
+#[derive(Clone, Debug)]
+pub struct SchemaRegistryClient {
+    config: SchemaRegistryConfig,
+    client: reqwest::Client,
+}
+
+impl SchemaRegistryClient {
+    /// Creates a client of the schema registry. No request is sent until one is needed.
+    pub fn new(config: SchemaRegistryConfig) -> vector_common::Result<Self> {
+        // Requests are rare, as their results are cached, so connections aren't kept alive. This
+        // also lets the client be used from the temporary runtimes of `block_on`.
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .pool_max_idle_per_host(0)
+            .build()?;
+        Ok(Self { config, client })
+    }
+
+    /// Fetches the schema registered with the given ID.
+    pub async fn fetch_schema(&self, id: u32) -> vector_common::Result<String> {
+        let url = self.endpoint(&["schemas", "ids", &id.to_string()])?;
+        let response: SchemaResponse = self.request(url, None).await?;
+        Ok(response.schema)
+    }
+
+    /// Registers the schema under the configured subject, returning its ID.
+    ///
+    /// Registering a schema which is already registered under the subject returns its existing ID.
+    pub async fn register_schema(&self, schema: &str) -> vector_common::Result<u32> {
+        let subject = self
+            .config
+            .subject
+            .as_deref()
+            .ok_or("A subject is required to register a schema")?;
+        let url = self.endpoint(&["subjects", subject, "versions"])?;
+        let body = serde_json::json!({ "schema": schema });
+        let response: RegisterResponse = self.request(url, Some(body)).await?;
+        Ok(response.id)
+    }
+
+    fn endpoint(&self, segments: &[&str]) -> vector_common::Result<Url> {
+        let mut url = Url::parse(&self.config.url)?;
+        url.path_segments_mut()
+            .map_err(|()| format!("Invalid schema registry URL: {}", self.config.url))?
+            .pop_if_empty()
+            .extend(segments);
+        Ok(url)
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        url: Url,
+        body: Option<serde_json::Value>,
+    ) -> vector_common::Result<T> {
+        let mut request = match body {
+            Some(body) => self.client.post(url).json(&body),
+            None => self.client.get(url),
+        };
+        if let Some(username) = &self.config.username {
+            request = request.basic_auth(
+                username,
+                self.config.password.as_ref().map(SensitiveString::inner),
+            );
+        }
+
+        let response = request
+            .header(ACCEPT, CONTENT_TYPE)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(response.json().await?)
+    }
+}
+
+/// A failed request to the schema registry, cached so that the request is retried with an
+/// exponential backoff rather than for each message.
+#[derive(Clone, Debug)]
+pub struct FailedRequest {
+    error: String,
+    retry_at: Instant,
+    backoff: Duration,
+}
+
+impl FailedRequest {
+    /// Records a failure, doubling the backoff of the `previous` failure of the same request.
+    pub fn new(error: &vector_common::Error, previous: Option<&Self>) -> Self {
+        let backoff = previous.map_or(MIN_RETRY_BACKOFF, |previous| {
+            (previous.backoff * 2).min(MAX_RETRY_BACKOFF)
+        });
+        Self {
+            error: error.to_string(),
+            retry_at: Instant::now() + backoff,
+            backoff,
+        }
+    }
+
+    /// The error of the request, as long as it's too early to retry it.
+    pub fn pending_error(&self) -> Option<vector_common::Error> {
+        (Instant::now() < self.retry_at).then(|| {
+            format!(
+                "{} (retrying in {}s)",
+                self.error,
+                self.retry_at
+                    .saturating_duration_since(Instant::now())
+                    .as_secs()
+            )
+            .into()
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread::JoinHandle,
+    };
+
+    use super::*;
+
+    /// Serves a single HTTP request with the given JSON body, returning the URL of the server and
+    /// a handle resolving to the request line.
+    pub(crate) fn serve_once(body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            std::io::Read::read_exact(&mut reader, &mut vec![0; content_length]).unwrap();
+
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: {CONTENT_TYPE}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request_line.trim_end().to_owned()
+        });
+
+        (url, handle)
+    }
+
+    pub(crate) fn config(url: String) -> SchemaRegistryConfig {
+        SchemaRegistryConfig {
+            url,
+            subject: Some("logs-value".into()),
+            username: None,
+            password: None,
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+
+    /// The URL of a port nothing listens on.
+    pub(crate) fn unreachable_url() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::runtime::block_on;
+
+    use super::{test_util::*, *};
+
+    fn client(url: String) -> SchemaRegistryClient {
+        SchemaRegistryClient::new(config(url)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn fetches_schema() {
+        let (url, request) = serve_once(r#"{"schema": "\"string\""}"#);
+
+        let schema = client(url).fetch_schema(42).await.unwrap();
+
+        assert_eq!(schema, r#""string""#);
+        assert_eq!(request.join().unwrap(), "GET /schemas/ids/42 HTTP/1.1");
+    }
+
+    #[tokio::test]
+    async fn registers_schema() {
+        let (url, request) = serve_once(r#"{"id": 7}"#);
+
+        let id = client(format!("{url}/registry/"))
+            .register_schema(r#""string""#)
+            .await
+            .unwrap();
+
+        assert_eq!(id, 7);
+        assert_eq!(
+            request.join().unwrap(),
+            "POST /registry/subjects/logs-value/versions HTTP/1.1"
+        );
+    }
+
+    #[test]
+    fn blocks_on_requests_outside_of_runtimes() {
+        let (url, request) = serve_once(r#"{"id": 7}"#);
+
+        let id = block_on(client(url).register_schema(r#""string""#)).unwrap();
+
+        assert_eq!(id, 7);
+        request.join().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn blocks_on_requests_in_multi_threaded_runtimes() {
+        let (url, request) = serve_once(r#"{"schema": "\"string\""}"#);
+
+        let schema = block_on(client(url).fetch_schema(42)).unwrap();
+
+        assert_eq!(schema, r#""string""#);
+        request.join().unwrap();
+    }
+
+    #[test]
+    fn backs_off_failed_requests() {
+        let error = vector_common::Error::from("unreachable");
+        let first = FailedRequest::new(&error, None);
+        assert_eq!(first.backoff, MIN_RETRY_BACKOFF);
+        assert!(first
+            .pending_error()
+            .unwrap()
+            .to_string()
+            .starts_with("unreachable"));
+
+        let mut failure = first;
+        for _ in 0..10 {
+            failure = FailedRequest::new(&error, Some(&failure));
+        }
+        assert_eq!(failure.backoff, MAX_RETRY_BACKOFF);
+
+        let expired = FailedRequest {
+            retry_at: Instant::now(),
+            ..failure
+        };
+        assert!(expired.pending_error().is_none());
+    }
+}
@@ -1,4 +1,13 @@
+#[cfg(feature = "schema_registry")]
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
 use super::Deserializer;
+use crate::common::schema_registry::split_schema_id;
+#[cfg(feature = "schema_registry")]
+use crate::common::schema_registry::{FailedRequest, SchemaRegistryClient, SchemaRegistryConfig};
 use crate::encoding::AvroSerializerOptions;
 use bytes::Buf;
 use bytes::Bytes;
@@ -17,9 +26,6 @@ use vrl::value::KeyString;
 type VrlValue = vrl::value::Value;
 type AvroValue = apache_avro::types::Value;
 
-const CONFLUENT_MAGIC_BYTE: u8 = 0;
-const CONFLUENT_SCHEMA_PREFIX_LEN: usize = 5;
-
 /// Config used to build a `AvroDeserializer`.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AvroDeserializerConfig {
@@ -34,19 +40,27 @@ impl AvroDeserializerConfig {
             avro_options: AvroDeserializerOptions {
                 schema,
                 strip_schema_id_prefix,
+                #[cfg(feature = "schema_registry")]
+                schema_registry: None,
             },
         }
     }
 
     /// Build the `AvroDeserializer` from this configuration.
-    pub fn build(&self) -> AvroDeserializer {
+    pub fn build(&self) -> vector_common::Result<AvroDeserializer> {
         let schema = apache_avro::Schema::parse_str(&self.avro_options.schema)
-            .map_err(|error| format!("Failed building Avro serializer: {}", error))
-            .unwrap();
-        AvroDeserializer {
+            .map_err(|error| format!("Failed building Avro deserializer: {}", error))?;
+        Ok(AvroDeserializer {
             schema,
             strip_schema_id_prefix: self.avro_options.strip_schema_id_prefix,
-        }
+            #[cfg(feature = "schema_registry")]
+            registry_schemas: self
+                .avro_options
+                .schema_registry
+                .clone()
+                .map(RegistrySchemas::new)
+                .transpose()?,
+        })
     }
 
     /// The data type of events that are accepted by `AvroDeserializer`.
@@ -82,6 +96,8 @@ impl From<&AvroDeserializerOptions> for AvroSerializerOptions {
     fn from(value: &AvroDeserializerOptions) -> Self {
         Self {
             schema: value.schema.clone(),
+            #[cfg(feature = "schema_registry")]
+            schema_registry: None,
         }
     }
 }
@@ -106,6 +122,81 @@ pub struct AvroDeserializerOptions {
     /// For Avro datum encoded in Kafka messages, the bytes are prefixed with the schema ID.  Set this to true to strip the schema ID prefix.
     /// According to [Confluent Kafka's document](https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format).
     pub strip_schema_id_prefix: bool,
+
+    /// The [Confluent Schema Registry][schema_registry] to fetch the schemas messages were written
+    /// with from.
+    ///
+    /// When set, messages are expected to be prefixed with the ID of the schema they were written
+    /// with, which is fetched from the registry and cached. Messages are then resolved to `schema`,
+    /// following the Avro [schema resolution][schema_resolution] rules.
+    ///
+    /// [schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+    /// [schema_resolution]: https://avro.apache.org/docs/current/specification/#schema-resolution
+    #[cfg(feature = "schema_registry")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_registry: Option<SchemaRegistryConfig>,
+}
+
+/// A writer schema fetched from a schema registry, or the failure to fetch it.
+#[cfg(feature = "schema_registry")]
+#[derive(Debug, Clone)]
+enum RegistrySchema {
+    Fetched(Arc<apache_avro::Schema>),
+    Failed(FailedRequest),
+}
+
+/// Writer schemas fetched from a schema registry, cached by ID.
+///
+/// Failures are cached as well, so that the messages written with a schema which can't be fetched
+/// are rejected without querying the registry again, until the failed request is retried.
+#[cfg(feature = "schema_registry")]
+#[derive(Debug, Clone)]
+struct RegistrySchemas {
+    client: SchemaRegistryClient,
+    schemas: Arc<RwLock<HashMap<u32, RegistrySchema>>>,
+}
+
+#[cfg(feature = "schema_registry")]
+impl RegistrySchemas {
+    fn new(config: SchemaRegistryConfig) -> vector_common::Result<Self> {
+        Ok(Self {
+            client: SchemaRegistryClient::new(config)?,
+            schemas: Default::default(),
+        })
+    }
+
+    fn get(&self, id: u32) -> vector_common::Result<Arc<apache_avro::Schema>> {
+        let previous_failure = match self.schemas.read().expect("lock poisoned").get(&id) {
+            Some(RegistrySchema::Fetched(schema)) => return Ok(Arc::clone(schema)),
+            Some(RegistrySchema::Failed(failure)) => {
+                if let Some(error) = failure.pending_error() {
+                    return Err(error);
+                }
+                Some(failure.clone())
+            }
+            None => None,
+        };
+
+        let result = vector_common::runtime::block_on(self.fetch(id));
+        let entry = match &result {
+            Ok(schema) => RegistrySchema::Fetched(Arc::clone(schema)),
+            Err(error) => {
+                RegistrySchema::Failed(FailedRequest::new(error, previous_failure.as_ref()))
+            }
+        };
+        self.schemas
+            .write()
+            .expect("lock poisoned")
+            .insert(id, entry);
+        result
+    }
+
+    async fn fetch(&self, id: u32) -> vector_common::Result<Arc<apache_avro::Schema>> {
+        let schema = self.client.fetch_schema(id).await?;
+        let schema = apache_avro::Schema::parse_str(&schema)
+            .map_err(|error| format!("Invalid Avro schema {} in the registry: {}", id, error))?;
+        Ok(Arc::new(schema))
+    }
 }
 
 /// Serializer that converts bytes to an `Event` using the Apache Avro format.
@@ -113,6 +204,8 @@ pub struct AvroDeserializerOptions {
 pub struct AvroDeserializer {
     schema: apache_avro::Schema,
     strip_schema_id_prefix: bool,
+    #[cfg(feature = "schema_registry")]
+    registry_schemas: Option<RegistrySchemas>,
 }
 
 impl AvroDeserializer {
@@ -121,7 +214,35 @@ impl AvroDeserializer {
         Self {
             schema,
             strip_schema_id_prefix,
+            #[cfg(feature = "schema_registry")]
+            registry_schemas: None,
+        }
+    }
+
+    /// Reads a datum, resolving it to the schema of the deserializer from the schema it was
+    /// written with if it's fetched from a schema registry.
+    fn read_datum(&self, bytes: Bytes) -> vector_common::Result<AvroValue> {
+        #[cfg(feature = "schema_registry")]
+        if let Some(registry_schemas) = &self.registry_schemas {
+            let (id, bytes) = split_schema_id(&bytes)?;
+            let writer_schema = registry_schemas.get(id)?;
+            return Ok(apache_avro::from_avro_datum(
+                &writer_schema,
+                &mut bytes.reader(),
+                Some(&self.schema),
+            )?);
         }
+
+        let bytes = if self.strip_schema_id_prefix {
+            split_schema_id(&bytes)?.1
+        } else {
+            bytes
+        };
+        Ok(apache_avro::from_avro_datum(
+            &self.schema,
+            &mut bytes.reader(),
+            None,
+        )?)
     }
 }
 
@@ -136,21 +257,7 @@ impl Deserializer for AvroDeserializer {
             return Ok(smallvec![]);
         }
 
-        let bytes = if self.strip_schema_id_prefix {
-            if bytes.len() >= CONFLUENT_SCHEMA_PREFIX_LEN && bytes[0] == CONFLUENT_MAGIC_BYTE {
-                bytes.slice(CONFLUENT_SCHEMA_PREFIX_LEN..)
-            } else {
-                return Err(vector_common::Error::from(
-                    "Expected avro datum to be prefixed with schema id",
-                ));
-            }
-        } else {
-            bytes
-        };
-
-        let value = apache_avro::from_avro_datum(&self.schema, &mut bytes.reader(), None)?;
-
-        let apache_avro::types::Value::Record(fields) = value else {
+        let apache_avro::types::Value::Record(fields) = self.read_datum(bytes)? else {
             return Err(vector_common::Error::from("Expected an avro Record"));
         };
 
@@ -313,6 +420,91 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "schema_registry")]
+    #[test]
+    fn deserialize_avro_schema_registry() {
+        #[derive(Serialize)]
+        struct WriterLog {
+            message: String,
+            host: String,
+        }
+
+        let writer_schema = Schema::parse_str(
+            r#"{"type": "record", "name": "log", "fields": [{"name": "message", "type": "string"}, {"name": "host", "type": "string"}]}"#,
+        )
+        .unwrap();
+        let (url, request) = crate::common::schema_registry::test_util::serve_once(
+            r#"{"schema": "{\"type\": \"record\", \"name\": \"log\", \"fields\": [{\"name\": \"message\", \"type\": \"string\"}, {\"name\": \"host\", \"type\": \"string\"}]}"}"#,
+        );
+
+        let event = WriterLog {
+            message: "hello from avro".to_owned(),
+            host: "localhost".to_owned(),
+        };
+        let value = apache_avro::to_value(event).unwrap();
+        let datum = apache_avro::to_avro_datum(&writer_schema, value).unwrap();
+
+        let mut bytes = BytesMut::new();
+        bytes.extend([0, 0, 0, 0, 42]); // 0 prefix + 4 byte schema id
+        bytes.extend(datum);
+        let bytes = bytes.freeze();
+
+        let deserializer = AvroDeserializerConfig {
+            avro_options: AvroDeserializerOptions {
+                schema: get_schema().canonical_form(),
+                strip_schema_id_prefix: false,
+                schema_registry: Some(crate::common::schema_registry::test_util::config(url)),
+            },
+        }
+        .build()
+        .unwrap();
+
+        // The schema is only fetched once: the server stops after the first request.
+        for _ in 0..2 {
+            let events = deserializer
+                .parse(bytes.clone(), LogNamespace::Vector)
+                .unwrap();
+            assert_eq!(events.len(), 1);
+            let log = events[0].as_log();
+            assert_eq!(
+                log.get("message").unwrap(),
+                &VrlValue::from("hello from avro")
+            );
+            assert!(log.get("host").is_none());
+        }
+        assert_eq!(request.join().unwrap(), "GET /schemas/ids/42 HTTP/1.1");
+    }
+
+    #[cfg(feature = "schema_registry")]
+    #[test]
+    fn deserialize_avro_schema_registry_caches_failures() {
+        let deserializer = AvroDeserializerConfig {
+            avro_options: AvroDeserializerOptions {
+                schema: get_schema().canonical_form(),
+                strip_schema_id_prefix: false,
+                schema_registry: Some(crate::common::schema_registry::test_util::config(
+                    crate::common::schema_registry::test_util::unreachable_url(),
+                )),
+            },
+        }
+        .build()
+        .unwrap();
+        let bytes = Bytes::from_static(b"\0\0\0\0\x2a\x02a");
+
+        let error = deserializer
+            .parse(bytes.clone(), LogNamespace::Vector)
+            .unwrap_err()
+            .to_string();
+        assert!(!error.contains("retrying"), "{error}");
+
+        // The failure is cached, so the registry isn't queried again until the backoff elapses.
+        let error = deserializer
+            .parse(bytes, LogNamespace::Vector)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("retrying in"), "{error}");
+    }
+
     #[test]
     fn deserialize_avro_uuid() {
         let schema = get_schema();
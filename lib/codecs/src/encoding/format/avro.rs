@@ -1,3 +1,10 @@
+#[cfg(feature = "schema_registry")]
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "schema_registry")]
+use crate::common::schema_registry::{
+    FailedRequest, SchemaRegistryClient, SchemaRegistryConfig, CONFLUENT_MAGIC_BYTE,
+};
 use crate::encoding::BuildError;
 use bytes::{BufMut, BytesMut};
 use serde::{Deserialize, Serialize};
@@ -16,15 +23,35 @@ impl AvroSerializerConfig {
     /// Creates a new `AvroSerializerConfig`.
     pub const fn new(schema: String) -> Self {
         Self {
-            avro: AvroSerializerOptions { schema },
+            avro: AvroSerializerOptions {
+                schema,
+                #[cfg(feature = "schema_registry")]
+                schema_registry: None,
+            },
         }
     }
 
     /// Build the `AvroSerializer` from this configuration.
+    ///
+    /// If a schema registry is configured, the schema is only registered once the first event is
+    /// encoded, so that building the serializer, such as when validating a configuration, doesn't
+    /// reach the registry.
     pub fn build(&self) -> Result<AvroSerializer, BuildError> {
         let schema = apache_avro::Schema::parse_str(&self.avro.schema)
             .map_err(|error| format!("Failed building Avro serializer: {}", error))?;
-        Ok(AvroSerializer { schema })
+        #[cfg(feature = "schema_registry")]
+        let registration = self
+            .avro
+            .schema_registry
+            .clone()
+            .map(|registry| SchemaRegistration::new(registry, self.avro.schema.clone()))
+            .transpose()?
+            .map(Arc::new);
+        Ok(AvroSerializer {
+            schema,
+            #[cfg(feature = "schema_registry")]
+            registration,
+        })
     }
 
     /// The data type of events that are accepted by `AvroSerializer`.
@@ -49,18 +76,83 @@ pub struct AvroSerializerOptions {
     ))]
     #[configurable(metadata(docs::human_name = "Schema JSON"))]
     pub schema: String,
+
+    /// The [Confluent Schema Registry][schema_registry] to register the schema in.
+    ///
+    /// When set, the schema is registered under the `subject` of the registry before the first
+    /// event is encoded, and messages are prefixed with its ID, following the Confluent wire format.
+    ///
+    /// [schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+    #[cfg(feature = "schema_registry")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_registry: Option<SchemaRegistryConfig>,
+}
+
+/// The registration of the schema of a serializer in a schema registry, shared by its clones.
+#[cfg(feature = "schema_registry")]
+#[derive(Debug)]
+struct SchemaRegistration {
+    client: SchemaRegistryClient,
+    schema: String,
+    state: Mutex<Option<Result<u32, FailedRequest>>>,
+}
+
+#[cfg(feature = "schema_registry")]
+impl SchemaRegistration {
+    fn new(config: SchemaRegistryConfig, schema: String) -> vector_common::Result<Self> {
+        Ok(Self {
+            client: SchemaRegistryClient::new(config)?,
+            schema,
+            state: Mutex::new(None),
+        })
+    }
+
+    /// The ID of the schema, registering it if it isn't registered yet.
+    ///
+    /// A failed registration is retried with a backoff, and the events encoded in the meantime
+    /// are rejected.
+    fn schema_id(&self) -> vector_common::Result<u32> {
+        let mut state = self.state.lock().expect("lock poisoned");
+        let previous_failure = match &*state {
+            Some(Ok(id)) => return Ok(*id),
+            Some(Err(failure)) => {
+                if let Some(error) = failure.pending_error() {
+                    return Err(format!("Failed registering Avro schema: {}", error).into());
+                }
+                Some(failure.clone())
+            }
+            None => None,
+        };
+
+        match vector_common::runtime::block_on(self.client.register_schema(&self.schema)) {
+            Ok(id) => {
+                *state = Some(Ok(id));
+                Ok(id)
+            }
+            Err(error) => {
+                *state = Some(Err(FailedRequest::new(&error, previous_failure.as_ref())));
+                Err(format!("Failed registering Avro schema: {}", error).into())
+            }
+        }
+    }
 }
 
 /// Serializer that converts an `Event` to bytes using the Apache Avro format.
 #[derive(Debug, Clone)]
 pub struct AvroSerializer {
     schema: apache_avro::Schema,
+    #[cfg(feature = "schema_registry")]
+    registration: Option<Arc<SchemaRegistration>>,
 }
 
 impl AvroSerializer {
     /// Creates a new `AvroSerializer`.
     pub const fn new(schema: apache_avro::Schema) -> Self {
-        Self { schema }
+        Self {
+            schema,
+            #[cfg(feature = "schema_registry")]
+            registration: None,
+        }
     }
 }
 
@@ -68,10 +160,21 @@ impl Encoder<Event> for AvroSerializer {
     type Error = vector_common::Error;
 
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        #[cfg(feature = "schema_registry")]
+        let schema_id = self
+            .registration
+            .as_ref()
+            .map(|registration| registration.schema_id())
+            .transpose()?;
         let log = event.into_log();
         let value = apache_avro::to_value(log)?;
         let value = value.resolve(&self.schema)?;
         let bytes = apache_avro::to_avro_datum(&self.schema, value)?;
+        #[cfg(feature = "schema_registry")]
+        if let Some(schema_id) = schema_id {
+            buffer.put_u8(CONFLUENT_MAGIC_BYTE);
+            buffer.put_u32(schema_id);
+        }
         buffer.put_slice(&bytes);
         Ok(())
     }
@@ -112,4 +215,67 @@ mod tests {
 
         assert_eq!(bytes.freeze(), b"\0\x06bar".as_slice());
     }
+
+    #[cfg(feature = "schema_registry")]
+    #[test]
+    fn serialize_avro_schema_registry() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "foo" => Value::from("bar")
+        }));
+        let (url, request) = crate::common::schema_registry::test_util::serve_once(r#"{"id": 7}"#);
+        let config = AvroSerializerConfig {
+            avro: AvroSerializerOptions {
+                schema: r#"{"type": "record", "name": "Log", "fields": [{"name": "foo", "type": "string"}]}"#.to_owned(),
+                schema_registry: Some(crate::common::schema_registry::test_util::config(url)),
+            },
+        };
+        let mut serializer = config.build().unwrap();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event.clone(), &mut bytes).unwrap();
+        // The schema is only registered once: the server stops after the first request.
+        serializer.encode(event, &mut bytes).unwrap();
+
+        assert_eq!(
+            bytes.freeze(),
+            b"\0\0\0\0\x07\x06bar\0\0\0\0\x07\x06bar".as_slice()
+        );
+        assert_eq!(
+            request.join().unwrap(),
+            "POST /subjects/logs-value/versions HTTP/1.1"
+        );
+    }
+
+    #[cfg(feature = "schema_registry")]
+    #[test]
+    fn serialize_avro_schema_registry_registers_lazily() {
+        let config = AvroSerializerConfig {
+            avro: AvroSerializerOptions {
+                schema: r#"{"type": "record", "name": "Log", "fields": [{"name": "foo", "type": "string"}]}"#.to_owned(),
+                schema_registry: Some(crate::common::schema_registry::test_util::config(
+                    crate::common::schema_registry::test_util::unreachable_url(),
+                )),
+            },
+        };
+
+        // Building the serializer doesn't reach the registry, encoding events does.
+        let mut serializer = config.build().unwrap();
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "foo" => Value::from("bar")
+        }));
+        let error = serializer
+            .encode(event.clone(), &mut BytesMut::new())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.starts_with("Failed registering Avro schema"),
+            "{error}"
+        );
+
+        let error = serializer
+            .encode(event, &mut BytesMut::new())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("retrying in"), "{error}");
+    }
 }
@@ -0,0 +1,483 @@
+//! An encoder writing batches of log events as [Apache Parquet][parquet] files.
+//!
+//! [parquet]: https://parquet.apache.org/
+
+mod thrift;
+mod writer;
+
+use std::{borrow::Cow, collections::BTreeMap};
+
+use bytes::BytesMut;
+use chrono::SecondsFormat;
+use lookup::event_path;
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+use self::writer::{Column, ColumnChunkBuilder, FileWriter, Scalar};
+use crate::encoding::BuildError;
+
+const fn default_row_group_size() -> usize {
+    10_000
+}
+
+/// Config used to build a `ParquetSerializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct ParquetSerializerConfig {
+    /// Apache Parquet-specific encoder options.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub parquet: ParquetSerializerOptions,
+}
+
+impl ParquetSerializerConfig {
+    /// Creates a new `ParquetSerializerConfig`.
+    pub const fn new(parquet: ParquetSerializerOptions) -> Self {
+        Self { parquet }
+    }
+
+    /// Build the `ParquetSerializer` from this configuration.
+    pub fn build(&self) -> Result<ParquetSerializer, BuildError> {
+        if self.parquet.row_group_size == 0 {
+            return Err("The Parquet row group size must be greater than zero".into());
+        }
+
+        let schema = match &self.parquet.schema {
+            Some(fields) if fields.is_empty() => {
+                return Err("At least one Parquet schema field must be specified".into())
+            }
+            Some(fields) => Some(
+                fields
+                    .iter()
+                    .map(|field| Column {
+                        name: field.name.clone(),
+                        column_type: field.column_type,
+                    })
+                    .collect(),
+            ),
+            None => None,
+        };
+
+        Ok(ParquetSerializer {
+            schema,
+            compression: self.parquet.compression,
+            row_group_size: self.parquet.row_group_size,
+        })
+    }
+
+    /// The data type of events that are accepted by `ParquetSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Apache Parquet serializer options.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParquetSerializerOptions {
+    /// The columns of the written files, in order.
+    ///
+    /// Each column is read from the top-level event field of the same name. Fields missing from an
+    /// event are written as null.
+    ///
+    /// If not set, the columns are derived from the top-level fields of the events in each batch,
+    /// in alphabetical order, with their types inferred from the values. Fields holding values of
+    /// conflicting types are written as strings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Vec<ParquetField>>,
+
+    /// The compression codec applied to the pages of the written files.
+    #[serde(default)]
+    pub compression: ParquetCompression,
+
+    /// The maximum number of events in each row group of the written files.
+    #[serde(default = "default_row_group_size")]
+    #[configurable(metadata(docs::type_unit = "events"))]
+    pub row_group_size: usize,
+}
+
+impl Default for ParquetSerializerOptions {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            compression: ParquetCompression::default(),
+            row_group_size: default_row_group_size(),
+        }
+    }
+}
+
+/// A column of the written Parquet files.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParquetField {
+    /// The name of the column, and of the top-level event field it is read from.
+    #[configurable(metadata(docs::examples = "message"))]
+    pub name: String,
+
+    /// The type of the column.
+    #[serde(rename = "type")]
+    pub column_type: ParquetColumnType,
+}
+
+/// The type of a Parquet column.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetColumnType {
+    /// A boolean.
+    Boolean,
+
+    /// A signed 64-bit integer.
+    Int64,
+
+    /// A double-precision floating point number. Integers are converted.
+    Double,
+
+    /// A UTF-8 string.
+    ///
+    /// Timestamps are written in RFC 3339 format, and other non-string values as JSON.
+    String,
+
+    /// A timestamp with microsecond precision, adjusted to UTC.
+    Timestamp,
+}
+
+impl ParquetColumnType {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Boolean => "boolean",
+            Self::Int64 => "int64",
+            Self::Double => "double",
+            Self::String => "string",
+            Self::Timestamp => "timestamp",
+        }
+    }
+
+    /// Infers the type of a column from a value, `None` for nulls.
+    fn infer(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::Boolean(_) => Some(Self::Boolean),
+            Value::Integer(_) => Some(Self::Int64),
+            Value::Float(_) => Some(Self::Double),
+            Value::Timestamp(_) => Some(Self::Timestamp),
+            _ => Some(Self::String),
+        }
+    }
+
+    /// The type of a column holding values of both types.
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Int64, Self::Double) | (Self::Double, Self::Int64) => Self::Double,
+            _ => Self::String,
+        }
+    }
+}
+
+/// The compression codec applied to the pages of Parquet files.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCompression {
+    /// No compression.
+    #[cfg_attr(not(feature = "parquet_compression"), default)]
+    None,
+
+    /// [Snappy][snappy] compression.
+    ///
+    /// [snappy]: https://google.github.io/snappy/
+    #[cfg(feature = "parquet_compression")]
+    #[default]
+    Snappy,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    #[cfg(feature = "parquet_compression")]
+    Gzip,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    #[cfg(feature = "parquet_compression")]
+    Zstd,
+}
+
+/// Serializer that converts batches of log events to Apache Parquet files.
+#[derive(Debug, Clone)]
+pub struct ParquetSerializer {
+    schema: Option<Vec<Column>>,
+    compression: ParquetCompression,
+    row_group_size: usize,
+}
+
+impl ParquetSerializer {
+    /// Creates a new `ParquetSerializer`.
+    pub fn new(options: ParquetSerializerOptions) -> Result<Self, BuildError> {
+        ParquetSerializerConfig::new(options).build()
+    }
+}
+
+impl Encoder<Vec<Event>> for ParquetSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, events: Vec<Event>, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let logs = events
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Log(log) => Some(log),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let columns = match &self.schema {
+            Some(columns) => Cow::Borrowed(columns.as_slice()),
+            None => Cow::Owned(derive_columns(&logs)),
+        };
+
+        let mut writer = FileWriter::new(buffer, &columns, self.compression);
+        for rows in logs.chunks(self.row_group_size) {
+            let chunks = columns
+                .iter()
+                .map(|column| {
+                    let mut chunk = ColumnChunkBuilder::new(column.column_type);
+                    for log in rows {
+                        chunk.push(to_scalar(
+                            column,
+                            log.get(event_path!(column.name.as_str())),
+                        )?);
+                    }
+                    Ok(chunk)
+                })
+                .collect::<Result<Vec<_>, Self::Error>>()?;
+            writer.write_row_group(chunks)?;
+        }
+        writer.finish();
+
+        Ok(())
+    }
+}
+
+/// Derives the columns from the top-level fields of the events, in alphabetical order. Fields
+/// which are always null are written as strings.
+fn derive_columns(logs: &[LogEvent]) -> Vec<Column> {
+    let mut types = BTreeMap::<&str, Option<ParquetColumnType>>::new();
+    for map in logs.iter().filter_map(LogEvent::as_map) {
+        for (name, value) in map {
+            let column_type = types.entry(name.as_str()).or_default();
+            *column_type = match (*column_type, ParquetColumnType::infer(value)) {
+                (Some(a), Some(b)) => Some(a.merge(b)),
+                (a, b) => a.or(b),
+            };
+        }
+    }
+
+    types
+        .into_iter()
+        .map(|(name, column_type)| Column {
+            name: name.to_owned(),
+            column_type: column_type.unwrap_or(ParquetColumnType::String),
+        })
+        .collect()
+}
+
+fn to_scalar(column: &Column, value: Option<&Value>) -> vector_common::Result<Option<Scalar>> {
+    let scalar = match (column.column_type, value) {
+        (_, None | Some(Value::Null)) => return Ok(None),
+        (ParquetColumnType::Boolean, Some(Value::Boolean(value))) => Scalar::Boolean(*value),
+        (ParquetColumnType::Int64, Some(Value::Integer(value))) => Scalar::Int64(*value),
+        (ParquetColumnType::Double, Some(Value::Float(value))) => {
+            Scalar::Double(value.into_inner())
+        }
+        (ParquetColumnType::Double, Some(Value::Integer(value))) => Scalar::Double(*value as f64),
+        (ParquetColumnType::Timestamp, Some(Value::Timestamp(value))) => {
+            Scalar::Int64(value.timestamp_micros())
+        }
+        (ParquetColumnType::String, Some(Value::Bytes(value))) => Scalar::ByteArray(value.to_vec()),
+        (ParquetColumnType::String, Some(Value::Timestamp(value))) => Scalar::ByteArray(
+            value
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                .into_bytes(),
+        ),
+        (ParquetColumnType::String, Some(value)) => Scalar::ByteArray(serde_json::to_vec(value)?),
+        (column_type, Some(value)) => {
+            return Err(format!(
+                "Field `{}` of type `{}` can't be written to a column of type `{}`",
+                column.name,
+                value.kind_str(),
+                column_type.as_str()
+            )
+            .into())
+        }
+    };
+    Ok(Some(scalar))
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::{TimeZone, Utc};
+    use vector_core::event::LogEvent;
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn encode(options: ParquetSerializerOptions, events: Vec<Event>) -> Bytes {
+        let mut buffer = BytesMut::new();
+        ParquetSerializer::new(options)
+            .unwrap()
+            .encode(events, &mut buffer)
+            .unwrap();
+        buffer.freeze()
+    }
+
+    fn log(fields: vrl::value::ObjectMap) -> Event {
+        Event::Log(LogEvent::from(fields))
+    }
+
+    #[test]
+    fn derives_columns() {
+        let logs = [
+            LogEvent::from(btreemap! {
+                "message" => "foo",
+                "count" => 1,
+                "nothing" => Value::Null,
+                "mixed" => 1,
+            }),
+            LogEvent::from(btreemap! {
+                "count" => 2.5,
+                "mixed" => true,
+                "timestamp" => Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            }),
+        ];
+
+        let columns = derive_columns(&logs);
+
+        let column = |name: &str, column_type| Column {
+            name: name.to_owned(),
+            column_type,
+        };
+        assert_eq!(
+            columns,
+            [
+                column("count", ParquetColumnType::Double),
+                column("message", ParquetColumnType::String),
+                column("mixed", ParquetColumnType::String),
+                column("nothing", ParquetColumnType::String),
+                column("timestamp", ParquetColumnType::Timestamp),
+            ]
+        );
+    }
+
+    #[test]
+    fn converts_values() {
+        let column = |column_type| Column {
+            name: "field".to_owned(),
+            column_type,
+        };
+        let timestamp = Value::from(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 1).unwrap());
+
+        assert_eq!(
+            to_scalar(&column(ParquetColumnType::Double), Some(&Value::from(2))).unwrap(),
+            Some(Scalar::Double(2.0))
+        );
+        assert_eq!(
+            to_scalar(&column(ParquetColumnType::Timestamp), Some(&timestamp)).unwrap(),
+            Some(Scalar::Int64(1_704_067_201_000_000))
+        );
+        assert_eq!(
+            to_scalar(&column(ParquetColumnType::String), Some(&timestamp)).unwrap(),
+            Some(Scalar::ByteArray(b"2024-01-01T00:00:01Z".to_vec()))
+        );
+        assert_eq!(
+            to_scalar(
+                &column(ParquetColumnType::String),
+                Some(&Value::from(btreemap! { "a" => 1 }))
+            )
+            .unwrap(),
+            Some(Scalar::ByteArray(br#"{"a":1}"#.to_vec()))
+        );
+        assert_eq!(
+            to_scalar(&column(ParquetColumnType::Int64), None).unwrap(),
+            None
+        );
+        assert!(to_scalar(&column(ParquetColumnType::Int64), Some(&Value::from("1"))).is_err());
+    }
+
+    #[test]
+    fn serialize_parquet_file() {
+        let options = ParquetSerializerOptions {
+            schema: Some(vec![ParquetField {
+                name: "message".to_owned(),
+                column_type: ParquetColumnType::String,
+            }]),
+            compression: ParquetCompression::None,
+            row_group_size: 1,
+        };
+        let events = vec![
+            log(btreemap! { "message" => "foo" }),
+            log(btreemap! { "other" => "bar" }),
+        ];
+
+        let bytes = encode(options, events);
+
+        assert!(bytes.starts_with(b"PAR1"));
+        assert!(bytes.ends_with(b"PAR1"));
+        let metadata_len =
+            u32::from_le_bytes(bytes[bytes.len() - 8..bytes.len() - 4].try_into().unwrap());
+        let metadata_start = bytes.len() - 8 - metadata_len as usize;
+        let metadata = &bytes[metadata_start..bytes.len() - 8];
+        assert!(metadata.windows(6).any(|window| window == b"vector"));
+        // One page per row group: the first row holds the value, the second is null.
+        assert!(bytes[..metadata_start]
+            .windows(3)
+            .any(|window| window == b"foo"));
+        assert!(!bytes.windows(3).any(|window| window == b"bar"));
+    }
+
+    #[cfg(feature = "parquet_compression")]
+    #[test]
+    fn serialize_compressed_parquet_file() {
+        for compression in [
+            ParquetCompression::Snappy,
+            ParquetCompression::Gzip,
+            ParquetCompression::Zstd,
+        ] {
+            let options = ParquetSerializerOptions {
+                compression,
+                ..Default::default()
+            };
+            let events = vec![log(btreemap! { "message" => "foo".repeat(100) })];
+
+            let bytes = encode(options, events);
+
+            assert!(bytes.starts_with(b"PAR1"));
+            assert!(bytes.ends_with(b"PAR1"));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let options = ParquetSerializerOptions {
+            row_group_size: 0,
+            ..Default::default()
+        };
+        assert!(ParquetSerializer::new(options).is_err());
+
+        let options = ParquetSerializerOptions {
+            schema: Some(Vec::new()),
+            ..Default::default()
+        };
+        assert!(ParquetSerializer::new(options).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! A writer for the [Thrift compact protocol][compact_protocol], which Parquet uses to serialize
+//! page headers and file metadata.
+//!
+//! [compact_protocol]: https://github.com/apache/thrift/blob/master/doc/specs/thrift-compact-protocol.md
+
+const STOP: u8 = 0;
+const BOOLEAN_TRUE: u8 = 1;
+const BOOLEAN_FALSE: u8 = 2;
+const I32: u8 = 5;
+const I64: u8 = 6;
+const BINARY: u8 = 8;
+const LIST: u8 = 9;
+const STRUCT: u8 = 12;
+
+/// Writes a struct in the Thrift compact protocol.
+///
+/// Fields must be written in increasing order of their IDs.
+#[derive(Debug, Default)]
+pub(super) struct CompactWriter {
+    buffer: Vec<u8>,
+    last_field_id: i16,
+    parents: Vec<i16>,
+}
+
+impl CompactWriter {
+    /// Writes a whole struct, returning its bytes.
+    pub(super) fn write_struct(f: impl FnOnce(&mut Self)) -> Vec<u8> {
+        let mut writer = Self::default();
+        f(&mut writer);
+        writer.buffer.push(STOP);
+        writer.buffer
+    }
+
+    pub(super) fn bool(&mut self, id: i16, value: bool) {
+        self.field_header(id, if value { BOOLEAN_TRUE } else { BOOLEAN_FALSE });
+    }
+
+    pub(super) fn i32(&mut self, id: i16, value: i32) {
+        self.field_header(id, I32);
+        self.varint(zigzag_i32(value));
+    }
+
+    pub(super) fn i64(&mut self, id: i16, value: i64) {
+        self.field_header(id, I64);
+        self.varint(zigzag_i64(value));
+    }
+
+    pub(super) fn binary(&mut self, id: i16, value: &[u8]) {
+        self.field_header(id, BINARY);
+        self.bytes(value);
+    }
+
+    pub(super) fn i32_list(&mut self, id: i16, values: &[i32]) {
+        self.field_header(id, LIST);
+        self.list_header(I32, values.len());
+        for value in values {
+            self.varint(zigzag_i32(*value));
+        }
+    }
+
+    pub(super) fn binary_list<T: AsRef<[u8]>>(&mut self, id: i16, values: &[T]) {
+        self.field_header(id, LIST);
+        self.list_header(BINARY, values.len());
+        for value in values {
+            self.bytes(value.as_ref());
+        }
+    }
+
+    pub(super) fn struct_field(&mut self, id: i16, f: impl FnOnce(&mut Self)) {
+        self.field_header(id, STRUCT);
+        self.nested(f);
+    }
+
+    pub(super) fn struct_list<T>(&mut self, id: i16, values: &[T], f: impl Fn(&mut Self, &T)) {
+        self.field_header(id, LIST);
+        self.list_header(STRUCT, values.len());
+        for value in values {
+            self.nested(|writer| f(writer, value));
+        }
+    }
+
+    fn nested(&mut self, f: impl FnOnce(&mut Self)) {
+        self.parents.push(self.last_field_id);
+        self.last_field_id = 0;
+        f(self);
+        self.buffer.push(STOP);
+        self.last_field_id = self.parents.pop().expect("unbalanced struct");
+    }
+
+    fn field_header(&mut self, id: i16, field_type: u8) {
+        let delta = id - self.last_field_id;
+        if (1..=15).contains(&delta) {
+            self.buffer.push(((delta as u8) << 4) | field_type);
+        } else {
+            self.buffer.push(field_type);
+            self.varint(zigzag_i32(id.into()));
+        }
+        self.last_field_id = id;
+    }
+
+    fn list_header(&mut self, element_type: u8, len: usize) {
+        if len < 15 {
+            self.buffer.push(((len as u8) << 4) | element_type);
+        } else {
+            self.buffer.push(0xf0 | element_type);
+            self.varint(len as u64);
+        }
+    }
+
+    fn bytes(&mut self, value: &[u8]) {
+        self.varint(value.len() as u64);
+        self.buffer.extend_from_slice(value);
+    }
+
+    fn varint(&mut self, value: impl Into<u64>) {
+        write_uleb128(&mut self.buffer, value.into());
+    }
+}
+
+/// Writes an unsigned LEB128 variable-length integer.
+pub(super) fn write_uleb128(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+const fn zigzag_i32(value: i32) -> u32 {
+    ((value << 1) ^ (value >> 31)) as u32
+}
+
+const fn zigzag_i64(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zigzag() {
+        assert_eq!(zigzag_i32(0), 0);
+        assert_eq!(zigzag_i32(-1), 1);
+        assert_eq!(zigzag_i32(1), 2);
+        assert_eq!(zigzag_i64(-2), 3);
+        assert_eq!(zigzag_i64(i64::MAX), u64::MAX - 1);
+    }
+
+    #[test]
+    fn writes_struct() {
+        let bytes = CompactWriter::write_struct(|writer| {
+            writer.i32(1, 1);
+            writer.binary(2, b"ab");
+            writer.struct_field(3, |writer| {
+                writer.bool(1, true);
+            });
+            writer.i32_list(4, &[0, 3]);
+            writer.i64(20, -1);
+        });
+
+        assert_eq!(
+            bytes,
+            [
+                0x15, 0x02, // field 1, i32 1
+                0x18, 0x02, b'a', b'b', // field 2, binary "ab"
+                0x1c, 0x11, 0x00, // field 3, struct with field 1 true
+                0x19, 0x25, 0x00, 0x06, // field 4, list of 2 i32
+                0x06, 0x28, 0x01, // field 20 (long form), i64 -1
+                0x00, // stop
+            ]
+        );
+    }
+}
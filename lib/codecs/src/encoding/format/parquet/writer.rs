@@ -0,0 +1,373 @@
+//! A writer for [Parquet][parquet] files of flat, optional columns.
+//!
+//! Each column chunk is written as a single data page (version 1), with values in the `PLAIN`
+//! encoding and definition levels in the `RLE` encoding.
+//!
+//! [parquet]: https://parquet.apache.org/docs/file-format/
+
+use std::io;
+#[cfg(feature = "parquet_compression")]
+use std::io::Write;
+
+use bytes::{BufMut, BytesMut};
+
+use super::thrift::{write_uleb128, CompactWriter};
+use super::{ParquetColumnType, ParquetCompression};
+
+const MAGIC: &[u8] = b"PAR1";
+const CREATED_BY: &str = "vector";
+
+// Values of the `Type` enum.
+const TYPE_BOOLEAN: i32 = 0;
+const TYPE_INT64: i32 = 2;
+const TYPE_DOUBLE: i32 = 5;
+const TYPE_BYTE_ARRAY: i32 = 6;
+
+// Values of the `ConvertedType` enum.
+const CONVERTED_TYPE_UTF8: i32 = 0;
+const CONVERTED_TYPE_TIMESTAMP_MICROS: i32 = 10;
+
+// Values of the `Encoding` enum.
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_RLE: i32 = 3;
+
+const REPETITION_OPTIONAL: i32 = 1;
+const PAGE_TYPE_DATA_PAGE: i32 = 0;
+
+/// A column of the written file.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Column {
+    pub(super) name: String,
+    pub(super) column_type: ParquetColumnType,
+}
+
+/// A non-null value of a column.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Scalar {
+    Boolean(bool),
+    Int64(i64),
+    Double(f64),
+    ByteArray(Vec<u8>),
+}
+
+impl ParquetColumnType {
+    const fn physical_type(self) -> i32 {
+        match self {
+            Self::Boolean => TYPE_BOOLEAN,
+            Self::Int64 | Self::Timestamp => TYPE_INT64,
+            Self::Double => TYPE_DOUBLE,
+            Self::String => TYPE_BYTE_ARRAY,
+        }
+    }
+
+    fn write_schema_element(self, writer: &mut CompactWriter, name: &str) {
+        writer.i32(1, self.physical_type());
+        writer.i32(3, REPETITION_OPTIONAL);
+        writer.binary(4, name.as_bytes());
+        match self {
+            Self::Boolean | Self::Int64 | Self::Double => {}
+            Self::String => {
+                writer.i32(6, CONVERTED_TYPE_UTF8);
+                // LogicalType.STRING
+                writer.struct_field(10, |writer| writer.struct_field(1, |_| {}));
+            }
+            Self::Timestamp => {
+                writer.i32(6, CONVERTED_TYPE_TIMESTAMP_MICROS);
+                // LogicalType.TIMESTAMP, adjusted to UTC, in microseconds.
+                writer.struct_field(10, |writer| {
+                    writer.struct_field(8, |writer| {
+                        writer.bool(1, true);
+                        writer.struct_field(2, |writer| writer.struct_field(2, |_| {}));
+                    })
+                });
+            }
+        }
+    }
+}
+
+impl ParquetCompression {
+    const fn codec(self) -> i32 {
+        match self {
+            Self::None => 0,
+            #[cfg(feature = "parquet_compression")]
+            Self::Snappy => 1,
+            #[cfg(feature = "parquet_compression")]
+            Self::Gzip => 2,
+            #[cfg(feature = "parquet_compression")]
+            Self::Zstd => 6,
+        }
+    }
+
+    fn compress(self, data: Vec<u8>) -> io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(data),
+            #[cfg(feature = "parquet_compression")]
+            Self::Snappy => snap::raw::Encoder::new()
+                .compress_vec(&data)
+                .map_err(io::Error::other),
+            #[cfg(feature = "parquet_compression")]
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(&data)?;
+                encoder.finish()
+            }
+            #[cfg(feature = "parquet_compression")]
+            Self::Zstd => zstd::bulk::compress(&data, zstd::DEFAULT_COMPRESSION_LEVEL),
+        }
+    }
+}
+
+/// Accumulates the values of a column for a row group.
+#[derive(Debug)]
+pub(super) struct ColumnChunkBuilder {
+    column_type: ParquetColumnType,
+    definition_levels: Vec<bool>,
+    values: Vec<u8>,
+    booleans: Vec<bool>,
+}
+
+impl ColumnChunkBuilder {
+    pub(super) const fn new(column_type: ParquetColumnType) -> Self {
+        Self {
+            column_type,
+            definition_levels: Vec::new(),
+            values: Vec::new(),
+            booleans: Vec::new(),
+        }
+    }
+
+    /// Appends a value, which must match the physical type of the column.
+    pub(super) fn push(&mut self, value: Option<Scalar>) {
+        self.definition_levels.push(value.is_some());
+        match value {
+            None => {}
+            Some(Scalar::Boolean(value)) => self.booleans.push(value),
+            Some(Scalar::Int64(value)) => self.values.extend_from_slice(&value.to_le_bytes()),
+            Some(Scalar::Double(value)) => self.values.extend_from_slice(&value.to_le_bytes()),
+            Some(Scalar::ByteArray(value)) => {
+                let len = u32::try_from(value.len()).expect("value exceeds 4 GiB");
+                self.values.extend_from_slice(&len.to_le_bytes());
+                self.values.extend_from_slice(&value);
+            }
+        }
+    }
+
+    /// Returns the uncompressed data of the page: the length-prefixed definition levels followed
+    /// by the values.
+    fn page_data(self) -> (usize, Vec<u8>) {
+        let num_values = self.definition_levels.len();
+        let levels = bit_packed_hybrid(&self.definition_levels);
+
+        let mut data = Vec::with_capacity(4 + levels.len() + self.values.len());
+        data.extend_from_slice(&(levels.len() as u32).to_le_bytes());
+        data.extend_from_slice(&levels);
+        if self.column_type == ParquetColumnType::Boolean {
+            data.extend_from_slice(&pack_bits(&self.booleans));
+        } else {
+            data.extend_from_slice(&self.values);
+        }
+        (num_values, data)
+    }
+}
+
+/// Encodes boolean values in the RLE/bit-packing hybrid encoding with a bit width of 1, as a
+/// single bit-packed run.
+fn bit_packed_hybrid(values: &[bool]) -> Vec<u8> {
+    let packed = pack_bits(values);
+    let mut buffer = Vec::with_capacity(packed.len() + 5);
+    write_uleb128(&mut buffer, ((packed.len() as u64) << 1) | 1);
+    buffer.extend_from_slice(&packed);
+    buffer
+}
+
+/// Packs booleans into bytes, least significant bit first.
+fn pack_bits(values: &[bool]) -> Vec<u8> {
+    values
+        .chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |byte, (i, value)| byte | (u8::from(*value) << i))
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct ColumnChunkMetadata {
+    column_type: ParquetColumnType,
+    name: String,
+    num_values: i64,
+    data_page_offset: i64,
+    uncompressed_size: i64,
+    compressed_size: i64,
+}
+
+#[derive(Debug)]
+struct RowGroupMetadata {
+    columns: Vec<ColumnChunkMetadata>,
+    num_rows: i64,
+}
+
+/// Writes a Parquet file into a buffer, one row group at a time.
+pub(super) struct FileWriter<'a> {
+    buffer: &'a mut BytesMut,
+    start: usize,
+    columns: &'a [Column],
+    compression: ParquetCompression,
+    row_groups: Vec<RowGroupMetadata>,
+}
+
+impl<'a> FileWriter<'a> {
+    pub(super) fn new(
+        buffer: &'a mut BytesMut,
+        columns: &'a [Column],
+        compression: ParquetCompression,
+    ) -> Self {
+        let start = buffer.len();
+        buffer.put_slice(MAGIC);
+        Self {
+            buffer,
+            start,
+            columns,
+            compression,
+            row_groups: Vec::new(),
+        }
+    }
+
+    /// Writes a row group from the chunks of each column, in the order of the columns.
+    pub(super) fn write_row_group(&mut self, chunks: Vec<ColumnChunkBuilder>) -> io::Result<()> {
+        let mut columns = Vec::with_capacity(chunks.len());
+        let mut num_rows = 0;
+
+        for (column, chunk) in self.columns.iter().zip(chunks) {
+            let (num_values, data) = chunk.page_data();
+            let uncompressed_len = data.len();
+            let compressed = self.compression.compress(data)?;
+
+            let header = CompactWriter::write_struct(|writer| {
+                writer.i32(1, PAGE_TYPE_DATA_PAGE);
+                writer.i32(2, to_i32(uncompressed_len));
+                writer.i32(3, to_i32(compressed.len()));
+                writer.struct_field(5, |writer| {
+                    writer.i32(1, to_i32(num_values));
+                    writer.i32(2, ENCODING_PLAIN);
+                    writer.i32(3, ENCODING_RLE);
+                    writer.i32(4, ENCODING_RLE);
+                });
+            });
+
+            let data_page_offset = (self.buffer.len() - self.start) as i64;
+            self.buffer.put_slice(&header);
+            self.buffer.put_slice(&compressed);
+
+            num_rows = num_values as i64;
+            columns.push(ColumnChunkMetadata {
+                column_type: column.column_type,
+                name: column.name.clone(),
+                num_values: num_values as i64,
+                data_page_offset,
+                uncompressed_size: (header.len() + uncompressed_len) as i64,
+                compressed_size: (header.len() + compressed.len()) as i64,
+            });
+        }
+
+        self.row_groups.push(RowGroupMetadata { columns, num_rows });
+        Ok(())
+    }
+
+    /// Writes the file metadata, completing the file.
+    pub(super) fn finish(self) {
+        let num_rows = self.row_groups.iter().map(|group| group.num_rows).sum();
+        let compression = self.compression;
+
+        let metadata = CompactWriter::write_struct(|writer| {
+            writer.i32(1, 1);
+            let root = [None];
+            let leaves = self.columns.iter().map(Some);
+            let schema = root.into_iter().chain(leaves).collect::<Vec<_>>();
+            writer.struct_list(2, &schema, |writer, column| match column {
+                None => {
+                    writer.binary(4, b"schema");
+                    writer.i32(5, to_i32(self.columns.len()));
+                }
+                Some(column) => column
+                    .column_type
+                    .write_schema_element(writer, &column.name),
+            });
+            writer.i64(3, num_rows);
+            writer.struct_list(4, &self.row_groups, |writer, group| {
+                writer.struct_list(1, &group.columns, |writer, column| {
+                    writer.i64(2, column.data_page_offset);
+                    writer.struct_field(3, |writer| {
+                        writer.i32(1, column.column_type.physical_type());
+                        writer.i32_list(2, &[ENCODING_PLAIN, ENCODING_RLE]);
+                        writer.binary_list(3, &[column.name.as_bytes()]);
+                        writer.i32(4, compression.codec());
+                        writer.i64(5, column.num_values);
+                        writer.i64(6, column.uncompressed_size);
+                        writer.i64(7, column.compressed_size);
+                        writer.i64(9, column.data_page_offset);
+                    });
+                });
+                let total_byte_size = group.columns.iter().map(|c| c.uncompressed_size).sum();
+                writer.i64(2, total_byte_size);
+                writer.i64(3, group.num_rows);
+                if let Some(first) = group.columns.first() {
+                    writer.i64(5, first.data_page_offset);
+                }
+                let total_compressed_size = group.columns.iter().map(|c| c.compressed_size).sum();
+                writer.i64(6, total_compressed_size);
+            });
+            writer.binary(6, CREATED_BY.as_bytes());
+        });
+
+        self.buffer.put_slice(&metadata);
+        self.buffer.put_u32_le(metadata.len() as u32);
+        self.buffer.put_slice(MAGIC);
+    }
+}
+
+fn to_i32(value: usize) -> i32 {
+    i32::try_from(value).expect("page exceeds 2 GiB")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_definition_levels() {
+        let levels = [true, false, true, true, false, false, false, false, true];
+        // Two bit-packed groups of 8 values, the last one padded.
+        assert_eq!(bit_packed_hybrid(&levels), [0x05, 0b0000_1101, 0b0000_0001]);
+    }
+
+    #[test]
+    fn writes_page_data() {
+        let mut chunk = ColumnChunkBuilder::new(ParquetColumnType::String);
+        chunk.push(Some(Scalar::ByteArray(b"ab".to_vec())));
+        chunk.push(None);
+
+        let (num_values, data) = chunk.page_data();
+
+        assert_eq!(num_values, 2);
+        assert_eq!(
+            data,
+            [2, 0, 0, 0, 0x03, 0b01, 2, 0, 0, 0, b'a', b'b'].as_slice()
+        );
+    }
+
+    #[test]
+    fn writes_booleans() {
+        let mut chunk = ColumnChunkBuilder::new(ParquetColumnType::Boolean);
+        chunk.push(Some(Scalar::Boolean(true)));
+        chunk.push(Some(Scalar::Boolean(false)));
+        chunk.push(Some(Scalar::Boolean(true)));
+
+        let (_, data) = chunk.page_data();
+
+        assert_eq!(data, [2, 0, 0, 0, 0x03, 0b111, 0b101].as_slice());
+    }
+}
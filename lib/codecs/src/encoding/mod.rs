@@ -12,7 +12,9 @@ pub use format::{
     CsvSerializerConfig, GelfSerializer, GelfSerializerConfig, JsonSerializer,
     JsonSerializerConfig, JsonSerializerOptions, LogfmtSerializer, LogfmtSerializerConfig,
     NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig,
-    ProtobufSerializer, ProtobufSerializerConfig, ProtobufSerializerOptions, RawMessageSerializer,
+    ParquetColumnType, ParquetCompression, ParquetField, ParquetSerializer,
+    ParquetSerializerConfig, ParquetSerializerOptions, ProtobufSerializer,
+    ProtobufSerializerConfig, ProtobufSerializerOptions, RawMessageSerializer,
     RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
 };
 pub use framing::{
@@ -324,7 +326,7 @@ impl SerializerConfig {
     pub fn build(&self) -> Result<Serializer, Box<dyn std::error::Error + Send + Sync + 'static>> {
         match self {
             SerializerConfig::Avro { avro } => Ok(Serializer::Avro(
-                AvroSerializerConfig::new(avro.schema.clone()).build()?,
+                AvroSerializerConfig { avro: avro.clone() }.build()?,
             )),
             SerializerConfig::Csv(config) => Ok(Serializer::Csv(config.build()?)),
             SerializerConfig::Gelf => Ok(Serializer::Gelf(GelfSerializerConfig::new().build())),
@@ -552,3 +554,84 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
         }
     }
 }
+
+/// Batch serializer configuration.
+///
+/// Batch serializers encode a whole batch of events at once, into a single file.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "codec", rename_all = "snake_case")]
+#[configurable(metadata(
+    docs::enum_tag_description = "The codec to use for encoding batches of events."
+))]
+pub enum BatchSerializerConfig {
+    /// Encodes a batch of events as an [Apache Parquet][apache_parquet] file.
+    ///
+    /// Only log events are supported.
+    ///
+    /// [apache_parquet]: https://parquet.apache.org/
+    Parquet {
+        /// Apache Parquet-specific encoder options.
+        #[serde(default)]
+        parquet: ParquetSerializerOptions,
+    },
+}
+
+impl BatchSerializerConfig {
+    /// Build the `BatchSerializer` from this configuration.
+    pub fn build(&self) -> Result<BatchSerializer, BuildError> {
+        match self {
+            BatchSerializerConfig::Parquet { parquet } => Ok(BatchSerializer::Parquet(
+                ParquetSerializerConfig::new(parquet.clone()).build()?,
+            )),
+        }
+    }
+
+    /// The data type of events that are accepted by this `BatchSerializer`.
+    pub fn input_type(&self) -> DataType {
+        match self {
+            BatchSerializerConfig::Parquet { parquet } => {
+                ParquetSerializerConfig::new(parquet.clone()).input_type()
+            }
+        }
+    }
+
+    /// The schema required by the batch serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        match self {
+            BatchSerializerConfig::Parquet { parquet } => {
+                ParquetSerializerConfig::new(parquet.clone()).schema_requirement()
+            }
+        }
+    }
+
+    /// The file extension of the files written by this batch serializer.
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            BatchSerializerConfig::Parquet { .. } => "parquet",
+        }
+    }
+}
+
+/// Serialize batches of structured events as bytes.
+#[derive(Debug, Clone)]
+pub enum BatchSerializer {
+    /// Uses a `ParquetSerializer` for serialization.
+    Parquet(ParquetSerializer),
+}
+
+impl From<ParquetSerializer> for BatchSerializer {
+    fn from(serializer: ParquetSerializer) -> Self {
+        Self::Parquet(serializer)
+    }
+}
+
+impl tokio_util::codec::Encoder<Vec<Event>> for BatchSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, events: Vec<Event>, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        match self {
+            BatchSerializer::Parquet(serializer) => serializer.encode(events, buffer),
+        }
+    }
+}
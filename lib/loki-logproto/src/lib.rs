@@ -88,6 +88,55 @@ pub mod util {
         labels.sort();
         format!("{{{}}}", labels.join(", "))
     }
+
+    // Parses labels in the format sent by clients such as promtail, `{key="value", ...}`, with
+    // values quoted and escaped the same way as Go strings.
+    pub fn decode_labels_string(labels: &str) -> Option<Vec<(String, String)>> {
+        let inner = labels.trim().strip_prefix('{')?.strip_suffix('}')?;
+        let mut chars = inner.chars().peekable();
+        let mut pairs = Vec::new();
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if chars.peek().is_none() {
+                return Some(pairs);
+            }
+
+            let mut name = String::new();
+            while let Some(c) = chars.next_if(|&c| c != '=') {
+                name.push(c);
+            }
+            chars.next()?;
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if chars.next()? != '"' {
+                return None;
+            }
+
+            let mut value = String::new();
+            loop {
+                match chars.next()? {
+                    '"' => break,
+                    '\\' => match chars.next()? {
+                        'n' => value.push('\n'),
+                        't' => value.push('\t'),
+                        c => value.push(c),
+                    },
+                    c => value.push(c),
+                }
+            }
+            let name = name.trim();
+            if name.is_empty() {
+                return None;
+            }
+            pairs.push((name.to_owned(), value));
+
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            match chars.next() {
+                Some(',') => {}
+                None => return Some(pairs),
+                Some(_) => return None,
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -112,6 +161,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_labels() {
+        let labels = util::decode_labels_string(
+            r#"{agent="vector", file="/path/to/log", msg="a \"b\", c"}"#,
+        );
+        assert_eq!(
+            labels,
+            Some(vec![
+                ("agent".into(), "vector".into()),
+                ("file".into(), "/path/to/log".into()),
+                ("msg".into(), r#"a "b", c"#.into()),
+            ])
+        );
+        assert_eq!(util::decode_labels_string("{}"), Some(vec![]));
+        assert_eq!(util::decode_labels_string(r#"{agent="vector""#), None);
+        assert_eq!(util::decode_labels_string(r#"{agent=vector}"#), None);
+    }
+
     #[test]
     fn encode_batch() {
         let ts1 = Utc
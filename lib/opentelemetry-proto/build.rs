@@ -1,17 +1,26 @@
-use std::io::Error;
+use std::{io::Error, path::PathBuf};
 
 fn main() -> Result<(), Error> {
+    // The file descriptor set allows decoding the JSON encoding of OTLP requests.
+    let descriptor_path =
+        PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR environment variable not set"))
+            .join("opentelemetry-fds.bin");
+
     tonic_build::configure()
         .build_client(true)
         .build_server(true)
+        .protoc_arg("--experimental_allow_proto3_optional")
+        .file_descriptor_set_path(descriptor_path)
         .compile(
             &[
                 "src/proto/opentelemetry-proto/opentelemetry/proto/common/v1/common.proto",
                 "src/proto/opentelemetry-proto/opentelemetry/proto/resource/v1/resource.proto",
                 "src/proto/opentelemetry-proto/opentelemetry/proto/logs/v1/logs.proto",
+                "src/proto/opentelemetry-proto/opentelemetry/proto/metrics/v1/metrics.proto",
                 "src/proto/opentelemetry-proto/opentelemetry/proto/trace/v1/trace.proto",
                 "src/proto/opentelemetry-proto/opentelemetry/proto/collector/trace/v1/trace_service.proto",
                 "src/proto/opentelemetry-proto/opentelemetry/proto/collector/logs/v1/logs_service.proto",
+                "src/proto/opentelemetry-proto/opentelemetry/proto/collector/metrics/v1/metrics_service.proto",
             ],
             &["src/proto/opentelemetry-proto"],
         )?;
@@ -5,7 +5,10 @@ use ordered_float::NotNan;
 use std::collections::BTreeMap;
 use vector_core::{
     config::{log_schema, LegacyKey, LogNamespace},
-    event::{Event, LogEvent, TraceEvent},
+    event::{
+        metric::{Bucket, Quantile},
+        Event, LogEvent, Metric, MetricKind, MetricTags, MetricValue, TraceEvent,
+    },
 };
 use vrl::value::KeyString;
 use vrl::{
@@ -16,6 +19,11 @@ use vrl::{
 use super::proto::{
     common::v1::{any_value::Value as PBValue, InstrumentationScope, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, SeverityNumber},
+    metrics::v1::{
+        metric::Data, number_data_point, AggregationTemporality, DataPointFlags,
+        ExponentialHistogramDataPoint, HistogramDataPoint, Metric as OtelMetric, NumberDataPoint,
+        ResourceMetrics,
+    },
     resource::v1::Resource,
     trace::v1::{
         span::{Event as SpanEvent, Link},
@@ -62,16 +70,46 @@ impl ResourceSpans {
         let resource = self.resource;
         let now = Utc::now();
 
-        self.scope_spans
-            .into_iter()
-            .flat_map(|instrumentation_library_spans| instrumentation_library_spans.spans)
-            .map(move |span| {
+        self.scope_spans.into_iter().flat_map(move |scope_spans| {
+            let scope = scope_spans.scope;
+            let resource = resource.clone();
+            scope_spans.spans.into_iter().map(move |span| {
                 ResourceSpan {
                     resource: resource.clone(),
+                    scope: scope.clone(),
                     span,
                 }
                 .into_event(now)
             })
+        })
+    }
+}
+
+impl ResourceMetrics {
+    pub fn into_event_iter(self) -> impl Iterator<Item = Event> {
+        let mut resource_tags = MetricTags::default();
+        if let Some(resource) = self.resource {
+            insert_attribute_tags(&mut resource_tags, "resource.", resource.attributes);
+        }
+
+        self.scope_metrics
+            .into_iter()
+            .flat_map(move |scope_metrics| {
+                let mut tags = resource_tags.clone();
+                if let Some(scope) = scope_metrics.scope {
+                    if !scope.name.is_empty() {
+                        tags.replace("scope.name".into(), scope.name);
+                    }
+                    if !scope.version.is_empty() {
+                        tags.replace("scope.version".into(), scope.version);
+                    }
+                    insert_attribute_tags(&mut tags, "scope.attributes.", scope.attributes);
+                }
+                scope_metrics
+                    .metrics
+                    .into_iter()
+                    .flat_map(move |metric| metric_into_events(metric, &tags))
+            })
     }
 }
 
@@ -102,6 +140,7 @@ struct ResourceLog {
 
 struct ResourceSpan {
     resource: Option<Resource>,
+    scope: Option<InstrumentationScope>,
     span: Span,
 }
 
@@ -192,6 +231,20 @@ impl ResourceSpan {
                 );
             }
         }
+        if let Some(scope) = self.scope {
+            if !scope.name.is_empty() {
+                trace.insert(event_path!(SCOPE_KEY, NAME_KEY), scope.name);
+            }
+            if !scope.version.is_empty() {
+                trace.insert(event_path!(SCOPE_KEY, VERSION_KEY), scope.version);
+            }
+            if !scope.attributes.is_empty() {
+                trace.insert(
+                    event_path!(SCOPE_KEY, ATTRIBUTES_KEY),
+                    kv_list_into_value(scope.attributes),
+                );
+            }
+        }
         trace.insert(event_path!("ingest_timestamp"), Value::from(now));
         trace.into()
     }
@@ -422,3 +475,201 @@ impl From<SpanStatus> for Value {
         Value::Object(obj)
     }
 }
+
+/// Inserts attributes as metric tags, their values being converted to strings.
+fn insert_attribute_tags(tags: &mut MetricTags, prefix: &str, attributes: Vec<KeyValue>) {
+    for kv in attributes {
+        if let Some(value) = kv.value.and_then(|av| av.value) {
+            tags.replace(
+                format!("{prefix}{}", kv.key),
+                Value::from(value).to_string_lossy().into_owned(),
+            );
+        }
+    }
+}
+
+fn timestamp(time_unix_nano: u64) -> Option<DateTime<Utc>> {
+    (time_unix_nano > 0).then(|| Utc.timestamp_nanos(time_unix_nano as i64))
+}
+
+fn metric_kind(aggregation_temporality: i32) -> MetricKind {
+    if aggregation_temporality == AggregationTemporality::Delta as i32 {
+        MetricKind::Incremental
+    } else {
+        MetricKind::Absolute
+    }
+}
+
+const fn is_recorded(flags: u32) -> bool {
+    flags & DataPointFlags::NoRecordedValueMask as u32 == 0
+}
+
+fn number_value(point: &NumberDataPoint) -> Option<f64> {
+    match point.value.as_ref()? {
+        number_data_point::Value::AsDouble(value) => Some(*value),
+        number_data_point::Value::AsInt(value) => Some(*value as f64),
+    }
+}
+
+fn explicit_buckets(point: &HistogramDataPoint) -> Vec<Bucket> {
+    // The last bucket, without an explicit bound, holds the values above all bounds.
+    point
+        .bucket_counts
+        .iter()
+        .enumerate()
+        .map(|(index, count)| Bucket {
+            upper_limit: point
+                .explicit_bounds
+                .get(index)
+                .copied()
+                .unwrap_or(f64::INFINITY),
+            count: *count,
+        })
+        .collect()
+}
+
+/// Converts the buckets of an exponential histogram into buckets with explicit bounds.
+///
+/// The positive bucket at index `i` holds the values within `(base^i, base^(i + 1)]`, with
+/// `base = 2^(2^-scale)`, while negative buckets mirror positive ones.
+fn exponential_buckets(point: &ExponentialHistogramDataPoint) -> Vec<Bucket> {
+    let base = 2f64.powf(2f64.powi(-point.scale));
+    let mut buckets = Vec::new();
+    if let Some(negative) = &point.negative {
+        buckets.extend(
+            negative
+                .bucket_counts
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(index, count)| Bucket {
+                    upper_limit: -base.powi(negative.offset + index as i32),
+                    count: *count,
+                }),
+        );
+    }
+    buckets.push(Bucket {
+        upper_limit: point.zero_threshold,
+        count: point.zero_count,
+    });
+    if let Some(positive) = &point.positive {
+        buckets.extend(
+            positive
+                .bucket_counts
+                .iter()
+                .enumerate()
+                .map(|(index, count)| Bucket {
+                    upper_limit: base.powi(positive.offset + index as i32 + 1),
+                    count: *count,
+                }),
+        );
+    }
+    buckets
+}
+
+// Resource and scope attributes are kept as tags of the metrics, prefixed by `resource.` and
+// `scope.attributes.` respectively, along with the attributes of their data points.
+fn metric_into_events(metric: OtelMetric, tags: &MetricTags) -> Vec<Event> {
+    let name = metric.name;
+    let event = |kind, value, time_unix_nano, attributes| {
+        let mut tags = tags.clone();
+        insert_attribute_tags(&mut tags, "", attributes);
+        Event::from(
+            Metric::new(name.clone(), kind, value)
+                .with_tags(Some(tags))
+                .with_timestamp(timestamp(time_unix_nano)),
+        )
+    };
+
+    match metric.data {
+        Some(Data::Gauge(gauge)) => gauge
+            .data_points
+            .into_iter()
+            .filter(|point| is_recorded(point.flags))
+            .filter_map(|point| {
+                let value = number_value(&point)?;
+                Some(event(
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value },
+                    point.time_unix_nano,
+                    point.attributes,
+                ))
+            })
+            .collect(),
+        Some(Data::Sum(sum)) => {
+            let kind = metric_kind(sum.aggregation_temporality);
+            sum.data_points
+                .into_iter()
+                .filter(|point| is_recorded(point.flags))
+                .filter_map(|point| {
+                    let value = number_value(&point)?;
+                    // Non-monotonic sums may decrease, so they can't be counters.
+                    let value = if sum.is_monotonic {
+                        MetricValue::Counter { value }
+                    } else {
+                        MetricValue::Gauge { value }
+                    };
+                    Some(event(kind, value, point.time_unix_nano, point.attributes))
+                })
+                .collect()
+        }
+        Some(Data::Histogram(histogram)) => {
+            let kind = metric_kind(histogram.aggregation_temporality);
+            histogram
+                .data_points
+                .into_iter()
+                .filter(|point| is_recorded(point.flags))
+                .map(|point| {
+                    let value = MetricValue::AggregatedHistogram {
+                        buckets: explicit_buckets(&point),
+                        count: point.count,
+                        sum: point.sum.unwrap_or_default(),
+                    };
+                    event(kind, value, point.time_unix_nano, point.attributes)
+                })
+                .collect()
+        }
+        Some(Data::ExponentialHistogram(histogram)) => {
+            let kind = metric_kind(histogram.aggregation_temporality);
+            histogram
+                .data_points
+                .into_iter()
+                .filter(|point| is_recorded(point.flags))
+                .map(|point| {
+                    let value = MetricValue::AggregatedHistogram {
+                        buckets: exponential_buckets(&point),
+                        count: point.count,
+                        sum: point.sum.unwrap_or_default(),
+                    };
+                    event(kind, value, point.time_unix_nano, point.attributes)
+                })
+                .collect()
+        }
+        Some(Data::Summary(summary)) => summary
+            .data_points
+            .into_iter()
+            .filter(|point| is_recorded(point.flags))
+            .map(|point| {
+                let value = MetricValue::AggregatedSummary {
+                    quantiles: point
+                        .quantile_values
+                        .iter()
+                        .map(|quantile| Quantile {
+                            quantile: quantile.quantile,
+                            value: quantile.value,
+                        })
+                        .collect(),
+                    count: point.count,
+                    sum: point.sum,
+                };
+                event(
+                    MetricKind::Absolute,
+                    value,
+                    point.time_unix_nano,
+                    point.attributes,
+                )
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
@@ -0,0 +1,148 @@
+//! Support for the JSON encoding of OTLP requests and responses.
+//!
+//! The OTLP JSON encoding follows the Protobuf JSON mapping, except for trace and span IDs, which
+//! are hex encoded rather than base64 encoded.
+//!
+//! See <https://opentelemetry.io/docs/specs/otlp/#json-protobuf-encoding>.
+
+use std::sync::OnceLock;
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use prost::Message;
+use prost_reflect::{DescriptorPool, DeserializeOptions, DynamicMessage};
+use serde_json::Value as JsonValue;
+
+pub const EXPORT_LOGS_REQUEST: &str =
+    "opentelemetry.proto.collector.logs.v1.ExportLogsServiceRequest";
+pub const EXPORT_LOGS_RESPONSE: &str =
+    "opentelemetry.proto.collector.logs.v1.ExportLogsServiceResponse";
+pub const EXPORT_METRICS_REQUEST: &str =
+    "opentelemetry.proto.collector.metrics.v1.ExportMetricsServiceRequest";
+pub const EXPORT_METRICS_RESPONSE: &str =
+    "opentelemetry.proto.collector.metrics.v1.ExportMetricsServiceResponse";
+pub const EXPORT_TRACE_REQUEST: &str =
+    "opentelemetry.proto.collector.trace.v1.ExportTraceServiceRequest";
+pub const EXPORT_TRACE_RESPONSE: &str =
+    "opentelemetry.proto.collector.trace.v1.ExportTraceServiceResponse";
+
+/// The fields holding trace and span IDs, by their JSON and Protobuf names.
+const ID_FIELDS: [&str; 6] = [
+    "traceId",
+    "spanId",
+    "parentSpanId",
+    "trace_id",
+    "span_id",
+    "parent_span_id",
+];
+
+fn descriptors() -> &'static DescriptorPool {
+    static DESCRIPTORS: OnceLock<DescriptorPool> = OnceLock::new();
+    DESCRIPTORS.get_or_init(|| {
+        DescriptorPool::decode(
+            include_bytes!(concat!(env!("OUT_DIR"), "/opentelemetry-fds.bin")).as_ref(),
+        )
+        .expect(
+            "should not fail to decode protobuf file descriptor set generated from build script",
+        )
+    })
+}
+
+/// Decodes the JSON encoding of the message with the given full name.
+///
+/// Unknown fields are ignored, as required by the specification.
+pub fn decode<M: Message + Default>(
+    body: &[u8],
+    message_name: &str,
+) -> Result<M, serde_json::Error> {
+    let mut value: JsonValue = serde_json::from_slice(body)?;
+    hex_ids_to_base64(&mut value);
+
+    let descriptor = descriptors()
+        .get_message_by_name(message_name)
+        .expect("OTLP message descriptors are generated from build script");
+    let message = DynamicMessage::deserialize_with_options(
+        descriptor,
+        value,
+        &DeserializeOptions::new().deny_unknown_fields(false),
+    )?;
+    message
+        .transcode_to()
+        .map_err(|error| serde_json::Error::io(std::io::Error::other(error)))
+}
+
+/// Encodes the message with the given full name to JSON.
+pub fn encode<M: Message>(message: &M, message_name: &str) -> Result<Vec<u8>, serde_json::Error> {
+    let descriptor = descriptors()
+        .get_message_by_name(message_name)
+        .expect("OTLP message descriptors are generated from build script");
+    let mut dynamic = DynamicMessage::new(descriptor);
+    dynamic
+        .transcode_from(message)
+        .map_err(|error| serde_json::Error::io(std::io::Error::other(error)))?;
+    serde_json::to_vec(&dynamic)
+}
+
+fn hex_ids_to_base64(value: &mut JsonValue) {
+    match value {
+        JsonValue::Object(fields) => {
+            for (key, value) in fields.iter_mut() {
+                match value {
+                    JsonValue::String(id) if ID_FIELDS.contains(&key.as_str()) => {
+                        if let Ok(bytes) = hex::decode(id.as_bytes()) {
+                            *id = BASE64_STANDARD.encode(bytes);
+                        }
+                    }
+                    value => hex_ids_to_base64(value),
+                }
+            }
+        }
+        JsonValue::Array(values) => values.iter_mut().for_each(hex_ids_to_base64),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::collector::trace::v1::{
+        ExportTraceServiceRequest, ExportTraceServiceResponse,
+    };
+
+    #[test]
+    fn decodes_hex_ids() {
+        let body = br#"{
+            "resourceSpans": [{
+                "scopeSpans": [{
+                    "spans": [{
+                        "traceId": "5b8efff798038103d269b633813fc60c",
+                        "spanId": "eee19b7ec3c1b174",
+                        "name": "request",
+                        "kind": 2,
+                        "startTimeUnixNano": "1544712660000000000",
+                        "unknownField": true
+                    }]
+                }]
+            }]
+        }"#;
+
+        let request: ExportTraceServiceRequest = decode(body, EXPORT_TRACE_REQUEST).unwrap();
+        let span = &request.resource_spans[0].scope_spans[0].spans[0];
+        assert_eq!(
+            hex::encode(&span.trace_id),
+            "5b8efff798038103d269b633813fc60c"
+        );
+        assert_eq!(hex::encode(&span.span_id), "eee19b7ec3c1b174");
+        assert_eq!(span.name, "request");
+        assert_eq!(span.kind, 2);
+        assert_eq!(span.start_time_unix_nano, 1544712660000000000);
+    }
+
+    #[test]
+    fn encodes_responses() {
+        let response = encode(
+            &ExportTraceServiceResponse::default(),
+            EXPORT_TRACE_RESPONSE,
+        );
+        assert_eq!(response.unwrap(), b"{}");
+    }
+}
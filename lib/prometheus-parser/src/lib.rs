@@ -7,7 +7,7 @@ use snafu::ResultExt;
 
 mod line;
 
-pub use line::ErrorKind;
+pub use line::{ErrorKind, Exemplar};
 use line::{Line, Metric, MetricKind};
 
 pub const METRIC_NAME_LABEL: &str = "__name__";
@@ -81,6 +81,7 @@ pub struct SummaryMetric {
     pub quantiles: Vec<SummaryQuantile>,
     pub sum: f64,
     pub count: u64,
+    pub exemplars: Vec<Exemplar>,
 }
 
 #[derive(Debug, Default, PartialEq, PartialOrd)]
@@ -94,6 +95,7 @@ pub struct HistogramMetric {
     pub buckets: Vec<HistogramBucket>,
     pub sum: f64,
     pub count: u64,
+    pub exemplars: Vec<Exemplar>,
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -153,6 +155,7 @@ impl GroupKind {
             labels: metric.labels,
         };
         let value = metric.value;
+        let exemplar = metric.exemplar;
 
         match self {
             Self::Counter(ref mut metrics)
@@ -164,6 +167,7 @@ impl GroupKind {
                         timestamp: key.timestamp,
                         labels: key.labels,
                         value,
+                        exemplar,
                     }));
                 }
                 metrics.insert(key, SimpleMetric { value });
@@ -175,9 +179,9 @@ impl GroupKind {
                         .map_err(Into::into)
                         .context(ParseLabelValueSnafu)?;
                     let count = try_f64_to_u64(metric.value)?;
-                    matching_group(metrics, key)
-                        .buckets
-                        .push(HistogramBucket { bucket, count });
+                    let group = matching_group(metrics, key);
+                    group.buckets.push(HistogramBucket { bucket, count });
+                    group.exemplars.extend(exemplar);
                 }
                 "_sum" => {
                     let sum = metric.value;
@@ -193,6 +197,7 @@ impl GroupKind {
                         timestamp: key.timestamp,
                         labels: key.labels,
                         value,
+                        exemplar,
                     }))
                 }
             },
@@ -206,9 +211,9 @@ impl GroupKind {
                     let (_, quantile) = line::Metric::parse_value(&quantile)
                         .map_err(Into::into)
                         .context(ParseLabelValueSnafu)?;
-                    matching_group(metrics, key)
-                        .quantiles
-                        .push(SummaryQuantile { quantile, value });
+                    let group = matching_group(metrics, key);
+                    group.quantiles.push(SummaryQuantile { quantile, value });
+                    group.exemplars.extend(exemplar);
                 }
                 "_sum" => {
                     let sum = metric.value;
@@ -224,6 +229,7 @@ impl GroupKind {
                         timestamp: key.timestamp,
                         labels: key.labels,
                         value,
+                        exemplar,
                     }))
                 }
             },
@@ -262,6 +268,7 @@ impl MetricGroup {
             labels,
             value,
             timestamp,
+            ..
         } = metric;
         let key = GroupKey { timestamp, labels };
         MetricGroup {
@@ -364,6 +371,7 @@ impl MetricGroupSet {
                 labels: labels.clone(),
                 value: sample.value,
                 timestamp: Some(sample.timestamp),
+                exemplar: None,
             },
         )? {
             let key = GroupKey {
@@ -563,6 +571,7 @@ mod test {
                     ],
                     count: 144320,
                     sum: 53423.0,
+                    exemplars: vec![],
                 },
             ));
         });
@@ -579,6 +588,7 @@ mod test {
                     ],
                     count: 10,
                     sum: 5.0,
+                    exemplars: vec![],
                 },
             ));
         });
@@ -599,11 +609,43 @@ mod test {
                     ],
                     count: 4588206224,
                     sum: 1.7560473e+07,
+                    exemplars: vec![],
                 },
             ));
         });
     }
 
+    #[test]
+    fn test_parse_text_exemplars() {
+        let input = r#"
+            # TYPE http_request_duration_seconds histogram
+            http_request_duration_seconds_bucket{le="0.1"} 8 # {trace_id="KOO5S4vxi0o"} 0.067 1600000000
+            http_request_duration_seconds_bucket{le="+Inf"} 10 # {trace_id="oHg5SJYRHA0"} 9.8
+            http_request_duration_seconds_sum 12.3
+            http_request_duration_seconds_count 10
+            "#;
+        let output = parse_text(input).unwrap();
+        assert_eq!(output.len(), 1);
+        match_group!(output[0], "http_request_duration_seconds", Histogram => |metrics: &MetricMap<HistogramMetric>| {
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(
+                metrics.get_index(0).unwrap().1.exemplars,
+                vec![
+                    Exemplar {
+                        labels: labels!(trace_id => "KOO5S4vxi0o"),
+                        value: 0.067,
+                        timestamp: Some(1600000000000),
+                    },
+                    Exemplar {
+                        labels: labels!(trace_id => "oHg5SJYRHA0"),
+                        value: 9.8,
+                        timestamp: None,
+                    },
+                ]
+            );
+        });
+    }
+
     #[test]
     fn test_f64_to_u64() {
         let value = -1.0;
@@ -736,6 +778,7 @@ mod test {
                     samples: vec![
                         $( proto::Sample { value: $sample as f64, timestamp: $timestamp as i64 }, )*
                     ],
+                    exemplars: vec![],
                 }, )* ],
             }
         };
@@ -839,6 +882,7 @@ mod test {
                         ],
                         count: 19,
                         sum: 12.0,
+                        exemplars: vec![],
                     })
             );
         });
@@ -878,6 +922,7 @@ mod test {
                         ],
                         count: 21,
                         sum: 12.0,
+                        exemplars: vec![],
                     })
             );
         });
@@ -100,6 +100,16 @@ pub struct Metric {
     pub labels: BTreeMap<String, String>,
     pub value: f64,
     pub timestamp: Option<i64>,
+    pub exemplar: Option<Exemplar>,
+}
+
+/// An OpenMetrics exemplar, attached to a sample.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exemplar {
+    pub labels: BTreeMap<String, String>,
+    pub value: f64,
+    /// The timestamp of the exemplar, in milliseconds.
+    pub timestamp: Option<i64>,
 }
 
 impl Metric {
@@ -108,16 +118,15 @@ impl Metric {
     /// ``` text
     /// metric_name [
     ///   "{" label_name "=" `"` label_value `"` { "," label_name "=" `"` label_value `"` } [ "," ] "}"
-    /// ] value [ timestamp ]
+    /// ] value [ timestamp ] [ "#" exemplar ]
     /// ```
-    ///
-    /// We don't parse timestamp.
     fn parse(input: &str) -> IResult<Self> {
         let input = trim_space(input);
         let (input, name) = parse_name(input)?;
         let (input, labels) = Self::parse_labels(input)?;
         let (input, value) = Self::parse_value(input)?;
         let (input, timestamp) = Self::parse_timestamp(input)?;
+        let (input, exemplar) = Self::parse_exemplar(input);
         Ok((
             input,
             Metric {
@@ -125,10 +134,46 @@ impl Metric {
                 labels,
                 value,
                 timestamp,
+                exemplar,
             },
         ))
     }
 
+    /// Parse an OpenMetrics exemplar with format
+    ///
+    /// ``` text
+    /// "#" "{" label_name "=" `"` label_value `"` { "," label_name "=" `"` label_value `"` } "}"
+    /// value [ timestamp ]
+    /// ```
+    ///
+    /// where the timestamp is in seconds. Invalid exemplars are ignored, like any other trailing
+    /// content of the line.
+    fn parse_exemplar(input: &str) -> (&str, Option<Exemplar>) {
+        fn parse(input: &str) -> IResult<Exemplar> {
+            let (input, _) = match_char('#')(input)?;
+            let (input, _) = match_char('{')(input)?;
+            let (input, labels) = Metric::parse_labels_inner(input)?;
+            let (input, value) = Metric::parse_value(input)?;
+            let (input, timestamp) = match Metric::parse_value(input) {
+                Ok((input, seconds)) => (input, Some((seconds * 1000.0).round() as i64)),
+                Err(_) => (input, None),
+            };
+            Ok((
+                input,
+                Exemplar {
+                    labels,
+                    value,
+                    timestamp,
+                },
+            ))
+        }
+
+        match parse(input) {
+            Ok((input, exemplar)) => (input, Some(exemplar)),
+            Err(_) => (input, None),
+        }
+    }
+
     /// Float value, and +Inf, -Int, Nan.
     pub(crate) fn parse_value(input: &str) -> IResult<f64> {
         let input = trim_space(input);
@@ -711,4 +756,27 @@ mod test {
             "#;
         assert!(input.lines().map(Line::parse).all(|r| r.is_ok()));
     }
+
+    #[test]
+    fn test_parse_exemplar() {
+        let input = r#"foo_bucket{le="0.5"} 17 # {trace_id="oHg5SJYRHA0"} 0.43 1520879607.789"#;
+        let (_, metric) = Metric::parse(input).unwrap();
+        assert_eq!(metric.value, 17.0);
+        assert_eq!(
+            metric.exemplar,
+            Some(Exemplar {
+                labels: btreemap! { "trace_id" => "oHg5SJYRHA0" },
+                value: 0.43,
+                timestamp: Some(1520879607789),
+            })
+        );
+
+        let (_, metric) = Metric::parse(r#"foo_bucket{le="1"} 3 # {trace_id="abc"} 0.9"#).unwrap();
+        assert_eq!(metric.exemplar.unwrap().timestamp, None);
+
+        // Invalid exemplars are ignored.
+        let (_, metric) = Metric::parse("foo 3 # not an exemplar").unwrap();
+        assert_eq!(metric.value, 3.0);
+        assert_eq!(metric.exemplar, None);
+    }
 }
@@ -74,7 +74,8 @@ impl Drop for PathGuard {
 
 fn create_disk_v2_variant(_max_events: usize, max_size: u64) -> BufferType {
     BufferType::DiskV2 {
-        max_size: NonZeroU64::new(max_size).unwrap(),
+        max_size: NonZeroU64::new(max_size),
+        weight: None,
         when_full: WhenFull::DropNewest,
     }
 }
@@ -271,7 +271,8 @@ where
                 max_size_bytes
             );
             BufferType::DiskV2 {
-                max_size: max_size_bytes,
+                max_size: Some(max_size_bytes),
+                weight: None,
                 when_full,
             }
         }
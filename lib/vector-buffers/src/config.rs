@@ -1,6 +1,6 @@
 use std::{
     fmt,
-    num::{NonZeroU64, NonZeroUsize},
+    num::{NonZeroU32, NonZeroU64, NonZeroUsize},
     path::{Path, PathBuf},
     slice,
 };
@@ -16,7 +16,7 @@ use crate::{
         builder::{TopologyBuilder, TopologyError},
         channel::{BufferReceiver, BufferSender},
     },
-    variants::{DiskV2Buffer, MemoryBuffer},
+    variants::{self, DiskV2Buffer, MemoryBuffer},
     Bufferable, WhenFull,
 };
 
@@ -28,6 +28,15 @@ pub enum BufferBuildError {
     FailedToBuildTopology { source: TopologyError },
     #[snafu(display("`max_events` must be greater than zero"))]
     InvalidMaxEvents,
+    #[snafu(display(
+        "the disk buffer has a `weight` but no `max_size` was allocated from the global disk buffer budget"
+    ))]
+    RequiresMaxSize,
+}
+
+/// Gets the minimum `max_size` of a disk buffer, below which building the buffer fails.
+pub fn disk_buffer_minimum_size() -> u64 {
+    variants::disk_v2::get_default_minimum_buffer_size()
 }
 
 #[derive(Deserialize, Serialize)]
@@ -38,7 +47,7 @@ enum BufferTypeKind {
     DiskV2,
 }
 
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
+const ALL_FIELDS: [&str; 5] = ["type", "max_events", "max_size", "weight", "when_full"];
 
 struct BufferTypeVisitor;
 
@@ -50,6 +59,7 @@ impl BufferTypeVisitor {
         let mut kind: Option<BufferTypeKind> = None;
         let mut max_events: Option<NonZeroUsize> = None;
         let mut max_size: Option<NonZeroU64> = None;
+        let mut weight: Option<NonZeroU32> = None;
         let mut when_full: Option<WhenFull> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
@@ -71,6 +81,12 @@ impl BufferTypeVisitor {
                     }
                     max_size = Some(map.next_value()?);
                 }
+                "weight" => {
+                    if weight.is_some() {
+                        return Err(de::Error::duplicate_field("weight"));
+                    }
+                    weight = Some(map.next_value()?);
+                }
                 "when_full" => {
                     if when_full.is_some() {
                         return Err(de::Error::duplicate_field("when_full"));
@@ -86,11 +102,16 @@ impl BufferTypeVisitor {
         let when_full = when_full.unwrap_or_default();
         match kind {
             BufferTypeKind::Memory => {
-                if max_size.is_some() {
-                    return Err(de::Error::unknown_field(
-                        "max_size",
-                        &["type", "max_events", "when_full"],
-                    ));
+                for (field, present) in [
+                    ("max_size", max_size.is_some()),
+                    ("weight", weight.is_some()),
+                ] {
+                    if present {
+                        return Err(de::Error::unknown_field(
+                            field,
+                            &["type", "max_events", "when_full"],
+                        ));
+                    }
                 }
                 Ok(BufferType::Memory {
                     max_events: max_events.unwrap_or_else(memory_buffer_default_max_events),
@@ -101,13 +122,20 @@ impl BufferTypeVisitor {
                 if max_events.is_some() {
                     return Err(de::Error::unknown_field(
                         "max_events",
-                        &["type", "max_size", "when_full"],
+                        &["type", "max_size", "weight", "when_full"],
                     ));
                 }
-                Ok(BufferType::DiskV2 {
-                    max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
-                    when_full,
-                })
+                match (max_size, weight) {
+                    (Some(_), Some(_)) => Err(de::Error::custom(
+                        "only one of `max_size` and `weight` can be set for a disk buffer",
+                    )),
+                    (None, None) => Err(de::Error::missing_field("max_size")),
+                    (max_size, weight) => Ok(BufferType::DiskV2 {
+                        max_size,
+                        weight,
+                        when_full,
+                    }),
+                }
             }
         }
     }
@@ -209,11 +237,24 @@ pub enum BufferType {
         /// The maximum size of the buffer on disk.
         ///
         /// Must be at least ~256 megabytes (268435488 bytes).
+        ///
+        /// Required unless `weight` is set.
         #[configurable(
             validation(range(min = 268435488)),
             metadata(docs::type_unit = "bytes")
         )]
-        max_size: NonZeroU64,
+        max_size: Option<NonZeroU64>,
+
+        /// The weight of this buffer's share of the global disk buffer budget.
+        ///
+        /// Instead of a fixed `max_size`, the buffer is allocated a share of the
+        /// `disk_buffer_budget` left over once buffers with a fixed `max_size` are accounted for,
+        /// proportional to its weight among all weighted disk buffers. The share must be at least the
+        /// minimum `max_size` of ~256 megabytes (268435488 bytes).
+        ///
+        /// Cannot be set together with `max_size`.
+        #[configurable(metadata(docs::examples = 1))]
+        weight: Option<NonZeroU32>,
 
         #[configurable(derived)]
         #[serde(default)]
@@ -250,12 +291,13 @@ impl BufferType {
             Some(global_data_dir) => match self {
                 Self::Memory { .. } => None,
                 Self::DiskV2 { max_size, .. } => {
+                    let max_size = (*max_size)?;
                     let data_dir = crate::variants::disk_v2::get_disk_v2_data_dir_path(
                         &global_data_dir,
                         id.id(),
                     );
 
-                    Some(DiskUsage::new(id.clone(), data_dir, *max_size))
+                    Some(DiskUsage::new(id.clone(), data_dir, max_size))
                 }
             },
         }
@@ -286,7 +328,9 @@ impl BufferType {
             BufferType::DiskV2 {
                 when_full,
                 max_size,
+                ..
             } => {
+                let max_size = max_size.ok_or(BufferBuildError::RequiresMaxSize)?;
                 let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
                 builder.stage(DiskV2Buffer::new(id, data_dir, max_size), when_full);
             }
@@ -310,10 +354,9 @@ impl BufferType {
 /// functionality to allow chaining buffers together, you'll see "buffer topology" used in internal
 /// documentation to correctly reflect the internal structure.
 ///
-// TODO: We need to limit chained buffers to only allowing a single copy of each buffer type to be
-// defined, otherwise, for example, two instances of the same disk buffer type in a single chained
-// buffer topology would try to both open the same buffer files on disk, which wouldn't work or
-// would go horribly wrong.
+/// Chained buffers are limited to a memory stage overflowing to a disk stage, as two instances of
+/// the same disk buffer type in a single chained buffer topology would try to both open the same
+/// buffer files on disk.
 #[configurable_component]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[serde(untagged)]
@@ -322,6 +365,10 @@ impl BufferType {
     description = r#"More information about the individual buffer types, and buffer behavior, can be found in the
 [Buffering Model][buffering_model] section.
 
+A memory buffer can spill to a disk buffer by configuring a list of two buffers: the memory buffer,
+with `when_full` set to `overflow`, followed by the disk buffer. Events are then only written to
+disk when the memory buffer is full.
+
 [buffering_model]: /docs/about/under-the-hood/architecture/buffering-model/"#
 )]
 pub enum BufferConfig {
@@ -350,6 +397,14 @@ impl BufferConfig {
         }
     }
 
+    /// Gets all of the configured stages for this buffer, mutably.
+    pub fn stages_mut(&mut self) -> &mut [BufferType] {
+        match self {
+            Self::Single(stage) => slice::from_mut(stage),
+            Self::Chained(stages) => stages.as_mut_slice(),
+        }
+    }
+
     /// Builds the buffer components represented by this configuration.
     ///
     /// The caller gets back a `Sink` and `Stream` implementation that represent a way to push items
@@ -387,7 +442,7 @@ impl BufferConfig {
 
 #[cfg(test)]
 mod test {
-    use std::num::{NonZeroU64, NonZeroUsize};
+    use std::num::{NonZeroU32, NonZeroU64, NonZeroUsize};
 
     use crate::{BufferConfig, BufferType, WhenFull};
 
@@ -409,6 +464,11 @@ mod test {
     const BUFFER_CONFIG_NO_MATCH_ERR: &str =
         "data did not match any variant of untagged enum BufferConfig";
 
+    #[test]
+    fn disk_buffer_minimum_size_matches_docs() {
+        assert_eq!(super::disk_buffer_minimum_size(), 268_435_488);
+    }
+
     #[test]
     fn parse_empty() {
         let source = "";
@@ -466,6 +526,30 @@ max_events: 42
         );
     }
 
+    #[test]
+    fn parse_memory_overflow_to_disk() {
+        check_multiple_stages(
+            r"
+          - type: memory
+            max_events: 1000
+            when_full: overflow
+          - type: disk
+            max_size: 268435488
+          ",
+            &[
+                BufferType::Memory {
+                    max_events: NonZeroUsize::new(1000).unwrap(),
+                    when_full: WhenFull::Overflow,
+                },
+                BufferType::DiskV2 {
+                    max_size: NonZeroU64::new(268435488),
+                    weight: None,
+                    when_full: WhenFull::Block,
+                },
+            ],
+        );
+    }
+
     #[test]
     fn ensure_field_defaults_for_all_types() {
         check_single_stage(
@@ -517,9 +601,38 @@ max_events: 42
           max_size: 1024
           ",
             BufferType::DiskV2 {
-                max_size: NonZeroU64::new(1024).unwrap(),
+                max_size: NonZeroU64::new(1024),
+                weight: None,
                 when_full: WhenFull::Block,
             },
         );
     }
+
+    #[test]
+    fn parse_disk_weight() {
+        check_single_stage(
+            r"
+          type: disk
+          weight: 3
+          when_full: drop_newest
+          ",
+            BufferType::DiskV2 {
+                max_size: None,
+                weight: NonZeroU32::new(3),
+                when_full: WhenFull::DropNewest,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_disk_requires_max_size_or_weight() {
+        for source in [
+            "type: disk",
+            "type: disk\nmax_size: 1024\nweight: 1",
+            "type: memory\nweight: 1",
+        ] {
+            let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+            assert_eq!(error.to_string(), BUFFER_CONFIG_NO_MATCH_ERR);
+        }
+    }
 }
@@ -62,12 +62,12 @@ pub enum WhenFull {
     /// Overflows to the next stage in the buffer topology.
     ///
     /// If the current buffer stage is full, attempt to send this event to the next buffer stage.
-    /// That stage may also be configured overflow, and so on, but ultimately the last stage in a
-    /// buffer topology must use one of the other handling behaviors. This means that next stage may
-    /// potentially be able to buffer the event, but it may also block or drop the event.
+    /// The last stage in a buffer topology must use one of the other handling behaviors, so the
+    /// next stage may potentially be able to buffer the event, but it may also block or drop the
+    /// event.
     ///
-    /// This mode can only be used when two or more buffer stages are configured.
-    #[configurable(metadata(docs::hidden))]
+    /// This mode can only be used by a memory buffer stage in front of a disk buffer stage, so that
+    /// events are only written to disk when the memory buffer is full.
     Overflow,
 }
 
@@ -95,6 +95,14 @@ fn get_minimum_buffer_size(max_data_file_size: u64) -> Option<u64> {
         .and_then(|doubled| doubled.checked_add(ledger_len))
 }
 
+/// Gets the minimum buffer size of disk buffers built with the default maximum data file size.
+pub(crate) fn get_default_minimum_buffer_size() -> u64 {
+    let max_data_file_size = u64::try_from(DEFAULT_MAX_DATA_FILE_SIZE)
+        .expect("Default maximum data file size should never be greater than 2^64 bytes.");
+    get_minimum_buffer_size(max_data_file_size)
+        .expect("Default maximum data file size should be correctly limited.")
+}
+
 #[derive(Debug, Snafu)]
 pub enum BuildError {
     #[snafu(display("parameter '{}' was invalid: {}", param_name, reason))]
@@ -194,6 +194,7 @@ mod writer;
 #[cfg(test)]
 mod tests;
 
+pub(crate) use self::common::get_default_minimum_buffer_size;
 use self::ledger::Ledger;
 pub use self::{
     common::{DiskBufferConfig, DiskBufferConfigBuilder},
@@ -50,6 +50,9 @@ pub mod internal_event;
 
 pub mod request_metadata;
 
+#[cfg(feature = "block_on")]
+pub mod runtime;
+
 pub mod shutdown;
 
 #[cfg(feature = "sensitive_string")]
@@ -0,0 +1,61 @@
+//! Running asynchronous code from synchronous code, such as VRL functions and codecs.
+
+use std::future::Future;
+
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// Drives the future to completion from synchronous code.
+///
+/// Within a multi-threaded runtime, such as the one Vector runs on, the worker thread is handed
+/// over to the runtime while blocking, so that its other tasks keep being driven by the other
+/// workers. Otherwise, such as in tests, the future is driven by a temporary runtime.
+///
+/// # Panics
+///
+/// Panics if the temporary runtime can't be created, or if the future panics while driven by a
+/// temporary runtime on another thread.
+pub fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    let temporary_runtime = || {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Creating a runtime shouldn't fail")
+    };
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(|| handle.block_on(future))
+        }
+        // A runtime can't be started from a thread already driving one.
+        Ok(_) => std::thread::scope(|scope| {
+            scope
+                .spawn(|| temporary_runtime().block_on(future))
+                .join()
+                .expect("Blocking future panicked")
+        }),
+        Err(_) => temporary_runtime().block_on(future),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::block_on;
+
+    #[test]
+    fn blocks_outside_of_runtimes() {
+        assert_eq!(block_on(async { 1 }), 1);
+    }
+
+    #[tokio::test]
+    async fn blocks_in_current_thread_runtimes() {
+        assert_eq!(block_on(async { 1 }), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn blocks_in_multi_threaded_runtimes() {
+        assert_eq!(block_on(async { 1 }), 1);
+    }
+}
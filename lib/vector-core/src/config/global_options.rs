@@ -1,4 +1,4 @@
-use std::{fs::DirBuilder, path::PathBuf, time::Duration};
+use std::{fs::DirBuilder, num::NonZeroU64, path::PathBuf, time::Duration};
 
 use snafu::{ResultExt, Snafu};
 use vector_common::TimeZone;
@@ -108,6 +108,17 @@ pub struct GlobalOptions {
     /// a small amount of memory for each metric.
     #[serde(default, skip_serializing_if = "crate::serde::is_default")]
     pub expire_metrics_secs: Option<f64>,
+
+    /// The total amount of disk space, in bytes, shared between all disk buffers.
+    ///
+    /// Disk buffers configured with a `weight` instead of a `max_size` are allocated a share of
+    /// this budget proportional to their weight, once the space used by disk buffers with a fixed
+    /// `max_size` has been subtracted. Each buffer then applies its own `when_full` behavior when
+    /// its share is used up.
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 53687091200))]
+    pub disk_buffer_budget: Option<NonZeroU64>,
 }
 
 impl GlobalOptions {
@@ -193,6 +204,10 @@ impl GlobalOptions {
             errors.push("conflicting values for 'acknowledgements' found".to_owned());
         }
 
+        if conflicts(&self.disk_buffer_budget, &with.disk_buffer_budget) {
+            errors.push("conflicting values for 'disk_buffer_budget' found".to_owned());
+        }
+
         let data_dir = if self.data_dir.is_none() || self.data_dir == default_data_dir() {
             with.data_dir
         } else if with.data_dir != default_data_dir() && self.data_dir != with.data_dir {
@@ -224,6 +239,7 @@ impl GlobalOptions {
                 proxy: self.proxy.merge(&with.proxy),
                 expire_metrics: self.expire_metrics.or(with.expire_metrics),
                 expire_metrics_secs: self.expire_metrics_secs.or(with.expire_metrics_secs),
+                disk_buffer_budget: self.disk_buffer_budget.or(with.disk_buffer_budget),
             })
         } else {
             Err(errors)
@@ -347,6 +363,26 @@ mod tests {
         assert_eq!(merge(Some(4.0), Some(5.0)), Ok(Some(4.0))); // Uses minimum
     }
 
+    #[test]
+    fn merges_disk_buffer_budget() {
+        let merge = |a, b| {
+            merge("disk_buffer_budget", a, b, |result| {
+                result.disk_buffer_budget.map(NonZeroU64::get)
+            })
+        };
+
+        assert_eq!(merge(None, None), Ok(None));
+        assert_eq!(merge(Some(1), None), Ok(Some(1)));
+        assert_eq!(merge(None, Some(2)), Ok(Some(2)));
+        assert_eq!(merge(Some(3), Some(3)), Ok(Some(3)));
+        assert_eq!(
+            merge(Some(4), Some(5)),
+            Err(vec![
+                "conflicting values for 'disk_buffer_budget' found".into()
+            ])
+        );
+    }
+
     fn merge<P: Debug, T>(
         name: &str,
         dd1: Option<P>,
@@ -171,7 +171,7 @@ impl SourceOutput {
 
         self.schema_definition.as_ref().map(|definition| {
             if schema_enabled {
-                definition.deref().clone()
+                definition.deref().clone().with_default_meanings()
             } else {
                 let mut new_definition =
                     schema::Definition::default_for_namespace(definition.log_namespaces());
@@ -235,7 +235,12 @@ impl TransformOutput {
         schema_enabled: bool,
     ) -> HashMap<OutputId, schema::Definition> {
         if schema_enabled {
-            self.log_schema_definitions.clone()
+            self.log_schema_definitions
+                .iter()
+                .map(|(output, definition)| {
+                    (output.clone(), definition.clone().with_default_meanings())
+                })
+                .collect()
         } else {
             self.log_schema_definitions
                 .iter()
@@ -1,10 +1,11 @@
-use std::num::NonZeroU32;
+use std::{collections::BTreeMap, num::NonZeroU32};
 
 use chrono::{DateTime, Utc};
 use vector_common::byte_size_of::ByteSizeOf;
 use vector_config::configurable_component;
 
 use super::{MetricKind, MetricValue};
+use crate::float_eq;
 
 /// Metric data.
 #[configurable_component]
@@ -18,6 +19,12 @@ pub struct MetricData {
 
     #[serde(flatten)]
     pub value: MetricValue,
+
+    /// Exemplars of the observations summarized by the value.
+    ///
+    /// Exemplars link the value to the traces the observations were made in, through their labels.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exemplars: Vec<Exemplar>,
 }
 
 /// Metric time.
@@ -65,6 +72,7 @@ impl MetricData {
             time: self.time,
             kind: MetricKind::Absolute,
             value: self.value,
+            exemplars: self.exemplars,
         }
     }
 
@@ -77,12 +85,25 @@ impl MetricData {
             time: self.time,
             kind: MetricKind::Incremental,
             value: self.value,
+            exemplars: self.exemplars,
         }
     }
 
     /// Creates a `MetricData` directly from the raw components of another `MetricData`.
+    ///
+    /// The created data has no exemplars.
     pub fn from_parts(time: MetricTime, kind: MetricKind, value: MetricValue) -> Self {
-        Self { time, kind, value }
+        Self {
+            time,
+            kind,
+            value,
+            exemplars: Vec::new(),
+        }
+    }
+
+    /// Gets a reference to the exemplars of this data.
+    pub fn exemplars(&self) -> &[Exemplar] {
+        &self.exemplars
     }
 
     /// Decomposes a `MetricData` into its individual parts.
@@ -125,6 +146,10 @@ impl MetricData {
         self.value.add(&other.value) && {
             self.time.timestamp = new_ts;
             self.time.interval_ms = new_interval;
+            // Only the most recent exemplars are kept, to bound the exemplars of aggregated metrics.
+            if !other.exemplars.is_empty() {
+                self.exemplars.clone_from(&other.exemplars);
+            }
             true
         }
     }
@@ -165,6 +190,38 @@ impl PartialOrd for MetricData {
 
 impl ByteSizeOf for MetricData {
     fn allocated_bytes(&self) -> usize {
-        self.value.allocated_bytes()
+        self.value.allocated_bytes() + self.exemplars.allocated_bytes()
+    }
+}
+
+/// An exemplar.
+///
+/// Exemplars are single observations, such as the duration of a request, annotated with labels
+/// identifying where they were made, typically the ID of a trace.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct Exemplar {
+    /// The labels of the exemplar, such as `trace_id`.
+    pub labels: BTreeMap<String, String>,
+
+    /// The value of the observation.
+    pub value: f64,
+
+    /// The timestamp of the observation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl PartialEq for Exemplar {
+    fn eq(&self, other: &Self) -> bool {
+        self.labels == other.labels
+            && float_eq(self.value, other.value)
+            && self.timestamp == other.timestamp
+    }
+}
+
+impl ByteSizeOf for Exemplar {
+    fn allocated_bytes(&self) -> usize {
+        self.labels.allocated_bytes()
     }
 }
@@ -97,6 +97,7 @@ impl Metric {
                 },
                 kind,
                 value,
+                exemplars: Vec::new(),
             },
             metadata,
         }
@@ -168,6 +169,14 @@ impl Metric {
         self
     }
 
+    /// Consumes this metric, returning it with updated exemplars.
+    #[inline]
+    #[must_use]
+    pub fn with_exemplars(mut self, exemplars: Vec<Exemplar>) -> Self {
+        self.data.exemplars = exemplars;
+        self
+    }
+
     /// Gets a reference to the series of this metric.
     ///
     /// The "series" is the name of the metric itself, including any tags. In other words, it is the unique identifier
@@ -241,6 +250,11 @@ impl Metric {
         self.data.time.interval_ms
     }
 
+    /// Gets a reference to the exemplars of this metric.
+    pub fn exemplars(&self) -> &[Exemplar] {
+        &self.data.exemplars
+    }
+
     /// Gets a reference to the value of this metric.
     #[inline]
     pub fn value(&self) -> &MetricValue {
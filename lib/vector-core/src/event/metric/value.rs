@@ -165,6 +165,51 @@ impl MetricValue {
         }
     }
 
+    /// Converts a sketch to an aggregated histogram.
+    ///
+    /// Histogram bucket bounds are based on `buckets`, as with distributions, the observations of each bin of the
+    /// sketch being counted towards the bucket holding the value represented by the bin.
+    ///
+    /// If this value is not a sketch, then `None` is returned.  Otherwise, `Some(MetricValue::AggregatedHistogram)`
+    /// is returned.
+    pub fn sketch_to_agg_histogram(&self, buckets: &[f64]) -> Option<MetricValue> {
+        match self {
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(ddsketch),
+            } => {
+                let (buckets, count, sum) = ddsketch.to_buckets(buckets);
+
+                Some(MetricValue::AggregatedHistogram {
+                    buckets,
+                    count,
+                    sum,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Converts an aggregated histogram to a sketch.
+    ///
+    /// The observations of each bucket are interpolated across the bins of the sketch spanning the bucket, using the
+    /// `AgentDDSketch` sketch variant in the default configuration that matches the Datadog Agent.
+    ///
+    /// If this value is not an aggregated histogram, or one of its buckets holds more than `u32::MAX` observations,
+    /// then `None` is returned.  Otherwise, `Some(MetricValue::Sketch)` is returned.
+    pub fn agg_histogram_to_sketch(&self) -> Option<MetricValue> {
+        match self {
+            MetricValue::AggregatedHistogram { buckets, .. } => {
+                let mut sketch = AgentDDSketch::with_agent_defaults();
+                sketch.insert_interpolate_buckets(buckets.clone()).ok()?;
+
+                Some(MetricValue::Sketch {
+                    sketch: MetricSketch::AgentDDSketch(sketch),
+                })
+            }
+            _ => None,
+        }
+    }
+
     /// Zeroes out all the values contained in this value.
     ///
     /// This keeps all the bucket/value vectors for the histogram and summary metric types intact while zeroing the
@@ -257,11 +257,14 @@ impl From<Metric> for super::Metric {
             })
             .unwrap_or_default();
 
+        let exemplars = metric.exemplars.into_iter().map(Into::into).collect();
+
         Self::new_with_metadata(name, kind, value, metadata)
             .with_namespace(namespace)
             .with_tags(tags)
             .with_timestamp(timestamp)
             .with_interval_ms(std::num::NonZeroU32::new(metric.interval_ms))
+            .with_exemplars(exemplars)
     }
 }
 
@@ -415,6 +418,33 @@ impl From<super::MetricValue> for MetricValue {
     }
 }
 
+impl From<super::metric::Exemplar> for Exemplar {
+    fn from(exemplar: super::metric::Exemplar) -> Self {
+        Self {
+            labels: exemplar.labels.into_iter().collect(),
+            value: exemplar.value,
+            timestamp: exemplar.timestamp.map(|ts| prost_types::Timestamp {
+                seconds: ts.timestamp(),
+                nanos: ts.timestamp_subsec_nanos() as i32,
+            }),
+        }
+    }
+}
+
+impl From<Exemplar> for super::metric::Exemplar {
+    fn from(exemplar: Exemplar) -> Self {
+        Self {
+            labels: exemplar.labels.into_iter().collect(),
+            value: exemplar.value,
+            timestamp: exemplar.timestamp.and_then(|ts| {
+                chrono::Utc
+                    .timestamp_opt(ts.seconds, ts.nanos as u32)
+                    .single()
+            }),
+        }
+    }
+}
+
 impl From<super::Metric> for WithMetadata<Metric> {
     fn from(metric: super::Metric) -> Self {
         let (series, data, metadata) = metric.into_parts();
@@ -464,6 +494,8 @@ impl From<super::Metric> for WithMetadata<Metric> {
             })
             .collect();
 
+        let exemplars = data.exemplars.into_iter().map(Into::into).collect();
+
         #[allow(deprecated)]
         let data = Metric {
             name,
@@ -476,6 +508,7 @@ impl From<super::Metric> for WithMetadata<Metric> {
             value: Some(metric),
             metadata: Some(encode_value(metadata.value().clone())),
             metadata_full: Some(metadata.clone().into()),
+            exemplars,
         };
 
         Self { data, metadata }
@@ -568,6 +568,7 @@ impl Arbitrary for MetricData {
             },
             kind: MetricKind::arbitrary(g),
             value: MetricValue::arbitrary(g),
+            exemplars: Vec::new(),
         }
     }
 
@@ -807,6 +807,37 @@ impl AgentDDSketch {
             Some(sketch) => Ok(metric.with_value(sketch.into())),
         }
     }
+
+    /// Converts the sketch to the buckets of an aggregated histogram.
+    ///
+    /// Histogram bucket bounds are based on `buckets`, where the value is the upper bound of the
+    /// bucket.  The observations of each bin are counted towards the first bucket whose upper bound
+    /// is greater than or equal to the value represented by the bin, so observations above all
+    /// bounds are only part of the returned count, as with distributions.
+    ///
+    /// Returns the buckets, along with the count and sum of the sketch.
+    pub fn to_buckets(&self, buckets: &[f64]) -> (Vec<Bucket>, u64, f64) {
+        let mut counts = vec![0; buckets.len()];
+        for bin in &self.bins {
+            // Bins are keyed by the rounded logarithm of their values, so their lower bound is the
+            // value they represent, which can't be outside of the observed range.
+            let value = self.config.bin_lower_bound(bin.k).clamp(self.min, self.max);
+            if let Some(i) = buckets.iter().position(|upper_limit| *upper_limit >= value) {
+                counts[i] += u64::from(bin.n);
+            }
+        }
+
+        let buckets = buckets
+            .iter()
+            .zip(counts)
+            .map(|(upper_limit, count)| Bucket {
+                upper_limit: *upper_limit,
+                count,
+            })
+            .collect();
+
+        (buckets, u64::from(self.count), self.sum().unwrap_or(0.0))
+    }
 }
 
 impl PartialEq for AgentDDSketch {
@@ -1236,6 +1267,33 @@ mod tests {
         assert_eq!(sketch, AgentDDSketch::with_agent_defaults());
     }
 
+    #[test]
+    fn test_to_buckets() {
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch.insert_many(&[0.5, 1.0, 3.0, 7.0, 20.0]);
+
+        let (buckets, count, sum) = sketch.to_buckets(&[1.0, 5.0, 10.0]);
+        assert_eq!(
+            buckets,
+            vec![
+                Bucket {
+                    upper_limit: 1.0,
+                    count: 2,
+                },
+                Bucket {
+                    upper_limit: 5.0,
+                    count: 1,
+                },
+                Bucket {
+                    upper_limit: 10.0,
+                    count: 1,
+                },
+            ]
+        );
+        assert_eq!(count, 5);
+        assert!((sum - 31.5).abs() < FLOATING_POINT_ACCEPTABLE_ERROR);
+    }
+
     #[test]
     fn test_merge() {
         let mut all_values = AgentDDSketch::with_agent_defaults();
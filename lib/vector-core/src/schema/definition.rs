@@ -4,6 +4,7 @@ use lookup::lookup_v2::TargetPath;
 use lookup::{owned_value_path, OwnedTargetPath, OwnedValuePath, PathPrefix};
 use vrl::value::{kind::Collection, Kind};
 
+use super::meaning;
 use crate::config::{log_schema, LegacyKey, LogNamespace};
 
 /// The definition of a schema.
@@ -423,6 +424,35 @@ impl Definition {
         }
     }
 
+    /// Assigns the commonly used semantic meanings that aren't defined yet to the top-level fields
+    /// of the same name, such as `timestamp` or `host`, where the definition declares such a field
+    /// with the type expected of the meaning.
+    ///
+    /// This only applies to definitions of the `Vector` namespace, as the fields of events using
+    /// the `Legacy` namespace are located with the global log schema instead.
+    #[must_use]
+    pub fn with_default_meanings(mut self) -> Self {
+        if !self.log_namespaces.contains(&LogNamespace::Vector) {
+            return self;
+        }
+
+        for meaning in meaning::DEFAULT_FIELDS {
+            if self.meaning.contains_key(meaning) {
+                continue;
+            }
+            let path = owned_value_path!(meaning);
+            let kind = self.event_kind.at_path(&path);
+            // Fields that may be missing, or only covered by unknown fields, are not mapped.
+            if !kind.contains_undefined() && meaning::kind(meaning).is_superset(&kind).is_ok() {
+                self.meaning.insert(
+                    meaning.to_owned(),
+                    MeaningPointer::Valid(OwnedTargetPath::event(path)),
+                );
+            }
+        }
+        self
+    }
+
     /// Set the kind for all unknown fields.
     #[must_use]
     pub fn unknown_fields(mut self, unknown: impl Into<Kind>) -> Self {
@@ -691,6 +721,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_default_meanings() {
+        let definition = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [LogNamespace::Vector],
+        )
+        .with_event_field(&owned_value_path!("timestamp"), Kind::timestamp(), None)
+        .with_event_field(&owned_value_path!("host"), Kind::integer(), None)
+        .optional_field(&owned_value_path!("service"), Kind::bytes(), None)
+        .with_event_field(&owned_value_path!("message"), Kind::bytes(), None)
+        .with_event_field(&owned_value_path!("msg"), Kind::bytes(), Some("message"))
+        .with_default_meanings();
+
+        assert_eq!(
+            definition.meanings().collect::<Vec<_>>(),
+            vec![
+                (
+                    &"message".to_owned(),
+                    &OwnedTargetPath::event(owned_value_path!("msg"))
+                ),
+                (
+                    &"timestamp".to_owned(),
+                    &OwnedTargetPath::event(owned_value_path!("timestamp"))
+                ),
+            ]
+        );
+
+        // The fields of the `Legacy` namespace are located with the global log schema.
+        let definition = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [LogNamespace::Legacy],
+        )
+        .with_event_field(&owned_value_path!("timestamp"), Kind::timestamp(), None)
+        .with_default_meanings();
+        assert_eq!(definition.meanings().count(), 0);
+    }
+
     #[test]
     fn test_empty_legacy_field() {
         let definition = Definition::default_legacy_namespace().with_vector_metadata(
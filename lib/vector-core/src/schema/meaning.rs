@@ -1,5 +1,7 @@
 //! Constants for commonly used semantic meanings.
 
+use vrl::value::Kind;
+
 /// The service typically represents the application that generated the event.
 pub const SERVICE: &str = "service";
 
@@ -18,3 +20,20 @@ pub const TAGS: &str = "tags";
 pub const SOURCE: &str = "source";
 pub const SEVERITY: &str = "severity";
 pub const TRACE_ID: &str = "trace_id";
+
+/// The commonly used meanings assigned by default to the fields of the same name.
+///
+/// See [`Definition::with_default_meanings`](super::Definition::with_default_meanings).
+pub const DEFAULT_FIELDS: [&str; 6] = [HOST, MESSAGE, SERVICE, SEVERITY, TIMESTAMP, TRACE_ID];
+
+/// Returns the type expected of the fields with the given meaning.
+///
+/// Meanings that aren't commonly used can have any type.
+pub fn kind(meaning: &str) -> Kind {
+    match meaning {
+        TIMESTAMP => Kind::timestamp(),
+        HOST | SERVICE | SOURCE | TRACE_ID => Kind::bytes(),
+        SEVERITY => Kind::bytes().or_integer(),
+        _ => Kind::any(),
+    }
+}
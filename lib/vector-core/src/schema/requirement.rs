@@ -5,7 +5,7 @@ use vrl::value::Kind;
 
 use crate::config::LogNamespace;
 
-use super::Definition;
+use super::{meaning, Definition};
 
 /// The input schema for a given component.
 ///
@@ -68,6 +68,24 @@ impl Requirement {
         self
     }
 
+    /// Makes the given meanings required, in addition to the meanings already required.
+    ///
+    /// Meanings that were optional keep their type, while the type of the others is the one
+    /// expected of the meaning, as returned by [`meaning::kind`].
+    #[must_use]
+    pub fn with_required_meanings<'a>(
+        mut self,
+        meanings: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        for identifier in meanings {
+            match self.meaning.get_mut(identifier) {
+                Some(meaning) => meaning.optional = false,
+                None => self.insert_meaning(identifier, meaning::kind(identifier), false),
+            }
+        }
+        self
+    }
+
     fn insert_meaning(&mut self, identifier: impl Into<String>, kind: Kind, optional: bool) {
         let meaning = SemanticMeaning { kind, optional };
         self.meaning.insert(identifier.into(), meaning);
@@ -258,6 +276,20 @@ mod tests {
         assert_eq!(Ok(()), requirement.validate(&definition, false));
     }
 
+    #[test]
+    fn test_with_required_meanings() {
+        let requirement = Requirement::empty()
+            .optional_meaning("severity", Kind::integer())
+            .with_required_meanings(["severity", "timestamp"]);
+
+        assert_eq!(
+            requirement,
+            Requirement::empty()
+                .required_meaning("severity", Kind::integer())
+                .required_meaning("timestamp", Kind::timestamp())
+        );
+    }
+
     #[test]
     fn test_doesnt_validate_legacy_namespace() {
         let requirement = Requirement::empty().required_meaning("foo", Kind::boolean());
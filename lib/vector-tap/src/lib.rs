@@ -118,6 +118,7 @@ pub struct TapRunner<'a> {
     url: &'a Url,
     input_patterns: Vec<String>,
     output_patterns: Vec<String>,
+    filter: Option<String>,
     output_channel: &'a OutputChannel,
     format: TapEncodingFormat,
 }
@@ -127,6 +128,7 @@ impl<'a> TapRunner<'a> {
         url: &'a Url,
         input_patterns: Vec<String>,
         output_patterns: Vec<String>,
+        filter: Option<String>,
         output_channel: &'a OutputChannel,
         format: TapEncodingFormat,
     ) -> Self {
@@ -134,6 +136,7 @@ impl<'a> TapRunner<'a> {
             url,
             input_patterns,
             output_patterns,
+            filter,
             output_channel,
             format,
         }
@@ -154,6 +157,7 @@ impl<'a> TapRunner<'a> {
             let stream = subscription_client.output_events_by_component_id_patterns_subscription(
                 self.output_patterns.clone(),
                 self.input_patterns.clone(),
+                self.filter.clone(),
                 self.format,
                 limit,
                 interval,
@@ -338,6 +342,7 @@ mod tests {
             &url,
             vec![],
             vec![],
+            None,
             &output_channel,
             TapEncodingFormat::Json,
         );
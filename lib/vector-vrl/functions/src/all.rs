@@ -0,0 +1,117 @@
+use vrl::prelude::*;
+
+/// Whether the closure returns `true` for all elements, stopping at the first one it doesn't.
+fn all<T>(value: Value, ctx: &mut Context, runner: closure::Runner<T>) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                if !runner.run_key_value(ctx, &key, &value)?.try_boolean()? {
+                    return Ok(false.into());
+                }
+            }
+        }
+        Value::Array(array) => {
+            for (index, value) in array.into_iter().enumerate() {
+                if !runner.run_index_value(ctx, index, &value)?.try_boolean()? {
+                    return Ok(false.into());
+                }
+            }
+        }
+        _ => return Err("function requires collection types as input".into()),
+    }
+    Ok(true.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct All;
+
+impl Function for All {
+    fn identifier(&self) -> &'static str {
+        "all"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Check whether all elements of an array match",
+                source: r#"all([1, 2, 3]) -> |_index, value| { value > 2 }"#,
+                result: Ok("false"),
+            },
+            Example {
+                title: "Check whether all keys of an object match",
+                source: r#"all({ "a": "foo", "b": "bar" }) -> |key, _value| { length(key) == 1 }"#,
+                result: Ok("true"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure()?;
+        Ok(AllFn { value, closure }.as_expr())
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::object(Collection::any()).or_array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::TargetInnerKey,
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::boolean()),
+                example: Example {
+                    title: "all even",
+                    source: r#"all([2, 4, 6]) -> |_index, value| { mod(value, 2) == 0 }"#,
+                    result: Ok("true"),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AllFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl FunctionExpression for AllFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let FunctionClosure {
+            variables, block, ..
+        } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        all(value, ctx, runner)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::boolean().infallible()
+    }
+}
@@ -0,0 +1,117 @@
+use vrl::prelude::*;
+
+/// Whether the closure returns `true` for any element, stopping at the first one it does.
+fn any<T>(value: Value, ctx: &mut Context, runner: closure::Runner<T>) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                if runner.run_key_value(ctx, &key, &value)?.try_boolean()? {
+                    return Ok(true.into());
+                }
+            }
+        }
+        Value::Array(array) => {
+            for (index, value) in array.into_iter().enumerate() {
+                if runner.run_index_value(ctx, index, &value)?.try_boolean()? {
+                    return Ok(true.into());
+                }
+            }
+        }
+        _ => return Err("function requires collection types as input".into()),
+    }
+    Ok(false.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Any;
+
+impl Function for Any {
+    fn identifier(&self) -> &'static str {
+        "any"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Check whether any element of an array matches",
+                source: r#"any([1, 2, 3]) -> |_index, value| { value > 2 }"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "Check whether any value of an object matches",
+                source: r#"any({ "a": "foo", "b": "bar" }) -> |_key, value| { value == "baz" }"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure()?;
+        Ok(AnyFn { value, closure }.as_expr())
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::object(Collection::any()).or_array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::TargetInnerKey,
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::boolean()),
+                example: Example {
+                    title: "any even",
+                    source: r#"any([1, 2, 3]) -> |_index, value| { mod(value, 2) == 0 }"#,
+                    result: Ok("true"),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AnyFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl FunctionExpression for AnyFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let FunctionClosure {
+            variables, block, ..
+        } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        any(value, ctx, runner)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::boolean().infallible()
+    }
+}
@@ -0,0 +1,448 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use hickory_resolver::config::{ResolverConfig, ResolverOpts};
+use hickory_resolver::error::{ResolveError, ResolveErrorKind};
+use hickory_resolver::proto::rr::RecordType;
+use hickory_resolver::TokioAsyncResolver;
+use vector_common::runtime::block_on;
+use vrl::diagnostic::{Label, Span};
+use vrl::prelude::*;
+use vrl::value::kind::Collection;
+
+const DEFAULT_TIMEOUT_SECS: i64 = 2;
+const DEFAULT_ATTEMPTS: i64 = 2;
+
+/// The maximum number of answers cached by each call to the functions.
+const MAX_CACHED_ANSWERS: usize = 1000;
+
+/// The outcome of a query, an array of records for `cached_dns_lookup` and a hostname for
+/// `cached_reverse_dns`.
+type Answer = Result<Value, String>;
+
+/// The resolver configuration of a call to the functions, read from the system configuration
+/// along with the literal `timeout_secs` and `attempts` arguments.
+#[derive(Debug)]
+struct LookupOptions {
+    config: ResolverConfig,
+    opts: ResolverOpts,
+}
+
+impl LookupOptions {
+    /// Builds a resolver for a single query.
+    ///
+    /// The connections of a resolver are bound to the runtime which opened them, so a resolver
+    /// isn't reused across the temporary runtimes of `block_on`. Its own cache is then useless,
+    /// the answers being cached by the function instead.
+    fn resolver(&self) -> TokioAsyncResolver {
+        TokioAsyncResolver::tokio(self.config.clone(), self.opts.clone())
+    }
+}
+
+fn lookup_options(
+    timeout_secs: Option<Value>,
+    attempts: Option<Value>,
+) -> Result<LookupOptions, String> {
+    let timeout_secs = positive(timeout_secs, "timeout_secs", DEFAULT_TIMEOUT_SECS)?;
+    let attempts = positive(attempts, "attempts", DEFAULT_ATTEMPTS)?;
+
+    let (config, mut opts) = hickory_resolver::system_conf::read_system_conf()
+        .map_err(|error| format!("failed to read the system DNS configuration: {error}"))?;
+    opts.timeout = Duration::from_secs(timeout_secs);
+    opts.attempts = usize::try_from(attempts).unwrap_or(usize::MAX);
+    Ok(LookupOptions { config, opts })
+}
+
+fn positive(value: Option<Value>, name: &str, default: i64) -> Result<u64, String> {
+    let value = match value {
+        Some(value) => value
+            .try_integer()
+            .map_err(|error| format!("invalid `{name}`: {error}"))?,
+        None => default,
+    };
+    u64::try_from(value)
+        .ok()
+        .filter(|value| *value > 0)
+        .ok_or_else(|| format!("`{name}` must be positive"))
+}
+
+/// The answers cached by a call to the functions, each until the TTL of its records expires.
+#[derive(Debug, Default)]
+struct AnswerCache {
+    answers: HashMap<(String, RecordType), (Instant, Answer)>,
+}
+
+impl AnswerCache {
+    fn cached(&mut self, key: &(String, RecordType)) -> Option<Answer> {
+        let now = Instant::now();
+        match self.answers.get(key) {
+            Some((expires_at, answer)) if *expires_at > now => Some(answer.clone()),
+            Some(_) => {
+                self.answers.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn cache(&mut self, key: (String, RecordType), answer: Answer, expires_at: Instant) {
+        let now = Instant::now();
+        if expires_at <= now {
+            return;
+        }
+        if self.answers.len() >= MAX_CACHED_ANSWERS {
+            self.answers.retain(|_, (expires_at, _)| *expires_at > now);
+        }
+        // Answers aren't cached while the cache is full of live entries.
+        if self.answers.len() < MAX_CACHED_ANSWERS {
+            self.answers.insert(key, (expires_at, answer));
+        }
+    }
+}
+
+/// Maps a failed query to its answer, along with the instant it can be cached until.
+///
+/// Only the absence of records is cached, for the negative TTL given by the authority of the
+/// name, other failures being transient.
+fn failure(name: &str, error: &ResolveError) -> (Answer, Option<Instant>) {
+    match error.kind() {
+        ResolveErrorKind::NoRecordsFound { negative_ttl, .. } => (
+            Err(format!("no records found for {name:?}")),
+            negative_ttl.map(|ttl| Instant::now() + Duration::from_secs(ttl.into())),
+        ),
+        ResolveErrorKind::Timeout => (Err(format!("DNS lookup of {name:?} timed out")), None),
+        _ => (Err(format!("DNS lookup of {name:?} failed: {error}")), None),
+    }
+}
+
+async fn lookup(
+    options: &LookupOptions,
+    name: &str,
+    record_type: RecordType,
+) -> (Answer, Option<Instant>) {
+    match options.resolver().lookup(name, record_type).await {
+        Ok(lookup) => {
+            // The records of the aliases followed to answer the query are left out.
+            let records = lookup
+                .record_iter()
+                .filter(|record| record.record_type() == record_type)
+                .filter_map(|record| record.data())
+                .map(|data| Value::from(data.to_string()))
+                .collect::<Vec<_>>();
+            (Ok(Value::Array(records)), Some(lookup.valid_until()))
+        }
+        Err(error) => failure(name, &error),
+    }
+}
+
+async fn reverse_lookup(options: &LookupOptions, address: IpAddr) -> (Answer, Option<Instant>) {
+    let name = address.to_string();
+    match options.resolver().reverse_lookup(address).await {
+        Ok(lookup) => match lookup.iter().next() {
+            Some(hostname) => (
+                Ok(Value::from(
+                    hostname.to_string().trim_end_matches('.').to_owned(),
+                )),
+                Some(lookup.as_lookup().valid_until()),
+            ),
+            None => (Err(format!("no records found for {name:?}")), None),
+        },
+        Err(error) => failure(&name, &error),
+    }
+}
+
+/// Answers the query from the cache, or by sending it and caching its answer.
+fn resolve<F>(cache: &Mutex<AnswerCache>, key: (String, RecordType), query: F) -> Resolved
+where
+    F: std::future::Future<Output = (Answer, Option<Instant>)> + Send,
+{
+    if let Some(answer) = cache.lock().expect("poisoned lock").cached(&key) {
+        return Ok(answer?);
+    }
+    let (answer, expires_at) = block_on(query);
+    if let Some(expires_at) = expires_at {
+        cache
+            .lock()
+            .expect("poisoned lock")
+            .cache(key, answer.clone(), expires_at);
+    }
+    Ok(answer?)
+}
+
+fn compile_error(message: String, span: Span) -> Box<dyn DiagnosticMessage> {
+    Box::new(ExpressionError::Error {
+        message,
+        labels: vec![Label::primary("invalid DNS lookup", span)],
+        notes: vec![],
+    })
+}
+
+const TIMEOUT_SECS: Parameter = Parameter {
+    keyword: "timeout_secs",
+    kind: kind::INTEGER,
+    required: false,
+};
+
+const ATTEMPTS: Parameter = Parameter {
+    keyword: "attempts",
+    kind: kind::INTEGER,
+    required: false,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct CachedDnsLookup;
+
+impl Function for CachedDnsLookup {
+    fn identifier(&self) -> &'static str {
+        "cached_dns_lookup"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "qtype",
+                kind: kind::BYTES,
+                required: false,
+            },
+            TIMEOUT_SECS,
+            ATTEMPTS,
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Resolve the addresses of a host",
+            source: r#"cached_dns_lookup!("localhost.example.com")"#,
+            result: Ok(r#"["127.0.0.1"]"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let span = ctx.span();
+        let value = arguments.required("value");
+        let qtype = arguments.optional_literal("qtype", state)?;
+        let timeout_secs = arguments.optional_literal("timeout_secs", state)?;
+        let attempts = arguments.optional_literal("attempts", state)?;
+
+        let record_type = match qtype {
+            Some(qtype) => {
+                let qtype = qtype
+                    .try_bytes_utf8_lossy()
+                    .map_err(|error| compile_error(format!("invalid `qtype`: {error}"), span))?
+                    .to_ascii_uppercase();
+                RecordType::from_str(&qtype)
+                    .map_err(|_| compile_error(format!("unsupported `qtype` {qtype:?}"), span))?
+            }
+            None => RecordType::A,
+        };
+        let options = lookup_options(timeout_secs, attempts)
+            .map_err(|message| compile_error(message, span))?;
+
+        Ok(CachedDnsLookupFn {
+            value,
+            record_type,
+            options: Arc::new(options),
+            cache: Arc::default(),
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedDnsLookupFn {
+    value: Box<dyn Expression>,
+    record_type: RecordType,
+    options: Arc<LookupOptions>,
+    cache: Arc<Mutex<AnswerCache>>,
+}
+
+impl FunctionExpression for CachedDnsLookupFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let name = self
+            .value
+            .resolve(ctx)?
+            .try_bytes_utf8_lossy()?
+            .into_owned();
+        let query = lookup(&self.options, &name, self.record_type);
+        resolve(&self.cache, (name.clone(), self.record_type), query)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CachedReverseDns;
+
+impl Function for CachedReverseDns {
+    fn identifier(&self) -> &'static str {
+        "cached_reverse_dns"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            TIMEOUT_SECS,
+            ATTEMPTS,
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Resolve the hostname of an address",
+            source: r#"cached_reverse_dns!("127.0.0.1")"#,
+            result: Ok("localhost"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let span = ctx.span();
+        let value = arguments.required("value");
+        let timeout_secs = arguments.optional_literal("timeout_secs", state)?;
+        let attempts = arguments.optional_literal("attempts", state)?;
+
+        let options = lookup_options(timeout_secs, attempts)
+            .map_err(|message| compile_error(message, span))?;
+
+        Ok(CachedReverseDnsFn {
+            value,
+            options: Arc::new(options),
+            cache: Arc::default(),
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedReverseDnsFn {
+    value: Box<dyn Expression>,
+    options: Arc<LookupOptions>,
+    cache: Arc<Mutex<AnswerCache>>,
+}
+
+impl FunctionExpression for CachedReverseDnsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self
+            .value
+            .resolve(ctx)?
+            .try_bytes_utf8_lossy()?
+            .into_owned();
+        let address = IpAddr::from_str(&value)
+            .map_err(|error| format!("unable to parse IP address {value:?}: {error}"))?;
+        let query = reverse_lookup(&self.options, address);
+        resolve(&self.cache, (address.to_string(), RecordType::PTR), query)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str) -> (String, RecordType) {
+        (name.to_owned(), RecordType::A)
+    }
+
+    #[test]
+    fn cached_answers_expire() {
+        let mut cache = AnswerCache::default();
+
+        cache.cache(
+            key("live.example.com"),
+            Ok(Value::from("live")),
+            Instant::now() + Duration::from_secs(60),
+        );
+        assert_eq!(
+            cache.cached(&key("live.example.com")),
+            Some(Ok(Value::from("live")))
+        );
+
+        cache.answers.insert(
+            key("expired.example.com"),
+            (Instant::now(), Ok(Value::from("expired"))),
+        );
+        assert_eq!(cache.cached(&key("expired.example.com")), None);
+        assert_eq!(cache.answers.len(), 1);
+    }
+
+    #[test]
+    fn answers_with_a_zero_ttl_arent_cached() {
+        let mut cache = AnswerCache::default();
+
+        cache.cache(key("example.com"), Ok(Value::from("zero")), Instant::now());
+        assert!(cache.answers.is_empty());
+    }
+
+    #[test]
+    fn expired_answers_are_evicted_when_full() {
+        let mut cache = AnswerCache::default();
+        let expired = Instant::now();
+        for index in 0..MAX_CACHED_ANSWERS {
+            cache
+                .answers
+                .insert(key(&index.to_string()), (expired, Ok(Value::Null)));
+        }
+
+        cache.cache(
+            key("example.com"),
+            Ok(Value::Null),
+            Instant::now() + Duration::from_secs(60),
+        );
+        assert_eq!(cache.answers.len(), 1);
+        assert!(cache.cached(&key("example.com")).is_some());
+    }
+
+    #[test]
+    fn cached_failures_are_returned_as_errors() {
+        let cache = Mutex::new(AnswerCache::default());
+        let expires_at = Instant::now() + Duration::from_secs(60);
+        let failure = async move {
+            (
+                Err("no records found for \"example.com\"".to_owned()),
+                Some(expires_at),
+            )
+        };
+        assert!(resolve(&cache, key("example.com"), failure).is_err());
+
+        // The query isn't sent again while the failure is cached.
+        let success = async { (Ok(Value::from("sent again")), None) };
+        assert!(resolve(&cache, key("example.com"), success).is_err());
+    }
+
+    #[test]
+    fn options_must_be_positive() {
+        assert!(positive(None, "timeout_secs", DEFAULT_TIMEOUT_SECS).is_ok());
+        assert_eq!(
+            positive(Some(3.into()), "attempts", DEFAULT_ATTEMPTS),
+            Ok(3)
+        );
+        assert!(positive(Some(0.into()), "timeout_secs", DEFAULT_TIMEOUT_SECS).is_err());
+        assert!(positive(Some((-1).into()), "attempts", DEFAULT_ATTEMPTS).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use ring::aead::Aad;
+use vrl::prelude::*;
+
+use crate::encrypt_aead::aead_key;
+
+fn decrypt_aead(
+    ciphertext: Value,
+    algorithm: Value,
+    key: Value,
+    iv: Value,
+    aad: Option<Value>,
+) -> Resolved {
+    let algorithm = algorithm.try_bytes_utf8_lossy()?;
+    let (key, nonce) = aead_key(&algorithm, &key.try_bytes()?, &iv.try_bytes()?)?;
+    let aad = aad.map(Value::try_bytes).transpose()?.unwrap_or_default();
+
+    let mut in_out = ciphertext.try_bytes()?.to_vec();
+    let plaintext = key
+        .open_in_place(nonce, Aad::from(&aad[..]), &mut in_out)
+        .map_err(|_| "unable to decrypt the ciphertext: authentication failed")?;
+    Ok(Value::Bytes(Bytes::copy_from_slice(plaintext)))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecryptAead;
+
+impl Function for DecryptAead {
+    fn identifier(&self) -> &'static str {
+        "decrypt_aead"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "ciphertext",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "iv",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "aad",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Decrypt a value encrypted with AES-256-GCM",
+            source: r#"decrypt_aead!(decode_base64!("ox+pG2aU8rwuD1/APFEo9U53vp6F0nrmAljEzD64N14Hlqk3"), "AES-256-GCM", key: "01234567890123456789012345678912", iv: "123456789012", aad: "user-1234")"#,
+            result: Ok("super secret message"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let ciphertext = arguments.required("ciphertext");
+        let algorithm = arguments.required("algorithm");
+        let key = arguments.required("key");
+        let iv = arguments.required("iv");
+        let aad = arguments.optional("aad");
+        Ok(DecryptAeadFn {
+            ciphertext,
+            algorithm,
+            key,
+            iv,
+            aad,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DecryptAeadFn {
+    ciphertext: Box<dyn Expression>,
+    algorithm: Box<dyn Expression>,
+    key: Box<dyn Expression>,
+    iv: Box<dyn Expression>,
+    aad: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for DecryptAeadFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let ciphertext = self.ciphertext.resolve(ctx)?;
+        let algorithm = self.algorithm.resolve(ctx)?;
+        let key = self.key.resolve(ctx)?;
+        let iv = self.iv.resolve(ctx)?;
+        let aad = self.aad.as_ref().map(|aad| aad.resolve(ctx)).transpose()?;
+        decrypt_aead(ciphertext, algorithm, key, iv, aad)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
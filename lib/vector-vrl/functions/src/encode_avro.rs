@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use apache_avro::types::Value as AvroValue;
+use apache_avro::Schema;
+use vrl::prelude::*;
+
+use crate::parse_avro::{compile_schema, AvroSchema};
+
+fn encode_avro(value: Value, schema: &AvroSchema) -> Resolved {
+    let value = to_avro(value, &schema.schema, schema)?;
+    let datum = apache_avro::to_avro_datum(&schema.schema, value)
+        .map_err(|error| format!("unable to encode Avro value: {error}"))?;
+
+    let mut bytes = Vec::with_capacity(schema.header.len() + datum.len());
+    bytes.extend_from_slice(&schema.header);
+    bytes.extend_from_slice(&datum);
+    Ok(Value::Bytes(bytes.into()))
+}
+
+/// Converts `value` to an Avro value of the given `schema`. Missing record fields are encoded as
+/// nulls, and union values as the first of the variants they can be converted to.
+fn to_avro(value: Value, schema: &Schema, avro: &AvroSchema) -> Result<AvroValue, String> {
+    match (schema, value) {
+        (Schema::Ref { name }, value) => {
+            let schema = avro
+                .names
+                .get(name)
+                .ok_or_else(|| format!("unknown Avro type {name:?}"))?;
+            to_avro(value, schema, avro)
+        }
+        (Schema::Union(union), value) => union
+            .variants()
+            .iter()
+            .enumerate()
+            .find_map(|(index, variant)| {
+                to_avro(value.clone(), variant, avro)
+                    .ok()
+                    .map(|value| AvroValue::Union(index as u32, Box::new(value)))
+            })
+            .ok_or_else(|| format!("value {value} matches none of the union variants")),
+        (Schema::Record(record), Value::Object(mut fields)) => record
+            .fields
+            .iter()
+            .map(|field| {
+                let value = fields.remove(field.name.as_str()).unwrap_or(Value::Null);
+                let value = to_avro(value, &field.schema, avro)
+                    .map_err(|error| format!("field {:?}: {error}", field.name))?;
+                Ok((field.name.clone(), value))
+            })
+            .collect::<Result<Vec<_>, String>>()
+            .map(AvroValue::Record),
+        (Schema::Array(items), Value::Array(values)) => values
+            .into_iter()
+            .map(|value| to_avro(value, items, avro))
+            .collect::<Result<Vec<_>, _>>()
+            .map(AvroValue::Array),
+        (Schema::Map(values), Value::Object(fields)) => fields
+            .into_iter()
+            .map(|(key, value)| Ok((key.into(), to_avro(value, values, avro)?)))
+            .collect::<Result<HashMap<_, _>, String>>()
+            .map(AvroValue::Map),
+        (Schema::TimestampMillis, Value::Timestamp(timestamp)) => {
+            Ok(AvroValue::TimestampMillis(timestamp.timestamp_millis()))
+        }
+        (Schema::TimestampMicros, Value::Timestamp(timestamp)) => {
+            Ok(AvroValue::TimestampMicros(timestamp.timestamp_micros()))
+        }
+        (schema, value) => {
+            let value = match value {
+                Value::Null => AvroValue::Null,
+                Value::Boolean(boolean) => AvroValue::Boolean(boolean),
+                Value::Integer(integer) => AvroValue::Long(integer),
+                Value::Float(float) => AvroValue::Double(float.into_inner()),
+                Value::Bytes(bytes) => match String::from_utf8(bytes.to_vec()) {
+                    Ok(string) => AvroValue::String(string),
+                    Err(error) => AvroValue::Bytes(error.into_bytes()),
+                },
+                Value::Regex(regex) => AvroValue::String(regex.as_str().to_owned()),
+                value => {
+                    return Err(format!(
+                        "{} values can't be encoded as {schema:?}",
+                        value.kind_str()
+                    ))
+                }
+            };
+            value.resolve(schema).map_err(|error| error.to_string())
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeAvro;
+
+impl Function for EncodeAvro {
+    fn identifier(&self) -> &'static str {
+        "encode_avro"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "schema",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "schema_file",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Encode a record as Avro single-object encoding",
+            source: r#"encode_base64(encode_avro!({ "message": "hello" }, schema: s'{"type": "record", "name": "Message", "fields": [{"name": "message", "type": "string"}]}'))"#,
+            result: Ok("wwEXzGcl6JXfawpoZWxsbw=="),
+        }]
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let schema = compile_schema(&arguments, state, ctx)?;
+        Ok(EncodeAvroFn { value, schema }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EncodeAvroFn {
+    value: Box<dyn Expression>,
+    schema: Arc<AvroSchema>,
+}
+
+impl FunctionExpression for EncodeAvroFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        encode_avro(value, &self.schema)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
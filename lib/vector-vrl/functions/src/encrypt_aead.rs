@@ -0,0 +1,146 @@
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_128_GCM, AES_256_GCM};
+use vrl::prelude::*;
+
+/// Builds the key of an AEAD `algorithm`, checking the lengths of the key and nonce.
+pub(crate) fn aead_key(
+    algorithm: &str,
+    key: &[u8],
+    iv: &[u8],
+) -> Result<(LessSafeKey, Nonce), String> {
+    let algorithm = match algorithm.to_uppercase().as_str() {
+        "AES-128-GCM" => &AES_128_GCM,
+        "AES-256-GCM" => &AES_256_GCM,
+        other => return Err(format!("unsupported algorithm: {other}")),
+    };
+    let key = UnboundKey::new(algorithm, key).map_err(|_| {
+        format!(
+            "invalid key length: expected {} bytes, got {}",
+            algorithm.key_len(),
+            key.len()
+        )
+    })?;
+    let nonce = Nonce::try_assume_unique_for_key(iv).map_err(|_| {
+        format!(
+            "invalid iv length: expected {} bytes, got {}",
+            algorithm.nonce_len(),
+            iv.len()
+        )
+    })?;
+    Ok((LessSafeKey::new(key), nonce))
+}
+
+fn encrypt_aead(
+    plaintext: Value,
+    algorithm: Value,
+    key: Value,
+    iv: Value,
+    aad: Option<Value>,
+) -> Resolved {
+    let algorithm = algorithm.try_bytes_utf8_lossy()?;
+    let (key, nonce) = aead_key(&algorithm, &key.try_bytes()?, &iv.try_bytes()?)?;
+    let aad = aad.map(Value::try_bytes).transpose()?.unwrap_or_default();
+
+    let mut in_out = plaintext.try_bytes()?.to_vec();
+    key.seal_in_place_append_tag(nonce, Aad::from(&aad[..]), &mut in_out)
+        .map_err(|_| "unable to encrypt the plaintext")?;
+    Ok(Value::Bytes(in_out.into()))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncryptAead;
+
+impl Function for EncryptAead {
+    fn identifier(&self) -> &'static str {
+        "encrypt_aead"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "plaintext",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "iv",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "aad",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Encrypt a value with AES-256-GCM",
+                source: r#"encode_base64(encrypt_aead!("super secret message", "AES-256-GCM", key: "01234567890123456789012345678912", iv: "123456789012"))"#,
+                result: Ok("ox+pG2aU8rwuD1/APFEo9U53vp78azhsm7nRUYjwq0ZT11TG"),
+            },
+            Example {
+                title: "Encrypt a value bound to additional authenticated data",
+                source: r#"encode_base64(encrypt_aead!("super secret message", "AES-256-GCM", key: "01234567890123456789012345678912", iv: "123456789012", aad: "user-1234"))"#,
+                result: Ok("ox+pG2aU8rwuD1/APFEo9U53vp6F0nrmAljEzD64N14Hlqk3"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let plaintext = arguments.required("plaintext");
+        let algorithm = arguments.required("algorithm");
+        let key = arguments.required("key");
+        let iv = arguments.required("iv");
+        let aad = arguments.optional("aad");
+        Ok(EncryptAeadFn {
+            plaintext,
+            algorithm,
+            key,
+            iv,
+            aad,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EncryptAeadFn {
+    plaintext: Box<dyn Expression>,
+    algorithm: Box<dyn Expression>,
+    key: Box<dyn Expression>,
+    iv: Box<dyn Expression>,
+    aad: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for EncryptAeadFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let plaintext = self.plaintext.resolve(ctx)?;
+        let algorithm = self.algorithm.resolve(ctx)?;
+        let key = self.key.resolve(ctx)?;
+        let iv = self.iv.resolve(ctx)?;
+        let aad = self.aad.as_ref().map(|aad| aad.resolve(ctx)).transpose()?;
+        encrypt_aead(plaintext, algorithm, key, iv, aad)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
@@ -0,0 +1,68 @@
+use vrl::prelude::*;
+
+use crate::set_state::{state_store, StateStore};
+
+fn get_state(store: &StateStore, key: Value) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?;
+    Ok(store.get(&key))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetState;
+
+impl Function for GetState {
+    fn identifier(&self) -> &'static str {
+        "get_state"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "keyspace",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Get a missing key",
+            source: r#"get_state("last_login")"#,
+            result: Ok("null"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let store = state_store(&arguments, state, ctx)?;
+        let key = arguments.required("key");
+        Ok(GetStateFn { store, key }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetStateFn {
+    store: StateStore,
+    key: Box<dyn Expression>,
+}
+
+impl FunctionExpression for GetStateFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        get_state(&self.store, key)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::any().infallible()
+    }
+}
@@ -0,0 +1,622 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use reqwest::{Method, Url};
+use vector_common::runtime::block_on;
+use vrl::diagnostic::Label;
+use vrl::prelude::*;
+use vrl::value::kind::Collection;
+use vrl::value::ObjectMap;
+
+const DEFAULT_TIMEOUT_SECS: i64 = 5;
+const DEFAULT_CACHE_TTL_SECS: i64 = 60;
+const DEFAULT_FAILURE_THRESHOLD: i64 = 5;
+const DEFAULT_COOLDOWN_SECS: i64 = 30;
+
+/// The maximum number of responses cached by each call to the function.
+const MAX_CACHED_RESPONSES: usize = 1000;
+
+/// The options of a call to the function, all given as literals.
+#[derive(Debug)]
+struct RequestOptions {
+    method: Method,
+    allowed_hosts: Vec<String>,
+    timeout: Duration,
+    cache_ttl: Duration,
+    failure_threshold: u32,
+    cooldown: Duration,
+}
+
+impl RequestOptions {
+    /// Checks that the host of `url` is allowed, the leading `*.` of an allowed host matching any
+    /// of its subdomains.
+    fn check_url(&self, url: &str) -> Result<Url, String> {
+        let url = Url::parse(url).map_err(|error| format!("invalid URL {url:?}: {error}"))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!("unsupported URL scheme {:?}", url.scheme()));
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| format!("URL {url} has no host"))?
+            .to_ascii_lowercase();
+        let allowed = self
+            .allowed_hosts
+            .iter()
+            .any(|allowed| match allowed.strip_prefix("*.") {
+                Some(domain) => host
+                    .strip_suffix(domain)
+                    .is_some_and(|subdomain| subdomain.ends_with('.')),
+                None => host == *allowed,
+            });
+        if allowed {
+            Ok(url)
+        } else {
+            Err(format!("host {host:?} is not in `allowed_hosts`"))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    url: String,
+    headers: Vec<(String, String)>,
+}
+
+/// The responses cached and the state of the circuit breaker, shared by all resolutions of a call
+/// to the function.
+#[derive(Debug, Default)]
+struct RequestState {
+    cache: HashMap<CacheKey, (Instant, Value)>,
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl RequestState {
+    fn cached(&mut self, key: &CacheKey) -> Option<Value> {
+        let now = Instant::now();
+        match self.cache.get(key) {
+            Some((expires_at, response)) if *expires_at > now => Some(response.clone()),
+            Some(_) => {
+                self.cache.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn cache(&mut self, key: CacheKey, response: Value, ttl: Duration) {
+        let now = Instant::now();
+        if self.cache.len() >= MAX_CACHED_RESPONSES {
+            self.cache.retain(|_, (expires_at, _)| *expires_at > now);
+        }
+        // Responses aren't cached while the cache is full of live entries.
+        if self.cache.len() < MAX_CACHED_RESPONSES {
+            self.cache.insert(key, (now + ttl, response));
+        }
+    }
+
+    fn check_circuit(&mut self) -> Result<(), String> {
+        match self.open_until {
+            Some(open_until) if open_until > Instant::now() => Err(format!(
+                "circuit breaker open after {} consecutive failed requests",
+                self.consecutive_failures
+            )),
+            // Once the cooldown elapses, requests are let through until one fails again.
+            _ => Ok(()),
+        }
+    }
+
+    fn record(&mut self, succeeded: bool, options: &RequestOptions) {
+        if succeeded {
+            self.consecutive_failures = 0;
+            self.open_until = None;
+        } else {
+            self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+            if self.consecutive_failures >= options.failure_threshold {
+                self.open_until = Some(Instant::now() + options.cooldown);
+            }
+        }
+    }
+}
+
+fn http_request(
+    client: &reqwest::Client,
+    url: Value,
+    headers: Option<Value>,
+    body: Option<Value>,
+    options: &RequestOptions,
+    state: &Mutex<RequestState>,
+) -> Resolved {
+    let url = options.check_url(&url.try_bytes_utf8_lossy()?)?;
+    let mut headers = headers
+        .map(Value::try_object)
+        .transpose()?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, value)| Ok((name.into(), value.try_bytes_utf8_lossy()?.into_owned())))
+        .collect::<Result<Vec<(String, String)>, ExpressionError>>()?;
+    headers.sort();
+    let body = body.map(Value::try_bytes).transpose()?;
+
+    // Only the responses to `GET` requests are cached, other methods not being safe to repeat.
+    let cache_key =
+        (options.method == Method::GET && !options.cache_ttl.is_zero()).then(|| CacheKey {
+            url: url.to_string(),
+            headers: headers.clone(),
+        });
+    {
+        let mut state = state.lock().expect("poisoned lock");
+        if let Some(response) = cache_key.as_ref().and_then(|key| state.cached(key)) {
+            return Ok(response);
+        }
+        state.check_circuit()?;
+    }
+
+    let result = block_on(send(client, url, headers, body, options))
+        .map_err(|error| format!("HTTP request failed: {error}"));
+    let mut state = state.lock().expect("poisoned lock");
+    // Server errors count as failures for the circuit breaker, while still being returned.
+    let succeeded = matches!(&result, Ok((status, _)) if *status < 500);
+    state.record(succeeded, options);
+    let (_, response) = result?;
+    if let Some(key) = cache_key.filter(|_| succeeded) {
+        state.cache(key, response.clone(), options.cache_ttl);
+    }
+    Ok(response)
+}
+
+/// Returns the client shared by all calls to the function, building it on first use.
+fn shared_client() -> Result<reqwest::Client, String> {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    if let Some(client) = CLIENT.get() {
+        return Ok(client.clone());
+    }
+    // Idle connections are bound to the runtime which opened them, so none are kept open, which
+    // lets the client be used from the temporary runtimes of `block_on`. Redirects aren't followed,
+    // as their target could be any host rather than one of the `allowed_hosts`, so they're
+    // returned as is.
+    let client = reqwest::Client::builder()
+        .pool_max_idle_per_host(0)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(|error| format!("failed to build HTTP client: {error}"))?;
+    Ok(CLIENT.get_or_init(|| client).clone())
+}
+
+/// Sends the request, returning the status code of the response along with the response as an
+/// object.
+async fn send(
+    client: &reqwest::Client,
+    url: Url,
+    headers: Vec<(String, String)>,
+    body: Option<Bytes>,
+    options: &RequestOptions,
+) -> reqwest::Result<(u16, Value)> {
+    let mut request = client
+        .request(options.method.clone(), url)
+        .timeout(options.timeout);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    if let Some(body) = body {
+        request = request.body(body);
+    }
+
+    let response = request.send().await?;
+    let status = response.status().as_u16();
+    let headers = response
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.as_str().into(),
+                Value::from(String::from_utf8_lossy(value.as_bytes()).into_owned()),
+            )
+        })
+        .collect::<ObjectMap>();
+    let body = response.bytes().await?;
+    let response = ObjectMap::from([
+        ("status".into(), Value::from(i64::from(status))),
+        ("headers".into(), Value::Object(headers)),
+        ("body".into(), Value::Bytes(body)),
+    ]);
+    Ok((status, Value::Object(response)))
+}
+
+/// Builds the options of the call from its literal arguments.
+fn request_options(
+    method: Option<Value>,
+    allowed_hosts: Value,
+    timeout_secs: Option<Value>,
+    cache_ttl_secs: Option<Value>,
+    failure_threshold: Option<Value>,
+    cooldown_secs: Option<Value>,
+) -> Result<RequestOptions, String> {
+    let method = match method {
+        Some(method) => {
+            let method = method
+                .try_bytes_utf8_lossy()
+                .map_err(|error| format!("invalid method: {error}"))?
+                .to_ascii_uppercase();
+            match method.as_str() {
+                "GET" | "HEAD" | "POST" | "PUT" | "PATCH" | "DELETE" => {
+                    Method::from_bytes(method.as_bytes()).expect("valid method")
+                }
+                _ => return Err(format!("unsupported method {method:?}")),
+            }
+        }
+        None => Method::GET,
+    };
+
+    let allowed_hosts = allowed_hosts
+        .try_array()
+        .map_err(|error| format!("invalid allowed hosts: {error}"))?
+        .into_iter()
+        .map(|host| {
+            host.try_bytes_utf8_lossy()
+                .map(|host| host.to_ascii_lowercase())
+                .map_err(|error| format!("invalid allowed host: {error}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if allowed_hosts.is_empty() {
+        return Err("at least one allowed host must be provided".to_owned());
+    }
+
+    let timeout_secs = non_negative(timeout_secs, "timeout_secs", DEFAULT_TIMEOUT_SECS)?;
+    if timeout_secs == 0 {
+        return Err("`timeout_secs` must be positive".to_owned());
+    }
+    let cache_ttl_secs = non_negative(cache_ttl_secs, "cache_ttl_secs", DEFAULT_CACHE_TTL_SECS)?;
+    let failure_threshold = non_negative(
+        failure_threshold,
+        "failure_threshold",
+        DEFAULT_FAILURE_THRESHOLD,
+    )?;
+    let cooldown_secs = non_negative(cooldown_secs, "cooldown_secs", DEFAULT_COOLDOWN_SECS)?;
+
+    Ok(RequestOptions {
+        method,
+        allowed_hosts,
+        timeout: Duration::from_secs(timeout_secs),
+        cache_ttl: Duration::from_secs(cache_ttl_secs),
+        failure_threshold: u32::try_from(failure_threshold.max(1)).unwrap_or(u32::MAX),
+        cooldown: Duration::from_secs(cooldown_secs),
+    })
+}
+
+fn non_negative(value: Option<Value>, name: &str, default: i64) -> Result<u64, String> {
+    let value = match value {
+        Some(value) => value
+            .try_integer()
+            .map_err(|error| format!("invalid `{name}`: {error}"))?,
+        None => default,
+    };
+    u64::try_from(value).map_err(|_| format!("`{name}` must not be negative"))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct HttpRequest;
+
+impl Function for HttpRequest {
+    fn identifier(&self) -> &'static str {
+        "http_request"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "url",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "allowed_hosts",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "method",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "headers",
+                kind: kind::OBJECT,
+                required: false,
+            },
+            Parameter {
+                keyword: "body",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "timeout_secs",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "cache_ttl_secs",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "failure_threshold",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "cooldown_secs",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Look up a user",
+            source: r#"http_request!("https://users.example.com/users/42", allowed_hosts: ["users.example.com"]).status"#,
+            result: Ok("200"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let span = ctx.span();
+        let url = arguments.required("url");
+        let headers = arguments.optional("headers");
+        let body = arguments.optional("body");
+
+        let method = arguments.optional_literal("method", state)?;
+        let allowed_hosts = arguments.required_literal("allowed_hosts", state)?;
+        let timeout_secs = arguments.optional_literal("timeout_secs", state)?;
+        let cache_ttl_secs = arguments.optional_literal("cache_ttl_secs", state)?;
+        let failure_threshold = arguments.optional_literal("failure_threshold", state)?;
+        let cooldown_secs = arguments.optional_literal("cooldown_secs", state)?;
+
+        let options = request_options(
+            method,
+            allowed_hosts,
+            timeout_secs,
+            cache_ttl_secs,
+            failure_threshold,
+            cooldown_secs,
+        )
+        .and_then(|options| {
+            // Literal URLs are checked right away rather than only once resolved.
+            if let Some(url) = url.resolve_constant(state) {
+                let url = url
+                    .try_bytes_utf8_lossy()
+                    .map_err(|error| format!("invalid URL: {error}"))?;
+                options.check_url(&url)?;
+            }
+            Ok(options)
+        });
+        let client_and_options = options.and_then(|options| Ok((shared_client()?, options)));
+        let (client, options) = client_and_options.map_err(|message| {
+            Box::new(ExpressionError::Error {
+                message,
+                labels: vec![Label::primary("invalid HTTP request", span)],
+                notes: vec![],
+            }) as Box<dyn DiagnosticMessage>
+        })?;
+
+        Ok(HttpRequestFn {
+            url,
+            headers,
+            body,
+            client,
+            options: Arc::new(options),
+            state: Arc::default(),
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HttpRequestFn {
+    url: Box<dyn Expression>,
+    headers: Option<Box<dyn Expression>>,
+    body: Option<Box<dyn Expression>>,
+    client: reqwest::Client,
+    options: Arc<RequestOptions>,
+    state: Arc<Mutex<RequestState>>,
+}
+
+impl FunctionExpression for HttpRequestFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let url = self.url.resolve(ctx)?;
+        let headers = self
+            .headers
+            .as_ref()
+            .map(|headers| headers.resolve(ctx))
+            .transpose()?;
+        let body = self
+            .body
+            .as_ref()
+            .map(|body| body.resolve(ctx))
+            .transpose()?;
+        http_request(&self.client, url, headers, body, &self.options, &self.state)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::object(BTreeMap::from([
+            ("status".into(), Kind::integer()),
+            (
+                "headers".into(),
+                Kind::object(Collection::from_unknown(Kind::bytes())),
+            ),
+            ("body".into(), Kind::bytes()),
+        ]))
+        .fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    use super::*;
+
+    fn options(allowed_hosts: &[&str], cache_ttl_secs: i64, cooldown_secs: i64) -> RequestOptions {
+        request_options(
+            None,
+            Value::Array(
+                allowed_hosts
+                    .iter()
+                    .map(|host| Value::from(*host))
+                    .collect(),
+            ),
+            None,
+            Some(cache_ttl_secs.into()),
+            Some(2.into()),
+            Some(cooldown_secs.into()),
+        )
+        .unwrap()
+    }
+
+    /// Serves `count` requests on loopback, answering each with a `200 OK` response.
+    fn serve(count: usize) -> String {
+        serve_response(
+            count,
+            "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok".to_owned(),
+        )
+    }
+
+    /// Serves `count` requests on loopback, answering each with `response`.
+    fn serve_response(count: usize, response: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(count) {
+                let mut stream = stream.unwrap();
+                let mut request = [0; 1024];
+                _ = stream.read(&mut request).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        format!("http://{address}/")
+    }
+
+    #[test]
+    fn allows_listed_hosts_and_subdomains_of_wildcards() {
+        let options = options(&["users.example.com", "*.example.org"], 0, 0);
+
+        assert!(options
+            .check_url("https://users.example.com/users/42")
+            .is_ok());
+        assert!(options.check_url("https://USERS.example.com/").is_ok());
+        assert!(options.check_url("https://api.example.org/").is_ok());
+        assert!(options.check_url("https://eu.api.example.org/").is_ok());
+
+        assert!(options.check_url("https://example.org/").is_err());
+        assert!(options.check_url("https://badexample.org/").is_err());
+        assert!(options.check_url("https://other.example.com/").is_err());
+        assert!(options
+            .check_url("https://users.example.com.evil.net/")
+            .is_err());
+        assert!(options.check_url("ftp://users.example.com/").is_err());
+    }
+
+    #[test]
+    fn cached_responses_expire() {
+        let key = CacheKey {
+            url: "https://users.example.com/".to_owned(),
+            headers: vec![],
+        };
+        let mut state = RequestState::default();
+
+        state.cache(key.clone(), Value::from("live"), Duration::from_secs(60));
+        assert_eq!(state.cached(&key), Some(Value::from("live")));
+
+        state.cache(key.clone(), Value::from("expired"), Duration::ZERO);
+        assert_eq!(state.cached(&key), None);
+        assert!(state.cache.is_empty());
+    }
+
+    #[test]
+    fn circuit_breaker_trips_and_resets() {
+        let mut state = RequestState::default();
+
+        let options = options(&["users.example.com"], 0, 60);
+        state.record(false, &options);
+        assert!(state.check_circuit().is_ok());
+        state.record(false, &options);
+        assert!(state.check_circuit().is_err());
+
+        // Once the cooldown elapses, a successful request closes the circuit.
+        let options = RequestOptions {
+            cooldown: Duration::ZERO,
+            ..options
+        };
+        state.record(false, &options);
+        assert!(state.check_circuit().is_ok());
+        state.record(true, &options);
+        assert_eq!(state.consecutive_failures, 0);
+        state.record(false, &options);
+        assert!(state.check_circuit().is_ok());
+    }
+
+    #[test]
+    fn redirects_to_other_hosts_arent_followed() {
+        // The redirect targets `localhost`, which isn't allowed, on a port where a request would be
+        // noticed.
+        let target = TcpListener::bind("127.0.0.1:0").unwrap();
+        target.set_nonblocking(true).unwrap();
+        let location = format!("http://localhost:{}/", target.local_addr().unwrap().port());
+        let url = serve_response(
+            1,
+            format!(
+                "HTTP/1.1 302 Found\r\nlocation: {location}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+            ),
+        );
+        let options = options(&["127.0.0.1"], 0, 0);
+        let client = shared_client().unwrap();
+
+        let response =
+            http_request(&client, url.into(), None, None, &options, &Mutex::default()).unwrap();
+        let response = response.as_object().unwrap();
+        assert_eq!(response["status"], Value::from(302_i64));
+        assert_eq!(
+            response["headers"].as_object().unwrap()["location"],
+            Value::from(location)
+        );
+        assert_eq!(
+            target.accept().unwrap_err().kind(),
+            std::io::ErrorKind::WouldBlock
+        );
+    }
+
+    #[test]
+    fn sends_requests_with_the_shared_client() {
+        let url = serve(2);
+        let options = options(&["127.0.0.1"], 0, 0);
+        let state = Mutex::default();
+        let client = shared_client().unwrap();
+        let status = |response: Resolved| response.unwrap().as_object().unwrap()["status"].clone();
+
+        // Outside of any runtime, as when compiling and running programs from the CLI.
+        let response = http_request(&client, url.clone().into(), None, None, &options, &state);
+        assert_eq!(status(response), Value::from(200_i64));
+
+        // Within a multi-threaded runtime, as when running programs in transforms.
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let response = runtime.block_on(async {
+            tokio::spawn(
+                async move { http_request(&client, url.into(), None, None, &options, &state) },
+            )
+            .await
+            .unwrap()
+        });
+        assert_eq!(status(response), Value::from(200_i64));
+    }
+}
@@ -3,10 +3,31 @@
 use vrl::compiler::Function;
 use vrl::path::OwnedTargetPath;
 
+pub mod all;
+pub mod any;
+#[cfg(feature = "cached_dns")]
+pub mod cached_dns;
+pub mod decrypt_aead;
+pub mod encode_avro;
+pub mod encrypt_aead;
 pub mod get_secret;
+pub mod get_state;
+#[cfg(feature = "http_request")]
+pub mod http_request;
+pub mod match_type;
+pub mod parse_avro;
+pub mod parse_leef;
+pub mod parse_named_groks;
+pub mod parse_xml_nodes;
 pub mod remove_secret;
+pub mod set_abort_reason;
 pub mod set_secret;
 pub mod set_semantic_meaning;
+pub mod set_state;
+pub mod sign_ed25519;
+pub mod user_function;
+pub mod verify_ed25519;
+pub mod verify_hmac;
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug)]
@@ -18,10 +39,35 @@ pub enum MetadataKey {
 pub const LEGACY_METADATA_KEYS: [&str; 2] = ["datadog_api_key", "splunk_hec_token"];
 
 pub fn all() -> Vec<Box<dyn Function>> {
-    vec![
+    #[allow(unused_mut)]
+    let mut functions: Vec<Box<dyn Function>> = vec![
         Box::new(set_semantic_meaning::SetSemanticMeaning) as _,
         Box::new(get_secret::GetSecret) as _,
+        Box::new(parse_avro::ParseAvro) as _,
+        Box::new(parse_leef::ParseLeef) as _,
+        Box::new(parse_named_groks::ParseNamedGroks) as _,
+        Box::new(parse_xml_nodes::ParseXmlNodes) as _,
         Box::new(remove_secret::RemoveSecret) as _,
         Box::new(set_secret::SetSecret) as _,
-    ]
+        Box::new(set_abort_reason::SetAbortReason) as _,
+        Box::new(get_state::GetState) as _,
+        Box::new(set_state::SetState) as _,
+        Box::new(encrypt_aead::EncryptAead) as _,
+        Box::new(decrypt_aead::DecryptAead) as _,
+        Box::new(encode_avro::EncodeAvro) as _,
+        Box::new(sign_ed25519::SignEd25519) as _,
+        Box::new(verify_ed25519::VerifyEd25519) as _,
+        Box::new(verify_hmac::VerifyHmac) as _,
+        Box::new(any::Any) as _,
+        Box::new(all::All) as _,
+        Box::new(match_type::MatchType) as _,
+    ];
+    #[cfg(feature = "cached_dns")]
+    functions.extend([
+        Box::new(cached_dns::CachedDnsLookup) as _,
+        Box::new(cached_dns::CachedReverseDns) as _,
+    ]);
+    #[cfg(feature = "http_request")]
+    functions.push(Box::new(http_request::HttpRequest) as _);
+    functions
 }
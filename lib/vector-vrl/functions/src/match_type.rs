@@ -0,0 +1,275 @@
+use std::collections::BTreeMap;
+
+use vrl::diagnostic::Label;
+use vrl::prelude::*;
+
+/// The names of the types values can be matched on, as reported in error messages.
+const TYPES: [&str; 9] = [
+    "array",
+    "boolean",
+    "float",
+    "integer",
+    "null",
+    "object",
+    "regex",
+    "string",
+    "timestamp",
+];
+
+/// Whether values of the kind can be of the named type.
+fn kind_contains(kind: &Kind, name: &str) -> bool {
+    match name {
+        "array" => kind.contains_array(),
+        "boolean" => kind.contains_boolean(),
+        "float" => kind.contains_float(),
+        "integer" => kind.contains_integer(),
+        "null" => kind.contains_null(),
+        "object" => kind.contains_object(),
+        "regex" => kind.contains_regex(),
+        "string" => kind.contains_bytes(),
+        "timestamp" => kind.contains_timestamp(),
+        _ => false,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct MatchType;
+
+impl Function for MatchType {
+    fn identifier(&self) -> &'static str {
+        "match_type"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "cases",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "default",
+                kind: kind::ANY,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Match on the type of a value",
+                source: r#"match_type(42, { "integer": "number", "string": "text" })"#,
+                result: Ok(r#""number""#),
+            },
+            Example {
+                title: "Fall back to the default",
+                source: r#"match_type([1, 2], { "integer": "number" }, default: "other")"#,
+                result: Ok(r#""other""#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let cases = arguments.required_object("cases")?;
+        let default = arguments.optional("default");
+
+        if let Some(name) = cases.keys().find(|name| !TYPES.contains(&name.as_str())) {
+            return Err(Box::new(ExpressionError::Error {
+                message: format!(
+                    "unknown type {name:?}, expected one of {}",
+                    TYPES.join(", ")
+                ),
+                labels: vec![Label::primary("unknown type", ctx.span())],
+                notes: vec![],
+            }) as Box<dyn DiagnosticMessage>);
+        }
+
+        // The cases a constant value resolves to are known when compiling, so the other cases
+        // aren't kept, and neither is the value.
+        if let Some(constant) = value.resolve_constant(state) {
+            return Ok(match (cases.get(constant.kind_str()), default) {
+                (Some(case), _) => Box::new(case.clone()) as Box<dyn Expression>,
+                (None, Some(default)) => default,
+                (None, None) => Box::new(expression::Literal::Null),
+            });
+        }
+
+        // Otherwise, only the cases of the types the value can be are kept, and the default is only
+        // kept if they aren't exhaustive.
+        let kind = value.type_def(state).kind().clone();
+        let mut cases = cases;
+        cases.retain(|name, _| kind_contains(&kind, name.as_str()));
+        let exhaustive = TYPES
+            .into_iter()
+            .filter(|name| kind_contains(&kind, name))
+            .all(|name| cases.contains_key(name));
+        let default = default.filter(|_| !exhaustive);
+
+        Ok(MatchTypeFn {
+            value,
+            cases,
+            default,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MatchTypeFn {
+    value: Box<dyn Expression>,
+    /// The expression of each type, only resolved for values of this type.
+    cases: BTreeMap<KeyString, expression::Expr>,
+    default: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for MatchTypeFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        match (self.cases.get(value.kind_str()), &self.default) {
+            (Some(case), _) => case.resolve(ctx),
+            (None, Some(default)) => default.resolve(ctx),
+            (None, None) => Ok(Value::Null),
+        }
+    }
+
+    /// Only the cases of the types the value can be are reachable, and the default is only
+    /// reachable if these cases aren't exhaustive.
+    fn type_def(&self, state: &TypeState) -> TypeDef {
+        let kind = self.value.type_def(state).kind().clone();
+        let mut exhaustive = true;
+        let mut type_defs = Vec::new();
+        for name in TYPES.into_iter().filter(|name| kind_contains(&kind, name)) {
+            match self.cases.get(name) {
+                Some(case) => type_defs.push(case.type_def(state)),
+                None => exhaustive = false,
+            }
+        }
+        if !exhaustive {
+            type_defs.push(match &self.default {
+                Some(default) => default.type_def(state),
+                None => TypeDef::null().infallible(),
+            });
+        }
+
+        type_defs
+            .into_iter()
+            .reduce(TypeDef::union)
+            .unwrap_or_else(|| TypeDef::null().infallible())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vrl::compiler::runtime::{Runtime, Terminate};
+    use vrl::compiler::{compile_with_state, CompileConfig, Program, TargetValue, TimeZone};
+    use vrl::diagnostic::Formatter;
+    use vrl::value;
+    use vrl::value::Secrets;
+
+    use super::*;
+
+    fn compile(source: &str) -> std::result::Result<Program, String> {
+        let mut functions = vrl::stdlib::all();
+        functions.push(Box::new(MatchType));
+        compile_with_state(
+            source,
+            &functions,
+            &TypeState::default(),
+            CompileConfig::default(),
+        )
+        .map(|result| result.program)
+        .map_err(|diagnostics| Formatter::new(source, diagnostics).to_string())
+    }
+
+    fn run(program: &Program, event: Value) -> std::result::Result<Value, Terminate> {
+        let mut target = TargetValue {
+            value: event,
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        };
+        Runtime::default().resolve(&mut target, program, &TimeZone::default())
+    }
+
+    #[test]
+    fn matches_types() {
+        let program = compile(
+            r#"match_type(.status, { "integer": .status, "string": to_int(.status) ?? 0 }, default: -1)"#,
+        )
+        .unwrap();
+
+        assert_eq!(run(&program, value!({ "status": 200 })), Ok(value!(200)));
+        assert_eq!(run(&program, value!({ "status": "404" })), Ok(value!(404)));
+        assert_eq!(run(&program, value!({ "status": [500] })), Ok(value!(-1)));
+        assert_eq!(run(&program, value!({})), Ok(value!(-1)));
+    }
+
+    #[test]
+    fn only_resolves_matched_case() {
+        // The `integer` case fails the program when it's resolved.
+        let program = compile(
+            r#"match_type(.message, { "string": "text", "integer": assert!(false, "resolved") })"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            run(&program, value!({ "message": "hello" })),
+            Ok(value!("text"))
+        );
+        assert!(run(&program, value!({ "message": 1 })).is_err());
+    }
+
+    #[test]
+    fn infers_kind_of_reachable_cases() {
+        // Only the `integer` case is reachable, so the result is known to be a string.
+        assert!(compile(r#"upcase(match_type(1, { "integer": "one", "string": 2 }))"#).is_ok());
+        // The cases aren't exhaustive without a default, so the result can be null.
+        assert!(compile(r#"upcase(match_type(.message, { "string": "text" }))"#).is_err());
+        assert!(
+            compile(r#"upcase(match_type(.message, { "string": "text" }, default: "none"))"#)
+                .is_ok()
+        );
+        // The default isn't reachable once the cases are exhaustive.
+        assert!(compile(
+            r#"upcase(match_type(string!(.message), { "string": "text" }, default: 1))"#
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn resolves_constants_when_compiling() {
+        let program = compile(
+            r#"
+                status = 404
+                [
+                    match_type(status, { "integer": "number", "string": "text" }),
+                    match_type("a", { "integer": "number" }, default: "other"),
+                    match_type("a", { "integer": "number" }),
+                ]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            run(&program, value!({})),
+            Ok(value!(["number", "other", null]))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_types() {
+        let error = compile(r#"match_type(.message, { "int": 1 })"#).unwrap_err();
+        assert!(error.contains(r#"unknown type "int""#));
+    }
+}
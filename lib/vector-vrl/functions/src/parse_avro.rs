@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use apache_avro::rabin::Rabin;
+use apache_avro::schema::{Name, ResolvedSchema};
+use apache_avro::types::Value as AvroValue;
+use apache_avro::Schema;
+use vrl::diagnostic::Label;
+use vrl::prelude::*;
+use vrl::value::ObjectMap;
+
+/// The marker starting Avro single-object encoded payloads, followed by the fingerprint of the
+/// schema.
+const SINGLE_OBJECT_MARKER: [u8; 2] = [0xC3, 0x01];
+
+/// An Avro schema, along with what's needed to decode and encode single-object payloads with it.
+#[derive(Debug)]
+pub(crate) struct AvroSchema {
+    pub(crate) schema: Schema,
+    /// The named types of the schema, for resolving references to them.
+    pub(crate) names: HashMap<Name, Schema>,
+    /// The single-object marker followed by the 64-bit Rabin fingerprint of the schema.
+    pub(crate) header: Vec<u8>,
+}
+
+impl AvroSchema {
+    fn parse(json: &str) -> Result<Self, String> {
+        let schema =
+            Schema::parse_str(json).map_err(|error| format!("invalid Avro schema: {error}"))?;
+        let names = ResolvedSchema::try_from(&schema)
+            .map_err(|error| format!("invalid Avro schema: {error}"))?
+            .get_names()
+            .iter()
+            .map(|(name, schema)| (name.clone(), (*schema).clone()))
+            .collect();
+        let mut header = SINGLE_OBJECT_MARKER.to_vec();
+        header.extend_from_slice(&schema.fingerprint::<Rabin>().bytes);
+        Ok(Self {
+            schema,
+            names,
+            header,
+        })
+    }
+}
+
+/// Compiles the schema given either inline with the `schema` argument or as a path with the
+/// `schema_file` argument, which are mutually exclusive.
+pub(crate) fn compile_schema(
+    arguments: &ArgumentList,
+    state: &TypeState,
+    ctx: &FunctionCompileContext,
+) -> Result<Arc<AvroSchema>, Box<dyn DiagnosticMessage>> {
+    let span = ctx.span();
+    let schema = arguments.optional_literal("schema", state)?;
+    let schema_file = arguments.optional_literal("schema_file", state)?;
+
+    load_schema(schema, schema_file)
+        .map(Arc::new)
+        .map_err(|message| {
+            Box::new(ExpressionError::Error {
+                message,
+                labels: vec![Label::primary("invalid Avro schema", span)],
+                notes: vec![],
+            }) as Box<dyn DiagnosticMessage>
+        })
+}
+
+fn load_schema(schema: Option<Value>, schema_file: Option<Value>) -> Result<AvroSchema, String> {
+    let json = match (schema, schema_file) {
+        (Some(schema), None) => schema
+            .try_bytes_utf8_lossy()
+            .map_err(|error| format!("invalid schema: {error}"))?
+            .into_owned(),
+        (None, Some(path)) => {
+            let path = path
+                .try_bytes_utf8_lossy()
+                .map_err(|error| format!("invalid schema file: {error}"))?;
+            std::fs::read_to_string(path.as_ref())
+                .map_err(|error| format!("unable to read schema file {path:?}: {error}"))?
+        }
+        (None, None) => return Err("one of `schema` or `schema_file` must be provided".to_owned()),
+        (Some(_), Some(_)) => {
+            return Err("only one of `schema` or `schema_file` can be provided".to_owned())
+        }
+    };
+    AvroSchema::parse(&json)
+}
+
+fn parse_avro(value: Value, schema: &AvroSchema) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let mut datum = bytes
+        .strip_prefix(schema.header.as_slice())
+        .ok_or("missing the single-object marker, or the schema fingerprint doesn't match")?;
+    let value = apache_avro::from_avro_datum(&schema.schema, &mut datum, None)
+        .map_err(|error| format!("unable to decode Avro value: {error}"))?;
+    from_avro(value).map_err(Into::into)
+}
+
+/// Converts a decoded Avro value. Logical types with no VRL equivalent are kept as their underlying
+/// integers, as done by the `avro` codec.
+fn from_avro(value: AvroValue) -> Result<Value, String> {
+    match value {
+        AvroValue::Null => Ok(Value::Null),
+        AvroValue::Boolean(boolean) => Ok(boolean.into()),
+        AvroValue::Int(int) => Ok(int.into()),
+        AvroValue::Long(long) => Ok(long.into()),
+        AvroValue::Float(float) => Ok(Value::from_f64_or_zero(float.into())),
+        AvroValue::Double(double) => Ok(Value::from_f64_or_zero(double)),
+        AvroValue::Bytes(bytes) | AvroValue::Fixed(_, bytes) => Ok(bytes.into()),
+        AvroValue::String(string) | AvroValue::Enum(_, string) => Ok(string.into()),
+        AvroValue::Uuid(uuid) => Ok(uuid.as_hyphenated().to_string().into()),
+        AvroValue::Date(days) | AvroValue::TimeMillis(days) => Ok(days.into()),
+        AvroValue::TimeMicros(int)
+        | AvroValue::TimestampMillis(int)
+        | AvroValue::TimestampMicros(int)
+        | AvroValue::LocalTimestampMillis(int)
+        | AvroValue::LocalTimestampMicros(int) => Ok(int.into()),
+        AvroValue::Union(_, value) => from_avro(*value),
+        AvroValue::Array(values) => values
+            .into_iter()
+            .map(from_avro)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        AvroValue::Map(fields) => fields
+            .into_iter()
+            .map(|(key, value)| Ok((key.into(), from_avro(value)?)))
+            .collect::<Result<ObjectMap, String>>()
+            .map(Value::Object),
+        AvroValue::Record(fields) => fields
+            .into_iter()
+            .map(|(key, value)| Ok((key.into(), from_avro(value)?)))
+            .collect::<Result<ObjectMap, String>>()
+            .map(Value::Object),
+        AvroValue::Decimal(_) => Err("decimal values are not supported".to_owned()),
+        AvroValue::Duration(_) => Err("duration values are not supported".to_owned()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseAvro;
+
+impl Function for ParseAvro {
+    fn identifier(&self) -> &'static str {
+        "parse_avro"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "schema",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "schema_file",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Parse an Avro single-object encoded record",
+            source: r#"parse_avro!(decode_base64!("wwEXzGcl6JXfawpoZWxsbw=="), schema: s'{"type": "record", "name": "Message", "fields": [{"name": "message", "type": "string"}]}')"#,
+            result: Ok(r#"{ "message": "hello" }"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let schema = compile_schema(&arguments, state, ctx)?;
+        Ok(ParseAvroFn { value, schema }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseAvroFn {
+    value: Box<dyn Expression>,
+    schema: Arc<AvroSchema>,
+}
+
+impl FunctionExpression for ParseAvroFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        parse_avro(value, &self.schema)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::any().fallible()
+    }
+}
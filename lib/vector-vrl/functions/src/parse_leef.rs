@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+
+use vrl::prelude::*;
+use vrl::value::kind::{Collection, Field};
+use vrl::value::{KeyString, ObjectMap};
+
+/// The separator of the header fields.
+const HEADER_SEPARATOR: char = '|';
+
+/// The attribute delimiter used when none is specified, as mandated by LEEF 1.0.
+const DEFAULT_DELIMITER: char = '\t';
+
+/// Delimiters tried, in order, when the attributes are not separated by tabs.
+const DELIMITER_CANDIDATES: [char; 5] = ['|', '^', ';', ',', ' '];
+
+/// The fields of the header shared by all LEEF versions.
+const HEADER_FIELDS: [&str; 5] = [
+    "leefVersion",
+    "vendor",
+    "productName",
+    "productVersion",
+    "eventId",
+];
+
+fn parse_leef(value: Value, delimiter: Option<Value>) -> Resolved {
+    let delimiter = match delimiter {
+        Some(delimiter) => {
+            let delimiter = delimiter.try_bytes_utf8_lossy()?;
+            let parsed = parse_delimiter(&delimiter)
+                .ok_or_else(|| format!("invalid delimiter: {delimiter:?}"))?;
+            Some(parsed)
+        }
+        None => None,
+    };
+
+    let value = value.try_bytes_utf8_lossy()?;
+    let start = value
+        .find("LEEF:")
+        .ok_or("unable to find the LEEF header")?;
+    let mut input = &value[start + "LEEF:".len()..];
+
+    let mut header = Vec::with_capacity(HEADER_FIELDS.len());
+    for field in HEADER_FIELDS {
+        let (value, rest) = header_field(input).ok_or_else(|| {
+            format!("unable to parse the LEEF header: missing the `{field}` field")
+        })?;
+        header.push(value);
+        input = rest;
+    }
+
+    let version = header[0].trim();
+    let header_delimiter = match version.split('.').next() {
+        Some("1") => None,
+        Some("2") => match header_field(input) {
+            // The delimiter field is optional, an attribute is never a valid delimiter.
+            Some((value, rest)) if value.is_empty() => {
+                input = rest;
+                None
+            }
+            Some((value, rest)) => match parse_delimiter(&value) {
+                Some(delimiter) => {
+                    input = rest;
+                    Some(delimiter)
+                }
+                None => None,
+            },
+            None => None,
+        },
+        _ => return Err(format!("unsupported LEEF version: {version:?}").into()),
+    };
+
+    let delimiter = delimiter
+        .or(header_delimiter)
+        .unwrap_or_else(|| detect_delimiter(input));
+    let mut event = HEADER_FIELDS
+        .into_iter()
+        .map(KeyString::from)
+        .zip(header.into_iter().map(Value::from))
+        .collect::<ObjectMap>();
+    event.insert(
+        "attributes".into(),
+        parse_attributes(input, delimiter).into(),
+    );
+
+    Ok(event.into())
+}
+
+/// Splits the leading header field off `input`, unescaping `\|` and `\\`. Returns `None` if the
+/// field is not terminated by a separator.
+fn header_field(input: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, escaped @ (HEADER_SEPARATOR | '\\'))) => value.push(escaped),
+                Some((_, other)) => {
+                    value.push('\\');
+                    value.push(other);
+                }
+                None => value.push('\\'),
+            },
+            HEADER_SEPARATOR => return Some((value, &input[index + 1..])),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// Parses a delimiter given either as a single character or as its hexadecimal code point,
+/// prefixed with `x` or `0x`.
+fn parse_delimiter(value: &str) -> Option<char> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some('='), None) => None,
+        (Some(c), None) => Some(c),
+        _ => value
+            .strip_prefix("0x")
+            .or_else(|| value.strip_prefix('x'))
+            .or_else(|| value.strip_prefix('X'))
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .and_then(char::from_u32)
+            .filter(|c| *c != '='),
+    }
+}
+
+/// Detects the delimiter of attributes which are not separated by tabs, by looking for a
+/// candidate which only separates `key=value` pairs.
+fn detect_delimiter(attributes: &str) -> char {
+    if attributes.contains(DEFAULT_DELIMITER) {
+        return DEFAULT_DELIMITER;
+    }
+
+    DELIMITER_CANDIDATES
+        .into_iter()
+        .find(|candidate| {
+            attributes.contains(*candidate)
+                && attributes
+                    .split(*candidate)
+                    .filter(|pair| !pair.is_empty())
+                    .all(|pair| pair.contains('='))
+        })
+        .unwrap_or(DEFAULT_DELIMITER)
+}
+
+/// Parses the `key=value` pairs of the attributes. A segment without `=` is considered part of the
+/// value of the previous attribute, as the delimiter is not escaped in values.
+fn parse_attributes(input: &str, delimiter: char) -> ObjectMap {
+    let mut attributes = ObjectMap::new();
+    let mut last: Option<(KeyString, String)> = None;
+
+    for segment in input.split(delimiter) {
+        if segment.is_empty() {
+            continue;
+        }
+
+        match segment.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                if let Some((key, value)) = last.take() {
+                    attributes.insert(key, value.into());
+                }
+                last = Some((key.trim().into(), value.to_owned()));
+            }
+            _ => {
+                if let Some((_, value)) = &mut last {
+                    value.push(delimiter);
+                    value.push_str(segment);
+                }
+            }
+        }
+    }
+
+    if let Some((key, value)) = last {
+        attributes.insert(key, value.into());
+    }
+
+    attributes
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseLeef;
+
+impl Function for ParseLeef {
+    fn identifier(&self) -> &'static str {
+        "parse_leef"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "delimiter",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Parse a LEEF 1.0 event",
+                source: r#"parse_leef!("LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=10.50.1.1\tdst=2.10.20.20\tspt=1200")"#,
+                result: Ok(
+                    r#"{"leefVersion": "1.0", "vendor": "Microsoft", "productName": "MSExchange", "productVersion": "4.0 SP1", "eventId": "15345", "attributes": {"src": "10.50.1.1", "dst": "2.10.20.20", "spt": "1200"}}"#,
+                ),
+            },
+            Example {
+                title: "Parse a LEEF 2.0 event with a custom delimiter",
+                source: r#"parse_leef!("LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^sev=5^srcPort=81^dstPort=21")"#,
+                result: Ok(
+                    r#"{"leefVersion": "2.0", "vendor": "Lancope", "productName": "StealthWatch", "productVersion": "1.0", "eventId": "41", "attributes": {"src": "10.0.1.8", "dst": "10.0.0.5", "sev": "5", "srcPort": "81", "dstPort": "21"}}"#,
+                ),
+            },
+            Example {
+                title: "Parse a LEEF 2.0 event with a hexadecimal delimiter",
+                source: r#"parse_leef!("<13>Jan 18 11:07:53 host LEEF:2.0|Vendor|Product|2.1|login|x7C|usrName=admin|src=192.168.0.1")"#,
+                result: Ok(
+                    r#"{"leefVersion": "2.0", "vendor": "Vendor", "productName": "Product", "productVersion": "2.1", "eventId": "login", "attributes": {"usrName": "admin", "src": "192.168.0.1"}}"#,
+                ),
+            },
+            Example {
+                title: "Parse a LEEF event with a detected delimiter",
+                source: r#"parse_leef!("LEEF:1.0|Vendor|Product|1.0|alert|cat=malware;sev=7;msg=a=b")"#,
+                result: Ok(
+                    r#"{"leefVersion": "1.0", "vendor": "Vendor", "productName": "Product", "productVersion": "1.0", "eventId": "alert", "attributes": {"cat": "malware", "sev": "7", "msg": "a=b"}}"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let delimiter = arguments.optional("delimiter");
+        Ok(ParseLeefFn { value, delimiter }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseLeefFn {
+    value: Box<dyn Expression>,
+    delimiter: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for ParseLeefFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let delimiter = self
+            .delimiter
+            .as_ref()
+            .map(|delimiter| delimiter.resolve(ctx))
+            .transpose()?;
+        parse_leef(value, delimiter)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    let mut kind = HEADER_FIELDS
+        .into_iter()
+        .map(|field| (field.into(), Kind::bytes()))
+        .collect::<BTreeMap<_, _>>();
+    kind.insert(
+        "attributes".into(),
+        Kind::object(Collection::from_unknown(Kind::bytes())),
+    );
+    kind
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use grok::{Grok, Pattern};
+use vrl::diagnostic::Label;
+use vrl::prelude::*;
+use vrl::value::kind::Collection;
+use vrl::value::ObjectMap;
+
+/// A compiled grok pattern, along with the name it's reported under when it matches.
+struct NamedPattern {
+    name: String,
+    pattern: Pattern,
+}
+
+impl fmt::Debug for NamedPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NamedPattern")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Compiles the `patterns`, each an object with a `name` and a `pattern`, in order, with the
+/// `aliases` available to all of them.
+fn compile_patterns(patterns: Value, aliases: Option<Value>) -> Result<Vec<NamedPattern>, String> {
+    let mut grok = Grok::with_default_patterns();
+    if let Some(aliases) = aliases {
+        let aliases = aliases
+            .try_object()
+            .map_err(|error| format!("invalid aliases: {error}"))?;
+        for (alias, definition) in aliases {
+            let definition = definition
+                .try_bytes_utf8_lossy()
+                .map_err(|error| format!("invalid definition of alias {alias:?}: {error}"))?
+                .into_owned();
+            grok.add_pattern(alias.to_string(), definition);
+        }
+    }
+
+    patterns
+        .try_array()
+        .map_err(|error| format!("invalid patterns: {error}"))?
+        .into_iter()
+        .enumerate()
+        .map(|(index, pattern)| {
+            let string_field = |pattern: &Value, field: &str| {
+                pattern
+                    .as_object()
+                    .and_then(|pattern| pattern.get(field))
+                    .and_then(Value::as_bytes)
+                    .map(|value| String::from_utf8_lossy(value).into_owned())
+                    .ok_or_else(|| {
+                        format!(
+                            "pattern at index {index} must be an object with a string `{field}`"
+                        )
+                    })
+            };
+            let name = string_field(&pattern, "name")?;
+            let pattern = grok
+                .compile(&string_field(&pattern, "pattern")?, true)
+                .map_err(|error| format!("failed to compile pattern {name:?}: {error}"))?;
+            Ok(NamedPattern { name, pattern })
+        })
+        .collect()
+}
+
+fn parse_named_groks(value: Value, patterns: &[NamedPattern]) -> Resolved {
+    let value = value.try_bytes_utf8_lossy()?;
+    for NamedPattern { name, pattern } in patterns {
+        if let Some(matches) = pattern.match_against(&value) {
+            let captures = matches
+                .iter()
+                .filter(|(_, capture)| !capture.is_empty())
+                .map(|(field, capture)| (field.into(), capture.into()))
+                .collect::<ObjectMap>();
+            return Ok(Value::from(ObjectMap::from([
+                ("pattern".into(), name.as_str().into()),
+                ("captures".into(), captures.into()),
+            ])));
+        }
+    }
+    Err("unable to parse input with any of the patterns".into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseNamedGroks;
+
+impl Function for ParseNamedGroks {
+    fn identifier(&self) -> &'static str {
+        "parse_named_groks"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "patterns",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "aliases",
+                kind: kind::OBJECT,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Parse using the first matching pattern",
+                source: r#"parse_named_groks!("2020-10-02T23:22:12.223222Z info Hello world", patterns: [{"name": "access", "pattern": "%{IPORHOST:client} %{WORD:method} %{URIPATHPARAM:path}"}, {"name": "application", "pattern": "%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{GREEDYDATA:message}"}])"#,
+                result: Ok(
+                    r#"{"pattern": "application", "captures": {"timestamp": "2020-10-02T23:22:12.223222Z", "level": "info", "message": "Hello world"}}"#,
+                ),
+            },
+            Example {
+                title: "Parse using shared aliases",
+                source: r#"parse_named_groks!("user=alice action=login", patterns: [{"name": "audit", "pattern": "%{_user} %{_action}"}], aliases: {"_user": "user=%{WORD:user}", "_action": "action=%{WORD:action}"})"#,
+                result: Ok(
+                    r#"{"pattern": "audit", "captures": {"user": "alice", "action": "login"}}"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let span = ctx.span();
+        let value = arguments.required("value");
+        let patterns = arguments.required_literal("patterns", state)?;
+        let aliases = arguments.optional_literal("aliases", state)?;
+
+        let patterns = compile_patterns(patterns, aliases).map_err(|message| {
+            Box::new(ExpressionError::Error {
+                message,
+                labels: vec![Label::primary("invalid grok patterns", span)],
+                notes: vec![],
+            }) as Box<dyn DiagnosticMessage>
+        })?;
+
+        Ok(ParseNamedGroksFn {
+            value,
+            patterns: Arc::new(patterns),
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseNamedGroksFn {
+    value: Box<dyn Expression>,
+    patterns: Arc<Vec<NamedPattern>>,
+}
+
+impl FunctionExpression for ParseNamedGroksFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        parse_named_groks(value, &self.patterns)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::object(BTreeMap::from([
+            ("pattern".into(), Kind::bytes()),
+            (
+                "captures".into(),
+                Kind::object(Collection::from_unknown(Kind::bytes())),
+            ),
+        ]))
+        .fallible()
+    }
+}
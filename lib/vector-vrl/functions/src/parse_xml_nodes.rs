@@ -0,0 +1,420 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use vrl::prelude::*;
+use vrl::value::kind::Collection;
+use vrl::value::{KeyString, ObjectMap};
+
+/// A path selecting elements, such as `/Envelope/Body/*` or `//Item`.
+#[derive(Debug)]
+struct XmlPath {
+    /// Whether the path starts from the root element, rather than matching at any depth.
+    anchored: bool,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug)]
+enum Segment {
+    Any,
+    /// Matches the qualified name of an element if prefixed, otherwise its local name.
+    Name {
+        name: String,
+        qualified: bool,
+    },
+}
+
+impl XmlPath {
+    fn parse(path: &str) -> Result<Self, String> {
+        let (anchored, rest) = match path.strip_prefix("//") {
+            Some(rest) => (false, rest),
+            None => match path.strip_prefix('/') {
+                Some(rest) => (true, rest),
+                None => (false, path),
+            },
+        };
+
+        let segments = rest
+            .split('/')
+            .map(|segment| match segment {
+                "" => Err(format!("invalid path {path:?}: empty segment")),
+                "*" => Ok(Segment::Any),
+                name => Ok(Segment::Name {
+                    name: name.to_owned(),
+                    qualified: name.contains(':'),
+                }),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { anchored, segments })
+    }
+
+    fn matches(&self, stack: &[(String, String)]) -> bool {
+        if stack.len() < self.segments.len()
+            || (self.anchored && stack.len() != self.segments.len())
+        {
+            return false;
+        }
+
+        self.segments
+            .iter()
+            .zip(&stack[stack.len() - self.segments.len()..])
+            .all(|(segment, (qualified_name, local_name))| match segment {
+                Segment::Any => true,
+                Segment::Name { name, qualified } if *qualified => name == qualified_name,
+                Segment::Name { name, .. } => name == local_name,
+            })
+    }
+}
+
+#[derive(Debug)]
+struct Options {
+    include_attr: bool,
+    attr_prefix: String,
+    text_key: KeyString,
+    always_use_text_key: bool,
+    parse_bool: bool,
+    parse_null: bool,
+    parse_number: bool,
+}
+
+/// An element being captured, built up as its contents are read.
+struct Node {
+    name: KeyString,
+    fields: ObjectMap,
+    text: String,
+}
+
+impl Node {
+    fn new(element: &BytesStart, options: &Options) -> Result<Self, String> {
+        let mut node = Node {
+            name: String::from_utf8_lossy(element.local_name().as_ref()).into(),
+            fields: ObjectMap::new(),
+            text: String::new(),
+        };
+
+        if options.include_attr {
+            for attribute in element.attributes() {
+                let attribute =
+                    attribute.map_err(|error| format!("unable to parse xml: {error}"))?;
+                // Namespace declarations aren't attributes of the element.
+                let key = attribute.key.as_ref();
+                if key == b"xmlns" || key.starts_with(b"xmlns:") {
+                    continue;
+                }
+                let value = attribute
+                    .unescape_value()
+                    .map_err(|error| format!("unable to parse xml: {error}"))?;
+                node.fields.insert(
+                    format!(
+                        "{}{}",
+                        options.attr_prefix,
+                        String::from_utf8_lossy(attribute.key.local_name().as_ref())
+                    )
+                    .into(),
+                    convert_text(value.into_owned(), options),
+                );
+            }
+        }
+
+        Ok(node)
+    }
+
+    /// Adds a child element, grouping repeated elements into an array.
+    fn add_child(&mut self, name: KeyString, value: Value) {
+        match self.fields.get_mut(&name) {
+            Some(Value::Array(values)) => values.push(value),
+            Some(existing) => {
+                let first = std::mem::replace(existing, Value::Null);
+                *existing = Value::Array(vec![first, value]);
+            }
+            None => {
+                self.fields.insert(name, value);
+            }
+        }
+    }
+
+    fn into_value(self, options: &Options) -> Value {
+        let Node {
+            mut fields, text, ..
+        } = self;
+
+        if fields.is_empty() && !options.always_use_text_key {
+            return if text.is_empty() {
+                Value::Null
+            } else {
+                convert_text(text, options)
+            };
+        }
+
+        if !text.is_empty() {
+            fields.insert(options.text_key.clone(), convert_text(text, options));
+        }
+        Value::Object(fields)
+    }
+}
+
+fn convert_text(text: String, options: &Options) -> Value {
+    match text.as_str() {
+        "true" if options.parse_bool => true.into(),
+        "false" if options.parse_bool => false.into(),
+        "null" if options.parse_null => Value::Null,
+        _ if options.parse_number => text
+            .parse::<i64>()
+            .map(Value::from)
+            .ok()
+            .or_else(|| {
+                text.parse::<f64>()
+                    .ok()
+                    .filter(|number| number.is_finite())
+                    .map(Value::from_f64_or_zero)
+            })
+            .unwrap_or_else(|| text.into()),
+        _ => text.into(),
+    }
+}
+
+/// Reads `value` as a stream of events, only building the elements matching one of `paths`. The
+/// contents of a matching element are captured as a whole, without matching the paths against its
+/// descendants.
+fn parse_xml_nodes(value: Bytes, paths: Vec<String>, options: &Options) -> Resolved {
+    let parsed = paths
+        .iter()
+        .map(|path| XmlPath::parse(path))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut matches = vec![Vec::new(); parsed.len()];
+
+    let mut reader = Reader::from_reader(value.as_ref());
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+
+    // The qualified and local names of the elements enclosing the current position.
+    let mut stack: Vec<(String, String)> = Vec::new();
+    // The elements being captured, and the index of the path the outermost one matched.
+    let mut captured: Vec<Node> = Vec::new();
+    let mut captured_path = 0;
+
+    loop {
+        buf.clear();
+        let event = reader
+            .read_event_into(&mut buf)
+            .map_err(|error| format!("unable to parse xml: {error}"))?;
+        let (start, end) = match &event {
+            Event::Start(element) => (Some(element), false),
+            Event::Empty(element) => (Some(element), true),
+            Event::End(_) => (None, true),
+            Event::Text(text) => {
+                if let Some(node) = captured.last_mut() {
+                    let text = text
+                        .unescape()
+                        .map_err(|error| format!("unable to parse xml: {error}"))?;
+                    node.text.push_str(&text);
+                }
+                (None, false)
+            }
+            Event::CData(data) => {
+                if let Some(node) = captured.last_mut() {
+                    node.text.push_str(&String::from_utf8_lossy(data));
+                }
+                (None, false)
+            }
+            Event::Eof => break,
+            _ => (None, false),
+        };
+
+        if let Some(element) = start {
+            stack.push((
+                String::from_utf8_lossy(element.name().as_ref()).into_owned(),
+                String::from_utf8_lossy(element.local_name().as_ref()).into_owned(),
+            ));
+            if captured.is_empty() {
+                if let Some(index) = parsed.iter().position(|path| path.matches(&stack)) {
+                    captured_path = index;
+                    captured.push(Node::new(element, options)?);
+                }
+            } else {
+                captured.push(Node::new(element, options)?);
+            }
+        }
+
+        if end {
+            stack.pop();
+            if let Some(node) = captured.pop() {
+                let name = node.name.clone();
+                let value = node.into_value(options);
+                match captured.last_mut() {
+                    Some(parent) => parent.add_child(name, value),
+                    None => matches[captured_path].push(value),
+                }
+            }
+        }
+    }
+
+    Ok(paths
+        .into_iter()
+        .map(KeyString::from)
+        .zip(matches.into_iter().map(Value::Array))
+        .collect::<ObjectMap>()
+        .into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseXmlNodes;
+
+impl Function for ParseXmlNodes {
+    fn identifier(&self) -> &'static str {
+        "parse_xml_nodes"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "paths",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "include_attr",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "attr_prefix",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "text_key",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "always_use_text_key",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "parse_bool",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "parse_null",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "parse_number",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Extract the items of a SOAP response",
+                source: r#"parse_xml_nodes!(s'<soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope"><soap:Body><GetItemsResponse><Item id="1"><Name>foo</Name></Item><Item id="2"><Name>bar</Name></Item></GetItemsResponse></soap:Body></soap:Envelope>', ["/Envelope/Body/GetItemsResponse/Item"])"#,
+                result: Ok(
+                    r#"{"/Envelope/Body/GetItemsResponse/Item": [{"@id": 1, "Name": "foo"}, {"@id": 2, "Name": "bar"}]}"#,
+                ),
+            },
+            Example {
+                title: "Extract elements at any depth",
+                source: r#"parse_xml_nodes!(s'<root><a><status>ok</status></a><b><status>failed</status></b></root>', ["//status", "/root/c"])"#,
+                result: Ok(r#"{"//status": ["ok", "failed"], "/root/c": []}"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        Ok(ParseXmlNodesFn {
+            value: arguments.required("value"),
+            paths: arguments.required("paths"),
+            include_attr: arguments.optional("include_attr"),
+            attr_prefix: arguments.optional("attr_prefix"),
+            text_key: arguments.optional("text_key"),
+            always_use_text_key: arguments.optional("always_use_text_key"),
+            parse_bool: arguments.optional("parse_bool"),
+            parse_null: arguments.optional("parse_null"),
+            parse_number: arguments.optional("parse_number"),
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseXmlNodesFn {
+    value: Box<dyn Expression>,
+    paths: Box<dyn Expression>,
+    include_attr: Option<Box<dyn Expression>>,
+    attr_prefix: Option<Box<dyn Expression>>,
+    text_key: Option<Box<dyn Expression>>,
+    always_use_text_key: Option<Box<dyn Expression>>,
+    parse_bool: Option<Box<dyn Expression>>,
+    parse_null: Option<Box<dyn Expression>>,
+    parse_number: Option<Box<dyn Expression>>,
+}
+
+fn resolve_boolean(
+    expression: &Option<Box<dyn Expression>>,
+    default: bool,
+    ctx: &mut Context,
+) -> Result<bool, ExpressionError> {
+    match expression {
+        Some(expression) => Ok(expression.resolve(ctx)?.try_boolean()?),
+        None => Ok(default),
+    }
+}
+
+fn resolve_string(
+    expression: &Option<Box<dyn Expression>>,
+    default: &str,
+    ctx: &mut Context,
+) -> Result<String, ExpressionError> {
+    match expression {
+        Some(expression) => Ok(expression
+            .resolve(ctx)?
+            .try_bytes_utf8_lossy()?
+            .into_owned()),
+        None => Ok(default.to_owned()),
+    }
+}
+
+impl FunctionExpression for ParseXmlNodesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+        let paths = self
+            .paths
+            .resolve(ctx)?
+            .try_array()?
+            .into_iter()
+            .map(|path| path.try_bytes_utf8_lossy().map(|path| path.into_owned()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let options = Options {
+            include_attr: resolve_boolean(&self.include_attr, true, ctx)?,
+            attr_prefix: resolve_string(&self.attr_prefix, "@", ctx)?,
+            text_key: resolve_string(&self.text_key, "text", ctx)?.into(),
+            always_use_text_key: resolve_boolean(&self.always_use_text_key, false, ctx)?,
+            parse_bool: resolve_boolean(&self.parse_bool, true, ctx)?,
+            parse_null: resolve_boolean(&self.parse_null, true, ctx)?,
+            parse_number: resolve_boolean(&self.parse_number, true, ctx)?,
+        };
+
+        parse_xml_nodes(value, paths, &options)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::object(Collection::from_unknown(Kind::array(Collection::any()))).fallible()
+    }
+}
@@ -0,0 +1,73 @@
+use std::cell::RefCell;
+
+use vrl::prelude::*;
+
+thread_local! {
+    // Programs run to completion on the thread they start on, so the code set by a program is
+    // taken on the same thread once it aborts.
+    static ABORT_REASON: RefCell<Option<Value>> = const { RefCell::new(None) };
+}
+
+/// Takes the reason code set by the last program run on this thread, if any.
+///
+/// The `remap` transform takes it before running a program, to discard codes set by other programs,
+/// and after the program aborts, to annotate the dropped event with it.
+pub fn take_abort_reason() -> Option<Value> {
+    ABORT_REASON.with_borrow_mut(Option::take)
+}
+
+fn set_abort_reason(code: Value) -> Resolved {
+    ABORT_REASON.with_borrow_mut(|reason| *reason = Some(code));
+    Ok(Value::Null)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SetAbortReason;
+
+impl Function for SetAbortReason {
+    fn identifier(&self) -> &'static str {
+        "set_abort_reason"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "code",
+            kind: kind::INTEGER | kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Set the reason code of an abort",
+            source: r#"set_abort_reason("rate_limited")"#,
+            result: Ok("null"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let code = arguments.required("code");
+        Ok(SetAbortReasonFn { code }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SetAbortReasonFn {
+    code: Box<dyn Expression>,
+}
+
+impl FunctionExpression for SetAbortReasonFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let code = self.code.resolve(ctx)?;
+        set_abort_reason(code)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::null().infallible().impure()
+    }
+}
@@ -0,0 +1,626 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use vrl::diagnostic::Label;
+use vrl::prelude::*;
+use vrl::value::KeyString;
+
+/// The keyspaces of a topology, shared by the programs of all its `remap` transforms, by name.
+///
+/// Programs are given the registry through the external context of the compiler, and look up the
+/// keyspaces they use when compiled.
+#[derive(Clone, Debug, Default)]
+pub struct Keyspaces {
+    stores: Arc<Mutex<BTreeMap<String, StateStore>>>,
+    /// Whether every keyspace resolves to an empty store, for programs only compiled to be type
+    /// checked.
+    placeholder: bool,
+}
+
+impl Keyspaces {
+    /// Returns a registry resolving every keyspace to an empty store, for programs only compiled to
+    /// be type checked.
+    pub fn placeholder() -> Self {
+        static PLACEHOLDER: OnceLock<Keyspaces> = OnceLock::new();
+        PLACEHOLDER
+            .get_or_init(|| Self {
+                stores: Arc::default(),
+                placeholder: true,
+            })
+            .clone()
+    }
+
+    /// Loads the given keyspaces, given as their name, maximum number of keys, and default time to
+    /// live. Keyspaces which were already loaded keep their entries, so that they survive
+    /// configuration reloads, while the keyspaces missing from `keyspaces` are dropped.
+    pub fn load<'a>(
+        &self,
+        keyspaces: impl IntoIterator<Item = (&'a str, usize, Option<Duration>)>,
+    ) {
+        let mut stores = self.stores.lock().expect("poisoned lock");
+        let mut loaded = BTreeMap::new();
+        for (name, max_keys, default_ttl) in keyspaces {
+            let store = stores.remove(name).unwrap_or_default();
+            {
+                let mut state = store.lock();
+                state.max_keys = Some(max_keys);
+                state.default_ttl = default_ttl;
+            }
+            loaded.insert(name.to_owned(), store);
+        }
+        *stores = loaded;
+    }
+
+    /// Returns the keyspace named `name`.
+    pub fn get(&self, name: &str) -> Option<StateStore> {
+        if self.placeholder {
+            return Some(StateStore::default());
+        }
+        self.stores
+            .lock()
+            .expect("poisoned lock")
+            .get(name)
+            .cloned()
+    }
+}
+
+impl PartialEq for Keyspaces {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.stores, &other.stores)
+    }
+}
+
+/// A key/value store shared by the runs of a program, made available to the `get_state` and
+/// `set_state` functions by the `remap` transform, or shared by all programs as a keyspace.
+#[derive(Clone, Debug, Default)]
+pub struct StateStore(Arc<Mutex<StateEntries>>);
+
+#[derive(Debug, Default)]
+struct StateEntries {
+    entries: HashMap<String, StateEntry>,
+    /// The keys by the order they were last set in, the oldest first.
+    order: BTreeMap<u64, String>,
+    next_sequence: u64,
+    max_keys: Option<usize>,
+    /// The time to live of the keys set without one.
+    default_ttl: Option<Duration>,
+    /// Whether the entries changed since they were last persisted.
+    dirty: bool,
+}
+
+#[derive(Debug)]
+struct StateEntry {
+    value: Value,
+    /// When the entry expires, in milliseconds since the Unix epoch.
+    expires_at_ms: Option<u64>,
+    /// The position of the entry in the order the keys were set in.
+    sequence: u64,
+}
+
+impl StateEntry {
+    fn is_expired(&self, now_ms: u64) -> bool {
+        self.expires_at_ms
+            .is_some_and(|expires_at_ms| expires_at_ms <= now_ms)
+    }
+}
+
+/// An entry as persisted. The entries are persisted in the order their keys were set in, so that
+/// the keys set the longest ago are still evicted first once restored.
+#[derive(Deserialize, Serialize)]
+struct PersistedEntry {
+    key: String,
+    value: PersistedValue,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at_ms: Option<u64>,
+}
+
+/// A value as persisted, tagged with its type so that it's restored with the same type, where
+/// plain JSON would restore timestamps and bytes as strings.
+///
+/// Regular expressions are persisted as their pattern, and restored as strings.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum PersistedValue {
+    /// Bytes which are valid UTF-8.
+    String(String),
+    Bytes(Vec<u8>),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Timestamp(DateTime<Utc>),
+    Object(BTreeMap<String, PersistedValue>),
+    Array(Vec<PersistedValue>),
+    Null,
+}
+
+impl From<&Value> for PersistedValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Bytes(bytes) => match std::str::from_utf8(bytes) {
+                Ok(string) => Self::String(string.to_owned()),
+                Err(_) => Self::Bytes(bytes.to_vec()),
+            },
+            Value::Regex(regex) => Self::String(regex.as_str().to_owned()),
+            Value::Integer(integer) => Self::Integer(*integer),
+            Value::Float(float) => Self::Float(float.into_inner()),
+            Value::Boolean(boolean) => Self::Boolean(*boolean),
+            Value::Timestamp(timestamp) => Self::Timestamp(*timestamp),
+            Value::Object(object) => Self::Object(
+                object
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.into()))
+                    .collect(),
+            ),
+            Value::Array(array) => Self::Array(array.iter().map(Into::into).collect()),
+            Value::Null => Self::Null,
+        }
+    }
+}
+
+impl From<PersistedValue> for Value {
+    fn from(value: PersistedValue) -> Self {
+        match value {
+            PersistedValue::String(string) => Value::Bytes(Bytes::from(string)),
+            PersistedValue::Bytes(bytes) => Value::Bytes(Bytes::from(bytes)),
+            PersistedValue::Integer(integer) => Value::Integer(integer),
+            PersistedValue::Float(float) => Value::from_f64_or_zero(float),
+            PersistedValue::Boolean(boolean) => Value::Boolean(boolean),
+            PersistedValue::Timestamp(timestamp) => Value::Timestamp(timestamp),
+            PersistedValue::Object(object) => Value::Object(
+                object
+                    .into_iter()
+                    .map(|(key, value)| (KeyString::from(key), value.into()))
+                    .collect(),
+            ),
+            PersistedValue::Array(array) => {
+                Value::Array(array.into_iter().map(Into::into).collect())
+            }
+            PersistedValue::Null => Value::Null,
+        }
+    }
+}
+
+impl StateEntries {
+    fn insert(&mut self, key: String, mut entry: StateEntry) {
+        entry.sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.order.insert(entry.sequence, key.clone());
+        if let Some(previous) = self.entries.insert(key, entry) {
+            self.order.remove(&previous.sequence);
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.order.remove(&entry.sequence);
+        }
+    }
+
+    fn remove_expired(&mut self, now_ms: u64) {
+        let order = &mut self.order;
+        self.entries.retain(|_, entry| {
+            let expired = entry.is_expired(now_ms);
+            if expired {
+                order.remove(&entry.sequence);
+            }
+            !expired
+        });
+    }
+
+    /// Makes room for a new key, removing the expired keys, and then the keys set the longest ago,
+    /// returning whether there's room for it.
+    fn make_room(&mut self, max_keys: usize, now_ms: u64) -> bool {
+        if self.entries.len() < max_keys {
+            return true;
+        }
+        self.remove_expired(now_ms);
+        while self.entries.len() >= max_keys {
+            match self.order.pop_first() {
+                Some((_, key)) => {
+                    self.entries.remove(&key);
+                }
+                None => return false,
+            }
+        }
+        true
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, duration_ms)
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    duration.as_millis().try_into().unwrap_or(u64::MAX)
+}
+
+impl StateStore {
+    /// Creates an empty store holding up to `max_keys` keys.
+    pub fn new(max_keys: usize) -> Self {
+        Self(Arc::new(Mutex::new(StateEntries {
+            max_keys: Some(max_keys),
+            ..Default::default()
+        })))
+    }
+
+    /// Creates a store holding up to `max_keys` keys, restoring the entries persisted in the file
+    /// at `path` if it exists.
+    pub fn load(path: &Path, max_keys: usize) -> io::Result<Self> {
+        let store = Self::new(max_keys);
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(store),
+            Err(error) => return Err(error),
+        };
+        let entries: Vec<PersistedEntry> = serde_json::from_reader(BufReader::new(file))?;
+        let now_ms = now_ms();
+        let mut state = store.lock();
+        // The entries are restored in the order they were set in, so that once the maximum number
+        // of keys is reached, the keys set the longest ago are the ones left out.
+        for PersistedEntry {
+            key,
+            value,
+            expires_at_ms,
+        } in entries
+        {
+            let entry = StateEntry {
+                value: value.into(),
+                expires_at_ms,
+                sequence: 0,
+            };
+            if !entry.is_expired(now_ms) && state.make_room(max_keys, now_ms) {
+                state.insert(key, entry);
+            }
+        }
+        drop(state);
+        Ok(store)
+    }
+
+    /// Writes the entries to the file at `path` if they changed since they were last persisted.
+    ///
+    /// The entries are first written to a temporary file which then replaces the previous one, so
+    /// that the file is never left partially written.
+    pub fn persist(&self, path: &Path) -> io::Result<()> {
+        let mut state = self.lock();
+        if !state.dirty {
+            return Ok(());
+        }
+        state.remove_expired(now_ms());
+
+        let entries: Vec<_> = state
+            .order
+            .values()
+            .map(|key| {
+                let entry = &state.entries[key];
+                PersistedEntry {
+                    key: key.clone(),
+                    value: (&entry.value).into(),
+                    expires_at_ms: entry.expires_at_ms,
+                }
+            })
+            .collect();
+        let temporary = path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&temporary)?);
+        serde_json::to_writer(&mut writer, &entries)?;
+        writer
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)?;
+        fs::rename(&temporary, path)?;
+        state.dirty = false;
+        Ok(())
+    }
+
+    /// Returns the value of `key`, or `null` if it's missing or expired.
+    pub fn get(&self, key: &str) -> Value {
+        let state = self.lock();
+        match state.entries.get(key) {
+            Some(entry) if !entry.is_expired(now_ms()) => entry.value.clone(),
+            _ => Value::Null,
+        }
+    }
+
+    /// Sets `key` to `value`, expiring after `ttl` if given, or else after the default time to live
+    /// of the store. Setting a key to `null` removes it.
+    ///
+    /// Setting a new key once the store is full evicts the expired keys, or else the key set the
+    /// longest ago.
+    pub fn set(&self, key: String, value: Value, ttl: Option<Duration>) -> Result<(), String> {
+        let mut state = self.lock();
+        state.dirty = true;
+        if value.is_null() {
+            state.remove(&key);
+            return Ok(());
+        }
+
+        let now_ms = now_ms();
+        if let Some(max_keys) = state.max_keys {
+            if !state.entries.contains_key(&key) && !state.make_room(max_keys, now_ms) {
+                return Err("the state can't hold any keys".to_owned());
+            }
+        }
+        let expires_at_ms = ttl
+            .or(state.default_ttl)
+            .map(|ttl| now_ms.saturating_add(duration_ms(ttl)));
+        state.insert(
+            key,
+            StateEntry {
+                value,
+                expires_at_ms,
+                sequence: 0,
+            },
+        );
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StateEntries> {
+        self.0.lock().expect("poisoned lock")
+    }
+}
+
+/// Returns the keyspace named by the `keyspace` argument if given, or else the state store of the
+/// transform, failing to compile if there's no such store.
+pub(crate) fn state_store(
+    arguments: &ArgumentList,
+    state: &TypeState,
+    ctx: &FunctionCompileContext,
+) -> Result<StateStore, Box<dyn DiagnosticMessage>> {
+    let compile_error = |message: String, label: &str| {
+        Box::new(ExpressionError::Error {
+            message,
+            labels: vec![Label::primary(label, ctx.span())],
+            notes: vec![],
+        }) as Box<dyn DiagnosticMessage>
+    };
+
+    match arguments.optional_literal("keyspace", state)? {
+        Some(name) => {
+            let name = name
+                .try_bytes_utf8_lossy()
+                .map_err(|error| compile_error(error.to_string(), "invalid keyspace"))?;
+            let keyspaces = ctx.get_external_context::<Keyspaces>().ok_or_else(|| {
+                compile_error(
+                    "keyspaces are not available".to_owned(),
+                    "keyspaces are only available in `remap` transforms",
+                )
+            })?;
+            keyspaces.get(&name).ok_or_else(|| {
+                compile_error(
+                    format!("unknown keyspace {name:?}"),
+                    "keyspaces must be configured in the `keyspaces` section",
+                )
+            })
+        }
+        None => ctx
+            .get_external_context::<StateStore>()
+            .cloned()
+            .ok_or_else(|| {
+                compile_error(
+                    "state is not enabled".to_owned(),
+                    "state is only available in `remap` transforms configured with `state`",
+                )
+            }),
+    }
+}
+
+fn set_state(store: &StateStore, key: Value, value: Value, ttl_secs: Option<Value>) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?.into_owned();
+    let ttl = match ttl_secs {
+        Some(ttl_secs) => {
+            let ttl_secs = ttl_secs.try_integer()?;
+            let ttl_secs = u64::try_from(ttl_secs)
+                .map_err(|_| format!("`ttl_secs` must not be negative, got {ttl_secs}"))?;
+            Some(Duration::from_secs(ttl_secs))
+        }
+        None => None,
+    };
+    store.set(key, value, ttl)?;
+    Ok(Value::Null)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SetState;
+
+impl Function for SetState {
+    fn identifier(&self) -> &'static str {
+        "set_state"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "ttl_secs",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "keyspace",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Count events",
+            source: r#"set_state!("events", (int(get_state("events")) ?? 0) + 1)"#,
+            result: Ok("null"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let store = state_store(&arguments, state, ctx)?;
+        let key = arguments.required("key");
+        let value = arguments.required("value");
+        let ttl_secs = arguments.optional("ttl_secs");
+        Ok(SetStateFn {
+            store,
+            key,
+            value,
+            ttl_secs,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SetStateFn {
+    store: StateStore,
+    key: Box<dyn Expression>,
+    value: Box<dyn Expression>,
+    ttl_secs: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for SetStateFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let value = self.value.resolve(ctx)?;
+        let ttl_secs = self
+            .ttl_secs
+            .as_ref()
+            .map(|ttl_secs| ttl_secs.resolve(ctx))
+            .transpose()?;
+        set_state(&self.store, key, value, ttl_secs)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::null().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(store: &StateStore, key: &str, ttl: Option<Duration>) {
+        store.set(key.to_owned(), Value::from(key), ttl).unwrap();
+    }
+
+    #[test]
+    fn evicts_the_key_set_the_longest_ago() {
+        let store = StateStore::new(2);
+        set(&store, "a", None);
+        set(&store, "b", None);
+        // Setting `a` again makes `b` the key set the longest ago.
+        set(&store, "a", None);
+        set(&store, "c", None);
+
+        assert_eq!(store.get("a"), Value::from("a"));
+        assert_eq!(store.get("b"), Value::Null);
+        assert_eq!(store.get("c"), Value::from("c"));
+    }
+
+    #[test]
+    fn evicts_expired_keys_first() {
+        let store = StateStore::new(2);
+        set(&store, "a", None);
+        set(&store, "b", Some(Duration::ZERO));
+        set(&store, "c", None);
+
+        assert_eq!(store.get("a"), Value::from("a"));
+        assert_eq!(store.get("c"), Value::from("c"));
+        assert_eq!(store.lock().entries.len(), 2);
+    }
+
+    #[test]
+    fn keeps_subsecond_ttls() {
+        let store = StateStore::new(1);
+        let before = now_ms();
+        set(&store, "a", Some(Duration::from_millis(1500)));
+
+        let expires_at_ms = store.lock().entries["a"].expires_at_ms.unwrap();
+        assert!(expires_at_ms >= before + 1500);
+        assert!(expires_at_ms < before + 2000);
+    }
+
+    #[test]
+    fn restores_persisted_entries_in_order_with_their_types() {
+        let path = std::env::temp_dir().join(format!("vector-state-{}.json", std::process::id()));
+        let timestamp = DateTime::from_timestamp_nanos(1_700_000_000_123_456_789);
+        let store = StateStore::new(3);
+        set(&store, "a", None);
+        store
+            .set("b".to_owned(), Value::Timestamp(timestamp), None)
+            .unwrap();
+        store
+            .set(
+                "c".to_owned(),
+                Value::from(Bytes::from_static(&[0xff, 0])),
+                None,
+            )
+            .unwrap();
+        // Setting `a` again makes `b` the key set the longest ago.
+        set(&store, "a", None);
+        store.persist(&path).unwrap();
+
+        let restored = StateStore::load(&path, 3).unwrap();
+        assert_eq!(restored.get("b"), Value::Timestamp(timestamp));
+        assert_eq!(
+            restored.get("c"),
+            Value::from(Bytes::from_static(&[0xff, 0]))
+        );
+
+        // Once full, the keys set the longest ago are left out.
+        let restored = StateStore::load(&path, 2).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(restored.get("a"), Value::from("a"));
+        assert_eq!(restored.get("b"), Value::Null);
+        assert_eq!(
+            restored.get("c"),
+            Value::from(Bytes::from_static(&[0xff, 0]))
+        );
+    }
+
+    #[test]
+    fn empty_state_rejects_keys() {
+        let store = StateStore::new(0);
+        assert!(store.set("a".to_owned(), Value::from(1), None).is_err());
+    }
+
+    #[test]
+    fn loading_keyspaces_drops_removed_ones() {
+        let keyspaces = Keyspaces::default();
+        keyspaces.load([("kept", 10, None), ("removed", 10, None)]);
+        set(&keyspaces.get("kept").unwrap(), "a", None);
+
+        keyspaces.load([("kept", 1, None), ("added", 10, None)]);
+        let kept = keyspaces.get("kept").unwrap();
+        assert_eq!(kept.get("a"), Value::from("a"));
+        // The new maximum number of keys applies to the kept entries.
+        set(&kept, "b", None);
+        assert_eq!(kept.get("a"), Value::Null);
+        assert!(keyspaces.get("added").is_some());
+        assert!(keyspaces.get("removed").is_none());
+    }
+
+    #[test]
+    fn placeholder_keyspaces_resolve_every_name() {
+        let placeholder = Keyspaces::placeholder();
+        assert!(placeholder.get("anything").is_some());
+        assert!(placeholder == Keyspaces::placeholder());
+        assert!(placeholder != Keyspaces::default());
+    }
+}
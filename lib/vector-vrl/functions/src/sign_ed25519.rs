@@ -0,0 +1,77 @@
+use ring::signature::Ed25519KeyPair;
+use vrl::prelude::*;
+
+fn sign_ed25519(value: Value, private_key: Value) -> Resolved {
+    let private_key = private_key.try_bytes()?;
+    let key_pair = Ed25519KeyPair::from_seed_unchecked(&private_key).map_err(|_| {
+        format!(
+            "invalid private key length: expected 32 bytes, got {}",
+            private_key.len()
+        )
+    })?;
+    let signature = key_pair.sign(&value.try_bytes()?);
+    Ok(Value::Bytes(Bytes::copy_from_slice(signature.as_ref())))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SignEd25519;
+
+impl Function for SignEd25519 {
+    fn identifier(&self) -> &'static str {
+        "sign_ed25519"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "private_key",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Sign a message",
+            source: r#"encode_base64(sign_ed25519!("Hello there", private_key: "00000000000000000000000000000000"))"#,
+            result: Ok(
+                "+hDVC8ZIPy/c3JD+EkBFs3q9xkXOyVSodcqoxW417TCIhL/h7W61+XRjYDZSI9YzqaJQ/dnoDIAx76O0UCNPBQ==",
+            ),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let private_key = arguments.required("private_key");
+        Ok(SignEd25519Fn { value, private_key }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SignEd25519Fn {
+    value: Box<dyn Expression>,
+    private_key: Box<dyn Expression>,
+}
+
+impl FunctionExpression for SignEd25519Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let private_key = self.private_key.resolve(ctx)?;
+        sign_ed25519(value, private_key)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
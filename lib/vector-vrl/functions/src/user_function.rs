@@ -0,0 +1,420 @@
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use vrl::compiler::runtime::{Runtime, Terminate};
+use vrl::compiler::state::ExternalEnv;
+use vrl::compiler::{compile_with_state, CompileConfig, Program, TargetValue};
+use vrl::diagnostic::{Formatter, Label};
+use vrl::prelude::*;
+use vrl::value::kind::{Collection, Field};
+use vrl::value::{ObjectMap, Secrets};
+
+use crate::set_state::{Keyspaces, StateStore};
+
+/// A function defined by the configuration of a `remap` transform, with a VRL program as its body.
+///
+/// The arguments of a call are the fields of the target of the body, such as `.name` for the
+/// `name` parameter, which keeps the event being processed out of reach of the body. The body is
+/// compiled at every call site with the kinds of the arguments given there, which gives each call
+/// its own return kind and fallibility.
+#[derive(Clone, Debug)]
+pub struct UserFunction {
+    identifier: &'static str,
+    parameters: &'static [Parameter],
+    source: Arc<str>,
+    /// The functions the body can call, which don't include the function itself.
+    functions: Arc<Vec<Box<dyn Function>>>,
+    max_depth: Option<usize>,
+}
+
+thread_local! {
+    // The number of bodies of user functions being compiled, as they're compiled at every call.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Leaves the body of a function being compiled when dropped.
+struct DepthGuard;
+
+impl DepthGuard {
+    fn enter() -> (Self, usize) {
+        let depth = DEPTH.get() + 1;
+        DEPTH.set(depth);
+        (Self, depth)
+    }
+}
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        DEPTH.set(DEPTH.get() - 1);
+    }
+}
+
+impl UserFunction {
+    pub fn new(
+        name: &str,
+        parameters: &[String],
+        source: String,
+        functions: Vec<Box<dyn Function>>,
+    ) -> Self {
+        let (identifier, parameters) = intern(name, parameters);
+        Self {
+            identifier,
+            parameters,
+            source: source.into(),
+            functions: Arc::new(functions),
+            max_depth: None,
+        }
+    }
+
+    /// Limits the depth of the calls nested in the body of the function, counting the call of the
+    /// function itself.
+    ///
+    /// Calls are nested as deep at runtime as they are in the program, so deeper calls are
+    /// rejected when the program is compiled.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+}
+
+/// Returns the static identifier and parameters VRL requires of functions.
+///
+/// They're interned rather than leaked, as the programs of a transform are compiled again whenever
+/// the configuration is reloaded.
+fn intern(name: &str, parameters: &[String]) -> (&'static str, &'static [Parameter]) {
+    type Interned = HashMap<(String, Vec<String>), (&'static str, &'static [Parameter])>;
+    static INTERNED: OnceLock<Mutex<Interned>> = OnceLock::new();
+
+    let mut interned = INTERNED
+        .get_or_init(Default::default)
+        .lock()
+        .expect("poisoned lock");
+    *interned
+        .entry((name.to_owned(), parameters.to_vec()))
+        .or_insert_with(|| {
+            let parameters = parameters
+                .iter()
+                .map(|keyword| Parameter {
+                    keyword: Box::leak(keyword.clone().into_boxed_str()),
+                    kind: kind::ANY,
+                    required: true,
+                })
+                .collect::<Vec<_>>();
+            (
+                Box::leak(name.to_owned().into_boxed_str()),
+                Box::leak(parameters.into_boxed_slice()),
+            )
+        })
+}
+
+impl Function for UserFunction {
+    fn identifier(&self) -> &'static str {
+        self.identifier
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        self.parameters
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let (_guard, depth) = DepthGuard::enter();
+        if let Some(max_depth) = self.max_depth.filter(|max_depth| depth > *max_depth) {
+            return Err(Box::new(ExpressionError::Error {
+                message: format!(
+                    "calling function {:?} exceeds the maximum depth of {max_depth} nested calls",
+                    self.identifier
+                ),
+                labels: vec![Label::primary("call too deep", ctx.span())],
+                notes: vec![],
+            }) as Box<dyn DiagnosticMessage>);
+        }
+
+        let arguments = self
+            .parameters
+            .iter()
+            .map(|parameter| (parameter.keyword, arguments.required(parameter.keyword)))
+            .collect::<Vec<_>>();
+        let kinds = arguments
+            .iter()
+            .map(|(keyword, argument)| {
+                (
+                    Field::from(*keyword),
+                    argument.type_def(state).kind().clone(),
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+        let body_state = TypeState {
+            local: Default::default(),
+            external: ExternalEnv::new_with_kind(
+                Kind::object(kinds),
+                Kind::object(Collection::empty()),
+            ),
+        };
+
+        // The body shares the state of the program calling it.
+        let mut config = CompileConfig::default();
+        if let Some(store) = ctx.get_external_context::<StateStore>() {
+            config.set_custom(store.clone());
+        }
+        if let Some(keyspaces) = ctx.get_external_context::<Keyspaces>() {
+            config.set_custom(keyspaces.clone());
+        }
+
+        let program = compile_with_state(&self.source, &self.functions, &body_state, config)
+            .map_err(|diagnostics| {
+                Box::new(ExpressionError::Error {
+                    message: format!(
+                        "function {:?} doesn't compile with these arguments:\n{}",
+                        self.identifier,
+                        Formatter::new(&self.source, diagnostics)
+                    ),
+                    labels: vec![Label::primary("invalid call", ctx.span())],
+                    notes: vec![],
+                }) as Box<dyn DiagnosticMessage>
+            })?
+            .program;
+
+        let result = program.final_type_info().result;
+        let type_def = if program.info().fallible || program.info().abortable {
+            result.fallible()
+        } else {
+            result.infallible()
+        };
+
+        Ok(UserFunctionFn {
+            identifier: self.identifier,
+            arguments,
+            program: Arc::new(program),
+            type_def,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UserFunctionFn {
+    identifier: &'static str,
+    arguments: Vec<(&'static str, Box<dyn Expression>)>,
+    program: Arc<Program>,
+    type_def: TypeDef,
+}
+
+impl FunctionExpression for UserFunctionFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let mut arguments = ObjectMap::new();
+        for (keyword, argument) in &self.arguments {
+            arguments.insert((*keyword).into(), argument.resolve(ctx)?);
+        }
+        let mut target = TargetValue {
+            value: Value::Object(arguments),
+            metadata: Value::Object(ObjectMap::new()),
+            secrets: Secrets::new(),
+        };
+
+        match Runtime::default().resolve(&mut target, &self.program, ctx.timezone()) {
+            Ok(value) => Ok(value),
+            Err(Terminate::Error(error)) => Err(error.to_string().into()),
+            // Aborting is left to the calling program, which only sees an error.
+            Err(Terminate::Abort(_)) => {
+                Err(format!("function {:?} aborted", self.identifier).into())
+            }
+        }
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        self.type_def.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vrl::compiler::TimeZone;
+    use vrl::value;
+
+    use super::*;
+
+    fn compile(source: &str, functions: Vec<Box<dyn Function>>) -> Program {
+        let mut all = vrl::stdlib::all();
+        all.extend(functions);
+        compile_with_state(
+            source,
+            &all,
+            &TypeState::default(),
+            CompileConfig::default(),
+        )
+        .map_err(|diagnostics| Formatter::new(source, diagnostics).to_string())
+        .unwrap()
+        .program
+    }
+
+    fn run(program: &Program, event: Value) -> std::result::Result<Value, Terminate> {
+        let mut target = TargetValue {
+            value: event,
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        };
+        Runtime::default().resolve(&mut target, program, &TimeZone::default())
+    }
+
+    fn greet() -> Box<dyn Function> {
+        Box::new(UserFunction::new(
+            "greet",
+            &["greeting".to_owned(), "name".to_owned()],
+            r#"join!([.greeting, .name], ", ")"#.to_owned(),
+            vrl::stdlib::all(),
+        ))
+    }
+
+    #[test]
+    fn calls_function_with_arguments() {
+        let program = compile(r#".message = greet("hello", .name)"#, vec![greet()]);
+
+        let mut target = TargetValue {
+            value: value!({ "name": "vector" }),
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        };
+        Runtime::default()
+            .resolve(&mut target, &program, &TimeZone::default())
+            .unwrap();
+        assert_eq!(
+            target.value,
+            value!({ "name": "vector", "message": "hello, vector" })
+        );
+    }
+
+    #[test]
+    fn infers_return_kind_of_each_call() {
+        let double = Box::new(UserFunction::new(
+            "double",
+            &["value".to_owned()],
+            "[.value, .value]".to_owned(),
+            vrl::stdlib::all(),
+        ));
+        let program = compile(
+            r#"
+                integers = double(1)
+                strings = double("a")
+                # Only type checks if the kinds of the elements are known at each call.
+                [integers[0] + 1, upcase(strings[1])]
+            "#,
+            vec![double],
+        );
+
+        assert_eq!(run(&program, value!({})), Ok(value!([2, "A"])));
+    }
+
+    #[test]
+    fn infers_fallibility_of_each_call() {
+        let square = || -> Box<dyn Function> {
+            Box::new(UserFunction::new(
+                "square",
+                &["value".to_owned()],
+                "to_int!(.value) * to_int!(.value)".to_owned(),
+                vrl::stdlib::all(),
+            ))
+        };
+
+        // The body fails at runtime if the argument isn't a number, so calls must handle errors.
+        let mut all = vrl::stdlib::all();
+        all.push(square());
+        assert!(compile_with_state(
+            "square(.value)",
+            &all,
+            &TypeState::default(),
+            CompileConfig::default()
+        )
+        .is_err());
+
+        let program = compile("square(.value) ?? 0", vec![square()]);
+        assert_eq!(run(&program, value!({ "value": 3 })), Ok(value!(9)));
+        assert_eq!(run(&program, value!({ "value": "three" })), Ok(value!(0)));
+    }
+
+    #[test]
+    fn calls_other_functions() {
+        let shout = Box::new(UserFunction::new(
+            "shout",
+            &["name".to_owned()],
+            r#"upcase(greet("hello", .name))"#.to_owned(),
+            {
+                let mut functions = vrl::stdlib::all();
+                functions.push(greet());
+                functions
+            },
+        ));
+        let program = compile(r#"shout("vector")"#, vec![greet(), shout]);
+
+        assert_eq!(run(&program, value!({})), Ok(value!("HELLO, VECTOR")));
+    }
+
+    #[test]
+    fn limits_depth_of_nested_calls() {
+        let inner = UserFunction::new(
+            "inner",
+            &["value".to_owned()],
+            "upcase(.value)".to_owned(),
+            vrl::stdlib::all(),
+        )
+        .with_max_depth(1);
+        let outer = UserFunction::new(
+            "outer",
+            &["value".to_owned()],
+            "inner(.value)".to_owned(),
+            {
+                let mut functions = vrl::stdlib::all();
+                functions.push(Box::new(inner.clone()));
+                functions
+            },
+        )
+        .with_max_depth(1);
+
+        let mut all = vrl::stdlib::all();
+        all.push(Box::new(inner));
+        all.push(Box::new(outer));
+        let compile = |source| {
+            compile_with_state(
+                source,
+                &all,
+                &TypeState::default(),
+                CompileConfig::default(),
+            )
+        };
+
+        assert!(compile(r#"inner("a")"#).is_ok());
+        assert!(compile(r#"outer("a")"#).is_err());
+        // The depth is back to zero after a failed compilation.
+        assert!(compile(r#"inner("a")"#).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_bodies_at_call_sites() {
+        let broken = Box::new(UserFunction::new(
+            "broken",
+            &["value".to_owned()],
+            "upcase(.value)".to_owned(),
+            vrl::stdlib::all(),
+        ));
+        let mut all = vrl::stdlib::all();
+        all.push(broken);
+
+        // `upcase` requires a string.
+        assert!(compile_with_state(
+            "broken(1)",
+            &all,
+            &TypeState::default(),
+            CompileConfig::default()
+        )
+        .is_err());
+    }
+}
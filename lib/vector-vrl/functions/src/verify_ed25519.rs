@@ -0,0 +1,101 @@
+use ring::signature::{UnparsedPublicKey, ED25519};
+use vrl::prelude::*;
+
+const PUBLIC_KEY_LEN: usize = 32;
+
+fn verify_ed25519(value: Value, signature: Value, public_key: Value) -> Resolved {
+    let public_key = public_key.try_bytes()?;
+    if public_key.len() != PUBLIC_KEY_LEN {
+        return Err(format!(
+            "invalid public key length: expected {PUBLIC_KEY_LEN} bytes, got {}",
+            public_key.len()
+        )
+        .into());
+    }
+
+    let verified = UnparsedPublicKey::new(&ED25519, &public_key)
+        .verify(&value.try_bytes()?, &signature.try_bytes()?)
+        .is_ok();
+    Ok(verified.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct VerifyEd25519;
+
+impl Function for VerifyEd25519 {
+    fn identifier(&self) -> &'static str {
+        "verify_ed25519"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "signature",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "public_key",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Verify a valid signature",
+                source: r#"verify_ed25519!("Hello there", signature: decode_base64!("+hDVC8ZIPy/c3JD+EkBFs3q9xkXOyVSodcqoxW417TCIhL/h7W61+XRjYDZSI9YzqaJQ/dnoDIAx76O0UCNPBQ=="), public_key: decode_base64!("G6QHW3fJ4/s+zeFc2vUiHzwQNz5iP3sOHvdjZrCvcTc="))"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "Verify the signature of a tampered message",
+                source: r#"verify_ed25519!("Hello where", signature: decode_base64!("+hDVC8ZIPy/c3JD+EkBFs3q9xkXOyVSodcqoxW417TCIhL/h7W61+XRjYDZSI9YzqaJQ/dnoDIAx76O0UCNPBQ=="), public_key: decode_base64!("G6QHW3fJ4/s+zeFc2vUiHzwQNz5iP3sOHvdjZrCvcTc="))"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let signature = arguments.required("signature");
+        let public_key = arguments.required("public_key");
+        Ok(VerifyEd25519Fn {
+            value,
+            signature,
+            public_key,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct VerifyEd25519Fn {
+    value: Box<dyn Expression>,
+    signature: Box<dyn Expression>,
+    public_key: Box<dyn Expression>,
+}
+
+impl FunctionExpression for VerifyEd25519Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let signature = self.signature.resolve(ctx)?;
+        let public_key = self.public_key.resolve(ctx)?;
+        verify_ed25519(value, signature, public_key)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
@@ -0,0 +1,115 @@
+use ring::hmac;
+use vrl::prelude::*;
+
+fn verify_hmac(value: Value, signature: Value, key: Value, algorithm: Option<Value>) -> Resolved {
+    let algorithm = match algorithm {
+        Some(algorithm) => algorithm.try_bytes_utf8_lossy()?.to_uppercase(),
+        None => "SHA-256".to_owned(),
+    };
+    let algorithm = match algorithm.as_str() {
+        "SHA1" => hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
+        "SHA-256" => hmac::HMAC_SHA256,
+        "SHA-384" => hmac::HMAC_SHA384,
+        "SHA-512" => hmac::HMAC_SHA512,
+        other => return Err(format!("unsupported algorithm: {other}").into()),
+    };
+
+    // The comparison of the signatures takes constant time, to not leak the expected signature.
+    let key = hmac::Key::new(algorithm, &key.try_bytes()?);
+    let verified = hmac::verify(&key, &value.try_bytes()?, &signature.try_bytes()?).is_ok();
+    Ok(verified.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct VerifyHmac;
+
+impl Function for VerifyHmac {
+    fn identifier(&self) -> &'static str {
+        "verify_hmac"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "signature",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Verify the signature of a webhook payload",
+                source: r#"verify_hmac!("Hello there", signature: decode_base16!("78b184f1832f8aff3934f5e0212454671b2d04d494e3b25075c5e45167029662"), key: "super-secret-key")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "Verify a signature made with another key",
+                source: r#"verify_hmac!("Hello there", signature: decode_base16!("78b184f1832f8aff3934f5e0212454671b2d04d494e3b25075c5e45167029662"), key: "another-key", algorithm: "SHA-256")"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let signature = arguments.required("signature");
+        let key = arguments.required("key");
+        let algorithm = arguments.optional("algorithm");
+        Ok(VerifyHmacFn {
+            value,
+            signature,
+            key,
+            algorithm,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct VerifyHmacFn {
+    value: Box<dyn Expression>,
+    signature: Box<dyn Expression>,
+    key: Box<dyn Expression>,
+    algorithm: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for VerifyHmacFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let signature = self.signature.resolve(ctx)?;
+        let key = self.key.resolve(ctx)?;
+        let algorithm = self
+            .algorithm
+            .as_ref()
+            .map(|algorithm| algorithm.resolve(ctx))
+            .transpose()?;
+        verify_hmac(value, signature, key, algorithm)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
@@ -21,6 +21,9 @@ const SKIP_FUNCTION_EXAMPLES: &[&str] = &[
     "get_hostname",
     "now",
     "get_env_var",
+    "http_request",
+    "cached_dns_lookup",
+    "cached_reverse_dns",
 ];
 
 #[derive(Debug, Deserialize)]
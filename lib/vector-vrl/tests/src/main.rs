@@ -107,6 +107,7 @@ fn main() {
             let mut config = CompileConfig::default();
             let enrichment_table = test_enrichment::test_enrichment_table();
             config.set_custom(enrichment_table.clone());
+            config.set_custom(vector_vrl_functions::set_state::StateStore::default());
             (config, enrichment_table)
         },
         |registry| registry.finish_load(),
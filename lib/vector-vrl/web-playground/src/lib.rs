@@ -76,34 +76,55 @@ impl VrlDiagnosticResult {
     }
 }
 
-fn compile(mut input: Input) -> Result<VrlCompileResult, VrlDiagnosticResult> {
-    let mut functions = vrl::stdlib::all();
-    functions.extend(vector_vrl_functions::all());
-    functions.extend(enrichment::vrl_functions());
-
-    let event = &mut input.event;
-    let state = TypeState::default();
-    let mut runtime = Runtime::default();
-    let config = CompileConfig::default();
-    let timezone = TimeZone::default();
-
-    let mut target_value = TargetValue {
-        value: event.clone(),
-        metadata: Value::Object(BTreeMap::new()),
-        secrets: Secrets::new(),
-    };
-
-    let program = match compile_with_state(&input.program, &functions, &state, config) {
-        Ok(program) => program,
-        Err(diagnostics) => return Err(VrlDiagnosticResult::new(&input.program, diagnostics)),
-    };
-
-    match runtime.resolve(&mut target_value, &program.program, &timezone) {
-        Ok(result) => Ok(VrlCompileResult::new(result, target_value.value)),
-        Err(err) => Err(VrlDiagnosticResult::new_runtime_error(&input.program, err)),
+/// A VRL program compiled with the functions available in Vector, which can be run against any
+/// number of events.
+pub struct Program {
+    source: String,
+    program: vrl::compiler::Program,
+}
+
+impl Program {
+    /// Compiles the VRL program, returning the diagnostics of the compilation if it fails.
+    pub fn compile(source: &str) -> Result<Self, VrlDiagnosticResult> {
+        let mut functions = vrl::stdlib::all();
+        functions.extend(vector_vrl_functions::all());
+        functions.extend(enrichment::vrl_functions());
+
+        let state = TypeState::default();
+        let config = CompileConfig::default();
+
+        match compile_with_state(source, &functions, &state, config) {
+            Ok(program) => Ok(Self {
+                source: source.to_owned(),
+                program: program.program,
+            }),
+            Err(diagnostics) => Err(VrlDiagnosticResult::new(source, diagnostics)),
+        }
+    }
+
+    /// Runs the program against the event, returning the result of the last expression along with
+    /// the modified event.
+    pub fn run(&self, event: Value) -> Result<VrlCompileResult, VrlDiagnosticResult> {
+        let mut runtime = Runtime::default();
+        let timezone = TimeZone::default();
+
+        let mut target_value = TargetValue {
+            value: event,
+            metadata: Value::Object(BTreeMap::new()),
+            secrets: Secrets::new(),
+        };
+
+        match runtime.resolve(&mut target_value, &self.program, &timezone) {
+            Ok(result) => Ok(VrlCompileResult::new(result, target_value.value)),
+            Err(err) => Err(VrlDiagnosticResult::new_runtime_error(&self.source, err)),
+        }
     }
 }
 
+fn compile(input: Input) -> Result<VrlCompileResult, VrlDiagnosticResult> {
+    Program::compile(&input.program)?.run(input.event)
+}
+
 // The user-facing function
 #[wasm_bindgen]
 pub fn run_vrl(incoming: &JsValue) -> JsValue {
@@ -115,6 +136,32 @@ pub fn run_vrl(incoming: &JsValue) -> JsValue {
     }
 }
 
+/// A compiled VRL program, for running the same program against many events without compiling it
+/// each time, as in edge workers.
+#[wasm_bindgen]
+pub struct VrlProgram(Program);
+
+#[wasm_bindgen]
+impl VrlProgram {
+    /// Compiles the program, throwing the diagnostics of the compilation if it fails.
+    #[wasm_bindgen(constructor)]
+    pub fn new(program: &str) -> Result<VrlProgram, JsValue> {
+        Program::compile(program)
+            .map(Self)
+            .map_err(|err| JsValue::from_serde(&err).unwrap())
+    }
+
+    /// Runs the program against the event, returning the same result as `run_vrl`.
+    pub fn run(&self, event: &JsValue) -> JsValue {
+        let event: Value = event.into_serde().unwrap();
+
+        match self.0.run(event) {
+            Ok(res) => JsValue::from_serde(&res).unwrap(),
+            Err(err) => JsValue::from_serde(&err).unwrap(),
+        }
+    }
+}
+
 #[wasm_bindgen]
 pub fn vector_version() -> String {
     built_info::VECTOR_VERSION.to_string()
@@ -133,3 +180,37 @@ pub fn vrl_version() -> String {
 pub fn vrl_link() -> String {
     built_info::VRL_LINK.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use vrl::value;
+
+    use super::*;
+
+    #[test]
+    fn runs_compiled_programs_against_events() {
+        let program = Program::compile(r#".message = upcase!(.message); "done""#).unwrap();
+
+        let result = program.run(value!({"message": "hello"})).unwrap();
+        assert_eq!(result.output, value!("done"));
+        assert_eq!(result.result, value!({"message": "HELLO"}));
+
+        let result = program.run(value!({"message": "again"})).unwrap();
+        assert_eq!(result.result, value!({"message": "AGAIN"}));
+    }
+
+    #[test]
+    fn reports_compilation_errors() {
+        let err = Program::compile("upcase(").err().unwrap();
+        assert!(!err.list.is_empty());
+        assert!(!err.msg.is_empty());
+    }
+
+    #[test]
+    fn reports_runtime_errors() {
+        let program = Program::compile(r#"abort "stop""#).unwrap();
+
+        let err = program.run(value!({})).err().unwrap();
+        assert!(!err.msg.is_empty());
+    }
+}
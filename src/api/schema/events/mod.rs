@@ -11,50 +11,119 @@ use output::{from_tap_payload_to_output_events, OutputEventsPayload};
 use rand::{rngs::SmallRng, Rng, SeedableRng};
 use tokio::{select, sync::mpsc, time};
 use tokio_stream::wrappers::ReceiverStream;
-use vector_lib::tap::{
-    controller::{TapController, TapPatterns},
-    topology::WatchRx,
+use vector_lib::{
+    enrichment::TableRegistry,
+    event::Event,
+    tap::{
+        controller::{TapController, TapPatterns, TapPayload},
+        topology::WatchRx,
+    },
 };
 
+use crate::conditions::{Condition, ConditionalConfig, VrlConfig};
+
 #[derive(Debug, Default)]
 pub struct EventsSubscription;
 
 #[Subscription]
 impl EventsSubscription {
     /// A stream of events emitted from matched component ID patterns
+    ///
+    /// If a `filter` is provided, it's evaluated as a VRL boolean expression against each event,
+    /// and only the events it matches are streamed.
     pub async fn output_events_by_component_id_patterns<'a>(
         &'a self,
         ctx: &'a Context<'a>,
         outputs_patterns: Vec<String>,
         inputs_patterns: Option<Vec<String>>,
+        filter: Option<String>,
         #[graphql(default = 500)] interval: u32,
         #[graphql(default = 100, validator(minimum = 1, maximum = 10_000))] limit: u32,
-    ) -> impl Stream<Item = Vec<OutputEventsPayload>> + 'a {
+    ) -> async_graphql::Result<impl Stream<Item = Vec<OutputEventsPayload>> + 'a> {
         let watch_rx = ctx.data_unchecked::<WatchRx>().clone();
 
         let patterns = TapPatterns {
             for_outputs: outputs_patterns.into_iter().collect(),
             for_inputs: inputs_patterns.unwrap_or_default().into_iter().collect(),
         };
+        let filter = filter
+            .map(build_filter)
+            .transpose()
+            .map_err(|error| format!("Invalid filter: {error}"))?;
         // Client input is confined to `u32` to provide sensible bounds.
-        create_events_stream(watch_rx, patterns, interval as u64, limit as usize)
+        Ok(create_events_stream(
+            watch_rx,
+            patterns,
+            filter,
+            interval as u64,
+            limit as usize,
+        ))
+    }
+}
+
+/// Compiles a tap filter. Enrichment tables aren't available to filters, as the tables of the
+/// running topology can't be shared with the API.
+fn build_filter(source: String) -> crate::Result<Condition> {
+    VrlConfig {
+        source,
+        ..Default::default()
+    }
+    .build(&TableRegistry::default())
+}
+
+/// Keeps the events of a tap payload that match the filter.
+fn filter_tap_payload(payload: TapPayload, filter: &Condition) -> TapPayload {
+    fn matching<T>(
+        events: Vec<T>,
+        filter: &Condition,
+        into_event: impl Fn(T) -> Event,
+        from_event: impl Fn(Event) -> T,
+    ) -> Vec<T> {
+        events
+            .into_iter()
+            .filter_map(|event| match filter.check(into_event(event)) {
+                (true, event) => Some(from_event(event)),
+                (false, _) => None,
+            })
+            .collect()
+    }
+
+    match payload {
+        TapPayload::Log(output, logs) => {
+            TapPayload::Log(output, matching(logs, filter, Event::Log, Event::into_log))
+        }
+        TapPayload::Metric(output, metrics) => TapPayload::Metric(
+            output,
+            matching(metrics, filter, Event::Metric, Event::into_metric),
+        ),
+        TapPayload::Trace(output, traces) => TapPayload::Trace(
+            output,
+            matching(traces, filter, Event::Trace, Event::into_trace),
+        ),
+        notification @ TapPayload::Notification(_) => notification,
     }
 }
 
 /// Creates an events stream based on component ids, and a provided interval. Will emit
 /// control messages that bubble up the application if the sink goes away. The stream contains
-/// all matching events; filtering should be done at the caller level.
+/// all matching events that pass the optional filter.
 pub(crate) fn create_events_stream(
     watch_rx: WatchRx,
     patterns: TapPatterns,
+    filter: Option<Condition>,
     interval: u64,
     limit: usize,
 ) -> impl Stream<Item = Vec<OutputEventsPayload>> {
     // Channel for receiving individual tap payloads. Since we can process at most `limit` per
     // interval, this is capped to the same value.
     let (tap_tx, tap_rx) = mpsc::channel(limit);
-    let mut tap_rx = ReceiverStream::new(tap_rx)
-        .flat_map(|payload| stream::iter(from_tap_payload_to_output_events(payload)));
+    let mut tap_rx = ReceiverStream::new(tap_rx).flat_map(move |payload| {
+        let payload = match &filter {
+            Some(filter) => filter_tap_payload(payload, filter),
+            None => payload,
+        };
+        stream::iter(from_tap_payload_to_output_events(payload))
+    });
 
     // The resulting vector of `Event` sent to the client. Only one result set will be streamed
     // back to the client at a time. This value is set higher than `1` to prevent blocking the event
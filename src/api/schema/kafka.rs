@@ -0,0 +1,106 @@
+use async_graphql::{Context, InputObject, Object};
+
+use crate::{config::ComponentKey, sources::kafka};
+
+#[derive(InputObject)]
+pub struct KafkaPartitionInput {
+    /// Topic of the partition
+    topic: String,
+
+    /// Number of the partition within its topic
+    partition: i32,
+}
+
+/// Marker included in the schema data when the `api.kafka_partition_control` option is enabled.
+#[derive(Clone, Copy, Debug)]
+pub struct KafkaPartitionControl;
+
+/// Fails unless pausing and resuming partitions is enabled.
+fn check_enabled(ctx: &Context<'_>) -> async_graphql::Result<()> {
+    ctx.data_opt::<KafkaPartitionControl>()
+        .map(|_| ())
+        .ok_or_else(|| {
+            "Pausing and resuming partitions is disabled, set `api.kafka_partition_control` to enable it."
+                .into()
+        })
+}
+
+fn topic_partitions(partitions: Vec<KafkaPartitionInput>) -> Vec<(String, i32)> {
+    partitions
+        .into_iter()
+        .map(|partition| (partition.topic, partition.partition))
+        .collect()
+}
+
+#[derive(Default)]
+pub struct KafkaMutation;
+
+#[Object]
+impl KafkaMutation {
+    /// Pauses the consumption of partitions assigned to a running `kafka` source, until they're
+    /// resumed or the source restarts. Messages already fetched from the partitions are still
+    /// processed. Requires the `api.kafka_partition_control` option to be enabled.
+    async fn pause_kafka_partitions(
+        &self,
+        ctx: &Context<'_>,
+        component_id: String,
+        partitions: Vec<KafkaPartitionInput>,
+    ) -> async_graphql::Result<bool> {
+        check_enabled(ctx)?;
+        kafka::pause_partitions(
+            &ComponentKey::from(component_id),
+            &topic_partitions(partitions),
+        )?;
+        Ok(true)
+    }
+
+    /// Resumes the consumption of partitions paused with `pauseKafkaPartitions`. Requires the
+    /// `api.kafka_partition_control` option to be enabled.
+    async fn resume_kafka_partitions(
+        &self,
+        ctx: &Context<'_>,
+        component_id: String,
+        partitions: Vec<KafkaPartitionInput>,
+    ) -> async_graphql::Result<bool> {
+        check_enabled(ctx)?;
+        kafka::resume_partitions(
+            &ComponentKey::from(component_id),
+            &topic_partitions(partitions),
+        )?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::schema::build_schema;
+
+    const PAUSE_PARTITIONS: &str = r#"mutation {
+        pauseKafkaPartitions(componentId: "unknown", partitions: [{ topic: "topic", partition: 0 }])
+    }"#;
+
+    #[tokio::test]
+    async fn partition_control_is_disabled_by_default() {
+        let response = build_schema().finish().execute(PAUSE_PARTITIONS).await;
+
+        assert_eq!(
+            response.errors[0].message,
+            "Pausing and resuming partitions is disabled, set `api.kafka_partition_control` to enable it."
+        );
+    }
+
+    #[tokio::test]
+    async fn partition_control_can_be_enabled() {
+        let response = build_schema()
+            .data(KafkaPartitionControl)
+            .finish()
+            .execute(PAUSE_PARTITIONS)
+            .await;
+
+        assert_eq!(
+            response.errors[0].message,
+            "No running kafka source has the ID unknown."
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use async_graphql::Object;
+
+use crate::config::ComponentKey;
+
+pub struct ComponentBufferUtilization {
+    component_key: ComponentKey,
+    utilization: f64,
+}
+
+impl ComponentBufferUtilization {
+    /// Returns a new `ComponentBufferUtilization` for the given component.
+    pub const fn new(component_key: ComponentKey, utilization: f64) -> Self {
+        Self {
+            component_key,
+            utilization,
+        }
+    }
+}
+
+#[Object]
+impl ComponentBufferUtilization {
+    /// Component ID.
+    async fn component_id(&self) -> &str {
+        self.component_key.id()
+    }
+
+    /// Utilization of the component's fullest buffer stage, between 0 and 1.
+    async fn utilization(&self) -> f64 {
+        self.utilization
+    }
+}
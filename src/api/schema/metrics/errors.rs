@@ -69,3 +69,31 @@ impl ComponentErrorsTotal {
         ErrorsTotal::new(self.metric.clone())
     }
 }
+
+pub struct ComponentErrorsThroughput {
+    component_key: ComponentKey,
+    throughput: i64,
+}
+
+impl ComponentErrorsThroughput {
+    /// Returns a new `ComponentErrorsThroughput` for the given component.
+    pub const fn new(component_key: ComponentKey, throughput: i64) -> Self {
+        Self {
+            component_key,
+            throughput,
+        }
+    }
+}
+
+#[Object]
+impl ComponentErrorsThroughput {
+    /// Component ID.
+    async fn component_id(&self) -> &str {
+        self.component_key.id()
+    }
+
+    /// Throughput of errors.
+    async fn throughput(&self) -> i64 {
+        self.throughput
+    }
+}
@@ -369,6 +369,56 @@ pub fn component_sent_events_total_throughputs_with_outputs(
         .skip(1)
 }
 
+/// Returns a stream of the buffer utilization of each component, sampled over `interval`
+/// milliseconds. The utilization of a buffer stage is its current size relative to its
+/// configured maximum, in events or bytes depending on how the stage is limited, and the
+/// utilization of a component is that of its fullest stage.
+pub fn component_buffer_utilizations(
+    interval: i32,
+) -> impl Stream<Item = Vec<(ComponentKey, f64)>> {
+    component_to_filtered_metrics(interval, &|m| m.name().starts_with("buffer_")).map(|map| {
+        map.into_iter()
+            .filter_map(|(id, metrics)| {
+                let stages = metrics
+                    .iter()
+                    .filter_map(|m| m.tag_value("stage"))
+                    .collect::<HashSet<_>>();
+
+                stages
+                    .iter()
+                    .filter_map(|stage| buffer_stage_utilization(&metrics, stage))
+                    .reduce(f64::max)
+                    .map(|utilization| (ComponentKey::from(id), utilization))
+            })
+            .collect()
+    })
+}
+
+/// Returns the utilization of a single buffer stage, or `None` if the stage is unbounded.
+fn buffer_stage_utilization(metrics: &[Metric], stage: &str) -> Option<f64> {
+    let gauge = |name: &str| {
+        let m = sum_metrics(
+            metrics
+                .iter()
+                .filter(|m| m.name() == name && m.tag_matches("stage", stage)),
+        )?;
+        match m.value() {
+            MetricValue::Gauge { value } => Some(*value),
+            _ => None,
+        }
+    };
+
+    let (size, max_size) = match gauge("buffer_max_event_size") {
+        Some(max_size) if max_size > 0.0 => (gauge("buffer_events")?, max_size),
+        _ => (
+            gauge("buffer_byte_size")?,
+            gauge("buffer_max_byte_size").filter(|max_size| *max_size > 0.0)?,
+        ),
+    };
+
+    Some((size / max_size).clamp(0.0, 1.0))
+}
+
 /// Returns a map of Component ID to list of metrics where metrics have been
 /// filtered by `filter_fn`
 fn component_to_filtered_metrics(
@@ -1,4 +1,5 @@
 mod allocated_bytes;
+mod buffer_utilization;
 mod errors;
 pub mod filter;
 mod output;
@@ -16,8 +17,9 @@ mod host;
 
 pub use allocated_bytes::{AllocatedBytes, ComponentAllocatedBytes};
 use async_graphql::{Interface, Subscription};
+pub use buffer_utilization::ComponentBufferUtilization;
 use chrono::{DateTime, Utc};
-pub use errors::{ComponentErrorsTotal, ErrorsTotal};
+pub use errors::{ComponentErrorsThroughput, ComponentErrorsTotal, ErrorsTotal};
 pub use filter::*;
 pub use output::*;
 pub use received_bytes::{
@@ -270,6 +272,35 @@ impl MetricsSubscription {
             .map(|m| m.into_iter().map(ComponentErrorsTotal::new).collect())
     }
 
+    /// Component error throughput over `interval`.
+    async fn component_errors_throughputs(
+        &self,
+        #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentErrorsThroughput>> {
+        component_counter_throughputs(interval, &|m| m.name().ends_with("_errors_total")).map(|m| {
+            m.into_iter()
+                .map(|(m, throughput)| {
+                    ComponentErrorsThroughput::new(
+                        ComponentKey::from(m.tag_value("component_id").unwrap()),
+                        throughput as i64,
+                    )
+                })
+                .collect()
+        })
+    }
+
+    /// Component buffer utilization over `interval`.
+    async fn component_buffer_utilizations(
+        &self,
+        #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentBufferUtilization>> {
+        component_buffer_utilizations(interval).map(|m| {
+            m.into_iter()
+                .map(|(key, utilization)| ComponentBufferUtilization::new(key, utilization))
+                .collect()
+        })
+    }
+
     /// All metrics.
     async fn metrics(
         &self,
@@ -2,12 +2,15 @@ pub mod components;
 pub mod events;
 pub mod filter;
 mod health;
+#[cfg(feature = "sources-kafka")]
+pub mod kafka;
 mod meta;
 mod metrics;
 mod relay;
+pub mod reload;
 pub mod sort;
 
-use async_graphql::{EmptyMutation, MergedObject, MergedSubscription, Schema, SchemaBuilder};
+use async_graphql::{MergedObject, MergedSubscription, Schema, SchemaBuilder};
 
 #[derive(MergedObject, Default)]
 pub struct Query(
@@ -17,6 +20,12 @@ pub struct Query(
     meta::MetaQuery,
 );
 
+#[derive(MergedObject, Default)]
+pub struct Mutation(
+    reload::ReloadMutation,
+    #[cfg(feature = "sources-kafka")] kafka::KafkaMutation,
+);
+
 #[derive(MergedSubscription, Default)]
 pub struct Subscription(
     health::HealthSubscription,
@@ -26,6 +35,10 @@ pub struct Subscription(
 );
 
 /// Build a new GraphQL schema, comprised of Query, Mutation and Subscription types
-pub fn build_schema() -> SchemaBuilder<Query, EmptyMutation, Subscription> {
-    Schema::build(Query::default(), EmptyMutation, Subscription::default())
+pub fn build_schema() -> SchemaBuilder<Query, Mutation, Subscription> {
+    Schema::build(
+        Query::default(),
+        Mutation::default(),
+        Subscription::default(),
+    )
 }
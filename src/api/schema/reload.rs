@@ -0,0 +1,225 @@
+use std::sync::RwLock;
+
+use async_graphql::{Context, Enum, Object, SimpleObject};
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::{
+    config::{self, ComponentKey, Config, ConfigBuilder, Difference, Format},
+    signal::{SignalTo, SignalTx},
+};
+
+const INVARIANT: &str = "Couldn't acquire lock on the running configuration. Please report this.";
+
+/// The serialized configuration of the running topology, used to compute what a new configuration
+/// changes.
+#[derive(Default)]
+struct RunningConfig {
+    global: serde_json::Value,
+    sources: IndexMap<ComponentKey, serde_json::Value>,
+    transforms: IndexMap<ComponentKey, serde_json::Value>,
+    sinks: IndexMap<ComponentKey, serde_json::Value>,
+}
+
+impl RunningConfig {
+    fn new(config: &Config) -> Self {
+        Self {
+            global: to_value(&config.global),
+            sources: to_values(config.sources()),
+            transforms: to_values(config.transforms()),
+            sinks: to_values(config.sinks()),
+        }
+    }
+}
+
+static RUNNING_CONFIG: Lazy<RwLock<RunningConfig>> = Lazy::new(Default::default);
+
+fn to_value(value: &impl Serialize) -> serde_json::Value {
+    serde_json::to_value(value).expect("Configuration should always serialize. Please report this.")
+}
+
+fn to_values<'a, C: Serialize + 'a>(
+    components: impl Iterator<Item = (&'a ComponentKey, &'a C)>,
+) -> IndexMap<ComponentKey, serde_json::Value> {
+    components
+        .map(|(key, component)| (key.clone(), to_value(component)))
+        .collect()
+}
+
+/// Update the running configuration that new configurations are compared against.
+pub fn update_config(config: &Config) {
+    *RUNNING_CONFIG.write().expect(INVARIANT) = RunningConfig::new(config);
+}
+
+#[derive(Enum, Copy, Clone, Default, Eq, PartialEq)]
+pub enum ConfigFormat {
+    #[default]
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl From<ConfigFormat> for Format {
+    fn from(format: ConfigFormat) -> Self {
+        match format {
+            ConfigFormat::Toml => Format::Toml,
+            ConfigFormat::Yaml => Format::Yaml,
+            ConfigFormat::Json => Format::Json,
+        }
+    }
+}
+
+#[derive(SimpleObject, Default)]
+pub struct ReloadConfigResult {
+    /// Whether the configuration was handed over to the running topology. Components which fail to
+    /// build are rolled back to their previous configuration, as when reloading from disk.
+    applied: bool,
+
+    /// Errors found while validating the configuration
+    errors: Vec<String>,
+
+    /// Warnings found while validating the configuration
+    warnings: Vec<String>,
+
+    /// Component IDs added by the configuration
+    added: Vec<String>,
+
+    /// Component IDs whose configuration is changed
+    changed: Vec<String>,
+
+    /// Component IDs removed by the configuration
+    removed: Vec<String>,
+}
+
+impl ReloadConfigResult {
+    fn errors(errors: Vec<String>) -> Self {
+        Self {
+            errors,
+            ..Default::default()
+        }
+    }
+
+    fn diff(config: &Config, warnings: Vec<String>) -> Result<Self, Vec<String>> {
+        let running = RUNNING_CONFIG.read().expect(INVARIANT);
+        if running.global != to_value(&config.global) {
+            return Err(vec![
+                "Global options can't be changed while reloading the configuration.".to_owned(),
+            ]);
+        }
+
+        let differences = [
+            Difference::new(&running.sources, &to_values(config.sources())),
+            Difference::new(&running.transforms, &to_values(config.transforms())),
+            Difference::new(&running.sinks, &to_values(config.sinks())),
+        ];
+        let sorted = |keys: Vec<&ComponentKey>| {
+            let mut keys = keys
+                .into_iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            keys.sort();
+            keys
+        };
+
+        Ok(Self {
+            applied: false,
+            errors: Vec::new(),
+            warnings,
+            added: sorted(differences.iter().flat_map(|d| &d.to_add).collect()),
+            changed: sorted(differences.iter().flat_map(|d| &d.to_change).collect()),
+            removed: sorted(differences.iter().flat_map(|d| &d.to_remove).collect()),
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct ReloadMutation;
+
+#[Object]
+impl ReloadMutation {
+    /// Validates a new configuration and, unless `dryRun` is set, reloads the running topology
+    /// with it, adding, changing and removing components as needed. Requires the `api.reload`
+    /// option to be enabled.
+    async fn reload_config(
+        &self,
+        ctx: &Context<'_>,
+        config: String,
+        #[graphql(default)] format: ConfigFormat,
+        #[graphql(default)] dry_run: bool,
+    ) -> async_graphql::Result<ReloadConfigResult> {
+        let signal_tx = ctx
+            .data_opt::<SignalTx>()
+            .ok_or("Reloading the configuration is disabled, set `api.reload` to enable it.")?;
+
+        let builder: ConfigBuilder = match config::load(config.as_bytes(), format.into()) {
+            Ok(builder) => builder,
+            Err(errors) => return Ok(ReloadConfigResult::errors(errors)),
+        };
+        let result = builder
+            .clone()
+            .build_with_warnings()
+            .and_then(|(config, warnings)| ReloadConfigResult::diff(&config, warnings));
+        let mut result = match result {
+            Ok(result) => result,
+            Err(errors) => return Ok(ReloadConfigResult::errors(errors)),
+        };
+
+        if !dry_run {
+            signal_tx
+                .send(SignalTo::ReloadFromConfigBuilder(builder))
+                .map_err(|_| "Couldn't send the configuration to the running topology.")?;
+            result.applied = true;
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs_against_running_config() {
+        let running = config::load_from_str(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            "#,
+            Format::Toml,
+        )
+        .unwrap();
+        update_config(&running);
+
+        let config = config::load_from_str(
+            r#"
+            [sources.in]
+            type = "test_basic"
+            data = "changed"
+
+            [transforms.suffix]
+            type = "test_basic"
+            inputs = ["in"]
+            suffix = "foo"
+            increase = 1.25
+
+            [sinks.console]
+            type = "test_basic"
+            inputs = ["suffix"]
+            "#,
+            Format::Toml,
+        )
+        .unwrap();
+        let result = ReloadConfigResult::diff(&config, Vec::new()).unwrap();
+
+        assert!(!result.applied);
+        assert_eq!(result.added, ["console", "suffix"]);
+        assert_eq!(result.changed, ["in"]);
+        assert_eq!(result.removed, ["out"]);
+    }
+}
@@ -22,6 +22,7 @@ use crate::{
     config::{self, api},
     http::build_http_trace_layer,
     internal_events::{SocketBindError, SocketMode},
+    signal::SignalTx,
 };
 
 pub struct Server {
@@ -36,9 +37,10 @@ impl Server {
         config: &config::Config,
         watch_rx: topology::WatchRx,
         running: Arc<AtomicBool>,
+        signal_tx: SignalTx,
         handle: &Handle,
     ) -> crate::Result<Self> {
-        let routes = make_routes(config.api, watch_rx, running);
+        let routes = make_routes(config.api, watch_rx, running, signal_tx);
 
         let (_shutdown, rx) = oneshot::channel();
         // warp uses `tokio::spawn` and so needs us to enter the runtime context.
@@ -75,6 +77,7 @@ impl Server {
 
         // Update component schema with the config before starting the server.
         schema::components::update_config(config);
+        schema::reload::update_config(config);
 
         // Spawn the server in the background.
         handle.spawn(server);
@@ -91,7 +94,8 @@ impl Server {
     /// directly involve `self`, it provides a neater API to expose an internal implementation
     /// detail than exposing the function of the sub-mod directly.
     pub fn update_config(&self, config: &config::Config) {
-        schema::components::update_config(config)
+        schema::components::update_config(config);
+        schema::reload::update_config(config);
     }
 }
 
@@ -99,7 +103,25 @@ fn make_routes(
     api: api::Options,
     watch_tx: topology::WatchRx,
     running: Arc<AtomicBool>,
+    signal_tx: SignalTx,
 ) -> BoxedFilter<(impl Reply,)> {
+    // The `reloadConfig` mutation is only available when the signal transmitter is part of the
+    // schema data, which requires the `reload` option to be enabled.
+    let signal_tx = api.reload.then_some(signal_tx);
+    let build_schema = move || {
+        let mut builder = schema::build_schema();
+        if let Some(signal_tx) = signal_tx.clone() {
+            builder = builder.data(signal_tx);
+        }
+        // Likewise, the `kafka` source partitions can only be paused and resumed when enabled.
+        #[cfg(feature = "sources-kafka")]
+        if api.kafka_partition_control {
+            builder = builder.data(schema::kafka::KafkaPartitionControl);
+        }
+        builder.finish()
+    };
+    let build_subscription_schema = build_schema.clone();
+
     // Routes...
 
     // Health.
@@ -119,7 +141,7 @@ fn make_routes(
         warp::ws()
             .and(graphql_protocol())
             .map(move |ws: Ws, protocol: WebSocketProtocols| {
-                let schema = schema::build_schema().finish();
+                let schema = build_subscription_schema();
                 let watch_tx = watch_tx.clone();
 
                 let reply = ws.on_upgrade(move |socket| {
@@ -144,7 +166,7 @@ fn make_routes(
     let graphql_handler = if api.graphql {
         warp::path("graphql")
             .and(graphql_subscription_handler.or(
-                async_graphql_warp::graphql(schema::build_schema().finish()).and_then(
+                async_graphql_warp::graphql(build_schema()).and_then(
                     |(schema, request): (Schema<_, _, _>, Request)| async move {
                         Ok::<_, Infallible>(GraphQLResponse::from(schema.execute(request).await))
                     },
@@ -112,12 +112,17 @@ impl ApplicationConfig {
 
     /// Configure the API server, if applicable
     #[cfg(feature = "api")]
-    pub fn setup_api(&self, handle: &Handle) -> Option<api::Server> {
+    pub fn setup_api(
+        &self,
+        handle: &Handle,
+        signal_tx: crate::signal::SignalTx,
+    ) -> Option<api::Server> {
         if self.api.enabled {
             match api::Server::start(
                 self.topology.config(),
                 self.topology.watch(),
                 std::sync::Arc::clone(&self.topology.running),
+                signal_tx,
                 handle,
             ) {
                 Ok(api_server) => {
@@ -232,7 +237,9 @@ impl Application {
 
         let topology_controller = SharedTopologyController::new(TopologyController {
             #[cfg(feature = "api")]
-            api_server: config.setup_api(handle),
+            api_server: config.setup_api(handle, signals.handler.clone_tx()),
+            #[cfg(feature = "api")]
+            signal_tx: signals.handler.clone_tx(),
             topology: config.topology,
             config_paths: config.config_paths.clone(),
             require_healthy: root_opts.require_healthy,
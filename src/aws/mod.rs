@@ -144,7 +144,7 @@ fn region_provider(
     ))
 }
 
-async fn resolve_region(
+pub(crate) async fn resolve_region(
     proxy: &ProxyConfig,
     tls_options: &Option<TlsConfig>,
     region: Option<Region>,
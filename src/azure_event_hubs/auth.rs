@@ -0,0 +1,196 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use azure_core::auth::TokenCredential;
+use azure_identity::DefaultAzureCredential;
+use openssl::{base64, hash, pkey, sign};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use snafu::Snafu;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use super::{
+    codec::{AmqpValue, Message},
+    connection::{AmqpError, Connection},
+};
+
+/// The scope of the Microsoft Entra ID tokens authorizing access to Event Hubs.
+const EVENT_HUBS_SCOPE: &str = "https://eventhubs.azure.net/.default";
+
+/// How long the shared access signatures generated from a key are valid for.
+const SAS_TOKEN_VALIDITY: Duration = Duration::from_secs(60 * 60);
+
+/// The node accepting claims-based security tokens.
+const CBS_NODE: &str = "$cbs";
+
+#[derive(Debug, Snafu)]
+pub(crate) enum AuthError {
+    #[snafu(display("Unable to sign the shared access signature: {}", source))]
+    Sign { source: openssl::error::ErrorStack },
+
+    #[snafu(display("Unable to get a token from Azure: {}", source))]
+    Credential { source: azure_core::Error },
+
+    #[snafu(display("{}", source))]
+    Amqp { source: AmqpError },
+}
+
+impl AuthError {
+    pub(crate) fn is_retriable(&self) -> bool {
+        match self {
+            Self::Sign { .. } => false,
+            Self::Credential { .. } => true,
+            Self::Amqp { source } => source.is_retriable(),
+        }
+    }
+}
+
+impl From<AmqpError> for AuthError {
+    fn from(source: AmqpError) -> Self {
+        Self::Amqp { source }
+    }
+}
+
+/// The credentials used to authorize access to an Event Hub.
+#[derive(Clone)]
+pub(crate) enum Credentials {
+    /// A shared access key, from which shared access signatures are generated.
+    SharedAccessKey { name: String, key: String },
+    /// A shared access signature, used as-is.
+    SharedAccessSignature(String),
+    /// A Microsoft Entra ID token credential.
+    TokenCredential(Arc<dyn TokenCredential>),
+}
+
+impl Credentials {
+    pub(crate) fn default_azure_credential() -> Self {
+        Self::TokenCredential(Arc::new(DefaultAzureCredential::default()))
+    }
+
+    /// Gets a token for the given audience, along with its type and expiry.
+    async fn token(&self, audience: &str) -> Result<Token, AuthError> {
+        match self {
+            Self::SharedAccessKey { name, key } => {
+                let expires_at = SystemTime::now() + SAS_TOKEN_VALIDITY;
+                Ok(Token {
+                    value: shared_access_signature(audience, name, key, expires_at)?,
+                    kind: "servicebus.windows.net:sastoken",
+                    expires_at: Some(expires_at),
+                })
+            }
+            Self::SharedAccessSignature(signature) => Ok(Token {
+                value: signature.clone(),
+                kind: "servicebus.windows.net:sastoken",
+                expires_at: None,
+            }),
+            Self::TokenCredential(credential) => {
+                let token = credential
+                    .get_token(&[EVENT_HUBS_SCOPE])
+                    .await
+                    .map_err(|source| AuthError::Credential { source })?;
+                let expires_at = u64::try_from(token.expires_on.unix_timestamp())
+                    .ok()
+                    .map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+                Ok(Token {
+                    value: token.token.secret().to_owned(),
+                    kind: "jwt",
+                    expires_at,
+                })
+            }
+        }
+    }
+
+    /// Authorizes the connection to access `audience` by putting a token on its claims-based
+    /// security node, returning when the authorization expires.
+    pub(crate) async fn authorize<S>(
+        &self,
+        connection: &mut Connection<S>,
+        audience: &str,
+    ) -> Result<Option<SystemTime>, AuthError>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let token = self.token(audience).await?;
+
+        let mut request = Message::with_value(AmqpValue::string(token.value));
+        request.application_properties = vec![
+            ("operation".into(), "put-token".into()),
+            ("type".into(), token.kind.into()),
+            ("name".into(), audience.into()),
+        ];
+        connection.request(CBS_NODE, request).await?;
+        Ok(token.expires_at)
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SharedAccessKey { name, .. } => f
+                .debug_struct("SharedAccessKey")
+                .field("name", name)
+                .finish_non_exhaustive(),
+            Self::SharedAccessSignature(_) => f.write_str("SharedAccessSignature"),
+            Self::TokenCredential(_) => f.write_str("TokenCredential"),
+        }
+    }
+}
+
+struct Token {
+    value: String,
+    kind: &'static str,
+    expires_at: Option<SystemTime>,
+}
+
+/// Generates a shared access signature granting access to `audience` until `expires_at`.
+///
+/// See <https://learn.microsoft.com/en-us/azure/event-hubs/authenticate-shared-access-signature>.
+fn shared_access_signature(
+    audience: &str,
+    key_name: &str,
+    key: &str,
+    expires_at: SystemTime,
+) -> Result<String, AuthError> {
+    let expiry = expires_at
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let resource = utf8_percent_encode(audience, NON_ALPHANUMERIC).to_string();
+
+    let sign = || {
+        let key = pkey::PKey::hmac(key.as_bytes())?;
+        let mut signer = sign::Signer::new(hash::MessageDigest::sha256(), &key)?;
+        signer.update(format!("{resource}\n{expiry}").as_bytes())?;
+        signer.sign_to_vec()
+    };
+    let signature = sign().map_err(|source| AuthError::Sign { source })?;
+    let signature = base64::encode_block(&signature);
+
+    Ok(format!(
+        "SharedAccessSignature sr={resource}&sig={}&se={expiry}&skn={key_name}",
+        utf8_percent_encode(&signature, NON_ALPHANUMERIC)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_shared_access_signatures() {
+        let expires_at = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let signature = shared_access_signature(
+            "sb://vector.servicebus.windows.net/logs",
+            "send",
+            "c2VjcmV0",
+            expires_at,
+        )
+        .unwrap();
+
+        assert!(signature.starts_with(
+            "SharedAccessSignature sr=sb%3A%2F%2Fvector%2Eservicebus%2Ewindows%2Enet%2Flogs&sig="
+        ));
+        assert!(signature.ends_with("&se=1700000000&skn=send"));
+    }
+}
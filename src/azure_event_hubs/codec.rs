@@ -0,0 +1,754 @@
+//! Encoding and decoding of the AMQP 1.0 type system and of the messages built on top of it.
+//!
+//! Values are always encoded in their widest form, which every peer must accept, while any valid
+//! encoding is decoded.
+//!
+//! Type system reference: <https://docs.oasis-open.org/amqp/core/v1.0/os/amqp-core-types-v1.0-os.html>
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use snafu::Snafu;
+
+/// Descriptors of the message sections.
+pub(crate) mod section {
+    pub const HEADER: u64 = 0x70;
+    pub const DELIVERY_ANNOTATIONS: u64 = 0x71;
+    pub const MESSAGE_ANNOTATIONS: u64 = 0x72;
+    pub const PROPERTIES: u64 = 0x73;
+    pub const APPLICATION_PROPERTIES: u64 = 0x74;
+    pub const DATA: u64 = 0x75;
+    pub const AMQP_SEQUENCE: u64 = 0x76;
+    pub const AMQP_VALUE: u64 = 0x77;
+    pub const FOOTER: u64 = 0x78;
+}
+
+#[derive(Debug, PartialEq, Snafu)]
+pub(crate) enum DecodeError {
+    #[snafu(display("Unexpected end of AMQP data"))]
+    Truncated,
+
+    #[snafu(display("Unknown AMQP type constructor 0x{:02x}", constructor))]
+    UnknownConstructor { constructor: u8 },
+
+    #[snafu(display("Invalid AMQP {}", kind))]
+    Invalid { kind: &'static str },
+}
+
+/// A value of the AMQP type system.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum AmqpValue {
+    Null,
+    Bool(bool),
+    Ubyte(u8),
+    Ushort(u16),
+    Uint(u32),
+    Ulong(u64),
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    /// Decimal values are kept in their IEEE 754 encoding, as nothing needs to interpret them.
+    Decimal(Bytes),
+    Char(char),
+    /// Milliseconds since the Unix epoch.
+    Timestamp(i64),
+    Uuid([u8; 16]),
+    Binary(Bytes),
+    String(String),
+    Symbol(String),
+    List(Vec<AmqpValue>),
+    Map(Vec<(AmqpValue, AmqpValue)>),
+    Array(Vec<AmqpValue>),
+    Described(Box<AmqpValue>, Box<AmqpValue>),
+}
+
+impl AmqpValue {
+    pub(crate) fn symbol(symbol: impl Into<String>) -> Self {
+        Self::Symbol(symbol.into())
+    }
+
+    pub(crate) fn string(string: impl Into<String>) -> Self {
+        Self::String(string.into())
+    }
+
+    pub(crate) fn described(descriptor: u64, value: Self) -> Self {
+        Self::Described(Box::new(Self::Ulong(descriptor)), Box::new(value))
+    }
+
+    /// Returns the numeric descriptor and the value of a described value.
+    pub(crate) fn as_described(&self) -> Option<(u64, &AmqpValue)> {
+        match self {
+            Self::Described(descriptor, value) => match **descriptor {
+                Self::Ulong(code) => Some((code, value)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(string) | Self::Symbol(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub(crate) const fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::Ubyte(value) => Some(u64::from(*value)),
+            Self::Ushort(value) => Some(u64::from(*value)),
+            Self::Uint(value) => Some(u64::from(*value)),
+            Self::Ulong(value) => Some(*value),
+            Self::Byte(value) => u64::try_from(*value).ok(),
+            Self::Short(value) => u64::try_from(*value).ok(),
+            Self::Int(value) => u64::try_from(*value).ok(),
+            Self::Long(value) => u64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Byte(value) => Some(i64::from(*value)),
+            Self::Short(value) => Some(i64::from(*value)),
+            Self::Int(value) => Some(i64::from(*value)),
+            Self::Long(value) | Self::Timestamp(value) => Some(*value),
+            value => value.as_u64().and_then(|value| i64::try_from(value).ok()),
+        }
+    }
+
+    pub(crate) const fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Looks up the value of a map by its string or symbol key.
+    pub(crate) fn get(&self, key: &str) -> Option<&AmqpValue> {
+        match self {
+            Self::Map(entries) => entries
+                .iter()
+                .find(|(entry, _)| entry.as_str() == Some(key))
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn encode(&self, buf: &mut BytesMut) {
+        if let Self::Described(descriptor, value) = self {
+            buf.put_u8(0x00);
+            descriptor.encode(buf);
+            value.encode(buf);
+        } else {
+            buf.put_u8(self.constructor());
+            self.encode_body(buf);
+        }
+    }
+
+    pub(crate) fn to_bytes(&self) -> Bytes {
+        let mut buf = BytesMut::new();
+        self.encode(&mut buf);
+        buf.freeze()
+    }
+
+    /// The constructor of the widest encoding of the value.
+    const fn constructor(&self) -> u8 {
+        match self {
+            Self::Null => 0x40,
+            Self::Bool(_) => 0x56,
+            Self::Ubyte(_) => 0x50,
+            Self::Ushort(_) => 0x60,
+            Self::Uint(_) => 0x70,
+            Self::Ulong(_) => 0x80,
+            Self::Byte(_) => 0x51,
+            Self::Short(_) => 0x61,
+            Self::Int(_) => 0x71,
+            Self::Long(_) => 0x81,
+            Self::Float(_) => 0x72,
+            Self::Double(_) => 0x82,
+            Self::Decimal(_) => 0x94,
+            Self::Char(_) => 0x73,
+            Self::Timestamp(_) => 0x83,
+            Self::Uuid(_) => 0x98,
+            Self::Binary(_) => 0xb0,
+            Self::String(_) => 0xb1,
+            Self::Symbol(_) => 0xb3,
+            Self::List(_) => 0xd0,
+            Self::Map(_) => 0xd1,
+            Self::Array(_) => 0xf0,
+            Self::Described(..) => 0x00,
+        }
+    }
+
+    fn encode_body(&self, buf: &mut BytesMut) {
+        match self {
+            Self::Null | Self::Described(..) => {}
+            Self::Bool(value) => buf.put_u8(u8::from(*value)),
+            Self::Ubyte(value) => buf.put_u8(*value),
+            Self::Ushort(value) => buf.put_u16(*value),
+            Self::Uint(value) => buf.put_u32(*value),
+            Self::Ulong(value) => buf.put_u64(*value),
+            Self::Byte(value) => buf.put_i8(*value),
+            Self::Short(value) => buf.put_i16(*value),
+            Self::Int(value) => buf.put_i32(*value),
+            Self::Long(value) | Self::Timestamp(value) => buf.put_i64(*value),
+            Self::Float(value) => buf.put_f32(*value),
+            Self::Double(value) => buf.put_f64(*value),
+            Self::Decimal(value) => {
+                // Decimals are re-encoded as decimal128, padding narrower values.
+                let mut decimal = [0; 16];
+                let len = value.len().min(16);
+                decimal[16 - len..].copy_from_slice(&value[..len]);
+                buf.put_slice(&decimal);
+            }
+            Self::Char(value) => buf.put_u32(u32::from(*value)),
+            Self::Uuid(value) => buf.put_slice(value),
+            Self::Binary(value) => {
+                buf.put_u32(value.len() as u32);
+                buf.put_slice(value);
+            }
+            Self::String(value) | Self::Symbol(value) => {
+                buf.put_u32(value.len() as u32);
+                buf.put_slice(value.as_bytes());
+            }
+            Self::List(values) => {
+                let mut items = BytesMut::new();
+                values.iter().for_each(|value| value.encode(&mut items));
+                put_compound(buf, values.len(), &items);
+            }
+            Self::Map(entries) => {
+                let mut items = BytesMut::new();
+                for (key, value) in entries {
+                    key.encode(&mut items);
+                    value.encode(&mut items);
+                }
+                put_compound(buf, entries.len() * 2, &items);
+            }
+            Self::Array(values) => {
+                // Arrays share the constructor of their first element, so they can only hold
+                // values of a single, non-described type.
+                let constructor = values.first().map_or(0x40, Self::constructor);
+                let mut items = BytesMut::new();
+                items.put_u8(constructor);
+                values
+                    .iter()
+                    .for_each(|value| value.encode_body(&mut items));
+                put_compound(buf, values.len(), &items);
+            }
+        }
+    }
+
+    pub(crate) fn decode(buf: &mut Bytes) -> Result<Self, DecodeError> {
+        let constructor = get_u8(buf)?;
+        if constructor == 0x00 {
+            let descriptor = Self::decode(buf)?;
+            let value = Self::decode(buf)?;
+            return Ok(Self::Described(Box::new(descriptor), Box::new(value)));
+        }
+        Self::decode_body(constructor, buf)
+    }
+
+    fn decode_body(constructor: u8, buf: &mut Bytes) -> Result<Self, DecodeError> {
+        Ok(match constructor {
+            0x40 => Self::Null,
+            0x41 => Self::Bool(true),
+            0x42 => Self::Bool(false),
+            0x56 => Self::Bool(get_u8(buf)? != 0),
+            0x50 => Self::Ubyte(get_u8(buf)?),
+            0x60 => Self::Ushort(take(buf, 2)?.get_u16()),
+            0x70 => Self::Uint(take(buf, 4)?.get_u32()),
+            0x52 => Self::Uint(u32::from(get_u8(buf)?)),
+            0x43 => Self::Uint(0),
+            0x80 => Self::Ulong(take(buf, 8)?.get_u64()),
+            0x53 => Self::Ulong(u64::from(get_u8(buf)?)),
+            0x44 => Self::Ulong(0),
+            0x51 => Self::Byte(get_u8(buf)? as i8),
+            0x61 => Self::Short(take(buf, 2)?.get_i16()),
+            0x71 => Self::Int(take(buf, 4)?.get_i32()),
+            0x54 => Self::Int(i32::from(get_u8(buf)? as i8)),
+            0x81 => Self::Long(take(buf, 8)?.get_i64()),
+            0x55 => Self::Long(i64::from(get_u8(buf)? as i8)),
+            0x72 => Self::Float(take(buf, 4)?.get_f32()),
+            0x82 => Self::Double(take(buf, 8)?.get_f64()),
+            0x74 => Self::Decimal(take(buf, 4)?),
+            0x84 => Self::Decimal(take(buf, 8)?),
+            0x94 => Self::Decimal(take(buf, 16)?),
+            0x73 => Self::Char(
+                char::from_u32(take(buf, 4)?.get_u32())
+                    .ok_or(DecodeError::Invalid { kind: "char" })?,
+            ),
+            0x83 => Self::Timestamp(take(buf, 8)?.get_i64()),
+            0x98 => {
+                let mut uuid = [0; 16];
+                take(buf, 16)?.copy_to_slice(&mut uuid);
+                Self::Uuid(uuid)
+            }
+            0xa0 => {
+                let len = get_u8(buf)? as usize;
+                Self::Binary(take(buf, len)?)
+            }
+            0xb0 => {
+                let len = take(buf, 4)?.get_u32() as usize;
+                Self::Binary(take(buf, len)?)
+            }
+            0xa1 | 0xa3 | 0xb1 | 0xb3 => {
+                let len = if constructor & 0xf0 == 0xa0 {
+                    get_u8(buf)? as usize
+                } else {
+                    take(buf, 4)?.get_u32() as usize
+                };
+                let string = String::from_utf8(take(buf, len)?.to_vec())
+                    .map_err(|_| DecodeError::Invalid { kind: "string" })?;
+                if constructor & 0x0f == 0x01 {
+                    Self::String(string)
+                } else {
+                    Self::Symbol(string)
+                }
+            }
+            0x45 => Self::List(Vec::new()),
+            0xc0 | 0xd0 | 0xc1 | 0xd1 => {
+                let (count, mut items) = get_compound(constructor & 0xf0 == 0xc0, buf)?;
+                let mut values = Vec::with_capacity(count.min(items.len()));
+                for _ in 0..count {
+                    values.push(Self::decode(&mut items)?);
+                }
+                if constructor & 0x0f == 0x00 {
+                    Self::List(values)
+                } else {
+                    if values.len() % 2 != 0 {
+                        return Err(DecodeError::Invalid { kind: "map" });
+                    }
+                    let mut entries = Vec::with_capacity(values.len() / 2);
+                    let mut values = values.into_iter();
+                    while let (Some(key), Some(value)) = (values.next(), values.next()) {
+                        entries.push((key, value));
+                    }
+                    Self::Map(entries)
+                }
+            }
+            0xe0 | 0xf0 => {
+                let (count, mut items) = get_compound(constructor == 0xe0, buf)?;
+                let element = get_u8(&mut items)?;
+                let descriptor = if element == 0x00 {
+                    Some(Self::decode(&mut items)?)
+                } else {
+                    None
+                };
+                let element = match descriptor {
+                    Some(_) => get_u8(&mut items)?,
+                    None => element,
+                };
+                let mut values = Vec::with_capacity(count.min(items.len()));
+                for _ in 0..count {
+                    let value = Self::decode_body(element, &mut items)?;
+                    values.push(match &descriptor {
+                        Some(descriptor) => {
+                            Self::Described(Box::new(descriptor.clone()), Box::new(value))
+                        }
+                        None => value,
+                    });
+                }
+                Self::Array(values)
+            }
+            constructor => return Err(DecodeError::UnknownConstructor { constructor }),
+        })
+    }
+}
+
+impl From<&str> for AmqpValue {
+    fn from(string: &str) -> Self {
+        Self::String(string.to_owned())
+    }
+}
+
+impl From<String> for AmqpValue {
+    fn from(string: String) -> Self {
+        Self::String(string)
+    }
+}
+
+impl From<bool> for AmqpValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<u32> for AmqpValue {
+    fn from(value: u32) -> Self {
+        Self::Uint(value)
+    }
+}
+
+impl From<u64> for AmqpValue {
+    fn from(value: u64) -> Self {
+        Self::Ulong(value)
+    }
+}
+
+impl<T: Into<AmqpValue>> From<Option<T>> for AmqpValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+fn put_compound(buf: &mut BytesMut, count: usize, items: &[u8]) {
+    // The size covers the count as well as the items.
+    buf.put_u32(items.len() as u32 + 4);
+    buf.put_u32(count as u32);
+    buf.put_slice(items);
+}
+
+fn get_compound(narrow: bool, buf: &mut Bytes) -> Result<(usize, Bytes), DecodeError> {
+    if narrow {
+        let size = get_u8(buf)? as usize;
+        let mut items = take(buf, size)?;
+        let count = get_u8(&mut items)? as usize;
+        Ok((count, items))
+    } else {
+        let size = take(buf, 4)?.get_u32() as usize;
+        let mut items = take(buf, size)?;
+        let count = take(&mut items, 4)?.get_u32() as usize;
+        Ok((count, items))
+    }
+}
+
+fn get_u8(buf: &mut Bytes) -> Result<u8, DecodeError> {
+    if buf.has_remaining() {
+        Ok(buf.get_u8())
+    } else {
+        Err(DecodeError::Truncated)
+    }
+}
+
+fn take(buf: &mut Bytes, len: usize) -> Result<Bytes, DecodeError> {
+    if buf.remaining() < len {
+        Err(DecodeError::Truncated)
+    } else {
+        Ok(buf.split_to(len))
+    }
+}
+
+/// The body of a message.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum MessageBody {
+    /// One or more opaque binary sections.
+    Data(Vec<Bytes>),
+    /// A single AMQP value.
+    Value(AmqpValue),
+    /// One or more sequences of AMQP values.
+    Sequence(Vec<AmqpValue>),
+}
+
+impl Default for MessageBody {
+    fn default() -> Self {
+        Self::Data(Vec::new())
+    }
+}
+
+/// The subset of the immutable properties of a message used by Vector.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct MessageProperties {
+    pub(crate) message_id: Option<AmqpValue>,
+    pub(crate) to: Option<String>,
+    pub(crate) reply_to: Option<String>,
+    pub(crate) correlation_id: Option<AmqpValue>,
+    pub(crate) content_type: Option<String>,
+}
+
+impl MessageProperties {
+    fn to_value(&self) -> AmqpValue {
+        let string = |value: &Option<String>| {
+            value
+                .as_ref()
+                .map_or(AmqpValue::Null, |value| AmqpValue::string(value.as_str()))
+        };
+        AmqpValue::described(
+            section::PROPERTIES,
+            AmqpValue::List(vec![
+                self.message_id.clone().unwrap_or(AmqpValue::Null),
+                AmqpValue::Null, // user-id
+                string(&self.to),
+                AmqpValue::Null, // subject
+                string(&self.reply_to),
+                self.correlation_id.clone().unwrap_or(AmqpValue::Null),
+                self.content_type
+                    .as_ref()
+                    .map_or(AmqpValue::Null, |value| AmqpValue::symbol(value.as_str())),
+            ]),
+        )
+    }
+
+    fn from_fields(fields: &[AmqpValue]) -> Self {
+        let value = |index: usize| fields.get(index).filter(|value| !value.is_null()).cloned();
+        let string =
+            |index: usize| value(index).and_then(|value| value.as_str().map(ToOwned::to_owned));
+        Self {
+            message_id: value(0),
+            to: string(2),
+            reply_to: string(4),
+            correlation_id: value(5),
+            content_type: string(6),
+        }
+    }
+}
+
+/// An AMQP message.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Message {
+    pub(crate) message_annotations: Vec<(AmqpValue, AmqpValue)>,
+    pub(crate) properties: Option<MessageProperties>,
+    pub(crate) application_properties: Vec<(AmqpValue, AmqpValue)>,
+    pub(crate) body: MessageBody,
+}
+
+impl Message {
+    pub(crate) fn with_data(data: Bytes) -> Self {
+        Self {
+            body: MessageBody::Data(vec![data]),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn with_value(value: AmqpValue) -> Self {
+        Self {
+            body: MessageBody::Value(value),
+            ..Default::default()
+        }
+    }
+
+    /// Looks up a message annotation by its symbol key.
+    pub(crate) fn annotation(&self, key: &str) -> Option<&AmqpValue> {
+        self.message_annotations
+            .iter()
+            .find(|(entry, _)| entry.as_str() == Some(key))
+            .map(|(_, value)| value)
+    }
+
+    /// Looks up an application property by its key.
+    pub(crate) fn application_property(&self, key: &str) -> Option<&AmqpValue> {
+        self.application_properties
+            .iter()
+            .find(|(entry, _)| entry.as_str() == Some(key))
+            .map(|(_, value)| value)
+    }
+
+    pub(crate) fn encode(&self, buf: &mut BytesMut) {
+        if !self.message_annotations.is_empty() {
+            AmqpValue::described(
+                section::MESSAGE_ANNOTATIONS,
+                AmqpValue::Map(self.message_annotations.clone()),
+            )
+            .encode(buf);
+        }
+        if let Some(properties) = &self.properties {
+            properties.to_value().encode(buf);
+        }
+        if !self.application_properties.is_empty() {
+            AmqpValue::described(
+                section::APPLICATION_PROPERTIES,
+                AmqpValue::Map(self.application_properties.clone()),
+            )
+            .encode(buf);
+        }
+        match &self.body {
+            MessageBody::Data(sections) => {
+                for data in sections {
+                    encode_data_section(data, buf);
+                }
+            }
+            MessageBody::Value(value) => {
+                AmqpValue::described(section::AMQP_VALUE, value.clone()).encode(buf)
+            }
+            MessageBody::Sequence(sequences) => {
+                for sequence in sequences {
+                    AmqpValue::described(section::AMQP_SEQUENCE, sequence.clone()).encode(buf);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn to_bytes(&self) -> Bytes {
+        let mut buf = BytesMut::new();
+        self.encode(&mut buf);
+        buf.freeze()
+    }
+
+    pub(crate) fn decode(mut buf: Bytes) -> Result<Self, DecodeError> {
+        let mut message = Self::default();
+        let mut data = Vec::new();
+        let mut sequences = Vec::new();
+
+        while buf.has_remaining() {
+            let value = AmqpValue::decode(&mut buf)?;
+            let Some((descriptor, value)) = value.as_described() else {
+                return Err(DecodeError::Invalid {
+                    kind: "message section",
+                });
+            };
+            match (descriptor, value) {
+                (section::MESSAGE_ANNOTATIONS, AmqpValue::Map(entries)) => {
+                    message.message_annotations = entries.clone()
+                }
+                (section::PROPERTIES, AmqpValue::List(fields)) => {
+                    message.properties = Some(MessageProperties::from_fields(fields))
+                }
+                (section::APPLICATION_PROPERTIES, AmqpValue::Map(entries)) => {
+                    message.application_properties = entries.clone()
+                }
+                (section::DATA, AmqpValue::Binary(bytes)) => data.push(bytes.clone()),
+                (section::AMQP_SEQUENCE, value) => sequences.push(value.clone()),
+                (section::AMQP_VALUE, value) => message.body = MessageBody::Value(value.clone()),
+                (
+                    section::HEADER
+                    | section::DELIVERY_ANNOTATIONS
+                    | section::FOOTER
+                    | section::MESSAGE_ANNOTATIONS
+                    | section::PROPERTIES
+                    | section::APPLICATION_PROPERTIES,
+                    _,
+                ) => {}
+                _ => {
+                    return Err(DecodeError::Invalid {
+                        kind: "message section",
+                    })
+                }
+            }
+        }
+
+        if !data.is_empty() {
+            message.body = MessageBody::Data(data);
+        } else if !sequences.is_empty() {
+            message.body = MessageBody::Sequence(sequences);
+        }
+        Ok(message)
+    }
+}
+
+/// Encodes a `data` section holding the given bytes.
+pub(crate) fn encode_data_section(data: &[u8], buf: &mut BytesMut) {
+    buf.put_u8(0x00);
+    AmqpValue::Ulong(section::DATA).encode(buf);
+    buf.put_u8(0xb0);
+    buf.put_u32(data.len() as u32);
+    buf.put_slice(data);
+}
+
+/// Splits a sequence of encoded message sections, returning the encoding of each of them.
+pub(crate) fn split_sections(mut buf: Bytes) -> Result<Vec<Bytes>, DecodeError> {
+    let mut sections = Vec::new();
+    while buf.has_remaining() {
+        let mut rest = buf.clone();
+        AmqpValue::decode(&mut rest)?;
+        let len = buf.len() - rest.len();
+        sections.push(buf.split_to(len));
+    }
+    Ok(sections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(value: AmqpValue) {
+        let mut bytes = value.to_bytes();
+        assert_eq!(AmqpValue::decode(&mut bytes), Ok(value));
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn roundtrips_values() {
+        roundtrip(AmqpValue::Null);
+        roundtrip(AmqpValue::Bool(true));
+        roundtrip(AmqpValue::Uint(7));
+        roundtrip(AmqpValue::Ulong(u64::MAX));
+        roundtrip(AmqpValue::Int(-3));
+        roundtrip(AmqpValue::Timestamp(1_700_000_000_000));
+        roundtrip(AmqpValue::Binary(Bytes::from_static(b"\x00\x01")));
+        roundtrip(AmqpValue::string("hello"));
+        roundtrip(AmqpValue::symbol("x-opt-partition-key"));
+        roundtrip(AmqpValue::List(vec![AmqpValue::Null, AmqpValue::Uint(1)]));
+        roundtrip(AmqpValue::Map(vec![(
+            AmqpValue::symbol("key"),
+            AmqpValue::string("value"),
+        )]));
+        roundtrip(AmqpValue::Array(vec![
+            AmqpValue::symbol("a"),
+            AmqpValue::symbol("b"),
+        ]));
+        roundtrip(AmqpValue::described(0x10, AmqpValue::List(vec![])));
+    }
+
+    #[test]
+    fn decodes_compact_encodings() {
+        // A described list8 holding a smallulong, a str8, uint0, and a sym8 array8.
+        let mut bytes = Bytes::from_static(&[
+            0x00, 0x53, 0x10, 0xc0, 0x0d, 0x04, 0x53, 0x05, 0xa1, 0x01, b'c', 0x43, 0xe0, 0x04,
+            0x01, 0xa3, 0x01, b's',
+        ]);
+
+        assert_eq!(
+            AmqpValue::decode(&mut bytes),
+            Ok(AmqpValue::described(
+                0x10,
+                AmqpValue::List(vec![
+                    AmqpValue::Ulong(5),
+                    AmqpValue::string("c"),
+                    AmqpValue::Uint(0),
+                    AmqpValue::Array(vec![AmqpValue::symbol("s")]),
+                ])
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_values() {
+        let mut bytes = Bytes::from_static(&[0xb1, 0x00, 0x00, 0x00, 0x05, b'a']);
+        assert_eq!(AmqpValue::decode(&mut bytes), Err(DecodeError::Truncated));
+    }
+
+    #[test]
+    fn roundtrips_messages() {
+        let message = Message {
+            message_annotations: vec![(
+                AmqpValue::symbol("x-opt-partition-key"),
+                AmqpValue::string("key"),
+            )],
+            properties: Some(MessageProperties {
+                message_id: Some(AmqpValue::string("id")),
+                reply_to: Some("cbs".to_owned()),
+                ..Default::default()
+            }),
+            application_properties: vec![(AmqpValue::string("operation"), "put-token".into())],
+            body: MessageBody::Data(vec![Bytes::from_static(b"one"), Bytes::from_static(b"two")]),
+        };
+
+        let decoded = Message::decode(message.to_bytes()).unwrap();
+
+        assert_eq!(decoded, message);
+        assert_eq!(
+            decoded.annotation("x-opt-partition-key"),
+            Some(&AmqpValue::string("key"))
+        );
+    }
+
+    #[test]
+    fn splits_sections() {
+        let mut buf = BytesMut::new();
+        encode_data_section(b"first", &mut buf);
+        encode_data_section(b"second", &mut buf);
+
+        let sections = split_sections(buf.freeze()).unwrap();
+
+        assert_eq!(sections.len(), 2);
+        assert_eq!(
+            Message::decode(sections[1].clone()).unwrap().body,
+            MessageBody::Data(vec![Bytes::from_static(b"second")])
+        );
+    }
+}
@@ -0,0 +1,1307 @@
+//! A minimal AMQP 1.0 client.
+//!
+//! A connection runs a single session, on which any number of sender and receiver links can be
+//! attached. Frames are only read and written while an operation waits for the peer, so idle
+//! connections don't send the heartbeats the peer expects. Callers are expected to drop
+//! connections that failed or sat idle for too long, and to open new ones.
+//!
+//! Transport reference: <https://docs.oasis-open.org/amqp/core/v1.0/os/amqp-core-transport-v1.0-os.html>
+
+use std::{
+    collections::{HashMap, VecDeque},
+    net::SocketAddr,
+    time::Duration,
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use snafu::{ResultExt, Snafu};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+    net::TcpStream,
+};
+
+use super::codec::{AmqpValue, DecodeError, Message, MessageBody};
+use crate::{
+    dns,
+    tls::{MaybeTlsSettings, MaybeTlsStream, TlsError},
+};
+
+const SASL_HEADER: &[u8; 8] = b"AMQP\x03\x01\x00\x00";
+const AMQP_HEADER: &[u8; 8] = b"AMQP\x00\x01\x00\x00";
+
+const FRAME_AMQP: u8 = 0x00;
+const FRAME_SASL: u8 = 0x01;
+
+/// A frame without a body, sent to keep connections alive.
+const EMPTY_FRAME: [u8; 8] = [0, 0, 0, 8, 2, FRAME_AMQP, 0, 0];
+
+/// The largest frame this client accepts, as advertised in its `open`.
+const MAX_FRAME_SIZE: u32 = 256 * 1024;
+/// The smallest frame size peers must accept.
+const MIN_MAX_FRAME_SIZE: u32 = 512;
+/// The number of incoming transfer frames the session accepts before granting more.
+const INCOMING_WINDOW: u32 = 5000;
+
+mod performative {
+    pub const OPEN: u64 = 0x10;
+    pub const BEGIN: u64 = 0x11;
+    pub const ATTACH: u64 = 0x12;
+    pub const FLOW: u64 = 0x13;
+    pub const TRANSFER: u64 = 0x14;
+    pub const DISPOSITION: u64 = 0x15;
+    pub const DETACH: u64 = 0x16;
+    pub const END: u64 = 0x17;
+    pub const CLOSE: u64 = 0x18;
+}
+
+mod sasl {
+    pub const MECHANISMS: u64 = 0x40;
+    pub const INIT: u64 = 0x41;
+    pub const OUTCOME: u64 = 0x44;
+}
+
+mod descriptor {
+    pub const ERROR: u64 = 0x1d;
+    pub const ACCEPTED: u64 = 0x24;
+    pub const REJECTED: u64 = 0x25;
+    pub const RELEASED: u64 = 0x26;
+    pub const MODIFIED: u64 = 0x27;
+    pub const SOURCE: u64 = 0x28;
+    pub const TARGET: u64 = 0x29;
+}
+
+/// Errors returned while talking to an AMQP 1.0 peer.
+#[derive(Debug, Snafu)]
+pub(crate) enum AmqpError {
+    #[snafu(display("Unable to connect: {}", source))]
+    Connect { source: TlsError },
+
+    #[snafu(display("Unable to resolve DNS: {}", source))]
+    Dns { source: dns::DnsError },
+
+    #[snafu(display("No addresses returned."))]
+    NoAddresses,
+
+    #[snafu(display("I/O error: {}", source))]
+    Io { source: std::io::Error },
+
+    #[snafu(display("Invalid frame: {}", source))]
+    Decode { source: DecodeError },
+
+    #[snafu(display("Protocol error while {}", context))]
+    Protocol { context: &'static str },
+
+    #[snafu(display("Authentication failed with SASL outcome {}", code))]
+    Sasl { code: u8 },
+
+    #[snafu(display("The request failed with status {}: {}", status, description))]
+    Request { status: u64, description: String },
+
+    #[snafu(display("The peer closed the {}: {}", what, error))]
+    Closed {
+        what: &'static str,
+        error: RemoteError,
+    },
+}
+
+impl From<std::io::Error> for AmqpError {
+    fn from(source: std::io::Error) -> Self {
+        Self::Io { source }
+    }
+}
+
+impl From<DecodeError> for AmqpError {
+    fn from(source: DecodeError) -> Self {
+        Self::Decode { source }
+    }
+}
+
+impl AmqpError {
+    /// Whether the operation could succeed on a new connection.
+    pub(crate) fn is_retriable(&self) -> bool {
+        match self {
+            Self::Connect { .. }
+            | Self::Dns { .. }
+            | Self::NoAddresses
+            | Self::Io { .. }
+            | Self::Decode { .. }
+            | Self::Protocol { .. } => true,
+            Self::Sasl { .. } => false,
+            Self::Request { status, .. } => *status == 429 || *status >= 500,
+            Self::Closed { error, .. } => error.is_retriable(),
+        }
+    }
+}
+
+/// An error condition sent by the peer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct RemoteError {
+    pub(crate) condition: String,
+    pub(crate) description: Option<String>,
+}
+
+impl RemoteError {
+    fn from_value(value: &AmqpValue) -> Option<Self> {
+        let (descriptor::ERROR, AmqpValue::List(fields)) = value.as_described()? else {
+            return None;
+        };
+        Some(Self {
+            condition: field(fields, 0).as_str().unwrap_or_default().to_owned(),
+            description: field(fields, 1).as_str().map(ToOwned::to_owned),
+        })
+    }
+
+    /// Whether the condition is transient.
+    pub(crate) fn is_retriable(&self) -> bool {
+        !matches!(
+            self.condition.as_str(),
+            "amqp:unauthorized-access"
+                | "amqp:not-found"
+                | "amqp:not-allowed"
+                | "amqp:not-implemented"
+                | "amqp:invalid-field"
+                | "amqp:decode-error"
+                | "amqp:link:message-size-exceeded"
+                | "amqp:link:stolen"
+                | "com.microsoft:argument-error"
+                | "com.microsoft:argument-out-of-range"
+        )
+    }
+}
+
+impl std::fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.description {
+            Some(description) => write!(f, "{} ({})", self.condition, description),
+            None => write!(f, "{}", self.condition),
+        }
+    }
+}
+
+/// The SASL mechanism used to authenticate the connection.
+#[derive(Clone, Debug)]
+pub(crate) enum SaslMechanism {
+    /// Used when the connection is authorized afterwards, for example through claims-based
+    /// security.
+    Anonymous,
+    Plain {
+        username: String,
+        password: String,
+    },
+}
+
+/// Connection parameters shared by every connection opened by a component.
+#[derive(Clone, Debug)]
+pub(crate) struct ConnectionOptions {
+    pub(crate) host: String,
+    pub(crate) port: u16,
+    pub(crate) tls: MaybeTlsSettings,
+    pub(crate) sasl: SaslMechanism,
+    pub(crate) container_id: String,
+}
+
+/// The outcome of a delivery, as settled by the receiver.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Outcome {
+    Accepted,
+    Rejected(RemoteError),
+    Released,
+    Modified,
+}
+
+impl Outcome {
+    fn from_state(state: &AmqpValue) -> Option<Self> {
+        match state.as_described()? {
+            (descriptor::ACCEPTED, _) => Some(Self::Accepted),
+            (descriptor::REJECTED, AmqpValue::List(fields)) => Some(Self::Rejected(
+                RemoteError::from_value(field(fields, 0)).unwrap_or_default(),
+            )),
+            (descriptor::RELEASED, _) => Some(Self::Released),
+            (descriptor::MODIFIED, _) => Some(Self::Modified),
+            _ => None,
+        }
+    }
+}
+
+/// A message received on a link.
+#[derive(Clone, Debug)]
+pub(crate) struct Delivery {
+    pub(crate) delivery_id: u32,
+    pub(crate) settled: bool,
+    pub(crate) payload: Bytes,
+}
+
+/// Identifies a link attached on a connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct LinkHandle(u32);
+
+#[derive(Debug, Default)]
+struct Link {
+    name: String,
+    receiver: bool,
+    remote_handle: Option<u32>,
+    remote_max_message_size: Option<u64>,
+    delivery_count: u32,
+    link_credit: u32,
+    /// The credit a receiver link is topped up to.
+    credit: u32,
+    /// A transfer spanning several frames, along with its delivery ID and settlement.
+    partial: Option<(u32, bool, BytesMut)>,
+    deliveries: VecDeque<Delivery>,
+    detached: Option<RemoteError>,
+}
+
+struct Frame {
+    kind: u8,
+    body: Option<(u64, Vec<AmqpValue>)>,
+    payload: Bytes,
+}
+
+/// An open connection, with a single session begun on channel 0.
+pub(crate) struct Connection<S> {
+    stream: BufReader<S>,
+    remote_max_frame_size: u32,
+    remote_idle_timeout: Option<Duration>,
+
+    next_outgoing_id: u32,
+    next_incoming_id: u32,
+    remote_incoming_window: u32,
+    incoming_window: u32,
+
+    next_delivery_id: u32,
+    next_handle: u32,
+    links: HashMap<u32, Link>,
+    remote_handles: HashMap<u32, u32>,
+    outcomes: HashMap<u32, Outcome>,
+}
+
+impl Connection<MaybeTlsStream<TcpStream>> {
+    pub(crate) async fn connect(options: &ConnectionOptions) -> Result<Self, AmqpError> {
+        let ip = dns::Resolver
+            .lookup_ip(options.host.clone())
+            .await
+            .context(DnsSnafu)?
+            .next()
+            .ok_or(AmqpError::NoAddresses)?;
+        let addr = SocketAddr::new(ip, options.port);
+
+        let stream = options
+            .tls
+            .connect(&options.host, &addr)
+            .await
+            .context(ConnectSnafu)?;
+
+        Self::handshake(stream, options).await
+    }
+}
+
+impl<S> Connection<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    async fn handshake(stream: S, options: &ConnectionOptions) -> Result<Self, AmqpError> {
+        let mut connection = Self {
+            stream: BufReader::new(stream),
+            remote_max_frame_size: MIN_MAX_FRAME_SIZE,
+            remote_idle_timeout: None,
+            next_outgoing_id: 0,
+            next_incoming_id: 0,
+            remote_incoming_window: 0,
+            incoming_window: INCOMING_WINDOW,
+            next_delivery_id: 0,
+            next_handle: 0,
+            links: HashMap::new(),
+            remote_handles: HashMap::new(),
+            outcomes: HashMap::new(),
+        };
+
+        connection.authenticate(options).await?;
+        connection.open(options).await?;
+        connection.begin().await?;
+        Ok(connection)
+    }
+
+    async fn exchange_headers(&mut self, header: &[u8; 8]) -> Result<(), AmqpError> {
+        self.write(header).await?;
+        let mut reply = [0; 8];
+        self.stream.read_exact(&mut reply).await?;
+        if &reply == header {
+            Ok(())
+        } else {
+            Err(AmqpError::Protocol {
+                context: "negotiating the protocol version",
+            })
+        }
+    }
+
+    async fn authenticate(&mut self, options: &ConnectionOptions) -> Result<(), AmqpError> {
+        self.exchange_headers(SASL_HEADER).await?;
+
+        let (code, _) = self.expect(FRAME_SASL, "reading SASL mechanisms").await?;
+        if code != sasl::MECHANISMS {
+            return Err(AmqpError::Protocol {
+                context: "reading SASL mechanisms",
+            });
+        }
+
+        let (mechanism, response) = match &options.sasl {
+            SaslMechanism::Anonymous => ("ANONYMOUS", AmqpValue::Null),
+            SaslMechanism::Plain { username, password } => (
+                "PLAIN",
+                AmqpValue::Binary(Bytes::from(format!("\0{username}\0{password}"))),
+            ),
+        };
+        self.write_frame(
+            FRAME_SASL,
+            sasl::INIT,
+            vec![
+                AmqpValue::symbol(mechanism),
+                response,
+                AmqpValue::string(options.host.as_str()),
+            ],
+            &[],
+        )
+        .await?;
+
+        let (code, fields) = self.expect(FRAME_SASL, "reading the SASL outcome").await?;
+        if code != sasl::OUTCOME {
+            return Err(AmqpError::Protocol {
+                context: "reading the SASL outcome",
+            });
+        }
+        match field(&fields, 0).as_u64() {
+            Some(0) => {}
+            code => {
+                return Err(AmqpError::Sasl {
+                    code: code
+                        .and_then(|code| u8::try_from(code).ok())
+                        .unwrap_or(u8::MAX),
+                })
+            }
+        }
+
+        self.exchange_headers(AMQP_HEADER).await
+    }
+
+    async fn open(&mut self, options: &ConnectionOptions) -> Result<(), AmqpError> {
+        self.write_frame(
+            FRAME_AMQP,
+            performative::OPEN,
+            vec![
+                AmqpValue::string(options.container_id.as_str()),
+                AmqpValue::string(options.host.as_str()),
+                AmqpValue::Uint(MAX_FRAME_SIZE),
+                AmqpValue::Ushort(0),
+            ],
+            &[],
+        )
+        .await?;
+
+        let (code, fields) = self.expect(FRAME_AMQP, "opening the connection").await?;
+        match code {
+            performative::OPEN => {
+                self.remote_max_frame_size = field(&fields, 2)
+                    .as_u64()
+                    .map_or(u32::MAX, |size| u32::try_from(size).unwrap_or(u32::MAX))
+                    .max(MIN_MAX_FRAME_SIZE);
+                self.remote_idle_timeout = field(&fields, 4)
+                    .as_u64()
+                    .filter(|timeout| *timeout > 0)
+                    .map(Duration::from_millis);
+                Ok(())
+            }
+            performative::CLOSE => Err(closed("connection", &fields, 0)),
+            _ => Err(AmqpError::Protocol {
+                context: "opening the connection",
+            }),
+        }
+    }
+
+    async fn begin(&mut self) -> Result<(), AmqpError> {
+        self.write_frame(
+            FRAME_AMQP,
+            performative::BEGIN,
+            vec![
+                AmqpValue::Null,
+                AmqpValue::Uint(self.next_outgoing_id),
+                AmqpValue::Uint(self.incoming_window),
+                AmqpValue::Uint(u32::MAX),
+            ],
+            &[],
+        )
+        .await?;
+
+        loop {
+            let (code, fields) = self.expect(FRAME_AMQP, "beginning the session").await?;
+            match code {
+                performative::BEGIN => {
+                    self.next_incoming_id = field(&fields, 1).as_u64().unwrap_or_default() as u32;
+                    self.remote_incoming_window =
+                        field(&fields, 2).as_u64().unwrap_or_default() as u32;
+                    return Ok(());
+                }
+                performative::CLOSE => return Err(closed("connection", &fields, 0)),
+                performative::END => return Err(closed("session", &fields, 0)),
+                _ => {}
+            }
+        }
+    }
+
+    /// The idle timeout requested by the peer, after which it closes connections that didn't
+    /// send any frame.
+    pub(crate) const fn remote_idle_timeout(&self) -> Option<Duration> {
+        self.remote_idle_timeout
+    }
+
+    /// The largest message the receiving end of a sender link accepts, if it announced any.
+    pub(crate) fn max_message_size(&self, link: LinkHandle) -> Option<u64> {
+        self.links
+            .get(&link.0)
+            .and_then(|link| link.remote_max_message_size)
+            .filter(|size| *size > 0)
+    }
+
+    /// Attaches a link sending messages to the given address.
+    pub(crate) async fn attach_sender(
+        &mut self,
+        name: &str,
+        address: &str,
+    ) -> Result<LinkHandle, AmqpError> {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.links.insert(
+            handle,
+            Link {
+                name: name.to_owned(),
+                ..Default::default()
+            },
+        );
+
+        self.write_frame(
+            FRAME_AMQP,
+            performative::ATTACH,
+            vec![
+                AmqpValue::string(name),
+                AmqpValue::Uint(handle),
+                AmqpValue::Bool(false),
+                AmqpValue::Ubyte(0), // unsettled
+                AmqpValue::Ubyte(0), // first
+                AmqpValue::described(
+                    descriptor::SOURCE,
+                    AmqpValue::List(vec![AmqpValue::string(name)]),
+                ),
+                AmqpValue::described(
+                    descriptor::TARGET,
+                    AmqpValue::List(vec![AmqpValue::string(address)]),
+                ),
+                AmqpValue::Null,
+                AmqpValue::Bool(false),
+                AmqpValue::Uint(0),
+            ],
+            &[],
+        )
+        .await?;
+
+        self.wait_for_attach(handle).await
+    }
+
+    /// Attaches a link receiving messages from the given address, granting it `credit` messages.
+    pub(crate) async fn attach_receiver(
+        &mut self,
+        name: &str,
+        address: &str,
+        filter: Option<AmqpValue>,
+        properties: Vec<(AmqpValue, AmqpValue)>,
+        credit: u32,
+    ) -> Result<LinkHandle, AmqpError> {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.links.insert(
+            handle,
+            Link {
+                name: name.to_owned(),
+                receiver: true,
+                credit,
+                ..Default::default()
+            },
+        );
+
+        let mut source = vec![AmqpValue::string(address)];
+        if let Some(filter) = filter {
+            source.extend([
+                AmqpValue::Null, // durable
+                AmqpValue::Null, // expiry-policy
+                AmqpValue::Null, // timeout
+                AmqpValue::Null, // dynamic
+                AmqpValue::Null, // dynamic-node-properties
+                AmqpValue::Null, // distribution-mode
+                filter,
+            ]);
+        }
+        let mut fields = vec![
+            AmqpValue::string(name),
+            AmqpValue::Uint(handle),
+            AmqpValue::Bool(true),
+            AmqpValue::Ubyte(2), // mixed
+            AmqpValue::Ubyte(0), // first
+            AmqpValue::described(descriptor::SOURCE, AmqpValue::List(source)),
+            AmqpValue::described(
+                descriptor::TARGET,
+                AmqpValue::List(vec![AmqpValue::string(name)]),
+            ),
+        ];
+        if !properties.is_empty() {
+            fields.extend([
+                AmqpValue::Null,        // unsettled
+                AmqpValue::Bool(false), // incomplete-unsettled
+                AmqpValue::Null,        // initial-delivery-count
+                AmqpValue::Null,        // max-message-size
+                AmqpValue::Null,        // offered-capabilities
+                AmqpValue::Null,        // desired-capabilities
+                AmqpValue::Map(properties),
+            ]);
+        }
+        self.write_frame(FRAME_AMQP, performative::ATTACH, fields, &[])
+            .await?;
+
+        let link = self.wait_for_attach(handle).await?;
+        self.grant_credit(link, credit).await?;
+        Ok(link)
+    }
+
+    async fn wait_for_attach(&mut self, handle: u32) -> Result<LinkHandle, AmqpError> {
+        loop {
+            let link = &self.links[&handle];
+            if let Some(error) = &link.detached {
+                return Err(AmqpError::Closed {
+                    what: "link",
+                    error: error.clone(),
+                });
+            }
+            if link.remote_handle.is_some() {
+                return Ok(LinkHandle(handle));
+            }
+            self.process_frame().await?;
+        }
+    }
+
+    /// Sends a message on a sender link and waits for the receiver to settle it.
+    pub(crate) async fn send(
+        &mut self,
+        link: LinkHandle,
+        message: &[u8],
+        message_format: u32,
+    ) -> Result<Outcome, AmqpError> {
+        loop {
+            let state = self.link(link)?;
+            if state.link_credit > 0 {
+                break;
+            }
+            self.process_frame().await?;
+        }
+
+        let delivery_id = self.next_delivery_id;
+        self.next_delivery_id = self.next_delivery_id.wrapping_add(1);
+
+        let mut remaining = message;
+        let mut first = true;
+        loop {
+            while self.remote_incoming_window == 0 {
+                self.process_frame().await?;
+            }
+
+            let fields = if first {
+                vec![
+                    AmqpValue::Uint(link.0),
+                    AmqpValue::Uint(delivery_id),
+                    AmqpValue::Binary(Bytes::copy_from_slice(&delivery_id.to_be_bytes())),
+                    AmqpValue::Uint(message_format),
+                    AmqpValue::Bool(false),
+                    AmqpValue::Bool(true),
+                ]
+            } else {
+                vec![
+                    AmqpValue::Uint(link.0),
+                    AmqpValue::Null,
+                    AmqpValue::Null,
+                    AmqpValue::Null,
+                    AmqpValue::Null,
+                    AmqpValue::Bool(true),
+                ]
+            };
+            // Leave room for the frame header and the transfer performative.
+            let header_len =
+                8 + AmqpValue::described(performative::TRANSFER, AmqpValue::List(fields.clone()))
+                    .to_bytes()
+                    .len();
+            let room = (self.remote_max_frame_size as usize).saturating_sub(header_len);
+            let (chunk, rest) = remaining.split_at(room.min(remaining.len()));
+            let mut fields = fields;
+            fields[5] = AmqpValue::Bool(!rest.is_empty());
+
+            self.write_frame(FRAME_AMQP, performative::TRANSFER, fields, chunk)
+                .await?;
+            self.next_outgoing_id = self.next_outgoing_id.wrapping_add(1);
+            self.remote_incoming_window -= 1;
+
+            remaining = rest;
+            first = false;
+            if remaining.is_empty() {
+                break;
+            }
+        }
+
+        let state = self.link_mut(link)?;
+        state.delivery_count = state.delivery_count.wrapping_add(1);
+        state.link_credit -= 1;
+
+        loop {
+            if let Some(outcome) = self.outcomes.remove(&delivery_id) {
+                return Ok(outcome);
+            }
+            self.link(link)?;
+            self.process_frame().await?;
+        }
+    }
+
+    /// Waits for the next message on a receiver link, topping its credit up as needed.
+    pub(crate) async fn receive(&mut self, link: LinkHandle) -> Result<Delivery, AmqpError> {
+        loop {
+            let state = self.link_mut(link)?;
+            if let Some(delivery) = state.deliveries.pop_front() {
+                if state.link_credit <= state.credit / 2 {
+                    let credit = state.credit;
+                    self.grant_credit(link, credit).await?;
+                }
+                return Ok(delivery);
+            }
+            self.process_frame().await?;
+        }
+    }
+
+    /// Accepts and settles a delivery received on a link.
+    pub(crate) async fn accept(&mut self, delivery_id: u32) -> Result<(), AmqpError> {
+        self.write_frame(
+            FRAME_AMQP,
+            performative::DISPOSITION,
+            vec![
+                AmqpValue::Bool(true),
+                AmqpValue::Uint(delivery_id),
+                AmqpValue::Null,
+                AmqpValue::Bool(true),
+                AmqpValue::described(descriptor::ACCEPTED, AmqpValue::List(Vec::new())),
+            ],
+            &[],
+        )
+        .await
+    }
+
+    /// Sends a request to a node following the AMQP request-response pattern, returning the
+    /// response if its status code reports success.
+    pub(crate) async fn request(
+        &mut self,
+        node: &str,
+        mut request: Message,
+    ) -> Result<Message, AmqpError> {
+        let sender_name = format!("{node}-sender");
+        let receiver_name = format!("{node}-receiver");
+        let sender = self.attach_sender(&sender_name, node).await?;
+        let receiver = self
+            .attach_receiver(&receiver_name, node, None, Vec::new(), 1)
+            .await?;
+
+        let properties = request.properties.get_or_insert_with(Default::default);
+        properties.message_id = Some(AmqpValue::Ulong(u64::from(self.next_delivery_id)));
+        properties.reply_to = Some(receiver_name);
+        self.send(sender, &request.to_bytes(), 0).await?;
+
+        let delivery = self.receive(receiver).await?;
+        if !delivery.settled {
+            self.accept(delivery.delivery_id).await?;
+        }
+        self.detach(sender).await?;
+        self.detach(receiver).await?;
+
+        let response = Message::decode(delivery.payload)?;
+        let status = response
+            .application_property("status-code")
+            .and_then(AmqpValue::as_u64)
+            .unwrap_or_default();
+        if (200..300).contains(&status) {
+            return Ok(response);
+        }
+        let description = response
+            .application_property("status-description")
+            .and_then(AmqpValue::as_str)
+            .or(match &response.body {
+                MessageBody::Value(value) => value.as_str(),
+                _ => None,
+            })
+            .unwrap_or_default()
+            .to_owned();
+        Err(AmqpError::Request {
+            status,
+            description,
+        })
+    }
+
+    async fn grant_credit(&mut self, link: LinkHandle, credit: u32) -> Result<(), AmqpError> {
+        let state = self.link_mut(link)?;
+        state.link_credit = credit;
+        let delivery_count = state.delivery_count;
+        self.write_session_flow(Some((link.0, delivery_count, credit)))
+            .await
+    }
+
+    async fn write_session_flow(&mut self, link: Option<(u32, u32, u32)>) -> Result<(), AmqpError> {
+        let mut fields = vec![
+            AmqpValue::Uint(self.next_incoming_id),
+            AmqpValue::Uint(self.incoming_window),
+            AmqpValue::Uint(self.next_outgoing_id),
+            AmqpValue::Uint(u32::MAX),
+        ];
+        if let Some((handle, delivery_count, credit)) = link {
+            fields.extend([
+                AmqpValue::Uint(handle),
+                AmqpValue::Uint(delivery_count),
+                AmqpValue::Uint(credit),
+            ]);
+        }
+        self.write_frame(FRAME_AMQP, performative::FLOW, fields, &[])
+            .await
+    }
+
+    /// Detaches a link, without waiting for the peer to confirm it.
+    pub(crate) async fn detach(&mut self, link: LinkHandle) -> Result<(), AmqpError> {
+        self.links.remove(&link.0);
+        self.remote_handles.retain(|_, handle| *handle != link.0);
+        self.write_frame(
+            FRAME_AMQP,
+            performative::DETACH,
+            vec![AmqpValue::Uint(link.0), AmqpValue::Bool(true)],
+            &[],
+        )
+        .await
+    }
+
+    /// Closes the connection, without waiting for the peer to confirm it.
+    pub(crate) async fn close(mut self) -> Result<(), AmqpError> {
+        self.write_frame(FRAME_AMQP, performative::CLOSE, Vec::new(), &[])
+            .await
+    }
+
+    fn link(&self, link: LinkHandle) -> Result<&Link, AmqpError> {
+        let state = self.links.get(&link.0).ok_or(AmqpError::Protocol {
+            context: "using a link that isn't attached",
+        })?;
+        match &state.detached {
+            Some(error) => Err(AmqpError::Closed {
+                what: "link",
+                error: error.clone(),
+            }),
+            None => Ok(state),
+        }
+    }
+
+    fn link_mut(&mut self, link: LinkHandle) -> Result<&mut Link, AmqpError> {
+        self.link(link)?;
+        Ok(self.links.get_mut(&link.0).expect("link exists"))
+    }
+
+    /// Reads a single frame and updates the state of the session and its links with it.
+    async fn process_frame(&mut self) -> Result<(), AmqpError> {
+        self.wait_readable().await?;
+        let frame = self.read_frame().await?;
+        let Some((code, fields)) = frame.body else {
+            // An empty frame, which only keeps the connection alive.
+            return Ok(());
+        };
+        if frame.kind != FRAME_AMQP {
+            return Err(AmqpError::Protocol {
+                context: "reading a frame",
+            });
+        }
+
+        match code {
+            performative::ATTACH => {
+                let name = field(&fields, 0).as_str().unwrap_or_default();
+                let remote_handle = field(&fields, 1).as_u64().unwrap_or_default() as u32;
+                if let Some((handle, link)) = self
+                    .links
+                    .iter_mut()
+                    .find(|(_, link)| link.name == name && link.remote_handle.is_none())
+                {
+                    link.remote_handle = Some(remote_handle);
+                    link.remote_max_message_size = field(&fields, 10).as_u64();
+                    if link.receiver {
+                        link.delivery_count = field(&fields, 9).as_u64().unwrap_or_default() as u32;
+                    }
+                    self.remote_handles.insert(remote_handle, *handle);
+                }
+            }
+            performative::FLOW => {
+                let next_incoming_id = field(&fields, 0).as_u64().map_or(0, |id| id as u32);
+                let incoming_window = field(&fields, 1).as_u64().unwrap_or_default() as u32;
+                self.remote_incoming_window = next_incoming_id
+                    .wrapping_add(incoming_window)
+                    .wrapping_sub(self.next_outgoing_id);
+
+                let handle = field(&fields, 4)
+                    .as_u64()
+                    .and_then(|handle| self.remote_handles.get(&(handle as u32)));
+                if let Some(link) = handle.and_then(|handle| self.links.get_mut(handle)) {
+                    if !link.receiver {
+                        let delivery_count =
+                            field(&fields, 5).as_u64().map_or(0, |count| count as u32);
+                        let credit = field(&fields, 6).as_u64().unwrap_or_default() as u32;
+                        link.link_credit = delivery_count
+                            .wrapping_add(credit)
+                            .wrapping_sub(link.delivery_count);
+                    }
+                }
+            }
+            performative::TRANSFER => {
+                self.next_incoming_id = self.next_incoming_id.wrapping_add(1);
+                self.incoming_window = self.incoming_window.saturating_sub(1);
+
+                let handle = field(&fields, 0)
+                    .as_u64()
+                    .and_then(|handle| self.remote_handles.get(&(handle as u32)))
+                    .copied();
+                if let Some(link) = handle.and_then(|handle| self.links.get_mut(&handle)) {
+                    let (delivery_id, settled, mut payload) = link.partial.take().unwrap_or((
+                        field(&fields, 1).as_u64().unwrap_or_default() as u32,
+                        field(&fields, 4).as_bool().unwrap_or_default(),
+                        BytesMut::new(),
+                    ));
+                    payload.put_slice(&frame.payload);
+
+                    if field(&fields, 5).as_bool().unwrap_or_default() {
+                        link.partial = Some((delivery_id, settled, payload));
+                    } else if !field(&fields, 9).as_bool().unwrap_or_default() {
+                        link.delivery_count = link.delivery_count.wrapping_add(1);
+                        link.link_credit = link.link_credit.saturating_sub(1);
+                        link.deliveries.push_back(Delivery {
+                            delivery_id,
+                            settled,
+                            payload: payload.freeze(),
+                        });
+                    }
+                }
+
+                if self.incoming_window <= INCOMING_WINDOW / 2 {
+                    self.incoming_window = INCOMING_WINDOW;
+                    self.write_session_flow(None).await?;
+                }
+            }
+            performative::DISPOSITION => {
+                let first = field(&fields, 1).as_u64().unwrap_or_default() as u32;
+                let last = field(&fields, 2).as_u64().map_or(first, |last| last as u32);
+                if let Some(outcome) = Outcome::from_state(field(&fields, 4)) {
+                    let mut id = first;
+                    loop {
+                        self.outcomes.insert(id, outcome.clone());
+                        if id == last {
+                            break;
+                        }
+                        id = id.wrapping_add(1);
+                    }
+                }
+            }
+            performative::DETACH => {
+                let remote_handle = field(&fields, 0).as_u64().unwrap_or_default() as u32;
+                let error = RemoteError::from_value(field(&fields, 2)).unwrap_or(RemoteError {
+                    condition: "amqp:link:detach-forced".to_owned(),
+                    description: None,
+                });
+                // A link that's refused is detached before being fully attached.
+                let handle = self.remote_handles.remove(&remote_handle).or_else(|| {
+                    self.links
+                        .iter()
+                        .find(|(_, link)| link.remote_handle == Some(remote_handle))
+                        .map(|(handle, _)| *handle)
+                });
+                if let Some(link) = handle.and_then(|handle| self.links.get_mut(&handle)) {
+                    link.detached = Some(error);
+                }
+            }
+            performative::END => return Err(closed("session", &fields, 0)),
+            performative::CLOSE => return Err(closed("connection", &fields, 0)),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Waits for the peer to send data, sending empty frames often enough to keep the connection
+    /// from reaching the peer's idle timeout in the meantime.
+    async fn wait_readable(&mut self) -> Result<(), AmqpError> {
+        let Some(idle_timeout) = self.remote_idle_timeout else {
+            return Ok(());
+        };
+        // Waiting for the stream to fill its buffer can be cancelled without losing data, unlike
+        // reading a frame.
+        while tokio::time::timeout(idle_timeout / 2, self.stream.fill_buf())
+            .await
+            .is_err()
+        {
+            self.write(&EMPTY_FRAME).await?;
+        }
+        Ok(())
+    }
+
+    /// Reads frames until one with a body is found, returning its performative.
+    async fn expect(
+        &mut self,
+        kind: u8,
+        context: &'static str,
+    ) -> Result<(u64, Vec<AmqpValue>), AmqpError> {
+        loop {
+            let frame = self.read_frame().await?;
+            match frame.body {
+                Some(body) if frame.kind == kind => return Ok(body),
+                Some(_) => return Err(AmqpError::Protocol { context }),
+                None => {}
+            }
+        }
+    }
+
+    async fn read_frame(&mut self) -> Result<Frame, AmqpError> {
+        let size = self.stream.read_u32().await?;
+        if !(8..=MAX_FRAME_SIZE).contains(&size) {
+            return Err(AmqpError::Protocol {
+                context: "reading the frame size",
+            });
+        }
+        let mut frame = vec![0; size as usize - 4];
+        self.stream.read_exact(&mut frame).await?;
+        let mut frame = Bytes::from(frame);
+
+        let data_offset = frame[0] as usize * 4;
+        let kind = frame[1];
+        if data_offset < 8 || data_offset > size as usize {
+            return Err(AmqpError::Protocol {
+                context: "reading the frame header",
+            });
+        }
+        let mut body = frame.split_off(data_offset - 4);
+        if body.is_empty() {
+            return Ok(Frame {
+                kind,
+                body: None,
+                payload: Bytes::new(),
+            });
+        }
+
+        let performative = AmqpValue::decode(&mut body)?;
+        let Some((code, AmqpValue::List(fields))) = performative.as_described() else {
+            return Err(AmqpError::Protocol {
+                context: "reading a performative",
+            });
+        };
+        Ok(Frame {
+            kind,
+            body: Some((code, fields.clone())),
+            payload: body,
+        })
+    }
+
+    async fn write_frame(
+        &mut self,
+        kind: u8,
+        code: u64,
+        fields: Vec<AmqpValue>,
+        payload: &[u8],
+    ) -> Result<(), AmqpError> {
+        let mut body = BytesMut::new();
+        AmqpValue::described(code, AmqpValue::List(fields)).encode(&mut body);
+
+        let mut frame = BytesMut::with_capacity(8 + body.len() + payload.len());
+        frame.put_u32((8 + body.len() + payload.len()) as u32);
+        frame.put_u8(2); // data offset, in 4 byte words
+        frame.put_u8(kind);
+        frame.put_u16(0); // channel
+        frame.put_slice(&body);
+        frame.put_slice(payload);
+        self.write(&frame).await
+    }
+
+    async fn write(&mut self, buf: &[u8]) -> Result<(), AmqpError> {
+        let stream = self.stream.get_mut();
+        stream.write_all(buf).await?;
+        stream.flush().await?;
+        Ok(())
+    }
+}
+
+fn field(fields: &[AmqpValue], index: usize) -> &AmqpValue {
+    fields.get(index).unwrap_or(&AmqpValue::Null)
+}
+
+fn closed(what: &'static str, fields: &[AmqpValue], index: usize) -> AmqpError {
+    AmqpError::Closed {
+        what,
+        error: RemoteError::from_value(field(fields, index)).unwrap_or(RemoteError {
+            condition: "amqp:connection:forced".to_owned(),
+            description: None,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{duplex, DuplexStream};
+
+    use super::*;
+
+    /// The peer side of a connection, reading and writing raw frames.
+    struct Peer(DuplexStream);
+
+    impl Peer {
+        async fn expect_header(&mut self, header: &[u8; 8]) {
+            let mut received = [0; 8];
+            self.0.read_exact(&mut received).await.unwrap();
+            assert_eq!(&received, header);
+            self.0.write_all(header).await.unwrap();
+        }
+
+        async fn read(&mut self) -> (u64, Vec<AmqpValue>, Bytes) {
+            let size = self.0.read_u32().await.unwrap();
+            let mut frame = vec![0; size as usize - 4];
+            self.0.read_exact(&mut frame).await.unwrap();
+            let mut body = Bytes::from(frame).split_off(4);
+            let performative = AmqpValue::decode(&mut body).unwrap();
+            let (code, AmqpValue::List(fields)) = performative.as_described().unwrap() else {
+                panic!("invalid performative");
+            };
+            (code, fields.clone(), body)
+        }
+
+        async fn write(&mut self, kind: u8, code: u64, fields: Vec<AmqpValue>, payload: &[u8]) {
+            let body = AmqpValue::described(code, AmqpValue::List(fields)).to_bytes();
+            let mut frame = BytesMut::new();
+            frame.put_u32((8 + body.len() + payload.len()) as u32);
+            frame.put_slice(&[2, kind, 0, 0]);
+            frame.put_slice(&body);
+            frame.put_slice(payload);
+            self.0.write_all(&frame).await.unwrap();
+        }
+
+        async fn handshake(&mut self, max_frame_size: u32) {
+            self.expect_header(SASL_HEADER).await;
+            self.write(
+                FRAME_SASL,
+                sasl::MECHANISMS,
+                vec![AmqpValue::Array(vec![AmqpValue::symbol("ANONYMOUS")])],
+                &[],
+            )
+            .await;
+            let (code, fields, _) = self.read().await;
+            assert_eq!(code, sasl::INIT);
+            assert_eq!(fields[0], AmqpValue::symbol("ANONYMOUS"));
+            self.write(FRAME_SASL, sasl::OUTCOME, vec![AmqpValue::Ubyte(0)], &[])
+                .await;
+            self.expect_header(AMQP_HEADER).await;
+
+            let (code, _, _) = self.read().await;
+            assert_eq!(code, performative::OPEN);
+            self.write(
+                FRAME_AMQP,
+                performative::OPEN,
+                vec![
+                    AmqpValue::string("peer"),
+                    AmqpValue::Null,
+                    AmqpValue::Uint(max_frame_size),
+                ],
+                &[],
+            )
+            .await;
+
+            let (code, _, _) = self.read().await;
+            assert_eq!(code, performative::BEGIN);
+            self.write(
+                FRAME_AMQP,
+                performative::BEGIN,
+                vec![
+                    AmqpValue::Ushort(0),
+                    AmqpValue::Uint(1),
+                    AmqpValue::Uint(100),
+                    AmqpValue::Uint(100),
+                ],
+                &[],
+            )
+            .await;
+        }
+
+        /// Answers the attach of a link, returning the handle the client picked for it.
+        async fn attach(&mut self, remote_handle: u32, max_message_size: u64) -> u32 {
+            let (code, fields, _) = self.read().await;
+            assert_eq!(code, performative::ATTACH);
+            let handle = fields[1].as_u64().unwrap() as u32;
+            let receiver = fields[2].as_bool().unwrap();
+            let mut reply = fields.clone();
+            reply[1] = AmqpValue::Uint(remote_handle);
+            reply[2] = AmqpValue::Bool(!receiver);
+            reply.resize(11, AmqpValue::Null);
+            reply[9] = AmqpValue::Uint(0);
+            reply[10] = AmqpValue::Ulong(max_message_size);
+            self.write(FRAME_AMQP, performative::ATTACH, reply, &[])
+                .await;
+            handle
+        }
+    }
+
+    fn options() -> ConnectionOptions {
+        ConnectionOptions {
+            host: "localhost".into(),
+            port: 5671,
+            tls: MaybeTlsSettings::Raw(()),
+            sasl: SaslMechanism::Anonymous,
+            container_id: "vector".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_messages_split_across_frames() {
+        let (client, server) = duplex(64 * 1024);
+        let mut peer = Peer(server);
+
+        let server = tokio::spawn(async move {
+            peer.handshake(MIN_MAX_FRAME_SIZE).await;
+            peer.attach(7, 1024).await;
+            peer.write(
+                FRAME_AMQP,
+                performative::FLOW,
+                vec![
+                    AmqpValue::Uint(0),
+                    AmqpValue::Uint(100),
+                    AmqpValue::Uint(1),
+                    AmqpValue::Uint(100),
+                    AmqpValue::Uint(7),
+                    AmqpValue::Uint(0),
+                    AmqpValue::Uint(10),
+                ],
+                &[],
+            )
+            .await;
+
+            let mut message = BytesMut::new();
+            loop {
+                let (code, fields, payload) = peer.read().await;
+                assert_eq!(code, performative::TRANSFER);
+                message.put_slice(&payload);
+                if !fields[5].as_bool().unwrap() {
+                    break;
+                }
+            }
+            peer.write(
+                FRAME_AMQP,
+                performative::DISPOSITION,
+                vec![
+                    AmqpValue::Bool(true),
+                    AmqpValue::Uint(0),
+                    AmqpValue::Null,
+                    AmqpValue::Bool(true),
+                    AmqpValue::described(descriptor::ACCEPTED, AmqpValue::List(vec![])),
+                ],
+                &[],
+            )
+            .await;
+            message
+        });
+
+        let mut connection = Connection::handshake(client, &options()).await.unwrap();
+        let link = connection.attach_sender("sender", "hub").await.unwrap();
+        assert_eq!(connection.max_message_size(link), Some(1024));
+
+        let message = vec![42; 2000];
+        let outcome = connection.send(link, &message, 0).await.unwrap();
+
+        assert_eq!(outcome, Outcome::Accepted);
+        assert_eq!(server.await.unwrap(), message);
+    }
+
+    #[tokio::test]
+    async fn receives_messages() {
+        let (client, server) = duplex(64 * 1024);
+        let mut peer = Peer(server);
+
+        let server = tokio::spawn(async move {
+            peer.handshake(MAX_FRAME_SIZE).await;
+            peer.attach(3, 0).await;
+            let (code, fields, _) = peer.read().await;
+            assert_eq!(code, performative::FLOW);
+            assert_eq!(fields[6], AmqpValue::Uint(10));
+
+            for (delivery_id, payload) in [(1, &b"first"[..]), (2, &b"second"[..])] {
+                peer.write(
+                    FRAME_AMQP,
+                    performative::TRANSFER,
+                    vec![
+                        AmqpValue::Uint(3),
+                        AmqpValue::Uint(delivery_id),
+                        AmqpValue::Binary(Bytes::from_static(b"tag")),
+                        AmqpValue::Uint(0),
+                        AmqpValue::Bool(true),
+                    ],
+                    payload,
+                )
+                .await;
+            }
+            peer
+        });
+
+        let mut connection = Connection::handshake(client, &options()).await.unwrap();
+        let link = connection
+            .attach_receiver("receiver", "hub/Partitions/0", None, Vec::new(), 10)
+            .await
+            .unwrap();
+
+        let first = connection.receive(link).await.unwrap();
+        let second = connection.receive(link).await.unwrap();
+
+        assert_eq!(first.payload, Bytes::from_static(b"first"));
+        assert!(first.settled);
+        assert_eq!(second.delivery_id, 2);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reports_refused_links() {
+        let (client, server) = duplex(64 * 1024);
+        let mut peer = Peer(server);
+
+        let server = tokio::spawn(async move {
+            peer.handshake(MAX_FRAME_SIZE).await;
+            peer.attach(0, 0).await;
+            peer.write(
+                FRAME_AMQP,
+                performative::DETACH,
+                vec![
+                    AmqpValue::Uint(0),
+                    AmqpValue::Bool(true),
+                    AmqpValue::described(
+                        descriptor::ERROR,
+                        AmqpValue::List(vec![
+                            AmqpValue::symbol("amqp:not-found"),
+                            AmqpValue::string("no such hub"),
+                        ]),
+                    ),
+                ],
+                &[],
+            )
+            .await;
+            peer
+        });
+
+        let mut connection = Connection::handshake(client, &options()).await.unwrap();
+        let link = connection.attach_sender("sender", "missing").await.unwrap();
+        let error = connection.send(link, b"message", 0).await.unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "The peer closed the link: amqp:not-found (no such hub)"
+        );
+        assert!(!error.is_retriable());
+        server.await.unwrap();
+    }
+}
@@ -0,0 +1,399 @@
+//! Functionality shared by the Azure Event Hubs components.
+//!
+//! Event Hubs are accessed over AMQP 1.0, with a minimal client implemented in [`connection`].
+//! Connections are authorized through claims-based security, by putting either a shared access
+//! signature or a Microsoft Entra ID token on the connection for the Event Hub being accessed.
+
+pub(crate) mod auth;
+pub(crate) mod codec;
+pub(crate) mod connection;
+
+use std::time::{Duration, SystemTime};
+
+use snafu::Snafu;
+use tokio::net::TcpStream;
+use vector_lib::{configurable::configurable_component, sensitive_string::SensitiveString};
+
+use self::{
+    auth::{AuthError, Credentials},
+    codec::{AmqpValue, Message, MessageBody},
+    connection::{AmqpError, Connection, ConnectionOptions, SaslMechanism},
+};
+use crate::tls::{MaybeTlsSettings, MaybeTlsStream, TlsConfig};
+
+const AMQPS_PORT: u16 = 5671;
+const AMQP_PORT: u16 = 5672;
+
+/// The node answering requests about the Event Hub.
+const MANAGEMENT_NODE: &str = "$management";
+
+/// How long before their authorization expires connections are authorized again.
+const REAUTHORIZE_BEFORE: Duration = Duration::from_secs(5 * 60);
+
+pub(crate) type EventHubsConnection = Connection<MaybeTlsStream<TcpStream>>;
+
+#[derive(Debug, Snafu)]
+pub(crate) enum ConfigError {
+    #[snafu(display("Only one of `connection_string` or `namespace` can be set"))]
+    ConflictingAuthentication,
+    #[snafu(display("Either `connection_string` or `namespace` must be set"))]
+    MissingAuthentication,
+    #[snafu(display("Invalid connection string: {}", reason))]
+    InvalidConnectionString { reason: &'static str },
+    #[snafu(display(
+        "`event_hub_name` must be set when the connection string has no `EntityPath`"
+    ))]
+    MissingEventHubName,
+    #[snafu(display(
+        "`event_hub_name` {:?} does not match the connection string's `EntityPath` {:?}",
+        event_hub_name,
+        entity_path
+    ))]
+    ConflictingEventHubName {
+        event_hub_name: String,
+        entity_path: String,
+    },
+}
+
+/// Connection settings for an Azure Event Hub.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct EventHubsConnectionConfig {
+    /// The connection string of the Event Hubs namespace, or of the Event Hub itself.
+    ///
+    /// The connection string authenticates with a shared access key or signature. Connection
+    /// strings for the Event Hubs emulator, which set `UseDevelopmentEmulator=true`, connect over
+    /// plaintext AMQP.
+    ///
+    /// Either `connection_string` or `namespace` must be set.
+    #[configurable(metadata(
+        docs::examples = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=RootManageSharedAccessKey;SharedAccessKey=${EVENT_HUBS_KEY};EntityPath=myhub"
+    ))]
+    pub connection_string: Option<SensitiveString>,
+
+    /// The fully qualified host name of the Event Hubs namespace.
+    ///
+    /// When set, access is authorized with Microsoft Entra ID, using credentials from the
+    /// environment, a managed identity or the Azure CLI.
+    ///
+    /// Either `connection_string` or `namespace` must be set.
+    #[configurable(metadata(docs::examples = "mynamespace.servicebus.windows.net"))]
+    pub namespace: Option<String>,
+
+    /// The name of the Event Hub.
+    ///
+    /// Required unless the connection string contains an `EntityPath`.
+    #[configurable(metadata(docs::examples = "myhub"))]
+    pub event_hub_name: Option<String>,
+
+    /// The address to connect to instead of the namespace's host, as `host:port`.
+    ///
+    /// The namespace's host name is still used to authorize access, so this can point to a
+    /// proxy or a private endpoint.
+    #[configurable(metadata(docs::examples = "10.0.0.4:5671"))]
+    pub endpoint: Option<String>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+/// The parts of an Event Hubs connection string used by Vector.
+#[derive(Debug, Default, PartialEq)]
+struct ConnectionString {
+    host: String,
+    key_name: Option<String>,
+    key: Option<String>,
+    shared_access_signature: Option<String>,
+    entity_path: Option<String>,
+    development_emulator: bool,
+}
+
+impl ConnectionString {
+    fn parse(connection_string: &str) -> Result<Self, ConfigError> {
+        let mut parsed = Self::default();
+        for pair in connection_string.split(';').filter(|pair| !pair.is_empty()) {
+            let (key, value) =
+                pair.split_once('=')
+                    .ok_or(ConfigError::InvalidConnectionString {
+                        reason: "expected `key=value` pairs",
+                    })?;
+            let value = value.trim().to_owned();
+            match key.trim() {
+                "Endpoint" => {
+                    parsed.host = value
+                        .trim_start_matches("sb://")
+                        .trim_end_matches('/')
+                        .to_owned();
+                }
+                "SharedAccessKeyName" => parsed.key_name = Some(value),
+                "SharedAccessKey" => parsed.key = Some(value),
+                "SharedAccessSignature" => parsed.shared_access_signature = Some(value),
+                "EntityPath" => parsed.entity_path = Some(value),
+                "UseDevelopmentEmulator" => {
+                    parsed.development_emulator = value.eq_ignore_ascii_case("true")
+                }
+                _ => {}
+            }
+        }
+
+        if parsed.host.is_empty() {
+            return Err(ConfigError::InvalidConnectionString {
+                reason: "missing `Endpoint`",
+            });
+        }
+        Ok(parsed)
+    }
+
+    fn credentials(&self) -> Result<Credentials, ConfigError> {
+        match (&self.key_name, &self.key, &self.shared_access_signature) {
+            (_, _, Some(signature)) => Ok(Credentials::SharedAccessSignature(signature.clone())),
+            (Some(name), Some(key), None) => Ok(Credentials::SharedAccessKey {
+                name: name.clone(),
+                key: key.clone(),
+            }),
+            _ => Err(ConfigError::InvalidConnectionString {
+                reason: "missing `SharedAccessKeyName` and `SharedAccessKey`",
+            }),
+        }
+    }
+}
+
+/// Opens authorized connections to an Event Hub.
+#[derive(Clone, Debug)]
+pub(crate) struct EventHubsConnector {
+    options: ConnectionOptions,
+    credentials: Credentials,
+    namespace: String,
+    event_hub_name: String,
+}
+
+impl EventHubsConnector {
+    pub(crate) fn new(config: &EventHubsConnectionConfig) -> crate::Result<Self> {
+        let (namespace, credentials, entity_path, development_emulator) =
+            match (&config.connection_string, &config.namespace) {
+                (Some(connection_string), None) => {
+                    let parsed = ConnectionString::parse(connection_string.inner())?;
+                    (
+                        parsed.host.clone(),
+                        parsed.credentials()?,
+                        parsed.entity_path,
+                        parsed.development_emulator,
+                    )
+                }
+                (None, Some(namespace)) => (
+                    namespace.clone(),
+                    Credentials::default_azure_credential(),
+                    None,
+                    false,
+                ),
+                (Some(_), Some(_)) => return Err(ConfigError::ConflictingAuthentication.into()),
+                (None, None) => return Err(ConfigError::MissingAuthentication.into()),
+            };
+
+        let event_hub_name = match (&config.event_hub_name, entity_path) {
+            (Some(event_hub_name), Some(entity_path)) if *event_hub_name != entity_path => {
+                return Err(ConfigError::ConflictingEventHubName {
+                    event_hub_name: event_hub_name.clone(),
+                    entity_path,
+                }
+                .into())
+            }
+            (Some(event_hub_name), _) => event_hub_name.clone(),
+            (None, Some(entity_path)) => entity_path,
+            (None, None) => return Err(ConfigError::MissingEventHubName.into()),
+        };
+
+        let (tls, default_port) = if development_emulator {
+            (MaybeTlsSettings::Raw(()), AMQP_PORT)
+        } else {
+            (MaybeTlsSettings::tls_client(&config.tls)?, AMQPS_PORT)
+        };
+        let (host, port) = match &config.endpoint {
+            Some(endpoint) => {
+                let uri = endpoint.parse::<http::Uri>()?;
+                let host = uri.host().ok_or("`endpoint` is missing a host")?.to_owned();
+                (host, uri.port_u16().unwrap_or(default_port))
+            }
+            None => {
+                let (host, port) = namespace
+                    .split_once(':')
+                    .and_then(|(host, port)| Some((host.to_owned(), port.parse().ok()?)))
+                    .unwrap_or_else(|| (namespace.clone(), default_port));
+                (host, port)
+            }
+        };
+
+        Ok(Self {
+            options: ConnectionOptions {
+                host,
+                port,
+                tls,
+                sasl: SaslMechanism::Anonymous,
+                container_id: format!("vector-{}", uuid::Uuid::new_v4()),
+            },
+            credentials,
+            namespace,
+            event_hub_name,
+        })
+    }
+
+    pub(crate) fn event_hub_name(&self) -> &str {
+        &self.event_hub_name
+    }
+
+    /// The host name of the Event Hubs namespace.
+    pub(crate) fn namespace(&self) -> &str {
+        self.namespace.split(':').next().unwrap_or_default()
+    }
+
+    /// The resource that connections are authorized to access.
+    fn audience(&self) -> String {
+        format!("sb://{}/{}", self.namespace(), self.event_hub_name)
+    }
+
+    /// Opens a connection authorized to access the Event Hub.
+    pub(crate) async fn connect(&self) -> Result<AuthorizedConnection, AuthError> {
+        let mut connection = Connection::connect(&self.options).await?;
+        let expires_at = self
+            .credentials
+            .authorize(&mut connection, &self.audience())
+            .await?;
+        Ok(AuthorizedConnection {
+            connection,
+            expires_at,
+        })
+    }
+
+    /// Authorizes the connection again if its authorization is about to expire.
+    pub(crate) async fn refresh(
+        &self,
+        connection: &mut AuthorizedConnection,
+    ) -> Result<(), AuthError> {
+        if connection.expires_soon() {
+            connection.expires_at = self
+                .credentials
+                .authorize(&mut connection.connection, &self.audience())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Lists the IDs of the partitions of the Event Hub.
+    pub(crate) async fn partition_ids(
+        &self,
+        connection: &mut AuthorizedConnection,
+    ) -> Result<Vec<String>, AmqpError> {
+        let mut request = Message::with_value(AmqpValue::Null);
+        request.application_properties = vec![
+            ("operation".into(), "READ".into()),
+            ("name".into(), self.event_hub_name.as_str().into()),
+            ("type".into(), "com.microsoft:eventhub".into()),
+        ];
+        let response = connection
+            .connection
+            .request(MANAGEMENT_NODE, request)
+            .await?;
+
+        let MessageBody::Value(properties) = &response.body else {
+            return Err(AmqpError::Protocol {
+                context: "reading the Event Hub properties",
+            });
+        };
+        match properties.get("partition_ids") {
+            Some(AmqpValue::Array(ids) | AmqpValue::List(ids)) => Ok(ids
+                .iter()
+                .filter_map(|id| id.as_str().map(ToOwned::to_owned))
+                .collect()),
+            _ => Err(AmqpError::Protocol {
+                context: "reading the Event Hub partitions",
+            }),
+        }
+    }
+}
+
+/// A connection authorized to access an Event Hub.
+pub(crate) struct AuthorizedConnection {
+    pub(crate) connection: EventHubsConnection,
+    expires_at: Option<SystemTime>,
+}
+
+impl AuthorizedConnection {
+    fn expires_soon(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= SystemTime::now() + REAUTHORIZE_BEFORE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(connection_string: &str, event_hub_name: Option<&str>) -> EventHubsConnectionConfig {
+        EventHubsConnectionConfig {
+            connection_string: Some(connection_string.to_owned().into()),
+            event_hub_name: event_hub_name.map(ToOwned::to_owned),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_connection_strings() {
+        let parsed = ConnectionString::parse(
+            "Endpoint=sb://vector.servicebus.windows.net/;SharedAccessKeyName=send;SharedAccessKey=secret;EntityPath=logs",
+        )
+        .unwrap();
+
+        assert_eq!(
+            parsed,
+            ConnectionString {
+                host: "vector.servicebus.windows.net".into(),
+                key_name: Some("send".into()),
+                key: Some("secret".into()),
+                entity_path: Some("logs".into()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn connects_to_the_emulator_without_tls() {
+        let connector = EventHubsConnector::new(&config(
+            "Endpoint=sb://localhost;SharedAccessKeyName=RootManageSharedAccessKey;SharedAccessKey=SAS_KEY_VALUE;UseDevelopmentEmulator=true;",
+            Some("logs"),
+        ))
+        .unwrap();
+
+        assert_eq!(connector.options.port, AMQP_PORT);
+        assert!(matches!(connector.options.tls, MaybeTlsSettings::Raw(())));
+        assert_eq!(connector.audience(), "sb://localhost/logs");
+    }
+
+    #[test]
+    fn resolves_the_event_hub_name() {
+        let connection_string =
+            "Endpoint=sb://vector.servicebus.windows.net/;SharedAccessKeyName=send;SharedAccessKey=secret;EntityPath=logs";
+
+        let connector = EventHubsConnector::new(&config(connection_string, None)).unwrap();
+        assert_eq!(connector.event_hub_name(), "logs");
+        assert_eq!(connector.options.port, AMQPS_PORT);
+
+        let error = EventHubsConnector::new(&config(connection_string, Some("metrics")))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            r#"`event_hub_name` "metrics" does not match the connection string's `EntityPath` "logs""#
+        );
+
+        let error = EventHubsConnector::new(&config(
+            "Endpoint=sb://vector.servicebus.windows.net/;SharedAccessKeyName=send;SharedAccessKey=secret",
+            None,
+        ))
+        .unwrap_err()
+        .to_string();
+        assert_eq!(
+            error,
+            "`event_hub_name` must be set when the connection string has no `EntityPath`"
+        );
+    }
+}
@@ -11,7 +11,7 @@ use crate::tap;
 #[cfg(feature = "api-client")]
 use crate::top;
 use crate::{config, convert_config, generate, get_version, graph, list, unit_test, validate};
-use crate::{generate_schema, signal};
+use crate::{generate_schema, signal, vrl_cli};
 
 #[derive(Parser, Debug)]
 #[command(rename_all = "kebab-case")]
@@ -292,7 +292,7 @@ pub enum SubCommand {
     Service(service::Opts),
 
     /// Vector Remap Language CLI
-    Vrl(vrl::cli::Opts),
+    Vrl(vrl_cli::Opts),
 }
 
 impl SubCommand {
@@ -316,11 +316,7 @@ impl SubCommand {
             #[cfg(feature = "api-client")]
             Self::Top(t) => top::cmd(t).await,
             Self::Validate(v) => validate::validate(v, color).await,
-            Self::Vrl(s) => {
-                let mut functions = vrl::stdlib::all();
-                functions.extend(vector_vrl_functions::all());
-                vrl::cli::cmd::cmd(s, functions)
-            }
+            Self::Vrl(s) => vrl_cli::cmd(s),
         }
     }
 }
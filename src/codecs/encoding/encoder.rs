@@ -1,7 +1,7 @@
 use bytes::BytesMut;
 use tokio_util::codec::Encoder as _;
 use vector_lib::codecs::{
-    encoding::{Error, Framer, Serializer},
+    encoding::{BatchSerializer, Error, Framer, Serializer},
     CharacterDelimitedEncoder, NewlineDelimitedEncoder, TextSerializerConfig,
 };
 
@@ -181,6 +181,81 @@ impl tokio_util::codec::Encoder<Event> for Encoder<()> {
     }
 }
 
+/// An encoder that can encode batches of structured events into a single payload.
+#[derive(Debug, Clone)]
+pub struct BatchEncoder {
+    serializer: BatchSerializer,
+}
+
+impl BatchEncoder {
+    /// Creates a new `BatchEncoder` with the specified `BatchSerializer` to produce bytes from a
+    /// batch of structured events.
+    pub const fn new(serializer: BatchSerializer) -> Self {
+        Self { serializer }
+    }
+
+    /// Get the batch serializer.
+    pub const fn serializer(&self) -> &BatchSerializer {
+        &self.serializer
+    }
+
+    /// Get the HTTP content type.
+    pub const fn content_type(&self) -> &'static str {
+        match self.serializer {
+            BatchSerializer::Parquet(_) => "application/vnd.apache.parquet",
+        }
+    }
+
+    /// Get the extension of the files written by this encoder.
+    pub const fn extension(&self) -> &'static str {
+        match self.serializer {
+            BatchSerializer::Parquet(_) => "parquet",
+        }
+    }
+}
+
+impl tokio_util::codec::Encoder<Vec<Event>> for BatchEncoder {
+    type Error = Error;
+
+    fn encode(&mut self, events: Vec<Event>, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        self.serializer.encode(events, buffer).map_err(|error| {
+            emit!(EncoderSerializeError { error: &error });
+            Error::SerializingError(error)
+        })
+    }
+}
+
+/// An encoder which either frames each event of a batch, or encodes the whole batch at once.
+#[derive(Debug, Clone)]
+pub enum EncoderKind {
+    /// Serializes and frames each event individually.
+    Framed(Box<Encoder<Framer>>),
+    /// Serializes the whole batch of events at once.
+    Batch(BatchEncoder),
+}
+
+impl EncoderKind {
+    /// Get the HTTP content type.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            EncoderKind::Framed(encoder) => encoder.content_type(),
+            EncoderKind::Batch(encoder) => encoder.content_type(),
+        }
+    }
+}
+
+impl From<Encoder<Framer>> for EncoderKind {
+    fn from(encoder: Encoder<Framer>) -> Self {
+        Self::Framed(Box::new(encoder))
+    }
+}
+
+impl From<BatchEncoder> for EncoderKind {
+    fn from(encoder: BatchEncoder) -> Self {
+        Self::Batch(encoder)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::BufMut;
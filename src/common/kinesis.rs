@@ -0,0 +1,11 @@
+use crate::aws::ClientBuilder;
+
+pub(crate) struct KinesisClientBuilder;
+
+impl ClientBuilder for KinesisClientBuilder {
+    type Client = aws_sdk_kinesis::Client;
+
+    fn build(config: &aws_types::SdkConfig) -> Self::Client {
+        aws_sdk_kinesis::Client::new(config)
+    }
+}
@@ -17,3 +17,12 @@ pub(crate) mod sqs;
 
 #[cfg(any(feature = "sources-aws_s3", feature = "sinks-aws_s3"))]
 pub(crate) mod s3;
+
+#[cfg(any(
+    feature = "sources-aws_kinesis_streams",
+    feature = "sinks-aws_kinesis_streams"
+))]
+pub(crate) mod kinesis;
+
+#[cfg(any(feature = "sources-mqtt", feature = "sinks-mqtt"))]
+pub(crate) mod mqtt;
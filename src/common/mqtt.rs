@@ -0,0 +1,70 @@
+use rumqttc::{QoS, TlsConfiguration, Transport};
+use vector_lib::configurable::configurable_component;
+
+use crate::tls::MaybeTlsSettings;
+
+/// Supported Quality of Service types for MQTT.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+#[allow(clippy::enum_variant_names)]
+pub enum MqttQoS {
+    /// AtLeastOnce.
+    #[derivative(Default)]
+    AtLeastOnce,
+
+    /// AtMostOnce.
+    AtMostOnce,
+
+    /// ExactlyOnce.
+    ExactlyOnce,
+}
+
+impl From<MqttQoS> for QoS {
+    fn from(value: MqttQoS) -> Self {
+        match value {
+            MqttQoS::AtLeastOnce => QoS::AtLeastOnce,
+            MqttQoS::AtMostOnce => QoS::AtMostOnce,
+            MqttQoS::ExactlyOnce => QoS::ExactlyOnce,
+        }
+    }
+}
+
+impl From<MqttQoS> for rumqttc::v5::mqttbytes::QoS {
+    fn from(value: MqttQoS) -> Self {
+        match value {
+            MqttQoS::AtLeastOnce => Self::AtLeastOnce,
+            MqttQoS::AtMostOnce => Self::AtMostOnce,
+            MqttQoS::ExactlyOnce => Self::ExactlyOnce,
+        }
+    }
+}
+
+/// Supported versions of the MQTT protocol.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MqttProtocolVersion {
+    /// MQTT 3.1.1.
+    #[default]
+    #[serde(rename = "v3_1_1")]
+    V311,
+
+    /// MQTT 5.0.
+    #[serde(rename = "v5")]
+    V5,
+}
+
+/// Builds the transport for the given TLS settings, or `None` when TLS is disabled.
+pub(crate) fn tls_transport(tls: &MaybeTlsSettings) -> Option<Transport> {
+    tls.tls().map(|tls| {
+        let ca = tls.authorities_pem().flatten().collect();
+        let client_auth = None;
+        let alpn = Some(vec!["mqtt".into()]);
+        Transport::Tls(TlsConfiguration::Simple {
+            ca,
+            client_auth,
+            alpn,
+        })
+    })
+}
@@ -23,6 +23,19 @@ pub struct Options {
     /// Whether or not the GraphQL endpoint is enabled
     #[serde(default = "default_graphql", skip_serializing_if = "is_true")]
     pub graphql: bool,
+
+    /// Whether or not the configuration can be reloaded through the `reloadConfig` GraphQL mutation.
+    ///
+    /// Anyone able to reach the API endpoint is then able to replace the running configuration.
+    #[serde(default = "default_reload")]
+    pub reload: bool,
+
+    /// Whether or not the partitions consumed by `kafka` sources can be paused and resumed through
+    /// the `pauseKafkaPartitions` and `resumeKafkaPartitions` GraphQL mutations.
+    ///
+    /// Anyone able to reach the API endpoint is then able to stop the consumption of partitions.
+    #[serde(default = "default_kafka_partition_control")]
+    pub kafka_partition_control: bool,
 }
 
 impl Default for Options {
@@ -32,6 +45,8 @@ impl Default for Options {
             playground: default_playground(),
             address: default_address(),
             graphql: default_graphql(),
+            reload: default_reload(),
+            kafka_partition_control: default_kafka_partition_control(),
         }
     }
 }
@@ -68,6 +83,14 @@ const fn default_graphql() -> bool {
     true
 }
 
+const fn default_reload() -> bool {
+    false
+}
+
+const fn default_kafka_partition_control() -> bool {
+    false
+}
+
 impl Options {
     pub fn merge(&mut self, other: Self) -> Result<(), String> {
         // Merge options
@@ -94,6 +117,8 @@ impl Options {
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
             graphql: self.graphql & other.graphql,
+            reload: self.reload | other.reload,
+            kafka_partition_control: self.kafka_partition_control | other.kafka_partition_control,
         };
 
         *self = options;
@@ -108,6 +133,8 @@ fn bool_merge() {
         address: None,
         playground: false,
         graphql: false,
+        reload: false,
+        kafka_partition_control: false,
     };
 
     a.merge(Options::default()).unwrap();
@@ -118,7 +145,9 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
-            graphql: false
+            graphql: false,
+            reload: false,
+            kafka_partition_control: false,
         }
     );
 }
@@ -131,6 +160,8 @@ fn bind_merge() {
         address: Some(address),
         playground: true,
         graphql: true,
+        reload: true,
+        kafka_partition_control: true,
     };
 
     a.merge(Options::default()).unwrap();
@@ -142,6 +173,8 @@ fn bind_merge() {
             address: Some(address),
             playground: true,
             graphql: true,
+            reload: true,
+            kafka_partition_control: true,
         }
     );
 }
@@ -10,8 +10,8 @@ use crate::{enrichment_tables::EnrichmentTables, providers::Providers, secrets::
 use super::api;
 use super::{
     compiler, schema, BoxedSink, BoxedSource, BoxedTransform, ComponentKey, Config,
-    EnrichmentTableOuter, HealthcheckOptions, SinkOuter, SourceOuter, TestDefinition,
-    TransformOuter,
+    EnrichmentTableOuter, HealthcheckOptions, KeyspaceConfig, SinkOuter, SourceOuter,
+    TestDefinition, TransformOuter,
 };
 
 /// A complete Vector configuration.
@@ -66,6 +66,10 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub secret: IndexMap<ComponentKey, SecretBackends>,
 
+    /// All configured keyspaces.
+    #[serde(default)]
+    pub keyspaces: IndexMap<ComponentKey, KeyspaceConfig>,
+
     /// The duration in seconds to wait for graceful shutdown after SIGINT or SIGTERM are received.
     /// After the duration has passed, Vector will force shutdown. Default value is 60 seconds. This
     /// value can be set using a [cli arg](crate::cli::RootOpts::graceful_shutdown_limit_secs).
@@ -93,6 +97,7 @@ impl From<Config> for ConfigBuilder {
             transforms,
             tests,
             secret,
+            keyspaces,
             graceful_shutdown_duration,
         } = config;
 
@@ -121,6 +126,7 @@ impl From<Config> for ConfigBuilder {
             provider: None,
             tests,
             secret,
+            keyspaces,
             graceful_shutdown_duration,
             allow_empty: false,
         }
@@ -249,6 +255,11 @@ impl ConfigBuilder {
                 errors.push(format!("duplicate secret id found: {}", k));
             }
         });
+        with.keyspaces.keys().for_each(|k| {
+            if self.keyspaces.contains_key(k) {
+                errors.push(format!("duplicate keyspace id found: {}", k));
+            }
+        });
         if !errors.is_empty() {
             return Err(errors);
         }
@@ -259,6 +270,7 @@ impl ConfigBuilder {
         self.transforms.extend(with.transforms);
         self.tests.extend(with.tests);
         self.secret.extend(with.secret);
+        self.keyspaces.extend(with.keyspaces);
 
         Ok(())
     }
@@ -3,8 +3,13 @@ use super::{
     OutputId,
 };
 
+use std::num::NonZeroU64;
+
 use indexmap::IndexSet;
-use vector_lib::id::Inputs;
+use vector_lib::{
+    buffers::{config::disk_buffer_minimum_size, BufferType},
+    id::Inputs,
+};
 
 pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<String>> {
     let mut errors = Vec::new();
@@ -32,10 +37,18 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(type_errors);
     }
 
+    if let Err(buffer_errors) = validation::check_buffers(&builder) {
+        errors.extend(buffer_errors);
+    }
+
     if let Err(output_errors) = validation::check_outputs(&builder) {
         errors.extend(output_errors);
     }
 
+    if let Err(budget_errors) = allocate_disk_buffer_budget(&mut builder) {
+        errors.extend(budget_errors);
+    }
+
     let ConfigBuilder {
         global,
         #[cfg(feature = "api")]
@@ -49,6 +62,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         tests,
         provider: _,
         secret,
+        keyspaces,
         graceful_shutdown_duration,
         allow_empty: _,
     } = builder;
@@ -103,6 +117,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             transforms,
             tests,
             secret,
+            keyspaces,
             graceful_shutdown_duration,
         };
 
@@ -145,6 +160,95 @@ pub(crate) fn expand_globs(config: &mut ConfigBuilder) {
     }
 }
 
+const fn is_weighted(stage: &BufferType) -> bool {
+    matches!(
+        stage,
+        BufferType::DiskV2 {
+            weight: Some(_),
+            ..
+        }
+    )
+}
+
+/// Allocates the global disk buffer budget between the disk buffers configured with a `weight`.
+///
+/// The budget left once the disk buffers with a fixed `max_size` are accounted for is split
+/// between the weighted buffers proportionally to their weight, which then behave as if they had
+/// been configured with the resulting `max_size`, which must be at least the minimum size of a disk
+/// buffer.
+fn allocate_disk_buffer_budget(config: &mut ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut reserved = 0_u64;
+    let mut total_weight = 0_u64;
+    for stage in config.sinks.values().flat_map(|sink| sink.buffer.stages()) {
+        if let BufferType::DiskV2 {
+            max_size, weight, ..
+        } = stage
+        {
+            reserved = reserved.saturating_add(max_size.map_or(0, NonZeroU64::get));
+            total_weight += weight.map_or(0, |weight| u64::from(weight.get()));
+        }
+    }
+
+    if total_weight == 0 {
+        return Ok(());
+    }
+
+    let Some(budget) = config.global.disk_buffer_budget else {
+        return Err(config
+            .sinks
+            .iter()
+            .filter(|(_, sink)| sink.buffer.stages().iter().any(is_weighted))
+            .map(|(key, _)| {
+                format!(
+                    "Sink \"{key}\" has a disk buffer with a `weight`, but no global `disk_buffer_budget` is set."
+                )
+            })
+            .collect());
+    };
+
+    let Some(available) = budget.get().checked_sub(reserved) else {
+        return Err(vec![format!(
+            "Disk buffers with a fixed `max_size` use {reserved} bytes, which exceeds the global `disk_buffer_budget` of {budget} bytes."
+        )]);
+    };
+
+    let minimum_size = disk_buffer_minimum_size();
+    let mut errors = Vec::new();
+    for (key, sink) in config.sinks.iter_mut() {
+        for stage in sink.buffer.stages_mut() {
+            if let BufferType::DiskV2 {
+                weight: Some(weight),
+                when_full,
+                ..
+            } = *stage
+            {
+                // The share is at most `available`, so it always fits back into a `u64`.
+                let share =
+                    u128::from(available) * u128::from(weight.get()) / u128::from(total_weight);
+                let share = share as u64;
+                match NonZeroU64::new(share).filter(|max_size| max_size.get() >= minimum_size) {
+                    Some(max_size) => {
+                        *stage = BufferType::DiskV2 {
+                            max_size: Some(max_size),
+                            weight: None,
+                            when_full,
+                        };
+                    }
+                    None => errors.push(format!(
+                        "Sink \"{key}\" has a disk buffer with a `weight` allocated {share} bytes of the global `disk_buffer_budget`, which is less than the minimum disk buffer size of {minimum_size} bytes."
+                    )),
+                }
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 enum InputMatcher {
     Pattern(glob::Pattern),
     String(String),
@@ -189,6 +293,8 @@ fn expand_globs_inner(inputs: &mut Inputs<String>, id: &str, candidates: &IndexS
 mod test {
     use super::*;
     use crate::test_util::mock::{basic_sink, basic_source, basic_transform};
+    use std::num::NonZeroU32;
+    use vector_lib::buffers::{BufferConfig, WhenFull};
     use vector_lib::config::ComponentKey;
 
     #[test]
@@ -247,6 +353,85 @@ mod test {
         );
     }
 
+    fn disk_buffer(max_size: Option<u64>, weight: Option<u32>) -> BufferConfig {
+        BufferConfig::Single(BufferType::DiskV2 {
+            max_size: max_size.and_then(NonZeroU64::new),
+            weight: weight.and_then(NonZeroU32::new),
+            when_full: WhenFull::Block,
+        })
+    }
+
+    fn builder_with_buffers(buffers: Vec<BufferConfig>) -> ConfigBuilder {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", basic_source().1);
+        for (index, buffer) in buffers.into_iter().enumerate() {
+            let name = format!("out{index}");
+            builder.add_sink(&name, &["in"], basic_sink(1).1);
+            builder.sinks[&ComponentKey::from(name)].buffer = buffer;
+        }
+        builder
+    }
+
+    const MIB: u64 = 1024 * 1024;
+
+    #[test]
+    fn disk_buffer_budget_allocation() {
+        let mut builder = builder_with_buffers(vec![
+            disk_buffer(Some(1024 * MIB), None),
+            disk_buffer(None, Some(1)),
+            disk_buffer(None, Some(3)),
+            BufferConfig::default(),
+        ]);
+        builder.global.disk_buffer_budget = NonZeroU64::new(5120 * MIB);
+
+        let config = builder.build().expect("build should succeed");
+
+        let buffers = ["out0", "out1", "out2", "out3"]
+            .map(|key| config.sinks[&ComponentKey::from(key)].buffer.clone());
+        assert_eq!(
+            buffers,
+            [
+                disk_buffer(Some(1024 * MIB), None),
+                disk_buffer(Some(1024 * MIB), None),
+                disk_buffer(Some(3072 * MIB), None),
+                BufferConfig::default(),
+            ]
+        );
+    }
+
+    #[test]
+    fn disk_buffer_budget_errors() {
+        let builder = builder_with_buffers(vec![disk_buffer(None, Some(1))]);
+        assert_eq!(
+            builder.build().unwrap_err(),
+            vec!["Sink \"out0\" has a disk buffer with a `weight`, but no global `disk_buffer_budget` is set."]
+        );
+
+        let mut builder = builder_with_buffers(vec![
+            disk_buffer(Some(3072 * MIB), None),
+            disk_buffer(None, Some(1)),
+        ]);
+        builder.global.disk_buffer_budget = NonZeroU64::new(2048 * MIB);
+        assert_eq!(
+            builder.build().unwrap_err(),
+            vec!["Disk buffers with a fixed `max_size` use 3221225472 bytes, which exceeds the global `disk_buffer_budget` of 2147483648 bytes."]
+        );
+    }
+
+    #[test]
+    fn disk_buffer_budget_share_too_small() {
+        let mut builder =
+            builder_with_buffers(vec![disk_buffer(None, Some(1)), disk_buffer(None, Some(7))]);
+        builder.global.disk_buffer_budget = NonZeroU64::new(1024 * MIB);
+        assert_eq!(
+            builder.build().unwrap_err(),
+            vec![format!(
+                "Sink \"out0\" has a disk buffer with a `weight` allocated 134217728 bytes of the global `disk_buffer_budget`, which is less than the minimum disk buffer size of {} bytes.",
+                disk_buffer_minimum_size()
+            )]
+        );
+    }
+
     fn without_ports(outputs: Inputs<OutputId>) -> Vec<ComponentKey> {
         outputs
             .into_iter()
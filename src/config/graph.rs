@@ -3,7 +3,7 @@ use std::collections::{HashMap, HashSet, VecDeque};
 
 use super::{
     schema, ComponentKey, DataType, OutputId, SinkOuter, SourceOuter, SourceOutput, TransformOuter,
-    TransformOutput,
+    TransformOutput, REJECTED_OUTPUT,
 };
 
 #[derive(Debug, Clone)]
@@ -17,6 +17,7 @@ pub enum Node {
     },
     Sink {
         ty: DataType,
+        reroute_rejected: bool,
     },
 }
 
@@ -90,6 +91,7 @@ impl Graph {
                 id.clone(),
                 Node::Sink {
                     ty: config.inner.input().data_type(),
+                    reroute_rejected: config.reroute_rejected,
                 },
             );
         }
@@ -156,7 +158,7 @@ impl Graph {
         match self.nodes[key] {
             Node::Source { .. } => panic!("no inputs on sources"),
             Node::Transform { in_ty, .. } => in_ty,
-            Node::Sink { ty } => ty,
+            Node::Sink { ty, .. } => ty,
         }
     }
 
@@ -164,8 +166,8 @@ impl Graph {
     ///
     /// # Panics
     ///
-    /// Will panic if the given id is not present in the graph or identifies a sink, which can't
-    /// have inputs.
+    /// Will panic if the given id is not present in the graph or identifies an output that doesn't
+    /// exist.
     fn get_output_type(&self, id: &OutputId) -> DataType {
         match &self.nodes[&id.component] {
             Node::Source { outputs } => outputs
@@ -178,7 +180,17 @@ impl Graph {
                 .find(|output| output.port == id.port)
                 .map(|output| output.ty)
                 .expect("output didn't exist"),
-            Node::Sink { .. } => panic!("no outputs on sinks"),
+            Node::Sink {
+                ty,
+                reroute_rejected,
+            } => {
+                assert!(
+                    *reroute_rejected && id.port.as_deref() == Some(REJECTED_OUTPUT),
+                    "output didn't exist"
+                );
+                // Rejected events are rerouted as they were received by the sink.
+                *ty
+            }
         }
     }
 
@@ -263,7 +275,18 @@ impl Graph {
         self.nodes
             .iter()
             .flat_map(|(key, node)| match node {
-                Node::Sink { .. } => vec![],
+                Node::Sink {
+                    reroute_rejected, ..
+                } => {
+                    if *reroute_rejected {
+                        vec![OutputId {
+                            component: key.clone(),
+                            port: Some(REJECTED_OUTPUT.to_owned()),
+                        }]
+                    } else {
+                        vec![]
+                    }
+                }
                 Node::Source { outputs } => outputs
                     .iter()
                     .map(|output| OutputId {
@@ -355,7 +378,7 @@ impl Graph {
             .into_iter()
             .filter(|path| {
                 if let Some(key) = path.last() {
-                    matches!(self.nodes.get(key), Some(Node::Sink { ty: _ }))
+                    matches!(self.nodes.get(key), Some(Node::Sink { .. }))
                 } else {
                     false
                 }
@@ -429,7 +452,13 @@ mod test {
         fn add_sink(&mut self, id: &str, ty: DataType, inputs: Vec<&str>) {
             let id = ComponentKey::from(id);
             let inputs = clean_inputs(inputs);
-            self.nodes.insert(id.clone(), Node::Sink { ty });
+            self.nodes.insert(
+                id.clone(),
+                Node::Sink {
+                    ty,
+                    reroute_rejected: false,
+                },
+            );
             for from in inputs {
                 self.edges.push(Edge {
                     from,
@@ -438,6 +467,15 @@ mod test {
             }
         }
 
+        fn reroute_rejected(&mut self, id: &str) {
+            match self.nodes.get_mut(&id.into()) {
+                Some(Node::Sink {
+                    reroute_rejected, ..
+                }) => *reroute_rejected = true,
+                _ => panic!("invalid sink"),
+            }
+        }
+
         fn test_add_input(&mut self, node: &str, input: &str) -> Result<(), String> {
             let available_inputs = self.input_map().unwrap();
             self.add_input(input, &node.into(), &available_inputs)
@@ -632,6 +670,29 @@ mod test {
         );
     }
 
+    #[test]
+    fn allows_rejected_sink_outputs() {
+        let mut graph = Graph::default();
+        graph.add_source("log_source", DataType::Log);
+        graph.add_sink("es", DataType::Log, vec!["log_source"]);
+        graph.reroute_rejected("es");
+        graph.add_sink("dlq", DataType::Log, vec![]);
+        graph.add_sink("unrouted", DataType::Log, vec!["log_source"]);
+        graph.add_sink("bad_dlq", DataType::Log, vec![]);
+
+        assert_eq!(Ok(()), graph.test_add_input("dlq", "es.rejected"));
+        assert_eq!(Ok(()), graph.typecheck());
+        assert_eq!(Ok(()), graph.check_for_cycles());
+
+        let expected =
+            "Input \"unrouted.rejected\" for sink \"bad_dlq\" doesn't match any components."
+                .to_string();
+        assert_eq!(
+            Err(expected),
+            graph.test_add_input("bad_dlq", "unrouted.rejected")
+        );
+    }
+
     #[test]
     fn disallows_ambiguous_inputs() {
         let mut graph = Graph::default();
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use indexmap::IndexMap;
+use vector_lib::configurable::configurable_component;
+use vector_vrl_functions::set_state::Keyspaces;
+
+use super::ComponentKey;
+
+const fn default_max_keys() -> usize {
+    100_000
+}
+
+/// A key/value store shared by the programs of all `remap` transforms.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KeyspaceConfig {
+    /// The maximum number of keys held by the keyspace.
+    ///
+    /// Once reached, setting a new key evicts the expired keys, or else the key set the longest ago.
+    #[serde(default = "default_max_keys")]
+    #[configurable(metadata(docs::type_unit = "keys"))]
+    pub max_keys: usize,
+
+    /// The time to live of the keys set without one, in seconds.
+    ///
+    /// If not set, such keys never expire.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub default_ttl_secs: Option<u64>,
+}
+
+impl Default for KeyspaceConfig {
+    fn default() -> Self {
+        Self {
+            max_keys: default_max_keys(),
+            default_ttl_secs: None,
+        }
+    }
+}
+
+/// Loads the configured keyspaces into the keyspaces of the topology, keeping the entries of the
+/// keyspaces which were already loaded.
+///
+/// Keyspaces removed from the configuration are dropped, while the programs of the transforms
+/// which aren't rebuilt keep using them until they are.
+pub(crate) fn load_keyspaces(
+    registry: &Keyspaces,
+    keyspaces: &IndexMap<ComponentKey, KeyspaceConfig>,
+) {
+    registry.load(keyspaces.iter().map(|(key, config)| {
+        (
+            key.id(),
+            config.max_keys,
+            config.default_ttl_secs.map(Duration::from_secs),
+        )
+    }));
+}
@@ -3,7 +3,7 @@ use std::{collections::HashMap, io::Read};
 use indexmap::IndexMap;
 use toml::value::Table;
 
-use super::{deserialize_table, loader, prepare_input, secret};
+use super::{deserialize_table, loader, prepare_input, secret, template};
 use super::{ComponentHint, Process};
 use crate::config::{
     ComponentKey, ConfigBuilder, EnrichmentTableOuter, SinkOuter, SourceOuter, TestDefinition,
@@ -76,7 +76,8 @@ impl Process for ConfigBuilderLoader {
                 );
             }
             None => {
-                self.builder.append(deserialize_table(table)?)?;
+                self.builder
+                    .append(deserialize_table(template::expand(table)?)?)?;
             }
         };
 
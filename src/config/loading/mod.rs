@@ -2,6 +2,7 @@ mod config_builder;
 mod loader;
 mod secret;
 mod source;
+mod template;
 
 use std::{
     collections::HashMap,
@@ -141,13 +142,14 @@ pub async fn load_from_paths_with_provider_and_secrets(
     // Load secret backends first
     let mut secrets_backends_loader = load_secret_backends_from_paths(config_paths)?;
     // And then, if needed, retrieve secrets from configured backends
+    let mut resolved_secrets = HashMap::new();
     let mut builder = if secrets_backends_loader.has_secrets_to_retrieve() {
         debug!(message = "Secret placeholders found, retrieving secrets from configured backends.");
-        let resolved_secrets = secrets_backends_loader
+        resolved_secrets = secrets_backends_loader
             .retrieve(&mut signal_handler.subscribe())
             .await
             .map_err(|e| vec![e])?;
-        load_builder_from_paths_with_secrets(config_paths, resolved_secrets)?
+        load_builder_from_paths_with_secrets(config_paths, resolved_secrets.clone())?
     } else {
         debug!(message = "No secret placeholder found, skipping secret resolution.");
         load_builder_from_paths(config_paths)?
@@ -172,6 +174,9 @@ pub async fn load_from_paths_with_provider_and_secrets(
         warn!("{}", warning);
     }
 
+    // Only watch the secrets of configurations that were loaded successfully.
+    secrets_backends_loader.refresh_periodically(&resolved_secrets, signal_handler.clone_tx());
+
     Ok(new_config)
 }
 
@@ -257,7 +262,11 @@ fn load_from_inputs(
     let mut errors = Vec::new();
 
     for (input, format) in inputs {
-        if let Err(errs) = load(input, format).and_then(|n| config.append(n)) {
+        if let Err(errs) = load(input, format)
+            .and_then(template::expand)
+            .and_then(deserialize_table)
+            .and_then(|n| config.append(n))
+        {
             // TODO: add back paths
             errors.extend(errs.iter().map(|e| e.to_string()));
         }
@@ -1,6 +1,8 @@
 use std::{
     collections::{HashMap, HashSet},
     io::Read,
+    sync::Mutex,
+    time::Duration,
 };
 
 use futures::TryFutureExt;
@@ -8,6 +10,7 @@ use indexmap::IndexMap;
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
 use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
 use toml::value::Table;
 use vector_lib::config::ComponentKey;
 
@@ -17,7 +20,7 @@ use crate::{
         SecretBackend,
     },
     secrets::SecretBackends,
-    signal,
+    signal::{self, SignalTo},
 };
 
 // The following regex aims to extract a pair of strings, the first being the secret backend name
@@ -30,6 +33,9 @@ use crate::{
 pub static COLLECTOR: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"SECRET\[([[:word:]]+)\.([[:word:].]+)\]").unwrap());
 
+/// Tasks watching the secrets of the currently loaded configuration for changes.
+static REFRESH_TASKS: Lazy<Mutex<Vec<JoinHandle<()>>>> = Lazy::new(Default::default);
+
 /// Helper type for specifically deserializing secrets backends.
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub(crate) struct SecretBackendOuter {
@@ -85,6 +91,79 @@ impl SecretBackendLoader {
     pub(crate) fn has_secrets_to_retrieve(&self) -> bool {
         !self.secret_keys.is_empty()
     }
+
+    /// Periodically retrieves the secrets of backends that have a refresh interval, triggering a
+    /// configuration reload when they no longer match the `resolved` ones.
+    ///
+    /// This replaces the tasks started for any previously loaded configuration.
+    pub(crate) fn refresh_periodically(
+        &self,
+        resolved: &HashMap<String, String>,
+        signal_tx: signal::SignalTx,
+    ) {
+        let mut tasks = REFRESH_TASKS.lock().expect("poisoned lock");
+        for task in tasks.drain(..) {
+            task.abort();
+        }
+
+        for (backend_name, keys) in &self.secret_keys {
+            let Some(backend) = self.backends.get(&ComponentKey::from(backend_name.clone())) else {
+                continue;
+            };
+            let Some(interval) = backend.refresh_interval() else {
+                continue;
+            };
+
+            let current = keys
+                .iter()
+                .filter_map(|key| {
+                    resolved
+                        .get(&format!("{backend_name}.{key}"))
+                        .map(|secret| (key.clone(), secret.clone()))
+                })
+                .collect();
+            tasks.push(tokio::spawn(refresh_secrets(
+                backend_name.clone(),
+                backend.clone(),
+                keys.clone(),
+                current,
+                interval,
+                signal_tx.clone(),
+            )));
+        }
+    }
+}
+
+async fn refresh_secrets(
+    backend_name: String,
+    mut backend: SecretBackends,
+    keys: HashSet<String>,
+    mut current: HashMap<String, String>,
+    interval: Duration,
+    signal_tx: signal::SignalTx,
+) {
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    loop {
+        interval.tick().await;
+
+        debug!(message = "Refreshing secrets from a backend.", backend = ?backend_name);
+        match backend
+            .retrieve(keys.clone(), &mut signal_tx.subscribe())
+            .await
+        {
+            Ok(secrets) if secrets != current => {
+                info!(message = "Secrets changed, reloading configuration.", backend = ?backend_name);
+                current = secrets;
+                _ = signal_tx.send(SignalTo::ReloadFromDisk).map_err(
+                    |error| error!(message = "Unable to reload configuration.", cause = %error),
+                );
+            }
+            Ok(_) => {}
+            Err(error) => {
+                warn!(message = "Failed to refresh secrets.", backend = ?backend_name, %error);
+            }
+        }
+    }
 }
 
 impl Process for SecretBackendLoader {
@@ -0,0 +1,403 @@
+use std::collections::{BTreeSet, HashSet};
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use toml::value::{Table, Value};
+
+// Matches the `PARAM[<name>]` placeholders used in the components of a template, capturing the
+// name of the parameter.
+static PARAMETER: Lazy<Regex> = Lazy::new(|| Regex::new(r"PARAM\[([[:word:]]+)\]").unwrap());
+
+const COMPONENT_KINDS: [&str; 3] = ["sources", "transforms", "sinks"];
+
+/// A parameterized set of components, instantiated once per entry of `instances`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Template {
+    #[serde(default)]
+    parameters: IndexMap<String, Parameter>,
+    #[serde(default)]
+    sources: Table,
+    #[serde(default)]
+    transforms: Table,
+    #[serde(default)]
+    sinks: Table,
+    #[serde(default)]
+    instances: IndexMap<String, Table>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Parameter {
+    /// The value used by instances that don't set the parameter. The parameter is required if
+    /// there is none.
+    default: Option<Value>,
+}
+
+impl Template {
+    fn components(&self, kind: &str) -> &Table {
+        match kind {
+            "sources" => &self.sources,
+            "transforms" => &self.transforms,
+            "sinks" => &self.sinks,
+            _ => unreachable!("unknown component kind"),
+        }
+    }
+
+    fn component_ids(&self) -> HashSet<&str> {
+        COMPONENT_KINDS
+            .iter()
+            .flat_map(|kind| self.components(kind).keys())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Resolves the value of each parameter for the given instance.
+    fn arguments<'a>(
+        &'a self,
+        template_name: &str,
+        instance_id: &str,
+        instance: &'a Table,
+    ) -> Result<IndexMap<&'a str, &'a Value>, Vec<String>> {
+        let mut errors = Vec::new();
+
+        for name in instance.keys() {
+            if !self.parameters.contains_key(name) {
+                errors.push(format!(
+                    "Instance \"{instance_id}\" of template \"{template_name}\" sets unknown parameter \"{name}\"."
+                ));
+            }
+        }
+
+        let mut arguments = IndexMap::new();
+        for (name, parameter) in &self.parameters {
+            match instance.get(name).or(parameter.default.as_ref()) {
+                Some(value) => {
+                    arguments.insert(name.as_str(), value);
+                }
+                None => errors.push(format!(
+                    "Instance \"{instance_id}\" of template \"{template_name}\" is missing required parameter \"{name}\"."
+                )),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(arguments)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Replaces the `templates` section of a configuration with the components of every instance of
+/// its templates.
+///
+/// The ID of an instance's component is the component's ID in the template prefixed with the
+/// instance ID, for example the `parse` transform of the `acme` instance is added as `acme_parse`.
+/// Inputs referring to components of the same template are rewritten the same way, while other
+/// inputs are kept as-is so that instances can consume from, or be consumed by, regular
+/// components.
+pub(super) fn expand(mut table: Table) -> Result<Table, Vec<String>> {
+    let Some(templates) = table.remove("templates") else {
+        return Ok(table);
+    };
+    let templates = templates
+        .try_into::<IndexMap<String, Template>>()
+        .map_err(|error| vec![format!("Invalid templates: {error}")])?;
+
+    let mut errors = Vec::new();
+    for (template_name, template) in &templates {
+        let mut referenced = BTreeSet::new();
+        for kind in COMPONENT_KINDS {
+            for component in template.components(kind).values() {
+                collect_parameters(component, &mut referenced);
+            }
+        }
+        for name in referenced {
+            if !template.parameters.contains_key(name) {
+                errors.push(format!(
+                    "Template \"{template_name}\" references undeclared parameter \"{name}\"."
+                ));
+            }
+        }
+
+        let component_ids = template.component_ids();
+        for (instance_id, instance) in &template.instances {
+            let arguments = match template.arguments(template_name, instance_id, instance) {
+                Ok(arguments) => arguments,
+                Err(errs) => {
+                    errors.extend(errs);
+                    continue;
+                }
+            };
+
+            for kind in COMPONENT_KINDS {
+                let components = table
+                    .entry(kind)
+                    .or_insert_with(|| Value::Table(Table::new()));
+                let Value::Table(components) = components else {
+                    errors.push(format!("The \"{kind}\" section must be a table."));
+                    continue;
+                };
+
+                for (component_id, component) in template.components(kind) {
+                    let id = format!("{instance_id}_{component_id}");
+                    if components.contains_key(&id) {
+                        errors.push(format!(
+                            "Instance \"{instance_id}\" of template \"{template_name}\" defines component \"{id}\", which already exists."
+                        ));
+                        continue;
+                    }
+
+                    let mut component = substitute(component.clone(), &arguments);
+                    namespace_inputs(&mut component, instance_id, &component_ids);
+                    components.insert(id, component);
+                }
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(table)
+    } else {
+        Err(errors)
+    }
+}
+
+fn collect_parameters<'a>(value: &'a Value, names: &mut BTreeSet<&'a str>) {
+    match value {
+        Value::String(string) => names.extend(
+            PARAMETER
+                .captures_iter(string)
+                .filter_map(|captures| captures.get(1))
+                .map(|name| name.as_str()),
+        ),
+        Value::Array(values) => values
+            .iter()
+            .for_each(|value| collect_parameters(value, names)),
+        Value::Table(table) => table
+            .values()
+            .for_each(|value| collect_parameters(value, names)),
+        _ => {}
+    }
+}
+
+/// Replaces the parameter placeholders in `value`. A string consisting of a single placeholder
+/// takes the type of the argument, so that parameters can also be used for non-string options.
+fn substitute(value: Value, arguments: &IndexMap<&str, &Value>) -> Value {
+    match value {
+        Value::String(string) => {
+            if let Some(captures) = PARAMETER.captures(&string) {
+                if captures[0].len() == string.len() {
+                    if let Some(argument) = arguments.get(&captures[1]) {
+                        return (*argument).clone();
+                    }
+                }
+            }
+            Value::String(
+                PARAMETER
+                    .replace_all(&string, |captures: &Captures<'_>| {
+                        match arguments.get(&captures[1]) {
+                            Some(Value::String(argument)) => argument.clone(),
+                            Some(argument) => argument.to_string(),
+                            None => captures[0].to_string(),
+                        }
+                    })
+                    .into_owned(),
+            )
+        }
+        Value::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(|value| substitute(value, arguments))
+                .collect(),
+        ),
+        Value::Table(table) => Value::Table(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, substitute(value, arguments)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+fn namespace_inputs(component: &mut Value, instance_id: &str, component_ids: &HashSet<&str>) {
+    let Some(Value::Array(inputs)) = component.get_mut("inputs") else {
+        return;
+    };
+    for input in inputs {
+        if let Value::String(input) = input {
+            // Inputs may refer to a named output of a component, as in `route.errors`.
+            let id = input.split_once('.').map_or(input.as_str(), |(id, _)| id);
+            if component_ids.contains(id) {
+                *input = format!("{instance_id}_{input}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    fn parse(config: &str) -> Table {
+        toml::from_str(config).unwrap()
+    }
+
+    #[test]
+    fn without_templates() {
+        let config = parse(indoc! {r#"
+            [sources.in]
+            type = "demo_logs"
+        "#});
+
+        assert_eq!(expand(config.clone()).unwrap(), config);
+    }
+
+    #[test]
+    fn expands_instances() {
+        let config = parse(indoc! {r#"
+            [sources.shared]
+            type = "demo_logs"
+
+            [templates.tenant.parameters.tenant]
+            [templates.tenant.parameters.index]
+            default = "logs"
+            [templates.tenant.parameters.batch_size]
+            default = 100
+
+            [templates.tenant.transforms.tag]
+            type = "remap"
+            inputs = ["shared"]
+            source = '.tenant = "PARAM[tenant]"'
+
+            [templates.tenant.sinks.out]
+            type = "elasticsearch"
+            inputs = ["tag"]
+            bulk.index = "PARAM[tenant]-PARAM[index]"
+            batch.max_events = "PARAM[batch_size]"
+
+            [templates.tenant.instances.acme]
+            tenant = "acme"
+
+            [templates.tenant.instances.globex]
+            tenant = "globex"
+            index = "audit"
+            batch_size = 10
+        "#});
+
+        let expected = parse(indoc! {r#"
+            [sources.shared]
+            type = "demo_logs"
+
+            [transforms.acme_tag]
+            type = "remap"
+            inputs = ["shared"]
+            source = '.tenant = "acme"'
+
+            [transforms.globex_tag]
+            type = "remap"
+            inputs = ["shared"]
+            source = '.tenant = "globex"'
+
+            [sinks.acme_out]
+            type = "elasticsearch"
+            inputs = ["acme_tag"]
+            bulk.index = "acme-logs"
+            batch.max_events = 100
+
+            [sinks.globex_out]
+            type = "elasticsearch"
+            inputs = ["globex_tag"]
+            bulk.index = "globex-audit"
+            batch.max_events = 10
+        "#});
+
+        assert_eq!(expand(config).unwrap(), expected);
+    }
+
+    #[test]
+    fn namespaces_named_outputs() {
+        let config = parse(indoc! {r#"
+            [templates.split.sources.in]
+            type = "demo_logs"
+
+            [templates.split.transforms.route]
+            type = "route"
+            inputs = ["in"]
+            route.errors = '.level == "error"'
+
+            [templates.split.sinks.out]
+            type = "console"
+            inputs = ["route.errors"]
+
+            [templates.split.instances.a]
+        "#});
+
+        let expanded = expand(config).unwrap();
+
+        assert_eq!(
+            expanded["sinks"]["a_out"]["inputs"],
+            Value::Array(vec![Value::String("a_route.errors".into())])
+        );
+        assert_eq!(
+            expanded["transforms"]["a_route"]["inputs"],
+            Value::Array(vec![Value::String("a_in".into())])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_parameters() {
+        let config = parse(indoc! {r#"
+            [templates.tenant.parameters.tenant]
+
+            [templates.tenant.sinks.out]
+            type = "console"
+            inputs = ["in"]
+            target = "PARAM[target]"
+
+            [templates.tenant.instances.acme]
+            token = "secret"
+        "#});
+
+        let errors = expand(config).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                r#"Template "tenant" references undeclared parameter "target"."#,
+                r#"Instance "acme" of template "tenant" sets unknown parameter "token"."#,
+                r#"Instance "acme" of template "tenant" is missing required parameter "tenant"."#,
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_conflicting_ids() {
+        let config = parse(indoc! {r#"
+            [sinks.acme_out]
+            type = "console"
+            inputs = ["in"]
+
+            [templates.tenant.sinks.out]
+            type = "console"
+            inputs = ["in"]
+
+            [templates.tenant.instances.acme]
+        "#});
+
+        let errors = expand(config).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                r#"Instance "acme" of template "tenant" defines component "acme_out", which already exists."#
+            ]
+        );
+    }
+}
@@ -30,6 +30,7 @@ mod dot_graph;
 mod enrichment_table;
 pub mod format;
 mod graph;
+mod keyspace;
 mod loading;
 pub mod provider;
 pub mod schema;
@@ -44,9 +45,11 @@ pub mod watcher;
 
 pub use builder::ConfigBuilder;
 pub use cmd::{cmd, Opts};
-pub use diff::ConfigDiff;
+pub use diff::{ConfigDiff, Difference};
 pub use enrichment_table::{EnrichmentTableConfig, EnrichmentTableOuter};
 pub use format::{Format, FormatHint};
+pub(crate) use keyspace::load_keyspaces;
+pub use keyspace::KeyspaceConfig;
 pub use loading::{
     load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider_and_secrets,
     load_from_str, load_source_from_paths, merge_path_lists, process_paths, COLLECTOR,
@@ -54,12 +57,17 @@ pub use loading::{
 };
 pub use provider::ProviderConfig;
 pub use secret::SecretBackend;
-pub use sink::{BoxedSink, SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter};
+pub use sink::{
+    BoxedSink, SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter, REJECTED_OUTPUT,
+};
 pub use source::{BoxedSource, SourceConfig, SourceContext, SourceOuter};
 pub use transform::{
     get_transform_output_ids, BoxedTransform, TransformConfig, TransformContext, TransformOuter,
 };
-pub use unit_test::{build_unit_tests, build_unit_tests_main, UnitTestResult};
+pub use unit_test::{
+    build_unit_tests, build_unit_tests_main, build_unit_tests_with_golden_mode, GoldenMode,
+    UnitTestResult,
+};
 pub use validation::warnings;
 pub use vars::{interpolate, ENVIRONMENT_VARIABLE_INTERPOLATION_REGEX};
 pub use vector_lib::{
@@ -107,6 +115,7 @@ pub struct Config {
     pub enrichment_tables: IndexMap<ComponentKey, EnrichmentTableOuter>,
     tests: Vec<TestDefinition>,
     secret: IndexMap<ComponentKey, SecretBackends>,
+    pub keyspaces: IndexMap<ComponentKey, KeyspaceConfig>,
     pub graceful_shutdown_duration: Option<Duration>,
 }
 
@@ -190,6 +199,15 @@ impl Config {
                     .map(|input| (sink.clone(), input.clone()))
                     .collect();
                 self.propagate_acks_rec(inputs);
+            } else if let Some(rerouting_sink) = self.sinks.get(component) {
+                // Events rejected by a sink are acknowledged by the sinks its `rejected` output
+                // leads to, so their sources must be told to wait for them.
+                let inputs = rerouting_sink
+                    .inputs
+                    .iter()
+                    .map(|input| (sink.clone(), input.clone()))
+                    .collect();
+                self.propagate_acks_rec(inputs);
             }
         }
     }
@@ -346,6 +364,13 @@ pub struct TestDefinition<T: 'static = OutputId> {
     /// A set of component outputs that should not have emitted any events.
     #[serde(default)]
     pub no_outputs_from: Vec<T>,
+
+    /// A set of enrichment table fixtures, keyed by the name of the table.
+    ///
+    /// Each fixture replaces the enrichment table of the same name, if any, for the duration of
+    /// the test.
+    #[serde(default)]
+    pub enrichment_tables: IndexMap<ComponentKey, TestEnrichmentTable>,
 }
 
 impl TestDefinition<String> {
@@ -359,6 +384,7 @@ impl TestDefinition<String> {
             inputs,
             outputs,
             no_outputs_from,
+            enrichment_tables,
         } = self;
         let mut errors = Vec::new();
 
@@ -370,11 +396,12 @@ impl TestDefinition<String> {
                 let TestOutput {
                     extract_from,
                     conditions,
+                    golden_file,
                 } = old;
 
-                (extract_from.to_vec(), conditions)
+                (extract_from.to_vec(), conditions, golden_file)
             })
-            .filter_map(|(extract_from, conditions)| {
+            .filter_map(|(extract_from, conditions, golden_file)| {
                 let mut outputs = Vec::new();
                 for from in extract_from {
                     if let Some(output_id) = output_map.get(&from) {
@@ -392,6 +419,7 @@ impl TestDefinition<String> {
                     Some(TestOutput {
                         extract_from: outputs.into(),
                         conditions,
+                        golden_file,
                     })
                 }
             })
@@ -419,6 +447,7 @@ impl TestDefinition<String> {
                 inputs,
                 outputs,
                 no_outputs_from,
+                enrichment_tables,
             })
         } else {
             Err(errors)
@@ -434,6 +463,7 @@ impl TestDefinition<OutputId> {
             inputs,
             outputs,
             no_outputs_from,
+            enrichment_tables,
         } = self;
 
         let outputs = outputs
@@ -447,6 +477,7 @@ impl TestDefinition<OutputId> {
                     .collect::<Vec<_>>()
                     .into(),
                 conditions: old.conditions,
+                golden_file: old.golden_file,
             })
             .collect();
 
@@ -458,6 +489,7 @@ impl TestDefinition<OutputId> {
             inputs,
             outputs,
             no_outputs_from,
+            enrichment_tables,
         }
     }
 }
@@ -480,6 +512,17 @@ pub enum TestInputValue {
     Boolean(bool),
 }
 
+/// An enrichment table fixture for a unit test.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TestEnrichmentTable {
+    /// The rows of the table.
+    ///
+    /// Each row maps column names to values. Columns missing from a row are `null`.
+    pub rows: Vec<IndexMap<String, TestInputValue>>,
+}
+
 /// A unit test input.
 ///
 /// An input describes not only the type of event to insert, but also which transform within the
@@ -536,6 +579,14 @@ pub struct TestOutput<T: 'static = OutputId> {
 
     /// The conditions to run against the output to validate that they were transformed as expected.
     pub conditions: Option<Vec<conditions::AnyCondition>>,
+
+    /// The path of a golden file holding the events expected from the outputs, in order.
+    ///
+    /// The file holds either a JSON array of events, when its name ends with `.json`, or one
+    /// JSON event per line. Running `vector test --update-golden` writes the events the outputs
+    /// actually emitted to the file instead of comparing them.
+    #[configurable(metadata(docs::examples = "tests/golden/parse_logs.ndjson"))]
+    pub golden_file: Option<PathBuf>,
 }
 
 #[cfg(all(test, feature = "sources-file", feature = "sinks-console"))]
@@ -802,6 +853,76 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn bad_buffer_stages() {
+        let errors = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+
+            [[sinks.out.buffer]]
+            type = "disk"
+            max_size = 268435488
+            when_full = "overflow"
+
+            [[sinks.out.buffer]]
+            type = "memory"
+            max_events = 100
+            when_full = "overflow"
+
+            [[sinks.out.buffer]]
+            type = "memory"
+            max_events = 100
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                "Sink \"out\" has a disk buffer stage in front of another stage, but only a memory buffer stage can overflow to a disk buffer stage.",
+                "Sink \"out\" has more than one memory buffer stage.",
+            ]
+        );
+
+        let errors = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+
+            [[sinks.out.buffer]]
+            type = "memory"
+            max_events = 100
+
+            [[sinks.out.buffer]]
+            type = "disk"
+            max_size = 268435488
+            when_full = "overflow"
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                "Sink \"out\" has buffer stage 0 in front of another stage, which requires `when_full = \"overflow\"`.",
+                "Sink \"out\" has its last buffer stage set to `when_full = \"overflow\"`, but there is no next stage to overflow to.",
+            ]
+        );
+    }
+
     #[test]
     fn default_data_dir() {
         let config = load_from_str(
@@ -1,4 +1,7 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 use enum_dispatch::enum_dispatch;
 use vector_lib::configurable::NamedComponent;
@@ -13,4 +16,11 @@ pub trait SecretBackend: NamedComponent + core::fmt::Debug + Send + Sync {
         secret_keys: HashSet<String>,
         signal_rx: &mut signal::SignalRx,
     ) -> crate::Result<HashMap<String, String>>;
+
+    /// How often the secrets retrieved from this backend are checked for changes.
+    ///
+    /// Backends returning `None` are only queried when the configuration is loaded.
+    fn refresh_interval(&self) -> Option<Duration> {
+        None
+    }
 }
@@ -21,6 +21,9 @@ use crate::sinks::{util::UriSerde, Healthcheck};
 
 pub type BoxedSink = Box<dyn SinkConfig>;
 
+/// The name of the output that sinks reroute the events they reject to.
+pub const REJECTED_OUTPUT: &str = "rejected";
+
 impl Configurable for BoxedSink {
     fn referenceable_name() -> Option<&'static str> {
         Some("vector::sinks::Sinks")
@@ -79,6 +82,32 @@ where
     #[serde(default, skip_serializing_if = "vector_lib::serde::is_default")]
     proxy: ProxyConfig,
 
+    /// Reroutes the events the sink fails to deliver to a named output instead of dropping them.
+    ///
+    /// Normally, events that are rejected by the downstream service, or that still fail once all
+    /// retries are exhausted, are dropped. Setting `reroute_rejected` to `true` forwards a copy of
+    /// each of these events, as it was received by the sink, to a specially-named output,
+    /// `rejected`, which other components can consume as `<sink_id>.rejected`: for example, a
+    /// sink writing them to object storage as a dead-letter queue.
+    ///
+    /// Events sent to the `rejected` output are only acknowledged once they are delivered by the
+    /// components consuming it.
+    #[serde(default, skip_serializing_if = "vector_lib::serde::is_default")]
+    #[configurable(metadata(docs::human_name = "Reroute Rejected Events"))]
+    pub reroute_rejected: bool,
+
+    /// The semantic meanings the events sent to this sink must have, such as `timestamp`,
+    /// `severity`, or `host`, in addition to the ones required by the sink itself.
+    ///
+    /// Vector fails to load the configuration when the schema of the events of one of the inputs
+    /// of the sink doesn't define one of these meanings. Only events using the `Vector` log
+    /// namespace are validated, and their types are only checked when `schema.validation` is
+    /// enabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "timestamp", docs::examples = "severity"))]
+    pub required_meanings: Vec<String>,
+
     #[serde(flatten)]
     #[configurable(metadata(docs::hidden))]
     pub inner: BoxedSink,
@@ -100,6 +129,8 @@ where
             healthcheck_uri: None,
             inner: inner.into(),
             proxy: Default::default(),
+            reroute_rejected: false,
+            required_meanings: Vec::new(),
             graph: Default::default(),
         }
     }
@@ -157,6 +188,8 @@ where
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            reroute_rejected: self.reroute_rejected,
+            required_meanings: self.required_meanings,
             graph: self.graph,
         }
     }
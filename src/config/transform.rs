@@ -1,5 +1,6 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::num::NonZeroUsize;
 
 use async_trait::async_trait;
 use dyn_clone::DynClone;
@@ -13,10 +14,13 @@ use vector_lib::configurable::{
 use vector_lib::{
     config::{GlobalOptions, Input, LogNamespace, TransformOutput},
     id::Inputs,
+    lookup::lookup_v2::ConfigTargetPath,
     schema,
     transform::Transform,
 };
 
+use vector_vrl_functions::set_state::Keyspaces;
+
 use super::dot_graph::GraphConfig;
 use super::schema::Options as SchemaOptions;
 use super::ComponentKey;
@@ -64,6 +68,27 @@ where
     #[configurable(derived)]
     pub inputs: Inputs<T>,
 
+    /// The maximum number of tasks processing events for this transform concurrently.
+    ///
+    /// Only transforms that can safely process events concurrently, such as `remap`, `filter`, and
+    /// `route`, support this option. They default to using as many tasks as there are worker
+    /// threads. Setting this to `1` processes all events in a single task.
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<NonZeroUsize>,
+
+    /// The field used to assign events to tasks when processing events concurrently.
+    ///
+    /// Events with the same value for this field are processed by the same task and output in the
+    /// order they were received, while events with different values may be reordered. Metrics are
+    /// assigned to tasks by series.
+    ///
+    /// If unset, all events are output in the order they were received.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = ".tenant"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ordering_key: Option<ConfigTargetPath>,
+
     #[configurable(metadata(docs::hidden))]
     #[serde(flatten)]
     pub inner: BoxedTransform,
@@ -84,6 +109,8 @@ where
             inputs,
             inner,
             graph: Default::default(),
+            concurrency: None,
+            ordering_key: None,
         }
     }
 
@@ -104,6 +131,8 @@ where
             inputs: Inputs::from_iter(inputs),
             inner: self.inner,
             graph: self.graph,
+            concurrency: self.concurrency,
+            ordering_key: self.ordering_key,
         }
     }
 }
@@ -118,6 +147,9 @@ pub struct TransformContext {
 
     pub enrichment_tables: vector_lib::enrichment::TableRegistry,
 
+    /// The keyspaces of the topology, shared by the programs of all `remap` transforms.
+    pub keyspaces: Keyspaces,
+
     /// Tracks the schema IDs assigned to schemas exposed by the transform.
     ///
     /// Given a transform can expose multiple [`TransformOutput`] channels, the ID is tied to the identifier of
@@ -144,6 +176,7 @@ impl Default for TransformContext {
             key: Default::default(),
             globals: Default::default(),
             enrichment_tables: Default::default(),
+            keyspaces: Default::default(),
             schema_definitions: HashMap::from([(None, HashMap::new())]),
             merged_schema_definition: schema::Definition::any(),
             schema: SchemaOptions::default(),
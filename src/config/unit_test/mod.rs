@@ -13,6 +13,7 @@ mod unit_test_components;
 
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
     sync::Arc,
 };
 
@@ -31,19 +32,23 @@ use vrl::{
 };
 
 pub use self::unit_test_components::{
-    UnitTestSinkCheck, UnitTestSinkConfig, UnitTestSinkResult, UnitTestSourceConfig,
-    UnitTestStreamSinkConfig, UnitTestStreamSourceConfig,
+    GoldenFile, GoldenMode, UnitTestEnrichmentTableConfig, UnitTestSinkCheck, UnitTestSinkConfig,
+    UnitTestSinkResult, UnitTestSourceConfig, UnitTestStreamSinkConfig, UnitTestStreamSourceConfig,
 };
 use super::{compiler::expand_globs, graph::Graph, transform::get_transform_output_ids, OutputId};
 use crate::{
     conditions::Condition,
     config::{
-        self, loading, ComponentKey, Config, ConfigBuilder, ConfigPath, SinkOuter, SourceOuter,
-        TestDefinition, TestInput, TestInputValue, TestOutput,
+        self, loading, ComponentKey, Config, ConfigBuilder, ConfigPath, EnrichmentTableOuter,
+        SinkOuter, SourceOuter, TestDefinition, TestEnrichmentTable, TestInput, TestInputValue,
+        TestOutput,
     },
     event::{Event, EventMetadata, LogEvent, Value},
     signal,
-    topology::{builder::TopologyPieces, RunningTopology},
+    topology::{
+        builder::{reload_enrichment_tables, TopologyPieces},
+        RunningTopology,
+    },
 };
 
 pub struct UnitTest {
@@ -59,6 +64,12 @@ pub struct UnitTestResult {
 
 impl UnitTest {
     pub async fn run(self) -> UnitTestResult {
+        if !self.config.enrichment_tables.is_empty() {
+            if let Err(errors) = reload_enrichment_tables(&self.config).await {
+                return UnitTestResult { errors };
+            }
+        }
+
         let diff = config::ConfigDiff::initial(&self.config);
         let (topology, _) = RunningTopology::start_validated(self.config, diff, self.pieces)
             .await
@@ -99,6 +110,7 @@ fn init_log_schema_from_paths(
 pub async fn build_unit_tests_main(
     paths: &[ConfigPath],
     signal_handler: &mut signal::SignalHandler,
+    golden_mode: GoldenMode,
 ) -> Result<Vec<UnitTest>, Vec<String>> {
     init_log_schema_from_paths(paths, false)?;
     let mut secrets_backends_loader = loading::load_secret_backends_from_paths(paths)?;
@@ -112,11 +124,16 @@ pub async fn build_unit_tests_main(
         loading::load_builder_from_paths(paths)?
     };
 
-    build_unit_tests(config_builder).await
+    build_unit_tests_with_golden_mode(config_builder, golden_mode).await
 }
 
-pub async fn build_unit_tests(
+pub async fn build_unit_tests(config_builder: ConfigBuilder) -> Result<Vec<UnitTest>, Vec<String>> {
+    build_unit_tests_with_golden_mode(config_builder, GoldenMode::Compare).await
+}
+
+pub async fn build_unit_tests_with_golden_mode(
     mut config_builder: ConfigBuilder,
+    golden_mode: GoldenMode,
 ) -> Result<Vec<UnitTest>, Vec<String>> {
     // Sanitize config by removing existing sources and sinks
     config_builder.sources = Default::default();
@@ -125,7 +142,7 @@ pub async fn build_unit_tests(
     let test_definitions = std::mem::take(&mut config_builder.tests);
     let mut tests = Vec::new();
     let mut build_errors = Vec::new();
-    let metadata = UnitTestBuildMetadata::initialize(&mut config_builder)?;
+    let metadata = UnitTestBuildMetadata::initialize(&mut config_builder, golden_mode)?;
 
     for mut test_definition in test_definitions {
         let test_name = test_definition.name.clone();
@@ -163,10 +180,15 @@ pub struct UnitTestBuildMetadata {
     template_sources: IndexMap<ComponentKey, UnitTestSourceConfig>,
     // A mapping from transform name to unit test sink name.
     sink_ids: HashMap<OutputId, String>,
+    // Whether golden files are compared against, or updated with, the test outputs.
+    golden_mode: GoldenMode,
 }
 
 impl UnitTestBuildMetadata {
-    pub fn initialize(config_builder: &mut ConfigBuilder) -> Result<Self, Vec<String>> {
+    pub fn initialize(
+        config_builder: &mut ConfigBuilder,
+        golden_mode: GoldenMode,
+    ) -> Result<Self, Vec<String>> {
         // A unique id used to name test sources and sinks to avoid name clashes
         let random_id = Uuid::new_v4().to_string();
 
@@ -226,6 +248,7 @@ impl UnitTestBuildMetadata {
             source_ids,
             template_sources,
             sink_ids,
+            golden_mode,
         })
     }
 
@@ -286,7 +309,7 @@ impl UnitTestBuildMetadata {
         let mut template_sinks = IndexMap::new();
         let mut test_result_rxs = Vec::new();
         // Add sinks with checks
-        for (ids, checks) in outputs {
+        for (ids, (checks, golden_paths)) in outputs {
             let (tx, rx) = oneshot::channel();
             let sink_ids = ids.clone();
             let sink_config = UnitTestSinkConfig {
@@ -294,6 +317,13 @@ impl UnitTestBuildMetadata {
                 transform_ids: ids.iter().map(|id| id.to_string()).collect(),
                 result_tx: Arc::new(Mutex::new(Some(tx))),
                 check: UnitTestSinkCheck::Checks(checks),
+                golden_files: golden_paths
+                    .into_iter()
+                    .map(|path| GoldenFile {
+                        path,
+                        mode: self.golden_mode,
+                    })
+                    .collect(),
             };
 
             test_result_rxs.push(rx);
@@ -308,6 +338,7 @@ impl UnitTestBuildMetadata {
                 transform_ids: vec![id.to_string()],
                 result_tx: Arc::new(Mutex::new(Some(tx))),
                 check: UnitTestSinkCheck::NoOutputs,
+                golden_files: Vec::new(),
             };
 
             test_result_rxs.push(rx);
@@ -395,6 +426,12 @@ async fn build_unit_test(
 
     config_builder.sources = sources;
     config_builder.sinks = sinks;
+    for (key, fixture) in &test.enrichment_tables {
+        let table = build_enrichment_table(key, fixture)?;
+        config_builder
+            .enrichment_tables
+            .insert(key.clone(), EnrichmentTableOuter::new(table));
+    }
     expand_globs(&mut config_builder);
 
     let graph = Graph::new_unchecked(
@@ -501,6 +538,7 @@ fn get_loose_end_outputs_sink(config: &ConfigBuilder) -> Option<SinkOuter<String
             transform_ids: vec![],
             result_tx: Arc::new(Mutex::new(None)),
             check: UnitTestSinkCheck::NoOp,
+            golden_files: Vec::new(),
         };
         Some(SinkOuter::new(loose_end_outputs, noop_sink))
     }
@@ -545,10 +583,13 @@ fn build_and_validate_inputs(
     }
 }
 
+/// Conditions, and paths of golden files, to check the events of a set of outputs against.
+type OutputChecks = (Vec<Vec<Condition>>, Vec<PathBuf>);
+
 fn build_outputs(
     test_outputs: &[TestOutput],
-) -> Result<IndexMap<Vec<OutputId>, Vec<Vec<Condition>>>, Vec<String>> {
-    let mut outputs: IndexMap<Vec<OutputId>, Vec<Vec<Condition>>> = IndexMap::new();
+) -> Result<IndexMap<Vec<OutputId>, OutputChecks>, Vec<String>> {
+    let mut outputs: IndexMap<Vec<OutputId>, OutputChecks> = IndexMap::new();
     let mut errors = Vec::new();
 
     for output in test_outputs {
@@ -569,10 +610,11 @@ fn build_outputs(
             }
         }
 
-        outputs
+        let (existing_conditions, golden_paths) = outputs
             .entry(output.extract_from.clone().to_vec())
-            .and_modify(|existing_conditions| existing_conditions.push(conditions.clone()))
-            .or_insert(vec![conditions.clone()]);
+            .or_default();
+        existing_conditions.push(conditions);
+        golden_paths.extend(output.golden_file.clone());
     }
 
     if errors.is_empty() {
@@ -622,16 +664,8 @@ fn build_input_event(input: &TestInput) -> Result<Event, String> {
             if let Some(log_fields) = &input.log_fields {
                 let mut event = LogEvent::from_str_legacy("");
                 for (path, value) in log_fields {
-                    let value: Value = match value {
-                        TestInputValue::String(s) => Value::from(s.to_owned()),
-                        TestInputValue::Boolean(b) => Value::from(*b),
-                        TestInputValue::Integer(i) => Value::from(*i),
-                        TestInputValue::Float(f) => Value::from(
-                            NotNan::new(*f).map_err(|_| "NaN value not supported".to_string())?,
-                        ),
-                    };
                     event
-                        .parse_path_and_insert(path, value)
+                        .parse_path_and_insert(path, build_input_value(value)?)
                         .map_err(|e| e.to_string())?;
                 }
                 Ok(event.into())
@@ -652,3 +686,48 @@ fn build_input_event(input: &TestInput) -> Result<Event, String> {
         )),
     }
 }
+
+fn build_input_value(value: &TestInputValue) -> Result<Value, String> {
+    Ok(match value {
+        TestInputValue::String(s) => Value::from(s.to_owned()),
+        TestInputValue::Boolean(b) => Value::from(*b),
+        TestInputValue::Integer(i) => Value::from(*i),
+        TestInputValue::Float(f) => {
+            Value::from(NotNan::new(*f).map_err(|_| "NaN value not supported".to_string())?)
+        }
+    })
+}
+
+fn build_enrichment_table(
+    key: &ComponentKey,
+    fixture: &TestEnrichmentTable,
+) -> Result<UnitTestEnrichmentTableConfig, Vec<String>> {
+    let mut headers = Vec::<String>::new();
+    for column in fixture.rows.iter().flat_map(|row| row.keys()) {
+        if !headers.contains(column) {
+            headers.push(column.clone());
+        }
+    }
+
+    let mut data = Vec::new();
+    let mut errors = Vec::new();
+    for (index, row) in fixture.rows.iter().enumerate() {
+        let values = headers
+            .iter()
+            .map(|column| row.get(column).map_or(Ok(Value::Null), build_input_value))
+            .collect::<Result<Vec<_>, _>>();
+        match values {
+            Ok(values) => data.push(values),
+            Err(error) => errors.push(format!(
+                "enrichment_tables.{}.rows[{}]: {}",
+                key, index, error
+            )),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(UnitTestEnrichmentTableConfig { headers, data })
+    } else {
+        Err(errors)
+    }
+}
@@ -1277,3 +1277,154 @@ async fn test_glob_input() {
     let mut tests = build_unit_tests(config).await.unwrap();
     assert!(tests.remove(0).run().await.errors.is_empty());
 }
+
+#[tokio::test]
+async fn test_enrichment_table_fixtures() {
+    crate::test_util::trace_init();
+
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+    [transforms.enrich]
+      type = "remap"
+      inputs = [ "ignored" ]
+      drop_on_error = true
+      reroute_dropped = true
+      source = """
+          .user = get_enrichment_table_record!("users", { "id": .user_id }).name
+      """
+
+    [transforms.greet]
+      type = "remap"
+      inputs = [ "enrich" ]
+      source = """
+          .greeting = "hello " + string!(.user)
+      """
+
+    [[tests]]
+      name = "enrichment fixture through a chain"
+
+      [tests.enrichment_tables.users]
+        rows = [ { id = "1", name = "alice" } ]
+
+      [[tests.inputs]]
+        type = "log"
+        insert_at = "enrich"
+
+        [tests.inputs.log_fields]
+          user_id = "1"
+
+      [[tests.inputs]]
+        type = "log"
+        insert_at = "enrich"
+
+        [tests.inputs.log_fields]
+          user_id = "2"
+
+      [[tests.outputs]]
+        extract_from = "greet"
+
+        [[tests.outputs.conditions]]
+          type = "vrl"
+          source = """
+              assert_eq!(.greeting, "hello alice", "incorrect greeting")
+          """
+
+      [[tests.outputs]]
+        extract_from = "enrich.dropped"
+
+        [[tests.outputs.conditions]]
+          type = "vrl"
+          source = """
+              assert_eq!(.user_id, "2", "incorrect dropped event")
+          """
+
+    [[tests]]
+      name = "another enrichment fixture"
+
+      [tests.enrichment_tables.users]
+        rows = [ { id = "1", name = "bob" } ]
+
+      [[tests.inputs]]
+        type = "log"
+        insert_at = "enrich"
+
+        [tests.inputs.log_fields]
+          user_id = "1"
+
+      [[tests.outputs]]
+        extract_from = "greet"
+
+        [[tests.outputs.conditions]]
+          type = "vrl"
+          source = """
+              assert_eq!(.greeting, "hello bob", "incorrect greeting")
+          """
+  "#})
+    .unwrap();
+
+    // Run the tests in the reverse order they were built in, so that each one has to load its
+    // own fixture again.
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert_eq!(tests.remove(1).run().await.errors, Vec::<String>::new());
+    assert_eq!(tests.remove(0).run().await.errors, Vec::<String>::new());
+}
+
+#[tokio::test]
+async fn test_golden_file() {
+    crate::test_util::trace_init();
+
+    let golden_file = crate::test_util::temp_dir().join("golden.ndjson");
+    let config = format!(
+        indoc! {r#"
+        [transforms.shout]
+          type = "remap"
+          inputs = [ "ignored" ]
+          source = """
+              .message = upcase!(.message)
+          """
+
+        [[tests]]
+          name = "golden file test"
+
+          [[tests.inputs]]
+            type = "vrl"
+            insert_at = "shout"
+            source = """
+                .message = "hello"
+            """
+
+          [[tests.outputs]]
+            extract_from = "shout"
+            golden_file = '{}'
+    "#},
+        golden_file.display()
+    );
+    std::fs::create_dir_all(golden_file.parent().unwrap()).unwrap();
+
+    let build = |golden_mode| {
+        let config: ConfigBuilder = toml::from_str(&config).unwrap();
+        build_unit_tests_with_golden_mode(config, golden_mode)
+    };
+
+    // Comparing against a missing golden file fails
+    let mut tests = build(GoldenMode::Compare).await.unwrap();
+    assert!(!tests.remove(0).run().await.errors.is_empty());
+
+    let mut tests = build(GoldenMode::Update).await.unwrap();
+    assert_eq!(tests.remove(0).run().await.errors, Vec::<String>::new());
+    assert_eq!(
+        std::fs::read_to_string(&golden_file).unwrap(),
+        "{\"message\":\"HELLO\"}\n"
+    );
+
+    let mut tests = build(GoldenMode::Compare).await.unwrap();
+    assert_eq!(tests.remove(0).run().await.errors, Vec::<String>::new());
+
+    std::fs::write(&golden_file, "{\"message\":\"hello\"}\n").unwrap();
+    let mut tests = build(GoldenMode::Compare).await.unwrap();
+    let errors = tests.remove(0).run().await.errors;
+    assert!(
+        errors.iter().any(|error| error.contains("event 0 differs")),
+        "{:?}",
+        errors
+    );
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc, time::SystemTime};
 
 use futures::{stream, Sink, Stream};
 use futures_util::{future, stream::BoxStream, FutureExt, StreamExt};
@@ -6,16 +6,20 @@ use tokio::sync::{oneshot, Mutex};
 use vector_lib::configurable::configurable_component;
 use vector_lib::{
     config::{DataType, Input, LogNamespace},
+    enrichment::Table,
     event::Event,
     schema,
     sink::{StreamSink, VectorSink},
 };
+use vrl::value::Value;
 
 use crate::{
     conditions::Condition,
     config::{
-        AcknowledgementsConfig, SinkConfig, SinkContext, SourceConfig, SourceContext, SourceOutput,
+        AcknowledgementsConfig, EnrichmentTableConfig, GlobalOptions, SinkConfig, SinkContext,
+        SourceConfig, SourceContext, SourceOutput,
     },
+    enrichment_tables::file::{File, FileConfig, FileData},
     sinks::Healthcheck,
     sources,
 };
@@ -127,6 +131,90 @@ pub enum UnitTestSinkCheck {
     NoOp,
 }
 
+/// How the golden files of test outputs are handled.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum GoldenMode {
+    /// Compare the events received against the golden files.
+    #[default]
+    Compare,
+
+    /// Write the events received to the golden files.
+    Update,
+}
+
+/// A golden file holding the events expected from the outputs of a test.
+#[derive(Clone, Debug)]
+pub struct GoldenFile {
+    pub path: PathBuf,
+    pub mode: GoldenMode,
+}
+
+impl GoldenFile {
+    /// Whether the file holds a JSON array of events rather than one event per line.
+    fn is_json_array(&self) -> bool {
+        self.path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+    }
+
+    async fn check(&self, events: &[Event]) -> Result<(), String> {
+        let actual = events.iter().map(event_to_json).collect::<Vec<_>>();
+
+        match self.mode {
+            GoldenMode::Update => {
+                let contents = if self.is_json_array() {
+                    serde_json::to_string_pretty(&actual).map_err(|error| error.to_string())?
+                } else {
+                    actual
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                };
+                tokio::fs::write(&self.path, contents + "\n")
+                    .await
+                    .map_err(|error| format!("unable to write golden file: {}", error))
+            }
+            GoldenMode::Compare => {
+                let contents = tokio::fs::read_to_string(&self.path)
+                    .await
+                    .map_err(|error| format!("unable to read golden file: {}", error))?;
+                let expected = if self.is_json_array() {
+                    serde_json::from_str::<Vec<serde_json::Value>>(&contents)
+                        .map_err(|error| format!("invalid golden file: {}", error))?
+                } else {
+                    contents
+                        .lines()
+                        .filter(|line| !line.trim().is_empty())
+                        .map(serde_json::from_str)
+                        .collect::<Result<Vec<serde_json::Value>, _>>()
+                        .map_err(|error| format!("invalid golden file: {}", error))?
+                };
+
+                if let Some((index, (expected, actual))) = expected
+                    .iter()
+                    .zip(actual.iter())
+                    .enumerate()
+                    .find(|(_, (expected, actual))| expected != actual)
+                {
+                    Err(format!(
+                        "event {} differs from the golden file:\n    expected: {}\n    actual:   {}",
+                        index, expected, actual
+                    ))
+                } else if expected.len() != actual.len() {
+                    Err(format!(
+                        "expected {} events from the golden file, received {}",
+                        expected.len(),
+                        actual.len()
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct UnitTestSinkResult {
     pub test_name: String,
@@ -152,6 +240,10 @@ pub struct UnitTestSinkConfig {
     #[serde(skip)]
     #[derivative(Debug = "ignore")]
     pub check: UnitTestSinkCheck,
+
+    /// Golden files the events that reach the sink are checked against.
+    #[serde(skip)]
+    pub golden_files: Vec<GoldenFile>,
 }
 
 impl_generate_config_from_default!(UnitTestSinkConfig);
@@ -166,6 +258,7 @@ impl SinkConfig for UnitTestSinkConfig {
             transform_ids: self.transform_ids.clone(),
             result_tx: tx,
             check: self.check.clone(),
+            golden_files: self.golden_files.clone(),
         };
         let healthcheck = future::ok(()).boxed();
 
@@ -187,6 +280,7 @@ pub struct UnitTestSink {
     // None for NoOp test sinks
     pub result_tx: Option<oneshot::Sender<UnitTestSinkResult>>,
     pub check: UnitTestSinkCheck,
+    pub golden_files: Vec<GoldenFile>,
 }
 
 #[async_trait::async_trait]
@@ -241,6 +335,15 @@ impl StreamSink<Event> for UnitTestSink {
                         result.test_errors.extend(check_errors);
                     }
 
+                    for golden_file in self.golden_files.iter() {
+                        if let Err(error) = golden_file.check(&output_events).await {
+                            result.test_errors.push(format!(
+                                "golden file {:?} for transforms {:?} failed: {}",
+                                golden_file.path, self.transform_ids, error
+                            ));
+                        }
+                    }
+
                     // If there are errors, add a summary of events received
                     if !result.test_errors.is_empty() {
                         result.test_errors.push(format!(
@@ -316,6 +419,35 @@ impl SinkConfig for UnitTestStreamSinkConfig {
     }
 }
 
+/// Configuration for the `unit_test` enrichment table.
+#[configurable_component(enrichment_table("unit_test"))]
+#[derive(Clone, Debug, Default)]
+pub struct UnitTestEnrichmentTableConfig {
+    /// Names of the columns of the table.
+    #[serde(skip)]
+    pub headers: Vec<String>,
+
+    /// Rows of the table, with a value for each of the columns.
+    #[serde(skip)]
+    pub data: Vec<Vec<Value>>,
+}
+
+impl_generate_config_from_default!(UnitTestEnrichmentTableConfig);
+
+impl EnrichmentTableConfig for UnitTestEnrichmentTableConfig {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        // The fixture is searched the same way as a file table, without a file to reload from.
+        Ok(Box::new(File::new(
+            FileConfig::default(),
+            FileData {
+                headers: self.headers.clone(),
+                data: self.data.clone(),
+                modified: SystemTime::now(),
+            },
+        )))
+    }
+}
+
 fn events_to_string(events: &[Event]) -> String {
     events
         .iter()
@@ -331,3 +463,12 @@ fn events_to_string(events: &[Event]) -> String {
         .collect::<Vec<_>>()
         .join("\n  ")
 }
+
+fn event_to_json(event: &Event) -> serde_json::Value {
+    match event {
+        Event::Log(log) => serde_json::to_value(log),
+        Event::Metric(metric) => serde_json::to_value(metric),
+        Event::Trace(trace) => serde_json::to_value(trace),
+    }
+    .unwrap_or_else(|_| serde_json::Value::Object(Default::default()))
+}
@@ -3,11 +3,14 @@ use futures_util::{stream, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use heim::{disk::Partition, units::information::byte};
 use indexmap::IndexMap;
 use std::{collections::HashMap, path::PathBuf};
-use vector_lib::{buffers::config::DiskUsage, internal_event::DEFAULT_OUTPUT};
+use vector_lib::{
+    buffers::{config::DiskUsage, BufferType, WhenFull},
+    internal_event::DEFAULT_OUTPUT,
+};
 
 use super::{
     builder::ConfigBuilder, transform::get_transform_output_ids, ComponentKey, Config, OutputId,
-    Resource,
+    Resource, REJECTED_OUTPUT,
 };
 
 /// Check that provide + topology config aren't present in the same builder, which is an error.
@@ -80,6 +83,17 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         ));
     }
 
+    for (key, transform) in &config.transforms {
+        if (transform.concurrency.is_some() || transform.ordering_key.is_some())
+            && !transform.inner.enable_concurrency()
+        {
+            errors.push(format!(
+                "Transform \"{}\" does not support processing events concurrently, so it can't set `concurrency` or `ordering_key`.",
+                key
+            ));
+        }
+    }
+
     // Warnings and errors
     let sink_inputs = config
         .sinks
@@ -149,6 +163,50 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// Check that buffers made of multiple stages only overflow from a memory stage to a disk stage.
+pub fn check_buffers(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    for (key, sink) in config.sinks.iter() {
+        let stages = sink.buffer.stages();
+        let mut seen = Vec::new();
+        for (index, stage) in stages.iter().enumerate() {
+            let (buffer_type, when_full) = match stage {
+                BufferType::Memory { when_full, .. } => ("memory", when_full),
+                BufferType::DiskV2 { when_full, .. } => ("disk", when_full),
+            };
+            let is_last = index == stages.len() - 1;
+
+            if seen.contains(&buffer_type) {
+                errors.push(format!(
+                    "Sink \"{key}\" has more than one {buffer_type} buffer stage."
+                ));
+            }
+            seen.push(buffer_type);
+
+            if !is_last && buffer_type == "disk" {
+                errors.push(format!(
+                    "Sink \"{key}\" has a disk buffer stage in front of another stage, but only a memory buffer stage can overflow to a disk buffer stage."
+                ));
+            }
+            match (when_full, is_last) {
+                (WhenFull::Overflow, true) => errors.push(format!(
+                    "Sink \"{key}\" has its last buffer stage set to `when_full = \"overflow\"`, but there is no next stage to overflow to."
+                )),
+                (WhenFull::Block | WhenFull::DropNewest, false) => errors.push(format!(
+                    "Sink \"{key}\" has buffer stage {index} in front of another stage, which requires `when_full = \"overflow\"`."
+                )),
+                _ => {}
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 /// To avoid collisions between `output` metric tags, check that a component
 /// does not have a named output with the name [`DEFAULT_OUTPUT`]
 pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
@@ -349,7 +407,13 @@ pub fn warnings(config: &Config) -> Vec<String> {
         .collect::<Vec<_>>()
     });
 
-    for (input_type, id) in transform_ids.chain(source_ids) {
+    let sink_ids = config
+        .sinks
+        .iter()
+        .filter(|(_, sink)| sink.reroute_rejected)
+        .map(|(key, _)| ("sink", OutputId::from((key, REJECTED_OUTPUT.to_owned()))));
+
+    for (input_type, id) in transform_ids.chain(source_ids).chain(sink_ids) {
         if !config
             .transforms
             .iter()
@@ -1,5 +1,5 @@
 //! Handles enrichment tables for `type = file`.
-use std::{collections::HashMap, fs, hash::Hasher, path::PathBuf, time::SystemTime};
+use std::{collections::HashMap, fs, hash::Hasher, io, path::PathBuf, time::SystemTime};
 
 use bytes::Bytes;
 use tracing::trace;
@@ -119,7 +119,7 @@ const fn default_delimiter() -> char {
 }
 
 impl FileConfig {
-    fn parse_column(
+    pub(crate) fn parse_column(
         &self,
         timezone: TimeZone,
         column: &str,
@@ -182,6 +182,29 @@ impl FileConfig {
 
     /// Load the configured file into memory. Required to create a new file enrichment table.
     pub fn load_file(&self, timezone: TimeZone) -> crate::Result<FileData> {
+        let file = fs::File::open(&self.file.path)?;
+        let modified = file.metadata()?.modified()?;
+        let (headers, data) = self.read_csv(timezone, file)?;
+
+        trace!(
+            "Loaded enrichment file {} with headers {:?}.",
+            self.file.path.to_str().unwrap_or("path with invalid utf"),
+            headers
+        );
+
+        Ok(FileData {
+            headers,
+            data,
+            modified,
+        })
+    }
+
+    /// Reads CSV data according to the configured encoding, returning the headers and the rows.
+    pub(crate) fn read_csv<R: io::Read>(
+        &self,
+        timezone: TimeZone,
+        reader: R,
+    ) -> crate::Result<(Vec<String>, Vec<Vec<Value>>)> {
         let Encoding::Csv {
             include_headers,
             delimiter,
@@ -190,7 +213,7 @@ impl FileConfig {
         let mut reader = csv::ReaderBuilder::new()
             .has_headers(include_headers)
             .delimiter(delimiter as u8)
-            .from_path(&self.file.path)?;
+            .from_reader(reader);
 
         let headers = if include_headers {
             reader
@@ -218,19 +241,7 @@ impl FileConfig {
             })
             .collect::<crate::Result<Vec<_>>>()?;
 
-        trace!(
-            "Loaded enrichment file {} with headers {:?}.",
-            self.file.path.to_str().unwrap_or("path with invalid utf"),
-            headers
-        );
-
-        let file = reader.into_inner();
-
-        Ok(FileData {
-            headers,
-            data,
-            modified: file.metadata()?.modified()?,
-        })
+        Ok((headers, data))
     }
 }
 
@@ -0,0 +1,536 @@
+//! Handles enrichment tables for `type = http`.
+//!
+//! The table is downloaded from an HTTP endpoint when loaded, and then downloaded again on a fixed
+//! interval. Every successful download replaces the data of the table in one go, so lookups
+//! always see either the previous or the new version of the table.
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::PathBuf,
+    sync::{Arc, RwLock, Weak},
+    time::{Duration, SystemTime},
+};
+
+use bytes::Bytes;
+use http::{StatusCode, Uri};
+use hyper::Body;
+use indexmap::IndexMap;
+use snafu::{ResultExt, Snafu};
+use vector_lib::configurable::configurable_component;
+use vector_lib::enrichment::{Case, Condition, IndexHandle, Table};
+use vector_lib::TimeZone;
+use vrl::value::{ObjectMap, Value};
+
+use super::file::{self, File, FileConfig, FileData, FileSettings};
+use crate::{
+    config::{EnrichmentTableConfig, GenerateConfig, ProxyConfig},
+    http::{Auth, HttpClient, HttpError},
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[derive(Debug, Snafu)]
+enum FetchError {
+    #[snafu(display("Failed to build request: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("Failed to send request: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("Failed to read response: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display("Unexpected status code: {}", status))]
+    UnexpectedStatus { status: StatusCode },
+}
+
+/// Encoding of the data served by the endpoint.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Encoding {
+    /// Decodes the data as a [CSV][csv] (comma-separated values) document.
+    ///
+    /// [csv]: https://wikipedia.org/wiki/Comma-separated_values
+    Csv {
+        /// Whether or not the data contains column headers.
+        ///
+        /// When set to `true`, the first row of the data will be read as the header row, and
+        /// the values will be used for the names of each column. This is the default behavior.
+        ///
+        /// When set to `false`, columns are referred to by their numerical index.
+        #[serde(default = "crate::serde::default_true")]
+        include_headers: bool,
+
+        /// The delimiter used to separate fields in each row of the data.
+        #[serde(default = "default_delimiter")]
+        delimiter: char,
+    },
+
+    /// Decodes the data as a [JSON][json] array of objects, each object being a row.
+    ///
+    /// The columns of the table are the union of the keys of all objects. Rows missing a column
+    /// have a `null` value for it.
+    ///
+    /// [json]: https://www.json.org/
+    Json,
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Self::Csv {
+            include_headers: true,
+            delimiter: default_delimiter(),
+        }
+    }
+}
+
+const fn default_delimiter() -> char {
+    ','
+}
+
+const fn default_reload_interval_secs() -> u64 {
+    300
+}
+
+const fn default_timeout_secs() -> u64 {
+    30
+}
+
+/// Configuration for the `http` enrichment table.
+#[configurable_component(enrichment_table("http"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpConfig {
+    /// The URL to download the table from.
+    #[configurable(metadata(docs::examples = "https://example.com/allow-list.csv"))]
+    #[configurable(metadata(docs::examples = "http://127.0.0.1:8080/tenants.json"))]
+    pub endpoint: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub encoding: Encoding,
+
+    /// How often to download the table again, in seconds.
+    ///
+    /// When a download fails, the previously downloaded data keeps being used until the next
+    /// successful one.
+    #[serde(default = "default_reload_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub reload_interval_secs: u64,
+
+    /// The maximum time, in seconds, to wait for a download to complete.
+    #[serde(default = "default_timeout_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub timeout_secs: u64,
+
+    /// Key/value pairs representing mapped column names and types.
+    ///
+    /// This is used to coerce values from strings into their proper types. The available types
+    /// are the same as for the `file` enrichment table.
+    #[serde(default)]
+    pub schema: HashMap<String, String>,
+
+    /// HTTP headers to add to the request.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "An HTTP request header and its value."
+    ))]
+    pub headers: IndexMap<String, String>,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+impl GenerateConfig for HttpConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "https://example.com/allow-list.csv""#).unwrap()
+    }
+}
+
+impl EnrichmentTableConfig for HttpConfig {
+    async fn build(
+        &self,
+        globals: &crate::config::GlobalOptions,
+    ) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        let fetcher = Fetcher::new(self.clone(), globals.timezone(), &globals.proxy)?;
+        let file = fetcher.fetch().await?;
+
+        let state = Arc::new(RwLock::new(State {
+            file,
+            indexes: Vec::new(),
+        }));
+        tokio::spawn(fetcher.reload(
+            Duration::from_secs(self.reload_interval_secs),
+            Arc::downgrade(&state),
+        ));
+
+        Ok(Box::new(Http {
+            endpoint: self.endpoint.clone(),
+            state,
+        }))
+    }
+}
+
+/// Downloads and parses the table.
+struct Fetcher {
+    config: HttpConfig,
+    uri: Uri,
+    client: HttpClient,
+    timezone: TimeZone,
+}
+
+impl Fetcher {
+    fn new(config: HttpConfig, timezone: TimeZone, proxy: &ProxyConfig) -> crate::Result<Self> {
+        let uri = config.endpoint.parse::<Uri>()?;
+        let tls = TlsSettings::from_options(&config.tls)?;
+        let client = HttpClient::new(tls, proxy)?;
+
+        Ok(Self {
+            config,
+            uri,
+            client,
+            timezone,
+        })
+    }
+
+    /// The configuration the data is parsed with, shared with the `file` enrichment table.
+    fn file_config(&self) -> FileConfig {
+        let encoding = match self.config.encoding {
+            Encoding::Csv {
+                include_headers,
+                delimiter,
+            } => file::Encoding::Csv {
+                include_headers,
+                delimiter,
+            },
+            Encoding::Json => file::Encoding::default(),
+        };
+
+        FileConfig {
+            file: FileSettings {
+                path: PathBuf::from(&self.config.endpoint),
+                encoding,
+            },
+            schema: self.config.schema.clone(),
+        }
+    }
+
+    async fn download(&self) -> Result<Bytes, FetchError> {
+        let mut builder = http::Request::get(&self.uri);
+        for (header, value) in &self.config.headers {
+            builder = builder.header(header.as_str(), value.as_str());
+        }
+        let mut request = builder.body(Body::empty()).context(BuildRequestSnafu)?;
+        if let Some(auth) = &self.config.auth {
+            auth.apply(&mut request);
+        }
+
+        let response = self.client.send(request).await.context(RequestSnafu)?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(FetchError::UnexpectedStatus { status });
+        }
+
+        hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadResponseSnafu)
+    }
+
+    async fn fetch(&self) -> crate::Result<File> {
+        let timeout = Duration::from_secs(self.config.timeout_secs);
+        let body = tokio::time::timeout(timeout, self.download()).await??;
+
+        let config = self.file_config();
+        let (headers, data) = match self.config.encoding {
+            Encoding::Csv { .. } => config.read_csv(self.timezone, body.as_ref()),
+            Encoding::Json => parse_json(&config, self.timezone, &body),
+        }?;
+
+        debug!(
+            message = "Downloaded enrichment table.",
+            endpoint = %self.config.endpoint,
+            rows = data.len(),
+        );
+
+        Ok(File::new(
+            config,
+            FileData {
+                headers,
+                data,
+                modified: SystemTime::now(),
+            },
+        ))
+    }
+
+    /// Downloads the table on every interval, until the table is dropped.
+    async fn reload(self, interval: Duration, state: Weak<RwLock<State>>) {
+        let mut interval = tokio::time::interval(interval);
+        // The first tick completes immediately, and the table was just downloaded.
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            if state.strong_count() == 0 {
+                break;
+            }
+
+            match self.fetch().await {
+                Ok(file) => match state.upgrade() {
+                    Some(state) => {
+                        if let Err(error) = State::replace(&state, file) {
+                            error!(
+                                message = "Unable to add index to reloaded enrichment table.",
+                                endpoint = %self.config.endpoint,
+                                %error,
+                            );
+                        }
+                    }
+                    None => break,
+                },
+                Err(error) => error!(
+                    message = "Failed to reload enrichment table.",
+                    endpoint = %self.config.endpoint,
+                    %error,
+                    internal_log_rate_limit = true,
+                ),
+            }
+        }
+    }
+}
+
+/// Turns a JSON array of objects into rows, the headers being the sorted union of all keys.
+fn parse_json(
+    config: &FileConfig,
+    timezone: TimeZone,
+    body: &[u8],
+) -> crate::Result<(Vec<String>, Vec<Vec<Value>>)> {
+    let objects = serde_json::from_slice::<Vec<ObjectMap>>(body)?;
+
+    let headers = objects
+        .iter()
+        .flat_map(|object| object.keys())
+        .map(|key| key.as_str())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    let data = objects
+        .into_iter()
+        .enumerate()
+        .map(|(row, mut object)| {
+            headers
+                .iter()
+                .map(|header| match object.remove(header.as_str()) {
+                    // Only strings need coercing, other JSON values already have a type.
+                    Some(Value::Bytes(bytes)) => {
+                        config.parse_column(timezone, header, row, &String::from_utf8_lossy(&bytes))
+                    }
+                    Some(value) => Ok(value),
+                    None => Ok(Value::Null),
+                })
+                .collect::<Result<Vec<_>, String>>()
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok((headers, data))
+}
+
+/// The current version of the table, along with the indexes requested on it.
+struct State {
+    file: File,
+    indexes: Vec<(Case, Vec<String>)>,
+}
+
+impl State {
+    /// Swaps in a newly downloaded table, after building the same indexes on it.
+    ///
+    /// Indexes are added in the order they were originally requested, so the handles returned
+    /// earlier stay valid.
+    fn replace(state: &RwLock<State>, mut file: File) -> Result<(), String> {
+        let indexes = state.read().expect("poisoned lock").indexes.clone();
+        for (case, fields) in &indexes {
+            let fields = fields.iter().map(String::as_str).collect::<Vec<_>>();
+            file.add_index(*case, &fields)?;
+        }
+
+        state.write().expect("poisoned lock").file = file;
+        Ok(())
+    }
+}
+
+/// A struct that implements [vector_lib::enrichment::Table] to handle enrichment data
+/// downloaded from an HTTP endpoint.
+#[derive(Clone)]
+pub struct Http {
+    endpoint: String,
+    state: Arc<RwLock<State>>,
+}
+
+impl Table for Http {
+    fn find_table_row<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<ObjectMap, String> {
+        self.state
+            .read()
+            .expect("poisoned lock")
+            .file
+            .find_table_row(case, condition, select, index)
+    }
+
+    fn find_table_rows<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<Vec<ObjectMap>, String> {
+        self.state
+            .read()
+            .expect("poisoned lock")
+            .file
+            .find_table_rows(case, condition, select, index)
+    }
+
+    fn add_index(&mut self, case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        let mut state = self.state.write().expect("poisoned lock");
+        let handle = state.file.add_index(case, fields)?;
+        state
+            .indexes
+            .push((case, fields.iter().map(|field| field.to_string()).collect()));
+        Ok(handle)
+    }
+
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        self.state
+            .read()
+            .expect("poisoned lock")
+            .file
+            .index_fields()
+    }
+
+    /// The table reloads itself in the background, it never needs to be rebuilt.
+    fn needs_reload(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Debug for Http {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Http {}", self.endpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Response, Server,
+    };
+    use tokio::sync::watch;
+
+    use super::*;
+    use crate::test_util::next_addr;
+
+    fn condition<'a>(field: &'a str, value: &str) -> Vec<Condition<'a>> {
+        vec![Condition::Equals {
+            field,
+            value: value.into(),
+        }]
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<HttpConfig>();
+    }
+
+    #[test]
+    fn parses_json_rows() {
+        let config = FileConfig {
+            schema: HashMap::from([("seats".to_string(), "integer".to_string())]),
+            ..Default::default()
+        };
+        let (headers, data) = parse_json(
+            &config,
+            TimeZone::default(),
+            br#"[{"name": "acme", "seats": "3"}, {"name": "globex", "active": true}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(headers, vec!["active", "name", "seats"]);
+        assert_eq!(
+            data,
+            vec![
+                vec![Value::Null, "acme".into(), Value::from(3_i64)],
+                vec![Value::from(true), "globex".into(), Value::Null],
+            ]
+        );
+
+        assert!(parse_json(&config, TimeZone::default(), br#"{"name": "acme"}"#).is_err());
+    }
+
+    /// Serves the current value of the channel, then checks that the table picks up changes.
+    #[tokio::test]
+    async fn reloads_table() {
+        let (tx, rx) = watch::channel("name,tier\nacme,gold\n".to_string());
+        let addr = next_addr();
+        let make_service = make_service_fn(move |_| {
+            let rx = rx.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |_| {
+                    let body = rx.borrow().clone();
+                    async move { Ok::<_, Infallible>(Response::new(Body::from(body))) }
+                }))
+            }
+        });
+        tokio::spawn(Server::bind(&addr).serve(make_service));
+
+        let config = HttpConfig {
+            endpoint: format!("http://{addr}/table.csv"),
+            reload_interval_secs: 1,
+            ..toml::from_str(r#"endpoint = "http://localhost""#).unwrap()
+        };
+        let mut table = config
+            .build(&crate::config::GlobalOptions::default())
+            .await
+            .unwrap();
+        let index = table.add_index(Case::Sensitive, &["name"]).unwrap();
+
+        let row = table
+            .find_table_row(
+                Case::Sensitive,
+                &condition("name", "acme"),
+                None,
+                Some(index),
+            )
+            .unwrap();
+        assert_eq!(row.get("tier"), Some(&Value::from("gold")));
+
+        tx.send("name,tier\nacme,silver\nglobex,gold\n".to_string())
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+
+        let row = table
+            .find_table_row(
+                Case::Sensitive,
+                &condition("name", "acme"),
+                None,
+                Some(index),
+            )
+            .unwrap();
+        assert_eq!(row.get("tier"), Some(&Value::from("silver")));
+        assert!(table
+            .find_table_row(
+                Case::Sensitive,
+                &condition("name", "globex"),
+                None,
+                Some(index)
+            )
+            .is_ok());
+    }
+}
@@ -3,16 +3,24 @@ use enum_dispatch::enum_dispatch;
 use vector_lib::configurable::{configurable_component, NamedComponent};
 pub use vector_lib::enrichment::{Condition, IndexHandle, Table};
 
-use crate::config::{EnrichmentTableConfig, GlobalOptions};
+use crate::config::{
+    unit_test::UnitTestEnrichmentTableConfig, EnrichmentTableConfig, GlobalOptions,
+};
 
 pub mod file;
 
+#[cfg(feature = "enrichment-tables-http")]
+pub mod http;
+
 #[cfg(feature = "enrichment-tables-geoip")]
 pub mod geoip;
 
 #[cfg(feature = "enrichment-tables-mmdb")]
 pub mod mmdb;
 
+#[cfg(feature = "enrichment-tables-redis")]
+pub mod redis;
+
 /// Configurable enrichment tables.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -29,11 +37,27 @@ pub enum EnrichmentTables {
     #[cfg(feature = "enrichment-tables-geoip")]
     Geoip(geoip::GeoipConfig),
 
+    /// Exposes data downloaded from an HTTP endpoint as an enrichment table.
+    ///
+    /// The data is downloaded again on a fixed interval.
+    #[cfg(feature = "enrichment-tables-http")]
+    Http(http::HttpConfig),
+
     /// Exposes data from a [MaxMind][maxmind] database as an enrichment table.
     ///
     /// [maxmind]: https://www.maxmind.com/
     #[cfg(feature = "enrichment-tables-mmdb")]
     Mmdb(mmdb::MmdbConfig),
+
+    /// Exposes data from a live [Redis][redis] server as an enrichment table.
+    ///
+    /// [redis]: https://redis.io
+    #[cfg(feature = "enrichment-tables-redis")]
+    Redis(redis::RedisConfig),
+
+    /// Exposes the rows of a unit test fixture as an enrichment table.
+    #[configurable(metadata(docs::hidden))]
+    UnitTest(UnitTestEnrichmentTableConfig),
 }
 
 // TODO: Use `enum_dispatch` here.
@@ -43,8 +67,13 @@ impl NamedComponent for EnrichmentTables {
             Self::File(config) => config.get_component_name(),
             #[cfg(feature = "enrichment-tables-geoip")]
             Self::Geoip(config) => config.get_component_name(),
+            #[cfg(feature = "enrichment-tables-http")]
+            Self::Http(config) => config.get_component_name(),
             #[cfg(feature = "enrichment-tables-mmdb")]
             Self::Mmdb(config) => config.get_component_name(),
+            #[cfg(feature = "enrichment-tables-redis")]
+            Self::Redis(config) => config.get_component_name(),
+            Self::UnitTest(config) => config.get_component_name(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
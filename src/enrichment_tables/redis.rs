@@ -0,0 +1,503 @@
+//! Handles enrichment tables for `type = redis`.
+//!
+//! Rows are read from a live [Redis][redis] server on lookup, and cached for a configurable
+//! amount of time.
+//!
+//! [redis]: https://redis.io
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use lru::LruCache;
+use snafu::{ResultExt, Snafu};
+use vector_lib::configurable::configurable_component;
+use vector_lib::enrichment::{Case, Condition, IndexHandle, Table};
+use vrl::value::{ObjectMap, Value};
+
+use crate::config::{EnrichmentTableConfig, GenerateConfig};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid Redis URL: {}", source))]
+    InvalidUrl { source: redis::RedisError },
+    #[snafu(display("Failed to connect to Redis: {}", source))]
+    Connect { source: redis::RedisError },
+}
+
+/// How rows are stored in Redis.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DataType {
+    /// Each row is stored as a hash, and read with `HGETALL`.
+    ///
+    /// All values of the row are strings.
+    #[default]
+    Hash,
+
+    /// Each row is stored as a string holding a JSON object, and read with `GET`.
+    String,
+}
+
+/// Configuration for the `redis` enrichment table.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[configurable_component(enrichment_table("redis"))]
+pub struct RedisConfig {
+    /// The Redis URL to connect to.
+    ///
+    /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be `redis` or `rediss` for connections secured using TLS.
+    #[configurable(metadata(docs::examples = "redis://127.0.0.1:6379/0"))]
+    pub url: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub data_type: DataType,
+
+    /// A prefix prepended to the looked up value to build the Redis key of a row.
+    ///
+    /// For example, with a prefix of `tenant:`, looking up `acme` reads the key `tenant:acme`.
+    #[configurable(metadata(docs::examples = "tenant:"))]
+    #[serde(default)]
+    pub key_prefix: String,
+
+    /// The time, in seconds, for which a row read from Redis is cached.
+    ///
+    /// Keys missing from Redis are cached as well. Set to `0` to disable caching, in which case
+    /// every lookup queries Redis.
+    #[serde(default = "default_cache_ttl_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub cache_ttl_secs: u64,
+
+    /// The maximum number of rows kept in the cache.
+    ///
+    /// Once reached, the least recently used rows are evicted first.
+    #[serde(default = "default_cache_max_entries")]
+    pub cache_max_entries: NonZeroUsize,
+
+    /// The maximum time, in seconds, to wait for Redis to answer a lookup.
+    ///
+    /// Lookups missing the cache block event processing for up to this long.
+    #[serde(default = "default_request_timeout_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub request_timeout_secs: u64,
+}
+
+const fn default_cache_ttl_secs() -> u64 {
+    60
+}
+
+fn default_cache_max_entries() -> NonZeroUsize {
+    NonZeroUsize::new(10_000).expect("static non-zero number")
+}
+
+const fn default_request_timeout_secs() -> u64 {
+    1
+}
+
+impl GenerateConfig for RedisConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            url: "redis://127.0.0.1:6379/0".to_string(),
+            data_type: DataType::default(),
+            key_prefix: String::new(),
+            cache_ttl_secs: default_cache_ttl_secs(),
+            cache_max_entries: default_cache_max_entries(),
+            request_timeout_secs: default_request_timeout_secs(),
+        })
+        .unwrap()
+    }
+}
+
+impl EnrichmentTableConfig for RedisConfig {
+    async fn build(
+        &self,
+        _: &crate::config::GlobalOptions,
+    ) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        let client = redis::Client::open(self.url.as_str()).context(InvalidUrlSnafu)?;
+
+        // Make sure the server is reachable, so that a misconfiguration is reported when the
+        // table is loaded rather than on every lookup.
+        let mut connection = client
+            .get_multiplexed_async_connection()
+            .await
+            .context(ConnectSnafu)?;
+        redis::cmd("PING")
+            .query_async::<_, ()>(&mut connection)
+            .await
+            .context(ConnectSnafu)?;
+
+        Ok(Box::new(Redis::new(self.clone(), client)))
+    }
+}
+
+#[derive(Clone)]
+struct CachedRow {
+    row: Option<ObjectMap>,
+    fetched_at: Instant,
+}
+
+/// A struct that implements [vector_lib::enrichment::Table] to handle looking up enrichment data
+/// in Redis.
+#[derive(Clone)]
+pub struct Redis {
+    config: RedisConfig,
+    client: redis::Client,
+    connection: Arc<Mutex<Option<redis::Connection>>>,
+    cache: Arc<Mutex<LruCache<String, CachedRow>>>,
+}
+
+impl Redis {
+    fn new(config: RedisConfig, client: redis::Client) -> Self {
+        let cache = LruCache::new(config.cache_max_entries);
+        Self {
+            config,
+            client,
+            connection: Arc::default(),
+            cache: Arc::new(Mutex::new(cache)),
+        }
+    }
+
+    fn cache_ttl(&self) -> Duration {
+        Duration::from_secs(self.config.cache_ttl_secs)
+    }
+
+    fn cached(&self, key: &str) -> Option<Option<ObjectMap>> {
+        let mut cache = self.cache.lock().expect("poisoned lock");
+        match cache.get(key) {
+            Some(cached) if cached.fetched_at.elapsed() < self.cache_ttl() => {
+                Some(cached.row.clone())
+            }
+            Some(_) => {
+                cache.pop(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn lookup(&self, key: &str) -> Result<Option<ObjectMap>, String> {
+        if let Some(row) = self.cached(key) {
+            return Ok(row);
+        }
+
+        let row = self.query(key).map_err(|error| error.to_string())?;
+        if !self.cache_ttl().is_zero() {
+            self.cache.lock().expect("poisoned lock").put(
+                key.to_string(),
+                CachedRow {
+                    row: row.clone(),
+                    fetched_at: Instant::now(),
+                },
+            );
+        }
+        Ok(row)
+    }
+
+    /// Reads a row from Redis, reusing the connection of previous lookups.
+    ///
+    /// The connection is dropped on error, so that the next lookup connects again.
+    fn query(&self, key: &str) -> redis::RedisResult<Option<ObjectMap>> {
+        let mut guard = self.connection.lock().expect("poisoned lock");
+        let mut connection = match guard.take() {
+            Some(connection) => connection,
+            None => {
+                let timeout = Duration::from_secs(self.config.request_timeout_secs);
+                let connection = self.client.get_connection_with_timeout(timeout)?;
+                connection.set_read_timeout(Some(timeout))?;
+                connection.set_write_timeout(Some(timeout))?;
+                connection
+            }
+        };
+
+        match self.config.data_type {
+            DataType::Hash => {
+                let hash = redis::cmd("HGETALL")
+                    .arg(key)
+                    .query::<HashMap<String, String>>(&mut connection)?;
+                *guard = Some(connection);
+                Ok(hash_to_row(hash))
+            }
+            DataType::String => {
+                let json = redis::cmd("GET")
+                    .arg(key)
+                    .query::<Option<String>>(&mut connection)?;
+                *guard = Some(connection);
+                json.map(|json| json_to_row(&json)).transpose()
+            }
+        }
+    }
+}
+
+/// An empty hash is how Redis reports a missing key to `HGETALL`.
+fn hash_to_row(hash: HashMap<String, String>) -> Option<ObjectMap> {
+    (!hash.is_empty()).then(|| {
+        hash.into_iter()
+            .map(|(field, value)| (field.into(), Value::from(value)))
+            .collect()
+    })
+}
+
+fn json_to_row(json: &str) -> redis::RedisResult<ObjectMap> {
+    let value = serde_json::from_str::<Value>(json).map_err(|error| {
+        redis::RedisError::from((
+            redis::ErrorKind::TypeError,
+            "row is not valid JSON",
+            error.to_string(),
+        ))
+    })?;
+    value.into_object().ok_or_else(|| {
+        redis::RedisError::from((redis::ErrorKind::TypeError, "row is not a JSON object"))
+    })
+}
+
+fn select_fields(row: ObjectMap, select: Option<&[String]>) -> ObjectMap {
+    match select {
+        Some(fields) => {
+            let mut row = row;
+            fields
+                .iter()
+                .map(|field| {
+                    let value = row.remove(field.as_str()).unwrap_or(Value::Null);
+                    (field.as_str().into(), value)
+                })
+                .collect()
+        }
+        None => row,
+    }
+}
+
+impl Table for Redis {
+    /// Search the enrichment table data with the given condition.
+    /// All conditions must match (AND).
+    ///
+    /// # Errors
+    /// Errors if no rows, or more than 1 row is found.
+    fn find_table_row<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<ObjectMap, String> {
+        let mut rows = self.find_table_rows(case, condition, select, index)?;
+
+        match rows.pop() {
+            Some(row) if rows.is_empty() => Ok(row),
+            Some(_) => Err("More than 1 row found".to_string()),
+            None => Err("Key not found".to_string()),
+        }
+    }
+
+    /// Search the enrichment table data with the given condition.
+    /// All conditions must match (AND).
+    /// Can return multiple matched records
+    fn find_table_rows<'a>(
+        &self,
+        _: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        _: Option<IndexHandle>,
+    ) -> Result<Vec<ObjectMap>, String> {
+        match condition.first() {
+            Some(_) if condition.len() > 1 => Err("Only one condition is allowed".to_string()),
+            Some(Condition::Equals { value, .. }) => {
+                let key = format!("{}{}", self.config.key_prefix, value.to_string_lossy());
+                Ok(self
+                    .lookup(&key)?
+                    .map(|row| vec![select_fields(row, select)])
+                    .unwrap_or_default())
+            }
+            Some(_) => Err("Only equality condition is allowed".to_string()),
+            None => Err("Key condition must be specified".to_string()),
+        }
+    }
+
+    /// Hints to the enrichment table what data is going to be searched to allow it to index the
+    /// data in advance.
+    ///
+    /// # Errors
+    /// Errors if the fields are not in the table.
+    fn add_index(&mut self, _: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        match fields.len() {
+            0 => Err("Key field is required".to_string()),
+            1 => Ok(IndexHandle(0)),
+            _ => Err("Only one field is allowed".to_string()),
+        }
+    }
+
+    /// Returns a list of the field names that are in each index
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        Vec::new()
+    }
+
+    /// Rows are read from Redis on lookup, so the table never needs reloading.
+    fn needs_reload(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Debug for Redis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Redis enrichment table {}", self.config.url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RedisConfig>();
+    }
+
+    #[test]
+    fn hash_rows() {
+        assert_eq!(hash_to_row(HashMap::new()), None);
+
+        let row = hash_to_row(HashMap::from([("tier".to_string(), "gold".to_string())])).unwrap();
+        assert_eq!(row, ObjectMap::from([("tier".into(), Value::from("gold"))]));
+    }
+
+    #[test]
+    fn json_rows() {
+        let row = json_to_row(r#"{"tier": "gold", "seats": 3}"#).unwrap();
+        assert_eq!(
+            row,
+            ObjectMap::from([
+                ("seats".into(), Value::from(3_i64)),
+                ("tier".into(), Value::from("gold")),
+            ])
+        );
+
+        assert!(json_to_row("[1, 2]").is_err());
+        assert!(json_to_row("not json").is_err());
+    }
+
+    #[test]
+    fn selects_fields() {
+        let row = ObjectMap::from([
+            ("seats".into(), Value::from(3_i64)),
+            ("tier".into(), Value::from("gold")),
+        ]);
+        let selected = select_fields(row, Some(&["tier".to_string(), "region".to_string()]));
+        assert_eq!(
+            selected,
+            ObjectMap::from([
+                ("region".into(), Value::Null),
+                ("tier".into(), Value::from("gold")),
+            ])
+        );
+    }
+
+    #[test]
+    fn serves_cached_rows() {
+        let config: RedisConfig = toml::from_str(r#"url = "redis://127.0.0.1:1/0""#).unwrap();
+        let client = redis::Client::open(config.url.as_str()).unwrap();
+        let table = Redis::new(config, client);
+
+        let row = ObjectMap::from([("tier".into(), Value::from("gold"))]);
+        table.cache.lock().unwrap().put(
+            "acme".to_string(),
+            CachedRow {
+                row: Some(row.clone()),
+                fetched_at: Instant::now(),
+            },
+        );
+        table.cache.lock().unwrap().put(
+            "missing".to_string(),
+            CachedRow {
+                row: None,
+                fetched_at: Instant::now(),
+            },
+        );
+
+        let condition = |value: &str| {
+            vec![Condition::Equals {
+                field: "tenant",
+                value: value.into(),
+            }]
+        };
+        assert_eq!(
+            table
+                .find_table_row(Case::Sensitive, &condition("acme"), None, None)
+                .unwrap(),
+            row
+        );
+        assert_eq!(
+            table
+                .find_table_rows(Case::Sensitive, &condition("missing"), None, None)
+                .unwrap(),
+            vec![]
+        );
+    }
+}
+
+#[cfg(all(test, feature = "redis-integration-tests"))]
+mod integration_tests {
+    use redis::AsyncCommands;
+
+    use super::*;
+    use crate::test_util::random_string;
+
+    fn redis_server() -> String {
+        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379/0".to_owned())
+    }
+
+    #[tokio::test]
+    async fn looks_up_hashes_and_strings() {
+        let prefix = format!("{}:", random_string(10));
+        let client = redis::Client::open(redis_server()).unwrap();
+        let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+        let _: () = connection
+            .hset(format!("{prefix}acme"), "tier", "gold")
+            .await
+            .unwrap();
+        let _: () = connection
+            .set(format!("{prefix}json"), r#"{"tier": "silver"}"#)
+            .await
+            .unwrap();
+
+        let config = |data_type| RedisConfig {
+            url: redis_server(),
+            data_type,
+            key_prefix: prefix.clone(),
+            cache_ttl_secs: 0,
+            cache_max_entries: default_cache_max_entries(),
+            request_timeout_secs: default_request_timeout_secs(),
+        };
+        let find = |table: &Box<dyn Table + Send + Sync>, key: &str| {
+            table.find_table_row(
+                Case::Sensitive,
+                &[Condition::Equals {
+                    field: "tenant",
+                    value: key.into(),
+                }],
+                None,
+                None,
+            )
+        };
+
+        let globals = crate::config::GlobalOptions::default();
+        let table = config(DataType::Hash).build(&globals).await.unwrap();
+        let row = tokio::task::spawn_blocking(move || find(&table, "acme"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(row, ObjectMap::from([("tier".into(), Value::from("gold"))]));
+
+        let table = config(DataType::String).build(&globals).await.unwrap();
+        let (row, missing) =
+            tokio::task::spawn_blocking(move || (find(&table, "json"), find(&table, "missing")))
+                .await
+                .unwrap();
+        assert_eq!(
+            row.unwrap(),
+            ObjectMap::from([("tier".into(), Value::from("silver"))])
+        );
+        assert!(missing.is_err());
+    }
+}
@@ -685,6 +685,7 @@ mod tests {
                 - source0
                 drop_on_abort: false
                 drop_on_error: false
+                dropped_metadata_key: dropped
                 metric_tag_values: single
                 reroute_dropped: false
                 runtime: ast
@@ -750,6 +751,7 @@ mod tests {
                   ],
                   "drop_on_abort": false,
                   "drop_on_error": false,
+                  "dropped_metadata_key": "dropped",
                   "metric_tag_values": "single",
                   "reroute_dropped": false,
                   "runtime": "ast",
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use metrics::{histogram, Histogram};
+use vector_lib::internal_event::SharedString;
 
 #[derive(Clone, Copy)]
 pub struct AdaptiveConcurrencyLimitData {
@@ -12,15 +13,27 @@ pub struct AdaptiveConcurrencyLimitData {
     pub past_rtt_deviation: Duration,
 }
 
+/// Registers a histogram of the controller, tagged with the endpoint it limits requests to when
+/// the sink balances requests between several endpoints.
+fn endpoint_histogram(name: &'static str, endpoint: &Option<SharedString>) -> Histogram {
+    match endpoint {
+        Some(endpoint) => histogram!(name, "endpoint" => endpoint.clone()),
+        None => histogram!(name),
+    }
+}
+
 registered_event! {
-    AdaptiveConcurrencyLimit => {
+    AdaptiveConcurrencyLimit {
+        endpoint: Option<SharedString>,
+    } => {
         // These are histograms, as they may have a number of different
         // values over each reporting interval, and each of those values
         // is valuable for diagnosis.
-        limit: Histogram = histogram!("adaptive_concurrency_limit"),
-        reached_limit: Histogram = histogram!("adaptive_concurrency_reached_limit"),
-        back_pressure: Histogram = histogram!("adaptive_concurrency_back_pressure"),
-        past_rtt_mean: Histogram = histogram!("adaptive_concurrency_past_rtt_mean"),
+        limit: Histogram = endpoint_histogram("adaptive_concurrency_limit", &self.endpoint),
+        reached_limit: Histogram = endpoint_histogram("adaptive_concurrency_reached_limit", &self.endpoint),
+        back_pressure: Histogram = endpoint_histogram("adaptive_concurrency_back_pressure", &self.endpoint),
+        past_rtt_mean: Histogram = endpoint_histogram("adaptive_concurrency_past_rtt_mean", &self.endpoint),
+        past_rtt_deviation: Histogram = endpoint_histogram("adaptive_concurrency_past_rtt_deviation", &self.endpoint),
     }
 
     fn emit(&self, data: AdaptiveConcurrencyLimitData) {
@@ -30,13 +43,15 @@ registered_event! {
         let back_pressure = data.had_back_pressure.then_some(1.0).unwrap_or_default();
         self.back_pressure.record(back_pressure);
         self.past_rtt_mean.record(data.past_rtt);
-        // past_rtt_deviation is unrecorded
+        self.past_rtt_deviation.record(data.past_rtt_deviation);
     }
 }
 
 registered_event! {
-    AdaptiveConcurrencyInFlight => {
-        in_flight: Histogram = histogram!("adaptive_concurrency_in_flight"),
+    AdaptiveConcurrencyInFlight {
+        endpoint: Option<SharedString>,
+    } => {
+        in_flight: Histogram = endpoint_histogram("adaptive_concurrency_in_flight", &self.endpoint),
     }
 
     fn emit(&self, in_flight: u64) {
@@ -45,8 +60,10 @@ registered_event! {
 }
 
 registered_event! {
-    AdaptiveConcurrencyObservedRtt => {
-        observed_rtt: Histogram = histogram!("adaptive_concurrency_observed_rtt"),
+    AdaptiveConcurrencyObservedRtt {
+        endpoint: Option<SharedString>,
+    } => {
+        observed_rtt: Histogram = endpoint_histogram("adaptive_concurrency_observed_rtt", &self.endpoint),
     }
 
     fn emit(&self, rtt: Duration) {
@@ -55,8 +72,10 @@ registered_event! {
 }
 
 registered_event! {
-    AdaptiveConcurrencyAveragedRtt => {
-        averaged_rtt: Histogram = histogram!("adaptive_concurrency_averaged_rtt"),
+    AdaptiveConcurrencyAveragedRtt {
+        endpoint: Option<SharedString>,
+    } => {
+        averaged_rtt: Histogram = endpoint_histogram("adaptive_concurrency_averaged_rtt", &self.endpoint),
     }
 
     fn emit(&self, rtt: Duration) {
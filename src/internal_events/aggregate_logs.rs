@@ -0,0 +1,29 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct AggregateLogsFieldInvalid<'a> {
+    pub field: &'a str,
+}
+
+impl InternalEvent for AggregateLogsFieldInvalid<'_> {
+    fn emit(self) {
+        error!(
+            message = "Field is not a number, ignoring it for the aggregate.",
+            field = %self.field,
+            error_code = "field_not_a_number",
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "field_not_a_number",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+            "field" => self.field.to_string(),
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_lib::internal_event::{error_stage, error_type, InternalEvent};
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsShardDiscoveryError<E> {
+    pub stream_name: String,
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for AwsKinesisStreamsShardDiscoveryError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to list the shards of Kinesis stream.",
+            stream_name = %self.stream_name,
+            error = %self.error,
+            error_code = "list_shards",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "list_shards",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsReceiveError<'a, E> {
+    pub shard_id: &'a str,
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for AwsKinesisStreamsReceiveError<'_, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to get records from Kinesis shard.",
+            shard_id = %self.shard_id,
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsCheckpointError<'a> {
+    pub path: &'a Path,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for AwsKinesisStreamsCheckpointError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to write Kinesis shard checkpoints.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "write_checkpoints",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "write_checkpoints",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt::Display;
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+use crate::sources::aws_s3::sqs::ProcessingError;
+
+#[derive(Debug)]
+pub struct AwsS3ListObjectsError<'a, E> {
+    pub bucket: &'a str,
+    pub error: E,
+}
+
+impl<'a, E: Display> InternalEvent for AwsS3ListObjectsError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to list S3 objects.",
+            bucket = %self.bucket,
+            error = %self.error,
+            error_code = "failed_listing_s3_objects",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "failed_listing_s3_objects",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsS3ObjectProcessingError<'a> {
+    pub key: &'a str,
+    pub error: &'a ProcessingError,
+}
+
+impl<'a> InternalEvent for AwsS3ObjectProcessingError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to process S3 object.",
+            key = %self.key,
+            error = %self.error,
+            error_code = "failed_processing_s3_object",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "failed_processing_s3_object",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsS3CheckpointWriteError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a std::path::Path,
+}
+
+impl<'a> InternalEvent for AwsS3CheckpointWriteError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to write S3 objects checkpoint.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "failed_writing_checkpoint",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "failed_writing_checkpoint",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,79 @@
+use metrics::counter;
+use vector_lib::internal_event::{error_stage, error_type, InternalEvent};
+
+#[derive(Debug)]
+pub struct AzureEventHubsReceiveError<'a, E> {
+    pub partition_id: &'a str,
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for AzureEventHubsReceiveError<'_, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to receive events from Event Hubs partition.",
+            partition_id = %self.partition_id,
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureEventHubsCheckpointError<'a, E> {
+    pub partition_id: &'a str,
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for AzureEventHubsCheckpointError<'_, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to update Event Hubs partition checkpoint.",
+            partition_id = %self.partition_id,
+            error = %self.error,
+            error_code = "update_checkpoint",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "update_checkpoint",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureEventHubsLoadBalancingError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for AzureEventHubsLoadBalancingError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to balance Event Hubs partitions.",
+            error = %self.error,
+            error_code = "load_balancing",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "load_balancing",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,28 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+use crate::sources::ebpf_flows::bpf::BpfError;
+
+#[derive(Debug)]
+pub struct EbpfFlowsReadError {
+    pub error: BpfError,
+}
+
+impl InternalEvent for EbpfFlowsReadError {
+    fn emit(self) {
+        error!(
+            message = "Could not read flows from the eBPF maps.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::READER_FAILED,
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,46 @@
+use http::StatusCode;
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+/// A document of a bulk request failed to be written by Elasticsearch.
+#[derive(Debug)]
+pub struct ElasticsearchBulkItemError<'a> {
+    pub status: StatusCode,
+    pub document_error_type: Option<&'a str>,
+    pub reason: Option<&'a str>,
+    pub retriable: bool,
+}
+
+impl InternalEvent for ElasticsearchBulkItemError<'_> {
+    fn emit(self) {
+        let document_error_type = self.document_error_type.unwrap_or("unknown");
+        if self.retriable {
+            warn!(
+                message = "Document failed with a retriable error.",
+                status = %self.status,
+                document_error_type,
+                reason = self.reason,
+                internal_log_rate_limit = true,
+            );
+        } else {
+            error!(
+                message = "Document rejected by Elasticsearch.",
+                status = %self.status,
+                document_error_type,
+                reason = self.reason,
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::SENDING,
+                internal_log_rate_limit = true,
+            );
+        }
+
+        counter!(
+            "elasticsearch_bulk_item_errors_total",
+            "document_error_type" => document_error_type.to_owned(),
+            "status" => self.status.as_u16().to_string(),
+            "retriable" => self.retriable.to_string(),
+        )
+        .increment(1);
+    }
+}
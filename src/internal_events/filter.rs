@@ -1,3 +1,4 @@
+use metrics::{counter, Counter};
 use vector_lib::internal_event::{ComponentEventsDropped, Count, Registered, INTENTIONAL};
 
 vector_lib::registered_event! (
@@ -12,3 +13,26 @@ vector_lib::registered_event! (
         self.events_dropped.emit(data);
     }
 );
+
+vector_lib::registered_event! (
+    FilterConditionChecked {
+        condition: String,
+    } => {
+        events_matched: Counter = counter!(
+            "filter_condition_events_matched_total",
+            "condition" => self.condition.clone(),
+        ),
+        events_dropped: Counter = counter!(
+            "filter_condition_events_dropped_total",
+            "condition" => self.condition.clone(),
+        ),
+    }
+
+    fn emit(&self, matched: bool) {
+        if matched {
+            self.events_matched.increment(1);
+        } else {
+            self.events_dropped.increment(1);
+        }
+    }
+);
@@ -0,0 +1,26 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct KubernetesEventsWatchError {
+    pub error: kube::runtime::watcher::Error,
+}
+
+impl InternalEvent for KubernetesEventsWatchError {
+    fn emit(self) {
+        error!(
+            message = "Watching the Kubernetes Events failed. Retrying.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::REQUEST_FAILED,
+        )
+        .increment(1);
+    }
+}
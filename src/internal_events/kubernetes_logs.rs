@@ -173,6 +173,31 @@ impl InternalEvent for KubernetesLogsDockerFormatParseError<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct KubernetesLogsPodAnnotationParseError<'a> {
+    pub parser: &'a str,
+    pub error: &'a dyn std::fmt::Display,
+}
+
+impl InternalEvent for KubernetesLogsPodAnnotationParseError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse log line with the parser of the Pod annotation.",
+            parser = %self.parser,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
+
 const KUBERNETES_LIFECYCLE: &str = "kubernetes_lifecycle";
 
 #[derive(Debug)]
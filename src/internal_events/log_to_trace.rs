@@ -0,0 +1,31 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL};
+
+pub struct LogToTraceInvalidId<'a> {
+    pub field: &'a str,
+}
+
+impl<'a> InternalEvent for LogToTraceInvalidId<'a> {
+    fn emit(self) {
+        let reason = "Missing or invalid trace ID or span ID.";
+        error!(
+            message = reason,
+            field = %self.field,
+            error_code = "invalid_id",
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "invalid_id",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+            "field" => self.field.to_string(),
+        )
+        .increment(1);
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason })
+    }
+}
@@ -1,10 +1,17 @@
 use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
 use vector_lib::internal_event::{error_stage, error_type};
-use vector_lib::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+#[cfg(feature = "sinks-loki")]
+use vector_lib::internal_event::{ComponentEventsDropped, INTENTIONAL};
 
+#[cfg(feature = "sources-loki")]
+use crate::sources::loki::ParseError;
+
+#[cfg(feature = "sinks-loki")]
 #[derive(Debug)]
 pub struct LokiEventUnlabeledError;
 
+#[cfg(feature = "sinks-loki")]
 impl InternalEvent for LokiEventUnlabeledError {
     fn emit(self) {
         error!(
@@ -25,11 +32,13 @@ impl InternalEvent for LokiEventUnlabeledError {
     }
 }
 
+#[cfg(feature = "sinks-loki")]
 #[derive(Debug)]
 pub struct LokiOutOfOrderEventDroppedError {
     pub count: usize,
 }
 
+#[cfg(feature = "sinks-loki")]
 impl InternalEvent for LokiOutOfOrderEventDroppedError {
     fn emit(self) {
         let reason = "Dropping out-of-order event(s).";
@@ -57,11 +66,13 @@ impl InternalEvent for LokiOutOfOrderEventDroppedError {
     }
 }
 
+#[cfg(feature = "sinks-loki")]
 #[derive(Debug)]
 pub struct LokiOutOfOrderEventRewritten {
     pub count: usize,
 }
 
+#[cfg(feature = "sinks-loki")]
 impl InternalEvent for LokiOutOfOrderEventRewritten {
     fn emit(self) {
         debug!(
@@ -74,9 +85,11 @@ impl InternalEvent for LokiOutOfOrderEventRewritten {
     }
 }
 
+#[cfg(feature = "sinks-loki")]
 #[derive(Debug)]
 pub struct LokiTimestampNonParsableEventsDropped;
 
+#[cfg(feature = "sinks-loki")]
 impl InternalEvent for LokiTimestampNonParsableEventsDropped {
     fn emit(self) {
         let reason = "Dropping timestamp non-parsable event(s).";
@@ -100,3 +113,28 @@ impl InternalEvent for LokiTimestampNonParsableEventsDropped {
         .increment(1);
     }
 }
+
+#[cfg(feature = "sources-loki")]
+#[derive(Debug)]
+pub struct LokiPushRequestParseError<'a> {
+    pub error: &'a ParseError,
+}
+
+#[cfg(feature = "sources-loki")]
+impl<'a> InternalEvent for LokiPushRequestParseError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Could not decode push request.",
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
@@ -107,6 +107,7 @@ const fn lua_build_error_code(err: &BuildError) -> &'static str {
 
     match err {
         InvalidSearchDirs { .. } => "invalid_search_dir",
+        InvalidSandbox { .. } => "invalid_sandbox",
         InvalidSource { .. } => "invalid_source",
         InvalidHooksInit { .. } => "invalid_hook_init",
         InvalidHooksProcess { .. } => "invalid_hook_process",
@@ -3,6 +3,8 @@ pub mod prelude;
 
 mod adaptive_concurrency;
 mod aggregate;
+#[cfg(feature = "transforms-aggregate_logs")]
+mod aggregate_logs;
 #[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
 mod amqp;
 #[cfg(feature = "sources-apache_metrics")]
@@ -24,8 +26,14 @@ mod aws_ecs_metrics;
 mod aws_kinesis;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 mod aws_kinesis_firehose;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+mod aws_kinesis_streams;
+#[cfg(feature = "sources-aws_s3")]
+mod aws_s3;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 mod aws_sqs;
+#[cfg(feature = "sources-azure_event_hubs")]
+mod azure_event_hubs;
 mod batch;
 mod codecs;
 mod common;
@@ -44,6 +52,14 @@ mod demo_logs;
 mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 mod docker_logs;
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    feature = "sources-ebpf_flows"
+))]
+mod ebpf_flows;
+#[cfg(feature = "sinks-elasticsearch")]
+mod elasticsearch;
 mod encoding_transcode;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 mod eventstoredb_metrics;
@@ -74,12 +90,16 @@ mod internal_logs;
 mod journald;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 mod kafka;
+#[cfg(feature = "sources-kubernetes_events")]
+mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes_logs")]
 mod kubernetes_logs;
 #[cfg(feature = "transforms-log_to_metric")]
 mod log_to_metric;
+#[cfg(feature = "transforms-log_to_trace")]
+mod log_to_trace;
 mod logplex;
-#[cfg(feature = "sinks-loki")]
+#[cfg(any(feature = "sinks-loki", feature = "sources-loki"))]
 mod loki;
 #[cfg(feature = "transforms-lua")]
 mod lua;
@@ -87,12 +107,22 @@ mod lua;
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
-#[cfg(feature = "sinks-mqtt")]
+#[cfg(any(feature = "sinks-mqtt", feature = "sources-mqtt"))]
 mod mqtt;
+#[cfg(feature = "sources-nats")]
+mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
+#[cfg(feature = "sinks-opentelemetry")]
+mod opentelemetry_sink;
+#[cfg(feature = "sinks-pagerduty")]
+mod pagerduty;
 mod parser;
+#[cfg(any(feature = "sources-plugin", feature = "sinks-plugin"))]
+mod plugin;
+#[cfg(feature = "sources-postgres_cdc")]
+mod postgres_cdc;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
 mod process;
@@ -109,12 +139,22 @@ mod redis;
 #[cfg(feature = "transforms-impl-reduce")]
 mod reduce;
 mod remap;
+#[cfg(feature = "transforms-route")]
+mod route;
 mod sample;
+#[cfg(feature = "transforms-schema_enforce")]
+mod schema_enforce;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
+#[cfg(feature = "sinks-slack")]
+mod slack;
+#[cfg(feature = "sources-snmp_trap")]
+mod snmp_trap;
 mod socket;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
+#[cfg(feature = "sources-splunk_s2s")]
+mod splunk_s2s;
 #[cfg(feature = "sinks-statsd")]
 mod statsd_sink;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
@@ -125,6 +165,10 @@ mod template;
 mod throttle;
 mod udp;
 mod unix;
+#[cfg(feature = "transforms-wasm")]
+mod wasm;
+#[cfg(feature = "sinks-webhook")]
+mod webhook;
 #[cfg(feature = "sinks-websocket")]
 mod websocket;
 
@@ -135,12 +179,16 @@ mod websocket;
 ))]
 mod file;
 mod windows;
+#[cfg(feature = "sources-windows_event_log")]
+mod windows_event_log;
 
 #[cfg(feature = "sources-mongodb_metrics")]
 pub(crate) use mongodb_metrics::*;
 
 #[cfg(feature = "transforms-aggregate")]
 pub(crate) use self::aggregate::*;
+#[cfg(feature = "transforms-aggregate_logs")]
+pub(crate) use self::aggregate_logs::*;
 #[cfg(feature = "sources-amqp")]
 pub(crate) use self::amqp::*;
 #[cfg(feature = "sources-apache_metrics")]
@@ -162,8 +210,14 @@ pub(crate) use self::aws_ecs_metrics::*;
 pub(crate) use self::aws_kinesis::*;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 pub(crate) use self::aws_kinesis_firehose::*;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+pub(crate) use self::aws_kinesis_streams::*;
+#[cfg(feature = "sources-aws_s3")]
+pub(crate) use self::aws_s3::*;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 pub(crate) use self::aws_sqs::*;
+#[cfg(feature = "sources-azure_event_hubs")]
+pub(crate) use self::azure_event_hubs::*;
 pub(crate) use self::codecs::*;
 #[cfg(feature = "sources-datadog_agent")]
 pub(crate) use self::datadog_agent::*;
@@ -179,6 +233,14 @@ pub(crate) use self::demo_logs::*;
 pub(crate) use self::dnstap::*;
 #[cfg(feature = "sources-docker_logs")]
 pub(crate) use self::docker_logs::*;
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    feature = "sources-ebpf_flows"
+))]
+pub(crate) use self::ebpf_flows::*;
+#[cfg(feature = "sinks-elasticsearch")]
+pub(crate) use self::elasticsearch::*;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub(crate) use self::eventstoredb_metrics::*;
 #[cfg(feature = "sources-exec")]
@@ -211,24 +273,38 @@ pub(crate) use self::internal_logs::*;
 pub(crate) use self::journald::*;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 pub(crate) use self::kafka::*;
+#[cfg(feature = "sources-kubernetes_events")]
+pub(crate) use self::kubernetes_events::*;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub(crate) use self::kubernetes_logs::*;
 #[cfg(feature = "transforms-log_to_metric")]
 pub(crate) use self::log_to_metric::*;
+#[cfg(feature = "transforms-log_to_trace")]
+pub(crate) use self::log_to_trace::*;
 #[cfg(feature = "sources-heroku_logs")]
 pub(crate) use self::logplex::*;
-#[cfg(feature = "sinks-loki")]
+#[cfg(any(feature = "sinks-loki", feature = "sources-loki"))]
 pub(crate) use self::loki::*;
 #[cfg(feature = "transforms-lua")]
 pub(crate) use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
-#[cfg(feature = "sinks-mqtt")]
+#[cfg(any(feature = "sinks-mqtt", feature = "sources-mqtt"))]
 pub(crate) use self::mqtt::*;
+#[cfg(feature = "sources-nats")]
+pub(crate) use self::nats::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
+#[cfg(feature = "sinks-opentelemetry")]
+pub(crate) use self::opentelemetry_sink::*;
+#[cfg(feature = "sinks-pagerduty")]
+pub(crate) use self::pagerduty::*;
 #[allow(unused_imports)]
 pub(crate) use self::parser::*;
+#[cfg(any(feature = "sources-plugin", feature = "sinks-plugin"))]
+pub(crate) use self::plugin::*;
+#[cfg(feature = "sources-postgres_cdc")]
+pub(crate) use self::postgres_cdc::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
 #[cfg(any(
@@ -245,12 +321,22 @@ pub(crate) use self::redis::*;
 pub(crate) use self::reduce::*;
 #[cfg(feature = "transforms-remap")]
 pub(crate) use self::remap::*;
+#[cfg(feature = "transforms-route")]
+pub(crate) use self::route::*;
 #[cfg(feature = "transforms-impl-sample")]
 pub(crate) use self::sample::*;
+#[cfg(feature = "transforms-schema_enforce")]
+pub(crate) use self::schema_enforce::*;
 #[cfg(feature = "sinks-sematext")]
 pub(crate) use self::sematext_metrics::*;
+#[cfg(feature = "sinks-slack")]
+pub(crate) use self::slack::*;
+#[cfg(feature = "sources-snmp_trap")]
+pub(crate) use self::snmp_trap::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
+#[cfg(feature = "sources-splunk_s2s")]
+pub(crate) use self::splunk_s2s::*;
 #[cfg(feature = "sinks-statsd")]
 pub(crate) use self::statsd_sink::*;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
@@ -259,10 +345,16 @@ pub(crate) use self::tag_cardinality_limit::*;
 pub(crate) use self::throttle::*;
 #[cfg(unix)]
 pub(crate) use self::unix::*;
+#[cfg(feature = "transforms-wasm")]
+pub(crate) use self::wasm::*;
+#[cfg(feature = "sinks-webhook")]
+pub(crate) use self::webhook::*;
 #[cfg(feature = "sinks-websocket")]
 pub(crate) use self::websocket::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
+#[cfg(feature = "sources-windows_event_log")]
+pub(crate) use self::windows_event_log::*;
 pub use self::{
     adaptive_concurrency::*, batch::*, common::*, conditions::*, encoding_transcode::*,
     heartbeat::*, http::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
@@ -1,16 +1,15 @@
 use std::fmt::Debug;
 
 use metrics::counter;
-use rumqttc::ConnectionError;
 use vector_lib::internal_event::InternalEvent;
 use vector_lib::internal_event::{error_stage, error_type};
 
 #[derive(Debug)]
-pub struct MqttConnectionError {
-    pub error: ConnectionError,
+pub struct MqttConnectionError<E> {
+    pub error: E,
 }
 
-impl InternalEvent for MqttConnectionError {
+impl<E: std::fmt::Display> InternalEvent for MqttConnectionError<E> {
     fn emit(self) {
         error!(
             message = "MQTT connection error.",
@@ -33,3 +32,32 @@ impl InternalEvent for MqttConnectionError {
         Some("MqttConnectionError")
     }
 }
+
+#[derive(Debug)]
+pub struct MqttSourceConnectionError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for MqttSourceConnectionError<E> {
+    fn emit(self) {
+        error!(
+            message = "MQTT connection error.",
+            error = %self.error,
+            error_code = "mqtt_connection_error",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "mqtt_connection_error",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("MqttSourceConnectionError")
+    }
+}
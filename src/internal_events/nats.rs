@@ -0,0 +1,63 @@
+use std::fmt::Display;
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct NatsJetStreamReadError<E> {
+    pub error: E,
+}
+
+impl<E: Display> InternalEvent for NatsJetStreamReadError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to pull messages from JetStream.",
+            error = %self.error,
+            error_code = "jetstream_pull_failed",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "jetstream_pull_failed",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("NatsJetStreamReadError")
+    }
+}
+
+#[derive(Debug)]
+pub struct NatsJetStreamAckError {
+    pub error: async_nats::Error,
+}
+
+impl InternalEvent for NatsJetStreamAckError {
+    fn emit(self) {
+        error!(
+            message = "Unable to acknowledge JetStream message.",
+            error = %self.error,
+            error_code = "jetstream_ack_failed",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "jetstream_ack_failed",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("NatsJetStreamAckError")
+    }
+}
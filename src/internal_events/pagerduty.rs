@@ -0,0 +1,29 @@
+use metrics::counter;
+use vector_lib::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, InternalEvent, UNINTENTIONAL,
+};
+
+#[derive(Debug)]
+pub struct PagerDutyInvalidEventError {
+    pub error: String,
+}
+
+impl InternalEvent for PagerDutyInvalidEventError {
+    fn emit(self) {
+        let reason = "Event could not be mapped to a PagerDuty event.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,53 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+use super::prelude::io_error_code;
+
+#[derive(Debug)]
+pub struct PluginProcessSpawnError<'a> {
+    pub command: &'a str,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for PluginProcessSpawnError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Unable to start plugin.",
+            command = %self.command,
+            error = %self.error,
+            error_type = error_type::COMMAND_FAILED,
+            error_code = %io_error_code(&self.error),
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::COMMAND_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct PluginProcessExited<'a> {
+    pub command: &'a str,
+    pub exit_status: Option<i32>,
+}
+
+impl InternalEvent for PluginProcessExited<'_> {
+    fn emit(self) {
+        let exit_status = self
+            .exit_status
+            .map_or_else(|| "unknown".to_owned(), |status| status.to_string());
+        warn!(
+            message = "Plugin exited, restarting it.",
+            command = %self.command,
+            exit_status = %exit_status,
+            internal_log_rate_limit = true,
+        );
+        counter!("plugin_restarts_total").increment(1);
+    }
+}
@@ -0,0 +1,57 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+use crate::sources::postgres_cdc::DecodeError;
+
+#[derive(Debug)]
+pub struct PostgresCdcQueryError<'a> {
+    pub error: String,
+    pub slot_name: &'a str,
+}
+
+impl<'a> InternalEvent for PostgresCdcQueryError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed reading changes from the replication slot.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            slot_name = %self.slot_name,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct PostgresCdcDecodeError<'a> {
+    pub error: DecodeError,
+    pub slot_name: &'a str,
+    pub lsn: &'a str,
+}
+
+impl<'a> InternalEvent for PostgresCdcDecodeError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Discarding undecodable logical replication message.",
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            slot_name = %self.slot_name,
+            lsn = %self.lsn,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
@@ -1,5 +1,7 @@
 use metrics::counter;
-use vector_lib::internal_event::{error_stage, error_type, InternalEvent};
+use vector_lib::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, InternalEvent, INTENTIONAL, UNINTENTIONAL,
+};
 use vrl::path::PathParseError;
 use vrl::value::KeyString;
 
@@ -12,6 +14,18 @@ impl InternalEvent for ReduceStaleEventFlushed {
     }
 }
 
+#[derive(Debug)]
+pub struct ReduceLateEventDropped;
+
+impl InternalEvent for ReduceLateEventDropped {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Event arrived after its window was flushed.",
+        });
+    }
+}
+
 #[derive(Debug)]
 pub struct ReduceAddEventError {
     pub error: PathParseError,
@@ -36,3 +50,30 @@ impl InternalEvent for ReduceAddEventError {
         .increment(1);
     }
 }
+
+#[derive(Debug)]
+pub struct ReduceMergeError {
+    pub error: String,
+}
+
+impl InternalEvent for ReduceMergeError {
+    fn emit(self) {
+        error!(
+            message = "Event could not be merged.",
+            error = %self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: 1,
+            reason: "Event could not be merged.",
+        });
+    }
+}
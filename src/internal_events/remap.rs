@@ -36,6 +36,42 @@ impl InternalEvent for RemapMappingError {
     }
 }
 
+#[derive(Debug)]
+pub struct RemapExecutionTimeExceeded {
+    /// If set to true, the remap transform has dropped the event after the program exceeded its
+    /// execution time budget. This internal event reflects that in its messaging.
+    pub event_dropped: bool,
+    pub elapsed: std::time::Duration,
+    pub max_execution_time: std::time::Duration,
+}
+
+impl InternalEvent for RemapExecutionTimeExceeded {
+    fn emit(self) {
+        error!(
+            message = "Mapping exceeded its execution time budget.",
+            elapsed_ms = self.elapsed.as_millis() as u64,
+            max_execution_time_ms = self.max_execution_time.as_millis() as u64,
+            error_code = "execution_time_exceeded",
+            error_type = error_type::TIMED_OUT,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "execution_time_exceeded",
+            "error_type" => error_type::TIMED_OUT,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+        if self.event_dropped {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                count: 1,
+                reason: "Mapping exceeded its execution time budget.",
+            });
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct RemapMappingAbort {
     /// If set to true, the remap transform has dropped the event after an abort
@@ -58,3 +94,30 @@ impl InternalEvent for RemapMappingAbort {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct RemapStatePersistError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a std::path::Path,
+}
+
+impl InternalEvent for RemapStatePersistError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed persisting state.",
+            error = %self.error,
+            path = ?self.path,
+            error_code = "persisting_state",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "persisting_state",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,26 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct RouteDynamicOutputError<'a> {
+    pub error: &'a str,
+}
+
+impl InternalEvent for RouteDynamicOutputError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to resolve the dynamic output of event.",
+            error = %self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
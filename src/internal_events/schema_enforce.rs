@@ -0,0 +1,30 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct SchemaEnforceViolation<'a> {
+    pub violations: usize,
+    pub error: &'a str,
+}
+
+impl InternalEvent for SchemaEnforceViolation<'_> {
+    fn emit(self) {
+        error!(
+            message = "Event does not match the schema.",
+            violations = %self.violations,
+            error = %self.error,
+            error_code = "schema_violation",
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "schema_violation",
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
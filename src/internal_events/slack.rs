@@ -0,0 +1,30 @@
+use metrics::counter;
+use vector_lib::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, InternalEvent, UNINTENTIONAL,
+};
+
+/// Webhook URIs embed their secret, so the rendered URI itself isn't logged.
+#[derive(Debug)]
+pub struct SlackInvalidUriError {
+    pub error: String,
+}
+
+impl InternalEvent for SlackInvalidUriError {
+    fn emit(self) {
+        let reason = "Rendered webhook URI is invalid.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::TEMPLATE_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::TEMPLATE_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,39 @@
+use std::net::SocketAddr;
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+use crate::sources::snmp_trap::TrapError;
+
+#[derive(Debug)]
+pub struct SnmpTrapParseError {
+    pub error: TrapError,
+    pub peer: SocketAddr,
+}
+
+impl InternalEvent for SnmpTrapParseError {
+    fn emit(self) {
+        let error_code = self.error.error_code();
+        error!(
+            message = "Discarding invalid SNMP message.",
+            error = %self.error,
+            error_code = error_code,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::RECEIVING,
+            peer = %self.peer,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => error_code,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("SnmpTrapParseError")
+    }
+}
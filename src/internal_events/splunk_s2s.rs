@@ -0,0 +1,28 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+use crate::sources::splunk_s2s::DecodeError;
+
+#[derive(Debug)]
+pub struct SplunkS2sMessageDecodeError<'a> {
+    pub error: &'a DecodeError,
+}
+
+impl<'a> InternalEvent for SplunkS2sMessageDecodeError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Error decoding Splunk forwarder message.",
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
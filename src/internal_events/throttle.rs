@@ -1,5 +1,7 @@
 use metrics::counter;
-use vector_lib::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+use vector_lib::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, InternalEvent, INTENTIONAL,
+};
 
 #[derive(Debug)]
 pub(crate) struct ThrottleEventDiscarded {
@@ -31,3 +33,26 @@ impl InternalEvent for ThrottleEventDiscarded {
         })
     }
 }
+
+#[derive(Debug)]
+pub(crate) struct ThrottleThresholdResolveError {
+    pub error: String,
+}
+
+impl InternalEvent for ThrottleThresholdResolveError {
+    fn emit(self) {
+        error!(
+            message = "Failed to resolve threshold, using the static threshold instead.",
+            error = %self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL};
+
+#[derive(Debug)]
+pub struct WasmProcessingError<'a> {
+    pub error: &'a str,
+    pub error_code: &'static str,
+}
+
+impl InternalEvent for WasmProcessingError<'_> {
+    fn emit(self) {
+        let reason = "Error in WebAssembly component.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_code = self.error_code,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => self.error_code,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct WasmComponentReloaded<'a> {
+    pub path: &'a Path,
+}
+
+impl InternalEvent for WasmComponentReloaded<'_> {
+    fn emit(self) {
+        info!(
+            message = "Reloaded WebAssembly component.",
+            path = %self.path.display(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WasmComponentReloadError<'a> {
+    pub path: &'a Path,
+    pub error: &'a str,
+}
+
+impl InternalEvent for WasmComponentReloadError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to reload WebAssembly component, keeping the previous one.",
+            path = %self.path.display(),
+            error = %self.error,
+            error_code = "reload_failed",
+            error_type = error_type::CONFIGURATION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "reload_failed",
+            "error_type" => error_type::CONFIGURATION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,33 @@
+use metrics::counter;
+use vector_lib::internal_event::{
+    error_stage, ComponentEventsDropped, InternalEvent, UNINTENTIONAL,
+};
+
+#[derive(Debug)]
+pub struct WebhookRenderError {
+    /// The part of the request that failed to render, `uri` or `body`.
+    pub part: &'static str,
+    pub error: String,
+    pub error_type: &'static str,
+}
+
+impl InternalEvent for WebhookRenderError {
+    fn emit(self) {
+        let reason = "Failed to render the request.";
+        error!(
+            message = reason,
+            part = self.part,
+            error = %self.error,
+            error_type = self.error_type,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => self.error_type,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
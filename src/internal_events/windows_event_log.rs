@@ -0,0 +1,89 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL};
+
+use crate::sources::windows_event_log::parser::ParseError;
+#[cfg(windows)]
+use crate::sources::windows_event_log::subscription::SubscriptionError;
+
+#[cfg(windows)]
+#[derive(Debug)]
+pub struct WindowsEventLogReadError<'a> {
+    pub channel: &'a str,
+    pub error: SubscriptionError,
+}
+
+#[cfg(windows)]
+impl<'a> InternalEvent for WindowsEventLogReadError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Could not read events from the Windows Event Log.",
+            channel = %self.channel,
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::READER_FAILED,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogParseError<'a> {
+    pub channel: &'a str,
+    pub error: &'a ParseError,
+}
+
+impl<'a> InternalEvent for WindowsEventLogParseError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Invalid event from the Windows Event Log, discarding.",
+            channel = %self.channel,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::PARSER_FAILED,
+        )
+        .increment(1);
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: 1,
+            reason: "Invalid event XML.",
+        });
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogBookmarkError {
+    pub error: std::io::Error,
+    pub filename: String,
+}
+
+impl InternalEvent for WindowsEventLogBookmarkError {
+    fn emit(self) {
+        error!(
+            message = "Could not persist the bookmarks of the Windows Event Log channels.",
+            filename = %self.filename,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::IO_FAILED,
+        )
+        .increment(1);
+    }
+}
@@ -8,7 +8,8 @@ use vector_lib::configurable::configurable_component;
 use vector_lib::sensitive_string::SensitiveString;
 
 use crate::{
-    internal_events::KafkaStatisticsReceived, tls::TlsEnableableConfig, tls::PEM_START_MARKER,
+    config::ComponentKey, internal_events::KafkaStatisticsReceived, tls::TlsEnableableConfig,
+    tls::PEM_START_MARKER,
 };
 
 #[derive(Debug, Snafu)]
@@ -185,3 +186,28 @@ impl ClientContext for KafkaStatisticsContext {
 }
 
 impl ConsumerContext for KafkaStatisticsContext {}
+
+/// The position of the message an event was read from by a running `kafka` source.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ConsumedOffset {
+    pub(crate) source: ComponentKey,
+    pub(crate) topic: String,
+    pub(crate) partition: i32,
+    pub(crate) offset: i64,
+}
+
+#[cfg(feature = "sources-kafka")]
+pub(crate) use crate::sources::kafka::{consumed_offset, consumer_group_metadata};
+
+/// Without the `kafka` source, no event is ever read from a consumer.
+#[cfg(not(feature = "sources-kafka"))]
+pub(crate) const fn consumed_offset(_event: &crate::event::Event) -> Option<ConsumedOffset> {
+    None
+}
+
+#[cfg(not(feature = "sources-kafka"))]
+pub(crate) const fn consumer_group_metadata(
+    _key: &ComponentKey,
+) -> Option<rdkafka::consumer::ConsumerGroupMetadata> {
+    None
+}
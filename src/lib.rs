@@ -67,6 +67,8 @@ pub mod app;
 pub mod async_read;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+#[cfg(any(feature = "sinks-azure_event_hubs", feature = "sources-azure_event_hubs"))]
+pub mod azure_event_hubs;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub mod common;
@@ -89,6 +91,8 @@ pub mod list;
 #[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub(crate) mod nats;
 pub mod net;
+#[cfg(any(feature = "sources-plugin", feature = "sinks-plugin"))]
+pub(crate) mod plugin;
 #[allow(unreachable_pub)]
 pub(crate) mod proto;
 pub mod providers;
@@ -123,6 +127,7 @@ pub(crate) mod utilization;
 pub mod validate;
 #[cfg(windows)]
 pub mod vector_windows;
+pub mod vrl_cli;
 
 pub use source_sender::SourceSender;
 pub use vector_lib::{event, metrics, schema, tcp, tls};
@@ -111,17 +111,28 @@ pub struct LineAgg<T, K, C> {
     draining: Option<Vec<(K, Bytes, C, Option<C>)>>,
 }
 
+/// Selects the index of the configuration to aggregate the lines of a key with, `None` to pass
+/// its lines through.
+pub type Selector<K> = Box<dyn Fn(&K) -> Option<usize> + Send + Sync>;
+
 /// Core line aggregation logic.
 ///
 /// Encapsulates the essential state and the core logic for the line
 /// aggregation algorithm.
 pub struct Logic<K, C> {
     /// Configuration parameters to use.
-    config: Config,
+    configs: Vec<Config>,
+
+    /// Selects the configuration of each key, the first configuration being used for all the keys
+    /// if not set.
+    selector: Option<Selector<K>>,
+
+    /// The configurations selected per key.
+    selections: HashMap<K, Option<usize>>,
 
-    /// Line per key.
+    /// Line per key, along with the index of its configuration.
     /// Key is usually a filename or other line source identifier.
-    buffers: HashMap<K, (Key, Aggregate<C>)>,
+    buffers: HashMap<K, (Key, usize, Aggregate<C>)>,
 
     /// A queue of key timeouts.
     timeouts: DelayQueue<K>,
@@ -131,7 +142,21 @@ impl<K, C> Logic<K, C> {
     /// Create a new `Logic` using the specified `Config`.
     pub fn new(config: Config) -> Self {
         Self {
-            config,
+            configs: vec![config],
+            selector: None,
+            selections: HashMap::new(),
+            buffers: HashMap::new(),
+            timeouts: DelayQueue::new(),
+        }
+    }
+
+    /// Create a new `Logic` aggregating the lines of every key with the configuration selected by
+    /// `selector`, among the specified `Config`s.
+    pub fn with_selector(configs: Vec<Config>, selector: Selector<K>) -> Self {
+        Self {
+            configs,
+            selector: Some(selector),
+            selections: HashMap::new(),
             buffers: HashMap::new(),
             timeouts: DelayQueue::new(),
         }
@@ -207,7 +232,7 @@ where
                         this.logic
                             .buffers
                             .drain()
-                            .map(|(src, (_, aggregate))| {
+                            .map(|(src, (_, _, aggregate))| {
                                 let (line, initial_context, last_context) = aggregate.merge();
                                 (src, line, initial_context, last_context)
                             })
@@ -220,7 +245,7 @@ where
                     while let Poll::Ready(Some(expired_key)) = this.logic.timeouts.poll_expired(cx)
                     {
                         let key = expired_key.into_inner();
-                        if let Some((_, aggregate)) = this.logic.buffers.remove(&key) {
+                        if let Some((_, _, aggregate)) = this.logic.buffers.remove(&key) {
                             let (line, initial_context, last_context) = aggregate.merge();
                             return Poll::Ready(Some((key, line, initial_context, last_context)));
                         }
@@ -307,8 +332,9 @@ where
         // Check if we already have the buffered data for the source.
         match self.buffers.entry(src) {
             Entry::Occupied(mut entry) => {
-                let condition_matched = self.config.condition_pattern.is_match(line.as_ref());
-                let decision = match (self.config.mode, condition_matched) {
+                let config = &self.configs[entry.get().1];
+                let condition_matched = config.condition_pattern.is_match(line.as_ref());
+                let decision = match (config.mode, condition_matched) {
                     // All consecutive lines matching this pattern are included in
                     // the group.
                     (Mode::ContinueThrough, true) => Decision::Continue,
@@ -330,32 +356,41 @@ where
                 match decision {
                     Decision::Continue => {
                         let buffered = entry.get_mut();
-                        self.timeouts.reset(&buffered.0, self.config.timeout);
-                        buffered.1.add_next_line(line, context);
+                        self.timeouts.reset(&buffered.0, config.timeout);
+                        buffered.2.add_next_line(line, context);
                         None
                     }
                     Decision::EndInclude => {
-                        let (src, (key, mut buffered)) = entry.remove_entry();
+                        let (src, (key, _, mut buffered)) = entry.remove_entry();
                         self.timeouts.remove(&key);
                         buffered.add_next_line(line, context);
                         Some((src, Emit::One(buffered.merge())))
                     }
                     Decision::EndExclude => {
-                        let (src, (key, buffered)) = entry.remove_entry();
+                        let (src, (key, _, buffered)) = entry.remove_entry();
                         self.timeouts.remove(&key);
                         Some((src, Emit::Two(buffered.merge(), (line, context, None))))
                     }
                 }
             }
             Entry::Vacant(entry) => {
+                let index = match &self.selector {
+                    None => Some(0),
+                    Some(selector) => *self
+                        .selections
+                        .entry(entry.key().clone())
+                        .or_insert_with(|| selector(entry.key())),
+                };
+                let config = index.map(|index| (index, &self.configs[index]));
+
                 // This line is a candidate for buffering, or passing through.
-                if self.config.start_pattern.is_match(line.as_ref()) {
+                if let Some((index, config)) =
+                    config.filter(|(_, config)| config.start_pattern.is_match(line.as_ref()))
+                {
                     // It was indeed a new line we need to filter.
                     // Set the timeout and buffer this line.
-                    let key = self
-                        .timeouts
-                        .insert(entry.key().clone(), self.config.timeout);
-                    entry.insert((key, Aggregate::new(line, context)));
+                    let key = self.timeouts.insert(entry.key().clone(), config.timeout);
+                    entry.insert((key, index, Aggregate::new(line, context)));
                     None
                 } else {
                     // It's just a regular line we don't really care about.
@@ -786,6 +821,66 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn selects_config_per_key() {
+        let lines = [
+            ("java.log", "Exception in thread main"),
+            ("plain.log", "first line"),
+            ("java.log", "  at Main.main"),
+            ("plain.log", "  indented line"),
+            ("java.log", "next message"),
+        ];
+        let config = Config {
+            start_pattern: Regex::new("^[^\\s]").unwrap(),
+            condition_pattern: Regex::new("^[\\s]+").unwrap(),
+            mode: Mode::ContinueThrough,
+            timeout: Duration::from_millis(10),
+        };
+        let logic = Logic::with_selector(
+            vec![config],
+            Box::new(|filename: &Filename| (filename == "java.log").then_some(0)),
+        );
+        let stream =
+            futures::stream::iter(lines.iter().enumerate().map(|(index, (filename, line))| {
+                (
+                    filename.to_string(),
+                    Bytes::from_static(line.as_bytes()),
+                    index,
+                )
+            }));
+
+        let results: Vec<_> = LineAgg::new(stream, logic)
+            .map(|(filename, line, context, last_context)| {
+                (
+                    filename,
+                    String::from_utf8(line.to_vec()).unwrap(),
+                    context,
+                    last_context,
+                )
+            })
+            .collect()
+            .await;
+        assert_eq!(
+            results,
+            [
+                ("plain.log".to_owned(), "first line".to_owned(), 1, None),
+                (
+                    "plain.log".to_owned(),
+                    "  indented line".to_owned(),
+                    3,
+                    None
+                ),
+                (
+                    "java.log".to_owned(),
+                    "Exception in thread main\n  at Main.main".to_owned(),
+                    0,
+                    Some(2)
+                ),
+                ("java.log".to_owned(), "next message".to_owned(), 4, None),
+            ]
+        );
+    }
+
     // Test helpers.
 
     /// Private type alias to be more expressive in the internal implementation.
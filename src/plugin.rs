@@ -0,0 +1,175 @@
+//! Supervision of the external processes backing the `plugin` source and sink.
+//!
+//! Plugins speak the gRPC protocol of the `vector` source and sink, defined in
+//! `proto/vector/vector.proto`: source plugins push their events to Vector, while sink plugins
+//! serve the requests Vector sends them. Vector runs the plugin command, passes it the address to
+//! connect to or listen on through the `VECTOR_PLUGIN_ADDRESS` environment variable, and restarts
+//! it whenever it exits. Buffering, batching, retries, and acknowledgements are all handled by
+//! Vector, so plugins only have to implement the protocol.
+
+use std::{collections::HashMap, path::PathBuf, process::Stdio, time::Duration};
+
+use serde_with::serde_as;
+use snafu::Snafu;
+use tokio::{process::Command, task::JoinHandle};
+use vector_lib::configurable::configurable_component;
+
+use crate::internal_events::{PluginProcessExited, PluginProcessSpawnError};
+
+/// The environment variable holding the address the plugin connects to or listens on.
+pub(crate) const ADDRESS_ENV_VAR: &str = "VECTOR_PLUGIN_ADDRESS";
+
+#[derive(Debug, PartialEq, Eq, Snafu)]
+pub enum PluginConfigError {
+    #[snafu(display("A non-empty list for command must be provided"))]
+    CommandEmpty,
+}
+
+/// Configuration of the plugin process.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct PluginProcessConfig {
+    /// The command running the plugin, plus any arguments required.
+    #[configurable(metadata(docs::examples = "/usr/local/bin/vector-plugin-example"))]
+    pub command: Vec<String>,
+
+    /// Custom environment variables to set when running the plugin.
+    ///
+    /// This is typically used to pass options to the plugin.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "An environment variable."))]
+    #[configurable(metadata(docs::examples = "environment_examples()"))]
+    pub environment: HashMap<String, String>,
+
+    /// The directory in which to run the plugin.
+    pub working_directory: Option<PathBuf>,
+
+    /// The amount of time, in seconds, to wait before restarting the plugin when it exits.
+    #[serde(default = "default_restart_delay_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Restart Delay"))]
+    pub restart_delay_secs: Duration,
+}
+
+const fn default_restart_delay_secs() -> Duration {
+    Duration::from_secs(5)
+}
+
+fn environment_examples() -> HashMap<String, String> {
+    HashMap::<_, _>::from_iter([("PLUGIN_LOG_LEVEL".to_owned(), "info".to_owned())])
+}
+
+impl PluginProcessConfig {
+    /// Creates the configuration of a plugin running the given command.
+    pub fn new(command: Vec<String>) -> Self {
+        Self {
+            command,
+            environment: HashMap::new(),
+            working_directory: None,
+            restart_delay_secs: default_restart_delay_secs(),
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), PluginConfigError> {
+        if self.command.is_empty() {
+            Err(PluginConfigError::CommandEmpty)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Starts supervising the plugin, which runs until the returned handle is dropped.
+    pub(crate) fn spawn(&self, address: String) -> PluginProcess {
+        PluginProcess(tokio::spawn(self.clone().supervise(address)))
+    }
+
+    async fn supervise(self, address: String) {
+        let command_line = self.command.join(" ");
+        loop {
+            match self.build_command(&address).spawn() {
+                Ok(mut child) => {
+                    debug!(
+                        message = "Started plugin.",
+                        command = %command_line,
+                        pid = ?child.id(),
+                    );
+                    let exit_status = child.wait().await.ok().and_then(|status| status.code());
+                    emit!(PluginProcessExited {
+                        command: &command_line,
+                        exit_status,
+                    });
+                }
+                Err(error) => emit!(PluginProcessSpawnError {
+                    command: &command_line,
+                    error,
+                }),
+            }
+            tokio::time::sleep(self.restart_delay_secs).await;
+        }
+    }
+
+    fn build_command(&self, address: &str) -> Command {
+        let mut command = Command::new(&self.command[0]);
+        command
+            .args(&self.command[1..])
+            .envs(&self.environment)
+            .env(ADDRESS_ENV_VAR, address)
+            .stdin(Stdio::null())
+            // The plugin is stopped along with the component.
+            .kill_on_drop(true);
+        if let Some(directory) = &self.working_directory {
+            command.current_dir(directory);
+        }
+        command
+    }
+}
+
+/// A supervised plugin, stopped when dropped.
+pub(crate) struct PluginProcess(JoinHandle<()>);
+
+impl Drop for PluginProcess {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_empty_command() {
+        assert_eq!(
+            PluginProcessConfig::new(vec![]).validate(),
+            Err(PluginConfigError::CommandEmpty)
+        );
+    }
+
+    #[tokio::test]
+    async fn restarts_plugin_with_its_address() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("addresses");
+        let mut config = PluginProcessConfig::new(vec![
+            "sh".to_owned(),
+            "-c".to_owned(),
+            format!("echo ${ADDRESS_ENV_VAR} >> {}", output.display()),
+        ]);
+        config.restart_delay_secs = Duration::from_millis(10);
+
+        let process = config.spawn("127.0.0.1:6001".to_owned());
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while std::fs::read_to_string(&output).map_or(0, |addresses| addresses.lines().count())
+                < 2
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("plugin was not restarted");
+        drop(process);
+
+        let addresses = std::fs::read_to_string(&output).unwrap();
+        assert!(addresses.lines().all(|address| address == "127.0.0.1:6001"));
+    }
+}
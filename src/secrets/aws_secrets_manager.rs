@@ -1,4 +1,7 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 use aws_sdk_secretsmanager::{config, Client};
 use vector_lib::configurable::{component::GenerateConfig, configurable_component};
@@ -26,6 +29,13 @@ pub struct AwsSecretsManagerBackend {
     /// ID of the secret to resolve.
     pub secret_id: String,
 
+    /// How often, in seconds, to check the secret for changes.
+    ///
+    /// When it changed, the configuration is reloaded with the new values. The secret is only
+    /// resolved at startup and on configuration reloads if unset.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub refresh_interval_secs: Option<u64>,
+
     #[serde(flatten)]
     #[configurable(derived)]
     pub region: RegionOrEndpoint,
@@ -42,6 +52,7 @@ impl GenerateConfig for AwsSecretsManagerBackend {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(AwsSecretsManagerBackend {
             secret_id: String::from("secret-id"),
+            refresh_interval_secs: None,
             region: Default::default(),
             auth: Default::default(),
             tls: None,
@@ -102,4 +113,8 @@ impl SecretBackend for AwsSecretsManagerBackend {
         }
         Ok(secrets)
     }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        self.refresh_interval_secs.map(Duration::from_secs)
+    }
 }
@@ -0,0 +1,279 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use http::{Method, Request, StatusCode};
+use hyper::Body;
+use serde::{Deserialize, Serialize};
+use vector_lib::configurable::{component::GenerateConfig, configurable_component};
+
+use crate::aws::{resolve_region, sign_request, AwsAuthentication, RegionOrEndpoint};
+use crate::config::ProxyConfig;
+use crate::http::HttpClient;
+use crate::tls::{TlsConfig, TlsSettings};
+use crate::{config::SecretBackend, signal};
+
+/// The maximum number of parameters a single `GetParameters` call can resolve.
+const MAX_PARAMETERS_PER_REQUEST: usize = 10;
+
+/// Configuration for the `aws_ssm_parameter_store` secrets backend.
+#[configurable_component(secrets("aws_ssm_parameter_store"))]
+#[derive(Clone, Debug)]
+pub struct AwsSsmParameterStoreBackend {
+    /// Prefix prepended to each secret key to form the name of the parameter to resolve.
+    ///
+    /// For example, with a prefix of `/vector/production/`, `SECRET[<backend_name>.db_password]`
+    /// resolves the `/vector/production/db_password` parameter.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/vector/production/"))]
+    pub prefix: String,
+
+    /// Whether to decrypt `SecureString` parameters.
+    #[serde(default = "crate::serde::default_true")]
+    pub with_decryption: bool,
+
+    /// How often, in seconds, to check the resolved parameters for changes.
+    ///
+    /// When any of them changed, the configuration is reloaded with the new values. Parameters are
+    /// only resolved at startup and on configuration reloads if unset.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub refresh_interval_secs: Option<u64>,
+
+    #[serde(flatten)]
+    #[configurable(derived)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+impl GenerateConfig for AwsSsmParameterStoreBackend {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(AwsSsmParameterStoreBackend {
+            prefix: String::from("/vector/"),
+            with_decryption: true,
+            refresh_interval_secs: None,
+            region: Default::default(),
+            auth: Default::default(),
+            tls: None,
+        })
+        .unwrap()
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetParametersRequest<'a> {
+    names: &'a [String],
+    with_decryption: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetParametersResponse {
+    #[serde(default)]
+    parameters: Vec<Parameter>,
+    #[serde(default)]
+    invalid_parameters: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Parameter {
+    name: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    #[serde(rename = "__type", default)]
+    kind: String,
+    #[serde(alias = "Message", default)]
+    message: String,
+}
+
+impl SecretBackend for AwsSsmParameterStoreBackend {
+    async fn retrieve(
+        &mut self,
+        secret_keys: HashSet<String>,
+        _: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let proxy = ProxyConfig::from_env();
+        let region = resolve_region(&proxy, &self.tls, self.region.region()).await?;
+        let credentials_provider = self
+            .auth
+            .credentials_provider(region.clone(), &proxy, &self.tls)
+            .await?;
+        let endpoint = self
+            .region
+            .endpoint()
+            .unwrap_or_else(|| format!("https://ssm.{region}.amazonaws.com"));
+        let client = HttpClient::new(TlsSettings::from_options(&self.tls)?, &proxy)?;
+
+        let keys_by_name = secret_keys
+            .into_iter()
+            .map(|key| (format!("{}{}", self.prefix, key), key))
+            .collect::<HashMap<_, _>>();
+        let names = keys_by_name.keys().cloned().collect::<Vec<_>>();
+
+        let mut secrets = HashMap::new();
+        for names in names.chunks(MAX_PARAMETERS_PER_REQUEST) {
+            let body = serde_json::to_vec(&GetParametersRequest {
+                names,
+                with_decryption: self.with_decryption,
+            })?;
+            let mut request = Request::builder()
+                .method(Method::POST)
+                .uri(&endpoint)
+                .header("Content-Type", "application/x-amz-json-1.1")
+                .header("X-Amz-Target", "AmazonSSM.GetParameters")
+                .body(Bytes::from(body))?;
+            sign_request(
+                "ssm",
+                &mut request,
+                &credentials_provider,
+                &Some(region.clone()),
+            )
+            .await?;
+
+            let response = client.send(request.map(Body::from)).await?;
+            let status = response.status();
+            let body = hyper::body::to_bytes(response.into_body()).await?;
+            if status != StatusCode::OK {
+                let error = serde_json::from_slice::<ErrorResponse>(&body).map_or_else(
+                    |_| String::from_utf8_lossy(&body).into_owned(),
+                    |error| format!("{}: {}", error.kind, error.message),
+                );
+                return Err(format!("failed to get parameters ({status}): {error}").into());
+            }
+
+            let response = serde_json::from_slice::<GetParametersResponse>(&body)?;
+            if let Some(name) = response.invalid_parameters.first() {
+                return Err(format!("parameter '{name}' does not exist").into());
+            }
+            for parameter in response.parameters {
+                if parameter.value.is_empty() {
+                    return Err(format!("value of parameter '{}' was empty", parameter.name).into());
+                }
+                if let Some(key) = keys_by_name.get(&parameter.name) {
+                    secrets.insert(key.clone(), parameter.value);
+                }
+            }
+        }
+
+        if let Some(key) = keys_by_name
+            .values()
+            .find(|key| !secrets.contains_key(*key))
+        {
+            return Err(format!("parameter for key '{key}' could not be retrieved").into());
+        }
+        Ok(secrets)
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        self.refresh_interval_secs.map(Duration::from_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{body_json, header, method},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsSsmParameterStoreBackend>();
+    }
+
+    fn backend(endpoint: String) -> AwsSsmParameterStoreBackend {
+        AwsSsmParameterStoreBackend {
+            prefix: "/vector/".to_string(),
+            with_decryption: true,
+            refresh_interval_secs: None,
+            region: RegionOrEndpoint::with_both("us-east-1", endpoint),
+            auth: AwsAuthentication::test_auth(),
+            tls: None,
+        }
+    }
+
+    async fn retrieve(
+        backend: &mut AwsSsmParameterStoreBackend,
+        keys: &[&str],
+    ) -> crate::Result<HashMap<String, String>> {
+        let (_tx, mut rx) = tokio::sync::broadcast::channel(1);
+        let keys = keys.iter().map(|key| key.to_string()).collect();
+        backend.retrieve(keys, &mut rx).await
+    }
+
+    #[tokio::test]
+    async fn retrieves_prefixed_parameters() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header("X-Amz-Target", "AmazonSSM.GetParameters"))
+            .and(body_json(serde_json::json!({
+                "Names": ["/vector/token"],
+                "WithDecryption": true,
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Parameters": [{"Name": "/vector/token", "Type": "SecureString", "Value": "hunter2"}],
+                "InvalidParameters": [],
+            })))
+            .mount(&server)
+            .await;
+
+        let secrets = retrieve(&mut backend(server.uri()), &["token"])
+            .await
+            .unwrap();
+
+        assert_eq!(secrets, HashMap::from([("token".into(), "hunter2".into())]));
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_parameters() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Parameters": [],
+                "InvalidParameters": ["/vector/missing"],
+            })))
+            .mount(&server)
+            .await;
+
+        let error = retrieve(&mut backend(server.uri()), &["missing"])
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("does not exist"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn reports_service_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "__type": "AccessDeniedException",
+                "message": "not authorized",
+            })))
+            .mount(&server)
+            .await;
+
+        let error = retrieve(&mut backend(server.uri()), &["token"])
+            .await
+            .unwrap_err();
+
+        assert!(
+            error.to_string().contains("AccessDeniedException"),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,649 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use http::{Method, Request, StatusCode};
+use hyper::Body;
+use once_cell::sync::Lazy;
+use serde::{de::DeserializeOwned, Deserialize};
+use tokio::task::JoinHandle;
+use vector_lib::configurable::{component::GenerateConfig, configurable_component};
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    config::{ProxyConfig, SecretBackend},
+    http::HttpClient,
+    signal,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Remaining token lifetime below which a cached token is not reused.
+const MIN_TOKEN_TTL: Duration = Duration::from_secs(10);
+
+/// Renewed Vault tokens, shared across configuration reloads.
+static SESSIONS: Lazy<Mutex<HashMap<SessionKey, Session>>> = Lazy::new(Default::default);
+
+/// Configuration for the `hashicorp_vault` secrets backend.
+#[configurable_component(secrets("hashicorp_vault"))]
+#[derive(Clone, Debug)]
+pub struct HashicorpVaultBackend {
+    /// The address of the Vault server.
+    #[configurable(metadata(docs::examples = "https://vault.example.com:8200"))]
+    pub endpoint: String,
+
+    /// The Vault Enterprise namespace to send requests to.
+    #[configurable(metadata(docs::examples = "team-a"))]
+    pub namespace: Option<String>,
+
+    /// The path at which the KV version 2 secrets engine is mounted.
+    #[serde(default = "default_mount")]
+    pub mount: String,
+
+    /// The path of the secret to resolve, relative to `mount`.
+    ///
+    /// Each key of the secret's data can then be referenced as `SECRET[<backend_name>.<key>]`.
+    #[configurable(metadata(docs::examples = "vector/credentials"))]
+    pub path: String,
+
+    /// The version of the secret to resolve.
+    ///
+    /// The latest version is resolved if unset.
+    pub version: Option<u64>,
+
+    #[configurable(derived)]
+    pub auth: VaultAuth,
+
+    /// Whether to keep renewable Vault tokens alive in the background.
+    ///
+    /// When enabled, the token used by this backend is renewed before its lease expires and is
+    /// reused when the configuration is reloaded, so that reloads don't fail because the token has
+    /// expired in the meantime. Tokens stop being renewed once they reach their maximum TTL.
+    #[serde(default = "crate::serde::default_true")]
+    pub renew_token: bool,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+/// The method used to authenticate with Vault.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+#[configurable(metadata(docs::enum_tag_description = "The authentication strategy to use."))]
+pub enum VaultAuth {
+    /// Authenticate with a Vault token.
+    Token {
+        /// The Vault token.
+        #[configurable(metadata(docs::examples = "${VAULT_TOKEN}"))]
+        token: SensitiveString,
+    },
+
+    /// Authenticate with the [AppRole][approle] auth method.
+    ///
+    /// [approle]: https://developer.hashicorp.com/vault/docs/auth/approle
+    #[serde(rename = "approle")]
+    AppRole {
+        /// The role ID of the AppRole.
+        #[configurable(metadata(docs::examples = "${VAULT_ROLE_ID}"))]
+        role_id: String,
+
+        /// The secret ID of the AppRole.
+        #[configurable(metadata(docs::examples = "${VAULT_SECRET_ID}"))]
+        secret_id: SensitiveString,
+
+        /// The path at which the AppRole auth method is mounted.
+        #[serde(default = "default_approle_mount")]
+        mount: String,
+    },
+}
+
+fn default_mount() -> String {
+    "secret".to_string()
+}
+
+fn default_approle_mount() -> String {
+    "approle".to_string()
+}
+
+impl GenerateConfig for HashicorpVaultBackend {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(HashicorpVaultBackend {
+            endpoint: String::from("https://vault.example.com:8200"),
+            namespace: None,
+            mount: default_mount(),
+            path: String::from("vector/credentials"),
+            version: None,
+            auth: VaultAuth::Token {
+                token: String::from("${VAULT_TOKEN}").into(),
+            },
+            renew_token: true,
+            tls: None,
+        })
+        .unwrap()
+    }
+}
+
+/// Identifies the credentials a token was obtained with.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct SessionKey {
+    endpoint: String,
+    namespace: Option<String>,
+    credentials: String,
+}
+
+#[derive(Debug)]
+struct Session {
+    token: String,
+    expires_at: Arc<Mutex<Instant>>,
+    renewal: JoinHandle<()>,
+}
+
+impl Session {
+    fn is_usable(&self) -> bool {
+        !self.renewal.is_finished()
+            && *self.expires_at.lock().expect("poisoned lock") > Instant::now() + MIN_TOKEN_TTL
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.renewal.abort();
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct VaultErrors {
+    #[serde(default)]
+    errors: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AuthResponse {
+    auth: TokenLease,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenLease {
+    client_token: String,
+    lease_duration: u64,
+    renewable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct LookupResponse {
+    data: TokenLookup,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenLookup {
+    ttl: u64,
+    renewable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct KvResponse {
+    data: KvData,
+}
+
+#[derive(Debug, Deserialize)]
+struct KvData {
+    data: HashMap<String, serde_json::Value>,
+}
+
+/// The parts of the backend needed to talk to Vault, also owned by the token renewal task.
+#[derive(Clone)]
+struct VaultClient {
+    client: HttpClient,
+    endpoint: String,
+    namespace: Option<String>,
+}
+
+impl VaultClient {
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        token: Option<&str>,
+        body: Option<serde_json::Value>,
+    ) -> crate::Result<T> {
+        let uri = format!("{}/v1/{}", self.endpoint.trim_end_matches('/'), path);
+        let mut builder = Request::builder().method(method).uri(&uri);
+        if let Some(token) = token {
+            builder = builder.header("X-Vault-Token", token);
+        }
+        if let Some(namespace) = &self.namespace {
+            builder = builder.header("X-Vault-Namespace", namespace);
+        }
+        let body = match body {
+            Some(body) => Body::from(serde_json::to_vec(&body)?),
+            None => Body::empty(),
+        };
+
+        let response = self.client.send(builder.body(body)?).await?;
+        let status = response.status();
+        let body: Bytes = hyper::body::to_bytes(response.into_body()).await?;
+
+        if status != StatusCode::OK {
+            let errors = serde_json::from_slice::<VaultErrors>(&body)
+                .map(|errors| errors.errors.join(", "))
+                .unwrap_or_default();
+            return Err(format!("Vault request to {uri} failed with {status}: {errors}").into());
+        }
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Obtains a token and its lease for the configured credentials.
+    async fn login(&self, auth: &VaultAuth) -> crate::Result<TokenLease> {
+        match auth {
+            VaultAuth::Token { token } => {
+                let lookup: LookupResponse = self
+                    .call(
+                        Method::GET,
+                        "auth/token/lookup-self",
+                        Some(token.inner()),
+                        None,
+                    )
+                    .await?;
+                Ok(TokenLease {
+                    client_token: token.inner().to_string(),
+                    lease_duration: lookup.data.ttl,
+                    renewable: lookup.data.renewable,
+                })
+            }
+            VaultAuth::AppRole {
+                role_id,
+                secret_id,
+                mount,
+            } => {
+                let response: AuthResponse = self
+                    .call(
+                        Method::POST,
+                        &format!("auth/{}/login", mount.trim_matches('/')),
+                        None,
+                        Some(serde_json::json!({
+                            "role_id": role_id,
+                            "secret_id": secret_id.inner(),
+                        })),
+                    )
+                    .await?;
+                Ok(response.auth)
+            }
+        }
+    }
+
+    /// Renews `token` shortly before its lease runs out, for as long as Vault allows it.
+    async fn renew_periodically(
+        self,
+        token: String,
+        mut lease: Duration,
+        expires_at: Arc<Mutex<Instant>>,
+    ) {
+        loop {
+            tokio::time::sleep((lease * 2 / 3).max(Duration::from_secs(1))).await;
+
+            match self
+                .call::<AuthResponse>(
+                    Method::POST,
+                    "auth/token/renew-self",
+                    Some(&token),
+                    Some(serde_json::json!({})),
+                )
+                .await
+            {
+                Ok(AuthResponse { auth }) if auth.renewable && auth.lease_duration > 0 => {
+                    lease = Duration::from_secs(auth.lease_duration);
+                    *expires_at.lock().expect("poisoned lock") = Instant::now() + lease;
+                    debug!(
+                        message = "Renewed Vault token.",
+                        lease_duration_secs = auth.lease_duration
+                    );
+                }
+                Ok(_) => {
+                    debug!(message = "Vault token can no longer be renewed.");
+                    break;
+                }
+                Err(error) => {
+                    warn!(message = "Failed to renew Vault token.", %error);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl HashicorpVaultBackend {
+    fn session_key(&self) -> SessionKey {
+        let credentials = match &self.auth {
+            VaultAuth::Token { token } => format!("token:{}", token.inner()),
+            VaultAuth::AppRole { role_id, mount, .. } => format!("approle:{mount}:{role_id}"),
+        };
+        SessionKey {
+            endpoint: self.endpoint.clone(),
+            namespace: self.namespace.clone(),
+            credentials,
+        }
+    }
+
+    /// Returns a token for the configured credentials, reusing one kept alive by a previous load
+    /// when possible.
+    async fn token(&self, client: &VaultClient) -> crate::Result<String> {
+        let key = self.session_key();
+        if self.renew_token {
+            let mut sessions = SESSIONS.lock().expect("poisoned lock");
+            match sessions.get(&key) {
+                Some(session) if session.is_usable() => return Ok(session.token.clone()),
+                Some(_) => {
+                    sessions.remove(&key);
+                }
+                None => {}
+            }
+        }
+
+        let lease = client.login(&self.auth).await?;
+        if self.renew_token && lease.renewable && lease.lease_duration > 0 {
+            let lease_duration = Duration::from_secs(lease.lease_duration);
+            let expires_at = Arc::new(Mutex::new(Instant::now() + lease_duration));
+            let renewal = tokio::spawn(client.clone().renew_periodically(
+                lease.client_token.clone(),
+                lease_duration,
+                Arc::clone(&expires_at),
+            ));
+            SESSIONS.lock().expect("poisoned lock").insert(
+                key,
+                Session {
+                    token: lease.client_token.clone(),
+                    expires_at,
+                    renewal,
+                },
+            );
+        }
+        Ok(lease.client_token)
+    }
+}
+
+impl SecretBackend for HashicorpVaultBackend {
+    async fn retrieve(
+        &mut self,
+        secret_keys: HashSet<String>,
+        _: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = VaultClient {
+            client: HttpClient::new(tls_settings, &ProxyConfig::from_env())?,
+            endpoint: self.endpoint.clone(),
+            namespace: self.namespace.clone(),
+        };
+        let token = self.token(&client).await?;
+
+        let mut path = format!(
+            "{}/data/{}",
+            self.mount.trim_matches('/'),
+            self.path.trim_matches('/')
+        );
+        if let Some(version) = self.version {
+            path.push_str(&format!("?version={version}"));
+        }
+        let response: KvResponse = client.call(Method::GET, &path, Some(&token), None).await?;
+
+        let mut secrets = HashMap::new();
+        for k in secret_keys.into_iter() {
+            let secret = match response.data.data.get(&k) {
+                Some(serde_json::Value::String(secret)) => secret.clone(),
+                Some(serde_json::Value::Number(secret)) => secret.to_string(),
+                Some(serde_json::Value::Bool(secret)) => secret.to_string(),
+                Some(_) => {
+                    return Err(format!(
+                        "value for key '{}' in secret at path '{}' is not a string",
+                        k, &self.path
+                    )
+                    .into());
+                }
+                None => {
+                    return Err(format!(
+                        "key '{}' in secret at path '{}' does not exist",
+                        k, &self.path
+                    )
+                    .into());
+                }
+            };
+            if secret.is_empty() {
+                return Err(format!(
+                    "value for key '{}' in secret at path '{}' was empty",
+                    k, &self.path
+                )
+                .into());
+            }
+            secrets.insert(k, secret);
+        }
+        Ok(secrets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{body_json, header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<HashicorpVaultBackend>();
+    }
+
+    fn backend(endpoint: String, auth: VaultAuth) -> HashicorpVaultBackend {
+        HashicorpVaultBackend {
+            endpoint,
+            namespace: None,
+            mount: default_mount(),
+            path: "vector/credentials".to_string(),
+            version: None,
+            auth,
+            renew_token: false,
+            tls: None,
+        }
+    }
+
+    async fn mount_secret(server: &MockServer, token: &str) {
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/vector/credentials"))
+            .and(header("X-Vault-Token", token))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "data": {"username": "vector", "password": "hunter2", "port": 5432, "empty": ""},
+                    "metadata": {"version": 3}
+                }
+            })))
+            .mount(server)
+            .await;
+    }
+
+    fn keys(keys: &[&str]) -> HashSet<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    async fn retrieve(
+        backend: &mut HashicorpVaultBackend,
+        secret_keys: HashSet<String>,
+    ) -> crate::Result<HashMap<String, String>> {
+        let (_tx, mut rx) = tokio::sync::broadcast::channel(1);
+        backend.retrieve(secret_keys, &mut rx).await
+    }
+
+    #[tokio::test]
+    async fn retrieves_secrets_with_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/auth/token/lookup-self"))
+            .and(header("X-Vault-Token", "s.token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"ttl": 0, "renewable": false}
+            })))
+            .mount(&server)
+            .await;
+        mount_secret(&server, "s.token").await;
+
+        let mut backend = backend(
+            server.uri(),
+            VaultAuth::Token {
+                token: "s.token".to_string().into(),
+            },
+        );
+        let secrets = retrieve(&mut backend, keys(&["username", "password", "port"]))
+            .await
+            .unwrap();
+
+        assert_eq!(secrets["username"], "vector");
+        assert_eq!(secrets["password"], "hunter2");
+        assert_eq!(secrets["port"], "5432");
+    }
+
+    #[tokio::test]
+    async fn retrieves_secrets_with_approle() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/auth/approle/login"))
+            .and(body_json(
+                serde_json::json!({"role_id": "role", "secret_id": "secret"}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "auth": {"client_token": "s.approle", "lease_duration": 3600, "renewable": true}
+            })))
+            .mount(&server)
+            .await;
+        mount_secret(&server, "s.approle").await;
+
+        let mut backend = backend(
+            server.uri(),
+            VaultAuth::AppRole {
+                role_id: "role".to_string(),
+                secret_id: "secret".to_string().into(),
+                mount: default_approle_mount(),
+            },
+        );
+        let secrets = retrieve(&mut backend, keys(&["username"])).await.unwrap();
+
+        assert_eq!(secrets["username"], "vector");
+    }
+
+    #[tokio::test]
+    async fn reuses_renewed_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/auth/approle/login"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "auth": {"client_token": "s.renewed", "lease_duration": 3600, "renewable": true}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        mount_secret(&server, "s.renewed").await;
+
+        let mut backend = backend(
+            server.uri(),
+            VaultAuth::AppRole {
+                role_id: "reused-role".to_string(),
+                secret_id: "secret".to_string().into(),
+                mount: default_approle_mount(),
+            },
+        );
+        backend.renew_token = true;
+        retrieve(&mut backend, keys(&["username"])).await.unwrap();
+        retrieve(&mut backend, keys(&["password"])).await.unwrap();
+
+        SESSIONS
+            .lock()
+            .unwrap()
+            .remove(&backend.session_key())
+            .expect("token is kept alive");
+    }
+
+    #[tokio::test]
+    async fn resolves_secret_version() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/auth/token/lookup-self"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"ttl": 0, "renewable": false}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/vector/credentials"))
+            .and(query_param("version", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"data": {"username": "previous"}}
+            })))
+            .mount(&server)
+            .await;
+
+        let mut backend = backend(
+            server.uri(),
+            VaultAuth::Token {
+                token: "s.token".to_string().into(),
+            },
+        );
+        backend.version = Some(2);
+        let secrets = retrieve(&mut backend, keys(&["username"])).await.unwrap();
+
+        assert_eq!(secrets["username"], "previous");
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_and_empty_keys() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/auth/token/lookup-self"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"ttl": 0, "renewable": false}
+            })))
+            .mount(&server)
+            .await;
+        mount_secret(&server, "s.token").await;
+
+        let mut backend = backend(
+            server.uri(),
+            VaultAuth::Token {
+                token: "s.token".to_string().into(),
+            },
+        );
+
+        let error = retrieve(&mut backend, keys(&["missing"]))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("does not exist"), "{error}");
+
+        let error = retrieve(&mut backend, keys(&["empty"])).await.unwrap_err();
+        assert!(error.to_string().contains("was empty"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn reports_vault_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/auth/token/lookup-self"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .set_body_json(serde_json::json!({"errors": ["permission denied"]})),
+            )
+            .mount(&server)
+            .await;
+
+        let mut backend = backend(
+            server.uri(),
+            VaultAuth::Token {
+                token: "s.revoked".to_string().into(),
+            },
+        );
+        let error = retrieve(&mut backend, keys(&["username"]))
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("permission denied"), "{error}");
+    }
+}
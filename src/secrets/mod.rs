@@ -8,7 +8,11 @@ use crate::{config::SecretBackend, signal};
 
 #[cfg(feature = "secrets-aws-secrets-manager")]
 mod aws_secrets_manager;
+#[cfg(feature = "secrets-aws-ssm-parameter-store")]
+mod aws_ssm_parameter_store;
 mod exec;
+#[cfg(feature = "secrets-hashicorp-vault")]
+mod hashicorp_vault;
 mod test;
 
 /// Configurable secret backends in Vector.
@@ -25,6 +29,14 @@ pub enum SecretBackends {
     #[cfg(feature = "secrets-aws-secrets-manager")]
     AwsSecretsManager(aws_secrets_manager::AwsSecretsManagerBackend),
 
+    /// AWS Systems Manager Parameter Store.
+    #[cfg(feature = "secrets-aws-ssm-parameter-store")]
+    AwsSsmParameterStore(aws_ssm_parameter_store::AwsSsmParameterStoreBackend),
+
+    /// HashiCorp Vault.
+    #[cfg(feature = "secrets-hashicorp-vault")]
+    HashicorpVault(hashicorp_vault::HashicorpVaultBackend),
+
     /// Test.
     #[configurable(metadata(docs::hidden))]
     Test(test::TestBackend),
@@ -37,6 +49,10 @@ impl NamedComponent for SecretBackends {
             Self::Exec(config) => config.get_component_name(),
             #[cfg(feature = "secrets-aws-secrets-manager")]
             Self::AwsSecretsManager(config) => config.get_component_name(),
+            #[cfg(feature = "secrets-aws-ssm-parameter-store")]
+            Self::AwsSsmParameterStore(config) => config.get_component_name(),
+            #[cfg(feature = "secrets-hashicorp-vault")]
+            Self::HashicorpVault(config) => config.get_component_name(),
             Self::Test(config) => config.get_component_name(),
         }
     }
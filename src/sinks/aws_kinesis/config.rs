@@ -27,9 +27,13 @@ use super::{
 pub struct KinesisSinkBaseConfig {
     /// The [stream name][stream_name] of the target Kinesis Firehose delivery stream.
     ///
+    /// Events are batched by the stream name rendered for them, so that a single sink can send
+    /// events to several streams.
+    ///
     /// [stream_name]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/logs/Working-with-log-groups-and-streams.html
     #[configurable(metadata(docs::examples = "my-stream"))]
-    pub stream_name: String,
+    #[configurable(metadata(docs::examples = "logs-{{ tenant }}"))]
+    pub stream_name: Template,
 
     #[serde(flatten)]
     #[configurable(derived)]
@@ -108,7 +112,6 @@ where
         .settings::<RT, BatchKinesisRequest<RR>>(request_limits, retry_logic)
         .service(KinesisService::<C, R, E> {
             client,
-            stream_name: config.stream_name.clone(),
             region,
             _phantom_t: PhantomData,
             _phantom_e: PhantomData,
@@ -128,6 +131,7 @@ where
         batch_settings,
         service,
         request_builder,
+        stream_name: config.stream_name.clone(),
         partition_key_field,
         _phantom: PhantomData,
     };
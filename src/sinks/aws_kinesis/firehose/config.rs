@@ -74,7 +74,11 @@ pub struct KinesisFirehoseSinkConfig {
 
 impl KinesisFirehoseSinkConfig {
     async fn healthcheck(self, client: KinesisClient) -> crate::Result<()> {
-        let stream_name = self.base.stream_name;
+        // The streams of a templated stream name are only known once events are rendered.
+        if self.base.stream_name.is_dynamic() {
+            return Ok(());
+        }
+        let stream_name = self.base.stream_name.get_ref().to_owned();
 
         let result = client
             .describe_delivery_stream()
@@ -49,7 +49,7 @@ async fn firehose_put_records_without_partition_key() {
     let region = RegionOrEndpoint::with_both("us-east-1", kinesis_address().as_str());
 
     let base = KinesisSinkBaseConfig {
-        stream_name: stream.clone(),
+        stream_name: Template::try_from(stream.as_str()).unwrap(),
         region: region.clone(),
         encoding: JsonSerializerConfig::default().into(), // required for ES destination w/ localstack
         compression: Compression::None,
@@ -150,7 +150,7 @@ async fn firehose_put_records_with_partition_key() {
     let partition_key = ConfigValuePath::try_from("partition_key".to_string()).unwrap();
 
     let base = KinesisSinkBaseConfig {
-        stream_name: stream.clone(),
+        stream_name: Template::try_from(stream.as_str()).unwrap(),
         region: region.clone(),
         encoding: JsonSerializerConfig::default().into(), // required for ES destination w/ localstack
         compression: Compression::None,
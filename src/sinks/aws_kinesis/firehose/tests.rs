@@ -6,12 +6,17 @@ use super::*;
 use crate::{
     aws::RegionOrEndpoint,
     config::{SinkConfig, SinkContext},
+    event::LogEvent,
     sinks::{
-        aws_kinesis::firehose::config::{
-            KinesisFirehoseDefaultBatchSettings, MAX_PAYLOAD_EVENTS, MAX_PAYLOAD_SIZE,
+        aws_kinesis::{
+            firehose::config::{
+                KinesisFirehoseDefaultBatchSettings, MAX_PAYLOAD_EVENTS, MAX_PAYLOAD_SIZE,
+            },
+            sink::process_log,
         },
         util::{batch::BatchError, BatchConfig, Compression},
     },
+    template::Template,
 };
 
 #[test]
@@ -26,7 +31,7 @@ async fn check_batch_size() {
     batch.max_bytes = Some(MAX_PAYLOAD_SIZE + 1);
 
     let base = KinesisSinkBaseConfig {
-        stream_name: String::from("test"),
+        stream_name: Template::try_from("test").unwrap(),
         region: RegionOrEndpoint::with_both("us-east-1", "http://localhost:4566"),
         encoding: JsonSerializerConfig::default().into(),
         compression: Compression::None,
@@ -57,7 +62,7 @@ async fn check_batch_events() {
     batch.max_events = Some(MAX_PAYLOAD_EVENTS + 1);
 
     let base = KinesisSinkBaseConfig {
-        stream_name: String::from("test"),
+        stream_name: Template::try_from("test").unwrap(),
         region: RegionOrEndpoint::with_both("us-east-1", "http://localhost:4566"),
         encoding: JsonSerializerConfig::default().into(),
         compression: Compression::None,
@@ -81,3 +86,16 @@ async fn check_batch_events() {
         }))
     );
 }
+
+#[test]
+fn renders_stream_name_per_event() {
+    let stream_name = Template::try_from("logs-{{ tenant }}").unwrap();
+
+    let mut log = LogEvent::from("message");
+    log.insert("tenant", "acme");
+    let processed = process_log(log, &stream_name, None).unwrap();
+    assert_eq!(processed.metadata.stream_name, "logs-acme");
+
+    // Events the stream name can't be rendered for are dropped.
+    assert!(process_log(LogEvent::from("message"), &stream_name, None).is_none());
+}
@@ -27,6 +27,7 @@ pub struct KinesisRequestBuilder<R> {
 pub struct KinesisMetadata {
     pub finalizers: EventFinalizers,
     pub partition_key: String,
+    pub stream_name: String,
 }
 
 #[derive(Clone)]
@@ -104,6 +105,7 @@ where
         let kinesis_metadata = KinesisMetadata {
             finalizers: processed_event.event.take_finalizers(),
             partition_key: processed_event.metadata.partition_key,
+            stream_name: processed_event.metadata.stream_name,
         };
         let event = Event::from(processed_event.event);
         let builder = RequestMetadataBuilder::from_event(&event);
@@ -124,6 +126,7 @@ where
         KinesisRequest {
             key: KinesisKey {
                 partition_key: kinesis_metadata.partition_key.clone(),
+                stream_name: kinesis_metadata.stream_name,
             },
             record,
             finalizers: kinesis_metadata.finalizers,
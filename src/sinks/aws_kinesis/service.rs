@@ -14,7 +14,6 @@ use crate::{event::EventStatus, sinks::prelude::*};
 
 pub struct KinesisService<C, T, E> {
     pub client: C,
-    pub stream_name: String,
     pub region: Option<Region>,
     pub _phantom_t: PhantomData<T>,
     pub _phantom_e: PhantomData<E>,
@@ -27,7 +26,6 @@ where
     fn clone(&self) -> Self {
         Self {
             client: self.client.clone(),
-            stream_name: self.stream_name.clone(),
             region: self.region.clone(),
             _phantom_e: self._phantom_e,
             _phantom_t: self._phantom_t,
@@ -78,7 +76,7 @@ where
             .collect();
 
         let client = self.client.clone();
-        let stream_name = self.stream_name.clone();
+        let stream_name = requests.stream_name;
 
         Box::pin(async move {
             client.send(records, stream_name).await.map(|mut r| {
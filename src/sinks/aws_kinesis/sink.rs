@@ -5,7 +5,9 @@ use vector_lib::lookup::lookup_v2::ConfigValuePath;
 use vrl::path::PathPrefix;
 
 use crate::{
-    internal_events::{AwsKinesisStreamNoPartitionKeyError, SinkRequestBuildError},
+    internal_events::{
+        AwsKinesisStreamNoPartitionKeyError, SinkRequestBuildError, TemplateRenderingError,
+    },
     sinks::{
         prelude::*,
         util::{processed_event::ProcessedEvent, StreamSink},
@@ -22,6 +24,7 @@ pub type KinesisProcessedEvent = ProcessedEvent<LogEvent, KinesisKey>;
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct KinesisKey {
     pub partition_key: String,
+    pub stream_name: String,
 }
 
 #[derive(Clone)]
@@ -29,6 +32,7 @@ pub struct KinesisSink<S, R> {
     pub batch_settings: BatcherSettings,
     pub service: S,
     pub request_builder: KinesisRequestBuilder<R>,
+    pub stream_name: Template,
     pub partition_key_field: Option<ConfigValuePath>,
     pub _phantom: PhantomData<R>,
 }
@@ -48,7 +52,8 @@ where
             .filter_map(|event| {
                 // Panic: This sink only accepts Logs, so this should never panic
                 let log = event.into_log();
-                let processed = process_log(log, self.partition_key_field.as_ref());
+                let processed =
+                    process_log(log, &self.stream_name, self.partition_key_field.as_ref());
 
                 future::ready(processed)
             })
@@ -65,12 +70,18 @@ where
                     Ok(req) => Some(req),
                 }
             })
-            .batched(batch_settings.as_byte_size_config())
-            .map(|events| {
+            .batched_partitioned(KinesisStreamPartitioner, || {
+                batch_settings.as_byte_size_config()
+            })
+            .map(|(stream_name, events)| {
                 let metadata = RequestMetadata::from_batch(
                     events.iter().map(|req| req.get_metadata().clone()),
                 );
-                BatchKinesisRequest { events, metadata }
+                BatchKinesisRequest {
+                    events,
+                    stream_name,
+                    metadata,
+                }
             })
             .into_driver(self.service)
             .run()
@@ -92,16 +103,42 @@ where
     }
 }
 
+/// Batches the requests by the stream they are sent to.
+struct KinesisStreamPartitioner;
+
+impl<R> Partitioner for KinesisStreamPartitioner
+where
+    R: Record,
+{
+    type Item = KinesisRequest<R>;
+    type Key = String;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        item.key.stream_name.clone()
+    }
+}
+
 /// Returns a `KinesisProcessedEvent` containing the unmodified log event + metadata consisting of
-/// the partition key. The partition key is either generated from the provided partition_key_field
-/// or is generated randomly.
+/// the stream name rendered for the event and the partition key. The partition key is either
+/// generated from the provided partition_key_field or is generated randomly.
 ///
-/// If the provided partition_key_field was not found in the log, `Error` `EventsDropped` internal
-/// events are emitted and None is returned.
+/// If the stream name can't be rendered, or the provided partition_key_field was not found in the
+/// log, `Error` `EventsDropped` internal events are emitted and None is returned.
 pub(crate) fn process_log(
     log: LogEvent,
+    stream_name: &Template,
     partition_key_field: Option<&ConfigValuePath>,
 ) -> Option<KinesisProcessedEvent> {
+    let stream_name = stream_name
+        .render_string(&log)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some("stream_name"),
+                drop_event: true,
+            });
+        })
+        .ok()?;
     let partition_key = if let Some(partition_key_field) = partition_key_field {
         if let Some(v) = log.get((PathPrefix::Event, partition_key_field)) {
             v.to_string_lossy()
@@ -122,7 +159,10 @@ pub(crate) fn process_log(
 
     Some(KinesisProcessedEvent {
         event: log,
-        metadata: KinesisKey { partition_key },
+        metadata: KinesisKey {
+            partition_key,
+            stream_name,
+        },
     })
 }
 
@@ -140,6 +180,7 @@ where
     R: Record + Clone,
 {
     pub events: Vec<KinesisRequest<R>>,
+    pub stream_name: String,
     metadata: RequestMetadata,
 }
 
@@ -150,6 +191,7 @@ where
     fn clone(&self) -> Self {
         Self {
             events: self.events.to_vec(),
+            stream_name: self.stream_name.clone(),
             metadata: self.metadata.clone(),
         }
     }
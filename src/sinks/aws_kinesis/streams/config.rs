@@ -7,7 +7,8 @@ use vector_lib::configurable::{component::GenerateConfig, configurable_component
 
 use crate::sinks::util::retries::RetryAction;
 use crate::{
-    aws::{create_client, is_retriable_error, ClientBuilder},
+    aws::{create_client, is_retriable_error},
+    common::kinesis::KinesisClientBuilder,
     config::{AcknowledgementsConfig, Input, ProxyConfig, SinkConfig, SinkContext},
     sinks::{
         util::{retries::RetryLogic, BatchConfig, SinkBatchSettings},
@@ -32,16 +33,6 @@ enum HealthcheckError {
     StreamNamesMismatch { name: String, stream_name: String },
 }
 
-pub struct KinesisClientBuilder;
-
-impl ClientBuilder for KinesisClientBuilder {
-    type Client = KinesisClient;
-
-    fn build(config: &aws_types::SdkConfig) -> Self::Client {
-        KinesisClient::new(config)
-    }
-}
-
 pub const MAX_PAYLOAD_SIZE: usize = 5_000_000;
 pub const MAX_PAYLOAD_EVENTS: usize = 500;
 
@@ -71,7 +62,11 @@ pub struct KinesisStreamsSinkConfig {
 
 impl KinesisStreamsSinkConfig {
     async fn healthcheck(self, client: KinesisClient) -> crate::Result<()> {
-        let stream_name = self.base.stream_name;
+        // The streams of a templated stream name are only known once events are rendered.
+        if self.base.stream_name.is_dynamic() {
+            return Ok(());
+        }
+        let stream_name = self.base.stream_name.get_ref().to_owned();
 
         let describe_result = client
             .describe_stream()
@@ -9,11 +9,13 @@ use tokio::time::{sleep, Duration};
 use vector_lib::codecs::TextSerializerConfig;
 use vector_lib::lookup::lookup_v2::ConfigValuePath;
 
-use super::{config::KinesisClientBuilder, *};
+use super::*;
 use crate::{
     aws::{create_client, AwsAuthentication, RegionOrEndpoint},
+    common::kinesis::KinesisClientBuilder,
     config::{ProxyConfig, SinkConfig, SinkContext},
     sinks::util::{BatchConfig, Compression},
+    template::Template,
     test_util::{
         components::{run_and_assert_sink_compliance, AWS_SINK_TAGS},
         random_lines_with_stream, random_string,
@@ -38,7 +40,7 @@ async fn kinesis_put_records_with_partition_key() {
     let partition_key = ConfigValuePath::try_from("partition_key".to_string()).unwrap();
 
     let base = KinesisSinkBaseConfig {
-        stream_name: stream.clone(),
+        stream_name: Template::try_from(stream.as_str()).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", kinesis_address().as_str()),
         encoding: TextSerializerConfig::default().into(),
         compression: Compression::None,
@@ -98,7 +100,7 @@ async fn kinesis_put_records_without_partition_key() {
     batch.max_events = Some(2);
 
     let base = KinesisSinkBaseConfig {
-        stream_name: stream.clone(),
+        stream_name: Template::try_from(stream.as_str()).unwrap(),
         region: RegionOrEndpoint::with_both("us-east-1", kinesis_address().as_str()),
         encoding: TextSerializerConfig::default().into(),
         compression: Compression::None,
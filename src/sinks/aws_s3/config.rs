@@ -2,7 +2,7 @@ use aws_sdk_s3::Client as S3Client;
 use tower::ServiceBuilder;
 use vector_lib::codecs::{
     encoding::{Framer, FramingConfig},
-    TextSerializerConfig,
+    BatchSerializerConfig, TextSerializerConfig,
 };
 use vector_lib::configurable::configurable_component;
 use vector_lib::sink::VectorSink;
@@ -11,13 +11,13 @@ use vector_lib::TimeZone;
 use super::sink::S3RequestOptions;
 use crate::{
     aws::{AwsAuthentication, RegionOrEndpoint},
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType},
+    codecs::{BatchEncoder, Encoder, EncoderKind, EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, GenerateConfig, Input, ProxyConfig, SinkConfig, SinkContext},
     sinks::{
         s3_common::{
             self,
             config::{S3Options, S3RetryLogic},
-            partitioner::S3KeyPartitioner,
+            partitioner::{validate_hive_key_prefix, S3KeyPartitioner},
             service::S3Service,
             sink::S3Sink,
         },
@@ -57,6 +57,21 @@ pub struct S3SinkConfig {
     #[configurable(metadata(docs::examples = "application_id={{ application_id }}/date=%F"))]
     pub key_prefix: String,
 
+    /// Whether or not `key_prefix` is a template of Hive-style partitions, such as
+    /// `dt=%Y-%m-%d/hour=%H/`.
+    ///
+    /// When enabled, `key_prefix` is validated when Vector starts: it must end in `/`, and after any
+    /// leading path segments, each of its segments must be a `name=value` partition with a unique
+    /// name made of letters, digits, and underscores. The values rendered from the fields of the
+    /// events are escaped as Hive escapes partition values, so that they can't create additional
+    /// partitions.
+    ///
+    /// Events are batched per partition, so `batch.max_bytes` and `batch.timeout_secs` set the target
+    /// size of the objects written to each partition.
+    #[serde(default)]
+    #[configurable(metadata(docs::human_name = "Hive-style Partitioning"))]
+    pub hive_partitioning: bool,
+
     /// The timestamp format for the time component of the object key.
     ///
     /// By default, object keys are appended with a timestamp that reflects when the objects are
@@ -90,7 +105,8 @@ pub struct S3SinkConfig {
 
     /// The filename extension to use in the object key.
     ///
-    /// This overrides setting the extension based on the configured `compression`.
+    /// This overrides setting the extension based on the configured `batch_encoding` or
+    /// `compression`.
     #[configurable(metadata(docs::examples = "json"))]
     pub filename_extension: Option<String>,
 
@@ -103,6 +119,15 @@ pub struct S3SinkConfig {
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
+    /// Encodes each batch of events as a single object, such as a Parquet file, instead of
+    /// encoding events one by one.
+    ///
+    /// When set, the `encoding.codec` and `framing` options are ignored, while the other
+    /// `encoding` options still apply. Batch codecs compress their output themselves, so
+    /// `compression` must be set to `none`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_encoding: Option<BatchSerializerConfig>,
+
     /// Compression configuration.
     ///
     /// All compression algorithms use the default compression level unless otherwise specified.
@@ -154,12 +179,14 @@ impl GenerateConfig for S3SinkConfig {
         toml::Value::try_from(Self {
             bucket: "".to_owned(),
             key_prefix: default_key_prefix(),
+            hive_partitioning: false,
             filename_time_format: default_filename_time_format(),
             filename_append_uuid: true,
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
@@ -183,7 +210,10 @@ impl SinkConfig for S3SinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type())
+        match &self.batch_encoding {
+            Some(batch_encoding) => Input::new(batch_encoding.input_type()),
+            None => Input::new(self.encoding.config().1.input_type()),
+        }
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -214,6 +244,9 @@ impl S3SinkConfig {
         // Configure our partitioning/batching.
         let batch_settings = self.batch.into_batcher_settings()?;
 
+        if self.hive_partitioning {
+            validate_hive_key_prefix(&self.key_prefix)?;
+        }
         let key_prefix = Template::try_from(self.key_prefix.clone())?.with_tz_offset(offset);
 
         let ssekms_key_id = self
@@ -224,11 +257,10 @@ impl S3SinkConfig {
             .map(|ssekms_key_id| Template::try_from(ssekms_key_id.as_str()))
             .transpose()?;
 
-        let partitioner = S3KeyPartitioner::new(key_prefix, ssekms_key_id);
+        let partitioner = S3KeyPartitioner::new(key_prefix, ssekms_key_id, self.hive_partitioning);
 
         let transformer = self.encoding.transformer();
-        let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let encoder = self.build_encoder()?;
 
         let request_options = S3RequestOptions {
             bucket: self.bucket.clone(),
@@ -246,6 +278,21 @@ impl S3SinkConfig {
         Ok(VectorSink::from_event_streamsink(sink))
     }
 
+    fn build_encoder(&self) -> crate::Result<EncoderKind> {
+        match &self.batch_encoding {
+            Some(batch_encoding) => {
+                if self.compression != Compression::None {
+                    return Err("`compression` must be `none` when `batch_encoding` is set".into());
+                }
+                Ok(BatchEncoder::new(batch_encoding.build()?).into())
+            }
+            None => {
+                let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
+                Ok(Encoder::<Framer>::new(framer, serializer).into())
+            }
+        }
+    }
+
     pub fn build_healthcheck(&self, client: S3Client) -> crate::Result<Healthcheck> {
         s3_common::config::build_healthcheck(self.bucket.clone(), client)
     }
@@ -257,10 +304,32 @@ impl S3SinkConfig {
 
 #[cfg(test)]
 mod tests {
-    use super::S3SinkConfig;
+    use super::*;
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<S3SinkConfig>();
     }
+
+    #[test]
+    fn batch_encoding_requires_no_compression() {
+        let config: S3SinkConfig = toml::from_str(
+            r#"
+            bucket = "my-bucket"
+            encoding.codec = "json"
+            batch_encoding.codec = "parquet"
+            "#,
+        )
+        .unwrap();
+        assert!(config.build_encoder().is_err());
+
+        let config = S3SinkConfig {
+            compression: Compression::None,
+            ..config
+        };
+        assert!(matches!(
+            config.build_encoder().unwrap(),
+            EncoderKind::Batch(_)
+        ));
+    }
 }
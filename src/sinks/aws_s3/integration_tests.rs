@@ -425,12 +425,14 @@ async fn s3_flush_on_exhaustion() {
         S3SinkConfig {
             bucket: bucket.to_string(),
             key_prefix: random_string(10) + "/date=%F",
+            hive_partitioning: false,
             filename_time_format: default_filename_time_format(),
             filename_append_uuid: true,
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::with_both("us-east-1", s3_address()),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
             compression: Compression::None,
             batch,
             request: TowerRequestConfig::default(),
@@ -509,12 +511,14 @@ fn config(bucket: &str, batch_size: usize) -> S3SinkConfig {
     S3SinkConfig {
         bucket: bucket.to_string(),
         key_prefix: random_string(10) + "/date=%F",
+        hive_partitioning: false,
         filename_time_format: default_filename_time_format(),
         filename_append_uuid: true,
         filename_extension: None,
         options: S3Options::default(),
         region: RegionOrEndpoint::with_both("us-east-1", s3_address()),
         encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+        batch_encoding: None,
         compression: Compression::None,
         batch,
         request: TowerRequestConfig::default(),
@@ -0,0 +1,158 @@
+//! Configuration for the `azure_event_hubs` sink.
+
+use super::{
+    request_builder::EventHubsRequestBuilder,
+    service::{EventHubsRetryLogic, EventHubsService},
+    sink::EventHubsSink,
+};
+use crate::{
+    azure_event_hubs::{EventHubsConnectionConfig, EventHubsConnector},
+    sinks::prelude::*,
+};
+
+/// The largest batch accepted by the Basic and Standard tiers of Event Hubs.
+///
+/// Batches are split further if the Event Hub announces a smaller maximum message size.
+const MAX_BATCH_SIZE: usize = 1_000_000;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AzureEventHubsDefaultBatchSettings;
+
+impl SinkBatchSettings for AzureEventHubsDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(MAX_BATCH_SIZE);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `azure_event_hubs` sink.
+#[configurable_component(sink(
+    "azure_event_hubs",
+    "Publish observability events to Azure Event Hubs over AMQP."
+))]
+#[derive(Clone, Debug)]
+pub struct AzureEventHubsSinkConfig {
+    #[serde(flatten)]
+    #[configurable(derived)]
+    pub connection: EventHubsConnectionConfig,
+
+    /// The partition key of the events.
+    ///
+    /// Events with the same partition key are sent to the same partition of the Event Hub, in
+    /// order. Events are distributed across partitions by Event Hubs when unset, or when the key
+    /// renders to an empty string.
+    #[configurable(metadata(docs::examples = "{{ host }}"))]
+    #[configurable(metadata(docs::examples = "{{ tenant_id }}-{{ service }}"))]
+    pub partition_key: Option<Template>,
+
+    #[configurable(derived)]
+    pub encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<AzureEventHubsDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for AzureEventHubsSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"connection_string = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=RootManageSharedAccessKey;SharedAccessKey=${EVENT_HUBS_KEY};EntityPath=myhub"
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_event_hubs")]
+impl SinkConfig for AzureEventHubsSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let connector = EventHubsConnector::new(&self.connection)?;
+
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_BATCH_SIZE)?
+            .into_batcher_settings()?;
+
+        let transformer = self.encoding.transformer();
+        let serializer = self.encoding.build()?;
+        let request_builder =
+            EventHubsRequestBuilder::new(transformer, Encoder::<()>::new(serializer));
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), EventHubsRetryLogic)
+            .service(EventHubsService::new(connector.clone()));
+
+        let sink = EventHubsSink::new(
+            batch_settings,
+            service,
+            self.partition_key.clone(),
+            request_builder,
+        );
+
+        let healthcheck = Box::pin(healthcheck(connector));
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type())
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Checks that a connection authorized to send to the Event Hub can be opened.
+async fn healthcheck(connector: EventHubsConnector) -> crate::Result<()> {
+    let mut connection = connector.connect().await?;
+    let link = connection
+        .connection
+        .attach_sender("vector-healthcheck", connector.event_hub_name())
+        .await?;
+    connection.connection.detach(link).await?;
+    connection.connection.close().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureEventHubsSinkConfig>();
+    }
+
+    #[test]
+    fn rejects_ambiguous_authentication() {
+        let config: AzureEventHubsSinkConfig = toml::from_str(
+            r#"connection_string = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=send;SharedAccessKey=secret"
+            namespace = "mynamespace.servicebus.windows.net"
+            event_hub_name = "myhub"
+            encoding.codec = "json""#,
+        )
+        .unwrap();
+
+        let error = EventHubsConnector::new(&config.connection)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "Only one of `connection_string` or `namespace` can be set"
+        );
+    }
+}
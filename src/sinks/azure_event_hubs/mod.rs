@@ -0,0 +1,12 @@
+//! The `azure_event_hubs` [`vector_lib::sink::VectorSink`].
+//!
+//! Events are sent to an Event Hub over AMQP 1.0, rather than through its Kafka endpoint. Each
+//! batch of events sharing a partition key is sent as one or more Event Hubs batch messages,
+//! split to fit the maximum message size the Event Hub announces when the sender link attaches.
+
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::AzureEventHubsSinkConfig;
@@ -0,0 +1,147 @@
+//! Request builder for the `azure_event_hubs` sink.
+
+use std::io;
+
+use bytes::{Bytes, BytesMut};
+use tokio_util::codec::Encoder as _;
+
+use super::service::EventHubsRequest;
+use crate::{
+    azure_event_hubs::codec::{encode_data_section, Message},
+    sinks::{prelude::*, util::encoding::Encoder as SinkEncoder},
+};
+
+/// Encodes a batch of events as a sequence of `data` sections, each holding an AMQP message
+/// wrapping a single encoded event, which is the body of an Event Hubs batch message.
+#[derive(Clone)]
+pub(super) struct EventHubsEncoder {
+    transformer: Transformer,
+    encoder: Encoder<()>,
+}
+
+impl SinkEncoder<Vec<Event>> for EventHubsEncoder {
+    fn encode_input(
+        &self,
+        events: Vec<Event>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut encoder = self.encoder.clone();
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        let mut body = BytesMut::new();
+
+        for mut event in events {
+            self.transformer.transform(&mut event);
+            byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+
+            let mut bytes = BytesMut::new();
+            encoder
+                .encode(event, &mut bytes)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+            let message = Message::with_data(bytes.freeze()).to_bytes();
+            encode_data_section(&message, &mut body);
+        }
+
+        writer.write_all(&body)?;
+        Ok((body.len(), byte_size))
+    }
+}
+
+pub(super) struct EventHubsRequestBuilder {
+    encoder: EventHubsEncoder,
+}
+
+impl EventHubsRequestBuilder {
+    pub(super) const fn new(transformer: Transformer, encoder: Encoder<()>) -> Self {
+        Self {
+            encoder: EventHubsEncoder {
+                transformer,
+                encoder,
+            },
+        }
+    }
+}
+
+impl RequestBuilder<(Option<String>, Vec<Event>)> for EventHubsRequestBuilder {
+    type Metadata = (Option<String>, EventFinalizers);
+    type Events = Vec<Event>;
+    type Encoder = EventHubsEncoder;
+    type Payload = Bytes;
+    type Request = EventHubsRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (Option<String>, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (partition_key, mut events) = input;
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        ((partition_key, finalizers), builder, events)
+    }
+
+    fn build_request(
+        &self,
+        (partition_key, finalizers): Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        EventHubsRequest {
+            partition_key,
+            sections: payload.into_payload(),
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::codecs::TextSerializerConfig;
+
+    use super::*;
+    use crate::azure_event_hubs::codec::{split_sections, MessageBody};
+
+    #[test]
+    fn wraps_each_event_in_a_message() {
+        let builder = EventHubsRequestBuilder::new(
+            Transformer::default(),
+            Encoder::<()>::new(TextSerializerConfig::default().build().into()),
+        );
+        let events = vec![Event::from("first"), Event::from("second")];
+
+        let mut payload = Vec::new();
+        let (written, _) = builder
+            .encoder()
+            .encode_input(events, &mut payload)
+            .unwrap();
+        assert_eq!(written, payload.len());
+
+        let bodies = split_sections(Bytes::from(payload))
+            .unwrap()
+            .into_iter()
+            .map(|section| {
+                let section = Message::decode(section).unwrap();
+                let MessageBody::Data(data) = section.body else {
+                    panic!("expected a data section");
+                };
+                Message::decode(data[0].clone()).unwrap().body
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            bodies,
+            vec![
+                MessageBody::Data(vec![Bytes::from_static(b"first")]),
+                MessageBody::Data(vec![Bytes::from_static(b"second")]),
+            ]
+        );
+    }
+}
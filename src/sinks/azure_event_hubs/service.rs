@@ -0,0 +1,359 @@
+//! Service implementation for the `azure_event_hubs` sink.
+
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use snafu::Snafu;
+
+use crate::{
+    azure_event_hubs::{
+        auth::AuthError,
+        codec::{section, split_sections, AmqpValue},
+        connection::{AmqpError, LinkHandle, Outcome, RemoteError},
+        AuthorizedConnection, EventHubsConnector,
+    },
+    sinks::prelude::*,
+};
+
+/// The message format of Event Hubs batches, whose body holds one message per `data` section.
+const BATCH_MESSAGE_FORMAT: u32 = 0x8001_3700;
+
+const PARTITION_KEY_ANNOTATION: &str = "x-opt-partition-key";
+
+/// How long idle connections are kept when the Event Hub doesn't request an idle timeout.
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A batch of events sharing a partition key, encoded as the `data` sections of a batch message.
+#[derive(Clone)]
+pub(super) struct EventHubsRequest {
+    pub(super) partition_key: Option<String>,
+    pub(super) sections: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for EventHubsRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for EventHubsRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct EventHubsResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for EventHubsResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum EventHubsSinkError {
+    #[snafu(display("{}", source))]
+    Auth { source: AuthError },
+
+    #[snafu(display("{}", source))]
+    Amqp { source: AmqpError },
+
+    #[snafu(display(
+        "Event of {} bytes exceeds the maximum message size of {} bytes",
+        size,
+        max_size
+    ))]
+    MessageTooLarge { size: usize, max_size: usize },
+
+    #[snafu(display("Events were rejected: {}", error))]
+    Rejected { error: RemoteError },
+
+    #[snafu(display("Events were released without being accepted"))]
+    Released,
+}
+
+impl From<AuthError> for EventHubsSinkError {
+    fn from(source: AuthError) -> Self {
+        Self::Auth { source }
+    }
+}
+
+impl From<AmqpError> for EventHubsSinkError {
+    fn from(source: AmqpError) -> Self {
+        Self::Amqp { source }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct EventHubsRetryLogic;
+
+impl RetryLogic for EventHubsRetryLogic {
+    type Error = EventHubsSinkError;
+    type Response = EventHubsResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            EventHubsSinkError::Auth { source } => source.is_retriable(),
+            EventHubsSinkError::Amqp { source } => source.is_retriable(),
+            EventHubsSinkError::MessageTooLarge { .. } => false,
+            // Rejected batches are only retried when the Event Hub is throttling or timed out.
+            EventHubsSinkError::Rejected { error } => matches!(
+                error.condition.as_str(),
+                "com.microsoft:server-busy"
+                    | "com.microsoft:timeout"
+                    | "amqp:resource-limit-exceeded"
+            ),
+            EventHubsSinkError::Released => true,
+        }
+    }
+}
+
+/// A connection with a sender link attached to the Event Hub.
+struct Sender {
+    connection: AuthorizedConnection,
+    link: LinkHandle,
+    last_used: Instant,
+}
+
+impl Sender {
+    /// Whether the Event Hub may have closed the connection for being idle.
+    fn is_stale(&self) -> bool {
+        let idle_timeout = self
+            .connection
+            .connection
+            .remote_idle_timeout()
+            .unwrap_or(DEFAULT_IDLE_TIMEOUT);
+        self.last_used.elapsed() >= idle_timeout / 2
+    }
+}
+
+/// Sends batches over a small pool of reusable connections.
+#[derive(Clone)]
+pub(super) struct EventHubsService {
+    connector: Arc<EventHubsConnector>,
+    idle: Arc<Mutex<Vec<Sender>>>,
+}
+
+impl EventHubsService {
+    pub(super) fn new(connector: EventHubsConnector) -> Self {
+        Self {
+            connector: Arc::new(connector),
+            idle: Arc::default(),
+        }
+    }
+
+    async fn sender(&self) -> Result<Sender, EventHubsSinkError> {
+        loop {
+            let idle = self.idle.lock().expect("poisoned lock").pop();
+            match idle {
+                Some(sender) if sender.is_stale() => continue,
+                Some(sender) => return Ok(sender),
+                None => break,
+            }
+        }
+
+        let mut connection = self.connector.connect().await?;
+        let name = format!("vector-sender-{}", uuid::Uuid::new_v4());
+        let link = connection
+            .connection
+            .attach_sender(&name, self.connector.event_hub_name())
+            .await?;
+        Ok(Sender {
+            connection,
+            link,
+            last_used: Instant::now(),
+        })
+    }
+
+    async fn send(
+        connector: &EventHubsConnector,
+        sender: &mut Sender,
+        request: &EventHubsRequest,
+    ) -> Result<(), EventHubsSinkError> {
+        connector.refresh(&mut sender.connection).await?;
+
+        let max_size = sender
+            .connection
+            .connection
+            .max_message_size(sender.link)
+            .map_or(usize::MAX, |size| {
+                usize::try_from(size).unwrap_or(usize::MAX)
+            });
+        let batches = split_batch(
+            request.partition_key.as_deref(),
+            request.sections.clone(),
+            max_size,
+        )?;
+
+        for batch in batches {
+            let outcome = sender
+                .connection
+                .connection
+                .send(sender.link, &batch, BATCH_MESSAGE_FORMAT)
+                .await?;
+            match outcome {
+                Outcome::Accepted => {}
+                Outcome::Rejected(error) => return Err(EventHubsSinkError::Rejected { error }),
+                Outcome::Released | Outcome::Modified => return Err(EventHubsSinkError::Released),
+            }
+        }
+        sender.last_used = Instant::now();
+        Ok(())
+    }
+}
+
+impl Service<EventHubsRequest> for EventHubsService {
+    type Response = EventHubsResponse;
+    type Error = EventHubsSinkError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: EventHubsRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let mut sender = service.sender().await?;
+
+            // Senders are kept when the Event Hub settled the batch, but connections are left in
+            // an unknown state by other failures.
+            let result = Self::send(&service.connector, &mut sender, &request).await;
+            match &result {
+                Ok(())
+                | Err(EventHubsSinkError::Rejected { .. } | EventHubsSinkError::Released) => {
+                    service.idle.lock().expect("poisoned lock").push(sender)
+                }
+                Err(_) => {}
+            }
+            result?;
+
+            Ok(EventHubsResponse {
+                events_byte_size: request
+                    .metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+                byte_size: request.sections.len(),
+            })
+        })
+    }
+}
+
+/// Splits the `data` sections of a request into batch messages no larger than `max_size`.
+fn split_batch(
+    partition_key: Option<&str>,
+    sections: Bytes,
+    max_size: usize,
+) -> Result<Vec<Bytes>, EventHubsSinkError> {
+    let mut header = BytesMut::new();
+    if let Some(partition_key) = partition_key {
+        AmqpValue::described(
+            section::MESSAGE_ANNOTATIONS,
+            AmqpValue::Map(vec![(
+                AmqpValue::symbol(PARTITION_KEY_ANNOTATION),
+                AmqpValue::string(partition_key),
+            )]),
+        )
+        .encode(&mut header);
+    }
+
+    let sections = split_sections(sections).map_err(AmqpError::from)?;
+    let mut batches = Vec::new();
+    let mut batch = header.clone();
+    for section in sections {
+        if header.len() + section.len() > max_size {
+            return Err(EventHubsSinkError::MessageTooLarge {
+                size: section.len(),
+                max_size,
+            });
+        }
+        if batch.len() + section.len() > max_size {
+            batches.push(std::mem::replace(&mut batch, header.clone()).freeze());
+        }
+        batch.put_slice(&section);
+    }
+    if batch.len() > header.len() {
+        batches.push(batch.freeze());
+    }
+    Ok(batches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure_event_hubs::codec::{encode_data_section, Message, MessageBody};
+
+    fn sections(messages: &[&[u8]]) -> Bytes {
+        let mut sections = BytesMut::new();
+        for message in messages {
+            encode_data_section(message, &mut sections);
+        }
+        sections.freeze()
+    }
+
+    #[test]
+    fn splits_batches_to_fit_the_maximum_message_size() {
+        let batches = split_batch(Some("host"), sections(&[&[1; 40], &[2; 40], &[3; 40]]), 120)
+            .unwrap()
+            .into_iter()
+            .map(|batch| Message::decode(batch).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(batches.len(), 2);
+        for batch in &batches {
+            assert_eq!(
+                batch.annotation(PARTITION_KEY_ANNOTATION),
+                Some(&AmqpValue::string("host"))
+            );
+        }
+        let MessageBody::Data(data) = &batches[0].body else {
+            panic!("expected data sections");
+        };
+        assert_eq!(data.len(), 2);
+    }
+
+    #[test]
+    fn rejects_oversized_events() {
+        let error = split_batch(None, sections(&[&[1; 200]]), 120).unwrap_err();
+
+        assert!(matches!(
+            error,
+            EventHubsSinkError::MessageTooLarge { max_size: 120, .. }
+        ));
+        assert!(!EventHubsRetryLogic.is_retriable_error(&error));
+    }
+
+    #[test]
+    fn retries_throttled_batches() {
+        let rejected = |condition: &str| EventHubsSinkError::Rejected {
+            error: RemoteError {
+                condition: condition.to_owned(),
+                description: None,
+            },
+        };
+
+        assert!(EventHubsRetryLogic.is_retriable_error(&rejected("com.microsoft:server-busy")));
+        assert!(!EventHubsRetryLogic.is_retriable_error(&rejected("amqp:unauthorized-access")));
+    }
+}
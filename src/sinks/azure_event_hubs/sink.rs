@@ -0,0 +1,125 @@
+//! Implementation of the `azure_event_hubs` sink.
+
+use super::{request_builder::EventHubsRequestBuilder, service::EventHubsRequest};
+use crate::sinks::prelude::*;
+
+pub(super) struct EventHubsSink<S> {
+    batch_settings: BatcherSettings,
+    service: S,
+    partition_key: Option<Template>,
+    request_builder: EventHubsRequestBuilder,
+}
+
+impl<S> EventHubsSink<S>
+where
+    S: Service<EventHubsRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    pub(super) const fn new(
+        batch_settings: BatcherSettings,
+        service: S,
+        partition_key: Option<Template>,
+        request_builder: EventHubsRequestBuilder,
+    ) -> Self {
+        Self {
+            batch_settings,
+            service,
+            partition_key,
+            request_builder,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+
+        input
+            .batched_partitioned(PartitionKeyPartitioner(self.partition_key), || {
+                batch_settings.as_byte_size_config()
+            })
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) })
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(request) => Some(request),
+                }
+            })
+            .into_driver(self.service)
+            .protocol("amqp")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for EventHubsSink<S>
+where
+    S: Service<EventHubsRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+/// Partitions events by their rendered partition key.
+///
+/// Events whose key fails to render are dropped, while events rendering an empty key are sent
+/// without one.
+struct PartitionKeyPartitioner(Option<Template>);
+
+impl Partitioner for PartitionKeyPartitioner {
+    type Item = Event;
+    type Key = Option<Option<String>>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let Some(template) = &self.0 else {
+            return Some(None);
+        };
+        template
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("partition_key"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+            .map(|key| Some(key).filter(|key| !key.is_empty()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vrl::value;
+
+    use super::*;
+
+    #[test]
+    fn partitions_by_rendered_key() {
+        let partitioner =
+            PartitionKeyPartitioner(Some(Template::try_from("{{ tenant }}").unwrap()));
+
+        let event = Event::from(LogEvent::from(value!({"tenant": "acme"})));
+        assert_eq!(partitioner.partition(&event), Some(Some("acme".into())));
+
+        let event = Event::from(LogEvent::from(value!({"tenant": ""})));
+        assert_eq!(partitioner.partition(&event), Some(None));
+
+        let event = Event::from(LogEvent::from(value!({"host": "a"})));
+        assert_eq!(partitioner.partition(&event), None);
+
+        assert_eq!(PartitionKeyPartitioner(None).partition(&event), Some(None));
+    }
+}
@@ -8,11 +8,16 @@
 //! delimited JSON.
 //!
 //! This sink only supports logs for now but could support metrics and traces as well in the future.
+//!
+//! The `native` module contains the `clickhouse_native` sink, which inserts the same data over
+//! ClickHouse's native TCP protocol instead.
 
 mod config;
 #[cfg(all(test, feature = "clickhouse-integration-tests"))]
 mod integration_tests;
+mod native;
 mod request_builder;
 mod service;
 mod sink;
 pub use self::config::ClickhouseConfig;
+pub use self::native::ClickhouseNativeConfig;
@@ -0,0 +1,646 @@
+//! Conversion of event values into ClickHouse native columns.
+
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, NaiveDate, Utc};
+use vrl::value::Value;
+
+use super::protocol::put_bytes;
+
+/// The column types supported by the native sink.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColumnType {
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+    Bool,
+    String,
+    FixedString(usize),
+    Date,
+    Date32,
+    DateTime,
+    DateTime64(u32),
+    Uuid,
+    IPv4,
+    IPv6,
+    Enum8(Vec<(String, i8)>),
+    Enum16(Vec<(String, i16)>),
+    Nullable(Box<ColumnType>),
+    Array(Box<ColumnType>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseColumnTypeError {
+    type_name: String,
+}
+
+impl fmt::Display for ParseColumnTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unsupported type `{}`", self.type_name)
+    }
+}
+
+impl std::error::Error for ParseColumnTypeError {}
+
+impl FromStr for ColumnType {
+    type Err = ParseColumnTypeError;
+
+    fn from_str(type_name: &str) -> Result<Self, Self::Err> {
+        let type_name = type_name.trim();
+        let unsupported = || ParseColumnTypeError {
+            type_name: type_name.to_string(),
+        };
+
+        let column_type = match type_name {
+            "UInt8" => Self::UInt8,
+            "UInt16" => Self::UInt16,
+            "UInt32" => Self::UInt32,
+            "UInt64" => Self::UInt64,
+            "Int8" => Self::Int8,
+            "Int16" => Self::Int16,
+            "Int32" => Self::Int32,
+            "Int64" => Self::Int64,
+            "Float32" => Self::Float32,
+            "Float64" => Self::Float64,
+            "Bool" => Self::Bool,
+            "String" => Self::String,
+            "Date" => Self::Date,
+            "Date32" => Self::Date32,
+            "DateTime" => Self::DateTime,
+            "UUID" => Self::Uuid,
+            "IPv4" => Self::IPv4,
+            "IPv6" => Self::IPv6,
+            _ => {
+                let (name, args) = type_name
+                    .strip_suffix(')')
+                    .and_then(|rest| rest.split_once('('))
+                    .ok_or_else(unsupported)?;
+                match name {
+                    "Nullable" => Self::Nullable(Box::new(args.parse()?)),
+                    "Array" => Self::Array(Box::new(args.parse()?)),
+                    "FixedString" => {
+                        Self::FixedString(args.trim().parse().map_err(|_| unsupported())?)
+                    }
+                    // The time zone only affects how the server displays the value.
+                    "DateTime" => Self::DateTime,
+                    "DateTime64" => {
+                        let precision = args.split(',').next().unwrap_or_default().trim();
+                        match precision.parse() {
+                            Ok(precision) if precision <= 9 => Self::DateTime64(precision),
+                            _ => return Err(unsupported()),
+                        }
+                    }
+                    "Enum8" => Self::Enum8(parse_enum_variants(args).ok_or_else(unsupported)?),
+                    "Enum16" => Self::Enum16(parse_enum_variants(args).ok_or_else(unsupported)?),
+                    _ => return Err(unsupported()),
+                }
+            }
+        };
+        Ok(column_type)
+    }
+}
+
+/// Parses the `'name' = value, ...` list of an enum type.
+fn parse_enum_variants<T: FromStr>(args: &str) -> Option<Vec<(String, T)>> {
+    let mut variants = Vec::new();
+    let mut rest = args.trim();
+    while !rest.is_empty() {
+        let quoted = rest.strip_prefix('\'')?;
+        let mut name = String::new();
+        let mut chars = quoted.char_indices();
+        let end = loop {
+            match chars.next()? {
+                (_, '\\') => name.push(chars.next()?.1),
+                (index, '\'') => break index,
+                (_, c) => name.push(c),
+            }
+        };
+        let after = quoted[end + 1..].trim_start().strip_prefix('=')?;
+        let (value, remainder) = after.split_once(',').unwrap_or((after, ""));
+        variants.push((name, value.trim().parse().ok()?));
+        rest = remainder.trim();
+    }
+    Some(variants)
+}
+
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UInt8 => write!(f, "UInt8"),
+            Self::UInt16 => write!(f, "UInt16"),
+            Self::UInt32 => write!(f, "UInt32"),
+            Self::UInt64 => write!(f, "UInt64"),
+            Self::Int8 => write!(f, "Int8"),
+            Self::Int16 => write!(f, "Int16"),
+            Self::Int32 => write!(f, "Int32"),
+            Self::Int64 => write!(f, "Int64"),
+            Self::Float32 => write!(f, "Float32"),
+            Self::Float64 => write!(f, "Float64"),
+            Self::Bool => write!(f, "Bool"),
+            Self::String => write!(f, "String"),
+            Self::FixedString(size) => write!(f, "FixedString({})", size),
+            Self::Date => write!(f, "Date"),
+            Self::Date32 => write!(f, "Date32"),
+            Self::DateTime => write!(f, "DateTime"),
+            Self::DateTime64(precision) => write!(f, "DateTime64({})", precision),
+            Self::Uuid => write!(f, "UUID"),
+            Self::IPv4 => write!(f, "IPv4"),
+            Self::IPv6 => write!(f, "IPv6"),
+            Self::Enum8(variants) => write_enum(f, "Enum8", variants),
+            Self::Enum16(variants) => write_enum(f, "Enum16", variants),
+            Self::Nullable(inner) => write!(f, "Nullable({})", inner),
+            Self::Array(inner) => write!(f, "Array({})", inner),
+        }
+    }
+}
+
+fn write_enum<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    variants: &[(String, T)],
+) -> fmt::Result {
+    write!(f, "{}(", name)?;
+    for (index, (variant, value)) in variants.iter().enumerate() {
+        if index > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "'{}' = {}", variant.replace('\'', "\\'"), value)?;
+    }
+    write!(f, ")")
+}
+
+/// Encodes `values` as the data of a single column of type `column_type`.
+///
+/// Missing and `null` values are encoded as the type's default value, or as `NULL` for nullable
+/// columns, mirroring how ClickHouse fills omitted columns on insert.
+pub fn encode_column(
+    column_type: &ColumnType,
+    values: &[Option<&Value>],
+    buf: &mut BytesMut,
+) -> Result<(), String> {
+    match column_type {
+        ColumnType::Nullable(inner) => {
+            let values = values
+                .iter()
+                .map(|value| value.filter(|value| !matches!(value, Value::Null)))
+                .collect::<Vec<_>>();
+            for value in &values {
+                buf.put_u8(u8::from(value.is_none()));
+            }
+            encode_column(inner, &values, buf)
+        }
+        ColumnType::Array(inner) => {
+            let mut items = Vec::new();
+            for value in values {
+                match value {
+                    Some(Value::Array(array)) => items.extend(array.iter().map(Some)),
+                    None | Some(Value::Null) => {}
+                    Some(value) => items.push(Some(*value)),
+                }
+                // Offsets mark where each row ends in the flattened element column.
+                buf.put_u64_le(items.len() as u64);
+            }
+            encode_column(inner, &items, buf)
+        }
+        scalar => values.iter().try_for_each(|value| {
+            encode_scalar(
+                scalar,
+                value.filter(|value| !matches!(value, Value::Null)),
+                buf,
+            )
+        }),
+    }
+}
+
+fn encode_scalar(
+    column_type: &ColumnType,
+    value: Option<&Value>,
+    buf: &mut BytesMut,
+) -> Result<(), String> {
+    match column_type {
+        ColumnType::UInt8 => buf.put_u8(integer(value)?),
+        ColumnType::UInt16 => buf.put_u16_le(integer(value)?),
+        ColumnType::UInt32 => buf.put_u32_le(integer(value)?),
+        ColumnType::UInt64 => buf.put_u64_le(integer(value)?),
+        ColumnType::Int8 => buf.put_i8(integer(value)?),
+        ColumnType::Int16 => buf.put_i16_le(integer(value)?),
+        ColumnType::Int32 => buf.put_i32_le(integer(value)?),
+        ColumnType::Int64 => buf.put_i64_le(integer(value)?),
+        ColumnType::Float32 => buf.put_f32_le(float(value)? as f32),
+        ColumnType::Float64 => buf.put_f64_le(float(value)?),
+        ColumnType::Bool => buf.put_u8(u8::from(boolean(value)?)),
+        ColumnType::String => put_bytes(buf, &string(value)),
+        ColumnType::FixedString(size) => {
+            let bytes = string(value);
+            if bytes.len() > *size {
+                return Err(format!(
+                    "value is {} bytes long but the column holds at most {}",
+                    bytes.len(),
+                    size
+                ));
+            }
+            buf.extend_from_slice(&bytes);
+            buf.put_bytes(0, size - bytes.len());
+        }
+        ColumnType::Date => {
+            let days = days_since_epoch(value)?;
+            buf.put_u16_le(u16::try_from(days).map_err(|_| "date is out of range".to_string())?);
+        }
+        ColumnType::Date32 => {
+            let days = days_since_epoch(value)?;
+            buf.put_i32_le(i32::try_from(days).map_err(|_| "date is out of range".to_string())?);
+        }
+        ColumnType::DateTime => {
+            let seconds = timestamp(value)?.map_or(0, |timestamp| timestamp.timestamp());
+            buf.put_u32_le(
+                u32::try_from(seconds).map_err(|_| "timestamp is out of range".to_string())?,
+            );
+        }
+        ColumnType::DateTime64(precision) => {
+            let ticks = match timestamp(value)? {
+                Some(timestamp) => {
+                    let scale = 10i64.pow(*precision);
+                    timestamp
+                        .timestamp()
+                        .checked_mul(scale)
+                        .and_then(|ticks| {
+                            let nanos = i64::from(timestamp.timestamp_subsec_nanos());
+                            ticks.checked_add(nanos / 10i64.pow(9 - precision))
+                        })
+                        .ok_or_else(|| "timestamp is out of range".to_string())?
+                }
+                None => 0,
+            };
+            buf.put_i64_le(ticks);
+        }
+        ColumnType::Uuid => {
+            let uuid = match value {
+                None => uuid::Uuid::nil(),
+                Some(value) => uuid::Uuid::try_parse_ascii(&string(Some(value)))
+                    .map_err(|error| format!("invalid UUID: {}", error))?,
+            };
+            // ClickHouse stores UUIDs as two little-endian 64-bit halves.
+            let (high, low) = uuid.as_u64_pair();
+            buf.put_u64_le(high);
+            buf.put_u64_le(low);
+        }
+        ColumnType::IPv4 => {
+            let addr = match ip_addr(value)? {
+                None => Ipv4Addr::UNSPECIFIED,
+                Some(IpAddr::V4(addr)) => addr,
+                Some(IpAddr::V6(addr)) => addr
+                    .to_ipv4_mapped()
+                    .ok_or_else(|| format!("`{}` is not an IPv4 address", addr))?,
+            };
+            buf.put_u32_le(u32::from(addr));
+        }
+        ColumnType::IPv6 => {
+            let addr = match ip_addr(value)? {
+                None => Ipv6Addr::UNSPECIFIED,
+                Some(IpAddr::V4(addr)) => addr.to_ipv6_mapped(),
+                Some(IpAddr::V6(addr)) => addr,
+            };
+            buf.extend_from_slice(&addr.octets());
+        }
+        ColumnType::Enum8(variants) => buf.put_i8(enum_value(variants, value)?),
+        ColumnType::Enum16(variants) => buf.put_i16_le(enum_value(variants, value)?),
+        ColumnType::Nullable(_) | ColumnType::Array(_) => {
+            return Err(format!(
+                "nested `{}` columns are not supported",
+                column_type
+            ))
+        }
+    }
+    Ok(())
+}
+
+fn integer<T>(value: Option<&Value>) -> Result<T, String>
+where
+    T: TryFrom<i64> + FromStr + Default,
+{
+    let integer = match value {
+        None => return Ok(T::default()),
+        Some(Value::Integer(integer)) => *integer,
+        Some(Value::Boolean(boolean)) => i64::from(*boolean),
+        Some(Value::Float(float)) if float.fract() == 0.0 => float.into_inner() as i64,
+        Some(Value::Timestamp(timestamp)) => timestamp.timestamp(),
+        Some(Value::Bytes(bytes)) => {
+            return std::str::from_utf8(bytes)
+                .ok()
+                .and_then(|string| string.trim().parse().ok())
+                .ok_or_else(|| "expected an integer".to_string())
+        }
+        Some(value) => return Err(format!("expected an integer, got {}", value.kind_str())),
+    };
+    T::try_from(integer).map_err(|_| format!("integer {} is out of range", integer))
+}
+
+fn float(value: Option<&Value>) -> Result<f64, String> {
+    match value {
+        None => Ok(0.0),
+        Some(Value::Float(float)) => Ok(float.into_inner()),
+        Some(Value::Integer(integer)) => Ok(*integer as f64),
+        Some(Value::Bytes(bytes)) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|string| string.trim().parse().ok())
+            .ok_or_else(|| "expected a float".to_string()),
+        Some(value) => Err(format!("expected a float, got {}", value.kind_str())),
+    }
+}
+
+fn boolean(value: Option<&Value>) -> Result<bool, String> {
+    match value {
+        None => Ok(false),
+        Some(Value::Boolean(boolean)) => Ok(*boolean),
+        Some(Value::Integer(integer)) => Ok(*integer != 0),
+        Some(Value::Bytes(bytes)) => match bytes.as_ref() {
+            b"true" | b"1" => Ok(true),
+            b"false" | b"0" => Ok(false),
+            _ => Err("expected a boolean".to_string()),
+        },
+        Some(value) => Err(format!("expected a boolean, got {}", value.kind_str())),
+    }
+}
+
+/// Renders any value as the bytes stored in a `String` column.
+///
+/// Strings are stored as-is, timestamps as RFC 3339, and everything else as JSON.
+fn string(value: Option<&Value>) -> Vec<u8> {
+    match value {
+        None => Vec::new(),
+        Some(Value::Bytes(bytes)) => bytes.to_vec(),
+        Some(Value::Timestamp(timestamp)) => timestamp
+            .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+            .into_bytes(),
+        Some(value) => serde_json::to_vec(value).unwrap_or_default(),
+    }
+}
+
+fn timestamp(value: Option<&Value>) -> Result<Option<DateTime<Utc>>, String> {
+    match value {
+        None => Ok(None),
+        Some(Value::Timestamp(timestamp)) => Ok(Some(*timestamp)),
+        Some(Value::Integer(seconds)) => DateTime::from_timestamp(*seconds, 0)
+            .map(Some)
+            .ok_or_else(|| "timestamp is out of range".to_string()),
+        Some(Value::Bytes(bytes)) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|string| DateTime::parse_from_rfc3339(string.trim()).ok())
+            .map(|timestamp| Some(timestamp.with_timezone(&Utc)))
+            .ok_or_else(|| "expected an RFC 3339 timestamp".to_string()),
+        Some(value) => Err(format!("expected a timestamp, got {}", value.kind_str())),
+    }
+}
+
+fn days_since_epoch(value: Option<&Value>) -> Result<i64, String> {
+    let date = match value {
+        Some(Value::Bytes(bytes)) => match std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|string| NaiveDate::parse_from_str(string.trim(), "%Y-%m-%d").ok())
+        {
+            Some(date) => date,
+            None => match timestamp(value)? {
+                Some(timestamp) => timestamp.date_naive(),
+                None => return Ok(0),
+            },
+        },
+        _ => match timestamp(value)? {
+            Some(timestamp) => timestamp.date_naive(),
+            None => return Ok(0),
+        },
+    };
+    Ok((date - NaiveDate::default()).num_days())
+}
+
+fn ip_addr(value: Option<&Value>) -> Result<Option<IpAddr>, String> {
+    match value {
+        None => Ok(None),
+        Some(Value::Bytes(bytes)) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|string| string.trim().parse().ok())
+            .map(Some)
+            .ok_or_else(|| "expected an IP address".to_string()),
+        Some(value) => Err(format!("expected an IP address, got {}", value.kind_str())),
+    }
+}
+
+fn enum_value<T>(variants: &[(String, T)], value: Option<&Value>) -> Result<T, String>
+where
+    T: Copy + PartialEq + TryFrom<i64>,
+{
+    let found = match value {
+        // Like ClickHouse, fall back to the first variant when the value is omitted.
+        None => variants.first().map(|(_, value)| *value),
+        Some(Value::Bytes(bytes)) => variants
+            .iter()
+            .find(|(name, _)| name.as_bytes() == bytes.as_ref())
+            .map(|(_, value)| *value),
+        Some(Value::Integer(integer)) => T::try_from(*integer)
+            .ok()
+            .filter(|value| variants.iter().any(|(_, variant)| variant == value)),
+        Some(value) => {
+            return Err(format!(
+                "expected an enum variant, got {}",
+                value.kind_str()
+            ))
+        }
+    };
+    found.ok_or_else(|| "value is not a variant of the enum".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vrl::value;
+
+    use super::*;
+
+    fn encode(column_type: &str, values: &[Option<Value>]) -> Result<Vec<u8>, String> {
+        let column_type = column_type.parse::<ColumnType>().unwrap();
+        let values = values.iter().map(Option::as_ref).collect::<Vec<_>>();
+        let mut buf = BytesMut::new();
+        encode_column(&column_type, &values, &mut buf).map(|_| buf.to_vec())
+    }
+
+    #[test]
+    fn parses_column_types() {
+        let cases = [
+            ("UInt8", ColumnType::UInt8),
+            ("String", ColumnType::String),
+            ("DateTime('Europe/Paris')", ColumnType::DateTime),
+            ("DateTime64(3, 'UTC')", ColumnType::DateTime64(3)),
+            ("FixedString(16)", ColumnType::FixedString(16)),
+            (
+                "Nullable(Int64)",
+                ColumnType::Nullable(Box::new(ColumnType::Int64)),
+            ),
+            (
+                "Array(Nullable(String))",
+                ColumnType::Array(Box::new(ColumnType::Nullable(Box::new(ColumnType::String)))),
+            ),
+            (
+                "Enum8('a' = 1, 'it\\'s' = -2)",
+                ColumnType::Enum8(vec![("a".into(), 1), ("it's".into(), -2)]),
+            ),
+        ];
+        for (type_name, expected) in cases {
+            assert_eq!(
+                type_name.parse::<ColumnType>(),
+                Ok(expected),
+                "{}",
+                type_name
+            );
+        }
+
+        assert!("Map(String, String)".parse::<ColumnType>().is_err());
+        assert!("LowCardinality(String)".parse::<ColumnType>().is_err());
+        assert!("DateTime64(12)".parse::<ColumnType>().is_err());
+    }
+
+    #[test]
+    fn column_type_display_roundtrips() {
+        for type_name in [
+            "UInt64",
+            "DateTime64(6)",
+            "Array(Nullable(UUID))",
+            "Enum16('low' = 1, 'high' = 1000)",
+        ] {
+            let column_type = type_name.parse::<ColumnType>().unwrap();
+            assert_eq!(column_type.to_string(), type_name);
+        }
+    }
+
+    #[test]
+    fn encodes_integers() {
+        assert_eq!(
+            encode("Int32", &[Some(value!(1)), Some(value!("-2")), None]).unwrap(),
+            [1, 0, 0, 0, 0xfe, 0xff, 0xff, 0xff, 0, 0, 0, 0]
+        );
+        assert!(encode("UInt8", &[Some(value!(256))]).is_err());
+        assert!(encode("UInt8", &[Some(value!({"a": 1}))]).is_err());
+    }
+
+    #[test]
+    fn encodes_strings() {
+        assert_eq!(
+            encode(
+                "String",
+                &[
+                    Some(value!("foo")),
+                    Some(value!({"a": 1})),
+                    Some(Value::Null)
+                ]
+            )
+            .unwrap(),
+            b"\x03foo\x07{\"a\":1}\x00"
+        );
+        assert_eq!(
+            encode("FixedString(4)", &[Some(value!("ab"))]).unwrap(),
+            b"ab\0\0"
+        );
+        assert!(encode("FixedString(1)", &[Some(value!("ab"))]).is_err());
+    }
+
+    #[test]
+    fn encodes_nullable() {
+        assert_eq!(
+            encode(
+                "Nullable(UInt8)",
+                &[Some(value!(7)), None, Some(Value::Null)]
+            )
+            .unwrap(),
+            [0, 1, 1, 7, 0, 0]
+        );
+    }
+
+    #[test]
+    fn encodes_arrays() {
+        assert_eq!(
+            encode(
+                "Array(UInt8)",
+                &[Some(value!([1, 2])), None, Some(value!(3))]
+            )
+            .unwrap(),
+            [
+                2, 0, 0, 0, 0, 0, 0, 0, // first row ends at 2
+                2, 0, 0, 0, 0, 0, 0, 0, // second row is empty
+                3, 0, 0, 0, 0, 0, 0, 0, // a scalar is a single element array
+                1, 2, 3,
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_timestamps() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap()
+            + chrono::Duration::milliseconds(678);
+        let values = [Some(Value::Timestamp(timestamp))];
+
+        assert_eq!(
+            encode("DateTime", &values).unwrap(),
+            1_704_164_645u32.to_le_bytes()
+        );
+        assert_eq!(
+            encode("DateTime64(3)", &values).unwrap(),
+            1_704_164_645_678i64.to_le_bytes()
+        );
+        assert_eq!(encode("Date", &values).unwrap(), 19_724u16.to_le_bytes());
+        assert_eq!(
+            encode("Date", &[Some(value!("1970-01-02"))]).unwrap(),
+            1u16.to_le_bytes()
+        );
+        assert_eq!(
+            encode("DateTime", &[Some(value!("2024-01-02T03:04:05Z"))]).unwrap(),
+            1_704_164_645u32.to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn encodes_enums() {
+        assert_eq!(
+            encode(
+                "Enum8('info' = 1, 'error' = 2)",
+                &[Some(value!("error")), Some(value!(1)), None]
+            )
+            .unwrap(),
+            [2, 1, 1]
+        );
+        assert!(encode("Enum8('info' = 1)", &[Some(value!("debug"))]).is_err());
+    }
+
+    #[test]
+    fn encodes_addresses_and_uuids() {
+        assert_eq!(
+            encode("IPv4", &[Some(value!("1.2.3.4"))]).unwrap(),
+            0x0102_0304u32.to_le_bytes()
+        );
+        assert_eq!(
+            encode("IPv6", &[Some(value!("::1"))]).unwrap(),
+            Ipv6Addr::LOCALHOST.octets()
+        );
+        let encoded = encode(
+            "UUID",
+            &[Some(value!("00112233-4455-6677-8899-aabbccddeeff"))],
+        )
+        .unwrap();
+        assert_eq!(
+            encoded,
+            [
+                0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x00, 0xff, 0xee, 0xdd, 0xcc, 0xbb, 0xaa,
+                0x99, 0x88
+            ]
+        );
+    }
+}
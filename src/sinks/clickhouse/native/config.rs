@@ -0,0 +1,234 @@
+//! Configuration for the `clickhouse_native` sink.
+
+use std::collections::HashMap;
+
+use vector_lib::{lookup::lookup_v2::ConfigValuePath, sensitive_string::SensitiveString};
+
+use super::{
+    protocol::{Connection, ConnectionOptions},
+    service::{ClickhouseNativeRetryLogic, ClickhouseNativeService, ColumnMapping},
+    sink::ClickhouseNativeSink,
+};
+use crate::{
+    sinks::{
+        prelude::*,
+        util::{RealtimeSizeBasedDefaultBatchSettings, SinkBuildError},
+    },
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+const DEFAULT_PORT: u16 = 9000;
+
+/// Configuration for the `clickhouse_native` sink.
+#[configurable_component(sink(
+    "clickhouse_native",
+    "Deliver log data to a ClickHouse database using the native TCP protocol."
+))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ClickhouseNativeConfig {
+    /// The address of the ClickHouse server's native protocol interface.
+    ///
+    /// Both IP address and hostname are accepted formats. If no port is given, `9000` is used.
+    #[configurable(metadata(docs::examples = "localhost:9000"))]
+    #[configurable(metadata(docs::examples = "clickhouse.example.com:9440"))]
+    pub address: String,
+
+    /// The table that data is inserted into.
+    #[configurable(metadata(docs::examples = "mytable"))]
+    pub table: Template,
+
+    /// The database that contains the table that data is inserted into.
+    #[configurable(metadata(docs::examples = "mydatabase"))]
+    pub database: Option<Template>,
+
+    /// Maps table columns to the event fields they are filled from.
+    ///
+    /// When set, only the listed columns are inserted and the server fills the remaining ones from
+    /// their `DEFAULT` expressions. When unset, every column of the table is filled from the
+    /// top-level event field of the same name.
+    ///
+    /// Fields missing from an event are inserted as `NULL` for `Nullable` columns and as the
+    /// column type's default value otherwise.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The event field to fill the column from."
+    ))]
+    #[configurable(metadata(docs::examples = "columns_examples()"))]
+    pub columns: HashMap<String, ConfigValuePath>,
+
+    #[configurable(derived)]
+    pub auth: Option<ClickhouseNativeAuth>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// Credentials used to log in to the ClickHouse server.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ClickhouseNativeAuth {
+    /// The username.
+    #[configurable(metadata(docs::examples = "${CLICKHOUSE_USER}"))]
+    #[configurable(metadata(docs::examples = "default"))]
+    pub user: String,
+
+    /// The password.
+    #[configurable(metadata(docs::examples = "${CLICKHOUSE_PASSWORD}"))]
+    pub password: SensitiveString,
+}
+
+fn columns_examples() -> HashMap<String, String> {
+    HashMap::<_, _>::from_iter([
+        ("timestamp".to_owned(), "timestamp".to_owned()),
+        ("message".to_owned(), "message".to_owned()),
+        ("status_code".to_owned(), "http.status".to_owned()),
+    ])
+}
+
+impl GenerateConfig for ClickhouseNativeConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "localhost:9000"
+            table = "mytable""#,
+        )
+        .unwrap()
+    }
+}
+
+impl ClickhouseNativeConfig {
+    fn connection_options(&self) -> crate::Result<ConnectionOptions> {
+        let uri = self.address.parse::<http::Uri>()?;
+        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
+        let port = uri.port_u16().unwrap_or(DEFAULT_PORT);
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let (user, password) = self.auth.as_ref().map_or_else(
+            || ("default".to_string(), String::new()),
+            |auth| (auth.user.clone(), auth.password.inner().to_string()),
+        );
+
+        Ok(ConnectionOptions {
+            host,
+            port,
+            tls,
+            // Tables are always addressed by their fully qualified name, so the session can use
+            // the server's default database.
+            database: String::new(),
+            user,
+            password,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "clickhouse_native")]
+impl SinkConfig for ClickhouseNativeConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let options = self.connection_options()?;
+        let mapping = ColumnMapping::new(
+            self.columns
+                .iter()
+                .map(|(column, field)| (column.clone(), field.0.clone()))
+                .collect(),
+        );
+
+        let service = ClickhouseNativeService::new(options.clone(), mapping);
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), ClickhouseNativeRetryLogic)
+            .service(service);
+
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let database = self.database.clone().unwrap_or_else(|| {
+            "default"
+                .try_into()
+                .expect("'default' should be a valid template")
+        });
+
+        let sink = ClickhouseNativeSink::new(
+            batch_settings,
+            service,
+            database,
+            self.table.clone(),
+            self.encoding.clone(),
+        );
+
+        let healthcheck = Box::pin(healthcheck(options));
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+async fn healthcheck(options: ConnectionOptions) -> crate::Result<()> {
+    let mut connection = Connection::connect(&options).await?;
+    connection.ping().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ClickhouseNativeConfig>();
+    }
+
+    #[test]
+    fn parses_address() {
+        let config = toml::from_str::<ClickhouseNativeConfig>(
+            r#"
+            address = "clickhouse.example.com"
+            table = "logs"
+            auth.user = "vector"
+            auth.password = "secret"
+            "#,
+        )
+        .unwrap();
+        let options = config.connection_options().unwrap();
+        assert_eq!(options.host, "clickhouse.example.com");
+        assert_eq!(options.port, DEFAULT_PORT);
+        assert_eq!(options.user, "vector");
+        assert_eq!(options.password, "secret");
+
+        let config = toml::from_str::<ClickhouseNativeConfig>(
+            r#"
+            address = "127.0.0.1:19000"
+            table = "logs"
+            "#,
+        )
+        .unwrap();
+        let options = config.connection_options().unwrap();
+        assert_eq!(options.host, "127.0.0.1");
+        assert_eq!(options.port, 19000);
+        assert_eq!(options.user, "default");
+    }
+}
@@ -0,0 +1,130 @@
+use futures::{future::ready, stream};
+use serde_json::Value as JsonValue;
+use vector_lib::event::{BatchNotifier, BatchStatus, Event, LogEvent};
+
+use super::*;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    sinks::util::{BatchConfig, TowerRequestConfig},
+    test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        random_string, trace_init,
+    },
+};
+
+fn clickhouse_http_address() -> String {
+    std::env::var("CLICKHOUSE_ADDRESS").unwrap_or_else(|_| "http://localhost:8123".into())
+}
+
+fn clickhouse_native_address() -> String {
+    std::env::var("CLICKHOUSE_NATIVE_ADDRESS").unwrap_or_else(|_| "localhost:9000".into())
+}
+
+async fn query(body: String) -> String {
+    let response = reqwest::Client::new()
+        .post(clickhouse_http_address())
+        .body(body)
+        .send()
+        .await
+        .unwrap();
+    let status = response.status();
+    let text = response.text().await.unwrap();
+    assert!(status.is_success(), "query failed: {}", text);
+    text
+}
+
+fn config(table: &str) -> ClickhouseNativeConfig {
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(10);
+
+    ClickhouseNativeConfig {
+        address: clickhouse_native_address(),
+        table: table.try_into().unwrap(),
+        batch,
+        request: TowerRequestConfig {
+            retry_attempts: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn healthcheck() {
+    trace_init();
+
+    let (_sink, healthcheck) = config("unused")
+        .build(SinkContext::default())
+        .await
+        .unwrap();
+    healthcheck.await.unwrap();
+}
+
+#[tokio::test]
+async fn insert_events() {
+    trace_init();
+
+    let table = format!("test_{}", random_string(10).to_lowercase());
+    query(format!(
+        "CREATE TABLE {} (
+            host String,
+            message String,
+            count Nullable(UInt32),
+            tags Array(String),
+            timestamp DateTime64(3)
+        ) ENGINE = MergeTree() ORDER BY (host, timestamp)",
+        table
+    ))
+    .await;
+
+    let (sink, _hc) = config(&table).build(SinkContext::default()).await.unwrap();
+
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let events = (0..5)
+        .map(|i| {
+            let mut log = LogEvent::from(format!("message {}", i)).with_batch_notifier(&batch);
+            log.insert("host", "example.com");
+            log.insert("tags", vec!["a", "b"]);
+            if i % 2 == 0 {
+                log.insert("count", i);
+            }
+            Event::from(log)
+        })
+        .collect::<Vec<_>>();
+    drop(batch);
+
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+    assert_eq!(receiver.await, BatchStatus::Delivered);
+
+    let output = query(format!(
+        "SELECT message, count, tags FROM {} ORDER BY message FORMAT JSONEachRow",
+        table
+    ))
+    .await;
+    let rows = output
+        .lines()
+        .map(|line| serde_json::from_str::<JsonValue>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[0]["message"], "message 0");
+    assert_eq!(rows[0]["count"], 0);
+    assert_eq!(rows[1]["count"], JsonValue::Null);
+    assert_eq!(rows[0]["tags"], serde_json::json!(["a", "b"]));
+}
+
+#[tokio::test]
+async fn rejects_missing_table() {
+    trace_init();
+
+    let (sink, _hc) = config("does_not_exist")
+        .build(SinkContext::default())
+        .await
+        .unwrap();
+
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let event = LogEvent::from("raw log line").with_batch_notifier(&batch);
+    drop(batch);
+
+    sink.run(stream::once(ready(event.into()))).await.unwrap();
+    assert_eq!(receiver.await, BatchStatus::Rejected);
+}
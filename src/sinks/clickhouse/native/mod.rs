@@ -0,0 +1,16 @@
+//! The `clickhouse_native` [`vector_lib::sink::VectorSink`].
+//!
+//! Events are sent to ClickHouse over the native TCP protocol. Each batch of events is converted
+//! into a single columnar block laid out according to the structure of the target table, which
+//! the server announces when the `INSERT` query starts, so no serialization format has to be
+//! negotiated and the server does not need to parse rows.
+
+mod columns;
+mod config;
+#[cfg(all(test, feature = "clickhouse-integration-tests"))]
+mod integration_tests;
+mod protocol;
+mod service;
+mod sink;
+
+pub use self::config::ClickhouseNativeConfig;
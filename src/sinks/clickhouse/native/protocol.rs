@@ -0,0 +1,654 @@
+//! A minimal client for the ClickHouse native TCP protocol.
+//!
+//! Only the subset of the protocol required to run `INSERT` queries is implemented: the
+//! handshake, sending a query followed by blocks of column data, and reading the replies the
+//! server sends back. Compression is never negotiated, so blocks are sent uncompressed.
+//!
+//! Protocol reference: <https://clickhouse.com/docs/en/native-protocol/basics>
+
+use std::net::SocketAddr;
+
+use bytes::{BufMut, BytesMut};
+use snafu::{ResultExt, Snafu};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+    net::TcpStream,
+};
+
+use super::columns::{ColumnType, ParseColumnTypeError};
+use crate::{
+    dns,
+    tls::{MaybeTlsSettings, MaybeTlsStream, TlsError},
+};
+
+const CLIENT_NAME: &str = "Vector";
+const CLIENT_VERSION_MAJOR: u64 = 1;
+const CLIENT_VERSION_MINOR: u64 = 0;
+
+/// The protocol revision advertised to the server.
+///
+/// Staying below `DBMS_MIN_REVISION_WITH_LOW_CARDINALITY_TYPE` (54406) makes the server convert
+/// `LowCardinality` columns to their plain types and keeps it from sending server logs, neither
+/// of which this client knows how to decode.
+const CLIENT_REVISION: u64 = 54405;
+
+const DBMS_MIN_REVISION_WITH_TEMPORARY_TABLES: u64 = 50264;
+const DBMS_MIN_REVISION_WITH_TOTAL_ROWS_IN_PROGRESS: u64 = 51554;
+const DBMS_MIN_REVISION_WITH_BLOCK_INFO: u64 = 51903;
+const DBMS_MIN_REVISION_WITH_CLIENT_INFO: u64 = 54032;
+const DBMS_MIN_REVISION_WITH_SERVER_TIMEZONE: u64 = 54058;
+const DBMS_MIN_REVISION_WITH_QUOTA_KEY_IN_CLIENT_INFO: u64 = 54060;
+const DBMS_MIN_REVISION_WITH_SERVER_DISPLAY_NAME: u64 = 54372;
+const DBMS_MIN_REVISION_WITH_VERSION_PATCH: u64 = 54401;
+
+mod client {
+    pub const HELLO: u64 = 0;
+    pub const QUERY: u64 = 1;
+    pub const DATA: u64 = 2;
+    pub const PING: u64 = 4;
+}
+
+mod server {
+    pub const HELLO: u64 = 0;
+    pub const DATA: u64 = 1;
+    pub const EXCEPTION: u64 = 2;
+    pub const PROGRESS: u64 = 3;
+    pub const PONG: u64 = 4;
+    pub const END_OF_STREAM: u64 = 5;
+    pub const PROFILE_INFO: u64 = 6;
+}
+
+const QUERY_KIND_INITIAL: u8 = 1;
+const INTERFACE_TCP: u8 = 1;
+const STAGE_COMPLETE: u64 = 2;
+const COMPRESSION_DISABLED: u64 = 0;
+
+/// Errors returned while talking to a ClickHouse server over the native protocol.
+#[derive(Debug, Snafu)]
+pub enum ClickhouseNativeError {
+    #[snafu(display("Unable to connect to ClickHouse: {}", source))]
+    Connect { source: TlsError },
+
+    #[snafu(display("Unable to resolve DNS: {}", source))]
+    Dns { source: dns::DnsError },
+
+    #[snafu(display("No addresses returned."))]
+    NoAddresses,
+
+    #[snafu(display("I/O error while talking to ClickHouse: {}", source))]
+    Io { source: std::io::Error },
+
+    #[snafu(display(
+        "ClickHouse returned an exception: Code: {}. {}: {}",
+        code,
+        name,
+        message
+    ))]
+    Server {
+        code: i32,
+        name: String,
+        message: String,
+    },
+
+    #[snafu(display("Unexpected packet {} from ClickHouse while {}", packet, context))]
+    UnexpectedPacket { packet: u64, context: &'static str },
+
+    #[snafu(display("Unsupported column type for column `{}`: {}", column, source))]
+    UnsupportedColumn {
+        column: String,
+        source: ParseColumnTypeError,
+    },
+
+    #[snafu(display("Invalid value for column `{}`: {}", column, message))]
+    InvalidValue { column: String, message: String },
+}
+
+impl From<std::io::Error> for ClickhouseNativeError {
+    fn from(source: std::io::Error) -> Self {
+        Self::Io { source }
+    }
+}
+
+impl ClickhouseNativeError {
+    /// Whether sending the same data again could succeed.
+    pub fn is_retriable(&self) -> bool {
+        match self {
+            Self::Connect { .. }
+            | Self::Dns { .. }
+            | Self::NoAddresses
+            | Self::Io { .. }
+            | Self::UnexpectedPacket { .. } => true,
+            Self::Server { code, .. } => !NON_RETRIABLE_SERVER_CODES.contains(code),
+            Self::UnsupportedColumn { .. } | Self::InvalidValue { .. } => false,
+        }
+    }
+}
+
+/// Server error codes which indicate that the data or the query itself is at fault.
+///
+/// Error code definitions: <https://github.com/ClickHouse/ClickHouse/blob/master/src/Common/ErrorCodes.cpp>
+const NON_RETRIABLE_SERVER_CODES: &[i32] = &[
+    16,  // NO_SUCH_COLUMN_IN_TABLE
+    27,  // CANNOT_PARSE_INPUT_ASSERTION_FAILED
+    33,  // CANNOT_READ_ALL_DATA
+    53,  // TYPE_MISMATCH
+    60,  // UNKNOWN_TABLE
+    62,  // SYNTAX_ERROR
+    81,  // UNKNOWN_DATABASE
+    117, // INCORRECT_DATA
+    497, // ACCESS_DENIED
+    516, // AUTHENTICATION_FAILED
+];
+
+/// Description of the server, as sent in its `Hello` packet.
+#[derive(Clone, Debug, Default)]
+pub struct ServerInfo {
+    pub name: String,
+    pub revision: u64,
+    pub timezone: Option<String>,
+}
+
+/// A column of the table targeted by an insert, as announced by the server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnHeader {
+    pub name: String,
+    /// The type exactly as the server spelled it, which is echoed back in data blocks.
+    pub type_name: String,
+    pub column_type: ColumnType,
+}
+
+/// A block of encoded column data, ready to be sent to the server.
+#[derive(Debug, Default)]
+pub struct Block {
+    pub rows: usize,
+    pub columns: Vec<EncodedColumn>,
+}
+
+#[derive(Debug)]
+pub struct EncodedColumn {
+    pub name: String,
+    pub type_name: String,
+    pub data: BytesMut,
+}
+
+/// Connection parameters shared by every connection opened by the sink.
+#[derive(Clone, Debug)]
+pub struct ConnectionOptions {
+    pub host: String,
+    pub port: u16,
+    pub tls: MaybeTlsSettings,
+    pub database: String,
+    pub user: String,
+    pub password: String,
+}
+
+/// An established, authenticated connection to a ClickHouse server.
+pub struct Connection<S> {
+    stream: BufReader<S>,
+    server: ServerInfo,
+}
+
+impl Connection<MaybeTlsStream<TcpStream>> {
+    pub async fn connect(options: &ConnectionOptions) -> Result<Self, ClickhouseNativeError> {
+        let ip = dns::Resolver
+            .lookup_ip(options.host.clone())
+            .await
+            .context(DnsSnafu)?
+            .next()
+            .ok_or(ClickhouseNativeError::NoAddresses)?;
+        let addr = SocketAddr::new(ip, options.port);
+
+        let stream = options
+            .tls
+            .connect(&options.host, &addr)
+            .await
+            .context(ConnectSnafu)?;
+
+        Self::handshake(stream, options).await
+    }
+}
+
+impl<S> Connection<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    async fn handshake(
+        stream: S,
+        options: &ConnectionOptions,
+    ) -> Result<Self, ClickhouseNativeError> {
+        let mut connection = Self {
+            stream: BufReader::new(stream),
+            server: ServerInfo::default(),
+        };
+
+        let mut buf = BytesMut::new();
+        put_varint(&mut buf, client::HELLO);
+        put_string(&mut buf, CLIENT_NAME);
+        put_varint(&mut buf, CLIENT_VERSION_MAJOR);
+        put_varint(&mut buf, CLIENT_VERSION_MINOR);
+        put_varint(&mut buf, CLIENT_REVISION);
+        put_string(&mut buf, &options.database);
+        put_string(&mut buf, &options.user);
+        put_string(&mut buf, &options.password);
+        connection.send(&buf).await?;
+
+        match read_varint(&mut connection.stream).await? {
+            server::HELLO => {
+                let name = read_string(&mut connection.stream).await?;
+                let _major = read_varint(&mut connection.stream).await?;
+                let _minor = read_varint(&mut connection.stream).await?;
+                let server_revision = read_varint(&mut connection.stream).await?;
+                let revision = server_revision.min(CLIENT_REVISION);
+
+                let timezone = if revision >= DBMS_MIN_REVISION_WITH_SERVER_TIMEZONE {
+                    Some(read_string(&mut connection.stream).await?)
+                } else {
+                    None
+                };
+                if revision >= DBMS_MIN_REVISION_WITH_SERVER_DISPLAY_NAME {
+                    let _display_name = read_string(&mut connection.stream).await?;
+                }
+                if revision >= DBMS_MIN_REVISION_WITH_VERSION_PATCH {
+                    let _patch = read_varint(&mut connection.stream).await?;
+                }
+
+                connection.server = ServerInfo {
+                    name,
+                    revision,
+                    timezone,
+                };
+                Ok(connection)
+            }
+            server::EXCEPTION => Err(read_exception(&mut connection.stream).await?),
+            packet => Err(ClickhouseNativeError::UnexpectedPacket {
+                packet,
+                context: "waiting for the server hello",
+            }),
+        }
+    }
+
+    pub const fn server(&self) -> &ServerInfo {
+        &self.server
+    }
+
+    /// Sends a ping and waits for the matching pong.
+    pub async fn ping(&mut self) -> Result<(), ClickhouseNativeError> {
+        let mut buf = BytesMut::new();
+        put_varint(&mut buf, client::PING);
+        self.send(&buf).await?;
+
+        loop {
+            match read_varint(&mut self.stream).await? {
+                server::PONG => return Ok(()),
+                server::PROGRESS => self.skip_progress().await?,
+                server::EXCEPTION => return Err(read_exception(&mut self.stream).await?),
+                packet => {
+                    return Err(ClickhouseNativeError::UnexpectedPacket {
+                        packet,
+                        context: "waiting for pong",
+                    })
+                }
+            }
+        }
+    }
+
+    /// Starts an `INSERT` query and returns the structure of the target table.
+    ///
+    /// The caller must follow up with [`Connection::send_block`] and
+    /// [`Connection::finish_insert`].
+    pub async fn start_insert(
+        &mut self,
+        query: &str,
+    ) -> Result<Vec<ColumnHeader>, ClickhouseNativeError> {
+        let revision = self.server.revision;
+        let mut buf = BytesMut::new();
+
+        put_varint(&mut buf, client::QUERY);
+        put_string(&mut buf, ""); // query id, assigned by the server
+        if revision >= DBMS_MIN_REVISION_WITH_CLIENT_INFO {
+            buf.put_u8(QUERY_KIND_INITIAL);
+            put_string(&mut buf, ""); // initial user
+            put_string(&mut buf, ""); // initial query id
+            put_string(&mut buf, "[::ffff:127.0.0.1]:0"); // initial address
+            buf.put_u8(INTERFACE_TCP);
+            put_string(&mut buf, ""); // os user
+            put_string(&mut buf, &crate::get_hostname().unwrap_or_default());
+            put_string(&mut buf, CLIENT_NAME);
+            put_varint(&mut buf, CLIENT_VERSION_MAJOR);
+            put_varint(&mut buf, CLIENT_VERSION_MINOR);
+            put_varint(&mut buf, CLIENT_REVISION);
+            if revision >= DBMS_MIN_REVISION_WITH_QUOTA_KEY_IN_CLIENT_INFO {
+                put_string(&mut buf, ""); // quota key
+            }
+            if revision >= DBMS_MIN_REVISION_WITH_VERSION_PATCH {
+                put_varint(&mut buf, 0);
+            }
+        }
+        put_string(&mut buf, ""); // end of settings
+        put_varint(&mut buf, STAGE_COMPLETE);
+        put_varint(&mut buf, COMPRESSION_DISABLED);
+        put_string(&mut buf, query);
+
+        // An empty block terminates the (absent) list of external tables.
+        encode_block(&mut buf, &Block::default(), revision);
+        self.send(&buf).await?;
+
+        loop {
+            match read_varint(&mut self.stream).await? {
+                server::DATA => return self.read_header_block().await,
+                server::PROGRESS => self.skip_progress().await?,
+                server::EXCEPTION => return Err(read_exception(&mut self.stream).await?),
+                packet => {
+                    return Err(ClickhouseNativeError::UnexpectedPacket {
+                        packet,
+                        context: "waiting for the table structure",
+                    })
+                }
+            }
+        }
+    }
+
+    pub async fn send_block(&mut self, block: &Block) -> Result<(), ClickhouseNativeError> {
+        let mut buf = BytesMut::new();
+        encode_block(&mut buf, block, self.server.revision);
+        self.send(&buf).await
+    }
+
+    /// Signals the end of the inserted data and waits for the server to confirm the insert.
+    pub async fn finish_insert(&mut self) -> Result<(), ClickhouseNativeError> {
+        self.send_block(&Block::default()).await?;
+
+        loop {
+            match read_varint(&mut self.stream).await? {
+                server::END_OF_STREAM => return Ok(()),
+                server::PROGRESS => self.skip_progress().await?,
+                server::PROFILE_INFO => self.skip_profile_info().await?,
+                server::DATA => {
+                    self.read_header_block().await?;
+                }
+                server::EXCEPTION => return Err(read_exception(&mut self.stream).await?),
+                packet => {
+                    return Err(ClickhouseNativeError::UnexpectedPacket {
+                        packet,
+                        context: "finishing the insert",
+                    })
+                }
+            }
+        }
+    }
+
+    async fn send(&mut self, buf: &[u8]) -> Result<(), ClickhouseNativeError> {
+        let stream = self.stream.get_mut();
+        stream.write_all(buf).await?;
+        stream.flush().await?;
+        Ok(())
+    }
+
+    async fn read_header_block(&mut self) -> Result<Vec<ColumnHeader>, ClickhouseNativeError> {
+        let revision = self.server.revision;
+        if revision >= DBMS_MIN_REVISION_WITH_TEMPORARY_TABLES {
+            let _table_name = read_string(&mut self.stream).await?;
+        }
+        if revision >= DBMS_MIN_REVISION_WITH_BLOCK_INFO {
+            loop {
+                match read_varint(&mut self.stream).await? {
+                    0 => break,
+                    1 => {
+                        let _is_overflows = self.stream.read_u8().await?;
+                    }
+                    2 => {
+                        let _bucket_num = self.stream.read_i32_le().await?;
+                    }
+                    field => {
+                        return Err(ClickhouseNativeError::UnexpectedPacket {
+                            packet: field,
+                            context: "reading block info",
+                        })
+                    }
+                }
+            }
+        }
+
+        let columns = read_varint(&mut self.stream).await?;
+        let rows = read_varint(&mut self.stream).await?;
+        if rows != 0 {
+            // Only header blocks are expected while inserting, and those never carry rows.
+            return Err(ClickhouseNativeError::UnexpectedPacket {
+                packet: server::DATA,
+                context: "reading the table structure",
+            });
+        }
+
+        let mut headers = Vec::with_capacity(columns as usize);
+        for _ in 0..columns {
+            let name = read_string(&mut self.stream).await?;
+            let type_name = read_string(&mut self.stream).await?;
+            let column_type = type_name.parse::<ColumnType>().map_err(|source| {
+                ClickhouseNativeError::UnsupportedColumn {
+                    column: name.clone(),
+                    source,
+                }
+            })?;
+            headers.push(ColumnHeader {
+                name,
+                type_name,
+                column_type,
+            });
+        }
+        Ok(headers)
+    }
+
+    async fn skip_progress(&mut self) -> Result<(), ClickhouseNativeError> {
+        let _rows = read_varint(&mut self.stream).await?;
+        let _bytes = read_varint(&mut self.stream).await?;
+        if self.server.revision >= DBMS_MIN_REVISION_WITH_TOTAL_ROWS_IN_PROGRESS {
+            let _total_rows = read_varint(&mut self.stream).await?;
+        }
+        Ok(())
+    }
+
+    async fn skip_profile_info(&mut self) -> Result<(), ClickhouseNativeError> {
+        let _rows = read_varint(&mut self.stream).await?;
+        let _blocks = read_varint(&mut self.stream).await?;
+        let _bytes = read_varint(&mut self.stream).await?;
+        let _applied_limit = self.stream.read_u8().await?;
+        let _rows_before_limit = read_varint(&mut self.stream).await?;
+        let _calculated_rows_before_limit = self.stream.read_u8().await?;
+        Ok(())
+    }
+}
+
+fn encode_block(buf: &mut BytesMut, block: &Block, revision: u64) {
+    put_varint(buf, client::DATA);
+    if revision >= DBMS_MIN_REVISION_WITH_TEMPORARY_TABLES {
+        put_string(buf, "");
+    }
+    if revision >= DBMS_MIN_REVISION_WITH_BLOCK_INFO {
+        put_varint(buf, 1);
+        buf.put_u8(0); // is_overflows
+        put_varint(buf, 2);
+        buf.put_i32_le(-1); // bucket_num
+        put_varint(buf, 0);
+    }
+    put_varint(buf, block.columns.len() as u64);
+    put_varint(buf, block.rows as u64);
+    for column in &block.columns {
+        put_string(buf, &column.name);
+        put_string(buf, &column.type_name);
+        buf.extend_from_slice(&column.data);
+    }
+}
+
+pub fn put_varint(buf: &mut BytesMut, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf.put_u8(byte);
+            return;
+        }
+        buf.put_u8(byte | 0x80);
+    }
+}
+
+pub fn put_bytes(buf: &mut BytesMut, value: &[u8]) {
+    put_varint(buf, value.len() as u64);
+    buf.extend_from_slice(value);
+}
+
+pub fn put_string(buf: &mut BytesMut, value: &str) {
+    put_bytes(buf, value.as_bytes());
+}
+
+async fn read_varint<R: AsyncRead + Unpin>(reader: &mut R) -> std::io::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = reader.read_u8().await?;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "varint is too long",
+    ))
+}
+
+async fn read_string<R: AsyncRead + Unpin>(reader: &mut R) -> std::io::Result<String> {
+    let len = read_varint(reader).await? as usize;
+    let mut buf = vec![0; len];
+    reader.read_exact(&mut buf).await?;
+    String::from_utf8(buf)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+}
+
+async fn read_exception<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> std::io::Result<ClickhouseNativeError> {
+    let code = reader.read_i32_le().await?;
+    let name = read_string(reader).await?;
+    let message = read_string(reader).await?;
+    let _stack_trace = read_string(reader).await?;
+    // Nested exceptions carry the root cause, but the outermost one already summarizes it.
+    let mut has_nested = reader.read_u8().await? != 0;
+    while has_nested {
+        let _code = reader.read_i32_le().await?;
+        for _ in 0..3 {
+            read_string(reader).await?;
+        }
+        has_nested = reader.read_u8().await? != 0;
+    }
+    Ok(ClickhouseNativeError::Server {
+        code,
+        name,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::duplex;
+
+    use super::*;
+
+    #[test]
+    fn encodes_varints() {
+        let cases: &[(u64, &[u8])] = &[
+            (0, &[0x00]),
+            (1, &[0x01]),
+            (127, &[0x7f]),
+            (128, &[0x80, 0x01]),
+            (300, &[0xac, 0x02]),
+            (54405, &[0x85, 0xa9, 0x03]),
+        ];
+        for (value, expected) in cases {
+            let mut buf = BytesMut::new();
+            put_varint(&mut buf, *value);
+            assert_eq!(&buf[..], *expected, "encoding {}", value);
+        }
+    }
+
+    #[tokio::test]
+    async fn varints_roundtrip() {
+        for value in [0, 1, 255, 54405, u32::MAX as u64, u64::MAX] {
+            let mut buf = BytesMut::new();
+            put_varint(&mut buf, value);
+            assert_eq!(read_varint(&mut &buf[..]).await.unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn encodes_empty_block() {
+        let mut buf = BytesMut::new();
+        encode_block(&mut buf, &Block::default(), CLIENT_REVISION);
+        assert_eq!(&buf[..], &[2, 0, 1, 0, 2, 0xff, 0xff, 0xff, 0xff, 0, 0, 0]);
+    }
+
+    #[tokio::test]
+    async fn reads_exception() {
+        let mut buf = BytesMut::new();
+        buf.put_i32_le(60);
+        put_string(&mut buf, "DB::Exception");
+        put_string(&mut buf, "Table default.missing does not exist");
+        put_string(&mut buf, "");
+        buf.put_u8(0);
+
+        let error = read_exception(&mut &buf[..]).await.unwrap();
+        assert!(!error.is_retriable());
+        assert_eq!(
+            error.to_string(),
+            "ClickHouse returned an exception: Code: 60. DB::Exception: Table default.missing does not exist"
+        );
+    }
+
+    #[tokio::test]
+    async fn handshake_and_insert() {
+        let (client, mut server) = duplex(4096);
+        let options = ConnectionOptions {
+            host: "localhost".into(),
+            port: 9000,
+            tls: MaybeTlsSettings::Raw(()),
+            database: "default".into(),
+            user: "default".into(),
+            password: String::new(),
+        };
+
+        let server = tokio::spawn(async move {
+            // Client hello.
+            assert_eq!(read_varint(&mut server).await.unwrap(), client::HELLO);
+            assert_eq!(read_string(&mut server).await.unwrap(), CLIENT_NAME);
+            for _ in 0..3 {
+                read_varint(&mut server).await.unwrap();
+            }
+            assert_eq!(read_string(&mut server).await.unwrap(), "default");
+            assert_eq!(read_string(&mut server).await.unwrap(), "default");
+            assert_eq!(read_string(&mut server).await.unwrap(), "");
+
+            let mut reply = BytesMut::new();
+            put_varint(&mut reply, server::HELLO);
+            put_string(&mut reply, "ClickHouse");
+            put_varint(&mut reply, 24);
+            put_varint(&mut reply, 8);
+            put_varint(&mut reply, 54470);
+            put_string(&mut reply, "UTC");
+            put_string(&mut reply, "clickhouse-server");
+            put_varint(&mut reply, 1);
+            server.write_all(&reply).await.unwrap();
+
+            // Ping.
+            assert_eq!(read_varint(&mut server).await.unwrap(), client::PING);
+            let mut reply = BytesMut::new();
+            put_varint(&mut reply, server::PONG);
+            server.write_all(&reply).await.unwrap();
+        });
+
+        let mut connection = Connection::handshake(client, &options).await.unwrap();
+        assert_eq!(connection.server().name, "ClickHouse");
+        assert_eq!(connection.server().revision, CLIENT_REVISION);
+        assert_eq!(connection.server().timezone.as_deref(), Some("UTC"));
+        connection.ping().await.unwrap();
+
+        server.await.unwrap();
+    }
+}
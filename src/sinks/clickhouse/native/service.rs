@@ -0,0 +1,307 @@
+//! Service implementation for the `clickhouse_native` sink.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use bytes::BytesMut;
+use tokio::net::TcpStream;
+use vector_lib::lookup::{OwnedValuePath, PathPrefix};
+
+use super::{
+    columns::encode_column,
+    protocol::{
+        Block, ClickhouseNativeError, ColumnHeader, Connection, ConnectionOptions, EncodedColumn,
+    },
+};
+use crate::{sinks::prelude::*, tls::MaybeTlsStream};
+
+type NativeConnection = Connection<MaybeTlsStream<TcpStream>>;
+
+/// A batch of events to insert into a single table.
+#[derive(Clone)]
+pub(super) struct ClickhouseNativeRequest {
+    pub(super) database: String,
+    pub(super) table: String,
+    pub(super) events: Vec<LogEvent>,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for ClickhouseNativeRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for ClickhouseNativeRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct ClickhouseNativeResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for ClickhouseNativeResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct ClickhouseNativeRetryLogic;
+
+impl RetryLogic for ClickhouseNativeRetryLogic {
+    type Error = ClickhouseNativeError;
+    type Response = ClickhouseNativeResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
+
+/// How table columns are filled from event fields.
+#[derive(Clone, Debug, Default)]
+pub(super) struct ColumnMapping {
+    /// Explicit column to field mapping. When empty, every column of the table is filled from the
+    /// top-level field of the same name.
+    fields: HashMap<String, OwnedValuePath>,
+}
+
+impl ColumnMapping {
+    pub(super) const fn new(fields: HashMap<String, OwnedValuePath>) -> Self {
+        Self { fields }
+    }
+
+    fn lookup<'a>(&self, log: &'a LogEvent, column: &str) -> Option<&'a Value> {
+        if self.fields.is_empty() {
+            log.as_map().and_then(|fields| fields.get(column))
+        } else {
+            self.fields
+                .get(column)
+                .and_then(|path| log.get((PathPrefix::Event, path)))
+        }
+    }
+
+    /// Builds the `INSERT` query for the given table.
+    ///
+    /// With an explicit mapping only the mapped columns are listed, so the server applies the
+    /// `DEFAULT` expressions of the remaining ones.
+    pub(super) fn insert_query(&self, database: &str, table: &str) -> String {
+        let mut query = format!(
+            "INSERT INTO {}.{}",
+            quote_identifier(database),
+            quote_identifier(table)
+        );
+        if !self.fields.is_empty() {
+            let mut columns = self.fields.keys().map(String::as_str).collect::<Vec<_>>();
+            columns.sort_unstable();
+            let columns = columns
+                .into_iter()
+                .map(quote_identifier)
+                .collect::<Vec<_>>()
+                .join(", ");
+            query.push_str(&format!(" ({})", columns));
+        }
+        query.push_str(" VALUES");
+        query
+    }
+
+    pub(super) fn build_block(
+        &self,
+        headers: &[ColumnHeader],
+        events: &[LogEvent],
+    ) -> Result<Block, ClickhouseNativeError> {
+        let columns = headers
+            .iter()
+            .map(|header| {
+                let values = events
+                    .iter()
+                    .map(|log| self.lookup(log, &header.name))
+                    .collect::<Vec<_>>();
+                let mut data = BytesMut::new();
+                encode_column(&header.column_type, &values, &mut data).map_err(|message| {
+                    ClickhouseNativeError::InvalidValue {
+                        column: header.name.clone(),
+                        message,
+                    }
+                })?;
+                Ok(EncodedColumn {
+                    name: header.name.clone(),
+                    type_name: header.type_name.clone(),
+                    data,
+                })
+            })
+            .collect::<Result<Vec<_>, ClickhouseNativeError>>()?;
+
+        Ok(Block {
+            rows: events.len(),
+            columns,
+        })
+    }
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("`{}`", identifier.replace('\\', "\\\\").replace('`', "\\`"))
+}
+
+/// Sends inserts over a small pool of reusable native connections.
+#[derive(Clone)]
+pub(super) struct ClickhouseNativeService {
+    options: Arc<ConnectionOptions>,
+    mapping: Arc<ColumnMapping>,
+    idle: Arc<Mutex<Vec<NativeConnection>>>,
+}
+
+impl ClickhouseNativeService {
+    pub(super) fn new(options: ConnectionOptions, mapping: ColumnMapping) -> Self {
+        Self {
+            options: Arc::new(options),
+            mapping: Arc::new(mapping),
+            idle: Arc::default(),
+        }
+    }
+
+    async fn insert(
+        connection: &mut NativeConnection,
+        mapping: &ColumnMapping,
+        request: &ClickhouseNativeRequest,
+    ) -> Result<usize, ClickhouseNativeError> {
+        let query = mapping.insert_query(&request.database, &request.table);
+        let headers = connection.start_insert(&query).await?;
+        let block = mapping.build_block(&headers, &request.events)?;
+        let byte_size = block.columns.iter().map(|column| column.data.len()).sum();
+        connection.send_block(&block).await?;
+        connection.finish_insert().await?;
+        Ok(byte_size)
+    }
+}
+
+impl Service<ClickhouseNativeRequest> for ClickhouseNativeService {
+    type Response = ClickhouseNativeResponse;
+    type Error = ClickhouseNativeError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: ClickhouseNativeRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let idle = service.idle.lock().expect("poisoned lock").pop();
+            let mut connection = match idle {
+                Some(connection) => connection,
+                None => Connection::connect(&service.options).await?,
+            };
+
+            // A connection is only returned to the pool once the insert went through, as any
+            // failure can leave it in the middle of a query.
+            let byte_size = Self::insert(&mut connection, &service.mapping, &request).await?;
+            service.idle.lock().expect("poisoned lock").push(connection);
+
+            Ok(ClickhouseNativeResponse {
+                events_byte_size: request
+                    .metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+                byte_size,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vrl::{event_path, value};
+
+    use super::*;
+
+    fn header(name: &str, type_name: &str) -> ColumnHeader {
+        ColumnHeader {
+            name: name.into(),
+            type_name: type_name.into(),
+            column_type: type_name.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn insert_query_lists_mapped_columns() {
+        let mapping = ColumnMapping::default();
+        assert_eq!(
+            mapping.insert_query("db", "my`table"),
+            "INSERT INTO `db`.`my\\`table` VALUES"
+        );
+
+        let mapping = ColumnMapping::new(HashMap::from([
+            (
+                "message".to_string(),
+                OwnedValuePath::try_from("msg".to_string()).unwrap(),
+            ),
+            (
+                "host".to_string(),
+                OwnedValuePath::try_from("host".to_string()).unwrap(),
+            ),
+        ]));
+        assert_eq!(
+            mapping.insert_query("db", "logs"),
+            "INSERT INTO `db`.`logs` (`host`, `message`) VALUES"
+        );
+    }
+
+    #[test]
+    fn builds_block_from_mapped_fields() {
+        let mut log = LogEvent::from(value!({"nested": {"count": 3}, "message": "hello"}));
+        log.insert(event_path!("ignored"), true);
+
+        let mapping = ColumnMapping::new(HashMap::from([(
+            "count".to_string(),
+            OwnedValuePath::try_from("nested.count".to_string()).unwrap(),
+        )]));
+        let block = mapping
+            .build_block(&[header("count", "UInt8")], &[log.clone()])
+            .unwrap();
+        assert_eq!(block.rows, 1);
+        assert_eq!(block.columns.len(), 1);
+        assert_eq!(&block.columns[0].data[..], &[3]);
+
+        let block = ColumnMapping::default()
+            .build_block(
+                &[
+                    header("message", "String"),
+                    header("missing", "Nullable(Int8)"),
+                ],
+                &[log],
+            )
+            .unwrap();
+        assert_eq!(&block.columns[0].data[..], b"\x05hello");
+        assert_eq!(&block.columns[1].data[..], &[1, 0]);
+        assert_eq!(block.columns[1].type_name, "Nullable(Int8)");
+    }
+
+    #[test]
+    fn invalid_values_are_not_retried() {
+        let log = LogEvent::from(value!({"count": "many"}));
+        let error = ColumnMapping::default()
+            .build_block(&[header("count", "UInt8")], &[log])
+            .unwrap_err();
+        assert!(!ClickhouseNativeRetryLogic.is_retriable_error(&error));
+    }
+}
@@ -0,0 +1,146 @@
+//! Implementation of the `clickhouse_native` sink.
+
+use std::num::NonZeroUsize;
+
+use super::service::ClickhouseNativeRequest;
+use crate::sinks::prelude::*;
+
+pub(super) struct ClickhouseNativeSink<S> {
+    batch_settings: BatcherSettings,
+    service: S,
+    database: Template,
+    table: Template,
+    transformer: Transformer,
+}
+
+impl<S> ClickhouseNativeSink<S>
+where
+    S: Service<ClickhouseNativeRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    pub(super) const fn new(
+        batch_settings: BatcherSettings,
+        service: S,
+        database: Template,
+        table: Template,
+        transformer: Transformer,
+    ) -> Self {
+        Self {
+            batch_settings,
+            service,
+            database,
+            table,
+            transformer,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let transformer = self.transformer;
+
+        input
+            .batched_partitioned(KeyPartitioner::new(self.database, self.table), || {
+                batch_settings.as_byte_size_config()
+            })
+            .filter_map(|(key, batch)| async move { key.map(move |k| (k, batch)) })
+            .map(|(key, events)| build_request(key, events, &transformer))
+            .into_driver(self.service)
+            .protocol("tcp")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for ClickhouseNativeSink<S>
+where
+    S: Service<ClickhouseNativeRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+/// Builds the insert request for a batch of events.
+///
+/// Events are kept as structured logs rather than encoded up front, because the column layout is
+/// only known once the server has announced the structure of the target table.
+fn build_request(
+    key: PartitionKey,
+    mut events: Vec<Event>,
+    transformer: &Transformer,
+) -> ClickhouseNativeRequest {
+    let finalizers = events.take_finalizers();
+    let mut byte_size = telemetry().create_request_count_byte_size();
+    let mut json_size = 0;
+    let logs = events
+        .into_iter()
+        .map(|mut event| {
+            transformer.transform(&mut event);
+            let event_json_size = event.estimated_json_encoded_size_of();
+            json_size += event_json_size.get();
+            byte_size.add_event(&event, event_json_size);
+            event.into_log()
+        })
+        .collect::<Vec<_>>();
+
+    // The encoded size is only known once the block is built, so the JSON size stands in for it.
+    let metadata = RequestMetadataBuilder::new(logs.len(), logs.size_of(), byte_size)
+        .with_request_size(NonZeroUsize::new(json_size).unwrap_or(NonZeroUsize::MIN));
+
+    ClickhouseNativeRequest {
+        database: key.database,
+        table: key.table,
+        events: logs,
+        finalizers,
+        metadata,
+    }
+}
+
+/// PartitionKey used to partition events by (database, table) pair.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+struct PartitionKey {
+    database: String,
+    table: String,
+}
+
+/// KeyPartitioner that partitions events by (database, table) pair.
+struct KeyPartitioner {
+    database: Template,
+    table: Template,
+}
+
+impl KeyPartitioner {
+    const fn new(database: Template, table: Template) -> Self {
+        Self { database, table }
+    }
+
+    fn render(template: &Template, item: &Event, field: &'static str) -> Option<String> {
+        template
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+impl Partitioner for KeyPartitioner {
+    type Item = Event;
+    type Key = Option<PartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let database = Self::render(&self.database, item, "database_key")?;
+        let table = Self::render(&self.table, item, "table_key")?;
+        Some(PartitionKey { database, table })
+    }
+}
@@ -19,6 +19,7 @@ use http::{
     Request, StatusCode, Uri,
 };
 use snafu::ResultExt;
+use std::time::Duration;
 
 #[derive(Debug, Default, Clone)]
 pub struct ClickhouseRetryLogic {
@@ -57,6 +58,10 @@ impl RetryLogic for ClickhouseRetryLogic {
             _ => self.inner.should_retry_response(&response.http_response),
         }
     }
+
+    fn retry_after(&self, response: &Self::Response) -> Option<Duration> {
+        self.inner.retry_after(&response.http_response)
+    }
 }
 
 #[derive(Debug, Clone)]
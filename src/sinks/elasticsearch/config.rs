@@ -92,6 +92,9 @@ pub struct ElasticsearchConfig {
 
     /// Whether or not to retry successful requests containing partial failures.
     ///
+    /// Only the documents which failed with a retriable error, such as `429 Too Many Requests`, are
+    /// sent again. Documents rejected by Elasticsearch are never retried.
+    ///
     /// To avoid duplicates in Elasticsearch, please use option `id_key`.
     #[serde(default)]
     #[configurable(metadata(docs::advanced))]
@@ -1,5 +1,7 @@
+use std::sync::Arc;
+
 use bytes::Bytes;
-use vector_lib::EstimatedJsonEncodedSizeOf;
+use vector_lib::{config::telemetry, EstimatedJsonEncodedSizeOf};
 use vector_lib::{json_size::JsonSize, request_metadata::RequestMetadata};
 
 use crate::{
@@ -7,11 +9,11 @@ use crate::{
     sinks::{
         elasticsearch::{
             encoder::{ElasticsearchEncoder, ProcessedEvent},
-            service::ElasticsearchRequest,
+            service::{bulk_payload, BulkItems, ElasticsearchRequest},
         },
         util::{
-            metadata::RequestMetadataBuilder, request_builder::EncodeResult, Compression,
-            RequestBuilder,
+            encoding::Encoder, metadata::RequestMetadataBuilder, request_builder::EncodeResult,
+            Compression, RequestBuilder,
         },
     },
 };
@@ -23,16 +25,39 @@ pub struct ElasticsearchRequestBuilder {
 }
 
 pub struct Metadata {
-    finalizers: EventFinalizers,
+    finalizers: Vec<EventFinalizers>,
     batch_size: usize,
     events_byte_size: JsonSize,
 }
 
+/// The payload of a bulk request, along with its documents to send them again on their own.
+pub struct BulkPayload {
+    payload: Bytes,
+    documents: Vec<Bytes>,
+}
+
+impl From<Bytes> for BulkPayload {
+    // Required by `RequestBuilder`, though unused as `encode_events` is overridden to split the
+    // documents.
+    fn from(payload: Bytes) -> Self {
+        Self {
+            payload,
+            documents: Vec::new(),
+        }
+    }
+}
+
+impl AsRef<[u8]> for BulkPayload {
+    fn as_ref(&self) -> &[u8] {
+        self.payload.as_ref()
+    }
+}
+
 impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
     type Metadata = Metadata;
     type Events = Vec<ProcessedEvent>;
     type Encoder = ElasticsearchEncoder;
-    type Payload = Bytes;
+    type Payload = BulkPayload;
     type Request = ElasticsearchRequest;
     type Error = std::io::Error;
 
@@ -57,22 +82,51 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
         let metadata_builder = RequestMetadataBuilder::from_events(&events);
 
         let es_metadata = Metadata {
-            finalizers: events.take_finalizers(),
+            finalizers: events
+                .iter_mut()
+                .map(Finalizable::take_finalizers)
+                .collect(),
             batch_size: events.len(),
             events_byte_size,
         };
         (es_metadata, metadata_builder, events)
     }
 
+    fn encode_events(
+        &self,
+        events: Self::Events,
+    ) -> Result<EncodeResult<Self::Payload>, Self::Error> {
+        let mut documents = Vec::with_capacity(events.len());
+        let mut json_size = telemetry().create_request_count_byte_size();
+        for event in events {
+            let mut document = Vec::new();
+            let (_, event_json_size) = self.encoder.encode_input(vec![event], &mut document)?;
+            json_size += event_json_size;
+            documents.push(Bytes::from(document));
+        }
+
+        let uncompressed_byte_size = documents.iter().map(Bytes::len).sum();
+        let payload = BulkPayload {
+            payload: bulk_payload(&documents, self.compression)?,
+            documents,
+        };
+        Ok(if self.compression.is_compressed() {
+            EncodeResult::compressed(payload, uncompressed_byte_size, json_size)
+        } else {
+            EncodeResult::uncompressed(payload, json_size)
+        })
+    }
+
     fn build_request(
         &self,
         es_metadata: Self::Metadata,
         metadata: RequestMetadata,
         payload: EncodeResult<Self::Payload>,
     ) -> Self::Request {
+        let BulkPayload { payload, documents } = payload.into_payload();
         ElasticsearchRequest {
-            payload: payload.into_payload(),
-            finalizers: es_metadata.finalizers,
+            payload,
+            bulk: Arc::new(BulkItems::new(documents, es_metadata.finalizers)),
             batch_size: es_metadata.batch_size,
             events_byte_size: es_metadata.events_byte_size,
             metadata,
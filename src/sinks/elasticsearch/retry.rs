@@ -9,13 +9,20 @@ use crate::{
     },
 };
 
+/// Whether a request, or a document of a bulk request, failing with the given status may succeed
+/// when sent again: those are backpressure and server errors.
+pub(super) fn is_retriable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (status.is_server_error() && status != StatusCode::NOT_IMPLEMENTED)
+}
+
 #[derive(Deserialize, Debug)]
-struct EsResultResponse {
+pub(super) struct EsResultResponse {
     items: Vec<EsResultItem>,
 }
 
 impl EsResultResponse {
-    fn parse(body: &str) -> Result<Self, String> {
+    pub(super) fn parse(body: &str) -> Result<Self, String> {
         serde_json::from_str::<EsResultResponse>(body).map_err(|json_error| {
             format!(
                 "some messages failed, could not parse response, error: {}",
@@ -24,6 +31,21 @@ impl EsResultResponse {
         })
     }
 
+    /// Returns iterator over the status codes and optional error details of all items, in the
+    /// order of the documents of the request.
+    pub(super) fn iter_items(
+        &self,
+    ) -> impl Iterator<Item = (Option<StatusCode>, Option<&EsErrorDetails>)> {
+        self.items.iter().map(|item| {
+            (
+                item.result()
+                    .status
+                    .and_then(|status| StatusCode::from_u16(status).ok()),
+                item.result().error.as_ref(),
+            )
+        })
+    }
+
     /// Returns iterator over status codes for items and optional error details.
     fn iter_status(&self) -> impl Iterator<Item = (StatusCode, Option<&EsErrorDetails>)> {
         self.items.iter().filter_map(|item| {
@@ -75,10 +97,10 @@ struct EsIndexResult {
 }
 
 #[derive(Deserialize, Debug)]
-struct EsErrorDetails {
-    reason: String,
+pub(super) struct EsErrorDetails {
+    pub(super) reason: String,
     #[serde(rename = "type")]
-    err_type: String,
+    pub(super) err_type: String,
 }
 
 #[derive(Clone)]
@@ -99,6 +121,7 @@ impl RetryLogic for ElasticsearchRetryLogic {
 
         match status {
             StatusCode::TOO_MANY_REQUESTS => RetryAction::Retry("too many requests".into()),
+            // Kept apart from the other server errors, see `is_retriable_status`.
             StatusCode::NOT_IMPLEMENTED => {
                 RetryAction::DontRetry("endpoint not implemented".into())
             }
@@ -122,13 +145,11 @@ impl RetryLogic for ElasticsearchRetryLogic {
                         Ok(resp) => {
                             if self.retry_partial {
                                 // We will retry if there exists at least one item that
-                                // failed with a retriable error.
-                                // Those are backpressure and server errors.
-                                if let Some((status, error)) =
-                                    resp.iter_status().find(|(status, _)| {
-                                        *status == StatusCode::TOO_MANY_REQUESTS
-                                            || status.is_server_error()
-                                    })
+                                // failed with a retriable error. Only those items are sent
+                                // again, the others having been finalized by the service.
+                                if let Some((status, error)) = resp
+                                    .iter_status()
+                                    .find(|(status, _)| is_retriable_status(*status))
                                 {
                                     let msg = if let Some(error) = error {
                                         format!(
@@ -1,11 +1,12 @@
 use std::{
-    sync::Arc,
+    io::{self, Write},
+    sync::{Arc, Mutex},
     task::{Context, Poll},
 };
 
 use bytes::Bytes;
 use futures::future::BoxFuture;
-use http::{Response, Uri};
+use http::{Response, StatusCode, Uri};
 use hyper::{service::Service, Body, Request};
 use tower::ServiceExt;
 use vector_lib::stream::DriverResponse;
@@ -15,21 +16,146 @@ use vector_lib::{
     request_metadata::{GroupedCountByteSize, MetaDescriptive, RequestMetadata},
 };
 
-use super::{ElasticsearchCommon, ElasticsearchConfig};
+use super::{
+    retry::{is_retriable_status, EsResultResponse},
+    ElasticsearchCommon, ElasticsearchConfig,
+};
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     http::HttpClient,
+    internal_events::ElasticsearchBulkItemError,
     sinks::util::{
         auth::Auth,
         http::{HttpBatchService, RequestConfig},
-        Compression, ElementCount,
+        Compression, Compressor, ElementCount,
     },
 };
 
+/// Concatenates the given documents into the payload of a bulk request.
+pub(super) fn bulk_payload<'a>(
+    documents: impl IntoIterator<Item = &'a Bytes>,
+    compression: Compression,
+) -> io::Result<Bytes> {
+    let mut compressor = Compressor::from(compression);
+    for document in documents {
+        compressor.write_all(document)?;
+    }
+    Ok(compressor.finish()?.freeze())
+}
+
+/// The documents of a bulk request, each finalized on its own according to the outcome Elasticsearch
+/// reports for it.
+///
+/// The documents are shared by the retries of the request, which only send the documents still
+/// pending, that is the ones which failed with a retriable error.
+#[derive(Debug)]
+pub struct BulkItems {
+    /// The action and source lines of each document, uncompressed.
+    documents: Vec<Bytes>,
+    /// The finalizers of each document, taken once it is finalized.
+    finalizers: Mutex<Vec<Option<EventFinalizers>>>,
+}
+
+impl BulkItems {
+    pub(super) fn new(documents: Vec<Bytes>, finalizers: Vec<EventFinalizers>) -> Self {
+        assert_eq!(documents.len(), finalizers.len());
+        Self {
+            documents,
+            finalizers: Mutex::new(finalizers.into_iter().map(Some).collect()),
+        }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// The indices of the documents not finalized yet.
+    fn pending(&self) -> Vec<usize> {
+        let finalizers = self.finalizers.lock().expect("poisoned lock");
+        (0..finalizers.len())
+            .filter(|index| finalizers[*index].is_some())
+            .collect()
+    }
+
+    fn payload(&self, indices: &[usize], compression: Compression) -> io::Result<Bytes> {
+        bulk_payload(
+            indices.iter().map(|index| &self.documents[*index]),
+            compression,
+        )
+    }
+
+    fn finalize(&self, indices: impl IntoIterator<Item = usize>, status: EventStatus) {
+        let mut finalizers = self.finalizers.lock().expect("poisoned lock");
+        for index in indices {
+            if let Some(finalizers) = finalizers[index].take() {
+                finalizers.update_status(status);
+            }
+        }
+    }
+
+    /// Finalizes the documents which were sent, in the given order, according to the response to
+    /// the request. The documents failing with a retriable error are left pending.
+    fn resolve(&self, sent: &[usize], response: &Response<Bytes>) {
+        let status = response.status();
+        if !status.is_success() {
+            if !is_retriable_status(status) {
+                self.finalize(sent.iter().copied(), EventStatus::Rejected);
+            }
+            return;
+        }
+
+        let body = String::from_utf8_lossy(response.body());
+        if !body.contains("\"errors\":true") {
+            self.finalize(sent.iter().copied(), EventStatus::Delivered);
+            return;
+        }
+
+        let Ok(result) = EsResultResponse::parse(&body) else {
+            self.finalize(sent.iter().copied(), EventStatus::Rejected);
+            return;
+        };
+
+        let mut delivered = Vec::new();
+        let mut rejected = Vec::new();
+        for (index, (status, error)) in sent.iter().zip(result.iter_items()) {
+            match (status, error) {
+                (Some(status), None) if status.is_success() => delivered.push(*index),
+                (None, None) => delivered.push(*index),
+                (status, error) => {
+                    let status = status.unwrap_or(StatusCode::BAD_REQUEST);
+                    let retriable = is_retriable_status(status);
+                    emit!(ElasticsearchBulkItemError {
+                        status,
+                        document_error_type: error.map(|error| error.err_type.as_str()),
+                        reason: error.map(|error| error.reason.as_str()),
+                        retriable,
+                    });
+                    if !retriable {
+                        rejected.push(*index);
+                    }
+                }
+            }
+        }
+        self.finalize(delivered, EventStatus::Delivered);
+        self.finalize(rejected, EventStatus::Rejected);
+    }
+}
+
+impl Drop for BulkItems {
+    fn drop(&mut self) {
+        // The documents still pending once the request is done failed with a retriable error,
+        // either because the request ran out of retries, or because partial retries are disabled.
+        let finalizers = self.finalizers.get_mut().expect("poisoned lock");
+        for finalizers in finalizers.drain(..).flatten() {
+            finalizers.update_status(EventStatus::Errored);
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ElasticsearchRequest {
     pub payload: Bytes,
-    pub finalizers: EventFinalizers,
+    pub bulk: Arc<BulkItems>,
     pub batch_size: usize,
     pub events_byte_size: JsonSize,
     pub metadata: RequestMetadata,
@@ -37,7 +163,13 @@ pub struct ElasticsearchRequest {
 
 impl ByteSizeOf for ElasticsearchRequest {
     fn allocated_bytes(&self) -> usize {
-        self.payload.allocated_bytes() + self.finalizers.allocated_bytes()
+        self.payload.allocated_bytes()
+            + self
+                .bulk
+                .documents
+                .iter()
+                .map(ByteSizeOf::allocated_bytes)
+                .sum::<usize>()
     }
 }
 
@@ -49,7 +181,8 @@ impl ElementCount for ElasticsearchRequest {
 
 impl Finalizable for ElasticsearchRequest {
     fn take_finalizers(&mut self) -> EventFinalizers {
-        std::mem::take(&mut self.finalizers)
+        // The documents are finalized one by one by the service, see `BulkItems`.
+        EventFinalizers::default()
     }
 }
 
@@ -72,6 +205,7 @@ pub struct ElasticsearchService {
         BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
         ElasticsearchRequest,
     >,
+    compression: Compression,
 }
 
 impl ElasticsearchService {
@@ -79,6 +213,7 @@ impl ElasticsearchService {
         http_client: HttpClient<Body>,
         http_request_builder: HttpRequestBuilder,
     ) -> ElasticsearchService {
+        let compression = http_request_builder.compression;
         let http_request_builder = Arc::new(http_request_builder);
         let batch_service = HttpBatchService::new(http_client, move |req| {
             let request_builder = Arc::clone(&http_request_builder);
@@ -86,7 +221,10 @@ impl ElasticsearchService {
                 Box::pin(async move { request_builder.build_request(req).await });
             future
         });
-        ElasticsearchService { batch_service }
+        ElasticsearchService {
+            batch_service,
+            compression,
+        }
     }
 }
 
@@ -184,11 +322,20 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, mut req: ElasticsearchRequest) -> Self::Future {
         let mut http_service = self.batch_service.clone();
+        let compression = self.compression;
         Box::pin(async move {
             http_service.ready().await?;
             let events_byte_size =
                 std::mem::take(req.metadata_mut()).into_events_estimated_json_encoded_byte_size();
+
+            // Retries only send the documents which previous attempts failed to write.
+            let bulk = Arc::clone(&req.bulk);
+            let sent = bulk.pending();
+            if sent.len() < bulk.len() {
+                req.payload = bulk.payload(&sent, compression)?;
+            }
             let http_response = http_service.call(req).await?;
+            bulk.resolve(&sent, &http_response);
 
             let event_status = get_event_status(&http_response);
             Ok(ElasticsearchResponse {
@@ -231,3 +378,78 @@ fn get_event_status(response: &Response<Bytes>) -> EventStatus {
         EventStatus::Rejected
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer};
+
+    use super::*;
+
+    fn bulk_items(count: usize) -> (BulkItems, Vec<BatchStatusReceiver>) {
+        let (finalizers, receivers) = (0..count)
+            .map(|_| {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                (EventFinalizers::new(EventFinalizer::new(batch)), receiver)
+            })
+            .unzip();
+        let documents = (0..count)
+            .map(|index| Bytes::from(format!("{{\"index\":{{}}}}\n{{\"n\":{index}}}\n")))
+            .collect();
+        (BulkItems::new(documents, finalizers), receivers)
+    }
+
+    fn response(status: u16, body: &str) -> Response<Bytes> {
+        Response::builder()
+            .status(status)
+            .body(Bytes::from(body.to_owned()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn finalizes_documents_individually() {
+        let (bulk, mut receivers) = bulk_items(3);
+        let sent = bulk.pending();
+        bulk.resolve(
+            &sent,
+            &response(
+                200,
+                r#"{"errors":true,"items":[
+                    {"index":{"status":201}},
+                    {"index":{"status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse"}}},
+                    {"index":{"status":429,"error":{"type":"es_rejected_execution_exception","reason":"queue full"}}}
+                ]}"#,
+            ),
+        );
+        assert_eq!(bulk.pending(), vec![2]);
+        assert_eq!(
+            bulk.payload(&bulk.pending(), Compression::None).unwrap(),
+            Bytes::from_static(b"{\"index\":{}}\n{\"n\":2}\n")
+        );
+
+        // The retry only contains the pending document.
+        bulk.resolve(
+            &bulk.pending(),
+            &response(
+                200,
+                r#"{"errors":false,"items":[{"index":{"status":201}}]}"#,
+            ),
+        );
+        assert!(bulk.pending().is_empty());
+
+        assert_eq!((&mut receivers[0]).await, BatchStatus::Delivered);
+        assert_eq!((&mut receivers[1]).await, BatchStatus::Rejected);
+        assert_eq!((&mut receivers[2]).await, BatchStatus::Delivered);
+    }
+
+    #[tokio::test]
+    async fn errors_pending_documents_when_dropped() {
+        let (bulk, receivers) = bulk_items(2);
+        bulk.resolve(&bulk.pending(), &response(503, ""));
+        assert_eq!(bulk.pending(), vec![0, 1]);
+
+        drop(bulk);
+        for receiver in receivers {
+            assert_eq!(receiver.await, BatchStatus::Errored);
+        }
+    }
+}
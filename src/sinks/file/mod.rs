@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::time::{Duration, Instant};
 
@@ -17,16 +18,20 @@ use tokio::{
 use tokio_util::codec::Encoder as _;
 use vector_lib::codecs::{
     encoding::{Framer, FramingConfig},
-    TextSerializerConfig,
+    BatchSerializerConfig, TextSerializerConfig,
 };
 use vector_lib::configurable::configurable_component;
 use vector_lib::{
+    event::EventFinalizers,
     internal_event::{CountByteSize, EventsSent, InternalEventHandle as _, Output, Registered},
+    json_size::JsonSize,
     EstimatedJsonEncodedSizeOf, TimeZone,
 };
 
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    codecs::{
+        BatchEncoder, Encoder, EncoderKind, EncodingConfigWithFraming, SinkType, Transformer,
+    },
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     event::{Event, EventStatus, Finalizable},
     expiring_hash_map::ExpiringHashMap,
@@ -38,8 +43,11 @@ use crate::{
 };
 
 mod bytes_path;
+mod rotation;
 
 use bytes_path::BytesPath;
+pub use rotation::RotationConfig;
+use rotation::{rotate_file, FileRotation};
 
 /// Configuration for the `file` sink.
 #[serde_as]
@@ -70,10 +78,31 @@ pub struct FileSinkConfig {
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
+    /// Encodes all the events written to a file at once, such as into a Parquet file, instead of
+    /// encoding events one by one.
+    ///
+    /// Events are buffered until the file is closed, after being idle for `idle_timeout_secs` or
+    /// when Vector shuts down. A file receiving events again after being closed is overwritten.
+    ///
+    /// When set, the `encoding.codec` and `framing` options are ignored, while the other
+    /// `encoding` options still apply. Batch codecs compress their output themselves, so
+    /// `compression` must be set to `none`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_encoding: Option<BatchSerializerConfig>,
+
     #[configurable(derived)]
     #[serde(default, skip_serializing_if = "crate::serde::is_default")]
     pub compression: Compression,
 
+    /// Rotates the files once they grow too large or too old, optionally compressing and
+    /// deleting the rotated files.
+    ///
+    /// Rotated files are renamed by appending the time of their rotation to their path, such as
+    /// `/tmp/vector.log.20240101T000000.000000Z`. Rotation is not supported with `batch_encoding`.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<RotationConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -97,7 +126,9 @@ impl GenerateConfig for FileSinkConfig {
             path: Template::try_from("/tmp/vector-%Y-%m-%d.log").unwrap(),
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
             compression: Default::default(),
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: Default::default(),
@@ -194,7 +225,11 @@ impl SinkConfig for FileSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type() & DataType::Log)
+        let input_type = match &self.batch_encoding {
+            Some(batch_encoding) => batch_encoding.input_type(),
+            None => self.encoding.config().1.input_type(),
+        };
+        Input::new(input_type & DataType::Log)
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -202,13 +237,24 @@ impl SinkConfig for FileSinkConfig {
     }
 }
 
+/// Events buffered for a file until it is closed, when encoding batches.
+#[derive(Default)]
+struct PendingBatch {
+    events: Vec<Event>,
+    finalizers: EventFinalizers,
+    byte_size: JsonSize,
+}
+
 pub struct FileSink {
     path: Template,
     transformer: Transformer,
-    encoder: Encoder<Framer>,
+    encoder: EncoderKind,
     idle_timeout: Duration,
     files: ExpiringHashMap<Bytes, OutFile>,
+    batches: HashMap<Bytes, PendingBatch>,
     compression: Compression,
+    rotation: Option<RotationConfig>,
+    rotations: HashMap<Bytes, FileRotation>,
     events_sent: Registered<EventsSent>,
     include_file_metric_tag: bool,
 }
@@ -216,8 +262,30 @@ pub struct FileSink {
 impl FileSink {
     pub fn new(config: &FileSinkConfig, cx: SinkContext) -> crate::Result<Self> {
         let transformer = config.encoding.transformer();
-        let (framer, serializer) = config.encoding.build(SinkType::StreamBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let encoder = match &config.batch_encoding {
+            Some(batch_encoding) => {
+                if config.compression != Compression::None {
+                    return Err("`compression` must be `none` when `batch_encoding` is set".into());
+                }
+                if config.rotation.is_some() {
+                    return Err("`rotation` is not supported when `batch_encoding` is set".into());
+                }
+                BatchEncoder::new(batch_encoding.build()?).into()
+            }
+            None => {
+                let (framer, serializer) = config.encoding.build(SinkType::StreamBased)?;
+                Encoder::<Framer>::new(framer, serializer).into()
+            }
+        };
+
+        if let Some(rotation) = &config.rotation {
+            if config.compression != Compression::None && rotation.compression != Compression::None
+            {
+                return Err(
+                    "`rotation.compression` must be `none` when `compression` is set".into(),
+                );
+            }
+        }
 
         let offset = config
             .timezone
@@ -230,7 +298,10 @@ impl FileSink {
             encoder,
             idle_timeout: config.idle_timeout,
             files: ExpiringHashMap::default(),
+            batches: HashMap::default(),
             compression: config.compression,
+            rotation: config.rotation.clone(),
+            rotations: HashMap::default(),
             events_sent: register!(EventsSent::from(Output(None))),
             include_file_metric_tag: config.internal_metrics.include_file_tag,
         })
@@ -272,17 +343,14 @@ impl FileSink {
 
                             // Close all the open files.
                             debug!(message = "Closing all the open files.");
-                            for (path, file) in self.files.iter_mut() {
-                                if let Err(error) = file.close().await {
-                                    emit!(FileIoError {
-                                        error,
-                                        code: "failed_closing_file",
-                                        message: "Failed to close file.",
-                                        path,
-                                        dropped_events: 0,
-                                    });
-                                } else{
-                                    trace!(message = "Successfully closed file.", path = ?path);
+                            let paths = self
+                                .files
+                                .iter_mut()
+                                .map(|(path, _)| path.clone())
+                                .collect::<Vec<_>>();
+                            for path in paths {
+                                if let Some((mut file, _)) = self.files.remove(&path) {
+                                    self.close_file(&path, &mut file).await;
                                 }
                             }
 
@@ -302,15 +370,7 @@ impl FileSink {
                         Some((mut expired_file, path)) => {
                             // We got an expired file. All we really want is to
                             // flush and close it.
-                            if let Err(error) = expired_file.close().await {
-                                emit!(FileIoError {
-                                    error,
-                                    code: "failed_closing_file",
-                                    message: "Failed to close file.",
-                                    path: &path,
-                                    dropped_events: 0,
-                                });
-                            }
+                            self.close_file(path.get_ref(), &mut expired_file).await;
                             drop(expired_file); // ignore close error
                             emit!(FileOpen {
                                 count: self.files.len()
@@ -345,7 +405,8 @@ impl FileSink {
             file
         } else {
             trace!(message = "Opening new file.", ?path);
-            let file = match open_file(BytesPath::new(path.clone())).await {
+            let truncate = matches!(self.encoder, EncoderKind::Batch(_));
+            let file = match open_file(BytesPath::new(path.clone()), truncate).await {
                 Ok(file) => file,
                 Err(error) => {
                     // We couldn't open the file for this event.
@@ -363,6 +424,11 @@ impl FileSink {
                 }
             };
 
+            if self.rotation.is_some() {
+                let written = file.metadata().await.map_or(0, |metadata| metadata.len());
+                self.rotations
+                    .insert(path.clone(), FileRotation::new(written));
+            }
             let outfile = OutFile::new(file, self.compression);
 
             self.files.insert_at(path.clone(), outfile, next_deadline);
@@ -372,10 +438,23 @@ impl FileSink {
             self.files.get_mut(&path).unwrap()
         };
 
+        let encoder = match &mut self.encoder {
+            EncoderKind::Framed(encoder) => encoder,
+            EncoderKind::Batch(_) => {
+                trace!(message = "Buffering an event until the file is closed.", path = ?path);
+                let batch = self.batches.entry(path).or_default();
+                batch.byte_size += event.estimated_json_encoded_size_of();
+                batch.finalizers.merge(event.take_finalizers());
+                self.transformer.transform(&mut event);
+                batch.events.push(event);
+                return;
+            }
+        };
+
         trace!(message = "Writing an event to file.", path = ?path);
         let event_size = event.estimated_json_encoded_size_of();
         let finalizers = event.take_finalizers();
-        match write_event_to_file(file, event, &self.transformer, &mut self.encoder).await {
+        match write_event_to_file(file, event, &self.transformer, encoder).await {
             Ok(byte_size) => {
                 finalizers.update_status(EventStatus::Delivered);
                 self.events_sent.emit(CountByteSize(1, event_size));
@@ -384,6 +463,7 @@ impl FileSink {
                     file: String::from_utf8_lossy(&path),
                     include_file_metric_tag: self.include_file_metric_tag,
                 });
+                self.rotate_if_needed(&path, byte_size).await;
             }
             Err(error) => {
                 finalizers.update_status(EventStatus::Errored);
@@ -397,9 +477,93 @@ impl FileSink {
             }
         }
     }
+
+    /// Rotates the file once the bytes written to it or its age exceed the rotation limits.
+    async fn rotate_if_needed(&mut self, path: &Bytes, byte_size: usize) {
+        let (Some(rotation), Some(state)) = (&self.rotation, self.rotations.get_mut(path)) else {
+            return;
+        };
+        if !state.record(rotation, byte_size) {
+            return;
+        }
+
+        trace!(message = "Rotating file.", path = ?path);
+        if let Some((mut file, _)) = self.files.remove(path) {
+            self.close_file(path, &mut file).await;
+            emit!(FileOpen {
+                count: self.files.len()
+            });
+        }
+        let rotation = self.rotation.as_ref().expect("rotation is configured");
+        if let Err(error) = rotate_file(BytesPath::new(path.clone()).as_ref(), rotation).await {
+            emit!(FileIoError {
+                error,
+                code: "failed_rotating_file",
+                message: "Failed to rotate file.",
+                path,
+                dropped_events: 0,
+            });
+        }
+    }
+
+    /// Writes the events buffered for the file, if any, then closes it.
+    async fn close_file(&mut self, path: &Bytes, file: &mut OutFile) {
+        self.rotations.remove(path);
+        if let Some(batch) = self.batches.remove(path) {
+            self.write_batch(path, file, batch).await;
+        }
+
+        if let Err(error) = file.close().await {
+            emit!(FileIoError {
+                error,
+                code: "failed_closing_file",
+                message: "Failed to close file.",
+                path,
+                dropped_events: 0,
+            });
+        } else {
+            trace!(message = "Successfully closed file.", path = ?path);
+        }
+    }
+
+    async fn write_batch(&mut self, path: &Bytes, file: &mut OutFile, batch: PendingBatch) {
+        let EncoderKind::Batch(encoder) = &mut self.encoder else {
+            unreachable!("events are only buffered when encoding batches");
+        };
+
+        trace!(message = "Writing a batch of events to file.", path = ?path);
+        let count = batch.events.len();
+        let mut buffer = BytesMut::new();
+        let result = match encoder.encode(batch.events, &mut buffer) {
+            Ok(()) => file.write_all(&buffer).await.map(|()| buffer.len()),
+            Err(error) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error)),
+        };
+
+        match result {
+            Ok(byte_size) => {
+                batch.finalizers.update_status(EventStatus::Delivered);
+                self.events_sent.emit(CountByteSize(count, batch.byte_size));
+                emit!(FileBytesSent {
+                    byte_size,
+                    file: String::from_utf8_lossy(path),
+                    include_file_metric_tag: self.include_file_metric_tag,
+                });
+            }
+            Err(error) => {
+                batch.finalizers.update_status(EventStatus::Errored);
+                emit!(FileIoError {
+                    code: "failed_writing_file",
+                    message: "Failed to write the file.",
+                    error,
+                    path,
+                    dropped_events: count,
+                });
+            }
+        }
+    }
 }
 
-async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
+async fn open_file(path: impl AsRef<std::path::Path>, truncate: bool) -> std::io::Result<File> {
     let parent = path.as_ref().parent();
 
     if let Some(parent) = parent {
@@ -410,7 +574,8 @@ async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
         .read(false)
         .write(true)
         .create(true)
-        .append(true)
+        .append(!truncate)
+        .truncate(truncate)
         .open(path)
         .await
 }
@@ -470,7 +635,9 @@ mod tests {
             path: template.clone().try_into().unwrap(),
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -496,7 +663,9 @@ mod tests {
             path: template.clone().try_into().unwrap(),
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
             compression: Compression::Gzip,
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -522,7 +691,9 @@ mod tests {
             path: template.clone().try_into().unwrap(),
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
             compression: Compression::Zstd,
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -540,6 +711,35 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn single_partition_parquet() {
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: Some(BatchSerializerConfig::Parquet {
+                parquet: Default::default(),
+            }),
+            compression: Compression::None,
+            rotation: None,
+            acknowledgements: Default::default(),
+            timezone: Default::default(),
+            internal_metrics: FileInternalMetricsConfig {
+                include_file_tag: true,
+            },
+        };
+
+        let (input, _) = random_lines_with_stream(100, 64, None);
+
+        run_assert_log_sink(config, input.clone()).await;
+
+        let output = std::fs::read(template).unwrap();
+        assert!(output.starts_with(b"PAR1"));
+        assert!(output.ends_with(b"PAR1"));
+    }
+
     #[tokio::test]
     async fn many_partitions() {
         let directory = temp_dir();
@@ -553,7 +753,9 @@ mod tests {
             path: template.try_into().unwrap(),
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -635,7 +837,9 @@ mod tests {
             path: template.clone().try_into().unwrap(),
             idle_timeout: Duration::from_secs(1),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -683,6 +887,74 @@ mod tests {
         sink_handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn rotation() {
+        let directory = temp_dir();
+        let path = directory.join("rotated.log");
+
+        let config = FileSinkConfig {
+            path: path.clone().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
+            compression: Compression::None,
+            rotation: Some(RotationConfig {
+                max_bytes: Some(10 * 65),
+                compression: Compression::Gzip,
+                max_files: Some(3),
+                ..Default::default()
+            }),
+            acknowledgements: Default::default(),
+            timezone: Default::default(),
+            internal_metrics: FileInternalMetricsConfig {
+                include_file_tag: true,
+            },
+        };
+
+        let (input, _) = random_lines_with_stream(100, 64, None);
+
+        run_assert_log_sink(config, input.clone()).await;
+
+        let mut rotated = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|rotated| *rotated != path)
+            .collect::<Vec<_>>();
+        rotated.sort();
+        assert_eq!(rotated.len(), 3);
+        assert!(rotated
+            .iter()
+            .all(|rotated| rotated.extension().unwrap() == "gz"));
+
+        // The last rotation happens after the last event, leaving only the rotated files.
+        assert!(!path.exists());
+        let output = rotated
+            .into_iter()
+            .flat_map(lines_from_gzip_file)
+            .collect::<Vec<_>>();
+        assert_eq!(output, input[70..].to_vec());
+    }
+
+    #[test]
+    fn rotation_requires_uncompressed_rotated_files() {
+        let config = FileSinkConfig {
+            path: temp_file().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
+            compression: Compression::Zstd,
+            rotation: Some(RotationConfig {
+                max_bytes: Some(1024),
+                compression: Compression::Gzip,
+                ..Default::default()
+            }),
+            acknowledgements: Default::default(),
+            timezone: Default::default(),
+            internal_metrics: Default::default(),
+        };
+        assert!(FileSink::new(&config, SinkContext::default()).is_err());
+    }
+
     async fn run_assert_log_sink(config: FileSinkConfig, events: Vec<String>) {
         run_assert_sink(
             config,
@@ -0,0 +1,271 @@
+//! Rotation of the files written by the sink, along with the compression and the retention of
+//! the rotated files.
+
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use chrono::{NaiveDateTime, Utc};
+use serde_with::serde_as;
+use tokio::{
+    fs::{self, File},
+    io::AsyncReadExt,
+};
+use vector_lib::configurable::configurable_component;
+
+use super::{Compression, OutFile};
+
+/// The format of the timestamps appended to the names of the rotated files, sorting them by the
+/// time they were rotated.
+const ROTATED_SUFFIX_FORMAT: &str = "%Y%m%dT%H%M%S%.6fZ";
+
+const COPY_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Rotation configuration.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RotationConfig {
+    /// The number of bytes written to a file after which it is rotated.
+    ///
+    /// With `compression` set, the bytes are counted before being compressed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 104857600))]
+    pub max_bytes: Option<u64>,
+
+    /// The amount of time after which a file opened by Vector is rotated.
+    ///
+    /// The age of the file is checked whenever an event is written to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[configurable(metadata(docs::examples = 3600))]
+    #[configurable(metadata(docs::human_name = "Maximum Age"))]
+    pub max_age_secs: Option<Duration>,
+
+    /// The compression applied to the rotated files.
+    ///
+    /// Must be `none` when the sink already compresses the files it writes.
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub compression: Compression,
+
+    /// The maximum number of rotated files kept for each file, after which the oldest ones are
+    /// deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::type_unit = "files"))]
+    #[configurable(metadata(docs::examples = 10))]
+    pub max_files: Option<usize>,
+
+    /// The amount of time after which a rotated file is deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[configurable(metadata(docs::examples = 604800))]
+    #[configurable(metadata(docs::human_name = "Retention"))]
+    pub retention_secs: Option<Duration>,
+}
+
+/// The state of an open file, telling when it needs to be rotated.
+pub struct FileRotation {
+    written: u64,
+    opened_at: Instant,
+}
+
+impl FileRotation {
+    /// Starts tracking a file opened with `written` bytes already in it.
+    pub fn new(written: u64) -> Self {
+        Self {
+            written,
+            opened_at: Instant::now(),
+        }
+    }
+
+    /// Records the bytes written to the file, returning whether it needs to be rotated.
+    pub fn record(&mut self, config: &RotationConfig, byte_size: usize) -> bool {
+        self.written += byte_size as u64;
+        config.max_bytes.is_some_and(|max| self.written >= max)
+            || config
+                .max_age_secs
+                .is_some_and(|max| self.opened_at.elapsed() >= max)
+    }
+}
+
+const fn extension(compression: Compression) -> Option<&'static str> {
+    match compression {
+        Compression::Gzip => Some("gz"),
+        Compression::Zstd => Some("zst"),
+        Compression::None => None,
+    }
+}
+
+/// Renames a closed file with the time it is rotated, then compresses it and deletes the rotated
+/// files no longer retained.
+pub async fn rotate_file(path: &Path, config: &RotationConfig) -> std::io::Result<()> {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(format!(".{}", Utc::now().format(ROTATED_SUFFIX_FORMAT)));
+    let rotated = PathBuf::from(rotated);
+    fs::rename(path, &rotated).await?;
+
+    if let Some(extension) = extension(config.compression) {
+        let mut compressed = rotated.as_os_str().to_owned();
+        compressed.push(format!(".{extension}"));
+        compress_file(&rotated, Path::new(&compressed), config.compression).await?;
+        fs::remove_file(&rotated).await?;
+    }
+
+    prune_rotated_files(path, config, SystemTime::now()).await
+}
+
+async fn compress_file(from: &Path, to: &Path, compression: Compression) -> std::io::Result<()> {
+    let mut input = File::open(from).await?;
+    let mut output = OutFile::new(File::create(to).await?, compression);
+    let mut buffer = vec![0; COPY_BUFFER_SIZE];
+    loop {
+        let read = input.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        output.write_all(&buffer[..read]).await?;
+    }
+    output.close().await
+}
+
+/// Gets the time a file was rotated from its name, `None` if it isn't a rotation of `file_name`.
+fn rotated_at(file_name: &str, candidate: &str) -> Option<NaiveDateTime> {
+    let suffix = candidate.strip_prefix(file_name)?.strip_prefix('.')?;
+    let suffix = [Compression::Gzip, Compression::Zstd]
+        .into_iter()
+        .filter_map(extension)
+        .find_map(|extension| suffix.strip_suffix(&format!(".{extension}")))
+        .unwrap_or(suffix);
+    NaiveDateTime::parse_from_str(suffix, ROTATED_SUFFIX_FORMAT).ok()
+}
+
+/// Deletes the rotated files of `path` exceeding `max_files`, or older than `retention_secs`.
+async fn prune_rotated_files(
+    path: &Path,
+    config: &RotationConfig,
+    now: SystemTime,
+) -> std::io::Result<()> {
+    if config.max_files.is_none() && config.retention_secs.is_none() {
+        return Ok(());
+    }
+    let (Some(directory), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return Ok(());
+    };
+    let directory = if directory.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        directory
+    };
+    let file_name = file_name.to_string_lossy();
+
+    let mut rotated_files = Vec::new();
+    let mut entries = fs::read_dir(directory).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name();
+        if let Some(rotated_at) = rotated_at(&file_name, &name.to_string_lossy()) {
+            rotated_files.push((rotated_at, entry.path()));
+        }
+    }
+    // Newest first.
+    rotated_files.sort_by(|a, b| b.0.cmp(&a.0));
+
+    for (index, (_, rotated)) in rotated_files.into_iter().enumerate() {
+        let exceeds_count = config.max_files.is_some_and(|max| index >= max);
+        let expired = match config.retention_secs {
+            Some(retention) => fs::metadata(&rotated)
+                .await?
+                .modified()
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age >= retention),
+            None => false,
+        };
+        if exceeds_count || expired {
+            debug!(message = "Deleting rotated file.", path = ?rotated);
+            fs::remove_file(&rotated).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn parses_rotated_file_names() {
+        let rotated =
+            NaiveDateTime::parse_from_str("20261014T120000.000001Z", ROTATED_SUFFIX_FORMAT).ok();
+        assert_eq!(
+            rotated_at("app.log", "app.log.20261014T120000.000001Z"),
+            rotated
+        );
+        assert_eq!(
+            rotated_at("app.log", "app.log.20261014T120000.000001Z.gz"),
+            rotated
+        );
+        assert_eq!(
+            rotated_at("app.log", "app.log.20261014T120000.000001Z.zst"),
+            rotated
+        );
+        assert_eq!(rotated_at("app.log", "app.log"), None);
+        assert_eq!(rotated_at("app.log", "app.log.1"), None);
+        assert_eq!(
+            rotated_at("app.log", "other.log.20261014T120000.000001Z"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn prunes_rotated_files() {
+        let directory = temp_dir();
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("app.log");
+        for name in [
+            "app.log",
+            "app.log.20261014T120000.000001Z",
+            "app.log.20261014T120000.000002Z.gz",
+            "app.log.20261014T120000.000003Z",
+            "other.log.20261014T120000.000001Z",
+        ] {
+            std::fs::write(directory.join(name), "line\n").unwrap();
+        }
+
+        let config = RotationConfig {
+            max_files: Some(2),
+            ..Default::default()
+        };
+        prune_rotated_files(&path, &config, SystemTime::now())
+            .await
+            .unwrap();
+        let mut names = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "app.log",
+                "app.log.20261014T120000.000002Z.gz",
+                "app.log.20261014T120000.000003Z",
+                "other.log.20261014T120000.000001Z",
+            ]
+        );
+
+        let config = RotationConfig {
+            retention_secs: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        prune_rotated_files(&path, &config, SystemTime::now() + Duration::from_secs(120))
+            .await
+            .unwrap();
+        assert!(directory.join("app.log").exists());
+        assert!(directory.join("other.log.20261014T120000.000001Z").exists());
+        assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 2);
+    }
+}
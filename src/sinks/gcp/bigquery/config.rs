@@ -0,0 +1,252 @@
+//! Configuration for the `gcp_bigquery` sink.
+
+use http::{uri::Scheme, Uri};
+use indoc::indoc;
+use prost::Message as _;
+use snafu::{ResultExt, Snafu};
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+use vector_lib::codecs::encoding::{ProtobufSerializerConfig, ProtobufSerializerOptions};
+
+use super::{
+    proto,
+    service::{AuthInterceptor, BigqueryRetryLogic, BigqueryService},
+    sink::BigquerySink,
+};
+use crate::{
+    gcp::{GcpAuthConfig, Scope},
+    sinks::prelude::*,
+};
+
+/// The largest append request accepted by the Storage Write API.
+///
+/// Batches are limited to a little less, leaving room for the schema sent along with the rows.
+const MAX_APPEND_REQUEST_SIZE: usize = 10_000_000;
+const MAX_BATCH_SIZE: usize = 9_000_000;
+
+#[derive(Debug, Snafu)]
+enum BigqueryConfigError {
+    #[snafu(display("Invalid endpoint URI: {}", source))]
+    Uri { source: http::uri::InvalidUri },
+    #[snafu(display("Could not create endpoint: {}", source))]
+    Endpoint { source: tonic::transport::Error },
+    #[snafu(display("Could not set up endpoint TLS settings: {}", source))]
+    EndpointTls { source: tonic::transport::Error },
+    #[snafu(display("Invalid Protobuf descriptor of the rows: {}", source))]
+    Descriptor { source: prost::DecodeError },
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BigqueryDefaultBatchSettings;
+
+impl SinkBatchSettings for BigqueryDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(10_000);
+    const MAX_BYTES: Option<usize> = Some(MAX_BATCH_SIZE);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// How rows are written to the table.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteMode {
+    /// Rows are appended to the default stream of the table.
+    ///
+    /// Rows can be queried as soon as they are written, but rows of retried requests may be
+    /// written more than once.
+    #[default]
+    Default,
+
+    /// Rows are appended to a committed stream created by Vector, at explicit offsets.
+    ///
+    /// Rows can be queried as soon as they are written, and are written exactly once: a retried
+    /// request whose rows were already written is recognized by their offset.
+    Committed,
+
+    /// The rows of each batch are appended to their own pending stream, which is committed once
+    /// the batch is written.
+    ///
+    /// The rows of a batch can be queried all at once, after the stream is committed, and are
+    /// written exactly once.
+    Pending,
+}
+
+/// Configuration for the `gcp_bigquery` sink.
+#[configurable_component(sink(
+    "gcp_bigquery",
+    "Write rows to Google BigQuery tables with the Storage Write API."
+))]
+#[derive(Clone, Debug)]
+pub struct BigqueryConfig {
+    /// The project of the table to write rows to.
+    #[configurable(metadata(docs::examples = "my-project"))]
+    pub project: String,
+
+    /// The dataset of the table to write rows to.
+    #[configurable(metadata(docs::examples = "my_dataset"))]
+    pub dataset: String,
+
+    /// The table to write rows to.
+    #[configurable(metadata(docs::examples = "my_table"))]
+    pub table: String,
+
+    /// The endpoint of the BigQuery Storage API.
+    ///
+    /// The scheme (`http` or `https`) must be specified.
+    #[serde(default = "default_endpoint")]
+    #[configurable(metadata(docs::examples = "https://bigquerystorage.googleapis.com"))]
+    pub endpoint: String,
+
+    /// The Protobuf message each event is serialized as, describing the rows of the table.
+    ///
+    /// The message must be self-contained: messages of nested fields must be declared inside of
+    /// it, rather than imported or declared next to it. Its fields are mapped to the columns of
+    /// the table with the same names, as described in the [data type conversions][conversions].
+    ///
+    /// [conversions]: https://cloud.google.com/bigquery/docs/write-api#data_type_conversions
+    #[configurable(derived)]
+    pub schema: ProtobufSerializerOptions,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub write_mode: WriteMode,
+
+    #[serde(default, flatten)]
+    pub auth: GcpAuthConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<BigqueryDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_endpoint() -> String {
+    "https://bigquerystorage.googleapis.com".to_string()
+}
+
+impl GenerateConfig for BigqueryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            project = "my-project"
+            dataset = "my_dataset"
+            table = "my_table"
+            schema.desc_file = "/etc/vector/rows.desc"
+            schema.message_type = "Row"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "gcp_bigquery")]
+impl SinkConfig for BigqueryConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let serializer = ProtobufSerializerConfig {
+            protobuf: self.schema.clone(),
+        }
+        .build()?;
+        // The descriptor is defined with the `prost` version of the serializer, which may differ
+        // from the one the Storage Write API client is generated with.
+        let descriptor =
+            prost_reflect::prost::Message::encode_to_vec(serializer.descriptor_proto());
+        let schema = proto::ProtoSchema {
+            proto_descriptor: Some(
+                prost_types::DescriptorProto::decode(descriptor.as_slice())
+                    .context(DescriptorSnafu)?,
+            ),
+        };
+
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_APPEND_REQUEST_SIZE)?
+            .into_batcher_settings()?;
+
+        let auth = self.auth.build(Scope::BigQueryInsertdata).await?;
+        let mut uri: Uri = self.endpoint.parse().context(UriSnafu)?;
+        auth.apply_uri(&mut uri);
+        let protocol = uri.scheme_str().unwrap_or("https").to_owned();
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let host = uri.host().unwrap_or("bigquerystorage.googleapis.com");
+        let mut tls_config = ClientTlsConfig::new().domain_name(host);
+        if let Some((cert, key)) = tls.identity_pem() {
+            tls_config = tls_config.identity(Identity::from_pem(cert, key));
+        }
+        for authority in tls.authorities_pem() {
+            tls_config = tls_config.ca_certificate(Certificate::from_pem(authority));
+        }
+
+        let mut endpoint: Endpoint = uri.to_string().parse().context(EndpointSnafu)?;
+        if uri.scheme() != Some(&Scheme::HTTP) {
+            endpoint = endpoint.tls_config(tls_config).context(EndpointTlsSnafu)?;
+        }
+        auth.spawn_regenerate_token();
+
+        let client = proto::big_query_write_client::BigQueryWriteClient::with_interceptor(
+            endpoint.connect_lazy(),
+            AuthInterceptor::new(auth),
+        );
+        let table = format!(
+            "projects/{}/datasets/{}/tables/{}",
+            self.project, self.dataset, self.table
+        );
+        let service = BigqueryService::new(client, table, self.write_mode, schema);
+        let healthcheck = service.clone().healthcheck().boxed();
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), BigqueryRetryLogic)
+            .service(service);
+        let sink = BigquerySink::new(batch_settings, serializer, service, protocol);
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<BigqueryConfig>();
+    }
+
+    #[test]
+    fn parses_write_mode() {
+        let config: BigqueryConfig = toml::from_str(indoc! {r#"
+            project = "my-project"
+            dataset = "my_dataset"
+            table = "my_table"
+            schema.desc_file = "/etc/vector/rows.desc"
+            schema.message_type = "Row"
+            write_mode = "pending"
+        "#})
+        .unwrap();
+
+        assert_eq!(config.write_mode, WriteMode::Pending);
+        assert_eq!(config.endpoint, "https://bigquerystorage.googleapis.com");
+    }
+}
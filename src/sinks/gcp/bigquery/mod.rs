@@ -0,0 +1,42 @@
+//! The `gcp_bigquery` [`vector_lib::sink::VectorSink`].
+//!
+//! Rows are written with the BigQuery Storage Write API, as Protobuf messages described by a
+//! user-provided descriptor set. Depending on the write mode, rows are appended to the table's
+//! default stream, to a committed stream at explicit offsets, or to a pending stream per batch
+//! that is committed once the batch is written.
+
+mod config;
+mod service;
+mod sink;
+
+pub use self::config::{BigqueryConfig, WriteMode};
+
+// prost emits some generated code that includes clones on `Arc`
+// objects, which causes a clippy ding on this block. We don't
+// directly control the generated code, so allow this lint here.
+#[allow(clippy::clone_on_ref_ptr)]
+// https://github.com/hyperium/tonic/issues/1350
+#[allow(clippy::missing_const_for_fn)]
+#[allow(warnings)]
+mod proto {
+    pub mod google {
+        pub mod cloud {
+            pub mod bigquery {
+                pub mod storage {
+                    pub mod v1 {
+                        include!(concat!(
+                            env!("OUT_DIR"),
+                            "/google.cloud.bigquery.storage.v1.rs"
+                        ));
+                    }
+                }
+            }
+        }
+
+        pub mod rpc {
+            include!(concat!(env!("OUT_DIR"), "/google.rpc.rs"));
+        }
+    }
+
+    pub use google::cloud::bigquery::storage::v1::*;
+}
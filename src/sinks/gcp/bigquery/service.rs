@@ -0,0 +1,562 @@
+//! Service implementation for the `gcp_bigquery` sink.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use futures::stream;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use snafu::{ResultExt, Snafu};
+use tonic::{
+    metadata::MetadataValue,
+    service::{interceptor::InterceptedService, Interceptor},
+    transport::Channel,
+    Code, Status,
+};
+
+use super::{config::WriteMode, proto};
+use crate::{gcp::GcpAuthenticator, sinks::prelude::*};
+
+/// The name of the stream every table has, whose rows are committed as soon as they're written.
+const DEFAULT_STREAM: &str = "_default";
+
+type BigqueryClient = proto::big_query_write_client::BigQueryWriteClient<
+    InterceptedService<Channel, AuthInterceptor>,
+>;
+
+/// Authorizes requests with the current GCP token.
+#[derive(Clone)]
+pub(super) struct AuthInterceptor {
+    auth: GcpAuthenticator,
+}
+
+impl AuthInterceptor {
+    pub(super) const fn new(auth: GcpAuthenticator) -> Self {
+        Self { auth }
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: tonic::Request<()>) -> Result<tonic::Request<()>, Status> {
+        if let Some(token) = self.auth.make_token() {
+            let authorization = MetadataValue::try_from(&token).map_err(|_| {
+                Status::new(
+                    Code::FailedPrecondition,
+                    "Invalid token text returned by GCP",
+                )
+            })?;
+            request
+                .metadata_mut()
+                .insert("authorization", authorization);
+        }
+        Ok(request)
+    }
+}
+
+/// Where the rows of a request are appended.
+#[derive(Clone, Debug, PartialEq)]
+struct Position {
+    stream: String,
+    offset: i64,
+}
+
+/// How far the writing of a request got, shared between its retries.
+#[derive(Debug, Default)]
+pub(super) struct Progress {
+    position: Option<Position>,
+    /// Whether the pending stream the rows were appended to was finalized.
+    finalized: bool,
+}
+
+#[derive(Clone)]
+pub(super) struct BigqueryRequest {
+    pub(super) rows: Vec<Vec<u8>>,
+    pub(super) progress: Arc<Mutex<Progress>>,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for BigqueryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for BigqueryRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct BigqueryResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for BigqueryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum BigqueryError {
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: Status },
+
+    #[snafu(display("No response was received to the append request"))]
+    MissingResponse,
+
+    #[snafu(display("Appending rows failed: {}", message))]
+    Append { code: Code, message: String },
+
+    #[snafu(display("{} rows were rejected, such as with: {}", count, message))]
+    RowsRejected { count: usize, message: String },
+
+    #[snafu(display("Committing the stream failed: {}", message))]
+    Commit { message: String },
+}
+
+impl BigqueryError {
+    fn code(&self) -> Option<Code> {
+        match self {
+            Self::Request { source } => Some(source.code()),
+            Self::Append { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct BigqueryRetryLogic;
+
+impl RetryLogic for BigqueryRetryLogic {
+    type Error = BigqueryError;
+    type Response = BigqueryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            BigqueryError::RowsRejected { .. } => false,
+            // Appends beyond the end of a committed stream are retried in a new stream.
+            BigqueryError::Append {
+                code: Code::OutOfRange,
+                ..
+            } => true,
+            _ => !matches!(
+                error.code(),
+                Some(
+                    Code::NotFound
+                        | Code::InvalidArgument
+                        | Code::AlreadyExists
+                        | Code::PermissionDenied
+                        | Code::OutOfRange
+                        | Code::Unimplemented
+                        | Code::Unauthenticated
+                        | Code::DataLoss
+                )
+            ),
+        }
+    }
+}
+
+/// The committed stream rows are appended to, and the streams abandoned before it.
+#[derive(Debug, Default)]
+struct CommittedStreams {
+    /// The current stream, with the offset at which its next rows are appended.
+    current: Option<Position>,
+    /// The number of rows of each abandoned stream when it was finalized.
+    finalized: HashMap<String, i64>,
+}
+
+impl CommittedStreams {
+    /// Whether the rows of a request were already written to an abandoned stream.
+    ///
+    /// Requests whose rows weren't written to an abandoned stream lose their position, so that
+    /// they are appended to the current stream instead.
+    fn was_written(&self, progress: &mut Progress) -> bool {
+        let Some(position) = &progress.position else {
+            return false;
+        };
+        match self.finalized.get(&position.stream) {
+            Some(&row_count) if position.offset < row_count => true,
+            Some(_) => {
+                progress.position = None;
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Reserves the offsets of `rows` rows at the end of the current stream.
+    fn reserve(&mut self, rows: usize) -> Option<Position> {
+        let current = self.current.as_mut()?;
+        let position = current.clone();
+        current.offset += rows as i64;
+        Some(position)
+    }
+
+    /// Abandons the given stream, finalized with `row_count` rows, if it is the current stream.
+    fn abandon(&mut self, stream: &str, row_count: i64) {
+        if self
+            .current
+            .as_ref()
+            .is_some_and(|current| current.stream == stream)
+        {
+            self.current = None;
+        }
+        self.finalized.insert(stream.to_owned(), row_count);
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct BigqueryService {
+    client: BigqueryClient,
+    /// The table, as `projects/<project>/datasets/<dataset>/tables/<table>`.
+    table: String,
+    write_mode: WriteMode,
+    schema: proto::ProtoSchema,
+    committed: Arc<tokio::sync::Mutex<CommittedStreams>>,
+}
+
+impl BigqueryService {
+    pub(super) fn new(
+        client: BigqueryClient,
+        table: String,
+        write_mode: WriteMode,
+        schema: proto::ProtoSchema,
+    ) -> Self {
+        Self {
+            client,
+            table,
+            write_mode,
+            schema,
+            committed: Arc::default(),
+        }
+    }
+
+    /// Checks that the default stream of the table can be accessed.
+    pub(super) async fn healthcheck(mut self) -> crate::Result<()> {
+        let name = format!("{}/streams/{DEFAULT_STREAM}", self.table);
+        let request = proto::GetWriteStreamRequest {
+            name: name.clone(),
+            view: proto::WriteStreamView::Basic as i32,
+        };
+        self.client
+            .get_write_stream(routed(request, "name", &name))
+            .await?;
+        Ok(())
+    }
+
+    async fn create_stream(
+        &mut self,
+        stream_type: proto::write_stream::Type,
+    ) -> Result<String, BigqueryError> {
+        let request = proto::CreateWriteStreamRequest {
+            parent: self.table.clone(),
+            write_stream: Some(proto::WriteStream {
+                r#type: stream_type as i32,
+                ..Default::default()
+            }),
+        };
+        let stream = self
+            .client
+            .create_write_stream(routed(request, "parent", &self.table))
+            .await
+            .context(RequestSnafu)?;
+        Ok(stream.into_inner().name)
+    }
+
+    /// Finalizes a stream, returning the number of rows written to it.
+    async fn finalize(&mut self, stream: &str) -> Result<i64, BigqueryError> {
+        let request = proto::FinalizeWriteStreamRequest {
+            name: stream.to_owned(),
+        };
+        let response = self
+            .client
+            .finalize_write_stream(routed(request, "name", stream))
+            .await
+            .context(RequestSnafu)?;
+        Ok(response.into_inner().row_count)
+    }
+
+    /// Appends rows to a stream, at the given offset or at the end of the stream.
+    async fn append(
+        &mut self,
+        stream: &str,
+        offset: Option<i64>,
+        rows: &[Vec<u8>],
+    ) -> Result<(), BigqueryError> {
+        let request = proto::AppendRowsRequest {
+            write_stream: stream.to_owned(),
+            offset,
+            rows: Some(proto::append_rows_request::Rows::ProtoRows(
+                proto::append_rows_request::ProtoData {
+                    writer_schema: Some(self.schema.clone()),
+                    rows: Some(proto::ProtoRows {
+                        serialized_rows: rows.to_vec(),
+                    }),
+                },
+            )),
+            trace_id: format!("vector:{}", crate::get_version()),
+        };
+        let mut responses = self
+            .client
+            .append_rows(routed(stream::iter([request]), "write_stream", stream))
+            .await
+            .context(RequestSnafu)?
+            .into_inner();
+        let response = responses
+            .message()
+            .await
+            .context(RequestSnafu)?
+            .ok_or(BigqueryError::MissingResponse)?;
+
+        if let Some(row_error) = response.row_errors.first() {
+            return Err(BigqueryError::RowsRejected {
+                count: response.row_errors.len(),
+                message: row_error.message.clone(),
+            });
+        }
+        match response.response {
+            Some(proto::append_rows_response::Response::Error(status)) => {
+                Err(BigqueryError::Append {
+                    code: Code::from(status.code),
+                    message: status.message,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Appends rows to a stream at the given offset, treating rows already written there as
+    /// appended by an earlier attempt.
+    async fn append_at(
+        &mut self,
+        position: &Position,
+        rows: &[Vec<u8>],
+    ) -> Result<(), BigqueryError> {
+        match self
+            .append(&position.stream, Some(position.offset), rows)
+            .await
+        {
+            Err(error) if error.code() == Some(Code::AlreadyExists) => Ok(()),
+            result => result,
+        }
+    }
+
+    async fn write_committed(&mut self, request: &BigqueryRequest) -> Result<(), BigqueryError> {
+        // Appends are serialized, as rows can't be appended after offsets still being written.
+        let committed = Arc::clone(&self.committed);
+        let mut streams = committed.lock().await;
+
+        let position = {
+            let mut progress = request.progress.lock().expect("poisoned lock");
+            if streams.was_written(&mut progress) {
+                return Ok(());
+            }
+            progress.position.clone()
+        };
+        let position = match position {
+            Some(position) => position,
+            None => {
+                if streams.current.is_none() {
+                    let stream = self
+                        .create_stream(proto::write_stream::Type::Committed)
+                        .await?;
+                    streams.current = Some(Position { stream, offset: 0 });
+                }
+                let position = streams
+                    .reserve(request.rows.len())
+                    .expect("current stream was just created");
+                request.progress.lock().expect("poisoned lock").position = Some(position.clone());
+                position
+            }
+        };
+
+        let result = self.append_at(&position, &request.rows).await;
+        if matches!(&result, Err(error) if error.code() == Some(Code::OutOfRange)) {
+            // The rows of an earlier request were never written, leaving a gap that no rows can be
+            // appended after. The stream is abandoned, and the requests that weren't written to it
+            // are appended to a new one when retried.
+            let row_count = self.finalize(&position.stream).await?;
+            streams.abandon(&position.stream, row_count);
+        }
+        result
+    }
+
+    async fn write_pending(&mut self, request: &BigqueryRequest) -> Result<(), BigqueryError> {
+        let (position, finalized) = {
+            let progress = request.progress.lock().expect("poisoned lock");
+            (progress.position.clone(), progress.finalized)
+        };
+        let position = match position {
+            Some(position) => position,
+            None => {
+                let stream = self
+                    .create_stream(proto::write_stream::Type::Pending)
+                    .await?;
+                let position = Position { stream, offset: 0 };
+                request.progress.lock().expect("poisoned lock").position = Some(position.clone());
+                position
+            }
+        };
+
+        if !finalized {
+            self.append_at(&position, &request.rows).await?;
+            self.finalize(&position.stream).await?;
+            request.progress.lock().expect("poisoned lock").finalized = true;
+        }
+
+        let commit = proto::BatchCommitWriteStreamsRequest {
+            parent: self.table.clone(),
+            write_streams: vec![position.stream],
+        };
+        let response = self
+            .client
+            .batch_commit_write_streams(routed(commit, "parent", &self.table))
+            .await
+            .context(RequestSnafu)?
+            .into_inner();
+        let already_committed = proto::storage_error::StorageErrorCode::StreamAlreadyCommitted;
+        match response
+            .stream_errors
+            .into_iter()
+            .find(|error| error.code != already_committed as i32)
+        {
+            Some(error) => Err(BigqueryError::Commit {
+                message: error.error_message,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Service<BigqueryRequest> for BigqueryService {
+    type Response = BigqueryResponse;
+    type Error = BigqueryError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: BigqueryRequest) -> Self::Future {
+        let mut service = self.clone();
+
+        Box::pin(async move {
+            match service.write_mode {
+                WriteMode::Default => {
+                    let stream = format!("{}/streams/{DEFAULT_STREAM}", service.table);
+                    service.append(&stream, None, &request.rows).await?;
+                }
+                WriteMode::Committed => service.write_committed(&request).await?,
+                WriteMode::Pending => service.write_pending(&request).await?,
+            }
+
+            Ok(BigqueryResponse {
+                byte_size: request.metadata.request_encoded_size(),
+                events_byte_size: request
+                    .metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+            })
+        })
+    }
+}
+
+/// Adds the routing header the Storage Write API requires to a request.
+fn routed<T>(message: T, param: &str, value: &str) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    let params = format!("{param}={}", utf8_percent_encode(value, NON_ALPHANUMERIC));
+    if let Ok(params) = MetadataValue::try_from(params) {
+        request
+            .metadata_mut()
+            .insert("x-goog-request-params", params);
+    }
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(stream: &str, offset: i64) -> Progress {
+        Progress {
+            position: Some(Position {
+                stream: stream.to_owned(),
+                offset,
+            }),
+            finalized: false,
+        }
+    }
+
+    #[test]
+    fn reserves_consecutive_offsets() {
+        let mut streams = CommittedStreams::default();
+        assert_eq!(streams.reserve(10), None);
+
+        streams.current = Some(Position {
+            stream: "a".into(),
+            offset: 0,
+        });
+        assert_eq!(streams.reserve(10).unwrap().offset, 0);
+        assert_eq!(streams.reserve(5).unwrap().offset, 10);
+        assert_eq!(streams.current.as_ref().unwrap().offset, 15);
+    }
+
+    #[test]
+    fn resumes_requests_of_abandoned_streams() {
+        let mut streams = CommittedStreams {
+            current: Some(Position {
+                stream: "a".into(),
+                offset: 30,
+            }),
+            ..Default::default()
+        };
+        streams.abandon("a", 10);
+        assert!(streams.current.is_none());
+
+        // Rows before the end of the abandoned stream were written by an earlier attempt.
+        assert!(streams.was_written(&mut progress("a", 0)));
+
+        // Rows after it were not, and are appended to a new stream.
+        let mut unwritten = progress("a", 20);
+        assert!(!streams.was_written(&mut unwritten));
+        assert!(unwritten.position.is_none());
+
+        let mut current = progress("b", 0);
+        assert!(!streams.was_written(&mut current));
+        assert!(current.position.is_some());
+    }
+
+    #[test]
+    fn retries_appends_beyond_the_end_of_streams() {
+        let append = |code| BigqueryError::Append {
+            code,
+            message: String::new(),
+        };
+
+        assert!(BigqueryRetryLogic.is_retriable_error(&append(Code::OutOfRange)));
+        assert!(BigqueryRetryLogic.is_retriable_error(&append(Code::Unavailable)));
+        assert!(!BigqueryRetryLogic.is_retriable_error(&append(Code::InvalidArgument)));
+        assert!(
+            !BigqueryRetryLogic.is_retriable_error(&BigqueryError::RowsRejected {
+                count: 1,
+                message: String::new(),
+            })
+        );
+    }
+}
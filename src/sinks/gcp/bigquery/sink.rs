@@ -0,0 +1,145 @@
+//! Implementation of the `gcp_bigquery` sink.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use bytes::BytesMut;
+use tokio_util::codec::Encoder as _;
+use vector_lib::codecs::encoding::ProtobufSerializer;
+use vector_lib::stream::batcher::data::BatchReduce;
+
+use super::service::{BigqueryRequest, Progress};
+use crate::{internal_events::EncoderSerializeError, sinks::prelude::*};
+
+/// An event serialized as a row of the table.
+struct Row {
+    bytes: Vec<u8>,
+    byte_size: usize,
+    json_byte_size: GroupedCountByteSize,
+    finalizers: EventFinalizers,
+}
+
+/// The rows of a batch.
+struct Rows {
+    rows: Vec<Vec<u8>>,
+    byte_size: usize,
+    json_byte_size: GroupedCountByteSize,
+    finalizers: EventFinalizers,
+}
+
+impl Default for Rows {
+    fn default() -> Self {
+        Self {
+            rows: Vec::new(),
+            byte_size: 0,
+            json_byte_size: telemetry().create_request_count_byte_size(),
+            finalizers: EventFinalizers::default(),
+        }
+    }
+}
+
+impl Rows {
+    fn push(&mut self, row: Row) {
+        self.rows.push(row.bytes);
+        self.byte_size += row.byte_size;
+        self.json_byte_size += row.json_byte_size;
+        self.finalizers.merge(row.finalizers);
+    }
+
+    fn into_request(self) -> BigqueryRequest {
+        let request_size = self.rows.iter().map(Vec::len).sum::<usize>();
+        let metadata =
+            RequestMetadataBuilder::new(self.rows.len(), self.byte_size, self.json_byte_size)
+                .with_request_size(NonZeroUsize::new(request_size).unwrap_or(NonZeroUsize::MIN));
+
+        BigqueryRequest {
+            rows: self.rows,
+            progress: Arc::new(Mutex::new(Progress::default())),
+            finalizers: self.finalizers,
+            metadata,
+        }
+    }
+}
+
+pub(super) struct BigquerySink<S> {
+    batch_settings: BatcherSettings,
+    serializer: ProtobufSerializer,
+    service: S,
+    protocol: String,
+}
+
+impl<S> BigquerySink<S>
+where
+    S: Service<BigqueryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    pub(super) const fn new(
+        batch_settings: BatcherSettings,
+        serializer: ProtobufSerializer,
+        service: S,
+        protocol: String,
+    ) -> Self {
+        Self {
+            batch_settings,
+            serializer,
+            service,
+            protocol,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut serializer = self.serializer;
+
+        input
+            .filter_map(move |event| future::ready(encode_row(&mut serializer, event)))
+            .batched(self.batch_settings.as_reducer_config(
+                |row: &Row| row.bytes.len(),
+                BatchReduce::new(|rows: &mut Rows, row: Row| rows.push(row)),
+            ))
+            .map(Rows::into_request)
+            .into_driver(self.service)
+            .protocol(self.protocol)
+            .run()
+            .await
+    }
+}
+
+/// Serializes an event as a row, rejecting events that don't fit the Protobuf message.
+fn encode_row(serializer: &mut ProtobufSerializer, mut event: Event) -> Option<Row> {
+    let finalizers = event.take_finalizers();
+    let byte_size = event.size_of();
+    let mut json_byte_size = telemetry().create_request_count_byte_size();
+    json_byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+
+    let mut bytes = BytesMut::new();
+    match serializer.encode(event, &mut bytes) {
+        Ok(()) => Some(Row {
+            bytes: bytes.to_vec(),
+            byte_size,
+            json_byte_size,
+            finalizers,
+        }),
+        Err(error) => {
+            emit!(EncoderSerializeError { error: &error });
+            finalizers.update_status(EventStatus::Rejected);
+            None
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for BigquerySink<S>
+where
+    S: Service<BigqueryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
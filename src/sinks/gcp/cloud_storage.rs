@@ -9,7 +9,7 @@ use snafu::ResultExt;
 use snafu::Snafu;
 use tower::ServiceBuilder;
 use uuid::Uuid;
-use vector_lib::codecs::encoding::Framer;
+use vector_lib::codecs::{encoding::Framer, BatchSerializerConfig};
 use vector_lib::configurable::configurable_component;
 use vector_lib::event::{EventFinalizers, Finalizable};
 use vector_lib::{request_metadata::RequestMetadata, TimeZone};
@@ -17,7 +17,9 @@ use vector_lib::{request_metadata::RequestMetadata, TimeZone};
 use crate::sinks::util::metadata::RequestMetadataBuilder;
 use crate::sinks::util::service::TowerRequestConfigDefaults;
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    codecs::{
+        BatchEncoder, Encoder, EncoderKind, EncodingConfigWithFraming, SinkType, Transformer,
+    },
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     event::Event,
     gcp::{GcpAuthConfig, GcpAuthenticator, Scope},
@@ -141,13 +143,24 @@ pub struct GcsSinkConfig {
 
     /// The filename extension to use in the object key.
     ///
-    /// If not specified, the extension is determined by the compression scheme used.
+    /// If not specified, the extension is determined by the batch codec or the compression
+    /// scheme used.
     #[configurable(metadata(docs::advanced))]
     filename_extension: Option<String>,
 
     #[serde(flatten)]
     encoding: EncodingConfigWithFraming,
 
+    /// Encodes each batch of events as a single object, such as a Parquet file, instead of
+    /// encoding events one by one.
+    ///
+    /// When set, the `encoding.codec` and `framing` options are ignored, while the other
+    /// `encoding` options still apply. Batch codecs compress their output themselves, so
+    /// `compression` must be set to `none`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::advanced))]
+    batch_encoding: Option<BatchSerializerConfig>,
+
     #[configurable(derived)]
     #[serde(default)]
     compression: Compression,
@@ -201,6 +214,7 @@ fn default_config(encoding: EncodingConfigWithFraming) -> GcsSinkConfig {
         filename_append_uuid: true,
         filename_extension: Default::default(),
         encoding,
+        batch_encoding: None,
         compression: Compression::gzip_default(),
         batch: Default::default(),
         endpoint: Default::default(),
@@ -245,7 +259,11 @@ impl SinkConfig for GcsSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type() & DataType::Log)
+        let input_type = match &self.batch_encoding {
+            Some(batch_encoding) => batch_encoding.input_type(),
+            None => self.encoding.config().1.input_type(),
+        };
+        Input::new(input_type & DataType::Log)
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -280,6 +298,21 @@ impl GcsSinkConfig {
         Ok(VectorSink::from_event_streamsink(sink))
     }
 
+    fn build_encoder(&self) -> crate::Result<EncoderKind> {
+        match &self.batch_encoding {
+            Some(batch_encoding) => {
+                if self.compression != Compression::None {
+                    return Err("`compression` must be `none` when `batch_encoding` is set".into());
+                }
+                Ok(BatchEncoder::new(batch_encoding.build()?).into())
+            }
+            None => {
+                let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
+                Ok(Encoder::<Framer>::new(framer, serializer).into())
+            }
+        }
+    }
+
     fn key_partitioner(&self) -> crate::Result<KeyPartitioner> {
         Ok(KeyPartitioner::new(
             Template::try_from(self.key_prefix.as_deref().unwrap_or("date=%F/"))
@@ -301,7 +334,7 @@ struct RequestSettings {
     extension: String,
     time_format: String,
     append_uuid: bool,
-    encoder: (Transformer, Encoder<Framer>),
+    encoder: (Transformer, EncoderKind),
     compression: Compression,
     tz_offset: Option<FixedOffset>,
 }
@@ -309,7 +342,7 @@ struct RequestSettings {
 impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
     type Metadata = (String, EventFinalizers);
     type Events = Vec<Event>;
-    type Encoder = (Transformer, Encoder<Framer>);
+    type Encoder = (Transformer, EncoderKind);
     type Payload = Bytes;
     type Request = GcsRequest;
     type Error = io::Error;
@@ -379,8 +412,7 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
 impl RequestSettings {
     fn new(config: &GcsSinkConfig, cx: SinkContext) -> crate::Result<Self> {
         let transformer = config.encoding.transformer();
-        let (framer, serializer) = config.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let encoder = config.build_encoder()?;
         let acl = config
             .acl
             .map(|acl| HeaderValue::from_str(&to_string(acl)).unwrap());
@@ -404,7 +436,10 @@ impl RequestSettings {
         let extension = config
             .filename_extension
             .clone()
-            .unwrap_or_else(|| config.compression.extension().into());
+            .unwrap_or_else(|| match &encoder {
+                EncoderKind::Batch(encoder) => encoder.extension().into(),
+                EncoderKind::Framed(_) => config.compression.extension().into(),
+            });
         let time_format = config.filename_time_format.clone();
         let append_uuid = config.filename_append_uuid;
         let offset = config
@@ -559,4 +594,52 @@ mod tests {
         let req = build_request(None, true, Compression::gzip_default());
         assert_ne!(req.key, "key/date.log.gz".to_string());
     }
+
+    #[test]
+    fn gcs_build_request_with_batch_encoding() {
+        let context = SinkContext::default();
+        let sink_config = GcsSinkConfig {
+            key_prefix: Some("key/".into()),
+            filename_time_format: "date".into(),
+            filename_append_uuid: false,
+            batch_encoding: Some(BatchSerializerConfig::Parquet {
+                parquet: Default::default(),
+            }),
+            compression: Compression::None,
+            ..default_config((None::<FramingConfig>, JsonSerializerConfig::default()).into())
+        };
+        let log = LogEvent::default().into();
+        let key = sink_config
+            .key_partitioner()
+            .unwrap()
+            .partition(&log)
+            .expect("key wasn't provided");
+
+        let mut byte_size = GroupedCountByteSize::new_untagged();
+        byte_size.add_event(&log, log.estimated_json_encoded_size_of());
+
+        let request_settings = request_settings(&sink_config, context);
+        let (metadata, metadata_request_builder, _events) =
+            request_settings.split_input((key, vec![log]));
+        let payload = EncodeResult::uncompressed(Bytes::new(), byte_size);
+        let request_metadata = metadata_request_builder.build(&payload);
+
+        let req = request_settings.build_request(metadata, request_metadata, payload);
+        assert_eq!(req.key, "key/date.parquet".to_string());
+        assert_eq!(
+            req.settings.content_type,
+            HeaderValue::from_static("application/vnd.apache.parquet")
+        );
+    }
+
+    #[test]
+    fn gcs_batch_encoding_requires_no_compression() {
+        let sink_config = GcsSinkConfig {
+            batch_encoding: Some(BatchSerializerConfig::Parquet {
+                parquet: Default::default(),
+            }),
+            ..default_config((None::<FramingConfig>, JsonSerializerConfig::default()).into())
+        };
+        assert!(sink_config.build_encoder().is_err());
+    }
 }
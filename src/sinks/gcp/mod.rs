@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use vector_lib::configurable::configurable_component;
 
+pub mod bigquery;
 pub mod cloud_storage;
 pub mod pubsub;
 pub mod stackdriver;
@@ -16,7 +16,7 @@ use http::{
 };
 use hyper::Body;
 use snafu::ResultExt;
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 use vector_lib::codecs::encoding::Framer;
 
 /// Partition key for GreptimeDB logs sink.
@@ -246,6 +246,10 @@ impl RetryLogic for GreptimeDBHttpRetryLogic {
     fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
         self.inner.should_retry_response(&response.http_response)
     }
+
+    fn retry_after(&self, response: &Self::Response) -> Option<Duration> {
+        self.inner.retry_after(&response.http_response)
+    }
 }
 
 #[cfg(test)]
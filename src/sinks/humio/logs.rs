@@ -191,6 +191,8 @@ impl HumioLogsConfig {
         HecLogsSinkConfig {
             default_token: self.token.clone(),
             endpoint: self.endpoint.clone(),
+            endpoints: Vec::new(),
+            endpoint_health: None,
             host_key: Some(self.host_key.clone()),
             indexed_fields: self.indexed_fields.clone(),
             index: self.index.clone(),
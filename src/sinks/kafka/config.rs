@@ -1,4 +1,4 @@
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, num::NonZeroUsize, time::Duration};
 
 use futures::FutureExt;
 use rdkafka::ClientConfig;
@@ -96,6 +96,18 @@ pub struct KafkaSinkConfig {
     #[configurable(metadata(docs::advanced))]
     pub message_timeout_ms: Duration,
 
+    /// Enables the idempotent producer, which ensures that the retries of the producer don't
+    /// write duplicates of the events, and keeps their order within each partition.
+    ///
+    /// This is always enabled when `transaction` is set.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    pub idempotence: bool,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    pub transaction: Option<KafkaTransactionConfig>,
+
     /// A map of advanced options to pass directly to the underlying `librdkafka` client.
     ///
     /// For more information on configuration options, see [Configuration properties][config_props_docs].
@@ -126,6 +138,62 @@ pub struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Settings of the transactional producer, which writes the events in transactions.
+///
+/// A transaction is only committed once all its events are written, and the events are only
+/// acknowledged once their transaction is committed, so consumers reading with the
+/// `read_committed` isolation level never see the events of aborted transactions.
+///
+/// The events read by a `kafka` source have their offsets committed within the transaction they
+/// are written in, so that they're written exactly once, provided that the source has
+/// acknowledgements enabled and that the events keep their topic, partition and offset fields.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaTransactionConfig {
+    /// The transactional ID of the producer.
+    ///
+    /// It must be unique to each instance of the sink, and stay the same across restarts, so that
+    /// Kafka fences off the transactions left in progress by previous instances.
+    #[configurable(metadata(docs::examples = "vector-${HOSTNAME}"))]
+    pub id: String,
+
+    /// The maximum number of events written in a transaction.
+    #[serde(default = "default_transaction_max_events")]
+    #[configurable(metadata(docs::type_unit = "events"))]
+    pub max_events: NonZeroUsize,
+
+    /// The maximum time, in milliseconds, events are gathered for before their transaction is
+    /// committed.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[serde(default = "default_transaction_commit_interval_ms")]
+    #[configurable(metadata(docs::human_name = "Commit Interval"))]
+    pub commit_interval_ms: Duration,
+
+    /// The maximum time, in milliseconds, a transaction can be in progress for before Kafka aborts
+    /// it.
+    ///
+    /// The `message_timeout_ms` option is lowered to this timeout if it's greater, as required by
+    /// `librdkafka`.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[serde(default = "default_transaction_timeout_ms")]
+    #[configurable(metadata(docs::human_name = "Transaction Timeout"))]
+    pub timeout_ms: Duration,
+}
+
+fn default_transaction_max_events() -> NonZeroUsize {
+    NonZeroUsize::new(1000).unwrap()
+}
+
+const fn default_transaction_commit_interval_ms() -> Duration {
+    Duration::from_millis(1000)
+}
+
+const fn default_transaction_timeout_ms() -> Duration {
+    Duration::from_millis(60000) // default in librdkafka
+}
+
 const fn default_socket_timeout_ms() -> Duration {
     Duration::from_millis(60000) // default in librdkafka
 }
@@ -156,13 +224,35 @@ impl KafkaSinkConfig {
         self.auth.apply(&mut client_config)?;
 
         // All batch options are producer only.
+        let message_timeout = match &self.transaction {
+            // librdkafka requires messages to time out before their transaction does.
+            Some(transaction) => self.message_timeout_ms.min(transaction.timeout_ms),
+            None => self.message_timeout_ms,
+        };
         client_config
             .set("compression.codec", to_string(self.compression))
             .set(
                 "message.timeout.ms",
-                self.message_timeout_ms.as_millis().to_string(),
+                message_timeout.as_millis().to_string(),
             );
 
+        if self.idempotence || self.transaction.is_some() {
+            client_config.set("enable.idempotence", "true");
+        }
+        if let Some(transaction) = &self.transaction {
+            for key in ["transactional.id", "transaction.timeout.ms"] {
+                if let Some(val) = self.librdkafka_options.get(key) {
+                    return Err(format!("Transaction settings set `librdkafka_options.{key}`.\
+                                        The config already sets this as `librdkafka_options.{key}={val}`.\
+                                        Please delete one.").into());
+                }
+            }
+            client_config.set("transactional.id", &transaction.id).set(
+                "transaction.timeout.ms",
+                transaction.timeout_ms.as_millis().to_string(),
+            );
+        }
+
         if let Some(value) = self.batch.timeout_secs {
             // Delay in milliseconds to wait for messages in the producer queue to accumulate before
             // constructing message batches (MessageSets) to transmit to brokers. A higher value
@@ -245,6 +335,8 @@ impl GenerateConfig for KafkaSinkConfig {
             auth: Default::default(),
             socket_timeout_ms: default_socket_timeout_ms(),
             message_timeout_ms: default_message_timeout_ms(),
+            idempotence: false,
+            transaction: None,
             librdkafka_options: Default::default(),
             headers_key: None,
             acknowledgements: Default::default(),
@@ -282,4 +374,40 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    #[test]
+    fn transactional_producer_options() {
+        let config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic-1234"
+            encoding.codec = "json"
+            transaction.id = "vector-1"
+            transaction.timeout_ms = 30000
+            "#,
+        )
+        .unwrap();
+
+        let client_config = config.to_rdkafka().unwrap();
+        assert_eq!(client_config.get("enable.idempotence"), Some("true"));
+        assert_eq!(client_config.get("transactional.id"), Some("vector-1"));
+        assert_eq!(client_config.get("transaction.timeout.ms"), Some("30000"));
+        assert_eq!(client_config.get("message.timeout.ms"), Some("30000"));
+    }
+
+    #[test]
+    fn transactional_producer_options_errors_on_double_set() {
+        let config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic-1234"
+            encoding.codec = "json"
+            transaction.id = "vector-1"
+            librdkafka_options."transactional.id" = "vector-2"
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.to_rdkafka().is_err());
+    }
 }
@@ -4,6 +4,7 @@ use vector_lib::lookup::OwnedTargetPath;
 
 use crate::{
     internal_events::KafkaHeaderExtractionError,
+    kafka::consumed_offset,
     sinks::{
         kafka::service::{KafkaRequest, KafkaRequestMetadata},
         prelude::*,
@@ -14,6 +15,8 @@ pub struct KafkaRequestBuilder {
     pub key_field: Option<OwnedTargetPath>,
     pub headers_key: Option<OwnedTargetPath>,
     pub encoder: (Transformer, Encoder<()>),
+    /// Whether the positions the events were read from by `kafka` sources are looked up.
+    pub source_offsets: bool,
 }
 
 impl RequestBuilder<(String, Event)> for KafkaRequestBuilder {
@@ -45,6 +48,10 @@ impl RequestBuilder<(String, Event)> for KafkaRequestBuilder {
             timestamp_millis: get_timestamp_millis(&event),
             headers: get_headers(&event, self.headers_key.as_ref()),
             topic,
+            source_offset: self
+                .source_offsets
+                .then(|| consumed_offset(&event))
+                .flatten(),
         };
 
         (metadata, builder, event)
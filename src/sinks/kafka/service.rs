@@ -16,7 +16,10 @@ use rdkafka::{
 };
 use vector_lib::config;
 
-use crate::{kafka::KafkaStatisticsContext, sinks::prelude::*};
+use crate::{
+    kafka::{ConsumedOffset, KafkaStatisticsContext},
+    sinks::prelude::*,
+};
 
 pub struct KafkaRequest {
     pub body: Bytes,
@@ -30,12 +33,15 @@ pub struct KafkaRequestMetadata {
     pub timestamp_millis: Option<i64>,
     pub headers: Option<OwnedHeaders>,
     pub topic: String,
+    /// The position the event was read from by a `kafka` source, committed within the
+    /// transaction the event is written in.
+    pub source_offset: Option<ConsumedOffset>,
 }
 
 pub struct KafkaResponse {
-    event_byte_size: GroupedCountByteSize,
-    raw_byte_size: usize,
-    event_status: EventStatus,
+    pub(super) event_byte_size: GroupedCountByteSize,
+    pub(super) raw_byte_size: usize,
+    pub(super) event_status: EventStatus,
 }
 
 impl DriverResponse for KafkaResponse {
@@ -8,10 +8,14 @@ use tokio::time::Duration;
 use tracing::Span;
 use vrl::path::OwnedTargetPath;
 
-use super::config::KafkaSinkConfig;
+use super::config::{KafkaSinkConfig, KafkaTransactionConfig};
 use crate::{
     kafka::KafkaStatisticsContext,
-    sinks::kafka::{request_builder::KafkaRequestBuilder, service::KafkaService},
+    sinks::kafka::{
+        request_builder::KafkaRequestBuilder,
+        service::KafkaService,
+        transaction::{KafkaTransaction, KafkaTransactionService},
+    },
     sinks::prelude::*,
 };
 
@@ -26,6 +30,8 @@ pub struct KafkaSink {
     transformer: Transformer,
     encoder: Encoder<()>,
     service: KafkaService,
+    producer: FutureProducer<KafkaStatisticsContext>,
+    transaction: Option<KafkaTransactionConfig>,
     topic: Template,
     key_field: Option<OwnedTargetPath>,
     headers_key: Option<OwnedTargetPath>,
@@ -55,7 +61,9 @@ impl KafkaSink {
             headers_key: config.headers_key.map(|key| key.0),
             transformer,
             encoder,
-            service: KafkaService::new(producer),
+            service: KafkaService::new(producer.clone()),
+            producer,
+            transaction: config.transaction,
             topic: config.topic,
             key_field: config.key_field.map(|key| key.0),
         })
@@ -66,9 +74,10 @@ impl KafkaSink {
             key_field: self.key_field,
             headers_key: self.headers_key,
             encoder: (self.transformer, self.encoder),
+            source_offsets: self.transaction.is_some(),
         };
 
-        input
+        let requests = input
             .filter_map(|event| {
                 // Compute the topic.
                 future::ready(
@@ -94,11 +103,33 @@ impl KafkaSink {
                     }
                     Ok(req) => Some(req),
                 }
-            })
-            .into_driver(self.service)
-            .protocol("kafka")
-            .run()
-            .await
+            });
+
+        let Some(transaction) = self.transaction else {
+            return requests
+                .into_driver(self.service)
+                .protocol("kafka")
+                .run()
+                .await;
+        };
+
+        let service =
+            KafkaTransactionService::new(self.service, self.producer, transaction.timeout_ms);
+        if let Err(error) = service.init_transactions().await {
+            error!(message = "Failed to initialize the transactions of the producer.", %error);
+            return Err(());
+        }
+
+        tokio_stream::StreamExt::chunks_timeout(
+            requests,
+            transaction.max_events.get(),
+            transaction.commit_interval_ms,
+        )
+        .map(KafkaTransaction::new)
+        .into_driver(service)
+        .protocol("kafka")
+        .run()
+        .await
     }
 }
 
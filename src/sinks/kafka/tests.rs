@@ -19,7 +19,11 @@ mod integration_test {
         event::{BatchNotifier, BatchStatus},
     };
 
-    use super::super::{config::KafkaSinkConfig, sink::KafkaSink, *};
+    use super::super::{
+        config::{KafkaSinkConfig, KafkaTransactionConfig},
+        sink::KafkaSink,
+        *,
+    };
     use crate::{
         event::{ObjectMap, Value},
         kafka::{KafkaAuthConfig, KafkaCompression, KafkaSaslConfig},
@@ -59,6 +63,8 @@ mod integration_test {
             auth: KafkaAuthConfig::default(),
             socket_timeout_ms: Duration::from_millis(60000),
             message_timeout_ms: Duration::from_millis(300000),
+            idempotence: false,
+            transaction: None,
             librdkafka_options: HashMap::new(),
             headers_key: None,
             acknowledgements: Default::default(),
@@ -83,6 +89,8 @@ mod integration_test {
             auth: KafkaAuthConfig::default(),
             socket_timeout_ms: Duration::from_millis(60000),
             message_timeout_ms: Duration::from_millis(300000),
+            idempotence: false,
+            transaction: None,
             librdkafka_options: HashMap::new(),
             headers_key: None,
             acknowledgements: Default::default(),
@@ -181,6 +189,8 @@ mod integration_test {
             },
             socket_timeout_ms: Duration::from_millis(60000),
             message_timeout_ms: Duration::from_millis(300000),
+            idempotence: false,
+            transaction: None,
             batch,
             librdkafka_options,
             headers_key: None,
@@ -257,6 +267,82 @@ mod integration_test {
         .is_err())
     }
 
+    #[tokio::test]
+    async fn kafka_transactional_producer() {
+        crate::test_util::trace_init();
+
+        let server = kafka_address(9091);
+        let topic = format!("test-{}", random_string(10));
+        let config = KafkaSinkConfig {
+            bootstrap_servers: server.clone(),
+            topic: Template::try_from(topic.clone()).unwrap(),
+            healthcheck_topic: None,
+            key_field: None,
+            encoding: TextSerializerConfig::default().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: Duration::from_millis(60000),
+            message_timeout_ms: Duration::from_millis(300000),
+            idempotence: false,
+            transaction: Some(KafkaTransactionConfig {
+                id: format!("vector-{}", random_string(10)),
+                max_events: 100.try_into().unwrap(),
+                commit_interval_ms: Duration::from_millis(100),
+                timeout_ms: Duration::from_millis(10000),
+            }),
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            acknowledgements: Default::default(),
+        };
+
+        let num_events = 1000;
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
+
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let sink = KafkaSink::new(config).unwrap();
+            let sink = VectorSink::from_event_streamsink(sink);
+            sink.run(events).await
+        })
+        .await
+        .expect("Running sink failed");
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        // Only the events of committed transactions are read with the `read_committed` isolation
+        // level.
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", server.as_str());
+        client_config.set("group.id", random_string(10));
+        client_config.set("isolation.level", "read_committed");
+
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(&topic, 0)
+            .set_offset(Offset::Beginning)
+            .unwrap();
+
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        consumer.assign(&tpl).unwrap();
+
+        let mut failures = 0;
+        let mut out = Vec::new();
+        while failures < 100 {
+            match consumer.poll(Duration::from_secs(3)) {
+                Some(Ok(msg)) => {
+                    let s: &str = msg.payload_view().unwrap().unwrap();
+                    out.push(s.to_owned());
+                }
+                None if out.len() >= input.len() => break,
+                _ => {
+                    failures += 1;
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+        }
+
+        assert_eq!(out, input);
+    }
+
     #[tokio::test]
     async fn kafka_happy_path_tls() {
         crate::test_util::trace_init();
@@ -330,6 +416,8 @@ mod integration_test {
             auth: kafka_auth.clone(),
             socket_timeout_ms: Duration::from_millis(60000),
             message_timeout_ms: Duration::from_millis(300000),
+            idempotence: false,
+            transaction: None,
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
             acknowledgements: Default::default(),
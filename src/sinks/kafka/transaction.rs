@@ -0,0 +1,302 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use rdkafka::{
+    error::{KafkaError, KafkaResult},
+    producer::{FutureProducer, Producer},
+    Offset, TopicPartitionList,
+};
+use tokio::sync::Mutex;
+
+use crate::{
+    config::ComponentKey,
+    kafka::{consumer_group_metadata, KafkaStatisticsContext},
+    sinks::{
+        kafka::service::{KafkaRequest, KafkaResponse, KafkaService},
+        prelude::*,
+    },
+};
+
+/// The offsets to commit for each partition consumed by a `kafka` source.
+type SourceOffsets = HashMap<(String, i32), i64>;
+
+/// The records written in a single transaction.
+pub struct KafkaTransaction {
+    requests: Vec<KafkaRequest>,
+    finalizers: EventFinalizers,
+    request_metadata: RequestMetadata,
+
+    /// The offsets of the `kafka` sources the records were read from, committed within the
+    /// transaction.
+    offsets: HashMap<ComponentKey, SourceOffsets>,
+}
+
+impl KafkaTransaction {
+    pub(super) fn new(mut requests: Vec<KafkaRequest>) -> Self {
+        let finalizers = requests
+            .iter_mut()
+            .map(Finalizable::take_finalizers)
+            .collect();
+        let request_metadata = RequestMetadata::from_batch(
+            requests
+                .iter()
+                .map(|request| request.request_metadata.clone()),
+        );
+
+        let mut offsets = HashMap::<_, SourceOffsets>::new();
+        for consumed in requests
+            .iter()
+            .filter_map(|request| request.metadata.source_offset.as_ref())
+        {
+            // The committed offset is the one of the next message to consume.
+            let next = offsets
+                .entry(consumed.source.clone())
+                .or_default()
+                .entry((consumed.topic.clone(), consumed.partition))
+                .or_insert(consumed.offset + 1);
+            *next = (*next).max(consumed.offset + 1);
+        }
+
+        Self {
+            requests,
+            finalizers,
+            request_metadata,
+            offsets,
+        }
+    }
+}
+
+impl Finalizable for KafkaTransaction {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for KafkaTransaction {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.request_metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.request_metadata
+    }
+}
+
+/// Writes the records of each transaction, which is committed once they are all delivered, and
+/// aborted otherwise.
+#[derive(Clone)]
+pub struct KafkaTransactionService {
+    service: KafkaService,
+    producer: FutureProducer<KafkaStatisticsContext>,
+    timeout: Duration,
+
+    /// A producer has at most one transaction in progress at a time. The lock is fair, so the
+    /// transactions are committed in the order they are sent in.
+    transaction_lock: Arc<Mutex<()>>,
+}
+
+impl KafkaTransactionService {
+    pub(crate) fn new(
+        service: KafkaService,
+        producer: FutureProducer<KafkaStatisticsContext>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            service,
+            producer,
+            timeout,
+            transaction_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Initializes the transactions of the producer, which fences off the producers previously
+    /// using the same transactional ID, and aborts the transactions they left in progress.
+    pub(crate) async fn init_transactions(&self) -> KafkaResult<()> {
+        let timeout = self.timeout;
+        self.run_blocking(move |producer| producer.init_transactions(timeout))
+            .await
+    }
+
+    /// Runs a transactional call of the producer, which blocks until the brokers reply.
+    async fn run_blocking<F>(&self, call: F) -> KafkaResult<()>
+    where
+        F: FnOnce(&FutureProducer<KafkaStatisticsContext>) -> KafkaResult<()> + Send + 'static,
+    {
+        let producer = self.producer.clone();
+        tokio::task::spawn_blocking(move || call(&producer))
+            .await
+            .unwrap_or_else(|error| {
+                error!(message = "Transactional call of the producer failed.", %error);
+                Err(KafkaError::Canceled)
+            })
+    }
+
+    /// Commits the offsets consumed by a `kafka` source within the transaction in progress, so
+    /// that they're committed if and only if the transaction is.
+    async fn send_offsets(&self, source: ComponentKey, offsets: SourceOffsets) -> KafkaResult<()> {
+        let Some(group_metadata) = consumer_group_metadata(&source) else {
+            error!(
+                message = "Source of the events isn't running anymore, so their offsets can't be committed.",
+                %source,
+            );
+            return Err(KafkaError::Canceled);
+        };
+
+        let timeout = self.timeout;
+        self.run_blocking(move |producer| {
+            let mut tpl = TopicPartitionList::new();
+            for ((topic, partition), offset) in offsets {
+                tpl.add_partition_offset(&topic, partition, Offset::Offset(offset))?;
+            }
+            producer.send_offsets_to_transaction(&tpl, &group_metadata, timeout)
+        })
+        .await
+    }
+
+    /// Aborts the transaction in progress. A failure to abort is only logged, as the transaction
+    /// already failed for another reason, and Kafka aborts it once it times out anyway.
+    async fn abort(&self) {
+        let timeout = self.timeout;
+        if let Err(error) = self
+            .run_blocking(move |producer| producer.abort_transaction(timeout))
+            .await
+        {
+            error!(message = "Failed to abort the transaction.", %error);
+        }
+    }
+}
+
+impl Service<KafkaTransaction> for KafkaTransactionService {
+    type Response = KafkaResponse;
+    type Error = KafkaError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, transaction: KafkaTransaction) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            let _transaction_guard = this.transaction_lock.lock().await;
+            this.run_blocking(|producer| producer.begin_transaction())
+                .await?;
+
+            let mut service = this.service.clone();
+            let responses = future::join_all(
+                transaction
+                    .requests
+                    .into_iter()
+                    .map(|request| service.call(request)),
+            )
+            .await;
+
+            let mut raw_byte_size = 0;
+            for response in responses {
+                match response {
+                    Ok(response) if response.event_status == EventStatus::Delivered => {
+                        raw_byte_size += response.raw_byte_size;
+                    }
+                    // The events of an aborted transaction are never read by `read_committed`
+                    // consumers, so none of them is delivered.
+                    Ok(response) => {
+                        this.abort().await;
+                        return Ok(KafkaResponse {
+                            event_byte_size: telemetry().create_request_count_byte_size(),
+                            raw_byte_size: 0,
+                            event_status: response.event_status,
+                        });
+                    }
+                    Err(error) => {
+                        this.abort().await;
+                        return Err(error);
+                    }
+                }
+            }
+
+            for (source, offsets) in transaction.offsets {
+                if let Err(error) = this.send_offsets(source, offsets).await {
+                    this.abort().await;
+                    return Err(error);
+                }
+            }
+
+            let timeout = this.timeout;
+            if let Err(error) = this
+                .run_blocking(move |producer| producer.commit_transaction(timeout))
+                .await
+            {
+                this.abort().await;
+                return Err(error);
+            }
+
+            Ok(KafkaResponse {
+                event_byte_size: transaction
+                    .request_metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+                raw_byte_size,
+                event_status: EventStatus::Delivered,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::{kafka::ConsumedOffset, sinks::kafka::service::KafkaRequestMetadata};
+
+    fn request(source_offset: Option<(&str, &str, i32, i64)>) -> KafkaRequest {
+        KafkaRequest {
+            body: Bytes::new(),
+            metadata: KafkaRequestMetadata {
+                finalizers: EventFinalizers::default(),
+                key: None,
+                timestamp_millis: None,
+                headers: None,
+                topic: "output".to_string(),
+                source_offset: source_offset.map(|(source, topic, partition, offset)| {
+                    ConsumedOffset {
+                        source: ComponentKey::from(source),
+                        topic: topic.to_string(),
+                        partition,
+                        offset,
+                    }
+                }),
+            },
+            request_metadata: RequestMetadata::default(),
+        }
+    }
+
+    #[test]
+    fn transaction_commits_next_offset_of_each_partition() {
+        let transaction = KafkaTransaction::new(vec![
+            request(Some(("in_1", "topic", 0, 7))),
+            request(Some(("in_1", "topic", 0, 5))),
+            request(Some(("in_1", "topic", 1, 2))),
+            request(Some(("in_2", "topic", 0, 1))),
+            request(None),
+        ]);
+
+        assert_eq!(
+            transaction.offsets,
+            HashMap::from([
+                (
+                    ComponentKey::from("in_1"),
+                    HashMap::from([(("topic".to_string(), 0), 8), (("topic".to_string(), 1), 3)]),
+                ),
+                (
+                    ComponentKey::from("in_2"),
+                    HashMap::from([(("topic".to_string(), 0), 2)]),
+                ),
+            ])
+        );
+    }
+}
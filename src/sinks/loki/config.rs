@@ -17,6 +17,8 @@ fn default_loki_path() -> String {
     "/loki/api/v1/push".to_string()
 }
 
+const OTLP_PATH: &str = "/otlp/v1/logs";
+
 /// Configuration for the `loki` sink.
 #[configurable_component(sink("loki", "Deliver log event data to the Loki aggregation system."))]
 #[derive(Clone, Debug)]
@@ -29,9 +31,16 @@ pub struct LokiConfig {
     pub endpoint: UriSerde,
 
     /// The path to use in the URL of the Loki instance.
+    ///
+    /// When `api` is set to `otlp` and this is left to its default, `/otlp/v1/logs` is used
+    /// instead.
     #[serde(default = "default_loki_path")]
     pub path: String,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub api: LokiApi,
+
     #[configurable(derived)]
     pub encoding: EncodingConfig,
 
@@ -190,6 +199,29 @@ pub enum OutOfOrderAction {
     Drop,
 }
 
+/// The Loki API that events are sent to.
+#[configurable_component]
+#[derive(Copy, Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LokiApi {
+    /// The push API of Loki.
+    #[derivative(Default)]
+    Push,
+
+    /// The native OTLP endpoint of Loki.
+    ///
+    /// Events are sent as OpenTelemetry log records encoded as JSON. Labels are sent as resource
+    /// attributes, and structured metadata as attributes of the log records. Loki indexes the
+    /// resource attributes configured in its `otlp_config` as labels, and stores the others as
+    /// structured metadata.
+    ///
+    /// Snappy compression is not supported by this endpoint.
+    ///
+    /// Requires Loki 3.0.0 or newer.
+    Otlp,
+}
+
 impl GenerateConfig for LokiConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(
@@ -202,6 +234,14 @@ impl GenerateConfig for LokiConfig {
 }
 
 impl LokiConfig {
+    /// The path requests are sent to, depending on the API used.
+    pub(super) fn request_path(&self) -> String {
+        match self.api {
+            LokiApi::Otlp if self.path == default_loki_path() => OTLP_PATH.to_string(),
+            _ => self.path.clone(),
+        }
+    }
+
     pub(super) fn build_client(&self, cx: SinkContext) -> crate::Result<HttpClient> {
         let tls = TlsSettings::from_options(&self.tls)?;
         let client = HttpClient::new(tls, cx.proxy())?;
@@ -226,6 +266,10 @@ impl SinkConfig for LokiConfig {
             }
         }
 
+        if self.api == LokiApi::Otlp && self.compression == Compression::Snappy {
+            return Err("Snappy compression is not supported by the `otlp` API.".into());
+        }
+
         let client = self.build_client(cx)?;
 
         let config = LokiConfig {
@@ -12,6 +12,7 @@ pub type StructuredMetadata = Vec<(String, String)>;
 pub enum LokiBatchEncoding {
     Json,
     Protobuf,
+    Otlp,
 }
 
 #[derive(Clone)]
@@ -59,6 +60,15 @@ impl Encoder<Vec<LokiRecord>> for LokiBatchEncoder {
                 );
                 batch.encode()
             }
+            LokiBatchEncoding::Otlp => {
+                let resource_logs: Vec<serde_json::Value> = batch
+                    .stream_by_labels
+                    .into_values()
+                    .map(LokiStream::into_otlp)
+                    .collect();
+                let body = serde_json::json!({ "resourceLogs": resource_logs });
+                serde_json::to_vec(&body)?
+            }
         };
         write_all(writer, count, &body).map(|()| (body.len(), byte_size))
     }
@@ -77,6 +87,35 @@ pub struct LokiStream {
     values: Vec<LokiEvent>,
 }
 
+impl LokiStream {
+    /// Converts the stream into OTLP resource logs, its labels becoming the attributes of the
+    /// resource.
+    fn into_otlp(self) -> serde_json::Value {
+        let log_records: Vec<serde_json::Value> = self
+            .values
+            .into_iter()
+            .map(|event| {
+                serde_json::json!({
+                    "timeUnixNano": event.timestamp.to_string(),
+                    "body": { "stringValue": String::from_utf8_lossy(&event.event) },
+                    "attributes": otlp_attributes(event.structured_metadata),
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "resource": { "attributes": otlp_attributes(self.stream) },
+            "scopeLogs": [{ "logRecords": log_records }],
+        })
+    }
+}
+
+fn otlp_attributes(pairs: impl IntoIterator<Item = (String, String)>) -> serde_json::Value {
+    pairs
+        .into_iter()
+        .map(|(key, value)| serde_json::json!({ "key": key, "value": { "stringValue": value } }))
+        .collect()
+}
+
 impl From<Vec<LokiRecord>> for LokiBatch {
     fn from(events: Vec<LokiRecord>) -> Self {
         let mut result = events
@@ -8,7 +8,7 @@ use tokio_util::codec::Encoder as _;
 use vrl::path::parse_target_path;
 
 use super::{
-    config::{LokiConfig, OutOfOrderAction},
+    config::{LokiApi, LokiConfig, OutOfOrderAction},
     event::{LokiBatchEncoder, LokiEvent, LokiRecord, PartitionKey},
     service::{LokiRequest, LokiRetryLogic, LokiService},
 };
@@ -517,21 +517,23 @@ impl LokiSink {
         };
 
         let protocol = get_http_scheme_from_uri(&config.endpoint.uri);
+        let path = config.request_path();
         let service = tower::ServiceBuilder::new()
             .settings(request_limits, LokiRetryLogic)
             .service(LokiService::new(
                 client,
                 config.endpoint,
-                config.path,
+                path,
                 config.auth,
             )?);
 
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
-        let batch_encoder = match config.compression {
-            Compression::Snappy => LokiBatchEncoder(LokiBatchEncoding::Protobuf),
-            _ => LokiBatchEncoder(LokiBatchEncoding::Json),
+        let batch_encoder = match (config.api, config.compression) {
+            (LokiApi::Otlp, _) => LokiBatchEncoder(LokiBatchEncoding::Otlp),
+            (LokiApi::Push, Compression::Snappy) => LokiBatchEncoder(LokiBatchEncoding::Protobuf),
+            (LokiApi::Push, _) => LokiBatchEncoder(LokiBatchEncoding::Json),
         };
 
         Ok(Self {
@@ -200,3 +200,78 @@ async fn structured_metadata_as_json() {
 
     assert_eq!(body[2], expected_metadata);
 }
+
+#[tokio::test]
+async fn sends_otlp_logs() {
+    let (mut config, cx) = load_sink::<LokiConfig>(
+        r#"
+        endpoint = "http://localhost:3100"
+        api = "otlp"
+        compression = "none"
+        labels = {service_name = "web"}
+        structured_metadata.pod = "{{ pod }}"
+        remove_structured_metadata_fields = true
+        encoding.codec = "text"
+        "#,
+    )
+    .unwrap();
+
+    let addr = test_util::next_addr();
+    config.endpoint = format!("http://{}", addr)
+        .parse::<http::Uri>()
+        .expect("could not create URI")
+        .into();
+
+    let (rx, _trigger, server) = build_test_server(addr);
+    tokio::spawn(server);
+
+    let (sink, _healthcheck) = config.build(cx).await.unwrap();
+    let mut event = LogEvent::from("hello world");
+    event.insert("pod", "web-1");
+    if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+        event.insert(
+            timestamp_key,
+            chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+        );
+    }
+    sink.run_events(vec![Event::Log(event)]).await.unwrap();
+
+    let output = rx.take(1).collect::<Vec<_>>().await;
+    let (parts, body) = &output[0];
+    assert_eq!(parts.uri.path(), "/otlp/v1/logs");
+    assert_eq!(parts.headers["content-type"], "application/json");
+
+    let body: serde_json::Value = serde_json::from_slice(body).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "resourceLogs": [{
+                "resource": {
+                    "attributes": [{ "key": "service_name", "value": { "stringValue": "web" } }],
+                },
+                "scopeLogs": [{
+                    "logRecords": [{
+                        "timeUnixNano": "1700000000000000000",
+                        "body": { "stringValue": "hello world" },
+                        "attributes": [{ "key": "pod", "value": { "stringValue": "web-1" } }],
+                    }],
+                }],
+            }],
+        })
+    );
+}
+
+#[tokio::test]
+async fn otlp_rejects_snappy_compression() {
+    let (config, cx) = load_sink::<LokiConfig>(
+        r#"
+        endpoint = "http://localhost:3100"
+        api = "otlp"
+        labels = {service_name = "web"}
+        encoding.codec = "text"
+        "#,
+    )
+    .unwrap();
+
+    assert!(config.build(cx).await.is_err());
+}
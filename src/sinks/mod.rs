@@ -26,8 +26,10 @@ pub mod aws_s_s;
 pub mod axiom;
 #[cfg(feature = "sinks-azure_blob")]
 pub mod azure_blob;
-#[cfg(feature = "sinks-azure_blob")]
+#[cfg(any(feature = "sinks-azure_blob", feature = "sources-azure_event_hubs"))]
 pub mod azure_common;
+#[cfg(feature = "sinks-azure_event_hubs")]
+pub mod azure_event_hubs;
 #[cfg(feature = "sinks-azure_monitor_logs")]
 pub mod azure_monitor_logs;
 #[cfg(feature = "sinks-blackhole")]
@@ -82,8 +84,16 @@ pub mod nats;
 pub mod new_relic;
 #[cfg(feature = "sinks-webhdfs")]
 pub mod opendal_common;
+#[cfg(feature = "sinks-opentelemetry")]
+pub mod opentelemetry;
+#[cfg(feature = "sinks-pagerduty")]
+pub mod pagerduty;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
+#[cfg(feature = "sinks-plugin")]
+pub mod plugin;
+#[cfg(feature = "sinks-postgres")]
+pub mod postgres;
 #[cfg(feature = "sinks-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
@@ -94,6 +104,12 @@ pub mod redis;
 pub mod s3_common;
 #[cfg(feature = "sinks-sematext")]
 pub mod sematext;
+#[cfg(feature = "sinks-slack")]
+pub mod slack;
+#[cfg(feature = "sinks-smtp")]
+pub mod smtp;
+#[cfg(feature = "sinks-snowflake")]
+pub mod snowflake;
 #[cfg(feature = "sinks-socket")]
 pub mod socket;
 #[cfg(feature = "sinks-splunk_hec")]
@@ -104,6 +120,8 @@ pub mod statsd;
 pub mod vector;
 #[cfg(feature = "sinks-webhdfs")]
 pub mod webhdfs;
+#[cfg(feature = "sinks-webhook")]
+pub mod webhook;
 #[cfg(feature = "sinks-websocket")]
 pub mod websocket;
 
@@ -1,16 +1,19 @@
 use std::time::Duration;
 
 use rand::Rng;
-use rumqttc::{MqttOptions, QoS, TlsConfiguration, Transport};
 use snafu::{ResultExt, Snafu};
 use vector_lib::codecs::JsonSerializerConfig;
 
+pub use crate::common::mqtt::{MqttProtocolVersion, MqttQoS};
 use crate::template::Template;
 use crate::{
     codecs::EncodingConfig,
+    common::mqtt::tls_transport,
     config::{AcknowledgementsConfig, Input, SinkConfig, SinkContext},
     sinks::{
-        mqtt::sink::{ConfigurationSnafu, MqttConnector, MqttError, MqttSink, TlsSnafu},
+        mqtt::sink::{
+            ConfigurationSnafu, MqttConnector, MqttError, MqttOptions, MqttSink, TlsSnafu,
+        },
         prelude::*,
         Healthcheck, VectorSink,
     },
@@ -36,6 +39,9 @@ pub struct MqttSinkConfig {
     pub password: Option<String>,
 
     /// MQTT client ID.
+    ///
+    /// If unset, a random client ID is generated. Set a fixed client ID, together with
+    /// `clean_session` disabled, to resume the session with the broker after a restart.
     pub client_id: Option<String>,
 
     /// Connection keep-alive interval.
@@ -46,6 +52,10 @@ pub struct MqttSinkConfig {
     #[serde(default = "default_clean_session")]
     pub clean_session: bool,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol_version: MqttProtocolVersion,
+
     #[configurable(derived)]
     pub tls: Option<TlsEnableableConfig>,
 
@@ -72,34 +82,6 @@ pub struct MqttSinkConfig {
     pub quality_of_service: MqttQoS,
 }
 
-/// Supported Quality of Service types for MQTT.
-#[configurable_component]
-#[derive(Clone, Copy, Debug, Derivative)]
-#[derivative(Default)]
-#[serde(rename_all = "lowercase")]
-#[allow(clippy::enum_variant_names)]
-pub enum MqttQoS {
-    /// AtLeastOnce.
-    #[derivative(Default)]
-    AtLeastOnce,
-
-    /// AtMostOnce.
-    AtMostOnce,
-
-    /// ExactlyOnce.
-    ExactlyOnce,
-}
-
-impl From<MqttQoS> for QoS {
-    fn from(value: MqttQoS) -> Self {
-        match value {
-            MqttQoS::AtLeastOnce => QoS::AtLeastOnce,
-            MqttQoS::AtMostOnce => QoS::AtMostOnce,
-            MqttQoS::ExactlyOnce => QoS::ExactlyOnce,
-        }
-    }
-}
-
 const fn default_port() -> u16 {
     1883
 }
@@ -130,6 +112,7 @@ impl Default for MqttSinkConfig {
             client_id: None,
             keep_alive: default_keep_alive(),
             clean_session: default_clean_session(),
+            protocol_version: MqttProtocolVersion::default(),
             tls: None,
             topic: Template::try_from("vector").expect("Cannot parse as a template"),
             retain: default_retain(),
@@ -186,31 +169,44 @@ impl MqttSinkConfig {
         if client_id.is_empty() {
             return Err(ConfigurationError::EmptyClientId).context(ConfigurationSnafu);
         }
-        let tls = MaybeTlsSettings::from_config(&self.tls, false).context(TlsSnafu)?;
-        let mut options = MqttOptions::new(&client_id, &self.host, self.port);
-        options.set_keep_alive(Duration::from_secs(self.keep_alive.into()));
-        options.set_clean_session(self.clean_session);
-        match (&self.user, &self.password) {
-            (Some(user), Some(password)) => {
-                options.set_credentials(user, password);
-            }
-            (None, None) => {}
+        let credentials = match (&self.user, &self.password) {
+            (Some(user), Some(password)) => Some((user, password)),
+            (None, None) => None,
             _ => {
                 return Err(MqttError::Configuration {
                     source: ConfigurationError::InvalidCredentials,
                 });
             }
-        }
-        if let Some(tls) = tls.tls() {
-            let ca = tls.authorities_pem().flatten().collect();
-            let client_auth = None;
-            let alpn = Some(vec!["mqtt".into()]);
-            options.set_transport(Transport::Tls(TlsConfiguration::Simple {
-                ca,
-                client_auth,
-                alpn,
-            }));
-        }
+        };
+        let tls = MaybeTlsSettings::from_config(&self.tls, false).context(TlsSnafu)?;
+        let keep_alive = Duration::from_secs(self.keep_alive.into());
+
+        let options = match self.protocol_version {
+            MqttProtocolVersion::V311 => {
+                let mut options = rumqttc::MqttOptions::new(&client_id, &self.host, self.port);
+                options.set_keep_alive(keep_alive);
+                options.set_clean_session(self.clean_session);
+                if let Some((user, password)) = credentials {
+                    options.set_credentials(user, password);
+                }
+                if let Some(transport) = tls_transport(&tls) {
+                    options.set_transport(transport);
+                }
+                MqttOptions::V311(options)
+            }
+            MqttProtocolVersion::V5 => {
+                let mut options = rumqttc::v5::MqttOptions::new(&client_id, &self.host, self.port);
+                options.set_keep_alive(keep_alive);
+                options.set_clean_start(self.clean_session);
+                if let Some((user, password)) = credentials {
+                    options.set_credentials(user, password);
+                }
+                if let Some(transport) = tls_transport(&tls) {
+                    options.set_transport(transport);
+                }
+                MqttOptions::V5(options)
+            }
+        };
         MqttConnector::new(options, self.topic.to_string())
     }
 }
@@ -223,4 +219,22 @@ mod test {
     fn generate_config() {
         crate::test_util::test_generate_config::<MqttSinkConfig>();
     }
+
+    #[test]
+    fn parses_protocol_version() {
+        let config: MqttSinkConfig = toml::from_str(
+            r#"
+            host = "localhost"
+            topic = "vector"
+            protocol_version = "v5"
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.protocol_version, MqttProtocolVersion::V5);
+        assert!(matches!(
+            config.build_connector().unwrap().options,
+            MqttOptions::V5(_)
+        ));
+    }
 }
@@ -3,7 +3,6 @@ use std::task::{Context, Poll};
 use crate::sinks::prelude::*;
 use bytes::Bytes;
 use futures::future::BoxFuture;
-use rumqttc::{AsyncClient, ClientError};
 use snafu::Snafu;
 
 use super::config::MqttQoS;
@@ -50,8 +49,36 @@ impl MetaDescriptive for MqttRequest {
     }
 }
 
+/// A client for either version of the MQTT protocol.
+#[derive(Clone)]
+pub(super) enum MqttClient {
+    V311(rumqttc::AsyncClient),
+    V5(rumqttc::v5::AsyncClient),
+}
+
+impl MqttClient {
+    async fn publish(
+        &self,
+        topic: &str,
+        quality_of_service: MqttQoS,
+        retain: bool,
+        body: Bytes,
+    ) -> Result<(), MqttError> {
+        match self {
+            Self::V311(client) => client
+                .publish(topic, quality_of_service.into(), retain, body)
+                .await
+                .map_err(|error| MqttError::V311 { error }),
+            Self::V5(client) => client
+                .publish(topic, quality_of_service.into(), retain, body)
+                .await
+                .map_err(|error| MqttError::V5 { error }),
+        }
+    }
+}
+
 pub(super) struct MqttService {
-    pub(super) client: AsyncClient,
+    pub(super) client: MqttClient,
     pub(super) quality_of_service: MqttQoS,
     pub(super) retain: bool,
 }
@@ -59,7 +86,9 @@ pub(super) struct MqttService {
 #[derive(Debug, Snafu)]
 pub(super) enum MqttError {
     #[snafu(display("error"))]
-    Error { error: ClientError },
+    V311 { error: rumqttc::ClientError },
+    #[snafu(display("error"))]
+    V5 { error: rumqttc::v5::ClientError },
 }
 
 impl Service<MqttRequest> for MqttService {
@@ -79,16 +108,13 @@ impl Service<MqttRequest> for MqttService {
         Box::pin(async move {
             let byte_size = req.body.len();
 
-            let res = client
-                .publish(&req.topic, quality_of_service.into(), retain, req.body)
-                .await;
-            match res {
-                Ok(()) => Ok(MqttResponse {
-                    byte_size,
-                    json_size: req.metadata.into_events_estimated_json_encoded_byte_size(),
-                }),
-                Err(error) => Err(MqttError::Error { error }),
-            }
+            client
+                .publish(&req.topic, quality_of_service, retain, req.body)
+                .await?;
+            Ok(MqttResponse {
+                byte_size,
+                json_size: req.metadata.into_events_estimated_json_encoded_byte_size(),
+            })
         })
     }
 }
@@ -1,6 +1,5 @@
 use async_trait::async_trait;
 use futures::{stream::BoxStream, StreamExt};
-use rumqttc::{AsyncClient, EventLoop, MqttOptions};
 use snafu::{ResultExt, Snafu};
 use vector_lib::tls::TlsError;
 
@@ -10,7 +9,7 @@ use crate::sinks::prelude::*;
 use super::{
     config::{ConfigurationError, MqttQoS},
     request_builder::{MqttEncoder, MqttRequestBuilder},
-    service::MqttService,
+    service::{MqttClient, MqttService},
     MqttSinkConfig,
 };
 
@@ -25,9 +24,16 @@ pub enum MqttError {
     Configuration { source: ConfigurationError },
 }
 
+/// Connection options for either version of the MQTT protocol.
+#[derive(Clone)]
+pub enum MqttOptions {
+    V311(rumqttc::MqttOptions),
+    V5(rumqttc::v5::MqttOptions),
+}
+
 #[derive(Clone)]
 pub struct MqttConnector {
-    options: MqttOptions,
+    pub(super) options: MqttOptions,
     topic: Template,
 }
 
@@ -37,8 +43,37 @@ impl MqttConnector {
         Ok(Self { options, topic })
     }
 
-    fn connect(&self) -> (AsyncClient, EventLoop) {
-        AsyncClient::new(self.options.clone(), 1024)
+    /// Creates a client, and spawns the task driving its event loop.
+    fn connect(&self) -> MqttClient {
+        // If an error is returned by the event loop there is currently no way to tie this back
+        // to the event that was posted which means we can't accurately provide delivery
+        // guarantees.
+        // We need this issue resolved first:
+        // https://github.com/bytebeamio/rumqtt/issues/349
+        match &self.options {
+            MqttOptions::V311(options) => {
+                let (client, mut eventloop) = rumqttc::AsyncClient::new(options.clone(), 1024);
+                tokio::spawn(async move {
+                    loop {
+                        if let Err(error) = eventloop.poll().await {
+                            emit!(MqttConnectionError { error });
+                        }
+                    }
+                });
+                MqttClient::V311(client)
+            }
+            MqttOptions::V5(options) => {
+                let (client, mut eventloop) = rumqttc::v5::AsyncClient::new(options.clone(), 1024);
+                tokio::spawn(async move {
+                    loop {
+                        if let Err(error) = eventloop.poll().await {
+                            emit!(MqttConnectionError { error });
+                        }
+                    }
+                });
+                MqttClient::V5(client)
+            }
+        }
     }
 
     pub async fn healthcheck(&self) -> crate::Result<()> {
@@ -93,26 +128,7 @@ impl MqttSink {
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
-        let (client, mut connection) = self.connector.connect();
-
-        // This is necessary to keep the mqtt event loop moving forward.
-        tokio::spawn(async move {
-            loop {
-                // If an error is returned here there is currently no way to tie this back
-                // to the event that was posted which means we can't accurately provide
-                // delivery guarantees.
-                // We need this issue resolved first:
-                // https://github.com/bytebeamio/rumqtt/issues/349
-                match connection.poll().await {
-                    Ok(_) => {}
-                    Err(connection_error) => {
-                        emit!(MqttConnectionError {
-                            error: connection_error
-                        });
-                    }
-                }
-            }
-        });
+        let client = self.connector.connect();
 
         let service = ServiceBuilder::new().service(MqttService {
             client,
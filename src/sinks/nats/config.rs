@@ -85,6 +85,17 @@ pub struct NatsSinkConfig {
     /// [jetstream]: https://docs.nats.io/nats-concepts/jetstream
     #[serde(default)]
     pub(super) jetstream: bool,
+
+    /// The template for the ID of published messages, used by JetStream to deduplicate them.
+    ///
+    /// Messages published with the ID of a message already stored in the stream within its
+    /// [duplicate window][duplicate_window] are acknowledged by the server but discarded, so
+    /// retried publications aren't stored twice. Requires `jetstream` to be enabled.
+    ///
+    /// [duplicate_window]: https://docs.nats.io/using-nats/developer/develop_jetstream/model_deep_dive#message-deduplication
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(docs::examples = "{{ event_id }}"))]
+    pub(super) message_id: Option<Template>,
 }
 
 fn default_name() -> String {
@@ -103,6 +114,7 @@ impl GenerateConfig for NatsSinkConfig {
             url: "nats://127.0.0.1:4222".into(),
             request: Default::default(),
             jetstream: Default::default(),
+            message_id: None,
         })
         .unwrap()
     }
@@ -167,6 +179,7 @@ impl NatsPublisher {
     pub(super) async fn publish<S: async_nats::subject::ToSubject>(
         &self,
         subject: S,
+        message_id: Option<String>,
         payload: Bytes,
     ) -> Result<(), NatsError> {
         match self {
@@ -186,14 +199,32 @@ impl NatsPublisher {
                     .await
             }
             NatsPublisher::JetStream(jetstream) => {
-                let ack = jetstream.publish(subject, payload).await.map_err(|e| {
-                    NatsError::PublishError {
-                        source: Box::new(e),
+                let ack = match message_id {
+                    Some(message_id) => {
+                        let mut headers = async_nats::HeaderMap::new();
+                        headers.insert(async_nats::header::NATS_MESSAGE_ID, message_id.as_str());
+                        jetstream
+                            .publish_with_headers(subject, headers, payload)
+                            .await
                     }
+                    None => jetstream.publish(subject, payload).await,
+                }
+                .map_err(|e| NatsError::PublishError {
+                    source: Box::new(e),
                 })?;
-                ack.await.map(|_| ()).map_err(|e| NatsError::PublishError {
+
+                // Wait for the stream to store the message before acknowledging its events.
+                let ack = ack.await.map_err(|e| NatsError::PublishError {
                     source: Box::new(e),
-                })
+                })?;
+                if ack.duplicate {
+                    debug!(
+                        message = "Message discarded as a duplicate by the JetStream stream.",
+                        stream = %ack.stream,
+                        sequence = ack.sequence,
+                    );
+                }
+                Ok(())
             }
         }
     }
@@ -79,6 +79,7 @@ async fn nats_no_auth() {
         auth: None,
         request: Default::default(),
         jetstream: false,
+        message_id: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -112,6 +113,7 @@ async fn nats_userpass_auth_valid() {
         }),
         request: Default::default(),
         jetstream: false,
+        message_id: None,
     };
 
     publish_and_check(conf)
@@ -142,6 +144,7 @@ async fn nats_userpass_auth_invalid() {
         }),
         request: Default::default(),
         jetstream: false,
+        message_id: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -174,6 +177,7 @@ async fn nats_token_auth_valid() {
         }),
         request: Default::default(),
         jetstream: false,
+        message_id: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -206,6 +210,7 @@ async fn nats_token_auth_invalid() {
         }),
         request: Default::default(),
         jetstream: false,
+        message_id: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -239,6 +244,7 @@ async fn nats_nkey_auth_valid() {
         }),
         request: Default::default(),
         jetstream: false,
+        message_id: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -272,6 +278,7 @@ async fn nats_nkey_auth_invalid() {
         }),
         request: Default::default(),
         jetstream: false,
+        message_id: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -306,6 +313,7 @@ async fn nats_tls_valid() {
         auth: None,
         request: Default::default(),
         jetstream: false,
+        message_id: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -334,6 +342,7 @@ async fn nats_tls_invalid() {
         auth: None,
         request: Default::default(),
         jetstream: false,
+        message_id: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -370,6 +379,7 @@ async fn nats_tls_client_cert_valid() {
         auth: None,
         request: Default::default(),
         jetstream: false,
+        message_id: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -404,6 +414,7 @@ async fn nats_tls_client_cert_invalid() {
         auth: None,
         request: Default::default(),
         jetstream: false,
+        message_id: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -442,6 +453,7 @@ async fn nats_tls_jwt_auth_valid() {
         }),
         request: Default::default(),
         jetstream: false,
+        message_id: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -480,6 +492,7 @@ async fn nats_tls_jwt_auth_invalid() {
         }),
         request: Default::default(),
         jetstream: false,
+        message_id: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -489,3 +502,43 @@ async fn nats_tls_jwt_auth_invalid() {
         r
     );
 }
+
+#[tokio::test]
+async fn nats_jetstream_message_id_deduplicates() {
+    trace_init();
+
+    let subject = format!("test-{}", random_string(10));
+    let url =
+        std::env::var("NATS_ADDRESS").unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+    let conf = NatsSinkConfig {
+        acknowledgements: Default::default(),
+        encoding: TextSerializerConfig::default().into(),
+        connection_name: "".to_owned(),
+        subject: Template::try_from(subject.as_str()).unwrap(),
+        url,
+        tls: None,
+        auth: None,
+        request: Default::default(),
+        jetstream: true,
+        message_id: Some(Template::try_from("duplicate").unwrap()),
+    };
+
+    let context = async_nats::jetstream::new(conf.connect().await.unwrap());
+    let mut stream = context
+        .create_stream(async_nats::jetstream::stream::Config {
+            name: subject.clone(),
+            subjects: vec![subject.clone()],
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let sink = NatsSink::new(conf).await.unwrap();
+    let sink = VectorSink::from_event_streamsink(sink);
+    let (_, events) = random_lines_with_stream(100, 10, None);
+    run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+    // All the events share the same message ID, so only the first one is stored.
+    assert_eq!(stream.info().await.unwrap().state.messages, 1);
+}
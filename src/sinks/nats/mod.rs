@@ -29,4 +29,6 @@ enum NatsError {
     ServerError { source: async_nats::Error },
     #[snafu(display("NATS Publish Error: {}", source))]
     PublishError { source: async_nats::Error },
+    #[snafu(display("The `message_id` option requires `jetstream` to be enabled."))]
+    MessageIdWithoutJetStream,
 }
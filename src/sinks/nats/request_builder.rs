@@ -39,6 +39,7 @@ impl encoding::Encoder<Event> for NatsEncoder {
 
 pub(super) struct NatsMetadata {
     subject: String,
+    message_id: Option<String>,
     finalizers: EventFinalizers,
 }
 
@@ -50,6 +51,7 @@ pub(super) struct NatsRequestBuilder {
 pub(super) struct NatsRequest {
     pub(super) bytes: Bytes,
     pub(super) subject: String,
+    pub(super) message_id: Option<String>,
     finalizers: EventFinalizers,
     pub(super) metadata: RequestMetadata,
 }
@@ -94,6 +96,7 @@ impl RequestBuilder<NatsEvent> for NatsRequestBuilder {
 
         let metadata = NatsMetadata {
             subject: input.subject,
+            message_id: input.message_id,
             finalizers: input.event.take_finalizers(),
         };
 
@@ -110,6 +113,7 @@ impl RequestBuilder<NatsEvent> for NatsRequestBuilder {
         NatsRequest {
             bytes: body,
             subject: nats_metadata.subject,
+            message_id: nats_metadata.message_id,
             finalizers: nats_metadata.finalizers,
             metadata,
         }
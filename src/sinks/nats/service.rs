@@ -48,7 +48,7 @@ impl Service<NatsRequest> for NatsService {
 
         Box::pin(async move {
             match publisher
-                .publish(req.subject, req.bytes)
+                .publish(req.subject, req.message_id, req.bytes)
                 .map_err(async_nats::Error::from)
                 .await
             {
@@ -14,6 +14,7 @@ use super::{
 pub(super) struct NatsEvent {
     pub(super) event: Event,
     pub(super) subject: String,
+    pub(super) message_id: Option<String>,
 }
 
 pub(super) struct NatsSink {
@@ -22,6 +23,7 @@ pub(super) struct NatsSink {
     encoder: Encoder<()>,
     publisher: Arc<NatsPublisher>,
     subject: Template,
+    message_id: Option<Template>,
 }
 
 impl NatsSink {
@@ -38,16 +40,41 @@ impl NatsSink {
             })
             .ok()?;
 
-        Some(NatsEvent { event, subject })
+        let message_id = match &self.message_id {
+            Some(message_id) => Some(
+                message_id
+                    .render_string(&event)
+                    .map_err(|missing_keys| {
+                        emit!(TemplateRenderingError {
+                            error: missing_keys,
+                            field: Some("message_id"),
+                            drop_event: true,
+                        });
+                    })
+                    .ok()?,
+            ),
+            None => None,
+        };
+
+        Some(NatsEvent {
+            event,
+            subject,
+            message_id,
+        })
     }
 
     pub(super) async fn new(config: NatsSinkConfig) -> Result<Self, NatsError> {
+        if config.message_id.is_some() && !config.jetstream {
+            return Err(NatsError::MessageIdWithoutJetStream);
+        }
+
         let publisher = Arc::new(config.publisher().await?);
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build().context(EncodingSnafu)?;
         let encoder = Encoder::<()>::new(serializer);
         let request = config.request;
         let subject = config.subject;
+        let message_id = config.message_id;
 
         Ok(NatsSink {
             request,
@@ -55,6 +82,7 @@ impl NatsSink {
             encoder,
             publisher,
             subject,
+            message_id,
         })
     }
 
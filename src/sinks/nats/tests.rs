@@ -1,6 +1,24 @@
-use super::config::NatsSinkConfig;
+use super::{config::NatsSinkConfig, sink::NatsSink, NatsError};
 
 #[test]
 fn generate_config() {
     crate::test_util::test_generate_config::<NatsSinkConfig>();
 }
+
+#[tokio::test]
+async fn message_id_requires_jetstream() {
+    let config: NatsSinkConfig = toml::from_str(
+        r#"
+            subject = "events"
+            url = "nats://127.0.0.1:4222"
+            message_id = "{{ id }}"
+            encoding.codec = "json"
+        "#,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        NatsSink::new(config).await,
+        Err(NatsError::MessageIdWithoutJetStream)
+    ));
+}
@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+
+use futures::FutureExt;
+use http::Uri;
+use hyper::client::HttpConnector;
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use tonic::body::BoxBody;
+use tower::ServiceBuilder;
+use vector_lib::{configurable::configurable_component, lookup::lookup_v2::ConfigTargetPath};
+
+use super::{
+    encoder::OtlpEncoder,
+    service::{OpentelemetryResponse, OpentelemetryService},
+    sink::OpentelemetrySink,
+    OpentelemetrySinkError,
+};
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, ProxyConfig, SinkConfig, SinkContext},
+    http::build_proxy_connector,
+    sinks::{
+        util::{
+            retries::RetryLogic, BatchConfig, RealtimeEventBasedDefaultBatchSettings,
+            ServiceBuilderExt, TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+/// Configuration for the `opentelemetry` sink.
+#[configurable_component(sink(
+    "opentelemetry",
+    "Export logs, metrics, and traces to an OTLP endpoint."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetrySinkConfig {
+    /// The OTLP/gRPC endpoint to export to.
+    ///
+    /// The endpoint _must_ include a scheme and a port.
+    #[configurable(validation(format = "uri"))]
+    #[configurable(metadata(docs::examples = "http://localhost:4317"))]
+    #[configurable(metadata(docs::examples = "https://otel-collector:4317"))]
+    pub endpoint: String,
+
+    /// Whether or not to compress requests.
+    ///
+    /// If set to `true`, requests are compressed with [`gzip`][gzip_docs].
+    ///
+    /// [gzip_docs]: https://www.gzip.org/
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    pub compression: bool,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub logs: LogMappingConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// How the fields of log events are mapped to the fields of OTLP log records.
+///
+/// The defaults match the layout of the logs emitted by the `opentelemetry` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LogMappingConfig {
+    /// The field holding the body of the log records.
+    ///
+    /// Defaults to the message of the logs.
+    #[configurable(metadata(docs::examples = "message"))]
+    pub body_field: Option<ConfigTargetPath>,
+
+    /// The field holding the attributes of the log records, as an object.
+    #[serde(default = "default_attributes_field")]
+    pub attributes_field: ConfigTargetPath,
+
+    /// The field holding the attributes of the resource emitting the logs, as an object.
+    #[serde(default = "default_resource_field")]
+    pub resource_field: ConfigTargetPath,
+
+    /// Resource attributes to set from fields of the logs, by attribute name.
+    ///
+    /// This allows setting attributes from the OpenTelemetry semantic conventions, such as
+    /// `service.name`, from fields of logs which don't follow them.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The field to set the resource attribute from."
+    ))]
+    #[configurable(metadata(docs::examples = "example_resource_attributes()"))]
+    pub resource_attributes: HashMap<String, ConfigTargetPath>,
+
+    /// The field holding the instrumentation scope of the logs, as an object with the `name`,
+    /// `version`, and `attributes` fields.
+    #[serde(default = "default_scope_field")]
+    pub scope_field: ConfigTargetPath,
+
+    /// The field holding the severity text of the log records.
+    #[serde(default = "default_severity_text_field")]
+    pub severity_text_field: ConfigTargetPath,
+
+    /// The field holding the severity number of the log records.
+    #[serde(default = "default_severity_number_field")]
+    pub severity_number_field: ConfigTargetPath,
+
+    /// The field holding the hex encoded trace ID of the log records.
+    #[serde(default = "default_trace_id_field")]
+    pub trace_id_field: ConfigTargetPath,
+
+    /// The field holding the hex encoded span ID of the log records.
+    #[serde(default = "default_span_id_field")]
+    pub span_id_field: ConfigTargetPath,
+
+    /// Whether to add the fields of the logs which aren't mapped to an OTLP field as attributes of
+    /// the log records.
+    #[serde(default)]
+    pub remaining_fields_as_attributes: bool,
+}
+
+fn default_attributes_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("attributes")
+}
+
+fn default_resource_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("resources")
+}
+
+fn default_scope_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("scope")
+}
+
+fn default_severity_text_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("severity_text")
+}
+
+fn default_severity_number_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("severity_number")
+}
+
+fn default_trace_id_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("trace_id")
+}
+
+fn default_span_id_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("span_id")
+}
+
+fn example_resource_attributes() -> HashMap<String, ConfigTargetPath> {
+    HashMap::from([
+        ("service.name".to_owned(), ConfigTargetPath::from("service")),
+        ("host.name".to_owned(), ConfigTargetPath::from("host")),
+    ])
+}
+
+impl Default for LogMappingConfig {
+    fn default() -> Self {
+        Self {
+            body_field: None,
+            attributes_field: default_attributes_field(),
+            resource_field: default_resource_field(),
+            resource_attributes: HashMap::new(),
+            scope_field: default_scope_field(),
+            severity_text_field: default_severity_text_field(),
+            severity_number_field: default_severity_number_field(),
+            trace_id_field: default_trace_id_field(),
+            span_id_field: default_span_id_field(),
+            remaining_fields_as_attributes: false,
+        }
+    }
+}
+
+impl GenerateConfig for OpentelemetrySinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://localhost:4317""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpentelemetrySinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let uri = parse_endpoint(&self.endpoint)?;
+        let client = new_client(&tls, cx.proxy())?;
+
+        let service = OpentelemetryService::new(client, uri, self.compression);
+        let request_settings = self.request.into_settings();
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let service = ServiceBuilder::new()
+            .settings(request_settings, OpentelemetryRetryLogic)
+            .service(service);
+
+        let sink = OpentelemetrySink {
+            batch_settings,
+            encoder: OtlpEncoder::new(self.logs.clone()),
+            service,
+        };
+
+        // OTLP doesn't define a health check, and export requests can't be sent without data.
+        let healthcheck = futures::future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+fn parse_endpoint(endpoint: &str) -> crate::Result<Uri> {
+    let uri: Uri = endpoint.parse()?;
+    if uri.scheme().is_none() {
+        return Err(Box::new(OpentelemetrySinkError::NoScheme));
+    }
+    if uri.authority().is_none() {
+        return Err(Box::new(OpentelemetrySinkError::NoHost));
+    }
+    Ok(uri)
+}
+
+fn new_client(
+    tls_settings: &MaybeTlsSettings,
+    proxy_config: &ProxyConfig,
+) -> crate::Result<hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>> {
+    let proxy = build_proxy_connector(tls_settings.clone(), proxy_config)?;
+
+    Ok(hyper::Client::builder().http2_only(true).build(proxy))
+}
+
+#[derive(Debug, Clone)]
+struct OpentelemetryRetryLogic;
+
+impl RetryLogic for OpentelemetryRetryLogic {
+    type Error = OpentelemetrySinkError;
+    type Response = OpentelemetryResponse;
+
+    fn is_retriable_error(&self, err: &Self::Error) -> bool {
+        use tonic::Code::*;
+
+        match err {
+            OpentelemetrySinkError::Request { source } => matches!(
+                source.code(),
+                // List taken from
+                //
+                // <https://opentelemetry.io/docs/specs/otlp/#failures>
+                Cancelled
+                    | DeadlineExceeded
+                    | ResourceExhausted
+                    | Aborted
+                    | OutOfRange
+                    | Unavailable
+                    | DataLoss
+            ),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetrySinkConfig>();
+    }
+
+    #[test]
+    fn requires_endpoint_scheme() {
+        assert!(parse_endpoint("localhost:4317").is_err());
+        assert!(parse_endpoint("http://localhost:4317").is_ok());
+    }
+}
@@ -0,0 +1,802 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use vector_lib::{
+    event::{
+        metric::{Bucket, MetricSketch, Quantile},
+        Event, LogEvent, Metric, MetricKind, MetricValue, TraceEvent,
+    },
+    lookup::lookup_v2::ConfigTargetPath,
+    metrics::AgentDDSketch,
+    opentelemetry::proto::{
+        common::v1::{
+            any_value::Value as PBValue, AnyValue, ArrayValue, InstrumentationScope, KeyValue,
+            KeyValueList,
+        },
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        metrics::v1::{
+            exponential_histogram_data_point::Buckets, metric::Data, number_data_point,
+            summary_data_point::ValueAtQuantile, AggregationTemporality, ExponentialHistogram,
+            ExponentialHistogramDataPoint, Gauge, Histogram, HistogramDataPoint,
+            Metric as OtelMetric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum, Summary,
+            SummaryDataPoint,
+        },
+        resource::v1::Resource,
+        trace::v1::{
+            span::{Event as SpanEvent, Link},
+            ResourceSpans, ScopeSpans, Span, Status,
+        },
+    },
+};
+use vrl::value::{ObjectMap, Value};
+
+use super::config::LogMappingConfig;
+use crate::internal_events::OpentelemetryInvalidMetricError;
+
+/// A single event, converted to its OTLP representation.
+#[derive(Clone, Debug)]
+pub(super) enum OtlpRecord {
+    Logs(ResourceLogs),
+    Metrics(ResourceMetrics),
+    Traces(ResourceSpans),
+}
+
+/// Converts events to OTLP records.
+#[derive(Clone, Debug)]
+pub(super) struct OtlpEncoder {
+    logs: LogMappingConfig,
+}
+
+impl OtlpEncoder {
+    pub(super) const fn new(logs: LogMappingConfig) -> Self {
+        Self { logs }
+    }
+
+    /// Converts an event, or returns `None` if it can't be represented in OTLP.
+    pub(super) fn encode(&self, event: Event) -> Option<OtlpRecord> {
+        match event {
+            Event::Log(log) => Some(OtlpRecord::Logs(self.encode_log(log))),
+            Event::Metric(metric) => encode_metric(metric).map(OtlpRecord::Metrics),
+            Event::Trace(trace) => Some(OtlpRecord::Traces(encode_trace(trace))),
+        }
+    }
+
+    fn encode_log(&self, mut log: LogEvent) -> ResourceLogs {
+        let mapping = &self.logs;
+        let timestamp = log.get_timestamp().and_then(Value::as_timestamp).copied();
+        if let Some(timestamp_path) = log.timestamp_path().cloned() {
+            log.remove(&timestamp_path);
+        }
+
+        let mut resource_attributes = take_object(&mut log, &mapping.resource_field);
+        for (name, field) in &mapping.resource_attributes {
+            if let Some(value) = log.get(&field.0).cloned() {
+                resource_attributes.insert(name.as_str().into(), value);
+            }
+        }
+        let scope = log.remove(&mapping.scope_field.0).map(into_scope);
+        let mut attributes = take_object(&mut log, &mapping.attributes_field);
+        let severity_text = log
+            .remove(&mapping.severity_text_field.0)
+            .map(|value| value.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let severity_number = match log.remove(&mapping.severity_number_field.0) {
+            Some(Value::Integer(number)) => number as i32,
+            _ => 0,
+        };
+        let trace_id = log
+            .remove(&mapping.trace_id_field.0)
+            .map(into_id)
+            .unwrap_or_default();
+        let span_id = log
+            .remove(&mapping.span_id_field.0)
+            .map(into_id)
+            .unwrap_or_default();
+
+        let body = match mapping.body_field.as_ref() {
+            Some(field) => log.remove(&field.0),
+            None => match log.message_path().cloned() {
+                // With the Vector namespace, the whole event is the body.
+                Some(path) if path.path.is_root() => Some(std::mem::replace(
+                    log.value_mut(),
+                    Value::Object(ObjectMap::new()),
+                )),
+                Some(path) => log.remove(&path),
+                None => None,
+            },
+        };
+
+        if mapping.remaining_fields_as_attributes {
+            if let Value::Object(fields) = log.value_mut() {
+                attributes.extend(std::mem::take(fields));
+            }
+        }
+
+        let record = LogRecord {
+            time_unix_nano: timestamp.map(unix_nanos).unwrap_or_default(),
+            severity_number,
+            severity_text,
+            body: body.and_then(into_any_value),
+            attributes: into_key_values(attributes),
+            trace_id,
+            span_id,
+            ..Default::default()
+        };
+
+        ResourceLogs {
+            resource: Some(Resource {
+                attributes: into_key_values(resource_attributes),
+                dropped_attributes_count: 0,
+            }),
+            scope_logs: vec![ScopeLogs {
+                scope,
+                log_records: vec![record],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }
+    }
+}
+
+/// Converts a trace event with the layout of the events emitted by the `opentelemetry` source,
+/// holding a single span.
+fn encode_trace(trace: TraceEvent) -> ResourceSpans {
+    let (mut fields, _) = trace.into_parts();
+    let mut take = |key: &str| fields.remove(key);
+
+    let span = Span {
+        trace_id: take("trace_id").map(into_id).unwrap_or_default(),
+        span_id: take("span_id").map(into_id).unwrap_or_default(),
+        trace_state: take("trace_state").map(into_string).unwrap_or_default(),
+        parent_span_id: take("parent_span_id").map(into_id).unwrap_or_default(),
+        name: take("name").map(into_string).unwrap_or_default(),
+        kind: take("kind").map(into_integer).unwrap_or_default() as i32,
+        start_time_unix_nano: take("start_time_unix_nano")
+            .map(into_unix_nanos)
+            .unwrap_or_default(),
+        end_time_unix_nano: take("end_time_unix_nano")
+            .map(into_unix_nanos)
+            .unwrap_or_default(),
+        attributes: take("attributes")
+            .map(into_object)
+            .map(into_key_values)
+            .unwrap_or_default(),
+        dropped_attributes_count: take("dropped_attributes_count")
+            .map(into_integer)
+            .unwrap_or_default() as u32,
+        events: take("events")
+            .map(into_array)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|event| {
+                let mut event = into_object(event);
+                SpanEvent {
+                    time_unix_nano: event
+                        .remove("time_unix_nano")
+                        .map(into_unix_nanos)
+                        .unwrap_or_default(),
+                    name: event.remove("name").map(into_string).unwrap_or_default(),
+                    attributes: event
+                        .remove("attributes")
+                        .map(into_object)
+                        .map(into_key_values)
+                        .unwrap_or_default(),
+                    dropped_attributes_count: event
+                        .remove("dropped_attributes_count")
+                        .map(into_integer)
+                        .unwrap_or_default() as u32,
+                }
+            })
+            .collect(),
+        dropped_events_count: take("dropped_events_count")
+            .map(into_integer)
+            .unwrap_or_default() as u32,
+        links: take("links")
+            .map(into_array)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|link| {
+                let mut link = into_object(link);
+                Link {
+                    trace_id: link.remove("trace_id").map(into_id).unwrap_or_default(),
+                    span_id: link.remove("span_id").map(into_id).unwrap_or_default(),
+                    trace_state: link
+                        .remove("trace_state")
+                        .map(into_string)
+                        .unwrap_or_default(),
+                    attributes: link
+                        .remove("attributes")
+                        .map(into_object)
+                        .map(into_key_values)
+                        .unwrap_or_default(),
+                    dropped_attributes_count: link
+                        .remove("dropped_attributes_count")
+                        .map(into_integer)
+                        .unwrap_or_default() as u32,
+                }
+            })
+            .collect(),
+        dropped_links_count: take("dropped_links_count")
+            .map(into_integer)
+            .unwrap_or_default() as u32,
+        status: take("status").map(|status| {
+            let mut status = into_object(status);
+            Status {
+                message: status
+                    .remove("message")
+                    .map(into_string)
+                    .unwrap_or_default(),
+                code: status.remove("code").map(into_integer).unwrap_or_default() as i32,
+            }
+        }),
+    };
+
+    ResourceSpans {
+        resource: Some(Resource {
+            attributes: take("resources")
+                .map(into_object)
+                .map(into_key_values)
+                .unwrap_or_default(),
+            dropped_attributes_count: 0,
+        }),
+        scope_spans: vec![ScopeSpans {
+            scope: take("scope").map(into_scope),
+            spans: vec![span],
+            schema_url: String::new(),
+        }],
+        schema_url: String::new(),
+    }
+}
+
+/// Converts a metric, the tags prefixed with `resource.` being set as resource attributes, and the
+/// `scope.name`, `scope.version`, and `scope.attributes.` prefixed tags setting the instrumentation
+/// scope, as done by the `opentelemetry` source.
+fn encode_metric(metric: Metric) -> Option<ResourceMetrics> {
+    let kind = metric.kind();
+    let temporality = match kind {
+        MetricKind::Incremental => AggregationTemporality::Delta,
+        MetricKind::Absolute => AggregationTemporality::Cumulative,
+    } as i32;
+    let time_unix_nano = metric.timestamp().map(unix_nanos).unwrap_or_default();
+    let name = match metric.namespace() {
+        Some(namespace) => format!("{}.{}", namespace, metric.name()),
+        None => metric.name().to_owned(),
+    };
+
+    let mut resource_attributes = Vec::new();
+    let mut scope = InstrumentationScope::default();
+    let mut attributes = Vec::new();
+    for (key, value) in metric
+        .tags()
+        .into_iter()
+        .flat_map(|tags| tags.iter_single())
+    {
+        let value = Some(AnyValue {
+            value: Some(PBValue::StringValue(value.to_owned())),
+        });
+        if let Some(key) = key.strip_prefix("resource.") {
+            resource_attributes.push(KeyValue {
+                key: key.to_owned(),
+                value,
+            });
+        } else if key == "scope.name" {
+            scope.name = value_string(value);
+        } else if key == "scope.version" {
+            scope.version = value_string(value);
+        } else if let Some(key) = key.strip_prefix("scope.attributes.") {
+            scope.attributes.push(KeyValue {
+                key: key.to_owned(),
+                value,
+            });
+        } else {
+            attributes.push(KeyValue {
+                key: key.to_owned(),
+                value,
+            });
+        }
+    }
+
+    let number_point = |value: f64, attributes: Vec<KeyValue>| NumberDataPoint {
+        attributes,
+        time_unix_nano,
+        value: Some(number_data_point::Value::AsDouble(value)),
+        ..Default::default()
+    };
+    let data = match metric.value() {
+        MetricValue::Counter { value } => Data::Sum(Sum {
+            data_points: vec![number_point(*value, attributes)],
+            aggregation_temporality: temporality,
+            is_monotonic: true,
+        }),
+        MetricValue::Gauge { value } if kind == MetricKind::Absolute => Data::Gauge(Gauge {
+            data_points: vec![number_point(*value, attributes)],
+        }),
+        // Incremental gauges are changes of a value, which may decrease.
+        MetricValue::Gauge { value } => Data::Sum(Sum {
+            data_points: vec![number_point(*value, attributes)],
+            aggregation_temporality: temporality,
+            is_monotonic: false,
+        }),
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => {
+            let (explicit_bounds, bucket_counts) = explicit_buckets(buckets);
+            Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    attributes,
+                    time_unix_nano,
+                    count: *count,
+                    sum: Some(*sum),
+                    bucket_counts,
+                    explicit_bounds,
+                    ..Default::default()
+                }],
+                aggregation_temporality: temporality,
+            })
+        }
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                attributes,
+                time_unix_nano,
+                count: *count,
+                sum: *sum,
+                quantile_values: quantiles
+                    .iter()
+                    .map(|Quantile { quantile, value }| ValueAtQuantile {
+                        quantile: *quantile,
+                        value: *value,
+                    })
+                    .collect(),
+                ..Default::default()
+            }],
+        }),
+        MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(sketch),
+        } => exponential_histogram(sketch, attributes, time_unix_nano, temporality),
+        MetricValue::Distribution { samples, .. } => {
+            let mut sketch = AgentDDSketch::with_agent_defaults();
+            for sample in samples {
+                sketch.insert_n(sample.value, sample.rate);
+            }
+            exponential_histogram(&sketch, attributes, time_unix_nano, temporality)
+        }
+        value => {
+            emit!(OpentelemetryInvalidMetricError { value, kind });
+            return None;
+        }
+    };
+
+    Some(ResourceMetrics {
+        resource: Some(Resource {
+            attributes: resource_attributes,
+            dropped_attributes_count: 0,
+        }),
+        scope_metrics: vec![ScopeMetrics {
+            scope: Some(scope),
+            metrics: vec![OtelMetric {
+                name,
+                data: Some(data),
+                ..Default::default()
+            }],
+            schema_url: String::new(),
+        }],
+        schema_url: String::new(),
+    })
+}
+
+/// Splits buckets into OTLP bounds and counts, OTLP histograms always ending with a bucket holding
+/// the values above all bounds.
+fn explicit_buckets(buckets: &[Bucket]) -> (Vec<f64>, Vec<u64>) {
+    let mut bounds = Vec::with_capacity(buckets.len());
+    let mut counts = Vec::with_capacity(buckets.len() + 1);
+    for bucket in buckets {
+        if bucket.upper_limit == f64::INFINITY {
+            counts.push(bucket.count);
+            return (bounds, counts);
+        }
+        bounds.push(bucket.upper_limit);
+        counts.push(bucket.count);
+    }
+    counts.push(0);
+    (bounds, counts)
+}
+
+/// The largest scale of the exponential histograms sketches are exported as, their buckets being
+/// slightly narrower than the bins of the sketches.
+const MAX_EXPONENTIAL_SCALE: i32 = 6;
+
+/// The smallest scale of exponential histograms defined by the specification.
+const MIN_EXPONENTIAL_SCALE: i32 = -10;
+
+/// The maximum number of positive or negative buckets of exponential histograms, as the default of
+/// the OpenTelemetry SDKs.
+const MAX_EXPONENTIAL_BUCKETS: i32 = 160;
+
+/// Converts a sketch into an exponential histogram, with the largest scale keeping the number of
+/// buckets under `MAX_EXPONENTIAL_BUCKETS`.
+///
+/// The observations of each bin are counted towards the bucket holding the value represented by
+/// the bin, the bucket at index `i` holding the values within `(base^i, base^(i + 1)]`, with
+/// `base = 2^(2^-scale)`.
+fn exponential_histogram(
+    sketch: &AgentDDSketch,
+    attributes: Vec<KeyValue>,
+    time_unix_nano: u64,
+    aggregation_temporality: i32,
+) -> Data {
+    let (min, max) = (
+        sketch.min().unwrap_or_default(),
+        sketch.max().unwrap_or_default(),
+    );
+    let (keys, counts) = sketch.bin_map().into_parts();
+    let bins = keys
+        .into_iter()
+        .zip(counts)
+        .map(|(key, count)| {
+            let value = sketch.config().bin_lower_bound(key).clamp(min, max);
+            (value, u64::from(count))
+        })
+        .collect::<Vec<_>>();
+
+    let index = |value: f64, scale: i32| (value.abs().log2() * 2f64.powi(scale)).ceil() as i32 - 1;
+    let index_range = |positive: bool, scale: i32| {
+        bins.iter()
+            .filter(|(value, _)| *value != 0.0 && value.is_sign_positive() == positive)
+            .map(|(value, _)| index(*value, scale))
+            .fold(None, |range, index| match range {
+                None => Some((index, index)),
+                Some((low, high)) => Some((index.min(low), index.max(high))),
+            })
+    };
+    let fits = |range: Option<(i32, i32)>| {
+        range.map_or(true, |(low, high)| high - low < MAX_EXPONENTIAL_BUCKETS)
+    };
+
+    let mut scale = MAX_EXPONENTIAL_SCALE;
+    while scale > MIN_EXPONENTIAL_SCALE
+        && !(fits(index_range(true, scale)) && fits(index_range(false, scale)))
+    {
+        scale -= 1;
+    }
+
+    let buckets = |positive: bool| {
+        index_range(positive, scale).map(|(low, high)| {
+            let mut bucket_counts = vec![0; (high - low + 1) as usize];
+            for (value, count) in &bins {
+                if *value != 0.0 && value.is_sign_positive() == positive {
+                    let index = index(*value, scale).clamp(low, high);
+                    bucket_counts[(index - low) as usize] += count;
+                }
+            }
+            Buckets {
+                offset: low,
+                bucket_counts,
+            }
+        })
+    };
+
+    Data::ExponentialHistogram(ExponentialHistogram {
+        data_points: vec![ExponentialHistogramDataPoint {
+            attributes,
+            time_unix_nano,
+            count: u64::from(sketch.count()),
+            sum: sketch.sum(),
+            scale,
+            zero_count: bins
+                .iter()
+                .filter(|(value, _)| *value == 0.0)
+                .map(|(_, count)| count)
+                .sum(),
+            positive: buckets(true),
+            negative: buckets(false),
+            min: sketch.min(),
+            max: sketch.max(),
+            ..Default::default()
+        }],
+        aggregation_temporality,
+    })
+}
+
+fn unix_nanos(timestamp: DateTime<Utc>) -> u64 {
+    timestamp.timestamp_nanos_opt().unwrap_or_default() as u64
+}
+
+fn take_object(log: &mut LogEvent, field: &ConfigTargetPath) -> ObjectMap {
+    log.remove(&field.0).map(into_object).unwrap_or_default()
+}
+
+fn into_object(value: Value) -> ObjectMap {
+    match value {
+        Value::Object(object) => object,
+        _ => ObjectMap::new(),
+    }
+}
+
+fn into_array(value: Value) -> Vec<Value> {
+    match value {
+        Value::Array(array) => array,
+        _ => Vec::new(),
+    }
+}
+
+fn into_string(value: Value) -> String {
+    value.to_string_lossy().into_owned()
+}
+
+fn into_integer(value: Value) -> i64 {
+    match value {
+        Value::Integer(integer) => integer,
+        _ => 0,
+    }
+}
+
+fn into_unix_nanos(value: Value) -> u64 {
+    match value {
+        Value::Timestamp(timestamp) => unix_nanos(timestamp),
+        Value::Integer(nanos) => nanos as u64,
+        _ => 0,
+    }
+}
+
+/// Decodes a hex encoded trace or span ID, invalid IDs being left empty.
+fn into_id(value: Value) -> Vec<u8> {
+    match value {
+        Value::Bytes(id) => hex::decode(id).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+fn into_scope(value: Value) -> InstrumentationScope {
+    let mut scope = into_object(value);
+    InstrumentationScope {
+        name: scope.remove("name").map(into_string).unwrap_or_default(),
+        version: scope.remove("version").map(into_string).unwrap_or_default(),
+        attributes: scope
+            .remove("attributes")
+            .map(into_object)
+            .map(into_key_values)
+            .unwrap_or_default(),
+        dropped_attributes_count: scope
+            .remove("dropped_attributes_count")
+            .map(into_integer)
+            .unwrap_or_default() as u32,
+    }
+}
+
+fn value_string(value: Option<AnyValue>) -> String {
+    match value.and_then(|value| value.value) {
+        Some(PBValue::StringValue(value)) => value,
+        _ => String::new(),
+    }
+}
+
+fn into_key_values(object: ObjectMap) -> Vec<KeyValue> {
+    object
+        .into_iter()
+        .filter_map(|(key, value)| {
+            into_any_value(value).map(|value| KeyValue {
+                key: key.into(),
+                value: Some(value),
+            })
+        })
+        .collect()
+}
+
+/// Converts a value, or returns `None` for `null`, which has no OTLP representation.
+fn into_any_value(value: Value) -> Option<AnyValue> {
+    let value = match value {
+        Value::Bytes(bytes) => PBValue::StringValue(String::from_utf8_lossy(&bytes).into_owned()),
+        Value::Regex(regex) => {
+            PBValue::StringValue(String::from_utf8_lossy(&regex.as_bytes()).into_owned())
+        }
+        Value::Integer(integer) => PBValue::IntValue(integer),
+        Value::Float(float) => PBValue::DoubleValue(float.into_inner()),
+        Value::Boolean(boolean) => PBValue::BoolValue(boolean),
+        Value::Timestamp(timestamp) => {
+            PBValue::StringValue(timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        }
+        Value::Object(object) => PBValue::KvlistValue(KeyValueList {
+            values: into_key_values(object),
+        }),
+        Value::Array(array) => PBValue::ArrayValue(ArrayValue {
+            values: array.into_iter().filter_map(into_any_value).collect(),
+        }),
+        Value::Null => return None,
+    };
+    Some(AnyValue { value: Some(value) })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_lib::{buckets, event::StatisticKind, metric_tags, samples};
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn string_value(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(PBValue::StringValue(value.to_owned())),
+        })
+    }
+
+    #[test]
+    fn encodes_logs() {
+        let mut log = LogEvent::from("login failed");
+        log.insert("timestamp", Utc.timestamp_nanos(1));
+        log.insert("severity_text", "WARN");
+        log.insert("severity_number", 13);
+        log.insert("trace_id", "4ac52aadf321c2e531db005df08792f5");
+        log.insert("attributes", btreemap! { "user" => "alice" });
+        log.insert("resources", btreemap! { "host.name" => "web-1" });
+        log.insert("service", "auth");
+        log.insert("status", 401);
+
+        let logs = LogMappingConfig {
+            resource_attributes: [("service.name".into(), ConfigTargetPath::from("service"))]
+                .into(),
+            remaining_fields_as_attributes: true,
+            ..Default::default()
+        };
+        let Some(OtlpRecord::Logs(resource_logs)) = OtlpEncoder::new(logs).encode(log.into())
+        else {
+            panic!("log not encoded as a log record");
+        };
+
+        let resource = resource_logs.resource.unwrap();
+        assert_eq!(
+            resource.attributes,
+            vec![
+                KeyValue {
+                    key: "host.name".into(),
+                    value: string_value("web-1"),
+                },
+                KeyValue {
+                    key: "service.name".into(),
+                    value: string_value("auth"),
+                },
+            ]
+        );
+
+        let record = &resource_logs.scope_logs[0].log_records[0];
+        assert_eq!(record.time_unix_nano, 1);
+        assert_eq!(record.severity_text, "WARN");
+        assert_eq!(record.severity_number, 13);
+        assert_eq!(
+            hex::encode(&record.trace_id),
+            "4ac52aadf321c2e531db005df08792f5"
+        );
+        assert_eq!(record.body, string_value("login failed"));
+        assert_eq!(
+            record.attributes,
+            vec![
+                KeyValue {
+                    key: "service".into(),
+                    value: string_value("auth"),
+                },
+                KeyValue {
+                    key: "status".into(),
+                    value: Some(AnyValue {
+                        value: Some(PBValue::IntValue(401)),
+                    }),
+                },
+                KeyValue {
+                    key: "user".into(),
+                    value: string_value("alice"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_metrics() {
+        let metric = Metric::new(
+            "latency",
+            MetricKind::Incremental,
+            MetricValue::AggregatedHistogram {
+                buckets: buckets![0.1 => 1, 1.0 => 2],
+                count: 3,
+                sum: 1.5,
+            },
+        )
+        .with_tags(Some(metric_tags!(
+            "resource.service.name" => "api",
+            "scope.name" => "some.scope.name",
+            "host" => "localhost",
+        )));
+
+        let Some(OtlpRecord::Metrics(resource_metrics)) =
+            OtlpEncoder::new(Default::default()).encode(metric.into())
+        else {
+            panic!("metric not encoded as a metric");
+        };
+
+        assert_eq!(
+            resource_metrics.resource.unwrap().attributes,
+            vec![KeyValue {
+                key: "service.name".into(),
+                value: string_value("api"),
+            }]
+        );
+        let scope_metrics = &resource_metrics.scope_metrics[0];
+        assert_eq!(
+            scope_metrics.scope.as_ref().unwrap().name,
+            "some.scope.name"
+        );
+        let Some(Data::Histogram(histogram)) = &scope_metrics.metrics[0].data else {
+            panic!("histogram not encoded as a histogram");
+        };
+        assert_eq!(
+            histogram.aggregation_temporality,
+            AggregationTemporality::Delta as i32
+        );
+        let point = &histogram.data_points[0];
+        assert_eq!(point.explicit_bounds, vec![0.1, 1.0]);
+        assert_eq!(point.bucket_counts, vec![1, 2, 0]);
+        assert_eq!(
+            point.attributes,
+            vec![KeyValue {
+                key: "host".into(),
+                value: string_value("localhost"),
+            }]
+        );
+    }
+
+    #[test]
+    fn encodes_sketches_as_exponential_histograms() {
+        let metric = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::Distribution {
+                samples: samples![0.0 => 1, 1.0 => 2, 3.0 => 1, -2.0 => 1],
+                statistic: StatisticKind::Histogram,
+            },
+        );
+
+        let Some(OtlpRecord::Metrics(resource_metrics)) =
+            OtlpEncoder::new(Default::default()).encode(metric.into())
+        else {
+            panic!("metric not encoded as a metric");
+        };
+        let Some(Data::ExponentialHistogram(histogram)) =
+            &resource_metrics.scope_metrics[0].metrics[0].data
+        else {
+            panic!("distribution not encoded as an exponential histogram");
+        };
+        assert_eq!(
+            histogram.aggregation_temporality,
+            AggregationTemporality::Cumulative as i32
+        );
+        let point = &histogram.data_points[0];
+        assert_eq!(point.scale, MAX_EXPONENTIAL_SCALE);
+        assert_eq!(point.count, 5);
+        assert_eq!(point.zero_count, 1);
+        assert_eq!(point.min, Some(-2.0));
+        assert_eq!(point.max, Some(3.0));
+
+        let positive = point.positive.as_ref().unwrap();
+        assert_eq!(positive.offset, -1);
+        assert_eq!(positive.bucket_counts.iter().sum::<u64>(), 3);
+        assert_eq!(positive.bucket_counts[0], 2);
+        let negative = point.negative.as_ref().unwrap();
+        assert_eq!(negative.bucket_counts, vec![1]);
+    }
+
+    #[test]
+    fn drops_unsupported_metrics() {
+        let metric = Metric::new(
+            "users",
+            MetricKind::Incremental,
+            MetricValue::Set {
+                values: ["alice".to_owned()].into(),
+            },
+        );
+        assert!(OtlpEncoder::new(Default::default())
+            .encode(metric.into())
+            .is_none());
+    }
+}
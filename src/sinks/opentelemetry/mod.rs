@@ -0,0 +1,26 @@
+//! The `opentelemetry` sink.
+//!
+//! Exports logs, metrics, and traces to an OTLP/gRPC endpoint, such as an OpenTelemetry
+//! collector.
+
+use snafu::Snafu;
+
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+pub use config::OpentelemetrySinkConfig;
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum OpentelemetrySinkError {
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: tonic::Status },
+
+    #[snafu(display("Endpoint has no scheme."))]
+    NoScheme,
+
+    #[snafu(display("Endpoint has no host."))]
+    NoHost,
+}
@@ -0,0 +1,227 @@
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use http::Uri;
+use hyper::client::HttpConnector;
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use prost::Message;
+use tonic::{body::BoxBody, codec::CompressionEncoding, IntoRequest};
+use tower::Service;
+use vector_lib::opentelemetry::proto::collector::{
+    logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+    metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+    trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+};
+use vector_lib::request_metadata::{GroupedCountByteSize, MetaDescriptive, RequestMetadata};
+use vector_lib::stream::DriverResponse;
+
+use super::OpentelemetrySinkError;
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    internal_events::EndpointBytesSent,
+    sinks::util::uri,
+    Error,
+};
+
+#[derive(Clone, Debug)]
+pub struct OpentelemetryService {
+    logs: LogsServiceClient<HyperSvc>,
+    metrics: MetricsServiceClient<HyperSvc>,
+    traces: TraceServiceClient<HyperSvc>,
+    protocol: String,
+    endpoint: String,
+}
+
+pub struct OpentelemetryResponse {
+    events_byte_size: GroupedCountByteSize,
+}
+
+impl DriverResponse for OpentelemetryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+}
+
+/// An OTLP export request, of a single telemetry type.
+#[derive(Clone, Debug)]
+pub enum OtlpPayload {
+    Logs(ExportLogsServiceRequest),
+    Metrics(ExportMetricsServiceRequest),
+    Traces(ExportTraceServiceRequest),
+}
+
+impl OtlpPayload {
+    pub fn encoded_len(&self) -> usize {
+        match self {
+            Self::Logs(request) => request.encoded_len(),
+            Self::Metrics(request) => request.encoded_len(),
+            Self::Traces(request) => request.encoded_len(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OpentelemetryRequest {
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+    pub payload: OtlpPayload,
+}
+
+impl Finalizable for OpentelemetryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for OpentelemetryRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+impl OpentelemetryService {
+    pub fn new(
+        hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+        uri: Uri,
+        compression: bool,
+    ) -> Self {
+        let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
+        let svc = HyperSvc {
+            uri,
+            client: hyper_client,
+        };
+        let mut logs = LogsServiceClient::new(svc.clone());
+        let mut metrics = MetricsServiceClient::new(svc.clone());
+        let mut traces = TraceServiceClient::new(svc);
+
+        if compression {
+            logs = logs.send_compressed(CompressionEncoding::Gzip);
+            metrics = metrics.send_compressed(CompressionEncoding::Gzip);
+            traces = traces.send_compressed(CompressionEncoding::Gzip);
+        }
+        Self {
+            logs,
+            metrics,
+            traces,
+            protocol,
+            endpoint,
+        }
+    }
+
+    async fn export(&mut self, payload: OtlpPayload) -> Result<(), tonic::Status> {
+        // Warn about the items rejected by the endpoint, which would fail again if retried.
+        let (rejected, message) = match payload {
+            OtlpPayload::Logs(request) => {
+                let response = self.logs.export(request.into_request()).await?;
+                response
+                    .into_inner()
+                    .partial_success
+                    .map(|partial| (partial.rejected_log_records, partial.error_message))
+                    .unwrap_or_default()
+            }
+            OtlpPayload::Metrics(request) => {
+                let response = self.metrics.export(request.into_request()).await?;
+                response
+                    .into_inner()
+                    .partial_success
+                    .map(|partial| (partial.rejected_data_points, partial.error_message))
+                    .unwrap_or_default()
+            }
+            OtlpPayload::Traces(request) => {
+                let response = self.traces.export(request.into_request()).await?;
+                response
+                    .into_inner()
+                    .partial_success
+                    .map(|partial| (partial.rejected_spans, partial.error_message))
+                    .unwrap_or_default()
+            }
+        };
+        if rejected > 0 || !message.is_empty() {
+            warn!(
+                message = "Endpoint partially accepted the export request.",
+                rejected,
+                error = %message,
+                internal_log_rate_limit = true,
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Service<OpentelemetryRequest> for OpentelemetryService {
+    type Response = OpentelemetryResponse;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness check of the clients is done through the `export()` calls happening inside
+        // `call()`, which block until the client is ready to perform another request.
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, mut request: OpentelemetryRequest) -> Self::Future {
+        let mut service = self.clone();
+        let byte_size = request.payload.encoded_len();
+        let metadata = std::mem::take(request.metadata_mut());
+        let events_byte_size = metadata.into_events_estimated_json_encoded_byte_size();
+
+        let future = async move {
+            service
+                .export(request.payload)
+                .await
+                .map_err(|source| OpentelemetrySinkError::Request { source })?;
+
+            emit!(EndpointBytesSent {
+                byte_size,
+                protocol: &service.protocol,
+                endpoint: &service.endpoint,
+            });
+
+            Ok(OpentelemetryResponse { events_byte_size })
+        };
+
+        Box::pin(future)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HyperSvc {
+    uri: Uri,
+    client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+}
+
+impl Service<hyper::Request<BoxBody>> for HyperSvc {
+    type Response = hyper::Response<hyper::Body>;
+    type Error = hyper::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, mut req: hyper::Request<BoxBody>) -> Self::Future {
+        let uri = Uri::builder()
+            .scheme(self.uri.scheme().unwrap().clone())
+            .authority(self.uri.authority().unwrap().clone())
+            .path_and_query(req.uri().path_and_query().unwrap().clone())
+            .build()
+            .unwrap();
+
+        *req.uri_mut() = uri;
+
+        Box::pin(self.client.request(req))
+    }
+}
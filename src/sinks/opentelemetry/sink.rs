@@ -0,0 +1,197 @@
+use std::{fmt, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use prost::Message;
+use tower::Service;
+use vector_lib::opentelemetry::proto::{
+    collector::{
+        logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+        trace::v1::ExportTraceServiceRequest,
+    },
+    logs::v1::ResourceLogs,
+    metrics::v1::ResourceMetrics,
+    trace::v1::ResourceSpans,
+};
+use vector_lib::request_metadata::GroupedCountByteSize;
+use vector_lib::stream::{batcher::data::BatchReduce, BatcherSettings, DriverResponse};
+use vector_lib::{
+    config::telemetry, partition::Partitioner, ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::{
+    encoder::{OtlpEncoder, OtlpRecord},
+    service::{OpentelemetryRequest, OtlpPayload},
+};
+use crate::{
+    event::{Event, EventFinalizers, Finalizable},
+    sinks::util::{metadata::RequestMetadataBuilder, SinkBuilderExt, StreamSink},
+};
+
+/// The telemetry type of the events, each being exported by separate requests.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum TelemetryType {
+    Logs,
+    Metrics,
+    Traces,
+}
+
+struct TelemetryTypePartitioner;
+
+impl Partitioner for TelemetryTypePartitioner {
+    type Item = EventData;
+    type Key = TelemetryType;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match item.record {
+            OtlpRecord::Logs(_) => TelemetryType::Logs,
+            OtlpRecord::Metrics(_) => TelemetryType::Metrics,
+            OtlpRecord::Traces(_) => TelemetryType::Traces,
+        }
+    }
+}
+
+/// Data for a single event.
+struct EventData {
+    byte_size: usize,
+    json_byte_size: GroupedCountByteSize,
+    finalizers: EventFinalizers,
+    record: OtlpRecord,
+}
+
+impl EventData {
+    fn encoded_len(&self) -> usize {
+        match &self.record {
+            OtlpRecord::Logs(logs) => logs.encoded_len(),
+            OtlpRecord::Metrics(metrics) => metrics.encoded_len(),
+            OtlpRecord::Traces(spans) => spans.encoded_len(),
+        }
+    }
+}
+
+impl ByteSizeOf for EventData {
+    fn allocated_bytes(&self) -> usize {
+        self.byte_size
+    }
+}
+
+/// Temporary struct to collect events during batching.
+struct EventCollection {
+    finalizers: EventFinalizers,
+    logs: Vec<ResourceLogs>,
+    metrics: Vec<ResourceMetrics>,
+    spans: Vec<ResourceSpans>,
+    events_count: usize,
+    events_byte_size: usize,
+    events_json_byte_size: GroupedCountByteSize,
+}
+
+impl Default for EventCollection {
+    fn default() -> Self {
+        Self {
+            finalizers: Default::default(),
+            logs: Default::default(),
+            metrics: Default::default(),
+            spans: Default::default(),
+            events_count: 0,
+            events_byte_size: 0,
+            events_json_byte_size: telemetry().create_request_count_byte_size(),
+        }
+    }
+}
+
+pub struct OpentelemetrySink<S> {
+    pub batch_settings: BatcherSettings,
+    pub encoder: OtlpEncoder,
+    pub service: S,
+}
+
+impl<S> OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let encoder = self.encoder;
+        let batch_settings = self.batch_settings;
+
+        input
+            .filter_map(move |mut event| {
+                let mut json_byte_size = telemetry().create_request_count_byte_size();
+                json_byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+                let byte_size = event.size_of();
+                let finalizers = event.take_finalizers();
+
+                // Events which can't be encoded are reported by the encoder and dropped.
+                let data = encoder.encode(event).map(|record| EventData {
+                    byte_size,
+                    json_byte_size,
+                    finalizers,
+                    record,
+                });
+                futures::future::ready(data)
+            })
+            .batched_partitioned(TelemetryTypePartitioner, || {
+                batch_settings.as_reducer_config(
+                    EventData::encoded_len,
+                    BatchReduce::new(|collection: &mut EventCollection, item: EventData| {
+                        collection.finalizers.merge(item.finalizers);
+                        match item.record {
+                            OtlpRecord::Logs(logs) => collection.logs.push(logs),
+                            OtlpRecord::Metrics(metrics) => collection.metrics.push(metrics),
+                            OtlpRecord::Traces(spans) => collection.spans.push(spans),
+                        }
+                        collection.events_count += 1;
+                        collection.events_byte_size += item.byte_size;
+                        collection.events_json_byte_size += item.json_byte_size;
+                    }),
+                )
+            })
+            .map(|(telemetry_type, collection)| {
+                let builder = RequestMetadataBuilder::new(
+                    collection.events_count,
+                    collection.events_byte_size,
+                    collection.events_json_byte_size,
+                );
+
+                let payload = match telemetry_type {
+                    TelemetryType::Logs => OtlpPayload::Logs(ExportLogsServiceRequest {
+                        resource_logs: collection.logs,
+                    }),
+                    TelemetryType::Metrics => OtlpPayload::Metrics(ExportMetricsServiceRequest {
+                        resource_metrics: collection.metrics,
+                    }),
+                    TelemetryType::Traces => OtlpPayload::Traces(ExportTraceServiceRequest {
+                        resource_spans: collection.spans,
+                    }),
+                };
+
+                let bytes_len = NonZeroUsize::new(payload.encoded_len())
+                    .expect("payload should never be zero length");
+
+                OpentelemetryRequest {
+                    finalizers: collection.finalizers,
+                    metadata: builder.with_request_size(bytes_len),
+                    payload,
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,202 @@
+//! Configuration for the `pagerduty` sink.
+
+use http::Uri;
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    http::HttpClient,
+    sinks::{
+        prelude::*,
+        util::{
+            http::{http_response_retry_logic, HttpService},
+            service::TowerRequestConfigDefaults,
+        },
+    },
+};
+
+use super::{
+    request_builder::PagerDutyRequestBuilder, service::PagerDutyServiceRequestBuilder,
+    sink::PagerDutySink,
+};
+
+/// The path events are enqueued at.
+const ENQUEUE_PATH: &str = "/v2/enqueue";
+
+/// PagerDuty rate limits the events of each integration to around 120 per minute.
+#[derive(Clone, Copy, Debug)]
+pub struct PagerDutyTowerRequestConfigDefaults;
+
+impl TowerRequestConfigDefaults for PagerDutyTowerRequestConfigDefaults {
+    const RATE_LIMIT_DURATION_SECS: u64 = 60;
+    const RATE_LIMIT_NUM: u64 = 120;
+}
+
+fn default_endpoint() -> String {
+    "https://events.pagerduty.com".to_owned()
+}
+
+fn default_action() -> Template {
+    Template::try_from("trigger").unwrap()
+}
+
+fn default_summary() -> Template {
+    Template::try_from("{{ message }}").unwrap()
+}
+
+fn default_source() -> Template {
+    Template::try_from("{{ host }}").unwrap()
+}
+
+fn default_severity() -> Template {
+    Template::try_from("error").unwrap()
+}
+
+/// Configuration for the `pagerduty` sink.
+#[configurable_component(sink("pagerduty", "Trigger, acknowledge, and resolve PagerDuty alerts."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PagerDutySinkConfig {
+    /// The endpoint of the Events API.
+    #[serde(default = "default_endpoint")]
+    #[configurable(metadata(
+        docs::examples = "https://events.pagerduty.com",
+        docs::examples = "https://events.eu.pagerduty.com",
+    ))]
+    #[configurable(validation(format = "uri"))]
+    pub endpoint: String,
+
+    /// The integration key of the service the alerts are sent to.
+    #[configurable(metadata(docs::examples = "${PAGERDUTY_ROUTING_KEY}"))]
+    pub routing_key: SensitiveString,
+
+    /// The action taken for each event, one of `trigger`, `acknowledge`, or `resolve`.
+    ///
+    /// Events rendering any other action are dropped.
+    #[serde(default = "default_action")]
+    #[configurable(metadata(docs::examples = "{{ alert.action }}"))]
+    pub action: Template,
+
+    /// The deduplication key of the alert, which identifies the alert that events acknowledging or
+    /// resolving it refer to.
+    ///
+    /// Events acknowledging or resolving an alert are dropped when this isn't set, while
+    /// PagerDuty generates the key of triggered alerts when it isn't set.
+    #[configurable(metadata(docs::examples = "{{ host }}-{{ check }}"))]
+    pub dedup_key: Option<Template>,
+
+    /// A summary of the alert, which is truncated to 1024 characters.
+    #[serde(default = "default_summary")]
+    #[configurable(metadata(docs::examples = "{{ check }} failed on {{ host }}"))]
+    pub summary: Template,
+
+    /// The location of the affected system, such as its host name.
+    #[serde(default = "default_source")]
+    #[configurable(metadata(docs::examples = "{{ kubernetes.pod_name }}"))]
+    pub source: Template,
+
+    /// The severity of the alert, one of `critical`, `error`, `warning`, or `info`.
+    ///
+    /// Events rendering any other severity are dropped.
+    #[serde(default = "default_severity")]
+    #[configurable(metadata(docs::examples = "{{ level }}"))]
+    pub severity: Template,
+
+    /// The component of the source responsible for the alert.
+    #[configurable(metadata(docs::examples = "{{ service }}"))]
+    pub component: Option<Template>,
+
+    /// The logical grouping of the components of the source.
+    #[configurable(metadata(docs::examples = "{{ cluster }}"))]
+    pub group: Option<Template>,
+
+    /// The class or type of the alert.
+    #[configurable(metadata(docs::examples = "{{ check }}"))]
+    pub class: Option<Template>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig<PagerDutyTowerRequestConfigDefaults>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for PagerDutySinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            routing_key = "${PAGERDUTY_ROUTING_KEY}"
+            dedup_key = "{{ host }}-{{ check }}"
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "pagerduty")]
+impl SinkConfig for PagerDutySinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let uri = self.build_uri()?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let service = HttpService::new(client, PagerDutyServiceRequestBuilder { uri });
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), http_response_retry_logic())
+            .service(service);
+
+        let sink = PagerDutySink {
+            service,
+            request_builder: self.build_request_builder(),
+        };
+
+        // The Events API has no endpoint to check the routing key with, short of sending an alert.
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl PagerDutySinkConfig {
+    pub(super) fn build_request_builder(&self) -> PagerDutyRequestBuilder {
+        PagerDutyRequestBuilder {
+            routing_key: self.routing_key.clone(),
+            action: self.action.clone(),
+            dedup_key: self.dedup_key.clone(),
+            summary: self.summary.clone(),
+            source: self.source.clone(),
+            severity: self.severity.clone(),
+            component: self.component.clone(),
+            group: self.group.clone(),
+            class: self.class.clone(),
+            transformer: self.encoding.clone(),
+        }
+    }
+
+    fn build_uri(&self) -> crate::Result<Uri> {
+        let uri = format!("{}{ENQUEUE_PATH}", self.endpoint.trim_end_matches('/'));
+        uri.parse::<Uri>().map_err(Into::into)
+    }
+}
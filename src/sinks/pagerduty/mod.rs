@@ -0,0 +1,16 @@
+//! `pagerduty` sink.
+//!
+//! Sends events to the [PagerDuty Events API v2][events_api], which triggers, acknowledges, and
+//! resolves alerts. Each event is mapped to an alert event through templates rendering its
+//! action, deduplication key, and payload fields.
+//!
+//! [events_api]: https://developer.pagerduty.com/docs/events-api-v2/overview/
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::config::PagerDutySinkConfig;
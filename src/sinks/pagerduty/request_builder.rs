@@ -0,0 +1,203 @@
+//! Mapping of the events to the events of the PagerDuty Events API.
+
+use std::str::FromStr;
+
+use bytes::{Bytes, BytesMut};
+use chrono::SecondsFormat;
+use serde::Serialize;
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    internal_events::PagerDutyInvalidEventError,
+    sinks::{prelude::*, util::http::HttpRequest},
+};
+
+/// The maximum length of the summaries of the alerts, in characters.
+const MAX_SUMMARY_LENGTH: usize = 1024;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum EventAction {
+    Trigger,
+    Acknowledge,
+    Resolve,
+}
+
+impl FromStr for EventAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "trigger" => Ok(Self::Trigger),
+            "acknowledge" => Ok(Self::Acknowledge),
+            "resolve" => Ok(Self::Resolve),
+            _ => Err(format!("Invalid action {s:?}.")),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Severity {
+    Critical,
+    Error,
+    Warning,
+    Info,
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "critical" => Ok(Self::Critical),
+            "error" => Ok(Self::Error),
+            "warning" => Ok(Self::Warning),
+            "info" => Ok(Self::Info),
+            _ => Err(format!("Invalid severity {s:?}.")),
+        }
+    }
+}
+
+/// An event of the Events API.
+#[derive(Debug, Serialize)]
+pub(super) struct PagerDutyEvent<'a> {
+    pub(super) routing_key: &'a str,
+    pub(super) event_action: EventAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) dedup_key: Option<String>,
+    /// Only sent when triggering alerts, as it's ignored otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) payload: Option<Payload>,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct Payload {
+    pub(super) summary: String,
+    pub(super) source: String,
+    pub(super) severity: Severity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) component: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) class: Option<String>,
+    pub(super) custom_details: Value,
+}
+
+pub(super) struct PagerDutyRequestBuilder {
+    pub(super) routing_key: SensitiveString,
+    pub(super) action: Template,
+    pub(super) dedup_key: Option<Template>,
+    pub(super) summary: Template,
+    pub(super) source: Template,
+    pub(super) severity: Template,
+    pub(super) component: Option<Template>,
+    pub(super) group: Option<Template>,
+    pub(super) class: Option<Template>,
+    pub(super) transformer: Transformer,
+}
+
+impl PagerDutyRequestBuilder {
+    /// Builds the request sending an event.
+    /// Returns `None` if the event can't be mapped to an event of the Events API, in which case an
+    /// error event is also emitted.
+    pub(super) fn build(&self, mut event: Event) -> Option<HttpRequest<()>> {
+        let finalizers = event.take_finalizers();
+        let payload = self.encode(&mut event).ok()?;
+
+        let builder = RequestMetadataBuilder::from_event(&event);
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+        let metadata = builder.build(&EncodeResult::uncompressed(&payload, byte_size));
+
+        Some(HttpRequest::new(payload, finalizers, metadata, ()))
+    }
+
+    fn encode(&self, event: &mut Event) -> Result<Bytes, ()> {
+        let alert = self.map_event(event)?;
+        crate::serde::json::to_bytes(&alert)
+            .map(BytesMut::freeze)
+            .map_err(|error| invalid_event(error.to_string()))
+    }
+
+    /// Maps an event to an event of the Events API, applying the transformations to the event sent
+    /// as the custom details of triggered alerts.
+    pub(super) fn map_event<'a>(&'a self, event: &mut Event) -> Result<PagerDutyEvent<'a>, ()> {
+        let log = event.as_log();
+
+        let event_action = render(&self.action, "action", log)?
+            .parse::<EventAction>()
+            .map_err(invalid_event)?;
+        let dedup_key = render_optional(self.dedup_key.as_ref(), "dedup_key", log)?;
+        if event_action != EventAction::Trigger && dedup_key.is_none() {
+            invalid_event(
+                "A deduplication key is required to acknowledge or resolve an alert.".to_owned(),
+            );
+            return Err(());
+        }
+
+        let payload = match event_action {
+            EventAction::Trigger => {
+                let mut summary = render(&self.summary, "summary", log)?;
+                if let Some((index, _)) = summary.char_indices().nth(MAX_SUMMARY_LENGTH) {
+                    summary.truncate(index);
+                }
+
+                let mut payload = Payload {
+                    summary,
+                    source: render(&self.source, "source", log)?,
+                    severity: render(&self.severity, "severity", log)?
+                        .parse()
+                        .map_err(invalid_event)?,
+                    timestamp: log
+                        .get_timestamp()
+                        .and_then(Value::as_timestamp)
+                        .map(|timestamp| timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)),
+                    component: render_optional(self.component.as_ref(), "component", log)?,
+                    group: render_optional(self.group.as_ref(), "group", log)?,
+                    class: render_optional(self.class.as_ref(), "class", log)?,
+                    custom_details: Value::Null,
+                };
+
+                self.transformer.transform(event);
+                payload.custom_details = event.as_log().value().clone();
+                Some(payload)
+            }
+            EventAction::Acknowledge | EventAction::Resolve => None,
+        };
+
+        Ok(PagerDutyEvent {
+            routing_key: self.routing_key.inner(),
+            event_action,
+            dedup_key,
+            payload,
+        })
+    }
+}
+
+fn render(template: &Template, field: &'static str, log: &LogEvent) -> Result<String, ()> {
+    template.render_string(log).map_err(|error| {
+        emit!(TemplateRenderingError {
+            error,
+            field: Some(field),
+            drop_event: true,
+        });
+    })
+}
+
+fn render_optional(
+    template: Option<&Template>,
+    field: &'static str,
+    log: &LogEvent,
+) -> Result<Option<String>, ()> {
+    template
+        .map(|template| render(template, field, log))
+        .transpose()
+}
+
+fn invalid_event(error: String) {
+    emit!(PagerDutyInvalidEventError { error });
+}
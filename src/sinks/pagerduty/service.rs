@@ -0,0 +1,26 @@
+//! Service implementation for the `pagerduty` sink.
+
+use bytes::Bytes;
+use http::{header::CONTENT_TYPE, Request, Uri};
+use snafu::ResultExt;
+
+use crate::sinks::{
+    util::http::{HttpRequest, HttpServiceRequestBuilder},
+    HTTPRequestBuilderSnafu,
+};
+
+#[derive(Debug, Clone)]
+pub(super) struct PagerDutyServiceRequestBuilder {
+    pub(super) uri: Uri,
+}
+
+impl HttpServiceRequestBuilder<()> for PagerDutyServiceRequestBuilder {
+    fn build(&self, mut request: HttpRequest<()>) -> Result<Request<Bytes>, crate::Error> {
+        // The routing key is sent in the body, so no credentials are needed in the headers.
+        Request::post(&self.uri)
+            .header(CONTENT_TYPE, "application/json")
+            .body(request.take_payload())
+            .context(HTTPRequestBuilderSnafu)
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,47 @@
+//! Implementation of the `pagerduty` sink.
+
+use std::future;
+
+use crate::sinks::{prelude::*, util::http::HttpRequest};
+
+use super::request_builder::PagerDutyRequestBuilder;
+
+pub(super) struct PagerDutySink<S> {
+    pub(super) service: S,
+    pub(super) request_builder: PagerDutyRequestBuilder,
+}
+
+impl<S> PagerDutySink<S>
+where
+    S: Service<HttpRequest<()>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let Self {
+            service,
+            request_builder,
+        } = *self;
+
+        input
+            // The Events API takes a single event per request.
+            .filter_map(|event| future::ready(request_builder.build(event)))
+            .into_driver(service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for PagerDutySink<S>
+where
+    S: Service<HttpRequest<()>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
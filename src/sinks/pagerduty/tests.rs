@@ -0,0 +1,157 @@
+//! Unit tests for the `pagerduty` sink.
+
+use chrono::{TimeZone, Utc};
+use futures::stream;
+use hyper::{Body, Response};
+use serde_json::json;
+
+use crate::{
+    sinks::{prelude::*, util::test::build_test_server_generic},
+    test_util::{
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        next_addr,
+    },
+};
+
+use super::config::PagerDutySinkConfig;
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<PagerDutySinkConfig>();
+}
+
+fn test_config(extra: &str) -> PagerDutySinkConfig {
+    toml::from_str(&format!(
+        r#"
+        routing_key = "routing-key"
+        dedup_key = "{{{{ host }}}}-{{{{ check }}}}"
+        {extra}
+        "#
+    ))
+    .unwrap()
+}
+
+fn alert_event(action: &str) -> Event {
+    let mut log = LogEvent::default();
+    log.insert("message", "disk full");
+    log.insert("host", "web-1");
+    log.insert("check", "disk");
+    log.insert("level", "critical");
+    log.insert("action", action);
+    log.insert(
+        "timestamp",
+        Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+    );
+    Event::Log(log)
+}
+
+fn map_event(config: &PagerDutySinkConfig, mut event: Event) -> Option<serde_json::Value> {
+    let request_builder = config.build_request_builder();
+    let alert = request_builder.map_event(&mut event).ok()?;
+    Some(serde_json::to_value(alert).unwrap())
+}
+
+#[test]
+fn pagerduty_trigger() {
+    let config = test_config(
+        r#"
+        action = "{{ action }}"
+        severity = "{{ level }}"
+        class = "{{ check }}"
+        encoding.except_fields = ["action"]
+        "#,
+    );
+
+    assert_eq!(
+        map_event(&config, alert_event("trigger")).unwrap(),
+        json!({
+            "routing_key": "routing-key",
+            "event_action": "trigger",
+            "dedup_key": "web-1-disk",
+            "payload": {
+                "summary": "disk full",
+                "source": "web-1",
+                "severity": "critical",
+                "timestamp": "2024-01-02T03:04:05.000Z",
+                "class": "disk",
+                "custom_details": {
+                    "message": "disk full",
+                    "host": "web-1",
+                    "check": "disk",
+                    "level": "critical",
+                    "timestamp": "2024-01-02T03:04:05Z",
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn pagerduty_resolve() {
+    let config = test_config(r#"action = "{{ action }}""#);
+
+    assert_eq!(
+        map_event(&config, alert_event("resolve")).unwrap(),
+        json!({
+            "routing_key": "routing-key",
+            "event_action": "resolve",
+            "dedup_key": "web-1-disk",
+        })
+    );
+}
+
+#[test]
+fn pagerduty_truncates_summary() {
+    let config = test_config("");
+    let mut event = alert_event("trigger");
+    event.as_mut_log().insert("message", "é".repeat(2000));
+
+    let alert = map_event(&config, event).unwrap();
+    let summary = alert["payload"]["summary"].as_str().unwrap();
+    assert_eq!(summary.chars().count(), 1024);
+}
+
+#[test]
+fn pagerduty_rejects_invalid_events() {
+    let config = test_config(r#"action = "{{ action }}""#);
+    assert!(map_event(&config, alert_event("escalate")).is_none());
+
+    let config = test_config(r#"severity = "{{ message }}""#);
+    assert!(map_event(&config, alert_event("trigger")).is_none());
+
+    let mut config = test_config(r#"action = "{{ action }}""#);
+    config.dedup_key = None;
+    assert!(map_event(&config, alert_event("acknowledge")).is_none());
+    assert!(map_event(&config, alert_event("trigger")).is_some());
+}
+
+#[tokio::test]
+async fn pagerduty_sends_events() {
+    let addr = next_addr();
+    let mut config = test_config("");
+    config.endpoint = format!("http://{addr}");
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+    let (mut rx, _trigger, server) = build_test_server_generic(addr, || {
+        Response::builder()
+            .status(202)
+            .body(Body::from(r#"{"status":"success"}"#))
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    run_and_assert_sink_compliance(
+        sink,
+        stream::once(future::ready(alert_event("trigger"))),
+        &HTTP_SINK_TAGS,
+    )
+    .await;
+
+    let (parts, body) = rx.next().await.unwrap();
+    assert_eq!(parts.uri.path(), "/v2/enqueue");
+    assert_eq!(parts.headers["content-type"], "application/json");
+    let body = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+    assert_eq!(body["event_action"], "trigger");
+    assert_eq!(body["dedup_key"], "web-1-disk");
+    assert_eq!(body["payload"]["severity"], "error");
+}
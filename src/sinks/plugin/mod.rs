@@ -0,0 +1,164 @@
+//! The `plugin` sink. See [PluginSinkConfig].
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use http::Uri;
+use vector_lib::configurable::configurable_component;
+use vector_lib::{event::EventArray, sink::StreamSink};
+
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    plugin::{PluginProcess, PluginProcessConfig},
+    sinks::{
+        util::{BatchConfig, RealtimeEventBasedDefaultBatchSettings, TowerRequestConfig},
+        vector::VectorConfig,
+        Healthcheck, VectorSink,
+    },
+};
+
+/// Configuration for the `plugin` sink.
+#[configurable_component(sink("plugin", "Send observability data to an external plugin process."))]
+#[derive(Clone, Debug)]
+pub struct PluginSinkConfig {
+    #[serde(flatten)]
+    #[configurable(derived)]
+    process: PluginProcessConfig,
+
+    /// The address the plugin listens for events on.
+    ///
+    /// The plugin is given this address in the `VECTOR_PLUGIN_ADDRESS` environment variable. It
+    /// _must_ include a port.
+    #[configurable(validation(format = "uri"))]
+    #[configurable(metadata(docs::examples = "127.0.0.1:6101"))]
+    address: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for PluginSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            process: PluginProcessConfig::new(vec![
+                "/usr/local/bin/vector-plugin-example".to_owned()
+            ]),
+            address: "127.0.0.1:6101".to_owned(),
+            batch: BatchConfig::default(),
+            request: TowerRequestConfig::default(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "plugin")]
+impl SinkConfig for PluginSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.process.validate()?;
+
+        let mut config = VectorConfig::from_address(self.address.parse::<Uri>()?);
+        config.batch = self.batch;
+        config.request = self.request;
+        let (sink, healthcheck) = config.build(cx).await?;
+
+        // The plugin is started right away, so that it can answer the healthcheck.
+        let process = self.process.spawn(self.address.clone());
+        let sink = PluginSink { sink, process };
+
+        Ok((VectorSink::Stream(Box::new(sink)), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Sends events to the plugin with a `vector` sink, stopping the plugin once done.
+struct PluginSink {
+    sink: VectorSink,
+    process: PluginProcess,
+}
+
+#[async_trait]
+impl StreamSink<EventArray> for PluginSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, EventArray>) -> Result<(), ()> {
+        let Self { sink, process } = *self;
+        let result = sink.run(input).await;
+        drop(process);
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<super::PluginSinkConfig>();
+    }
+}
+
+#[cfg(all(test, unix, feature = "sources-vector"))]
+mod tests {
+    use vector_lib::assert_event_data_eq;
+    use vector_lib::config::log_schema;
+
+    use super::*;
+    use crate::{
+        config::{SourceConfig as _, SourceContext},
+        sources::vector::VectorConfig as VectorSourceConfig,
+        test_util, SourceSender,
+    };
+
+    #[tokio::test]
+    async fn sends_events_to_plugin() {
+        // The plugin itself only blocks, the events being received by a `vector` source standing
+        // in for the plugin.
+        let addr = test_util::next_addr();
+        let source = VectorSourceConfig::from_address(addr);
+        let (tx, rx) = SourceSender::new_test();
+        let server = source
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(addr).await;
+
+        let config: PluginSinkConfig = toml::from_str(&format!(
+            r#"
+            command = ["sleep", "60"]
+            address = "{addr}"
+            "#
+        ))
+        .unwrap();
+        let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+        let (mut events, stream) = test_util::random_events_with_stream(100, 10, None);
+        sink.run(stream).await.unwrap();
+
+        for event in &mut events {
+            event.as_mut_log().insert(
+                log_schema().source_type_key_target_path().unwrap(),
+                "vector",
+            );
+        }
+
+        let output = test_util::collect_ready(rx).await;
+        assert_event_data_eq!(events, output);
+    }
+}
@@ -0,0 +1,55 @@
+//! Encoding for the `postgres` sink.
+
+use std::{collections::BTreeMap, io};
+
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
+use vrl::path::PathPrefix;
+
+use crate::sinks::{prelude::*, util::encoding::Encoder as SinkEncoder};
+
+/// Encodes a batch of events as a JSON array with one object per row.
+///
+/// The array is bound as the single parameter of the insert statement, where
+/// `json_populate_recordset` expands it into rows of the target table.
+#[derive(Clone)]
+pub(super) struct PostgresEncoder {
+    pub(super) transformer: Transformer,
+    pub(super) columns: Option<BTreeMap<String, ConfigValuePath>>,
+}
+
+impl SinkEncoder<Vec<Event>> for PostgresEncoder {
+    fn encode_input(
+        &self,
+        events: Vec<Event>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        let n_events = events.len();
+
+        let rows = events
+            .into_iter()
+            .map(|mut event| {
+                self.transformer.transform(&mut event);
+
+                byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+
+                let log = event.into_log();
+                match &self.columns {
+                    // Without a mapping, the top-level fields of the event are matched to the
+                    // columns of the same name.
+                    None => serde_json::to_value(log.value()),
+                    Some(columns) => serde_json::to_value(
+                        columns
+                            .iter()
+                            .map(|(column, path)| (column, log.get((PathPrefix::Event, &path.0))))
+                            .collect::<BTreeMap<_, _>>(),
+                    ),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let body = serde_json::to_vec(&rows)?;
+
+        write_all(writer, n_events, &body).map(|()| (body.len(), byte_size))
+    }
+}
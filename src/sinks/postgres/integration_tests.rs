@@ -0,0 +1,160 @@
+use futures::stream;
+use tokio_postgres::{Client, NoTls};
+use vector_lib::event::{BatchNotifier, BatchStatus, Event, LogEvent};
+
+use crate::{
+    config::{SinkConfig, SinkContext},
+    sinks::util::test::load_sink,
+    test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        random_string, trace_init,
+    },
+};
+
+use super::config::PostgresConfig;
+
+fn pg_host() -> String {
+    std::env::var("PG_HOST").unwrap_or_else(|_| "localhost".into())
+}
+
+fn pg_url() -> String {
+    std::env::var("PG_URL")
+        .unwrap_or_else(|_| format!("postgres://vector:vector@{}/postgres", pg_host()))
+}
+
+fn gen_table() -> String {
+    format!("test_{}", random_string(10).to_lowercase())
+}
+
+async fn connect() -> Client {
+    let (client, connection) = tokio_postgres::connect(&pg_url(), NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client
+}
+
+async fn insert_events(config: &str, events: Vec<Event>) {
+    let (config, _) = load_sink::<PostgresConfig>(config).unwrap();
+    let (sink, healthcheck) = config.build(SinkContext::default()).await.unwrap();
+    healthcheck.await.unwrap();
+
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+}
+
+fn make_events(messages: &[(&str, &str)]) -> (Vec<Event>, vector_lib::event::BatchStatusReceiver) {
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let events = messages
+        .iter()
+        .map(|(id, message)| {
+            let mut log = LogEvent::from(*message).with_batch_notifier(&batch);
+            log.insert("id", *id);
+            log.insert("host", "example.com");
+            log.into()
+        })
+        .collect();
+    (events, receiver)
+}
+
+#[tokio::test]
+async fn inserts_top_level_fields() {
+    trace_init();
+
+    let client = connect().await;
+    let table = gen_table();
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE {table} (id TEXT, host TEXT, message TEXT, timestamp TIMESTAMPTZ)"
+        ))
+        .await
+        .unwrap();
+
+    let (events, mut receiver) = make_events(&[("1", "first"), ("2", "second")]);
+    insert_events(
+        &format!(
+            r#"
+                endpoint = "{}"
+                table = "{table}"
+            "#,
+            pg_url()
+        ),
+        events,
+    )
+    .await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let rows = client
+        .query(
+            &format!(
+                "SELECT id, host, message FROM {table} WHERE timestamp IS NOT NULL ORDER BY id"
+            ),
+            &[],
+        )
+        .await
+        .unwrap();
+    let rows = rows
+        .iter()
+        .map(|row| (row.get(0), row.get(1), row.get(2)))
+        .collect::<Vec<(String, String, String)>>();
+    assert_eq!(
+        rows,
+        vec![
+            ("1".into(), "example.com".into(), "first".into()),
+            ("2".into(), "example.com".into(), "second".into()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn ignores_conflicting_rows() {
+    trace_init();
+
+    let client = connect().await;
+    let table = gen_table();
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE {table} (event_id TEXT PRIMARY KEY, body TEXT, inserted_at TIMESTAMPTZ DEFAULT now())"
+        ))
+        .await
+        .unwrap();
+    client
+        .batch_execute(&format!(
+            "INSERT INTO {table} (event_id, body) VALUES ('1', 'already written')"
+        ))
+        .await
+        .unwrap();
+
+    let (events, mut receiver) = make_events(&[("1", "first"), ("2", "second")]);
+    insert_events(
+        &format!(
+            r#"
+                endpoint = "{}"
+                table = "{table}"
+                on_conflict = "ignore"
+                columns.event_id = "id"
+                columns.body = "message"
+            "#,
+            pg_url()
+        ),
+        events,
+    )
+    .await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let rows = client
+        .query(
+            &format!("SELECT event_id, body FROM {table} WHERE inserted_at IS NOT NULL ORDER BY event_id"),
+            &[],
+        )
+        .await
+        .unwrap();
+    let rows = rows
+        .iter()
+        .map(|row| (row.get(0), row.get(1)))
+        .collect::<Vec<(String, String)>>();
+    assert_eq!(
+        rows,
+        vec![
+            ("1".into(), "already written".into()),
+            ("2".into(), "second".into()),
+        ]
+    );
+}
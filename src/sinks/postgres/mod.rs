@@ -0,0 +1,32 @@
+//! The PostgreSQL [`vector_lib::sink::VectorSink`].
+//!
+//! This module contains the [`vector_lib::sink::VectorSink`] instance that is responsible for
+//! taking a stream of [`vector_lib::event::Event`]s and inserting them, one batch per statement,
+//! into a PostgreSQL table.
+
+mod config;
+mod encoder;
+#[cfg(all(test, feature = "postgres-integration-tests"))]
+mod integration_tests;
+mod pool;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+use deadpool::managed::PoolError;
+use snafu::Snafu;
+use tokio_postgres::Error as PgError;
+
+pub use self::config::PostgresConfig;
+
+#[derive(Debug, Snafu)]
+pub(super) enum PostgresSinkError {
+    #[snafu(display("Failed to get a connection from the pool: {}", source))]
+    Pool { source: PoolError<PgError> },
+
+    #[snafu(display("Failed to insert rows: {}", source))]
+    Insert { source: PgError },
+}
@@ -0,0 +1,51 @@
+//! Connection pooling for the `postgres` sink.
+
+use deadpool::managed::{self, Metrics, RecycleError, RecycleResult};
+use postgres_openssl::MakeTlsConnector;
+use tokio_postgres::{Client, Config, Error as PgError, NoTls};
+
+use crate::sinks::prelude::*;
+
+pub(super) type PostgresPool = managed::Pool<PostgresConnectionManager>;
+
+/// Opens new connections for the pool, and discards connections that were closed by the server.
+pub(super) struct PostgresConnectionManager {
+    config: Config,
+    tls: Option<MakeTlsConnector>,
+}
+
+impl PostgresConnectionManager {
+    pub(super) const fn new(config: Config, tls: Option<MakeTlsConnector>) -> Self {
+        Self { config, tls }
+    }
+}
+
+#[async_trait]
+impl managed::Manager for PostgresConnectionManager {
+    type Type = Client;
+    type Error = PgError;
+
+    async fn create(&self) -> Result<Client, PgError> {
+        let client = match &self.tls {
+            Some(tls) => {
+                let (client, connection) = self.config.connect(tls.clone()).await?;
+                tokio::spawn(connection);
+                client
+            }
+            None => {
+                let (client, connection) = self.config.connect(NoTls).await?;
+                tokio::spawn(connection);
+                client
+            }
+        };
+        Ok(client)
+    }
+
+    async fn recycle(&self, client: &mut Client, _: &Metrics) -> RecycleResult<PgError> {
+        if client.is_closed() {
+            Err(RecycleError::StaticMessage("connection closed"))
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! `RequestBuilder` implementation for the `postgres` sink.
+
+use bytes::Bytes;
+use std::io;
+
+use crate::sinks::prelude::*;
+
+use super::{encoder::PostgresEncoder, service::PostgresRequest};
+
+pub(super) struct PostgresRequestBuilder {
+    pub(super) encoder: PostgresEncoder,
+}
+
+impl RequestBuilder<Vec<Event>> for PostgresRequestBuilder {
+    type Metadata = EventFinalizers;
+    type Events = Vec<Event>;
+    type Encoder = PostgresEncoder;
+    type Payload = Bytes;
+    type Request = PostgresRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        mut events: Vec<Event>,
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        (finalizers, builder, events)
+    }
+
+    fn build_request(
+        &self,
+        finalizers: Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        PostgresRequest {
+            rows: payload.into_payload(),
+            finalizers,
+            metadata,
+        }
+    }
+}
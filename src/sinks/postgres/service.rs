@@ -0,0 +1,131 @@
+//! Service implementation for the `postgres` sink.
+
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use deadpool::managed::PoolError;
+use snafu::ResultExt;
+use tokio_postgres::Error as PgError;
+
+use crate::sinks::prelude::*;
+
+use super::{pool::PostgresPool, InsertSnafu, PoolSnafu, PostgresSinkError};
+
+/// SQLSTATE classes of errors that don't depend on the rows being inserted: connection
+/// exceptions, transaction rollbacks (serialization failures and deadlocks), insufficient
+/// resources, and operator intervention (such as a server shutting down).
+const TRANSIENT_SQLSTATE_CLASSES: [&str; 4] = ["08", "40", "53", "57"];
+
+fn is_transient(error: &PgError) -> bool {
+    // Errors without a SQLSTATE didn't come from the server, and are I/O or connection failures.
+    error.code().map_or(true, |state| {
+        TRANSIENT_SQLSTATE_CLASSES
+            .iter()
+            .any(|class| state.code().starts_with(class))
+    })
+}
+
+#[derive(Clone)]
+pub(super) struct PostgresRetryLogic;
+
+impl RetryLogic for PostgresRetryLogic {
+    type Error = PostgresSinkError;
+    type Response = PostgresResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            PostgresSinkError::Pool { source } => match source {
+                PoolError::Timeout(_) => true,
+                PoolError::Backend(error) => is_transient(error),
+                _ => false,
+            },
+            PostgresSinkError::Insert { source } => is_transient(source),
+        }
+    }
+}
+
+pub(super) struct PostgresRequest {
+    pub(super) rows: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for PostgresRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for PostgresRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct PostgresResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for PostgresResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        self.metadata.events_estimated_json_encoded_byte_size()
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct PostgresService {
+    pool: PostgresPool,
+    statement: Arc<str>,
+}
+
+impl PostgresService {
+    pub(super) const fn new(pool: PostgresPool, statement: Arc<str>) -> Self {
+        Self { pool, statement }
+    }
+}
+
+impl Service<PostgresRequest> for PostgresService {
+    type Response = PostgresResponse;
+    type Error = PostgresSinkError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Readiness is bounded by the pool, which is waited on when the request is sent.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: PostgresRequest) -> Self::Future {
+        let pool = self.pool.clone();
+        let statement = Arc::clone(&self.statement);
+
+        Box::pin(async move {
+            let client = pool.get().await.context(PoolSnafu)?;
+            // The encoder only produces JSON, which is always valid UTF-8.
+            let rows = String::from_utf8_lossy(&request.rows).into_owned();
+            client
+                .execute(statement.as_ref(), &[&rows])
+                .await
+                .context(InsertSnafu)?;
+
+            Ok(PostgresResponse {
+                metadata: request.metadata,
+            })
+        })
+    }
+}
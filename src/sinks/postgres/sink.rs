@@ -0,0 +1,58 @@
+//! Implementation of the `postgres` sink.
+
+use crate::sinks::prelude::*;
+
+use super::{
+    request_builder::PostgresRequestBuilder,
+    service::{PostgresRetryLogic, PostgresService},
+};
+
+pub(super) struct PostgresSink {
+    service: Svc<PostgresService, PostgresRetryLogic>,
+    batch_settings: BatcherSettings,
+    request_builder: PostgresRequestBuilder,
+}
+
+impl PostgresSink {
+    /// Creates a new `PostgresSink`.
+    pub(super) const fn new(
+        service: Svc<PostgresService, PostgresRetryLogic>,
+        batch_settings: BatcherSettings,
+        request_builder: PostgresRequestBuilder,
+    ) -> Self {
+        Self {
+            service,
+            batch_settings,
+            request_builder,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        input
+            .batched(self.batch_settings.as_byte_size_config())
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .protocol("postgres")
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for PostgresSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,108 @@
+//! Unit tests for the `postgres` sink.
+
+use std::collections::BTreeMap;
+
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
+
+use crate::sinks::{prelude::*, util::encoding::Encoder as SinkEncoder};
+
+use super::{
+    config::{insert_statement, OnConflict, PostgresConfig},
+    encoder::PostgresEncoder,
+};
+
+fn columns() -> BTreeMap<String, ConfigValuePath> {
+    BTreeMap::from([
+        (
+            "message".to_string(),
+            ConfigValuePath::try_from("message".to_string()).unwrap(),
+        ),
+        (
+            "user".to_string(),
+            ConfigValuePath::try_from("user.name".to_string()).unwrap(),
+        ),
+    ])
+}
+
+fn encode(encoder: &PostgresEncoder, events: Vec<Event>) -> serde_json::Value {
+    let mut body = Vec::new();
+    encoder.encode_input(events, &mut body).unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<PostgresConfig>();
+}
+
+#[test]
+fn insert_statement_without_columns() {
+    assert_eq!(
+        insert_statement("logs", None, OnConflict::Error).as_ref(),
+        "INSERT INTO logs SELECT * FROM json_populate_recordset(NULL::logs, $1::text::json)"
+    );
+}
+
+#[test]
+fn insert_statement_with_columns() {
+    assert_eq!(
+        insert_statement("audit.events", Some(&columns()), OnConflict::Error).as_ref(),
+        r#"INSERT INTO audit.events ("message", "user") SELECT "message", "user" FROM json_populate_recordset(NULL::audit.events, $1::text::json)"#
+    );
+}
+
+#[test]
+fn insert_statement_ignoring_conflicts() {
+    assert_eq!(
+        insert_statement("logs", None, OnConflict::Ignore).as_ref(),
+        "INSERT INTO logs SELECT * FROM json_populate_recordset(NULL::logs, $1::text::json) ON CONFLICT DO NOTHING"
+    );
+}
+
+#[test]
+fn insert_statement_quotes_columns() {
+    let columns = BTreeMap::from([(
+        r#"Odd "name""#.to_string(),
+        ConfigValuePath::try_from("message".to_string()).unwrap(),
+    )]);
+    assert_eq!(
+        insert_statement("logs", Some(&columns), OnConflict::Error).as_ref(),
+        r#"INSERT INTO logs ("Odd ""name""") SELECT "Odd ""name""" FROM json_populate_recordset(NULL::logs, $1::text::json)"#
+    );
+}
+
+#[test]
+fn encodes_top_level_fields() {
+    let encoder = PostgresEncoder {
+        transformer: Default::default(),
+        columns: None,
+    };
+    let mut log = LogEvent::default();
+    log.insert("message", "hello");
+    log.insert("host", "example.com");
+
+    assert_eq!(
+        encode(&encoder, vec![log.into()]),
+        serde_json::json!([{"host": "example.com", "message": "hello"}])
+    );
+}
+
+#[test]
+fn encodes_mapped_columns() {
+    let encoder = PostgresEncoder {
+        transformer: Default::default(),
+        columns: Some(columns()),
+    };
+    let mut first = LogEvent::from("first");
+    first.insert("user.name", "alice");
+    first.insert("host", "example.com");
+    let second = LogEvent::from("second");
+
+    assert_eq!(
+        encode(&encoder, vec![first.into(), second.into()]),
+        serde_json::json!([
+            {"message": "first", "user": "alice"},
+            {"message": "second", "user": null},
+        ])
+    );
+}
@@ -2,7 +2,7 @@ use std::{collections::BTreeMap, fmt::Write as _};
 
 use chrono::Utc;
 use indexmap::map::IndexMap;
-use vector_lib::event::metric::{samples_to_buckets, MetricSketch, MetricTags, Quantile};
+use vector_lib::event::metric::{samples_to_buckets, Exemplar, MetricSketch, MetricTags, Quantile};
 use vector_lib::prometheus::parser::{proto, METRIC_NAME_LABEL};
 
 use crate::{
@@ -27,6 +27,19 @@ pub(super) trait MetricCollector {
         extra: Option<(&str, String)>,
     );
 
+    /// Emits an exemplar of the series of the given name, suffix, and tags.
+    ///
+    /// Exemplars are dropped by default, for outputs that don't support them.
+    fn emit_exemplar(
+        &mut self,
+        _name: &str,
+        _suffix: &str,
+        _tags: Option<&MetricTags>,
+        _extra: Option<(&str, String)>,
+        _exemplar: &Exemplar,
+    ) {
+    }
+
     fn finish(self) -> Self::Output;
 
     fn encode_metric(
@@ -160,6 +173,16 @@ pub(super) trait MetricCollector {
                     );
                     self.emit_value(timestamp, name, "_sum", *sum, tags, None);
                     self.emit_value(timestamp, name, "_count", *count as f64, tags, None);
+
+                    for exemplar in metric.exemplars() {
+                        // Exemplars belong to the series of the first bucket counting their value.
+                        let le = buckets
+                            .iter()
+                            .map(|bucket| bucket.upper_limit)
+                            .find(|limit| !limit.is_infinite() && exemplar.value <= *limit)
+                            .map_or_else(|| "+Inf".to_string(), |limit| limit.to_string());
+                        self.emit_exemplar(name, "_bucket", tags, Some(("le", le)), exemplar);
+                    }
                 }
                 MetricValue::AggregatedSummary {
                     quantiles,
@@ -178,6 +201,10 @@ pub(super) trait MetricCollector {
                     }
                     self.emit_value(timestamp, name, "_sum", *sum, tags, None);
                     self.emit_value(timestamp, name, "_count", *count as f64, tags, None);
+
+                    for exemplar in metric.exemplars() {
+                        self.emit_exemplar(name, "_count", tags, None, exemplar);
+                    }
                 }
                 MetricValue::Sketch { sketch } => match sketch {
                     MetricSketch::AgentDDSketch(ddsketch) => {
@@ -318,6 +345,7 @@ type Labels = Vec<proto::Label>;
 
 pub(super) struct TimeSeries {
     buffer: IndexMap<Labels, Vec<proto::Sample>>,
+    exemplars: IndexMap<Labels, Vec<proto::Exemplar>>,
     metadata: IndexMap<String, proto::MetricMetadata>,
     timestamp: Option<i64>,
 }
@@ -362,6 +390,7 @@ impl MetricCollector for TimeSeries {
     fn new() -> Self {
         Self {
             buffer: Default::default(),
+            exemplars: Default::default(),
             metadata: Default::default(),
             timestamp: None,
         }
@@ -396,11 +425,48 @@ impl MetricCollector for TimeSeries {
             .push(proto::Sample { value, timestamp });
     }
 
-    fn finish(self) -> proto::WriteRequest {
+    fn emit_exemplar(
+        &mut self,
+        name: &str,
+        suffix: &str,
+        tags: Option<&MetricTags>,
+        extra: Option<(&str, String)>,
+        exemplar: &Exemplar,
+    ) {
+        let timestamp = exemplar
+            .timestamp
+            .map(|timestamp| timestamp.timestamp_millis())
+            .unwrap_or_else(|| self.default_timestamp());
+        let labels = exemplar
+            .labels
+            .iter()
+            .map(|(name, value)| proto::Label {
+                name: name.clone(),
+                value: value.clone(),
+            })
+            .collect();
+        self.exemplars
+            .entry(Self::make_labels(tags, name, suffix, extra))
+            .or_default()
+            .push(proto::Exemplar {
+                labels,
+                value: exemplar.value,
+                timestamp,
+            });
+    }
+
+    fn finish(mut self) -> proto::WriteRequest {
         let timeseries = self
             .buffer
             .into_iter()
-            .map(|(labels, samples)| proto::TimeSeries { labels, samples })
+            .map(|(labels, samples)| {
+                let exemplars = self.exemplars.swap_remove(&labels).unwrap_or_default();
+                proto::TimeSeries {
+                    labels,
+                    samples,
+                    exemplars,
+                }
+            })
             .collect::<Vec<_>>();
         let metadata = self
             .metadata
@@ -490,6 +556,7 @@ mod tests {
                                 value: $svalue,
                                 timestamp: $timestamp,
                             }],
+                            exemplars: vec![],
                         },
                     )*
                 ],
@@ -744,6 +811,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encodes_histogram_request_with_exemplars() {
+        let metric = Metric::new(
+            "requests".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vector_lib::buckets![1.0 => 1, 3.0 => 2],
+                count: 4,
+                sum: 11.5,
+            },
+        )
+        .with_timestamp(Some(timestamp()))
+        .with_exemplars(vec![
+            Exemplar {
+                labels: [("trace_id".to_owned(), "KOO5S4vxi0o".to_owned())].into(),
+                value: 2.5,
+                timestamp: Some(timestamp()),
+            },
+            Exemplar {
+                labels: [("trace_id".to_owned(), "oHg5SJYRHA0".to_owned())].into(),
+                value: 7.0,
+                timestamp: None,
+            },
+        ]);
+        let request = encode_one::<TimeSeries>(Some("vector"), &[], &[], &metric);
+
+        let exemplars = request
+            .timeseries
+            .iter()
+            .map(|series| {
+                let le = series.labels.iter().find(|label| label.name == "le");
+                (le.map(|label| label.value.as_str()), series.exemplars.len())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            exemplars,
+            vec![
+                (Some("1"), 0),
+                (Some("3"), 1),
+                (Some("+Inf"), 1),
+                (None, 0),
+                (None, 0)
+            ]
+        );
+        assert_eq!(
+            request.timeseries[1].exemplars[0],
+            proto::Exemplar {
+                labels: vec![proto::Label {
+                    name: "trace_id".into(),
+                    value: "KOO5S4vxi0o".into(),
+                }],
+                value: 2.5,
+                timestamp: 1612325106789,
+            }
+        );
+    }
+
     fn encode_histogram<T: MetricCollector>(add_inf_bound: bool) -> T::Output {
         let bounds = if add_inf_bound {
             &[1.0, 2.1, 3.0, f64::INFINITY][..]
@@ -2,7 +2,10 @@ use crate::{
     schema,
     sinks::{
         prelude::*,
-        pulsar::sink::{healthcheck, PulsarSink},
+        pulsar::sink::{
+            healthcheck, KeySharedBatchingSnafu, MissingSchemaDefinitionSnafu,
+            PartitionKeyConflictSnafu, PulsarSink, SchemaEncodingMismatchSnafu,
+        },
     },
 };
 use futures_util::FutureExt;
@@ -51,6 +54,25 @@ pub struct PulsarSinkConfig {
     #[configurable(metadata(docs::examples = "my_field"))]
     pub(crate) partition_key_field: Option<OptionalTargetPath>,
 
+    /// The partition key to use for each event, rendered from a template.
+    ///
+    /// Takes the place of `partition_key_field` when the key is built from multiple fields or
+    /// static text. Events for which the template fails to render are dropped.
+    #[configurable(metadata(docs::examples = "{{ tenant }}-{{ host }}"))]
+    pub(crate) partition_key: Option<Template>,
+
+    /// Whether to route events for consumers of `Key_Shared` subscriptions.
+    ///
+    /// When enabled, the partition key is also set as the ordering key of each message, so that
+    /// the broker dispatches all messages sharing a key to the same consumer. As the producer
+    /// batches messages regardless of their key, which would dispatch a whole batch to the
+    /// consumer of its first key, this can't be combined with `batch` settings.
+    #[serde(default)]
+    pub(crate) key_shared: bool,
+
+    #[configurable(derived)]
+    pub(crate) schema: Option<PulsarSchemaConfig>,
+
     /// The log field name to use for the Pulsar properties key.
     ///
     /// If omitted, no properties will be written.
@@ -96,6 +118,39 @@ pub(crate) struct PulsarBatchConfig {
     pub max_bytes: Option<usize>,
 }
 
+/// Schema registered with the topics written to.
+///
+/// Pulsar rejects messages from producers whose schema is incompatible with the schema of the
+/// topic, so registering it lets the broker enforce it and consumers decode messages with it.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub(crate) struct PulsarSchemaConfig {
+    #[configurable(derived)]
+    #[serde(rename = "type")]
+    pub(crate) kind: PulsarSchemaType,
+
+    /// The schema definition, in the Avro schema JSON format.
+    ///
+    /// Pulsar also uses this format to describe the structure of JSON messages. Defaults to the
+    /// schema of the `avro` encoding for Avro schemas, and is required for JSON schemas.
+    #[configurable(metadata(
+        docs::examples = r#"{ "type": "record", "name": "log", "fields": [{ "name": "message", "type": "string" }] }"#
+    ))]
+    pub(crate) definition: Option<String>,
+}
+
+/// Supported schema types for Pulsar.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PulsarSchemaType {
+    /// JSON messages, requiring the `json` encoding.
+    Json,
+
+    /// Avro messages, requiring the `avro` encoding.
+    Avro,
+}
+
 /// Authentication configuration.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -180,6 +235,9 @@ impl Default for PulsarSinkConfig {
             producer_name: None,
             properties_key: None,
             partition_key_field: None,
+            partition_key: None,
+            key_shared: false,
+            schema: None,
             batch: Default::default(),
             compression: Default::default(),
             encoding: TextSerializerConfig::default().into(),
@@ -263,15 +321,55 @@ impl PulsarSinkConfig {
             }
         }
 
-        if let SerializerConfig::Avro { avro } = self.encoding.config() {
-            opts.schema = Some(proto::Schema {
+        opts.schema = match (&self.schema, self.encoding.config()) {
+            (Some(schema), encoding) => {
+                let definition = match (&schema.definition, encoding) {
+                    (Some(definition), _) => definition.clone(),
+                    (None, SerializerConfig::Avro { avro }) => avro.schema.clone(),
+                    (None, _) => String::new(),
+                };
+                let r#type = match schema.kind {
+                    PulsarSchemaType::Json => proto::schema::Type::Json,
+                    PulsarSchemaType::Avro => proto::schema::Type::Avro,
+                };
+                Some(proto::Schema {
+                    schema_data: definition.into_bytes(),
+                    r#type: r#type as i32,
+                    ..Default::default()
+                })
+            }
+            (None, SerializerConfig::Avro { avro }) => Some(proto::Schema {
                 schema_data: avro.schema.as_bytes().into(),
                 r#type: proto::schema::Type::Avro as i32,
                 ..Default::default()
-            });
-        }
+            }),
+            (None, _) => None,
+        };
         opts
     }
+
+    /// Checks the options that can't be combined with each other.
+    pub(crate) fn validate(&self) -> Result<(), super::sink::BuildError> {
+        if self.partition_key.is_some() && self.partition_key_field.is_some() {
+            return PartitionKeyConflictSnafu.fail();
+        }
+        if self.key_shared && (self.batch.max_events.is_some() || self.batch.max_bytes.is_some()) {
+            return KeySharedBatchingSnafu.fail();
+        }
+        if let Some(schema) = &self.schema {
+            match (schema.kind, self.encoding.config()) {
+                (PulsarSchemaType::Json, SerializerConfig::Json(_))
+                    if schema.definition.is_none() =>
+                {
+                    return MissingSchemaDefinitionSnafu.fail();
+                }
+                (PulsarSchemaType::Json, SerializerConfig::Json(_))
+                | (PulsarSchemaType::Avro, SerializerConfig::Avro { .. }) => {}
+                _ => return SchemaEncodingMismatchSnafu.fail(),
+            }
+        }
+        Ok(())
+    }
 }
 
 impl GenerateConfig for PulsarSinkConfig {
@@ -284,6 +382,8 @@ impl GenerateConfig for PulsarSinkConfig {
 #[typetag::serde(name = "pulsar")]
 impl SinkConfig for PulsarSinkConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.validate()?;
+
         let client = self
             .create_pulsar_client()
             .await
@@ -56,6 +56,7 @@ pub struct PulsarService<Exe: Executor> {
     // NOTE: the reason for the Mutex here is because the `Producer` from the pulsar crate
     // needs to be `mut`, and the `Service::call()` returns a Future.
     producer: Arc<Mutex<MultiTopicProducer<Exe>>>,
+    key_shared: bool,
 }
 
 impl<Exe: Executor> PulsarService<Exe> {
@@ -63,6 +64,7 @@ impl<Exe: Executor> PulsarService<Exe> {
         pulsar_client: Pulsar<Exe>,
         producer_options: ProducerOptions,
         producer_name: Option<String>,
+        key_shared: bool,
     ) -> PulsarService<Exe> {
         let mut builder = pulsar_client.producer().with_options(producer_options);
 
@@ -74,6 +76,7 @@ impl<Exe: Executor> PulsarService<Exe> {
 
         PulsarService {
             producer: Arc::new(Mutex::new(producer)),
+            key_shared,
         }
     }
 }
@@ -92,6 +95,7 @@ impl<Exe: Executor> Service<PulsarRequest> for PulsarService<Exe> {
 
     fn call(&mut self, request: PulsarRequest) -> Self::Future {
         let producer = Arc::clone(&self.producer);
+        let key_shared = self.key_shared;
         let topic = request.metadata.topic.clone();
         let event_time = request
             .metadata
@@ -110,6 +114,13 @@ impl<Exe: Executor> Service<PulsarRequest> for PulsarService<Exe> {
                 }
             }
 
+            // Consumers of `Key_Shared` subscriptions are dispatched messages by their ordering key.
+            let ordering_key = request
+                .metadata
+                .key
+                .as_ref()
+                .filter(|_| key_shared)
+                .map(|key| key.to_vec());
             let partition_key = request
                 .metadata
                 .key
@@ -119,6 +130,7 @@ impl<Exe: Executor> Service<PulsarRequest> for PulsarService<Exe> {
                 payload: body.as_ref().to_vec(),
                 properties,
                 partition_key,
+                ordering_key,
                 event_time,
                 ..Default::default()
             };
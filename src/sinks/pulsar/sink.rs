@@ -17,6 +17,16 @@ use crate::sinks::prelude::*;
 pub(crate) enum BuildError {
     #[snafu(display("creating pulsar producer failed: {}", source))]
     CreatePulsarSink { source: PulsarError },
+    #[snafu(display("`partition_key` and `partition_key_field` can't both be set"))]
+    PartitionKeyConflict,
+    #[snafu(display("`key_shared` can't be combined with `batch` settings"))]
+    KeySharedBatching,
+    #[snafu(display("a `definition` is required for JSON schemas"))]
+    MissingSchemaDefinition,
+    #[snafu(display(
+        "the `schema` type must match the encoding codec, which must be `json` or `avro`"
+    ))]
+    SchemaEncodingMismatch,
 }
 
 pub(crate) struct PulsarSink {
@@ -84,7 +94,12 @@ impl PulsarSink {
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
-        let service = PulsarService::new(client, producer_opts, config.producer_name.clone());
+        let service = PulsarService::new(
+            client,
+            producer_opts,
+            config.producer_name.clone(),
+            config.key_shared,
+        );
         let topic_template = config.topic.clone();
 
         Ok(PulsarSink {
@@ -1,10 +1,11 @@
 use bytes::Bytes;
+use pulsar::message::proto;
 use vector_lib::configurable::component::GenerateConfig;
 use vector_lib::lookup::lookup_v2::OptionalTargetPath;
 use vrl::value::{ObjectMap, Value};
 
 use crate::event::{Event, LogEvent};
-use crate::sinks::pulsar::config::PulsarSinkConfig;
+use crate::sinks::pulsar::{config::PulsarSinkConfig, util::make_pulsar_event};
 
 #[test]
 fn generate_config() {
@@ -28,3 +29,91 @@ fn pulsar_get_headers() {
     assert_eq!(properties.get("a-key").unwrap(), "a-value".as_bytes());
     assert_eq!(properties.get("b-key").unwrap(), "b-value".as_bytes());
 }
+
+fn parse_config(extra: &str) -> PulsarSinkConfig {
+    toml::from_str(&format!(
+        r#"
+        endpoint = "pulsar://127.0.0.1:6650"
+        topic = "logs"
+        {extra}
+        "#
+    ))
+    .unwrap()
+}
+
+#[test]
+fn pulsar_renders_partition_key_template() {
+    let config = parse_config(
+        r#"
+        partition_key = "{{ tenant }}-{{ host }}"
+        encoding.codec = "text"
+        "#,
+    );
+    let mut log = LogEvent::from("hello");
+    log.insert("tenant", "acme");
+    log.insert("host", "web-1");
+
+    let event = make_pulsar_event(&config.topic, &config, Event::Log(log)).unwrap();
+    assert_eq!(event.key, Some(Bytes::from("acme-web-1")));
+
+    // Events whose key can't be rendered are dropped.
+    let event = Event::Log(LogEvent::from("hello"));
+    assert!(make_pulsar_event(&config.topic, &config, event).is_none());
+}
+
+#[test]
+fn pulsar_rejects_conflicting_options() {
+    let config = parse_config(
+        r#"
+        partition_key = "{{ tenant }}"
+        partition_key_field = "tenant"
+        encoding.codec = "text"
+        "#,
+    );
+    assert!(config.validate().is_err());
+
+    let config = parse_config(
+        r#"
+        key_shared = true
+        batch.max_events = 100
+        encoding.codec = "text"
+        "#,
+    );
+    assert!(config.validate().is_err());
+
+    let config = parse_config(
+        r#"
+        schema.type = "json"
+        encoding.codec = "json"
+        "#,
+    );
+    assert!(config.validate().is_err());
+
+    let config = parse_config(
+        r#"
+        schema.type = "avro"
+        schema.definition = '{ "type": "string" }'
+        encoding.codec = "json"
+        "#,
+    );
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn pulsar_registers_json_schema() {
+    let config = parse_config(
+        r#"
+        schema.type = "json"
+        schema.definition = '{ "type": "record", "name": "log", "fields": [] }'
+        encoding.codec = "json"
+        "#,
+    );
+    config.validate().unwrap();
+
+    let schema = config.build_producer_options().schema.unwrap();
+    assert_eq!(schema.r#type, proto::schema::Type::Json as i32);
+    assert_eq!(
+        schema.schema_data,
+        br#"{ "type": "record", "name": "log", "fields": [] }"#.to_vec()
+    );
+}
@@ -1,3 +1,5 @@
+use std::{borrow::Cow, collections::HashSet};
+
 use vector_lib::{event::Event, partition::Partitioner};
 
 use crate::{internal_events::TemplateRenderingError, template::Template};
@@ -9,14 +11,19 @@ pub struct S3PartitionKey {
 }
 
 /// Partitions items based on the generated key for the given event.
-pub struct S3KeyPartitioner(Template, Option<Template>);
+pub struct S3KeyPartitioner(Template, Option<Template>, bool);
 
 impl S3KeyPartitioner {
     pub const fn new(
         key_prefix_template: Template,
         ssekms_key_id_template: Option<Template>,
+        hive_partitioning: bool,
     ) -> Self {
-        Self(key_prefix_template, ssekms_key_id_template)
+        Self(
+            key_prefix_template,
+            ssekms_key_id_template,
+            hive_partitioning,
+        )
     }
 }
 
@@ -25,9 +32,13 @@ impl Partitioner for S3KeyPartitioner {
     type Key = Option<S3PartitionKey>;
 
     fn partition(&self, item: &Self::Item) -> Self::Key {
-        let key_prefix = self
-            .0
-            .render_string(item)
+        let key_prefix = if self.2 {
+            self.0
+                .render_string_escaped(item, escape_hive_partition_value)
+        } else {
+            self.0.render_string(item)
+        };
+        let key_prefix = key_prefix
             .map_err(|error| {
                 emit!(TemplateRenderingError {
                     error,
@@ -56,3 +67,125 @@ impl Partitioner for S3KeyPartitioner {
         })
     }
 }
+
+/// Validates a key prefix as Hive-style partitions, such as `logs/dt=%F/hour=%H/`.
+///
+/// After any leading path segments, every segment must be a `name=value` partition with a unique
+/// literal name, and the prefix must end with `/` to separate the partitions from the filenames.
+pub fn validate_hive_key_prefix(key_prefix: &str) -> Result<(), String> {
+    let Some(segments) = key_prefix.strip_suffix('/') else {
+        return Err(format!(
+            "Hive-style key prefix {key_prefix:?} must end with `/`."
+        ));
+    };
+
+    let mut names = HashSet::new();
+    for segment in segments.split('/') {
+        let Some((name, value)) = segment.split_once('=') else {
+            if names.is_empty() && !segment.is_empty() {
+                // A path segment preceding the partitions, such as the location of a table.
+                continue;
+            }
+            return Err(format!(
+                "Segment {segment:?} of Hive-style key prefix {key_prefix:?} is not a `name=value` partition."
+            ));
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!(
+                "Partition name {name:?} of Hive-style key prefix {key_prefix:?} must only contain letters, digits and underscores."
+            ));
+        }
+        if value.is_empty() {
+            return Err(format!(
+                "Partition {name:?} of Hive-style key prefix {key_prefix:?} has no value."
+            ));
+        }
+        if !names.insert(name) {
+            return Err(format!(
+                "Partition {name:?} is repeated in Hive-style key prefix {key_prefix:?}."
+            ));
+        }
+    }
+
+    if names.is_empty() {
+        return Err(format!(
+            "Hive-style key prefix {key_prefix:?} has no `name=value` partition."
+        ));
+    }
+    Ok(())
+}
+
+/// Escapes the characters of a partition value the way Hive escapes them, so that values rendered
+/// from events cannot create additional partitions.
+fn escape_hive_partition_value(value: &str) -> Cow<'_, str> {
+    const fn needs_escape(c: char) -> bool {
+        (c.is_ascii_control() && c != '\0')
+            || matches!(
+                c,
+                '"' | '#' | '%' | '\'' | '*' | '/' | ':' | '=' | '?' | '\\' | '{' | '[' | ']' | '^'
+            )
+    }
+
+    if !value.chars().any(needs_escape) {
+        return Cow::Borrowed(value);
+    }
+    let mut escaped = String::with_capacity(value.len() + 8);
+    for c in value.chars() {
+        if needs_escape(c) {
+            escaped.push_str(&format!("%{:02X}", c as u32));
+        } else {
+            escaped.push(c);
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::event::LogEvent;
+
+    use super::*;
+
+    #[test]
+    fn validates_hive_key_prefixes() {
+        for key_prefix in [
+            "dt=%F/",
+            "dt=%Y-%m-%d/hour=%H/",
+            "logs/app/dt=%F/service={{ service }}/",
+        ] {
+            assert_eq!(validate_hive_key_prefix(key_prefix), Ok(()), "{key_prefix}");
+        }
+
+        for key_prefix in [
+            "dt=%F",
+            "logs/",
+            "dt=%F/logs/",
+            "dt=%F//hour=%H/",
+            "=%F/",
+            "{{ name }}=value/",
+            "dt=/",
+            "dt=%F/dt=%H/",
+        ] {
+            assert!(
+                validate_hive_key_prefix(key_prefix).is_err(),
+                "{key_prefix}"
+            );
+        }
+    }
+
+    #[test]
+    fn escapes_hive_partition_values() {
+        let partitioner = S3KeyPartitioner::new(
+            Template::try_from("service={{ service }}/").unwrap(),
+            None,
+            true,
+        );
+        let mut log = LogEvent::from("message");
+        log.insert("service", "api/v1=beta");
+        let key = partitioner.partition(&log.into()).unwrap();
+        assert_eq!(key.key_prefix, "service=api%2Fv1%3Dbeta/");
+
+        assert_eq!(escape_hive_partition_value("api"), "api");
+        assert_eq!(escape_hive_partition_value("50%"), "50%25");
+    }
+}
@@ -0,0 +1,184 @@
+//! Encoding for the `slack` sink.
+
+use std::io;
+
+use serde_json::{json, Value as JsonValue};
+
+use crate::sinks::{
+    prelude::*,
+    util::encoding::{write_all, Encoder as SinkEncoder},
+};
+
+use super::config::SlackMode;
+
+/// Slack truncates the text of the blocks beyond 3000 characters.
+const MAX_TEXT_LENGTH: usize = 3000;
+
+/// Slack rejects fields longer than 2000 characters.
+const MAX_FIELD_LENGTH: usize = 2000;
+
+/// The events of a batch, and the thread they are posted to.
+pub(super) struct SlackMessage {
+    pub(super) thread_ts: Option<String>,
+    pub(super) events: Vec<Event>,
+}
+
+/// The text and fields rendered from an event.
+pub(super) struct RenderedEvent {
+    pub(super) text: String,
+    pub(super) fields: Vec<(String, String)>,
+}
+
+pub(super) struct SlackEncoder {
+    pub(super) mode: SlackMode,
+    pub(super) text: Template,
+    pub(super) fields: Vec<(String, Template)>,
+}
+
+impl SlackEncoder {
+    /// Renders the text and fields of an event.
+    /// Returns `None` if the text fails to render, in which case an error event is also emitted.
+    pub(super) fn render(&self, log: &LogEvent) -> Option<RenderedEvent> {
+        let text = self
+            .text
+            .render_string(log)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("text"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
+        let fields = self
+            .fields
+            .iter()
+            .filter_map(|(title, value)| {
+                value
+                    .render_string(log)
+                    .map_err(|error| {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some("fields"),
+                            drop_event: false,
+                        });
+                    })
+                    .ok()
+                    .map(|value| (title.clone(), value))
+            })
+            .collect();
+
+        Some(RenderedEvent { text, fields })
+    }
+
+    /// Builds the body of the message posting the rendered events.
+    pub(super) fn message(&self, thread_ts: Option<String>, events: &[RenderedEvent]) -> JsonValue {
+        match self.mode {
+            SlackMode::Slack => slack_message(thread_ts, events),
+            SlackMode::Teams => teams_message(events),
+        }
+    }
+}
+
+fn slack_message(thread_ts: Option<String>, events: &[RenderedEvent]) -> JsonValue {
+    let blocks = events
+        .iter()
+        .map(|event| {
+            let mut block = json!({
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": truncate(&event.text, MAX_TEXT_LENGTH) },
+            });
+            if !event.fields.is_empty() {
+                block["fields"] = event
+                    .fields
+                    .iter()
+                    .map(|(title, value)| {
+                        let text = truncate(&format!("*{title}*\n{value}"), MAX_FIELD_LENGTH);
+                        json!({ "type": "mrkdwn", "text": text })
+                    })
+                    .collect();
+            }
+            block
+        })
+        .collect::<Vec<_>>();
+
+    // Notifications show the top-level text rather than the blocks.
+    let mut message = json!({
+        "text": truncate(&events[0].text, MAX_TEXT_LENGTH),
+        "blocks": blocks,
+    });
+    if let Some(thread_ts) = thread_ts {
+        message["thread_ts"] = thread_ts.into();
+    }
+    message
+}
+
+fn teams_message(events: &[RenderedEvent]) -> JsonValue {
+    let mut body = Vec::with_capacity(events.len() * 2);
+    for (index, event) in events.iter().enumerate() {
+        body.push(json!({
+            "type": "TextBlock",
+            "text": event.text,
+            "wrap": true,
+            "separator": index > 0,
+        }));
+        if !event.fields.is_empty() {
+            let facts = event
+                .fields
+                .iter()
+                .map(|(title, value)| json!({ "title": title, "value": value }))
+                .collect::<Vec<_>>();
+            body.push(json!({ "type": "FactSet", "facts": facts }));
+        }
+    }
+
+    json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "body": body,
+            },
+        }],
+    })
+}
+
+impl SinkEncoder<SlackMessage> for SlackEncoder {
+    fn encode_input(
+        &self,
+        input: SlackMessage,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        let mut rendered = Vec::with_capacity(input.events.len());
+
+        for event in input.events {
+            if let Some(event_rendered) = self.render(event.as_log()) {
+                byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+                rendered.push(event_rendered);
+            }
+        }
+
+        if rendered.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "none of the events of the batch could be rendered",
+            ));
+        }
+
+        let body = serde_json::to_vec(&self.message(input.thread_ts, &rendered))?;
+
+        write_all(writer, rendered.len(), &body).map(|()| (body.len(), byte_size))
+    }
+}
+
+fn truncate(text: &str, max_length: usize) -> String {
+    match text.char_indices().nth(max_length) {
+        Some((index, _)) => text[..index].to_owned(),
+        None => text.to_owned(),
+    }
+}
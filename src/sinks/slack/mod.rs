@@ -0,0 +1,18 @@
+//! `slack` sink.
+//!
+//! Posts events as messages to [Slack incoming webhooks][slack_webhooks], or to Microsoft Teams
+//! webhooks in the `teams` mode. The events of each batch are posted as a single message, with
+//! their text and fields rendered from templates, optionally as replies in the thread of a
+//! message.
+//!
+//! [slack_webhooks]: https://api.slack.com/messaging/webhooks
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::config::SlackSinkConfig;
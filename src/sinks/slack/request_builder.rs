@@ -0,0 +1,106 @@
+//! `RequestBuilder` implementation for the `slack` sink.
+
+use std::io;
+
+use bytes::Bytes;
+use http::Uri;
+
+use crate::{
+    internal_events::SlackInvalidUriError,
+    sinks::{prelude::*, util::http::HttpRequest},
+};
+
+use super::encoder::{SlackEncoder, SlackMessage};
+
+/// The webhook and thread the events of a batch are posted to.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(super) struct SlackPartitionKey {
+    pub(super) uri: Uri,
+    pub(super) thread_ts: Option<String>,
+}
+
+/// Partitions the events by the webhook and thread they are posted to.
+pub(super) struct SlackPartitioner {
+    pub(super) uri: Template,
+    pub(super) thread_ts: Option<Template>,
+}
+
+impl Partitioner for SlackPartitioner {
+    type Item = Event;
+    type Key = Option<SlackPartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let uri = render(&self.uri, item, "uri")?
+            .parse::<Uri>()
+            .map_err(|error| {
+                emit!(SlackInvalidUriError {
+                    error: error.to_string(),
+                });
+            })
+            .ok()?;
+        let thread_ts = match &self.thread_ts {
+            Some(thread_ts) => Some(render(thread_ts, item, "thread_ts")?),
+            None => None,
+        };
+
+        Some(SlackPartitionKey { uri, thread_ts })
+    }
+}
+
+fn render(template: &Template, item: &Event, field: &'static str) -> Option<String> {
+    template
+        .render_string(item)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: true,
+            });
+        })
+        .ok()
+}
+
+pub(super) struct SlackRequestBuilder {
+    pub(super) encoder: SlackEncoder,
+}
+
+impl RequestBuilder<(SlackPartitionKey, Vec<Event>)> for SlackRequestBuilder {
+    type Metadata = (Uri, EventFinalizers);
+    type Events = SlackMessage;
+    type Encoder = SlackEncoder;
+    type Payload = Bytes;
+    type Request = HttpRequest<Uri>;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (SlackPartitionKey, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (key, mut events) = input;
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        let message = SlackMessage {
+            thread_ts: key.thread_ts,
+            events,
+        };
+        ((key.uri, finalizers), builder, message)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (uri, finalizers) = metadata;
+        HttpRequest::new(payload.into_payload(), finalizers, request_metadata, uri)
+    }
+}
@@ -0,0 +1,25 @@
+//! Service implementation for the `slack` sink.
+
+use bytes::Bytes;
+use http::{header::CONTENT_TYPE, Request, Uri};
+use snafu::ResultExt;
+
+use crate::sinks::{
+    util::http::{HttpRequest, HttpServiceRequestBuilder},
+    HTTPRequestBuilderSnafu,
+};
+
+/// Builds the requests, which carry the URI of the webhook they are posted to.
+#[derive(Debug, Clone)]
+pub(super) struct SlackServiceRequestBuilder;
+
+impl HttpServiceRequestBuilder<Uri> for SlackServiceRequestBuilder {
+    fn build(&self, mut request: HttpRequest<Uri>) -> Result<Request<Bytes>, crate::Error> {
+        // The webhook URIs embed their secret, so no credentials are needed in the headers.
+        Request::post(request.get_additional_metadata())
+            .header(CONTENT_TYPE, "application/json")
+            .body(request.take_payload())
+            .context(HTTPRequestBuilderSnafu)
+            .map_err(Into::into)
+    }
+}
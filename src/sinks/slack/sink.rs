@@ -0,0 +1,63 @@
+//! Implementation of the `slack` sink.
+
+use http::Uri;
+
+use crate::sinks::{prelude::*, util::http::HttpRequest};
+
+use super::request_builder::{SlackPartitioner, SlackRequestBuilder};
+
+pub(super) struct SlackSink<S> {
+    pub(super) service: S,
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) partitioner: SlackPartitioner,
+    pub(super) request_builder: SlackRequestBuilder,
+}
+
+impl<S> SlackSink<S>
+where
+    S: Service<HttpRequest<Uri>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let Self {
+            service,
+            batch_settings,
+            partitioner,
+            request_builder,
+        } = *self;
+
+        input
+            // Each batch is posted as a single message, so the events of a batch must share
+            // their webhook and thread.
+            .batched_partitioned(partitioner, || batch_settings.as_byte_size_config())
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) })
+            .request_builder(default_request_builder_concurrency_limit(), request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(request) => Some(request),
+                }
+            })
+            .into_driver(service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for SlackSink<S>
+where
+    S: Service<HttpRequest<Uri>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,167 @@
+//! Unit tests for the `slack` sink.
+
+use futures::stream;
+use hyper::{Body, Response};
+use serde_json::json;
+
+use crate::{
+    sinks::{prelude::*, util::test::build_test_server_generic},
+    test_util::{
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        next_addr,
+    },
+};
+
+use super::config::SlackSinkConfig;
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<SlackSinkConfig>();
+}
+
+fn test_config(extra: &str) -> SlackSinkConfig {
+    let config = format!(
+        r#"
+        uri = "https://hooks.slack.com/services/T0/B0/X"
+        text = "*{{{{ check }}}}* failed"
+        fields.Host = "{{{{ host }}}}"
+        fields.Team = "{{{{ team }}}}"
+        {extra}
+        "#
+    );
+    toml::from_str(&config).unwrap()
+}
+
+fn alert(check: &str, team: Option<&str>) -> LogEvent {
+    let mut log = LogEvent::default();
+    log.insert("check", check);
+    log.insert("host", "web-1");
+    if let Some(team) = team {
+        log.insert("team", team);
+    }
+    log
+}
+
+#[test]
+fn slack_message() {
+    let encoder = test_config("").build_encoder();
+    let events = [alert("disk", Some("storage")), alert("cpu", None)]
+        .iter()
+        .map(|log| encoder.render(log).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        encoder.message(Some("1700000000.000100".to_owned()), &events),
+        json!({
+            "text": "*disk* failed",
+            "blocks": [
+                {
+                    "type": "section",
+                    "text": { "type": "mrkdwn", "text": "*disk* failed" },
+                    "fields": [
+                        { "type": "mrkdwn", "text": "*Host*\nweb-1" },
+                        { "type": "mrkdwn", "text": "*Team*\nstorage" },
+                    ],
+                },
+                {
+                    "type": "section",
+                    "text": { "type": "mrkdwn", "text": "*cpu* failed" },
+                    "fields": [{ "type": "mrkdwn", "text": "*Host*\nweb-1" }],
+                },
+            ],
+            "thread_ts": "1700000000.000100",
+        })
+    );
+}
+
+#[test]
+fn teams_message() {
+    let encoder = test_config(r#"mode = "teams""#).build_encoder();
+    let events = [alert("disk", Some("storage"))]
+        .iter()
+        .map(|log| encoder.render(log).unwrap())
+        .collect::<Vec<_>>();
+
+    let message = encoder.message(None, &events);
+    assert_eq!(message["type"], "message");
+    assert_eq!(
+        message["attachments"][0]["content"]["body"],
+        json!([
+            { "type": "TextBlock", "text": "*disk* failed", "wrap": true, "separator": false },
+            {
+                "type": "FactSet",
+                "facts": [
+                    { "title": "Host", "value": "web-1" },
+                    { "title": "Team", "value": "storage" },
+                ],
+            },
+        ])
+    );
+}
+
+#[test]
+fn drops_events_without_text() {
+    let encoder = test_config("").build_encoder();
+    assert!(encoder.render(&LogEvent::default()).is_none());
+}
+
+#[tokio::test]
+async fn rejects_thread_ts_in_teams_mode() {
+    let config = test_config(
+        r#"
+        mode = "teams"
+        thread_ts = "{{ thread_ts }}"
+        "#,
+    );
+
+    let error = config.build(SinkContext::default()).await.err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "`thread_ts` is only supported in the `slack` mode"
+    );
+}
+
+#[tokio::test]
+async fn slack_posts_batches_per_thread() {
+    let addr = next_addr();
+    let config = format!(
+        r#"
+        uri = "http://{addr}/services/{{{{ team }}}}"
+        thread_ts = "{{{{ thread_ts }}}}"
+        batch.timeout_secs = 0.1
+        "#
+    );
+    let config: SlackSinkConfig = toml::from_str(&config).unwrap();
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+    let (mut rx, _trigger, server) =
+        build_test_server_generic(addr, || Response::new(Body::from("ok")));
+    tokio::spawn(server);
+
+    let events = [("storage", "1"), ("storage", "1"), ("network", "2")].map(|(team, ts)| {
+        let mut log = LogEvent::from(format!("alert for {team}"));
+        log.insert("team", team);
+        log.insert("thread_ts", ts);
+        Event::Log(log)
+    });
+    run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS).await;
+
+    let mut requests = Vec::new();
+    for _ in 0..2 {
+        requests.push(rx.next().await.unwrap());
+    }
+    requests.sort_by(|(a, _), (b, _)| a.uri.path().cmp(b.uri.path()));
+
+    for ((parts, body), (team, ts, count)) in requests
+        .into_iter()
+        .zip([("network", "2", 1), ("storage", "1", 2)])
+    {
+        assert_eq!(parts.uri.path(), format!("/services/{team}"));
+        assert_eq!(parts.headers["content-type"], "application/json");
+
+        let body = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        assert_eq!(body["thread_ts"], ts);
+        assert_eq!(body["text"], format!("alert for {team}"));
+        assert_eq!(body["blocks"].as_array().unwrap().len(), count);
+    }
+}
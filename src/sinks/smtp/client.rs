@@ -0,0 +1,218 @@
+//! A minimal SMTP client, covering what's needed to submit messages to a relay.
+
+use std::{net::SocketAddr, pin::Pin};
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use snafu::ResultExt;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufStream},
+    net::TcpStream,
+};
+use tokio_openssl::SslStream;
+use vector_lib::{
+    sensitive_string::SensitiveString,
+    tls::{tls_connector_builder, MaybeTls, MaybeTlsSettings, MaybeTlsStream},
+};
+
+use crate::dns;
+
+use super::{
+    config::SmtpSecurity, ConnectSnafu, DnsSnafu, HandshakeSnafu, IoSnafu, SmtpError,
+    TlsConfigureSnafu, TlsSettingsSnafu,
+};
+
+/// The credentials the client authenticates with, using the `PLAIN` mechanism.
+pub(super) struct SmtpCredentials {
+    pub(super) username: String,
+    pub(super) password: SensitiveString,
+}
+
+/// Opens connections to an SMTP server, ready to send messages.
+pub(super) struct SmtpClient {
+    pub(super) host: String,
+    pub(super) port: u16,
+    pub(super) security: SmtpSecurity,
+    pub(super) tls: MaybeTlsSettings,
+    pub(super) credentials: Option<SmtpCredentials>,
+    /// The name the client introduces itself with.
+    pub(super) hello_name: String,
+}
+
+impl SmtpClient {
+    /// Connects to the server, upgrading the connection to TLS with `STARTTLS` and authenticating
+    /// as configured.
+    pub(super) async fn connect(&self) -> Result<SmtpConnection, SmtpError> {
+        let ip = dns::Resolver
+            .lookup_ip(self.host.clone())
+            .await
+            .context(DnsSnafu)?
+            .next()
+            .ok_or(SmtpError::NoAddresses)?;
+        let addr = SocketAddr::new(ip, self.port);
+
+        let stream = match self.security {
+            SmtpSecurity::Tls => self.tls.connect(&self.host, &addr).await,
+            SmtpSecurity::None | SmtpSecurity::StartTls => {
+                MaybeTlsSettings::Raw(()).connect(&self.host, &addr).await
+            }
+        }
+        .context(ConnectSnafu)?;
+
+        let mut connection = SmtpConnection {
+            stream: BufStream::new(stream),
+        };
+        connection.read_reply("greeting").await?;
+        connection.hello(&self.hello_name).await?;
+
+        if self.security == SmtpSecurity::StartTls {
+            connection.command("STARTTLS", "STARTTLS").await?;
+            connection = connection.start_tls(&self.host, &self.tls).await?;
+            // The capabilities of the server may change once the connection is secured.
+            connection.hello(&self.hello_name).await?;
+        }
+
+        if let Some(credentials) = &self.credentials {
+            let token = BASE64_STANDARD.encode(format!(
+                "\0{}\0{}",
+                credentials.username,
+                credentials.password.inner()
+            ));
+            connection
+                .command(&format!("AUTH PLAIN {token}"), "AUTH")
+                .await?;
+        }
+
+        Ok(connection)
+    }
+}
+
+pub(super) struct SmtpConnection {
+    stream: BufStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl SmtpConnection {
+    /// Sends a message, which must be formatted with CRLF line endings and end with one.
+    pub(super) async fn send(
+        &mut self,
+        from: &str,
+        to: &[String],
+        message: &[u8],
+    ) -> Result<(), SmtpError> {
+        self.command(&format!("MAIL FROM:<{from}>"), "MAIL").await?;
+        for recipient in to {
+            self.command(&format!("RCPT TO:<{recipient}>"), "RCPT")
+                .await?;
+        }
+        self.command("DATA", "DATA").await?;
+
+        let stuffed = dot_stuff(message);
+        self.stream.write_all(&stuffed).await.context(IoSnafu)?;
+        self.stream.write_all(b".\r\n").await.context(IoSnafu)?;
+        self.stream.flush().await.context(IoSnafu)?;
+        self.read_reply("DATA").await
+    }
+
+    /// Ends the session.
+    pub(super) async fn quit(mut self) -> Result<(), SmtpError> {
+        self.command("QUIT", "QUIT").await
+    }
+
+    async fn hello(&mut self, name: &str) -> Result<(), SmtpError> {
+        self.command(&format!("EHLO {name}"), "EHLO").await
+    }
+
+    async fn start_tls(self, host: &str, tls: &MaybeTlsSettings) -> Result<Self, SmtpError> {
+        // Nothing is buffered, as the server waits for the handshake after its reply.
+        let stream = match self.stream.into_inner() {
+            MaybeTls::Raw(stream) => stream,
+            stream @ MaybeTls::Tls(_) => {
+                return Ok(Self {
+                    stream: BufStream::new(stream),
+                })
+            }
+        };
+
+        let mut configuration = tls_connector_builder(tls)
+            .context(TlsSettingsSnafu)?
+            .build()
+            .configure()
+            .context(TlsConfigureSnafu)?;
+        if let Some(settings) = tls.tls() {
+            settings
+                .apply_connect_configuration(&mut configuration)
+                .context(TlsConfigureSnafu)?;
+        }
+        let ssl = configuration.into_ssl(host).context(TlsConfigureSnafu)?;
+
+        let mut stream = SslStream::new(ssl, stream).context(TlsConfigureSnafu)?;
+        Pin::new(&mut stream)
+            .connect()
+            .await
+            .context(HandshakeSnafu)?;
+        debug!(message = "Negotiated TLS.");
+
+        Ok(Self {
+            stream: BufStream::new(MaybeTls::Tls(stream)),
+        })
+    }
+
+    /// Sends a command, failing if the server doesn't reply with a positive completion or
+    /// intermediate reply.
+    async fn command(&mut self, line: &str, command: &'static str) -> Result<(), SmtpError> {
+        self.stream
+            .write_all(line.as_bytes())
+            .await
+            .context(IoSnafu)?;
+        self.stream.write_all(b"\r\n").await.context(IoSnafu)?;
+        self.stream.flush().await.context(IoSnafu)?;
+        self.read_reply(command).await
+    }
+
+    async fn read_reply(&mut self, command: &'static str) -> Result<(), SmtpError> {
+        let mut message = String::new();
+        loop {
+            let mut line = String::new();
+            if self.stream.read_line(&mut line).await.context(IoSnafu)? == 0 {
+                return Err(SmtpError::ConnectionClosed);
+            }
+            let line = line.trim_end();
+            let code = line
+                .get(..3)
+                .and_then(|code| code.parse::<u16>().ok())
+                .ok_or_else(|| SmtpError::InvalidReply {
+                    line: line.to_owned(),
+                })?;
+            if !message.is_empty() {
+                message.push(' ');
+            }
+            message.push_str(line.get(4..).unwrap_or_default());
+
+            // Every line but the last of a reply has a hyphen after the code.
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return if code < 400 {
+                    Ok(())
+                } else {
+                    Err(SmtpError::Rejected {
+                        command,
+                        code,
+                        message,
+                    })
+                };
+            }
+        }
+    }
+}
+
+/// Escapes the lines of a message starting with a dot, which would otherwise end its data.
+pub(super) fn dot_stuff(message: &[u8]) -> Vec<u8> {
+    let mut stuffed = Vec::with_capacity(message.len());
+    let mut line_start = true;
+    for &byte in message {
+        if line_start && byte == b'.' {
+            stuffed.push(b'.');
+        }
+        stuffed.push(byte);
+        line_start = byte == b'\n';
+    }
+    stuffed
+}
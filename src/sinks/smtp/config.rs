@@ -0,0 +1,205 @@
+//! Configuration for the `smtp` sink.
+
+use std::sync::Arc;
+
+use vector_lib::{sensitive_string::SensitiveString, tls::MaybeTlsSettings};
+
+use crate::sinks::{prelude::*, util::service::TowerRequestConfigDefaults};
+
+use super::{
+    client::{SmtpClient, SmtpCredentials},
+    service::{SmtpRetryLogic, SmtpService},
+    sink::SmtpSink,
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SmtpDefaultBatchSettings;
+
+impl SinkBatchSettings for SmtpDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1);
+    const MAX_BYTES: Option<usize> = None;
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SmtpTowerRequestConfigDefaults;
+
+impl TowerRequestConfigDefaults for SmtpTowerRequestConfigDefaults {
+    const CONCURRENCY: Concurrency = Concurrency::None;
+}
+
+/// How the connection to the server is secured.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpSecurity {
+    /// The connection isn't secured.
+    None,
+
+    /// The connection is upgraded to TLS with the `STARTTLS` command, failing if the server
+    /// doesn't support it.
+    ///
+    /// This is the default, as used by submission servers listening on port 587.
+    #[derivative(Default)]
+    StartTls,
+
+    /// The connection is made over TLS, as expected by servers listening on port 465.
+    Tls,
+}
+
+/// The credentials to authenticate with, using the `PLAIN` mechanism.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SmtpAuthConfig {
+    /// The username to authenticate as.
+    #[configurable(metadata(docs::examples = "alerts@example.com"))]
+    pub username: String,
+
+    /// The password to authenticate with.
+    #[configurable(metadata(docs::examples = "${SMTP_PASSWORD}"))]
+    pub password: SensitiveString,
+}
+
+const fn default_port() -> u16 {
+    587
+}
+
+/// Configuration for the `smtp` sink.
+#[configurable_component(sink("smtp", "Send events as email over SMTP."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SmtpSinkConfig {
+    /// The host name of the SMTP server.
+    #[configurable(metadata(docs::examples = "smtp.example.com"))]
+    pub host: String,
+
+    /// The port of the SMTP server.
+    #[serde(default = "default_port")]
+    pub port: u16,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub security: SmtpSecurity,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    pub auth: Option<SmtpAuthConfig>,
+
+    /// The address the messages are sent from.
+    #[configurable(metadata(docs::examples = "vector@example.com"))]
+    pub from: String,
+
+    /// The addresses the messages are sent to.
+    #[configurable(metadata(docs::examples = "oncall@example.com"))]
+    pub to: Vec<String>,
+
+    /// The template of the subject of the messages.
+    ///
+    /// The subject of a message holding several events is rendered from the first of them.
+    #[configurable(metadata(docs::examples = "[{{ host }}] {{ level }}: {{ message }}"))]
+    pub subject: Template,
+
+    /// The template of the body of the messages, rendered for each event.
+    ///
+    /// The bodies of the events of a message are separated by a blank line.
+    #[configurable(metadata(docs::examples = "{{ timestamp }} {{ host }}: {{ message }}"))]
+    pub body: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<SmtpDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig<SmtpTowerRequestConfigDefaults>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for SmtpSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            host = "smtp.example.com"
+            from = "vector@example.com"
+            to = ["oncall@example.com"]
+            subject = "{{ message }}"
+            body = "{{ message }}"
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "smtp")]
+impl SinkConfig for SmtpSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.to.is_empty() {
+            return Err("`to` cannot be empty.".into());
+        }
+
+        let client = Arc::new(self.build_client()?);
+        let healthcheck = healthcheck(Arc::clone(&client)).boxed();
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), SmtpRetryLogic)
+            .service(SmtpService::new(client, self.from.clone(), self.to.clone()));
+
+        let sink = SmtpSink {
+            service,
+            batch_settings: self.batch.into_batcher_settings()?,
+            subject: self.subject.clone(),
+            body: self.body.clone(),
+            from: self.from.clone(),
+            to: self.to.clone(),
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl SmtpSinkConfig {
+    fn build_client(&self) -> crate::Result<SmtpClient> {
+        let tls = match self.security {
+            SmtpSecurity::None => MaybeTlsSettings::Raw(()),
+            SmtpSecurity::StartTls | SmtpSecurity::Tls => MaybeTlsSettings::tls_client(&self.tls)?,
+        };
+        let credentials = self.auth.as_ref().map(|auth| SmtpCredentials {
+            username: auth.username.clone(),
+            password: auth.password.clone(),
+        });
+
+        Ok(SmtpClient {
+            host: self.host.clone(),
+            port: self.port,
+            security: self.security,
+            tls,
+            credentials,
+            hello_name: crate::get_hostname().unwrap_or_else(|_| "localhost".to_owned()),
+        })
+    }
+}
+
+async fn healthcheck(client: Arc<SmtpClient>) -> crate::Result<()> {
+    client.connect().await?.quit().await?;
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//! `smtp` sink.
+//!
+//! Sends events as email over [SMTP](https://datatracker.ietf.org/doc/html/rfc5321), one message
+//! per batch of events, rendering each event into the message through templates.
+mod client;
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+use snafu::Snafu;
+use vector_lib::tls::TlsError;
+
+use crate::{dns::DnsError, sinks::prelude::*};
+
+pub use self::config::SmtpSinkConfig;
+
+#[derive(Debug, Snafu)]
+pub(super) enum SmtpError {
+    #[snafu(display("Failed to resolve the server address: {}", source))]
+    Dns { source: DnsError },
+
+    #[snafu(display("The server address resolved to no IP addresses"))]
+    NoAddresses,
+
+    #[snafu(display("Failed to connect to the server: {}", source))]
+    Connect { source: TlsError },
+
+    #[snafu(display("Failed to configure TLS: {}", source))]
+    TlsConfigure { source: openssl::error::ErrorStack },
+
+    #[snafu(display("Failed to configure TLS: {}", source))]
+    TlsSettings { source: TlsError },
+
+    #[snafu(display("TLS handshake failed: {}", source))]
+    Handshake { source: openssl::ssl::Error },
+
+    #[snafu(display("Failed to communicate with the server: {}", source))]
+    Io { source: std::io::Error },
+
+    #[snafu(display("The server closed the connection"))]
+    ConnectionClosed,
+
+    #[snafu(display("Invalid reply from the server: {:?}", line))]
+    InvalidReply { line: String },
+
+    #[snafu(display("The server rejected {}: {} {}", command, code, message))]
+    Rejected {
+        command: &'static str,
+        code: u16,
+        message: String,
+    },
+}
+
+/// Wrapper for an `Event` that also stores its rendered subject and body.
+pub(super) struct SmtpEvent {
+    event: Event,
+    subject: String,
+    body: String,
+}
+
+impl Finalizable for SmtpEvent {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.event.take_finalizers()
+    }
+}
+
+impl ByteSizeOf for SmtpEvent {
+    fn allocated_bytes(&self) -> usize {
+        self.event.allocated_bytes() + self.subject.allocated_bytes() + self.body.allocated_bytes()
+    }
+}
+
+impl GetEventCountTags for SmtpEvent {
+    fn get_tags(&self) -> TaggedEventsSent {
+        self.event.get_tags()
+    }
+}
+
+impl EstimatedJsonEncodedSizeOf for SmtpEvent {
+    fn estimated_json_encoded_size_of(&self) -> JsonSize {
+        self.event.estimated_json_encoded_size_of()
+    }
+}
@@ -0,0 +1,92 @@
+//! Building the messages sent by the `smtp` sink.
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+
+use crate::sinks::prelude::*;
+
+use super::{service::SmtpRequest, SmtpEvent};
+
+/// The maximum length of the lines of the base64 encoded body, as set by RFC 2045.
+const BODY_LINE_LENGTH: usize = 76;
+
+/// Builds the request sending a batch of events as a single message.
+pub(super) fn request_builder(
+    mut events: Vec<SmtpEvent>,
+    from: &str,
+    to: &[String],
+) -> SmtpRequest {
+    let finalizers = events.take_finalizers();
+    let builder = RequestMetadataBuilder::from_events(&events);
+
+    let mut byte_size = telemetry().create_request_count_byte_size();
+    for event in &events {
+        byte_size.add_event(&event.event, event.event.estimated_json_encoded_size_of());
+    }
+
+    let subject = events
+        .first()
+        .map(|event| event.subject.as_str())
+        .unwrap_or_default();
+    let body = events
+        .iter()
+        .map(|event| event.body.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let message = format_message(from, to, subject, &body, Utc::now());
+
+    let encoded = EncodeResult {
+        uncompressed_byte_size: message.len(),
+        payload: message,
+        transformed_json_size: byte_size,
+        compressed_byte_size: None,
+    };
+    let metadata = builder.build(&encoded);
+
+    SmtpRequest {
+        message: encoded.into_payload(),
+        finalizers,
+        metadata,
+    }
+}
+
+/// Formats a plain text message, with its body base64 encoded so that it's safe to transfer
+/// whatever it holds.
+pub(super) fn format_message(
+    from: &str,
+    to: &[String],
+    subject: &str,
+    body: &str,
+    date: DateTime<Utc>,
+) -> Bytes {
+    let mut message = format!(
+        "From: {from}\r\n\
+         To: {}\r\n\
+         Subject: {}\r\n\
+         Date: {}\r\n\
+         MIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=utf-8\r\n\
+         Content-Transfer-Encoding: base64\r\n\
+         \r\n",
+        to.join(", "),
+        encode_header(subject),
+        date.to_rfc2822(),
+    );
+    let body = BASE64_STANDARD.encode(body);
+    for line in body.as_bytes().chunks(BODY_LINE_LENGTH) {
+        message.push_str(std::str::from_utf8(line).expect("base64 is ASCII"));
+        message.push_str("\r\n");
+    }
+    message.into()
+}
+
+/// Encodes a header value as an RFC 2047 encoded word unless it's printable ASCII, which also
+/// prevents rendered values from injecting line breaks into the header.
+fn encode_header(value: &str) -> String {
+    if value.chars().all(|c| c.is_ascii() && !c.is_ascii_control()) {
+        value.to_owned()
+    } else {
+        format!("=?utf-8?b?{}?=", BASE64_STANDARD.encode(value))
+    }
+}
@@ -0,0 +1,124 @@
+//! Service implementation for the `smtp` sink.
+
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+
+use crate::sinks::prelude::*;
+
+use super::{client::SmtpClient, SmtpError};
+
+#[derive(Clone)]
+pub(super) struct SmtpRetryLogic;
+
+impl RetryLogic for SmtpRetryLogic {
+    type Error = SmtpError;
+    type Response = SmtpResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            // Transient negative completion replies have 4xx codes, permanent ones 5xx codes.
+            SmtpError::Rejected { code, .. } => (400..500).contains(code),
+            SmtpError::InvalidReply { .. }
+            | SmtpError::TlsConfigure { .. }
+            | SmtpError::TlsSettings { .. }
+            | SmtpError::Handshake { .. } => false,
+            SmtpError::Dns { .. }
+            | SmtpError::NoAddresses
+            | SmtpError::Connect { .. }
+            | SmtpError::Io { .. }
+            | SmtpError::ConnectionClosed => true,
+        }
+    }
+}
+
+pub(super) struct SmtpRequest {
+    pub(super) message: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for SmtpRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for SmtpRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct SmtpResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for SmtpResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        self.metadata.events_estimated_json_encoded_byte_size()
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+/// Sends each message over a new connection, as alerts are expected to be sent far apart.
+#[derive(Clone)]
+pub(super) struct SmtpService {
+    client: Arc<SmtpClient>,
+    from: Arc<str>,
+    to: Arc<[String]>,
+}
+
+impl SmtpService {
+    pub(super) fn new(client: Arc<SmtpClient>, from: String, to: Vec<String>) -> Self {
+        Self {
+            client,
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+}
+
+impl Service<SmtpRequest> for SmtpService {
+    type Response = SmtpResponse;
+    type Error = SmtpError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: SmtpRequest) -> Self::Future {
+        let client = Arc::clone(&self.client);
+        let from = Arc::clone(&self.from);
+        let to = Arc::clone(&self.to);
+
+        Box::pin(async move {
+            let mut connection = client.connect().await?;
+            connection.send(&from, &to, &request.message).await?;
+            // The message was accepted, so failing to end the session doesn't fail the request.
+            if let Err(error) = connection.quit().await {
+                debug!(message = "Failed to end the SMTP session.", %error);
+            }
+
+            Ok(SmtpResponse {
+                metadata: request.metadata,
+            })
+        })
+    }
+}
@@ -0,0 +1,74 @@
+//! Implementation of the `smtp` sink.
+
+use std::future;
+
+use crate::sinks::prelude::*;
+
+use super::{
+    request_builder::request_builder,
+    service::{SmtpRetryLogic, SmtpService},
+    SmtpEvent,
+};
+
+pub(super) struct SmtpSink {
+    pub(super) service: Svc<SmtpService, SmtpRetryLogic>,
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) subject: Template,
+    pub(super) body: Template,
+    pub(super) from: String,
+    pub(super) to: Vec<String>,
+}
+
+/// Renders the subject and body of an event.
+/// Returns `None` if there is an error whilst rendering. An error event is also emitted.
+fn make_smtp_event(subject: &Template, body: &Template, event: Event) -> Option<SmtpEvent> {
+    let render = |template: &Template, field| {
+        template
+            .render_string(&event)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    };
+    let subject = render(subject, "subject")?;
+    let body = render(body, "body")?;
+
+    Some(SmtpEvent {
+        event,
+        subject,
+        body,
+    })
+}
+
+impl SmtpSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let Self {
+            service,
+            batch_settings,
+            subject,
+            body,
+            from,
+            to,
+        } = *self;
+
+        input
+            .filter_map(|event| future::ready(make_smtp_event(&subject, &body, event)))
+            .batched(batch_settings.as_byte_size_config())
+            .map(|events| request_builder(events, &from, &to))
+            .into_driver(service)
+            .protocol("smtp")
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for SmtpSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
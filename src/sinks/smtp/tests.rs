@@ -0,0 +1,165 @@
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use chrono::{TimeZone, Utc};
+use futures::stream;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufStream},
+    net::TcpListener,
+    sync::mpsc,
+};
+use vector_lib::event::{Event, LogEvent};
+
+use super::{client::dot_stuff, config::SmtpSinkConfig, request_builder::format_message};
+use crate::{
+    sinks::prelude::*,
+    test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        next_addr,
+    },
+};
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<SmtpSinkConfig>();
+}
+
+#[test]
+fn smtp_format_message() {
+    let date = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+    let message = format_message(
+        "vector@example.com",
+        &["a@example.com".to_owned(), "b@example.com".to_owned()],
+        "disk full",
+        "first\n\nsecond",
+        date,
+    );
+    let message = std::str::from_utf8(&message).unwrap();
+
+    let (headers, body) = message.split_once("\r\n\r\n").unwrap();
+    assert_eq!(
+        headers,
+        "From: vector@example.com\r\n\
+         To: a@example.com, b@example.com\r\n\
+         Subject: disk full\r\n\
+         Date: Tue, 2 Jan 2024 03:04:05 +0000\r\n\
+         MIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=utf-8\r\n\
+         Content-Transfer-Encoding: base64"
+    );
+    let body = BASE64_STANDARD.decode(body.replace("\r\n", "")).unwrap();
+    assert_eq!(body, b"first\n\nsecond");
+}
+
+#[test]
+fn smtp_format_message_wraps_body() {
+    let body = "x".repeat(200);
+    let message = format_message("a@example.com", &[], "", &body, Utc::now());
+    let message = std::str::from_utf8(&message).unwrap();
+
+    let (_, encoded) = message.split_once("\r\n\r\n").unwrap();
+    assert!(encoded.ends_with("\r\n"));
+    assert!(encoded.split("\r\n").all(|line| line.len() <= 76));
+}
+
+#[test]
+fn smtp_format_message_encodes_subject() {
+    for subject in ["Disque plein à 95%", "injected\r\nBcc: evil@example.com"] {
+        let message = format_message("a@example.com", &[], subject, "", Utc::now());
+        let message = std::str::from_utf8(&message).unwrap();
+
+        let expected = format!(
+            "Subject: =?utf-8?b?{}?=\r\n",
+            BASE64_STANDARD.encode(subject)
+        );
+        assert!(message.contains(&expected), "{message}");
+    }
+}
+
+#[test]
+fn smtp_dot_stuff() {
+    assert_eq!(
+        dot_stuff(b"a\r\n.\r\n..b\r\nc.\r\n"),
+        b"a\r\n..\r\n...b\r\nc.\r\n"
+    );
+    assert_eq!(dot_stuff(b".a\r\n"), b"..a\r\n");
+}
+
+/// Runs a server accepting every message, sending their data on the returned channel.
+fn spawn_smtp_server(listener: TcpListener) -> mpsc::UnboundedReceiver<String> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = BufStream::new(stream);
+            stream.write_all(b"220 ready\r\n").await.unwrap();
+            stream.flush().await.unwrap();
+
+            let mut data = None::<String>;
+            loop {
+                let mut line = String::new();
+                if stream.read_line(&mut line).await.unwrap() == 0 {
+                    break;
+                }
+                let reply: &[u8] = match data.as_mut() {
+                    Some(message) if line == ".\r\n" => {
+                        tx.send(std::mem::take(message)).unwrap();
+                        data = None;
+                        b"250 queued\r\n"
+                    }
+                    Some(message) => {
+                        message.push_str(&line);
+                        continue;
+                    }
+                    None if line.starts_with("EHLO") => b"250-localhost\r\n250 8BITMIME\r\n",
+                    None if line.starts_with("DATA") => {
+                        data = Some(String::new());
+                        b"354 go ahead\r\n"
+                    }
+                    None if line.starts_with("QUIT") => b"221 bye\r\n",
+                    None => b"250 ok\r\n",
+                };
+                stream.write_all(reply).await.unwrap();
+                stream.flush().await.unwrap();
+            }
+        }
+    });
+    rx
+}
+
+#[tokio::test]
+async fn smtp_sends_digest() {
+    let addr = next_addr();
+    let listener = TcpListener::bind(addr).await.unwrap();
+    let mut messages = spawn_smtp_server(listener);
+
+    let config = format!(
+        r#"
+        host = "{}"
+        port = {}
+        security = "none"
+        from = "vector@example.com"
+        to = ["oncall@example.com"]
+        subject = "{{{{ message }}}}"
+        body = "{{{{ host }}}}: {{{{ message }}}}"
+        batch.max_events = 2
+        "#,
+        addr.ip(),
+        addr.port()
+    );
+    let config: SmtpSinkConfig = toml::from_str(&config).unwrap();
+    let (sink, healthcheck) = config.build(SinkContext::default()).await.unwrap();
+    healthcheck.await.unwrap();
+
+    let events = ["first", "second"].map(|message| {
+        let mut log = LogEvent::from(message);
+        log.insert("host", "web-1");
+        Event::Log(log)
+    });
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+
+    let message = messages.recv().await.unwrap();
+    let (headers, body) = message.split_once("\r\n\r\n").unwrap();
+    assert!(headers.contains("Subject: first\r\n"), "{headers}");
+    let body = BASE64_STANDARD.decode(body.replace("\r\n", "")).unwrap();
+    assert_eq!(body, b"web-1: first\n\nweb-1: second");
+    assert!(messages.try_recv().is_err());
+}
@@ -0,0 +1,136 @@
+//! Key-pair authentication.
+//!
+//! Requests to the account are authorized with JWTs signed with the private key of the user,
+//! which are exchanged for tokens scoped to the ingest host of the account.
+
+use std::{
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use base64::prelude::{Engine as _, BASE64_STANDARD, BASE64_URL_SAFE_NO_PAD};
+use openssl::{
+    hash::{hash, MessageDigest},
+    pkey::{PKey, Private},
+    sign::Signer,
+};
+use serde_json::json;
+use snafu::{ResultExt, Snafu};
+
+/// How long the JWTs are valid for, which Snowflake limits to an hour.
+const JWT_VALIDITY: Duration = Duration::from_secs(59 * 60);
+
+#[derive(Debug, Snafu)]
+pub(super) enum KeyPairError {
+    #[snafu(display("Unable to read the private key file {:?}: {}", path, source))]
+    ReadKey {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Invalid private key: {}", source))]
+    InvalidKey { source: openssl::error::ErrorStack },
+
+    #[snafu(display("Unable to sign the JWT: {}", source))]
+    Sign { source: openssl::error::ErrorStack },
+}
+
+/// The private key of a user, with which JWTs authorizing the user are signed.
+pub(super) struct KeyPair {
+    /// The qualified name of the user, as `<ACCOUNT>.<USER>`.
+    subject: String,
+    key: PKey<Private>,
+    /// The SHA-256 fingerprint of the public key, as registered with the user.
+    fingerprint: String,
+}
+
+impl KeyPair {
+    /// Loads a PEM encoded private key, decrypting it with the passphrase if it is encrypted.
+    pub(super) fn load(
+        account: &str,
+        user: &str,
+        path: &Path,
+        passphrase: Option<&str>,
+    ) -> Result<Self, KeyPairError> {
+        let pem = std::fs::read(path).context(ReadKeySnafu { path })?;
+        let key = match passphrase {
+            Some(passphrase) => PKey::private_key_from_pem_passphrase(&pem, passphrase.as_bytes()),
+            None => PKey::private_key_from_pem(&pem),
+        }
+        .context(InvalidKeySnafu)?;
+        Self::new(account, user, key)
+    }
+
+    fn new(account: &str, user: &str, key: PKey<Private>) -> Result<Self, KeyPairError> {
+        let public_key = key.public_key_to_der().context(InvalidKeySnafu)?;
+        let digest = hash(MessageDigest::sha256(), &public_key).context(InvalidKeySnafu)?;
+
+        Ok(Self {
+            subject: format!("{}.{}", account_name(account), user.to_uppercase()),
+            key,
+            fingerprint: format!("SHA256:{}", BASE64_STANDARD.encode(digest)),
+        })
+    }
+
+    /// Generates a JWT authorizing the user, issued at `now`.
+    pub(super) fn jwt(&self, now: SystemTime) -> Result<String, KeyPairError> {
+        let issued_at = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let header = json!({ "alg": "RS256", "typ": "JWT" });
+        let claims = json!({
+            "iss": format!("{}.{}", self.subject, self.fingerprint),
+            "sub": self.subject,
+            "iat": issued_at.as_secs(),
+            "exp": (issued_at + JWT_VALIDITY).as_secs(),
+        });
+        let message = format!(
+            "{}.{}",
+            BASE64_URL_SAFE_NO_PAD.encode(header.to_string()),
+            BASE64_URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+
+        let sign = || {
+            let mut signer = Signer::new(MessageDigest::sha256(), &self.key)?;
+            signer.update(message.as_bytes())?;
+            signer.sign_to_vec()
+        };
+        let signature = sign().context(SignSnafu)?;
+        Ok(format!(
+            "{message}.{}",
+            BASE64_URL_SAFE_NO_PAD.encode(signature)
+        ))
+    }
+}
+
+/// The account name used in JWTs, which is uppercase and excludes the region and cloud of
+/// account locators.
+fn account_name(account: &str) -> String {
+    account.split('.').next().unwrap_or(account).to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::rsa::Rsa;
+
+    use super::*;
+
+    #[test]
+    fn signs_jwts_for_the_user() {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let key_pair = KeyPair::new("xy12345.us-east-2.aws", "vector", key).unwrap();
+
+        let jwt = key_pair
+            .jwt(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+            .unwrap();
+        let parts = jwt.split('.').collect::<Vec<_>>();
+        assert_eq!(parts.len(), 3);
+
+        let claims: serde_json::Value =
+            serde_json::from_slice(&BASE64_URL_SAFE_NO_PAD.decode(parts[1]).unwrap()).unwrap();
+        assert_eq!(claims["sub"], "XY12345.VECTOR");
+        assert!(claims["iss"]
+            .as_str()
+            .unwrap()
+            .starts_with("XY12345.VECTOR.SHA256:"));
+        assert_eq!(claims["exp"], 1_700_000_000 + 59 * 60);
+    }
+}
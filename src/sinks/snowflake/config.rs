@@ -0,0 +1,226 @@
+//! Configuration for the `snowflake` sink.
+
+use std::path::PathBuf;
+
+use indoc::indoc;
+use vector_lib::{
+    codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig},
+    sensitive_string::SensitiveString,
+};
+
+use super::{
+    auth::KeyPair,
+    request_builder::SnowflakeRequestBuilder,
+    service::{SnowflakeRetryLogic, SnowflakeService},
+    sink::{KeyPartitioner, SnowflakeSink},
+};
+use crate::{
+    http::HttpClient,
+    sinks::{prelude::*, util::RealtimeSizeBasedDefaultBatchSettings},
+};
+
+/// The largest request accepted by the Snowpipe Streaming API.
+const MAX_REQUEST_SIZE: usize = 16 * 1024 * 1024;
+
+/// Configuration for the `snowflake` sink.
+#[configurable_component(sink(
+    "snowflake",
+    "Write rows to Snowflake tables with Snowpipe Streaming."
+))]
+#[derive(Clone, Debug)]
+pub struct SnowflakeConfig {
+    /// The identifier of the Snowflake account.
+    #[configurable(metadata(docs::examples = "myorg-myaccount"))]
+    #[configurable(metadata(docs::examples = "xy12345.us-east-2.aws"))]
+    pub account: String,
+
+    /// The URL of the account.
+    ///
+    /// Defaults to `https://<account>.snowflakecomputing.com`.
+    #[configurable(metadata(
+        docs::examples = "https://myorg-myaccount.privatelink.snowflakecomputing.com"
+    ))]
+    pub endpoint: Option<String>,
+
+    /// The user to authenticate as.
+    #[configurable(metadata(docs::examples = "VECTOR"))]
+    pub user: String,
+
+    /// The path of the private key of the user, in PEM encoded PKCS#8 format.
+    ///
+    /// The public key of the pair must be [assigned to the user][key_pair_auth].
+    ///
+    /// [key_pair_auth]: https://docs.snowflake.com/en/user-guide/key-pair-auth
+    #[configurable(metadata(docs::examples = "/etc/vector/snowflake_key.p8"))]
+    pub private_key_path: PathBuf,
+
+    /// The passphrase the private key is encrypted with.
+    #[configurable(metadata(docs::examples = "${SNOWFLAKE_PRIVATE_KEY_PASSPHRASE}"))]
+    pub private_key_passphrase: Option<SensitiveString>,
+
+    /// The database of the table to write rows to.
+    #[configurable(metadata(docs::examples = "ANALYTICS"))]
+    #[configurable(metadata(docs::examples = "{{ database }}"))]
+    pub database: Template,
+
+    /// The schema of the table to write rows to.
+    #[configurable(metadata(docs::examples = "PUBLIC"))]
+    #[configurable(metadata(docs::examples = "{{ schema }}"))]
+    pub schema: Template,
+
+    /// The table to write rows to.
+    ///
+    /// Rows are written through the default pipe of the table, named `<table>-STREAMING`, which
+    /// maps the fields of each event to the columns of the same names.
+    #[configurable(metadata(docs::examples = "LOGS"))]
+    #[configurable(metadata(docs::examples = "{{ table }}"))]
+    pub table: Template,
+
+    /// The pipe to write rows through, rather than through the default pipe of the table.
+    ///
+    /// The pipe must be created with the `DATA_SOURCE(TYPE => 'STREAMING')` clause.
+    #[configurable(metadata(docs::examples = "LOGS_PIPE"))]
+    pub pipe: Option<Template>,
+
+    /// The name of the channel the rows are appended to, in each pipe.
+    ///
+    /// Channels order the rows appended to them, so each Vector instance writing to the same
+    /// pipe must use a channel of its own. Defaults to `vector-<hostname>`.
+    #[configurable(metadata(docs::examples = "vector-aggregator-0"))]
+    pub channel_name: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for SnowflakeConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            account = "myorg-myaccount"
+            user = "VECTOR"
+            private_key_path = "/etc/vector/snowflake_key.p8"
+            database = "ANALYTICS"
+            schema = "PUBLIC"
+            table = "LOGS"
+        "#})
+        .unwrap()
+    }
+}
+
+impl SnowflakeConfig {
+    fn account_url(&self) -> String {
+        match &self.endpoint {
+            Some(endpoint) => endpoint.trim_end_matches('/').to_owned(),
+            None => format!("https://{}.snowflakecomputing.com", self.account),
+        }
+    }
+
+    fn channel_name(&self) -> crate::Result<String> {
+        match &self.channel_name {
+            Some(channel_name) => Ok(channel_name.clone()),
+            None => Ok(format!("vector-{}", crate::get_hostname()?)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "snowflake")]
+impl SinkConfig for SnowflakeConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let key_pair = KeyPair::load(
+            &self.account,
+            &self.user,
+            &self.private_key_path,
+            self.private_key_passphrase
+                .as_ref()
+                .map(SensitiveString::inner),
+        )?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+        let service =
+            SnowflakeService::new(client, self.account_url(), self.channel_name()?, key_pair);
+        let healthcheck = service.clone().healthcheck().boxed();
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), SnowflakeRetryLogic)
+            .service(service);
+
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_REQUEST_SIZE)?
+            .into_batcher_settings()?;
+
+        let request_builder = SnowflakeRequestBuilder {
+            encoding: (
+                self.encoding.clone(),
+                Encoder::<Framer>::new(
+                    NewlineDelimitedEncoderConfig.build().into(),
+                    JsonSerializerConfig::default().build().into(),
+                ),
+            ),
+        };
+
+        let partitioner = KeyPartitioner::new(
+            self.database.clone(),
+            self.schema.clone(),
+            self.table.clone(),
+            self.pipe.clone(),
+        );
+        let sink = SnowflakeSink::new(batch_settings, service, partitioner, request_builder);
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SnowflakeConfig>();
+    }
+
+    #[test]
+    fn defaults_to_the_account_url() {
+        let mut config: SnowflakeConfig =
+            toml::Value::try_into(SnowflakeConfig::generate_config()).unwrap();
+        assert_eq!(
+            config.account_url(),
+            "https://myorg-myaccount.snowflakecomputing.com"
+        );
+
+        config.endpoint = Some("http://localhost:8080/".to_owned());
+        assert_eq!(config.account_url(), "http://localhost:8080");
+    }
+}
@@ -0,0 +1,14 @@
+//! The Snowflake [`vector_lib::sink::VectorSink`].
+//!
+//! This module contains the [`vector_lib::sink::VectorSink`] instance that is responsible for
+//! writing rows to Snowflake tables with the [Snowpipe Streaming][snowpipe_streaming] REST API.
+//!
+//! [snowpipe_streaming]: https://docs.snowflake.com/en/user-guide/snowpipe-streaming/snowpipe-streaming-high-performance-overview
+
+mod auth;
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::SnowflakeConfig;
@@ -0,0 +1,54 @@
+//! `RequestBuilder` implementation for the `snowflake` sink.
+
+use bytes::Bytes;
+use vector_lib::codecs::encoding::Framer;
+
+use super::{service::SnowflakeRequest, sink::PartitionKey};
+use crate::sinks::prelude::*;
+
+pub(super) struct SnowflakeRequestBuilder {
+    pub(super) encoding: (Transformer, Encoder<Framer>),
+}
+
+impl RequestBuilder<(PartitionKey, Vec<Event>)> for SnowflakeRequestBuilder {
+    type Metadata = (PartitionKey, EventFinalizers);
+    type Events = Vec<Event>;
+    type Encoder = (Transformer, Encoder<Framer>);
+    type Payload = Bytes;
+    type Request = SnowflakeRequest;
+    type Error = std::io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoding
+    }
+
+    fn split_input(
+        &self,
+        input: (PartitionKey, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (key, mut events) = input;
+
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        ((key, finalizers), builder, events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (key, finalizers) = metadata;
+        SnowflakeRequest {
+            key,
+            payload: payload.into_payload(),
+            finalizers,
+            metadata: request_metadata,
+        }
+    }
+}
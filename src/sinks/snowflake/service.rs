@@ -0,0 +1,438 @@
+//! Service implementation for the `snowflake` sink.
+//!
+//! Rows are appended to channels of the pipes of the tables with the Snowpipe Streaming REST
+//! API. Each channel is opened once, then appended to with the continuation token returned by
+//! its previous append, which orders the appends of the channel.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant, SystemTime},
+};
+
+use bytes::Bytes;
+use http::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Method, Request, StatusCode,
+};
+use hyper::Body;
+use serde::{de::DeserializeOwned, Deserialize};
+use snafu::{ResultExt, Snafu};
+
+use super::{
+    auth::{KeyPair, KeyPairError},
+    sink::PartitionKey,
+};
+use crate::{
+    http::{HttpClient, HttpError},
+    sinks::prelude::*,
+};
+
+const TOKEN_TYPE_HEADER: &str = "X-Snowflake-Authorization-Token-Type";
+
+/// How long scoped tokens are used for before they're exchanged again.
+///
+/// Snowflake doesn't document their lifetime, which is an hour at the time of writing.
+const TOKEN_REFRESH_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Debug, Snafu)]
+pub(super) enum SnowflakeError {
+    #[snafu(display("{}", source))]
+    Authentication { source: KeyPairError },
+
+    #[snafu(display("Failed to build the request: {}", source))]
+    BuildRequest { source: http::Error },
+
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: HttpError },
+
+    #[snafu(display("Failed to read the response: {}", source))]
+    ReadResponse { source: hyper::Error },
+
+    #[snafu(display("{} failed with status {}: {}", operation, status, body))]
+    Status {
+        operation: &'static str,
+        status: StatusCode,
+        body: String,
+    },
+
+    #[snafu(display("Invalid response to {}: {}", operation, source))]
+    InvalidResponse {
+        operation: &'static str,
+        source: serde_json::Error,
+    },
+}
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct SnowflakeRetryLogic;
+
+impl RetryLogic for SnowflakeRetryLogic {
+    type Error = SnowflakeError;
+    type Response = SnowflakeResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            SnowflakeError::Request { source } => source.is_retriable(),
+            SnowflakeError::ReadResponse { .. } => true,
+            // Unauthorized requests are retried with a new token, and conflicting ones with a
+            // reopened channel.
+            SnowflakeError::Status { status, .. } => {
+                matches!(
+                    *status,
+                    StatusCode::UNAUTHORIZED
+                        | StatusCode::REQUEST_TIMEOUT
+                        | StatusCode::CONFLICT
+                        | StatusCode::TOO_MANY_REQUESTS
+                ) || status.is_server_error()
+            }
+            SnowflakeError::Authentication { .. }
+            | SnowflakeError::BuildRequest { .. }
+            | SnowflakeError::InvalidResponse { .. } => false,
+        }
+    }
+}
+
+pub(super) struct SnowflakeRequest {
+    pub(super) key: PartitionKey,
+    pub(super) payload: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for SnowflakeRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for SnowflakeRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct SnowflakeResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for SnowflakeResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+/// A token authorizing requests to the ingest host of the account.
+struct Session {
+    host: String,
+    token: String,
+    created_at: Instant,
+}
+
+/// The position of an open channel.
+#[derive(Debug, Default)]
+struct Channel {
+    continuation_token: String,
+    offset: u64,
+}
+
+impl Channel {
+    /// Resumes a channel from its status as it was opened, counting offsets from the last one
+    /// committed by Vector.
+    fn resume(opened: OpenChannelResponse) -> Self {
+        let offset = opened
+            .channel_status
+            .and_then(|status| status.last_committed_offset_token)
+            .and_then(|token| token.parse().ok())
+            .unwrap_or_default();
+        Self {
+            continuation_token: opened.next_continuation_token,
+            offset,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct OpenChannelResponse {
+    next_continuation_token: String,
+    channel_status: Option<ChannelStatus>,
+}
+
+#[derive(Deserialize)]
+struct ChannelStatus {
+    last_committed_offset_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AppendRowsResponse {
+    next_continuation_token: String,
+}
+
+/// The channels of the pipes written to, each locked while it's appended to.
+type Channels = HashMap<PartitionKey, Arc<tokio::sync::Mutex<Option<Channel>>>>;
+
+#[derive(Clone)]
+pub(super) struct SnowflakeService {
+    client: HttpClient,
+    account_url: String,
+    channel_name: String,
+    key_pair: Arc<KeyPair>,
+    session: Arc<tokio::sync::Mutex<Option<Session>>>,
+    channels: Arc<Mutex<Channels>>,
+}
+
+impl SnowflakeService {
+    pub(super) fn new(
+        client: HttpClient,
+        account_url: String,
+        channel_name: String,
+        key_pair: KeyPair,
+    ) -> Self {
+        Self {
+            client,
+            account_url,
+            channel_name,
+            key_pair: Arc::new(key_pair),
+            session: Arc::default(),
+            channels: Arc::default(),
+        }
+    }
+
+    /// Checks that the user is able to authenticate to the ingest host of the account.
+    pub(super) async fn healthcheck(self) -> crate::Result<()> {
+        self.session().await?;
+        Ok(())
+    }
+
+    /// Returns the ingest host and a token scoped to it, exchanging a new one if needed.
+    async fn session(&self) -> Result<(String, String), SnowflakeError> {
+        let mut session = self.session.lock().await;
+        if let Some(session) = session
+            .as_ref()
+            .filter(|session| session.created_at.elapsed() < TOKEN_REFRESH_INTERVAL)
+        {
+            return Ok((session.host.clone(), session.token.clone()));
+        }
+
+        let jwt = self
+            .key_pair
+            .jwt(SystemTime::now())
+            .context(AuthenticationSnafu)?;
+        let request = Request::get(format!("{}/v2/streaming/hostname", self.account_url))
+            .header(AUTHORIZATION, format!("Bearer {jwt}"))
+            .header(TOKEN_TYPE_HEADER, "KEYPAIR_JWT")
+            .body(Body::empty())
+            .context(BuildRequestSnafu)?;
+        let host = self.send(request, "getting the ingest host").await?;
+        let host = String::from_utf8_lossy(&host).trim().to_owned();
+
+        let form = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer")
+            .append_pair("scope", &host)
+            .append_pair("assertion", &jwt)
+            .finish();
+        let request = Request::post(format!("{}/oauth/token", self.account_url))
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(form))
+            .context(BuildRequestSnafu)?;
+        let token = self.send(request, "exchanging the scoped token").await?;
+        let token = String::from_utf8_lossy(&token).trim().to_owned();
+
+        *session = Some(Session {
+            host: host.clone(),
+            token: token.clone(),
+            created_at: Instant::now(),
+        });
+        Ok((host, token))
+    }
+
+    /// Sends a request, returning the body of successful responses.
+    async fn send(
+        &self,
+        request: Request<Body>,
+        operation: &'static str,
+    ) -> Result<Bytes, SnowflakeError> {
+        let response = self.client.send(request).await.context(RequestSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadResponseSnafu)?;
+
+        if status.is_success() {
+            Ok(body)
+        } else {
+            if status == StatusCode::UNAUTHORIZED {
+                *self.session.lock().await = None;
+            }
+            Err(SnowflakeError::Status {
+                operation,
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            })
+        }
+    }
+
+    /// Sends a request authorized with the scoped token to the ingest host.
+    async fn send_json<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: String,
+        content_type: &str,
+        body: Bytes,
+        operation: &'static str,
+    ) -> Result<T, SnowflakeError> {
+        let (host, token) = self.session().await?;
+        let request = Request::builder()
+            .method(method)
+            .uri(format!("https://{host}{path}"))
+            .header(AUTHORIZATION, format!("Bearer {token}"))
+            .header(TOKEN_TYPE_HEADER, "OAuth")
+            .header(CONTENT_TYPE, content_type)
+            .body(Body::from(body))
+            .context(BuildRequestSnafu)?;
+
+        let body = self.send(request, operation).await?;
+        serde_json::from_slice(&body).context(InvalidResponseSnafu { operation })
+    }
+
+    async fn open_channel(&self, key: &PartitionKey) -> Result<Channel, SnowflakeError> {
+        let opened = self
+            .send_json::<OpenChannelResponse>(
+                Method::PUT,
+                format!(
+                    "/v2/streaming/{}/channels/{}",
+                    key.pipe_path(),
+                    encode(&self.channel_name)
+                ),
+                "application/json",
+                Bytes::from_static(b"{}"),
+                "opening the channel",
+            )
+            .await?;
+        Ok(Channel::resume(opened))
+    }
+
+    async fn append(&self, request: &SnowflakeRequest) -> Result<(), SnowflakeError> {
+        let channel = Arc::clone(
+            self.channels
+                .lock()
+                .expect("channels lock poisoned")
+                .entry(request.key.clone())
+                .or_default(),
+        );
+        let mut channel = channel.lock().await;
+
+        let open = match channel.take() {
+            Some(open) => open,
+            None => self.open_channel(&request.key).await?,
+        };
+        let offset = open.offset + 1;
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("continuationToken", &open.continuation_token)
+            .append_pair("offsetToken", &offset.to_string())
+            .finish();
+
+        // The channel is only put back once the append succeeds, so that a failed append
+        // reopens it, resuming from the last committed offset.
+        let appended = self
+            .send_json::<AppendRowsResponse>(
+                Method::POST,
+                format!(
+                    "/v2/streaming/data/{}/channels/{}/rows?{query}",
+                    request.key.pipe_path(),
+                    encode(&self.channel_name)
+                ),
+                "application/x-ndjson",
+                request.payload.clone(),
+                "appending rows",
+            )
+            .await?;
+        *channel = Some(Channel {
+            continuation_token: appended.next_continuation_token,
+            offset,
+        });
+        Ok(())
+    }
+}
+
+impl Service<SnowflakeRequest> for SnowflakeService {
+    type Response = SnowflakeResponse;
+    type Error = SnowflakeError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: SnowflakeRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            service.append(&request).await?;
+
+            Ok(SnowflakeResponse {
+                byte_size: request.metadata.request_encoded_size(),
+                events_byte_size: request
+                    .metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+            })
+        })
+    }
+}
+
+/// Encodes a name as a segment of a path.
+pub(super) fn encode(name: &str) -> String {
+    percent_encoding::utf8_percent_encode(name, percent_encoding::NON_ALPHANUMERIC).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resumes_channels_from_the_last_committed_offset() {
+        let opened: OpenChannelResponse = serde_json::from_str(
+            r#"{
+                "next_continuation_token": "token",
+                "channel_status": { "last_committed_offset_token": "42" }
+            }"#,
+        )
+        .unwrap();
+        let channel = Channel::resume(opened);
+        assert_eq!(channel.continuation_token, "token");
+        assert_eq!(channel.offset, 42);
+
+        let opened: OpenChannelResponse =
+            serde_json::from_str(r#"{ "next_continuation_token": "token" }"#).unwrap();
+        assert_eq!(Channel::resume(opened).offset, 0);
+    }
+
+    #[test]
+    fn retries_unauthorized_and_throttled_requests() {
+        let status = |status| SnowflakeError::Status {
+            operation: "appending rows",
+            status,
+            body: String::new(),
+        };
+
+        let logic = SnowflakeRetryLogic;
+        assert!(logic.is_retriable_error(&status(StatusCode::UNAUTHORIZED)));
+        assert!(logic.is_retriable_error(&status(StatusCode::TOO_MANY_REQUESTS)));
+        assert!(logic.is_retriable_error(&status(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(!logic.is_retriable_error(&status(StatusCode::BAD_REQUEST)));
+        assert!(!logic.is_retriable_error(&status(StatusCode::NOT_FOUND)));
+    }
+}
@@ -0,0 +1,180 @@
+//! Implementation of the `snowflake` sink.
+
+use super::{
+    request_builder::SnowflakeRequestBuilder,
+    service::{encode, SnowflakeRequest},
+};
+use crate::sinks::prelude::*;
+
+pub(super) struct SnowflakeSink<S> {
+    batch_settings: BatcherSettings,
+    service: S,
+    partitioner: KeyPartitioner,
+    request_builder: SnowflakeRequestBuilder,
+}
+
+impl<S> SnowflakeSink<S>
+where
+    S: Service<SnowflakeRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    pub(super) const fn new(
+        batch_settings: BatcherSettings,
+        service: S,
+        partitioner: KeyPartitioner,
+        request_builder: SnowflakeRequestBuilder,
+    ) -> Self {
+        Self {
+            batch_settings,
+            service,
+            partitioner,
+            request_builder,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+
+        input
+            .batched_partitioned(self.partitioner, || batch_settings.as_byte_size_config())
+            .filter_map(|(key, batch)| async move { key.map(move |k| (k, batch)) })
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .protocol("https")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for SnowflakeSink<S>
+where
+    S: Service<SnowflakeRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+/// The pipe events are written through, which has a channel of its own.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub(super) struct PartitionKey {
+    pub database: String,
+    pub schema: String,
+    pub pipe: String,
+}
+
+impl PartitionKey {
+    /// The path of the pipe in the Snowpipe Streaming API.
+    pub(super) fn pipe_path(&self) -> String {
+        format!(
+            "databases/{}/schemas/{}/pipes/{}",
+            encode(&self.database),
+            encode(&self.schema),
+            encode(&self.pipe)
+        )
+    }
+}
+
+/// KeyPartitioner that partitions events by the pipe of their table.
+pub(super) struct KeyPartitioner {
+    database: Template,
+    schema: Template,
+    table: Template,
+    pipe: Option<Template>,
+}
+
+impl KeyPartitioner {
+    pub(super) const fn new(
+        database: Template,
+        schema: Template,
+        table: Template,
+        pipe: Option<Template>,
+    ) -> Self {
+        Self {
+            database,
+            schema,
+            table,
+            pipe,
+        }
+    }
+
+    fn render(template: &Template, item: &Event, field: &'static str) -> Option<String> {
+        template
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+impl Partitioner for KeyPartitioner {
+    type Item = Event;
+    type Key = Option<PartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let database = Self::render(&self.database, item, "database")?;
+        let schema = Self::render(&self.schema, item, "schema")?;
+        let pipe = match &self.pipe {
+            Some(pipe) => Self::render(pipe, item, "pipe")?,
+            // Every table has a default pipe, which maps the fields of the rows to its columns.
+            None => format!("{}-STREAMING", Self::render(&self.table, item, "table")?),
+        };
+        Some(PartitionKey {
+            database,
+            schema,
+            pipe,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::event::LogEvent;
+
+    use super::*;
+
+    #[test]
+    fn partitions_events_by_pipe() {
+        let partitioner = KeyPartitioner::new(
+            Template::try_from("ANALYTICS").unwrap(),
+            Template::try_from("PUBLIC").unwrap(),
+            Template::try_from("{{ table }}").unwrap(),
+            None,
+        );
+        let mut log = LogEvent::from("message");
+        log.insert("table", "AUDIT LOGS");
+
+        let key = partitioner.partition(&Event::Log(log)).unwrap();
+        assert_eq!(key.pipe, "AUDIT LOGS-STREAMING");
+        assert_eq!(
+            key.pipe_path(),
+            "databases/ANALYTICS/schemas/PUBLIC/pipes/AUDIT%20LOGS%2DSTREAMING"
+        );
+
+        let missing = Event::Log(LogEvent::from("message"));
+        assert_eq!(partitioner.partition(&missing), None);
+    }
+}
@@ -0,0 +1,126 @@
+//! Distributes requests across several HEC endpoints, keeping track of the endpoint each request
+//! was sent to so that its acknowledgement is polled from the same indexer.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+
+use super::service::ResponseExt;
+use crate::{
+    http::HttpError,
+    sinks::util::{
+        http::HttpRetryLogic,
+        retries::{RetryAction, RetryLogic},
+        service::HealthLogic,
+        sink::Response,
+    },
+};
+
+/// The response of one of the distributed endpoints.
+#[derive(Debug)]
+pub struct HecEndpointResponse {
+    /// The index of the endpoint that sent the response.
+    pub endpoint: usize,
+    pub response: http::Response<Bytes>,
+}
+
+impl Response for HecEndpointResponse {
+    fn is_successful(&self) -> bool {
+        self.response.is_successful()
+    }
+
+    fn is_transient(&self) -> bool {
+        self.response.is_transient()
+    }
+}
+
+impl ResponseExt for HecEndpointResponse {
+    fn body(&self) -> &Bytes {
+        self.response.body()
+    }
+
+    fn endpoint(&self) -> usize {
+        self.endpoint
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct HecRetryLogic;
+
+impl RetryLogic for HecRetryLogic {
+    type Error = HttpError;
+    type Response = HecEndpointResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        HttpRetryLogic.is_retriable_error(error)
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        HttpRetryLogic.should_retry_response(&response.response)
+    }
+
+    fn retry_after(&self, response: &Self::Response) -> Option<Duration> {
+        HttpRetryLogic.retry_after(&response.response)
+    }
+}
+
+/// Considers an endpoint unhealthy when it can't be reached or answers with a server error, such
+/// as the `503` sent by indexers whose queues are full.
+#[derive(Clone)]
+pub struct HecHealthLogic;
+
+impl HealthLogic for HecHealthLogic {
+    type Error = crate::Error;
+    type Response = HecEndpointResponse;
+
+    fn is_healthy(&self, response: &Result<Self::Response, Self::Error>) -> Option<bool> {
+        match response {
+            Ok(response) => {
+                let status = response.response.status();
+                if status.is_success() {
+                    Some(true)
+                } else if status.is_server_error() {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+            Err(error) => match error.downcast_ref::<HttpError>() {
+                Some(HttpError::CallRequest { .. }) => Some(false),
+                _ => None,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::*;
+
+    fn response(status: StatusCode) -> HecEndpointResponse {
+        HecEndpointResponse {
+            endpoint: 1,
+            response: http::Response::builder()
+                .status(status)
+                .body(Bytes::new())
+                .unwrap(),
+        }
+    }
+
+    #[test]
+    fn server_errors_make_endpoints_unhealthy() {
+        let logic = HecHealthLogic;
+        assert_eq!(logic.is_healthy(&Ok(response(StatusCode::OK))), Some(true));
+        assert_eq!(
+            logic.is_healthy(&Ok(response(StatusCode::SERVICE_UNAVAILABLE))),
+            Some(false)
+        );
+        assert_eq!(
+            logic.is_healthy(&Ok(response(StatusCode::BAD_REQUEST))),
+            None
+        );
+        assert_eq!(logic.is_healthy(&Err("invalid request".into())), None);
+    }
+}
@@ -30,9 +30,12 @@ use crate::{
     },
 };
 
+type AckFinalizerSender = mpsc::Sender<(u64, oneshot::Sender<EventStatus>)>;
+
 pub struct HecService<S> {
     pub inner: S,
-    ack_finalizer_tx: Option<mpsc::Sender<(u64, oneshot::Sender<EventStatus>)>>,
+    /// The senders of the ack IDs to poll, by endpoint.
+    ack_finalizer_txs: Option<Arc<[AckFinalizerSender]>>,
     ack_slots: PollSemaphore,
     current_ack_slot: Option<OwnedSemaphorePermit>,
 }
@@ -55,25 +58,44 @@ where
         ack_client: Option<HttpClient>,
         http_request_builder: Arc<HttpRequestBuilder>,
         indexer_acknowledgements: HecClientAcknowledgementsConfig,
+    ) -> Self {
+        Self::new_distributed(
+            inner,
+            ack_client,
+            vec![http_request_builder],
+            indexer_acknowledgements,
+        )
+    }
+
+    /// Builds a service whose inner service distributes requests across the endpoints of
+    /// `http_request_builders`, polling the acknowledgements of each endpoint on its own channel.
+    pub fn new_distributed(
+        inner: S,
+        ack_client: Option<HttpClient>,
+        http_request_builders: Vec<Arc<HttpRequestBuilder>>,
+        indexer_acknowledgements: HecClientAcknowledgementsConfig,
     ) -> Self {
         let max_pending_acks = indexer_acknowledgements.max_pending_acks.get();
-        let tx = if let Some(ack_client) = ack_client {
-            let (tx, rx) = mpsc::channel(128);
-            tokio::spawn(run_acknowledgements(
-                rx,
-                ack_client,
-                Arc::clone(&http_request_builder),
-                indexer_acknowledgements,
-            ));
-            Some(tx)
-        } else {
-            None
-        };
+        let txs = ack_client.map(|ack_client| {
+            http_request_builders
+                .into_iter()
+                .map(|http_request_builder| {
+                    let (tx, rx) = mpsc::channel(128);
+                    tokio::spawn(run_acknowledgements(
+                        rx,
+                        ack_client.clone(),
+                        http_request_builder,
+                        indexer_acknowledgements.clone(),
+                    ));
+                    tx
+                })
+                .collect()
+        });
 
         let ack_slots = PollSemaphore::new(Arc::new(Semaphore::new(max_pending_acks as usize)));
         Self {
             inner,
-            ack_finalizer_tx: tx,
+            ack_finalizer_txs: txs,
             ack_slots,
             current_ack_slot: None,
         }
@@ -94,7 +116,7 @@ where
     fn poll_ready(&mut self, cx: &mut Context) -> std::task::Poll<Result<(), Self::Error>> {
         // Ready if indexer acknowledgements is disabled or there is room for
         // additional pending acks. Otherwise, wait until there is room.
-        if self.ack_finalizer_tx.is_none() || self.current_ack_slot.is_some() {
+        if self.ack_finalizer_txs.is_none() || self.current_ack_slot.is_some() {
             self.inner.poll_ready(cx).map_err(Into::into)
         } else {
             match ready!(self.ack_slots.poll_acquire(cx)) {
@@ -110,7 +132,7 @@ where
     }
 
     fn call(&mut self, mut req: HecRequest) -> Self::Future {
-        let ack_finalizer_tx = self.ack_finalizer_tx.clone();
+        let ack_finalizer_txs = self.ack_finalizer_txs.clone();
         let ack_slot = self.current_ack_slot.take();
 
         let metadata = std::mem::take(req.metadata_mut());
@@ -121,6 +143,8 @@ where
         Box::pin(async move {
             let response = response.await.map_err(Into::into)?;
             let event_status = if response.is_successful() {
+                let ack_finalizer_tx =
+                    ack_finalizer_txs.and_then(|txs| txs.get(response.endpoint()).cloned());
                 if let Some(ack_finalizer_tx) = ack_finalizer_tx {
                     let _ack_slot = ack_slot.expect("poll_ready not called before invoking call");
                     let body = serde_json::from_slice::<HecAckResponseBody>(response.body());
@@ -170,6 +194,12 @@ where
 
 pub trait ResponseExt {
     fn body(&self) -> &Bytes;
+
+    /// The index of the endpoint that sent the response, when requests are distributed across
+    /// several endpoints.
+    fn endpoint(&self) -> usize {
+        0
+    }
 }
 
 impl ResponseExt for http::Response<Bytes> {
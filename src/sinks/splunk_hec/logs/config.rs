@@ -1,5 +1,7 @@
-use std::sync::Arc;
+use std::{fmt, sync::Arc};
 
+use futures::TryFutureExt;
+use tower::ServiceExt;
 use vector_lib::{
     codecs::TextSerializerConfig,
     lookup::lookup_v2::{ConfigValuePath, OptionalTargetPath},
@@ -13,10 +15,12 @@ use crate::{
         splunk_hec::common::{
             acknowledgements::HecClientAcknowledgementsConfig,
             build_healthcheck, build_http_batch_service, create_client,
-            service::{HecService, HttpRequestBuilder},
+            distribution::{HecEndpointResponse, HecHealthLogic, HecRetryLogic},
+            request::HecRequest,
+            service::{HecService, HttpRequestBuilder, ResponseExt},
             EndpointTarget, SplunkHecDefaultBatchSettings,
         },
-        util::http::HttpRetryLogic,
+        util::{http::HttpRetryLogic, service::HealthConfig, sink::Response},
     },
 };
 
@@ -50,6 +54,24 @@ pub struct HecLogsSinkConfig {
     #[configurable(validation(format = "uri"))]
     pub endpoint: String,
 
+    /// The base URLs of additional Splunk instances to distribute events across, along with
+    /// `endpoint`.
+    ///
+    /// Requests are balanced across the healthy endpoints, and an endpoint that can't be reached or
+    /// answers with server errors is set aside until it recovers. When indexer acknowledgements are
+    /// enabled, they are polled from the endpoint each request was sent to.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::examples = "https://hec-2.splunk.com:8088",
+        docs::examples = "https://hec-3.splunk.com:8088"
+    ))]
+    pub endpoints: Vec<String>,
+
+    #[serde(default)]
+    #[configurable(derived)]
+    #[serde(rename = "distribution")]
+    pub endpoint_health: Option<HealthConfig>,
+
     /// Overrides the name of the log field used to retrieve the hostname to send to Splunk HEC.
     ///
     /// By default, the [global `log_schema.host_key` option][global_host_key] is used if log
@@ -163,6 +185,8 @@ impl GenerateConfig for HecLogsSinkConfig {
         toml::Value::try_from(Self {
             default_token: "${VECTOR_SPLUNK_HEC_TOKEN}".to_owned().into(),
             endpoint: "endpoint".to_owned(),
+            endpoints: Vec::new(),
+            endpoint_health: None,
             host_key: None,
             indexed_fields: vec![],
             index: None,
@@ -192,12 +216,22 @@ impl SinkConfig for HecLogsSinkConfig {
         }
 
         let client = create_client(&self.tls, cx.proxy())?;
-        let healthcheck = build_healthcheck(
-            self.endpoint.clone(),
-            self.default_token.inner().to_owned(),
-            client.clone(),
-        )
-        .boxed();
+        let healthcheck = if self.endpoints.is_empty() {
+            build_healthcheck(
+                self.endpoint.clone(),
+                self.default_token.inner().to_owned(),
+                client.clone(),
+            )
+            .boxed()
+        } else {
+            // Events are delivered as long as one of the endpoints is healthy.
+            let token = self.default_token.inner().to_owned();
+            futures::future::select_ok(self.all_endpoints().map(|endpoint| {
+                build_healthcheck(endpoint.clone(), token.clone(), client.clone()).boxed()
+            }))
+            .map_ok(|((), _)| ())
+            .boxed()
+        };
         let sink = self.build_processor(client, cx)?;
 
         Ok((sink, healthcheck))
@@ -234,28 +268,89 @@ impl HecLogsSinkConfig {
         };
 
         let request_settings = self.request.into_settings();
-        let http_request_builder = Arc::new(HttpRequestBuilder::new(
-            self.endpoint.clone(),
+        if self.endpoints.is_empty() {
+            let http_request_builder = Arc::new(self.build_http_request_builder(&self.endpoint));
+            let http_service = ServiceBuilder::new()
+                .settings(request_settings, HttpRetryLogic)
+                .service(build_http_batch_service(
+                    client,
+                    Arc::clone(&http_request_builder),
+                    self.endpoint_target,
+                    self.auto_extract_timestamp.unwrap_or_default(),
+                ));
+
+            let service = HecService::new(
+                http_service,
+                ack_client,
+                http_request_builder,
+                self.acknowledgements.clone(),
+            );
+            self.build_sink(service, request_builder)
+        } else {
+            let http_request_builders = self
+                .all_endpoints()
+                .map(|endpoint| Arc::new(self.build_http_request_builder(endpoint)))
+                .collect::<Vec<_>>();
+            let services = http_request_builders
+                .iter()
+                .enumerate()
+                .map(|(index, http_request_builder)| {
+                    let service = build_http_batch_service(
+                        client.clone(),
+                        Arc::clone(http_request_builder),
+                        self.endpoint_target,
+                        self.auto_extract_timestamp.unwrap_or_default(),
+                    )
+                    .map_response(move |response| HecEndpointResponse {
+                        endpoint: index,
+                        response,
+                    });
+                    (http_request_builder.endpoint.clone(), service)
+                })
+                .collect::<Vec<_>>();
+            let http_service = request_settings.distributed_service::<HecRequest, _, _, _>(
+                HecRetryLogic,
+                services,
+                self.endpoint_health.clone().unwrap_or_default(),
+                HecHealthLogic,
+                1,
+            );
+
+            let service = HecService::new_distributed(
+                http_service,
+                ack_client,
+                http_request_builders,
+                self.acknowledgements.clone(),
+            );
+            self.build_sink(service, request_builder)
+        }
+    }
+
+    /// The endpoints to distribute events across, starting with `endpoint`.
+    fn all_endpoints(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.endpoint).chain(&self.endpoints)
+    }
+
+    fn build_http_request_builder(&self, endpoint: &str) -> HttpRequestBuilder {
+        HttpRequestBuilder::new(
+            endpoint.to_owned(),
             self.endpoint_target,
             self.default_token.inner().to_owned(),
             self.compression,
-        ));
-        let http_service = ServiceBuilder::new()
-            .settings(request_settings, HttpRetryLogic)
-            .service(build_http_batch_service(
-                client,
-                Arc::clone(&http_request_builder),
-                self.endpoint_target,
-                self.auto_extract_timestamp.unwrap_or_default(),
-            ));
-
-        let service = HecService::new(
-            http_service,
-            ack_client,
-            http_request_builder,
-            self.acknowledgements.clone(),
-        );
+        )
+    }
 
+    fn build_sink<S>(
+        &self,
+        service: HecService<S>,
+        request_builder: HecLogsRequestBuilder,
+    ) -> crate::Result<VectorSink>
+    where
+        S: Service<HecRequest> + Send + 'static,
+        S::Future: Send + 'static,
+        S::Response: Response + ResponseExt + Send + 'static,
+        S::Error: fmt::Debug + Into<crate::Error> + Send,
+    {
         let batch_settings = self.batch.into_batcher_settings()?;
 
         let sink = HecLogsSink {
@@ -304,6 +399,8 @@ mod tests {
 
             let config = Self {
                 endpoint: endpoint.clone(),
+                endpoints: Vec::new(),
+                endpoint_health: None,
                 default_token: "i_am_an_island".to_string().into(),
                 host_key: None,
                 indexed_fields: vec![],
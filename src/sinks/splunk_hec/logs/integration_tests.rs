@@ -118,6 +118,8 @@ async fn config(
     HecLogsSinkConfig {
         default_token: get_token().await.into(),
         endpoint: splunk_hec_address(),
+        endpoints: Vec::new(),
+        endpoint_health: None,
         host_key: Some(OptionalTargetPath::event("host")),
         indexed_fields,
         index: None,
@@ -26,7 +26,7 @@ use crate::{
             common::EndpointTarget,
             logs::{config::HecLogsSinkConfig, encoder::HecLogsEncoder, sink::process_log},
         },
-        util::{encoding::Encoder as _, test::build_test_server, Compression},
+        util::{encoding::Encoder as _, test::build_test_server, BatchConfig, Compression},
     },
     template::Template,
     test_util::next_addr,
@@ -222,6 +222,8 @@ async fn splunk_passthrough_token() {
     let config = HecLogsSinkConfig {
         default_token: "token".to_string().into(),
         endpoint: format!("http://{}", addr),
+        endpoints: Vec::new(),
+        endpoint_health: None,
         host_key: None,
         indexed_fields: Vec::new(),
         index: None,
@@ -270,6 +272,54 @@ async fn splunk_passthrough_token() {
     )
 }
 
+#[tokio::test]
+async fn splunk_fails_over_to_healthy_endpoints() {
+    // Nothing listens on the first endpoint.
+    let unreachable = next_addr();
+    let addr = next_addr();
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(1);
+    let config = HecLogsSinkConfig {
+        default_token: "token".to_string().into(),
+        endpoint: format!("http://{}", unreachable),
+        endpoints: vec![format!("http://{}", addr)],
+        endpoint_health: None,
+        host_key: None,
+        indexed_fields: Vec::new(),
+        index: None,
+        sourcetype: None,
+        source: None,
+        encoding: JsonSerializerConfig::default().into(),
+        compression: Compression::None,
+        batch,
+        request: Default::default(),
+        tls: None,
+        acknowledgements: Default::default(),
+        timestamp_nanos_key: None,
+        timestamp_key: None,
+        auto_extract_timestamp: None,
+        endpoint_target: EndpointTarget::Event,
+    };
+    let cx = SinkContext::default();
+
+    let (sink, _) = config.build(cx).await.unwrap();
+
+    let (rx, _trigger, server) = build_test_server(addr);
+    tokio::spawn(server);
+
+    let events = (0..3)
+        .map(|i| Event::Log(LogEvent::from(format!("message-{i}"))))
+        .collect::<Vec<_>>();
+
+    sink.run_events(events).await.unwrap();
+
+    let requests = rx.take(3).collect::<Vec<_>>().await;
+    assert_eq!(requests.len(), 3);
+    assert!(requests
+        .iter()
+        .all(|(parts, _)| parts.uri.path() == "/services/collector/event"));
+}
+
 #[test]
 fn splunk_encode_log_event_json_timestamps() {
     crate::test_util::trace_init();
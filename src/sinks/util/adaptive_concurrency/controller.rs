@@ -6,7 +6,7 @@ use std::{
 
 use tokio::sync::OwnedSemaphorePermit;
 use tower::timeout::error::Elapsed;
-use vector_lib::internal_event::{InternalEventHandle as _, Registered};
+use vector_lib::internal_event::{InternalEventHandle as _, Registered, SharedString};
 
 use super::{instant_now, semaphore::ShrinkableSemaphore, AdaptiveConcurrencySettings};
 #[cfg(test)]
@@ -64,6 +64,7 @@ impl<L> Controller<L> {
         concurrency: Option<usize>,
         settings: AdaptiveConcurrencySettings,
         logic: L,
+        endpoint: Option<SharedString>,
     ) -> Self {
         // If a `concurrency` is specified, it becomes both the
         // current limit and the maximum, effectively bypassing all the
@@ -86,10 +87,16 @@ impl<L> Controller<L> {
             })),
             #[cfg(test)]
             stats: Arc::new(Mutex::new(ControllerStatistics::default())),
-            limit: register!(AdaptiveConcurrencyLimit),
-            in_flight: register!(AdaptiveConcurrencyInFlight),
-            observed_rtt: register!(AdaptiveConcurrencyObservedRtt),
-            averaged_rtt: register!(AdaptiveConcurrencyAveragedRtt),
+            limit: register!(AdaptiveConcurrencyLimit {
+                endpoint: endpoint.clone()
+            }),
+            in_flight: register!(AdaptiveConcurrencyInFlight {
+                endpoint: endpoint.clone()
+            }),
+            observed_rtt: register!(AdaptiveConcurrencyObservedRtt {
+                endpoint: endpoint.clone()
+            }),
+            averaged_rtt: register!(AdaptiveConcurrencyAveragedRtt { endpoint }),
         }
     }
 
@@ -1,4 +1,5 @@
 use tower::Layer;
+use vector_lib::internal_event::SharedString;
 
 use super::{AdaptiveConcurrencyLimit, AdaptiveConcurrencySettings};
 use crate::sinks::util::retries::RetryLogic;
@@ -10,6 +11,7 @@ pub struct AdaptiveConcurrencyLimitLayer<L> {
     concurrency: Option<usize>,
     options: AdaptiveConcurrencySettings,
     logic: L,
+    endpoint: Option<SharedString>,
 }
 
 impl<L> AdaptiveConcurrencyLimitLayer<L> {
@@ -23,14 +25,30 @@ impl<L> AdaptiveConcurrencyLimitLayer<L> {
             concurrency,
             options,
             logic,
+            endpoint: None,
         }
     }
+
+    /// Tags the metrics of the limit with the endpoint the service sends requests to.
+    ///
+    /// Sinks balancing requests between several endpoints limit the concurrency of each endpoint
+    /// independently, which this distinguishes the metrics of.
+    pub fn with_endpoint(mut self, endpoint: impl Into<SharedString>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
 }
 
 impl<S, L: RetryLogic> Layer<S> for AdaptiveConcurrencyLimitLayer<L> {
     type Service = AdaptiveConcurrencyLimit<S, L>;
 
     fn layer(&self, service: S) -> Self::Service {
-        AdaptiveConcurrencyLimit::new(service, self.logic.clone(), self.concurrency, self.options)
+        AdaptiveConcurrencyLimit::new(
+            service,
+            self.logic.clone(),
+            self.concurrency,
+            self.options,
+            self.endpoint.clone(),
+        )
     }
 }
@@ -9,6 +9,7 @@ use std::{
 use futures::future::BoxFuture;
 use tokio::sync::OwnedSemaphorePermit;
 use tower::{load::Load, Service};
+use vector_lib::internal_event::SharedString;
 
 use super::{controller::Controller, future::ResponseFuture, AdaptiveConcurrencySettings};
 use crate::sinks::util::retries::RetryLogic;
@@ -35,10 +36,11 @@ impl<S, L> AdaptiveConcurrencyLimit<S, L> {
         logic: L,
         concurrency: Option<usize>,
         options: AdaptiveConcurrencySettings,
+        endpoint: Option<SharedString>,
     ) -> Self {
         AdaptiveConcurrencyLimit {
             inner,
-            controller: Arc::new(Controller::new(concurrency, options, logic)),
+            controller: Arc::new(Controller::new(concurrency, options, logic, endpoint)),
             state: State::Empty,
         }
     }
@@ -7,7 +7,11 @@ use vector_lib::codecs::encoding::Framer;
 use vector_lib::request_metadata::GroupedCountByteSize;
 use vector_lib::{config::telemetry, EstimatedJsonEncodedSizeOf};
 
-use crate::{codecs::Transformer, event::Event, internal_events::EncoderWriteError};
+use crate::{
+    codecs::{BatchEncoder, EncoderKind, Transformer},
+    event::Event,
+    internal_events::EncoderWriteError,
+};
 
 pub trait Encoder<T> {
     /// Encodes the input into the provided writer.
@@ -92,6 +96,47 @@ impl Encoder<Event> for (Transformer, crate::codecs::Encoder<()>) {
     }
 }
 
+impl Encoder<Vec<Event>> for (Transformer, BatchEncoder) {
+    fn encode_input(
+        &self,
+        mut events: Vec<Event>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut encoder = self.1.clone();
+        let n_events_pending = events.len();
+
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        for event in events.iter_mut() {
+            self.0.transform(event);
+            byte_size.add_event(event, event.estimated_json_encoded_size_of());
+        }
+
+        let mut bytes = BytesMut::new();
+        encoder
+            .encode(events, &mut bytes)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        write_all(writer, n_events_pending, &bytes)?;
+        Ok((bytes.len(), byte_size))
+    }
+}
+
+impl Encoder<Vec<Event>> for (Transformer, EncoderKind) {
+    fn encode_input(
+        &self,
+        events: Vec<Event>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        match &self.1 {
+            EncoderKind::Framed(encoder) => {
+                (self.0.clone(), encoder.as_ref().clone()).encode_input(events, writer)
+            }
+            EncoderKind::Batch(encoder) => {
+                (self.0.clone(), encoder.clone()).encode_input(events, writer)
+            }
+        }
+    }
+}
+
 /// Write the buffer to the writer. If the operation fails, emit an internal event which complies with the
 /// instrumentation spec- as this necessitates both an Error and EventsDropped event.
 ///
@@ -147,8 +192,8 @@ mod tests {
     use std::collections::BTreeMap;
 
     use vector_lib::codecs::{
-        CharacterDelimitedEncoder, JsonSerializerConfig, NewlineDelimitedEncoder,
-        TextSerializerConfig,
+        BatchSerializerConfig, CharacterDelimitedEncoder, JsonSerializerConfig,
+        NewlineDelimitedEncoder, TextSerializerConfig,
     };
     use vector_lib::event::LogEvent;
     use vector_lib::{internal_event::CountByteSize, json_size::JsonSize};
@@ -376,4 +421,34 @@ mod tests {
         assert_eq!(String::from_utf8(writer).unwrap(), r#"value"#);
         assert_eq!(CountByteSize(1, input_json_size), json_size.size().unwrap());
     }
+
+    #[test]
+    fn test_encode_batch_parquet() {
+        let serializer = BatchSerializerConfig::Parquet {
+            parquet: Default::default(),
+        }
+        .build()
+        .unwrap();
+        let encoding = (
+            Transformer::default(),
+            EncoderKind::from(BatchEncoder::new(serializer)),
+        );
+
+        let mut writer = Vec::new();
+        let input = vec![
+            Event::Log(LogEvent::from("foo")),
+            Event::Log(LogEvent::from("bar")),
+        ];
+        let input_json_size = input
+            .iter()
+            .map(|event| event.estimated_json_encoded_size_of())
+            .sum::<JsonSize>();
+
+        let (written, json_size) = encoding.encode_input(input, &mut writer).unwrap();
+        assert_eq!(written, writer.len());
+
+        assert!(writer.starts_with(b"PAR1"));
+        assert!(writer.ends_with(b"PAR1"));
+        assert_eq!(CountByteSize(2, input_json_size), json_size.size().unwrap());
+    }
 }
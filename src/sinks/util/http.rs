@@ -12,7 +12,7 @@ use std::{
 use bytes::{Buf, Bytes};
 use futures::{future::BoxFuture, Sink};
 use headers::HeaderName;
-use http::{header, HeaderValue, Request, Response, StatusCode};
+use http::{header, HeaderMap, HeaderValue, Request, Response, StatusCode};
 use hyper::{body, Body};
 use indexmap::IndexMap;
 use pin_project::pin_project;
@@ -481,6 +481,52 @@ impl RetryLogic for HttpRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn retry_after(&self, response: &Self::Response) -> Option<Duration> {
+        retry_after(response.headers())
+    }
+}
+
+/// The header rate limited APIs commonly send, instead of `Retry-After`, with the time their
+/// limit resets at.
+const RATELIMIT_RESET: &str = "x-ratelimit-reset";
+
+/// Values of `x-ratelimit-reset` beyond this are Unix timestamps, rather than delays in seconds.
+const RATELIMIT_RESET_MIN_TIMESTAMP: u64 = 1_000_000_000;
+
+/// Returns how long a response asks to wait before retrying the request, from its `Retry-After`
+/// or `x-ratelimit-reset` header.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    retry_after_at(headers, chrono::Utc::now())
+}
+
+fn retry_after_at(headers: &HeaderMap, now: chrono::DateTime<chrono::Utc>) -> Option<Duration> {
+    let until = |timestamp: chrono::DateTime<chrono::Utc>| {
+        (timestamp - now).to_std().unwrap_or(Duration::ZERO)
+    };
+
+    if let Some(value) = headers
+        .get(header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+    {
+        let value = value.trim();
+        // The header is either a delay in seconds, or an HTTP date.
+        return value.parse().map(Duration::from_secs).ok().or_else(|| {
+            chrono::DateTime::parse_from_rfc2822(value)
+                .ok()
+                .map(|date| until(date.with_timezone(&chrono::Utc)))
+        });
+    }
+
+    let reset: u64 = headers
+        .get(RATELIMIT_RESET)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())?;
+    if reset < RATELIMIT_RESET_MIN_TIMESTAMP {
+        Some(Duration::from_secs(reset))
+    } else {
+        chrono::DateTime::from_timestamp(reset as i64, 0).map(until)
+    }
 }
 
 /// A more generic version of `HttpRetryLogic` that accepts anything that can be converted
@@ -690,12 +736,39 @@ impl DriverResponse for HttpResponse {
     }
 }
 
+/// `RetryLogic` for `HttpResponse`, which retries based on the status of responses, after the
+/// delay they ask for.
+#[derive(Clone)]
+pub struct HttpResponseRetryLogic<F> {
+    inner: HttpStatusRetryLogic<F, HttpResponse>,
+}
+
+impl<F> RetryLogic for HttpResponseRetryLogic<F>
+where
+    F: Fn(&HttpResponse) -> StatusCode + Clone + Send + Sync + 'static,
+{
+    type Error = HttpError;
+    type Response = HttpResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        self.inner.is_retriable_error(error)
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        self.inner.should_retry_response(response)
+    }
+
+    fn retry_after(&self, response: &Self::Response) -> Option<Duration> {
+        retry_after(response.http_response.headers())
+    }
+}
+
 /// Creates a `RetryLogic` for use with `HttpResponse`.
-pub fn http_response_retry_logic() -> HttpStatusRetryLogic<
-    impl Fn(&HttpResponse) -> StatusCode + Clone + Send + Sync + 'static,
-    HttpResponse,
-> {
-    HttpStatusRetryLogic::new(|req: &HttpResponse| req.http_response.status())
+pub fn http_response_retry_logic(
+) -> HttpResponseRetryLogic<impl Fn(&HttpResponse) -> StatusCode + Clone + Send + Sync + 'static> {
+    HttpResponseRetryLogic {
+        inner: HttpStatusRetryLogic::new(|req: &HttpResponse| req.http_response.status()),
+    }
 }
 
 /// Uses the estimated json encoded size to determine batch sizing.
@@ -808,6 +881,47 @@ mod test {
             .is_not_retryable());
     }
 
+    #[test]
+    fn util_http_retry_after() {
+        let now = chrono::DateTime::parse_from_rfc3339("2024-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let headers = |name: &'static str, value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(name, HeaderValue::from_static(value));
+            headers
+        };
+
+        assert_eq!(
+            retry_after_at(&headers("retry-after", "120"), now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            retry_after_at(
+                &headers("retry-after", "Mon, 21 Oct 2024 07:28:30 GMT"),
+                now
+            ),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            retry_after_at(
+                &headers("retry-after", "Mon, 21 Oct 2024 07:27:00 GMT"),
+                now
+            ),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            retry_after_at(&headers("x-ratelimit-reset", "15"), now),
+            Some(Duration::from_secs(15))
+        );
+        assert_eq!(
+            retry_after_at(&headers("x-ratelimit-reset", "1729495740"), now),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(retry_after_at(&headers("retry-after", "soon"), now), None);
+        assert_eq!(retry_after_at(&HeaderMap::new(), now), None);
+    }
+
     #[tokio::test]
     async fn util_http_it_makes_http_requests() {
         let addr = next_addr();
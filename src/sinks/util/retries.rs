@@ -3,13 +3,17 @@ use std::{
     cmp,
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
 
 use futures::FutureExt;
 use tokio::time::{sleep, Sleep};
-use tower::{retry::Policy, timeout::error::Elapsed};
+use tower::{
+    retry::{budget::Budget, Policy},
+    timeout::error::Elapsed,
+};
 use vector_lib::configurable::configurable_component;
 
 use crate::Error;
@@ -41,6 +45,66 @@ pub trait RetryLogic: Clone + Send + Sync + 'static {
         // Treat the default as the request is successful
         RetryAction::Successful
     }
+
+    /// When a response is retried, this function allows implementors to specify how long the
+    /// service asked to wait before retrying, such as with a `Retry-After` header. The request is
+    /// retried once both this delay and the backoff have elapsed.
+    fn retry_after(&self, _response: &Self::Response) -> Option<Duration> {
+        None
+    }
+}
+
+/// The window over which the retry budget tracks requests.
+const RETRY_BUDGET_TTL: Duration = Duration::from_secs(10);
+
+/// A budget limiting the fraction of requests that are retries.
+///
+/// Each request deposits `ratio` retries into the budget, and each retry withdraws one, so that
+/// retries don't multiply the load on a service that is already failing. Requests which would be
+/// retried beyond the budget are dropped instead.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RetryBudget {
+    /// The number of retries allowed per request, over the last 10 seconds.
+    ///
+    /// For example, `0.2` allows one retry for every five requests.
+    #[configurable(validation(range(min = 0.0, max = 1000.0)))]
+    #[serde(default = "default_retry_budget_ratio")]
+    pub ratio: f64,
+
+    /// The number of retries per second allowed regardless of `ratio`, so that sinks sending few
+    /// requests are still able to retry them.
+    #[configurable(metadata(docs::type_unit = "retries"))]
+    #[serde(default = "default_retry_budget_min_retries_per_second")]
+    pub min_retries_per_second: u32,
+}
+
+const fn default_retry_budget_ratio() -> f64 {
+    0.2
+}
+
+const fn default_retry_budget_min_retries_per_second() -> u32 {
+    10
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self {
+            ratio: default_retry_budget_ratio(),
+            min_retries_per_second: default_retry_budget_min_retries_per_second(),
+        }
+    }
+}
+
+impl RetryBudget {
+    pub fn build(&self) -> Arc<Budget> {
+        Arc::new(Budget::new(
+            RETRY_BUDGET_TTL,
+            self.min_retries_per_second,
+            self.ratio as f32,
+        ))
+    }
 }
 
 /// The jitter mode to use for retry backoff behavior.
@@ -70,6 +134,8 @@ pub struct FibonacciRetryPolicy<L> {
     jitter_mode: JitterMode,
     current_jitter_duration: Duration,
     max_duration: Duration,
+    budget: Option<Arc<Budget>>,
+    retried: bool,
     logic: L,
 }
 
@@ -93,10 +159,18 @@ impl<L: RetryLogic> FibonacciRetryPolicy<L> {
             jitter_mode,
             current_jitter_duration: Self::add_full_jitter(initial_backoff),
             max_duration,
+            budget: None,
+            retried: false,
             logic,
         }
     }
 
+    /// Limits the retries of the requests sharing the budget.
+    pub fn with_budget(mut self, budget: Arc<Budget>) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
     fn add_full_jitter(d: Duration) -> Duration {
         let jitter = (rand::random::<u64>() % (d.as_millis() as u64)) + 1;
         Duration::from_millis(jitter)
@@ -115,6 +189,8 @@ impl<L: RetryLogic> FibonacciRetryPolicy<L> {
             current_jitter_duration: Self::add_full_jitter(next_duration),
             jitter_mode: self.jitter_mode,
             max_duration: self.max_duration,
+            budget: self.budget.clone(),
+            retried: true,
             logic: self.logic.clone(),
         }
     }
@@ -126,11 +202,31 @@ impl<L: RetryLogic> FibonacciRetryPolicy<L> {
         }
     }
 
-    fn build_retry(&self) -> RetryPolicyFuture<L> {
+    /// Withdraws a retry from the budget, if any, returning whether the request may be retried.
+    fn withdraw(&self) -> bool {
+        match &self.budget {
+            Some(budget) if budget.withdraw().is_err() => {
+                error!(
+                    message = "Retry budget exhausted; dropping the request.",
+                    internal_log_rate_limit = true,
+                );
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// Delays the retry by the backoff, or by the delay the service asked for if that's longer,
+    /// up to the maximum backoff.
+    fn build_retry(&self, retry_after: Option<Duration>) -> RetryPolicyFuture<L> {
         let policy = self.advance();
-        let delay = Box::pin(sleep(self.backoff()));
+        let backoff = match retry_after {
+            Some(retry_after) => cmp::max(self.backoff(), cmp::min(retry_after, self.max_duration)),
+            None => self.backoff(),
+        };
+        let delay = Box::pin(sleep(backoff));
 
-        debug!(message = "Retrying request.", delay_ms = %self.backoff().as_millis());
+        debug!(message = "Retrying request.", delay_ms = %backoff.as_millis());
         RetryPolicyFuture { delay, policy }
     }
 }
@@ -145,6 +241,11 @@ where
     // NOTE: in the error cases- `Error` and `EventsDropped` internal events are emitted by the
     // driver, so only need to log here.
     fn retry(&self, _: &Req, result: Result<&Res, &Error>) -> Option<Self::Future> {
+        // Only the first attempt of each request deposits into the budget.
+        if let (Some(budget), false) = (&self.budget, self.retried) {
+            budget.deposit();
+        }
+
         match result {
             Ok(response) => match self.logic.should_retry_response(response) {
                 RetryAction::Retry(reason) => {
@@ -157,8 +258,12 @@ where
                         return None;
                     }
 
+                    if !self.withdraw() {
+                        return None;
+                    }
+
                     warn!(message = "Retrying after response.", reason = %reason, internal_log_rate_limit = true);
-                    Some(self.build_retry())
+                    Some(self.build_retry(self.logic.retry_after(response)))
                 }
 
                 RetryAction::DontRetry(reason) => {
@@ -176,8 +281,12 @@ where
 
                 if let Some(expected) = error.downcast_ref::<L::Error>() {
                     if self.logic.is_retriable_error(expected) {
+                        if !self.withdraw() {
+                            return None;
+                        }
+
                         warn!(message = "Retrying after error.", error = %expected, internal_log_rate_limit = true);
-                        Some(self.build_retry())
+                        Some(self.build_retry(None))
                     } else {
                         error!(
                             message = "Non-retriable error; dropping the request.",
@@ -191,7 +300,11 @@ where
                         message = "Request timed out. If this happens often while the events are actually reaching their destination, try decreasing `batch.max_bytes` and/or using `compression` if applicable. Alternatively `request.timeout_secs` can be increased.",
                         internal_log_rate_limit = true
                     );
-                    Some(self.build_retry())
+                    if !self.withdraw() {
+                        return None;
+                    }
+
+                    Some(self.build_retry(None))
                 } else {
                     error!(
                         message = "Unexpected error type; dropping the request.",
@@ -326,7 +439,10 @@ mod tests {
     use tower_test::{assert_request_eq, mock};
 
     use super::*;
-    use crate::test_util::trace_init;
+    use crate::{
+        sinks::util::service::{GlobalTowerRequestConfigDefaults, TowerRequestConfig},
+        test_util::trace_init,
+    };
 
     #[tokio::test]
     async fn service_error_retry() {
@@ -410,6 +526,86 @@ mod tests {
         assert_eq!(fut.await.unwrap(), "world");
     }
 
+    #[tokio::test]
+    async fn retry_budget_exhausted() {
+        trace_init();
+
+        let budget = RetryBudget {
+            ratio: 0.0,
+            min_retries_per_second: 0,
+        };
+        let policy = FibonacciRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+            JitterMode::None,
+        )
+        .with_budget(budget.build());
+
+        let (mut svc, mut handle) = mock::spawn_layer(RetryLayer::new(policy));
+
+        assert_ready_ok!(svc.poll_ready());
+
+        let mut fut = task::spawn(svc.call("hello"));
+        assert_request_eq!(handle, "hello").send_error(Error(true));
+        assert_ready_err!(fut.poll());
+    }
+
+    #[tokio::test]
+    async fn retry_policies_of_settings_share_budget() {
+        let settings = TowerRequestConfig::<GlobalTowerRequestConfigDefaults> {
+            retry_budget: Some(RetryBudget {
+                ratio: 1.0,
+                min_retries_per_second: 0,
+            }),
+            ..Default::default()
+        }
+        .into_settings();
+        let first = settings.retry_policy(SvcRetryLogic);
+        let second = settings.retry_policy(SvcRetryLogic);
+        let error: crate::Error = Box::new(Error(true));
+
+        // Both requests deposit a retry into the budget, so the second one is retried twice.
+        assert!(first.retry(&"hello", Ok(&"world")).is_none());
+        let retry = second.retry(&"hello", Err(&error)).unwrap();
+        let retry = retry.policy.retry(&"hello", Err(&error)).unwrap();
+        assert!(retry.policy.retry(&"hello", Err(&error)).is_none());
+    }
+
+    #[tokio::test]
+    async fn retry_after_response() {
+        trace_init();
+
+        time::pause();
+
+        let policy = FibonacciRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            RetryAfterLogic,
+            JitterMode::None,
+        );
+
+        let (mut svc, mut handle) = mock::spawn_layer(RetryLayer::new(policy));
+
+        assert_ready_ok!(svc.poll_ready());
+
+        let mut fut = task::spawn(svc.call("hello"));
+        assert_request_eq!(handle, "hello").send_response("slow down");
+        assert_pending!(fut.poll());
+
+        // The retry waits for the delay asked for rather than the shorter backoff.
+        time::advance(Duration::from_secs(2)).await;
+        assert_pending!(fut.poll());
+        assert_pending!(task::spawn(handle.next_request()).poll());
+
+        time::advance(Duration::from_secs(4)).await;
+        assert_pending!(fut.poll());
+        assert_request_eq!(handle, "hello").send_response("world");
+        assert_eq!(fut.await.unwrap(), "world");
+    }
+
     #[test]
     fn backoff_grows_to_max() {
         let mut policy = FibonacciRetryPolicy::new(
@@ -498,6 +694,30 @@ mod tests {
         }
     }
 
+    #[derive(Debug, Clone)]
+    struct RetryAfterLogic;
+
+    impl RetryLogic for RetryAfterLogic {
+        type Error = Error;
+        type Response = &'static str;
+
+        fn is_retriable_error(&self, _error: &Self::Error) -> bool {
+            false
+        }
+
+        fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+            if *response == "slow down" {
+                RetryAction::Retry("slow down".into())
+            } else {
+                RetryAction::Successful
+            }
+        }
+
+        fn retry_after(&self, _response: &Self::Response) -> Option<Duration> {
+            Some(Duration::from_secs(5))
+        }
+    }
+
     #[derive(Debug)]
     struct Error(bool);
 
@@ -8,7 +8,7 @@ use tower::{
     discover::Change,
     layer::{util::Stack, Layer},
     limit::RateLimit,
-    retry::Retry,
+    retry::{budget::Budget, Retry},
     timeout::Timeout,
     Service, ServiceBuilder,
 };
@@ -25,7 +25,7 @@ use crate::{
         adaptive_concurrency::{
             AdaptiveConcurrencyLimit, AdaptiveConcurrencyLimitLayer, AdaptiveConcurrencySettings,
         },
-        retries::{FibonacciRetryPolicy, JitterMode, RetryLogic},
+        retries::{FibonacciRetryPolicy, JitterMode, RetryBudget, RetryLogic},
         service::map::MapLayer,
         sink::Response,
         Batch, BatchSink, Partition, PartitionBatchSink,
@@ -158,6 +158,13 @@ pub struct TowerRequestConfig<D: TowerRequestConfigDefaults = GlobalTowerRequest
     #[serde(default)]
     pub retry_jitter_mode: JitterMode,
 
+    /// A budget limiting the fraction of requests that are retries.
+    ///
+    /// By default, retries are only limited by `retry_attempts`.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_budget: Option<RetryBudget>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
@@ -210,6 +217,7 @@ impl<D: TowerRequestConfigDefaults> Default for TowerRequestConfig<D> {
             retry_initial_backoff_secs: default_retry_initial_backoff_secs::<D>(),
             adaptive_concurrency: AdaptiveConcurrencySettings::default(),
             retry_jitter_mode: JitterMode::default(),
+            retry_budget: None,
 
             _d: PhantomData,
         }
@@ -217,7 +225,7 @@ impl<D: TowerRequestConfigDefaults> Default for TowerRequestConfig<D> {
 }
 
 impl<D: TowerRequestConfigDefaults> TowerRequestConfig<D> {
-    pub const fn into_settings(&self) -> TowerRequestSettings {
+    pub fn into_settings(&self) -> TowerRequestSettings {
         // the unwrap() calls below are safe because the final defaults are always Some<>
         TowerRequestSettings {
             concurrency: self.concurrency.parse_concurrency(),
@@ -229,6 +237,7 @@ impl<D: TowerRequestConfigDefaults> TowerRequestConfig<D> {
             retry_initial_backoff: Duration::from_secs(self.retry_initial_backoff_secs.get()),
             adaptive_concurrency: self.adaptive_concurrency,
             retry_jitter_mode: self.retry_jitter_mode,
+            retry_budget: self.retry_budget.as_ref().map(RetryBudget::build),
         }
     }
 }
@@ -244,17 +253,24 @@ pub struct TowerRequestSettings {
     pub retry_initial_backoff: Duration,
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
     pub retry_jitter_mode: JitterMode,
+    /// The budget shared by all the retry policies built from these settings, so that it limits
+    /// the retries of all the requests of the sink.
+    pub retry_budget: Option<Arc<Budget>>,
 }
 
 impl TowerRequestSettings {
     pub fn retry_policy<L: RetryLogic>(&self, logic: L) -> FibonacciRetryPolicy<L> {
-        FibonacciRetryPolicy::new(
+        let policy = FibonacciRetryPolicy::new(
             self.retry_attempts,
             self.retry_initial_backoff,
             self.retry_max_duration,
             logic,
             self.retry_jitter_mode,
-        )
+        );
+        match &self.retry_budget {
+            Some(budget) => policy.with_budget(Arc::clone(budget)),
+            None => policy,
+        }
     }
 
     /// Note: This has been deprecated, please do not use when creating new Sinks.
@@ -335,11 +351,14 @@ impl TowerRequestSettings {
             .map(|(endpoint, inner)| {
                 // Build individual service
                 ServiceBuilder::new()
-                    .layer(AdaptiveConcurrencyLimitLayer::new(
-                        self.concurrency,
-                        self.adaptive_concurrency,
-                        retry_logic.clone(),
-                    ))
+                    .layer(
+                        AdaptiveConcurrencyLimitLayer::new(
+                            self.concurrency,
+                            self.adaptive_concurrency,
+                            retry_logic.clone(),
+                        )
+                        .with_endpoint(endpoint.clone()),
+                    )
                     .service(
                         health_config.build(
                             health_logic.clone(),
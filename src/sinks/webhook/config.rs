@@ -0,0 +1,140 @@
+//! Configuration for the `webhook` sink.
+
+use http::header::AUTHORIZATION;
+
+use crate::{
+    http::{Auth, HttpClient},
+    sinks::{
+        prelude::*,
+        util::http::{http_response_retry_logic, validate_headers, HttpService, RequestConfig},
+    },
+};
+
+use super::{
+    request_builder::{WebhookBody, WebhookRequestBuilder},
+    service::WebhookServiceRequestBuilder,
+    signing::WebhookSigningConfig,
+    sink::WebhookSink,
+};
+
+/// Configuration for the `webhook` sink.
+#[configurable_component(sink("webhook", "Deliver events to webhooks as signed JSON requests."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookSinkConfig {
+    /// The URI the events are delivered to.
+    ///
+    /// This is a template, rendered for each event.
+    #[configurable(metadata(docs::examples = "https://hooks.example.com/services/{{ team }}"))]
+    pub uri: Template,
+
+    /// The template of the bodies of the requests, which must render valid JSON.
+    ///
+    /// When neither `template` nor `mapping` is set, the event itself is sent.
+    #[configurable(metadata(
+        docs::examples = "{\"text\": \"{{ host }}: {{ message }}\", \"channel\": \"#alerts\"}"
+    ))]
+    pub template: Option<Template>,
+
+    /// A VRL program run on each event, which is then sent as the body of the request.
+    ///
+    /// Events for which the program fails are dropped.
+    #[configurable(metadata(docs::examples = ". = {\"text\": .message, \"level\": .level}"))]
+    pub mapping: Option<String>,
+
+    #[configurable(derived)]
+    pub signing: Option<WebhookSigningConfig>,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: RequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for WebhookSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            uri = "https://hooks.example.com/services/vector"
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "webhook")]
+impl SinkConfig for WebhookSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let body = match (&self.template, &self.mapping) {
+            (Some(_), Some(_)) => {
+                return Err("only one of `template` and `mapping` can be provided".into())
+            }
+            (Some(template), None) => WebhookBody::Template(template.clone()),
+            (None, Some(source)) => WebhookBody::mapping(source, cx.globals.timezone())?,
+            (None, None) => WebhookBody::Event,
+        };
+
+        let headers = validate_headers(&self.request.headers)?;
+        if self.auth.is_some() && headers.contains_key(AUTHORIZATION) {
+            return Err("Authorization header can not be used with defined auth options".into());
+        }
+        let signer = self
+            .signing
+            .as_ref()
+            .map(WebhookSigningConfig::build)
+            .transpose()?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let service = HttpService::new(
+            client,
+            WebhookServiceRequestBuilder {
+                auth: self.auth.clone(),
+                headers,
+                signer,
+            },
+        );
+        let service = ServiceBuilder::new()
+            .settings(
+                self.request.tower.into_settings(),
+                http_response_retry_logic(),
+            )
+            .service(service);
+
+        let sink = WebhookSink {
+            service,
+            request_builder: WebhookRequestBuilder {
+                uri: self.uri.clone(),
+                body,
+            },
+        };
+
+        // The URI is rendered from the events, so there is no endpoint to check ahead of them.
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
@@ -0,0 +1,15 @@
+//! `webhook` sink.
+//!
+//! Delivers each event as a JSON request to a URI rendered from it, as expected by the webhooks of
+//! SaaS services. The bodies are rendered with a template or a VRL mapping, and can be signed with
+//! an HMAC of the shared secret.
+mod config;
+mod request_builder;
+mod service;
+mod signing;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::config::WebhookSinkConfig;
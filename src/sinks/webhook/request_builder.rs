@@ -0,0 +1,169 @@
+//! Rendering of the requests of the `webhook` sink.
+
+use bytes::Bytes;
+use http::Uri;
+use vector_lib::{compile_vrl, internal_event::error_type, TimeZone};
+use vrl::{
+    compiler::{
+        runtime::Runtime, CompilationResult, CompileConfig, Program, TargetValue, TypeState,
+    },
+    diagnostic::Formatter,
+};
+
+use crate::{
+    internal_events::WebhookRenderError,
+    sinks::{prelude::*, util::http::HttpRequest},
+};
+
+/// Renders the request delivering each event.
+pub(super) struct WebhookRequestBuilder {
+    pub(super) uri: Template,
+    pub(super) body: WebhookBody,
+}
+
+impl WebhookRequestBuilder {
+    /// Renders the request delivering an event.
+    /// Returns `None` if there is an error whilst rendering. An error event is also emitted.
+    pub(super) fn build(&self, mut event: Event) -> Option<HttpRequest<Uri>> {
+        let log = event.as_log();
+
+        let uri = self
+            .uri
+            .render_string(log)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("uri"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+        let uri = uri
+            .parse::<Uri>()
+            .map_err(|error| {
+                emit!(WebhookRenderError {
+                    part: "uri",
+                    error: format!("{error}: {uri:?}"),
+                    error_type: error_type::TEMPLATE_FAILED,
+                });
+            })
+            .ok()?;
+
+        let payload = self.body.render(log)?;
+
+        let finalizers = event.take_finalizers();
+        let builder = RequestMetadataBuilder::from_event(&event);
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+        let metadata = builder.build(&EncodeResult::uncompressed(&payload, byte_size));
+
+        Some(HttpRequest::new(payload, finalizers, metadata, uri))
+    }
+}
+
+/// How the bodies of the requests are rendered from the events.
+pub(super) enum WebhookBody {
+    /// The event is encoded as JSON.
+    Event,
+
+    /// The rendered template is sent, once checked to be valid JSON.
+    Template(Template),
+
+    /// The program is run on the event, which is then encoded as JSON.
+    Mapping {
+        program: Program,
+        timezone: TimeZone,
+    },
+}
+
+impl WebhookBody {
+    pub(super) fn mapping(source: &str, timezone: TimeZone) -> crate::Result<Self> {
+        let functions = vrl::stdlib::all()
+            .into_iter()
+            .chain(vector_vrl_functions::all())
+            .collect::<Vec<_>>();
+
+        let CompilationResult {
+            program,
+            warnings,
+            config: _,
+        } = compile_vrl(
+            source,
+            &functions,
+            &TypeState::default(),
+            CompileConfig::default(),
+        )
+        .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(source, warnings).colored().to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(Self::Mapping { program, timezone })
+    }
+
+    /// Renders the body of the request for an event.
+    /// Returns `None` if there is an error whilst rendering. An error event is also emitted.
+    pub(super) fn render(&self, event: &LogEvent) -> Option<Bytes> {
+        match self {
+            Self::Event => encode(event.value()),
+            Self::Template(template) => {
+                let body = template
+                    .render(event)
+                    .map_err(|error| {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some("template"),
+                            drop_event: true,
+                        });
+                    })
+                    .ok()?;
+
+                match serde_json::from_slice::<serde::de::IgnoredAny>(&body) {
+                    Ok(_) => Some(body),
+                    Err(error) => {
+                        emit!(WebhookRenderError {
+                            part: "body",
+                            error: error.to_string(),
+                            error_type: error_type::ENCODER_FAILED,
+                        });
+                        None
+                    }
+                }
+            }
+            Self::Mapping { program, timezone } => {
+                let mut target = TargetValue {
+                    value: event.value().clone(),
+                    metadata: event.metadata().value().clone(),
+                    secrets: event.metadata().secrets().clone(),
+                };
+
+                match Runtime::default().resolve(&mut target, program, timezone) {
+                    Ok(_) => encode(&target.value),
+                    Err(error) => {
+                        emit!(WebhookRenderError {
+                            part: "body",
+                            error: error.to_string(),
+                            error_type: error_type::SCRIPT_FAILED,
+                        });
+                        None
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn encode(value: &Value) -> Option<Bytes> {
+    serde_json::to_vec(value)
+        .map(Into::into)
+        .map_err(|error| {
+            emit!(WebhookRenderError {
+                part: "body",
+                error: error.to_string(),
+                error_type: error_type::ENCODER_FAILED,
+            });
+        })
+        .ok()
+}
@@ -0,0 +1,55 @@
+//! Service implementation for the `webhook` sink.
+
+use bytes::Bytes;
+use chrono::Utc;
+use http::{header::CONTENT_TYPE, HeaderName, HeaderValue, Request, Uri};
+use indexmap::IndexMap;
+use snafu::ResultExt;
+
+use crate::{
+    http::Auth,
+    sinks::{
+        util::http::{HttpRequest, HttpServiceRequestBuilder},
+        HTTPRequestBuilderSnafu,
+    },
+};
+
+use super::signing::WebhookSigner;
+
+/// Builds the requests, which carry the URI rendered from their event.
+#[derive(Debug, Clone)]
+pub(super) struct WebhookServiceRequestBuilder {
+    pub(super) auth: Option<Auth>,
+    pub(super) headers: IndexMap<HeaderName, HeaderValue>,
+    pub(super) signer: Option<WebhookSigner>,
+}
+
+impl HttpServiceRequestBuilder<Uri> for WebhookServiceRequestBuilder {
+    fn build(&self, mut request: HttpRequest<Uri>) -> Result<Request<Bytes>, crate::Error> {
+        let mut builder = Request::post(request.get_additional_metadata())
+            .header(CONTENT_TYPE, "application/json");
+
+        let headers = builder
+            .headers_mut()
+            // The request building should not have errors at this point, and if it did it would fail in the call to `body()` also.
+            .expect("Failed to access headers in http::Request builder- builder has errors.");
+        for (header, value) in self.headers.iter() {
+            headers.insert(header, value.clone());
+        }
+
+        let mut http_request = builder
+            .body(request.take_payload())
+            .context(HTTPRequestBuilderSnafu)
+            .map_err(Into::<crate::Error>::into)?;
+
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut http_request);
+        }
+        // Requests are built again when retried, so retries are signed with a fresh timestamp.
+        if let Some(signer) = &self.signer {
+            signer.sign(&mut http_request, Utc::now());
+        }
+
+        Ok(http_request)
+    }
+}
@@ -0,0 +1,144 @@
+//! HMAC signing of the requests of the `webhook` sink.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use http::{HeaderName, HeaderValue, Request};
+use sha2::{Sha256, Sha512};
+use vector_lib::{configurable::configurable_component, sensitive_string::SensitiveString};
+
+/// The hash function the HMAC is computed with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookSigningAlgorithm {
+    /// SHA-256.
+    #[derivative(Default)]
+    Sha256,
+
+    /// SHA-512.
+    Sha512,
+}
+
+impl WebhookSigningAlgorithm {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Sha512 => "sha512",
+        }
+    }
+
+    fn hmac(self, key: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+        fn compute<M: Mac>(mut mac: M, parts: &[&[u8]]) -> Vec<u8> {
+            for part in parts {
+                mac.update(part);
+            }
+            mac.finalize().into_bytes().to_vec()
+        }
+
+        match self {
+            Self::Sha256 => compute(
+                <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts any key length"),
+                parts,
+            ),
+            Self::Sha512 => compute(
+                <Hmac<Sha512> as Mac>::new_from_slice(key).expect("HMAC accepts any key length"),
+                parts,
+            ),
+        }
+    }
+}
+
+fn default_signature_header() -> String {
+    "X-Signature".to_owned()
+}
+
+/// Signing of the requests with an HMAC of their body, which receivers verify with the secret
+/// they share with Vector.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookSigningConfig {
+    /// The secret the HMAC is keyed with.
+    #[configurable(metadata(docs::examples = "${WEBHOOK_SECRET}"))]
+    pub secret: SensitiveString,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub algorithm: WebhookSigningAlgorithm,
+
+    /// The header the signature is sent in.
+    ///
+    /// The signature is the hex encoded HMAC, prefixed with the name of the algorithm, such as
+    /// `sha256=`.
+    #[serde(default = "default_signature_header")]
+    #[configurable(metadata(docs::examples = "X-Hub-Signature-256"))]
+    pub header: String,
+
+    /// The header the time of the request is sent in, as a Unix timestamp.
+    ///
+    /// When set, the HMAC is computed over the timestamp, a `.`, and the body, rather than over
+    /// the body alone, so that receivers can reject replayed requests.
+    #[configurable(metadata(docs::examples = "X-Signature-Timestamp"))]
+    pub timestamp_header: Option<String>,
+}
+
+impl WebhookSigningConfig {
+    pub(super) fn build(&self) -> crate::Result<WebhookSigner> {
+        Ok(WebhookSigner {
+            secret: self.secret.clone(),
+            algorithm: self.algorithm,
+            header: HeaderName::try_from(&self.header)?,
+            timestamp_header: self
+                .timestamp_header
+                .as_ref()
+                .map(HeaderName::try_from)
+                .transpose()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct WebhookSigner {
+    secret: SensitiveString,
+    algorithm: WebhookSigningAlgorithm,
+    header: HeaderName,
+    timestamp_header: Option<HeaderName>,
+}
+
+impl WebhookSigner {
+    /// Adds the signature headers to a request, signed at `now`.
+    pub(super) fn sign<B: AsRef<[u8]>>(&self, request: &mut Request<B>, now: DateTime<Utc>) {
+        let timestamp = self
+            .timestamp_header
+            .as_ref()
+            .map(|header| (header, now.timestamp().to_string()));
+        let signature = self.signature(
+            timestamp.as_ref().map(|(_, timestamp)| timestamp.as_str()),
+            request.body().as_ref(),
+        );
+
+        let headers = request.headers_mut();
+        if let Some((header, timestamp)) = timestamp {
+            headers.insert(
+                header.clone(),
+                HeaderValue::try_from(timestamp).expect("timestamps are valid header values"),
+            );
+        }
+        headers.insert(
+            self.header.clone(),
+            HeaderValue::try_from(signature).expect("signatures are valid header values"),
+        );
+    }
+
+    pub(super) fn signature(&self, timestamp: Option<&str>, body: &[u8]) -> String {
+        let key = self.secret.inner().as_bytes();
+        let mac = match timestamp {
+            Some(timestamp) => self
+                .algorithm
+                .hmac(key, &[timestamp.as_bytes(), b".", body]),
+            None => self.algorithm.hmac(key, &[body]),
+        };
+        format!("{}={}", self.algorithm.name(), hex::encode(mac))
+    }
+}
@@ -0,0 +1,49 @@
+//! Implementation of the `webhook` sink.
+
+use std::future;
+
+use http::Uri;
+
+use crate::sinks::{prelude::*, util::http::HttpRequest};
+
+use super::request_builder::WebhookRequestBuilder;
+
+pub(super) struct WebhookSink<S> {
+    pub(super) service: S,
+    pub(super) request_builder: WebhookRequestBuilder,
+}
+
+impl<S> WebhookSink<S>
+where
+    S: Service<HttpRequest<Uri>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let Self {
+            service,
+            request_builder,
+        } = *self;
+
+        input
+            // Each event is delivered with a request of its own, to the URI rendered from it.
+            .filter_map(|event| future::ready(request_builder.build(event)))
+            .into_driver(service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for WebhookSink<S>
+where
+    S: Service<HttpRequest<Uri>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,191 @@
+//! Unit tests for the `webhook` sink.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use futures::stream;
+use hyper::{Body, Response, StatusCode};
+use vector_lib::event::{BatchNotifier, BatchStatus};
+
+use crate::{
+    sinks::{prelude::*, util::test::build_test_server_generic},
+    test_util::{
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        next_addr,
+    },
+};
+
+use super::{
+    config::WebhookSinkConfig, request_builder::WebhookBody, signing::WebhookSigningConfig,
+};
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<WebhookSinkConfig>();
+}
+
+fn signing_config(algorithm: &str, timestamp_header: Option<&str>) -> WebhookSigningConfig {
+    let mut config = format!("secret = \"secret\"\nalgorithm = \"{algorithm}\"\n");
+    if let Some(header) = timestamp_header {
+        config.push_str(&format!("timestamp_header = \"{header}\"\n"));
+    }
+    toml::from_str(&config).unwrap()
+}
+
+#[test]
+fn webhook_signature() {
+    let body = br#"{"a":1}"#;
+
+    let signer = signing_config("sha256", None).build().unwrap();
+    assert_eq!(
+        signer.signature(None, body),
+        "sha256=aa9e2e3575f5d7098b6caccd790888c36d5fdb63342a73bada2d6a51747a8494"
+    );
+    assert_eq!(
+        signer.signature(Some("1700000000"), body),
+        "sha256=49f24e537407743fa4a0242bb63b94b9a47ee99cbbe071ccd8a22550ae411686"
+    );
+
+    let signer = signing_config("sha512", None).build().unwrap();
+    assert_eq!(
+        signer.signature(None, body),
+        "sha512=42f08c0da01c9a46d4e5663d5f2140526a016507c598182c6769cca2bcf7ba43\
+         c395b719b01d288f5ad322564dce1d40ca28ef50b876fc49a33d13d6783114dd"
+    );
+}
+
+#[test]
+fn webhook_template_body_must_be_json() {
+    let body = WebhookBody::Template(Template::try_from(r#"{"text": "{{ message }}"}"#).unwrap());
+
+    let rendered = body.render(&LogEvent::from("disk full")).unwrap();
+    assert_eq!(&rendered[..], br#"{"text": "disk full"}"#);
+
+    assert!(body.render(&LogEvent::from(r#"disk "full""#)).is_none());
+}
+
+#[test]
+fn webhook_mapping_body() {
+    let body = WebhookBody::mapping(
+        r#". = {"text": upcase!(.message), "level": .level}"#,
+        Default::default(),
+    )
+    .unwrap();
+
+    let mut event = LogEvent::from("disk full");
+    event.insert("level", "error");
+    let rendered = body.render(&event).unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&rendered).unwrap(),
+        serde_json::json!({"text": "DISK FULL", "level": "error"})
+    );
+}
+
+#[tokio::test]
+async fn webhook_rejects_template_and_mapping() {
+    let config: WebhookSinkConfig = toml::from_str(
+        r#"
+        uri = "http://localhost/hooks"
+        template = "{}"
+        mapping = "."
+        "#,
+    )
+    .unwrap();
+
+    let error = config.build(SinkContext::default()).await.err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "only one of `template` and `mapping` can be provided"
+    );
+}
+
+#[tokio::test]
+async fn webhook_delivers_signed_events() {
+    let addr = next_addr();
+    let config = format!(
+        r#"
+        uri = "http://{addr}/hooks/{{{{ team }}}}"
+        mapping = '. = {{"text": .message}}'
+        signing.secret = "secret"
+        signing.header = "X-Hub-Signature-256"
+        signing.timestamp_header = "X-Signature-Timestamp"
+        "#
+    );
+    let config: WebhookSinkConfig = toml::from_str(&config).unwrap();
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+    let (mut rx, _trigger, server) =
+        build_test_server_generic(addr, || Response::new(Body::empty()));
+    tokio::spawn(server);
+
+    let events = ["a", "b"].map(|team| {
+        let mut log = LogEvent::from(format!("alert for {team}"));
+        log.insert("team", team);
+        Event::Log(log)
+    });
+    run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS).await;
+
+    let signer = signing_config("sha256", Some("X-Signature-Timestamp"))
+        .build()
+        .unwrap();
+    let mut requests = Vec::new();
+    for _ in 0..2 {
+        requests.push(rx.next().await.unwrap());
+    }
+    requests.sort_by(|(a, _), (b, _)| a.uri.path().cmp(b.uri.path()));
+
+    for ((parts, body), team) in requests.into_iter().zip(["a", "b"]) {
+        assert_eq!(parts.uri.path(), format!("/hooks/{team}"));
+        assert_eq!(parts.headers["content-type"], "application/json");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+            serde_json::json!({ "text": format!("alert for {team}") })
+        );
+
+        let timestamp = parts.headers["x-signature-timestamp"].to_str().unwrap();
+        assert_eq!(
+            parts.headers["x-hub-signature-256"],
+            signer.signature(Some(timestamp), &body).as_str()
+        );
+    }
+}
+
+#[tokio::test]
+async fn webhook_retries_rate_limited_requests() {
+    let addr = next_addr();
+    let config = format!(
+        r#"
+        uri = "http://{addr}/hooks"
+        "#
+    );
+    let config: WebhookSinkConfig = toml::from_str(&config).unwrap();
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let responder_attempts = Arc::clone(&attempts);
+    let (mut rx, _trigger, server) = build_test_server_generic(addr, move || {
+        if responder_attempts.fetch_add(1, Ordering::Relaxed) == 0 {
+            Response::builder()
+                .status(StatusCode::TOO_MANY_REQUESTS)
+                .header("Retry-After", "1")
+                .body(Body::empty())
+                .unwrap()
+        } else {
+            Response::new(Body::empty())
+        }
+    });
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let event = Event::Log(LogEvent::from("rate limited").with_batch_notifier(&batch));
+    drop(batch);
+    sink.run_events(vec![event]).await.unwrap();
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    assert_eq!(attempts.load(Ordering::Relaxed), 2);
+    let (_, body) = rx.next().await.unwrap();
+    let body = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+    assert_eq!(body["message"], "rate limited");
+}
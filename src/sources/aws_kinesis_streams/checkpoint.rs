@@ -0,0 +1,148 @@
+//! Records how far each shard of the stream was read, in a file of the source's data directory.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::internal_events::AwsKinesisStreamsCheckpointError;
+
+const CHECKPOINTS_FILE_NAME: &str = "checkpoints.json";
+
+/// The position up to which a shard was read.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Position {
+    /// After the record with the given sequence number.
+    After(String),
+    /// Every record of the shard was read, as it was closed by resharding the stream.
+    Finished,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Checkpoints {
+    /// The stream the shards are from, as shard IDs are only unique within a stream.
+    stream_name: String,
+    shards: BTreeMap<String, Position>,
+}
+
+pub(super) struct CheckpointStore {
+    path: PathBuf,
+    checkpoints: Mutex<Checkpoints>,
+}
+
+impl CheckpointStore {
+    /// Loads the checkpoints of `stream_name` from `data_dir`, if any were written.
+    pub(super) async fn load(data_dir: &Path, stream_name: &str) -> io::Result<Self> {
+        let path = data_dir.join(CHECKPOINTS_FILE_NAME);
+        let checkpoints = match fs::read(&path).await {
+            Ok(contents) => serde_json::from_slice::<Checkpoints>(&contents)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Checkpoints::default(),
+            Err(error) => return Err(error),
+        };
+        let checkpoints = if checkpoints.stream_name == stream_name {
+            checkpoints
+        } else {
+            if !checkpoints.stream_name.is_empty() {
+                warn!(
+                    message = "Ignoring the checkpoints of another Kinesis stream.",
+                    stream_name = %checkpoints.stream_name,
+                    path = ?path,
+                );
+            }
+            Checkpoints {
+                stream_name: stream_name.to_owned(),
+                shards: BTreeMap::new(),
+            }
+        };
+        Ok(Self {
+            path,
+            checkpoints: Mutex::new(checkpoints),
+        })
+    }
+
+    pub(super) fn get(&self, shard_id: &str) -> Option<Position> {
+        let checkpoints = self.checkpoints.lock().expect("poisoned lock");
+        checkpoints.shards.get(shard_id).cloned()
+    }
+
+    pub(super) fn update(&self, shard_id: &str, position: Position) {
+        let mut checkpoints = self.checkpoints.lock().expect("poisoned lock");
+        checkpoints.shards.insert(shard_id.to_owned(), position);
+    }
+
+    /// Forgets the shards no longer in the stream, once their records are past retention.
+    pub(super) fn retain(&self, shard_ids: &HashSet<&str>) {
+        let mut checkpoints = self.checkpoints.lock().expect("poisoned lock");
+        checkpoints
+            .shards
+            .retain(|shard_id, _| shard_ids.contains(shard_id.as_str()));
+    }
+
+    /// Writes the checkpoints to a temporary file replacing the previous one, so that they are
+    /// never left partially written.
+    pub(super) async fn save(&self) {
+        if let Err(error) = self.try_save().await {
+            emit!(AwsKinesisStreamsCheckpointError {
+                path: &self.path,
+                error,
+            });
+        }
+    }
+
+    async fn try_save(&self) -> io::Result<()> {
+        let contents = {
+            let checkpoints = self.checkpoints.lock().expect("poisoned lock");
+            serde_json::to_vec(&*checkpoints)?
+        };
+        let temporary = self.path.with_extension("json.tmp");
+        fs::write(&temporary, contents).await?;
+        fs::rename(temporary, &self.path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn checkpoints_round_trip() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::load(directory.path(), "logs")
+            .await
+            .unwrap();
+        assert_eq!(store.get("shardId-000000000000"), None);
+
+        store.update(
+            "shardId-000000000000",
+            Position::After("49590338271490256608559692538361571095921575989136588898".into()),
+        );
+        store.update("shardId-000000000001", Position::Finished);
+        store.save().await;
+
+        let store = CheckpointStore::load(directory.path(), "logs")
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get("shardId-000000000000"),
+            Some(Position::After(
+                "49590338271490256608559692538361571095921575989136588898".into()
+            ))
+        );
+        assert_eq!(store.get("shardId-000000000001"), Some(Position::Finished));
+
+        store.retain(&HashSet::from(["shardId-000000000000"]));
+        assert_eq!(store.get("shardId-000000000001"), None);
+
+        let other = CheckpointStore::load(directory.path(), "metrics")
+            .await
+            .unwrap();
+        assert_eq!(other.get("shardId-000000000000"), None);
+    }
+}
@@ -0,0 +1,536 @@
+//! The `aws_kinesis_streams` source.
+//!
+//! The shards of the stream are listed periodically, and each open shard is read by its own task
+//! with `GetRecords`. Shards split or merged by resharding are only read once the shards they were
+//! created from are read to their end, keeping the records of each partition key in order. The
+//! position read up to in each shard is checkpointed in the source's data directory.
+
+mod checkpoint;
+mod shard;
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+
+use aws_sdk_kinesis::Client as KinesisClient;
+use futures::FutureExt;
+use tokio::task::JoinHandle;
+use vector_lib::{
+    codecs::decoding::{DeserializerConfig, FramingConfig},
+    config::{log_schema, LegacyKey, LogNamespace, SourceAcknowledgementsConfig},
+    configurable::configurable_component,
+    lookup::owned_value_path,
+};
+use vrl::value::Kind;
+
+use self::{
+    checkpoint::{CheckpointStore, Position},
+    shard::{ShardReader, Start},
+};
+use crate::{
+    aws::{create_client, AwsAuthentication, RegionOrEndpoint},
+    codecs::{Decoder, DecodingConfig},
+    common::kinesis::KinesisClientBuilder,
+    config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    internal_events::AwsKinesisStreamsShardDiscoveryError,
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    tls::TlsConfig,
+    SourceSender,
+};
+
+/// Where reading shards starts when they have no checkpoint.
+///
+/// Shards created by resharding the stream are always read from their oldest record, once the
+/// shards they were created from are read.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartPosition {
+    /// Read the records already in the shard, from the oldest one retained.
+    TrimHorizon,
+
+    /// Only read records added after reading the shard started.
+    #[default]
+    Latest,
+}
+
+/// Configuration for the `aws_kinesis_streams` source.
+#[configurable_component(source(
+    "aws_kinesis_streams",
+    "Collect logs from AWS Kinesis Data Streams."
+))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AwsKinesisStreamsSourceConfig {
+    #[serde(flatten)]
+    #[configurable(derived)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    /// The name of the stream to read.
+    #[configurable(metadata(docs::examples = "my-stream"))]
+    pub stream_name: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub start_position: StartPosition,
+
+    /// How often, in seconds, the shards of the stream are listed to find the shards created by
+    /// resharding.
+    #[serde(default = "default_shard_discovery_interval_secs")]
+    #[derivative(Default(value = "default_shard_discovery_interval_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::advanced))]
+    pub shard_discovery_interval_secs: u64,
+
+    /// How long, in seconds, to wait before getting records again from a shard that has no new
+    /// records.
+    #[serde(default = "default_poll_secs")]
+    #[derivative(Default(value = "default_poll_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Poll Interval"))]
+    pub poll_secs: u64,
+
+    /// How often, in seconds, the position read up to in each shard is checkpointed.
+    #[serde(default = "default_checkpoint_interval_secs")]
+    #[derivative(Default(value = "default_checkpoint_interval_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::advanced))]
+    pub checkpoint_interval_secs: u64,
+
+    /// The directory used to persist the checkpoints of the shards.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    /// Make sure the running user has write permissions to this directory.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    pub data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: SourceAcknowledgementsConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+}
+
+const fn default_shard_discovery_interval_secs() -> u64 {
+    60
+}
+
+const fn default_poll_secs() -> u64 {
+    1
+}
+
+const fn default_checkpoint_interval_secs() -> u64 {
+    5
+}
+
+impl GenerateConfig for AwsKinesisStreamsSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"region = "us-east-1"
+            stream_name = "my-stream""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aws_kinesis_streams")]
+impl SourceConfig for AwsKinesisStreamsSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let client = create_client::<KinesisClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint(),
+            &cx.proxy,
+            &self.tls,
+            &None,
+        )
+        .await?;
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let store = CheckpointStore::load(&data_dir, &self.stream_name).await?;
+
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        let source = KinesisStreamsSource {
+            client,
+            store: Arc::new(store),
+            stream_name: self.stream_name.clone(),
+            start_position: self.start_position,
+            shard_discovery_interval: Duration::from_secs(self.shard_discovery_interval_secs),
+            poll_interval: Duration::from_secs(self.poll_secs),
+            checkpoint_interval: Duration::from_secs(self.checkpoint_interval_secs),
+            decoder,
+            log_namespace,
+            acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+        };
+        Ok(source.run(cx.out, cx.shutdown).boxed())
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                log_schema()
+                    .timestamp_key()
+                    .cloned()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp().or_undefined(),
+                Some("timestamp"),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("stream_name"))),
+                &owned_value_path!("stream_name"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("shard_id"))),
+                &owned_value_path!("shard_id"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(
+                    "sequence_number"
+                ))),
+                &owned_value_path!("sequence_number"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("partition_key"))),
+                &owned_value_path!("partition_key"),
+                Kind::bytes(),
+                None,
+            );
+
+        vec![SourceOutput::new_maybe_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// A shard of the stream, along with the shards it was split from or merged from.
+#[derive(Debug)]
+struct ListedShard {
+    shard_id: String,
+    parent_shard_ids: Vec<String>,
+}
+
+struct KinesisStreamsSource {
+    client: KinesisClient,
+    store: Arc<CheckpointStore>,
+    stream_name: String,
+    start_position: StartPosition,
+    shard_discovery_interval: Duration,
+    poll_interval: Duration,
+    checkpoint_interval: Duration,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+}
+
+impl KinesisStreamsSource {
+    async fn run(self, out: SourceSender, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        let mut readers = HashMap::<String, JoinHandle<()>>::new();
+        let mut discovery = tokio::time::interval(self.shard_discovery_interval);
+        let mut checkpoint = tokio::time::interval(self.checkpoint_interval);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = checkpoint.tick() => {
+                    self.store.save().await;
+                    continue;
+                }
+                _ = discovery.tick() => {}
+            }
+
+            // Shards whose reader stopped are started again unless they were read to their end.
+            readers.retain(|_, reader| !reader.is_finished());
+
+            let shards = match self.list_shards().await {
+                Ok(shards) => shards,
+                Err(error) => {
+                    emit!(AwsKinesisStreamsShardDiscoveryError {
+                        stream_name: self.stream_name.clone(),
+                        error,
+                    });
+                    continue;
+                }
+            };
+            self.store
+                .retain(&shards.iter().map(|shard| shard.shard_id.as_str()).collect());
+
+            for (shard_id, start) in
+                shards_to_read(&shards, &self.store, &readers, self.start_position)
+            {
+                let reader = ShardReader {
+                    client: self.client.clone(),
+                    store: Arc::clone(&self.store),
+                    stream_name: self.stream_name.clone(),
+                    shard_id: shard_id.clone(),
+                    decoder: self.decoder.clone(),
+                    log_namespace: self.log_namespace,
+                    acknowledgements: self.acknowledgements,
+                    poll_interval: self.poll_interval,
+                    out: out.clone(),
+                };
+                let reader = tokio::spawn(reader.run(start, shutdown.clone()));
+                readers.insert(shard_id, reader);
+            }
+        }
+
+        // Readers stop on shutdown, after their delivered records are checkpointed.
+        for (_, reader) in readers {
+            _ = reader.await;
+        }
+        self.store.save().await;
+        Ok(())
+    }
+
+    async fn list_shards(&self) -> crate::Result<Vec<ListedShard>> {
+        let mut shards = Vec::new();
+        let mut next_token = None;
+        loop {
+            // The stream can't be named along with the token of the next page.
+            let request = match next_token {
+                Some(token) => self.client.list_shards().next_token(token),
+                None => self.client.list_shards().stream_name(&self.stream_name),
+            };
+            let output = request.send().await?;
+            shards.extend(output.shards().iter().map(|shard| {
+                ListedShard {
+                    shard_id: shard.shard_id().to_owned(),
+                    parent_shard_ids: shard
+                        .parent_shard_id()
+                        .into_iter()
+                        .chain(shard.adjacent_parent_shard_id())
+                        .map(ToOwned::to_owned)
+                        .collect(),
+                }
+            }));
+            next_token = output.next_token().map(ToOwned::to_owned);
+            if next_token.is_none() {
+                return Ok(shards);
+            }
+        }
+    }
+}
+
+/// Chooses the shards to start reading, along with where to start reading them.
+///
+/// A shard is read once the shards it was created from, if they are still listed, are read to
+/// their end, so that the records of each partition key are read in order.
+fn shards_to_read(
+    shards: &[ListedShard],
+    store: &CheckpointStore,
+    readers: &HashMap<String, JoinHandle<()>>,
+    start_position: StartPosition,
+) -> Vec<(String, Start)> {
+    let listed = shards
+        .iter()
+        .map(|shard| shard.shard_id.as_str())
+        .collect::<HashSet<_>>();
+
+    shards
+        .iter()
+        .filter(|shard| !readers.contains_key(&shard.shard_id))
+        .filter_map(|shard| {
+            let position = store.get(&shard.shard_id);
+            if position == Some(Position::Finished) {
+                return None;
+            }
+            let mut parents = shard
+                .parent_shard_ids
+                .iter()
+                .filter(|parent| listed.contains(parent.as_str()))
+                .peekable();
+            let has_parents = parents.peek().is_some();
+            if !parents.all(|parent| store.get(parent) == Some(Position::Finished)) {
+                return None;
+            }
+
+            let start = match position {
+                Some(position) => Start::Position(position),
+                None if has_parents => Start::Initial(StartPosition::TrimHorizon),
+                None => Start::Initial(start_position),
+            };
+            Some((shard.shard_id.clone(), start))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::{lookup::OwnedTargetPath, schema::Definition};
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsKinesisStreamsSourceConfig>();
+    }
+
+    fn shard(shard_id: &str, parent_shard_ids: &[&str]) -> ListedShard {
+        ListedShard {
+            shard_id: shard_id.to_owned(),
+            parent_shard_ids: parent_shard_ids.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_child_shards_after_their_parents() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::load(directory.path(), "logs")
+            .await
+            .unwrap();
+        let shards = [
+            shard("shardId-0", &[]),
+            shard("shardId-1", &[]),
+            // Merged from both shards above.
+            shard("shardId-2", &["shardId-0", "shardId-1"]),
+            // Split from a shard past retention.
+            shard("shardId-3", &["shardId-expired"]),
+        ];
+        store.update("shardId-1", Position::After("42".into()));
+
+        let started = shards_to_read(&shards, &store, &HashMap::new(), StartPosition::Latest);
+        assert_eq!(
+            started,
+            vec![
+                (
+                    "shardId-0".to_owned(),
+                    Start::Initial(StartPosition::Latest)
+                ),
+                (
+                    "shardId-1".to_owned(),
+                    Start::Position(Position::After("42".into()))
+                ),
+                (
+                    "shardId-3".to_owned(),
+                    Start::Initial(StartPosition::Latest)
+                ),
+            ]
+        );
+
+        store.update("shardId-0", Position::Finished);
+        store.update("shardId-1", Position::Finished);
+        store.update("shardId-3", Position::After("7".into()));
+        let started = shards_to_read(&shards, &store, &HashMap::new(), StartPosition::Latest);
+        assert_eq!(
+            started,
+            vec![
+                (
+                    "shardId-2".to_owned(),
+                    Start::Initial(StartPosition::TrimHorizon)
+                ),
+                (
+                    "shardId-3".to_owned(),
+                    Start::Position(Position::After("7".into()))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let config = AwsKinesisStreamsSourceConfig {
+            log_namespace: Some(true),
+            ..Default::default()
+        };
+
+        let definition = config
+            .outputs(LogNamespace::Vector)
+            .remove(0)
+            .schema_definition(true);
+
+        let expected = Definition::new_with_default_metadata(Kind::bytes(), [LogNamespace::Vector])
+            .with_meaning(OwnedTargetPath::event_root(), "message")
+            .with_metadata_field(
+                &owned_value_path!("vector", "source_type"),
+                Kind::bytes(),
+                None,
+            )
+            .with_metadata_field(
+                &owned_value_path!("vector", "ingest_timestamp"),
+                Kind::timestamp(),
+                None,
+            )
+            .with_metadata_field(
+                &owned_value_path!(AwsKinesisStreamsSourceConfig::NAME, "timestamp"),
+                Kind::timestamp().or_undefined(),
+                Some("timestamp"),
+            )
+            .with_metadata_field(
+                &owned_value_path!(AwsKinesisStreamsSourceConfig::NAME, "stream_name"),
+                Kind::bytes(),
+                None,
+            )
+            .with_metadata_field(
+                &owned_value_path!(AwsKinesisStreamsSourceConfig::NAME, "shard_id"),
+                Kind::bytes(),
+                None,
+            )
+            .with_metadata_field(
+                &owned_value_path!(AwsKinesisStreamsSourceConfig::NAME, "sequence_number"),
+                Kind::bytes(),
+                None,
+            )
+            .with_metadata_field(
+                &owned_value_path!(AwsKinesisStreamsSourceConfig::NAME, "partition_key"),
+                Kind::bytes(),
+                None,
+            );
+
+        assert_eq!(definition, Some(expected));
+    }
+}
@@ -0,0 +1,359 @@
+//! Reads the records of a single shard of a Kinesis stream with `GetRecords`.
+
+use std::{sync::Arc, time::Duration};
+
+use aws_sdk_kinesis::{
+    operation::get_records::GetRecordsError,
+    types::{Record, ShardIteratorType},
+    Client as KinesisClient,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use futures::StreamExt;
+use tokio_util::codec::FramedRead;
+use vector_lib::{
+    config::{log_schema, LegacyKey, LogNamespace},
+    finalizer::OrderedFinalizer,
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+        Registered,
+    },
+    lookup::path,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use super::{
+    checkpoint::{CheckpointStore, Position},
+    AwsKinesisStreamsSourceConfig, StartPosition,
+};
+use crate::{
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus, Event, LogEvent},
+    internal_events::{AwsKinesisStreamsReceiveError, StreamClosedError},
+    shutdown::ShutdownSignal,
+    sinks::util::retries::ExponentialBackoff,
+    SourceSender,
+};
+
+/// The most records a single `GetRecords` call returns.
+const GET_RECORDS_LIMIT: i32 = 10_000;
+
+/// The shortest time between two `GetRecords` calls to a shard, as a shard serves at most five
+/// of them per second.
+const MIN_GET_RECORDS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Where in the shard reading starts.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Start {
+    Position(Position),
+    Initial(StartPosition),
+}
+
+pub(super) struct ShardReader {
+    pub(super) client: KinesisClient,
+    pub(super) store: Arc<CheckpointStore>,
+    pub(super) stream_name: String,
+    pub(super) shard_id: String,
+    pub(super) decoder: Decoder,
+    pub(super) log_namespace: LogNamespace,
+    pub(super) acknowledgements: bool,
+    pub(super) poll_interval: Duration,
+    pub(super) out: SourceSender,
+}
+
+impl ShardReader {
+    /// Reads records from `start` until shut down, or until every record of the closed shard
+    /// was read.
+    pub(super) async fn run(mut self, mut start: Start, mut shutdown: ShutdownSignal) {
+        let (finalizer, mut acknowledged) =
+            OrderedFinalizer::<Position>::maybe_new(self.acknowledgements, Some(shutdown.clone()));
+        let store = Arc::clone(&self.store);
+        let shard_id = self.shard_id.clone();
+        let checkpointer = tokio::spawn(async move {
+            while let Some((status, position)) = acknowledged.next().await {
+                if status == BatchStatus::Delivered {
+                    store.update(&shard_id, position);
+                }
+            }
+        });
+
+        let bytes_received = register!(BytesReceived::from(Protocol::HTTPS));
+        let events_received = register!(EventsReceived);
+        let mut backoff = ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(30));
+
+        'iterate: loop {
+            let iterator = tokio::select! {
+                _ = &mut shutdown => break,
+                iterator = self.shard_iterator(&start) => iterator,
+            };
+            let mut iterator = match iterator {
+                Ok(Some(iterator)) => iterator,
+                // The shard is closed and was already read up to its end.
+                Ok(None) => {
+                    self.finish(&finalizer);
+                    break;
+                }
+                Err(error) => {
+                    emit!(AwsKinesisStreamsReceiveError {
+                        shard_id: &self.shard_id,
+                        error,
+                    });
+                    tokio::select! {
+                        _ = &mut shutdown => break,
+                        _ = tokio::time::sleep(backoff.next().unwrap()) => continue,
+                    }
+                }
+            };
+
+            loop {
+                let output = tokio::select! {
+                    _ = &mut shutdown => break 'iterate,
+                    output = self
+                        .client
+                        .get_records()
+                        .shard_iterator(iterator)
+                        .limit(GET_RECORDS_LIMIT)
+                        .send() => output,
+                };
+                let output = match output {
+                    Ok(output) => output,
+                    Err(error) => {
+                        let not_found = matches!(
+                            error.as_service_error(),
+                            Some(GetRecordsError::ResourceNotFoundException(_))
+                        );
+                        emit!(AwsKinesisStreamsReceiveError {
+                            shard_id: &self.shard_id,
+                            error: &error,
+                        });
+                        if not_found {
+                            break 'iterate;
+                        }
+                        // Expired iterators are renewed from the last record read.
+                        tokio::select! {
+                            _ = &mut shutdown => break 'iterate,
+                            _ = tokio::time::sleep(backoff.next().unwrap()) => continue 'iterate,
+                        }
+                    }
+                };
+                backoff.reset();
+
+                let records = output.records();
+                if let Some(last) = records.last() {
+                    let position = Position::After(last.sequence_number().to_owned());
+                    start = Start::Position(position.clone());
+
+                    bytes_received.emit(ByteSize(
+                        records
+                            .iter()
+                            .map(|record| record.data().as_ref().len())
+                            .sum(),
+                    ));
+                    let mut events = Vec::new();
+                    for record in records {
+                        events.extend(self.decode(record, &events_received).await);
+                    }
+                    if !self.send(events, position, &finalizer).await {
+                        break 'iterate;
+                    }
+                }
+
+                // Closed shards have no next iterator once their last record is read.
+                let Some(next) = output.next_shard_iterator() else {
+                    debug!(message = "Finished reading closed shard.", shard_id = %self.shard_id);
+                    self.finish(&finalizer);
+                    break 'iterate;
+                };
+                iterator = next.to_owned();
+
+                let caught_up = records.is_empty() || output.millis_behind_latest() == Some(0);
+                let wait = if caught_up {
+                    self.poll_interval.max(MIN_GET_RECORDS_INTERVAL)
+                } else {
+                    MIN_GET_RECORDS_INTERVAL
+                };
+                tokio::select! {
+                    _ = &mut shutdown => break 'iterate,
+                    _ = tokio::time::sleep(wait) => {}
+                }
+            }
+        }
+
+        drop(finalizer);
+        _ = checkpointer.await;
+    }
+
+    async fn shard_iterator(&self, start: &Start) -> crate::Result<Option<String>> {
+        let request = self
+            .client
+            .get_shard_iterator()
+            .stream_name(&self.stream_name)
+            .shard_id(&self.shard_id);
+        let request = match start {
+            Start::Position(Position::Finished) => return Ok(None),
+            Start::Position(Position::After(sequence_number)) => request
+                .shard_iterator_type(ShardIteratorType::AfterSequenceNumber)
+                .starting_sequence_number(sequence_number),
+            Start::Initial(StartPosition::TrimHorizon) => {
+                request.shard_iterator_type(ShardIteratorType::TrimHorizon)
+            }
+            Start::Initial(StartPosition::Latest) => {
+                request.shard_iterator_type(ShardIteratorType::Latest)
+            }
+        };
+        let output = request.send().await?;
+
+        debug!(
+            message = "Reading from shard.",
+            shard_id = %self.shard_id,
+            start = ?start,
+        );
+        Ok(output.shard_iterator().map(ToOwned::to_owned))
+    }
+
+    async fn decode(
+        &self,
+        record: &Record,
+        events_received: &Registered<EventsReceived>,
+    ) -> Vec<Event> {
+        let arrival_time = record
+            .approximate_arrival_timestamp()
+            .and_then(|timestamp| timestamp.to_millis().ok())
+            .and_then(|millis| Utc.timestamp_millis_opt(millis).latest());
+
+        let now = Utc::now();
+        let mut decoded = Vec::new();
+        let mut stream = FramedRead::new(record.data().as_ref(), self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((events, _byte_size)) => {
+                    events_received.emit(CountByteSize(
+                        events.len(),
+                        events.estimated_json_encoded_size_of(),
+                    ));
+
+                    decoded.extend(events.into_iter().map(|mut event| {
+                        if let Event::Log(ref mut log) = event {
+                            self.enrich(log, record, arrival_time, now);
+                        }
+                        event
+                    }));
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+        decoded
+    }
+
+    fn enrich(
+        &self,
+        log: &mut LogEvent,
+        record: &Record,
+        arrival_time: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) {
+        let log_namespace = self.log_namespace;
+        if let Some(arrival_time) = arrival_time {
+            log_namespace.insert_source_metadata(
+                AwsKinesisStreamsSourceConfig::NAME,
+                log,
+                log_schema().timestamp_key().map(LegacyKey::Overwrite),
+                path!("timestamp"),
+                arrival_time,
+            );
+        }
+        log_namespace.insert_standard_vector_source_metadata(
+            log,
+            AwsKinesisStreamsSourceConfig::NAME,
+            now,
+        );
+
+        log_namespace.insert_source_metadata(
+            AwsKinesisStreamsSourceConfig::NAME,
+            log,
+            Some(LegacyKey::InsertIfEmpty(path!("stream_name"))),
+            path!("stream_name"),
+            self.stream_name.clone(),
+        );
+        log_namespace.insert_source_metadata(
+            AwsKinesisStreamsSourceConfig::NAME,
+            log,
+            Some(LegacyKey::InsertIfEmpty(path!("shard_id"))),
+            path!("shard_id"),
+            self.shard_id.clone(),
+        );
+        log_namespace.insert_source_metadata(
+            AwsKinesisStreamsSourceConfig::NAME,
+            log,
+            Some(LegacyKey::InsertIfEmpty(path!("sequence_number"))),
+            path!("sequence_number"),
+            record.sequence_number().to_owned(),
+        );
+        log_namespace.insert_source_metadata(
+            AwsKinesisStreamsSourceConfig::NAME,
+            log,
+            Some(LegacyKey::InsertIfEmpty(path!("partition_key"))),
+            path!("partition_key"),
+            record.partition_key().to_owned(),
+        );
+    }
+
+    /// Sends the events of a batch of records, returning whether the pipeline is still accepting
+    /// events.
+    async fn send(
+        &mut self,
+        events: Vec<Event>,
+        position: Position,
+        finalizer: &Option<OrderedFinalizer<Position>>,
+    ) -> bool {
+        let count = events.len();
+        let result = match finalizer {
+            Some(finalizer) => {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                let events = events
+                    .into_iter()
+                    .map(|event| event.with_batch_notifier(&batch))
+                    .collect::<Vec<_>>();
+                drop(batch);
+                let result = self.out.send_batch(events).await;
+                if result.is_ok() {
+                    finalizer.add(position, receiver);
+                }
+                result
+            }
+            None => {
+                let result = self.out.send_batch(events).await;
+                if result.is_ok() {
+                    self.store.update(&self.shard_id, position);
+                }
+                result
+            }
+        };
+
+        if result.is_err() {
+            emit!(StreamClosedError { count });
+            return false;
+        }
+        true
+    }
+
+    /// Marks the shard as finished once the events of its last records are delivered, so that
+    /// the shards it was split or merged into are read next.
+    fn finish(&self, finalizer: &Option<OrderedFinalizer<Position>>) {
+        match finalizer {
+            Some(finalizer) => {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                drop(batch);
+                finalizer.add(Position::Finished, receiver);
+            }
+            None => self.store.update(&self.shard_id, Position::Finished),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::{convert::TryInto, io::ErrorKind};
+use std::{convert::TryInto, io::ErrorKind, path::PathBuf};
 
 use async_compression::tokio::bufread;
 use aws_smithy_types::byte_stream::ByteStream;
@@ -27,6 +27,7 @@ use crate::{
     tls::TlsConfig,
 };
 
+mod poll;
 pub mod sqs;
 
 /// Compression scheme for objects retrieved from S3.
@@ -66,6 +67,13 @@ enum Strategy {
     /// [aws_sqs]: https://aws.amazon.com/sqs/
     #[derivative(Default)]
     Sqs,
+
+    /// Consumes objects by periodically listing the objects of a bucket, for buckets whose
+    /// notifications can't be sent to an AWS SQS queue.
+    ///
+    /// The keys and entity tags of the objects read are recorded in a checkpoint, so that only the
+    /// objects created or modified since the previous listing are read.
+    Poll,
 }
 
 /// Configuration for the `aws_s3` source.
@@ -86,12 +94,14 @@ pub struct AwsS3Config {
     compression: Compression,
 
     /// The strategy to use to consume objects from S3.
-    #[configurable(metadata(docs::hidden))]
     strategy: Strategy,
 
     /// Configuration options for SQS.
     sqs: Option<sqs::Config>,
 
+    /// Configuration options for polling, when `strategy` is set to `poll`.
+    poll: Option<poll::Config>,
+
     /// The ARN of an [IAM role][iam_role] to assume at startup.
     ///
     /// [iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
@@ -159,6 +169,26 @@ impl SourceConfig for AwsS3Config {
                     .await?
                     .run(cx, self.acknowledgements, log_namespace),
             )),
+            Strategy::Poll => {
+                let poll = self
+                    .poll
+                    .as_ref()
+                    .ok_or(CreatePollIngestorError::ConfigMissing)?;
+                let data_dir = cx
+                    .globals
+                    .resolve_and_make_data_subdir(poll.data_dir.as_ref(), cx.key.id())?;
+                Ok(Box::pin(
+                    self.create_poll_ingestor(
+                        poll,
+                        data_dir,
+                        multiline_config,
+                        &cx.proxy,
+                        log_namespace,
+                    )
+                    .await?
+                    .run(cx, self.acknowledgements, log_namespace),
+                ))
+            }
         }
     }
 
@@ -273,6 +303,39 @@ impl AwsS3Config {
             None => Err(CreateSqsIngestorError::ConfigMissing {}.into()),
         }
     }
+
+    async fn create_poll_ingestor(
+        &self,
+        poll: &poll::Config,
+        data_dir: PathBuf,
+        multiline: Option<line_agg::Config>,
+        proxy: &ProxyConfig,
+        log_namespace: LogNamespace,
+    ) -> crate::Result<poll::Ingestor> {
+        let (s3_client, region) = create_client_and_region::<S3ClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint(),
+            proxy,
+            &self.tls_options,
+            &None,
+        )
+        .await?;
+
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        Ok(poll::Ingestor::new(
+            region,
+            s3_client,
+            poll.clone(),
+            data_dir,
+            self.compression,
+            multiline,
+            decoder,
+        ))
+    }
 }
 
 #[derive(Debug, Snafu)]
@@ -281,6 +344,12 @@ enum CreateSqsIngestorError {
     ConfigMissing,
 }
 
+#[derive(Debug, Snafu)]
+enum CreatePollIngestorError {
+    #[snafu(display("Configuration for `poll` required when strategy=poll"))]
+    ConfigMissing,
+}
+
 /// None if body is empty
 async fn s3_object_decoder(
     compression: Compression,
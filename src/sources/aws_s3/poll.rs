@@ -0,0 +1,367 @@
+//! Consumes the objects of a bucket by listing them periodically, for buckets whose notifications
+//! can't be sent to SQS.
+
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aws_sdk_s3::operation::list_objects_v2::ListObjectsV2Error;
+use aws_sdk_s3::Client as S3Client;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_types::region::Region;
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use tokio::{fs, pin, select, time};
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+
+use super::sqs::{ObjectReader, ProcessingError};
+use crate::{
+    codecs::Decoder,
+    config::{SourceAcknowledgementsConfig, SourceContext},
+    event::BatchStatus,
+    internal_events::{
+        AwsS3CheckpointWriteError, AwsS3ListObjectsError, AwsS3ObjectProcessingError,
+    },
+    line_agg,
+};
+
+const CHECKPOINT_FILE_NAME: &str = "checkpoint.json";
+
+/// Polling configuration options.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// The name of the bucket to list the objects of.
+    #[configurable(metadata(docs::examples = "my-bucket"))]
+    pub(super) bucket: String,
+
+    /// The prefix of the keys of the objects to read.
+    ///
+    /// By default, all the objects of the bucket are read.
+    #[configurable(metadata(docs::examples = "logs/"))]
+    pub(super) prefix: Option<String>,
+
+    /// The interval between two listings of the objects, in seconds.
+    #[serde(default = "default_interval")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[derivative(Default(value = "default_interval()"))]
+    #[configurable(metadata(docs::human_name = "Interval"))]
+    pub(super) interval_secs: Duration,
+
+    /// Whether to read the objects already in the bucket when the source first starts.
+    ///
+    /// When `false`, only the objects created or modified after the first listing are read. This
+    /// has no effect once the source has written its checkpoint.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    pub(super) read_existing_objects: bool,
+
+    /// The directory used to persist the checkpoint of the objects read.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    /// Make sure the running user has write permissions to this directory.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    pub(super) data_dir: Option<PathBuf>,
+}
+
+const fn default_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+/// The objects read, by key, along with their entity tags to read them again once modified.
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Checkpoint {
+    objects: BTreeMap<String, String>,
+}
+
+impl Checkpoint {
+    /// Loads the checkpoint, `None` if it doesn't exist yet.
+    async fn load(path: &Path) -> io::Result<Option<Self>> {
+        match fs::read(path).await {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map(Some)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Writes the checkpoint to a temporary file replacing the previous one, so that it is never
+    /// left partially written.
+    async fn save(&self, path: &Path) -> io::Result<()> {
+        let contents = serde_json::to_vec(self)?;
+        let temporary = path.with_extension("json.tmp");
+        fs::write(&temporary, contents).await?;
+        fs::rename(temporary, path).await
+    }
+
+    /// Forgets the objects no longer listed, then returns the objects listed that are new or
+    /// modified since they were read.
+    fn update(&mut self, listed: BTreeMap<String, String>) -> Vec<(String, String)> {
+        self.objects.retain(|key, _| listed.contains_key(key));
+        listed
+            .into_iter()
+            .filter(|(key, e_tag)| self.objects.get(key) != Some(e_tag))
+            .collect()
+    }
+}
+
+pub(super) struct Ingestor {
+    region: Region,
+    s3_client: S3Client,
+    config: Config,
+    checkpoint_path: PathBuf,
+    compression: super::Compression,
+    multiline: Option<line_agg::Config>,
+    decoder: Decoder,
+}
+
+impl Ingestor {
+    pub(super) fn new(
+        region: Region,
+        s3_client: S3Client,
+        config: Config,
+        data_dir: PathBuf,
+        compression: super::Compression,
+        multiline: Option<line_agg::Config>,
+        decoder: Decoder,
+    ) -> Self {
+        Self {
+            region,
+            s3_client,
+            config,
+            checkpoint_path: data_dir.join(CHECKPOINT_FILE_NAME),
+            compression,
+            multiline,
+            decoder,
+        }
+    }
+
+    pub(super) async fn run(
+        self,
+        cx: SourceContext,
+        acknowledgements: SourceAcknowledgementsConfig,
+        log_namespace: LogNamespace,
+    ) -> Result<(), ()> {
+        let (mut checkpoint, mut skip_existing) =
+            match Checkpoint::load(&self.checkpoint_path).await {
+                Ok(Some(checkpoint)) => (checkpoint, false),
+                Ok(None) => (Checkpoint::default(), !self.config.read_existing_objects),
+                Err(error) => {
+                    error!(
+                        message = "Unable to load S3 objects checkpoint.",
+                        path = ?self.checkpoint_path,
+                        %error,
+                    );
+                    return Err(());
+                }
+            };
+
+        let mut process = PollProcess {
+            reader: ObjectReader::new(
+                self.s3_client.clone(),
+                self.compression,
+                self.multiline.clone(),
+                self.decoder.clone(),
+                cx.do_acknowledgements(acknowledgements),
+                log_namespace,
+            ),
+            out: cx.out,
+            ingestor: &self,
+        };
+
+        let shutdown = cx.shutdown.fuse();
+        pin!(shutdown);
+        let mut interval = time::interval(self.config.interval_secs);
+        let mut result = Ok(());
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                _ = interval.tick() => {}
+            }
+            select! {
+                _ = &mut shutdown => break,
+                round = process.run_once(&mut checkpoint, &mut skip_existing) => {
+                    if round.is_err() {
+                        result = Err(());
+                        break;
+                    }
+                }
+            }
+            self.save_checkpoint(&checkpoint).await;
+        }
+
+        // Persist the objects read by an interrupted round.
+        self.save_checkpoint(&checkpoint).await;
+        result
+    }
+
+    async fn save_checkpoint(&self, checkpoint: &Checkpoint) {
+        if let Err(error) = checkpoint.save(&self.checkpoint_path).await {
+            emit!(AwsS3CheckpointWriteError {
+                error,
+                path: &self.checkpoint_path,
+            });
+        }
+    }
+
+    async fn list_objects(
+        &self,
+    ) -> Result<BTreeMap<String, String>, SdkError<ListObjectsV2Error, HttpResponse>> {
+        let mut objects = BTreeMap::new();
+        let mut continuation_token = None;
+        loop {
+            let output = self
+                .s3_client
+                .list_objects_v2()
+                .bucket(self.config.bucket.clone())
+                .set_prefix(self.config.prefix.clone())
+                .set_continuation_token(continuation_token)
+                .send()
+                .await?;
+            for object in output.contents() {
+                match object.key() {
+                    // Skip the markers of the folders created by the console.
+                    Some(key) if !key.ends_with('/') => {
+                        let e_tag = object.e_tag().unwrap_or_default();
+                        objects.insert(key.to_owned(), e_tag.to_owned());
+                    }
+                    _ => {}
+                }
+            }
+            continuation_token = output.next_continuation_token().map(ToOwned::to_owned);
+            if continuation_token.is_none() {
+                return Ok(objects);
+            }
+        }
+    }
+}
+
+struct PollProcess<'a> {
+    reader: ObjectReader,
+    out: crate::SourceSender,
+    ingestor: &'a Ingestor,
+}
+
+impl<'a> PollProcess<'a> {
+    /// Lists the objects and reads the new ones, returning `Err` once the pipeline is closed.
+    async fn run_once(
+        &mut self,
+        checkpoint: &mut Checkpoint,
+        skip_existing: &mut bool,
+    ) -> Result<(), ()> {
+        let bucket = self.ingestor.config.bucket.as_str();
+        let listed = match self.ingestor.list_objects().await {
+            Ok(listed) => listed,
+            Err(error) => {
+                emit!(AwsS3ListObjectsError { bucket, error });
+                return Ok(());
+            }
+        };
+
+        let pending = checkpoint.update(listed);
+        if std::mem::take(skip_existing) {
+            debug!(
+                message = "Skipping the objects already in the bucket.",
+                count = pending.len()
+            );
+            checkpoint.objects.extend(pending);
+            return Ok(());
+        }
+
+        for (key, e_tag) in pending {
+            let result = self
+                .reader
+                .read_object(&mut self.out, bucket, &key, self.ingestor.region.as_ref())
+                .await;
+            match result {
+                // Rejected events aren't retried, like the messages deleted by the `sqs` strategy.
+                Ok(None | Some(BatchStatus::Delivered | BatchStatus::Rejected)) => {
+                    checkpoint.objects.insert(key, e_tag);
+                }
+                // The object is read again on the next round.
+                Ok(Some(BatchStatus::Errored)) => {
+                    emit!(AwsS3ObjectProcessingError {
+                        key: &key,
+                        error: &ProcessingError::ErrorAcknowledgement,
+                    });
+                }
+                Err(error @ ProcessingError::PipelineSend { .. }) => {
+                    emit!(AwsS3ObjectProcessingError {
+                        key: &key,
+                        error: &error,
+                    });
+                    return Err(());
+                }
+                Err(error) => {
+                    emit!(AwsS3ObjectProcessingError {
+                        key: &key,
+                        error: &error,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn objects(objects: &[(&str, &str)]) -> BTreeMap<String, String> {
+        objects
+            .iter()
+            .map(|(key, e_tag)| (key.to_string(), e_tag.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn checkpoint_tracks_new_and_modified_objects() {
+        let mut checkpoint = Checkpoint {
+            objects: objects(&[("a.log", "1"), ("b.log", "1"), ("deleted.log", "1")]),
+        };
+
+        let pending = checkpoint.update(objects(&[("a.log", "1"), ("b.log", "2"), ("c.log", "1")]));
+
+        assert_eq!(
+            pending,
+            vec![
+                ("b.log".to_owned(), "2".to_owned()),
+                ("c.log".to_owned(), "1".to_owned())
+            ]
+        );
+        assert_eq!(
+            checkpoint.objects,
+            objects(&[("a.log", "1"), ("b.log", "1")])
+        );
+    }
+
+    #[tokio::test]
+    async fn checkpoint_round_trips() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join(CHECKPOINT_FILE_NAME);
+        assert_eq!(Checkpoint::load(&path).await.unwrap(), None);
+
+        let checkpoint = Checkpoint {
+            objects: objects(&[("logs/a.log", "\"9a0364b9e99bb480dd25e1f0284c8555\"")]),
+        };
+        checkpoint.save(&path).await.unwrap();
+        checkpoint.save(&path).await.unwrap();
+
+        assert_eq!(Checkpoint::load(&path).await.unwrap(), Some(checkpoint));
+        assert_eq!(std::fs::read_dir(directory.path()).unwrap().count(), 1);
+    }
+}
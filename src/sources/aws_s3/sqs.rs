@@ -319,12 +319,9 @@ impl Ingestor {
 
 pub struct IngestorProcess {
     state: Arc<State>,
+    reader: ObjectReader,
     out: SourceSender,
     shutdown: ShutdownSignal,
-    acknowledgements: bool,
-    log_namespace: LogNamespace,
-    bytes_received: Registered<BytesReceived>,
-    events_received: Registered<EventsReceived>,
 }
 
 impl IngestorProcess {
@@ -335,14 +332,19 @@ impl IngestorProcess {
         log_namespace: LogNamespace,
         acknowledgements: bool,
     ) -> Self {
+        let reader = ObjectReader::new(
+            state.s3_client.clone(),
+            state.compression,
+            state.multiline.clone(),
+            state.decoder.clone(),
+            acknowledgements,
+            log_namespace,
+        );
         Self {
             state,
+            reader,
             out,
             shutdown,
-            acknowledgements,
-            log_namespace,
-            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
-            events_received: register!(EventsReceived),
         }
     }
 
@@ -467,8 +469,7 @@ impl IngestorProcess {
 
     async fn handle_s3_event(&mut self, s3_event: S3Event) -> Result<(), ProcessingError> {
         for record in s3_event.records {
-            self.handle_s3_event_record(record, self.log_namespace)
-                .await?
+            self.handle_s3_event_record(record).await?
         }
         Ok(())
     }
@@ -476,7 +477,6 @@ impl IngestorProcess {
     async fn handle_s3_event_record(
         &mut self,
         s3_event: S3EventRecord,
-        log_namespace: LogNamespace,
     ) -> Result<(), ProcessingError> {
         let event_version: semver::Version = s3_event.event_version.clone().into();
         if !SUPPORTED_S3_EVENT_VERSION.matches(&event_version) {
@@ -505,17 +505,109 @@ impl IngestorProcess {
             });
         }
 
+        let status = self
+            .reader
+            .read_object(
+                &mut self.out,
+                &s3_event.s3.bucket.name,
+                &s3_event.s3.object.key,
+                &s3_event.aws_region,
+            )
+            .await?;
+        match status {
+            None | Some(BatchStatus::Delivered) => Ok(()),
+            Some(BatchStatus::Errored) => Err(ProcessingError::ErrorAcknowledgement),
+            Some(BatchStatus::Rejected) => {
+                if self.state.delete_failed_message {
+                    Ok(())
+                } else {
+                    Err(ProcessingError::ErrorAcknowledgement)
+                }
+            }
+        }
+    }
+
+    async fn receive_messages(
+        &mut self,
+    ) -> Result<Vec<Message>, SdkError<ReceiveMessageError, HttpResponse>> {
+        self.state
+            .sqs_client
+            .receive_message()
+            .queue_url(self.state.queue_url.clone())
+            .max_number_of_messages(self.state.max_number_of_messages)
+            .visibility_timeout(self.state.visibility_timeout_secs)
+            .wait_time_seconds(self.state.poll_secs)
+            .send()
+            .map_ok(|res| res.messages.unwrap_or_default())
+            .await
+    }
+
+    async fn delete_messages(
+        &mut self,
+        entries: Vec<DeleteMessageBatchRequestEntry>,
+    ) -> Result<DeleteMessageBatchOutput, SdkError<DeleteMessageBatchError, HttpResponse>> {
+        self.state
+            .sqs_client
+            .delete_message_batch()
+            .queue_url(self.state.queue_url.clone())
+            .set_entries(Some(entries))
+            .send()
+            .await
+    }
+}
+
+/// Reads objects from S3 and sends their events, for any strategy consuming the objects.
+pub(super) struct ObjectReader {
+    s3_client: S3Client,
+    compression: super::Compression,
+    multiline: Option<line_agg::Config>,
+    decoder: Decoder,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+}
+
+impl ObjectReader {
+    pub(super) fn new(
+        s3_client: S3Client,
+        compression: super::Compression,
+        multiline: Option<line_agg::Config>,
+        decoder: Decoder,
+        acknowledgements: bool,
+        log_namespace: LogNamespace,
+    ) -> Self {
+        Self {
+            s3_client,
+            compression,
+            multiline,
+            decoder,
+            acknowledgements,
+            log_namespace,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+            events_received: register!(EventsReceived),
+        }
+    }
+
+    /// Fetches an object and sends its events, returning the status of their delivery when the
+    /// events are acknowledged.
+    pub(super) async fn read_object(
+        &self,
+        out: &mut SourceSender,
+        bucket: &str,
+        key: &str,
+        region: &str,
+    ) -> Result<Option<BatchStatus>, ProcessingError> {
         let object_result = self
-            .state
             .s3_client
             .get_object()
-            .bucket(s3_event.s3.bucket.name.clone())
-            .key(s3_event.s3.object.key.clone())
+            .bucket(bucket.to_owned())
+            .key(key.to_owned())
             .send()
             .await
             .context(GetObjectSnafu {
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
             });
 
         let object = object_result?;
@@ -530,8 +622,8 @@ impl IngestorProcess {
 
         let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
         let object_reader = super::s3_object_decoder(
-            self.state.compression,
-            &s3_event.s3.object.key,
+            self.compression,
+            key,
             object.content_encoding.as_deref(),
             object.content_type.as_deref(),
             object.body,
@@ -554,7 +646,7 @@ impl IngestorProcess {
         let bytes_received = self.bytes_received.clone();
         let events_received = self.events_received.clone();
         let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
-            FramedRead::new(object_reader, self.state.decoder.framer.clone())
+            FramedRead::new(object_reader, self.decoder.framer.clone())
                 .map(|res| {
                     res.map(|bytes| {
                         bytes_received.emit(ByteSize(bytes.len()));
@@ -569,7 +661,7 @@ impl IngestorProcess {
                 .map(|r| r.expect("validated by take_while")),
         );
 
-        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = match &self.state.multiline {
+        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = match &self.multiline {
             Some(config) => Box::new(
                 LineAgg::new(
                     lines.map(|line| ((), line, ())),
@@ -581,7 +673,7 @@ impl IngestorProcess {
         };
 
         let mut stream = lines.flat_map(|line| {
-            let events = match self.state.decoder.deserializer_parse(line) {
+            let events = match self.decoder.deserializer_parse(line) {
                 Ok((events, _events_size)) => events,
                 Err(_error) => {
                     // Error is handled by `codecs::Decoder`, no further handling
@@ -597,8 +689,10 @@ impl IngestorProcess {
                     if let Some(log_event) = event.maybe_as_log_mut() {
                         handle_single_log(
                             log_event,
-                            log_namespace,
-                            &s3_event,
+                            self.log_namespace,
+                            bucket,
+                            key,
+                            region,
                             &metadata,
                             timestamp,
                         );
@@ -610,7 +704,7 @@ impl IngestorProcess {
             futures::stream::iter(events)
         });
 
-        let send_error = match self.out.send_event_stream(&mut stream).await {
+        let send_error = match out.send_event_stream(&mut stream).await {
             Ok(_) => None,
             Err(_) => {
                 let (count, _) = stream.size_hint();
@@ -630,69 +724,30 @@ impl IngestorProcess {
         if let Some(error) = read_error {
             Err(ProcessingError::ReadObject {
                 source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
             })
         } else if let Some(error) = send_error {
             Err(ProcessingError::PipelineSend {
                 source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
             })
         } else {
             match receiver {
-                None => Ok(()),
-                Some(receiver) => {
-                    let result = receiver.await;
-                    match result {
-                        BatchStatus::Delivered => Ok(()),
-                        BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
-                        BatchStatus::Rejected => {
-                            if self.state.delete_failed_message {
-                                Ok(())
-                            } else {
-                                Err(ProcessingError::ErrorAcknowledgement)
-                            }
-                        }
-                    }
-                }
+                None => Ok(None),
+                Some(receiver) => Ok(Some(receiver.await)),
             }
         }
     }
-
-    async fn receive_messages(
-        &mut self,
-    ) -> Result<Vec<Message>, SdkError<ReceiveMessageError, HttpResponse>> {
-        self.state
-            .sqs_client
-            .receive_message()
-            .queue_url(self.state.queue_url.clone())
-            .max_number_of_messages(self.state.max_number_of_messages)
-            .visibility_timeout(self.state.visibility_timeout_secs)
-            .wait_time_seconds(self.state.poll_secs)
-            .send()
-            .map_ok(|res| res.messages.unwrap_or_default())
-            .await
-    }
-
-    async fn delete_messages(
-        &mut self,
-        entries: Vec<DeleteMessageBatchRequestEntry>,
-    ) -> Result<DeleteMessageBatchOutput, SdkError<DeleteMessageBatchError, HttpResponse>> {
-        self.state
-            .sqs_client
-            .delete_message_batch()
-            .queue_url(self.state.queue_url.clone())
-            .set_entries(Some(entries))
-            .send()
-            .await
-    }
 }
 
 fn handle_single_log(
     log: &mut LogEvent,
     log_namespace: LogNamespace,
-    s3_event: &S3EventRecord,
+    bucket: &str,
+    key: &str,
+    region: &str,
     metadata: &Option<HashMap<String, String>>,
     timestamp: Option<DateTime<Utc>>,
 ) {
@@ -701,7 +756,7 @@ fn handle_single_log(
         log,
         Some(LegacyKey::Overwrite(path!("bucket"))),
         path!("bucket"),
-        Bytes::from(s3_event.s3.bucket.name.as_bytes().to_vec()),
+        Bytes::from(bucket.as_bytes().to_vec()),
     );
 
     log_namespace.insert_source_metadata(
@@ -709,14 +764,14 @@ fn handle_single_log(
         log,
         Some(LegacyKey::Overwrite(path!("object"))),
         path!("object"),
-        Bytes::from(s3_event.s3.object.key.as_bytes().to_vec()),
+        Bytes::from(key.as_bytes().to_vec()),
     );
     log_namespace.insert_source_metadata(
         AwsS3Config::NAME,
         log,
         Some(LegacyKey::Overwrite(path!("region"))),
         path!("region"),
-        Bytes::from(s3_event.aws_region.as_bytes().to_vec()),
+        Bytes::from(region.as_bytes().to_vec()),
     );
 
     if let Some(metadata) = metadata {
@@ -0,0 +1,293 @@
+//! Stores recording which instance owns each partition, and how far each partition was read.
+//!
+//! The blob store uses the same layout as the Azure SDKs' blob checkpoint stores, so Vector can
+//! take over from, or share a consumer group's checkpoints with, consumers built on those SDKs.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use azure_core::{
+    error::HttpError,
+    request_options::{IfMatchCondition, Metadata},
+};
+use azure_storage_blobs::prelude::*;
+use bytes::Bytes;
+use futures::StreamExt;
+use http::StatusCode;
+
+/// The claim of an instance on a partition.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Ownership {
+    pub(super) partition_id: String,
+    pub(super) owner_id: String,
+    /// When the claim was last renewed.
+    pub(super) last_modified: SystemTime,
+    /// The version of the claim, which must not change between listing and claiming a partition.
+    pub(super) etag: Option<String>,
+}
+
+/// The position up to which a partition was read.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Checkpoint {
+    pub(super) partition_id: String,
+    pub(super) offset: String,
+    pub(super) sequence_number: i64,
+}
+
+#[async_trait::async_trait]
+pub(super) trait CheckpointStore: Send + Sync {
+    /// Lists the claims on the partitions of the consumer group.
+    async fn list_ownership(&self) -> crate::Result<Vec<Ownership>>;
+
+    /// Claims the given partitions, returning the claims that succeeded.
+    ///
+    /// A claim fails when another instance changed the ownership of its partition since it was
+    /// listed.
+    async fn claim_ownership(&self, claims: Vec<Ownership>) -> crate::Result<Vec<Ownership>>;
+
+    /// Lists the checkpoints of the partitions of the consumer group.
+    async fn list_checkpoints(&self) -> crate::Result<Vec<Checkpoint>>;
+
+    async fn update_checkpoint(&self, checkpoint: Checkpoint) -> crate::Result<()>;
+}
+
+/// A checkpoint store kept in memory, used when no checkpoint store is configured.
+///
+/// The instance owns every partition, and reading starts over from the starting position
+/// whenever Vector restarts.
+#[derive(Default)]
+pub(super) struct InMemoryCheckpointStore {
+    ownership: Mutex<HashMap<String, Ownership>>,
+    checkpoints: Mutex<HashMap<String, Checkpoint>>,
+}
+
+#[async_trait::async_trait]
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn list_ownership(&self) -> crate::Result<Vec<Ownership>> {
+        let ownership = self.ownership.lock().expect("poisoned lock");
+        Ok(ownership.values().cloned().collect())
+    }
+
+    async fn claim_ownership(&self, claims: Vec<Ownership>) -> crate::Result<Vec<Ownership>> {
+        let mut ownership = self.ownership.lock().expect("poisoned lock");
+        let mut claimed = Vec::new();
+        for claim in claims {
+            let current = ownership
+                .get(&claim.partition_id)
+                .and_then(|current| current.etag.as_ref());
+            if current != claim.etag.as_ref() {
+                continue;
+            }
+            let claim = Ownership {
+                last_modified: SystemTime::now(),
+                etag: Some(uuid::Uuid::new_v4().to_string()),
+                ..claim
+            };
+            ownership.insert(claim.partition_id.clone(), claim.clone());
+            claimed.push(claim);
+        }
+        Ok(claimed)
+    }
+
+    async fn list_checkpoints(&self) -> crate::Result<Vec<Checkpoint>> {
+        let checkpoints = self.checkpoints.lock().expect("poisoned lock");
+        Ok(checkpoints.values().cloned().collect())
+    }
+
+    async fn update_checkpoint(&self, checkpoint: Checkpoint) -> crate::Result<()> {
+        let mut checkpoints = self.checkpoints.lock().expect("poisoned lock");
+        checkpoints.insert(checkpoint.partition_id.clone(), checkpoint);
+        Ok(())
+    }
+}
+
+/// A checkpoint store keeping one empty blob per claim and per checkpoint, with their contents
+/// in the blobs' metadata.
+pub(super) struct BlobCheckpointStore {
+    client: Arc<ContainerClient>,
+    /// The prefix of the blobs of the consumer group, as `<namespace>/<event hub>/<group>`.
+    prefix: String,
+}
+
+impl BlobCheckpointStore {
+    pub(super) fn new(
+        client: Arc<ContainerClient>,
+        namespace: &str,
+        event_hub_name: &str,
+        consumer_group: &str,
+    ) -> Self {
+        Self {
+            client,
+            prefix: format!(
+                "{}/{}/{}",
+                namespace.to_lowercase(),
+                event_hub_name.to_lowercase(),
+                consumer_group.to_lowercase()
+            ),
+        }
+    }
+
+    fn blob_name(&self, kind: &str, partition_id: &str) -> String {
+        format!("{}/{kind}/{partition_id}", self.prefix)
+    }
+
+    /// Lists the blobs of the given kind, with the ID of the partition each one is for.
+    async fn list(&self, kind: &str) -> crate::Result<Vec<(String, Blob)>> {
+        let mut pages = self
+            .client
+            .list_blobs()
+            .prefix(format!("{}/{kind}/", self.prefix))
+            .include_metadata(true)
+            .into_stream();
+
+        let mut blobs = Vec::new();
+        while let Some(page) = pages.next().await {
+            for blob in page?.blobs.blobs() {
+                if let Some(partition_id) = blob.name.rsplit('/').next() {
+                    blobs.push((partition_id.to_owned(), blob.clone()));
+                }
+            }
+        }
+        Ok(blobs)
+    }
+}
+
+#[async_trait::async_trait]
+impl CheckpointStore for BlobCheckpointStore {
+    async fn list_ownership(&self) -> crate::Result<Vec<Ownership>> {
+        Ok(self
+            .list("ownership")
+            .await?
+            .into_iter()
+            .map(|(partition_id, blob)| Ownership {
+                partition_id,
+                owner_id: metadata_value(&blob, "ownerid").unwrap_or_default(),
+                last_modified: blob.properties.last_modified.into(),
+                etag: Some(blob.properties.etag.to_string()),
+            })
+            .collect())
+    }
+
+    async fn claim_ownership(&self, claims: Vec<Ownership>) -> crate::Result<Vec<Ownership>> {
+        let mut claimed = Vec::new();
+        for claim in claims {
+            let mut metadata = Metadata::new();
+            metadata.insert("ownerid", claim.owner_id.clone());
+
+            // Partitions that were never claimed must still have no claim blob.
+            let condition = match &claim.etag {
+                Some(etag) => IfMatchCondition::Match(etag.clone()),
+                None => IfMatchCondition::NotMatch("*".to_owned()),
+            };
+            let response = self
+                .client
+                .blob_client(self.blob_name("ownership", &claim.partition_id))
+                .put_block_blob(Bytes::new())
+                .metadata(metadata)
+                .if_match(condition)
+                .into_future()
+                .await;
+
+            match response {
+                Ok(response) => claimed.push(Ownership {
+                    last_modified: response.last_modified.into(),
+                    etag: Some(response.etag),
+                    ..claim
+                }),
+                Err(error) if is_conflict(&error) => {
+                    debug!(
+                        message = "Partition was claimed by another instance.",
+                        partition_id = %claim.partition_id,
+                    );
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+        Ok(claimed)
+    }
+
+    async fn list_checkpoints(&self) -> crate::Result<Vec<Checkpoint>> {
+        Ok(self
+            .list("checkpoint")
+            .await?
+            .into_iter()
+            .filter_map(|(partition_id, blob)| {
+                Some(Checkpoint {
+                    partition_id,
+                    offset: metadata_value(&blob, "offset")?,
+                    sequence_number: metadata_value(&blob, "sequencenumber")?.parse().ok()?,
+                })
+            })
+            .collect())
+    }
+
+    async fn update_checkpoint(&self, checkpoint: Checkpoint) -> crate::Result<()> {
+        let mut metadata = Metadata::new();
+        metadata.insert("offset", checkpoint.offset);
+        metadata.insert("sequencenumber", checkpoint.sequence_number.to_string());
+
+        self.client
+            .blob_client(self.blob_name("checkpoint", &checkpoint.partition_id))
+            .put_block_blob(Bytes::new())
+            .metadata(metadata)
+            .into_future()
+            .await?;
+        Ok(())
+    }
+}
+
+fn metadata_value(blob: &Blob, key: &str) -> Option<String> {
+    blob.metadata.as_ref()?.get(key).cloned()
+}
+
+/// Whether a conditional write failed because the blob changed or already exists.
+fn is_conflict(error: &azure_core::Error) -> bool {
+    error.downcast_ref::<HttpError>().is_some_and(|error| {
+        let status = u16::from(error.status());
+        status == StatusCode::PRECONDITION_FAILED.as_u16()
+            || status == StatusCode::CONFLICT.as_u16()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim(partition_id: &str, owner_id: &str, etag: Option<String>) -> Ownership {
+        Ownership {
+            partition_id: partition_id.to_owned(),
+            owner_id: owner_id.to_owned(),
+            last_modified: SystemTime::now(),
+            etag,
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_outdated_claims() {
+        let store = InMemoryCheckpointStore::default();
+
+        let claimed = store
+            .claim_ownership(vec![claim("0", "a", None)])
+            .await
+            .unwrap();
+        assert_eq!(claimed.len(), 1);
+
+        // Another instance claiming the partition as unowned loses the race.
+        let claimed_by_b = store
+            .claim_ownership(vec![claim("0", "b", None)])
+            .await
+            .unwrap();
+        assert!(claimed_by_b.is_empty());
+
+        // Claims made with the current version succeed.
+        let stolen = store
+            .claim_ownership(vec![claim("0", "b", claimed[0].etag.clone())])
+            .await
+            .unwrap();
+        assert_eq!(stolen.len(), 1);
+        assert_eq!(store.list_ownership().await.unwrap()[0].owner_id, "b");
+    }
+}
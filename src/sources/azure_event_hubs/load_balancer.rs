@@ -0,0 +1,241 @@
+//! Balances the partitions of an Event Hub between the instances reading a consumer group.
+//!
+//! Every balancing cycle, each instance renews its claims and, while it owns fewer partitions
+//! than its share, claims unowned partitions up to its share. Once no partition is left
+//! unowned, it steals one partition per cycle from the instance owning the most, which lets the
+//! instances converge on an even split without fighting over partitions.
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, SystemTime},
+};
+
+use rand::seq::SliceRandom;
+
+use super::checkpoint::Ownership;
+
+/// Chooses the claims the instance `owner_id` should make this cycle.
+///
+/// Claims held by instances that didn't renew them within `expiration` are treated as abandoned.
+pub(super) fn claims(
+    owner_id: &str,
+    partition_ids: &[String],
+    ownerships: &[Ownership],
+    expiration: Duration,
+    now: SystemTime,
+) -> Vec<Ownership> {
+    let claim = |partition_id: &str| Ownership {
+        partition_id: partition_id.to_owned(),
+        owner_id: owner_id.to_owned(),
+        last_modified: now,
+        etag: ownerships
+            .iter()
+            .find(|ownership| ownership.partition_id == partition_id)
+            .and_then(|ownership| ownership.etag.clone()),
+    };
+
+    let partitions = partition_ids.iter().collect::<HashSet<_>>();
+    let active = ownerships
+        .iter()
+        .filter(|ownership| {
+            !ownership.owner_id.is_empty()
+                && partitions.contains(&ownership.partition_id)
+                && now
+                    .duration_since(ownership.last_modified)
+                    .unwrap_or_default()
+                    < expiration
+        })
+        .collect::<Vec<_>>();
+
+    let mut by_owner = HashMap::<&str, Vec<&str>>::new();
+    by_owner.entry(owner_id).or_default();
+    for ownership in &active {
+        by_owner
+            .entry(ownership.owner_id.as_str())
+            .or_default()
+            .push(ownership.partition_id.as_str());
+    }
+
+    let mine = &by_owner[owner_id];
+    let mut claims = mine
+        .iter()
+        .map(|partition_id| claim(partition_id))
+        .collect::<Vec<_>>();
+
+    let owners = by_owner.len();
+    let min = partition_ids.len() / owners;
+    let remainder = partition_ids.len() % owners;
+    let max = if remainder == 0 { min } else { min + 1 };
+
+    // Owning `min` partitions leaves room for one more while fewer owners than the remainder
+    // already own `max` of them.
+    let owners_with_max = by_owner.values().filter(|owned| owned.len() > min).count();
+    if mine.len() > min || (mine.len() == min && owners_with_max >= remainder) {
+        return claims;
+    }
+    let share = if owners_with_max < remainder {
+        max
+    } else {
+        min
+    };
+
+    let mut rng = rand::thread_rng();
+    let owned = active
+        .iter()
+        .map(|ownership| ownership.partition_id.as_str())
+        .collect::<HashSet<_>>();
+    let unowned = partition_ids
+        .iter()
+        .map(String::as_str)
+        .filter(|partition_id| !owned.contains(partition_id))
+        .collect::<Vec<_>>();
+    if !unowned.is_empty() {
+        let wanted = share - mine.len();
+        claims.extend(
+            unowned
+                .choose_multiple(&mut rng, wanted)
+                .map(|partition_id| claim(partition_id)),
+        );
+        return claims;
+    }
+
+    // Steal from the instances owning more than their share, or, while this instance has less
+    // than its share, from those owning the most.
+    let mut candidates = by_owner
+        .values()
+        .filter(|owned| owned.len() > max)
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>();
+    if candidates.is_empty() && mine.len() < min {
+        candidates = by_owner
+            .values()
+            .filter(|owned| owned.len() == max)
+            .flatten()
+            .copied()
+            .collect();
+    }
+    if let Some(partition_id) = candidates.choose(&mut rng) {
+        claims.push(claim(partition_id));
+    }
+    claims
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPIRATION: Duration = Duration::from_secs(60);
+
+    fn partition_ids(count: usize) -> Vec<String> {
+        (0..count).map(|id| id.to_string()).collect()
+    }
+
+    fn ownership(partition_id: usize, owner_id: &str, age: Duration) -> Ownership {
+        Ownership {
+            partition_id: partition_id.to_string(),
+            owner_id: owner_id.to_owned(),
+            last_modified: SystemTime::now() - age,
+            etag: Some(format!("{owner_id}-{partition_id}")),
+        }
+    }
+
+    fn claimed(claims: &[Ownership]) -> Vec<&str> {
+        let mut claimed = claims
+            .iter()
+            .map(|claim| claim.partition_id.as_str())
+            .collect::<Vec<_>>();
+        claimed.sort_unstable();
+        claimed
+    }
+
+    #[test]
+    fn claims_unowned_partitions_up_to_its_share() {
+        let claims_a = claims(
+            "a",
+            &partition_ids(4),
+            &[ownership(0, "a", Duration::ZERO)],
+            EXPIRATION,
+            SystemTime::now(),
+        );
+        assert_eq!(claimed(&claims_a), ["0", "1", "2", "3"]);
+        assert_eq!(claims_a[0].etag.as_deref(), Some("a-0"));
+        assert!(claims_a[1..].iter().all(|claim| claim.etag.is_none()));
+
+        let claims_b = claims(
+            "b",
+            &partition_ids(5),
+            &[
+                ownership(0, "a", Duration::ZERO),
+                ownership(1, "a", Duration::ZERO),
+                ownership(2, "a", Duration::ZERO),
+            ],
+            EXPIRATION,
+            SystemTime::now(),
+        );
+        assert_eq!(claimed(&claims_b), ["3", "4"]);
+    }
+
+    #[test]
+    fn claims_expired_partitions() {
+        let claims = claims(
+            "a",
+            &partition_ids(2),
+            &[
+                ownership(0, "a", Duration::ZERO),
+                ownership(1, "b", Duration::from_secs(120)),
+            ],
+            EXPIRATION,
+            SystemTime::now(),
+        );
+
+        assert_eq!(claimed(&claims), ["0", "1"]);
+        assert_eq!(claims[1].etag.as_deref(), Some("b-1"));
+    }
+
+    #[test]
+    fn steals_from_the_most_loaded_instance() {
+        let ownerships = (0..4)
+            .map(|partition_id| ownership(partition_id, "b", Duration::ZERO))
+            .collect::<Vec<_>>();
+
+        let claims = claims(
+            "a",
+            &partition_ids(4),
+            &ownerships,
+            EXPIRATION,
+            SystemTime::now(),
+        );
+
+        assert_eq!(claims.len(), 1);
+        assert_eq!(claims[0].owner_id, "a");
+        assert!(claims[0].etag.as_deref().unwrap().starts_with("b-"));
+    }
+
+    #[test]
+    fn keeps_balanced_claims() {
+        let ownerships = [
+            ownership(0, "a", Duration::ZERO),
+            ownership(1, "a", Duration::ZERO),
+            ownership(2, "b", Duration::ZERO),
+        ];
+
+        let claims_a = claims(
+            "a",
+            &partition_ids(3),
+            &ownerships,
+            EXPIRATION,
+            SystemTime::now(),
+        );
+        assert_eq!(claimed(&claims_a), ["0", "1"]);
+
+        let claims_b = claims(
+            "b",
+            &partition_ids(3),
+            &ownerships,
+            EXPIRATION,
+            SystemTime::now(),
+        );
+        assert_eq!(claimed(&claims_b), ["2"]);
+    }
+}
@@ -0,0 +1,501 @@
+//! The `azure_event_hubs` source.
+//!
+//! Events are received from an Event Hub over AMQP 1.0, with one connection per partition. The
+//! partitions of the Event Hub are balanced between the Vector instances reading the same
+//! consumer group through claims kept in a blob checkpoint store, where the position read up to
+//! in each partition is also recorded.
+
+mod checkpoint;
+mod load_balancer;
+mod partition;
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use futures::FutureExt;
+use tokio::task::JoinHandle;
+use vector_lib::{
+    codecs::decoding::{DeserializerConfig, FramingConfig},
+    config::{log_schema, LegacyKey, LogNamespace, SourceAcknowledgementsConfig},
+    configurable::configurable_component,
+    lookup::owned_value_path,
+    sensitive_string::SensitiveString,
+};
+use vrl::value::Kind;
+
+use self::{
+    checkpoint::{BlobCheckpointStore, CheckpointStore, InMemoryCheckpointStore},
+    partition::{PartitionReceiver, Position},
+};
+use crate::{
+    azure_event_hubs::{AuthorizedConnection, EventHubsConnectionConfig, EventHubsConnector},
+    codecs::{Decoder, DecodingConfig},
+    config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    internal_events::AzureEventHubsLoadBalancingError,
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    sinks::azure_common::config::build_client,
+    SourceSender,
+};
+
+/// Where reading partitions starts when they have no checkpoint.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartPosition {
+    /// Read the events already in the partition, from the oldest one retained.
+    Earliest,
+
+    /// Only read events enqueued after reading the partition started.
+    #[default]
+    Latest,
+}
+
+/// The checkpoint store balancing partitions between instances and recording what was read.
+///
+/// Claims and checkpoints are kept as blobs named after the Event Hubs namespace, the Event Hub
+/// and the consumer group, in the same layout as the checkpoint stores of the Azure SDKs.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CheckpointStoreConfig {
+    /// The Azure Blob Storage Account connection string.
+    ///
+    /// Either `storage_account`, or this field, must be specified.
+    #[configurable(metadata(
+        docs::examples = "DefaultEndpointsProtocol=https;AccountName=mycheckpoints;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"
+    ))]
+    pub connection_string: Option<SensitiveString>,
+
+    /// The Azure Blob Storage Account name.
+    ///
+    /// Credentials are loaded from the environment, a managed identity or the Azure CLI.
+    ///
+    /// Either `connection_string`, or this field, must be specified.
+    #[configurable(metadata(docs::examples = "mycheckpoints"))]
+    pub storage_account: Option<String>,
+
+    /// The Azure Blob Storage Endpoint URL.
+    ///
+    /// This may only be used with `storage_account` and is ignored when used with
+    /// `connection_string`.
+    #[configurable(metadata(docs::examples = "https://mycheckpoints.blob.core.windows.net/"))]
+    pub endpoint: Option<String>,
+
+    /// The Azure Blob Storage Account container name.
+    #[configurable(metadata(docs::examples = "event-hubs-checkpoints"))]
+    pub container_name: String,
+}
+
+/// Configuration for the `azure_event_hubs` source.
+#[configurable_component(source(
+    "azure_event_hubs",
+    "Collect events from Azure Event Hubs over AMQP."
+))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+pub struct AzureEventHubsSourceConfig {
+    #[serde(flatten)]
+    #[configurable(derived)]
+    pub connection: EventHubsConnectionConfig,
+
+    /// The consumer group the Event Hub is read as.
+    ///
+    /// Vector instances reading the same consumer group with the same checkpoint store share the
+    /// partitions of the Event Hub between them.
+    #[serde(default = "default_consumer_group")]
+    #[derivative(Default(value = "default_consumer_group()"))]
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub consumer_group: String,
+
+    /// The checkpoint store used to balance partitions and record what was read.
+    ///
+    /// Without a checkpoint store, this instance reads every partition, and reading starts over
+    /// from `start_position` when Vector restarts.
+    pub checkpoint_store: Option<CheckpointStoreConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub start_position: StartPosition,
+
+    /// How often, in seconds, claims on partitions are renewed and partitions are rebalanced.
+    #[serde(default = "default_load_balancing_interval_secs")]
+    #[derivative(Default(value = "default_load_balancing_interval_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::advanced))]
+    pub load_balancing_interval_secs: u64,
+
+    /// How long, in seconds, claims on partitions last without being renewed.
+    ///
+    /// Partitions of instances that stopped are taken over once their claims expire.
+    #[serde(default = "default_ownership_expiration_secs")]
+    #[derivative(Default(value = "default_ownership_expiration_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::advanced))]
+    pub ownership_expiration_secs: u64,
+
+    /// How often, in seconds, the position read up to in each partition is checkpointed.
+    #[serde(default = "default_checkpoint_interval_secs")]
+    #[derivative(Default(value = "default_checkpoint_interval_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::advanced))]
+    pub checkpoint_interval_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+}
+
+fn default_consumer_group() -> String {
+    "$Default".into()
+}
+
+const fn default_load_balancing_interval_secs() -> u64 {
+    10
+}
+
+const fn default_ownership_expiration_secs() -> u64 {
+    60
+}
+
+const fn default_checkpoint_interval_secs() -> u64 {
+    5
+}
+
+impl GenerateConfig for AzureEventHubsSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"connection_string = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=RootManageSharedAccessKey;SharedAccessKey=${EVENT_HUBS_KEY};EntityPath=myhub"
+            consumer_group = "$Default""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_event_hubs")]
+impl SourceConfig for AzureEventHubsSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let connector = EventHubsConnector::new(&self.connection)?;
+        let store: Arc<dyn CheckpointStore> = match &self.checkpoint_store {
+            Some(config) => {
+                let client = build_client(
+                    config
+                        .connection_string
+                        .as_ref()
+                        .map(|connection_string| connection_string.inner().to_owned()),
+                    config.storage_account.clone(),
+                    config.container_name.clone(),
+                    config.endpoint.clone(),
+                )?;
+                Arc::new(BlobCheckpointStore::new(
+                    client,
+                    connector.namespace(),
+                    connector.event_hub_name(),
+                    &self.consumer_group,
+                ))
+            }
+            None => Arc::new(InMemoryCheckpointStore::default()),
+        };
+
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        let source = EventHubsSource {
+            connector: Arc::new(connector),
+            store,
+            owner_id: uuid::Uuid::new_v4().to_string(),
+            consumer_group: self.consumer_group.clone(),
+            start_position: self.start_position,
+            load_balancing_interval: Duration::from_secs(self.load_balancing_interval_secs),
+            ownership_expiration: Duration::from_secs(self.ownership_expiration_secs),
+            checkpoint_interval: Duration::from_secs(self.checkpoint_interval_secs),
+            decoder,
+            log_namespace,
+            acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+        };
+        Ok(source.run(cx.out, cx.shutdown).boxed())
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                log_schema()
+                    .timestamp_key()
+                    .cloned()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp().or_undefined(),
+                Some("timestamp"),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("partition_id"))),
+                &owned_value_path!("partition_id"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("offset"))),
+                &owned_value_path!("offset"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(
+                    "sequence_number"
+                ))),
+                &owned_value_path!("sequence_number"),
+                Kind::integer(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("partition_key"))),
+                &owned_value_path!("partition_key"),
+                Kind::bytes().or_undefined(),
+                None,
+            );
+
+        vec![SourceOutput::new_maybe_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+struct EventHubsSource {
+    connector: Arc<EventHubsConnector>,
+    store: Arc<dyn CheckpointStore>,
+    /// The ID this instance claims partitions with.
+    owner_id: String,
+    consumer_group: String,
+    start_position: StartPosition,
+    load_balancing_interval: Duration,
+    ownership_expiration: Duration,
+    checkpoint_interval: Duration,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+}
+
+impl EventHubsSource {
+    async fn run(self, out: SourceSender, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        let mut receivers = HashMap::<String, JoinHandle<()>>::new();
+        let mut management = None;
+        let mut interval = tokio::time::interval(self.load_balancing_interval);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = interval.tick() => {}
+            }
+
+            // Partitions whose receiver stopped are started again if they are still owned.
+            receivers.retain(|_, receiver| !receiver.is_finished());
+
+            let owned = match self.balance(&mut management).await {
+                Ok(owned) => owned,
+                Err(error) => {
+                    emit!(AzureEventHubsLoadBalancingError { error });
+                    management = None;
+                    continue;
+                }
+            };
+
+            receivers.retain(|partition_id, receiver| {
+                let keep = owned.contains(partition_id);
+                if !keep {
+                    debug!(message = "Partition was claimed by another instance.", %partition_id);
+                    receiver.abort();
+                }
+                keep
+            });
+
+            let started = owned
+                .into_iter()
+                .filter(|partition_id| !receivers.contains_key(partition_id))
+                .collect::<Vec<_>>();
+            if started.is_empty() {
+                continue;
+            }
+            let checkpoints = match self.store.list_checkpoints().await {
+                Ok(checkpoints) => checkpoints,
+                Err(error) => {
+                    emit!(AzureEventHubsLoadBalancingError { error });
+                    continue;
+                }
+            };
+            for partition_id in started {
+                let position = checkpoints
+                    .iter()
+                    .find(|checkpoint| checkpoint.partition_id == partition_id)
+                    .map_or(Position::Start(self.start_position), |checkpoint| {
+                        Position::After(checkpoint.offset.clone())
+                    });
+                let receiver = PartitionReceiver {
+                    connector: Arc::clone(&self.connector),
+                    store: Arc::clone(&self.store),
+                    consumer_group: self.consumer_group.clone(),
+                    partition_id: partition_id.clone(),
+                    decoder: self.decoder.clone(),
+                    log_namespace: self.log_namespace,
+                    acknowledgements: self.acknowledgements,
+                    checkpoint_interval: self.checkpoint_interval,
+                    out: out.clone(),
+                };
+                let receiver = tokio::spawn(receiver.run(position, shutdown.clone()));
+                receivers.insert(partition_id, receiver);
+            }
+        }
+
+        // Receivers stop on shutdown, after checkpointing what they read.
+        for (_, receiver) in receivers {
+            _ = receiver.await;
+        }
+        Ok(())
+    }
+
+    /// Renews and makes claims on partitions, returning the partitions this instance owns.
+    async fn balance(
+        &self,
+        management: &mut Option<AuthorizedConnection>,
+    ) -> crate::Result<Vec<String>> {
+        let connection = match management {
+            Some(connection) => {
+                self.connector.refresh(connection).await?;
+                connection
+            }
+            None => management.insert(self.connector.connect().await?),
+        };
+        let partition_ids = self.connector.partition_ids(connection).await?;
+
+        let ownerships = self.store.list_ownership().await?;
+        let claims = load_balancer::claims(
+            &self.owner_id,
+            &partition_ids,
+            &ownerships,
+            self.ownership_expiration,
+            SystemTime::now(),
+        );
+        let claimed = self.store.claim_ownership(claims).await?;
+        Ok(claimed
+            .into_iter()
+            .map(|ownership| ownership.partition_id)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::{lookup::OwnedTargetPath, schema::Definition};
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureEventHubsSourceConfig>();
+    }
+
+    #[test]
+    fn parses_checkpoint_store() {
+        let config: AzureEventHubsSourceConfig = toml::from_str(
+            r#"connection_string = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=listen;SharedAccessKey=secret;EntityPath=myhub"
+            consumer_group = "vector"
+            start_position = "earliest"
+            checkpoint_store.storage_account = "mycheckpoints"
+            checkpoint_store.container_name = "checkpoints""#,
+        )
+        .unwrap();
+
+        assert_eq!(config.start_position, StartPosition::Earliest);
+        assert_eq!(config.load_balancing_interval_secs, 10);
+        let store = config.checkpoint_store.unwrap();
+        assert_eq!(store.storage_account.as_deref(), Some("mycheckpoints"));
+        assert_eq!(store.container_name, "checkpoints");
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let config = AzureEventHubsSourceConfig {
+            log_namespace: Some(true),
+            ..Default::default()
+        };
+
+        let definition = config
+            .outputs(LogNamespace::Vector)
+            .remove(0)
+            .schema_definition(true);
+
+        let expected = Definition::new_with_default_metadata(Kind::bytes(), [LogNamespace::Vector])
+            .with_meaning(OwnedTargetPath::event_root(), "message")
+            .with_metadata_field(
+                &owned_value_path!("vector", "source_type"),
+                Kind::bytes(),
+                None,
+            )
+            .with_metadata_field(
+                &owned_value_path!("vector", "ingest_timestamp"),
+                Kind::timestamp(),
+                None,
+            )
+            .with_metadata_field(
+                &owned_value_path!(AzureEventHubsSourceConfig::NAME, "timestamp"),
+                Kind::timestamp().or_undefined(),
+                Some("timestamp"),
+            )
+            .with_metadata_field(
+                &owned_value_path!(AzureEventHubsSourceConfig::NAME, "partition_id"),
+                Kind::bytes(),
+                None,
+            )
+            .with_metadata_field(
+                &owned_value_path!(AzureEventHubsSourceConfig::NAME, "offset"),
+                Kind::bytes(),
+                None,
+            )
+            .with_metadata_field(
+                &owned_value_path!(AzureEventHubsSourceConfig::NAME, "sequence_number"),
+                Kind::integer(),
+                None,
+            )
+            .with_metadata_field(
+                &owned_value_path!(AzureEventHubsSourceConfig::NAME, "partition_key"),
+                Kind::bytes().or_undefined(),
+                None,
+            );
+
+        assert_eq!(definition, Some(expected));
+    }
+}
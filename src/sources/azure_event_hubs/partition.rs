@@ -0,0 +1,487 @@
+//! Receives the events of a single partition of an Event Hub.
+
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{stream::BoxStream, StreamExt};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_util::codec::FramedRead;
+use vector_lib::{
+    config::{log_schema, LegacyKey, LogNamespace},
+    finalizer::OrderedFinalizer,
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+        Registered,
+    },
+    lookup::path,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use super::{
+    checkpoint::{Checkpoint, CheckpointStore},
+    AzureEventHubsSourceConfig, StartPosition,
+};
+use crate::{
+    azure_event_hubs::{
+        auth::AuthError,
+        codec::{AmqpValue, Message, MessageBody},
+        connection::{Delivery, LinkHandle},
+        AuthorizedConnection, EventHubsConnector,
+    },
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus, Event, LogEvent},
+    internal_events::{
+        AzureEventHubsCheckpointError, AzureEventHubsReceiveError, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    sinks::util::retries::ExponentialBackoff,
+    SourceSender,
+};
+
+const OFFSET_ANNOTATION: &str = "x-opt-offset";
+const SEQUENCE_NUMBER_ANNOTATION: &str = "x-opt-sequence-number";
+const ENQUEUED_TIME_ANNOTATION: &str = "x-opt-enqueued-time";
+const PARTITION_KEY_ANNOTATION: &str = "x-opt-partition-key";
+
+/// The filter selecting where a receiver link starts reading a partition.
+const SELECTOR_FILTER: &str = "apache.org:selector-filter:string";
+const SELECTOR_FILTER_DESCRIPTOR: u64 = 0x0000_468C_0000_0004;
+
+/// The receiver link property making a newer owner of a partition disconnect older ones.
+const EPOCH_PROPERTY: &str = "com.microsoft:epoch";
+
+/// How many messages the Event Hub may send ahead of those Vector has processed.
+const PREFETCH: u32 = 300;
+
+/// Where in the partition reading starts.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Position {
+    /// After the event at the given offset.
+    After(String),
+    Start(StartPosition),
+}
+
+impl Position {
+    fn selector(&self) -> String {
+        let offset = match self {
+            Self::After(offset) => offset.as_str(),
+            Self::Start(StartPosition::Earliest) => "-1",
+            Self::Start(StartPosition::Latest) => "@latest",
+        };
+        format!("amqp.annotation.{OFFSET_ANNOTATION} > '{offset}'")
+    }
+}
+
+pub(super) struct PartitionReceiver {
+    pub(super) connector: Arc<EventHubsConnector>,
+    pub(super) store: Arc<dyn CheckpointStore>,
+    pub(super) consumer_group: String,
+    pub(super) partition_id: String,
+    pub(super) decoder: Decoder,
+    pub(super) log_namespace: LogNamespace,
+    pub(super) acknowledgements: bool,
+    pub(super) checkpoint_interval: Duration,
+    pub(super) out: SourceSender,
+}
+
+impl PartitionReceiver {
+    /// Receives events from `position` until shut down, or until another instance takes the
+    /// partition over.
+    pub(super) async fn run(mut self, mut position: Position, mut shutdown: ShutdownSignal) {
+        let (finalizer, acknowledged) = OrderedFinalizer::<Checkpoint>::maybe_new(
+            self.acknowledgements,
+            Some(shutdown.clone()),
+        );
+        let (sent, sent_checkpoints) = mpsc::unbounded_channel();
+        let checkpoints = futures::stream::select(
+            acknowledged.filter_map(|(status, checkpoint)| async move {
+                (status == BatchStatus::Delivered).then_some(checkpoint)
+            }),
+            UnboundedReceiverStream::new(sent_checkpoints),
+        )
+        .boxed();
+        let checkpointer = tokio::spawn(write_checkpoints(
+            Arc::clone(&self.store),
+            self.partition_id.clone(),
+            checkpoints,
+            self.checkpoint_interval,
+        ));
+
+        let bytes_received = register!(BytesReceived::from(Protocol::from("amqp")));
+        let events_received = register!(EventsReceived);
+        let mut backoff = ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(30));
+        // Claiming the partition later than another instance gives a higher epoch.
+        let epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as i64;
+
+        'connect: loop {
+            let attached = tokio::select! {
+                _ = &mut shutdown => break,
+                attached = self.attach(&position, epoch) => attached,
+            };
+            let (mut connection, link) = match attached {
+                Ok(attached) => attached,
+                Err(error) => {
+                    emit!(AzureEventHubsReceiveError {
+                        partition_id: &self.partition_id,
+                        error: &error,
+                    });
+                    if !error.is_retriable() {
+                        break;
+                    }
+                    tokio::select! {
+                        _ = &mut shutdown => break,
+                        _ = tokio::time::sleep(backoff.next().unwrap()) => continue,
+                    }
+                }
+            };
+            backoff.reset();
+
+            loop {
+                // The connection isn't used again once receiving is cancelled.
+                let delivery = tokio::select! {
+                    _ = &mut shutdown => break 'connect,
+                    delivery = Self::receive(&self.connector, &mut connection, link) => delivery,
+                };
+                let delivery = match delivery {
+                    Ok(delivery) => delivery,
+                    Err(error) => {
+                        emit!(AzureEventHubsReceiveError {
+                            partition_id: &self.partition_id,
+                            error: &error,
+                        });
+                        if !error.is_retriable() {
+                            break 'connect;
+                        }
+                        continue 'connect;
+                    }
+                };
+
+                bytes_received.emit(ByteSize(delivery.payload.len()));
+                let message = match Message::decode(delivery.payload) {
+                    Ok(message) => message,
+                    Err(error) => {
+                        emit!(AzureEventHubsReceiveError {
+                            partition_id: &self.partition_id,
+                            error,
+                        });
+                        continue;
+                    }
+                };
+                let Some(checkpoint) = self.checkpoint(&message) else {
+                    emit!(AzureEventHubsReceiveError {
+                        partition_id: &self.partition_id,
+                        error: "Message has no offset.",
+                    });
+                    continue;
+                };
+                position = Position::After(checkpoint.offset.clone());
+
+                let events = self.decode(message, &checkpoint, &events_received).await;
+                if !self.send(events, checkpoint, &finalizer, &sent).await {
+                    break 'connect;
+                }
+            }
+        }
+
+        drop(finalizer);
+        drop(sent);
+        _ = checkpointer.await;
+    }
+
+    async fn attach(
+        &self,
+        position: &Position,
+        epoch: i64,
+    ) -> Result<(AuthorizedConnection, LinkHandle), AuthError> {
+        let mut connection = self.connector.connect().await?;
+        let address = format!(
+            "{}/ConsumerGroups/{}/Partitions/{}",
+            self.connector.event_hub_name(),
+            self.consumer_group,
+            self.partition_id
+        );
+        let filter = AmqpValue::Map(vec![(
+            AmqpValue::symbol(SELECTOR_FILTER),
+            AmqpValue::described(
+                SELECTOR_FILTER_DESCRIPTOR,
+                AmqpValue::string(position.selector()),
+            ),
+        )]);
+        let name = format!("vector-receiver-{}", uuid::Uuid::new_v4());
+        let link = connection
+            .connection
+            .attach_receiver(
+                &name,
+                &address,
+                Some(filter),
+                vec![(AmqpValue::symbol(EPOCH_PROPERTY), AmqpValue::Long(epoch))],
+                PREFETCH,
+            )
+            .await?;
+
+        debug!(
+            message = "Receiving from partition.",
+            partition_id = %self.partition_id,
+            position = %position.selector(),
+        );
+        Ok((connection, link))
+    }
+
+    async fn receive(
+        connector: &EventHubsConnector,
+        connection: &mut AuthorizedConnection,
+        link: LinkHandle,
+    ) -> Result<Delivery, AuthError> {
+        connector.refresh(connection).await?;
+        let delivery = connection.connection.receive(link).await?;
+        if !delivery.settled {
+            connection.connection.accept(delivery.delivery_id).await?;
+        }
+        Ok(delivery)
+    }
+
+    fn checkpoint(&self, message: &Message) -> Option<Checkpoint> {
+        let offset = match message.annotation(OFFSET_ANNOTATION)? {
+            AmqpValue::String(offset) => offset.clone(),
+            offset => offset.as_i64()?.to_string(),
+        };
+        Some(Checkpoint {
+            partition_id: self.partition_id.clone(),
+            offset,
+            sequence_number: message
+                .annotation(SEQUENCE_NUMBER_ANNOTATION)
+                .and_then(AmqpValue::as_i64)
+                .unwrap_or_default(),
+        })
+    }
+
+    async fn decode(
+        &self,
+        message: Message,
+        checkpoint: &Checkpoint,
+        events_received: &Registered<EventsReceived>,
+    ) -> Vec<Event> {
+        let enqueued_time = match message.annotation(ENQUEUED_TIME_ANNOTATION) {
+            Some(AmqpValue::Timestamp(millis)) => Utc.timestamp_millis_opt(*millis).latest(),
+            _ => None,
+        };
+        let partition_key = message
+            .annotation(PARTITION_KEY_ANNOTATION)
+            .and_then(AmqpValue::as_str)
+            .map(ToOwned::to_owned);
+        let body = match message.body {
+            MessageBody::Data(data) => Bytes::from(data.concat()),
+            MessageBody::Value(AmqpValue::Binary(data)) => data,
+            MessageBody::Value(AmqpValue::String(data)) => Bytes::from(data),
+            MessageBody::Value(_) | MessageBody::Sequence(_) => {
+                emit!(AzureEventHubsReceiveError {
+                    partition_id: &self.partition_id,
+                    error: "Message body is neither binary data nor a string.",
+                });
+                return Vec::new();
+            }
+        };
+
+        let now = Utc::now();
+        let mut decoded = Vec::new();
+        let mut stream = FramedRead::new(body.as_ref(), self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((events, _byte_size)) => {
+                    events_received.emit(CountByteSize(
+                        events.len(),
+                        events.estimated_json_encoded_size_of(),
+                    ));
+
+                    decoded.extend(events.into_iter().map(|mut event| {
+                        if let Event::Log(ref mut log) = event {
+                            self.enrich(
+                                log,
+                                checkpoint,
+                                partition_key.as_deref(),
+                                enqueued_time,
+                                now,
+                            );
+                        }
+                        event
+                    }));
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+        decoded
+    }
+
+    fn enrich(
+        &self,
+        log: &mut LogEvent,
+        checkpoint: &Checkpoint,
+        partition_key: Option<&str>,
+        enqueued_time: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) {
+        let log_namespace = self.log_namespace;
+        if let Some(enqueued_time) = enqueued_time {
+            log_namespace.insert_source_metadata(
+                AzureEventHubsSourceConfig::NAME,
+                log,
+                log_schema().timestamp_key().map(LegacyKey::Overwrite),
+                path!("timestamp"),
+                enqueued_time,
+            );
+        }
+        log_namespace.insert_standard_vector_source_metadata(
+            log,
+            AzureEventHubsSourceConfig::NAME,
+            now,
+        );
+
+        log_namespace.insert_source_metadata(
+            AzureEventHubsSourceConfig::NAME,
+            log,
+            Some(LegacyKey::InsertIfEmpty(path!("partition_id"))),
+            path!("partition_id"),
+            self.partition_id.clone(),
+        );
+        log_namespace.insert_source_metadata(
+            AzureEventHubsSourceConfig::NAME,
+            log,
+            Some(LegacyKey::InsertIfEmpty(path!("offset"))),
+            path!("offset"),
+            checkpoint.offset.clone(),
+        );
+        log_namespace.insert_source_metadata(
+            AzureEventHubsSourceConfig::NAME,
+            log,
+            Some(LegacyKey::InsertIfEmpty(path!("sequence_number"))),
+            path!("sequence_number"),
+            checkpoint.sequence_number,
+        );
+        if let Some(partition_key) = partition_key {
+            log_namespace.insert_source_metadata(
+                AzureEventHubsSourceConfig::NAME,
+                log,
+                Some(LegacyKey::InsertIfEmpty(path!("partition_key"))),
+                path!("partition_key"),
+                partition_key.to_owned(),
+            );
+        }
+    }
+
+    /// Sends the events of a message, returning whether the pipeline is still accepting events.
+    async fn send(
+        &mut self,
+        events: Vec<Event>,
+        checkpoint: Checkpoint,
+        finalizer: &Option<OrderedFinalizer<Checkpoint>>,
+        sent: &mpsc::UnboundedSender<Checkpoint>,
+    ) -> bool {
+        let count = events.len();
+        let result = match finalizer {
+            Some(finalizer) => {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                let events = events
+                    .into_iter()
+                    .map(|event| event.with_batch_notifier(&batch))
+                    .collect::<Vec<_>>();
+                drop(batch);
+                let result = self.out.send_batch(events).await;
+                if result.is_ok() {
+                    finalizer.add(checkpoint, receiver);
+                }
+                result
+            }
+            None => {
+                let result = self.out.send_batch(events).await;
+                if result.is_ok() {
+                    _ = sent.send(checkpoint);
+                }
+                result
+            }
+        };
+
+        if result.is_err() {
+            emit!(StreamClosedError { count });
+            return false;
+        }
+        true
+    }
+}
+
+/// Writes the latest checkpoint of the partition every `interval`, and once more when reading
+/// the partition stops.
+async fn write_checkpoints(
+    store: Arc<dyn CheckpointStore>,
+    partition_id: String,
+    mut checkpoints: BoxStream<'static, Checkpoint>,
+    interval: Duration,
+) {
+    let mut latest = None;
+    let mut timer = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            checkpoint = checkpoints.next() => match checkpoint {
+                Some(checkpoint) => latest = Some(checkpoint),
+                None => break,
+            },
+            _ = timer.tick() => {
+                if let Some(checkpoint) = latest.take() {
+                    update_checkpoint(&*store, &partition_id, checkpoint).await;
+                }
+            }
+        }
+    }
+    if let Some(checkpoint) = latest {
+        update_checkpoint(&*store, &partition_id, checkpoint).await;
+    }
+}
+
+async fn update_checkpoint(
+    store: &dyn CheckpointStore,
+    partition_id: &str,
+    checkpoint: Checkpoint,
+) {
+    if let Err(error) = store.update_checkpoint(checkpoint).await {
+        emit!(AzureEventHubsCheckpointError {
+            partition_id,
+            error,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_the_starting_offset() {
+        assert_eq!(
+            Position::After("4096".into()).selector(),
+            "amqp.annotation.x-opt-offset > '4096'"
+        );
+        assert_eq!(
+            Position::Start(StartPosition::Earliest).selector(),
+            "amqp.annotation.x-opt-offset > '-1'"
+        );
+        assert_eq!(
+            Position::Start(StartPosition::Latest).selector(),
+            "amqp.annotation.x-opt-offset > '@latest'"
+        );
+    }
+}
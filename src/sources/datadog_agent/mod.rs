@@ -5,6 +5,7 @@ mod tests;
 
 pub mod logs;
 pub mod metrics;
+pub mod profiles;
 pub mod traces;
 
 #[allow(warnings, clippy::pedantic, clippy::nursery)]
@@ -66,6 +67,7 @@ use crate::{
 pub const LOGS: &str = "logs";
 pub const METRICS: &str = "metrics";
 pub const TRACES: &str = "traces";
+pub const PROFILES: &str = "profiles";
 
 /// Configuration for the `datadog_agent` source.
 #[configurable_component(source(
@@ -102,12 +104,20 @@ pub struct DatadogAgentConfig {
     #[serde(default = "crate::serde::default_false")]
     disable_traces: bool,
 
+    /// If this is set to `true`, profiles (alpha) are accepted by the component.
+    ///
+    /// Profiles are passed through as log events whose message is the raw payload sent by the
+    /// Agent, along with its content type, so that they can be forwarded as is.
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default = "crate::serde::default_false")]
+    accept_profiles: bool,
+
     /// If this is set to `true`, logs, metrics (beta), and traces (alpha) are sent to different outputs.
     ///
     ///
     /// For a source component named `agent`, the received logs, metrics (beta), and traces (alpha) can then be
     /// configured as input to other components by specifying `agent.logs`, `agent.metrics`, and
-    /// `agent.traces`, respectively.
+    /// `agent.traces`, respectively. Profiles (alpha), when accepted, are sent to `agent.profiles`.
     #[configurable(metadata(docs::advanced))]
     #[serde(default = "crate::serde::default_false")]
     multiple_outputs: bool,
@@ -155,6 +165,7 @@ impl GenerateConfig for DatadogAgentConfig {
             disable_logs: false,
             disable_metrics: false,
             disable_traces: false,
+            accept_profiles: false,
             multiple_outputs: false,
             parse_ddtags: false,
             log_namespace: Some(false),
@@ -302,6 +313,13 @@ impl SourceConfig for DatadogAgentConfig {
             if !self.disable_traces {
                 output.push(SourceOutput::new_traces().with_port(TRACES))
             }
+            if self.accept_profiles {
+                let definition =
+                    profiles::schema_definition(global_log_namespace.merge(self.log_namespace));
+                output.push(
+                    SourceOutput::new_maybe_logs(DataType::Log, definition).with_port(PROFILES),
+                )
+            }
         } else {
             output.push(SourceOutput::new_maybe_logs(
                 DataType::all_bits(),
@@ -432,6 +450,18 @@ impl DatadogAgentSource {
                 .or(Some(trace_filter));
         }
 
+        if config.accept_profiles {
+            let profiles_filter = profiles::build_warp_filter(
+                acknowledgements,
+                config.multiple_outputs,
+                out.clone(),
+                self.clone(),
+            );
+            filters = filters
+                .map(|f| f.or(profiles_filter.clone()).unify().boxed())
+                .or(Some(profiles_filter));
+        }
+
         if !config.disable_metrics {
             let metrics_filter = metrics::build_warp_filter(
                 acknowledgements,
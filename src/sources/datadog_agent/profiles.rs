@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use chrono::Utc;
+use vector_lib::codecs::decoding::BytesDeserializerConfig;
+use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::internal_event::{CountByteSize, InternalEventHandle as _};
+use vector_lib::lookup::{owned_value_path, path};
+use vector_lib::EstimatedJsonEncodedSizeOf;
+use vrl::value::Kind;
+use warp::{filters::BoxedFilter, path as warp_path, path::FullPath, reply::Response, Filter};
+
+use crate::{
+    event::Event,
+    schema,
+    sources::datadog_agent::{
+        handle_request, ApiKeyQueryParams, DatadogAgentConfig, DatadogAgentSource,
+    },
+    SourceSender,
+};
+
+pub(crate) fn build_warp_filter(
+    acknowledgements: bool,
+    multiple_outputs: bool,
+    out: SourceSender,
+    source: DatadogAgentSource,
+) -> BoxedFilter<(Response,)> {
+    warp::post()
+        .and(warp_path!("api" / "v2" / "profile" / ..))
+        .and(warp::path::full())
+        .and(warp::header::optional::<String>("content-type"))
+        .and(warp::header::optional::<String>("content-encoding"))
+        .and(warp::header::optional::<String>("dd-api-key"))
+        .and(warp::query::<ApiKeyQueryParams>())
+        .and(warp::body::bytes())
+        .and_then(
+            move |path: FullPath,
+                  content_type: Option<String>,
+                  encoding_header: Option<String>,
+                  api_token: Option<String>,
+                  query_params: ApiKeyQueryParams,
+                  body: Bytes| {
+                let events = source
+                    .decode(&encoding_header, body, path.as_str())
+                    .map(|body| {
+                        handle_profile_payload(
+                            body,
+                            content_type,
+                            path.as_str(),
+                            source.api_key_extractor.extract(
+                                path.as_str(),
+                                api_token,
+                                query_params.dd_api_key,
+                            ),
+                            &source,
+                        )
+                    });
+                let output = multiple_outputs.then_some(super::PROFILES);
+                handle_request(events, acknowledgements, out.clone(), output)
+            },
+        )
+        .boxed()
+}
+
+/// Passes the profile through as a single event whose message is the raw payload, along with its
+/// content type, as the multipart boundary of the payload is needed to forward it.
+fn handle_profile_payload(
+    body: Bytes,
+    content_type: Option<String>,
+    path: &str,
+    api_key: Option<Arc<str>>,
+    source: &DatadogAgentSource,
+) -> Vec<Event> {
+    let namespace = source.log_namespace;
+    let mut log = namespace.new_log_from_data(body);
+
+    if let Some(content_type) = content_type {
+        namespace.insert_source_metadata(
+            DatadogAgentConfig::NAME,
+            &mut log,
+            Some(LegacyKey::InsertIfEmpty(path!("content_type"))),
+            path!("content_type"),
+            content_type,
+        );
+    }
+    namespace.insert_source_metadata(
+        DatadogAgentConfig::NAME,
+        &mut log,
+        Some(LegacyKey::InsertIfEmpty(path!("path"))),
+        path!("path"),
+        path.to_owned(),
+    );
+
+    source
+        .events_received
+        .emit(CountByteSize(1, log.estimated_json_encoded_size_of()));
+
+    namespace.insert_standard_vector_source_metadata(
+        &mut log,
+        DatadogAgentConfig::NAME,
+        Utc::now(),
+    );
+
+    if let Some(k) = &api_key {
+        log.metadata_mut().set_datadog_api_key(Arc::clone(k));
+    }
+
+    vec![Event::Log(log)]
+}
+
+/// The schema of the events of passed through profiles.
+pub(crate) fn schema_definition(log_namespace: LogNamespace) -> schema::Definition {
+    BytesDeserializerConfig::new()
+        .schema_definition(log_namespace)
+        .with_source_metadata(
+            DatadogAgentConfig::NAME,
+            Some(LegacyKey::InsertIfEmpty(owned_value_path!("content_type"))),
+            &owned_value_path!("content_type"),
+            Kind::bytes().or_undefined(),
+            None,
+        )
+        .with_source_metadata(
+            DatadogAgentConfig::NAME,
+            Some(LegacyKey::InsertIfEmpty(owned_value_path!("path"))),
+            &owned_value_path!("path"),
+            Kind::bytes(),
+            None,
+        )
+        .with_standard_vector_source_metadata()
+}
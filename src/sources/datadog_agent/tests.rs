@@ -48,7 +48,7 @@ use crate::{
     serde::{default_decoding, default_framing_message_based},
     sources::datadog_agent::{
         ddmetric_proto, ddtrace_proto, logs::decode_log_body, metrics::DatadogSeriesRequest,
-        DatadogAgentConfig, DatadogAgentSource, LogMsg, LOGS, METRICS, TRACES,
+        DatadogAgentConfig, DatadogAgentSource, LogMsg, LOGS, METRICS, PROFILES, TRACES,
     },
     test_util::{
         components::{assert_source_compliance, HTTP_PUSH_SOURCE_TAGS},
@@ -1369,6 +1369,78 @@ async fn decode_traces() {
     .await;
 }
 
+#[tokio::test]
+async fn traces_reject_unsupported_content_type() {
+    let (_, _, _, addr) = source(EventStatus::Delivered, true, true, false).await;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("content-type", "application/msgpack".parse().unwrap());
+
+    assert_eq!(
+        415,
+        send_with_path(addr, "", headers, "/api/v0.2/traces").await
+    );
+}
+
+#[tokio::test]
+async fn decode_profiles() {
+    assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
+        let (mut sender, _) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let rx = sender
+            .add_outputs(EventStatus::Delivered, PROFILES.to_string())
+            .flat_map(into_event_stream);
+        let address = next_addr();
+        let config = toml::from_str::<DatadogAgentConfig>(&format!(
+            indoc! { r#"
+                address = "{}"
+                multiple_outputs = true
+                accept_profiles = true
+            "#},
+            address
+        ))
+        .unwrap();
+        let schema_definitions =
+            HashMap::from([(Some(LOGS.to_owned()), test_logs_schema_definition())]);
+        let context = SourceContext::new_test(sender, Some(schema_definitions));
+        tokio::spawn(async move {
+            config.build(context).await.unwrap().await.unwrap();
+        });
+        wait_for_tcp(address).await;
+
+        let content_type = "multipart/form-data; boundary=d489b8b3dcf4e5e9";
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", content_type.parse().unwrap());
+        headers.insert(
+            "dd-api-key",
+            "12345678abcdefgh12345678abcdefgh".parse().unwrap(),
+        );
+        let body = "--d489b8b3dcf4e5e9\r\nContent-Disposition: form-data; name=\"event\"\r\n\r\n{}\r\n--d489b8b3dcf4e5e9--\r\n";
+
+        let mut events = spawn_collect_n(
+            async move {
+                assert_eq!(
+                    200,
+                    send_with_path(address, body, headers, "/api/v2/profile").await
+                );
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        let event = events.remove(0);
+        let log = event.as_log();
+        assert_eq!(log["message"], body.into());
+        assert_eq!(log["content_type"], content_type.into());
+        assert_eq!(log["path"], "/api/v2/profile".into());
+        assert_eq!(
+            &event.metadata().datadog_api_key().as_ref().unwrap()[..],
+            "12345678abcdefgh12345678abcdefgh"
+        );
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn split_outputs() {
     assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
@@ -1575,6 +1647,7 @@ fn test_config_outputs_with_disabled_data_types() {
             disable_logs,
             disable_metrics,
             disable_traces,
+            accept_profiles: false,
             parse_ddtags: false,
             log_namespace: Some(false),
             keepalive: Default::default(),
@@ -2017,6 +2090,7 @@ fn test_config_outputs() {
             disable_logs: false,
             disable_metrics: false,
             disable_traces: false,
+            accept_profiles: false,
             parse_ddtags: false,
             log_namespace: Some(false),
             keepalive: Default::default(),
@@ -2524,6 +2598,7 @@ impl ValidatableComponent for DatadogAgentConfig {
             disable_logs: false,
             disable_metrics: false,
             disable_traces: false,
+            accept_profiles: false,
             parse_ddtags: false,
             log_namespace: Some(false),
             keepalive: Default::default(),
@@ -21,6 +21,8 @@ use crate::{
     SourceSender,
 };
 
+const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
 pub(crate) fn build_warp_filter(
     acknowledgements: bool,
     multiple_outputs: bool,
@@ -42,6 +44,7 @@ fn build_trace_filter(
     warp::post()
         .and(path!("api" / "v0.2" / "traces" / ..))
         .and(warp::path::full())
+        .and(warp::header::optional::<String>("content-type"))
         .and(warp::header::optional::<String>("content-encoding"))
         .and(warp::header::optional::<String>("dd-api-key"))
         .and(warp::header::optional::<String>(
@@ -51,13 +54,14 @@ fn build_trace_filter(
         .and(warp::body::bytes())
         .and_then(
             move |path: FullPath,
+                  content_type: Option<String>,
                   encoding_header: Option<String>,
                   api_token: Option<String>,
                   reported_language: Option<String>,
                   query_params: ApiKeyQueryParams,
                   body: Bytes| {
-                let events = source
-                    .decode(&encoding_header, body, path.as_str())
+                let events = check_content_type(content_type.as_deref())
+                    .and_then(|()| source.decode(&encoding_header, body, path.as_str()))
                     .and_then(|body| {
                         handle_dd_trace_payload(
                             body,
@@ -83,6 +87,26 @@ fn build_trace_filter(
         .boxed()
 }
 
+/// Rejects the payloads that aren't protobuf encoded, such as the msgpack payloads sent by
+/// tracers to the Agent rather than by the Agent itself.
+fn check_content_type(content_type: Option<&str>) -> Result<(), ErrorMessage> {
+    let Some(content_type) = content_type else {
+        return Ok(());
+    };
+    let mime_type = content_type.split(';').next().unwrap_or_default().trim();
+    if mime_type.eq_ignore_ascii_case(PROTOBUF_CONTENT_TYPE) {
+        Ok(())
+    } else {
+        Err(ErrorMessage::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            format!(
+                "Unsupported content type {}, traces must be encoded as {}",
+                content_type, PROTOBUF_CONTENT_TYPE
+            ),
+        ))
+    }
+}
+
 fn build_stats_filter() -> BoxedFilter<(Response,)> {
     warp::post()
         .and(path!("api" / "v0.2" / "stats" / ..))
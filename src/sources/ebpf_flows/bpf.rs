@@ -0,0 +1,159 @@
+//! Loads the eBPF programs of the source with [aya], which relocates their accesses to the kernel
+//! structures from the BTF of the running kernel, attaches them, and reads the flows they record.
+
+use std::io;
+
+use aya::{
+    maps::{HashMap, MapData, MapError},
+    programs::{tc, KProbe, Program, ProgramError, SchedClassifier, TcAttachType},
+    sys::SyscallError,
+    Ebpf, EbpfError, EbpfLoader,
+};
+use snafu::{ResultExt, Snafu};
+
+use super::flow::{FlowKey, FlowStats, FLOW_KEY_SIZE, FLOW_VALUE_SIZE};
+
+/// The eBPF programs, compiled from `programs/flows.bpf.c` by the build script.
+static PROGRAMS: &[u8] = aya::include_bytes_aligned!(concat!(env!("OUT_DIR"), "/ebpf_flows.bpf.o"));
+
+/// The kprobes counting the data sent and read by the processes, and measuring the time to
+/// establish the connections, by program and probed function.
+const KPROBES: [(&str, &str); 4] = [
+    ("count_sent_bytes", "tcp_sendmsg"),
+    ("count_received_bytes", "tcp_cleanup_rbuf"),
+    ("start_connect", "tcp_connect"),
+    ("finish_connect", "tcp_finish_connect"),
+];
+
+/// The kprobes counting the packets of the flows when no interface is configured.
+const PACKET_KPROBES: [(&str, &str); 2] = [
+    ("count_sent_packets", "__tcp_transmit_skb"),
+    ("count_received_packets", "tcp_rcv_established"),
+];
+
+/// The TC programs counting the packets crossing the configured interfaces.
+const CLASSIFIERS: [(&str, TcAttachType); 2] = [
+    ("count_ingress_packets", TcAttachType::Ingress),
+    ("count_egress_packets", TcAttachType::Egress),
+];
+
+type FlowsMap = HashMap<MapData, [u8; FLOW_KEY_SIZE], [u8; FLOW_VALUE_SIZE]>;
+
+#[derive(Debug, Snafu)]
+pub enum BpfError {
+    #[snafu(display("Could not load the eBPF programs: {}", source))]
+    LoadPrograms { source: EbpfError },
+    #[snafu(display("Could not load or attach the eBPF program {}: {}", program, source))]
+    AttachProgram {
+        program: &'static str,
+        source: ProgramError,
+    },
+    #[snafu(display(
+        "Could not attach the eBPF program {} to interface {}: {}",
+        program,
+        interface,
+        source
+    ))]
+    AttachClassifier {
+        program: &'static str,
+        interface: String,
+        source: ProgramError,
+    },
+    #[snafu(display("Could not read eBPF map: {}", source))]
+    ReadMap { source: MapError },
+}
+
+/// The eBPF programs updating the flows, detached when dropped.
+pub struct FlowProbes {
+    flows: FlowsMap,
+    _ebpf: Ebpf,
+}
+
+impl FlowProbes {
+    /// Loads and attaches the programs, counting the packets crossing the given interfaces, or
+    /// counting them with kprobes of the TCP stack if there are none.
+    pub fn attach(max_flows: u32, interfaces: &[String]) -> Result<Self, BpfError> {
+        let mut ebpf = EbpfLoader::new()
+            .set_max_entries("flows", max_flows)
+            .set_max_entries("connections", max_flows)
+            .load(PROGRAMS)
+            .context(LoadProgramsSnafu)?;
+
+        let mut kprobes = KPROBES.to_vec();
+        if interfaces.is_empty() {
+            kprobes.extend(PACKET_KPROBES);
+        }
+        for (program, function) in kprobes {
+            attach_kprobe(&mut ebpf, program, function).context(AttachProgramSnafu { program })?;
+        }
+
+        if !interfaces.is_empty() {
+            for interface in interfaces {
+                // The queueing discipline may already have been added, such as by a previous run.
+                _ = tc::qdisc_add_clsact(interface);
+            }
+            for (program, attach_type) in CLASSIFIERS {
+                let classifier: &mut SchedClassifier = program_mut(&mut ebpf, program)
+                    .try_into()
+                    .context(AttachProgramSnafu { program })?;
+                classifier.load().context(AttachProgramSnafu { program })?;
+                for interface in interfaces {
+                    classifier
+                        .attach(interface, attach_type)
+                        .context(AttachClassifierSnafu {
+                            program,
+                            interface: interface.clone(),
+                        })?;
+                }
+            }
+        }
+
+        let flows = ebpf
+            .take_map("flows")
+            .expect("flows map in the eBPF object");
+        let flows = FlowsMap::try_from(flows).context(ReadMapSnafu)?;
+
+        Ok(Self { flows, _ebpf: ebpf })
+    }
+
+    /// Removes the flows recorded since the last call.
+    pub fn take_flows(&mut self) -> Result<Vec<(FlowKey, FlowStats)>, BpfError> {
+        // The keys are collected first, as removing the flows while iterating over them would
+        // restart the iteration.
+        let keys = self
+            .flows
+            .keys()
+            .collect::<Result<Vec<_>, _>>()
+            .context(ReadMapSnafu)?;
+
+        let mut flows = Vec::with_capacity(keys.len());
+        for key in keys {
+            let value = match self.flows.get(&key, 0) {
+                Ok(value) => value,
+                // Dropped to track a new flow since listing the keys.
+                Err(MapError::KeyNotFound) => continue,
+                Err(source) => return Err(BpfError::ReadMap { source }),
+            };
+            match self.flows.remove(&key) {
+                Ok(()) => {}
+                Err(MapError::SyscallError(SyscallError { io_error, .. }))
+                    if io_error.kind() == io::ErrorKind::NotFound => {}
+                Err(source) => return Err(BpfError::ReadMap { source }),
+            }
+            flows.push((FlowKey::decode(&key), FlowStats::decode(&value)));
+        }
+        Ok(flows)
+    }
+}
+
+fn program_mut<'a>(ebpf: &'a mut Ebpf, program: &str) -> &'a mut Program {
+    ebpf.program_mut(program)
+        .expect("program in the eBPF object")
+}
+
+fn attach_kprobe(ebpf: &mut Ebpf, program: &str, function: &str) -> Result<(), ProgramError> {
+    let kprobe: &mut KProbe = program_mut(ebpf, program).try_into()?;
+    kprobe.load()?;
+    kprobe.attach(function, 0)?;
+    Ok(())
+}
@@ -0,0 +1,178 @@
+//! The layout of the flows shared with the eBPF programs.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// The size of the keys of the flows: the local and remote addresses, the local and remote ports,
+/// the address family, and two bytes left zeroed, as laid out in `struct flow_key`.
+pub const FLOW_KEY_SIZE: usize = 40;
+
+/// The size of the values of the flows, as laid out in `struct flow_value`.
+pub const FLOW_VALUE_SIZE: usize = 64;
+
+const KEY_LOCAL_ADDRESS_OFFSET: usize = 0;
+const KEY_REMOTE_ADDRESS_OFFSET: usize = 16;
+const KEY_LOCAL_PORT_OFFSET: usize = 32;
+const KEY_REMOTE_PORT_OFFSET: usize = 34;
+const KEY_FAMILY_OFFSET: usize = 36;
+
+const AF_INET6: u16 = 10;
+
+const VALUE_BYTES_SENT_OFFSET: usize = 0;
+const VALUE_BYTES_RECEIVED_OFFSET: usize = 8;
+const VALUE_PACKETS_SENT_OFFSET: usize = 16;
+const VALUE_PACKETS_RECEIVED_OFFSET: usize = 24;
+const VALUE_CONNECT_LATENCY_OFFSET: usize = 32;
+const VALUE_PID_OFFSET: usize = 40;
+const VALUE_COMM_OFFSET: usize = 48;
+
+/// The local and remote endpoints of a TCP connection.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FlowKey {
+    pub local_address: IpAddr,
+    pub local_port: u16,
+    pub remote_address: IpAddr,
+    pub remote_port: u16,
+}
+
+impl FlowKey {
+    pub fn decode(key: &[u8; FLOW_KEY_SIZE]) -> Self {
+        // Addresses are in network byte order, while the ports and the family are in host byte
+        // order.
+        let u16_at = |offset: usize| u16::from_ne_bytes([key[offset], key[offset + 1]]);
+        let address_at = |offset: usize| -> IpAddr {
+            let address: [u8; 16] = key[offset..offset + 16].try_into().expect("16 bytes");
+            if u16_at(KEY_FAMILY_OFFSET) == AF_INET6 {
+                Ipv6Addr::from(address).into()
+            } else {
+                Ipv4Addr::new(address[0], address[1], address[2], address[3]).into()
+            }
+        };
+
+        Self {
+            local_address: address_at(KEY_LOCAL_ADDRESS_OFFSET),
+            local_port: u16_at(KEY_LOCAL_PORT_OFFSET),
+            remote_address: address_at(KEY_REMOTE_ADDRESS_OFFSET),
+            remote_port: u16_at(KEY_REMOTE_PORT_OFFSET),
+        }
+    }
+}
+
+/// The activity of a flow since it was last reported.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FlowStats {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub packets_sent: u64,
+    pub packets_received: u64,
+    /// The time to establish the connection, for connections initiated locally.
+    pub connect_latency_ns: Option<u64>,
+    /// The process which last sent or received data through the connection.
+    pub process: Option<FlowProcess>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlowProcess {
+    pub pid: u32,
+    pub name: String,
+}
+
+impl FlowStats {
+    pub fn decode(value: &[u8; FLOW_VALUE_SIZE]) -> Self {
+        let u64_at = |offset: usize| {
+            u64::from_ne_bytes(value[offset..offset + 8].try_into().expect("8 bytes"))
+        };
+        let pid = u32::from_ne_bytes(
+            value[VALUE_PID_OFFSET..VALUE_PID_OFFSET + 4]
+                .try_into()
+                .expect("4 bytes"),
+        );
+        let comm = &value[VALUE_COMM_OFFSET..];
+        let comm = &comm[..comm.iter().position(|&c| c == 0).unwrap_or(comm.len())];
+        let connect_latency_ns = u64_at(VALUE_CONNECT_LATENCY_OFFSET);
+
+        Self {
+            bytes_sent: u64_at(VALUE_BYTES_SENT_OFFSET),
+            bytes_received: u64_at(VALUE_BYTES_RECEIVED_OFFSET),
+            packets_sent: u64_at(VALUE_PACKETS_SENT_OFFSET),
+            packets_received: u64_at(VALUE_PACKETS_RECEIVED_OFFSET),
+            connect_latency_ns: (connect_latency_ns > 0).then_some(connect_latency_ns),
+            process: (pid > 0).then(|| FlowProcess {
+                pid,
+                name: String::from_utf8_lossy(comm).into_owned(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_key(family: u16, local: &[u8], remote: &[u8]) -> [u8; FLOW_KEY_SIZE] {
+        let mut key = [0; FLOW_KEY_SIZE];
+        key[KEY_LOCAL_ADDRESS_OFFSET..][..local.len()].copy_from_slice(local);
+        key[KEY_REMOTE_ADDRESS_OFFSET..][..remote.len()].copy_from_slice(remote);
+        key[KEY_LOCAL_PORT_OFFSET..][..2].copy_from_slice(&51234u16.to_ne_bytes());
+        key[KEY_REMOTE_PORT_OFFSET..][..2].copy_from_slice(&443u16.to_ne_bytes());
+        key[KEY_FAMILY_OFFSET..][..2].copy_from_slice(&family.to_ne_bytes());
+        key
+    }
+
+    #[test]
+    fn decodes_ipv4_keys() {
+        let key = encode_key(2, &[10, 0, 0, 2], &[93, 184, 216, 34]);
+
+        assert_eq!(
+            FlowKey::decode(&key),
+            FlowKey {
+                local_address: Ipv4Addr::new(10, 0, 0, 2).into(),
+                local_port: 51234,
+                remote_address: Ipv4Addr::new(93, 184, 216, 34).into(),
+                remote_port: 443,
+            }
+        );
+    }
+
+    #[test]
+    fn decodes_ipv6_keys() {
+        let local = "fd00::2".parse::<Ipv6Addr>().unwrap();
+        let remote = "2606:2800:220:1::248".parse::<Ipv6Addr>().unwrap();
+        let key = encode_key(AF_INET6, &local.octets(), &remote.octets());
+
+        assert_eq!(
+            FlowKey::decode(&key),
+            FlowKey {
+                local_address: local.into(),
+                local_port: 51234,
+                remote_address: remote.into(),
+                remote_port: 443,
+            }
+        );
+    }
+
+    #[test]
+    fn decodes_values() {
+        let mut value = [0; FLOW_VALUE_SIZE];
+        value[VALUE_BYTES_SENT_OFFSET..][..8].copy_from_slice(&1200u64.to_ne_bytes());
+        value[VALUE_BYTES_RECEIVED_OFFSET..][..8].copy_from_slice(&5400u64.to_ne_bytes());
+        value[VALUE_PACKETS_SENT_OFFSET..][..8].copy_from_slice(&3u64.to_ne_bytes());
+        value[VALUE_PACKETS_RECEIVED_OFFSET..][..8].copy_from_slice(&5u64.to_ne_bytes());
+        value[VALUE_PID_OFFSET..][..4].copy_from_slice(&4242u32.to_ne_bytes());
+        value[VALUE_COMM_OFFSET..][..4].copy_from_slice(b"curl");
+
+        assert_eq!(
+            FlowStats::decode(&value),
+            FlowStats {
+                bytes_sent: 1200,
+                bytes_received: 5400,
+                packets_sent: 3,
+                packets_received: 5,
+                connect_latency_ns: None,
+                process: Some(FlowProcess {
+                    pid: 4242,
+                    name: "curl".into(),
+                }),
+            }
+        );
+    }
+}
@@ -0,0 +1,464 @@
+use std::{net::IpAddr, time::Duration};
+
+use chrono::Utc;
+use futures::StreamExt;
+use serde_with::serde_as;
+use tokio::time;
+use tokio_stream::wrappers::IntervalStream;
+use vector_lib::{
+    config::LogNamespace,
+    configurable::configurable_component,
+    internal_event::{CountByteSize, InternalEventHandle as _},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind, ObjectMap, Value};
+
+use crate::{
+    config::{DataType, SourceConfig, SourceContext, SourceOutput},
+    event::{EventMetadata, LogEvent},
+    internal_events::{EbpfFlowsReadError, EventsReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+pub(crate) mod bpf;
+mod flow;
+
+use bpf::FlowProbes;
+use flow::{FlowKey, FlowStats};
+
+/// Configuration for the `ebpf_flows` source.
+#[serde_as]
+#[configurable_component(source(
+    "ebpf_flows",
+    "Collect TCP flow records from the Linux kernel with eBPF."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EbpfFlowsConfig {
+    /// The interval between reports of the flows, in seconds.
+    ///
+    /// Every report includes the activity of the flows active since the previous report.
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(default = "default_report_interval")]
+    #[configurable(metadata(docs::human_name = "Report Interval"))]
+    pub report_interval_secs: Duration,
+
+    /// The maximum number of flows tracked between reports.
+    ///
+    /// Once reached, the least recently active flows are dropped to track new ones.
+    #[serde(default = "default_max_flows")]
+    #[configurable(metadata(docs::type_unit = "flows"))]
+    pub max_flows: u32,
+
+    /// The network interfaces to attach TC programs to, counting the TCP packets crossing them.
+    ///
+    /// When empty, the packets of the flows are counted by kprobes of the TCP stack instead. The
+    /// interfaces must use Ethernet framing, such as the loopback and Ethernet interfaces.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "eth0"))]
+    #[configurable(metadata(docs::examples = "lo"))]
+    pub interfaces: Vec<String>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+}
+
+const fn default_report_interval() -> Duration {
+    Duration::from_secs(10)
+}
+
+const fn default_max_flows() -> u32 {
+    65_536
+}
+
+impl Default for EbpfFlowsConfig {
+    fn default() -> Self {
+        Self {
+            report_interval_secs: default_report_interval(),
+            max_flows: default_max_flows(),
+            interfaces: Vec::new(),
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(EbpfFlowsConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "ebpf_flows")]
+impl SourceConfig for EbpfFlowsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let probes = FlowProbes::attach(self.max_flows, &self.interfaces)?;
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        Ok(Box::pin(run(
+            probes,
+            self.report_interval_secs,
+            log_namespace,
+            cx.out,
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty().with_unknown(Kind::any())),
+            [log_namespace],
+        )
+        .with_standard_vector_source_metadata();
+
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn run(
+    mut probes: FlowProbes,
+    report_interval: Duration,
+    log_namespace: LogNamespace,
+    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let events_received = register!(EventsReceived);
+    let mut interval = IntervalStream::new(time::interval(report_interval)).take_until(shutdown);
+    // The first tick completes immediately.
+    interval.next().await;
+
+    while interval.next().await.is_some() {
+        let flows = match probes.take_flows() {
+            Ok(flows) => flows,
+            Err(error) => {
+                emit!(EbpfFlowsReadError { error });
+                continue;
+            }
+        };
+        if flows.is_empty() {
+            continue;
+        }
+
+        let now = Utc::now();
+        let events = flows
+            .iter()
+            .map(|(key, stats)| create_log_event(key, stats, log_namespace, now))
+            .collect::<Vec<_>>();
+        let count = events.len();
+        events_received.emit(CountByteSize(
+            count,
+            events.estimated_json_encoded_size_of(),
+        ));
+
+        if out.send_batch(events).await.is_err() {
+            emit!(StreamClosedError { count });
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
+
+fn create_log_event(
+    key: &FlowKey,
+    stats: &FlowStats,
+    log_namespace: LogNamespace,
+    now: chrono::DateTime<Utc>,
+) -> LogEvent {
+    let endpoint = |address: IpAddr, port: u16| {
+        Value::from(ObjectMap::from([
+            ("address".into(), address.to_string().into()),
+            ("port".into(), Value::Integer(port.into())),
+        ]))
+    };
+
+    let mut fields = ObjectMap::from([
+        ("protocol".into(), "tcp".into()),
+        ("local".into(), endpoint(key.local_address, key.local_port)),
+        (
+            "remote".into(),
+            endpoint(key.remote_address, key.remote_port),
+        ),
+        ("bytes_sent".into(), integer(stats.bytes_sent)),
+        ("bytes_received".into(), integer(stats.bytes_received)),
+        ("packets_sent".into(), integer(stats.packets_sent)),
+        ("packets_received".into(), integer(stats.packets_received)),
+    ]);
+    if let Some(latency) = stats.connect_latency_ns {
+        fields.insert("connect_latency_ns".into(), integer(latency));
+    }
+    if let Some(process) = &stats.process {
+        fields.insert(
+            "process".into(),
+            Value::from(ObjectMap::from([
+                ("pid".into(), Value::Integer(process.pid.into())),
+                ("name".into(), process.name.as_str().into()),
+            ])),
+        );
+    }
+
+    let mut log = LogEvent::from_map(fields, EventMetadata::default());
+    log_namespace.insert_standard_vector_source_metadata(&mut log, EbpfFlowsConfig::NAME, now);
+    log
+}
+
+fn integer(value: u64) -> Value {
+    Value::Integer(value.try_into().unwrap_or(i64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use vector_lib::lookup::event_path;
+
+    use super::*;
+    use crate::config::log_schema;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<EbpfFlowsConfig>();
+    }
+
+    #[test]
+    fn creates_flow_records() {
+        let key = FlowKey {
+            local_address: Ipv4Addr::new(10, 0, 0, 2).into(),
+            local_port: 51234,
+            remote_address: Ipv4Addr::new(93, 184, 216, 34).into(),
+            remote_port: 443,
+        };
+        let stats = FlowStats {
+            bytes_sent: 1200,
+            bytes_received: 5400,
+            packets_sent: 3,
+            packets_received: 5,
+            connect_latency_ns: Some(1_500_000),
+            process: Some(flow::FlowProcess {
+                pid: 4242,
+                name: "curl".into(),
+            }),
+        };
+        let log = create_log_event(&key, &stats, LogNamespace::Legacy, Utc::now());
+
+        assert_eq!(log["local.address"], "10.0.0.2".into());
+        assert_eq!(log["remote.port"], Value::Integer(443));
+        assert_eq!(log["bytes_received"], Value::Integer(5400));
+        assert_eq!(log["connect_latency_ns"], Value::Integer(1_500_000));
+        assert_eq!(log["process.name"], "curl".into());
+        assert_eq!(log["source_type"], EbpfFlowsConfig::NAME.into());
+        assert!(log
+            .get(log_schema().timestamp_key_target_path().unwrap())
+            .is_some());
+
+        let key = FlowKey {
+            local_address: Ipv6Addr::LOCALHOST.into(),
+            local_port: 8080,
+            remote_address: Ipv6Addr::LOCALHOST.into(),
+            remote_port: 51234,
+        };
+        let log = create_log_event(
+            &key,
+            &FlowStats::default(),
+            LogNamespace::Vector,
+            Utc::now(),
+        );
+        assert_eq!(
+            log.get(event_path!("local", "address")),
+            Some(&"::1".into())
+        );
+        assert_eq!(log.get(event_path!("process")), None);
+        assert_eq!(log.get(event_path!("connect_latency_ns")), None);
+    }
+}
+
+#[cfg(all(test, feature = "ebpf-flows-integration-tests"))]
+mod integration_tests {
+    use std::{
+        io::{Read, Write},
+        net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+        thread,
+    };
+
+    use futures::Stream;
+    use tokio::time::{sleep, timeout};
+
+    use super::*;
+    use crate::event::Event;
+
+    const REQUEST: &[u8] = b"GET / HTTP/1.0\r\nHost: nginx\r\n\r\n";
+    const RESPONSE: &[u8] = b"HTTP/1.0 204 No Content\r\n\r\n";
+
+    fn nginx_address() -> SocketAddr {
+        std::env::var("NGINX_ADDRESS")
+            .unwrap_or_else(|_| "localhost:80".into())
+            .to_socket_addrs()
+            .unwrap()
+            .next()
+            .unwrap()
+    }
+
+    /// Answers a single request on the given address, returning the address it listens on.
+    fn serve_once(address: &str) -> SocketAddr {
+        let listener = TcpListener::bind(address).unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            stream.read_to_end(&mut request).unwrap();
+            stream.write_all(RESPONSE).unwrap();
+        });
+        address
+    }
+
+    /// Sends a request to the given address, returning the local port of the connection along with
+    /// the size of the response.
+    fn exchange_request(address: SocketAddr) -> (u16, usize) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(REQUEST).unwrap();
+        stream.shutdown(Shutdown::Write).unwrap();
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).unwrap();
+        (stream.local_addr().unwrap().port(), response.len())
+    }
+
+    fn find_flow(
+        flows: &[(FlowKey, FlowStats)],
+        local_port: u16,
+        remote_port: u16,
+    ) -> &(FlowKey, FlowStats) {
+        flows
+            .iter()
+            .find(|(key, _)| key.local_port == local_port && key.remote_port == remote_port)
+            .expect("flow not recorded")
+    }
+
+    async fn next_flow(
+        events: &mut (impl Stream<Item = Event> + Unpin),
+        local_port: u16,
+    ) -> LogEvent {
+        timeout(Duration::from_secs(10), async {
+            loop {
+                let log = events.next().await.expect("source stopped").into_log();
+                if log["local.port"] == Value::Integer(local_port.into()) {
+                    return log;
+                }
+            }
+        })
+        .await
+        .expect("flow not reported")
+    }
+
+    #[test]
+    fn attaches_probes_and_records_flows() {
+        let mut probes = FlowProbes::attach(1024, &[]).unwrap();
+        let address = nginx_address();
+        let (local_port, response_len) = exchange_request(address);
+
+        let flows = probes.take_flows().unwrap();
+        let (key, stats) = find_flow(&flows, local_port, address.port());
+        assert_eq!(key.remote_address, address.ip());
+        assert_eq!(stats.bytes_sent, REQUEST.len() as u64);
+        assert_eq!(stats.bytes_received, response_len as u64);
+        assert!(stats.packets_sent > 0);
+        assert!(stats.packets_received > 0);
+        assert!(stats.connect_latency_ns.is_some());
+        assert_eq!(
+            stats.process.as_ref().map(|process| process.pid),
+            Some(std::process::id())
+        );
+
+        // Taking the flows empties the map.
+        assert!(probes
+            .take_flows()
+            .unwrap()
+            .iter()
+            .all(|(key, _)| key.local_port != local_port));
+    }
+
+    #[test]
+    fn records_ipv6_flows() {
+        let mut probes = FlowProbes::attach(1024, &[]).unwrap();
+        let address = serve_once("[::1]:0");
+        let (local_port, response_len) = exchange_request(address);
+
+        let flows = probes.take_flows().unwrap();
+        let (key, stats) = find_flow(&flows, local_port, address.port());
+        assert_eq!(key.local_address, Ipv6Addr::LOCALHOST);
+        assert_eq!(key.remote_address, Ipv6Addr::LOCALHOST);
+        assert_eq!(stats.bytes_sent, REQUEST.len() as u64);
+        assert_eq!(stats.bytes_received, response_len as u64);
+        assert!(stats.connect_latency_ns.is_some());
+
+        // The server end of the connection.
+        let (_, stats) = find_flow(&flows, address.port(), local_port);
+        assert_eq!(stats.bytes_received, REQUEST.len() as u64);
+        assert_eq!(stats.bytes_sent, RESPONSE.len() as u64);
+    }
+
+    #[test]
+    fn records_ipv4_mapped_flows_as_ipv4() {
+        let mut probes = FlowProbes::attach(1024, &[]).unwrap();
+        let port = serve_once("[::]:0").port();
+        let (local_port, _) = exchange_request((Ipv4Addr::LOCALHOST, port).into());
+
+        let flows = probes.take_flows().unwrap();
+        // The server end of the connection is an IPv6 socket.
+        let (key, stats) = find_flow(&flows, port, local_port);
+        assert_eq!(key.local_address, Ipv4Addr::LOCALHOST);
+        assert_eq!(key.remote_address, Ipv4Addr::LOCALHOST);
+        assert_eq!(stats.bytes_received, REQUEST.len() as u64);
+    }
+
+    #[test]
+    fn counts_packets_with_tc_programs() {
+        let mut probes = FlowProbes::attach(1024, &["lo".into()]).unwrap();
+        let ipv4_address = serve_once("127.0.0.1:0");
+        let (ipv4_port, _) = exchange_request(ipv4_address);
+        let ipv6_address = serve_once("[::1]:0");
+        let (ipv6_port, _) = exchange_request(ipv6_address);
+
+        let flows = probes.take_flows().unwrap();
+        for (local_port, address) in [(ipv4_port, ipv4_address), (ipv6_port, ipv6_address)] {
+            let (key, stats) = find_flow(&flows, local_port, address.port());
+            assert_eq!(key.remote_address, address.ip());
+            // At least the handshake, request, and shutdown of the connection.
+            assert!(stats.packets_sent >= 3);
+            assert!(stats.packets_received >= 3);
+            assert_eq!(stats.bytes_sent, REQUEST.len() as u64);
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_flows() {
+        let (tx, mut events) = SourceSender::new_test();
+        let config = EbpfFlowsConfig {
+            report_interval_secs: Duration::from_secs(1),
+            ..Default::default()
+        };
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        // Let the source start reporting before sending the request.
+        sleep(Duration::from_millis(100)).await;
+
+        let address = nginx_address();
+        let (local_port, _) = tokio::task::spawn_blocking(move || exchange_request(address))
+            .await
+            .unwrap();
+
+        let log = next_flow(&mut events, local_port).await;
+        assert_eq!(log["remote.port"], Value::Integer(address.port().into()));
+        assert_eq!(log["protocol"], "tcp".into());
+    }
+}
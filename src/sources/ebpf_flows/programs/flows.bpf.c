@@ -0,0 +1,269 @@
+// SPDX-License-Identifier: GPL-2.0
+/*
+ * The eBPF programs of the `ebpf_flows` source, compiled by the build script of Vector.
+ *
+ * The kprobes attribute the data sent and read through the TCP sockets to the processes doing so,
+ * and record the time to establish the connections initiated locally. The packets of the flows are
+ * either counted by kprobes of the TCP stack, or by the TC programs attached to the network
+ * interfaces configured in the source.
+ */
+
+#include "vmlinux.h"
+
+#include <bpf/bpf_core_read.h>
+#include <bpf/bpf_endian.h>
+#include <bpf/bpf_helpers.h>
+#include <bpf/bpf_tracing.h>
+
+/*
+ * The local and remote endpoints of a TCP connection. IPv4 addresses only use the first four bytes
+ * of the addresses, and IPv4 addresses mapped to IPv6 ones are recorded as IPv4 addresses, so that
+ * both ends of a connection agree on its family. Ports are in host byte order.
+ */
+struct flow_key {
+	__u8 local_addr[16];
+	__u8 remote_addr[16];
+	__u16 local_port;
+	__u16 remote_port;
+	__u16 family;
+	__u16 pad;
+};
+
+/* The activity of a flow since it was last reported, which is when it's removed from the map. */
+struct flow_value {
+	__u64 bytes_sent;
+	__u64 bytes_received;
+	__u64 packets_sent;
+	__u64 packets_received;
+	__u64 connect_latency_ns;
+	__u32 pid;
+	__u32 pad;
+	char comm[16];
+};
+
+/* The maximum numbers of entries are overridden by the source when loading the programs. */
+struct {
+	__uint(type, BPF_MAP_TYPE_LRU_HASH);
+	__uint(max_entries, 65536);
+	__type(key, struct flow_key);
+	__type(value, struct flow_value);
+} flows SEC(".maps");
+
+/* The start times of the connections being established, keyed by the addresses of their sockets. */
+struct {
+	__uint(type, BPF_MAP_TYPE_LRU_HASH);
+	__uint(max_entries, 65536);
+	__type(key, __u64);
+	__type(value, __u64);
+} connections SEC(".maps");
+
+static __always_inline bool is_ipv4_mapped(const __u8 *addr)
+{
+	for (int i = 0; i < 10; i++) {
+		if (addr[i])
+			return false;
+	}
+	return addr[10] == 0xff && addr[11] == 0xff;
+}
+
+static __always_inline void unmap_ipv4(__u8 *addr)
+{
+	__builtin_memcpy(addr, addr + 12, 4);
+	__builtin_memset(addr + 4, 0, 12);
+}
+
+static __always_inline bool read_socket_key(struct sock *sk, struct flow_key *key)
+{
+	__builtin_memset(key, 0, sizeof(*key));
+	key->local_port = BPF_CORE_READ(sk, __sk_common.skc_num);
+	key->remote_port = bpf_ntohs(BPF_CORE_READ(sk, __sk_common.skc_dport));
+
+	switch (BPF_CORE_READ(sk, __sk_common.skc_family)) {
+	case AF_INET: {
+		__be32 local_addr = BPF_CORE_READ(sk, __sk_common.skc_rcv_saddr);
+		__be32 remote_addr = BPF_CORE_READ(sk, __sk_common.skc_daddr);
+
+		key->family = AF_INET;
+		__builtin_memcpy(key->local_addr, &local_addr, 4);
+		__builtin_memcpy(key->remote_addr, &remote_addr, 4);
+		return true;
+	}
+	case AF_INET6:
+		key->family = AF_INET6;
+		bpf_core_read(key->local_addr, sizeof(key->local_addr),
+			      &sk->__sk_common.skc_v6_rcv_saddr);
+		bpf_core_read(key->remote_addr, sizeof(key->remote_addr),
+			      &sk->__sk_common.skc_v6_daddr);
+		if (is_ipv4_mapped(key->remote_addr)) {
+			key->family = AF_INET;
+			unmap_ipv4(key->local_addr);
+			unmap_ipv4(key->remote_addr);
+		}
+		return true;
+	default:
+		return false;
+	}
+}
+
+static __always_inline struct flow_value *flow_value(const struct flow_key *key)
+{
+	struct flow_value *value = bpf_map_lookup_elem(&flows, key);
+	if (value)
+		return value;
+
+	struct flow_value zero = {};
+	bpf_map_update_elem(&flows, key, &zero, BPF_NOEXIST);
+	return bpf_map_lookup_elem(&flows, key);
+}
+
+static __always_inline struct flow_value *socket_flow_value(struct sock *sk)
+{
+	struct flow_key key;
+	if (!read_socket_key(sk, &key))
+		return NULL;
+	return flow_value(&key);
+}
+
+/* Attributes a flow to the current process, as sending and reading data run in its context. */
+static __always_inline void attribute(struct flow_value *value)
+{
+	value->pid = bpf_get_current_pid_tgid() >> 32;
+	bpf_get_current_comm(value->comm, sizeof(value->comm));
+}
+
+SEC("kprobe/tcp_sendmsg")
+int BPF_KPROBE(count_sent_bytes, struct sock *sk, struct msghdr *msg, size_t size)
+{
+	struct flow_value *value = socket_flow_value(sk);
+	if (value) {
+		__sync_fetch_and_add(&value->bytes_sent, size);
+		attribute(value);
+	}
+	return 0;
+}
+
+SEC("kprobe/tcp_cleanup_rbuf")
+int BPF_KPROBE(count_received_bytes, struct sock *sk, int copied)
+{
+	if (copied <= 0)
+		return 0;
+
+	struct flow_value *value = socket_flow_value(sk);
+	if (value) {
+		__sync_fetch_and_add(&value->bytes_received, copied);
+		attribute(value);
+	}
+	return 0;
+}
+
+SEC("kprobe/__tcp_transmit_skb")
+int BPF_KPROBE(count_sent_packets, struct sock *sk)
+{
+	struct flow_value *value = socket_flow_value(sk);
+	if (value)
+		__sync_fetch_and_add(&value->packets_sent, 1);
+	return 0;
+}
+
+SEC("kprobe/tcp_rcv_established")
+int BPF_KPROBE(count_received_packets, struct sock *sk)
+{
+	struct flow_value *value = socket_flow_value(sk);
+	if (value)
+		__sync_fetch_and_add(&value->packets_received, 1);
+	return 0;
+}
+
+/* `tcp_connect` sends the SYN of the connections initiated locally, over IPv4 and IPv6. */
+SEC("kprobe/tcp_connect")
+int BPF_KPROBE(start_connect, struct sock *sk)
+{
+	__u64 socket = (__u64)sk;
+	__u64 now = bpf_ktime_get_ns();
+
+	bpf_map_update_elem(&connections, &socket, &now, BPF_ANY);
+	return 0;
+}
+
+SEC("kprobe/tcp_finish_connect")
+int BPF_KPROBE(finish_connect, struct sock *sk)
+{
+	__u64 socket = (__u64)sk;
+	__u64 *start = bpf_map_lookup_elem(&connections, &socket);
+	if (!start)
+		return 0;
+
+	__u64 latency = bpf_ktime_get_ns() - *start;
+	bpf_map_delete_elem(&connections, &socket);
+
+	struct flow_value *value = socket_flow_value(sk);
+	if (value)
+		value->connect_latency_ns = latency;
+	return 0;
+}
+
+/*
+ * Counts a TCP packet crossing an interface with Ethernet framing. The local endpoint is the
+ * destination of the packets received, and the source of the packets sent.
+ */
+static __always_inline int count_packet(struct __sk_buff *skb, bool ingress)
+{
+	struct flow_key key = {};
+	__u8 *local_addr = key.local_addr;
+	__u8 *remote_addr = key.remote_addr;
+	__u32 offset = ETH_HLEN;
+
+	if (skb->protocol == bpf_htons(ETH_P_IP)) {
+		struct iphdr ip;
+		if (bpf_skb_load_bytes(skb, offset, &ip, sizeof(ip)))
+			return TC_ACT_OK;
+		/* Only the first fragment of a packet holds its TCP header. */
+		if (ip.protocol != IPPROTO_TCP || (bpf_ntohs(ip.frag_off) & 0x1fff))
+			return TC_ACT_OK;
+
+		key.family = AF_INET;
+		__builtin_memcpy(ingress ? local_addr : remote_addr, &ip.daddr, 4);
+		__builtin_memcpy(ingress ? remote_addr : local_addr, &ip.saddr, 4);
+		offset += (ip.version_ihl & 0x0f) * 4;
+	} else if (skb->protocol == bpf_htons(ETH_P_IPV6)) {
+		struct ipv6hdr ip;
+		if (bpf_skb_load_bytes(skb, offset, &ip, sizeof(ip)))
+			return TC_ACT_OK;
+		/* Packets with extension headers aren't counted. */
+		if (ip.nexthdr != IPPROTO_TCP)
+			return TC_ACT_OK;
+
+		key.family = AF_INET6;
+		__builtin_memcpy(ingress ? local_addr : remote_addr, ip.daddr.addr, 16);
+		__builtin_memcpy(ingress ? remote_addr : local_addr, ip.saddr.addr, 16);
+		offset += sizeof(ip);
+	} else {
+		return TC_ACT_OK;
+	}
+
+	__be16 ports[2];
+	if (bpf_skb_load_bytes(skb, offset, ports, sizeof(ports)))
+		return TC_ACT_OK;
+	key.local_port = bpf_ntohs(ingress ? ports[1] : ports[0]);
+	key.remote_port = bpf_ntohs(ingress ? ports[0] : ports[1]);
+
+	struct flow_value *value = flow_value(&key);
+	if (value)
+		__sync_fetch_and_add(ingress ? &value->packets_received : &value->packets_sent, 1);
+	return TC_ACT_OK;
+}
+
+SEC("classifier")
+int count_ingress_packets(struct __sk_buff *skb)
+{
+	return count_packet(skb, true);
+}
+
+SEC("classifier")
+int count_egress_packets(struct __sk_buff *skb)
+{
+	return count_packet(skb, false);
+}
+
+/* The programs call helpers only available to GPL compatible programs. */
+char LICENSE[] SEC("license") = "GPL";
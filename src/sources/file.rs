@@ -1,8 +1,9 @@
-use std::{convert::TryInto, future, path::PathBuf, time::Duration};
+use std::{future, path::PathBuf, time::Duration};
 
 use bytes::Bytes;
 use chrono::Utc;
 use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
+use indexmap::IndexMap;
 use regex::bytes::Regex;
 use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
@@ -53,6 +54,17 @@ enum BuildError {
         indicator: String,
         source: regex::Error,
     },
+    #[snafu(display(
+        "multiline rule {:?} has an invalid include pattern {:?}: {}",
+        rule,
+        pattern,
+        source
+    ))]
+    InvalidMultilineRuleInclude {
+        rule: String,
+        pattern: String,
+        source: glob::PatternError,
+    },
 }
 
 /// Configuration for the `file` source.
@@ -190,6 +202,17 @@ pub struct FileConfig {
     #[serde(default)]
     pub multiline: Option<MultilineConfig>,
 
+    /// Multiline aggregation rules, by name, applied to the files matching their patterns.
+    ///
+    /// The lines of a file are aggregated with the first rule matching its path, in the order of
+    /// the rules, or with `multiline` if none of the rules match it. The lines of the files
+    /// matching none of the rules are not aggregated if `multiline` is not set.
+    #[configurable(metadata(
+        docs::additional_props_description = "A multiline aggregation rule."
+    ))]
+    #[serde(default)]
+    pub multiline_rules: IndexMap<String, MultilineRuleConfig>,
+
     /// Max amount of bytes to read from a single file before switching over to the next file.
     /// **Note:** This does not apply when `oldest_first` is `true`.
     ///
@@ -365,6 +388,77 @@ impl From<FingerprintConfig> for FingerprintStrategy {
     }
 }
 
+/// A multiline aggregation rule, applied to the files matching its patterns.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct MultilineRuleConfig {
+    /// Array of file patterns the rule applies to. [Globbing](https://vector.dev/docs/reference/configuration/sources/file/#globbing) is supported.
+    #[configurable(metadata(docs::examples = "/var/log/java/**/*.log"))]
+    pub include: Vec<PathBuf>,
+
+    #[configurable(derived)]
+    pub multiline: MultilineConfig,
+}
+
+/// The initial context of the lines aggregated: the fingerprint of their file, along with their
+/// start and end offsets.
+type LineContext = (FileFingerprint, u64, u64);
+
+/// Builds the aggregation logic of the lines of the files, `None` if the lines are not aggregated.
+fn build_line_agg_logic(
+    config: &FileConfig,
+) -> crate::Result<Option<line_agg::Logic<String, LineContext>>> {
+    let default = if let Some(ref multiline) = config.multiline {
+        Some(line_agg::Config::try_from(multiline)?)
+    } else if let Some(ref indicator) = config.message_start_indicator {
+        let marker = Regex::new(indicator)
+            .with_context(|_| InvalidMessageStartIndicatorSnafu { indicator })?;
+        Some(line_agg::Config::for_legacy(
+            marker,
+            config.multi_line_timeout,
+        ))
+    } else {
+        None
+    };
+
+    if config.multiline_rules.is_empty() {
+        return Ok(default.map(line_agg::Logic::new));
+    }
+
+    let mut configs = Vec::with_capacity(config.multiline_rules.len() + 1);
+    let mut rules = Vec::with_capacity(config.multiline_rules.len());
+    for (name, rule) in &config.multiline_rules {
+        let patterns = rule
+            .include
+            .iter()
+            .map(|pattern| {
+                let pattern = pattern.to_string_lossy();
+                glob::Pattern::new(&pattern).with_context(|_| InvalidMultilineRuleIncludeSnafu {
+                    rule: name,
+                    pattern: pattern.as_ref(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        rules.push(patterns);
+        configs.push(line_agg::Config::try_from(&rule.multiline)?);
+    }
+    let default = default.map(|default| {
+        configs.push(default);
+        configs.len() - 1
+    });
+
+    Ok(Some(line_agg::Logic::with_selector(
+        configs,
+        Box::new(move |filename: &String| {
+            rules
+                .iter()
+                .position(|patterns| patterns.iter().any(|pattern| pattern.matches(filename)))
+                .or(default)
+        }),
+    )))
+}
+
 #[derive(Debug)]
 pub(crate) struct FinalizerEntry {
     pub(crate) file_id: FileFingerprint,
@@ -391,6 +485,7 @@ impl Default for FileConfig {
             message_start_indicator: None,
             multi_line_timeout: default_multi_line_timeout(), // millis
             multiline: None,
+            multiline_rules: IndexMap::new(),
             max_read_bytes: default_max_read_bytes(),
             oldest_first: false,
             remove_after_secs: None,
@@ -419,18 +514,7 @@ impl SourceConfig for FileConfig {
             // source are only global, name can be used for subdir
             .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
 
-        // Clippy rule, because async_trait?
-        #[allow(clippy::suspicious_else_formatting)]
-        {
-            if let Some(ref config) = self.multiline {
-                let _: line_agg::Config = config.try_into()?;
-            }
-
-            if let Some(ref indicator) = self.message_start_indicator {
-                Regex::new(indicator)
-                    .with_context(|_| InvalidMessageStartIndicatorSnafu { indicator })?;
-            }
-        }
+        build_line_agg_logic(self)?;
 
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
 
@@ -581,9 +665,7 @@ pub fn file_source(
 
     let include = config.include.clone();
     let exclude = config.exclude.clone();
-    let multiline_config = config.multiline.clone();
-    let message_start_indicator = config.message_start_indicator.clone();
-    let multi_line_timeout = config.multi_line_timeout;
+    let line_agg_logic = build_line_agg_logic(config).unwrap(); // validated in build
 
     let (finalizer, shutdown_checkpointer) = if acknowledgements {
         // The shutdown sent in to the finalizer is the global
@@ -639,19 +721,8 @@ pub fn file_source(
             });
 
         let messages: Box<dyn Stream<Item = Line> + Send + std::marker::Unpin> =
-            if let Some(ref multiline_config) = multiline_config {
-                wrap_with_line_agg(
-                    rx,
-                    multiline_config.try_into().unwrap(), // validated in build
-                )
-            } else if let Some(msi) = message_start_indicator {
-                wrap_with_line_agg(
-                    rx,
-                    line_agg::Config::for_legacy(
-                        Regex::new(&msi).unwrap(), // validated in build
-                        multi_line_timeout,
-                    ),
-                )
+            if let Some(logic) = line_agg_logic {
+                wrap_with_line_agg(rx, logic)
             } else {
                 Box::new(rx)
             };
@@ -738,9 +809,8 @@ fn reconcile_position_options(
 
 fn wrap_with_line_agg(
     rx: impl Stream<Item = Line> + Send + std::marker::Unpin + 'static,
-    config: line_agg::Config,
+    logic: line_agg::Logic<String, LineContext>,
 ) -> Box<dyn Stream<Item = Line> + Send + std::marker::Unpin + 'static> {
-    let logic = line_agg::Logic::new(config);
     Box::new(
         LineAgg::new(
             rx.map(|line| {
@@ -2006,6 +2076,72 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_multi_line_aggregation_rules() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            multiline_rules: IndexMap::from([(
+                "java".to_owned(),
+                MultilineRuleConfig {
+                    include: vec![dir.path().join("*.java.log")],
+                    multiline: MultilineConfig {
+                        start_pattern: "^[^\\s]".to_owned(),
+                        condition_pattern: "^[\\s]+".to_owned(),
+                        mode: line_agg::Mode::ContinueThrough,
+                        timeout_ms: Duration::from_millis(25),
+                    },
+                },
+            )]),
+            multiline: Some(MultilineConfig {
+                start_pattern: "INFO".to_owned(),
+                condition_pattern: "INFO".to_owned(),
+                mode: line_agg::Mode::HaltBefore,
+                timeout_ms: Duration::from_millis(25),
+            }),
+            ..test_default_file_config(&dir)
+        };
+
+        let java_path = dir.path().join("app.java.log");
+        let other_path = dir.path().join("app.log");
+        let received = run_file_source(&config, false, NoAcks, LogNamespace::Legacy, async {
+            let mut java = File::create(&java_path).unwrap();
+            let mut other = File::create(&other_path).unwrap();
+
+            sleep_500_millis().await; // The files must be observed at their original lengths before writing to them
+
+            writeln!(&mut java, "Exception in thread main").unwrap();
+            writeln!(&mut java, "  at Main.main").unwrap();
+            writeln!(&mut other, "INFO hello").unwrap();
+            writeln!(&mut other, "  part of hello").unwrap();
+
+            sleep_500_millis().await;
+        })
+        .await;
+
+        let mut received = extract_messages_string(received);
+        received.sort();
+        assert_eq!(
+            received,
+            vec![
+                "Exception in thread main\n  at Main.main",
+                "INFO hello\n  part of hello",
+            ]
+        );
+
+        let config = file::FileConfig {
+            multiline_rules: IndexMap::from([(
+                "invalid".to_owned(),
+                MultilineRuleConfig {
+                    include: vec![PathBuf::from("/var/log/[.log")],
+                    multiline: config.multiline.clone().unwrap(),
+                },
+            )]),
+            ..config
+        };
+        assert!(build_line_agg_logic(&config).is_err());
+    }
+
     #[tokio::test]
     async fn test_multi_line_checkpointing() {
         let dir = tempdir().unwrap();
@@ -1,7 +1,13 @@
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::{
-    error::Error as _, future::Future, pin::Pin, sync::Arc, task::Context, task::Poll,
-    time::Duration,
+    collections::HashMap,
+    error::Error as _,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::Context,
+    task::Poll,
+    time::{Duration, Instant},
 };
 
 use chrono::DateTime;
@@ -56,7 +62,7 @@ const MAX_ACK_DEADLINE_SECS: u64 = 600;
 // processing.
 const ACK_QUEUE_SIZE: usize = 8;
 
-type Finalizer = UnorderedFinalizer<Vec<String>>;
+type Finalizer = UnorderedFinalizer<u64>;
 
 // prost emits some generated code that includes clones on `Arc`
 // objects, which causes a clippy ding on this block. We don't
@@ -195,6 +201,39 @@ pub struct PubsubConfig {
     #[configurable(metadata(docs::human_name = "Keepalive"))]
     pub keepalive_secs: Duration,
 
+    /// The maximum number of messages received on a stream that are not yet acknowledged.
+    ///
+    /// Once reached, the stream stops receiving messages until some of its messages are
+    /// acknowledged, so that messages don't wait for slow sinks until their acknowledgement
+    /// deadline expires.
+    #[serde(default = "default_max_outstanding_messages")]
+    #[derivative(Default(value = "default_max_outstanding_messages()"))]
+    #[configurable(metadata(docs::type_unit = "messages"))]
+    pub max_outstanding_messages: usize,
+
+    /// The maximum size, in bytes, of the messages received on a stream that are not yet
+    /// acknowledged.
+    ///
+    /// Once reached, the stream stops receiving messages until some of its messages are
+    /// acknowledged.
+    #[serde(default = "default_max_outstanding_bytes")]
+    #[derivative(Default(value = "default_max_outstanding_bytes()"))]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_outstanding_bytes: usize,
+
+    /// The maximum amount of time, in seconds, the acknowledgement deadline of a message is
+    /// extended for while its events are delivered.
+    ///
+    /// With acknowledgements enabled, the deadlines of messages whose events are not delivered
+    /// yet are extended before they expire, so that slow sinks don't cause messages to be
+    /// redelivered. Messages whose events are still not delivered after this time may be
+    /// redelivered.
+    #[serde(default = "default_max_lease_extension")]
+    #[derivative(Default(value = "default_max_lease_extension()"))]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Maximum Lease Extension"))]
+    pub max_lease_extension_secs: Duration,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -243,6 +282,18 @@ const fn default_poll_time() -> Duration {
     Duration::from_secs(2)
 }
 
+const fn default_max_outstanding_messages() -> usize {
+    1000
+}
+
+const fn default_max_outstanding_bytes() -> usize {
+    100 * 1024 * 1024
+}
+
+const fn default_max_lease_extension() -> Duration {
+    Duration::from_secs(3600)
+}
+
 #[async_trait::async_trait]
 #[typetag::serde(name = "gcp_pubsub")]
 impl SourceConfig for PubsubConfig {
@@ -316,6 +367,11 @@ impl SourceConfig for PubsubConfig {
             keepalive: self.keepalive_secs,
             concurrency: Default::default(),
             full_response_size: self.full_response_size,
+            flow_control: FlowControl {
+                max_messages: self.max_outstanding_messages,
+                max_bytes: self.max_outstanding_bytes,
+            },
+            max_lease_extension: self.max_lease_extension_secs,
             log_namespace,
             bytes_received: register!(BytesReceived::from(protocol)),
             events_received: register!(EventsReceived),
@@ -384,6 +440,8 @@ struct PubsubSource {
     // would result in repeatedly re-opening the stream on idle.
     concurrency: Arc<AtomicUsize>,
     full_response_size: usize,
+    flow_control: FlowControl,
+    max_lease_extension: Duration,
     log_namespace: LogNamespace,
     bytes_received: Registered<BytesReceived>,
     events_received: Registered<EventsReceived>,
@@ -484,11 +542,11 @@ impl PubsubSource {
             },
         );
 
-        let (ack_ids_sender, ack_ids_receiver) = mpsc::channel(ACK_QUEUE_SIZE);
+        let (updates_sender, updates_receiver) = mpsc::channel(ACK_QUEUE_SIZE);
 
         // Handle shutdown during startup, the streaming pull doesn't
         // start if there is no data in the subscription.
-        let request_stream = self.request_stream(ack_ids_receiver);
+        let request_stream = self.request_stream(updates_receiver);
         debug!("Starting streaming pull.");
         let stream = tokio::select! {
             _ = &mut self.shutdown => return State::Shutdown,
@@ -504,40 +562,53 @@ impl PubsubSource {
 
         let (finalizer, mut ack_stream) =
             Finalizer::maybe_new(self.acknowledgements, Some(self.shutdown.clone()));
-        let mut pending_acks = 0;
+        let mut outstanding = Outstanding::default();
+        // Leases are extended well before the acknowledgement deadline expires.
+        let lease_period = self.ack_deadline_secs / 2;
+        let mut lease_timer =
+            tokio::time::interval_at((Instant::now() + lease_period).into(), lease_period);
 
         loop {
             tokio::select! {
                 biased;
-                receipts = ack_stream.next() => if let Some((status, receipts)) = receipts {
-                    pending_acks -= 1;
+                receipts = ack_stream.next() => if let Some((status, id)) = receipts {
+                    let ack_ids = outstanding.remove(id);
                     if status == BatchStatus::Delivered {
-                        ack_ids_sender
-                            .send(receipts)
+                        updates_sender
+                            .send(StreamUpdate::Acknowledge(ack_ids))
                             .await
                             .unwrap_or_else(|_| unreachable!("request stream never closes"));
                     }
                 },
-                response = stream.next() => match response {
+                response = stream.next(), if !outstanding.is_full(&self.flow_control) => match response {
                     Some(Ok(response)) => {
                         self.handle_response(
                             response,
                             &finalizer,
-                            &ack_ids_sender,
-                            &mut pending_acks,
+                            &updates_sender,
+                            &mut outstanding,
                             busy_flag,
                         ).await;
                     }
                     Some(Err(error)) => break translate_error(error),
                     None => break State::RetryNow,
                 },
-                _ = &mut self.shutdown, if pending_acks == 0 => return State::Shutdown,
+                _ = &mut self.shutdown, if outstanding.is_empty() => return State::Shutdown,
                 _ = self.token_generator.changed() => {
                     debug!("New authentication token generated, restarting stream.");
                     break State::RetryNow;
                 },
+                _ = lease_timer.tick(), if !outstanding.is_empty() => {
+                    let ack_ids = outstanding.leases(self.max_lease_extension);
+                    if !ack_ids.is_empty() {
+                        updates_sender
+                            .send(StreamUpdate::ExtendDeadline(ack_ids))
+                            .await
+                            .unwrap_or_else(|_| unreachable!("request stream never closes"));
+                    }
+                },
                 _ = tokio::time::sleep(self.keepalive) => {
-                    if pending_acks == 0 {
+                    if outstanding.is_empty() {
                         // No pending acks, and no new data, so drop
                         // this stream if we aren't the only active
                         // one.
@@ -555,8 +626,8 @@ impl PubsubSource {
                     // other activity has happened. This will result
                     // in a new request with empty fields, effectively
                     // a keepalive.
-                    ack_ids_sender
-                        .send(Vec::new())
+                    updates_sender
+                        .send(StreamUpdate::Acknowledge(Vec::new()))
                         .await
                         .unwrap_or_else(|_| unreachable!("request stream never closes"));
                 }
@@ -566,12 +637,14 @@ impl PubsubSource {
 
     fn request_stream(
         &self,
-        ack_ids: mpsc::Receiver<Vec<String>>,
+        updates: mpsc::Receiver<StreamUpdate>,
     ) -> impl Stream<Item = proto::StreamingPullRequest> + 'static {
         let subscription = self.subscription.clone();
         let client_id = CLIENT_ID.clone();
         let stream_ack_deadline_seconds = self.ack_deadline_secs.as_secs() as i32;
-        let ack_ids = ReceiverStream::new(ack_ids).ready_chunks(ACK_QUEUE_SIZE);
+        let max_outstanding_messages = self.flow_control.max_messages as i64;
+        let max_outstanding_bytes = self.flow_control.max_bytes as i64;
+        let updates = ReceiverStream::new(updates).ready_chunks(ACK_QUEUE_SIZE);
 
         stream::once(async move {
             // These fields are only valid on the first request in the
@@ -580,19 +653,30 @@ impl PubsubSource {
                 subscription,
                 client_id,
                 stream_ack_deadline_seconds,
+                max_outstanding_messages,
+                max_outstanding_bytes,
                 ..Default::default()
             }
         })
-        .chain(ack_ids.map(|chunks| {
+        .chain(updates.map(move |chunks| {
             // These "requests" serve only to send updates about
             // acknowledgements to the server. None of the above
             // fields need to be repeated and, in fact, will cause
             // an stream error and cancellation if they are
             // present.
-            proto::StreamingPullRequest {
-                ack_ids: chunks.into_iter().flatten().collect(),
-                ..Default::default()
+            let mut request = proto::StreamingPullRequest::default();
+            for update in chunks {
+                match update {
+                    StreamUpdate::Acknowledge(ack_ids) => request.ack_ids.extend(ack_ids),
+                    StreamUpdate::ExtendDeadline(ack_ids) => {
+                        request
+                            .modify_deadline_seconds
+                            .extend(ack_ids.iter().map(|_| stream_ack_deadline_seconds));
+                        request.modify_deadline_ack_ids.extend(ack_ids);
+                    }
+                }
             }
+            request
         }))
     }
 
@@ -600,14 +684,15 @@ impl PubsubSource {
         &mut self,
         response: proto::StreamingPullResponse,
         finalizer: &Option<Finalizer>,
-        ack_ids: &mpsc::Sender<Vec<String>>,
-        pending_acks: &mut usize,
+        updates: &mpsc::Sender<StreamUpdate>,
+        outstanding: &mut Outstanding,
         busy_flag: &Arc<AtomicBool>,
     ) {
         if response.received_messages.len() >= self.full_response_size {
             busy_flag.store(true, Ordering::Relaxed);
         }
-        self.bytes_received.emit(ByteSize(response.size_of()));
+        let byte_size = response.size_of();
+        self.bytes_received.emit(ByteSize(byte_size));
 
         let (batch, notifier) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
         let (events, ids) = self.parse_messages(response.received_messages, batch).await;
@@ -616,16 +701,16 @@ impl PubsubSource {
         match self.out.send_batch(events).await {
             Err(_) => emit!(StreamClosedError { count }),
             Ok(()) => match notifier {
-                None => ack_ids
-                    .send(ids)
+                None => updates
+                    .send(StreamUpdate::Acknowledge(ids))
                     .await
                     .unwrap_or_else(|_| unreachable!("request stream never closes")),
                 Some(notifier) => {
+                    let id = outstanding.add(ids, byte_size);
                     finalizer
                         .as_ref()
                         .expect("Finalizer must have been set up for acknowledgements")
-                        .add(ids, notifier);
-                    *pending_acks += 1;
+                        .add(id, notifier);
                 }
             },
         }
@@ -696,6 +781,83 @@ impl PubsubSource {
     }
 }
 
+/// Updates sent to the server over the request stream of a streaming pull.
+enum StreamUpdate {
+    /// Acknowledges messages, which are then not redelivered.
+    Acknowledge(Vec<String>),
+    /// Extends the acknowledgement deadlines of messages by the stream's deadline.
+    ExtendDeadline(Vec<String>),
+}
+
+/// The limits on the messages a stream has received but not acknowledged yet.
+#[derive(Clone, Copy, Debug)]
+struct FlowControl {
+    max_messages: usize,
+    max_bytes: usize,
+}
+
+/// The batches of messages a stream has received whose events are not delivered yet.
+#[derive(Default)]
+struct Outstanding {
+    next_id: u64,
+    batches: HashMap<u64, OutstandingBatch>,
+    messages: usize,
+    bytes: usize,
+}
+
+struct OutstandingBatch {
+    ack_ids: Vec<String>,
+    bytes: usize,
+    received_at: Instant,
+}
+
+impl Outstanding {
+    /// Tracks a batch of messages until its events are delivered, returning the batch's ID.
+    fn add(&mut self, ack_ids: Vec<String>, bytes: usize) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.messages += ack_ids.len();
+        self.bytes += bytes;
+        self.batches.insert(
+            id,
+            OutstandingBatch {
+                ack_ids,
+                bytes,
+                received_at: Instant::now(),
+            },
+        );
+        id
+    }
+
+    /// Stops tracking a batch, returning the acknowledgement IDs of its messages.
+    fn remove(&mut self, id: u64) -> Vec<String> {
+        let Some(batch) = self.batches.remove(&id) else {
+            return Vec::new();
+        };
+        self.messages -= batch.ack_ids.len();
+        self.bytes -= batch.bytes;
+        batch.ack_ids
+    }
+
+    fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+
+    fn is_full(&self, flow_control: &FlowControl) -> bool {
+        self.messages >= flow_control.max_messages || self.bytes >= flow_control.max_bytes
+    }
+
+    /// The acknowledgement IDs of the messages whose leases are still extended, which are those
+    /// received less than `max_extension` ago.
+    fn leases(&self, max_extension: Duration) -> Vec<String> {
+        self.batches
+            .values()
+            .filter(|batch| batch.received_at.elapsed() < max_extension)
+            .flat_map(|batch| batch.ack_ids.iter().cloned())
+            .collect()
+    }
+}
+
 fn translate_error(error: tonic::Status) -> State {
     // GCP occasionally issues a connection reset
     // in the middle of the streaming pull. This
@@ -747,6 +909,30 @@ mod tests {
         crate::test_util::test_generate_config::<PubsubConfig>();
     }
 
+    #[test]
+    fn tracks_outstanding_messages() {
+        let flow_control = FlowControl {
+            max_messages: 3,
+            max_bytes: 100,
+        };
+        let mut outstanding = Outstanding::default();
+
+        let first = outstanding.add(vec!["a".into(), "b".into()], 10);
+        assert!(!outstanding.is_full(&flow_control));
+        let second = outstanding.add(vec!["c".into()], 10);
+        assert!(outstanding.is_full(&flow_control));
+        assert_eq!(outstanding.leases(Duration::from_secs(60)).len(), 3);
+        assert!(outstanding.leases(Duration::ZERO).is_empty());
+
+        assert_eq!(outstanding.remove(first), ["a", "b"]);
+        assert!(!outstanding.is_full(&flow_control));
+        assert_eq!(outstanding.remove(second), ["c"]);
+        assert!(outstanding.is_empty());
+
+        outstanding.add(vec!["d".into()], 100);
+        assert!(outstanding.is_full(&flow_control));
+    }
+
     #[test]
     fn output_schema_definition_vector_namespace() {
         let config = PubsubConfig {
@@ -9,6 +9,7 @@ use glob::{Pattern, PatternError};
 #[cfg(not(windows))]
 use heim::units::ratio::ratio;
 use heim::units::time::second;
+use regex::Regex;
 use serde_with::serde_as;
 use tokio::time;
 use tokio_stream::wrappers::IntervalStream;
@@ -34,6 +35,8 @@ mod disk;
 mod filesystem;
 mod memory;
 mod network;
+#[cfg(target_os = "linux")]
+mod process;
 
 /// Collector types.
 #[serde_as]
@@ -66,6 +69,11 @@ pub enum Collector {
 
     /// Metrics related to network utilization.
     Network,
+
+    /// Metrics related to the CPU, memory, and I/O utilization of processes.
+    ///
+    /// Only available on Linux.
+    Process,
 }
 
 /// Filtering configuration.
@@ -125,6 +133,10 @@ pub struct HostMetricsConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub network: network::NetworkConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub process: ProcessConfig,
 }
 
 /// Options for the cgroups (controller groups) metrics collector.
@@ -162,6 +174,33 @@ pub struct CGroupsConfig {
     base_dir: Option<PathBuf>,
 }
 
+/// Options for the process metrics collector.
+///
+/// This collector is only available on Linux systems. It isn't enabled by default, as it reports
+/// metrics for every matching process.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(default)]
+pub struct ProcessConfig {
+    /// Regular expressions matched against the names of processes.
+    ///
+    /// If set, only the processes whose name matches one of the expressions are reported.
+    #[configurable(metadata(docs::examples = "^nginx$"))]
+    names: Vec<RegexWrapper>,
+
+    /// Regular expressions matched against the command lines of processes, with their arguments
+    /// separated by spaces.
+    ///
+    /// If set, only the processes whose command line matches one of the expressions are reported.
+    #[configurable(metadata(docs::examples = "--config /etc/app"))]
+    cmdlines: Vec<RegexWrapper>,
+
+    /// Lists of cgroup name patterns of the processes to include or exclude in gathering usage
+    /// metrics.
+    #[configurable(metadata(docs::examples = "example_process_cgroups()"))]
+    cgroups: FilterList,
+}
+
 const fn default_scrape_interval() -> Duration {
     Duration::from_secs(15)
 }
@@ -170,7 +209,7 @@ pub fn default_namespace() -> Option<String> {
     Some(String::from("host"))
 }
 
-const fn example_collectors() -> [&'static str; 8] {
+const fn example_collectors() -> [&'static str; 9] {
     [
         "cgroups",
         "cpu",
@@ -180,6 +219,7 @@ const fn example_collectors() -> [&'static str; 8] {
         "host",
         "memory",
         "network",
+        "process",
     ]
 }
 
@@ -231,6 +271,13 @@ fn example_cgroups() -> FilterList {
     }
 }
 
+fn example_process_cgroups() -> FilterList {
+    FilterList {
+        includes: Some(vec!["system.slice/*".try_into().unwrap()]),
+        excludes: None,
+    }
+}
+
 fn default_cgroups_config() -> Option<CGroupsConfig> {
     // Check env variable to allow generating docs on non-linux systems.
     if std::env::var("VECTOR_GENERATE_SCHEMA").is_ok() {
@@ -261,6 +308,9 @@ impl SourceConfig for HostMetricsConfig {
             if self.cgroups.is_some() || self.has_collector(Collector::CGroups) {
                 return Err("CGroups collector is only available on Linux systems".into());
             }
+            if self.has_collector(Collector::Process) {
+                return Err("Process collector is only available on Linux systems".into());
+            }
         }
 
         let mut config = self.clone();
@@ -373,6 +423,10 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Network) {
             self.network_metrics(&mut buffer).await;
         }
+        #[cfg(target_os = "linux")]
+        if self.config.has_collector(Collector::Process) {
+            self.process_metrics(&mut buffer).await;
+        }
 
         let metrics = buffer.metrics;
         self.events_received.emit(CountByteSize(
@@ -626,6 +680,33 @@ impl From<PatternWrapper> for String {
     }
 }
 
+/// A compiled regular expression.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(try_from = "String", into = "String")]
+struct RegexWrapper(Regex);
+
+impl RegexWrapper {
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn is_match(&self, s: &str) -> bool {
+        self.0.is_match(s)
+    }
+}
+
+impl TryFrom<String> for RegexWrapper {
+    type Error = regex::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Regex::new(&value).map(RegexWrapper)
+    }
+}
+
+impl From<RegexWrapper> for String {
+    fn from(regex: RegexWrapper) -> Self {
+        regex.0.as_str().to_owned()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::test_util::components::{run_and_assert_source_compliance, SOURCE_TAGS};
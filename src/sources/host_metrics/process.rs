@@ -0,0 +1,411 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use snafu::{OptionExt, ResultExt, Snafu};
+use tokio::fs;
+use vector_lib::metric_tags;
+
+use super::{filter_result_sync, HostMetrics, MetricsBuffer, ProcessConfig};
+
+/// The unit of the CPU times of `/proc/<pid>/stat`, fixed by the kernel ABI.
+const CLOCK_TICKS_PER_SECOND: f64 = 100.0;
+
+const KIBIBYTES: f64 = 1024.0;
+
+#[derive(Debug, Snafu)]
+enum ProcessError {
+    #[snafu(display("Could not read process data file {:?}.", filename))]
+    Reading {
+        filename: PathBuf,
+        source: io::Error,
+    },
+    #[snafu(display("Could not parse process data file {:?}.", filename))]
+    Parsing { filename: PathBuf },
+}
+
+type ProcessResult<T> = Result<T, ProcessError>;
+
+impl HostMetrics {
+    pub(super) async fn process_metrics(&self, output: &mut MetricsBuffer) {
+        output.name = "process";
+        let Some(mut entries) = filter_result_sync(
+            fs::read_dir(procfs_root()).await,
+            "Failed to list processes.",
+        ) else {
+            return;
+        };
+
+        while let Some(Some(entry)) =
+            filter_result_sync(entries.next_entry().await, "Failed to list processes.")
+        {
+            let Some(pid) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            if let Some(Some(process)) = filter_result_sync(
+                Process::load(pid, &entry.path(), &self.config.process).await,
+                "Failed to load process statistics.",
+            ) {
+                process.emit(output);
+            }
+        }
+    }
+}
+
+/// The statistics of a single process.
+#[derive(Debug, Default, PartialEq)]
+struct Process {
+    pid: u32,
+    name: String,
+    status: ProcessStatus,
+    stat: ProcessStat,
+    io: Option<ProcessIo>,
+}
+
+impl Process {
+    /// Loads the statistics of the process, or returns `None` if it exited or isn't matched by the
+    /// configured filters.
+    async fn load(pid: u32, path: &Path, config: &ProcessConfig) -> ProcessResult<Option<Self>> {
+        let status_path = path.join("status");
+        let Some(status) = read(&status_path).await? else {
+            return Ok(None);
+        };
+        let (name, status) = parse_status(&status).context(ParsingSnafu {
+            filename: status_path,
+        })?;
+        if !config.names.is_empty() && !config.names.iter().any(|regex| regex.is_match(&name)) {
+            return Ok(None);
+        }
+
+        if !config.cmdlines.is_empty() {
+            let Some(cmdline) = read(&path.join("cmdline")).await? else {
+                return Ok(None);
+            };
+            let cmdline = parse_cmdline(&cmdline);
+            if !config.cmdlines.iter().any(|regex| regex.is_match(&cmdline)) {
+                return Ok(None);
+            }
+        }
+
+        if config.cgroups.includes.is_some() || config.cgroups.excludes.is_some() {
+            let cgroup = read(&path.join("cgroup")).await?;
+            let cgroup = cgroup.as_deref().and_then(parse_cgroup);
+            if !config.cgroups.contains_path(cgroup.map(Path::new)) {
+                return Ok(None);
+            }
+        }
+
+        let stat_path = path.join("stat");
+        let Some(stat) = read(&stat_path).await? else {
+            return Ok(None);
+        };
+        let stat = parse_stat(&stat).context(ParsingSnafu {
+            filename: stat_path,
+        })?;
+
+        // The I/O statistics of the processes of other users are only readable with privileges.
+        let io = read(&path.join("io")).await?.as_deref().and_then(parse_io);
+
+        Ok(Some(Self {
+            pid,
+            name,
+            status,
+            stat,
+            io,
+        }))
+    }
+
+    fn emit(self, output: &mut MetricsBuffer) {
+        let tags = metric_tags!(
+            "pid" => self.pid.to_string(),
+            "name" => self.name,
+        );
+        let mode_tags = |mode: &str| {
+            let mut tags = tags.clone();
+            tags.replace("mode".into(), mode.to_owned());
+            tags
+        };
+        output.counter(
+            "process_cpu_seconds_total",
+            self.stat.user_ticks as f64 / CLOCK_TICKS_PER_SECOND,
+            mode_tags("user"),
+        );
+        output.counter(
+            "process_cpu_seconds_total",
+            self.stat.system_ticks as f64 / CLOCK_TICKS_PER_SECOND,
+            mode_tags("system"),
+        );
+        output.gauge(
+            "process_memory_resident_bytes",
+            self.status.resident_kibibytes as f64 * KIBIBYTES,
+            tags.clone(),
+        );
+        output.gauge(
+            "process_memory_virtual_bytes",
+            self.status.virtual_kibibytes as f64 * KIBIBYTES,
+            tags.clone(),
+        );
+        output.gauge("process_threads", self.status.threads as f64, tags.clone());
+        if let Some(io) = self.io {
+            output.counter(
+                "process_io_read_bytes_total",
+                io.read_bytes as f64,
+                tags.clone(),
+            );
+            output.counter(
+                "process_io_written_bytes_total",
+                io.write_bytes as f64,
+                tags,
+            );
+        }
+    }
+}
+
+/// The fields of `/proc/<pid>/status`.
+#[derive(Debug, Default, PartialEq)]
+struct ProcessStatus {
+    threads: u64,
+    resident_kibibytes: u64,
+    virtual_kibibytes: u64,
+}
+
+/// The fields of `/proc/<pid>/stat`.
+#[derive(Debug, Default, PartialEq)]
+struct ProcessStat {
+    user_ticks: u64,
+    system_ticks: u64,
+}
+
+/// The fields of `/proc/<pid>/io`.
+#[derive(Debug, Default, PartialEq)]
+struct ProcessIo {
+    read_bytes: u64,
+    write_bytes: u64,
+}
+
+fn procfs_root() -> PathBuf {
+    std::env::var_os("PROCFS_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/proc"))
+}
+
+/// Reads a process data file, or returns `None` if the process exited or the file isn't readable.
+async fn read(path: &Path) -> ProcessResult<Option<String>> {
+    match fs::read(path).await {
+        Ok(contents) => Ok(Some(String::from_utf8_lossy(&contents).into_owned())),
+        Err(error)
+            if matches!(
+                error.kind(),
+                io::ErrorKind::NotFound | io::ErrorKind::PermissionDenied
+            ) =>
+        {
+            Ok(None)
+        }
+        Err(error) => Err(error).context(ReadingSnafu { filename: path }),
+    }
+}
+
+/// Parses the name of the process and its statistics from the `key: value` lines of the file.
+///
+/// Kernel threads have no memory statistics, which are left as zero.
+fn parse_status(status: &str) -> Option<(String, ProcessStatus)> {
+    let mut name = None;
+    let mut stats = ProcessStatus::default();
+    for line in status.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        // Memory sizes are suffixed with their unit, which is always `kB`.
+        let number = || value.trim_end_matches(" kB").parse().ok();
+        match key {
+            "Name" => name = Some(value.to_owned()),
+            "Threads" => stats.threads = number()?,
+            "VmRSS" => stats.resident_kibibytes = number()?,
+            "VmSize" => stats.virtual_kibibytes = number()?,
+            _ => {}
+        }
+    }
+    Some((name?, stats))
+}
+
+/// Parses the CPU times of the process, the fields following the name of the process, which may
+/// itself hold spaces and parentheses.
+fn parse_stat(stat: &str) -> Option<ProcessStat> {
+    let (_, fields) = stat.rsplit_once(')')?;
+    // The fields after the name start from the third field of the file, the state of the process,
+    // while the user and system times are the 14th and 15th fields.
+    let mut fields = fields.split_whitespace().skip(11);
+    Some(ProcessStat {
+        user_ticks: fields.next()?.parse().ok()?,
+        system_ticks: fields.next()?.parse().ok()?,
+    })
+}
+
+/// Parses the bytes read from and written to storage by the process.
+fn parse_io(io: &str) -> Option<ProcessIo> {
+    let mut stats = ProcessIo::default();
+    for line in io.lines() {
+        match line.split_once(": ") {
+            Some(("read_bytes", value)) => stats.read_bytes = value.trim().parse().ok()?,
+            Some(("write_bytes", value)) => stats.write_bytes = value.trim().parse().ok()?,
+            _ => {}
+        }
+    }
+    Some(stats)
+}
+
+/// Parses the command line of the process, whose arguments are separated by NUL characters.
+fn parse_cmdline(cmdline: &str) -> String {
+    cmdline
+        .split('\0')
+        .filter(|argument| !argument.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses the cgroup of the process, relative to the root cgroup, from the `id:controllers:path`
+/// lines of the file.
+///
+/// The cgroup of the unified hierarchy of version 2 or hybrid cgroups is preferred, falling back to
+/// the first legacy hierarchy.
+fn parse_cgroup(cgroup: &str) -> Option<&str> {
+    let paths = cgroup
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ':');
+            Some((parts.next()?, parts.nth(1)?))
+        })
+        .collect::<Vec<_>>();
+    paths
+        .iter()
+        .find(|(id, _)| *id == "0")
+        .or_else(|| paths.first())
+        .map(|(_, path)| path.trim_start_matches('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::{
+        super::{
+            tests::{all_counters, count_name, count_tag},
+            FilterList, HostMetrics, HostMetricsConfig, ProcessConfig,
+        },
+        parse_cgroup, parse_cmdline, parse_io, parse_stat, parse_status, MetricsBuffer, ProcessIo,
+        ProcessStat, ProcessStatus,
+    };
+
+    #[test]
+    fn parses_status() {
+        let status = "Name:\tnginx\nUmask:\t0022\nState:\tS (sleeping)\nVmSize:\t   12345 kB\nVmRSS:\t    2048 kB\nThreads:\t4\n";
+        assert_eq!(
+            parse_status(status),
+            Some((
+                "nginx".to_owned(),
+                ProcessStatus {
+                    threads: 4,
+                    resident_kibibytes: 2048,
+                    virtual_kibibytes: 12345,
+                }
+            ))
+        );
+
+        // Kernel threads have no memory statistics.
+        let (name, status) = parse_status("Name:\tkthreadd\nThreads:\t1\n").unwrap();
+        assert_eq!(name, "kthreadd");
+        assert_eq!(status.resident_kibibytes, 0);
+    }
+
+    #[test]
+    fn parses_stat() {
+        let stat = "1234 (my (odd) name) S 1 1234 1234 0 -1 4194560 1500 0 0 0 250 75 0 0 20 0 4 0 100 12345678 512 18446744073709551615";
+        assert_eq!(
+            parse_stat(stat),
+            Some(ProcessStat {
+                user_ticks: 250,
+                system_ticks: 75,
+            })
+        );
+        assert_eq!(parse_stat("1234 (truncated) S 1"), None);
+    }
+
+    #[test]
+    fn parses_io() {
+        let io = "rchar: 4096\nwchar: 1024\nsyscr: 10\nsyscw: 5\nread_bytes: 8192\nwrite_bytes: 512\ncancelled_write_bytes: 0\n";
+        assert_eq!(
+            parse_io(io),
+            Some(ProcessIo {
+                read_bytes: 8192,
+                write_bytes: 512,
+            })
+        );
+    }
+
+    #[test]
+    fn parses_cmdline() {
+        assert_eq!(
+            parse_cmdline("/usr/bin/app\0--config\0/etc/app.toml\0"),
+            "/usr/bin/app --config /etc/app.toml"
+        );
+    }
+
+    #[test]
+    fn parses_cgroup() {
+        assert_eq!(
+            parse_cgroup("0::/system.slice/nginx.service\n"),
+            Some("system.slice/nginx.service")
+        );
+        assert_eq!(
+            parse_cgroup("12:memory:/user.slice\n11:cpu,cpuacct:/user.slice\n0::/user.slice/session-1.scope\n"),
+            Some("user.slice/session-1.scope")
+        );
+        assert_eq!(parse_cgroup("12:memory:/docker/abc\n"), Some("docker/abc"));
+    }
+
+    #[tokio::test]
+    async fn generates_process_metrics() {
+        let mut buffer = MetricsBuffer::new(None);
+        HostMetrics::new(HostMetricsConfig::default())
+            .process_metrics(&mut buffer)
+            .await;
+        let metrics = buffer.metrics;
+
+        let pid = std::process::id().to_string();
+        assert!(metrics
+            .iter()
+            .any(|metric| metric.tags().unwrap().get("pid") == Some(pid.as_str())));
+        assert_eq!(
+            count_name(&metrics, "process_cpu_seconds_total"),
+            count_tag(&metrics, "mode")
+        );
+        assert!(all_counters(
+            &metrics
+                .into_iter()
+                .filter(|metric| metric.name().ends_with("_total"))
+                .collect::<Vec<_>>()
+        ));
+    }
+
+    #[tokio::test]
+    async fn filters_processes() {
+        let mut buffer = MetricsBuffer::new(None);
+        HostMetrics::new(HostMetricsConfig {
+            process: ProcessConfig {
+                names: vec!["^no such process$".to_owned().try_into().unwrap()],
+                cmdlines: vec![],
+                cgroups: FilterList::default(),
+            },
+            ..Default::default()
+        })
+        .process_metrics(&mut buffer)
+        .await;
+        assert!(buffer.metrics.is_empty());
+    }
+}
@@ -88,6 +88,10 @@ enum BuildError {
         value,
     ))]
     DuplicatedMatches { field: String, value: String },
+    #[snafu(display("The match groups of include_match_groups must not be empty"))]
+    EmptyMatchGroup,
+    #[snafu(display("Only one of journal_directory and journal_files can be set"))]
+    ConflictingJournalSources,
 }
 
 type Matches = HashMap<String, HashSet<String>>;
@@ -145,6 +149,22 @@ pub struct JournaldConfig {
     #[configurable(metadata(docs::examples = "matches_examples()"))]
     pub exclude_matches: Matches,
 
+    /// A list of groups of field/value pairs to monitor, passed to `journalctl` as [matches][matches].
+    ///
+    /// An entry is included if it matches all the fields of any of the groups, an entry matching a
+    /// field of a group if its value for the field is one of the values of the group. For example,
+    /// this includes the entries of a unit of the priorities from emergency to error, along with
+    /// all the entries of the kernel.
+    ///
+    /// As the entries are filtered by the journal, this is more efficient than `include_matches`,
+    /// which is applied to the entries read. The entries also need to match `include_matches` and
+    /// `include_units`, if specified.
+    ///
+    /// [matches]: https://www.freedesktop.org/software/systemd/man/latest/journalctl.html#Description
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "match_groups_examples()"))]
+    pub include_match_groups: Vec<Matches>,
+
     /// The directory used to persist file checkpoint positions.
     ///
     /// By default, the [global `data_dir` option][global_data_dir] is used.
@@ -184,10 +204,24 @@ pub struct JournaldConfig {
     #[serde(default)]
     pub journal_directory: Option<PathBuf>,
 
+    /// The journal files to read, such as the archives of `systemd-journal-remote`.
+    ///
+    /// The paths are passed to `journalctl` through the [`--file` option][journalctl-file-option],
+    /// supporting glob patterns. The entries of all the files are merged, so a single source can
+    /// read the journals received from several remote machines. If set, `journal_directory` must
+    /// not be set.
+    ///
+    /// [journalctl-file-option]: https://www.freedesktop.org/software/systemd/man/latest/journalctl.html#--file=GLOB
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/log/journal/remote/*.journal"))]
+    pub journal_files: Vec<PathBuf>,
+
     /// The [journal namespace][journal-namespace].
     ///
     /// This value is passed to `journalctl` through the [`--namespace` option][journalctl-namespace-option].
-    /// If not set, `journalctl` uses the default namespace.
+    /// If not set, `journalctl` uses the default namespace. The entries of all namespaces are read
+    /// with `*`, and the entries of a namespace along with the ones of the default namespace with
+    /// the name of the namespace prefixed with `+`.
     ///
     /// [journal-namespace]: https://www.freedesktop.org/software/systemd/man/systemd-journald.service.html#Journal%20Namespaces
     /// [journalctl-namespace-option]: https://www.freedesktop.org/software/systemd/man/journalctl.html#--namespace=NAMESPACE
@@ -224,6 +258,24 @@ const fn default_batch_size() -> usize {
     16
 }
 
+fn match_groups_examples() -> Vec<HashMap<String, Vec<String>>> {
+    vec![
+        HashMap::<_, _>::from_iter([
+            ("_SYSTEMD_UNIT".to_owned(), vec!["sshd.service".to_owned()]),
+            (
+                "PRIORITY".to_owned(),
+                vec![
+                    "0".to_owned(),
+                    "1".to_owned(),
+                    "2".to_owned(),
+                    "3".to_owned(),
+                ],
+            ),
+        ]),
+        HashMap::<_, _>::from_iter([("_TRANSPORT".to_owned(), vec!["kernel".to_owned()])]),
+    ]
+}
+
 fn matches_examples() -> HashMap<String, Vec<String>> {
     HashMap::<_, _>::from_iter([
         (
@@ -311,7 +363,9 @@ impl Default for JournaldConfig {
             data_dir: None,
             batch_size: default_batch_size(),
             journalctl_path: None,
+            include_match_groups: vec![],
             journal_directory: None,
+            journal_files: vec![],
             journal_namespace: None,
             extra_args: vec![],
             acknowledgements: Default::default(),
@@ -355,6 +409,18 @@ impl SourceConfig for JournaldConfig {
             return Err(BuildError::DuplicatedMatches { field, value }.into());
         }
 
+        if self
+            .include_match_groups
+            .iter()
+            .any(|group| group.is_empty())
+        {
+            return Err(BuildError::EmptyMatchGroup.into());
+        }
+
+        if self.journal_directory.is_some() && !self.journal_files.is_empty() {
+            return Err(BuildError::ConflictingJournalSources.into());
+        }
+
         let mut checkpoint_path = data_dir;
         checkpoint_path.push(CHECKPOINT_FILENAME);
 
@@ -366,10 +432,12 @@ impl SourceConfig for JournaldConfig {
         let starter = StartJournalctl::new(
             journalctl_path,
             self.journal_directory.clone(),
+            self.journal_files.clone(),
             self.journal_namespace.clone(),
             self.current_boot_only,
             self.since_now,
             self.extra_args.clone(),
+            self.include_match_groups.clone(),
         );
 
         let batch_size = self.batch_size;
@@ -646,28 +714,35 @@ type JournalStream = BoxStream<'static, Result<Bytes, BoxedFramingError>>;
 struct StartJournalctl {
     path: PathBuf,
     journal_dir: Option<PathBuf>,
+    journal_files: Vec<PathBuf>,
     journal_namespace: Option<String>,
     current_boot_only: bool,
     since_now: bool,
     extra_args: Vec<String>,
+    match_groups: Vec<Matches>,
 }
 
 impl StartJournalctl {
+    #[allow(clippy::too_many_arguments)]
     const fn new(
         path: PathBuf,
         journal_dir: Option<PathBuf>,
+        journal_files: Vec<PathBuf>,
         journal_namespace: Option<String>,
         current_boot_only: bool,
         since_now: bool,
         extra_args: Vec<String>,
+        match_groups: Vec<Matches>,
     ) -> Self {
         Self {
             path,
             journal_dir,
+            journal_files,
             journal_namespace,
             current_boot_only,
             since_now,
             extra_args,
+            match_groups,
         }
     }
 
@@ -683,6 +758,10 @@ impl StartJournalctl {
             command.arg(format!("--directory={}", dir.display()));
         }
 
+        for file in &self.journal_files {
+            command.arg(format!("--file={}", file.display()));
+        }
+
         if let Some(namespace) = &self.journal_namespace {
             command.arg(format!("--namespace={}", namespace));
         }
@@ -704,6 +783,9 @@ impl StartJournalctl {
             command.args(&self.extra_args);
         }
 
+        // The matches are positional arguments, so they follow all the options.
+        command.args(match_group_args(&self.match_groups));
+
         command
     }
 
@@ -929,6 +1011,31 @@ fn contains_match(record: &Record, matches: &Matches) -> bool {
     record.iter().any(f)
 }
 
+/// Builds the `journalctl` matches of the groups, the groups being separated by `+`.
+///
+/// Within a group, the values of a field are alternatives, while the fields are all required. The
+/// matches are sorted to keep the command stable.
+fn match_group_args(groups: &[Matches]) -> Vec<String> {
+    let mut args = Vec::new();
+    for group in groups {
+        if !args.is_empty() {
+            args.push("+".to_owned());
+        }
+        let mut fields = group.iter().collect::<Vec<_>>();
+        fields.sort_unstable_by_key(|(field, _)| *field);
+        for (field, values) in fields {
+            let mut values = values.iter().collect::<Vec<_>>();
+            values.sort_unstable();
+            args.extend(
+                values
+                    .into_iter()
+                    .map(|value| format!("{}={}", field, value)),
+            );
+        }
+    }
+    args
+}
+
 fn find_duplicate_match(a_matches: &Matches, b_matches: &Matches) -> Option<(String, String)> {
     for (a_key, a_values) in a_matches {
         if let Some(b_values) = b_matches.get(a_key.as_str()) {
@@ -1539,6 +1646,78 @@ mod tests {
         assert!(cmd_line.contains("--merge"));
     }
 
+    #[test]
+    fn command_journal_files_and_match_groups() {
+        let match_groups = vec![
+            create_matches(vec![
+                ("PRIORITY", "3"),
+                ("_SYSTEMD_UNIT", "sshd.service"),
+                ("PRIORITY", "2"),
+            ]),
+            create_matches(vec![("_TRANSPORT", "kernel")]),
+        ];
+        let command = StartJournalctl::new(
+            PathBuf::from("journalctl"),
+            None,
+            vec![
+                PathBuf::from("/var/log/journal/remote/a.journal"),
+                PathBuf::from("/var/log/journal/remote/b*.journal"),
+            ],
+            Some(String::from("*")),
+            false,
+            false,
+            vec!["--merge".to_string()],
+            match_groups,
+        )
+        .make_command(None);
+        let cmd_line = format!("{:?}", command);
+        assert!(cmd_line.contains("\"--file=/var/log/journal/remote/a.journal\""));
+        assert!(cmd_line.contains("\"--file=/var/log/journal/remote/b*.journal\""));
+        assert!(cmd_line.contains("\"--namespace=*\""));
+        assert!(cmd_line.ends_with(
+            "\"--merge\" \"PRIORITY=2\" \"PRIORITY=3\" \"_SYSTEMD_UNIT=sshd.service\" \"+\" \"_TRANSPORT=kernel\""
+        ));
+
+        assert!(match_group_args(&[]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_journal_options() {
+        let tempdir = tempdir().unwrap();
+        let build = |config: JournaldConfig| {
+            let (cx, _shutdown) = SourceContext::new_shutdown(
+                &ComponentKey::from(TEST_COMPONENT),
+                SourceSender::new_test().0,
+            );
+            async move { config.build(cx).await.map(|_| ()).unwrap_err() }
+        };
+
+        let error = build(JournaldConfig {
+            journalctl_path: Some(TEST_JOURNALCTL.into()),
+            data_dir: Some(tempdir.path().to_path_buf()),
+            include_match_groups: vec![
+                create_matches(vec![("_TRANSPORT", "kernel")]),
+                HashMap::new(),
+            ],
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(error.to_string(), BuildError::EmptyMatchGroup.to_string());
+
+        let error = build(JournaldConfig {
+            journalctl_path: Some(TEST_JOURNALCTL.into()),
+            data_dir: Some(tempdir.path().to_path_buf()),
+            journal_directory: Some(PathBuf::from("/var/log/journal")),
+            journal_files: vec![PathBuf::from("/var/log/journal/remote/*.journal")],
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(
+            error.to_string(),
+            BuildError::ConflictingJournalSources.to_string()
+        );
+    }
+
     fn create_command(
         path: &Path,
         journal_dir: Option<PathBuf>,
@@ -1551,10 +1730,12 @@ mod tests {
         StartJournalctl::new(
             path.into(),
             journal_dir,
+            vec![],
             journal_namespace,
             current_boot_only,
             since_now,
             extra_args,
+            vec![],
         )
         .make_command(cursor)
     }
@@ -4,7 +4,7 @@ use std::{
     pin::Pin,
     sync::{
         mpsc::{sync_channel, SyncSender},
-        Arc, OnceLock, Weak,
+        Arc, Mutex, OnceLock, Weak,
     },
     time::Duration,
 };
@@ -14,18 +14,19 @@ use bytes::Bytes;
 use chrono::{DateTime, TimeZone, Utc};
 use futures::{Stream, StreamExt};
 use futures_util::future::OptionFuture;
+use once_cell::sync::Lazy;
 use rdkafka::{
     consumer::{
-        stream_consumer::StreamPartitionQueue, CommitMode, Consumer, ConsumerContext, Rebalance,
-        StreamConsumer,
+        stream_consumer::StreamPartitionQueue, CommitMode, Consumer, ConsumerContext,
+        ConsumerGroupMetadata, Rebalance, StreamConsumer,
     },
-    error::KafkaError,
+    error::{KafkaError, KafkaResult},
     message::{BorrowedMessage, Headers as _, Message},
     types::RDKafkaErrorCode,
-    ClientConfig, ClientContext, Statistics, TopicPartitionList,
+    ClientConfig, ClientContext, Offset, Statistics, TopicPartitionList,
 };
 use serde_with::serde_as;
-use snafu::{ResultExt, Snafu};
+use snafu::{OptionExt, ResultExt, Snafu};
 use tokio::{
     runtime::Handle,
     sync::{
@@ -41,7 +42,9 @@ use vector_lib::codecs::{
     decoding::{DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
-use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath};
+use vector_lib::lookup::{
+    lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath, PathPrefix,
+};
 
 use vector_lib::configurable::configurable_component;
 use vector_lib::finalizer::OrderedFinalizer;
@@ -54,8 +57,8 @@ use vrl::value::{kind::Collection, Kind, ObjectMap};
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{
-        log_schema, LogSchema, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
-        SourceOutput,
+        log_schema, ComponentKey, LogSchema, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext, SourceOutput,
     },
     event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
@@ -79,6 +82,21 @@ enum BuildError {
     CreateError { source: rdkafka::error::KafkaError },
     #[snafu(display("Could not subscribe to Kafka topics: {}", source))]
     SubscribeError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Exactly one of `topics` and `partitions` must be set"))]
+    TopicsOrPartitions,
+    #[snafu(display("Could not assign Kafka partitions: {}", source))]
+    AssignError { source: rdkafka::error::KafkaError },
+}
+
+/// Errors pausing or resuming the consumption of partitions by a running source.
+#[derive(Debug, Snafu)]
+pub enum PartitionControlError {
+    #[snafu(display("No running kafka source has the ID {}.", key))]
+    UnknownSource { key: ComponentKey },
+    #[snafu(display("Partition {}:{} is not assigned to the source.", topic, partition))]
+    UnassignedPartition { topic: String, partition: i32 },
+    #[snafu(display("Could not update the partitions: {}", source))]
+    UpdatePartitions { source: rdkafka::error::KafkaError },
 }
 
 /// Metrics (beta) configuration.
@@ -89,6 +107,78 @@ struct Metrics {
     pub topic_lag_metric: bool,
 }
 
+/// A partition statically assigned to the source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct KafkaPartitionConfig {
+    /// The topic of the partition.
+    #[configurable(metadata(docs::examples = "topic-1"))]
+    topic: String,
+
+    /// The number of the partition within its topic.
+    #[configurable(metadata(docs::examples = 0))]
+    partition: i32,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    start: KafkaStartPosition,
+}
+
+/// The position the consumption of a partition starts from.
+///
+/// The position is applied each time the source starts, so only `committed` resumes from the
+/// progress of previous runs.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(tag = "from", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "Where the consumption starts."))]
+enum KafkaStartPosition {
+    /// The offset committed for the consumer group, or as set by `auto_offset_reset` when there
+    /// is none.
+    #[default]
+    Committed,
+
+    /// The oldest message of the partition.
+    Beginning,
+
+    /// The next message produced to the partition.
+    End,
+
+    /// The message at the given offset.
+    Offset {
+        /// The offset of the message.
+        ///
+        /// Negative offsets count back from the end of the partition, so that `-100` starts from
+        /// the last 100 messages.
+        #[configurable(metadata(docs::examples = 1234))]
+        offset: i64,
+    },
+
+    /// The first message whose timestamp is at or after the given time.
+    ///
+    /// Consumption starts from the end of the partition when no message is that recent.
+    Timestamp {
+        /// The time consumption starts from.
+        #[configurable(metadata(docs::examples = "2024-01-01T00:00:00Z"))]
+        timestamp: DateTime<Utc>,
+    },
+}
+
+impl KafkaStartPosition {
+    /// The offset consumption starts from, or the timestamp it's looked up with, in milliseconds.
+    fn offset(self) -> Offset {
+        match self {
+            Self::Committed => Offset::Stored,
+            Self::Beginning => Offset::Beginning,
+            Self::End => Offset::End,
+            Self::Offset { offset } if offset < 0 => Offset::OffsetTail(offset.saturating_neg()),
+            Self::Offset { offset } => Offset::Offset(offset),
+            Self::Timestamp { timestamp } => Offset::Offset(timestamp.timestamp_millis()),
+        }
+    }
+}
+
 /// Configuration for the `kafka` source.
 #[serde_as]
 #[configurable_component(source("kafka", "Collect logs from Apache Kafka."))]
@@ -108,6 +198,10 @@ pub struct KafkaSourceConfig {
     /// The Kafka topics names to read events from.
     ///
     /// Regular expression syntax is supported if the topic begins with `^`.
+    ///
+    /// The partitions of these topics are assigned to the source by the consumer group. Exactly one
+    /// of `topics` and `partitions` must be set.
+    #[serde(default)]
     #[configurable(metadata(
         docs::examples = "^(prefix1|prefix2)-.+",
         docs::examples = "topic-1",
@@ -115,6 +209,15 @@ pub struct KafkaSourceConfig {
     ))]
     topics: Vec<String>,
 
+    /// The partitions to read events from, rather than those assigned by the consumer group.
+    ///
+    /// These partitions are consumed regardless of the other consumers of the group, and are
+    /// never revoked from the source. Offsets are still committed for `group_id`, so that the
+    /// `committed` start position resumes from them.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    partitions: Vec<KafkaPartitionConfig>,
+
     /// The consumer group name to be used to consume events from Kafka.
     #[configurable(metadata(docs::examples = "consumer-group-name"))]
     group_id: String,
@@ -343,10 +446,15 @@ impl SourceConfig for KafkaSourceConfig {
                 }
             );
         }
+        snafu::ensure!(
+            self.topics.is_empty() != self.partitions.is_empty(),
+            TopicsOrPartitionsSnafu
+        );
 
         let (consumer, callback_rx) = create_consumer(self, acknowledgements)?;
 
         Ok(Box::pin(kafka_source(
+            cx.key,
             self.clone(),
             consumer,
             callback_rx,
@@ -420,8 +528,181 @@ impl SourceConfig for KafkaSourceConfig {
     }
 }
 
+/// The consumers of the running sources, through which the API pauses and resumes the consumption
+/// of their partitions, and transactional `kafka` sinks commit the offsets of the events they write.
+static CONSUMERS: Lazy<Mutex<HashMap<ComponentKey, RegisteredConsumer>>> =
+    Lazy::new(Default::default);
+
+const CONSUMERS_INVARIANT: &str =
+    "Couldn't acquire lock on the Kafka consumers. Please report this.";
+
+/// The consumer of a running source, with where the source inserts the positions of the messages
+/// into their events.
+struct RegisteredConsumer {
+    consumer: Weak<StreamConsumer<KafkaSourceContext>>,
+    keys: Keys,
+    log_namespace: LogNamespace,
+}
+
+/// The registration of the consumer of a running source in `CONSUMERS`, removed once dropped.
+struct ConsumerRegistration {
+    key: ComponentKey,
+    consumer: Weak<StreamConsumer<KafkaSourceContext>>,
+}
+
+impl ConsumerRegistration {
+    fn new(
+        key: ComponentKey,
+        consumer: &Arc<StreamConsumer<KafkaSourceContext>>,
+        keys: Keys,
+        log_namespace: LogNamespace,
+    ) -> Self {
+        let consumer = Arc::downgrade(consumer);
+        CONSUMERS.lock().expect(CONSUMERS_INVARIANT).insert(
+            key.clone(),
+            RegisteredConsumer {
+                consumer: Weak::clone(&consumer),
+                keys,
+                log_namespace,
+            },
+        );
+        Self { key, consumer }
+    }
+}
+
+impl Drop for ConsumerRegistration {
+    fn drop(&mut self) {
+        let mut consumers = CONSUMERS.lock().expect(CONSUMERS_INVARIANT);
+        // A reloaded source may already have registered its own consumer.
+        if consumers
+            .get(&self.key)
+            .is_some_and(|registered| Weak::ptr_eq(&registered.consumer, &self.consumer))
+        {
+            consumers.remove(&self.key);
+        }
+    }
+}
+
+/// Finds the position of the message an event was read from by a running source.
+///
+/// Returns `None` if the event doesn't come from a running source, or if its topic, partition or
+/// offset was removed from it.
+pub(crate) fn consumed_offset(event: &Event) -> Option<kafka::ConsumedOffset> {
+    let log = event.maybe_as_log()?;
+    let source: &ComponentKey = log.metadata().source_id()?;
+
+    let consumers = CONSUMERS.lock().expect(CONSUMERS_INVARIANT);
+    let registered = consumers.get(source)?;
+    let get = |legacy_key: &Option<OwnedValuePath>, field: &str| match registered.log_namespace {
+        LogNamespace::Vector => {
+            log.get((PathPrefix::Metadata, path!(KafkaSourceConfig::NAME, field)))
+        }
+        LogNamespace::Legacy => log.get((PathPrefix::Event, legacy_key.as_ref()?)),
+    };
+
+    Some(kafka::ConsumedOffset {
+        source: source.clone(),
+        topic: get(&registered.keys.topic, "topic")?.as_str()?.into_owned(),
+        partition: get(&registered.keys.partition, "partition")?
+            .as_integer()?
+            .try_into()
+            .ok()?,
+        offset: get(&registered.keys.offset, "offset")?.as_integer()?,
+    })
+}
+
+/// Returns the consumer group metadata of a running source, through which producers commit the
+/// offsets it consumed within their transactions.
+pub(crate) fn consumer_group_metadata(key: &ComponentKey) -> Option<ConsumerGroupMetadata> {
+    CONSUMERS
+        .lock()
+        .expect(CONSUMERS_INVARIANT)
+        .get(key)
+        .and_then(|registered| registered.consumer.upgrade())
+        .and_then(|consumer| consumer.group_metadata())
+}
+
+/// Pauses the consumption of partitions assigned to a running source, until they're resumed.
+///
+/// Messages already fetched from the partitions are still processed.
+pub fn pause_partitions(
+    key: &ComponentKey,
+    partitions: &[TopicPartition],
+) -> Result<(), PartitionControlError> {
+    update_partitions(key, partitions, |consumer, tpl| consumer.pause(tpl))
+}
+
+/// Resumes the consumption of partitions paused by `pause_partitions`.
+pub fn resume_partitions(
+    key: &ComponentKey,
+    partitions: &[TopicPartition],
+) -> Result<(), PartitionControlError> {
+    update_partitions(key, partitions, |consumer, tpl| consumer.resume(tpl))
+}
+
+fn update_partitions(
+    key: &ComponentKey,
+    partitions: &[TopicPartition],
+    update: impl FnOnce(&StreamConsumer<KafkaSourceContext>, &TopicPartitionList) -> KafkaResult<()>,
+) -> Result<(), PartitionControlError> {
+    let consumer = CONSUMERS
+        .lock()
+        .expect(CONSUMERS_INVARIANT)
+        .get(key)
+        .and_then(|registered| registered.consumer.upgrade())
+        .context(UnknownSourceSnafu { key: key.clone() })?;
+
+    let assignment = consumer.assignment().context(UpdatePartitionsSnafu)?;
+    let mut tpl = TopicPartitionList::new();
+    for (topic, partition) in partitions {
+        snafu::ensure!(
+            assignment.find_partition(topic, *partition).is_some(),
+            UnassignedPartitionSnafu {
+                topic: topic.clone(),
+                partition: *partition,
+            }
+        );
+        tpl.add_partition(topic, *partition);
+    }
+
+    update(&consumer, &tpl).context(UpdatePartitionsSnafu)
+}
+
+/// Builds the list of the statically assigned partitions, with the offsets they're consumed from.
+///
+/// The offsets of the partitions starting from a timestamp are looked up from the brokers.
+fn partition_assignment(
+    consumer: &StreamConsumer<KafkaSourceContext>,
+    partitions: &[KafkaPartitionConfig],
+    timeout: Duration,
+) -> KafkaResult<TopicPartitionList> {
+    let mut tpl = TopicPartitionList::new();
+    let mut timestamps = TopicPartitionList::new();
+    for partition in partitions {
+        let list = match partition.start {
+            KafkaStartPosition::Timestamp { .. } => &mut timestamps,
+            _ => &mut tpl,
+        };
+        list.add_partition_offset(
+            &partition.topic,
+            partition.partition,
+            partition.start.offset(),
+        )?;
+    }
+
+    if timestamps.count() > 0 {
+        for element in consumer.offsets_for_times(timestamps, timeout)?.elements() {
+            element.error()?;
+            tpl.add_partition_offset(element.topic(), element.partition(), element.offset())?;
+        }
+    }
+
+    Ok(tpl)
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn kafka_source(
+    key: ComponentKey,
     config: KafkaSourceConfig,
     consumer: StreamConsumer<KafkaSourceContext>,
     callback_rx: UnboundedReceiver<KafkaCallback>,
@@ -440,14 +721,43 @@ async fn kafka_source(
         .set(Arc::downgrade(&consumer))
         .expect("Error setting up consumer context.");
 
+    // The registration is dropped on every path out of the source, including failures to start.
+    let registration = ConsumerRegistration::new(key, &consumer, config.keys(), log_namespace);
+
     // EOF signal allowing the coordination task to tell the kafka client task when all partitions have reached EOF
     let (eof_tx, eof_rx) = eof.then(oneshot::channel::<()>).unzip();
 
-    let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
-    if let Err(e) = consumer.subscribe(&topics).context(SubscribeSnafu) {
-        error!("{}", e);
-        return Err(());
-    }
+    // Statically assigned partitions are never assigned through a rebalance, so the driver task
+    // sets up their consumers itself.
+    let assignment = if config.partitions.is_empty() {
+        let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
+        if let Err(e) = consumer.subscribe(&topics).context(SubscribeSnafu) {
+            error!("{}", e);
+            return Err(());
+        }
+        None
+    } else {
+        let consumer = Arc::clone(&consumer);
+        let partitions = config.partitions.clone();
+        let timeout = config.socket_timeout_ms;
+        let assignment = tokio::task::spawn_blocking(move || {
+            partition_assignment(&consumer, &partitions, timeout)
+        })
+        .await;
+        match assignment {
+            Ok(assignment) => match assignment.context(AssignSnafu) {
+                Ok(tpl) => Some(tpl),
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(());
+                }
+            },
+            Err(error) => {
+                error!(message = "Looking up the partition offsets failed.", %error);
+                return Err(());
+            }
+        }
+    };
 
     let coordination_task = {
         let span = span.clone();
@@ -471,13 +781,23 @@ async fn kafka_source(
 
     let client_task = {
         let consumer = Arc::clone(&consumer);
+        let shutdown = shutdown.clone();
         tokio::task::spawn_blocking(move || {
             let _enter = span.enter();
-            drive_kafka_consumer(consumer, shutdown, eof_rx);
+            drive_kafka_consumer(consumer, shutdown, eof_rx, assignment);
         })
     };
 
-    _ = tokio::join!(client_task, coordination_task);
+    let tasks = async { tokio::join!(client_task, coordination_task) };
+    tokio::pin!(tasks);
+    tokio::select! {
+        _ = &mut tasks => drop(registration),
+        // Partitions can't be paused or resumed once the source is shutting down, while it drains.
+        _ = shutdown => {
+            drop(registration);
+            _ = tasks.await;
+        }
+    }
     consumer.context().commit_consumer_state();
 
     Ok(())
@@ -921,7 +1241,19 @@ fn drive_kafka_consumer(
     consumer: Arc<StreamConsumer<KafkaSourceContext>>,
     mut shutdown: ShutdownSignal,
     eof: Option<oneshot::Receiver<()>>,
+    assignment: Option<TopicPartitionList>,
 ) {
+    if let Some(tpl) = assignment {
+        // As during a rebalance, the queues of the partitions are split off before they're
+        // assigned, so that none of their messages reach the main queue.
+        consumer.context().consume_partitions(&tpl);
+        if let Err(e) = consumer.assign(&tpl).context(AssignSnafu) {
+            error!("{}", e);
+            consumer.context().shutdown();
+            return;
+        }
+    }
+
     Handle::current().block_on(async move {
         let mut eof: OptionFuture<_> = eof.into();
         let mut stream = consumer.stream();
@@ -1395,6 +1727,7 @@ mod test {
     use vector_lib::schema::Definition;
 
     use super::*;
+    use crate::event::LogEvent;
 
     pub fn kafka_host() -> String {
         std::env::var("KAFKA_HOST").unwrap_or_else(|_| "localhost".into())
@@ -1537,6 +1870,129 @@ mod test {
         };
         assert!(create_consumer(&config, true).is_err());
     }
+
+    #[test]
+    fn parses_partition_start_positions() {
+        let config: KafkaSourceConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            group_id = "group"
+
+            [[partitions]]
+            topic = "topic"
+            partition = 0
+
+            [[partitions]]
+            topic = "topic"
+            partition = 1
+            start.from = "offset"
+            start.offset = -100
+
+            [[partitions]]
+            topic = "topic"
+            partition = 2
+            start.from = "timestamp"
+            start.timestamp = "2024-01-01T00:00:00Z"
+            "#,
+        )
+        .unwrap();
+
+        let offsets = config
+            .partitions
+            .iter()
+            .map(|partition| partition.start.offset())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            offsets,
+            [
+                Offset::Stored,
+                Offset::OffsetTail(100),
+                Offset::Offset(1704067200000)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_topics_and_partitions() {
+        let config = KafkaSourceConfig {
+            partitions: vec![KafkaPartitionConfig {
+                topic: "topic".into(),
+                partition: 0,
+                start: KafkaStartPosition::Beginning,
+            }],
+            ..make_config("topic", "group", LogNamespace::Legacy, None)
+        };
+
+        let error = config
+            .build(SourceContext::new_test(SourceSender::new_test().0, None))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Exactly one of `topics` and `partitions` must be set"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_neither_topics_nor_partitions() {
+        let config = KafkaSourceConfig {
+            topics: Vec::new(),
+            ..make_config("topic", "group", LogNamespace::Legacy, None)
+        };
+
+        let error = config
+            .build(SourceContext::new_test(SourceSender::new_test().0, None))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Exactly one of `topics` and `partitions` must be set"
+        );
+    }
+
+    #[test]
+    fn pausing_partitions_of_unknown_source_fails() {
+        let error =
+            pause_partitions(&ComponentKey::from("unknown"), &[("topic".into(), 0)]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No running kafka source has the ID unknown."
+        );
+    }
+
+    #[tokio::test]
+    async fn finds_consumed_offset_of_events() {
+        let config = make_config("topic", "group", LogNamespace::Legacy, None);
+        let consumer = Arc::new(create_consumer(&config, true).unwrap().0);
+        let key = ComponentKey::from("consumed_offset_source");
+        let _registration =
+            ConsumerRegistration::new(key.clone(), &consumer, config.keys(), LogNamespace::Legacy);
+
+        let mut log = LogEvent::from("message");
+        log.insert("topic", "topic");
+        log.insert("partition", 2);
+        log.insert("offset", 42);
+        let event = Event::from(log).with_source_id(Arc::new(key.clone()));
+
+        assert_eq!(
+            consumed_offset(&event),
+            Some(kafka::ConsumedOffset {
+                source: key,
+                topic: "topic".into(),
+                partition: 2,
+                offset: 42,
+            })
+        );
+
+        let mut event = event;
+        event.as_mut_log().remove("offset");
+        assert_eq!(consumed_offset(&event), None);
+        let other = Event::from(LogEvent::from("message"))
+            .with_source_id(Arc::new(ComponentKey::from("other")));
+        assert_eq!(consumed_offset(&other), None);
+    }
 }
 
 #[cfg(feature = "kafka-integration-tests")]
@@ -1775,6 +2231,55 @@ mod integration_test {
         }
     }
 
+    #[tokio::test]
+    async fn consumes_assigned_partitions_from_offset() {
+        let (topic, group_id, _) = send_to_test_topic(2, 10).await;
+        let config = KafkaSourceConfig {
+            topics: Vec::new(),
+            partitions: vec![
+                KafkaPartitionConfig {
+                    topic: topic.clone(),
+                    partition: 0,
+                    start: KafkaStartPosition::End,
+                },
+                KafkaPartitionConfig {
+                    topic: topic.clone(),
+                    partition: 1,
+                    start: KafkaStartPosition::Offset { offset: -2 },
+                },
+            ],
+            ..make_config(&topic, &group_id, LogNamespace::Legacy, None)
+        };
+
+        let (tx, rx) = SourceSender::new_test();
+        let (trigger_shutdown, shutdown_done) =
+            spawn_kafka(tx, config, false, false, LogNamespace::Legacy);
+        let events = collect_n(rx, 2).await;
+
+        let key = ComponentKey::from(group_id.as_str());
+        pause_partitions(&key, &[(topic.clone(), 1)]).unwrap();
+        resume_partitions(&key, &[(topic.clone(), 1)]).unwrap();
+        assert_eq!(
+            pause_partitions(&key, &[(topic.clone(), 2)])
+                .unwrap_err()
+                .to_string(),
+            format!("Partition {topic}:2 is not assigned to the source.")
+        );
+
+        drop(trigger_shutdown);
+        shutdown_done.await;
+        assert_eq!(
+            pause_partitions(&key, &[(topic.clone(), 1)])
+                .unwrap_err()
+                .to_string(),
+            format!("No running kafka source has the ID {group_id}.")
+        );
+
+        for event in events {
+            assert_eq!(event.as_log()["partition"], 1.into());
+        }
+    }
+
     fn make_rand_config() -> (String, String, KafkaSourceConfig) {
         let topic = format!("test-topic-{}", random_string(10));
         let group_id = format!("test-group-{}", random_string(10));
@@ -1825,6 +2330,7 @@ mod integration_test {
         let (consumer, callback_rx) = create_consumer(&config, acknowledgements).unwrap();
 
         tokio::spawn(kafka_source(
+            ComponentKey::from(config.group_id.as_str()),
             config,
             consumer,
             callback_rx,
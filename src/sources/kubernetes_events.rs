@@ -0,0 +1,443 @@
+//! This mod implements the `kubernetes_events` source.
+//! The source watches the Events of the Kubernetes API and emits them as logs.
+
+use std::{collections::HashMap, path::PathBuf};
+
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use k8s_openapi::api::core::v1::Event as KubeEvent;
+use kube::{
+    api::Api,
+    config::{self, KubeConfigOptions},
+    runtime::{watcher, WatchStreamExt},
+    Client, Config as ClientConfig,
+};
+use vector_lib::{
+    config::LogNamespace,
+    configurable::configurable_component,
+    internal_event::{CountByteSize, InternalEventHandle as _},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind, ObjectMap, Value};
+
+use crate::{
+    config::{DataType, SourceConfig, SourceContext, SourceOutput},
+    event::{EventMetadata, LogEvent},
+    internal_events::{EventsReceived, KubernetesEventsWatchError, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// Configuration for the `kubernetes_events` source.
+#[configurable_component(source(
+    "kubernetes_events",
+    "Collect the Events of a Kubernetes cluster."
+))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct KubernetesEventsConfig {
+    /// The [Namespace][namespace] to watch the Events of.
+    ///
+    /// If not set, the Events of all Namespaces are watched.
+    ///
+    /// [namespace]: https://kubernetes.io/docs/concepts/overview/working-with-objects/namespaces/
+    #[configurable(metadata(docs::examples = "kube-system"))]
+    namespace: Option<String>,
+
+    /// Specifies the [field selector][field_selector] to filter the Events with.
+    ///
+    /// [field_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/
+    #[configurable(metadata(docs::examples = "type=Warning"))]
+    #[configurable(metadata(docs::examples = "involvedObject.kind=Pod,reason!=Scheduled"))]
+    field_selector: String,
+
+    /// Specifies the [label selector][label_selector] to filter the Events with.
+    ///
+    /// [label_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/#label-selectors
+    #[configurable(metadata(docs::examples = "my_custom_label!=my_value"))]
+    label_selector: String,
+
+    /// Whether to emit the Events existing when the source starts.
+    ///
+    /// By default, only the Events created or updated once the source started are emitted.
+    read_existing_events: bool,
+
+    /// Optional path to a readable [kubeconfig][kubeconfig] file.
+    ///
+    /// If not set, a connection to Kubernetes is made using the in-cluster configuration.
+    ///
+    /// [kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+    #[configurable(metadata(docs::examples = "/path/to/.kube/config"))]
+    kube_config_file: Option<PathBuf>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    log_namespace: Option<bool>,
+}
+
+impl_generate_config_from_default!(KubernetesEventsConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kubernetes_events")]
+impl SourceConfig for KubernetesEventsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        let client = Client::try_from(client_config)?;
+        let api = match &self.namespace {
+            Some(namespace) => Api::<KubeEvent>::namespaced(client, namespace),
+            None => Api::<KubeEvent>::all(client),
+        };
+
+        let watcher_config = watcher::Config {
+            field_selector: (!self.field_selector.is_empty()).then(|| self.field_selector.clone()),
+            label_selector: (!self.label_selector.is_empty()).then(|| self.label_selector.clone()),
+            ..Default::default()
+        };
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        Ok(Box::pin(run(
+            api,
+            watcher_config,
+            self.read_existing_events,
+            log_namespace,
+            cx.out,
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty().with_unknown(Kind::any())),
+            [log_namespace],
+        )
+        .with_standard_vector_source_metadata();
+
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// Tracks the versions of the Events emitted, to only emit the Events created or updated since.
+///
+/// The watcher resumes watching from the last resource version received, bookmarks included, and
+/// lists all the Events again once that version expired. The Events of these lists are compared
+/// with the versions emitted so that only the Events changed in the meantime are emitted again.
+#[derive(Default)]
+struct EmittedVersions {
+    versions: HashMap<String, String>,
+    listed: bool,
+}
+
+impl EmittedVersions {
+    /// Records an Event, returning whether it is new or updated.
+    fn apply(&mut self, event: &KubeEvent) -> bool {
+        let (Some(uid), Some(version)) = (&event.metadata.uid, &event.metadata.resource_version)
+        else {
+            return true;
+        };
+        let changed = self.versions.get(uid) != Some(version);
+        if changed {
+            self.versions.insert(uid.clone(), version.clone());
+        }
+        changed
+    }
+
+    fn delete(&mut self, event: &KubeEvent) {
+        if let Some(uid) = &event.metadata.uid {
+            self.versions.remove(uid);
+        }
+    }
+
+    /// Replaces the Events recorded with a new list, returning the new or updated Events.
+    fn relist<'a>(&mut self, events: &'a [KubeEvent], emit_existing: bool) -> Vec<&'a KubeEvent> {
+        let previous = std::mem::take(&mut self.versions);
+        let first_list = !std::mem::replace(&mut self.listed, true);
+        events
+            .iter()
+            .filter(|event| {
+                let changed = match (&event.metadata.uid, &event.metadata.resource_version) {
+                    (Some(uid), Some(version)) => {
+                        self.versions.insert(uid.clone(), version.clone());
+                        previous.get(uid) != Some(version)
+                    }
+                    _ => true,
+                };
+                changed && (emit_existing || !first_list)
+            })
+            .collect()
+    }
+}
+
+async fn run(
+    api: Api<KubeEvent>,
+    watcher_config: watcher::Config,
+    read_existing_events: bool,
+    log_namespace: LogNamespace,
+    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let events_received = register!(EventsReceived);
+    let mut versions = EmittedVersions::default();
+    let mut stream = watcher(api, watcher_config)
+        .backoff(watcher::default_backoff())
+        .take_until(shutdown)
+        .boxed();
+
+    while let Some(result) = stream.next().await {
+        let now = Utc::now();
+        let events = match result {
+            Ok(watcher::Event::Applied(event)) => {
+                if !versions.apply(&event) {
+                    continue;
+                }
+                vec![create_log_event(&event, log_namespace, now)]
+            }
+            Ok(watcher::Event::Deleted(event)) => {
+                // Events are deleted once expired, which isn't an event by itself.
+                versions.delete(&event);
+                continue;
+            }
+            Ok(watcher::Event::Restarted(events)) => versions
+                .relist(&events, read_existing_events)
+                .into_iter()
+                .map(|event| create_log_event(event, log_namespace, now))
+                .collect(),
+            Err(error) => {
+                emit!(KubernetesEventsWatchError { error });
+                continue;
+            }
+        };
+        if events.is_empty() {
+            continue;
+        }
+
+        let count = events.len();
+        events_received.emit(CountByteSize(
+            count,
+            events.estimated_json_encoded_size_of(),
+        ));
+        if out.send_batch(events).await.is_err() {
+            emit!(StreamClosedError { count });
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
+
+fn create_log_event(
+    event: &KubeEvent,
+    log_namespace: LogNamespace,
+    now: DateTime<Utc>,
+) -> LogEvent {
+    let object = &event.involved_object;
+    let mut fields = ObjectMap::new();
+    insert(&mut fields, "message", event.message.as_deref());
+    insert(&mut fields, "reason", event.reason.as_deref());
+    insert(&mut fields, "type", event.type_.as_deref());
+    insert(&mut fields, "action", event.action.as_deref());
+    insert(&mut fields, "name", event.metadata.name.as_deref());
+    insert(
+        &mut fields,
+        "namespace",
+        event.metadata.namespace.as_deref(),
+    );
+    insert(&mut fields, "uid", event.metadata.uid.as_deref());
+    if let Some(count) = event.count {
+        fields.insert("count".into(), Value::Integer(count.into()));
+    }
+
+    let mut involved_object = ObjectMap::new();
+    insert(&mut involved_object, "kind", object.kind.as_deref());
+    insert(&mut involved_object, "name", object.name.as_deref());
+    insert(
+        &mut involved_object,
+        "namespace",
+        object.namespace.as_deref(),
+    );
+    insert(&mut involved_object, "uid", object.uid.as_deref());
+    insert(
+        &mut involved_object,
+        "api_version",
+        object.api_version.as_deref(),
+    );
+    insert(
+        &mut involved_object,
+        "field_path",
+        object.field_path.as_deref(),
+    );
+    fields.insert("involved_object".into(), Value::from(involved_object));
+
+    let mut source = ObjectMap::new();
+    let event_source = event.source.as_ref();
+    insert(
+        &mut source,
+        "component",
+        event
+            .reporting_component
+            .as_deref()
+            .filter(|component| !component.is_empty())
+            .or_else(|| event_source.and_then(|source| source.component.as_deref())),
+    );
+    insert(
+        &mut source,
+        "host",
+        event_source.and_then(|source| source.host.as_deref()),
+    );
+    insert(&mut source, "instance", event.reporting_instance.as_deref());
+    if !source.is_empty() {
+        fields.insert("source".into(), Value::from(source));
+    }
+
+    let first_timestamp = event
+        .first_timestamp
+        .as_ref()
+        .map(|time| time.0)
+        .or_else(|| event.event_time.as_ref().map(|time| time.0));
+    let last_timestamp = event
+        .last_timestamp
+        .as_ref()
+        .map(|time| time.0)
+        .or_else(|| {
+            event
+                .series
+                .as_ref()
+                .and_then(|series| series.last_observed_time.as_ref())
+                .map(|time| time.0)
+        })
+        .or(first_timestamp);
+    if let Some(timestamp) = first_timestamp {
+        fields.insert("first_timestamp".into(), Value::Timestamp(timestamp));
+    }
+    if let Some(timestamp) = last_timestamp {
+        fields.insert("last_timestamp".into(), Value::Timestamp(timestamp));
+    }
+
+    let mut log = LogEvent::from_map(fields, EventMetadata::default());
+    log_namespace.insert_standard_vector_source_metadata(
+        &mut log,
+        KubernetesEventsConfig::NAME,
+        last_timestamp.unwrap_or(now),
+    );
+    log
+}
+
+fn insert(fields: &mut ObjectMap, key: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        fields.insert(key.into(), value.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use k8s_openapi::{
+        api::core::v1::{EventSource, ObjectReference},
+        apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time},
+    };
+    use vector_lib::lookup::event_path;
+
+    use super::*;
+    use crate::config::log_schema;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<KubernetesEventsConfig>();
+    }
+
+    fn kube_event(uid: &str, version: &str) -> KubeEvent {
+        KubeEvent {
+            metadata: ObjectMeta {
+                name: Some("web-0.17a1c4d2".into()),
+                namespace: Some("default".into()),
+                uid: Some(uid.into()),
+                resource_version: Some(version.into()),
+                ..Default::default()
+            },
+            involved_object: ObjectReference {
+                kind: Some("Pod".into()),
+                name: Some("web-0".into()),
+                namespace: Some("default".into()),
+                ..Default::default()
+            },
+            reason: Some("BackOff".into()),
+            message: Some("Back-off restarting failed container".into()),
+            type_: Some("Warning".into()),
+            count: Some(3),
+            source: Some(EventSource {
+                component: Some("kubelet".into()),
+                host: Some("node-1".into()),
+            }),
+            first_timestamp: Some(Time(Utc.timestamp_opt(1_700_000_000, 0).unwrap())),
+            last_timestamp: Some(Time(Utc.timestamp_opt(1_700_000_060, 0).unwrap())),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn creates_normalized_logs() {
+        let log = create_log_event(&kube_event("a", "1"), LogNamespace::Legacy, Utc::now());
+
+        assert_eq!(log["reason"], "BackOff".into());
+        assert_eq!(log["type"], "Warning".into());
+        assert_eq!(log["count"], Value::Integer(3));
+        assert_eq!(log["involved_object.kind"], "Pod".into());
+        assert_eq!(log["involved_object.name"], "web-0".into());
+        assert_eq!(log["source.component"], "kubelet".into());
+        assert_eq!(log["source.host"], "node-1".into());
+        assert_eq!(log["source_type"], KubernetesEventsConfig::NAME.into());
+        assert_eq!(
+            log.get(log_schema().timestamp_key_target_path().unwrap()),
+            Some(&Value::Timestamp(
+                Utc.timestamp_opt(1_700_000_060, 0).unwrap()
+            ))
+        );
+        assert_eq!(log.get(event_path!("action")), None);
+    }
+
+    #[test]
+    fn emits_new_and_updated_events_once() {
+        let mut versions = EmittedVersions::default();
+
+        let existing = [kube_event("a", "1"), kube_event("b", "2")];
+        assert!(versions.relist(&existing, false).is_empty());
+
+        assert!(!versions.apply(&kube_event("a", "1")));
+        assert!(versions.apply(&kube_event("a", "3")));
+        assert!(versions.apply(&kube_event("c", "4")));
+        assert!(!versions.apply(&kube_event("c", "4")));
+
+        // A relist only emits the Events changed since the last version received.
+        let relisted = [
+            kube_event("a", "3"),
+            kube_event("b", "5"),
+            kube_event("d", "6"),
+        ];
+        let emitted = versions
+            .relist(&relisted, false)
+            .into_iter()
+            .filter_map(|event| event.metadata.uid.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(emitted, ["b", "d"]);
+
+        let mut versions = EmittedVersions::default();
+        assert_eq!(versions.relist(&existing, true).len(), 2);
+    }
+}
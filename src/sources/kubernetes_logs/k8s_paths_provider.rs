@@ -57,6 +57,7 @@ impl PathsProvider for K8sPathsProvider {
                     false
                 }
             })
+            .filter(|pod| !is_pod_excluded(pod))
             .flat_map(|pod| {
                 trace!(message = "Providing log paths for pod.", pod = ?pod.metadata.name);
                 let paths_iter = list_pod_log_paths(real_glob, pod.as_ref());
@@ -106,6 +107,18 @@ fn extract_pod_logs_directory(pod: &Pod) -> Option<PathBuf> {
     Some(build_pod_logs_directory(namespace, name, uid))
 }
 
+const POD_EXCLUSION_ANNOTATION_KEY: &str = "vector.dev/exclude";
+
+/// Whether the Pod opted out of the collection of its logs through its annotations, in addition
+/// to the label of the same name.
+fn is_pod_excluded(pod: &Pod) -> bool {
+    pod.metadata
+        .annotations
+        .as_ref()
+        .and_then(|annotations| annotations.get(POD_EXCLUSION_ANNOTATION_KEY))
+        .is_some_and(|value| value.trim() == "true")
+}
+
 const CONTAINER_EXCLUSION_ANNOTATION_KEY: &str = "vector.dev/exclude-containers";
 
 fn extract_excluded_containers_for_pod(pod: &Pod) -> impl Iterator<Item = &str> {
@@ -214,7 +227,7 @@ mod tests {
 
     use super::{
         build_container_exclusion_patterns, extract_excluded_containers_for_pod,
-        extract_pod_logs_directory, filter_paths, list_pod_log_paths,
+        extract_pod_logs_directory, filter_paths, is_pod_excluded, list_pod_log_paths,
     };
 
     #[test]
@@ -377,6 +390,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_pod_excluded() {
+        let pod = |annotations: Vec<(&str, &str)>| Pod {
+            metadata: ObjectMeta {
+                annotations: Some(
+                    annotations
+                        .into_iter()
+                        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                        .collect(),
+                ),
+                ..ObjectMeta::default()
+            },
+            ..Pod::default()
+        };
+
+        assert!(!is_pod_excluded(&Pod::default()));
+        assert!(!is_pod_excluded(&pod(vec![])));
+        assert!(!is_pod_excluded(&pod(vec![(
+            "vector.dev/exclude",
+            "false"
+        )])));
+        assert!(!is_pod_excluded(&pod(vec![(
+            "vector.dev/exclude-containers",
+            "true"
+        )])));
+        assert!(is_pod_excluded(&pod(vec![("vector.dev/exclude", "true")])));
+    }
+
     #[test]
     fn test_list_pod_log_paths() {
         let cases = vec![
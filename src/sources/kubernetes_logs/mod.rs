@@ -61,6 +61,7 @@ mod node_metadata_annotator;
 mod parser;
 mod partial_events_merger;
 mod path_helpers;
+mod pod_annotation_parser;
 mod pod_metadata_annotator;
 mod transform_utils;
 mod util;
@@ -68,6 +69,7 @@ mod util;
 use self::namespace_metadata_annotator::NamespaceMetadataAnnotator;
 use self::node_metadata_annotator::NodeMetadataAnnotator;
 use self::parser::Parser;
+use self::pod_annotation_parser::PodAnnotationParser;
 use self::pod_metadata_annotator::PodMetadataAnnotator;
 
 /// The `self_node_name` value env var key.
@@ -229,6 +231,14 @@ pub struct Config {
     /// Determines if requests to the kube-apiserver can be served by a cache.
     use_apiserver_cache: bool,
 
+    /// Whether to parse the messages of the Pods annotated with a parser.
+    ///
+    /// The messages of the Pods with the `vector.dev/parser` annotation set to `json` are parsed
+    /// as JSON objects, once the partial events are merged. The messages failing to be parsed are
+    /// forwarded unparsed.
+    #[configurable(metadata(docs::human_name = "Use Pod Parser Annotation"))]
+    use_pod_parser_annotation: bool,
+
     /// How long to delay removing metadata entries from the cache when a pod deletion event
     /// event is received from the watch stream.
     ///
@@ -296,6 +306,7 @@ impl Default for Config {
             timezone: None,
             kube_config_file: None,
             use_apiserver_cache: false,
+            use_pod_parser_annotation: false,
             delay_deletion_ms: default_delay_deletion_ms(),
             log_namespace: None,
             internal_metrics: Default::default(),
@@ -324,7 +335,7 @@ impl SourceConfig for Config {
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
         let log_namespace = global_log_namespace.merge(self.log_namespace);
-        let schema_definition = BytesDeserializerConfig
+        let mut schema_definition = BytesDeserializerConfig
             .schema_definition(log_namespace)
             .with_source_metadata(
                 Self::NAME,
@@ -518,6 +529,12 @@ impl SourceConfig for Config {
             )
             .with_standard_vector_source_metadata();
 
+        // The messages of the Pods annotated with a parser are replaced with the parsed objects.
+        if self.use_pod_parser_annotation {
+            let kind = schema_definition.event_kind_mut();
+            *kind = kind.clone().or_object(Collection::any());
+        }
+
         vec![SourceOutput::new_maybe_logs(
             DataType::Log,
             schema_definition,
@@ -552,6 +569,7 @@ struct Source {
     fingerprint_lines: usize,
     glob_minimum_cooldown: Duration,
     use_apiserver_cache: bool,
+    use_pod_parser_annotation: bool,
     ingestion_timestamp_field: Option<OwnedTargetPath>,
     delay_deletion: Duration,
     include_file_metric_tag: bool,
@@ -635,6 +653,7 @@ impl Source {
             fingerprint_lines: config.fingerprint_lines,
             glob_minimum_cooldown,
             use_apiserver_cache: config.use_apiserver_cache,
+            use_pod_parser_annotation: config.use_pod_parser_annotation,
             ingestion_timestamp_field,
             delay_deletion,
             include_file_metric_tag: config.internal_metrics.include_file_tag,
@@ -670,6 +689,7 @@ impl Source {
             fingerprint_lines,
             glob_minimum_cooldown,
             use_apiserver_cache,
+            use_pod_parser_annotation,
             ingestion_timestamp_field,
             delay_deletion,
             include_file_metric_tag,
@@ -759,6 +779,8 @@ impl Source {
             include_paths,
             exclude_paths,
         );
+        let pod_parser = use_pod_parser_annotation
+            .then(|| PodAnnotationParser::new(pod_state.clone(), log_namespace));
         let annotator = PodMetadataAnnotator::new(pod_state, pod_fields_spec, log_namespace);
         let ns_annotator =
             NamespaceMetadataAnnotator::new(ns_state, namespace_fields_spec, log_namespace);
@@ -883,12 +905,19 @@ impl Source {
 
         let (events_count, _) = events.size_hint();
 
-        let mut stream = if auto_partial_merge {
+        let events = if auto_partial_merge {
             merge_partial_events(events, log_namespace).left_stream()
         } else {
             events.right_stream()
         };
 
+        let mut stream = events.map(move |mut event| {
+            if let Some(pod_parser) = &pod_parser {
+                pod_parser.parse(&mut event);
+            }
+            event
+        });
+
         let event_processing_loop = out.send_event_stream(&mut stream);
 
         let mut lifecycle = Lifecycle::new();
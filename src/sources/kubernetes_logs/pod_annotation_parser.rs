@@ -0,0 +1,218 @@
+//! Parses the messages of the Pods opting into parsing through annotations.
+
+#![deny(missing_docs)]
+
+use k8s_openapi::api::core::v1::Pod;
+use kube::runtime::reflector::{store::Store, ObjectRef};
+use serde_json::Value as JsonValue;
+use vector_lib::config::LogNamespace;
+use vector_lib::lookup::{event_path, owned_value_path, OwnedTargetPath};
+
+use super::{path_helpers::parse_log_file_path, transform_utils::get_message_path, Config};
+use crate::{
+    event::{Event, LogEvent, Value},
+    internal_events::KubernetesLogsPodAnnotationParseError,
+};
+
+/// The annotation selecting the parser of the messages of a Pod.
+pub const PARSER_ANNOTATION_KEY: &str = "vector.dev/parser";
+
+/// The parsers that can be selected through the annotations of the Pods.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum PodParser {
+    /// Parses the messages as JSON objects.
+    Json,
+}
+
+impl PodParser {
+    /// Gets the parser selected by the annotations of a Pod, `Err` with the annotation value if
+    /// the parser is unknown.
+    fn from_pod(pod: &Pod) -> Result<Option<Self>, &str> {
+        let parser = pod
+            .metadata
+            .annotations
+            .as_ref()
+            .and_then(|annotations| annotations.get(PARSER_ANNOTATION_KEY));
+        match parser.map(|parser| parser.trim()) {
+            None => Ok(None),
+            Some("json") => Ok(Some(Self::Json)),
+            Some(parser) => Err(parser),
+        }
+    }
+}
+
+/// Parses the messages of the events with the parsers selected by the annotations of their Pods.
+pub struct PodAnnotationParser {
+    pods_state_reader: Store<Pod>,
+    file_path: OwnedTargetPath,
+    message_path: OwnedTargetPath,
+    log_namespace: LogNamespace,
+}
+
+impl PodAnnotationParser {
+    /// Create a new [`PodAnnotationParser`].
+    pub fn new(pods_state_reader: Store<Pod>, log_namespace: LogNamespace) -> Self {
+        let file_path = match log_namespace {
+            LogNamespace::Vector => {
+                OwnedTargetPath::metadata(owned_value_path!(Config::NAME, "file"))
+            }
+            LogNamespace::Legacy => OwnedTargetPath::event(owned_value_path!("file")),
+        };
+        Self {
+            pods_state_reader,
+            file_path,
+            message_path: get_message_path(log_namespace),
+            log_namespace,
+        }
+    }
+
+    /// Parses the message of an event if its Pod is annotated with a parser.
+    ///
+    /// The events failing to be parsed are left untouched.
+    pub fn parse(&self, event: &mut Event) {
+        let log = event.as_mut_log();
+        let Some(file) = log.get(&self.file_path).and_then(|file| file.as_str()) else {
+            return;
+        };
+        let Some(file_info) = parse_log_file_path(&file) else {
+            return;
+        };
+        let obj = ObjectRef::<Pod>::new(file_info.pod_name).within(file_info.pod_namespace);
+        let Some(pod) = self.pods_state_reader.get(&obj) else {
+            return;
+        };
+
+        match PodParser::from_pod(pod.as_ref()) {
+            Ok(None) => {}
+            Ok(Some(PodParser::Json)) => {
+                if let Err(error) = self.parse_json(log) {
+                    emit!(KubernetesLogsPodAnnotationParseError {
+                        parser: "json",
+                        error: &error,
+                    });
+                }
+            }
+            Err(parser) => emit!(KubernetesLogsPodAnnotationParseError {
+                parser,
+                error: &"unknown parser",
+            }),
+        }
+    }
+
+    /// Replaces the message with the fields of the JSON object it contains.
+    fn parse_json(&self, log: &mut LogEvent) -> Result<(), String> {
+        let object = match log.get(&self.message_path) {
+            Some(Value::Bytes(bytes)) => match serde_json::from_slice(bytes) {
+                Ok(JsonValue::Object(object)) => object,
+                Ok(_) => return Err("message is not a JSON object".into()),
+                Err(error) => return Err(error.to_string()),
+            },
+            Some(_) => return Err("message is not a string".into()),
+            None => return Err("event has no message".into()),
+        };
+
+        match self.log_namespace {
+            LogNamespace::Vector => {
+                log.insert(&self.message_path, JsonValue::Object(object));
+            }
+            LogNamespace::Legacy => {
+                log.remove(&self.message_path);
+                for (key, value) in object {
+                    log.insert(event_path!(key.as_str()), value);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use kube::runtime::reflector::store::Writer;
+    use kube::runtime::watcher;
+    use vector_lib::lookup::metadata_path;
+
+    use super::*;
+    use crate::config::log_schema;
+
+    const FILE: &str =
+        "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/sandbox0-container0-name/1.log";
+
+    fn pods_store(annotations: &[(&str, &str)]) -> Store<Pod> {
+        let mut writer = Writer::default();
+        writer.apply_watcher_event(&watcher::Event::Applied(Pod {
+            metadata: ObjectMeta {
+                namespace: Some("sandbox0-ns".to_owned()),
+                name: Some("sandbox0-name".to_owned()),
+                uid: Some("sandbox0-uid".to_owned()),
+                annotations: Some(
+                    annotations
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .collect::<BTreeMap<_, _>>(),
+                ),
+                ..ObjectMeta::default()
+            },
+            ..Pod::default()
+        }));
+        writer.as_reader()
+    }
+
+    fn legacy_event(message: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert(event_path!("file"), FILE);
+        log.into()
+    }
+
+    fn vector_event(message: &str) -> Event {
+        let mut log = LogEvent::from(Value::from(message));
+        log.insert(metadata_path!(Config::NAME, "file"), FILE);
+        log.into()
+    }
+
+    #[test]
+    fn parses_json_messages() {
+        let parser = PodAnnotationParser::new(
+            pods_store(&[(PARSER_ANNOTATION_KEY, "json")]),
+            LogNamespace::Legacy,
+        );
+        let mut event = legacy_event(r#"{"level":"info","msg":"started"}"#);
+        parser.parse(&mut event);
+        let log = event.as_log();
+        assert_eq!(log["level"], "info".into());
+        assert_eq!(log["msg"], "started".into());
+        assert!(log
+            .get(log_schema().message_key_target_path().unwrap())
+            .is_none());
+
+        let parser = PodAnnotationParser::new(
+            pods_store(&[(PARSER_ANNOTATION_KEY, "json")]),
+            LogNamespace::Vector,
+        );
+        let mut event = vector_event(r#"{"level":"info"}"#);
+        parser.parse(&mut event);
+        assert_eq!(event.as_log()["level"], "info".into());
+    }
+
+    #[test]
+    fn leaves_other_messages_untouched() {
+        let cases = [
+            (vec![(PARSER_ANNOTATION_KEY, "json")], "not json"),
+            (vec![(PARSER_ANNOTATION_KEY, "json")], "[1, 2]"),
+            (vec![(PARSER_ANNOTATION_KEY, "yaml")], r#"{"level":"info"}"#),
+            (vec![], r#"{"level":"info"}"#),
+        ];
+        for (annotations, message) in cases {
+            let parser = PodAnnotationParser::new(pods_store(&annotations), LogNamespace::Legacy);
+            let mut event = legacy_event(message);
+            parser.parse(&mut event);
+            assert_eq!(
+                event.as_log()[log_schema().message_key().unwrap().to_string()],
+                message.into()
+            );
+        }
+    }
+}
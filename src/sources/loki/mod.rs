@@ -0,0 +1,433 @@
+use std::{collections::HashMap, net::SocketAddr};
+
+use bytes::Bytes;
+use chrono::Utc;
+use vector_lib::codecs::BytesDeserializerConfig;
+use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::{owned_value_path, path};
+use vector_lib::schema::{meaning, Definition};
+use vrl::value::{kind::Collection, Kind, ObjectMap, Value};
+use warp::http::{header::CONTENT_TYPE, HeaderMap, StatusCode};
+
+use self::parser::{parse_json, parse_protobuf, Stream};
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Resource, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext, SourceOutput,
+    },
+    event::Event,
+    http::KeepaliveConfig,
+    internal_events::LokiPushRequestParseError,
+    serde::bool_or_struct,
+    sources::{
+        self,
+        util::{decode, http::HttpMethod, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+    },
+    tls::TlsEnableableConfig,
+};
+
+mod parser;
+
+pub use self::parser::ParseError;
+
+/// The path of the push API.
+const PUSH_PATH: &str = "/loki/api/v1/push";
+
+/// The header clients set the tenant of their events in.
+const TENANT_ID_HEADER: &str = "X-Scope-OrgID";
+
+/// Configuration for the `loki` source.
+#[configurable_component(source(
+    "loki",
+    "Receive logs from Loki clients such as Promtail and Grafana Agent."
+))]
+#[derive(Clone, Debug)]
+pub struct LokiConfig {
+    /// The socket address to accept connections on.
+    ///
+    /// The address _must_ include a port.
+    #[configurable(metadata(docs::examples = "0.0.0.0:3100"))]
+    address: SocketAddr,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    auth: Option<HttpSourceAuthConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    keepalive: KeepaliveConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+impl LokiConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        BytesDeserializerConfig
+            .schema_definition(log_namespace)
+            .with_source_metadata(
+                Self::NAME,
+                log_schema()
+                    .timestamp_key()
+                    .cloned()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some(meaning::TIMESTAMP),
+            )
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("labels"))),
+                &owned_value_path!("labels"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(
+                    "structured_metadata"
+                ))),
+                &owned_value_path!("structured_metadata"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("tenant_id"))),
+                &owned_value_path!("tenant_id"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+    }
+}
+
+impl GenerateConfig for LokiConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:3100".parse().unwrap(),
+            tls: None,
+            auth: None,
+            acknowledgements: SourceAcknowledgementsConfig::default(),
+            keepalive: KeepaliveConfig::default(),
+            log_namespace: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "loki")]
+impl SourceConfig for LokiConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let source = LokiSource { log_namespace };
+        source.run(
+            self.address,
+            PUSH_PATH,
+            HttpMethod::Post,
+            StatusCode::NO_CONTENT,
+            true,
+            &self.tls,
+            &self.auth,
+            cx,
+            self.acknowledgements,
+            self.keepalive.clone(),
+        )
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            self.schema_definition(global_log_namespace.merge(self.log_namespace)),
+        )]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+struct LokiSource {
+    log_namespace: LogNamespace,
+}
+
+impl LokiSource {
+    fn build_stream_events(
+        &self,
+        stream: Stream,
+        tenant_id: Option<&str>,
+        events: &mut Vec<Event>,
+    ) {
+        let log_namespace = self.log_namespace;
+        let now = Utc::now();
+        let labels = Value::Object(
+            stream
+                .labels
+                .into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect::<ObjectMap>(),
+        );
+
+        events.extend(stream.entries.into_iter().map(|entry| {
+            let mut log = log_namespace.new_log_from_data(Bytes::from(entry.line));
+            log_namespace.insert_source_metadata(
+                LokiConfig::NAME,
+                &mut log,
+                log_schema().timestamp_key().map(LegacyKey::Overwrite),
+                path!("timestamp"),
+                entry.timestamp,
+            );
+            log_namespace.insert_standard_vector_source_metadata(&mut log, LokiConfig::NAME, now);
+            log_namespace.insert_source_metadata(
+                LokiConfig::NAME,
+                &mut log,
+                Some(LegacyKey::InsertIfEmpty(path!("labels"))),
+                path!("labels"),
+                labels.clone(),
+            );
+            if !entry.structured_metadata.is_empty() {
+                log_namespace.insert_source_metadata(
+                    LokiConfig::NAME,
+                    &mut log,
+                    Some(LegacyKey::InsertIfEmpty(path!("structured_metadata"))),
+                    path!("structured_metadata"),
+                    entry
+                        .structured_metadata
+                        .into_iter()
+                        .map(|(name, value)| (name.into(), value.into()))
+                        .collect::<ObjectMap>(),
+                );
+            }
+            if let Some(tenant_id) = tenant_id {
+                log_namespace.insert_source_metadata(
+                    LokiConfig::NAME,
+                    &mut log,
+                    Some(LegacyKey::InsertIfEmpty(path!("tenant_id"))),
+                    path!("tenant_id"),
+                    tenant_id.to_owned(),
+                );
+            }
+            Event::Log(log)
+        }));
+    }
+}
+
+impl HttpSource for LokiSource {
+    fn decode(&self, encoding_header: Option<&str>, body: Bytes) -> Result<Bytes, ErrorMessage> {
+        // Protocol Buffers requests are always snappy compressed, which some clients advertise
+        // in their `Content-Encoding`, so it is left to the parsing of the request.
+        decode(
+            encoding_header.filter(|encoding| !encoding.eq_ignore_ascii_case("snappy")),
+            body,
+        )
+    }
+
+    fn build_events(
+        &self,
+        body: Bytes,
+        header_map: &HeaderMap,
+        _query_parameters: &HashMap<String, String>,
+        _full_path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        let is_json = header_map
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with("application/json"));
+        let streams = if is_json {
+            parse_json(&body)
+        } else {
+            parse_protobuf(&body)
+        }
+        .map_err(|error| {
+            emit!(LokiPushRequestParseError { error: &error });
+            ErrorMessage::new(StatusCode::BAD_REQUEST, error.to_string())
+        })?;
+
+        let tenant_id = header_map
+            .get(TENANT_ID_HEADER)
+            .and_then(|tenant_id| tenant_id.to_str().ok());
+        let mut events = Vec::new();
+        for stream in streams {
+            self.build_stream_events(stream, tenant_id, &mut events);
+        }
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use loki_logproto::util::{Batch, Entry, Stream};
+    use vector_lib::event::EventStatus;
+
+    use super::*;
+    use crate::{
+        test_util::{
+            self,
+            components::{assert_source_compliance, HTTP_PUSH_SOURCE_TAGS},
+            spawn_collect_n, wait_for_tcp,
+        },
+        SourceSender,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LokiConfig>();
+    }
+
+    async fn source(
+        log_namespace: Option<bool>,
+    ) -> (SocketAddr, impl futures::Stream<Item = Event> + Unpin) {
+        let address = test_util::next_addr();
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let source = LokiConfig {
+            address,
+            tls: None,
+            auth: None,
+            acknowledgements: SourceAcknowledgementsConfig::default(),
+            keepalive: KeepaliveConfig::default(),
+            log_namespace,
+        }
+        .build(SourceContext::new_test(tx, None))
+        .await
+        .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+        (address, rx)
+    }
+
+    async fn push(address: SocketAddr, content_type: &str, body: Vec<u8>) -> u16 {
+        reqwest::Client::new()
+            .post(format!("http://{}{}", address, PUSH_PATH))
+            .header("Content-Type", content_type)
+            .header(TENANT_ID_HEADER, "tenant-1")
+            .body(body)
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16()
+    }
+
+    #[tokio::test]
+    async fn receives_protobuf_push_requests() {
+        let events = assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
+            let (address, rx) = source(None).await;
+            let labels = HashMap::from([("job".to_owned(), "nginx".to_owned())]);
+            let batch = Batch(vec![Stream(
+                labels,
+                vec![
+                    Entry(1_700_000_000_000_000_000, "GET /".to_owned(), vec![]),
+                    Entry(
+                        1_700_000_001_000_000_000,
+                        "GET /health".to_owned(),
+                        vec![("trace_id".to_owned(), "abc".to_owned())],
+                    ),
+                ],
+            )]);
+            let body = snap::raw::Encoder::new()
+                .compress_vec(&batch.encode())
+                .unwrap();
+
+            spawn_collect_n(
+                async move {
+                    assert_eq!(204, push(address, "application/x-protobuf", body).await);
+                },
+                rx,
+                2,
+            )
+            .await
+        })
+        .await;
+
+        let log = events[0].as_log();
+        assert_eq!(*log.get("message").unwrap(), "GET /".into());
+        assert_eq!(*log.get("source_type").unwrap(), "loki".into());
+        assert_eq!(*log.get("labels.job").unwrap(), "nginx".into());
+        assert_eq!(*log.get("tenant_id").unwrap(), "tenant-1".into());
+        assert_eq!(
+            *log.get("timestamp").unwrap(),
+            Utc.timestamp_opt(1_700_000_000, 0).unwrap().into()
+        );
+        assert!(log.get("structured_metadata").is_none());
+
+        let log = events[1].as_log();
+        assert_eq!(*log.get("message").unwrap(), "GET /health".into());
+        assert_eq!(
+            *log.get("structured_metadata.trace_id").unwrap(),
+            "abc".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn receives_json_push_requests() {
+        let events = assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
+            let (address, rx) = source(Some(true)).await;
+            let body = serde_json::json!({
+                "streams": [{
+                    "stream": { "job": "nginx" },
+                    "values": [["1700000000000000000", "GET /", { "trace_id": "abc" }]],
+                }],
+            });
+
+            spawn_collect_n(
+                async move {
+                    let body = serde_json::to_vec(&body).unwrap();
+                    assert_eq!(204, push(address, "application/json", body).await);
+                },
+                rx,
+                1,
+            )
+            .await
+        })
+        .await;
+
+        let log = events[0].as_log();
+        assert_eq!(*log.value(), "GET /".into());
+        let meta = log.metadata().value();
+        assert_eq!(
+            *meta.get(path!("loki", "labels", "job")).unwrap(),
+            "nginx".into()
+        );
+        assert_eq!(
+            *meta
+                .get(path!("loki", "structured_metadata", "trace_id"))
+                .unwrap(),
+            "abc".into()
+        );
+        assert_eq!(
+            *meta.get(path!("loki", "tenant_id")).unwrap(),
+            "tenant-1".into()
+        );
+        assert_eq!(
+            *meta.get(path!("loki", "timestamp")).unwrap(),
+            Utc.timestamp_opt(1_700_000_000, 0).unwrap().into()
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_push_requests() {
+        let (address, _rx) = source(None).await;
+        assert_eq!(
+            400,
+            push(address, "application/x-protobuf", b"not snappy".to_vec()).await
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Parses the push requests of the Loki push API, sent either as snappy compressed Protocol
+//! Buffers or as JSON.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, TimeZone, Utc};
+use loki_logproto::{logproto, util::decode_labels_string};
+use prost::Message;
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum ParseError {
+    #[snafu(display("Could not decompress request: {}", source))]
+    Snappy { source: snap::Error },
+    #[snafu(display("Could not decode request: {}", source))]
+    Protobuf { source: prost::DecodeError },
+    #[snafu(display("Could not decode request: {}", source))]
+    Json { source: serde_json::Error },
+    #[snafu(display("Invalid stream labels: {:?}", labels))]
+    InvalidLabels { labels: String },
+    #[snafu(display("Invalid entry timestamp: {:?}", timestamp))]
+    InvalidTimestamp { timestamp: String },
+}
+
+/// A stream of entries sharing the same labels.
+#[derive(Debug, PartialEq)]
+pub(super) struct Stream {
+    pub(super) labels: Vec<(String, String)>,
+    pub(super) entries: Vec<Entry>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct Entry {
+    pub(super) timestamp: DateTime<Utc>,
+    pub(super) line: String,
+    pub(super) structured_metadata: Vec<(String, String)>,
+}
+
+pub(super) fn parse_protobuf(body: &[u8]) -> Result<Vec<Stream>, ParseError> {
+    let body = snap::raw::Decoder::new()
+        .decompress_vec(body)
+        .context(SnappySnafu)?;
+    let request = logproto::PushRequest::decode(body.as_slice()).context(ProtobufSnafu)?;
+
+    request
+        .streams
+        .into_iter()
+        .map(|stream| {
+            let labels = decode_labels_string(&stream.labels).ok_or(ParseError::InvalidLabels {
+                labels: stream.labels,
+            })?;
+            let entries = stream
+                .entries
+                .into_iter()
+                .map(|entry| {
+                    let timestamp = entry
+                        .timestamp
+                        .as_ref()
+                        .and_then(|ts| {
+                            let nanos = u32::try_from(ts.nanos).ok()?;
+                            Utc.timestamp_opt(ts.seconds, nanos).single()
+                        })
+                        .ok_or_else(|| ParseError::InvalidTimestamp {
+                            timestamp: format!("{:?}", entry.timestamp),
+                        })?;
+                    Ok(Entry {
+                        timestamp,
+                        line: entry.line,
+                        structured_metadata: entry
+                            .structured_metadata
+                            .into_iter()
+                            .map(|pair| (pair.name, pair.value))
+                            .collect(),
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(Stream { labels, entries })
+        })
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct JsonPushRequest {
+    streams: Vec<JsonStream>,
+}
+
+#[derive(Deserialize)]
+struct JsonStream {
+    #[serde(default)]
+    stream: BTreeMap<String, String>,
+    values: Vec<JsonEntry>,
+}
+
+/// An entry, as a `[<timestamp in nanoseconds>, <line>]` array optionally followed by its
+/// structured metadata.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonEntry {
+    WithStructuredMetadata(String, String, BTreeMap<String, String>),
+    Line(String, String),
+}
+
+pub(super) fn parse_json(body: &[u8]) -> Result<Vec<Stream>, ParseError> {
+    let request: JsonPushRequest = serde_json::from_slice(body).context(JsonSnafu)?;
+
+    request
+        .streams
+        .into_iter()
+        .map(|stream| {
+            let entries = stream
+                .values
+                .into_iter()
+                .map(|entry| {
+                    let (timestamp, line, structured_metadata) = match entry {
+                        JsonEntry::WithStructuredMetadata(timestamp, line, metadata) => {
+                            (timestamp, line, metadata.into_iter().collect())
+                        }
+                        JsonEntry::Line(timestamp, line) => (timestamp, line, Vec::new()),
+                    };
+                    let nanos = timestamp
+                        .parse::<i64>()
+                        .map_err(|_| ParseError::InvalidTimestamp { timestamp })?;
+                    Ok(Entry {
+                        timestamp: Utc.timestamp_nanos(nanos),
+                        line,
+                        structured_metadata,
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(Stream {
+                labels: stream.stream.into_iter().collect(),
+                entries,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_json_entries() {
+        let streams = parse_json(
+            br#"{"streams": [{
+                "stream": {"job": "nginx"},
+                "values": [
+                    ["1700000000000000001", "GET /"],
+                    ["1700000000000000002", "GET /health", {"trace_id": "abc"}]
+                ]
+            }]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            streams,
+            vec![Stream {
+                labels: vec![("job".to_owned(), "nginx".to_owned())],
+                entries: vec![
+                    Entry {
+                        timestamp: Utc.timestamp_nanos(1_700_000_000_000_000_001),
+                        line: "GET /".to_owned(),
+                        structured_metadata: vec![],
+                    },
+                    Entry {
+                        timestamp: Utc.timestamp_nanos(1_700_000_000_000_000_002),
+                        line: "GET /health".to_owned(),
+                        structured_metadata: vec![("trace_id".to_owned(), "abc".to_owned())],
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_timestamps() {
+        let error =
+            parse_json(br#"{"streams": [{"values": [["yesterday", "GET /"]]}]}"#).unwrap_err();
+        assert!(matches!(error, ParseError::InvalidTimestamp { .. }));
+    }
+
+    #[test]
+    fn rejects_invalid_labels() {
+        let request = logproto::PushRequest {
+            streams: vec![logproto::StreamAdapter {
+                labels: "job=nginx".to_owned(),
+                entries: vec![],
+                hash: 0,
+            }],
+        };
+        let body = snap::raw::Encoder::new()
+            .compress_vec(&request.encode_to_vec())
+            .unwrap();
+
+        let error = parse_protobuf(&body).unwrap_err();
+        assert!(matches!(error, ParseError::InvalidLabels { .. }));
+    }
+}
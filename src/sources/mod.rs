@@ -9,10 +9,14 @@ pub mod apache_metrics;
 pub mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 pub mod aws_kinesis_firehose;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+pub mod aws_kinesis_streams;
 #[cfg(feature = "sources-aws_s3")]
 pub mod aws_s3;
 #[cfg(feature = "sources-aws_sqs")]
 pub mod aws_sqs;
+#[cfg(feature = "sources-azure_event_hubs")]
+pub mod azure_event_hubs;
 #[cfg(feature = "sources-datadog_agent")]
 pub mod datadog_agent;
 #[cfg(feature = "sources-demo_logs")]
@@ -21,6 +25,12 @@ pub mod demo_logs;
 pub mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 pub mod docker_logs;
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    feature = "sources-ebpf_flows"
+))]
+pub mod ebpf_flows;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
@@ -52,18 +62,28 @@ pub mod internal_metrics;
 pub mod journald;
 #[cfg(feature = "sources-kafka")]
 pub mod kafka;
+#[cfg(feature = "sources-kubernetes_events")]
+pub mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub mod kubernetes_logs;
 #[cfg(feature = "sources-logstash")]
 pub mod logstash;
+#[cfg(feature = "sources-loki")]
+pub mod loki;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub mod mongodb_metrics;
+#[cfg(feature = "sources-mqtt")]
+pub mod mqtt;
 #[cfg(feature = "sources-nats")]
 pub mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 pub mod nginx_metrics;
 #[cfg(feature = "sources-opentelemetry")]
 pub mod opentelemetry;
+#[cfg(feature = "sources-plugin")]
+pub mod plugin;
+#[cfg(feature = "sources-postgres_cdc")]
+pub mod postgres_cdc;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub mod postgresql_metrics;
 #[cfg(any(
@@ -76,10 +96,14 @@ pub mod prometheus;
 pub mod pulsar;
 #[cfg(feature = "sources-redis")]
 pub mod redis;
+#[cfg(feature = "sources-snmp_trap")]
+pub mod snmp_trap;
 #[cfg(feature = "sources-socket")]
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
 pub mod splunk_hec;
+#[cfg(feature = "sources-splunk_s2s")]
+pub mod splunk_s2s;
 #[cfg(feature = "sources-static_metrics")]
 pub mod static_metrics;
 #[cfg(feature = "sources-statsd")]
@@ -88,6 +112,8 @@ pub mod statsd;
 pub mod syslog;
 #[cfg(feature = "sources-vector")]
 pub mod vector;
+#[cfg(feature = "sources-windows_event_log")]
+pub mod windows_event_log;
 
 pub mod util;
 
@@ -0,0 +1,365 @@
+use std::time::Duration;
+
+use rand::Rng;
+use snafu::{ResultExt, Snafu};
+use vector_lib::codecs::decoding::{DeserializerConfig, FramingConfig};
+use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path};
+use vector_lib::sensitive_string::SensitiveString;
+use vector_lib::tls::TlsError;
+use vrl::value::Kind;
+
+use crate::{
+    codecs::DecodingConfig,
+    common::mqtt::{tls_transport, MqttProtocolVersion, MqttQoS},
+    config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    serde::{default_decoding, default_framing_message_based},
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+use super::source::{MqttClient, MqttSource};
+
+#[derive(Clone, Debug, Eq, PartialEq, Snafu)]
+pub enum ConfigurationError {
+    #[snafu(display("Client ID is not allowed to be empty."))]
+    EmptyClientId,
+    #[snafu(display("Username and password must be either both provided or both missing."))]
+    InvalidCredentials,
+    #[snafu(display("At least one topic must be provided."))]
+    NoTopics,
+    #[snafu(display(
+        "Shared subscription group name must not be empty or contain `/`, `+` or `#`."
+    ))]
+    InvalidSharedGroup,
+}
+
+#[derive(Debug, Snafu)]
+pub enum MqttError {
+    #[snafu(display("TLS error: {}", source))]
+    Tls { source: TlsError },
+    #[snafu(display("MQTT configuration error: {}", source))]
+    Configuration { source: ConfigurationError },
+}
+
+/// Configuration for the `mqtt` source.
+#[configurable_component(source("mqtt", "Collect logs from MQTT."))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSourceConfig {
+    /// MQTT server address (The broker’s domain name or IP address).
+    #[configurable(metadata(docs::examples = "mqtt.example.com", docs::examples = "127.0.0.1"))]
+    #[derivative(Default(value = "\"localhost\".into()"))]
+    pub host: String,
+
+    /// TCP port of the MQTT server to connect to.
+    #[serde(default = "default_port")]
+    #[derivative(Default(value = "default_port()"))]
+    pub port: u16,
+
+    /// MQTT username.
+    pub user: Option<String>,
+
+    /// MQTT password.
+    pub password: Option<SensitiveString>,
+
+    /// MQTT client ID.
+    ///
+    /// If unset, a random client ID is generated. When several Vector instances consume from the
+    /// same broker, each of them needs a distinct client ID.
+    pub client_id: Option<String>,
+
+    /// Connection keep-alive interval.
+    #[serde(default = "default_keep_alive")]
+    #[derivative(Default(value = "default_keep_alive()"))]
+    pub keep_alive: u16,
+
+    /// If set to true, the MQTT session is cleaned on login.
+    ///
+    /// With a persistent session, the broker queues messages published while Vector is
+    /// disconnected and delivers them once it reconnects.
+    #[serde(default = "default_clean_session")]
+    pub clean_session: bool,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol_version: MqttProtocolVersion,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    /// MQTT topic filters to subscribe to.
+    ///
+    /// The single-level (`+`) and multi-level (`#`) wildcards are supported.
+    #[configurable(metadata(docs::examples = "vector"))]
+    #[configurable(metadata(docs::examples = "sensors/+/temperature"))]
+    #[configurable(metadata(docs::examples = "telemetry/#"))]
+    #[derivative(Default(value = "vec![\"vector\".into()]"))]
+    pub topics: Vec<String>,
+
+    /// The group name of a [shared subscription][shared_subscription].
+    ///
+    /// When set, every topic filter is subscribed to as `$share/<group>/<topic>`, so that the
+    /// broker balances messages between all clients of the group instead of sending each message
+    /// to all of them.
+    ///
+    /// [shared_subscription]: https://docs.oasis-open.org/mqtt/mqtt/v5.0/os/mqtt-v5.0-os.html#_Toc3901250
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub shared_group: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub quality_of_service: MqttQoS,
+
+    /// Overrides the name of the log field used to add the topic to each event.
+    ///
+    /// The value is the topic the message was published to.
+    ///
+    /// By default, `"topic"` is used.
+    #[serde(default = "default_topic_key")]
+    #[derivative(Default(value = "default_topic_key()"))]
+    #[configurable(metadata(docs::examples = "topic"))]
+    pub topic_key: OptionalValuePath,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+}
+
+const fn default_port() -> u16 {
+    1883
+}
+
+const fn default_keep_alive() -> u16 {
+    60
+}
+
+const fn default_clean_session() -> bool {
+    false
+}
+
+fn default_topic_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("topic"))
+}
+
+impl GenerateConfig for MqttSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            host = "localhost"
+            topics = ["vector"]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SourceConfig for MqttSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let client = self.build_client()?;
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        let source = MqttSource {
+            client,
+            subscriptions: self.subscriptions()?,
+            quality_of_service: self.quality_of_service,
+            topic_key: self.topic_key.clone(),
+            decoder,
+            log_namespace,
+        };
+
+        Ok(Box::pin(source.run(cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                self.topic_key.path.clone().map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("topic"),
+                Kind::bytes(),
+                None,
+            );
+
+        vec![SourceOutput::new_maybe_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+impl MqttSourceConfig {
+    fn client_id(&self) -> Result<String, MqttError> {
+        let client_id = self.client_id.clone().unwrap_or_else(|| {
+            let hash = rand::thread_rng()
+                .sample_iter(&rand_distr::Alphanumeric)
+                .take(6)
+                .map(char::from)
+                .collect::<String>();
+            format!("vectorSource{hash}")
+        });
+
+        if client_id.is_empty() {
+            return Err(ConfigurationError::EmptyClientId).context(ConfigurationSnafu);
+        }
+        Ok(client_id)
+    }
+
+    fn credentials(&self) -> Result<Option<(&str, &str)>, MqttError> {
+        match (&self.user, &self.password) {
+            (Some(user), Some(password)) => Ok(Some((user, password.inner()))),
+            (None, None) => Ok(None),
+            _ => Err(ConfigurationError::InvalidCredentials).context(ConfigurationSnafu),
+        }
+    }
+
+    /// Returns the topic filters to subscribe to, taking the shared subscription group into
+    /// account.
+    fn subscriptions(&self) -> Result<Vec<String>, MqttError> {
+        if self.topics.is_empty() {
+            return Err(ConfigurationError::NoTopics).context(ConfigurationSnafu);
+        }
+
+        match &self.shared_group {
+            None => Ok(self.topics.clone()),
+            Some(group) if group.is_empty() || group.contains(['/', '+', '#']) => {
+                Err(ConfigurationError::InvalidSharedGroup).context(ConfigurationSnafu)
+            }
+            Some(group) => Ok(self
+                .topics
+                .iter()
+                .map(|topic| format!("$share/{group}/{topic}"))
+                .collect()),
+        }
+    }
+
+    fn build_client(&self) -> Result<MqttClient, MqttError> {
+        let client_id = self.client_id()?;
+        let credentials = self.credentials()?;
+        let tls = MaybeTlsSettings::from_config(&self.tls, false).context(TlsSnafu)?;
+        let keep_alive = Duration::from_secs(self.keep_alive.into());
+
+        let client = match self.protocol_version {
+            MqttProtocolVersion::V311 => {
+                let mut options = rumqttc::MqttOptions::new(client_id, &self.host, self.port);
+                options.set_keep_alive(keep_alive);
+                options.set_clean_session(self.clean_session);
+                if let Some((user, password)) = credentials {
+                    options.set_credentials(user, password);
+                }
+                if let Some(transport) = tls_transport(&tls) {
+                    options.set_transport(transport);
+                }
+                MqttClient::new_v311(options)
+            }
+            MqttProtocolVersion::V5 => {
+                let mut options = rumqttc::v5::MqttOptions::new(client_id, &self.host, self.port);
+                options.set_keep_alive(keep_alive);
+                options.set_clean_start(self.clean_session);
+                if let Some((user, password)) = credentials {
+                    options.set_credentials(user, password);
+                }
+                if let Some(transport) = tls_transport(&tls) {
+                    options.set_transport(transport);
+                }
+                MqttClient::new_v5(options)
+            }
+        };
+
+        Ok(client)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSourceConfig>();
+    }
+
+    #[test]
+    fn parses_protocol_version() {
+        let config: MqttSourceConfig = toml::from_str(
+            r#"
+            host = "localhost"
+            topics = ["a"]
+            protocol_version = "v5"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.protocol_version, MqttProtocolVersion::V5);
+
+        let config: MqttSourceConfig = toml::from_str(r#"host = "localhost""#).unwrap();
+        assert_eq!(config.protocol_version, MqttProtocolVersion::V311);
+        assert_eq!(config.topics, vec!["vector".to_string()]);
+    }
+
+    #[test]
+    fn shared_subscriptions() {
+        let config = MqttSourceConfig {
+            topics: vec!["sensors/+/temperature".into(), "telemetry/#".into()],
+            shared_group: Some("vector".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.subscriptions().unwrap(),
+            vec![
+                "$share/vector/sensors/+/temperature".to_string(),
+                "$share/vector/telemetry/#".to_string()
+            ]
+        );
+
+        let config = MqttSourceConfig {
+            shared_group: Some("a/b".into()),
+            ..Default::default()
+        };
+        assert!(config.subscriptions().is_err());
+
+        let config = MqttSourceConfig {
+            topics: vec![],
+            ..Default::default()
+        };
+        assert!(config.subscriptions().is_err());
+    }
+
+    #[test]
+    fn rejects_partial_credentials() {
+        let config = MqttSourceConfig {
+            user: Some("user".into()),
+            ..Default::default()
+        };
+        assert!(config.build_client().is_err());
+
+        let config = MqttSourceConfig {
+            client_id: Some(String::new()),
+            ..Default::default()
+        };
+        assert!(config.build_client().is_err());
+    }
+}
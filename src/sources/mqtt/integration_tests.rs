@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use rumqttc::{AsyncClient, MqttOptions, QoS};
+use vector_lib::config::log_schema;
+
+use crate::common::mqtt::MqttProtocolVersion;
+use crate::config::{SourceConfig, SourceContext};
+use crate::sources::mqtt::MqttSourceConfig;
+use crate::test_util::components::{assert_source_compliance, SOURCE_TAGS};
+use crate::test_util::{collect_n, random_string, trace_init};
+use crate::SourceSender;
+
+fn mqtt_broker_address() -> String {
+    std::env::var("MQTT_BROKER_ADDRESS").unwrap_or_else(|_| "emqx".into())
+}
+
+fn mqtt_broker_port() -> u16 {
+    std::env::var("MQTT_BROKER_PORT")
+        .unwrap_or_else(|_| "1883".into())
+        .parse::<u16>()
+        .expect("Cannot parse as u16")
+}
+
+/// Publishes retained messages, so that they are delivered to the source whenever its
+/// subscription goes through.
+async fn publish(topic: &str, messages: &[String]) {
+    let mut options = MqttOptions::new(
+        format!("integration-test-producer-{}", random_string(6)),
+        mqtt_broker_address(),
+        mqtt_broker_port(),
+    );
+    options.set_keep_alive(Duration::from_secs(5));
+
+    let (client, mut eventloop) = AsyncClient::new(options, 10);
+    tokio::spawn(async move { while eventloop.poll().await.is_ok() {} });
+
+    for message in messages {
+        client
+            .publish(topic, QoS::AtLeastOnce, true, message.clone())
+            .await
+            .expect("Cannot publish the message");
+    }
+}
+
+async fn receive(config: MqttSourceConfig) {
+    trace_init();
+
+    let topic = format!("test/{}", random_string(10));
+    let messages = vec![random_string(20)];
+    publish(&topic, &messages).await;
+
+    let config = MqttSourceConfig {
+        host: mqtt_broker_address(),
+        port: mqtt_broker_port(),
+        topics: vec![topic.clone()],
+        ..config
+    };
+
+    let events = assert_source_compliance(&SOURCE_TAGS, async move {
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .expect("Cannot build the source");
+        tokio::spawn(source);
+
+        tokio::time::timeout(Duration::from_secs(10), collect_n(rx, messages.len()))
+            .await
+            .expect("Timed out waiting for messages")
+    })
+    .await;
+
+    let log = events[0].as_log();
+    assert_eq!(log["topic"], topic.as_str().into());
+    assert_eq!(
+        log[log_schema().message_key().unwrap().to_string()],
+        messages[0].as_str().into()
+    );
+}
+
+#[tokio::test]
+async fn mqtt_v311_happy() {
+    receive(MqttSourceConfig::default()).await;
+}
+
+#[tokio::test]
+async fn mqtt_v5_happy() {
+    receive(MqttSourceConfig {
+        protocol_version: MqttProtocolVersion::V5,
+        ..Default::default()
+    })
+    .await;
+}
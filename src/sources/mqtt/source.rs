@@ -0,0 +1,239 @@
+use bytes::Bytes;
+use chrono::Utc;
+use futures::StreamExt;
+use snafu::Snafu;
+use tokio_util::codec::FramedRead;
+use vector_lib::codecs::decoding::StreamDecodingError;
+use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+};
+use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path};
+use vector_lib::EstimatedJsonEncodedSizeOf;
+
+use crate::{
+    codecs::Decoder,
+    common::mqtt::MqttQoS,
+    event::Event,
+    internal_events::{MqttSourceConnectionError, StreamClosedError},
+    shutdown::ShutdownSignal,
+    sinks::util::retries::ExponentialBackoff,
+    SourceSender,
+};
+
+use super::MqttSourceConfig;
+
+/// The capacity of the request channel between the client and its event loop.
+const CLIENT_CAPACITY: usize = 1024;
+
+#[derive(Debug, Snafu)]
+pub enum MqttClientError {
+    #[snafu(display("{}", source))]
+    V311Connection { source: rumqttc::ConnectionError },
+    #[snafu(display("{}", source))]
+    V5Connection {
+        source: rumqttc::v5::ConnectionError,
+    },
+    #[snafu(display("{}", source))]
+    V311Subscribe { source: rumqttc::ClientError },
+    #[snafu(display("{}", source))]
+    V5Subscribe { source: rumqttc::v5::ClientError },
+}
+
+/// A message published to one of the subscribed topics.
+pub(super) struct MqttMessage {
+    topic: String,
+    payload: Bytes,
+}
+
+/// The outcome of polling the event loop of a client.
+enum Polled {
+    /// The client (re)connected to the broker, losing any subscription of a clean session.
+    Connected,
+    Message(MqttMessage),
+    Other,
+}
+
+/// An MQTT client speaking either version of the protocol.
+///
+/// Reconnecting is handled by the event loop of the client: polling it again after a connection
+/// error starts a new connection attempt.
+pub(super) enum MqttClient {
+    V311 {
+        client: rumqttc::AsyncClient,
+        eventloop: rumqttc::EventLoop,
+    },
+    V5 {
+        client: rumqttc::v5::AsyncClient,
+        eventloop: rumqttc::v5::EventLoop,
+    },
+}
+
+impl MqttClient {
+    pub(super) fn new_v311(options: rumqttc::MqttOptions) -> Self {
+        let (client, eventloop) = rumqttc::AsyncClient::new(options, CLIENT_CAPACITY);
+        Self::V311 { client, eventloop }
+    }
+
+    pub(super) fn new_v5(options: rumqttc::v5::MqttOptions) -> Self {
+        let (client, eventloop) = rumqttc::v5::AsyncClient::new(options, CLIENT_CAPACITY);
+        Self::V5 { client, eventloop }
+    }
+
+    async fn subscribe(&self, topics: &[String], qos: MqttQoS) -> Result<(), MqttClientError> {
+        for topic in topics {
+            match self {
+                Self::V311 { client, .. } => client
+                    .subscribe(topic, qos.into())
+                    .await
+                    .map_err(|source| MqttClientError::V311Subscribe { source })?,
+                Self::V5 { client, .. } => client
+                    .subscribe(topic, qos.into())
+                    .await
+                    .map_err(|source| MqttClientError::V5Subscribe { source })?,
+            }
+        }
+        Ok(())
+    }
+
+    async fn poll(&mut self) -> Result<Polled, MqttClientError> {
+        match self {
+            Self::V311 { eventloop, .. } => {
+                use rumqttc::{Event, Incoming};
+
+                match eventloop.poll().await {
+                    Ok(Event::Incoming(Incoming::ConnAck(_))) => Ok(Polled::Connected),
+                    Ok(Event::Incoming(Incoming::Publish(publish))) => {
+                        Ok(Polled::Message(MqttMessage {
+                            topic: publish.topic,
+                            payload: publish.payload,
+                        }))
+                    }
+                    Ok(_) => Ok(Polled::Other),
+                    Err(source) => Err(MqttClientError::V311Connection { source }),
+                }
+            }
+            Self::V5 { eventloop, .. } => {
+                use rumqttc::v5::{mqttbytes::v5::Packet, Event};
+
+                match eventloop.poll().await {
+                    Ok(Event::Incoming(Packet::ConnAck(_))) => Ok(Polled::Connected),
+                    Ok(Event::Incoming(Packet::Publish(publish))) => {
+                        Ok(Polled::Message(MqttMessage {
+                            topic: String::from_utf8_lossy(&publish.topic).into_owned(),
+                            payload: publish.payload,
+                        }))
+                    }
+                    Ok(_) => Ok(Polled::Other),
+                    Err(source) => Err(MqttClientError::V5Connection { source }),
+                }
+            }
+        }
+    }
+}
+
+pub(super) struct MqttSource {
+    pub(super) client: MqttClient,
+    pub(super) subscriptions: Vec<String>,
+    pub(super) quality_of_service: MqttQoS,
+    pub(super) topic_key: OptionalValuePath,
+    pub(super) decoder: Decoder,
+    pub(super) log_namespace: LogNamespace,
+}
+
+impl MqttSource {
+    pub(super) async fn run(
+        mut self,
+        mut out: SourceSender,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let events_received = register!(EventsReceived);
+        let bytes_received = register!(BytesReceived::from(Protocol::from("mqtt")));
+        let mut backoff = ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(std::time::Duration::from_secs(30));
+
+        loop {
+            let polled = tokio::select! {
+                _ = &mut shutdown => break,
+                polled = self.client.poll() => polled,
+            };
+
+            match polled {
+                Ok(Polled::Connected) => {
+                    backoff.reset();
+                    // Subscriptions are (re)sent on every connection, as the broker does not keep
+                    // them for clean sessions.
+                    if let Err(error) = self
+                        .client
+                        .subscribe(&self.subscriptions, self.quality_of_service)
+                        .await
+                    {
+                        emit!(MqttSourceConnectionError { error });
+                    }
+                }
+                Ok(Polled::Message(message)) => {
+                    bytes_received.emit(ByteSize(message.payload.len()));
+                    let mut stream =
+                        FramedRead::new(message.payload.as_ref(), self.decoder.clone());
+                    while let Some(next) = stream.next().await {
+                        match next {
+                            Ok((events, _byte_size)) => {
+                                let count = events.len();
+                                events_received.emit(CountByteSize(
+                                    count,
+                                    events.estimated_json_encoded_size_of(),
+                                ));
+
+                                let now = Utc::now();
+                                let events = events.into_iter().map(|mut event| {
+                                    self.annotate(&mut event, &message.topic, now);
+                                    event
+                                });
+
+                                out.send_batch(events).await.map_err(|_| {
+                                    emit!(StreamClosedError { count });
+                                })?;
+                            }
+                            Err(error) => {
+                                // Error is logged by `crate::codecs`, no further
+                                // handling is needed here.
+                                if !error.can_continue() {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+                Ok(Polled::Other) => {}
+                Err(error) => {
+                    emit!(MqttSourceConnectionError { error });
+                    let delay = backoff.next().expect("backoff never ends");
+                    tokio::select! {
+                        _ = &mut shutdown => break,
+                        _ = tokio::time::sleep(delay) => {},
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn annotate(&self, event: &mut Event, topic: &str, now: chrono::DateTime<Utc>) {
+        if let Event::Log(ref mut log) = event {
+            self.log_namespace.insert_standard_vector_source_metadata(
+                log,
+                MqttSourceConfig::NAME,
+                now,
+            );
+            self.log_namespace.insert_source_metadata(
+                MqttSourceConfig::NAME,
+                log,
+                self.topic_key.path.as_ref().map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("topic"),
+                topic,
+            );
+        }
+    }
+}
@@ -1,11 +1,20 @@
+use std::time::Duration;
+
+use async_nats::jetstream::{
+    self,
+    consumer::{pull, AckPolicy, PullConsumer},
+    AckKind,
+};
 use chrono::Utc;
 use futures::{pin_mut, StreamExt};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
 use vector_lib::codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
 use vector_lib::configurable::configurable_component;
+use vector_lib::finalizer::UnorderedFinalizer;
 use vector_lib::internal_event::{
     ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+    Registered,
 };
 use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path};
 use vector_lib::{
@@ -16,11 +25,13 @@ use vrl::value::Kind;
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
-    event::Event,
-    internal_events::StreamClosedError,
+    config::{
+        GenerateConfig, SourceAcknowledgementsConfig, SourceConfig, SourceContext, SourceOutput,
+    },
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{NatsJetStreamAckError, NatsJetStreamReadError, StreamClosedError},
     nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
     tls::TlsEnableableConfig,
     SourceSender,
@@ -34,6 +45,20 @@ enum BuildError {
     Connect { source: async_nats::ConnectError },
     #[snafu(display("NATS Subscribe Error: {}", source))]
     Subscribe { source: async_nats::SubscribeError },
+    #[snafu(display("NATS JetStream Stream Error: {}", source))]
+    GetStream {
+        source: jetstream::context::GetStreamError,
+    },
+    #[snafu(display("NATS JetStream Consumer Error: {}", source))]
+    Consumer {
+        source: jetstream::stream::ConsumerError,
+    },
+    #[snafu(display("NATS JetStream Consumer Error: {}", source))]
+    Messages {
+        source: jetstream::consumer::StreamError,
+    },
+    #[snafu(display("The `queue` option can't be used with `jetstream`."))]
+    JetStreamQueue,
 }
 
 /// Configuration for the `nats` source.
@@ -74,8 +99,14 @@ pub struct NatsSourceConfig {
     subject: String,
 
     /// The NATS queue group to join.
+    ///
+    /// Not supported with `jetstream`, as the messages of a durable consumer are already shared by
+    /// all of its clients.
     queue: Option<String>,
 
+    #[configurable(derived)]
+    jetstream: Option<NatsJetStreamConfig>,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -112,6 +143,58 @@ pub struct NatsSourceConfig {
     #[serde(default = "default_subscription_capacity")]
     #[derivative(Default(value = "default_subscription_capacity()"))]
     subscriber_capacity: usize,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+}
+
+/// Configuration for consuming messages from a [JetStream][jetstream] stream.
+///
+/// Messages are pulled by a durable consumer and explicitly acknowledged. When
+/// [end-to-end acknowledgements][e2e_acks] are enabled, a message is only acknowledged once all of
+/// its events are delivered by the sinks, and negatively acknowledged if delivery fails so that the
+/// server redelivers it.
+///
+/// [jetstream]: https://docs.nats.io/nats-concepts/jetstream
+/// [e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NatsJetStreamConfig {
+    /// The name of the stream to consume messages from.
+    #[configurable(metadata(docs::examples = "events"))]
+    stream: String,
+
+    /// The name of the durable consumer to pull messages with.
+    ///
+    /// The consumer is created on the stream if it doesn't exist yet, filtered on `subject`.
+    /// Vector instances using the same consumer share its messages.
+    #[configurable(metadata(docs::examples = "vector"))]
+    durable_name: String,
+
+    /// The time the server waits for a message to be acknowledged before redelivering it.
+    ///
+    /// Only used when creating the consumer.
+    #[serde(default = "default_ack_wait_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    ack_wait_secs: u64,
+
+    /// The maximum number of messages delivered to the consumer without being acknowledged.
+    ///
+    /// Once reached, the server stops delivering messages until some are acknowledged. Only used
+    /// when creating the consumer.
+    #[serde(default = "default_max_ack_pending")]
+    #[configurable(metadata(docs::type_unit = "messages"))]
+    max_ack_pending: i64,
+}
+
+const fn default_ack_wait_secs() -> u64 {
+    30
+}
+
+const fn default_max_ack_pending() -> i64 {
+    1000
 }
 
 fn default_subject_key_field() -> OptionalValuePath {
@@ -139,11 +222,27 @@ impl GenerateConfig for NatsSourceConfig {
 impl SourceConfig for NatsSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
-        let (connection, subscription) = create_subscription(self).await?;
         let decoder =
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
                 .build()?;
 
+        if let Some(jetstream) = &self.jetstream {
+            let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+            let (connection, consumer) = create_consumer(self, jetstream).await?;
+
+            return Ok(Box::pin(nats_jetstream_source(
+                self.clone(),
+                connection,
+                consumer,
+                decoder,
+                log_namespace,
+                acknowledgements,
+                cx.shutdown,
+                cx.out,
+            )));
+        }
+
+        let (connection, subscription) = create_subscription(self).await?;
         Ok(Box::pin(nats_source(
             self.clone(),
             connection,
@@ -181,7 +280,7 @@ impl SourceConfig for NatsSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        self.jetstream.is_some()
     }
 }
 
@@ -231,49 +330,85 @@ async fn nats_source(
     let bytes_received = register!(BytesReceived::from(Protocol::TCP));
     while let Some(msg) = stream.next().await {
         bytes_received.emit(ByteSize(msg.payload.len()));
-        let mut stream = FramedRead::new(msg.payload.as_ref(), decoder.clone());
-        while let Some(next) = stream.next().await {
-            match next {
-                Ok((events, _byte_size)) => {
-                    let count = events.len();
-                    let byte_size = events.estimated_json_encoded_size_of();
-                    events_received.emit(CountByteSize(count, byte_size));
-
-                    let now = Utc::now();
-
-                    let events = events.into_iter().map(|mut event| {
-                        if let Event::Log(ref mut log) = event {
-                            log_namespace.insert_standard_vector_source_metadata(
-                                log,
-                                NatsSourceConfig::NAME,
-                                now,
-                            );
-
-                            let legacy_subject_key_field = config
-                                .subject_key_field
-                                .path
-                                .as_ref()
-                                .map(LegacyKey::InsertIfEmpty);
-                            log_namespace.insert_source_metadata(
-                                NatsSourceConfig::NAME,
-                                log,
-                                legacy_subject_key_field,
-                                &owned_value_path!("subject"),
-                                msg.subject.as_str(),
-                            )
-                        }
-                        event
-                    });
-
-                    out.send_batch(events).await.map_err(|_| {
-                        emit!(StreamClosedError { count });
-                    })?;
+        let events = decode_message(&config, &decoder, log_namespace, &events_received, &msg).await;
+        let count = events.len();
+        out.send_batch(events).await.map_err(|_| {
+            emit!(StreamClosedError { count });
+        })?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn nats_jetstream_source(
+    config: NatsSourceConfig,
+    // Take ownership of the connection so it doesn't get dropped.
+    _connection: async_nats::Client,
+    consumer: PullConsumer,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let (finalizer, mut ack_stream) = UnorderedFinalizer::<jetstream::Message>::maybe_new(
+        acknowledgements,
+        Some(shutdown.clone()),
+    );
+    let mut messages = consumer
+        .messages()
+        .await
+        .context(MessagesSnafu)
+        .map_err(|error| emit!(NatsJetStreamReadError { error }))?;
+
+    let events_received = register!(EventsReceived);
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            entry = ack_stream.next() => {
+                if let Some((status, msg)) = entry {
+                    handle_ack(status, msg).await;
                 }
-                Err(error) => {
-                    // Error is logged by `crate::codecs`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
+            },
+            msg = messages.next() => {
+                let msg = match msg {
+                    Some(Ok(msg)) => msg,
+                    Some(Err(error)) => {
+                        // The pull consumer recovers from missed heartbeats and
+                        // reconnections by itself.
+                        emit!(NatsJetStreamReadError { error });
+                        continue;
+                    }
+                    None => break,
+                };
+
+                bytes_received.emit(ByteSize(msg.message.payload.len()));
+                let events = decode_message(
+                    &config,
+                    &decoder,
+                    log_namespace,
+                    &events_received,
+                    &msg.message,
+                )
+                .await;
+                let count = events.len();
+                match &finalizer {
+                    Some(finalizer) => {
+                        let (batch, receiver) = BatchNotifier::new_with_receiver();
+                        let events = events
+                            .into_iter()
+                            .map(|event| event.with_batch_notifier(&batch));
+                        out.send_batch(events).await.map_err(|_| {
+                            emit!(StreamClosedError { count });
+                        })?;
+                        finalizer.add(msg, receiver);
+                    }
+                    None => {
+                        out.send_batch(events).await.map_err(|_| {
+                            emit!(StreamClosedError { count });
+                        })?;
+                        handle_ack(BatchStatus::Delivered, msg).await;
                     }
                 }
             }
@@ -282,6 +417,75 @@ async fn nats_source(
     Ok(())
 }
 
+/// Acknowledges a JetStream message once its events are delivered, or asks the server to
+/// redeliver it if delivery failed. Messages whose events were rejected by a sink are terminated,
+/// as redelivering them would fail again.
+async fn handle_ack(status: BatchStatus, msg: jetstream::Message) {
+    let kind = match status {
+        BatchStatus::Delivered => AckKind::Ack,
+        BatchStatus::Errored => AckKind::Nak(None),
+        BatchStatus::Rejected => AckKind::Term,
+    };
+    if let Err(error) = msg.ack_with(kind).await {
+        emit!(NatsJetStreamAckError { error });
+    }
+}
+
+/// Decodes the payload of a message into events, along with their source metadata.
+async fn decode_message(
+    config: &NatsSourceConfig,
+    decoder: &Decoder,
+    log_namespace: LogNamespace,
+    events_received: &Registered<EventsReceived>,
+    msg: &async_nats::Message,
+) -> Vec<Event> {
+    let mut decoded = Vec::new();
+    let mut stream = FramedRead::new(msg.payload.as_ref(), decoder.clone());
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((events, _byte_size)) => {
+                let count = events.len();
+                let byte_size = events.estimated_json_encoded_size_of();
+                events_received.emit(CountByteSize(count, byte_size));
+
+                let now = Utc::now();
+
+                decoded.extend(events.into_iter().map(|mut event| {
+                    if let Event::Log(ref mut log) = event {
+                        log_namespace.insert_standard_vector_source_metadata(
+                            log,
+                            NatsSourceConfig::NAME,
+                            now,
+                        );
+
+                        let legacy_subject_key_field = config
+                            .subject_key_field
+                            .path
+                            .as_ref()
+                            .map(LegacyKey::InsertIfEmpty);
+                        log_namespace.insert_source_metadata(
+                            NatsSourceConfig::NAME,
+                            log,
+                            legacy_subject_key_field,
+                            &owned_value_path!("subject"),
+                            msg.subject.as_str(),
+                        )
+                    }
+                    event
+                }));
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+    decoded
+}
+
 async fn create_subscription(
     config: &NatsSourceConfig,
 ) -> Result<(async_nats::Client, async_nats::Subscriber), BuildError> {
@@ -300,6 +504,37 @@ async fn create_subscription(
     Ok((nc, subscription))
 }
 
+async fn create_consumer(
+    config: &NatsSourceConfig,
+    jetstream: &NatsJetStreamConfig,
+) -> Result<(async_nats::Client, PullConsumer), BuildError> {
+    if config.queue.is_some() {
+        return Err(BuildError::JetStreamQueue);
+    }
+
+    let nc = config.connect().await?;
+    let stream = jetstream::new(nc.clone())
+        .get_stream(&jetstream.stream)
+        .await
+        .context(GetStreamSnafu)?;
+    let consumer = stream
+        .get_or_create_consumer(
+            &jetstream.durable_name,
+            pull::Config {
+                durable_name: Some(jetstream.durable_name.clone()),
+                filter_subject: config.subject.clone(),
+                ack_policy: AckPolicy::Explicit,
+                ack_wait: Duration::from_secs(jetstream.ack_wait_secs),
+                max_ack_pending: jetstream.max_ack_pending,
+                ..Default::default()
+            },
+        )
+        .await
+        .context(ConsumerSnafu)?;
+
+    Ok((nc, consumer))
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::print_stdout)] //tests
@@ -315,6 +550,47 @@ mod tests {
         crate::test_util::test_generate_config::<NatsSourceConfig>();
     }
 
+    #[test]
+    fn parse_jetstream_config() {
+        let config: NatsSourceConfig = toml::from_str(
+            r#"
+            url = "nats://127.0.0.1:4222"
+            connection_name = "vector"
+            subject = "events.>"
+            acknowledgements = true
+
+            [jetstream]
+            stream = "events"
+            durable_name = "vector"
+            "#,
+        )
+        .unwrap();
+
+        let jetstream = config.jetstream.as_ref().unwrap();
+        assert_eq!(jetstream.stream, "events");
+        assert_eq!(jetstream.durable_name, "vector");
+        assert_eq!(jetstream.ack_wait_secs, default_ack_wait_secs());
+        assert_eq!(jetstream.max_ack_pending, default_max_ack_pending());
+        assert!(config.can_acknowledge());
+    }
+
+    #[tokio::test]
+    async fn jetstream_rejects_queue() {
+        let config = NatsSourceConfig {
+            queue: Some("vector".into()),
+            jetstream: Some(NatsJetStreamConfig {
+                stream: "events".into(),
+                durable_name: "vector".into(),
+                ack_wait_secs: default_ack_wait_secs(),
+                max_ack_pending: default_max_ack_pending(),
+            }),
+            ..Default::default()
+        };
+
+        let result = create_consumer(&config, config.jetstream.as_ref().unwrap()).await;
+        assert!(matches!(result, Err(BuildError::JetStreamQueue)));
+    }
+
     #[test]
     fn output_schema_definition_vector_namespace() {
         let config = NatsSourceConfig {
@@ -383,6 +659,7 @@ mod integration_tests {
     use vector_lib::config::log_schema;
 
     use super::*;
+    use crate::event::EventStatus;
     use crate::nats::{NatsAuthCredentialsFile, NatsAuthNKey, NatsAuthToken, NatsAuthUserPassword};
     use crate::test_util::{
         collect_n,
@@ -432,6 +709,90 @@ mod integration_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn nats_jetstream_acknowledgements() {
+        let subject = format!("test-{}", random_string(10));
+        let url =
+            std::env::var("NATS_ADDRESS").unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+        let conf = NatsSourceConfig {
+            connection_name: "".to_owned(),
+            subject: subject.clone(),
+            url,
+            jetstream: Some(NatsJetStreamConfig {
+                stream: subject.clone(),
+                durable_name: "vector".to_owned(),
+                ack_wait_secs: default_ack_wait_secs(),
+                max_ack_pending: default_max_ack_pending(),
+            }),
+            subject_key_field: default_subject_key_field(),
+            ..Default::default()
+        };
+
+        let nc = conf.connect().await.unwrap();
+        let context = jetstream::new(nc);
+        context
+            .create_stream(jetstream::stream::Config {
+                name: subject.clone(),
+                subjects: vec![subject.clone()],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        context
+            .publish(subject.clone(), Bytes::from_static(b"my message"))
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+
+        let (connection, consumer) = create_consumer(&conf, conf.jetstream.as_ref().unwrap())
+            .await
+            .unwrap();
+        let mut consumer_info = consumer.clone();
+
+        let events = assert_source_compliance(&SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let decoder = DecodingConfig::new(
+                conf.framing.clone(),
+                conf.decoding.clone(),
+                LogNamespace::Legacy,
+            )
+            .build()
+            .unwrap();
+            tokio::spawn(nats_jetstream_source(
+                conf.clone(),
+                connection,
+                consumer,
+                decoder,
+                LogNamespace::Legacy,
+                true,
+                ShutdownSignal::noop(),
+                tx,
+            ));
+
+            collect_n(rx, 1).await
+        })
+        .await;
+
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key().unwrap().to_string()],
+            "my message".into()
+        );
+        assert_eq!(events[0].as_log()["subject"], subject.into());
+
+        // The message is acknowledged once its event is delivered.
+        let mut num_ack_pending = 1;
+        for _ in 0..50 {
+            num_ack_pending = consumer_info.info().await.unwrap().num_ack_pending;
+            if num_ack_pending == 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+        assert_eq!(num_ack_pending, 0);
+    }
+
     #[tokio::test]
     async fn nats_no_auth() {
         let subject = format!("test-{}", random_string(10));
@@ -13,9 +13,13 @@ use tracing::Span;
 use vector_lib::internal_event::{
     ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Registered,
 };
-use vector_lib::opentelemetry::proto::collector::{
-    logs::v1::{ExportLogsServiceRequest, ExportLogsServiceResponse},
-    trace::v1::{ExportTraceServiceRequest, ExportTraceServiceResponse},
+use vector_lib::opentelemetry::{
+    json,
+    proto::collector::{
+        logs::v1::{ExportLogsServiceRequest, ExportLogsServiceResponse},
+        metrics::v1::{ExportMetricsServiceRequest, ExportMetricsServiceResponse},
+        trace::v1::{ExportTraceServiceRequest, ExportTraceServiceResponse},
+    },
 };
 use vector_lib::tls::MaybeTlsIncomingStream;
 use vector_lib::{
@@ -36,7 +40,10 @@ use crate::{
     SourceSender,
 };
 
-use super::{reply::protobuf, status::Status};
+use super::{
+    reply::{json as json_reply, protobuf},
+    status::Status,
+};
 
 #[derive(Clone, Copy, Debug, Snafu)]
 pub(crate) enum ApiError {
@@ -94,13 +101,64 @@ pub(crate) fn build_warp_filter(
         bytes_received.clone(),
         events_received.clone(),
     );
+    let metrics_filters = build_warp_metrics_filter(
+        acknowledgements,
+        out.clone(),
+        bytes_received.clone(),
+        events_received.clone(),
+    );
     let trace_filters = build_warp_trace_filter(
         acknowledgements,
         out.clone(),
         bytes_received,
         events_received,
     );
-    log_filters.or(trace_filters).unify().boxed()
+    log_filters
+        .or(metrics_filters)
+        .unify()
+        .or(trace_filters)
+        .unify()
+        .boxed()
+}
+
+/// The encodings of OTLP requests, given by their content type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Encoding {
+    Protobuf,
+    Json,
+}
+
+fn content_type() -> impl Filter<Extract = (Encoding,), Error = Rejection> + Clone {
+    warp::header::<String>("content-type").and_then(|content_type: String| async move {
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+        if media_type.eq_ignore_ascii_case("application/x-protobuf") {
+            Ok(Encoding::Protobuf)
+        } else if media_type.eq_ignore_ascii_case("application/json") {
+            Ok(Encoding::Json)
+        } else {
+            Err(warp::reject::custom(ErrorMessage::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!("Unsupported content type: {}", content_type),
+            )))
+        }
+    })
+}
+
+fn decode_request<M: Message + Default>(
+    encoding: Encoding,
+    body: Bytes,
+    message_name: &str,
+) -> Result<M, ErrorMessage> {
+    let request = match encoding {
+        Encoding::Protobuf => M::decode(body).map_err(|error| error.to_string()),
+        Encoding::Json => json::decode(&body, message_name).map_err(|error| error.to_string()),
+    };
+    request.map_err(|error| {
+        ErrorMessage::new(
+            StatusCode::BAD_REQUEST,
+            format!("Could not decode request: {}", error),
+        )
+    })
 }
 
 fn build_warp_log_filter(
@@ -112,26 +170,63 @@ fn build_warp_log_filter(
 ) -> BoxedFilter<(Response,)> {
     warp::post()
         .and(warp::path!("v1" / "logs"))
-        .and(warp::header::exact_ignore_case(
-            "content-type",
-            "application/x-protobuf",
-        ))
+        .and(content_type())
         .and(warp::header::optional::<String>("content-encoding"))
         .and(warp::body::bytes())
-        .and_then(move |encoding_header: Option<String>, body: Bytes| {
-            let events = decode(encoding_header.as_deref(), body).and_then(|body| {
-                bytes_received.emit(ByteSize(body.len()));
-                decode_log_body(body, log_namespace, &events_received)
-            });
-
-            handle_request(
-                events,
-                acknowledgements,
-                out.clone(),
-                super::LOGS,
-                ExportLogsServiceResponse::default(),
-            )
-        })
+        .and_then(
+            move |encoding: Encoding, encoding_header: Option<String>, body: Bytes| {
+                let events = decode(encoding_header.as_deref(), body).and_then(|body| {
+                    bytes_received.emit(ByteSize(body.len()));
+                    decode_log_body(encoding, body, log_namespace, &events_received)
+                });
+
+                handle_request(
+                    events,
+                    acknowledgements,
+                    out.clone(),
+                    super::LOGS,
+                    reply(
+                        encoding,
+                        ExportLogsServiceResponse::default(),
+                        json::EXPORT_LOGS_RESPONSE,
+                    ),
+                )
+            },
+        )
+        .boxed()
+}
+
+fn build_warp_metrics_filter(
+    acknowledgements: bool,
+    out: SourceSender,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+) -> BoxedFilter<(Response,)> {
+    warp::post()
+        .and(warp::path!("v1" / "metrics"))
+        .and(content_type())
+        .and(warp::header::optional::<String>("content-encoding"))
+        .and(warp::body::bytes())
+        .and_then(
+            move |encoding: Encoding, encoding_header: Option<String>, body: Bytes| {
+                let events = decode(encoding_header.as_deref(), body).and_then(|body| {
+                    bytes_received.emit(ByteSize(body.len()));
+                    decode_metrics_body(encoding, body, &events_received)
+                });
+
+                handle_request(
+                    events,
+                    acknowledgements,
+                    out.clone(),
+                    super::METRICS,
+                    reply(
+                        encoding,
+                        ExportMetricsServiceResponse::default(),
+                        json::EXPORT_METRICS_RESPONSE,
+                    ),
+                )
+            },
+        )
         .boxed()
 }
 
@@ -143,39 +238,47 @@ fn build_warp_trace_filter(
 ) -> BoxedFilter<(Response,)> {
     warp::post()
         .and(warp::path!("v1" / "traces"))
-        .and(warp::header::exact_ignore_case(
-            "content-type",
-            "application/x-protobuf",
-        ))
+        .and(content_type())
         .and(warp::header::optional::<String>("content-encoding"))
         .and(warp::body::bytes())
-        .and_then(move |encoding_header: Option<String>, body: Bytes| {
-            let events = decode(encoding_header.as_deref(), body).and_then(|body| {
-                bytes_received.emit(ByteSize(body.len()));
-                decode_trace_body(body, &events_received)
-            });
-
-            handle_request(
-                events,
-                acknowledgements,
-                out.clone(),
-                super::TRACES,
-                ExportTraceServiceResponse::default(),
-            )
-        })
+        .and_then(
+            move |encoding: Encoding, encoding_header: Option<String>, body: Bytes| {
+                let events = decode(encoding_header.as_deref(), body).and_then(|body| {
+                    bytes_received.emit(ByteSize(body.len()));
+                    decode_trace_body(encoding, body, &events_received)
+                });
+
+                handle_request(
+                    events,
+                    acknowledgements,
+                    out.clone(),
+                    super::TRACES,
+                    reply(
+                        encoding,
+                        ExportTraceServiceResponse::default(),
+                        json::EXPORT_TRACE_RESPONSE,
+                    ),
+                )
+            },
+        )
         .boxed()
 }
 
+/// Builds the response to a request, with the encoding of the request.
+fn reply<M: Message>(encoding: Encoding, response: M, message_name: &str) -> Response {
+    match encoding {
+        Encoding::Protobuf => protobuf(response).into_response(),
+        Encoding::Json => json_reply(&response, message_name).into_response(),
+    }
+}
+
 fn decode_trace_body(
+    encoding: Encoding,
     body: Bytes,
     events_received: &Registered<EventsReceived>,
 ) -> Result<Vec<Event>, ErrorMessage> {
-    let request = ExportTraceServiceRequest::decode(body).map_err(|error| {
-        ErrorMessage::new(
-            StatusCode::BAD_REQUEST,
-            format!("Could not decode request: {}", error),
-        )
-    })?;
+    let request: ExportTraceServiceRequest =
+        decode_request(encoding, body, json::EXPORT_TRACE_REQUEST)?;
 
     let events: Vec<Event> = request
         .resource_spans
@@ -191,17 +294,36 @@ fn decode_trace_body(
     Ok(events)
 }
 
+fn decode_metrics_body(
+    encoding: Encoding,
+    body: Bytes,
+    events_received: &Registered<EventsReceived>,
+) -> Result<Vec<Event>, ErrorMessage> {
+    let request: ExportMetricsServiceRequest =
+        decode_request(encoding, body, json::EXPORT_METRICS_REQUEST)?;
+
+    let events: Vec<Event> = request
+        .resource_metrics
+        .into_iter()
+        .flat_map(|v| v.into_event_iter())
+        .collect();
+
+    events_received.emit(CountByteSize(
+        events.len(),
+        events.estimated_json_encoded_size_of(),
+    ));
+
+    Ok(events)
+}
+
 fn decode_log_body(
+    encoding: Encoding,
     body: Bytes,
     log_namespace: LogNamespace,
     events_received: &Registered<EventsReceived>,
 ) -> Result<Vec<Event>, ErrorMessage> {
-    let request = ExportLogsServiceRequest::decode(body).map_err(|error| {
-        ErrorMessage::new(
-            StatusCode::BAD_REQUEST,
-            format!("Could not decode request: {}", error),
-        )
-    })?;
+    let request: ExportLogsServiceRequest =
+        decode_request(encoding, body, json::EXPORT_LOGS_REQUEST)?;
 
     let events: Vec<Event> = request
         .resource_logs
@@ -222,7 +344,7 @@ async fn handle_request(
     acknowledgements: bool,
     mut out: SourceSender,
     output: &str,
-    resp: impl Message,
+    resp: Response,
 ) -> Result<Response, Rejection> {
     match events {
         Ok(mut events) => {
@@ -235,9 +357,9 @@ async fn handle_request(
             })?;
 
             match receiver {
-                None => Ok(protobuf(resp).into_response()),
+                None => Ok(resp),
                 Some(receiver) => match receiver.await {
-                    BatchStatus::Delivered => Ok(protobuf(resp).into_response()),
+                    BatchStatus::Delivered => Ok(resp),
                     BatchStatus::Errored => Err(warp::reject::custom(Status {
                         code: 2, // UNKNOWN - OTLP doesn't require use of status.code, but we can't encode a None here
                         message: "Error delivering contents to sink".into(),
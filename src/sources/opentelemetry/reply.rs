@@ -1,5 +1,5 @@
 use bytes::BytesMut;
-use http::{header::CONTENT_TYPE, HeaderValue};
+use http::{header::CONTENT_TYPE, HeaderValue, StatusCode};
 use prost::Message;
 use warp::{reply::Response, Reply};
 
@@ -20,6 +20,20 @@ where
     }
 }
 
+/// If a type fails to be encoded as JSON, the error is logged at the `error`
+/// level, and the returned `impl Reply` will be an empty `500 Internal Server
+/// Error` response.
+pub fn json<T>(val: &T, message_name: &str) -> Json
+where
+    T: Message,
+{
+    Json {
+        inner: vector_lib::opentelemetry::json::encode(val, message_name).map_err(|err| {
+            error!("Failed to encode value: {}", err);
+        }),
+    }
+}
+
 /// A Protobuf formatted reply.
 #[allow(missing_debug_implementations)]
 pub struct Protobuf {
@@ -53,3 +67,28 @@ impl Reply for Protobuf {
         }
     }
 }
+
+/// A JSON formatted reply, to requests using the OTLP JSON encoding.
+#[allow(missing_debug_implementations)]
+pub struct Json {
+    inner: Result<Vec<u8>, ()>,
+}
+
+impl Reply for Json {
+    #[inline]
+    fn into_response(self) -> Response {
+        match self.inner {
+            Ok(body) => {
+                let mut res = Response::new(body.into());
+                res.headers_mut()
+                    .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                res
+            }
+            Err(()) => {
+                let mut res = Response::new(Default::default());
+                *res.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+                res
+            }
+        }
+    }
+}
@@ -8,18 +8,26 @@ use tonic::Request;
 use vector_lib::config::LogNamespace;
 use vector_lib::lookup::path;
 use vector_lib::opentelemetry::proto::{
-    collector::logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+    collector::{
+        logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+        metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+    },
     common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+    metrics::v1::{
+        metric::Data, number_data_point, AggregationTemporality, Histogram, HistogramDataPoint,
+        Metric as OtelMetric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+    },
     resource::v1::Resource as OtelResource,
 };
-use vrl::value;
+use vector_lib::{event::metric::MetricKind, metric_tags};
+use vrl::{event_path, value};
 
 use crate::config::OutputId;
 use crate::{
     config::{SourceConfig, SourceContext},
-    event::{into_event_stream, Event, EventStatus, LogEvent, ObjectMap, Value},
-    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS},
+    event::{into_event_stream, Event, EventStatus, LogEvent, MetricValue, ObjectMap, Value},
+    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS, METRICS, TRACES},
     test_util::{
         self,
         components::{assert_source_compliance, SOURCE_TAGS},
@@ -334,6 +342,201 @@ async fn receive_grpc_logs_legacy_namespace() {
     .await;
 }
 
+#[tokio::test]
+async fn receive_grpc_metrics() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let grpc_addr = next_addr();
+        let http_addr = next_addr();
+
+        let source = OpentelemetryConfig {
+            grpc: GrpcConfig {
+                address: grpc_addr,
+                tls: Default::default(),
+            },
+            http: HttpConfig {
+                address: http_addr,
+                tls: Default::default(),
+                keepalive: Default::default(),
+            },
+            acknowledgements: Default::default(),
+            log_namespace: Default::default(),
+        };
+
+        let (sender, metrics_output, _) = new_source(EventStatus::Delivered, METRICS.to_string());
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(grpc_addr).await;
+
+        let mut client = MetricsServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        let attributes = vec![KeyValue {
+            key: "host".into(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue("localhost".into())),
+            }),
+        }];
+        let req = Request::new(ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(OtelResource {
+                    attributes: vec![KeyValue {
+                        key: "service.name".into(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue("api".into())),
+                        }),
+                    }],
+                    dropped_attributes_count: 0,
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(InstrumentationScope {
+                        name: "some.scope.name".into(),
+                        ..Default::default()
+                    }),
+                    metrics: vec![
+                        OtelMetric {
+                            name: "requests".into(),
+                            data: Some(Data::Sum(Sum {
+                                data_points: vec![NumberDataPoint {
+                                    attributes: attributes.clone(),
+                                    time_unix_nano: 1,
+                                    value: Some(number_data_point::Value::AsInt(3)),
+                                    ..Default::default()
+                                }],
+                                aggregation_temporality: AggregationTemporality::Delta as i32,
+                                is_monotonic: true,
+                            })),
+                            ..Default::default()
+                        },
+                        OtelMetric {
+                            name: "latency".into(),
+                            data: Some(Data::Histogram(Histogram {
+                                data_points: vec![HistogramDataPoint {
+                                    attributes,
+                                    time_unix_nano: 1,
+                                    count: 3,
+                                    sum: Some(1.5),
+                                    bucket_counts: vec![1, 2, 0],
+                                    explicit_bounds: vec![0.1, 1.0],
+                                    ..Default::default()
+                                }],
+                                aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                            })),
+                            ..Default::default()
+                        },
+                    ],
+                    schema_url: "v1".into(),
+                }],
+                schema_url: "v1".into(),
+            }],
+        });
+        _ = client.export(req).await;
+        let output = test_util::collect_ready(metrics_output).await;
+        assert_eq!(output.len(), 2);
+
+        let tags = metric_tags!(
+            "resource.service.name" => "api",
+            "scope.name" => "some.scope.name",
+            "host" => "localhost",
+        );
+        let requests = output[0].as_metric();
+        assert_eq!(requests.name(), "requests");
+        assert_eq!(requests.kind(), MetricKind::Incremental);
+        assert_eq!(requests.value(), &MetricValue::Counter { value: 3.0 });
+        assert_eq!(requests.tags(), Some(&tags));
+        assert_eq!(requests.timestamp(), Some(Utc.timestamp_nanos(1)));
+
+        let latency = output[1].as_metric();
+        assert_eq!(latency.name(), "latency");
+        assert_eq!(latency.kind(), MetricKind::Absolute);
+        assert_eq!(
+            latency.value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vector_lib::buckets![0.1 => 1, 1.0 => 2, f64::INFINITY => 0],
+                count: 3,
+                sum: 1.5,
+            }
+        );
+        assert_eq!(latency.tags(), Some(&tags));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn receive_http_json_traces() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let grpc_addr = next_addr();
+        let http_addr = next_addr();
+
+        let source = OpentelemetryConfig {
+            grpc: GrpcConfig {
+                address: grpc_addr,
+                tls: Default::default(),
+            },
+            http: HttpConfig {
+                address: http_addr,
+                tls: Default::default(),
+                keepalive: Default::default(),
+            },
+            acknowledgements: Default::default(),
+            log_namespace: Default::default(),
+        };
+
+        let (sender, traces_output, _) = new_source(EventStatus::Delivered, TRACES.to_string());
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(http_addr).await;
+
+        let response = reqwest::Client::new()
+            .post(format!("http://{}/v1/traces", http_addr))
+            .header("content-type", "application/json")
+            .body(
+                r#"{
+                    "resourceSpans": [{
+                        "scopeSpans": [{
+                            "scope": {"name": "some.scope.name"},
+                            "spans": [{
+                                "traceId": "4ac52aadf321c2e531db005df08792f5",
+                                "spanId": "0b9e4bda2a55530d",
+                                "name": "request",
+                                "startTimeUnixNano": "1",
+                                "endTimeUnixNano": "2"
+                            }]
+                        }]
+                    }]
+                }"#,
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["content-type"], "application/json");
+        assert_eq!(response.text().await.unwrap(), "{}");
+
+        let mut output = test_util::collect_ready(traces_output).await;
+        assert_eq!(output.len(), 1);
+        let trace = output.pop().unwrap().into_trace();
+        assert_eq!(
+            trace.get(event_path!("trace_id")),
+            Some(&value!("4ac52aadf321c2e531db005df08792f5"))
+        );
+        assert_eq!(
+            trace.get(event_path!("span_id")),
+            Some(&value!("0b9e4bda2a55530d"))
+        );
+        assert_eq!(
+            trace.get(event_path!("scope", "name")),
+            Some(&value!("some.scope.name"))
+        );
+    })
+    .await;
+}
+
 pub(super) fn new_source(
     status: EventStatus,
     event_name: String,
@@ -0,0 +1,154 @@
+//! The `plugin` source. See [PluginSourceConfig].
+use std::net::SocketAddr;
+
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+
+use crate::{
+    config::{
+        GenerateConfig, Resource, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
+        SourceOutput,
+    },
+    plugin::PluginProcessConfig,
+    serde::bool_or_struct,
+    sources::{vector::VectorConfig, Source},
+};
+
+/// Configuration for the `plugin` source.
+#[configurable_component(source(
+    "plugin",
+    "Collect observability data from an external plugin process."
+))]
+#[derive(Clone, Debug)]
+pub struct PluginSourceConfig {
+    #[serde(flatten)]
+    #[configurable(derived)]
+    process: PluginProcessConfig,
+
+    /// The socket address to listen for the events sent by the plugin on.
+    ///
+    /// The plugin is given this address in the `VECTOR_PLUGIN_ADDRESS` environment variable. It
+    /// _must_ include a port.
+    #[configurable(metadata(docs::examples = "127.0.0.1:6100"))]
+    address: SocketAddr,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[serde(default)]
+    #[configurable(metadata(docs::hidden))]
+    log_namespace: Option<bool>,
+}
+
+impl GenerateConfig for PluginSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            process: PluginProcessConfig::new(vec![
+                "/usr/local/bin/vector-plugin-example".to_owned()
+            ]),
+            address: "127.0.0.1:6100".parse().unwrap(),
+            acknowledgements: Default::default(),
+            log_namespace: None,
+        })
+        .unwrap()
+    }
+}
+
+impl PluginSourceConfig {
+    /// The configuration of the `vector` source receiving the events of the plugin.
+    fn vector_config(&self) -> VectorConfig {
+        let mut config = VectorConfig::from_address(self.address);
+        config.acknowledgements = self.acknowledgements;
+        config.log_namespace = self.log_namespace;
+        config
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "plugin")]
+impl SourceConfig for PluginSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
+        self.process.validate()?;
+
+        let source = self.vector_config().build_source(cx, Self::NAME)?;
+        let process = self.process.spawn(self.address.to_string());
+
+        Ok(Box::pin(async move {
+            let result = source.await;
+            drop(process);
+            result
+        }))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        self.vector_config().outputs(global_log_namespace)
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<super::PluginSourceConfig>();
+    }
+}
+
+#[cfg(all(test, unix, feature = "sinks-vector"))]
+mod tests {
+    use vector_lib::assert_event_data_eq;
+    use vector_lib::config::log_schema;
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig as _, SinkContext},
+        sinks::vector::VectorConfig as VectorSinkConfig,
+        test_util, SourceSender,
+    };
+
+    #[tokio::test]
+    async fn receives_events_from_plugin() {
+        // The plugin itself only blocks, the events being sent by a `vector` sink standing in for
+        // the plugin.
+        let addr = test_util::next_addr();
+        let config: PluginSourceConfig = toml::from_str(&format!(
+            r#"
+            command = ["sleep", "60"]
+            address = "{addr}"
+            "#
+        ))
+        .unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        let server = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(addr).await;
+
+        let sink: VectorSinkConfig = toml::from_str(&format!(r#"address = "{addr}""#)).unwrap();
+        let (sink, _) = sink.build(SinkContext::default()).await.unwrap();
+        let (mut events, stream) = test_util::random_events_with_stream(100, 10, None);
+        sink.run(stream).await.unwrap();
+
+        for event in &mut events {
+            event.as_mut_log().insert(
+                log_schema().source_type_key_target_path().unwrap(),
+                "plugin",
+            );
+        }
+
+        let output = test_util::collect_ready(rx).await;
+        assert_event_data_eq!(events, output);
+    }
+}
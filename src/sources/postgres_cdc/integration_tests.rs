@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use tokio_postgres::{Client, NoTls};
+
+use super::*;
+use crate::{
+    event::Event,
+    test_util::{
+        components::{run_and_assert_source_compliance, SOURCE_TAGS},
+        random_string, trace_init,
+    },
+};
+
+fn pg_host() -> String {
+    std::env::var("PG_HOST").unwrap_or_else(|_| "localhost".into())
+}
+
+fn pg_url() -> String {
+    std::env::var("PG_URL")
+        .unwrap_or_else(|_| format!("postgres://vector:vector@{}/postgres", pg_host()))
+}
+
+async fn connect() -> Client {
+    let (client, connection) = tokio_postgres::connect(&pg_url(), NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client
+}
+
+fn config(name: &str) -> PostgresCdcConfig {
+    toml::from_str(&format!(
+        r#"
+            endpoint = "{}"
+            slot_name = "{name}"
+            publications = ["{name}"]
+            poll_interval_secs = 1
+        "#,
+        pg_url()
+    ))
+    .unwrap()
+}
+
+/// Creates a table, a publication of it, and a replication slot, all sharing the same name.
+async fn prepare(client: &Client) -> String {
+    let name = format!("test_{}", random_string(10).to_lowercase());
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE {name} (id INTEGER PRIMARY KEY, name TEXT, active BOOLEAN);
+             ALTER TABLE {name} REPLICA IDENTITY FULL;
+             CREATE PUBLICATION {name} FOR TABLE {name};
+             SELECT pg_create_logical_replication_slot('{name}', 'pgoutput');"
+        ))
+        .await
+        .unwrap();
+    name
+}
+
+#[tokio::test]
+async fn captures_changes() {
+    trace_init();
+
+    let client = connect().await;
+    let name = prepare(&client).await;
+    client
+        .batch_execute(&format!(
+            "INSERT INTO {name} VALUES (1, 'alice', true);
+             UPDATE {name} SET name = 'alicia' WHERE id = 1;
+             DELETE FROM {name} WHERE id = 1;"
+        ))
+        .await
+        .unwrap();
+
+    let events =
+        run_and_assert_source_compliance(config(&name), Duration::from_secs(3), &SOURCE_TAGS).await;
+
+    let logs = events.iter().map(Event::as_log).collect::<Vec<_>>();
+    assert_eq!(logs.len(), 3);
+
+    assert_eq!(logs[0]["operation"], "insert".into());
+    assert_eq!(logs[0]["table"], name.as_str().into());
+    assert_eq!(logs[0]["after.id"], 1.into());
+    assert_eq!(logs[0]["after.name"], "alice".into());
+    assert_eq!(logs[0]["after.active"], true.into());
+
+    assert_eq!(logs[1]["operation"], "update".into());
+    assert_eq!(logs[1]["before.name"], "alice".into());
+    assert_eq!(logs[1]["after.name"], "alicia".into());
+
+    assert_eq!(logs[2]["operation"], "delete".into());
+    assert_eq!(logs[2]["before.name"], "alicia".into());
+    assert!(logs[2].get("after").is_none());
+
+    // The slot was advanced past the delivered changes.
+    let pending = client
+        .query(
+            "SELECT 1 FROM pg_logical_slot_peek_binary_changes($1, NULL, NULL, \
+             'proto_version', '1', 'publication_names', $2)",
+            &[&name, &name],
+        )
+        .await
+        .unwrap();
+    assert!(pending.is_empty());
+}
+
+#[tokio::test]
+async fn creates_slot() {
+    trace_init();
+
+    let client = connect().await;
+    let name = format!("test_{}", random_string(10).to_lowercase());
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE {name} (id INTEGER PRIMARY KEY);
+             CREATE PUBLICATION {name} FOR TABLE {name};"
+        ))
+        .await
+        .unwrap();
+
+    let mut config = config(&name);
+    config.create_slot = true;
+    let (tx, _rx) = SourceSender::new_test();
+    let source = config
+        .build(SourceContext::new_test(tx, None))
+        .await
+        .unwrap();
+    tokio::spawn(source);
+    time::sleep(Duration::from_secs(2)).await;
+
+    let slots = client.query(SLOT_EXISTS, &[&name]).await.unwrap();
+    assert_eq!(slots.len(), 1);
+}
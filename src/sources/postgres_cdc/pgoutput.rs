@@ -0,0 +1,617 @@
+//! Decoding of the messages of the [`pgoutput`][pgoutput] logical decoding plugin.
+//!
+//! [pgoutput]: https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html
+
+use std::collections::HashMap;
+
+use bytes::{Buf, Bytes};
+use chrono::{DateTime, TimeZone, Utc};
+use snafu::Snafu;
+use vrl::value::{ObjectMap, Value};
+
+/// Seconds between the Unix epoch and the PostgreSQL epoch, `2000-01-01T00:00:00Z`.
+const POSTGRES_EPOCH_SECS: i64 = 946_684_800;
+
+// Object identifiers of the built-in types that are converted from their text representation.
+const BOOL_OID: u32 = 16;
+const INT8_OID: u32 = 20;
+const INT2_OID: u32 = 21;
+const INT4_OID: u32 = 23;
+const OID_OID: u32 = 26;
+const JSON_OID: u32 = 114;
+const FLOAT4_OID: u32 = 700;
+const FLOAT8_OID: u32 = 701;
+const JSONB_OID: u32 = 3802;
+
+#[derive(Debug, Snafu)]
+pub enum DecodeError {
+    #[snafu(display("Message is truncated"))]
+    Truncated,
+    #[snafu(display("String is not terminated"))]
+    UnterminatedString,
+    #[snafu(display("Unexpected {what} {tag:?}"))]
+    UnexpectedTag { what: &'static str, tag: char },
+    #[snafu(display("Change to relation {id} received before its description"))]
+    UnknownRelation { id: u32 },
+    #[snafu(display("Change received outside of a transaction"))]
+    NoTransaction,
+}
+
+/// The kind of change made to a row.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operation {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl Operation {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Insert => "insert",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// A change made to a row of a table.
+#[derive(Debug, PartialEq)]
+pub struct Change {
+    pub operation: Operation,
+    pub schema: String,
+    pub table: String,
+    /// The previous values of the row, when the replica identity of the table includes them.
+    ///
+    /// With the default replica identity, this is only set when the primary key changed, and only
+    /// holds its columns.
+    pub before: Option<ObjectMap>,
+    pub after: Option<ObjectMap>,
+    pub transaction_id: u32,
+    pub commit_timestamp: DateTime<Utc>,
+}
+
+/// The outcome of decoding a single message.
+#[derive(Debug, PartialEq)]
+pub enum Decoded {
+    /// A row was changed.
+    Change(Change),
+    /// A transaction was committed, so the changes up to it are complete.
+    Commit,
+    /// The message only updated the state of the decoder, or isn't relevant.
+    Nothing,
+}
+
+#[derive(Debug)]
+struct Column {
+    name: String,
+    type_id: u32,
+}
+
+#[derive(Debug)]
+struct Relation {
+    schema: String,
+    table: String,
+    columns: Vec<Column>,
+}
+
+#[derive(Debug)]
+struct Transaction {
+    id: u32,
+    commit_timestamp: DateTime<Utc>,
+}
+
+/// Decodes a stream of messages into row changes.
+///
+/// The descriptions of the relations are kept across transactions, as the server only sends them
+/// before the first change to each relation, and after the relation was altered.
+#[derive(Debug, Default)]
+pub struct Decoder {
+    relations: HashMap<u32, Relation>,
+    transaction: Option<Transaction>,
+}
+
+impl Decoder {
+    pub fn decode(&mut self, mut message: Bytes) -> Result<Decoded, DecodeError> {
+        let tag = get_u8(&mut message)?;
+        match tag {
+            b'B' => {
+                // The LSN of the commit record.
+                get_u64(&mut message)?;
+                let commit_timestamp = timestamp(get_i64(&mut message)?);
+                let id = get_u32(&mut message)?;
+                self.transaction = Some(Transaction {
+                    id,
+                    commit_timestamp,
+                });
+                Ok(Decoded::Nothing)
+            }
+            b'C' => {
+                self.transaction = None;
+                Ok(Decoded::Commit)
+            }
+            b'R' => {
+                let id = get_u32(&mut message)?;
+                let schema = get_string(&mut message)?;
+                let table = get_string(&mut message)?;
+                // The replica identity setting.
+                get_u8(&mut message)?;
+                let count = get_u16(&mut message)?;
+                let columns = (0..count)
+                    .map(|_| {
+                        // The flags, which only mark the columns of the key.
+                        get_u8(&mut message)?;
+                        let name = get_string(&mut message)?;
+                        let type_id = get_u32(&mut message)?;
+                        // The type modifier.
+                        get_u32(&mut message)?;
+                        Ok(Column { name, type_id })
+                    })
+                    .collect::<Result<_, _>>()?;
+                self.relations.insert(
+                    id,
+                    Relation {
+                        schema,
+                        table,
+                        columns,
+                    },
+                );
+                Ok(Decoded::Nothing)
+            }
+            b'I' => {
+                let relation = get_u32(&mut message)?;
+                expect_tag(&mut message, b'N')?;
+                let after = self.tuple(relation, &mut message)?;
+                self.change(Operation::Insert, relation, None, Some(after))
+            }
+            b'U' => {
+                let relation = get_u32(&mut message)?;
+                let (before, after) = match get_u8(&mut message)? {
+                    b'K' | b'O' => {
+                        let before = self.tuple(relation, &mut message)?;
+                        expect_tag(&mut message, b'N')?;
+                        (Some(before), self.tuple(relation, &mut message)?)
+                    }
+                    b'N' => (None, self.tuple(relation, &mut message)?),
+                    tag => {
+                        return Err(DecodeError::UnexpectedTag {
+                            what: "tuple kind",
+                            tag: tag.into(),
+                        })
+                    }
+                };
+                self.change(Operation::Update, relation, before, Some(after))
+            }
+            b'D' => {
+                let relation = get_u32(&mut message)?;
+                match get_u8(&mut message)? {
+                    b'K' | b'O' => {}
+                    tag => {
+                        return Err(DecodeError::UnexpectedTag {
+                            what: "tuple kind",
+                            tag: tag.into(),
+                        })
+                    }
+                }
+                let before = self.tuple(relation, &mut message)?;
+                self.change(Operation::Delete, relation, Some(before), None)
+            }
+            // Origins, types, truncations, and logical decoding messages.
+            _ => Ok(Decoded::Nothing),
+        }
+    }
+
+    fn change(
+        &self,
+        operation: Operation,
+        relation: u32,
+        before: Option<ObjectMap>,
+        after: Option<ObjectMap>,
+    ) -> Result<Decoded, DecodeError> {
+        let transaction = self
+            .transaction
+            .as_ref()
+            .ok_or(DecodeError::NoTransaction)?;
+        let relation = self.relation(relation)?;
+        Ok(Decoded::Change(Change {
+            operation,
+            schema: relation.schema.clone(),
+            table: relation.table.clone(),
+            before,
+            after,
+            transaction_id: transaction.id,
+            commit_timestamp: transaction.commit_timestamp,
+        }))
+    }
+
+    fn relation(&self, id: u32) -> Result<&Relation, DecodeError> {
+        self.relations
+            .get(&id)
+            .ok_or(DecodeError::UnknownRelation { id })
+    }
+
+    /// Decodes the values of a row, leaving out the unchanged TOASTed values, which aren't sent.
+    fn tuple(&self, relation: u32, message: &mut Bytes) -> Result<ObjectMap, DecodeError> {
+        let relation = self.relation(relation)?;
+        let count = get_u16(message)?;
+        let mut values = ObjectMap::new();
+        for index in 0..usize::from(count) {
+            let value = match get_u8(message)? {
+                b'n' => Some(Value::Null),
+                b'u' => None,
+                kind @ (b't' | b'b') => {
+                    let len = get_u32(message)? as usize;
+                    if message.remaining() < len {
+                        return Err(DecodeError::Truncated);
+                    }
+                    let data = message.split_to(len);
+                    let type_id = relation
+                        .columns
+                        .get(index)
+                        .map_or(0, |column| column.type_id);
+                    Some(if kind == b't' {
+                        text_value(type_id, data)
+                    } else {
+                        Value::Bytes(data)
+                    })
+                }
+                tag => {
+                    return Err(DecodeError::UnexpectedTag {
+                        what: "column kind",
+                        tag: tag.into(),
+                    })
+                }
+            };
+            if let (Some(value), Some(column)) = (value, relation.columns.get(index)) {
+                values.insert(column.name.as_str().into(), value);
+            }
+        }
+        Ok(values)
+    }
+}
+
+/// Converts the text representation of a value of the built-in boolean, numeric, and JSON types,
+/// and keeps the others as strings.
+fn text_value(type_id: u32, data: Bytes) -> Value {
+    let text = String::from_utf8_lossy(&data);
+    let converted = match type_id {
+        BOOL_OID => match text.as_ref() {
+            "t" => Some(Value::Boolean(true)),
+            "f" => Some(Value::Boolean(false)),
+            _ => None,
+        },
+        INT2_OID | INT4_OID | INT8_OID | OID_OID => text.parse::<i64>().ok().map(Value::from),
+        FLOAT4_OID | FLOAT8_OID => text
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .map(Value::from_f64_or_zero),
+        JSON_OID | JSONB_OID => serde_json::from_slice::<serde_json::Value>(&data)
+            .ok()
+            .map(Value::from),
+        _ => None,
+    };
+    converted.unwrap_or(Value::Bytes(data))
+}
+
+fn timestamp(micros: i64) -> DateTime<Utc> {
+    Utc.timestamp_opt(POSTGRES_EPOCH_SECS, 0).unwrap() + chrono::Duration::microseconds(micros)
+}
+
+fn expect_tag(message: &mut Bytes, expected: u8) -> Result<(), DecodeError> {
+    match get_u8(message)? {
+        tag if tag == expected => Ok(()),
+        tag => Err(DecodeError::UnexpectedTag {
+            what: "tuple kind",
+            tag: tag.into(),
+        }),
+    }
+}
+
+fn get_u8(message: &mut Bytes) -> Result<u8, DecodeError> {
+    if message.remaining() < 1 {
+        return Err(DecodeError::Truncated);
+    }
+    Ok(message.get_u8())
+}
+
+fn get_u16(message: &mut Bytes) -> Result<u16, DecodeError> {
+    if message.remaining() < 2 {
+        return Err(DecodeError::Truncated);
+    }
+    Ok(message.get_u16())
+}
+
+fn get_u32(message: &mut Bytes) -> Result<u32, DecodeError> {
+    if message.remaining() < 4 {
+        return Err(DecodeError::Truncated);
+    }
+    Ok(message.get_u32())
+}
+
+fn get_u64(message: &mut Bytes) -> Result<u64, DecodeError> {
+    if message.remaining() < 8 {
+        return Err(DecodeError::Truncated);
+    }
+    Ok(message.get_u64())
+}
+
+fn get_i64(message: &mut Bytes) -> Result<i64, DecodeError> {
+    if message.remaining() < 8 {
+        return Err(DecodeError::Truncated);
+    }
+    Ok(message.get_i64())
+}
+
+fn get_string(message: &mut Bytes) -> Result<String, DecodeError> {
+    let end = message
+        .iter()
+        .position(|byte| *byte == 0)
+        .ok_or(DecodeError::UnterminatedString)?;
+    let string = String::from_utf8_lossy(&message[..end]).into_owned();
+    message.advance(end + 1);
+    Ok(string)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use bytes::{BufMut, BytesMut};
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    pub(in crate::sources::postgres_cdc) fn begin(xid: u32, micros: i64) -> Bytes {
+        let mut message = BytesMut::new();
+        message.put_u8(b'B');
+        message.put_u64(0x16B_3748);
+        message.put_i64(micros);
+        message.put_u32(xid);
+        message.freeze()
+    }
+
+    pub(in crate::sources::postgres_cdc) fn commit() -> Bytes {
+        let mut message = BytesMut::new();
+        message.put_u8(b'C');
+        message.put_u8(0);
+        message.put_u64(0x16B_3748);
+        message.put_u64(0x16B_3778);
+        message.put_i64(0);
+        message.freeze()
+    }
+
+    pub(in crate::sources::postgres_cdc) fn relation(id: u32) -> Bytes {
+        let mut message = BytesMut::new();
+        message.put_u8(b'R');
+        message.put_u32(id);
+        message.put_slice(b"public\0users\0");
+        message.put_u8(b'd');
+        message.put_u16(4);
+        for (flags, name, type_id) in [
+            (1, "id", INT4_OID),
+            (0, "name", 25),
+            (0, "active", BOOL_OID),
+            (0, "settings", JSONB_OID),
+        ] {
+            message.put_u8(flags);
+            message.put_slice(name.as_bytes());
+            message.put_u8(0);
+            message.put_u32(type_id);
+            message.put_i32(-1);
+        }
+        message.freeze()
+    }
+
+    fn put_tuple(message: &mut BytesMut, columns: &[Option<&str>]) {
+        message.put_u16(columns.len() as u16);
+        for column in columns {
+            match column {
+                Some("<unchanged>") => message.put_u8(b'u'),
+                Some(text) => {
+                    message.put_u8(b't');
+                    message.put_u32(text.len() as u32);
+                    message.put_slice(text.as_bytes());
+                }
+                None => message.put_u8(b'n'),
+            }
+        }
+    }
+
+    pub(in crate::sources::postgres_cdc) fn insert(id: u32, columns: &[Option<&str>]) -> Bytes {
+        let mut message = BytesMut::new();
+        message.put_u8(b'I');
+        message.put_u32(id);
+        message.put_u8(b'N');
+        put_tuple(&mut message, columns);
+        message.freeze()
+    }
+
+    /// Builds an update message, which only includes the old tuple if `before` isn't empty.
+    fn update(id: u32, before: &[Option<&str>], after: &[Option<&str>]) -> Bytes {
+        let mut message = BytesMut::new();
+        message.put_u8(b'U');
+        message.put_u32(id);
+        if !before.is_empty() {
+            message.put_u8(b'O');
+            put_tuple(&mut message, before);
+        }
+        message.put_u8(b'N');
+        put_tuple(&mut message, after);
+        message.freeze()
+    }
+
+    fn delete(id: u32, before: &[Option<&str>]) -> Bytes {
+        let mut message = BytesMut::new();
+        message.put_u8(b'D');
+        message.put_u32(id);
+        message.put_u8(b'K');
+        put_tuple(&mut message, before);
+        message.freeze()
+    }
+
+    fn decode_all(messages: Vec<Bytes>) -> Vec<Decoded> {
+        let mut decoder = Decoder::default();
+        messages
+            .into_iter()
+            .map(|message| decoder.decode(message).unwrap())
+            .collect()
+    }
+
+    fn change(decoded: &Decoded) -> &Change {
+        match decoded {
+            Decoded::Change(change) => change,
+            other => panic!("expected a change, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decodes_insert() {
+        let decoded = decode_all(vec![
+            begin(742, 1_000_000),
+            relation(16384),
+            insert(
+                16384,
+                &[
+                    Some("1"),
+                    Some("alice"),
+                    Some("t"),
+                    Some(r#"{"theme": "dark"}"#),
+                ],
+            ),
+            commit(),
+        ]);
+
+        assert_eq!(decoded.len(), 4);
+        assert_eq!(decoded[0], Decoded::Nothing);
+        assert_eq!(decoded[1], Decoded::Nothing);
+        assert_eq!(
+            change(&decoded[2]),
+            &Change {
+                operation: Operation::Insert,
+                schema: "public".into(),
+                table: "users".into(),
+                before: None,
+                after: Some(ObjectMap::from([
+                    ("id".into(), Value::from(1)),
+                    ("name".into(), Value::from("alice")),
+                    ("active".into(), Value::Boolean(true)),
+                    (
+                        "settings".into(),
+                        Value::from(ObjectMap::from([("theme".into(), Value::from("dark"))]))
+                    ),
+                ])),
+                transaction_id: 742,
+                commit_timestamp: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 1).unwrap(),
+            }
+        );
+        assert_eq!(decoded[3], Decoded::Commit);
+    }
+
+    #[test]
+    fn decodes_update_with_old_tuple() {
+        let decoded = decode_all(vec![
+            begin(743, 0),
+            relation(16384),
+            update(
+                16384,
+                &[Some("1"), Some("alice"), Some("t"), None],
+                &[Some("1"), Some("alicia"), Some("f"), Some("<unchanged>")],
+            ),
+        ]);
+
+        let change = change(&decoded[2]);
+        assert_eq!(change.operation, Operation::Update);
+        assert_eq!(
+            change.before,
+            Some(ObjectMap::from([
+                ("id".into(), Value::from(1)),
+                ("name".into(), Value::from("alice")),
+                ("active".into(), Value::Boolean(true)),
+                ("settings".into(), Value::Null),
+            ]))
+        );
+        assert_eq!(
+            change.after,
+            Some(ObjectMap::from([
+                ("id".into(), Value::from(1)),
+                ("name".into(), Value::from("alicia")),
+                ("active".into(), Value::Boolean(false)),
+            ]))
+        );
+    }
+
+    #[test]
+    fn decodes_update_without_old_tuple() {
+        let decoded = decode_all(vec![
+            begin(743, 0),
+            relation(16384),
+            update(16384, &[], &[Some("1"), Some("bob"), Some("t"), None]),
+        ]);
+
+        let change = change(&decoded[2]);
+        assert_eq!(change.before, None);
+        assert!(change.after.is_some());
+    }
+
+    #[test]
+    fn decodes_delete() {
+        let decoded = decode_all(vec![
+            begin(744, 0),
+            relation(16384),
+            delete(16384, &[Some("1"), None, None, None]),
+        ]);
+
+        let change = change(&decoded[2]);
+        assert_eq!(change.operation, Operation::Delete);
+        assert_eq!(change.after, None);
+        assert_eq!(
+            change.before.as_ref().and_then(|before| before.get("id")),
+            Some(&Value::from(1))
+        );
+    }
+
+    #[test]
+    fn keeps_relations_across_transactions() {
+        let decoded = decode_all(vec![
+            begin(745, 0),
+            relation(16384),
+            commit(),
+            begin(746, 0),
+            insert(16384, &[Some("2"), Some("carol"), Some("f"), None]),
+        ]);
+
+        assert_eq!(change(&decoded[4]).transaction_id, 746);
+    }
+
+    #[test]
+    fn rejects_unknown_relation() {
+        let mut decoder = Decoder::default();
+        decoder.decode(begin(747, 0)).unwrap();
+        assert!(matches!(
+            decoder.decode(insert(1, &[Some("1")])),
+            Err(DecodeError::UnknownRelation { id: 1 })
+        ));
+    }
+
+    #[test]
+    fn rejects_truncated_message() {
+        let mut decoder = Decoder::default();
+        decoder.decode(begin(748, 0)).unwrap();
+        decoder.decode(relation(16384)).unwrap();
+        let message = insert(16384, &[Some("1"), Some("alice"), Some("t"), None]);
+        assert!(matches!(
+            decoder.decode(message.slice(..message.len() - 3)),
+            Err(DecodeError::Truncated)
+        ));
+    }
+
+    #[test]
+    fn keeps_unconverted_values_as_strings() {
+        assert_eq!(text_value(INT4_OID, "12a".into()), Value::from("12a"));
+        assert_eq!(text_value(FLOAT8_OID, "NaN".into()), Value::from("NaN"));
+        assert_eq!(
+            text_value(FLOAT8_OID, "1.5".into()),
+            Value::from_f64_or_zero(1.5)
+        );
+        assert_eq!(text_value(1700, "10.25".into()), Value::from("10.25"));
+    }
+}
@@ -3,20 +3,31 @@ use std::cmp::Ordering;
 use chrono::{DateTime, TimeZone, Utc};
 #[cfg(feature = "sources-prometheus-remote-write")]
 use vector_lib::prometheus::parser::proto;
-use vector_lib::prometheus::parser::{GroupKind, MetricGroup, ParserError};
+use vector_lib::prometheus::parser::{self, GroupKind, MetricGroup, ParserError};
 
 use crate::event::{
-    metric::{Bucket, Metric, MetricKind, MetricTags, MetricValue, Quantile},
+    metric::{Bucket, Exemplar, Metric, MetricKind, MetricTags, MetricValue, Quantile},
     Event,
 };
 
+fn timestamp_millis(timestamp: i64) -> Option<DateTime<Utc>> {
+    Utc.timestamp_opt(timestamp / 1000, (timestamp % 1000) as u32 * 1000000)
+        .latest()
+}
+
 fn utc_timestamp(timestamp: Option<i64>, default: DateTime<Utc>) -> DateTime<Utc> {
-    timestamp
-        .and_then(|timestamp| {
-            Utc.timestamp_opt(timestamp / 1000, (timestamp % 1000) as u32 * 1000000)
-                .latest()
+    timestamp.and_then(timestamp_millis).unwrap_or(default)
+}
+
+fn convert_exemplars(exemplars: Vec<parser::Exemplar>) -> Vec<Exemplar> {
+    exemplars
+        .into_iter()
+        .map(|exemplar| Exemplar {
+            labels: exemplar.labels,
+            value: exemplar.value,
+            timestamp: exemplar.timestamp.and_then(timestamp_millis),
         })
-        .unwrap_or(default)
+        .collect()
 }
 
 #[cfg(any(test, feature = "sources-prometheus-scrape"))]
@@ -126,6 +137,7 @@ fn reparse_groups(
                         )
                         .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
                         .with_tags(tags.as_option())
+                        .with_exemplars(convert_exemplars(metric.exemplars))
                         .into(),
                     );
                 }
@@ -154,6 +166,7 @@ fn reparse_groups(
                         )
                         .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
                         .with_tags(tags.as_option())
+                        .with_exemplars(convert_exemplars(metric.exemplars))
                         .into(),
                     );
                 }
@@ -741,6 +754,36 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_histogram_exemplars() {
+        let exp = r#"
+            # TYPE duration histogram
+            duration_bucket{le="1"} 2 1612411506789 # {trace_id="KOO5S4vxi0o"} 0.67 1612411506.789
+            duration_bucket{le="+Inf"} 3 1612411506789
+            duration_sum 4 1612411506789
+            duration_count 3 1612411506789
+            "#;
+
+        assert_event_data_eq!(
+            events_to_metrics(parse_text(exp)),
+            Ok(vec![Metric::new(
+                "duration",
+                MetricKind::Absolute,
+                MetricValue::AggregatedHistogram {
+                    buckets: vector_lib::buckets![1.0 => 2],
+                    count: 3,
+                    sum: 4.0,
+                },
+            )
+            .with_timestamp(Some(*TIMESTAMP))
+            .with_exemplars(vec![Exemplar {
+                labels: [("trace_id".to_owned(), "KOO5S4vxi0o".to_owned())].into(),
+                value: 0.67,
+                timestamp: Some(*TIMESTAMP),
+            }])]),
+        );
+    }
+
     #[test]
     fn test_histogram_backward_values() {
         let exp = r#"
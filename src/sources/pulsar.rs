@@ -2,6 +2,8 @@
 //! Accepts log events streamed from [`Apache Pulsar`][pulsar].
 //!
 //! [pulsar]: https://pulsar.apache.org/
+use std::path::PathBuf;
+
 use chrono::TimeZone;
 use futures_util::StreamExt;
 use pulsar::{
@@ -66,6 +68,10 @@ pub struct PulsarSourceConfig {
     #[configurable(metadata(docs::examples = "subscription_name"))]
     subscription_name: Option<String>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    subscription_type: SubscriptionType,
+
     /// The consumer's priority level.
     ///
     /// The broker follows descending priorities. For example, 0=max-priority, 1, 2,...
@@ -79,6 +85,9 @@ pub struct PulsarSourceConfig {
     #[configurable(derived)]
     auth: Option<AuthConfig>,
 
+    #[configurable(derived)]
+    tls: Option<TlsOptions>,
+
     #[configurable(derived)]
     dead_letter_queue_policy: Option<DeadLetterQueuePolicy>,
 
@@ -112,9 +121,10 @@ enum AuthConfig {
         /// Basic authentication name/username.
         ///
         /// This can be used either for basic authentication (username/password) or JWT authentication.
-        /// When used for JWT, the value should be `token`.
+        /// When used for JWT, the value should be `token`, which is the default.
         #[configurable(metadata(docs::examples = "${PULSAR_NAME}"))]
         #[configurable(metadata(docs::examples = "name123"))]
+        #[serde(default = "default_auth_name")]
         name: String,
 
         /// Basic authentication password/token.
@@ -133,6 +143,69 @@ enum AuthConfig {
     },
 }
 
+fn default_auth_name() -> String {
+    "token".to_string()
+}
+
+/// The type of subscription to consume topics with.
+///
+/// See the [Pulsar documentation][subscription_types] for how messages are dispatched to the
+/// consumers of each type.
+///
+/// [subscription_types]: https://pulsar.apache.org/docs/concepts-messaging/#subscription-types
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum SubscriptionType {
+    /// Only a single consumer is allowed to attach to the subscription.
+    Exclusive,
+
+    /// Multiple consumers can attach to the subscription, but only one of them receives messages
+    /// at a time, the others taking over if it disconnects.
+    Failover,
+
+    /// Messages are distributed across all consumers attached to the subscription.
+    #[default]
+    Shared,
+
+    /// Messages are distributed across all consumers attached to the subscription, with messages
+    /// sharing a key always dispatched to the same consumer.
+    KeyShared,
+}
+
+impl From<SubscriptionType> for SubType {
+    fn from(subscription_type: SubscriptionType) -> Self {
+        match subscription_type {
+            SubscriptionType::Exclusive => SubType::Exclusive,
+            SubscriptionType::Failover => SubType::Failover,
+            SubscriptionType::Shared => SubType::Shared,
+            SubscriptionType::KeyShared => SubType::KeyShared,
+        }
+    }
+}
+
+/// TLS options for connecting to brokers using the `pulsar+ssl` protocol.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct TlsOptions {
+    /// Absolute path to the certificate authority file, in PEM format, used to verify the broker
+    /// certificates.
+    #[configurable(metadata(docs::examples = "/etc/certs/ca.pem"))]
+    ca_file: Option<PathBuf>,
+
+    /// Enables certificate verification.
+    ///
+    /// Do NOT set this to `false` unless you understand the risks of not verifying the validity of
+    /// certificates.
+    verify_certificate: Option<bool>,
+
+    /// Enables hostname verification.
+    ///
+    /// If enabled, the hostname used to connect to the broker must match its TLS certificate.
+    verify_hostname: Option<bool>,
+}
+
 /// OAuth2-specific authentication configuration.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -264,12 +337,24 @@ impl PulsarSourceConfig {
             };
         }
 
+        if let Some(tls) = &self.tls {
+            if let Some(ca_file) = &tls.ca_file {
+                builder = builder.with_certificate_chain_file(ca_file)?;
+            }
+            if let Some(verify_certificate) = tls.verify_certificate {
+                builder = builder.with_allow_insecure_connection(!verify_certificate);
+            }
+            if let Some(verify_hostname) = tls.verify_hostname {
+                builder = builder.with_tls_hostname_verification_enabled(verify_hostname);
+            }
+        }
+
         let pulsar = builder.build().await?;
 
         let mut consumer_builder = pulsar
             .consumer()
             .with_topics(&self.topics)
-            .with_subscription_type(SubType::Shared)
+            .with_subscription_type(self.subscription_type.into())
             .with_options(pulsar::consumer::ConsumerOptions {
                 priority_level: self.priority_level,
                 ..Default::default()
@@ -508,12 +593,30 @@ async fn handle_ack(
 
 #[cfg(test)]
 mod tests {
-    use crate::sources::pulsar::PulsarSourceConfig;
+    use crate::sources::pulsar::{AuthConfig, PulsarSourceConfig, SubscriptionType};
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<PulsarSourceConfig>();
     }
+
+    #[test]
+    fn parses_subscription_type_and_token_auth() {
+        let config: PulsarSourceConfig = toml::from_str(
+            r#"
+            endpoint = "pulsar+ssl://127.0.0.1:6651"
+            topics = ["logs"]
+            subscription_type = "key_shared"
+            auth.token = "jwt"
+            tls.ca_file = "/etc/certs/ca.pem"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.subscription_type, SubscriptionType::KeyShared);
+        assert!(matches!(config.auth, Some(AuthConfig::Basic { name, .. }) if name == "token"));
+        assert!(config.tls.is_some());
+    }
 }
 
 #[cfg(feature = "pulsar-integration-tests")]
@@ -557,9 +660,11 @@ mod integration_tests {
             topics: vec![topic.clone()],
             consumer_name: None,
             subscription_name: None,
+            subscription_type: SubscriptionType::Shared,
             priority_level: None,
             batch_size: None,
             auth: None,
+            tls: None,
             dead_letter_queue_policy: None,
             framing: FramingConfig::Bytes,
             decoding: DeserializerConfig::Bytes,
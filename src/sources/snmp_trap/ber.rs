@@ -0,0 +1,264 @@
+//! A reader for the subset of the Basic Encoding Rules (BER) of ASN.1 used by SNMP messages.
+
+use snafu::Snafu;
+
+pub const INTEGER: u8 = 0x02;
+pub const OCTET_STRING: u8 = 0x04;
+pub const NULL: u8 = 0x05;
+pub const OBJECT_IDENTIFIER: u8 = 0x06;
+pub const SEQUENCE: u8 = 0x30;
+pub const IP_ADDRESS: u8 = 0x40;
+pub const COUNTER32: u8 = 0x41;
+pub const GAUGE32: u8 = 0x42;
+pub const TIME_TICKS: u8 = 0x43;
+pub const OPAQUE: u8 = 0x44;
+pub const COUNTER64: u8 = 0x46;
+pub const NO_SUCH_OBJECT: u8 = 0x80;
+pub const NO_SUCH_INSTANCE: u8 = 0x81;
+pub const END_OF_MIB_VIEW: u8 = 0x82;
+
+#[derive(Debug, PartialEq, Eq, Snafu)]
+pub enum BerError {
+    #[snafu(display("Unexpected end of message"))]
+    Truncated,
+    #[snafu(display("Expected tag {expected:#04x}, found {found:#04x}"))]
+    UnexpectedTag { expected: u8, found: u8 },
+    #[snafu(display("Unsupported length encoding"))]
+    UnsupportedLength,
+    #[snafu(display("Integer is too large"))]
+    IntegerOverflow,
+    #[snafu(display("Invalid object identifier"))]
+    InvalidObjectIdentifier,
+}
+
+/// Reads consecutive BER encoded values from a buffer.
+#[derive(Clone, Copy, Debug)]
+pub struct Reader<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub const fn new(input: &'a [u8]) -> Self {
+        Self { input }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    /// Reads the next value, returning its tag and contents.
+    pub fn read_any(&mut self) -> Result<(u8, &'a [u8]), BerError> {
+        let (&tag, rest) = self.input.split_first().ok_or(BerError::Truncated)?;
+        let (&first, mut rest) = rest.split_first().ok_or(BerError::Truncated)?;
+
+        let len = if first & 0x80 == 0 {
+            usize::from(first)
+        } else {
+            let num_bytes = usize::from(first & 0x7f);
+            if num_bytes == 0 || num_bytes > std::mem::size_of::<u32>() {
+                return Err(BerError::UnsupportedLength);
+            }
+            if rest.len() < num_bytes {
+                return Err(BerError::Truncated);
+            }
+            let (len_bytes, tail) = rest.split_at(num_bytes);
+            rest = tail;
+            len_bytes
+                .iter()
+                .fold(0, |len, byte| (len << 8) | usize::from(*byte))
+        };
+
+        if rest.len() < len {
+            return Err(BerError::Truncated);
+        }
+        let (contents, rest) = rest.split_at(len);
+        self.input = rest;
+        Ok((tag, contents))
+    }
+
+    /// Reads the next value, which must have the given tag, returning its contents.
+    pub fn read(&mut self, expected: u8) -> Result<&'a [u8], BerError> {
+        match self.read_any()? {
+            (tag, contents) if tag == expected => Ok(contents),
+            (found, _) => Err(BerError::UnexpectedTag { expected, found }),
+        }
+    }
+
+    pub fn read_sequence(&mut self) -> Result<Reader<'a>, BerError> {
+        self.read(SEQUENCE).map(Reader::new)
+    }
+
+    pub fn read_integer(&mut self) -> Result<i64, BerError> {
+        self.read(INTEGER).and_then(decode_integer)
+    }
+
+    pub fn read_octet_string(&mut self) -> Result<&'a [u8], BerError> {
+        self.read(OCTET_STRING)
+    }
+}
+
+/// Decodes a two's complement signed integer.
+pub fn decode_integer(contents: &[u8]) -> Result<i64, BerError> {
+    if contents.is_empty() {
+        return Err(BerError::Truncated);
+    }
+    if contents.len() > 8 {
+        return Err(BerError::IntegerOverflow);
+    }
+    let sign = if contents[0] & 0x80 != 0 { -1 } else { 0 };
+    Ok(contents
+        .iter()
+        .fold(sign, |value, byte| (value << 8) | i64::from(*byte)))
+}
+
+/// Decodes an unsigned integer, as used by the counter, gauge and time ticks types.
+pub fn decode_unsigned(contents: &[u8]) -> Result<u64, BerError> {
+    // Positive values with the high bit set are prefixed with a zero byte.
+    let contents = match contents {
+        [0, rest @ ..] if !rest.is_empty() => rest,
+        contents => contents,
+    };
+    if contents.is_empty() {
+        return Err(BerError::Truncated);
+    }
+    if contents.len() > 8 {
+        return Err(BerError::IntegerOverflow);
+    }
+    Ok(contents
+        .iter()
+        .fold(0, |value, byte| (value << 8) | u64::from(*byte)))
+}
+
+/// Decodes the sub-identifiers of an object identifier.
+pub fn decode_object_identifier(contents: &[u8]) -> Result<Vec<u32>, BerError> {
+    let mut components = Vec::with_capacity(contents.len() + 1);
+    let mut value: u32 = 0;
+    for (i, byte) in contents.iter().enumerate() {
+        value = value
+            .checked_mul(128)
+            .ok_or(BerError::InvalidObjectIdentifier)?
+            | u32::from(byte & 0x7f);
+        if byte & 0x80 != 0 {
+            if i == contents.len() - 1 {
+                return Err(BerError::InvalidObjectIdentifier);
+            }
+            continue;
+        }
+
+        if components.is_empty() {
+            // The first two sub-identifiers are encoded together as `40 * x + y`.
+            let first = (value / 40).min(2);
+            components.push(first);
+            components.push(value - first * 40);
+        } else {
+            components.push(value);
+        }
+        value = 0;
+    }
+
+    if components.is_empty() {
+        return Err(BerError::InvalidObjectIdentifier);
+    }
+    Ok(components)
+}
+
+/// Encodes a value with the given tag.
+pub fn encode(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut encoded = vec![tag];
+    let len = contents.len();
+    if len < 0x80 {
+        encoded.push(len as u8);
+    } else {
+        let len_bytes = len.to_be_bytes();
+        let start = len_bytes.iter().take_while(|byte| **byte == 0).count();
+        encoded.push(0x80 | (len_bytes.len() - start) as u8);
+        encoded.extend_from_slice(&len_bytes[start..]);
+    }
+    encoded.extend_from_slice(contents);
+    encoded
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    pub fn encode_integer(value: i64) -> Vec<u8> {
+        let bytes = value.to_be_bytes();
+        // Strip the redundant leading bytes of the two's complement representation.
+        let mut start = 0;
+        while start < 7
+            && ((bytes[start] == 0 && bytes[start + 1] & 0x80 == 0)
+                || (bytes[start] == 0xff && bytes[start + 1] & 0x80 != 0))
+        {
+            start += 1;
+        }
+        encode(INTEGER, &bytes[start..])
+    }
+
+    pub fn encode_object_identifier(components: &[u32]) -> Vec<u8> {
+        let mut contents = Vec::new();
+        let values = std::iter::once(components[0] * 40 + components[1])
+            .chain(components[2..].iter().copied());
+        for value in values {
+            let mut bytes = vec![(value & 0x7f) as u8];
+            let mut rest = value >> 7;
+            while rest > 0 {
+                bytes.push((rest & 0x7f) as u8 | 0x80);
+                rest >>= 7;
+            }
+            contents.extend(bytes.into_iter().rev());
+        }
+        encode(OBJECT_IDENTIFIER, &contents)
+    }
+
+    #[test]
+    fn reads_values() {
+        let mut input = encode(OCTET_STRING, b"public");
+        input.extend(encode(OCTET_STRING, &[0; 200]));
+        input.extend(encode_integer(-129));
+
+        let mut reader = Reader::new(&input);
+        assert_eq!(reader.read_octet_string().unwrap(), b"public");
+        assert_eq!(reader.read_octet_string().unwrap(), &[0; 200]);
+        assert_eq!(
+            reader.read_octet_string(),
+            Err(BerError::UnexpectedTag {
+                expected: OCTET_STRING,
+                found: INTEGER
+            })
+        );
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn rejects_truncated_values() {
+        let input = encode(OCTET_STRING, b"public");
+        let mut reader = Reader::new(&input[..input.len() - 1]);
+        assert_eq!(reader.read_octet_string(), Err(BerError::Truncated));
+    }
+
+    #[test]
+    fn decodes_integers() {
+        for value in [0, 1, 127, 128, 255, 256, -1, -128, -129, i64::MAX, i64::MIN] {
+            let encoded = encode_integer(value);
+            assert_eq!(Reader::new(&encoded).read_integer(), Ok(value));
+        }
+        assert_eq!(
+            decode_unsigned(&[0x00, 0xff, 0xff, 0xff, 0xff]),
+            Ok(u32::MAX.into())
+        );
+    }
+
+    #[test]
+    fn decodes_object_identifiers() {
+        let oid = [1, 3, 6, 1, 4, 1, 2021, 4294967295];
+        let encoded = encode_object_identifier(&oid);
+        let contents = Reader::new(&encoded).read(OBJECT_IDENTIFIER).unwrap();
+        assert_eq!(decode_object_identifier(contents).unwrap(), oid);
+
+        assert_eq!(
+            decode_object_identifier(&[0x2b, 0x86]),
+            Err(BerError::InvalidObjectIdentifier)
+        );
+    }
+}
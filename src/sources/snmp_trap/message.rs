@@ -0,0 +1,388 @@
+//! Parsing of SNMPv2c and SNMPv3 messages carrying notifications.
+
+use std::net::Ipv4Addr;
+
+use snafu::Snafu;
+
+use super::ber::{self, BerError, Reader};
+
+const VERSION_2C: i64 = 1;
+const VERSION_3: i64 = 3;
+
+const USER_BASED_SECURITY_MODEL: i64 = 3;
+
+pub const GET_RESPONSE_PDU: u8 = 0xa2;
+pub const INFORM_REQUEST_PDU: u8 = 0xa6;
+pub const TRAP_V2_PDU: u8 = 0xa7;
+
+pub const FLAG_AUTH: u8 = 0x01;
+pub const FLAG_PRIV: u8 = 0x02;
+
+#[derive(Debug, Snafu)]
+pub enum TrapError {
+    #[snafu(display("Invalid message: {source}"))]
+    Ber { source: BerError },
+    #[snafu(display("Unsupported SNMP version {version}"))]
+    UnsupportedVersion { version: i64 },
+    #[snafu(display("Unsupported PDU type {tag:#04x}"))]
+    UnsupportedPdu { tag: u8 },
+    #[snafu(display("Unsupported security model {model}"))]
+    UnsupportedSecurityModel { model: i64 },
+    #[snafu(display("Unknown community"))]
+    UnknownCommunity,
+    #[snafu(display("Unknown user {user:?}"))]
+    UnknownUser { user: String },
+    #[snafu(display("Security level of the message doesn't match the one of user {user:?}"))]
+    UnsupportedSecurityLevel { user: String },
+    #[snafu(display("Authentication failed for user {user:?}"))]
+    AuthenticationFailed { user: String },
+    #[snafu(display("Decryption failed for user {user:?}"))]
+    DecryptionFailed { user: String },
+}
+
+impl From<BerError> for TrapError {
+    fn from(source: BerError) -> Self {
+        Self::Ber { source }
+    }
+}
+
+impl TrapError {
+    pub const fn error_code(&self) -> &'static str {
+        match self {
+            Self::Ber { .. } => "invalid_message",
+            Self::UnsupportedVersion { .. } => "unsupported_version",
+            Self::UnsupportedPdu { .. } => "unsupported_pdu",
+            Self::UnsupportedSecurityModel { .. } => "unsupported_security_model",
+            Self::UnknownCommunity => "unknown_community",
+            Self::UnknownUser { .. } => "unknown_user",
+            Self::UnsupportedSecurityLevel { .. } => "unsupported_security_level",
+            Self::AuthenticationFailed { .. } => "authentication_failed",
+            Self::DecryptionFailed { .. } => "decryption_failed",
+        }
+    }
+}
+
+/// An SNMP message, whose PDU isn't parsed yet as it may be encrypted.
+#[derive(Debug)]
+pub enum Message<'a> {
+    V2c {
+        community: &'a [u8],
+        pdu: &'a [u8],
+        pdu_tag: u8,
+    },
+    V3 {
+        flags: u8,
+        security: SecurityParameters<'a>,
+        data: ScopedData<'a>,
+    },
+}
+
+/// The parameters of the User-based Security Model (USM) of a SNMPv3 message.
+#[derive(Debug)]
+pub struct SecurityParameters<'a> {
+    pub engine_id: &'a [u8],
+    pub engine_boots: u32,
+    pub engine_time: u32,
+    pub user_name: &'a [u8],
+    pub auth_params: &'a [u8],
+    pub priv_params: &'a [u8],
+}
+
+#[derive(Debug)]
+pub enum ScopedData<'a> {
+    /// The contents of the scoped PDU sequence.
+    Plaintext(&'a [u8]),
+    Encrypted(&'a [u8]),
+}
+
+impl<'a> Message<'a> {
+    pub fn parse(input: &'a [u8]) -> Result<Self, TrapError> {
+        let mut message = Reader::new(input).read_sequence()?;
+        match message.read_integer()? {
+            VERSION_2C => {
+                let community = message.read_octet_string()?;
+                let (pdu_tag, pdu) = message.read_any()?;
+                Ok(Self::V2c {
+                    community,
+                    pdu,
+                    pdu_tag,
+                })
+            }
+            VERSION_3 => {
+                let mut global = message.read_sequence()?;
+                let _id = global.read_integer()?;
+                let _max_size = global.read_integer()?;
+                let flags = global
+                    .read_octet_string()?
+                    .first()
+                    .copied()
+                    .ok_or(BerError::Truncated)?;
+                let model = global.read_integer()?;
+                if model != USER_BASED_SECURITY_MODEL {
+                    return Err(TrapError::UnsupportedSecurityModel { model });
+                }
+
+                let mut params = Reader::new(message.read_octet_string()?).read_sequence()?;
+                let security = SecurityParameters {
+                    engine_id: params.read_octet_string()?,
+                    engine_boots: read_u32(&mut params)?,
+                    engine_time: read_u32(&mut params)?,
+                    user_name: params.read_octet_string()?,
+                    auth_params: params.read_octet_string()?,
+                    priv_params: params.read_octet_string()?,
+                };
+
+                let data = match message.read_any()? {
+                    (ber::SEQUENCE, contents) => ScopedData::Plaintext(contents),
+                    (ber::OCTET_STRING, contents) => ScopedData::Encrypted(contents),
+                    (found, _) => {
+                        return Err(BerError::UnexpectedTag {
+                            expected: ber::SEQUENCE,
+                            found,
+                        }
+                        .into())
+                    }
+                };
+
+                Ok(Self::V3 {
+                    flags,
+                    security,
+                    data,
+                })
+            }
+            version => Err(TrapError::UnsupportedVersion { version }),
+        }
+    }
+}
+
+fn read_u32(reader: &mut Reader<'_>) -> Result<u32, BerError> {
+    u32::try_from(reader.read_integer()?).map_err(|_| BerError::IntegerOverflow)
+}
+
+/// Returns the tag and contents of the PDU of a scoped PDU.
+pub fn parse_scoped_pdu(contents: &[u8]) -> Result<(u8, &[u8]), TrapError> {
+    let mut scoped = Reader::new(contents);
+    let _context_engine_id = scoped.read_octet_string()?;
+    let _context_name = scoped.read_octet_string()?;
+    Ok(scoped.read_any()?)
+}
+
+/// The value of a variable binding.
+#[derive(Clone, Debug, PartialEq)]
+pub enum VarValue {
+    Integer(i64),
+    Unsigned(u64),
+    Bytes(Vec<u8>),
+    ObjectIdentifier(Vec<u32>),
+    IpAddress(Ipv4Addr),
+    Null,
+}
+
+/// A notification PDU.
+#[derive(Debug)]
+pub struct Pdu {
+    pub inform: bool,
+    pub variables: Vec<(Vec<u32>, VarValue)>,
+}
+
+impl Pdu {
+    pub fn parse(tag: u8, contents: &[u8]) -> Result<Self, TrapError> {
+        let inform = match tag {
+            TRAP_V2_PDU => false,
+            INFORM_REQUEST_PDU => true,
+            tag => return Err(TrapError::UnsupportedPdu { tag }),
+        };
+
+        let mut pdu = Reader::new(contents);
+        let _request_id = pdu.read_integer()?;
+        let _error_status = pdu.read_integer()?;
+        let _error_index = pdu.read_integer()?;
+
+        let mut bindings = pdu.read_sequence()?;
+        let mut variables = Vec::new();
+        while !bindings.is_empty() {
+            let mut binding = bindings.read_sequence()?;
+            let oid = ber::decode_object_identifier(binding.read(ber::OBJECT_IDENTIFIER)?)?;
+            let (tag, contents) = binding.read_any()?;
+            variables.push((oid, decode_value(tag, contents)?));
+        }
+
+        Ok(Self { inform, variables })
+    }
+}
+
+fn decode_value(tag: u8, contents: &[u8]) -> Result<VarValue, BerError> {
+    Ok(match tag {
+        ber::INTEGER => VarValue::Integer(ber::decode_integer(contents)?),
+        ber::COUNTER32 | ber::GAUGE32 | ber::TIME_TICKS | ber::COUNTER64 => {
+            VarValue::Unsigned(ber::decode_unsigned(contents)?)
+        }
+        ber::OBJECT_IDENTIFIER => {
+            VarValue::ObjectIdentifier(ber::decode_object_identifier(contents)?)
+        }
+        ber::IP_ADDRESS => match <[u8; 4]>::try_from(contents) {
+            Ok(octets) => VarValue::IpAddress(octets.into()),
+            Err(_) => VarValue::Bytes(contents.to_vec()),
+        },
+        ber::NULL | ber::NO_SUCH_OBJECT | ber::NO_SUCH_INSTANCE | ber::END_OF_MIB_VIEW => {
+            VarValue::Null
+        }
+        ber::OCTET_STRING | ber::OPAQUE => VarValue::Bytes(contents.to_vec()),
+        // Any other application types are kept as is.
+        _ => VarValue::Bytes(contents.to_vec()),
+    })
+}
+
+/// Builds the response acknowledging a SNMPv2c inform, which repeats its variable bindings.
+pub fn inform_response(community: &[u8], pdu: &[u8]) -> Result<Vec<u8>, TrapError> {
+    let mut reader = Reader::new(pdu);
+    let request_id = reader.read(ber::INTEGER)?;
+    let _error_status = reader.read_integer()?;
+    let _error_index = reader.read_integer()?;
+    let bindings = reader.read(ber::SEQUENCE)?;
+
+    let pdu = [
+        ber::encode(ber::INTEGER, request_id),
+        ber::encode(ber::INTEGER, &[0]),
+        ber::encode(ber::INTEGER, &[0]),
+        ber::encode(ber::SEQUENCE, bindings),
+    ]
+    .concat();
+    let message = [
+        ber::encode(ber::INTEGER, &[VERSION_2C as u8]),
+        ber::encode(ber::OCTET_STRING, community),
+        ber::encode(GET_RESPONSE_PDU, &pdu),
+    ]
+    .concat();
+    Ok(ber::encode(ber::SEQUENCE, &message))
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::sources::snmp_trap::ber::tests::{encode_integer, encode_object_identifier as oid};
+    use ber::encode;
+
+    pub const SYS_UP_TIME: [u32; 9] = [1, 3, 6, 1, 2, 1, 1, 3, 0];
+    pub const SNMP_TRAP_OID: [u32; 11] = [1, 3, 6, 1, 6, 3, 1, 1, 4, 1, 0];
+    pub const LINK_DOWN: [u32; 10] = [1, 3, 6, 1, 6, 3, 1, 1, 5, 3];
+
+    /// Encodes the PDU of a `linkDown` trap for interface 2.
+    pub fn link_down_pdu(tag: u8) -> Vec<u8> {
+        let binding =
+            |name: &[u32], value: Vec<u8>| encode(ber::SEQUENCE, &[oid(name), value].concat());
+        let bindings = [
+            binding(
+                &SYS_UP_TIME,
+                encode(ber::TIME_TICKS, &[0x00, 0xbc, 0x61, 0x4e]),
+            ),
+            binding(&SNMP_TRAP_OID, oid(&LINK_DOWN)),
+            binding(&[1, 3, 6, 1, 2, 1, 2, 2, 1, 1, 2], encode_integer(2)),
+            binding(
+                &[1, 3, 6, 1, 2, 1, 2, 2, 1, 2, 2],
+                encode(ber::OCTET_STRING, b"eth0"),
+            ),
+            binding(
+                &[1, 3, 6, 1, 4, 1, 9999, 1],
+                encode(ber::IP_ADDRESS, &[10, 0, 0, 1]),
+            ),
+        ]
+        .concat();
+        let contents = [
+            encode_integer(1234),
+            encode_integer(0),
+            encode_integer(0),
+            encode(ber::SEQUENCE, &bindings),
+        ]
+        .concat();
+        encode(tag, &contents)
+    }
+
+    pub fn v2c_message(community: &[u8], pdu: Vec<u8>) -> Vec<u8> {
+        let contents = [
+            encode_integer(VERSION_2C),
+            encode(ber::OCTET_STRING, community),
+            pdu,
+        ]
+        .concat();
+        encode(ber::SEQUENCE, &contents)
+    }
+
+    #[test]
+    fn parses_v2c_trap() {
+        let input = v2c_message(b"public", link_down_pdu(TRAP_V2_PDU));
+
+        let Message::V2c {
+            community,
+            pdu,
+            pdu_tag,
+        } = Message::parse(&input).unwrap()
+        else {
+            panic!("expected a SNMPv2c message");
+        };
+        assert_eq!(community, b"public");
+
+        let pdu = Pdu::parse(pdu_tag, pdu).unwrap();
+        assert!(!pdu.inform);
+        assert_eq!(
+            pdu.variables,
+            [
+                (SYS_UP_TIME.to_vec(), VarValue::Unsigned(12345678)),
+                (
+                    SNMP_TRAP_OID.to_vec(),
+                    VarValue::ObjectIdentifier(LINK_DOWN.to_vec())
+                ),
+                (vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 1, 2], VarValue::Integer(2)),
+                (
+                    vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 2, 2],
+                    VarValue::Bytes(b"eth0".to_vec())
+                ),
+                (
+                    vec![1, 3, 6, 1, 4, 1, 9999, 1],
+                    VarValue::IpAddress(Ipv4Addr::new(10, 0, 0, 1))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_messages() {
+        let input = v2c_message(b"public", link_down_pdu(GET_RESPONSE_PDU));
+        let Message::V2c { pdu, pdu_tag, .. } = Message::parse(&input).unwrap() else {
+            panic!("expected a SNMPv2c message");
+        };
+        assert!(matches!(
+            Pdu::parse(pdu_tag, pdu),
+            Err(TrapError::UnsupportedPdu {
+                tag: GET_RESPONSE_PDU
+            })
+        ));
+
+        let input = encode(
+            ber::SEQUENCE,
+            &[encode_integer(0), encode(ber::OCTET_STRING, b"public")].concat(),
+        );
+        assert!(matches!(
+            Message::parse(&input),
+            Err(TrapError::UnsupportedVersion { version: 0 })
+        ));
+
+        assert!(matches!(
+            Message::parse(&input[..input.len() - 1]),
+            Err(TrapError::Ber { .. })
+        ));
+    }
+
+    #[test]
+    fn builds_inform_response() {
+        let input = v2c_message(b"public", link_down_pdu(INFORM_REQUEST_PDU));
+        let Message::V2c { community, pdu, .. } = Message::parse(&input).unwrap() else {
+            panic!("expected a SNMPv2c message");
+        };
+
+        let response = inform_response(community, pdu).unwrap();
+
+        let expected = v2c_message(b"public", link_down_pdu(GET_RESPONSE_PDU));
+        assert_eq!(response, expected);
+    }
+}
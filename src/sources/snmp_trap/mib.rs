@@ -0,0 +1,387 @@
+//! Resolution of object identifiers into names, from the definitions of MIB modules.
+//!
+//! Only the object identifier assignments of MIB modules are parsed, which is all that is needed
+//! to name the objects of traps. Imports are ignored, so modules can be loaded in any order as long
+//! as the modules they depend on are loaded as well.
+
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    path::{Path, PathBuf},
+};
+
+/// The objects defined by the SMI and SNMPv2 modules, which most MIB modules build upon.
+const BUILTIN_OBJECTS: &[(&str, &[u32])] = &[
+    ("ccitt", &[0]),
+    ("iso", &[1]),
+    ("joint-iso-ccitt", &[2]),
+    ("org", &[1, 3]),
+    ("dod", &[1, 3, 6]),
+    ("internet", &[1, 3, 6, 1]),
+    ("directory", &[1, 3, 6, 1, 1]),
+    ("mgmt", &[1, 3, 6, 1, 2]),
+    ("mib-2", &[1, 3, 6, 1, 2, 1]),
+    ("system", &[1, 3, 6, 1, 2, 1, 1]),
+    ("sysUpTime", &[1, 3, 6, 1, 2, 1, 1, 3]),
+    ("experimental", &[1, 3, 6, 1, 3]),
+    ("private", &[1, 3, 6, 1, 4]),
+    ("enterprises", &[1, 3, 6, 1, 4, 1]),
+    ("security", &[1, 3, 6, 1, 5]),
+    ("snmpV2", &[1, 3, 6, 1, 6]),
+    ("snmpDomains", &[1, 3, 6, 1, 6, 1]),
+    ("snmpProxys", &[1, 3, 6, 1, 6, 2]),
+    ("snmpModules", &[1, 3, 6, 1, 6, 3]),
+    ("snmpTrapOID", &[1, 3, 6, 1, 6, 3, 1, 1, 4, 1]),
+    ("snmpTrapEnterprise", &[1, 3, 6, 1, 6, 3, 1, 1, 4, 3]),
+    ("coldStart", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 1]),
+    ("warmStart", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 2]),
+    ("linkDown", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 3]),
+    ("linkUp", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 4]),
+    ("authenticationFailure", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 5]),
+];
+
+/// The macros whose values are object identifiers.
+const OBJECT_MACROS: &[&str] = &[
+    "AGENT-CAPABILITIES",
+    "MODULE-COMPLIANCE",
+    "MODULE-IDENTITY",
+    "NOTIFICATION-GROUP",
+    "NOTIFICATION-TYPE",
+    "OBJECT-GROUP",
+    "OBJECT-IDENTITY",
+    "OBJECT-TYPE",
+];
+
+/// Names object identifiers.
+#[derive(Clone, Debug, Default)]
+pub struct Mib {
+    names: HashMap<Vec<u32>, String>,
+}
+
+/// The value of an object identifier assignment, relative to another object unless `parent` is
+/// unset.
+#[derive(Debug)]
+struct Assignment<'a> {
+    name: &'a str,
+    parent: Option<&'a str>,
+    components: Vec<u32>,
+}
+
+impl Mib {
+    /// Loads the MIB modules in the given files, or in the files of the given directories.
+    pub fn load(paths: &[PathBuf]) -> crate::Result<Self> {
+        let mut sources = Vec::new();
+        for path in paths {
+            if path.is_dir() {
+                let mut files = std::fs::read_dir(path)
+                    .and_then(|entries| {
+                        entries
+                            .map(|entry| entry.map(|entry| entry.path()))
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .map_err(|error| format!("Couldn't read MIB directory {path:?}: {error}"))?;
+                files.retain(|file| file.is_file());
+                files.sort();
+                for file in files {
+                    sources.push(read(&file)?);
+                }
+            } else {
+                sources.push(read(path)?);
+            }
+        }
+        Ok(Self::parse(sources.iter().map(String::as_str)))
+    }
+
+    /// Parses the given MIB modules.
+    pub fn parse<'a>(sources: impl IntoIterator<Item = &'a str>) -> Self {
+        let tokens = sources.into_iter().map(tokenize).collect::<Vec<_>>();
+        let mut assignments = tokens
+            .iter()
+            .flat_map(|tokens| parse_assignments(tokens))
+            .collect::<Vec<_>>();
+
+        let mut resolved: HashMap<&str, Vec<u32>> = HashMap::new();
+        let mut names = HashMap::new();
+        for (name, oid) in BUILTIN_OBJECTS {
+            resolved.insert(*name, oid.to_vec());
+            names.insert(oid.to_vec(), (*name).to_owned());
+        }
+
+        // Objects can be defined before their parents, so assignments are resolved until no more
+        // can be. Those left are relative to undefined objects.
+        loop {
+            let unresolved = assignments.len();
+            assignments.retain(|assignment| {
+                let oid = match assignment.parent {
+                    None => assignment.components.clone(),
+                    Some(parent) => match resolved.get(parent) {
+                        Some(parent) => [parent.as_slice(), &assignment.components].concat(),
+                        None => return true,
+                    },
+                };
+                names
+                    .entry(oid.clone())
+                    .or_insert_with(|| assignment.name.to_owned());
+                resolved.entry(assignment.name).or_insert(oid);
+                false
+            });
+            if assignments.len() == unresolved {
+                break;
+            }
+        }
+        if !assignments.is_empty() {
+            debug!(
+                message = "Ignoring MIB objects defined relative to undefined objects.",
+                count = assignments.len(),
+            );
+        }
+
+        Self { names }
+    }
+
+    /// Names an object identifier after its closest named ancestor, followed by the remaining
+    /// sub-identifiers, or formats it numerically if it has none.
+    pub fn name(&self, oid: &[u32]) -> String {
+        let (mut name, rest) = (1..=oid.len())
+            .rev()
+            .find_map(|len| {
+                self.names
+                    .get(&oid[..len])
+                    .map(|name| (name.clone(), &oid[len..]))
+            })
+            .unwrap_or_else(|| (String::new(), oid));
+
+        for (i, component) in rest.iter().enumerate() {
+            if i > 0 || !name.is_empty() {
+                name.push('.');
+            }
+            write!(name, "{component}").expect("writing to a string never fails");
+        }
+        name
+    }
+}
+
+fn read(path: &Path) -> crate::Result<String> {
+    let bytes =
+        std::fs::read(path).map_err(|error| format!("Couldn't read MIB file {path:?}: {error}"))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Splits a module into tokens, dropping comments and the contents of strings.
+fn tokenize(source: &str) -> Vec<&str> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &source[i..];
+        if bytes[i].is_ascii_whitespace() {
+            i += 1;
+        } else if rest.starts_with("--") {
+            // Comments end with another `--` or with the line.
+            i += 2;
+            while i < bytes.len() && bytes[i] != b'\n' && !bytes[i..].starts_with(b"--") {
+                i += 1;
+            }
+            if bytes[i..].starts_with(b"--") {
+                i += 2;
+            }
+        } else if bytes[i] == b'"' {
+            i += 1 + rest[1..].find('"').map_or(rest.len() - 1, |end| end + 1);
+            tokens.push("\"\"");
+        } else if rest.starts_with("::=") {
+            tokens.push("::=");
+            i += 3;
+        } else if bytes[i].is_ascii_alphanumeric() {
+            let start = i;
+            while i < bytes.len()
+                && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'-' || bytes[i] == b'_')
+                && !bytes[i..].starts_with(b"--")
+            {
+                i += 1;
+            }
+            tokens.push(&source[start..i]);
+        } else {
+            let len = rest.chars().next().map_or(1, char::len_utf8);
+            tokens.push(&source[i..i + len]);
+            i += len;
+        }
+    }
+    tokens
+}
+
+fn is_value_name(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_ascii_lowercase())
+}
+
+/// Finds the object identifier assignments of a module.
+fn parse_assignments<'a>(tokens: &[&'a str]) -> Vec<Assignment<'a>> {
+    let mut assignments = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let name = tokens[i];
+        let next = |offset: usize| tokens.get(i + offset).copied().unwrap_or_default();
+
+        if next(1) == "MACRO" {
+            // Macro definitions have a grammar of their own.
+            i += tokens[i..]
+                .iter()
+                .position(|token| *token == "END")
+                .unwrap_or(tokens.len() - i);
+        } else if is_value_name(name) && next(1) == "TRAP-TYPE" {
+            // SMIv1 traps are numbered within their enterprise, as described in RFC 3584.
+            let Some(end) = tokens[i..].iter().position(|token| *token == "::=") else {
+                break;
+            };
+            let enterprise = tokens[i..i + end]
+                .iter()
+                .position(|token| *token == "ENTERPRISE")
+                .and_then(|position| tokens.get(i + position + 1).copied());
+            if let (Some(enterprise), Some(Ok(number))) = (
+                enterprise,
+                tokens.get(i + end + 1).map(|token| token.parse()),
+            ) {
+                assignments.push(Assignment {
+                    name,
+                    parent: Some(enterprise),
+                    components: vec![0, number],
+                });
+            }
+            i += end + 1;
+        } else if is_value_name(name)
+            && (OBJECT_MACROS.contains(&next(1))
+                || (next(1) == "OBJECT" && next(2) == "IDENTIFIER" && next(3) == "::="))
+        {
+            let Some(end) = tokens[i..].iter().position(|token| *token == "::=") else {
+                break;
+            };
+            i += end + 1;
+            if tokens.get(i) == Some(&"{") {
+                let len = tokens[i..]
+                    .iter()
+                    .position(|token| *token == "}")
+                    .unwrap_or(tokens.len() - i);
+                parse_value(name, &tokens[i + 1..i + len], &mut assignments);
+                i += len;
+            }
+        }
+        i += 1;
+    }
+    assignments
+}
+
+/// Parses an object identifier value, like `{ ifEntry 1 }` or `{ iso org(3) dod(6) 1 }`. Named
+/// components like `org(3)` define objects as well.
+fn parse_value<'a>(name: &'a str, tokens: &[&'a str], assignments: &mut Vec<Assignment<'a>>) {
+    let mut parent = None;
+    let mut components = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        if let Ok(number) = token.parse() {
+            components.push(number);
+        } else if tokens.get(i + 1) == Some(&"(") {
+            let Some(Ok(number)) = tokens.get(i + 2).map(|token| token.parse()) else {
+                return;
+            };
+            components.push(number);
+            assignments.push(Assignment {
+                name: token,
+                parent,
+                components: components.clone(),
+            });
+            i += 3;
+        } else if i == 0 {
+            parent = Some(token);
+        } else {
+            return;
+        }
+        i += 1;
+    }
+
+    if parent.is_some() || !components.is_empty() {
+        assignments.push(Assignment {
+            name,
+            parent,
+            components,
+        });
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    pub const IF_MIB: &str = r#"
+IF-MIB DEFINITIONS ::= BEGIN
+
+IMPORTS
+    MODULE-IDENTITY, OBJECT-TYPE, NOTIFICATION-TYPE, mib-2 FROM SNMPv2-SMI;
+
+ifMIB MODULE-IDENTITY
+    LAST-UPDATED "200006140000Z"
+    DESCRIPTION
+            "The MIB module to describe generic objects for network
+            interface sub-layers. -- not a comment"
+    ::= { mib-2 31 }
+
+-- the Interfaces group
+
+ifTable OBJECT-TYPE
+    SYNTAX      SEQUENCE OF IfEntry
+    MAX-ACCESS  not-accessible
+    STATUS      current
+    ::= { interfaces 2 } -- defined after its children
+
+ifEntry OBJECT-TYPE
+    SYNTAX      IfEntry
+    INDEX   { ifIndex }
+    ::= { ifTable 1 }
+
+ifIndex OBJECT-TYPE
+    SYNTAX      InterfaceIndex
+    ::= { ifEntry 1 }
+
+interfaces   OBJECT IDENTIFIER ::= { mib-2 2 }
+
+ifDescr OBJECT-TYPE
+    SYNTAX      DisplayString (SIZE (0..255))
+    ::= { ifEntry 2 }
+
+ciscoSpecific OBJECT-TYPE
+    ::= { undefinedParent 1 }
+
+END
+"#;
+
+    const VENDOR_MIB: &str = "
+VENDOR-MIB DEFINITIONS ::= BEGIN
+vendor OBJECT IDENTIFIER ::= { iso org(3) dod(6) internet(1) private(4) enterprises(1) 9999 }
+vendorAlarm TRAP-TYPE
+    ENTERPRISE vendor
+    VARIABLES { ifIndex }
+    ::= 3
+END
+";
+
+    #[test]
+    fn names_objects() {
+        let mib = Mib::parse([IF_MIB, VENDOR_MIB]);
+
+        assert_eq!(mib.name(&[1, 3, 6, 1, 2, 1, 2, 2, 1, 1]), "ifIndex");
+        assert_eq!(mib.name(&[1, 3, 6, 1, 2, 1, 2, 2, 1, 2, 3]), "ifDescr.3");
+        assert_eq!(mib.name(&[1, 3, 6, 1, 2, 1, 31]), "ifMIB");
+        assert_eq!(mib.name(&[1, 3, 6, 1, 4, 1, 9999, 0, 3]), "vendorAlarm");
+        assert_eq!(mib.name(&[1, 3, 6, 1, 4, 1, 9999, 7]), "vendor.7");
+        assert_eq!(mib.name(&[1, 3, 6, 1, 4, 1, 1234, 1]), "enterprises.1234.1");
+        assert_eq!(mib.name(&[1, 3, 6, 1, 6, 3, 1, 1, 5, 3]), "linkDown");
+        assert_eq!(mib.name(&[3, 1]), "3.1");
+    }
+
+    #[test]
+    fn tokenizes_modules() {
+        assert_eq!(
+            tokenize("a-b--comment--c \"x -- y\" d ::= {e(1)}-- end\nf"),
+            ["a-b", "c", "\"\"", "d", "::=", "{", "e", "(", "1", ")", "}", "f"]
+        );
+    }
+}
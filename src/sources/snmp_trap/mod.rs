@@ -0,0 +1,543 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
+};
+
+use chrono::Utc;
+use listenfd::ListenFd;
+use tokio::net::UdpSocket;
+use vector_lib::config::{log_schema, LegacyKey, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol,
+};
+use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path, path};
+use vector_lib::sensitive_string::SensitiveString;
+use vector_lib::EstimatedJsonEncodedSizeOf;
+use vrl::value::{kind::Collection, Kind, ObjectMap, Value};
+
+use self::{
+    message::{Message, Pdu, VarValue},
+    mib::Mib,
+    usm::{UserConfig, Usm},
+};
+use crate::{
+    config::{DataType, GenerateConfig, Resource, SourceConfig, SourceContext, SourceOutput},
+    event::LogEvent,
+    internal_events::{
+        EventsReceived, SnmpTrapParseError, SocketBindError, SocketMode, SocketReceiveError,
+        StreamClosedError,
+    },
+    net,
+    shutdown::ShutdownSignal,
+    sources::util::net::{try_bind_udp_socket, SocketListenAddr},
+    SourceSender,
+};
+
+mod ber;
+mod message;
+mod mib;
+mod usm;
+
+pub(crate) use self::message::TrapError;
+
+/// The object identifier of `sysUpTime.0`, the first variable of every notification.
+const SYS_UP_TIME: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 3, 0];
+
+/// The object identifier of `snmpTrapOID.0`, the second variable of every notification.
+const SNMP_TRAP_OID: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 4, 1, 0];
+
+/// The largest possible UDP payload.
+const MAX_MESSAGE_SIZE: usize = 65535;
+
+/// Configuration for the `snmp_trap` source.
+#[configurable_component(source("snmp_trap", "Receive SNMP traps from network devices."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpTrapConfig {
+    #[configurable(derived)]
+    #[serde(default = "default_address")]
+    address: SocketListenAddr,
+
+    /// The SNMPv2c communities to accept traps from.
+    ///
+    /// If empty, SNMPv2c traps are accepted regardless of their community.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "public"))]
+    communities: Vec<SensitiveString>,
+
+    /// The SNMPv3 users to accept traps from.
+    ///
+    /// SNMPv3 traps from any other user are rejected.
+    #[serde(default)]
+    users: Vec<UserConfig>,
+
+    /// Paths of MIB modules, or of directories containing MIB modules, used to name object
+    /// identifiers.
+    ///
+    /// Only the object identifier assignments of the modules are read. The objects of the SMI and
+    /// SNMPv2 modules, as well as the standard traps, are always known.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/usr/share/snmp/mibs"))]
+    #[configurable(metadata(docs::examples = "/etc/vector/mibs/IF-MIB.txt"))]
+    mib_paths: Vec<PathBuf>,
+
+    /// The size of the receive buffer used for the listening socket.
+    ///
+    /// This should not typically needed to be changed.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    receive_buffer_bytes: Option<usize>,
+
+    /// Overrides the name of the log field used to add the address of the sending device to each
+    /// event.
+    ///
+    /// The value is the IP address of the device the trap was received from.
+    ///
+    /// By default, the [global `log_schema.host_key` option][global_host_key] is used.
+    ///
+    /// [global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+    host_key: Option<OptionalValuePath>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_address() -> SocketListenAddr {
+    SocketListenAddr::SocketAddr(SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 162))
+}
+
+impl Default for SnmpTrapConfig {
+    fn default() -> Self {
+        Self {
+            address: default_address(),
+            communities: Vec::new(),
+            users: Vec::new(),
+            mib_paths: Vec::new(),
+            receive_buffer_bytes: None,
+            host_key: None,
+            log_namespace: None,
+        }
+    }
+}
+
+impl GenerateConfig for SnmpTrapConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::default()).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "snmp_trap")]
+impl SourceConfig for SnmpTrapConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let receiver = TrapReceiver {
+            communities: self
+                .communities
+                .iter()
+                .map(|community| community.inner().as_bytes().to_vec())
+                .collect(),
+            usm: Usm::new(&self.users)?,
+            mib: Mib::load(&self.mib_paths)?,
+            host_key: self
+                .host_key
+                .clone()
+                .unwrap_or_else(|| log_schema().host_key().cloned().into()),
+            log_namespace: cx.log_namespace(self.log_namespace),
+        };
+
+        Ok(Box::pin(receiver.run(
+            self.address,
+            self.receive_buffer_bytes,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let host_key = self
+            .host_key
+            .clone()
+            .unwrap_or_else(|| log_schema().host_key().cloned().into())
+            .path
+            .map(LegacyKey::Overwrite);
+
+        let schema_definition = vector_lib::schema::Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [log_namespace],
+        )
+        .with_event_field(&owned_value_path!("version"), Kind::bytes(), None)
+        .with_event_field(
+            &owned_value_path!("community"),
+            Kind::bytes().or_undefined(),
+            None,
+        )
+        .with_event_field(
+            &owned_value_path!("user"),
+            Kind::bytes().or_undefined(),
+            None,
+        )
+        .with_event_field(
+            &owned_value_path!("trap_oid"),
+            Kind::bytes().or_undefined(),
+            None,
+        )
+        .with_event_field(
+            &owned_value_path!("uptime"),
+            Kind::integer().or_undefined(),
+            None,
+        )
+        .with_event_field(
+            &owned_value_path!("variables"),
+            Kind::object(Collection::any()),
+            None,
+        )
+        .with_standard_vector_source_metadata()
+        .with_source_metadata(
+            Self::NAME,
+            host_key,
+            &owned_value_path!("host"),
+            Kind::bytes(),
+            Some("host"),
+        );
+
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            schema_definition,
+        )]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.as_udp_resource()]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// A trap decoded from a message, along with the community or user it was sent with.
+struct Trap {
+    version: &'static str,
+    community: Option<String>,
+    user: Option<String>,
+    pdu: Pdu,
+    /// The response acknowledging an inform, if the trap is one.
+    response: Option<Vec<u8>>,
+}
+
+struct TrapReceiver {
+    communities: Vec<Vec<u8>>,
+    usm: Usm,
+    mib: Mib,
+    host_key: OptionalValuePath,
+    log_namespace: LogNamespace,
+}
+
+impl TrapReceiver {
+    async fn run(
+        self,
+        address: SocketListenAddr,
+        receive_buffer_bytes: Option<usize>,
+        mut shutdown: ShutdownSignal,
+        mut out: SourceSender,
+    ) -> Result<(), ()> {
+        let socket = try_bind_udp_socket(address, ListenFd::from_env())
+            .await
+            .map_err(|error| {
+                emit!(SocketBindError {
+                    mode: SocketMode::Udp,
+                    error,
+                })
+            })?;
+
+        if let Some(receive_buffer_bytes) = receive_buffer_bytes {
+            if let Err(error) = net::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+            }
+        }
+
+        info!(message = "Listening.", address = %address);
+
+        let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+        let events_received = register!(EventsReceived);
+
+        let mut buf = vec![0; MAX_MESSAGE_SIZE];
+        loop {
+            tokio::select! {
+                recv = socket.recv_from(&mut buf) => {
+                    let (byte_size, peer) = recv.map_err(|error| {
+                        emit!(SocketReceiveError {
+                            mode: SocketMode::Udp,
+                            error,
+                        })
+                    })?;
+                    bytes_received.emit(ByteSize(byte_size));
+
+                    let trap = match self.decode(&buf[..byte_size]) {
+                        Ok(trap) => trap,
+                        Err(error) => {
+                            emit!(SnmpTrapParseError { error, peer });
+                            continue;
+                        }
+                    };
+
+                    if let Some(response) = &trap.response {
+                        respond(&socket, response, peer).await;
+                    }
+
+                    let log = self.build_log(trap, peer);
+                    events_received.emit(CountByteSize(1, log.estimated_json_encoded_size_of()));
+
+                    tokio::select! {
+                        result = out.send_event(log) => {
+                            if result.is_err() {
+                                emit!(StreamClosedError { count: 1 });
+                                return Ok(());
+                            }
+                        }
+                        _ = &mut shutdown => return Ok(()),
+                    }
+                }
+                _ = &mut shutdown => return Ok(()),
+            }
+        }
+    }
+
+    fn decode(&self, input: &[u8]) -> Result<Trap, TrapError> {
+        match Message::parse(input)? {
+            Message::V2c {
+                community,
+                pdu,
+                pdu_tag,
+            } => {
+                if !self.communities.is_empty()
+                    && !self.communities.iter().any(|known| known == community)
+                {
+                    return Err(TrapError::UnknownCommunity);
+                }
+
+                let parsed = Pdu::parse(pdu_tag, pdu)?;
+                let response = parsed
+                    .inform
+                    .then(|| message::inform_response(community, pdu))
+                    .transpose()?;
+                Ok(Trap {
+                    version: "2c",
+                    community: Some(String::from_utf8_lossy(community).into_owned()),
+                    user: None,
+                    pdu: parsed,
+                    response,
+                })
+            }
+            Message::V3 {
+                flags,
+                security,
+                data,
+            } => {
+                let (user, scoped_pdu) = self.usm.process(input, flags, &security, data)?;
+                let (pdu_tag, pdu) = message::parse_scoped_pdu(&scoped_pdu)?;
+                // Acknowledging SNMPv3 informs requires the source to act as an authoritative SNMP
+                // engine, so they are only received.
+                Ok(Trap {
+                    version: "3",
+                    community: None,
+                    user: Some(user),
+                    pdu: Pdu::parse(pdu_tag, pdu)?,
+                    response: None,
+                })
+            }
+        }
+    }
+
+    fn build_log(&self, trap: Trap, peer: SocketAddr) -> LogEvent {
+        let mut map = ObjectMap::new();
+        map.insert("version".into(), trap.version.into());
+        if let Some(community) = trap.community {
+            map.insert("community".into(), community.into());
+        }
+        if let Some(user) = trap.user {
+            map.insert("user".into(), user.into());
+        }
+
+        let mut variables = ObjectMap::new();
+        for (oid, value) in trap.pdu.variables {
+            match (oid.as_slice(), value) {
+                (SYS_UP_TIME, VarValue::Unsigned(ticks)) => {
+                    map.insert("uptime".into(), unsigned_value(ticks));
+                }
+                (SNMP_TRAP_OID, VarValue::ObjectIdentifier(trap_oid)) => {
+                    map.insert("trap_oid".into(), self.mib.name(&trap_oid).into());
+                }
+                (_, value) => {
+                    variables.insert(self.mib.name(&oid).into(), self.value(value));
+                }
+            }
+        }
+        map.insert("variables".into(), Value::Object(variables));
+
+        let mut log = LogEvent::from(map);
+        self.log_namespace.insert_source_metadata(
+            SnmpTrapConfig::NAME,
+            &mut log,
+            self.host_key.path.as_ref().map(LegacyKey::Overwrite),
+            path!("host"),
+            peer.ip().to_string(),
+        );
+        self.log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            SnmpTrapConfig::NAME,
+            Utc::now(),
+        );
+        log
+    }
+
+    fn value(&self, value: VarValue) -> Value {
+        match value {
+            VarValue::Integer(value) => value.into(),
+            VarValue::Unsigned(value) => unsigned_value(value),
+            VarValue::Bytes(bytes) => Value::Bytes(bytes.into()),
+            VarValue::ObjectIdentifier(oid) => self.mib.name(&oid).into(),
+            VarValue::IpAddress(address) => address.to_string().into(),
+            VarValue::Null => Value::Null,
+        }
+    }
+}
+
+/// Converts an unsigned value, which only doesn't fit an integer for very large 64-bit counters.
+fn unsigned_value(value: u64) -> Value {
+    i64::try_from(value).map_or_else(|_| value.to_string().into(), Value::from)
+}
+
+async fn respond(socket: &UdpSocket, response: &[u8], peer: SocketAddr) {
+    if let Err(error) = socket.send_to(response, peer).await {
+        warn!(
+            message = "Failed acknowledging SNMP inform.",
+            %error,
+            %peer,
+            internal_log_rate_limit = true
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::timeout;
+
+    use super::{
+        message::tests::{link_down_pdu, v2c_message},
+        *,
+    };
+    use crate::{
+        event::Event,
+        test_util::{
+            collect_n,
+            components::{assert_source_compliance, SOCKET_PUSH_SOURCE_TAGS},
+            next_addr, temp_dir,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SnmpTrapConfig>();
+    }
+
+    async fn run_source(config: SnmpTrapConfig, messages: Vec<Vec<u8>>) -> (Vec<Event>, UdpSocket) {
+        let address = next_addr();
+        let config = SnmpTrapConfig {
+            address: address.into(),
+            ..config
+        };
+
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let events = assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            for message in &messages {
+                socket.send_to(message, address).await.unwrap();
+            }
+            collect_n(rx, 1).await
+        })
+        .await;
+        (events, socket)
+    }
+
+    #[tokio::test]
+    async fn receives_v2c_trap() {
+        let mib_dir = temp_dir();
+        std::fs::create_dir_all(&mib_dir).unwrap();
+        std::fs::write(mib_dir.join("IF-MIB.txt"), mib::tests::IF_MIB).unwrap();
+
+        let config = SnmpTrapConfig {
+            communities: vec!["public".to_owned().into()],
+            mib_paths: vec![mib_dir],
+            ..Default::default()
+        };
+        let messages = vec![
+            v2c_message(b"private", link_down_pdu(message::TRAP_V2_PDU)),
+            v2c_message(b"public", link_down_pdu(message::TRAP_V2_PDU)),
+        ];
+        let (events, _) = run_source(config, messages).await;
+
+        let log = events[0].as_log();
+        assert_eq!(log["version"], "2c".into());
+        assert_eq!(log["community"], "public".into());
+        assert_eq!(log["trap_oid"], "linkDown".into());
+        assert_eq!(log["uptime"], 12345678.into());
+        assert_eq!(log["host"], "127.0.0.1".into());
+        assert_eq!(log["source_type"], "snmp_trap".into());
+
+        let variables = log["variables"].as_object().unwrap();
+        assert_eq!(variables["ifIndex.2"], 2.into());
+        assert_eq!(variables["ifDescr.2"], "eth0".into());
+        assert_eq!(variables["enterprises.9999.1"], "10.0.0.1".into());
+    }
+
+    #[tokio::test]
+    async fn acknowledges_v2c_inform() {
+        let messages = vec![v2c_message(
+            b"public",
+            link_down_pdu(message::INFORM_REQUEST_PDU),
+        )];
+        let (events, socket) = run_source(SnmpTrapConfig::default(), messages).await;
+        assert_eq!(events.len(), 1);
+
+        let mut buf = vec![0; MAX_MESSAGE_SIZE];
+        let (len, _) = timeout(Duration::from_secs(1), socket.recv_from(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            &buf[..len],
+            v2c_message(b"public", link_down_pdu(message::GET_RESPONSE_PDU))
+        );
+    }
+
+    #[tokio::test]
+    async fn receives_v3_trap() {
+        let user = usm::tests::user(
+            Some(usm::AuthProtocol::Sha256),
+            Some(usm::PrivacyProtocol::Aes),
+        );
+        let config = SnmpTrapConfig {
+            users: vec![user.clone()],
+            ..Default::default()
+        };
+        let (events, _) = run_source(config, vec![usm::tests::v3_message(&user)]).await;
+
+        let log = events[0].as_log();
+        assert_eq!(log["version"], "3".into());
+        assert_eq!(log["user"], user.name.as_str().into());
+        assert_eq!(log["trap_oid"], "linkDown".into());
+    }
+}
@@ -0,0 +1,544 @@
+//! The User-based Security Model (USM) of SNMPv3, as described in [RFC 3414][rfc3414], with the
+//! AES privacy protocol of [RFC 3826][rfc3826] and the SHA-2 authentication protocols of
+//! [RFC 7860][rfc7860].
+//!
+//! [rfc3414]: https://datatracker.ietf.org/doc/html/rfc3414
+//! [rfc3826]: https://datatracker.ietf.org/doc/html/rfc3826
+//! [rfc7860]: https://datatracker.ietf.org/doc/html/rfc7860
+
+use std::{borrow::Cow, collections::HashMap};
+
+use cfb_mode::cipher::{AsyncStreamCipher, KeyIvInit};
+use hmac::{
+    digest::{core_api::BlockSizeUser, Digest},
+    Mac, SimpleHmac,
+};
+use vector_lib::configurable::configurable_component;
+use vector_lib::sensitive_string::SensitiveString;
+
+use super::{
+    ber::{self, Reader},
+    message::{ScopedData, SecurityParameters, TrapError, FLAG_AUTH, FLAG_PRIV},
+};
+
+/// The minimum length of passphrases, as required by RFC 3414.
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// A SNMPv3 user allowed to send traps.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct UserConfig {
+    /// The name of the user.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub name: String,
+
+    #[configurable(derived)]
+    pub auth: Option<AuthConfig>,
+
+    #[configurable(derived)]
+    pub privacy: Option<PrivacyConfig>,
+}
+
+/// Authentication of the traps sent by the user.
+///
+/// When unset, traps of the user must be sent without authentication.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AuthConfig {
+    #[configurable(derived)]
+    pub protocol: AuthProtocol,
+
+    /// The authentication passphrase of the user.
+    #[configurable(metadata(docs::examples = "${SNMP_AUTH_PASSPHRASE}"))]
+    pub passphrase: SensitiveString,
+}
+
+/// The authentication protocol of the user.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthProtocol {
+    /// HMAC-MD5-96.
+    Md5,
+
+    /// HMAC-SHA-96.
+    Sha,
+
+    /// HMAC-SHA-224.
+    Sha224,
+
+    /// HMAC-SHA-256.
+    Sha256,
+
+    /// HMAC-SHA-384.
+    Sha384,
+
+    /// HMAC-SHA-512.
+    Sha512,
+}
+
+/// Encryption of the traps sent by the user.
+///
+/// When unset, traps of the user must be sent unencrypted. Requires `auth` to be set.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PrivacyConfig {
+    #[configurable(derived)]
+    pub protocol: PrivacyProtocol,
+
+    /// The privacy passphrase of the user.
+    #[configurable(metadata(docs::examples = "${SNMP_PRIVACY_PASSPHRASE}"))]
+    pub passphrase: SensitiveString,
+}
+
+/// The privacy protocol of the user.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyProtocol {
+    /// AES-128 in CFB mode.
+    Aes,
+}
+
+/// Runs `$body` with `$digest` aliased to the hash function of the protocol.
+macro_rules! with_digest {
+    ($protocol:expr, $digest:ident => $body:expr) => {
+        match $protocol {
+            AuthProtocol::Md5 => {
+                type $digest = md5::Md5;
+                $body
+            }
+            AuthProtocol::Sha => {
+                type $digest = sha1::Sha1;
+                $body
+            }
+            AuthProtocol::Sha224 => {
+                type $digest = sha2::Sha224;
+                $body
+            }
+            AuthProtocol::Sha256 => {
+                type $digest = sha2::Sha256;
+                $body
+            }
+            AuthProtocol::Sha384 => {
+                type $digest = sha2::Sha384;
+                $body
+            }
+            AuthProtocol::Sha512 => {
+                type $digest = sha2::Sha512;
+                $body
+            }
+        }
+    };
+}
+
+impl AuthProtocol {
+    /// The length of the truncated HMAC sent in messages.
+    const fn mac_len(self) -> usize {
+        match self {
+            Self::Md5 | Self::Sha => 12,
+            Self::Sha224 => 16,
+            Self::Sha256 => 24,
+            Self::Sha384 => 32,
+            Self::Sha512 => 48,
+        }
+    }
+
+    /// Hashes a passphrase into a key, which still has to be localized to an engine.
+    fn password_to_key(self, passphrase: &[u8]) -> Vec<u8> {
+        with_digest!(self, D => password_to_key::<D>(passphrase))
+    }
+
+    fn localize_key(self, key: &[u8], engine_id: &[u8]) -> Vec<u8> {
+        with_digest!(self, D => D::new()
+            .chain_update(key)
+            .chain_update(engine_id)
+            .chain_update(key)
+            .finalize()
+            .to_vec())
+    }
+
+    fn hmac(self, key: &[u8], message: &[u8]) -> Vec<u8> {
+        with_digest!(self, D => compute_hmac::<D>(key, message))
+    }
+}
+
+fn password_to_key<D: Digest>(passphrase: &[u8]) -> Vec<u8> {
+    // The passphrase is repeated over a megabyte, which is hashed in blocks of 64 bytes.
+    let mut hasher = D::new();
+    let mut repeated = passphrase.iter().cycle();
+    let mut block = [0; 64];
+    for _ in 0..(1024 * 1024 / block.len()) {
+        block.fill_with(|| *repeated.next().expect("passphrase is not empty"));
+        hasher.update(block);
+    }
+    hasher.finalize().to_vec()
+}
+
+fn compute_hmac<D: Digest + BlockSizeUser>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = <SimpleHmac<D> as Mac>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// The keys of a user, before localization.
+#[derive(Clone)]
+struct User {
+    auth: Option<(AuthProtocol, Vec<u8>)>,
+    privacy: Option<(PrivacyProtocol, Vec<u8>)>,
+}
+
+/// Authenticates and decrypts SNMPv3 messages of the configured users.
+#[derive(Clone, Default)]
+pub struct Usm {
+    users: HashMap<Vec<u8>, User>,
+}
+
+impl Usm {
+    pub fn new(users: &[UserConfig]) -> crate::Result<Self> {
+        let mut usm = Self::default();
+        for user in users {
+            let auth = user
+                .auth
+                .as_ref()
+                .map(|auth| {
+                    let passphrase = check_passphrase(&user.name, &auth.passphrase, "auth")?;
+                    Ok::<_, String>((auth.protocol, auth.protocol.password_to_key(passphrase)))
+                })
+                .transpose()?;
+            let privacy = match (&user.privacy, &auth) {
+                (None, _) => None,
+                (Some(privacy), Some((auth_protocol, _))) => {
+                    let passphrase = check_passphrase(&user.name, &privacy.passphrase, "privacy")?;
+                    Some((privacy.protocol, auth_protocol.password_to_key(passphrase)))
+                }
+                (Some(_), None) => {
+                    return Err(format!(
+                        "SNMP user {:?} must set `auth` to set `privacy`",
+                        user.name
+                    )
+                    .into())
+                }
+            };
+
+            let user_name = user.name.as_bytes().to_vec();
+            if usm
+                .users
+                .insert(user_name, User { auth, privacy })
+                .is_some()
+            {
+                return Err(
+                    format!("SNMP user {:?} is configured more than once", user.name).into(),
+                );
+            }
+        }
+        Ok(usm)
+    }
+
+    /// Checks the authenticity of a message and decrypts its data, returning the name of the user
+    /// and the contents of the scoped PDU.
+    pub fn process<'a>(
+        &self,
+        message: &[u8],
+        flags: u8,
+        security: &SecurityParameters<'_>,
+        data: ScopedData<'a>,
+    ) -> Result<(String, Cow<'a, [u8]>), TrapError> {
+        let name = String::from_utf8_lossy(security.user_name).into_owned();
+        let Some(user) = self.users.get(security.user_name) else {
+            return Err(TrapError::UnknownUser { user: name });
+        };
+
+        if user.auth.is_some() != (flags & FLAG_AUTH != 0)
+            || user.privacy.is_some() != (flags & FLAG_PRIV != 0)
+        {
+            return Err(TrapError::UnsupportedSecurityLevel { user: name });
+        }
+
+        if let Some((protocol, key)) = &user.auth {
+            let key = protocol.localize_key(key, security.engine_id);
+            if !authenticate(*protocol, &key, message, security.auth_params) {
+                return Err(TrapError::AuthenticationFailed { user: name });
+            }
+        }
+
+        let contents = match (&user.privacy, data) {
+            (None, ScopedData::Plaintext(contents)) => Cow::Borrowed(contents),
+            (Some((PrivacyProtocol::Aes, key)), ScopedData::Encrypted(encrypted)) => {
+                let (auth_protocol, _) = user.auth.as_ref().expect("privacy requires auth");
+                let key = auth_protocol.localize_key(key, security.engine_id);
+                // Decrypting with the wrong key yields garbage rather than a scoped PDU.
+                let contents = decrypt_aes(&key, security, encrypted)
+                    .and_then(|scoped_pdu| {
+                        Reader::new(&scoped_pdu)
+                            .read(ber::SEQUENCE)
+                            .ok()
+                            .map(<[u8]>::to_vec)
+                    })
+                    .ok_or_else(|| TrapError::DecryptionFailed { user: name.clone() })?;
+                Cow::Owned(contents)
+            }
+            _ => return Err(TrapError::UnsupportedSecurityLevel { user: name }),
+        };
+
+        Ok((name, contents))
+    }
+}
+
+fn check_passphrase<'a>(
+    user: &str,
+    passphrase: &'a SensitiveString,
+    option: &str,
+) -> Result<&'a [u8], String> {
+    let passphrase = passphrase.inner().as_bytes();
+    if passphrase.len() < MIN_PASSPHRASE_LEN {
+        return Err(format!(
+            "The {option} passphrase of SNMP user {user:?} must be at least {MIN_PASSPHRASE_LEN} characters long"
+        ));
+    }
+    Ok(passphrase)
+}
+
+/// Checks the HMAC of a message, computed with its authentication parameters zeroed.
+fn authenticate(protocol: AuthProtocol, key: &[u8], message: &[u8], auth_params: &[u8]) -> bool {
+    if auth_params.len() != protocol.mac_len() {
+        return false;
+    }
+    // The authentication parameters are borrowed from the message itself.
+    let Some(offset) = (auth_params.as_ptr() as usize).checked_sub(message.as_ptr() as usize)
+    else {
+        return false;
+    };
+    if offset + auth_params.len() > message.len() {
+        return false;
+    }
+
+    let mut zeroed = message.to_vec();
+    zeroed[offset..offset + auth_params.len()].fill(0);
+    let mac = protocol.hmac(key, &zeroed);
+
+    // Compare in constant time.
+    mac[..auth_params.len()]
+        .iter()
+        .zip(auth_params)
+        .fold(0, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+fn decrypt_aes(key: &[u8], security: &SecurityParameters<'_>, encrypted: &[u8]) -> Option<Vec<u8>> {
+    if key.len() < 16 || security.priv_params.len() != 8 {
+        return None;
+    }
+    let mut iv = [0; 16];
+    iv[..4].copy_from_slice(&security.engine_boots.to_be_bytes());
+    iv[4..8].copy_from_slice(&security.engine_time.to_be_bytes());
+    iv[8..].copy_from_slice(security.priv_params);
+
+    let mut decrypted = encrypted.to_vec();
+    cfb_mode::Decryptor::<aes::Aes128>::new_from_slices(&key[..16], &iv)
+        .ok()?
+        .decrypt(&mut decrypted);
+    Some(decrypted)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::sources::snmp_trap::{
+        ber::tests::encode_integer,
+        message::{parse_scoped_pdu, tests::link_down_pdu, Message, Pdu},
+    };
+
+    pub const ENGINE_ID: &[u8] = b"\x80\x00\x1f\x88\x04vector";
+
+    pub fn user(auth: Option<AuthProtocol>, privacy: Option<PrivacyProtocol>) -> UserConfig {
+        UserConfig {
+            name: "vector".into(),
+            auth: auth.map(|protocol| AuthConfig {
+                protocol,
+                passphrase: "authpassphrase".to_string().into(),
+            }),
+            privacy: privacy.map(|protocol| PrivacyConfig {
+                protocol,
+                passphrase: "privpassphrase".to_string().into(),
+            }),
+        }
+    }
+
+    /// Encodes a SNMPv3 trap from the given user, as an agent would.
+    pub fn v3_message(user: &UserConfig) -> Vec<u8> {
+        let flags = user.auth.as_ref().map_or(0, |_| FLAG_AUTH)
+            | user.privacy.as_ref().map_or(0, |_| FLAG_PRIV);
+        let (boots, time, salt) = (3, 1234, *b"saltsalt");
+        let auth = user.auth.as_ref().map(|auth| {
+            let key = auth
+                .protocol
+                .password_to_key(auth.passphrase.inner().as_bytes());
+            (auth.protocol, auth.protocol.localize_key(&key, ENGINE_ID))
+        });
+
+        let scoped_pdu = ber::encode(
+            ber::SEQUENCE,
+            &[
+                ber::encode(ber::OCTET_STRING, ENGINE_ID),
+                ber::encode(ber::OCTET_STRING, b""),
+                link_down_pdu(0xa7),
+            ]
+            .concat(),
+        );
+        let (data, priv_params) = match (&user.privacy, &auth) {
+            (Some(privacy), Some((protocol, _))) => {
+                let key = protocol.localize_key(
+                    &protocol.password_to_key(privacy.passphrase.inner().as_bytes()),
+                    ENGINE_ID,
+                );
+                let mut iv = [0; 16];
+                iv[..4].copy_from_slice(&u32::to_be_bytes(boots));
+                iv[4..8].copy_from_slice(&u32::to_be_bytes(time));
+                iv[8..].copy_from_slice(&salt);
+                let mut encrypted = scoped_pdu;
+                cfb_mode::Encryptor::<aes::Aes128>::new_from_slices(&key[..16], &iv)
+                    .unwrap()
+                    .encrypt(&mut encrypted);
+                (ber::encode(ber::OCTET_STRING, &encrypted), salt.to_vec())
+            }
+            _ => (scoped_pdu, Vec::new()),
+        };
+
+        let build = |auth_params: &[u8]| {
+            let params = ber::encode(
+                ber::SEQUENCE,
+                &[
+                    ber::encode(ber::OCTET_STRING, ENGINE_ID),
+                    encode_integer(boots.into()),
+                    encode_integer(time.into()),
+                    ber::encode(ber::OCTET_STRING, user.name.as_bytes()),
+                    ber::encode(ber::OCTET_STRING, auth_params),
+                    ber::encode(ber::OCTET_STRING, &priv_params),
+                ]
+                .concat(),
+            );
+            let global = ber::encode(
+                ber::SEQUENCE,
+                &[
+                    encode_integer(1),
+                    encode_integer(65507),
+                    ber::encode(ber::OCTET_STRING, &[flags]),
+                    encode_integer(3),
+                ]
+                .concat(),
+            );
+            ber::encode(
+                ber::SEQUENCE,
+                &[
+                    encode_integer(3),
+                    global,
+                    ber::encode(ber::OCTET_STRING, &params),
+                    data.clone(),
+                ]
+                .concat(),
+            )
+        };
+
+        match auth {
+            Some((protocol, key)) => {
+                let unsigned = build(&vec![0; protocol.mac_len()]);
+                let mac = protocol.hmac(&key, &unsigned);
+                build(&mac[..protocol.mac_len()])
+            }
+            None => build(&[]),
+        }
+    }
+
+    fn process(usm: &Usm, input: &[u8]) -> Result<(String, Vec<u8>), TrapError> {
+        let Message::V3 {
+            flags,
+            security,
+            data,
+        } = Message::parse(input)?
+        else {
+            panic!("expected a SNMPv3 message");
+        };
+        usm.process(input, flags, &security, data)
+            .map(|(user, contents)| (user, contents.into_owned()))
+    }
+
+    #[test]
+    fn localizes_keys() {
+        // The test vectors of RFC 3414, appendix A.3.
+        let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+        let key = AuthProtocol::Md5.password_to_key(b"maplesyrup");
+        assert_eq!(
+            AuthProtocol::Md5.localize_key(&key, &engine_id),
+            [
+                0x52, 0x6f, 0x5e, 0xed, 0x9f, 0xcc, 0xe2, 0x6f, 0x89, 0x64, 0xc2, 0x93, 0x07, 0x87,
+                0xd8, 0x2b
+            ]
+        );
+        let key = AuthProtocol::Sha.password_to_key(b"maplesyrup");
+        assert_eq!(
+            AuthProtocol::Sha.localize_key(&key, &engine_id),
+            [
+                0x66, 0x95, 0xfe, 0xbc, 0x92, 0x88, 0xe3, 0x62, 0x82, 0x23, 0x5f, 0xc7, 0x15, 0x1f,
+                0x12, 0x84, 0x97, 0xb3, 0x8f, 0x3f
+            ]
+        );
+    }
+
+    #[test]
+    fn processes_messages_at_all_security_levels() {
+        for user in [
+            user(None, None),
+            user(Some(AuthProtocol::Md5), None),
+            user(Some(AuthProtocol::Sha256), None),
+            user(Some(AuthProtocol::Sha), Some(PrivacyProtocol::Aes)),
+            user(Some(AuthProtocol::Sha512), Some(PrivacyProtocol::Aes)),
+        ] {
+            let usm = Usm::new(&[user.clone()]).unwrap();
+            let (name, contents) = process(&usm, &v3_message(&user)).unwrap();
+            assert_eq!(name, "vector");
+            let (tag, pdu) = parse_scoped_pdu(&contents).unwrap();
+            assert_eq!(Pdu::parse(tag, pdu).unwrap().variables.len(), 5);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_messages() {
+        let user = user(Some(AuthProtocol::Sha), Some(PrivacyProtocol::Aes));
+        let usm = Usm::new(&[user.clone()]).unwrap();
+
+        let mut tampered = v3_message(&user);
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert!(matches!(
+            process(&usm, &tampered),
+            Err(TrapError::AuthenticationFailed { .. })
+        ));
+
+        let unauthenticated = v3_message(&self::user(None, None));
+        assert!(matches!(
+            process(&usm, &unauthenticated),
+            Err(TrapError::UnsupportedSecurityLevel { .. })
+        ));
+
+        let mut unknown = self::user(None, None);
+        unknown.name = "unknown".into();
+        assert!(matches!(
+            process(&usm, &v3_message(&unknown)),
+            Err(TrapError::UnknownUser { .. })
+        ));
+    }
+
+    #[test]
+    fn validates_users() {
+        let mut short = user(Some(AuthProtocol::Sha), None);
+        short.auth.as_mut().unwrap().passphrase = "short".to_string().into();
+        assert!(Usm::new(&[short]).is_err());
+
+        assert!(Usm::new(&[user(None, Some(PrivacyProtocol::Aes))]).is_err());
+        assert!(Usm::new(&[user(None, None), user(None, None)]).is_err());
+    }
+}
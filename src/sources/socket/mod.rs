@@ -311,7 +311,7 @@ impl SourceConfig for SocketConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        matches!(&self.mode, Mode::Tcp(config) if config.back_channel.is_some())
     }
 }
 
@@ -370,10 +370,10 @@ mod test {
     use super::{tcp::TcpConfig, udp::UdpConfig, SocketConfig};
     use crate::{
         config::{log_schema, ComponentKey, GlobalOptions, SourceConfig, SourceContext},
-        event::{Event, LogEvent},
+        event::{Event, EventStatus, LogEvent},
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
         sinks::util::tcp::TcpSinkConfig,
-        sources::util::net::SocketListenAddr,
+        sources::util::net::{SocketListenAddr, TcpBackChannelConfig},
         test_util::{
             collect_n, collect_n_limited,
             components::{assert_source_compliance, SOCKET_PUSH_SOURCE_TAGS},
@@ -692,6 +692,65 @@ mod test {
         .await;
     }
 
+    async fn back_channel_connection(status: EventStatus) -> TcpStream {
+        let (tx, mut rx) = SourceSender::new_test_finalize(status);
+        let addr = next_addr();
+
+        let mut config = TcpConfig::from_address(addr.into());
+        config.back_channel = Some(TcpBackChannelConfig::default());
+        let server = SocketConfig::from(config)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        tokio::spawn(async move { while rx.next().await.is_some() {} });
+
+        wait_for_tcp(addr).await;
+        TcpStream::connect(addr).await.unwrap()
+    }
+
+    async fn read_response(stream: &mut TcpStream, len: usize) -> String {
+        let mut response = vec![0; len];
+        timeout(Duration::from_secs(5), stream.read_exact(&mut response))
+            .await
+            .unwrap()
+            .unwrap();
+        String::from_utf8(response).unwrap()
+    }
+
+    #[tokio::test]
+    async fn tcp_back_channel_acknowledges_delivered_frames() {
+        let mut stream = back_channel_connection(EventStatus::Delivered).await;
+
+        tokio::io::AsyncWriteExt::write_all(&mut stream, b"foo\n")
+            .await
+            .unwrap();
+        assert_eq!(read_response(&mut stream, 7).await, "5 ack 1");
+
+        // The connection is read further once the previous frames are acknowledged.
+        tokio::io::AsyncWriteExt::write_all(&mut stream, b"bar\n")
+            .await
+            .unwrap();
+        assert_eq!(read_response(&mut stream, 7).await, "5 ack 1");
+    }
+
+    #[tokio::test]
+    async fn tcp_back_channel_rejects_and_closes() {
+        let mut stream = back_channel_connection(EventStatus::Rejected).await;
+
+        tokio::io::AsyncWriteExt::write_all(&mut stream, b"foo\n")
+            .await
+            .unwrap();
+        assert_eq!(read_response(&mut stream, 8).await, "6 nack 1");
+
+        let mut rest = Vec::new();
+        timeout(Duration::from_secs(5), stream.read_to_end(&mut rest))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(rest.is_empty());
+    }
+
     #[tokio::test]
     async fn tcp_shutdown_simple() {
         assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async {
@@ -13,7 +13,7 @@ use crate::{
     codecs::Decoder,
     event::Event,
     serde::default_decoding,
-    sources::util::net::{SocketListenAddr, TcpNullAcker, TcpSource},
+    sources::util::net::{SocketListenAddr, TcpBackChannelAcker, TcpBackChannelConfig, TcpSource},
     tcp::TcpKeepaliveConfig,
     tls::TlsSourceConfig,
 };
@@ -78,6 +78,9 @@ pub struct TcpConfig {
     #[configurable(metadata(docs::type_unit = "connections"))]
     pub connection_limit: Option<u32>,
 
+    #[configurable(derived)]
+    pub back_channel: Option<TcpBackChannelConfig>,
+
     #[configurable(derived)]
     pub(super) framing: Option<FramingConfig>,
 
@@ -114,6 +117,7 @@ impl TcpConfig {
             framing: None,
             decoding: default_decoding(),
             connection_limit: None,
+            back_channel: None,
             log_namespace: None,
         }
     }
@@ -210,7 +214,7 @@ impl TcpSource for RawTcpSource {
     type Error = vector_lib::codecs::decoding::Error;
     type Item = SmallVec<[Event; 1]>;
     type Decoder = Decoder;
-    type Acker = TcpNullAcker;
+    type Acker = Option<TcpBackChannelAcker>;
 
     fn decoder(&self) -> Self::Decoder {
         self.decoder.clone()
@@ -255,7 +259,13 @@ impl TcpSource for RawTcpSource {
         }
     }
 
-    fn build_acker(&self, _: &[Self::Item]) -> Self::Acker {
-        TcpNullAcker
+    fn build_acker(&self, frames: &[Self::Item]) -> Self::Acker {
+        self.config
+            .back_channel
+            .map(|_| TcpBackChannelAcker::new(frames.len()))
+    }
+
+    fn back_channel(&self) -> Option<TcpBackChannelConfig> {
+        self.config.back_channel
     }
 }
@@ -1342,6 +1342,8 @@ mod tests {
         HecLogsSinkConfig {
             default_token: TOKEN.to_owned().into(),
             endpoint: format!("http://{}", address),
+            endpoints: Vec::new(),
+            endpoint_health: None,
             host_key: None,
             indexed_fields: vec![],
             index: None,
@@ -0,0 +1,587 @@
+use std::{net::SocketAddr, time::Duration};
+
+use bytes::{Bytes, BytesMut};
+use chrono::{DateTime, TimeZone, Utc};
+use smallvec::SmallVec;
+use tokio_util::codec::Decoder;
+use vector_lib::codecs::BytesDeserializerConfig;
+use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::ipallowlist::IpAllowlistConfig;
+use vector_lib::lookup::{owned_value_path, path};
+use vector_lib::schema::{meaning, Definition};
+use vrl::value::{kind::Collection, Kind, ObjectMap, Value};
+
+use self::protocol::{capabilities_response, Message, MessageDecoder, CAPABILITIES_KEY};
+use super::util::net::{SocketListenAddr, TcpSource, TcpSourceAck, TcpSourceAcker};
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Resource, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext, SourceOutput,
+    },
+    event::{Event, LogEvent},
+    serde::bool_or_struct,
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, TlsSourceConfig},
+};
+
+mod protocol;
+
+pub use self::protocol::DecodeError;
+
+pub const INDEX: &str = "splunk_index";
+pub const SOURCE: &str = "splunk_source";
+pub const SOURCETYPE: &str = "splunk_sourcetype";
+
+/// Configuration for the `splunk_s2s` source.
+#[configurable_component(source(
+    "splunk_s2s",
+    "Receive events from Splunk forwarders over the Splunk-to-Splunk protocol."
+))]
+#[derive(Clone, Debug)]
+pub struct SplunkS2sConfig {
+    #[configurable(derived)]
+    address: SocketListenAddr,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    keepalive: Option<TcpKeepaliveConfig>,
+
+    #[configurable(derived)]
+    pub permit_origin: Option<IpAllowlistConfig>,
+
+    #[configurable(derived)]
+    tls: Option<TlsSourceConfig>,
+
+    /// The size of the receive buffer used for each connection.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 65536))]
+    #[configurable(metadata(docs::advanced))]
+    receive_buffer_bytes: Option<usize>,
+
+    /// The maximum number of TCP connections that are allowed at any given time.
+    #[configurable(metadata(docs::type_unit = "connections"))]
+    #[configurable(metadata(docs::advanced))]
+    connection_limit: Option<u32>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+impl SplunkS2sConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        let tls_client_metadata_path = self
+            .tls
+            .as_ref()
+            .and_then(|tls| tls.client_metadata_key.as_ref())
+            .and_then(|k| k.path.clone())
+            .map(LegacyKey::Overwrite);
+
+        BytesDeserializerConfig
+            .schema_definition(log_namespace)
+            .with_source_metadata(
+                Self::NAME,
+                log_schema()
+                    .timestamp_key()
+                    .cloned()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp().or_undefined(),
+                Some(meaning::TIMESTAMP),
+            )
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                log_schema()
+                    .host_key()
+                    .cloned()
+                    .map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("host"),
+                Kind::bytes(),
+                Some(meaning::HOST),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!(INDEX))),
+                &owned_value_path!("index"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!(SOURCE))),
+                &owned_value_path!("source"),
+                Kind::bytes().or_undefined(),
+                Some(meaning::SERVICE),
+            )
+            // Not to be confused with `source_type`.
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!(SOURCETYPE))),
+                &owned_value_path!("sourcetype"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("fields"))),
+                &owned_value_path!("fields"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                tls_client_metadata_path,
+                &owned_value_path!("tls_client_metadata"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                None,
+            )
+    }
+}
+
+impl Default for SplunkS2sConfig {
+    fn default() -> Self {
+        Self {
+            address: SocketListenAddr::SocketAddr("0.0.0.0:9997".parse().unwrap()),
+            keepalive: None,
+            permit_origin: None,
+            tls: None,
+            receive_buffer_bytes: None,
+            connection_limit: None,
+            acknowledgements: Default::default(),
+            log_namespace: None,
+        }
+    }
+}
+
+impl GenerateConfig for SplunkS2sConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(SplunkS2sConfig::default()).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "splunk_s2s")]
+impl SourceConfig for SplunkS2sConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let source = SplunkS2sSource { log_namespace };
+        let shutdown_secs = Duration::from_secs(30);
+        let tls_config = self.tls.as_ref().map(|tls| tls.tls_config.clone());
+        let tls_client_metadata_key = self
+            .tls
+            .as_ref()
+            .and_then(|tls| tls.client_metadata_key.clone())
+            .and_then(|k| k.path);
+
+        let tls = MaybeTlsSettings::from_config(&tls_config, true)?;
+        source.run(
+            self.address,
+            self.keepalive,
+            shutdown_secs,
+            tls,
+            tls_client_metadata_key,
+            self.receive_buffer_bytes,
+            None,
+            cx,
+            self.acknowledgements,
+            self.connection_limit,
+            self.permit_origin.clone().map(Into::into),
+            SplunkS2sConfig::NAME,
+            log_namespace,
+        )
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            self.schema_definition(global_log_namespace.merge(self.log_namespace)),
+        )]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.as_tcp_resource()]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SplunkS2sSource {
+    log_namespace: LogNamespace,
+}
+
+impl TcpSource for SplunkS2sSource {
+    type Error = DecodeError;
+    type Item = S2sFrame;
+    type Decoder = S2sDecoder;
+    type Acker = S2sAcker;
+
+    fn decoder(&self) -> Self::Decoder {
+        S2sDecoder {
+            inner: MessageDecoder::default(),
+            log_namespace: self.log_namespace,
+        }
+    }
+
+    fn build_acker(&self, frames: &[Self::Item]) -> Self::Acker {
+        S2sAcker {
+            capabilities: frames.iter().any(|frame| frame.capabilities),
+        }
+    }
+}
+
+/// The events of a single message, or the capabilities of the forwarder.
+#[derive(Debug)]
+struct S2sFrame {
+    events: SmallVec<[Event; 1]>,
+    capabilities: bool,
+}
+
+impl From<S2sFrame> for SmallVec<[Event; 1]> {
+    fn from(frame: S2sFrame) -> Self {
+        frame.events
+    }
+}
+
+/// Answers the capabilities of v3 forwarders, which don't send events until they are accepted.
+///
+/// Forwarders sending events with `useACK` enabled expect acknowledgements over the protocol,
+/// which aren't supported, so there is nothing else to answer.
+struct S2sAcker {
+    capabilities: bool,
+}
+
+impl TcpSourceAcker for S2sAcker {
+    fn build_ack(self, _ack: TcpSourceAck) -> Option<Bytes> {
+        self.capabilities.then(capabilities_response)
+    }
+}
+
+#[derive(Debug)]
+struct S2sDecoder {
+    inner: MessageDecoder,
+    log_namespace: LogNamespace,
+}
+
+impl Decoder for S2sDecoder {
+    type Item = (S2sFrame, usize);
+    type Error = DecodeError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let Some((mut message, byte_size)) = self.inner.decode(src)? else {
+            return Ok(None);
+        };
+
+        let mut frame = S2sFrame {
+            events: SmallVec::new(),
+            capabilities: message.get(CAPABILITIES_KEY).is_some(),
+        };
+        // Control messages start with `__s2s_` and carry no event.
+        let control = message
+            .fields
+            .iter()
+            .any(|(key, _)| key.starts_with("__s2s_"));
+        if let Some(raw) = message.raw.take().filter(|raw| !control && !raw.is_empty()) {
+            let log = self.build_log(&message, raw);
+            frame.events.push(Event::Log(log));
+        }
+        Ok(Some((frame, byte_size)))
+    }
+}
+
+impl S2sDecoder {
+    fn build_log(&self, message: &Message, raw: Bytes) -> LogEvent {
+        let log_namespace = self.log_namespace;
+        let mut log = log_namespace.new_log_from_data(raw);
+
+        if let Some(timestamp) = parse_timestamp(message) {
+            log_namespace.insert_source_metadata(
+                SplunkS2sConfig::NAME,
+                &mut log,
+                log_schema().timestamp_key().map(LegacyKey::Overwrite),
+                path!("timestamp"),
+                timestamp,
+            );
+        }
+        log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            SplunkS2sConfig::NAME,
+            Utc::now(),
+        );
+
+        let host = metadata(message, "MetaData:Host", "host::")
+            .or_else(|| self.inner.server_name())
+            .unwrap_or_default();
+        log_namespace.insert_source_metadata(
+            SplunkS2sConfig::NAME,
+            &mut log,
+            log_schema().host_key().map(LegacyKey::InsertIfEmpty),
+            path!("host"),
+            host.to_owned(),
+        );
+
+        let metadata_fields = [
+            ("_MetaData:Index", "", INDEX, "index"),
+            ("MetaData:Source", "source::", SOURCE, "source"),
+            (
+                "MetaData:Sourcetype",
+                "sourcetype::",
+                SOURCETYPE,
+                "sourcetype",
+            ),
+        ];
+        for (key, prefix, legacy_key, metadata_key) in metadata_fields {
+            if let Some(value) = metadata(message, key, prefix) {
+                log_namespace.insert_source_metadata(
+                    SplunkS2sConfig::NAME,
+                    &mut log,
+                    Some(LegacyKey::Overwrite(path!(legacy_key))),
+                    path!(metadata_key),
+                    value.to_owned(),
+                );
+            }
+        }
+
+        if let Some(fields) = indexed_fields(message) {
+            log_namespace.insert_source_metadata(
+                SplunkS2sConfig::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!("fields"))),
+                path!("fields"),
+                fields,
+            );
+        }
+
+        log
+    }
+}
+
+/// Gets a metadata field, which forwarders send prefixed with the name of the field.
+fn metadata<'a>(message: &'a Message, key: &str, prefix: &str) -> Option<&'a str> {
+    message
+        .get(key)
+        .map(|value| value.strip_prefix(prefix).unwrap_or(value))
+        .filter(|value| !value.is_empty())
+}
+
+/// Parses the time of the event, sent as seconds since the epoch with an optional fraction in
+/// `_subsecond`, such as `.250`.
+fn parse_timestamp(message: &Message) -> Option<DateTime<Utc>> {
+    let seconds = message.get("_time")?.trim().parse::<i64>().ok()?;
+    let nanoseconds = message
+        .get("_subsecond")
+        .and_then(|subsecond| format!("0{}", subsecond.trim()).parse::<f64>().ok())
+        .filter(|fraction| (0.0..1.0).contains(fraction))
+        .map_or(0, |fraction| (fraction * 1e9) as u32);
+    Utc.timestamp_opt(seconds, nanoseconds).single()
+}
+
+/// Parses the indexed fields of the event, sent in `_meta` as whitespace separated `key::value`
+/// pairs.
+fn indexed_fields(message: &Message) -> Option<Value> {
+    let fields = message
+        .get("_meta")?
+        .split_whitespace()
+        .filter_map(|pair| pair.split_once("::"))
+        .filter(|(key, _)| !key.is_empty())
+        .map(|(key, value)| (key.into(), Value::from(value.to_owned())))
+        .collect::<ObjectMap>();
+    (!fields.is_empty()).then_some(Value::Object(fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::Stream;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use vector_lib::lookup::OwnedTargetPath;
+
+    use super::protocol::{encode_message, encode_signature, RAW_KEY};
+    use super::*;
+    use crate::{
+        event::EventStatus,
+        test_util::{
+            components::{assert_source_compliance, SOCKET_PUSH_SOURCE_TAGS},
+            next_addr, spawn_collect_n, wait_for_tcp,
+        },
+        SourceSender,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SplunkS2sConfig>();
+    }
+
+    async fn start_splunk_s2s(
+        log_namespace: Option<bool>,
+    ) -> (SocketAddr, impl Stream<Item = Event> + Unpin) {
+        let (sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let source = SplunkS2sConfig {
+            address: address.into(),
+            log_namespace,
+            ..Default::default()
+        }
+        .build(SourceContext::new_test(sender, None))
+        .await
+        .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+        (address, recv)
+    }
+
+    async fn send_messages(address: SocketAddr) {
+        let mut socket = tokio::net::TcpStream::connect(address).await.unwrap();
+        socket
+            .write_all(&encode_signature("v3", "forwarder-1"))
+            .await
+            .unwrap();
+
+        // v3 forwarders wait for their capabilities to be accepted before sending events.
+        socket
+            .write_all(&encode_message(&[(
+                CAPABILITIES_KEY,
+                b"ack=0;compression=0",
+            )]))
+            .await
+            .unwrap();
+        let expected = capabilities_response();
+        let mut response = vec![0; expected.len()];
+        socket.read_exact(&mut response).await.unwrap();
+        assert_eq!(response, expected);
+
+        socket
+            .write_all(&encode_message(&[
+                ("_MetaData:Index", b"main"),
+                ("MetaData:Host", b"host::web-1"),
+                ("MetaData:Source", b"source::/var/log/nginx/access.log"),
+                ("MetaData:Sourcetype", b"sourcetype::nginx"),
+                ("_time", b"1760443200"),
+                ("_subsecond", b".25"),
+                ("_meta", b"datacenter::eu-west team::web"),
+                (RAW_KEY, b"GET /index.html 200"),
+            ]))
+            .await
+            .unwrap();
+        socket
+            .write_all(&encode_message(&[(RAW_KEY, b"GET /health 200")]))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn receives_forwarded_events() {
+        let events = assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async {
+            let (address, recv) = start_splunk_s2s(None).await;
+            spawn_collect_n(send_messages(address), recv, 2).await
+        })
+        .await;
+
+        assert_eq!(events.len(), 2);
+        let log = events[0].as_log();
+        assert_eq!(*log.get("message").unwrap(), "GET /index.html 200".into());
+        assert_eq!(*log.get("source_type").unwrap(), "splunk_s2s".into());
+        assert_eq!(*log.get("host").unwrap(), "web-1".into());
+        assert_eq!(*log.get(INDEX).unwrap(), "main".into());
+        assert_eq!(
+            *log.get(SOURCE).unwrap(),
+            "/var/log/nginx/access.log".into()
+        );
+        assert_eq!(*log.get(SOURCETYPE).unwrap(), "nginx".into());
+        assert_eq!(
+            *log.get("timestamp").unwrap(),
+            Utc.timestamp_opt(1760443200, 250_000_000).unwrap().into()
+        );
+        assert_eq!(*log.get("fields.datacenter").unwrap(), "eu-west".into());
+        assert_eq!(*log.get("fields.team").unwrap(), "web".into());
+
+        // Events without metadata are attributed to the forwarder.
+        let log = events[1].as_log();
+        assert_eq!(*log.get("message").unwrap(), "GET /health 200".into());
+        assert_eq!(*log.get("host").unwrap(), "forwarder-1".into());
+        assert!(log.get(INDEX).is_none());
+    }
+
+    #[tokio::test]
+    async fn receives_forwarded_events_vector_namespace() {
+        let events = assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async {
+            let (address, recv) = start_splunk_s2s(Some(true)).await;
+            spawn_collect_n(send_messages(address), recv, 2).await
+        })
+        .await;
+
+        let log = events[0].as_log();
+        assert_eq!(*log.value(), "GET /index.html 200".into());
+        let meta = log.metadata().value();
+        assert_eq!(
+            *meta.get(path!("splunk_s2s", "host")).unwrap(),
+            "web-1".into()
+        );
+        assert_eq!(
+            *meta.get(path!("splunk_s2s", "sourcetype")).unwrap(),
+            "nginx".into()
+        );
+        assert_eq!(
+            *meta.get(path!("splunk_s2s", "fields", "team")).unwrap(),
+            "web".into()
+        );
+        assert!(meta.get(path!("vector", "ingest_timestamp")).is_some());
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let config = SplunkS2sConfig {
+            log_namespace: Some(true),
+            ..Default::default()
+        };
+
+        let definition = config
+            .outputs(LogNamespace::Vector)
+            .remove(0)
+            .schema_definition(true)
+            .unwrap();
+
+        assert_eq!(
+            definition.meaning_path(meaning::MESSAGE),
+            Some(&OwnedTargetPath::event_root())
+        );
+        assert_eq!(
+            definition.meaning_path(meaning::HOST),
+            Some(&OwnedTargetPath::metadata(owned_value_path!(
+                "splunk_s2s",
+                "host"
+            )))
+        );
+        assert_eq!(
+            definition.meaning_path(meaning::SERVICE),
+            Some(&OwnedTargetPath::metadata(owned_value_path!(
+                "splunk_s2s",
+                "source"
+            )))
+        );
+    }
+
+    #[test]
+    fn parses_subsecond_timestamps() {
+        let message = Message {
+            fields: vec![
+                ("_time".to_owned(), "1760443200".to_owned()),
+                ("_subsecond".to_owned(), ".5".to_owned()),
+            ],
+            raw: None,
+        };
+        assert_eq!(
+            parse_timestamp(&message),
+            Utc.timestamp_opt(1760443200, 500_000_000).single()
+        );
+    }
+}
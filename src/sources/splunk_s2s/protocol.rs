@@ -0,0 +1,299 @@
+//! The Splunk-to-Splunk (S2S) protocol spoken by forwarders sending cooked events to indexers.
+//!
+//! A connection starts with a fixed size signature identifying the cooked mode version and the
+//! forwarder, followed by length prefixed messages. Every message is a list of key/value pairs,
+//! the event itself being the value of the `_raw` key. All integers are big-endian and strings
+//! are prefixed with their length, which includes their trailing null byte.
+
+use std::io;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use snafu::Snafu;
+use tokio_util::codec::Decoder;
+use vector_lib::codecs::StreamDecodingError;
+
+use crate::internal_events::SplunkS2sMessageDecodeError;
+
+const SIGNATURE_PREFIX: &[u8] = b"--splunk-cooked-mode-v";
+const SIGNATURE_LENGTH: usize = 400;
+const SIGNATURE_PREFIX_FIELD_LENGTH: usize = 128;
+const SERVER_NAME_FIELD_LENGTH: usize = 256;
+
+/// Messages beyond this length are rejected, as they are buffered in memory until fully read.
+const MAX_MESSAGE_LENGTH: usize = 64 * 1024 * 1024;
+
+pub(super) const RAW_KEY: &str = "_raw";
+/// The key of the message sent by v3 forwarders to negotiate the features of the connection.
+pub(super) const CAPABILITIES_KEY: &str = "__s2s_capabilities";
+const CONTROL_MESSAGE_KEY: &str = "__s2s_control_msg";
+
+/// The answer to the capabilities of v3 forwarders, accepting them without acknowledgements or
+/// compression, which aren't supported.
+const CAPABILITIES_RESPONSE: &str = "cap_response=success";
+
+#[derive(Debug, Snafu)]
+pub enum DecodeError {
+    #[snafu(display("i/o error: {}", source))]
+    IO { source: io::Error },
+    #[snafu(display("Connection did not start with a Splunk cooked mode signature"))]
+    InvalidSignature,
+    #[snafu(display(
+        "Message of {} bytes exceeds the maximum of {} bytes",
+        length,
+        MAX_MESSAGE_LENGTH
+    ))]
+    MessageTooLong { length: usize },
+    #[snafu(display("Malformed message: {}", reason))]
+    MalformedMessage { reason: &'static str },
+}
+
+impl StreamDecodingError for DecodeError {
+    fn can_continue(&self) -> bool {
+        use DecodeError::*;
+
+        match self {
+            IO { .. } => false,
+            InvalidSignature => false,
+            MessageTooLong { .. } => false,
+            // The message was skipped as a whole, so the following ones can still be read.
+            MalformedMessage { .. } => true,
+        }
+    }
+}
+
+impl From<io::Error> for DecodeError {
+    fn from(source: io::Error) -> Self {
+        DecodeError::IO { source }
+    }
+}
+
+/// A message sent by a forwarder.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct Message {
+    pub(super) fields: Vec<(String, String)>,
+    pub(super) raw: Option<Bytes>,
+}
+
+impl Message {
+    pub(super) fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Decodes the signature of a connection, then its messages.
+#[derive(Debug, Default)]
+pub(super) struct MessageDecoder {
+    /// The name of the forwarder, once the signature is read.
+    server_name: Option<String>,
+}
+
+impl MessageDecoder {
+    pub(super) fn server_name(&self) -> Option<&str> {
+        self.server_name.as_deref()
+    }
+
+    fn decode_signature(&mut self, src: &mut BytesMut) -> Result<bool, DecodeError> {
+        let prefix_length = src.len().min(SIGNATURE_PREFIX.len());
+        if src[..prefix_length] != SIGNATURE_PREFIX[..prefix_length] {
+            return Err(DecodeError::InvalidSignature);
+        }
+        if src.len() < SIGNATURE_LENGTH {
+            return Ok(false);
+        }
+
+        let signature = src.split_to(SIGNATURE_LENGTH);
+        let server_name = &signature[SIGNATURE_PREFIX_FIELD_LENGTH..][..SERVER_NAME_FIELD_LENGTH];
+        self.server_name = Some(null_terminated(server_name));
+        debug!(
+            message = "Splunk forwarder connected.",
+            version = %null_terminated(&signature[..SIGNATURE_PREFIX_FIELD_LENGTH]),
+            server_name = ?self.server_name,
+        );
+        Ok(true)
+    }
+
+    fn decode_message(
+        &mut self,
+        src: &mut BytesMut,
+    ) -> Result<Option<(Message, usize)>, DecodeError> {
+        if self.server_name.is_none() && !self.decode_signature(src)? {
+            return Ok(None);
+        }
+
+        if src.len() < 4 {
+            return Ok(None);
+        }
+        let length = (&src[..4]).get_u32() as usize;
+        if length > MAX_MESSAGE_LENGTH {
+            return Err(DecodeError::MessageTooLong { length });
+        }
+        if src.len() < 4 + length {
+            src.reserve(4 + length - src.len());
+            return Ok(None);
+        }
+
+        src.advance(4);
+        let body = src.split_to(length).freeze();
+        parse_message(body).map(|message| Some((message, 4 + length)))
+    }
+}
+
+impl Decoder for MessageDecoder {
+    type Item = (Message, usize);
+    type Error = DecodeError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode_message(src).map_err(|error| {
+            emit!(SplunkS2sMessageDecodeError { error: &error });
+            error
+        })
+    }
+}
+
+fn parse_message(mut body: Bytes) -> Result<Message, DecodeError> {
+    let count = read_u32(&mut body)?;
+    let mut message = Message::default();
+    for _ in 0..count {
+        let key = read_string(&mut body)?;
+        let value = read_string(&mut body)?;
+        if key.as_ref() == RAW_KEY.as_bytes() {
+            message.raw = Some(value);
+        } else {
+            message.fields.push((
+                String::from_utf8_lossy(&key).into_owned(),
+                String::from_utf8_lossy(&value).into_owned(),
+            ));
+        }
+    }
+    // The remaining trailer is always a zero followed by the `_raw` key, and carries no data.
+    Ok(message)
+}
+
+fn read_u32(body: &mut Bytes) -> Result<usize, DecodeError> {
+    if body.remaining() < 4 {
+        return Err(DecodeError::MalformedMessage {
+            reason: "truncated length",
+        });
+    }
+    Ok(body.get_u32() as usize)
+}
+
+fn read_string(body: &mut Bytes) -> Result<Bytes, DecodeError> {
+    let length = read_u32(body)?;
+    if body.remaining() < length {
+        return Err(DecodeError::MalformedMessage {
+            reason: "truncated string",
+        });
+    }
+    let mut string = body.split_to(length);
+    while string.last() == Some(&0) {
+        string.truncate(string.len() - 1);
+    }
+    Ok(string)
+}
+
+fn null_terminated(field: &[u8]) -> String {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+/// Encodes a message the way forwarders do.
+pub(super) fn encode_message(fields: &[(&str, &[u8])]) -> Bytes {
+    fn put_string(buf: &mut BytesMut, string: &[u8]) {
+        buf.put_u32(string.len() as u32 + 1);
+        buf.put_slice(string);
+        buf.put_u8(0);
+    }
+
+    let mut body = BytesMut::new();
+    body.put_u32(fields.len() as u32);
+    for (key, value) in fields {
+        put_string(&mut body, key.as_bytes());
+        put_string(&mut body, value);
+    }
+    body.put_u32(0);
+    put_string(&mut body, RAW_KEY.as_bytes());
+
+    let mut message = BytesMut::with_capacity(4 + body.len());
+    message.put_u32(body.len() as u32);
+    message.put(body);
+    message.freeze()
+}
+
+/// The message answering the capabilities of a v3 forwarder.
+pub(super) fn capabilities_response() -> Bytes {
+    encode_message(&[
+        (CONTROL_MESSAGE_KEY, CAPABILITIES_RESPONSE.as_bytes()),
+        (RAW_KEY, b""),
+    ])
+}
+
+/// Encodes the signature a forwarder starts its connections with.
+#[cfg(test)]
+pub(super) fn encode_signature(version: &str, server_name: &str) -> Bytes {
+    let mut signature = BytesMut::zeroed(SIGNATURE_LENGTH);
+    let prefix = format!("--splunk-cooked-mode-{version}--");
+    signature[..prefix.len()].copy_from_slice(prefix.as_bytes());
+    signature[SIGNATURE_PREFIX_FIELD_LENGTH..][..server_name.len()]
+        .copy_from_slice(server_name.as_bytes());
+    signature[SIGNATURE_PREFIX_FIELD_LENGTH + SERVER_NAME_FIELD_LENGTH..][..4]
+        .copy_from_slice(b"8089");
+    signature.freeze()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_signature_and_messages() {
+        let mut src = BytesMut::new();
+        src.put(encode_signature("v3", "forwarder-1"));
+        src.put(encode_message(&[
+            ("MetaData:Host", b"host::web-1"),
+            (RAW_KEY, b"GET /index.html 200"),
+            ("_done", b"_done"),
+        ]));
+
+        let mut decoder = MessageDecoder::default();
+        // Messages are only decoded once fully received.
+        let mut partial = src.split_to(SIGNATURE_LENGTH + 10);
+        assert_eq!(decoder.decode(&mut partial).unwrap(), None);
+        assert_eq!(decoder.server_name(), Some("forwarder-1"));
+        partial.unsplit(src);
+
+        let (message, _) = decoder.decode(&mut partial).unwrap().unwrap();
+        assert_eq!(message.get("MetaData:Host"), Some("host::web-1"));
+        assert_eq!(message.get("_done"), Some("_done"));
+        assert_eq!(message.raw, Some(Bytes::from("GET /index.html 200")));
+        assert!(partial.is_empty());
+        assert_eq!(decoder.decode(&mut partial).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_connections_without_signature() {
+        let mut src = BytesMut::from("<13>Oct 14 12:00:00 host app: message");
+        let error = MessageDecoder::default().decode(&mut src).unwrap_err();
+        assert!(matches!(error, DecodeError::InvalidSignature));
+    }
+
+    #[test]
+    fn skips_malformed_messages() {
+        let mut src = BytesMut::new();
+        src.put(encode_signature("v2", "forwarder-1"));
+        // A message claiming more pairs than it holds.
+        src.put_u32(8);
+        src.put_u32(2);
+        src.put_u32(0);
+        src.put(encode_message(&[(RAW_KEY, b"message")]));
+
+        let mut decoder = MessageDecoder::default();
+        let error = decoder.decode(&mut src).unwrap_err();
+        assert!(error.can_continue());
+        let (message, _) = decoder.decode(&mut src).unwrap().unwrap();
+        assert_eq!(message.raw, Some(Bytes::from("message")));
+    }
+}
@@ -166,6 +166,7 @@ impl StaticMetrics {
                             },
                             kind,
                             value: value.clone(),
+                            exemplars: Vec::new(),
                         },
                         EventMetadata::default(),
                     )
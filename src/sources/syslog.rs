@@ -30,7 +30,9 @@ use crate::{
     internal_events::{SocketBindError, SocketMode, SocketReceiveError},
     net,
     shutdown::ShutdownSignal,
-    sources::util::net::{try_bind_udp_socket, SocketListenAddr, TcpNullAcker, TcpSource},
+    sources::util::net::{
+        try_bind_udp_socket, SocketListenAddr, TcpBackChannelAcker, TcpBackChannelConfig, TcpSource,
+    },
     tcp::TcpKeepaliveConfig,
     tls::{MaybeTlsSettings, TlsSourceConfig},
     SourceSender,
@@ -95,6 +97,9 @@ pub enum Mode {
 
         /// The maximum number of TCP connections that are allowed at any given time.
         connection_limit: Option<u32>,
+
+        #[configurable(derived)]
+        back_channel: Option<TcpBackChannelConfig>,
     },
 
     /// Listen on UDP.
@@ -150,6 +155,7 @@ impl Default for SyslogConfig {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                back_channel: None,
             },
             host_key: None,
             max_length: crate::serde::default_max_length(),
@@ -183,11 +189,13 @@ impl SourceConfig for SyslogConfig {
                 tls,
                 receive_buffer_bytes,
                 connection_limit,
+                back_channel,
             } => {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
                     host_key,
                     log_namespace,
+                    back_channel,
                 };
                 let shutdown_secs = Duration::from_secs(30);
                 let tls_config = tls.as_ref().map(|tls| tls.tls_config.clone());
@@ -272,7 +280,13 @@ impl SourceConfig for SyslogConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        matches!(
+            self.mode,
+            Mode::Tcp {
+                back_channel: Some(_),
+                ..
+            }
+        )
     }
 }
 
@@ -281,13 +295,14 @@ struct SyslogTcpSource {
     max_length: usize,
     host_key: Option<OwnedValuePath>,
     log_namespace: LogNamespace,
+    back_channel: Option<TcpBackChannelConfig>,
 }
 
 impl TcpSource for SyslogTcpSource {
     type Error = vector_lib::codecs::decoding::Error;
     type Item = SmallVec<[Event; 1]>;
     type Decoder = Decoder;
-    type Acker = TcpNullAcker;
+    type Acker = Option<TcpBackChannelAcker>;
 
     fn decoder(&self) -> Self::Decoder {
         Decoder::new(
@@ -305,8 +320,13 @@ impl TcpSource for SyslogTcpSource {
         );
     }
 
-    fn build_acker(&self, _: &[Self::Item]) -> Self::Acker {
-        TcpNullAcker
+    fn build_acker(&self, frames: &[Self::Item]) -> Self::Acker {
+        self.back_channel
+            .map(|_| TcpBackChannelAcker::new(frames.len()))
+    }
+
+    fn back_channel(&self) -> Option<TcpBackChannelConfig> {
+        self.back_channel
     }
 }
 
@@ -1130,6 +1150,7 @@ mod test {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                back_channel: None,
             });
 
             let key = ComponentKey::from("in");
@@ -1274,6 +1295,7 @@ mod test {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                back_channel: None,
             });
 
             let key = ComponentKey::from("in");
@@ -1,6 +1,6 @@
 pub mod request_limiter;
 
-use std::{io, mem::drop, net::SocketAddr, time::Duration};
+use std::{io, mem::drop, net::SocketAddr, num::NonZeroUsize, time::Duration};
 
 use bytes::Bytes;
 use futures::{future::BoxFuture, FutureExt, StreamExt};
@@ -17,6 +17,7 @@ use tokio::{
 use tokio_util::codec::{Decoder, FramedRead};
 use tracing::Instrument;
 use vector_lib::codecs::StreamDecodingError;
+use vector_lib::configurable::configurable_component;
 use vector_lib::finalization::AddBatchNotifier;
 use vector_lib::lookup::{path, OwnedValuePath};
 use vector_lib::{
@@ -86,6 +87,69 @@ impl TcpSourceAcker for TcpNullAcker {
     }
 }
 
+impl<A: TcpSourceAcker> TcpSourceAcker for Option<A> {
+    fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes> {
+        self.and_then(|acker| acker.build_ack(ack))
+    }
+}
+
+/// Configuration for acknowledging events back to the sender over the connection they were
+/// received on.
+///
+/// Once the events decoded from a window of frames have been delivered by all sinks, an
+/// `ack <count>` message framed with octet counting, as described in [RFC 6587][rfc6587], is
+/// written back to the sender. If they could not be delivered, a `nack <count>` message is written
+/// instead and the connection is closed. No further frames are read from the connection until the
+/// window is acknowledged, so senders waiting for acknowledgements are never ahead of the sinks by
+/// more than a window.
+///
+/// Only enable this for senders that expect these acknowledgements.
+///
+/// [rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587#section-3.4.1
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TcpBackChannelConfig {
+    /// The maximum number of frames read from a connection before waiting for them to be
+    /// acknowledged.
+    #[serde(default = "default_back_channel_window")]
+    #[configurable(metadata(docs::type_unit = "frames"))]
+    pub window: NonZeroUsize,
+}
+
+fn default_back_channel_window() -> NonZeroUsize {
+    NonZeroUsize::new(100).expect("static non-zero number")
+}
+
+impl Default for TcpBackChannelConfig {
+    fn default() -> Self {
+        Self {
+            window: default_back_channel_window(),
+        }
+    }
+}
+
+/// Acknowledges a window of frames over the back-channel configured by [`TcpBackChannelConfig`].
+pub struct TcpBackChannelAcker {
+    frames: usize,
+}
+
+impl TcpBackChannelAcker {
+    pub const fn new(frames: usize) -> Self {
+        Self { frames }
+    }
+}
+
+impl TcpSourceAcker for TcpBackChannelAcker {
+    fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes> {
+        let message = match ack {
+            TcpSourceAck::Ack => format!("ack {}", self.frames),
+            TcpSourceAck::Error | TcpSourceAck::Reject => format!("nack {}", self.frames),
+        };
+        Some(Bytes::from(format!("{} {message}", message.len())))
+    }
+}
+
 pub trait TcpSource: Clone + Send + Sync + 'static
 where
     <<Self as TcpSource>::Decoder as tokio_util::codec::Decoder>::Item: std::marker::Send,
@@ -108,6 +172,12 @@ where
 
     fn build_acker(&self, item: &[Self::Item]) -> Self::Acker;
 
+    /// The back-channel used to acknowledge events to the sender, if any. Enabling it implies
+    /// waiting for events to be delivered before reading further from the connection.
+    fn back_channel(&self) -> Option<TcpBackChannelConfig> {
+        None
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
@@ -125,7 +195,8 @@ where
         source_name: &'static str,
         log_namespace: LogNamespace,
     ) -> crate::Result<crate::sources::Source> {
-        let acknowledgements = cx.do_acknowledgements(acknowledgements);
+        let acknowledgements =
+            cx.do_acknowledgements(acknowledgements) || self.back_channel().is_some();
 
         Ok(Box::pin(async move {
             let listenfd = ListenFd::from_env();
@@ -292,7 +363,10 @@ async fn handle_stream<T>(
         .map(CertificateMetadata::from);
 
     let reader = FramedRead::new(socket, source.decoder());
-    let mut reader = ReadyFrames::new(reader);
+    let mut reader = match source.back_channel() {
+        Some(back_channel) => ReadyFrames::with_capacity(reader, back_channel.window.get()),
+        None => ReadyFrames::new(reader),
+    };
 
     let connection_close_timeout = OptionFuture::from(
         max_connection_duration_secs
@@ -40,6 +40,7 @@ struct Service {
     pipeline: SourceSender,
     acknowledgements: bool,
     log_namespace: LogNamespace,
+    source_type: &'static str,
 }
 
 #[tonic::async_trait]
@@ -60,7 +61,7 @@ impl proto::Service for Service {
             if let Event::Log(ref mut log) = event {
                 self.log_namespace.insert_standard_vector_source_metadata(
                     log,
-                    VectorConfig::NAME,
+                    self.source_type,
                     now,
                 );
             }
@@ -132,7 +133,7 @@ pub struct VectorConfig {
 
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
-    acknowledgements: SourceAcknowledgementsConfig,
+    pub(crate) acknowledgements: SourceAcknowledgementsConfig,
 
     /// The namespace to use for logs. This overrides the global setting.
     #[serde(default)]
@@ -148,6 +149,35 @@ impl VectorConfig {
             ..Default::default()
         }
     }
+
+    /// Builds the gRPC server of the source, inserting `source_type` as the source type of the
+    /// received logs.
+    pub(crate) fn build_source(
+        &self,
+        cx: SourceContext,
+        source_type: &'static str,
+    ) -> crate::Result<Source> {
+        let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        let service = proto::Server::new(Service {
+            pipeline: cx.out,
+            acknowledgements,
+            log_namespace,
+            source_type,
+        })
+        .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
+        // Tonic added a default of 4MB in 0.9. This replaces the old behavior.
+        .max_decoding_message_size(usize::MAX);
+
+        let source =
+            run_grpc_server(self.address, tls_settings, service, cx.shutdown).map_err(|error| {
+                error!(message = "Source future failed.", %error);
+            });
+
+        Ok(Box::pin(source))
+    }
 }
 
 impl Default for VectorConfig {
@@ -172,25 +202,7 @@ impl GenerateConfig for VectorConfig {
 #[typetag::serde(name = "vector")]
 impl SourceConfig for VectorConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
-        let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
-        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
-        let log_namespace = cx.log_namespace(self.log_namespace);
-
-        let service = proto::Server::new(Service {
-            pipeline: cx.out,
-            acknowledgements,
-            log_namespace,
-        })
-        .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
-        // Tonic added a default of 4MB in 0.9. This replaces the old behavior.
-        .max_decoding_message_size(usize::MAX);
-
-        let source =
-            run_grpc_server(self.address, tls_settings, service, cx.shutdown).map_err(|error| {
-                error!(message = "Source future failed.", %error);
-            });
-
-        Ok(Box::pin(source))
+        self.build_source(cx, Self::NAME)
     }
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
@@ -0,0 +1,93 @@
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+};
+
+use tokio::fs;
+
+const BOOKMARKS_FILE: &str = "bookmarks.json";
+const BOOKMARKS_TMP_FILE: &str = "bookmarks.json.tmp";
+
+/// The bookmarks of the channels read, as rendered by `EvtRender`, persisted to resume reading
+/// after the last event processed of each channel across restarts.
+pub struct Bookmarks {
+    filename: PathBuf,
+    tmp_filename: PathBuf,
+    bookmarks: HashMap<String, String>,
+}
+
+impl Bookmarks {
+    pub async fn load(data_dir: &Path) -> Result<Self, io::Error> {
+        let filename = data_dir.join(BOOKMARKS_FILE);
+        let bookmarks = match fs::read(&filename).await {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(error) => return Err(error),
+        };
+        Ok(Self {
+            filename,
+            tmp_filename: data_dir.join(BOOKMARKS_TMP_FILE),
+            bookmarks,
+        })
+    }
+
+    pub fn get(&self, channel: &str) -> Option<&str> {
+        self.bookmarks.get(channel).map(String::as_str)
+    }
+
+    /// Updates the bookmark of a channel, writing the bookmarks to a temporary file first so they
+    /// aren't lost if Vector stops while writing them.
+    pub async fn set(&mut self, channel: String, bookmark: String) -> Result<(), io::Error> {
+        self.bookmarks.insert(channel, bookmark);
+        let contents = serde_json::to_vec(&self.bookmarks)?;
+        fs::write(&self.tmp_filename, contents).await?;
+        fs::rename(&self.tmp_filename, &self.filename).await
+    }
+
+    pub fn filename(&self) -> &Path {
+        &self.filename
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn persists_bookmarks() {
+        let data_dir = tempdir().unwrap();
+
+        let mut bookmarks = Bookmarks::load(data_dir.path()).await.unwrap();
+        assert_eq!(bookmarks.get("System"), None);
+
+        bookmarks
+            .set("System".into(), "<BookmarkList/>".into())
+            .await
+            .unwrap();
+        bookmarks
+            .set("Application".into(), "<BookmarkList></BookmarkList>".into())
+            .await
+            .unwrap();
+
+        let bookmarks = Bookmarks::load(data_dir.path()).await.unwrap();
+        assert_eq!(bookmarks.get("System"), Some("<BookmarkList/>"));
+        assert_eq!(
+            bookmarks.get("Application"),
+            Some("<BookmarkList></BookmarkList>")
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_corrupted_bookmarks() {
+        let data_dir = tempdir().unwrap();
+        fs::write(data_dir.path().join(BOOKMARKS_FILE), "{")
+            .await
+            .unwrap();
+
+        assert!(Bookmarks::load(data_dir.path()).await.is_err());
+    }
+}
@@ -0,0 +1,582 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use futures::StreamExt;
+use snafu::Snafu;
+use tokio::sync::mpsc;
+use vector_lib::{
+    config::{LegacyKey, LogNamespace},
+    configurable::configurable_component,
+    finalizer::OrderedFinalizer,
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol, Registered,
+    },
+    lookup::{event_path, owned_value_path, path},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind, Value};
+
+use crate::{
+    config::{
+        log_schema, DataType, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
+        SourceOutput,
+    },
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, EventMetadata, LogEvent},
+    internal_events::{
+        EventsReceived, StreamClosedError, WindowsEventLogBookmarkError, WindowsEventLogParseError,
+    },
+    serde::bool_or_struct,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod bookmarks;
+pub(crate) mod parser;
+#[cfg(windows)]
+pub(crate) mod subscription;
+
+use bookmarks::Bookmarks;
+use parser::{EventRecord, ParseError};
+#[cfg(windows)]
+use subscription::Subscription;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[cfg(not(windows))]
+    #[snafu(display("The windows_event_log source is only supported on Windows"))]
+    UnsupportedPlatform,
+    #[snafu(display("At least one channel must be configured"))]
+    NoChannels,
+    #[snafu(display("The batch size must be greater than zero"))]
+    ZeroBatchSize,
+}
+
+/// Configuration for the `windows_event_log` source.
+#[configurable_component(source(
+    "windows_event_log",
+    "Collect events from the Windows Event Log."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WindowsEventLogConfig {
+    /// The channels to read events from.
+    ///
+    /// The channels of the Event Viewer, such as the `System`, `Application`, and `Security`
+    /// channels, as well as the operational channels of applications and services, such as
+    /// `Microsoft-Windows-PowerShell/Operational`, can be read.
+    #[configurable(metadata(docs::examples = "System"))]
+    #[configurable(metadata(docs::examples = "Application"))]
+    #[configurable(metadata(docs::examples = "Microsoft-Windows-PowerShell/Operational"))]
+    pub channels: Vec<String>,
+
+    /// The [XPath query][xpath_query] selecting the events to read from every channel.
+    ///
+    /// By default, all events are read.
+    ///
+    /// [xpath_query]: https://learn.microsoft.com/en-us/windows/win32/wes/consuming-events#xpath-10-limitations
+    #[serde(default = "default_query")]
+    #[configurable(metadata(docs::examples = "*[System[(Level=1 or Level=2 or Level=3)]]"))]
+    #[configurable(metadata(docs::examples = "*[System[EventID=4624]]"))]
+    pub query: String,
+
+    /// Whether to read the events already logged in the channels.
+    ///
+    /// Only used for the channels without a bookmark, reading resuming after the last event read
+    /// from the channels with one. If `false`, only the events logged after Vector starts are read.
+    #[serde(default)]
+    pub read_existing_events: bool,
+
+    /// The maximum number of events read at once from a channel.
+    ///
+    /// The bookmark of a channel is updated after each batch of events read.
+    #[serde(default = "default_batch_size")]
+    #[configurable(metadata(docs::type_unit = "events"))]
+    pub batch_size: u32,
+
+    /// Whether to render the message of the events from the message resources of their providers,
+    /// as displayed by the Event Viewer.
+    ///
+    /// Events whose providers have no message resources are read without a message.
+    #[serde(default = "crate::serde::default_true")]
+    pub render_message: bool,
+
+    /// The directory used to persist the bookmarks of the channels.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    /// Make sure the running user has write permissions to this directory.
+    ///
+    /// If this directory is specified, then Vector will attempt to create it.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "C:\\ProgramData\\vector"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    pub data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_query() -> String {
+    "*".into()
+}
+
+const fn default_batch_size() -> u32 {
+    10
+}
+
+impl Default for WindowsEventLogConfig {
+    fn default() -> Self {
+        Self {
+            channels: vec!["System".into(), "Application".into()],
+            query: default_query(),
+            read_existing_events: false,
+            batch_size: default_batch_size(),
+            render_message: true,
+            data_dir: None,
+            acknowledgements: Default::default(),
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(WindowsEventLogConfig);
+
+impl WindowsEventLogConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        Definition::new_with_default_metadata(
+            Kind::object(Collection::empty().with_unknown(Kind::any())),
+            [log_namespace],
+        )
+        .with_standard_vector_source_metadata()
+        .with_source_metadata(
+            WindowsEventLogConfig::NAME,
+            log_schema()
+                .timestamp_key()
+                .cloned()
+                .map(LegacyKey::Overwrite),
+            &owned_value_path!("timestamp"),
+            Kind::timestamp().or_undefined(),
+            Some("timestamp"),
+        )
+        .with_event_field(
+            log_schema()
+                .message_key()
+                .filter(|_| log_namespace == LogNamespace::Legacy)
+                .unwrap_or(&owned_value_path!("message")),
+            Kind::bytes().or_undefined(),
+            Some("message"),
+        )
+    }
+
+    #[cfg(windows)]
+    fn subscribe(&self, bookmarks: &Bookmarks) -> crate::Result<Vec<Subscription>> {
+        self.channels
+            .iter()
+            .map(|channel| {
+                Subscription::new(
+                    channel,
+                    &self.query,
+                    bookmarks.get(channel),
+                    self.read_existing_events,
+                    self.batch_size,
+                    self.render_message,
+                )
+                .map_err(Into::into)
+            })
+            .collect()
+    }
+
+    #[cfg(not(windows))]
+    fn subscribe(&self, _bookmarks: &Bookmarks) -> crate::Result<Vec<Subscription>> {
+        Err(BuildError::UnsupportedPlatform.into())
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "windows_event_log")]
+impl SourceConfig for WindowsEventLogConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.channels.is_empty() {
+            return Err(BuildError::NoChannels.into());
+        }
+        if self.batch_size == 0 {
+            return Err(BuildError::ZeroBatchSize.into());
+        }
+
+        let data_dir = cx
+            .globals
+            // source are only global, name can be used for subdir
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let bookmarks = Bookmarks::load(&data_dir).await?;
+
+        // Subscribing to the channels when building the source reports unknown channels and
+        // invalid queries as configuration errors.
+        let subscriptions = self.subscribe(&bookmarks)?;
+        let (batches_tx, batches_rx) = mpsc::channel(subscriptions.len());
+
+        let source = WindowsEventLogSource {
+            batches: batches_rx,
+            out: cx.out,
+            acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+            log_namespace: cx.log_namespace(self.log_namespace),
+        };
+
+        Ok(Box::pin(async move {
+            for subscription in subscriptions {
+                let batches_tx = batches_tx.clone();
+                tokio::task::spawn_blocking(move || subscription.run(batches_tx));
+            }
+            drop(batches_tx);
+
+            source.run(bookmarks, cx.shutdown).await;
+            Ok(())
+        }))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// Subscriptions are only available on Windows.
+#[cfg(not(windows))]
+enum Subscription {}
+
+#[cfg(not(windows))]
+impl Subscription {
+    fn run(self, _batches: mpsc::Sender<EventBatch>) {
+        match self {}
+    }
+}
+
+/// The events read at once from a channel, along with the bookmark of the last one.
+struct EventBatch {
+    channel: String,
+    events: Vec<RawEvent>,
+    bookmark: String,
+}
+
+struct RawEvent {
+    record: Result<EventRecord, ParseError>,
+    message: Option<String>,
+    byte_size: usize,
+}
+
+struct WindowsEventLogSource {
+    batches: mpsc::Receiver<EventBatch>,
+    out: SourceSender,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+}
+
+impl WindowsEventLogSource {
+    async fn run(mut self, bookmarks: Bookmarks, mut shutdown: ShutdownSignal) {
+        let mut finalizer = Finalizer::new(self.acknowledgements, bookmarks, shutdown.clone());
+        let bytes_received = register!(BytesReceived::from(Protocol::NONE));
+        let events_received = register!(EventsReceived);
+
+        loop {
+            let batch = tokio::select! {
+                _ = &mut shutdown => break,
+                batch = self.batches.recv() => batch,
+            };
+            let Some(batch) = batch else { break };
+
+            let (notifier, receiver) =
+                BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+            let events = self.create_log_events(&batch, &notifier, &bytes_received);
+            drop(notifier);
+
+            if !events.is_empty() {
+                let count = events.len();
+                let byte_size = events.estimated_json_encoded_size_of();
+                events_received.emit(CountByteSize(count, byte_size));
+
+                if self.out.send_batch(events).await.is_err() {
+                    emit!(StreamClosedError { count });
+                    break;
+                }
+            }
+
+            finalizer
+                .finalize(batch.channel, batch.bookmark, receiver)
+                .await;
+        }
+    }
+
+    fn create_log_events(
+        &self,
+        batch: &EventBatch,
+        notifier: &Option<BatchNotifier>,
+        bytes_received: &Registered<BytesReceived>,
+    ) -> Vec<LogEvent> {
+        let now = Utc::now();
+        let mut byte_size = 0;
+        let mut events = Vec::with_capacity(batch.events.len());
+
+        for event in &batch.events {
+            byte_size += event.byte_size;
+            match &event.record {
+                Ok(record) => {
+                    let log = self
+                        .create_log_event(record, event.message.as_deref(), now)
+                        .with_batch_notifier_option(notifier);
+                    events.push(log);
+                }
+                Err(error) => emit!(WindowsEventLogParseError {
+                    channel: &batch.channel,
+                    error,
+                }),
+            }
+        }
+
+        bytes_received.emit(ByteSize(byte_size));
+        events
+    }
+
+    fn create_log_event(
+        &self,
+        record: &EventRecord,
+        message: Option<&str>,
+        now: chrono::DateTime<Utc>,
+    ) -> LogEvent {
+        let mut log = LogEvent::from_map(record.fields.clone(), EventMetadata::default());
+
+        if let Some(message) = message {
+            match self.log_namespace {
+                LogNamespace::Vector => {
+                    log.insert(event_path!("message"), message.trim_end());
+                }
+                LogNamespace::Legacy => {
+                    log.maybe_insert(log_schema().message_key_target_path(), message.trim_end());
+                }
+            }
+        }
+
+        if let Some(timestamp) = record.timestamp {
+            self.log_namespace.insert_source_metadata(
+                WindowsEventLogConfig::NAME,
+                &mut log,
+                log_schema().timestamp_key().map(LegacyKey::Overwrite),
+                path!("timestamp"),
+                Value::Timestamp(timestamp),
+            );
+        }
+
+        self.log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            WindowsEventLogConfig::NAME,
+            now,
+        );
+
+        log
+    }
+}
+
+enum Finalizer {
+    Sync(Bookmarks),
+    Async(OrderedFinalizer<(String, String)>),
+}
+
+impl Finalizer {
+    fn new(acknowledgements: bool, mut bookmarks: Bookmarks, shutdown: ShutdownSignal) -> Self {
+        if acknowledgements {
+            let (finalizer, mut ack_stream) = OrderedFinalizer::new(Some(shutdown));
+            tokio::spawn(async move {
+                while let Some((status, (channel, bookmark))) = ack_stream.next().await {
+                    if status == BatchStatus::Delivered {
+                        set_bookmark(&mut bookmarks, channel, bookmark).await;
+                    }
+                }
+            });
+            Self::Async(finalizer)
+        } else {
+            Self::Sync(bookmarks)
+        }
+    }
+
+    async fn finalize(
+        &mut self,
+        channel: String,
+        bookmark: String,
+        receiver: Option<BatchStatusReceiver>,
+    ) {
+        match (self, receiver) {
+            (Self::Sync(bookmarks), None) => set_bookmark(bookmarks, channel, bookmark).await,
+            (Self::Async(finalizer), Some(receiver)) => {
+                finalizer.add((channel, bookmark), receiver)
+            }
+            _ => {
+                unreachable!(
+                    "Cannot have async finalization without a receiver in windows_event_log source"
+                )
+            }
+        }
+    }
+}
+
+async fn set_bookmark(bookmarks: &mut Bookmarks, channel: String, bookmark: String) {
+    if let Err(error) = bookmarks.set(channel, bookmark).await {
+        emit!(WindowsEventLogBookmarkError {
+            error,
+            filename: bookmarks.filename().to_string_lossy().into_owned(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::event::EventStatus;
+
+    const EVENT: &str = r#"<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'>
+  <System>
+    <Provider Name='Service Control Manager'/>
+    <EventID Qualifiers='16384'>7036</EventID>
+    <Level>4</Level>
+    <TimeCreated SystemTime='2024-01-15T10:30:00.000000000Z'/>
+    <EventRecordID>42</EventRecordID>
+    <Channel>System</Channel>
+    <Computer>DESKTOP-1</Computer>
+  </System>
+  <EventData>
+    <Data Name='param1'>Windows Update</Data>
+    <Data Name='param2'>running</Data>
+  </EventData>
+</Event>"#;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WindowsEventLogConfig>();
+    }
+
+    fn raw_event(xml: &str, message: Option<&str>) -> RawEvent {
+        RawEvent {
+            record: parser::parse_event(xml),
+            message: message.map(Into::into),
+            byte_size: xml.len(),
+        }
+    }
+
+    async fn run_source(
+        acknowledgements: bool,
+        log_namespace: LogNamespace,
+        batch: EventBatch,
+    ) -> (Vec<LogEvent>, Bookmarks) {
+        let data_dir = tempdir().unwrap();
+        let bookmarks = Bookmarks::load(data_dir.path()).await.unwrap();
+        let (out, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let (batches_tx, batches) = mpsc::channel(1);
+
+        let source = WindowsEventLogSource {
+            batches,
+            out,
+            acknowledgements,
+            log_namespace,
+        };
+        batches_tx.send(batch).await.unwrap();
+        drop(batches_tx);
+        source.run(bookmarks, ShutdownSignal::noop()).await;
+
+        let events = rx.map(|event| event.into_log()).collect().await;
+        // Wait for the bookmarks of acknowledged batches to be persisted.
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        (events, Bookmarks::load(data_dir.path()).await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn reads_events() {
+        let batch = EventBatch {
+            channel: "System".into(),
+            events: vec![
+                raw_event(
+                    EVENT,
+                    Some("The Windows Update service entered the running state.\r\n"),
+                ),
+                raw_event("<Event>", None),
+            ],
+            bookmark: "<BookmarkList/>".into(),
+        };
+        let (events, bookmarks) = run_source(false, LogNamespace::Legacy, batch).await;
+
+        assert_eq!(events.len(), 1);
+        let log = &events[0];
+        assert_eq!(
+            log[log_schema().message_key().unwrap().to_string()],
+            "The Windows Update service entered the running state.".into()
+        );
+        assert_eq!(log["event_id"], Value::Integer(7036));
+        assert_eq!(log["level"], "information".into());
+        assert_eq!(log["event_data.param1"], "Windows Update".into());
+        assert_eq!(log["source_type"], WindowsEventLogConfig::NAME.into());
+        assert!(log
+            .get(log_schema().timestamp_key_target_path().unwrap())
+            .unwrap()
+            .is_timestamp());
+        assert_eq!(bookmarks.get("System"), Some("<BookmarkList/>"));
+    }
+
+    #[tokio::test]
+    async fn reads_events_with_vector_namespace() {
+        let batch = EventBatch {
+            channel: "System".into(),
+            events: vec![raw_event(EVENT, Some("Started."))],
+            bookmark: "<BookmarkList/>".into(),
+        };
+        let (events, _) = run_source(false, LogNamespace::Vector, batch).await;
+
+        let log = &events[0];
+        assert_eq!(log.get(event_path!("message")), Some(&"Started.".into()));
+        assert_eq!(log.get(event_path!("record_id")), Some(&Value::Integer(42)));
+        assert!(log
+            .metadata()
+            .value()
+            .get(path!(WindowsEventLogConfig::NAME, "timestamp"))
+            .unwrap()
+            .is_timestamp());
+    }
+
+    #[tokio::test]
+    async fn persists_bookmarks_once_acknowledged() {
+        let batch = EventBatch {
+            channel: "Application".into(),
+            events: vec![raw_event(EVENT, None)],
+            bookmark: "<BookmarkList/>".into(),
+        };
+        let (events, bookmarks) = run_source(true, LogNamespace::Legacy, batch).await;
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(bookmarks.get("Application"), Some("<BookmarkList/>"));
+    }
+
+    #[tokio::test]
+    async fn requires_channels() {
+        let config = WindowsEventLogConfig {
+            channels: vec![],
+            ..Default::default()
+        };
+        let cx = SourceContext::new_test(SourceSender::new_test().0, None);
+        assert!(config.build(cx).await.is_err());
+    }
+}
@@ -0,0 +1,310 @@
+use chrono::{DateTime, Utc};
+use roxmltree::{Document, Node};
+use snafu::{OptionExt, ResultExt, Snafu};
+use vrl::value::{KeyString, ObjectMap, Value};
+
+#[derive(Debug, Snafu)]
+pub enum ParseError {
+    #[snafu(display("Invalid event XML: {}", source))]
+    Xml { source: roxmltree::Error },
+    #[snafu(display("Event XML has no System element"))]
+    MissingSystem,
+}
+
+/// An event decoded from its XML rendering.
+#[derive(Debug, PartialEq)]
+pub struct EventRecord {
+    pub fields: ObjectMap,
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// Decodes the XML rendering of an event into structured fields.
+///
+/// The fields of the `System` element are flattened, while the named `Data` elements of the
+/// `EventData` element are gathered in the `event_data` object, falling back to an array for
+/// unnamed elements, as with classic event log providers. Provider-defined `UserData` is converted
+/// as is into the `user_data` object.
+pub fn parse_event(xml: &str) -> Result<EventRecord, ParseError> {
+    let document = Document::parse(xml).context(XmlSnafu)?;
+    let root = document.root_element();
+    let system = child(root, "System").context(MissingSystemSnafu)?;
+
+    let mut fields = ObjectMap::new();
+    let mut timestamp = None;
+    for node in system.children().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "Provider" => {
+                insert_attribute(&mut fields, "provider_name", node, "Name");
+                insert_attribute(&mut fields, "provider_guid", node, "Guid");
+                insert_attribute(&mut fields, "event_source_name", node, "EventSourceName");
+            }
+            "EventID" => {
+                insert_integer(&mut fields, "event_id", node.text());
+                if let Some(qualifiers) = node.attribute("Qualifiers") {
+                    insert_integer(&mut fields, "qualifiers", Some(qualifiers));
+                }
+            }
+            "Version" => insert_integer(&mut fields, "version", node.text()),
+            "Level" => {
+                let level = node.text().and_then(|level| level.trim().parse().ok());
+                if let Some(level) = level {
+                    fields.insert("level".into(), level_name(level).into());
+                    fields.insert("level_value".into(), Value::Integer(level));
+                }
+            }
+            "Task" => insert_integer(&mut fields, "task", node.text()),
+            "Opcode" => insert_integer(&mut fields, "opcode", node.text()),
+            "Keywords" => insert_text(&mut fields, "keywords", node.text()),
+            "TimeCreated" => {
+                timestamp = node
+                    .attribute("SystemTime")
+                    .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                    .map(|time| time.with_timezone(&Utc));
+            }
+            "EventRecordID" => insert_integer(&mut fields, "record_id", node.text()),
+            "Correlation" => {
+                insert_attribute(&mut fields, "activity_id", node, "ActivityID");
+                insert_attribute(
+                    &mut fields,
+                    "related_activity_id",
+                    node,
+                    "RelatedActivityID",
+                );
+            }
+            "Execution" => {
+                insert_integer(&mut fields, "process_id", node.attribute("ProcessID"));
+                insert_integer(&mut fields, "thread_id", node.attribute("ThreadID"));
+            }
+            "Channel" => insert_text(&mut fields, "channel", node.text()),
+            "Computer" => insert_text(&mut fields, "computer", node.text()),
+            "Security" => insert_attribute(&mut fields, "user_id", node, "UserID"),
+            _ => {}
+        }
+    }
+
+    if let Some(event_data) = child(root, "EventData") {
+        fields.insert("event_data".into(), event_data_value(event_data));
+    }
+    if let Some(user_data) = child(root, "UserData") {
+        fields.insert("user_data".into(), element_value(user_data));
+    }
+
+    Ok(EventRecord { fields, timestamp })
+}
+
+/// The names of the standard event levels, as displayed by the Event Viewer.
+const fn level_name(level: i64) -> &'static str {
+    match level {
+        1 => "critical",
+        2 => "error",
+        3 => "warning",
+        5 => "verbose",
+        // Level 0 is used by events logged regardless of the level, which are informational.
+        _ => "information",
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|child| child.is_element() && child.tag_name().name() == name)
+}
+
+fn insert_text(fields: &mut ObjectMap, key: &str, text: Option<&str>) {
+    if let Some(text) = text.map(str::trim).filter(|text| !text.is_empty()) {
+        fields.insert(key.into(), text.into());
+    }
+}
+
+fn insert_integer(fields: &mut ObjectMap, key: &str, text: Option<&str>) {
+    if let Some(value) = text.and_then(|text| text.trim().parse::<i64>().ok()) {
+        fields.insert(key.into(), Value::Integer(value));
+    }
+}
+
+fn insert_attribute(fields: &mut ObjectMap, key: &str, node: Node, attribute: &str) {
+    insert_text(fields, key, node.attribute(attribute));
+}
+
+fn event_data_value(event_data: Node) -> Value {
+    let data = event_data
+        .children()
+        .filter(|node| node.is_element() && node.tag_name().name() == "Data")
+        .collect::<Vec<_>>();
+
+    if data.iter().all(|node| node.attribute("Name").is_some()) {
+        Value::Object(
+            data.into_iter()
+                .map(|node| {
+                    let name = node.attribute("Name").unwrap_or_default();
+                    (name.into(), text_value(node))
+                })
+                .collect(),
+        )
+    } else {
+        Value::Array(data.into_iter().map(text_value).collect())
+    }
+}
+
+fn text_value(node: Node) -> Value {
+    node.text().unwrap_or_default().into()
+}
+
+/// Converts an element into an object of its child elements, elements sharing the same name being
+/// gathered in an array, or into its text if it has no child elements.
+fn element_value(node: Node) -> Value {
+    let mut children = node.children().filter(Node::is_element).peekable();
+    if children.peek().is_none() {
+        return text_value(node);
+    }
+
+    let mut object = ObjectMap::new();
+    for child in children {
+        let key = KeyString::from(child.tag_name().name());
+        let value = element_value(child);
+        match object.get_mut(&key) {
+            Some(Value::Array(values)) => values.push(value),
+            Some(existing) => {
+                let first = std::mem::replace(existing, Value::Null);
+                *existing = Value::Array(vec![first, value]);
+            }
+            None => {
+                object.insert(key, value);
+            }
+        }
+    }
+    Value::Object(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use similar_asserts::assert_eq;
+    use vrl::btreemap;
+
+    use super::*;
+
+    const LOGON_EVENT: &str = r#"<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'>
+  <System>
+    <Provider Name='Microsoft-Windows-Security-Auditing' Guid='{54849625-5478-4994-a5ba-3e3b0328c30d}'/>
+    <EventID>4624</EventID>
+    <Version>2</Version>
+    <Level>0</Level>
+    <Task>12544</Task>
+    <Opcode>0</Opcode>
+    <Keywords>0x8020000000000000</Keywords>
+    <TimeCreated SystemTime='2024-01-15T10:30:00.1234567Z'/>
+    <EventRecordID>12345</EventRecordID>
+    <Correlation ActivityID='{e5f1a9b2-0000-0000-0000-000000000000}'/>
+    <Execution ProcessID='716' ThreadID='7376'/>
+    <Channel>Security</Channel>
+    <Computer>DESKTOP-1</Computer>
+    <Security/>
+  </System>
+  <EventData>
+    <Data Name='SubjectUserSid'>S-1-5-18</Data>
+    <Data Name='TargetUserName'>alice</Data>
+    <Data Name='LogonType'>2</Data>
+  </EventData>
+</Event>"#;
+
+    #[test]
+    fn parses_system_and_event_data() {
+        let record = parse_event(LOGON_EVENT).unwrap();
+
+        assert_eq!(
+            record.timestamp,
+            Some(
+                Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap()
+                    + chrono::Duration::nanoseconds(123_456_700)
+            )
+        );
+        assert_eq!(
+            Value::Object(record.fields),
+            Value::from(btreemap! {
+                "provider_name" => "Microsoft-Windows-Security-Auditing",
+                "provider_guid" => "{54849625-5478-4994-a5ba-3e3b0328c30d}",
+                "event_id" => 4624,
+                "version" => 2,
+                "level" => "information",
+                "level_value" => 0,
+                "task" => 12544,
+                "opcode" => 0,
+                "keywords" => "0x8020000000000000",
+                "record_id" => 12345,
+                "activity_id" => "{e5f1a9b2-0000-0000-0000-000000000000}",
+                "process_id" => 716,
+                "thread_id" => 7376,
+                "channel" => "Security",
+                "computer" => "DESKTOP-1",
+                "event_data" => btreemap! {
+                    "SubjectUserSid" => "S-1-5-18",
+                    "TargetUserName" => "alice",
+                    "LogonType" => "2",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn parses_classic_events() {
+        let xml = r#"<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'>
+  <System>
+    <Provider Name='Application Error'/>
+    <EventID Qualifiers='0'>1000</EventID>
+    <Level>2</Level>
+    <Security UserID='S-1-5-21-1000'/>
+  </System>
+  <EventData>
+    <Data>app.exe</Data>
+    <Data>1.0.0.0</Data>
+  </EventData>
+</Event>"#;
+        let fields = parse_event(xml).unwrap().fields;
+
+        assert_eq!(fields["event_id"], Value::Integer(1000));
+        assert_eq!(fields["qualifiers"], Value::Integer(0));
+        assert_eq!(fields["level"], Value::from("error"));
+        assert_eq!(fields["user_id"], Value::from("S-1-5-21-1000"));
+        assert_eq!(
+            fields["event_data"],
+            Value::Array(vec!["app.exe".into(), "1.0.0.0".into()])
+        );
+    }
+
+    #[test]
+    fn parses_user_data() {
+        let xml = r#"<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'>
+  <System><EventID>104</EventID></System>
+  <UserData>
+    <LogFileCleared xmlns='http://manifests.microsoft.com/win/2004/08/windows/eventlog'>
+      <SubjectUserName>admin</SubjectUserName>
+      <Channel>System</Channel>
+      <Channel>Application</Channel>
+    </LogFileCleared>
+  </UserData>
+</Event>"#;
+        let fields = parse_event(xml).unwrap().fields;
+
+        assert_eq!(
+            fields["user_data"],
+            Value::from(btreemap! {
+                "LogFileCleared" => btreemap! {
+                    "SubjectUserName" => "admin",
+                    "Channel" => vec!["System", "Application"],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_events() {
+        assert!(matches!(
+            parse_event("<Event>"),
+            Err(ParseError::Xml { .. })
+        ));
+        assert!(matches!(
+            parse_event("<Event><EventData/></Event>"),
+            Err(ParseError::MissingSystem)
+        ));
+    }
+}
@@ -0,0 +1,334 @@
+use std::{collections::HashMap, ffi::OsStr, io, os::windows::ffi::OsStrExt, ptr, time::Duration};
+
+use snafu::Snafu;
+use tokio::sync::mpsc;
+use windows_sys::Win32::{
+    Foundation::{
+        CloseHandle, GetLastError, ERROR_INSUFFICIENT_BUFFER, ERROR_NO_MORE_ITEMS, HANDLE,
+        WAIT_OBJECT_0,
+    },
+    System::{
+        EventLog::{
+            EvtClose, EvtCreateBookmark, EvtFormatMessage, EvtFormatMessageEvent, EvtNext,
+            EvtOpenPublisherMetadata, EvtRender, EvtRenderBookmark, EvtRenderEventXml,
+            EvtSubscribe, EvtSubscribeStartAfterBookmark, EvtSubscribeStartAtOldestRecord,
+            EvtSubscribeToFutureEvents, EvtUpdateBookmark, EVT_HANDLE,
+        },
+        Threading::{CreateEventW, ResetEvent, WaitForSingleObject},
+    },
+};
+
+use super::{parser, EventBatch, RawEvent};
+use crate::internal_events::WindowsEventLogReadError;
+
+/// How long to wait for new events before checking whether the source is shutting down.
+const WAIT_TIMEOUT_MS: u32 = 500;
+
+const BACKOFF_DURATION: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+pub enum SubscriptionError {
+    #[snafu(display("Could not subscribe to channel {:?}: {}", channel, source))]
+    Subscribe { channel: String, source: io::Error },
+    #[snafu(display("{} failed: {}", operation, source))]
+    Api {
+        operation: &'static str,
+        source: io::Error,
+    },
+}
+
+fn last_error(operation: &'static str) -> SubscriptionError {
+    SubscriptionError::Api {
+        operation,
+        source: io::Error::last_os_error(),
+    }
+}
+
+/// An event log handle, closed when dropped.
+struct EvtHandle(EVT_HANDLE);
+
+impl EvtHandle {
+    fn new(handle: EVT_HANDLE, operation: &'static str) -> Result<Self, SubscriptionError> {
+        if handle == 0 {
+            Err(last_error(operation))
+        } else {
+            Ok(Self(handle))
+        }
+    }
+}
+
+impl Drop for EvtHandle {
+    fn drop(&mut self) {
+        unsafe { EvtClose(self.0) };
+    }
+}
+
+/// The event signaled when new events can be read from a subscription, closed when dropped.
+struct Signal(HANDLE);
+
+impl Drop for Signal {
+    fn drop(&mut self) {
+        unsafe { CloseHandle(self.0) };
+    }
+}
+
+/// A pull subscription to the events of a channel.
+pub struct Subscription {
+    channel: String,
+    batch_size: u32,
+    render_message: bool,
+    // The subscription is declared first to be closed before its signal.
+    subscription: EvtHandle,
+    signal: Signal,
+    bookmark: EvtHandle,
+    /// The metadata handles of the providers, `None` for the providers without message resources.
+    publishers: HashMap<String, Option<EvtHandle>>,
+}
+
+impl Subscription {
+    pub fn new(
+        channel: &str,
+        query: &str,
+        bookmark: Option<&str>,
+        read_existing_events: bool,
+        batch_size: u32,
+        render_message: bool,
+    ) -> Result<Self, SubscriptionError> {
+        // The signal is reset manually once all the available events are read.
+        let signal = unsafe { CreateEventW(ptr::null(), 1, 1, ptr::null()) };
+        if signal == 0 {
+            return Err(last_error("CreateEventW"));
+        }
+        let signal = Signal(signal);
+
+        let bookmark_xml = bookmark.map(to_wide);
+        let bookmark_handle = EvtHandle::new(
+            unsafe {
+                EvtCreateBookmark(
+                    bookmark_xml
+                        .as_ref()
+                        .map_or(ptr::null(), |bookmark| bookmark.as_ptr()),
+                )
+            },
+            "EvtCreateBookmark",
+        )?;
+
+        let flags = if bookmark.is_some() {
+            EvtSubscribeStartAfterBookmark
+        } else if read_existing_events {
+            EvtSubscribeStartAtOldestRecord
+        } else {
+            EvtSubscribeToFutureEvents
+        };
+        let channel_path = to_wide(channel);
+        let query = to_wide(query);
+        let subscription = unsafe {
+            EvtSubscribe(
+                0,
+                signal.0,
+                channel_path.as_ptr(),
+                query.as_ptr(),
+                if bookmark.is_some() {
+                    bookmark_handle.0
+                } else {
+                    0
+                },
+                ptr::null(),
+                None,
+                flags as u32,
+            )
+        };
+        if subscription == 0 {
+            return Err(SubscriptionError::Subscribe {
+                channel: channel.into(),
+                source: io::Error::last_os_error(),
+            });
+        }
+
+        Ok(Self {
+            channel: channel.into(),
+            batch_size,
+            render_message,
+            subscription: EvtHandle(subscription),
+            signal,
+            bookmark: bookmark_handle,
+            publishers: HashMap::new(),
+        })
+    }
+
+    /// Reads the events of the channel until the source stops receiving them.
+    pub fn run(mut self, batches: mpsc::Sender<EventBatch>) {
+        while !batches.is_closed() {
+            if unsafe { WaitForSingleObject(self.signal.0, WAIT_TIMEOUT_MS) } != WAIT_OBJECT_0 {
+                continue;
+            }
+
+            match self.next_batch() {
+                Ok(Some(batch)) => {
+                    if batches.blocking_send(batch).is_err() {
+                        break;
+                    }
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    emit!(WindowsEventLogReadError {
+                        channel: &self.channel,
+                        error,
+                    });
+                    std::thread::sleep(BACKOFF_DURATION);
+                }
+            }
+        }
+    }
+
+    fn next_batch(&mut self) -> Result<Option<EventBatch>, SubscriptionError> {
+        let mut handles = vec![0; self.batch_size as usize];
+        let mut returned = 0;
+        let ok = unsafe {
+            EvtNext(
+                self.subscription.0,
+                self.batch_size,
+                handles.as_mut_ptr(),
+                0,
+                0,
+                &mut returned,
+            )
+        };
+        if ok == 0 {
+            if unsafe { GetLastError() } == ERROR_NO_MORE_ITEMS {
+                unsafe { ResetEvent(self.signal.0) };
+                return Ok(None);
+            }
+            return Err(last_error("EvtNext"));
+        }
+
+        let handles = handles[..returned as usize]
+            .iter()
+            .map(|&handle| EvtHandle(handle))
+            .collect::<Vec<_>>();
+
+        let mut events = Vec::with_capacity(handles.len());
+        for handle in &handles {
+            let xml = render(handle.0, EvtRenderEventXml as u32)?;
+            let record = parser::parse_event(&xml);
+            let message = match (&record, self.render_message) {
+                (Ok(record), true) => record
+                    .fields
+                    .get("provider_name")
+                    .and_then(|provider| provider.as_str())
+                    .and_then(|provider| self.format_message(&provider, handle.0)),
+                _ => None,
+            };
+            events.push(RawEvent {
+                record,
+                message,
+                byte_size: xml.len(),
+            });
+
+            if unsafe { EvtUpdateBookmark(self.bookmark.0, handle.0) } == 0 {
+                return Err(last_error("EvtUpdateBookmark"));
+            }
+        }
+
+        Ok(Some(EventBatch {
+            channel: self.channel.clone(),
+            events,
+            bookmark: render(self.bookmark.0, EvtRenderBookmark as u32)?,
+        }))
+    }
+
+    /// Formats the message of an event from the message resources of its provider.
+    fn format_message(&mut self, provider: &str, event: EVT_HANDLE) -> Option<String> {
+        let publisher = self
+            .publishers
+            .entry(provider.into())
+            .or_insert_with(|| {
+                let provider = to_wide(provider);
+                let handle =
+                    unsafe { EvtOpenPublisherMetadata(0, provider.as_ptr(), ptr::null(), 0, 0) };
+                if handle == 0 {
+                    None
+                } else {
+                    Some(EvtHandle(handle))
+                }
+            })
+            .as_ref()?;
+
+        let format = |buffer: &mut Vec<u16>, used: &mut u32| unsafe {
+            EvtFormatMessage(
+                publisher.0,
+                event,
+                0,
+                0,
+                ptr::null(),
+                EvtFormatMessageEvent as u32,
+                buffer.len() as u32,
+                buffer.as_mut_ptr(),
+                used,
+            )
+        };
+
+        let mut buffer = Vec::new();
+        let mut used = 0;
+        if format(&mut buffer, &mut used) == 0 {
+            // Events without a message in the resources of their providers aren't errors.
+            if unsafe { GetLastError() } != ERROR_INSUFFICIENT_BUFFER {
+                return None;
+            }
+            buffer.resize(used as usize, 0);
+            if format(&mut buffer, &mut used) == 0 {
+                return None;
+            }
+        }
+        Some(from_wide(&buffer))
+    }
+}
+
+/// Renders an event or a bookmark as XML.
+fn render(handle: EVT_HANDLE, flags: u32) -> Result<String, SubscriptionError> {
+    let mut buffer = Vec::<u16>::new();
+    let mut used = 0;
+    let mut property_count = 0;
+
+    let ok = unsafe {
+        EvtRender(
+            0,
+            handle,
+            flags,
+            0,
+            ptr::null_mut(),
+            &mut used,
+            &mut property_count,
+        )
+    };
+    if ok == 0 && unsafe { GetLastError() } != ERROR_INSUFFICIENT_BUFFER {
+        return Err(last_error("EvtRender"));
+    }
+
+    // The size used is in bytes.
+    buffer.resize((used as usize).div_ceil(2), 0);
+    let ok = unsafe {
+        EvtRender(
+            0,
+            handle,
+            flags,
+            (buffer.len() * 2) as u32,
+            buffer.as_mut_ptr().cast(),
+            &mut used,
+            &mut property_count,
+        )
+    };
+    if ok == 0 {
+        return Err(last_error("EvtRender"));
+    }
+    Ok(from_wide(&buffer))
+}
+
+fn to_wide(s: &str) -> Vec<u16> {
+    OsStr::new(s).encode_wide().chain(Some(0)).collect()
+}
+
+fn from_wide(buffer: &[u16]) -> String {
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..len])
+}
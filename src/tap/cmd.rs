@@ -41,8 +41,9 @@ pub async fn tap(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode::ExitC
     let output_channel = OutputChannel::Stdout(EventFormatter::new(opts.meta, opts.format));
     let tap_runner = TapRunner::new(
         &subscription_url,
-        opts.inputs_of.clone(),
-        opts.outputs_patterns().clone(),
+        opts.inputs_patterns(),
+        opts.outputs_patterns(),
+        opts.filter.clone(),
         &output_channel,
         opts.format,
     );
@@ -41,6 +41,15 @@ pub struct Opts {
     #[arg(value_delimiter(','), long)]
     inputs_of: Vec<String>,
 
+    /// Components (transforms) IDs whose inputs and outputs to observe, to compare the events a
+    /// transform receives with the events it emits (comma-separated; accepts glob patterns)
+    #[arg(value_delimiter(','), long)]
+    around: Vec<String>,
+
+    /// VRL boolean expression that events must match to be observed (e.g. '.status >= 500')
+    #[arg(long)]
+    filter: Option<String>,
+
     /// Quiet output includes only events
     #[arg(short, long)]
     quiet: bool,
@@ -66,17 +75,24 @@ impl Opts {
         if self.component_id_patterns.is_empty()
             && self.outputs_of.is_empty()
             && self.inputs_of.is_empty()
+            && self.around.is_empty()
         {
             vec!["*".to_string()]
         } else {
             self.outputs_of
                 .iter()
+                .chain(&self.component_id_patterns)
+                .chain(&self.around)
                 .cloned()
-                .chain(self.component_id_patterns.iter().cloned())
                 .collect()
         }
     }
 
+    /// Component ID patterns whose inputs to tap
+    pub fn inputs_patterns(&self) -> Vec<String> {
+        self.inputs_of.iter().chain(&self.around).cloned().collect()
+    }
+
     /// Use the provided URL as the Vector GraphQL API server, or default to the local port
     /// provided by the API config.
     pub fn url(&self) -> Url {
@@ -162,11 +162,29 @@ impl Template {
         if self.is_static {
             Ok(self.src.clone())
         } else {
-            self.render_event(event.into())
+            self.render_event(event.into(), no_escape)
         }
     }
 
-    fn render_event(&self, event: EventRef<'_>) -> Result<String, TemplateRenderingError> {
+    /// Renders the given template with data from the event, escaping the values of the
+    /// referenced fields with `escape`.
+    pub fn render_string_escaped<'a>(
+        &self,
+        event: impl Into<EventRef<'a>>,
+        escape: fn(&str) -> Cow<'_, str>,
+    ) -> Result<String, TemplateRenderingError> {
+        if self.is_static {
+            Ok(self.src.clone())
+        } else {
+            self.render_event(event.into(), escape)
+        }
+    }
+
+    fn render_event(
+        &self,
+        event: EventRef<'_>,
+        escape: fn(&str) -> Cow<'_, str>,
+    ) -> Result<String, TemplateRenderingError> {
         let mut missing_keys = Vec::new();
         let mut out = String::with_capacity(self.reserve_size);
         for part in &self.parts {
@@ -176,25 +194,24 @@ impl Template {
                     out.push_str(&render_timestamp(items, event, self.tz_offset))
                 }
                 Part::Reference(key) => {
-                    out.push_str(
-                        &match event {
-                            EventRef::Log(log) => log
-                                .parse_path_and_get_value(key)
-                                .ok()
-                                .and_then(|v| v.map(Value::to_string_lossy)),
-                            EventRef::Metric(metric) => {
-                                render_metric_field(key, metric).map(Cow::Borrowed)
-                            }
-                            EventRef::Trace(trace) => trace
-                                .parse_path_and_get_value(key)
-                                .ok()
-                                .and_then(|v| v.map(Value::to_string_lossy)),
+                    let value = match event {
+                        EventRef::Log(log) => log
+                            .parse_path_and_get_value(key)
+                            .ok()
+                            .and_then(|v| v.map(Value::to_string_lossy)),
+                        EventRef::Metric(metric) => {
+                            render_metric_field(key, metric).map(Cow::Borrowed)
                         }
-                        .unwrap_or_else(|| {
-                            missing_keys.push(key.to_owned());
-                            Cow::Borrowed("")
-                        }),
-                    );
+                        EventRef::Trace(trace) => trace
+                            .parse_path_and_get_value(key)
+                            .ok()
+                            .and_then(|v| v.map(Value::to_string_lossy)),
+                    }
+                    .unwrap_or_else(|| {
+                        missing_keys.push(key.to_owned());
+                        Cow::Borrowed("")
+                    });
+                    out.push_str(&escape(&value));
                 }
             }
         }
@@ -346,6 +363,10 @@ fn parse_template(src: &str) -> Result<Vec<Part>, TemplateParseError> {
     Ok(parts)
 }
 
+fn no_escape(value: &str) -> Cow<'_, str> {
+    Cow::Borrowed(value)
+}
+
 fn render_metric_field<'a>(key: &str, metric: &'a Metric) -> Option<&'a str> {
     match key {
         "name" => Some(metric.name()),
@@ -447,6 +468,18 @@ mod tests {
         assert_eq!(Ok(Bytes::from("stream")), template.render(&event))
     }
 
+    #[test]
+    fn render_log_dynamic_escaped() {
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("log_stream", "a/b");
+        let template = Template::try_from("streams/{{log_stream}}/").unwrap();
+
+        assert_eq!(
+            Ok("streams/a_b/".to_owned()),
+            template.render_string_escaped(&event, |value| value.replace('/', "_").into())
+        )
+    }
+
     #[test]
     fn render_log_metadata() {
         let mut event = Event::Log(LogEvent::from("hello world"));
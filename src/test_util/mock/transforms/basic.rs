@@ -112,6 +112,7 @@ impl FunctionTransform for BasicTransform {
                         kind: metric.kind(),
                         time: metric.time(),
                         value: increment,
+                        exemplars: Vec::new(),
                     }));
                 }
             }
@@ -119,10 +119,26 @@ fn format_metric_bytes(total: i64, throughput: i64, human_metrics: bool) -> Stri
     }
 }
 
+/// Errors are only rendered with a rate once a component reports any, so that healthy
+/// components keep the `--` placeholder.
+fn format_errors(total: i64, throughput: i64, human_metrics: bool) -> String {
+    match total {
+        0 => "--".to_string(),
+        _ => format_metric(total, throughput, human_metrics),
+    }
+}
+
+fn format_utilization(utilization: Option<f64>) -> String {
+    match utilization {
+        Some(utilization) => format!("{:.1}%", utilization * 100.0),
+        None => "--".to_string(),
+    }
+}
+
 const NUM_COLUMNS: usize = if is_allocation_tracking_enabled() {
-    10
+    11
 } else {
-    9
+    10
 };
 
 static HEADER: [&str; NUM_COLUMNS] = [
@@ -135,6 +151,7 @@ static HEADER: [&str; NUM_COLUMNS] = [
     "Events Out",
     "Bytes Out",
     "Errors",
+    "Buffer",
     #[cfg(feature = "allocation-tracing")]
     "Memory Used",
 ];
@@ -231,11 +248,8 @@ impl<'a> Widgets<'a> {
                     r.sent_bytes_throughput_sec,
                     self.opts.human_metrics,
                 ),
-                if self.opts.human_metrics {
-                    r.errors.human_format()
-                } else {
-                    r.errors.thousands_format()
-                },
+                format_errors(r.errors, r.errors_throughput_sec, self.opts.human_metrics),
+                format_utilization(r.buffer_utilization),
                 #[cfg(feature = "allocation-tracing")]
                 r.allocated_bytes.human_format_bytes(),
             ];
@@ -264,28 +278,30 @@ impl<'a> Widgets<'a> {
 
         let widths: &[Constraint] = if is_allocation_tracking_enabled() {
             &[
-                Constraint::Percentage(13), // ID
-                Constraint::Percentage(8),  // Output
+                Constraint::Percentage(12), // ID
+                Constraint::Percentage(7),  // Output
                 Constraint::Percentage(4),  // Kind
-                Constraint::Percentage(9),  // Type
+                Constraint::Percentage(8),  // Type
                 Constraint::Percentage(10), // Events In
-                Constraint::Percentage(12), // Bytes In
+                Constraint::Percentage(11), // Bytes In
                 Constraint::Percentage(10), // Events Out
-                Constraint::Percentage(12), // Bytes Out
-                Constraint::Percentage(8),  // Errors
-                Constraint::Percentage(14), // Allocated Bytes
+                Constraint::Percentage(11), // Bytes Out
+                Constraint::Percentage(10), // Errors
+                Constraint::Percentage(6),  // Buffer
+                Constraint::Percentage(11), // Allocated Bytes
             ]
         } else {
             &[
-                Constraint::Percentage(13), // ID
-                Constraint::Percentage(12), // Output
-                Constraint::Percentage(9),  // Kind
+                Constraint::Percentage(12), // ID
+                Constraint::Percentage(10), // Output
+                Constraint::Percentage(8),  // Kind
                 Constraint::Percentage(6),  // Type
-                Constraint::Percentage(12), // Events In
-                Constraint::Percentage(14), // Bytes In
-                Constraint::Percentage(12), // Events Out
-                Constraint::Percentage(14), // Bytes Out
-                Constraint::Percentage(8),  // Errors
+                Constraint::Percentage(11), // Events In
+                Constraint::Percentage(13), // Bytes In
+                Constraint::Percentage(11), // Events Out
+                Constraint::Percentage(13), // Bytes Out
+                Constraint::Percentage(10), // Errors
+                Constraint::Percentage(6),  // Buffer
             ]
         };
         let w = Table::new(items, widths)
@@ -501,4 +517,20 @@ mod tests {
         assert_eq!((N * (N * (N * N))).human_format_bytes(), "1.00 TiB");
         assert_eq!((N * (N * (N * (N * N)))).human_format_bytes(), "1.00 PiB");
     }
+
+    #[test]
+    /// Errors should only show a rate once there are any
+    fn format_errors_with_rate() {
+        assert_eq!(format_errors(0, 0, false), "--");
+        assert_eq!(format_errors(1_500, 20, false), "1,500 (20/s)");
+        assert_eq!(format_errors(1_500, 20, true), "1.50 k (20/s)");
+    }
+
+    #[test]
+    /// Buffer utilization should be formatted as a percentage
+    fn format_buffer_utilization() {
+        assert_eq!(format_utilization(None), "--");
+        assert_eq!(format_utilization(Some(0.0)), "0.0%");
+        assert_eq!(format_utilization(Some(0.4567)), "45.7%");
+    }
 }
@@ -40,6 +40,8 @@ async fn component_added(client: Arc<SubscriptionClient>, tx: state::EventTx) {
                     #[cfg(feature = "allocation-tracing")]
                     allocated_bytes: 0,
                     errors: 0,
+                    errors_throughput_sec: 0,
+                    buffer_utilization: None,
                 }))
                 .await;
         }
@@ -309,6 +311,46 @@ async fn errors_totals(client: Arc<SubscriptionClient>, tx: state::EventTx, inte
     }
 }
 
+async fn errors_throughputs(client: Arc<SubscriptionClient>, tx: state::EventTx, interval: i64) {
+    tokio::pin! {
+        let stream = client.component_errors_throughputs_subscription(interval);
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_errors_throughputs;
+            _ = tx
+                .send(state::EventType::ErrorsThroughputs(
+                    interval,
+                    c.into_iter()
+                        .map(|c| (ComponentKey::from(c.component_id.as_str()), c.throughput))
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
+/// Buffer utilization per component
+async fn buffer_utilizations(client: Arc<SubscriptionClient>, tx: state::EventTx, interval: i64) {
+    tokio::pin! {
+        let stream = client.component_buffer_utilizations_subscription(interval);
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_buffer_utilizations;
+            _ = tx
+                .send(state::EventType::BufferUtilizations(
+                    c.into_iter()
+                        .map(|c| (ComponentKey::from(c.component_id.as_str()), c.utilization))
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
 /// Subscribe to each metrics channel through a separate client. This is a temporary workaround
 /// until client multiplexing is fixed. In future, we should be able to use a single client
 pub fn subscribe(
@@ -359,7 +401,13 @@ pub fn subscribe(
         )),
         #[cfg(feature = "allocation-tracing")]
         tokio::spawn(allocated_bytes(Arc::clone(&client), tx.clone(), interval)),
-        tokio::spawn(errors_totals(Arc::clone(&client), tx, interval)),
+        tokio::spawn(errors_totals(Arc::clone(&client), tx.clone(), interval)),
+        tokio::spawn(errors_throughputs(
+            Arc::clone(&client),
+            tx.clone(),
+            interval,
+        )),
+        tokio::spawn(buffer_utilizations(Arc::clone(&client), tx, interval)),
     ]
 }
 
@@ -404,6 +452,8 @@ pub async fn init_components(client: &Client) -> Result<state::State, ()> {
                     #[cfg(feature = "allocation-tracing")]
                     allocated_bytes: 0,
                     errors: 0,
+                    errors_throughput_sec: 0,
+                    buffer_utilization: None,
                 },
             ))
         })
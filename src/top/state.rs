@@ -36,6 +36,10 @@ pub enum EventType {
     /// Interval in ms + identified overall metric + output-specific metrics
     SentEventsThroughputs(i64, Vec<SentEventsMetric>),
     ErrorsTotals(Vec<IdentifiedMetric>),
+    /// Interval in ms + identified metric
+    ErrorsThroughputs(i64, Vec<IdentifiedMetric>),
+    /// Utilization of the fullest buffer stage of each component, between 0 and 1
+    BufferUtilizations(Vec<(ComponentKey, f64)>),
     #[cfg(feature = "allocation-tracing")]
     AllocatedBytes(Vec<IdentifiedMetric>),
     ComponentAdded(ComponentRow),
@@ -123,6 +127,9 @@ pub struct ComponentRow {
     #[cfg(feature = "allocation-tracing")]
     pub allocated_bytes: i64,
     pub errors: i64,
+    pub errors_throughput_sec: i64,
+    /// `None` for components without a bounded buffer
+    pub buffer_utilization: Option<f64>,
 }
 
 impl ComponentRow {
@@ -227,6 +234,21 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
                         }
                     }
                 }
+                EventType::ErrorsThroughputs(interval, rows) => {
+                    for (key, v) in rows {
+                        if let Some(r) = state.components.get_mut(&key) {
+                            r.errors_throughput_sec =
+                                (v as f64 * (1000.0 / interval as f64)) as i64;
+                        }
+                    }
+                }
+                EventType::BufferUtilizations(rows) => {
+                    for (key, v) in rows {
+                        if let Some(r) = state.components.get_mut(&key) {
+                            r.buffer_utilization = Some(v);
+                        }
+                    }
+                }
                 #[cfg(feature = "allocation-tracing")]
                 EventType::AllocatedBytes(rows) => {
                     for (key, v) in rows {
@@ -1,18 +1,22 @@
 use std::{
-    collections::HashMap,
+    collections::{hash_map::DefaultHasher, HashMap},
     future::ready,
+    hash::{Hash, Hasher},
     num::NonZeroUsize,
     sync::{Arc, Mutex},
     time::Instant,
 };
 
-use futures::{stream::FuturesOrdered, FutureExt, StreamExt, TryStreamExt};
+use futures::{future, stream::FuturesOrdered, FutureExt, StreamExt, TryStreamExt};
 use futures_util::stream::FuturesUnordered;
 use once_cell::sync::Lazy;
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
 use tokio::{
     select,
-    sync::{mpsc::UnboundedSender, oneshot},
+    sync::{
+        mpsc::{self, UnboundedSender},
+        oneshot,
+    },
     time::{timeout, Duration},
 };
 use tracing::Instrument;
@@ -20,6 +24,7 @@ use vector_lib::config::LogNamespace;
 use vector_lib::internal_event::{
     self, CountByteSize, EventsSent, InternalEventHandle as _, Registered,
 };
+use vector_lib::lookup::lookup_v2::ConfigTargetPath;
 use vector_lib::transform::update_runtime_schema_definition;
 use vector_lib::{
     buffers::{
@@ -32,17 +37,19 @@ use vector_lib::{
     schema::Definition,
     EstimatedJsonEncodedSizeOf,
 };
+use vector_vrl_functions::set_state::Keyspaces;
 
 use super::{
     fanout::{self, Fanout},
-    schema,
+    rejected, schema,
     task::{Task, TaskOutput, TaskResult},
     BuiltBuffer, ConfigDiff,
 };
 use crate::{
     config::{
-        ComponentKey, Config, DataType, EnrichmentTableConfig, Input, Inputs, OutputId,
-        ProxyConfig, SinkContext, SourceContext, TransformContext, TransformOuter, TransformOutput,
+        load_keyspaces, ComponentKey, Config, DataType, EnrichmentTableConfig, Input, Inputs,
+        OutputId, ProxyConfig, SinkContext, SourceContext, TransformContext, TransformOuter,
+        TransformOutput, REJECTED_OUTPUT,
     },
     event::{EventArray, EventContainer},
     extra_context::ExtraContext,
@@ -59,9 +66,16 @@ use crate::{
 static ENRICHMENT_TABLES: Lazy<vector_lib::enrichment::TableRegistry> =
     Lazy::new(vector_lib::enrichment::TableRegistry::default);
 
+/// The keyspaces of the topology, whose entries are kept across reloads.
+static KEYSPACES: Lazy<Keyspaces> = Lazy::new(Keyspaces::default);
+
 pub(crate) static SOURCE_SENDER_BUFFER_SIZE: Lazy<usize> =
     Lazy::new(|| *TRANSFORM_CONCURRENCY_LIMIT * CHUNK_SIZE);
 
+/// The number of event arrays that can be queued for each task of a transform running concurrently
+/// with an ordering key.
+const ORDERED_LANE_BUFFER_SIZE: usize = 4;
+
 const READY_ARRAY_CAPACITY: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(CHUNK_SIZE * 4) };
 pub(crate) const TOPOLOGY_BUFFER_SIZE: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(100) };
 
@@ -113,6 +127,7 @@ impl<'a> Builder<'a> {
     /// Builds the new pieces of the topology found in `self.diff`.
     async fn build(mut self) -> Result<TopologyPieces, Vec<String>> {
         let enrichment_tables = self.load_enrichment_tables().await;
+        load_keyspaces(&KEYSPACES, &self.config.keyspaces);
         let source_tasks = self.build_sources().await;
         self.build_transforms(enrichment_tables).await;
         self.build_sinks(enrichment_tables).await;
@@ -462,6 +477,7 @@ impl<'a> Builder<'a> {
                 key: Some(key.clone()),
                 globals: self.config.global.clone(),
                 enrichment_tables: enrichment_tables.clone(),
+                keyspaces: KEYSPACES.clone(),
                 schema_definitions,
                 merged_schema_definition: merged_definition.clone(),
                 schema: self.config.schema,
@@ -521,6 +537,7 @@ impl<'a> Builder<'a> {
 
             let typetag = sink.inner.get_component_name();
             let input_type = sink.inner.input().data_type();
+            let reroute_rejected = sink.reroute_rejected;
 
             let span = error_span!(
                 "sink",
@@ -585,8 +602,21 @@ impl<'a> Builder<'a> {
                 Ok(built) => built,
             };
 
+            let rejected_output = reroute_rejected.then(|| {
+                let (fanout, control) = Fanout::new();
+                self.outputs.insert(
+                    OutputId {
+                        component: key.clone(),
+                        port: Some(REJECTED_OUTPUT.to_owned()),
+                    },
+                    control,
+                );
+                fanout
+            });
+
             let (trigger, tripwire) = Tripwire::new();
 
+            let component_key = key.clone();
             let sink = async move {
                 debug!("Sink starting.");
 
@@ -605,26 +635,39 @@ impl<'a> Builder<'a> {
                 let mut rx = wrap(rx);
 
                 let events_received = register!(EventsReceived);
-                sink.run(
-                    rx.by_ref()
-                        .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
-                        .inspect(|events| {
-                            events_received.emit(CountByteSize(
-                                events.len(),
-                                events.estimated_json_encoded_size_of(),
-                            ))
-                        })
-                        .take_until_if(tripwire),
-                )
-                .await
-                .map(|_| {
-                    debug!("Sink finished normally.");
-                    TaskOutput::Sink(rx)
-                })
-                .map_err(|_| {
-                    debug!("Sink finished with an error.");
-                    TaskError::Opaque
-                })
+                let events = rx
+                    .by_ref()
+                    .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
+                    .inspect(|events| {
+                        events_received.emit(CountByteSize(
+                            events.len(),
+                            events.estimated_json_encoded_size_of(),
+                        ))
+                    })
+                    .take_until_if(tripwire);
+
+                let result = match rejected_output {
+                    None => sink.run(events).await.map_err(|_| TaskError::Opaque),
+                    Some(fanout) => {
+                        let (events, forward) =
+                            rejected::reroute_rejected(&component_key, events, fanout);
+                        let (result, forwarded) = future::join(sink.run(events), forward).await;
+                        forwarded
+                            .map_err(TaskError::wrapped)
+                            .and(result.map_err(|_| TaskError::Opaque))
+                    }
+                };
+
+                match result {
+                    Ok(()) => {
+                        debug!("Sink finished normally.");
+                        Ok(TaskOutput::Sink(rx))
+                    }
+                    Err(error) => {
+                        debug!("Sink finished with an error.");
+                        Err(error)
+                    }
+                }
             };
 
             let task = Task::new(key.clone(), typetag, sink);
@@ -717,6 +760,45 @@ impl TopologyPieces {
     }
 }
 
+/// Replaces the data of all the enrichment tables of `config`, keeping the indexes that were
+/// added to the tables they replace.
+///
+/// Unit tests share the enrichment tables, so each test loads its own tables again before
+/// running in case a test built after it loaded different data under the same names.
+pub(crate) async fn reload_enrichment_tables(config: &Config) -> Result<(), Vec<String>> {
+    let mut enrichment_tables = HashMap::new();
+    let mut errors = Vec::new();
+
+    for (name, table) in config.enrichment_tables.iter() {
+        let table_name = name.to_string();
+        let mut table = match table.inner.build(&config.global).await {
+            Ok(table) => table,
+            Err(error) => {
+                errors.push(format!("Enrichment Table \"{}\": {}", name, error));
+                continue;
+            }
+        };
+
+        for (case, index) in ENRICHMENT_TABLES.index_fields(&table_name) {
+            if let Err(error) =
+                table.add_index(case, &index.iter().map(|s| s.as_ref()).collect::<Vec<_>>())
+            {
+                errors.push(format!("Enrichment Table \"{}\": {}", name, error));
+            }
+        }
+
+        enrichment_tables.insert(table_name, table);
+    }
+
+    if errors.is_empty() {
+        ENRICHMENT_TABLES.load(enrichment_tables);
+        ENRICHMENT_TABLES.finish_load();
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 const fn filter_events_type(events: &EventArray, data_type: DataType) -> bool {
     match events {
         EventArray::Logs(_) => data_type.contains(DataType::Log),
@@ -733,6 +815,8 @@ struct TransformNode {
     input_details: Input,
     outputs: Vec<TransformOutput>,
     enable_concurrency: bool,
+    concurrency: Option<NonZeroUsize>,
+    ordering_key: Option<ConfigTargetPath>,
 }
 
 impl TransformNode {
@@ -754,6 +838,8 @@ impl TransformNode {
                 global_log_namespace,
             ),
             enable_concurrency: transform.inner.enable_concurrency(),
+            concurrency: transform.concurrency,
+            ordering_key: transform.ordering_key.clone(),
         }
     }
 }
@@ -786,10 +872,13 @@ fn build_sync_transform(
     let (outputs, controls) = TransformOutputs::new(node.outputs, &node.key);
 
     let runner = Runner::new(t, input_rx, node.input_details.data_type(), outputs);
-    let transform = if node.enable_concurrency {
-        runner.run_concurrently().boxed()
-    } else {
-        runner.run_inline().boxed()
+    let concurrency = node
+        .concurrency
+        .map_or(*TRANSFORM_CONCURRENCY_LIMIT, NonZeroUsize::get);
+    let transform = match node.ordering_key {
+        _ if !node.enable_concurrency || concurrency == 1 => runner.run_inline().boxed(),
+        Some(key) => runner.run_ordered_by_key(concurrency, key).boxed(),
+        None => runner.run_concurrently(concurrency).boxed(),
     };
 
     let transform = async move {
@@ -891,7 +980,7 @@ impl Runner {
         Ok(TaskOutput::Transform)
     }
 
-    async fn run_concurrently(mut self) -> TaskResult {
+    async fn run_concurrently(mut self, concurrency: usize) -> TaskResult {
         let input_rx = self
             .input_rx
             .take()
@@ -921,7 +1010,7 @@ impl Runner {
                     }
                 }
 
-                input_arrays = input_rx.next(), if in_flight.len() < *TRANSFORM_CONCURRENCY_LIMIT && !shutting_down => {
+                input_arrays = input_rx.next(), if in_flight.len() < concurrency && !shutting_down => {
                     match input_arrays {
                         Some(input_arrays) => {
                             let mut len = 0;
@@ -957,6 +1046,147 @@ impl Runner {
 
         Ok(TaskOutput::Transform)
     }
+
+    /// Runs `lanes` copies of the transform, each processing the events whose `key` hashes to it
+    /// in order. Events with the same key are output in the order they were received, while events
+    /// with different keys may be reordered.
+    async fn run_ordered_by_key(mut self, lanes: usize, key: ConfigTargetPath) -> TaskResult {
+        let mut input_rx = self
+            .input_rx
+            .take()
+            .expect("can't run runner twice")
+            .into_stream()
+            .filter(move |events| ready(filter_events_type(events, self.input_type)));
+
+        // The results channel is unbounded so that lanes never block on it while the runner is
+        // waiting for room in one of their inputs. The lane inputs bound the events in flight.
+        let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+        let mut lane_txs = Vec::with_capacity(lanes);
+        let mut lane_tasks = Vec::with_capacity(lanes);
+        for _ in 0..lanes {
+            let (lane_tx, mut lane_rx) = mpsc::channel::<EventArray>(ORDERED_LANE_BUFFER_SIZE);
+            let mut t = self.transform.clone();
+            let empty_buf = self.outputs.new_buf_with_capacity(0);
+            let results_tx = results_tx.clone();
+            lane_tasks.push(tokio::spawn(
+                async move {
+                    while let Some(events) = lane_rx.recv().await {
+                        let mut outputs_buf = empty_buf.clone();
+                        t.transform_all(events, &mut outputs_buf);
+                        if results_tx.send(outputs_buf).is_err() {
+                            break;
+                        }
+                    }
+                }
+                .in_current_span(),
+            ));
+            lane_txs.push(lane_tx);
+        }
+        drop(results_tx);
+
+        let mut shutting_down = false;
+
+        self.timer.start_wait();
+        loop {
+            tokio::select! {
+                biased;
+
+                result = results_rx.recv() => {
+                    match result {
+                        Some(mut outputs_buf) => {
+                            self.send_outputs(&mut outputs_buf).await
+                                .map_err(TaskError::wrapped)?;
+                        }
+                        // All lanes have finished, either because the input ended or because one
+                        // of them panicked, which is reported below.
+                        None => break,
+                    }
+                }
+
+                events = input_rx.next(), if !shutting_down => {
+                    match events {
+                        Some(events) => {
+                            self.on_events_received(&events);
+                            for (lane, events) in partition_by_key(events, &key, lanes) {
+                                // Lanes only stop receiving early if they panicked.
+                                lane_txs[lane].send(events).await
+                                    .map_err(|_| TaskError::Panicked)?;
+                            }
+                        }
+                        None => {
+                            shutting_down = true;
+                            lane_txs.clear();
+                        }
+                    }
+                }
+            }
+        }
+
+        for task in lane_tasks {
+            task.await.map_err(|_| TaskError::Panicked)?;
+        }
+
+        Ok(TaskOutput::Transform)
+    }
+}
+
+/// Splits `events` into arrays of events that hash to the same lane, preserving their order.
+///
+/// Logs and traces are hashed by the value of `key`, and metrics by their series.
+fn partition_by_key(
+    events: EventArray,
+    key: &ConfigTargetPath,
+    lanes: usize,
+) -> Vec<(usize, EventArray)> {
+    fn lane_of(hashable: impl Hash, lanes: usize) -> usize {
+        let mut hasher = DefaultHasher::new();
+        hashable.hash(&mut hasher);
+        (hasher.finish() % lanes as u64) as usize
+    }
+
+    fn split<T>(
+        events: Vec<T>,
+        lanes: usize,
+        lane: impl Fn(&T) -> usize,
+        into_array: impl Fn(Vec<T>) -> EventArray,
+    ) -> Vec<(usize, EventArray)> {
+        let mut partitions: Vec<Vec<T>> = (0..lanes).map(|_| Vec::new()).collect();
+        for event in events {
+            partitions[lane(&event)].push(event);
+        }
+        partitions
+            .into_iter()
+            .enumerate()
+            .filter(|(_, events)| !events.is_empty())
+            .map(|(lane, events)| (lane, into_array(events)))
+            .collect()
+    }
+
+    match events {
+        EventArray::Logs(logs) => split(
+            logs,
+            lanes,
+            |log| lane_of(log.get(&key.0).map(|value| value.to_string_lossy()), lanes),
+            EventArray::Logs,
+        ),
+        EventArray::Traces(traces) => split(
+            traces,
+            lanes,
+            |trace| {
+                lane_of(
+                    trace.get(&key.0).map(|value| value.to_string_lossy()),
+                    lanes,
+                )
+            },
+            EventArray::Traces,
+        ),
+        EventArray::Metrics(metrics) => split(
+            metrics,
+            lanes,
+            |metric| lane_of(metric.series(), lanes),
+            EventArray::Metrics,
+        ),
+    }
 }
 
 fn build_task_transform(
@@ -1034,3 +1264,90 @@ fn build_task_transform(
 
     (task, outputs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{LogEvent, Metric, MetricKind, MetricValue};
+
+    fn log(tenant: &str, message: &str) -> LogEvent {
+        let mut log = LogEvent::from(message);
+        log.insert("tenant", tenant);
+        log
+    }
+
+    #[test]
+    fn partitions_logs_by_key_in_order() {
+        let key = ConfigTargetPath::from("tenant");
+        let events = EventArray::Logs(vec![
+            log("a", "1"),
+            log("b", "2"),
+            log("a", "3"),
+            log("b", "4"),
+            log("a", "5"),
+        ]);
+
+        let partitions = partition_by_key(events, &key, 16);
+
+        let mut by_tenant = HashMap::new();
+        for (lane, events) in partitions {
+            let EventArray::Logs(logs) = events else {
+                panic!("expected logs");
+            };
+            for log in logs {
+                let tenant = log.get("tenant").unwrap().to_string_lossy().into_owned();
+                let message = log.get("message").unwrap().to_string_lossy().into_owned();
+                let (tenant_lane, messages) = by_tenant
+                    .entry(tenant)
+                    .or_insert_with(|| (lane, Vec::new()));
+                assert_eq!(
+                    *tenant_lane, lane,
+                    "all events of a key go to the same lane"
+                );
+                messages.push(message);
+            }
+        }
+
+        assert_eq!(by_tenant["a"].1, vec!["1", "3", "5"]);
+        assert_eq!(by_tenant["b"].1, vec!["2", "4"]);
+    }
+
+    #[test]
+    fn partitions_metrics_by_series() {
+        let key = ConfigTargetPath::from("tenant");
+        let metric = |name: &str, value: f64| {
+            Metric::new(
+                name,
+                MetricKind::Incremental,
+                MetricValue::Counter { value },
+            )
+        };
+        let events = EventArray::Metrics(vec![
+            metric("requests", 1.0),
+            metric("errors", 1.0),
+            metric("requests", 2.0),
+        ]);
+
+        let partitions = partition_by_key(events, &key, 16);
+
+        let requests = partitions
+            .into_iter()
+            .filter_map(|(_, events)| match events {
+                EventArray::Metrics(metrics) => Some(metrics),
+                _ => None,
+            })
+            .find(|metrics| metrics.iter().any(|metric| metric.name() == "requests"))
+            .unwrap()
+            .into_iter()
+            .filter(|metric| metric.name() == "requests")
+            .map(|metric| metric.value().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            requests,
+            vec![
+                MetricValue::Counter { value: 1.0 },
+                MetricValue::Counter { value: 2.0 }
+            ]
+        );
+    }
+}
@@ -38,6 +38,9 @@ pub struct TopologyController {
     pub require_healthy: Option<bool>,
     #[cfg(feature = "api")]
     pub api_server: Option<api::Server>,
+    /// Used by the API server to reload the configuration.
+    #[cfg(feature = "api")]
+    pub signal_tx: crate::signal::SignalTx,
     pub extra_context: ExtraContext,
 }
 
@@ -82,6 +85,7 @@ impl TopologyController {
                 self.topology.config(),
                 self.topology.watch(),
                 Arc::<AtomicBool>::clone(&self.topology.running),
+                self.signal_tx.clone(),
                 &Handle::current(),
             ) {
                 Ok(api_server) => {
@@ -13,6 +13,7 @@ pub mod schema;
 pub mod builder;
 mod controller;
 mod ready_arrays;
+mod rejected;
 mod running;
 mod task;
 
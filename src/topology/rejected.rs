@@ -0,0 +1,192 @@
+//! Rerouting of the events a sink fails to deliver to its `rejected` output.
+//!
+//! A sink rerouting its rejected events receives copies of its input events which are tracked by
+//! batch notifiers of their own, while the originals, and so their finalizers, are held back. Once
+//! the sink is done with an event, the original is either finalized as delivered, or sent to the
+//! `rejected` output along with its finalizers, to be acknowledged by the components consuming it.
+
+use std::sync::Arc;
+
+use futures::{Stream, StreamExt};
+use tokio::sync::mpsc;
+use vector_lib::{
+    event::{
+        array::events_into_arrays, BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer,
+        EventStatus, Finalizable,
+    },
+    internal_event::{self, CountByteSize, EventsSent, InternalEventHandle as _},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use super::fanout::Fanout;
+use crate::{
+    config::{ComponentKey, OutputId, REJECTED_OUTPUT},
+    event::{Event, EventArray, EventContainer},
+    source_sender::CHUNK_SIZE,
+};
+
+/// The number of event arrays the sink can have in flight before its input is blocked on their
+/// outcome.
+const PENDING_ARRAYS: usize = 128;
+
+/// The events of an array the sink received, waiting for the outcome of their copies.
+struct Pending {
+    events: Vec<(Event, BatchStatusReceiver)>,
+}
+
+/// Wraps the input of a sink, tracking the events the sink receives to send the ones it fails to
+/// deliver to the `rejected` output of the given fanout.
+///
+/// Returns the stream to run the sink on, and the future forwarding the rejected events, which
+/// completes once the stream is done and the outcome of all of its events is known.
+pub(super) fn reroute_rejected(
+    key: &ComponentKey,
+    input: impl Stream<Item = EventArray> + Send,
+    fanout: Fanout,
+) -> (
+    impl Stream<Item = EventArray> + Send,
+    impl std::future::Future<Output = crate::Result<()>>,
+) {
+    let (tx, rx) = mpsc::channel(PENDING_ARRAYS);
+    let output_id = Arc::new(OutputId {
+        component: key.clone(),
+        port: Some(REJECTED_OUTPUT.to_owned()),
+    });
+
+    let input = input.then(move |array| {
+        let tx = tx.clone();
+        async move {
+            let (tracked, pending) = track(array);
+            // The forwarder only stops once this stream is done, so this can't fail.
+            _ = tx.send(pending).await;
+            tracked
+        }
+    });
+
+    (input, forward(rx, fanout, output_id))
+}
+
+/// Splits an array into copies of its events for the sink to finalize, and the originals holding
+/// the finalizers of the upstream components.
+fn track(array: EventArray) -> (EventArray, Pending) {
+    let mut tracked = array.clone();
+    let mut receivers = Vec::with_capacity(array.len());
+    for mut event in tracked.iter_events_mut() {
+        // The finalizers are shared with the originals, which keep them alive.
+        drop(event.metadata_mut().take_finalizers());
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        event
+            .metadata_mut()
+            .add_finalizer(EventFinalizer::new(batch));
+        receivers.push(receiver);
+    }
+
+    let events = array.into_events().zip(receivers).collect();
+    (tracked, Pending { events })
+}
+
+async fn forward(
+    mut rx: mpsc::Receiver<Pending>,
+    mut fanout: Fanout,
+    output_id: Arc<OutputId>,
+) -> crate::Result<()> {
+    let events_sent = register!(EventsSent::from(internal_event::Output(Some(
+        REJECTED_OUTPUT.into()
+    ))));
+
+    while let Some(pending) = rx.recv().await {
+        let mut rejected = Vec::new();
+        for (mut event, receiver) in pending.events {
+            match receiver.await {
+                BatchStatus::Delivered => {
+                    event
+                        .take_finalizers()
+                        .update_status(EventStatus::Delivered);
+                }
+                BatchStatus::Errored | BatchStatus::Rejected => {
+                    event.metadata_mut().set_upstream_id(Arc::clone(&output_id));
+                    rejected.push(event);
+                }
+            }
+        }
+
+        for array in events_into_arrays(rejected, Some(CHUNK_SIZE)) {
+            events_sent.emit(CountByteSize(
+                array.len(),
+                array.estimated_json_encoded_size_of(),
+            ));
+            fanout.send(array, None).await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use futures::{future, stream};
+    use tracing::Span;
+    use vector_lib::buffers::{topology::builder::TopologyBuilder, WhenFull};
+
+    use super::*;
+    use crate::{
+        event::{EventRef, LogEvent},
+        topology::fanout::ControlMessage,
+    };
+
+    #[tokio::test]
+    async fn reroutes_rejected_events() {
+        let (batch, mut upstream) = BatchNotifier::new_with_receiver();
+        let array = EventArray::from(vec![
+            LogEvent::from("delivered").with_batch_notifier(&batch),
+            LogEvent::from("rejected").with_batch_notifier(&batch),
+        ]);
+        drop(batch);
+
+        let (fanout, control) = Fanout::new();
+        let (tx, rx) = TopologyBuilder::standalone_memory(
+            NonZeroUsize::new(10).unwrap(),
+            WhenFull::Block,
+            &Span::current(),
+        )
+        .await;
+        control
+            .send(ControlMessage::Add(ComponentKey::from("dlq"), tx))
+            .unwrap();
+
+        let (events, forward) =
+            reroute_rejected(&ComponentKey::from("sink"), stream::iter([array]), fanout);
+        let sink = events.for_each(|mut array| async move {
+            for (index, mut event) in array.iter_events_mut().enumerate() {
+                let status = if index == 0 {
+                    EventStatus::Delivered
+                } else {
+                    EventStatus::Rejected
+                };
+                event.metadata_mut().take_finalizers().update_status(status);
+            }
+        });
+        let ((), forwarded) = future::join(sink, forward).await;
+        forwarded.unwrap();
+
+        let arrays = rx.into_stream().collect::<Vec<_>>().await;
+        assert_eq!(arrays.len(), 1);
+        let rejected = arrays[0]
+            .iter_events()
+            .map(EventRef::as_log)
+            .collect::<Vec<_>>();
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0]["message"], "rejected".into());
+        assert_eq!(
+            rejected[0].metadata().upstream_id().unwrap().to_string(),
+            "sink.rejected"
+        );
+
+        // The source is only acknowledged once the rerouted events are finalized.
+        assert!(upstream.try_recv().is_err());
+        drop(arrays);
+        assert_eq!(upstream.await, BatchStatus::Delivered);
+    }
+}
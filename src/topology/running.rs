@@ -466,6 +466,7 @@ impl RunningTopology {
         for key in &diff.sinks.to_remove {
             debug!(component = %key, "Removing sink.");
             self.remove_inputs(key, diff, new_config).await;
+            self.remove_outputs(key);
         }
 
         // After that, for any changed sinks, we temporarily detach their inputs (not remove) so
@@ -494,6 +495,7 @@ impl RunningTopology {
                 buffer_tx.insert(key.clone(), self.inputs.get(key).unwrap().clone());
             }
             self.remove_inputs(key, diff, new_config).await;
+            self.remove_outputs(key);
         }
 
         // Now that we've disconnected or temporarily detached the inputs to all changed/removed
@@ -563,7 +565,8 @@ impl RunningTopology {
             }
 
             for key in &diff.sinks.to_remove {
-                // Sinks only have inputs
+                // Sinks have inputs, and an output if they reroute rejected events
+                self.outputs_tap_metadata.remove(key);
                 self.inputs_tap_metadata.remove(key);
             }
 
@@ -581,6 +584,17 @@ impl RunningTopology {
                 }
             }
 
+            for key in diff.sinks.changed_and_added() {
+                self.outputs_tap_metadata.remove(key);
+                if let (Some(task), true) = (
+                    new_pieces.tasks.get(key),
+                    new_pieces.outputs.contains_key(key),
+                ) {
+                    self.outputs_tap_metadata
+                        .insert(key.clone(), ("sink", task.typetag().to_string()));
+                }
+            }
+
             for (key, input) in &new_pieces.inputs {
                 self.inputs_tap_metadata
                     .insert(key.clone(), input.1.clone());
@@ -601,6 +615,15 @@ impl RunningTopology {
             self.setup_outputs(key, new_pieces).await;
         }
 
+        // Sinks rerouting the events they reject have an output as well, which may be consumed by
+        // transforms and other sinks.
+        for key in diff.sinks.changed_and_added() {
+            if new_pieces.outputs.contains_key(key) {
+                debug!(component = %key, "Configuring outputs for sink.");
+                self.setup_outputs(key, new_pieces).await;
+            }
+        }
+
         // Now that all possible outputs are configured, we can start wiring up inputs, starting
         // with transforms.
         for key in diff.transforms.changed_and_added() {
@@ -650,6 +673,7 @@ impl RunningTopology {
 
             let mut removals = diff.sources.to_remove.clone();
             removals.extend(diff.transforms.to_remove.iter().cloned());
+            removals.extend(diff.sinks.to_remove.iter().cloned());
             self.watch
                 .0
                 .send(TapResource {
@@ -665,8 +689,8 @@ impl RunningTopology {
                         .changed_and_added()
                         .map(|key| key.to_string())
                         .collect(),
-                    // Note, only sources and transforms are relevant. Sinks do
-                    // not have outputs to tap.
+                    // Note, sinks are only relevant if they reroute rejected
+                    // events, but removing the others is a no-op.
                     removals,
                 })
                 .expect("Couldn't broadcast config changes.");
@@ -1067,5 +1091,14 @@ fn get_changed_outputs(diff: &ConfigDiff, output_ids: Inputs<OutputId>) -> Vec<O
         );
     }
 
+    for sink_key in &diff.sinks.to_change {
+        changed_outputs.extend(
+            output_ids
+                .iter()
+                .filter(|id| &id.component == sink_key)
+                .cloned(),
+        );
+    }
+
     changed_outputs
 }
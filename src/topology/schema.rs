@@ -90,6 +90,20 @@ pub fn possible_definitions(
 
             definitions.append(&mut transform_definition);
         }
+
+        // If the input is the `rejected` output of a sink, the events are passed through as they
+        // were received by the sink.
+        if let Some(inputs) = config.sink_inputs(key) {
+            let sink_definitions =
+                possible_definitions(inputs, config, enrichment_tables.clone(), cache)?;
+            definitions.append(
+                &mut input.with_definitions(
+                    sink_definitions
+                        .into_iter()
+                        .map(|(_, definition)| definition),
+                ),
+            );
+        }
     }
 
     Ok(definitions)
@@ -193,6 +207,19 @@ pub(super) fn expanded_definitions(
             // Append whatever number of additional pipelines we created to the existing
             // pipeline definitions.
             definitions.append(&mut transform_definition);
+
+        // A sink reroutes the events it rejects as they were received, so each of the pipelines
+        // leading into it leads out of its `rejected` output too.
+        } else if let Some(inputs) = config.sink_inputs(key) {
+            let sink_definitions =
+                expanded_definitions(enrichment_tables.clone(), inputs, config, cache)?;
+            definitions.append(
+                &mut input.with_definitions(
+                    sink_definitions
+                        .into_iter()
+                        .map(|(_, definition)| definition),
+                ),
+            );
         }
     }
 
@@ -283,6 +310,20 @@ pub(crate) fn input_definitions(
 
             definitions.append(&mut transform_definitions);
         }
+
+        // If the input is the `rejected` output of a sink we retrieve the definitions of the
+        // inputs of the sink, since it reroutes events as it received them.
+        if let Some(inputs) = config.sink_inputs(key) {
+            let sink_definitions =
+                input_definitions(inputs, config, enrichment_tables.clone(), cache)?;
+            definitions.append(
+                &mut input.with_definitions(
+                    sink_definitions
+                        .into_iter()
+                        .map(|(_, definition)| definition),
+                ),
+            );
+        }
     }
 
     Ok(definitions)
@@ -308,7 +349,10 @@ pub(super) fn validate_sink_expectations(
     // Get the schema against which we need to validate the schemas of the components feeding into
     // this sink.
     let input = sink.inner.input();
-    let requirement = input.schema_requirement();
+    let requirement = input
+        .schema_requirement()
+        .clone()
+        .with_required_meanings(sink.required_meanings.iter().map(String::as_str));
 
     // Get all pipeline definitions feeding into this sink.
     let mut cache = HashMap::default();
@@ -349,6 +393,11 @@ pub trait ComponentContainer {
 
     fn transform_inputs(&self, key: &ComponentKey) -> Option<&[OutputId]>;
 
+    /// Gets the inputs of the sink with the given key, if it reroutes the events it rejects.
+    fn sink_inputs(&self, _key: &ComponentKey) -> Option<&[OutputId]> {
+        None
+    }
+
     fn transform_outputs(
         &self,
         key: &ComponentKey,
@@ -421,6 +470,12 @@ impl ComponentContainer for Config {
         self.transform(key).map(|transform| &transform.inputs[..])
     }
 
+    fn sink_inputs(&self, key: &ComponentKey) -> Option<&[OutputId]> {
+        self.sink(key)
+            .filter(|sink| sink.reroute_rejected)
+            .map(|sink| &sink.inputs[..])
+    }
+
     fn transform_outputs(
         &self,
         key: &ComponentKey,
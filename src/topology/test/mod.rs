@@ -1,6 +1,7 @@
 use std::{
     collections::HashMap,
     iter,
+    num::NonZeroUsize,
     sync::{
         atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
@@ -872,7 +873,8 @@ async fn topology_disk_buffer_flushes_on_idle() {
         sink1,
     );
     sink1_outer.buffer = BufferConfig::Single(BufferType::DiskV2 {
-        max_size: std::num::NonZeroU64::new(268435488).unwrap(),
+        max_size: std::num::NonZeroU64::new(268435488),
+        weight: None,
         when_full: WhenFull::DropNewest,
     });
     config.add_sink_outer("out1", sink1_outer);
@@ -931,6 +933,27 @@ async fn topology_transform_error_definition() {
     );
 }
 
+#[test]
+fn topology_concurrency_requires_concurrent_transform() {
+    trace_init();
+
+    let mut config = Config::builder();
+
+    config.add_source("in", basic_source().1);
+    config.add_transform("transform", &["in"], basic_transform("", 0.0));
+    config.add_sink("sink", &["transform"], basic_sink(10).1);
+    config.transforms[0].concurrency = NonZeroUsize::new(4);
+
+    let errors = config.build().unwrap_err();
+
+    assert_eq!(
+        errors,
+        vec![
+            r#"Transform "transform" does not support processing events concurrently, so it can't set `concurrency` or `ordering_key`."#
+        ]
+    );
+}
+
 #[tokio::test]
 async fn source_metadata_reaches_sink() {
     trace_init();
@@ -0,0 +1,293 @@
+use std::{collections::HashMap, num::NonZeroU64};
+
+use vector_lib::{
+    config::LogNamespace, configurable::configurable_component, lookup::lookup_v2::ConfigTargetPath,
+};
+use vrl::value::{Collection, Kind};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    schema,
+    transforms::{aggregate_logs::transform::AggregateLogs, Transform},
+};
+
+/// Configuration for the `aggregate_logs` transform.
+#[configurable_component(transform(
+    "aggregate_logs",
+    "Aggregate logs over time windows into metrics or summary logs."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AggregateLogsConfig {
+    /// An ordered list of fields by which to group events.
+    ///
+    /// Each group with matching values for the specified fields is aggregated independently. When
+    /// no fields are specified, all events of a window are aggregated together.
+    ///
+    /// The values of these fields are added as tags to the aggregated metrics, or as fields to the
+    /// aggregated logs.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "service", docs::examples = "status"))]
+    pub group_by: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub window: WindowConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: AggregateLogsMode,
+
+    /// Sets the namespace of the aggregated metrics.
+    ///
+    /// Only used in `metrics` mode.
+    #[configurable(metadata(docs::examples = "service"))]
+    pub namespace: Option<String>,
+
+    /// A list of aggregates to compute over the events of each group and window.
+    pub aggregates: Vec<AggregateConfig>,
+}
+
+/// The windows over which events are aggregated.
+///
+/// Windows are aligned on the Unix epoch, and events are assigned to windows according to the time
+/// they are received by the transform.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "The type of the windows."))]
+pub enum WindowConfig {
+    /// Consecutive windows which don't overlap, each event being aggregated in a single window.
+    Tumbling {
+        /// The duration of each window, in seconds.
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        duration_secs: NonZeroU64,
+    },
+
+    /// Windows which overlap, a new window starting every `slide_secs`.
+    ///
+    /// Each event is aggregated in every window it falls in, that is in about
+    /// `duration_secs / slide_secs` windows.
+    Sliding {
+        /// The duration of each window, in seconds.
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        duration_secs: NonZeroU64,
+
+        /// The interval between the starts of consecutive windows, in seconds.
+        ///
+        /// Must not be greater than `duration_secs`.
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        slide_secs: NonZeroU64,
+    },
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self::Tumbling {
+            duration_secs: NonZeroU64::new(10).expect("static non-zero number"),
+        }
+    }
+}
+
+impl WindowConfig {
+    /// The duration of the windows, and the interval between their starts, in seconds.
+    pub(super) fn duration_and_slide(&self) -> crate::Result<(u64, u64)> {
+        match self {
+            Self::Tumbling { duration_secs } => Ok((duration_secs.get(), duration_secs.get())),
+            Self::Sliding {
+                duration_secs,
+                slide_secs,
+            } => {
+                if slide_secs > duration_secs {
+                    return Err(format!(
+                        "`window.slide_secs` ({slide_secs}) must not be greater than `window.duration_secs` ({duration_secs})"
+                    )
+                    .into());
+                }
+                Ok((duration_secs.get(), slide_secs.get()))
+            }
+        }
+    }
+}
+
+/// The type of the events emitted for each group and window.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregateLogsMode {
+    /// Emits a metric for each aggregate, tagged with the values of the `group_by` fields.
+    #[default]
+    Metrics,
+
+    /// Emits a single log, holding the values of the `group_by` fields, the bounds of the window
+    /// as `window_start` and `window_end`, and a field for each aggregate.
+    Log,
+}
+
+/// An aggregate computed over the events of each group and window.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct AggregateConfig {
+    /// The name of the aggregate.
+    ///
+    /// This is the name of the metric in `metrics` mode, and the path of the field in `log` mode.
+    #[configurable(metadata(docs::examples = "requests_total"))]
+    pub name: String,
+
+    #[configurable(derived)]
+    #[serde(flatten)]
+    pub function: AggregateFunction,
+}
+
+/// The function of an aggregate.
+///
+/// Events whose field is missing are not taken into account, and neither are those whose field
+/// isn't a number, nor a string holding one.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The function of the aggregate."))]
+pub enum AggregateFunction {
+    /// Counts the events, as an incremental counter.
+    Count,
+
+    /// Sums the values of a field, as an incremental counter.
+    Sum {
+        /// The field to aggregate.
+        field: ConfigTargetPath,
+    },
+
+    /// Keeps the minimum value of a field, as a gauge.
+    Min {
+        /// The field to aggregate.
+        field: ConfigTargetPath,
+    },
+
+    /// Keeps the maximum value of a field, as a gauge.
+    Max {
+        /// The field to aggregate.
+        field: ConfigTargetPath,
+    },
+
+    /// Averages the values of a field, as a gauge.
+    Mean {
+        /// The field to aggregate.
+        field: ConfigTargetPath,
+    },
+
+    /// Estimates quantiles of the values of a field, as a summary.
+    ///
+    /// In `log` mode, each quantile is stored under the aggregate as `p<percentile>`, for example
+    /// `p99` for the `0.99` quantile.
+    Percentiles {
+        /// The field to aggregate.
+        field: ConfigTargetPath,
+
+        /// The quantiles to estimate, between `0.0` and `1.0`.
+        #[serde(default = "default_quantiles")]
+        quantiles: Vec<f64>,
+    },
+}
+
+impl AggregateFunction {
+    pub(super) const fn field(&self) -> Option<&ConfigTargetPath> {
+        match self {
+            Self::Count => None,
+            Self::Sum { field }
+            | Self::Min { field }
+            | Self::Max { field }
+            | Self::Mean { field }
+            | Self::Percentiles { field, .. } => Some(field),
+        }
+    }
+}
+
+fn default_quantiles() -> Vec<f64> {
+    vec![0.5, 0.9, 0.99]
+}
+
+impl GenerateConfig for AggregateLogsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"group_by = ["service"]
+            window.type = "tumbling"
+            window.duration_secs = 10
+            aggregates = [{ name = "requests_total", type = "count" }]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aggregate_logs")]
+impl TransformConfig for AggregateLogsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        AggregateLogs::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        match self.mode {
+            AggregateLogsMode::Metrics => {
+                vec![TransformOutput::new(DataType::Metric, HashMap::new())]
+            }
+            // The aggregated logs are built from scratch, so only the namespaces of the inputs
+            // are kept.
+            AggregateLogsMode::Log => vec![TransformOutput::new(
+                DataType::Log,
+                input_definitions
+                    .iter()
+                    .map(|(output, definition)| {
+                        (
+                            output.clone(),
+                            schema::Definition::new_with_default_metadata(
+                                Kind::object(Collection::any()),
+                                definition.log_namespaces().clone(),
+                            ),
+                        )
+                    })
+                    .collect(),
+            )],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AggregateLogsConfig>();
+    }
+
+    #[test]
+    fn rejects_slides_longer_than_windows() {
+        let window: WindowConfig = toml::from_str(
+            r#"type = "sliding"
+            duration_secs = 10
+            slide_secs = 20"#,
+        )
+        .unwrap();
+        assert!(window.duration_and_slide().is_err());
+
+        let window: WindowConfig = toml::from_str(
+            r#"type = "sliding"
+            duration_secs = 60
+            slide_secs = 20"#,
+        )
+        .unwrap();
+        assert_eq!(window.duration_and_slide().unwrap(), (60, 20));
+    }
+}
@@ -0,0 +1,492 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use chrono::{DateTime, TimeDelta, Utc};
+use futures::{Stream, StreamExt};
+use vector_lib::event::{
+    discriminant::Discriminant,
+    metric::{Metric, MetricKind, MetricTags, MetricValue, Quantile},
+};
+use vrl::path::{parse_target_path, OwnedTargetPath};
+
+use crate::{
+    event::{Event, EventMetadata, LogEvent, ObjectMap, Value},
+    internal_events::AggregateLogsFieldInvalid,
+    metrics::AgentDDSketch,
+    transforms::{
+        aggregate_logs::config::{
+            AggregateConfig, AggregateFunction, AggregateLogsConfig, AggregateLogsMode,
+        },
+        TaskTransform,
+    },
+};
+
+/// The interval at which the windows which ended are flushed.
+const FLUSH_PERIOD: Duration = Duration::from_secs(1);
+
+/// The state of an aggregate, for a group of events of a window.
+#[derive(Debug)]
+enum AggregateState {
+    Count(u64),
+    Sum(f64),
+    Min(Option<f64>),
+    Max(Option<f64>),
+    Mean { sum: f64, count: u64 },
+    Percentiles(AgentDDSketch),
+}
+
+impl AggregateState {
+    fn new(function: &AggregateFunction) -> Self {
+        match function {
+            AggregateFunction::Count => Self::Count(0),
+            AggregateFunction::Sum { .. } => Self::Sum(0.0),
+            AggregateFunction::Min { .. } => Self::Min(None),
+            AggregateFunction::Max { .. } => Self::Max(None),
+            AggregateFunction::Mean { .. } => Self::Mean { sum: 0.0, count: 0 },
+            AggregateFunction::Percentiles { .. } => {
+                Self::Percentiles(AgentDDSketch::with_agent_defaults())
+            }
+        }
+    }
+
+    /// Adds an event, given the value of the field of the aggregate in the event, if any.
+    fn add(&mut self, value: Option<f64>) {
+        match (self, value) {
+            (Self::Count(count), _) => *count += 1,
+            (_, None) => (),
+            (Self::Sum(sum), Some(value)) => *sum += value,
+            (Self::Min(min), Some(value)) => *min = Some(min.map_or(value, |min| min.min(value))),
+            (Self::Max(max), Some(value)) => *max = Some(max.map_or(value, |max| max.max(value))),
+            (Self::Mean { sum, count }, Some(value)) => {
+                *sum += value;
+                *count += 1;
+            }
+            (Self::Percentiles(sketch), Some(value)) => sketch.insert(value),
+        }
+    }
+}
+
+/// The events of a group, within a window.
+#[derive(Debug)]
+struct Group {
+    /// The values of the `group_by` fields.
+    fields: Vec<Option<Value>>,
+    aggregates: Vec<AggregateState>,
+    metadata: EventMetadata,
+}
+
+/// A window, holding the groups of the events received during it.
+#[derive(Debug)]
+struct Window {
+    start: DateTime<Utc>,
+    groups: HashMap<Discriminant, Group>,
+}
+
+#[derive(Debug)]
+pub struct AggregateLogs {
+    group_by: Vec<String>,
+    group_by_paths: Vec<OwnedTargetPath>,
+    duration: TimeDelta,
+    slide: TimeDelta,
+    mode: AggregateLogsMode,
+    namespace: Option<String>,
+    aggregates: Vec<AggregateConfig>,
+    /// The open windows, by start.
+    windows: VecDeque<Window>,
+}
+
+impl AggregateLogs {
+    pub fn new(config: &AggregateLogsConfig) -> crate::Result<Self> {
+        let (duration, slide) = config.window.duration_and_slide()?;
+
+        let group_by_paths = config
+            .group_by
+            .iter()
+            .map(|field| {
+                parse_target_path(field).map_err(|_| format!("Could not parse path: `{field}`"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        for aggregate in &config.aggregates {
+            if let AggregateFunction::Percentiles { quantiles, .. } = &aggregate.function {
+                if let Some(quantile) = quantiles.iter().find(|q| !(0.0..=1.0).contains(*q)) {
+                    return Err(format!(
+                        "Quantiles of aggregate `{}` must be between 0 and 1, got {quantile}",
+                        aggregate.name
+                    )
+                    .into());
+                }
+            }
+        }
+
+        Ok(Self {
+            group_by: config.group_by.clone(),
+            group_by_paths,
+            duration: TimeDelta::seconds(duration as i64),
+            slide: TimeDelta::seconds(slide as i64),
+            mode: config.mode,
+            namespace: config.namespace.clone(),
+            aggregates: config.aggregates.clone(),
+            windows: VecDeque::new(),
+        })
+    }
+
+    /// Opens the windows the given time falls in, which aren't yet.
+    fn open_windows(&mut self, now: DateTime<Utc>) {
+        let slide = self.slide.num_seconds();
+        let latest = now.timestamp().div_euclid(slide) * slide;
+        // From the latest window started by now, back to the earliest one not to have ended by now.
+        let mut start = DateTime::from_timestamp(latest, 0).expect("valid timestamp");
+        while start + self.duration > now {
+            let index = self.windows.partition_point(|window| window.start < start);
+            if self.windows.get(index).map(|window| window.start) != Some(start) {
+                self.windows.insert(
+                    index,
+                    Window {
+                        start,
+                        groups: HashMap::new(),
+                    },
+                );
+            }
+            start -= self.slide;
+        }
+    }
+
+    fn record(&mut self, event: LogEvent, now: DateTime<Utc>) {
+        self.open_windows(now);
+
+        let discriminant = Discriminant::from_log_event(&event, &self.group_by);
+        let values = self
+            .aggregates
+            .iter()
+            .map(|aggregate| {
+                let field = aggregate.function.field()?;
+                let value = event.get(&field.0)?;
+                let number = match value {
+                    Value::Integer(value) => Some(*value as f64),
+                    Value::Float(value) => Some(value.into_inner()),
+                    value => value.to_string_lossy().parse::<f64>().ok(),
+                };
+                if number.is_none() {
+                    emit!(AggregateLogsFieldInvalid {
+                        field: &field.to_string(),
+                    });
+                }
+                number
+            })
+            .collect::<Vec<_>>();
+
+        let end = now - self.duration;
+        for window in self
+            .windows
+            .iter_mut()
+            .filter(|window| window.start <= now && window.start > end)
+        {
+            let group = window
+                .groups
+                .entry(discriminant.clone())
+                .or_insert_with(|| Group {
+                    fields: self
+                        .group_by_paths
+                        .iter()
+                        .map(|path| event.get(path).cloned())
+                        .collect(),
+                    aggregates: self
+                        .aggregates
+                        .iter()
+                        .map(|aggregate| AggregateState::new(&aggregate.function))
+                        .collect(),
+                    metadata: EventMetadata::default(),
+                });
+            for (state, value) in group.aggregates.iter_mut().zip(&values) {
+                state.add(*value);
+            }
+            group.metadata.merge(event.metadata().clone());
+        }
+    }
+
+    /// Flushes the windows which ended by the given time, or all of them if `now` is `None`.
+    fn flush_into(&mut self, now: Option<DateTime<Utc>>, output: &mut Vec<Event>) {
+        while let Some(window) = self.windows.front() {
+            if now.is_some_and(|now| window.start + self.duration > now) {
+                break;
+            }
+            let window = self.windows.pop_front().expect("window exists");
+            let end = window.start + self.duration;
+            for group in window.groups.into_values() {
+                match self.mode {
+                    AggregateLogsMode::Metrics => self.push_metrics(group, end, output),
+                    AggregateLogsMode::Log => self.push_log(group, window.start, end, output),
+                }
+            }
+        }
+    }
+
+    fn push_metrics(&self, group: Group, end: DateTime<Utc>, output: &mut Vec<Event>) {
+        let mut tags = MetricTags::default();
+        for (name, value) in self.group_by.iter().zip(group.fields) {
+            if let Some(value) = value {
+                tags.replace(name.clone(), value.to_string_lossy().into_owned());
+            }
+        }
+
+        for (aggregate, state) in self.aggregates.iter().zip(group.aggregates) {
+            let (kind, value) = match state {
+                AggregateState::Count(count) => (
+                    MetricKind::Incremental,
+                    MetricValue::Counter {
+                        value: count as f64,
+                    },
+                ),
+                AggregateState::Sum(value) => {
+                    (MetricKind::Incremental, MetricValue::Counter { value })
+                }
+                AggregateState::Min(Some(value)) | AggregateState::Max(Some(value)) => {
+                    (MetricKind::Absolute, MetricValue::Gauge { value })
+                }
+                AggregateState::Mean { sum, count } if count > 0 => (
+                    MetricKind::Absolute,
+                    MetricValue::Gauge {
+                        value: sum / count as f64,
+                    },
+                ),
+                AggregateState::Percentiles(sketch) if !sketch.is_empty() => (
+                    MetricKind::Absolute,
+                    MetricValue::AggregatedSummary {
+                        quantiles: quantiles(&aggregate.function, &sketch)
+                            .map(|(quantile, value)| Quantile { quantile, value })
+                            .collect(),
+                        count: u64::from(sketch.count()),
+                        sum: sketch.sum().unwrap_or_default(),
+                    },
+                ),
+                // No event of the group had a value for the field.
+                _ => continue,
+            };
+
+            let metric = Metric::new_with_metadata(
+                aggregate.name.clone(),
+                kind,
+                value,
+                group.metadata.clone(),
+            )
+            .with_namespace(self.namespace.clone())
+            .with_tags(Some(tags.clone()))
+            .with_timestamp(Some(end));
+            output.push(Event::Metric(metric));
+        }
+    }
+
+    fn push_log(
+        &self,
+        group: Group,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        output: &mut Vec<Event>,
+    ) {
+        let mut log = LogEvent::new_with_metadata(group.metadata);
+        for (path, value) in self.group_by_paths.iter().zip(group.fields) {
+            if let Some(value) = value {
+                log.insert(path, value);
+            }
+        }
+        log.insert("window_start", start);
+        log.insert("window_end", end);
+
+        for (aggregate, state) in self.aggregates.iter().zip(group.aggregates) {
+            let value = match state {
+                AggregateState::Count(count) => Value::from(count as i64),
+                AggregateState::Sum(value) => Value::from(value),
+                AggregateState::Min(Some(value)) | AggregateState::Max(Some(value)) => {
+                    Value::from(value)
+                }
+                AggregateState::Mean { sum, count } if count > 0 => Value::from(sum / count as f64),
+                AggregateState::Percentiles(sketch) if !sketch.is_empty() => Value::from(
+                    quantiles(&aggregate.function, &sketch)
+                        .map(|(quantile, value)| {
+                            (format!("p{}", quantile * 100.0).into(), Value::from(value))
+                        })
+                        .collect::<ObjectMap>(),
+                ),
+                // No event of the group had a value for the field.
+                _ => continue,
+            };
+            log.insert(aggregate.name.as_str(), value);
+        }
+
+        output.push(Event::Log(log));
+    }
+}
+
+/// The configured quantiles of a `percentiles` aggregate, along with their estimated values.
+fn quantiles<'a>(
+    function: &'a AggregateFunction,
+    sketch: &'a AgentDDSketch,
+) -> impl Iterator<Item = (f64, f64)> + 'a {
+    let quantiles = match function {
+        AggregateFunction::Percentiles { quantiles, .. } => quantiles.as_slice(),
+        _ => &[],
+    };
+    quantiles
+        .iter()
+        .filter_map(|quantile| Some((*quantile, sketch.quantile(*quantile)?)))
+}
+
+impl TaskTransform<Event> for AggregateLogs {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = tokio::time::interval(FLUSH_PERIOD);
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_into(Some(Utc::now()), &mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_into(None, &mut output);
+                                done = true;
+                            }
+                            Some(event) => self.record(event.into_log(), Utc::now()),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::metric_tags;
+
+    use super::*;
+
+    fn aggregate_logs(config: &str) -> AggregateLogs {
+        let config: AggregateLogsConfig = toml::from_str(config).unwrap();
+        AggregateLogs::new(&config).unwrap()
+    }
+
+    fn at(timestamp: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(timestamp, 0).unwrap()
+    }
+
+    fn log(service: &str, duration: f64) -> LogEvent {
+        let mut log = LogEvent::from("request");
+        log.insert("service", service);
+        log.insert("duration", duration);
+        log
+    }
+
+    #[test]
+    fn aggregates_metrics_over_tumbling_windows() {
+        let mut transform = aggregate_logs(
+            r#"
+            group_by = ["service"]
+            window.type = "tumbling"
+            window.duration_secs = 10
+            aggregates = [
+                { name = "requests_total", type = "count" },
+                { name = "duration_max", type = "max", field = "duration" },
+                { name = "duration", type = "percentiles", field = "duration", quantiles = [0.5] },
+            ]
+            "#,
+        );
+
+        transform.record(log("api", 1.0), at(100));
+        transform.record(log("api", 3.0), at(105));
+        transform.record(log("web", 2.0), at(109));
+        transform.record(log("api", 5.0), at(110));
+
+        let mut output = Vec::new();
+        transform.flush_into(Some(at(109)), &mut output);
+        assert!(output.is_empty());
+
+        transform.flush_into(Some(at(110)), &mut output);
+        let mut metrics = output
+            .into_iter()
+            .map(Event::into_metric)
+            .filter(|metric| metric.tags() == Some(&metric_tags!("service" => "api")))
+            .collect::<Vec<_>>();
+        metrics.sort_by(|a, b| a.name().cmp(b.name()));
+        assert_eq!(metrics.len(), 3);
+
+        assert_eq!(metrics[0].name(), "duration");
+        let MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } = metrics[0].value()
+        else {
+            panic!("expected a summary, got {:?}", metrics[0].value());
+        };
+        assert_eq!((*count, *sum), (2, 4.0));
+        assert_eq!(quantiles.len(), 1);
+
+        assert_eq!(metrics[1].name(), "duration_max");
+        assert_eq!(metrics[1].value(), &MetricValue::Gauge { value: 3.0 });
+        assert_eq!(metrics[2].name(), "requests_total");
+        assert_eq!(metrics[2].value(), &MetricValue::Counter { value: 2.0 });
+        assert_eq!(metrics[2].timestamp(), Some(at(110)));
+
+        // The last event is in the next window.
+        let mut output = Vec::new();
+        transform.flush_into(None, &mut output);
+        assert_eq!(output.len(), 3);
+    }
+
+    #[test]
+    fn aggregates_logs_over_sliding_windows() {
+        let mut transform = aggregate_logs(
+            r#"
+            mode = "log"
+            window.type = "sliding"
+            window.duration_secs = 20
+            window.slide_secs = 10
+            aggregates = [
+                { name = "requests_total", type = "count" },
+                { name = "duration_sum", type = "sum", field = "duration" },
+            ]
+            "#,
+        );
+
+        // Each event falls in two windows.
+        transform.record(log("api", 1.0), at(105));
+        transform.record(log("api", 2.0), at(115));
+
+        let mut output = Vec::new();
+        transform.flush_into(Some(at(120)), &mut output);
+        assert_eq!(output.len(), 2);
+
+        let first = output[0].as_log();
+        assert_eq!(first["window_start"], Value::from(at(90)));
+        assert_eq!(first["window_end"], Value::from(at(110)));
+        assert_eq!(first["requests_total"], Value::from(1));
+
+        let second = output[1].as_log();
+        assert_eq!(second["window_start"], Value::from(at(100)));
+        assert_eq!(second["requests_total"], Value::from(2));
+        assert_eq!(second["duration_sum"], Value::from(3.0));
+
+        let mut output = Vec::new();
+        transform.flush_into(None, &mut output);
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].as_log()["window_start"], Value::from(at(110)));
+    }
+}
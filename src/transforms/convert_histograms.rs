@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, Metric},
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `convert_histograms` transform.
+#[configurable_component(transform(
+    "convert_histograms",
+    "Convert histograms between the representations of different metric backends."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ConvertHistogramsConfig {
+    #[configurable(derived)]
+    pub target: HistogramRepresentation,
+
+    /// The upper bounds of the buckets of the aggregated histograms converted to.
+    ///
+    /// Only used when converting to aggregated histograms. The observations above all bounds are
+    /// only part of the count of the histograms, as for the `+Inf` bucket of Prometheus histograms.
+    #[serde(default = "default_buckets")]
+    pub buckets: Vec<f64>,
+}
+
+/// The representation to convert distributions, aggregated histograms, and sketches to.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistogramRepresentation {
+    /// Aggregated histograms with the configured buckets, as the classic histograms of Prometheus
+    /// and the explicit bucket histograms of OpenTelemetry.
+    AggregatedHistogram,
+
+    /// Sketches, as the distributions of Datadog.
+    ///
+    /// The `opentelemetry` sink exports sketches as exponential histograms.
+    Sketch,
+}
+
+// The default buckets of the Prometheus client libraries.
+fn default_buckets() -> Vec<f64> {
+    vec![
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ]
+}
+
+impl GenerateConfig for ConvertHistogramsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"target = "aggregated_histogram""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "convert_histograms")]
+impl TransformConfig for ConvertHistogramsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(ConvertHistograms::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        _: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        vec![TransformOutput::new(DataType::Metric, HashMap::new())]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ConvertHistograms {
+    target: HistogramRepresentation,
+    buckets: Vec<f64>,
+}
+
+impl ConvertHistograms {
+    pub fn new(config: &ConvertHistogramsConfig) -> Self {
+        let mut buckets = config.buckets.clone();
+        buckets.sort_by(f64::total_cmp);
+        buckets.dedup();
+        Self {
+            target: config.target,
+            buckets,
+        }
+    }
+
+    fn convert(&self, metric: Metric) -> Metric {
+        let value = metric.value();
+        let converted = match self.target {
+            HistogramRepresentation::AggregatedHistogram => value
+                .distribution_to_agg_histogram(&self.buckets)
+                .or_else(|| value.sketch_to_agg_histogram(&self.buckets)),
+            // Histograms with buckets holding more observations than sketches can are kept as is.
+            HistogramRepresentation::Sketch => value
+                .distribution_to_sketch()
+                .or_else(|| value.agg_histogram_to_sketch()),
+        };
+        match converted {
+            Some(value) => metric.with_value(value),
+            None => metric,
+        }
+    }
+}
+
+impl FunctionTransform for ConvertHistograms {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        // Metrics which aren't histograms of another representation are forwarded unmodified.
+        output.push(Event::Metric(self.convert(event.into_metric())));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+    use vector_lib::event::{
+        metric::{MetricKind, MetricSketch, StatisticKind},
+        MetricValue,
+    };
+    use vector_lib::metrics::AgentDDSketch;
+
+    use super::*;
+    use crate::transforms::test::transform_one;
+
+    fn convert(target: HistogramRepresentation, value: MetricValue) -> MetricValue {
+        let config = ConvertHistogramsConfig {
+            target,
+            buckets: vec![10.0, 1.0],
+        };
+        let metric = Metric::new("latency", MetricKind::Incremental, value);
+        let event = transform_one(&mut ConvertHistograms::new(&config), metric.into()).unwrap();
+        event.into_metric().value().clone()
+    }
+
+    fn sketch(values: &[f64]) -> MetricValue {
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch.insert_many(values);
+        MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(sketch),
+        }
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ConvertHistogramsConfig>();
+    }
+
+    #[test]
+    fn converts_to_aggregated_histograms() {
+        let expected = MetricValue::AggregatedHistogram {
+            buckets: vector_lib::buckets![1.0 => 1, 10.0 => 2],
+            count: 4,
+            sum: 27.5,
+        };
+
+        let distribution = MetricValue::Distribution {
+            samples: vector_lib::samples![0.5 => 1, 2.0 => 1, 5.0 => 1, 20.0 => 1],
+            statistic: StatisticKind::Histogram,
+        };
+        assert_eq!(
+            convert(HistogramRepresentation::AggregatedHistogram, distribution),
+            expected
+        );
+
+        let MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } = convert(
+            HistogramRepresentation::AggregatedHistogram,
+            sketch(&[0.5, 2.0, 5.0, 20.0]),
+        )
+        else {
+            panic!("sketch should be converted to an aggregated histogram");
+        };
+        assert_eq!(buckets, vector_lib::buckets![1.0 => 1, 10.0 => 2]);
+        assert_eq!(count, 4);
+        assert!((sum - 27.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn converts_to_sketches() {
+        let histogram = MetricValue::AggregatedHistogram {
+            buckets: vector_lib::buckets![1.0 => 2, 10.0 => 3],
+            count: 5,
+            sum: 20.0,
+        };
+        let MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(sketch),
+        } = convert(HistogramRepresentation::Sketch, histogram)
+        else {
+            panic!("histogram should be converted to a sketch");
+        };
+        assert_eq!(sketch.count(), 5);
+
+        let counter = MetricValue::Counter { value: 1.0 };
+        assert_eq!(
+            convert(HistogramRepresentation::Sketch, counter.clone()),
+            counter
+        );
+    }
+
+    #[test]
+    fn sorts_buckets() {
+        let config = ConvertHistogramsConfig {
+            target: HistogramRepresentation::AggregatedHistogram,
+            buckets: vec![10.0, 1.0, 10.0],
+        };
+        assert_eq!(ConvertHistograms::new(&config).buckets, vec![1.0, 10.0]);
+    }
+}
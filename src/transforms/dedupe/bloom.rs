@@ -0,0 +1,184 @@
+//! A rotating bloom filter, remembering roughly the most recent distinct items in a fixed amount of
+//! memory.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    f64::consts::LN_2,
+    hash::{Hash, Hasher},
+    num::NonZeroUsize,
+};
+
+/// A bloom filter which doesn't track the items inserted into it.
+#[derive(Clone)]
+struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
+}
+
+impl BloomFilter {
+    /// Creates a filter holding `capacity` items with the given false positive rate.
+    fn new(capacity: NonZeroUsize, false_positive_rate: f64) -> Self {
+        let capacity = capacity.get() as f64;
+        let num_bits = (-capacity * false_positive_rate.ln() / (LN_2 * LN_2))
+            .ceil()
+            .max(64.0) as u64;
+        let num_hashes = ((num_bits as f64 / capacity) * LN_2).round().max(1.0) as u32;
+        Self {
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            num_bits,
+            num_hashes,
+        }
+    }
+
+    /// The bit indices of an item, derived from two hashes as described in "Less Hashing, Same
+    /// Performance: Building a Better Bloom Filter".
+    fn indices(&self, hashes: (u64, u64)) -> impl Iterator<Item = u64> + '_ {
+        let (h1, h2) = hashes;
+        (0..u64::from(self.num_hashes))
+            .map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % self.num_bits)
+    }
+
+    fn contains(&self, hashes: (u64, u64)) -> bool {
+        self.indices(hashes)
+            .all(|index| self.bits[(index / 64) as usize] & (1 << (index % 64)) != 0)
+    }
+
+    /// Inserts an item, returning whether it was already present.
+    fn insert(&mut self, hashes: (u64, u64)) -> bool {
+        let mut present = true;
+        let (h1, h2) = hashes;
+        for i in 0..u64::from(self.num_hashes) {
+            let index = h1.wrapping_add(i.wrapping_mul(h2)) % self.num_bits;
+            let word = &mut self.bits[(index / 64) as usize];
+            let bit = 1 << (index % 64);
+            present &= *word & bit != 0;
+            *word |= bit;
+        }
+        present
+    }
+
+    fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+}
+
+/// A pair of bloom filters, the newest of which holds up to `capacity` distinct items. Items are
+/// looked up in both filters, and once the newest filter is full the oldest one is discarded, so
+/// between `capacity` and twice as many of the most recent distinct items are remembered.
+#[derive(Clone)]
+pub(super) struct RotatingBloomFilter {
+    current: BloomFilter,
+    previous: BloomFilter,
+    capacity: usize,
+    len: usize,
+}
+
+impl RotatingBloomFilter {
+    /// Creates a filter with the given overall false positive rate, which is split evenly between
+    /// both filters.
+    pub(super) fn new(capacity: NonZeroUsize, false_positive_rate: f64) -> Self {
+        let filter = BloomFilter::new(capacity, false_positive_rate / 2.0);
+        Self {
+            current: filter.clone(),
+            previous: filter,
+            capacity: capacity.get(),
+            len: 0,
+        }
+    }
+
+    /// Inserts an item, returning whether it was (possibly falsely) already present.
+    pub(super) fn insert<T: Hash>(&mut self, item: &T) -> bool {
+        let hashes = hashes(item);
+        let in_previous = self.previous.contains(hashes);
+        // Items seen again are also inserted into the newest filter, so that items seen regularly
+        // aren't forgotten when the oldest filter is discarded.
+        if self.current.insert(hashes) {
+            return true;
+        }
+
+        self.len += 1;
+        if self.len >= self.capacity {
+            std::mem::swap(&mut self.current, &mut self.previous);
+            self.current.clear();
+            self.len = 0;
+        }
+        in_previous
+    }
+}
+
+fn hashes<T: Hash>(item: &T) -> (u64, u64) {
+    let mut hasher = DefaultHasher::new();
+    item.hash(&mut hasher);
+    let h1 = hasher.finish();
+
+    let mut hasher = DefaultHasher::new();
+    hasher.write_u8(1);
+    item.hash(&mut hasher);
+    // An odd step visits distinct bits for as many hashes as possible.
+    let h2 = hasher.finish() | 1;
+
+    (h1, h2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capacity(capacity: usize) -> NonZeroUsize {
+        NonZeroUsize::new(capacity).unwrap()
+    }
+
+    #[test]
+    fn remembers_inserted_items() {
+        let mut filter = RotatingBloomFilter::new(capacity(100), 0.01);
+
+        assert!(!filter.insert(&"a"));
+        assert!(!filter.insert(&"b"));
+        assert!(filter.insert(&"a"));
+        assert!(filter.insert(&"b"));
+    }
+
+    #[test]
+    fn forgets_items_after_two_generations() {
+        let mut filter = RotatingBloomFilter::new(capacity(2), 0.0001);
+
+        assert!(!filter.insert(&0));
+        assert!(!filter.insert(&1));
+        // The first generation is now the previous one.
+        assert!(!filter.insert(&2));
+        assert!(!filter.insert(&3));
+        // The first generation has been discarded.
+        assert!(!filter.insert(&0));
+    }
+
+    #[test]
+    fn remembers_items_seen_in_each_generation() {
+        let mut filter = RotatingBloomFilter::new(capacity(2), 0.0001);
+
+        assert!(!filter.insert(&0));
+        assert!(!filter.insert(&1));
+        // The first generation is now the previous one, and seeing the item again carries it over
+        // into the current generation.
+        assert!(filter.insert(&0));
+        assert!(!filter.insert(&2));
+        // The first generation has been discarded, while the item is still remembered.
+        assert!(filter.insert(&0));
+        assert!(!filter.insert(&1));
+        assert!(!filter.insert(&3));
+        assert!(!filter.insert(&4));
+        // Two rotations without the item being seen forget it.
+        assert!(!filter.insert(&0));
+    }
+
+    #[test]
+    fn false_positive_rate() {
+        let mut filter = RotatingBloomFilter::new(capacity(10_000), 0.01);
+        for i in 0..10_000 {
+            filter.insert(&i);
+        }
+
+        let false_positives = (10_000..20_000).filter(|i| filter.insert(i)).count();
+        assert!(false_positives < 200, "{false_positives} false positives");
+    }
+}
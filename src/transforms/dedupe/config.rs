@@ -13,7 +13,9 @@ use crate::{
 };
 
 use super::{
-    common::{default_cache_config, fill_default_fields_match, CacheConfig, FieldMatchConfig},
+    common::{
+        default_cache_config, fill_default_fields_match, CacheConfig, CacheMode, FieldMatchConfig,
+    },
     transform::Dedupe,
 };
 
@@ -45,10 +47,21 @@ impl GenerateConfig for DedupeConfig {
 #[typetag::serde(name = "dedupe")]
 impl TransformConfig for DedupeConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::event_task(Dedupe::new(
-            self.cache.num_events,
-            fill_default_fields_match(self.fields.as_ref()),
-        )))
+        let fields = fill_default_fields_match(self.fields.as_ref());
+        let dedupe = match self.cache.mode {
+            CacheMode::Exact => Dedupe::new(self.cache.num_events, fields),
+            CacheMode::Probabilistic => {
+                let rate = self.cache.false_positive_rate;
+                if !(rate > 0.0 && rate < 1.0) {
+                    return Err(format!(
+                        "`cache.false_positive_rate` must be between 0 and 1, got {rate}"
+                    )
+                    .into());
+                }
+                Dedupe::new_probabilistic(self.cache.num_events, rate, fields)
+            }
+        };
+        Ok(Transform::event_task(dedupe))
     }
 
     fn input(&self) -> Input {
@@ -78,12 +91,14 @@ mod tests {
     use vector_lib::config::OutputId;
     use vector_lib::lookup::lookup_v2::ConfigTargetPath;
 
-    use crate::config::schema::Definition;
+    use crate::config::{schema::Definition, TransformConfig, TransformContext};
     use crate::{
         event::{Event, LogEvent, ObjectMap, Value},
         test_util::components::assert_transform_compliance,
         transforms::{
-            dedupe::config::{CacheConfig, DedupeConfig, FieldMatchConfig},
+            dedupe::config::{
+                default_cache_config, CacheConfig, CacheMode, DedupeConfig, FieldMatchConfig,
+            },
             test::create_topology,
         },
     };
@@ -100,6 +115,7 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                ..default_cache_config()
             },
             fields: Some(FieldMatchConfig::MatchFields(fields)),
         }
@@ -116,11 +132,21 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                ..default_cache_config()
             },
             fields: Some(FieldMatchConfig::IgnoreFields(fields)),
         }
     }
 
+    fn make_probabilistic_transform_config(
+        num_events: usize,
+        fields: Vec<ConfigTargetPath>,
+    ) -> DedupeConfig {
+        let mut config = make_match_transform_config(num_events, fields);
+        config.cache.mode = CacheMode::Probabilistic;
+        config
+    }
+
     #[tokio::test]
     async fn dedupe_match_basic() {
         let transform_config = make_match_transform_config(5, vec!["matched".into()]);
@@ -194,6 +220,22 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn dedupe_probabilistic_basic() {
+        let transform_config = make_probabilistic_transform_config(5, vec!["matched".into()]);
+        basic(transform_config, "matched", "unmatched").await;
+    }
+
+    #[tokio::test]
+    async fn dedupe_probabilistic_invalid_false_positive_rate() {
+        let mut transform_config = make_probabilistic_transform_config(5, vec![]);
+        transform_config.cache.false_positive_rate = 1.0;
+        assert!(transform_config
+            .build(&TransformContext::default())
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn dedupe_match_field_name_matters() {
         let transform_config =
@@ -4,6 +4,9 @@ pub mod config;
 #[cfg(feature = "transforms-impl-dedupe")]
 pub mod transform;
 
+#[cfg(feature = "transforms-impl-dedupe")]
+mod bloom;
+
 #[cfg(feature = "transforms-impl-dedupe")]
 pub mod common {
     use std::num::NonZeroUsize;
@@ -18,15 +21,56 @@ pub mod common {
     #[serde(deny_unknown_fields)]
     pub struct CacheConfig {
         /// Number of events to cache and use for comparing incoming events to previously seen events.
+        ///
+        /// In `probabilistic` mode, this is the number of distinct events each generation of the
+        /// filter holds, so between one and two times this many events are remembered.
         pub num_events: NonZeroUsize,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        pub mode: CacheMode,
+
+        /// The rate at which new events are falsely considered duplicates in `probabilistic` mode.
+        ///
+        /// Lower rates require more memory for the same number of events.
+        #[serde(default = "default_false_positive_rate")]
+        #[configurable(metadata(docs::examples = 0.001))]
+        pub false_positive_rate: f64,
+    }
+
+    /// Controls the approach taken for remembering previously seen events.
+    #[configurable_component]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    #[serde(rename_all = "snake_case")]
+    pub enum CacheMode {
+        /// Remembers the most recently seen events exactly, in a least recently used cache.
+        ///
+        /// This mode never drops events it hasn't seen, but holds a copy of the matched fields of
+        /// each cached event in memory.
+        #[default]
+        Exact,
+
+        /// Remembers previously seen events in a rotating pair of bloom filters.
+        ///
+        /// This mode uses a small, fixed amount of memory, which allows deduplicating high
+        /// cardinality streams over many more events than `exact`, but may drop events it hasn't
+        /// seen at the configured `false_positive_rate`. Once the newest filter holds `num_events`
+        /// distinct events, the oldest one is discarded.
+        Probabilistic,
     }
 
     pub fn default_cache_config() -> CacheConfig {
         CacheConfig {
             num_events: NonZeroUsize::new(5000).expect("static non-zero number"),
+            mode: CacheMode::default(),
+            false_positive_rate: default_false_positive_rate(),
         }
     }
 
+    pub const fn default_false_positive_rate() -> f64 {
+        0.01
+    }
+
     /// Options to control what fields to match against.
     ///
     /// When no field matching configuration is specified, events are matched using the `timestamp`,
@@ -12,12 +12,18 @@ use crate::{
     transforms::TaskTransform,
 };
 
-use super::common::FieldMatchConfig;
+use super::{bloom::RotatingBloomFilter, common::FieldMatchConfig};
 
 #[derive(Clone)]
 pub struct Dedupe {
     fields: FieldMatchConfig,
-    cache: LruCache<CacheEntry, bool>,
+    cache: Cache,
+}
+
+#[derive(Clone)]
+enum Cache {
+    Exact(LruCache<CacheEntry, bool>),
+    Probabilistic(RotatingBloomFilter),
 }
 
 type TypeId = u8;
@@ -70,13 +76,28 @@ impl Dedupe {
     pub fn new(num_entries: NonZeroUsize, fields: FieldMatchConfig) -> Self {
         Self {
             fields,
-            cache: LruCache::new(num_entries),
+            cache: Cache::Exact(LruCache::new(num_entries)),
+        }
+    }
+
+    pub fn new_probabilistic(
+        num_entries: NonZeroUsize,
+        false_positive_rate: f64,
+        fields: FieldMatchConfig,
+    ) -> Self {
+        Self {
+            fields,
+            cache: Cache::Probabilistic(RotatingBloomFilter::new(num_entries, false_positive_rate)),
         }
     }
 
     pub fn transform_one(&mut self, event: Event) -> Option<Event> {
         let cache_entry = build_cache_entry(&event, &self.fields);
-        if self.cache.put(cache_entry, true).is_some() {
+        let duplicate = match &mut self.cache {
+            Cache::Exact(cache) => cache.put(cache_entry, true).is_some(),
+            Cache::Probabilistic(filter) => filter.insert(&cache_entry),
+        };
+        if duplicate {
             emit!(DedupeEventsDropped { count: 1 });
             None
         } else {
@@ -1,5 +1,7 @@
+use std::collections::HashSet;
 use vector_lib::config::{clone_input_definitions, LogNamespace};
 use vector_lib::configurable::configurable_component;
+
 use vector_lib::internal_event::{Count, InternalEventHandle as _, Registered};
 
 use crate::{
@@ -9,7 +11,7 @@ use crate::{
         TransformOutput,
     },
     event::Event,
-    internal_events::FilterEventsDropped,
+    internal_events::{FilterConditionChecked, FilterEventsDropped},
     schema,
     transforms::{FunctionTransform, OutputBuffer, Transform},
 };
@@ -23,12 +25,41 @@ pub struct FilterConfig {
     /// The condition that every input event is matched against.
     ///
     /// If an event is matched by the condition, it is forwarded. Otherwise, the event is dropped.
+    ///
+    /// Only one of `condition` and `conditions` can be provided.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    condition: Option<AnyCondition>,
+
+    /// An ordered list of named conditions that every input event is matched against.
+    ///
+    /// If an event is matched by all of the conditions, it is forwarded. The conditions are checked
+    /// in order, and the first one that doesn't match an event drops it, skipping the following
+    /// ones. The events matched and dropped by each condition are counted by the
+    /// `filter_condition_events_matched_total` and `filter_condition_events_dropped_total` metrics,
+    /// tagged with the name of the condition.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    conditions: Vec<NamedCondition>,
+}
+
+/// A condition of the `filter` transform, identified by its name in its metrics.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NamedCondition {
+    /// The name of the condition.
+    #[configurable(metadata(docs::examples = "drop_debug_logs"))]
+    name: String,
+
+    #[configurable(derived)]
     condition: AnyCondition,
 }
 
 impl From<AnyCondition> for FilterConfig {
     fn from(condition: AnyCondition) -> Self {
-        Self { condition }
+        Self {
+            condition: Some(condition),
+            conditions: Vec::new(),
+        }
     }
 }
 
@@ -42,9 +73,27 @@ impl GenerateConfig for FilterConfig {
 #[typetag::serde(name = "filter")]
 impl TransformConfig for FilterConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Filter::new(
-            self.condition.build(&context.enrichment_tables)?,
-        )))
+        let filter = match (&self.condition, self.conditions.as_slice()) {
+            (Some(condition), []) => Filter::new(condition.build(&context.enrichment_tables)?),
+            (None, []) => return Err("one of `condition` or `conditions` must be provided".into()),
+            (None, conditions) => {
+                let mut names = HashSet::new();
+                let conditions = conditions
+                    .iter()
+                    .map(|NamedCondition { name, condition }| {
+                        if !names.insert(name) {
+                            return Err(format!("duplicate condition name `{name}`").into());
+                        }
+                        Ok((name.clone(), condition.build(&context.enrichment_tables)?))
+                    })
+                    .collect::<crate::Result<Vec<_>>>()?;
+                Filter::named(conditions)
+            }
+            (Some(_), _) => {
+                return Err("only one of `condition` and `conditions` can be provided".into())
+            }
+        };
+        Ok(Transform::function(filter))
     }
 
     fn input(&self) -> Input {
@@ -70,27 +119,47 @@ impl TransformConfig for FilterConfig {
 
 #[derive(Clone)]
 pub struct Filter {
-    condition: Condition,
+    /// The conditions to check in order, with the metrics of the named ones.
+    conditions: Vec<(Condition, Option<Registered<FilterConditionChecked>>)>,
     events_dropped: Registered<FilterEventsDropped>,
 }
 
 impl Filter {
     pub fn new(condition: Condition) -> Self {
         Self {
-            condition,
+            conditions: vec![(condition, None)],
+            events_dropped: register!(FilterEventsDropped),
+        }
+    }
+
+    pub fn named(conditions: Vec<(String, Condition)>) -> Self {
+        Self {
+            conditions: conditions
+                .into_iter()
+                .map(|(name, condition)| {
+                    let checked = register!(FilterConditionChecked { condition: name });
+                    (condition, Some(checked))
+                })
+                .collect(),
             events_dropped: register!(FilterEventsDropped),
         }
     }
 }
 
 impl FunctionTransform for Filter {
-    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
-        let (result, event) = self.condition.check(event);
-        if result {
-            output.push(event);
-        } else {
-            self.events_dropped.emit(Count(1));
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        for (condition, checked) in &self.conditions {
+            let (result, checked_event) = condition.check(event);
+            event = checked_event;
+            if let Some(checked) = checked {
+                checked.emit(result);
+            }
+            if !result {
+                self.events_dropped.emit(Count(1));
+                return;
+            }
         }
+        output.push(event);
     }
 }
 
@@ -98,6 +167,7 @@ impl FunctionTransform for Filter {
 mod test {
     use std::sync::Arc;
 
+    use indoc::indoc;
     use tokio::sync::mpsc;
     use tokio_stream::wrappers::ReceiverStream;
     use vector_lib::config::ComponentKey;
@@ -149,4 +219,61 @@ mod test {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn filter_named_conditions() {
+        assert_transform_compliance(async {
+            let transform_config = toml::from_str::<FilterConfig>(indoc! {r#"
+                [[conditions]]
+                name = "drop_debug"
+                condition = '.level != "debug"'
+
+                [[conditions]]
+                name = "drop_healthchecks"
+                condition = '.path != "/health"'
+            "#})
+            .unwrap();
+
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) =
+                create_topology(ReceiverStream::new(rx), transform_config).await;
+
+            for (level, path) in [("debug", "/"), ("info", "/health"), ("info", "/")] {
+                let mut log = LogEvent::from("message");
+                log.insert("level", level);
+                log.insert("path", path);
+                tx.send(log.into()).await.unwrap();
+            }
+
+            let output = out.recv().await.unwrap().into_log();
+            assert_eq!(output["level"], "info".into());
+            assert_eq!(output["path"], "/".into());
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn filter_rejects_duplicate_condition_names() {
+        let transform_config = toml::from_str::<FilterConfig>(indoc! {r#"
+            [[conditions]]
+            name = "drop_debug"
+            condition = '.level != "debug"'
+
+            [[conditions]]
+            name = "drop_debug"
+            condition = '.level != "trace"'
+        "#})
+        .unwrap();
+
+        let error = transform_config
+            .build(&TransformContext::default())
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "duplicate condition name `drop_debug`");
+    }
 }
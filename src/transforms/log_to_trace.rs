@@ -0,0 +1,536 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use futures::Stream;
+use indexmap::IndexMap;
+use serde_with::serde_as;
+use vector_lib::{
+    config::LogNamespace,
+    configurable::configurable_component,
+    lookup::lookup_v2::ConfigTargetPath,
+    stream::expiration_map::{map_with_expiration, Emitter},
+};
+use vrl::path::OwnedTargetPath;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, EventMetadata, LogEvent, ObjectMap, TraceEvent, Value},
+    internal_events::LogToTraceInvalidId,
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `log_to_trace` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "log_to_trace",
+    "Build trace events from the spans described by correlated log events."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LogToTraceConfig {
+    /// The field holding the ID of the trace a log belongs to.
+    ///
+    /// IDs are either integers, decimal strings, or hexadecimal strings of 16 or 32 characters,
+    /// such as OpenTelemetry IDs, of which only the lower 64 bits are kept.
+    #[serde(default = "default_trace_id_field")]
+    pub trace_id_field: ConfigTargetPath,
+
+    /// The field holding the ID of the span a log belongs to.
+    ///
+    /// The logs of a trace sharing the same span ID are combined into a single span.
+    #[serde(default = "default_span_id_field")]
+    pub span_id_field: ConfigTargetPath,
+
+    /// The field holding the ID of the parent span of the span a log belongs to.
+    #[serde(default = "default_parent_id_field")]
+    pub parent_id_field: ConfigTargetPath,
+
+    /// The field holding the name of the service emitting the span.
+    #[serde(default = "default_service_field")]
+    pub service_field: ConfigTargetPath,
+
+    /// The field holding the name of the operation of the span.
+    #[serde(default = "default_name_field")]
+    pub name_field: ConfigTargetPath,
+
+    /// The field holding the resource the span operates on, such as an endpoint or a query.
+    ///
+    /// Defaults to the name of the operation.
+    #[serde(default = "default_resource_field")]
+    pub resource_field: ConfigTargetPath,
+
+    /// The field holding the duration of the span, in milliseconds.
+    ///
+    /// The span starts at the timestamp of its earliest log. If no log of the span has a duration,
+    /// the span ends at the timestamp of its latest log.
+    #[configurable(metadata(docs::examples = "duration_ms"))]
+    pub duration_ms_field: Option<ConfigTargetPath>,
+
+    /// The field telling whether the span failed.
+    ///
+    /// The span is flagged as an error if the field of any of its logs is `true`.
+    #[serde(default = "default_error_field")]
+    pub error_field: ConfigTargetPath,
+
+    /// The period of time to wait after the last log of a trace is received, in milliseconds,
+    /// before the trace is considered complete.
+    #[serde(default = "default_expire_after_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Expire After"))]
+    pub expire_after_ms: Duration,
+
+    /// The interval to check for and flush complete traces, in milliseconds.
+    #[serde(default = "default_flush_period_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Flush Period"))]
+    pub flush_period_ms: Duration,
+}
+
+fn default_trace_id_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("trace_id")
+}
+
+fn default_span_id_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("span_id")
+}
+
+fn default_parent_id_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("parent_id")
+}
+
+fn default_service_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("service")
+}
+
+fn default_name_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("name")
+}
+
+fn default_resource_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("resource")
+}
+
+fn default_error_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("error")
+}
+
+const fn default_expire_after_ms() -> Duration {
+    Duration::from_millis(10000)
+}
+
+const fn default_flush_period_ms() -> Duration {
+    Duration::from_millis(1000)
+}
+
+impl Default for LogToTraceConfig {
+    fn default() -> Self {
+        Self {
+            trace_id_field: default_trace_id_field(),
+            span_id_field: default_span_id_field(),
+            parent_id_field: default_parent_id_field(),
+            service_field: default_service_field(),
+            name_field: default_name_field(),
+            resource_field: default_resource_field(),
+            duration_ms_field: None,
+            error_field: default_error_field(),
+            expire_after_ms: default_expire_after_ms(),
+            flush_period_ms: default_flush_period_ms(),
+        }
+    }
+}
+
+impl GenerateConfig for LogToTraceConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::default()).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "log_to_trace")]
+impl TransformConfig for LogToTraceConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::event_task(LogToTrace::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        _: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        vec![TransformOutput::new(DataType::Trace, HashMap::new())]
+    }
+}
+
+/// Parses a trace or span ID, keeping the lower 64 bits of 128-bit IDs.
+fn parse_id(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(id) => Some(*id),
+        Value::Bytes(bytes) => {
+            let id = std::str::from_utf8(bytes).ok()?;
+            let is_hex = matches!(id.len(), 16 | 32) && id.bytes().all(|b| b.is_ascii_hexdigit());
+            let id = if is_hex {
+                u128::from_str_radix(id, 16).ok()? as u64
+            } else {
+                id.parse::<u64>().ok()?
+            };
+            // The IDs of the spans of trace events are stored as signed integers.
+            Some(id as i64)
+        }
+        _ => None,
+    }
+}
+
+/// A span, built from the logs sharing its ID.
+#[derive(Debug, Default)]
+struct Span {
+    parent_id: Option<i64>,
+    service: Option<Value>,
+    name: Option<Value>,
+    resource: Option<Value>,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    error: bool,
+    meta: ObjectMap,
+    metrics: ObjectMap,
+}
+
+impl Span {
+    fn into_value(self, trace_id: i64, span_id: i64) -> Value {
+        let mut span = ObjectMap::new();
+        span.insert("trace_id".into(), trace_id.into());
+        span.insert("span_id".into(), span_id.into());
+        span.insert(
+            "parent_id".into(),
+            self.parent_id.unwrap_or_default().into(),
+        );
+        let name = self.name.unwrap_or_else(|| Value::from(""));
+        span.insert(
+            "resource".into(),
+            self.resource.unwrap_or_else(|| name.clone()),
+        );
+        span.insert("name".into(), name);
+        span.insert(
+            "service".into(),
+            self.service.unwrap_or_else(|| Value::from("")),
+        );
+        if let Some(start) = self.start {
+            span.insert("start".into(), start.into());
+            let duration = self
+                .end
+                .and_then(|end| (end - start).num_nanoseconds())
+                .unwrap_or_default();
+            span.insert("duration".into(), duration.max(0).into());
+        }
+        span.insert("error".into(), i64::from(self.error).into());
+        span.insert("meta".into(), self.meta.into());
+        span.insert("metrics".into(), self.metrics.into());
+        span.into()
+    }
+}
+
+/// The spans of a trace, waiting for the trace to be complete.
+#[derive(Debug)]
+struct PendingTrace {
+    spans: IndexMap<i64, Span>,
+    stale_since: Instant,
+    metadata: EventMetadata,
+}
+
+#[derive(Debug)]
+pub struct LogToTrace {
+    trace_id_field: OwnedTargetPath,
+    span_id_field: OwnedTargetPath,
+    parent_id_field: OwnedTargetPath,
+    service_field: OwnedTargetPath,
+    name_field: OwnedTargetPath,
+    resource_field: OwnedTargetPath,
+    duration_ms_field: Option<OwnedTargetPath>,
+    error_field: OwnedTargetPath,
+    expire_after: Duration,
+    flush_period: Duration,
+    traces: HashMap<i64, PendingTrace>,
+}
+
+impl LogToTrace {
+    pub fn new(config: &LogToTraceConfig) -> Self {
+        Self {
+            trace_id_field: config.trace_id_field.0.clone(),
+            span_id_field: config.span_id_field.0.clone(),
+            parent_id_field: config.parent_id_field.0.clone(),
+            service_field: config.service_field.0.clone(),
+            name_field: config.name_field.0.clone(),
+            resource_field: config.resource_field.0.clone(),
+            duration_ms_field: config
+                .duration_ms_field
+                .as_ref()
+                .map(|field| field.0.clone()),
+            error_field: config.error_field.0.clone(),
+            expire_after: config.expire_after_ms,
+            flush_period: config.flush_period_ms,
+            traces: HashMap::new(),
+        }
+    }
+
+    fn id(&self, log: &LogEvent, field: &OwnedTargetPath) -> Option<i64> {
+        let id = log.get(field).and_then(parse_id);
+        if id.is_none() {
+            emit!(LogToTraceInvalidId {
+                field: &field.to_string(),
+            });
+        }
+        id
+    }
+
+    fn record(&mut self, mut log: LogEvent, now: Instant) {
+        let Some(trace_id) = self.id(&log, &self.trace_id_field) else {
+            return;
+        };
+        let Some(span_id) = self.id(&log, &self.span_id_field) else {
+            return;
+        };
+
+        let parent_id = log
+            .remove(&self.parent_id_field)
+            .as_ref()
+            .and_then(parse_id);
+        let service = log.remove(&self.service_field);
+        let name = log.remove(&self.name_field);
+        let resource = log.remove(&self.resource_field);
+        let error = log.remove(&self.error_field) == Some(Value::Boolean(true));
+        let duration_ms = self
+            .duration_ms_field
+            .as_ref()
+            .and_then(|field| log.remove(field))
+            .and_then(|duration| match duration {
+                Value::Integer(duration) => Some(duration as f64),
+                Value::Float(duration) => Some(duration.into_inner()),
+                _ => None,
+            });
+        let timestamp = log.get_timestamp().and_then(Value::as_timestamp).copied();
+        if let Some(timestamp_path) = log.timestamp_path().cloned() {
+            log.remove(&timestamp_path);
+        }
+        log.remove(&self.trace_id_field);
+        log.remove(&self.span_id_field);
+
+        let (value, metadata) = log.into_parts();
+        let trace = match self.traces.entry(trace_id) {
+            Entry::Occupied(entry) => {
+                let trace = entry.into_mut();
+                trace.metadata.merge(metadata);
+                trace
+            }
+            Entry::Vacant(entry) => entry.insert(PendingTrace {
+                spans: IndexMap::new(),
+                stale_since: now,
+                metadata,
+            }),
+        };
+        trace.stale_since = now;
+
+        let span = trace.spans.entry(span_id).or_default();
+        span.parent_id = parent_id.or(span.parent_id);
+        span.service = service.or(span.service.take());
+        span.name = name.or(span.name.take());
+        span.resource = resource.or(span.resource.take());
+        span.error |= error;
+        if let Some(timestamp) = timestamp {
+            let end = match duration_ms {
+                Some(duration_ms) => {
+                    timestamp + chrono::Duration::microseconds((duration_ms * 1000.0) as i64)
+                }
+                None => timestamp,
+            };
+            span.start = Some(span.start.map_or(timestamp, |start| start.min(timestamp)));
+            span.end = Some(span.end.map_or(end, |span_end| span_end.max(end)));
+        }
+
+        // The other fields of the log are kept as tags of the span, numbers as its metrics.
+        if let Value::Object(fields) = value {
+            for (key, value) in fields {
+                match value {
+                    Value::Integer(number) => {
+                        span.metrics.insert(key, Value::from(number as f64));
+                    }
+                    Value::Float(number) => {
+                        span.metrics.insert(key, Value::Float(number));
+                    }
+                    value => {
+                        span.meta
+                            .insert(key, Value::from(value.to_string_lossy().into_owned()));
+                    }
+                }
+            }
+        }
+    }
+
+    fn flush_trace(trace_id: i64, trace: PendingTrace) -> TraceEvent {
+        let mut spans = trace
+            .spans
+            .into_iter()
+            .map(|(span_id, span)| (span.start, span.into_value(trace_id, span_id)))
+            .collect::<Vec<_>>();
+        spans.sort_by_key(|(start, _)| *start);
+
+        let mut event = TraceEvent::from(LogEvent::new_with_metadata(trace.metadata));
+        event.insert("trace_id", trace_id);
+        event.insert(
+            "spans",
+            Value::Array(spans.into_iter().map(|(_, span)| span).collect()),
+        );
+        event
+    }
+
+    fn flush_into(&mut self, now: Instant, emitter: &mut Emitter<Event>) {
+        let expired = self
+            .traces
+            .iter()
+            .filter(|(_, trace)| now - trace.stale_since >= self.expire_after)
+            .map(|(trace_id, _)| *trace_id)
+            .collect::<Vec<_>>();
+        for trace_id in expired {
+            if let Some(trace) = self.traces.remove(&trace_id) {
+                emitter.emit(Event::Trace(Self::flush_trace(trace_id, trace)));
+            }
+        }
+    }
+
+    fn flush_all_into(&mut self, emitter: &mut Emitter<Event>) {
+        for (trace_id, trace) in self.traces.drain() {
+            emitter.emit(Event::Trace(Self::flush_trace(trace_id, trace)));
+        }
+    }
+}
+
+impl TaskTransform<Event> for LogToTrace {
+    fn transform(
+        self: Box<Self>,
+        input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let flush_period = self.flush_period;
+
+        Box::pin(map_with_expiration(
+            self,
+            input_rx,
+            flush_period,
+            |me: &mut Box<LogToTrace>, event, _: &mut Emitter<Event>| {
+                me.record(event.into_log(), Instant::now());
+            },
+            |me: &mut Box<LogToTrace>, emitter: &mut Emitter<Event>| {
+                me.flush_into(Instant::now(), emitter);
+            },
+            |me: &mut Box<LogToTrace>, emitter: &mut Emitter<Event>| {
+                me.flush_all_into(emitter);
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        test_util::components::assert_transform_compliance, transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LogToTraceConfig>();
+    }
+
+    #[test]
+    fn parses_ids() {
+        assert_eq!(parse_id(&Value::from(42)), Some(42));
+        assert_eq!(parse_id(&Value::from("1234")), Some(1234));
+        assert_eq!(parse_id(&Value::from("00000000000004d2")), Some(1234));
+        assert_eq!(
+            parse_id(&Value::from("4bf92f3577b34da6a3ce929d0e0e4736")),
+            Some(0xa3ce929d0e0e4736_u64 as i64)
+        );
+        assert_eq!(parse_id(&Value::from("not an id")), None);
+    }
+
+    fn log(span_id: &str, parent_id: Option<&str>, timestamp: i64) -> Event {
+        let mut log = LogEvent::from("request");
+        log.insert("trace_id", "1");
+        log.insert("span_id", span_id);
+        if let Some(parent_id) = parent_id {
+            log.insert("parent_id", parent_id);
+        }
+        log.insert("service", "api");
+        log.insert("name", "http.request");
+        log.insert("status", 200);
+        log.insert("timestamp", DateTime::from_timestamp(timestamp, 0).unwrap());
+        log.into()
+    }
+
+    #[tokio::test]
+    async fn builds_traces_from_logs() {
+        assert_transform_compliance(async move {
+            let config = LogToTraceConfig::default();
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            // The span `2` is described by two logs, at its start and end.
+            for event in [
+                log("2", Some("1"), 101),
+                log("1", None, 100),
+                log("2", None, 103),
+            ] {
+                tx.send(event).await.unwrap();
+            }
+            drop(tx);
+            topology.stop().await;
+
+            let trace = out.recv().await.unwrap().into_trace();
+            assert_eq!(trace.get("trace_id"), Some(&Value::from(1)));
+            let Some(Value::Array(spans)) = trace.get("spans") else {
+                panic!("spans are missing");
+            };
+            assert_eq!(spans.len(), 2);
+
+            let root = spans[0].as_object().unwrap();
+            assert_eq!(root["span_id"], Value::from(1));
+            assert_eq!(root["parent_id"], Value::from(0));
+            assert_eq!(root["duration"], Value::from(0));
+
+            let child = spans[1].as_object().unwrap();
+            assert_eq!(child["span_id"], Value::from(2));
+            assert_eq!(child["parent_id"], Value::from(1));
+            assert_eq!(child["service"], Value::from("api"));
+            assert_eq!(child["resource"], Value::from("http.request"));
+            assert_eq!(child["duration"], Value::from(2_000_000_000));
+            assert_eq!(
+                child["meta"].as_object().unwrap()["message"],
+                "request".into()
+            );
+            assert_eq!(
+                child["metrics"].as_object().unwrap()["status"],
+                Value::from(200.0)
+            );
+
+            assert_eq!(out.next().await, None);
+        })
+        .await;
+    }
+}
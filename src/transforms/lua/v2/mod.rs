@@ -1,4 +1,12 @@
-use std::{path::PathBuf, sync::Arc, time::Duration};
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
@@ -22,6 +30,8 @@ use crate::{
 pub enum BuildError {
     #[snafu(display("Invalid \"search_dirs\": {}", source))]
     InvalidSearchDirs { source: mlua::Error },
+    #[snafu(display("Cannot set up the Lua sandbox: {}", source))]
+    InvalidSandbox { source: mlua::Error },
     #[snafu(display("Cannot evaluate Lua code in \"source\": {}", source))]
     InvalidSource { source: mlua::Error },
 
@@ -86,6 +96,10 @@ pub struct LuaConfig {
     /// values.
     #[serde(default)]
     metric_tag_values: MetricTagValues,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    sandbox: SandboxConfig,
 }
 
 fn default_config_paths() -> Vec<PathBuf> {
@@ -150,6 +164,37 @@ struct HooksConfig {
     shutdown: Option<String>,
 }
 
+/// Sandboxing and resource limits.
+///
+/// These options make it possible to run Lua code which isn't trusted, such as code provided by tenants, without
+/// letting it access the host, hang the transform, or exhaust the memory of Vector.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+struct SandboxConfig {
+    /// Whether to restrict the Lua standard library to the functions which don't access the host.
+    ///
+    /// When enabled, only the `coroutine`, `math`, `string`, `table`, and `utf8` libraries are available, along with
+    /// the `clock`, `date`, `difftime`, and `time` functions of the `os` library. Code can't be loaded with `load`,
+    /// `loadfile`, `dofile`, or `require`, so `search_dirs` is ignored.
+    #[serde(default = "crate::serde::default_false")]
+    restrict_stdlib: bool,
+
+    /// The maximum number of Lua instructions each call of a hook or timer handler can execute.
+    ///
+    /// Calls exceeding it are aborted with an error, which can't be caught with `pcall` or `xpcall`. The limit is
+    /// checked every 1000 instructions.
+    #[configurable(metadata(docs::examples = 1000000))]
+    max_instructions: Option<NonZeroU64>,
+
+    /// The maximum amount of memory the Lua runtime can allocate.
+    ///
+    /// Allocations exceeding it fail with a memory error, aborting the call which made them.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 67108864))]
+    max_memory_bytes: Option<NonZeroUsize>,
+}
+
 /// A Lua timer.
 #[serde_as]
 #[configurable_component]
@@ -215,8 +260,38 @@ impl LuaConfig {
 // after each transform would have significant footprint on the performance.
 const GC_INTERVAL: usize = 16;
 
+// The number of instructions between two checks of the remaining instruction budget, as checking it
+// after every instruction would slow down the execution of Lua code considerably.
+const INSTRUCTION_CHECK_INTERVAL: u32 = 1000;
+
+// The functions of the `os` library kept when the standard library is restricted.
+const SANDBOXED_OS_FUNCTIONS: [&str; 4] = ["clock", "date", "difftime", "time"];
+
+// The functions of the base library removed when the standard library is restricted.
+const SANDBOXED_BASE_FUNCTIONS: [&str; 3] = ["dofile", "load", "loadfile"];
+
+// Wraps `pcall` and `xpcall` so that errors raised once the instruction budget is exhausted are
+// propagated instead of being caught, ensuring that the call is actually aborted.
+const PROTECTED_CALL_WRAPPERS: &str = r#"
+local exhausted = ...
+local error, pcall, xpcall = error, pcall, xpcall
+local function propagate(ok, ...)
+    if not ok and exhausted() then
+        error((...), 0)
+    end
+    return ok, ...
+end
+function pcall(f, ...)
+    return propagate(pcall(f, ...))
+end
+function xpcall(f, handler, ...)
+    return propagate(xpcall(f, handler, ...))
+end
+"#;
+
 pub struct Lua {
     lua: mlua::Lua,
+    instruction_budget: Option<(NonZeroU64, Arc<AtomicU64>)>,
     invocations_after_gc: usize,
     hook_init: Option<mlua::RegistryKey>,
     hook_process: mlua::RegistryKey,
@@ -235,12 +310,37 @@ fn make_registry_value(lua: &mlua::Lua, source: &str) -> mlua::Result<mlua::Regi
 
 impl Lua {
     pub fn new(config: &LuaConfig, key: ComponentKey) -> crate::Result<Self> {
-        // In order to support loading C modules in Lua, we need to create unsafe instance
-        // without debug library.
-        let lua = unsafe {
-            mlua::Lua::unsafe_new_with(mlua::StdLib::ALL_SAFE, mlua::LuaOptions::default())
+        let sandbox = &config.sandbox;
+        let lua = if sandbox.restrict_stdlib {
+            let libs = mlua::StdLib::COROUTINE
+                | mlua::StdLib::MATH
+                | mlua::StdLib::OS
+                | mlua::StdLib::STRING
+                | mlua::StdLib::TABLE
+                | mlua::StdLib::UTF8;
+            let lua = mlua::Lua::new_with(libs, mlua::LuaOptions::default())
+                .context(InvalidSandboxSnafu)?;
+            restrict_stdlib(&lua).context(InvalidSandboxSnafu)?;
+            lua
+        } else {
+            // In order to support loading C modules in Lua, we need to create unsafe instance
+            // without debug library.
+            unsafe {
+                mlua::Lua::unsafe_new_with(mlua::StdLib::ALL_SAFE, mlua::LuaOptions::default())
+            }
         };
 
+        if let Some(limit) = sandbox.max_memory_bytes {
+            lua.set_memory_limit(limit.get())
+                .context(InvalidSandboxSnafu)?;
+        }
+
+        let instruction_budget = sandbox
+            .max_instructions
+            .map(|limit| limit_instructions(&lua, limit))
+            .transpose()
+            .context(InvalidSandboxSnafu)?;
+
         let additional_paths = config
             .search_dirs
             .iter()
@@ -250,7 +350,7 @@ impl Lua {
 
         let mut timers = Vec::new();
 
-        if !additional_paths.is_empty() {
+        if !additional_paths.is_empty() && !sandbox.restrict_stdlib {
             let package = lua.globals().get::<_, mlua::Table<'_>>("package")?;
             let current_paths = package
                 .get::<_, String>("path")
@@ -260,6 +360,7 @@ impl Lua {
         }
 
         if let Some(source) = &config.source {
+            reset_instruction_budget(instruction_budget.as_ref());
             lua.load(source).eval().context(InvalidSourceSnafu)?;
         }
 
@@ -296,6 +397,7 @@ impl Lua {
 
         Ok(Self {
             lua,
+            instruction_budget,
             invocations_after_gc: 0,
             timers,
             hook_init,
@@ -309,6 +411,7 @@ impl Lua {
     #[cfg(test)]
     fn process(&mut self, event: Event, output: &mut Vec<Event>) -> Result<(), mlua::Error> {
         let source_id = event.source_id().cloned();
+        self.reset_instruction_budget();
         let lua = &self.lua;
         let result = lua.scope(|scope| {
             let emit = scope.create_function_mut(|_, mut event: Event| {
@@ -341,6 +444,10 @@ impl Lua {
         Ok(out.into_iter().next())
     }
 
+    fn reset_instruction_budget(&self) {
+        reset_instruction_budget(self.instruction_budget.as_ref());
+    }
+
     fn attempt_gc(&mut self) {
         self.invocations_after_gc += 1;
         if self.invocations_after_gc % GC_INTERVAL == 0 {
@@ -357,6 +464,66 @@ impl Lua {
     }
 }
 
+// Removes the functions of the standard library giving access to the host or loading code.
+fn restrict_stdlib(lua: &mlua::Lua) -> mlua::Result<()> {
+    let globals = lua.globals();
+    for name in SANDBOXED_BASE_FUNCTIONS {
+        globals.raw_set(name, mlua::Value::Nil)?;
+    }
+
+    let os = lua.create_table()?;
+    let full_os = globals.get::<_, mlua::Table<'_>>("os")?;
+    for name in SANDBOXED_OS_FUNCTIONS {
+        os.set(name, full_os.get::<_, mlua::Value<'_>>(name)?)?;
+    }
+    globals.set("os", os)
+}
+
+// Installs a hook aborting calls once they have executed `limit` instructions, returning the
+// budget shared with the hook.
+fn limit_instructions(
+    lua: &mlua::Lua,
+    limit: NonZeroU64,
+) -> mlua::Result<(NonZeroU64, Arc<AtomicU64>)> {
+    let remaining = Arc::new(AtomicU64::new(limit.get()));
+
+    let exhausted = {
+        let remaining = Arc::clone(&remaining);
+        lua.create_function(move |_, ()| Ok(remaining.load(Ordering::Relaxed) == 0))?
+    };
+    lua.load(PROTECTED_CALL_WRAPPERS).call::<_, ()>(exhausted)?;
+
+    let hook_remaining = Arc::clone(&remaining);
+    lua.set_hook(
+        mlua::HookTriggers {
+            every_nth_instruction: Some(INSTRUCTION_CHECK_INTERVAL),
+            ..Default::default()
+        },
+        move |_, _| {
+            let previous = hook_remaining
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| {
+                    Some(remaining.saturating_sub(INSTRUCTION_CHECK_INTERVAL as u64))
+                })
+                .unwrap_or_default();
+            if previous <= INSTRUCTION_CHECK_INTERVAL as u64 {
+                Err(mlua::Error::RuntimeError(format!(
+                    "instruction limit of {limit} exceeded"
+                )))
+            } else {
+                Ok(mlua::VmState::Continue)
+            }
+        },
+    );
+
+    Ok((limit, remaining))
+}
+
+fn reset_instruction_budget(budget: Option<&(NonZeroU64, Arc<AtomicU64>)>) {
+    if let Some((limit, remaining)) = budget {
+        remaining.store(limit.get(), Ordering::Relaxed);
+    }
+}
+
 // A helper that reduces code duplication.
 fn wrap_emit_fn<'lua, 'scope, F: 'scope + FnMut(Event)>(
     scope: &mlua::Scope<'lua, 'scope>,
@@ -375,6 +542,7 @@ impl RuntimeTransform for Lua {
     where
         F: FnMut(Event),
     {
+        self.reset_instruction_budget();
         let lua = &self.lua;
         let source_id = Arc::clone(event.source_id().unwrap_or(&self.source_id));
         _ = lua
@@ -398,6 +566,7 @@ impl RuntimeTransform for Lua {
     where
         F: FnMut(Event),
     {
+        self.reset_instruction_budget();
         let lua = &self.lua;
         _ = lua
             .scope(|scope| -> mlua::Result<()> {
@@ -418,6 +587,7 @@ impl RuntimeTransform for Lua {
     where
         F: FnMut(Event),
     {
+        self.reset_instruction_budget();
         let lua = &self.lua;
         _ = lua
             .scope(|scope| -> mlua::Result<()> {
@@ -438,6 +608,7 @@ impl RuntimeTransform for Lua {
     where
         F: FnMut(Event),
     {
+        self.reset_instruction_budget();
         let lua = &self.lua;
         _ = lua
             .scope(|scope| -> mlua::Result<()> {
@@ -861,6 +1032,94 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn lua_sandbox_restricts_stdlib() -> crate::Result<()> {
+        let mut transform = from_config(
+            r#"
+            sandbox.restrict_stdlib = true
+            hooks.process = """function (event, emit)
+                event.log.os = type(os.execute) .. "," .. type(os.time)
+                event.log.loaders = type(require) .. "," .. type(load) .. "," .. type(io)
+                emit(event)
+            end
+            """
+            "#,
+        )
+        .unwrap();
+
+        let event = transform
+            .process_single(LogEvent::default().into())
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.as_log()["os"], "nil,function".into());
+        assert_eq!(event.as_log()["loaders"], "nil,nil,nil".into());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lua_sandbox_limits_instructions() -> crate::Result<()> {
+        let mut transform = from_config(
+            r#"
+            sandbox.max_instructions = 100000
+            hooks.process = """function (event, emit)
+                if event.log.loop then
+                    while true do
+                        pcall(function() while true do end end)
+                    end
+                end
+                emit(event)
+            end
+            """
+            "#,
+        )
+        .unwrap();
+
+        let mut event = LogEvent::default();
+        event.insert("loop", true);
+        let err = transform.process_single(event.into()).unwrap_err();
+        let err = format_error(&err);
+        assert!(
+            err.contains("instruction limit of 100000 exceeded"),
+            "{}",
+            err
+        );
+
+        // The budget is reset for each event.
+        assert!(transform
+            .process_single(LogEvent::default().into())
+            .unwrap()
+            .is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lua_sandbox_limits_memory() -> crate::Result<()> {
+        let mut transform = from_config(
+            r#"
+            sandbox.max_memory_bytes = 4194304
+            hooks.process = """function (event, emit)
+                local chunks = {}
+                for i = 1, 1000000 do
+                    chunks[i] = string.rep("x", 1024) .. i
+                end
+                emit(event)
+            end
+            """
+            "#,
+        )
+        .unwrap();
+
+        let err = transform
+            .process_single(LogEvent::default().into())
+            .unwrap_err();
+        assert!(
+            matches!(err, mlua::Error::MemoryError(_)),
+            "{}",
+            format_error(&err)
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn lua_syntax_error() -> crate::Result<()> {
         let err = from_config(
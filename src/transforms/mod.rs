@@ -8,12 +8,18 @@ pub mod sample;
 
 #[cfg(feature = "transforms-aggregate")]
 pub mod aggregate;
+#[cfg(feature = "transforms-aggregate_logs")]
+pub mod aggregate_logs;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 pub mod aws_ec2_metadata;
+#[cfg(feature = "transforms-convert_histograms")]
+pub mod convert_histograms;
 #[cfg(feature = "transforms-filter")]
 pub mod filter;
 #[cfg(feature = "transforms-log_to_metric")]
 pub mod log_to_metric;
+#[cfg(feature = "transforms-log_to_trace")]
+pub mod log_to_trace;
 #[cfg(feature = "transforms-lua")]
 pub mod lua;
 #[cfg(feature = "transforms-metric_to_log")]
@@ -22,10 +28,14 @@ pub mod metric_to_log;
 pub mod remap;
 #[cfg(feature = "transforms-route")]
 pub mod route;
+#[cfg(feature = "transforms-schema_enforce")]
+pub mod schema_enforce;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
+#[cfg(feature = "transforms-wasm")]
+pub mod wasm;
 
 pub use vector_lib::transform::{
     FunctionTransform, OutputBuffer, SyncTransform, TaskTransform, Transform, TransformOutputs,
@@ -8,6 +8,7 @@ use vrl::path::{parse_target_path, PathPrefix};
 use vrl::prelude::{Collection, KeyString, Kind};
 
 use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigTargetPath;
 
 use crate::conditions::AnyCondition;
 use crate::config::{
@@ -87,6 +88,22 @@ pub struct ReduceConfig {
     ))]
     pub merge_strategies: IndexMap<KeyString, MergeStrategy>,
 
+    /// A [Vector Remap Language][vrl] (VRL) program merging each event into the state of its group,
+    /// in place of the merge strategies.
+    ///
+    /// The program is run for every event after the first of a group, with the state at `.state`
+    /// and the incoming event at `.event`, and updates `.state` in place. The first event of a group
+    /// becomes its initial state, and the final state is flushed as the reduced event. If the
+    /// program fails, the incoming event is dropped and the state is left unchanged.
+    ///
+    /// Only one of `merge` and `merge_strategies` can be provided.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[configurable(metadata(
+        docs::examples = "if .event.duration_ms > .state.duration_ms { .state.duration_ms = .event.duration_ms }",
+    ))]
+    pub merge: Option<String>,
+
     /// A condition used to distinguish the final event of a transaction.
     ///
     /// If this condition resolves to `true` for an event, the current transaction is immediately
@@ -98,6 +115,47 @@ pub struct ReduceConfig {
     /// If this condition resolves to `true` for an event, the previous transaction is flushed
     /// (without this event) and a new transaction is started.
     pub starts_when: Option<AnyCondition>,
+
+    #[configurable(derived)]
+    #[derivative(Default(value = "Option::None"))]
+    pub event_time: Option<EventTimeConfig>,
+}
+
+/// Reduces events within windows of their own time, rather than of the time they are received.
+///
+/// Each group is reduced separately within each window. A watermark follows the latest event time
+/// seen, minus `allowed_lateness_ms`, and flushes the windows it passes the end of. Events arriving
+/// for a window which was already flushed are dropped.
+///
+/// Groups are still flushed after `expire_after_ms` without events, so that the windows of idle
+/// streams are eventually flushed.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EventTimeConfig {
+    /// The field holding the time of the events.
+    ///
+    /// Defaults to the timestamp of the events, as configured by the [global log schema][global_log_schema]
+    /// in the legacy namespace, or as the field with the `timestamp` meaning in the `vector`
+    /// namespace. Events without a timestamp are assigned the latest event time seen.
+    ///
+    /// [global_log_schema]: https://vector.dev/docs/reference/configuration/global-options/#log_schema
+    #[configurable(metadata(docs::examples = "timestamp", docs::examples = "event.created"))]
+    pub timestamp_field: Option<ConfigTargetPath>,
+
+    /// The duration of the windows, in milliseconds.
+    ///
+    /// Windows are aligned on the Unix epoch.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Window Duration"))]
+    pub window_ms: Duration,
+
+    /// How late events may arrive, in milliseconds, relative to the latest event time seen.
+    #[serde(default)]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Allowed Lateness"))]
+    pub allowed_lateness_ms: Duration,
 }
 
 const fn default_expire_after_ms() -> Duration {
@@ -137,6 +195,11 @@ impl TransformConfig for ReduceConfig {
 
         let mut schema_definition = merged_definition;
 
+        // The state merged by a program can be of any shape.
+        if self.merge.is_some() {
+            *schema_definition.event_kind_mut() = Kind::any();
+        }
+
         for (key, merge_strategy) in self.merge_strategies.iter() {
             let key = if let Ok(key) = parse_target_path(key) {
                 key
@@ -3,21 +3,27 @@ use std::collections::{hash_map, HashMap};
 use std::pin::Pin;
 use std::time::{Duration, Instant};
 
-use crate::internal_events::ReduceAddEventError;
+use crate::internal_events::{ReduceAddEventError, ReduceLateEventDropped, ReduceMergeError};
 use crate::transforms::reduce::merge_strategy::{
     get_value_merger, MergeStrategy, ReduceValueMerger,
 };
 use crate::{
     conditions::Condition,
-    event::{discriminant::Discriminant, Event, EventMetadata, LogEvent},
+    event::{discriminant::Discriminant, Event, EventMetadata, LogEvent, Value},
     internal_events::ReduceStaleEventFlushed,
     transforms::{reduce::config::ReduceConfig, TaskTransform},
 };
+use chrono::{DateTime, TimeDelta, Utc};
 use futures::Stream;
 use indexmap::IndexMap;
 use vector_lib::stream::expiration_map::{map_with_expiration, Emitter};
+use vector_lib::{compile_vrl, TimeZone};
+use vrl::compiler::runtime::Runtime;
+use vrl::compiler::{CompilationResult, CompileConfig, Program, TargetValue, TypeState};
+use vrl::diagnostic::Formatter;
 use vrl::path::{parse_target_path, OwnedTargetPath};
 use vrl::prelude::KeyString;
+use vrl::value::{ObjectMap, Secrets};
 
 #[derive(Debug)]
 struct ReduceState {
@@ -26,6 +32,76 @@ struct ReduceState {
     stale_since: Instant,
     creation: Instant,
     metadata: EventMetadata,
+    /// The state merged by the `merge` program, if configured.
+    merged: Option<Value>,
+}
+
+/// Identifies the state events are reduced into: their group and, with event-time windows, the
+/// start of their window.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct StateKey {
+    discriminant: Discriminant,
+    window_start: Option<DateTime<Utc>>,
+}
+
+/// The event-time windows events are reduced within.
+#[derive(Debug)]
+struct EventTime {
+    timestamp_field: Option<OwnedTargetPath>,
+    window: TimeDelta,
+    allowed_lateness: TimeDelta,
+    /// The latest event time seen.
+    latest: Option<DateTime<Utc>>,
+    /// The end of the windows closed by the watermark when last advanced.
+    closed_until: Option<DateTime<Utc>>,
+}
+
+impl EventTime {
+    fn watermark(&self) -> Option<DateTime<Utc>> {
+        self.latest.map(|latest| latest - self.allowed_lateness)
+    }
+
+    fn align(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let window = self.window.num_milliseconds();
+        DateTime::from_timestamp_millis(time.timestamp_millis().div_euclid(window) * window)
+            .expect("valid timestamp")
+    }
+
+    fn is_closed(&self, window_start: DateTime<Utc>) -> bool {
+        self.watermark()
+            .is_some_and(|watermark| window_start + self.window <= watermark)
+    }
+
+    /// Assigns an event to its window, returning the start of the window, or `None` if the event
+    /// is late, its window having been closed by the watermark.
+    fn window_start(&mut self, event: &LogEvent) -> Option<DateTime<Utc>> {
+        let timestamp = match &self.timestamp_field {
+            Some(path) => event.get(path),
+            None => event.get_timestamp(),
+        }
+        .and_then(Value::as_timestamp)
+        .copied()
+        .or(self.latest)
+        .unwrap_or_else(Utc::now);
+
+        let window_start = self.align(timestamp);
+        if self.is_closed(window_start) {
+            return None;
+        }
+        self.latest = self.latest.max(Some(timestamp));
+        Some(window_start)
+    }
+
+    /// Returns the end of the windows closed by the watermark, if it moved past the end of a
+    /// window since the previous call.
+    fn advance(&mut self) -> Option<DateTime<Utc>> {
+        let closed_until = self.align(self.watermark()?);
+        if self.closed_until == Some(closed_until) {
+            return None;
+        }
+        self.closed_until = Some(closed_until);
+        Some(closed_until)
+    }
 }
 
 fn is_covered_by_strategy(
@@ -42,6 +118,72 @@ fn is_covered_by_strategy(
     false
 }
 
+/// The VRL program merging each event into the state of its group, in place of the merge
+/// strategies.
+#[derive(Debug)]
+struct MergeProgram {
+    program: Program,
+}
+
+impl MergeProgram {
+    fn compile(
+        source: &str,
+        enrichment_tables: &vector_lib::enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        let functions = vrl::stdlib::all()
+            .into_iter()
+            .chain(vector_lib::enrichment::vrl_functions())
+            .chain(vector_vrl_functions::all())
+            .collect::<Vec<_>>();
+
+        let mut config = CompileConfig::default();
+        config.set_custom(enrichment_tables.clone());
+
+        let CompilationResult {
+            program,
+            warnings,
+            config: _,
+        } = compile_vrl(source, &functions, &TypeState::default(), config)
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(source, warnings).colored().to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(Self { program })
+    }
+
+    /// Merges `event` into `state`, both exposed to the program under `.state` and `.event`,
+    /// returning the new state. If the program fails, the event is dropped and `state` is kept.
+    fn merge(&self, state: Value, event: Value) -> Value {
+        let info = self.program.info();
+        // Changes are discarded when the program fails, so the state is only copied if it can.
+        let previous = (info.fallible || info.abortable).then(|| state.clone());
+
+        let mut target = TargetValue {
+            value: Value::Object(ObjectMap::from([
+                ("state".into(), state),
+                ("event".into(), event),
+            ])),
+            metadata: Value::Object(ObjectMap::new()),
+            secrets: Secrets::default(),
+        };
+        match Runtime::default().resolve(&mut target, &self.program, &TimeZone::default()) {
+            Ok(_) => match target.value {
+                Value::Object(mut fields) => fields.remove("state").unwrap_or(Value::Null),
+                _ => Value::Null,
+            },
+            Err(error) => {
+                emit!(ReduceMergeError {
+                    error: error.to_string()
+                });
+                previous.unwrap_or(Value::Null)
+            }
+        }
+    }
+}
+
 impl ReduceState {
     fn new() -> Self {
         Self {
@@ -50,12 +192,34 @@ impl ReduceState {
             creation: Instant::now(),
             fields: HashMap::new(),
             metadata: EventMetadata::default(),
+            merged: None,
         }
     }
 
-    fn add_event(&mut self, e: LogEvent, strategies: &IndexMap<OwnedTargetPath, MergeStrategy>) {
+    fn add_event(
+        &mut self,
+        e: LogEvent,
+        strategies: &IndexMap<OwnedTargetPath, MergeStrategy>,
+        merge: Option<&MergeProgram>,
+    ) {
         self.metadata.merge(e.metadata().clone());
 
+        match merge {
+            Some(merge) => {
+                let (value, _) = e.into_parts();
+                self.merged = Some(match self.merged.take() {
+                    Some(state) => merge.merge(state, value),
+                    None => value,
+                });
+            }
+            None => self.add_fields(&e, strategies),
+        }
+
+        self.events += 1;
+        self.stale_since = Instant::now();
+    }
+
+    fn add_fields(&mut self, e: &LogEvent, strategies: &IndexMap<OwnedTargetPath, MergeStrategy>) {
         for (path, strategy) in strategies {
             if let Some(value) = e.get(path) {
                 match self.fields.entry(path.clone()) {
@@ -115,13 +279,13 @@ impl ReduceState {
             }
         }
         // else the event root is not an object (see https://github.com/vectordotdev/vector/issues/18219)
-
-        self.events += 1;
-        self.stale_since = Instant::now();
     }
 
     fn flush(mut self) -> LogEvent {
-        let mut event = LogEvent::new_with_metadata(self.metadata);
+        let mut event = match self.merged.take() {
+            Some(value) => LogEvent::from_parts(value, self.metadata),
+            None => LogEvent::new_with_metadata(self.metadata),
+        };
         for (path, v) in self.fields.drain() {
             if let Err(error) = v.insert_into(&path, &mut event) {
                 warn!(message = "Failed to merge values for field.", %error);
@@ -139,10 +303,12 @@ pub struct Reduce {
     end_every_period: Option<Duration>,
     group_by: Vec<String>,
     merge_strategies: IndexMap<OwnedTargetPath, MergeStrategy>,
-    reduce_merge_states: HashMap<Discriminant, ReduceState>,
+    merge: Option<MergeProgram>,
+    reduce_merge_states: HashMap<StateKey, ReduceState>,
     ends_when: Option<Condition>,
     starts_when: Option<Condition>,
     max_events: Option<usize>,
+    event_time: Option<EventTime>,
 }
 
 fn validate_merge_strategies(strategies: IndexMap<KeyString, MergeStrategy>) -> crate::Result<()> {
@@ -183,11 +349,41 @@ impl Reduce {
             .as_ref()
             .map(|c| c.build(enrichment_tables))
             .transpose()?;
+        if config.merge.is_some() && !config.merge_strategies.is_empty() {
+            return Err("only one of `merge` and `merge_strategies` can be provided".into());
+        }
+        let merge = config
+            .merge
+            .as_deref()
+            .map(|source| MergeProgram::compile(source, enrichment_tables))
+            .transpose()?;
         let group_by = config.group_by.clone().into_iter().collect();
         let max_events = config.max_events.map(|max| max.into());
 
         validate_merge_strategies(config.merge_strategies.clone())?;
 
+        let event_time = config
+            .event_time
+            .as_ref()
+            .map(|event_time| {
+                if event_time.window_ms.is_zero() {
+                    return Err::<_, crate::Error>(
+                        "`event_time.window_ms` must be greater than zero".into(),
+                    );
+                }
+                Ok(EventTime {
+                    timestamp_field: event_time
+                        .timestamp_field
+                        .as_ref()
+                        .map(|field| field.0.clone()),
+                    window: TimeDelta::from_std(event_time.window_ms)?,
+                    allowed_lateness: TimeDelta::from_std(event_time.allowed_lateness_ms)?,
+                    latest: None,
+                    closed_until: None,
+                })
+            })
+            .transpose()?;
+
         Ok(Reduce {
             expire_after: config.expire_after_ms,
             flush_period: config.flush_period_ms,
@@ -207,10 +403,12 @@ impl Reduce {
                     parsed_path.map(|path| (path, strategy.clone()))
                 })
                 .collect(),
+            merge,
             reduce_merge_states: HashMap::new(),
             ends_when,
             starts_when,
             max_events,
+            event_time,
         })
     }
 
@@ -242,15 +440,44 @@ impl Reduce {
             .for_each(|(_, s)| emitter.emit(Event::from(s.flush())));
     }
 
-    fn push_or_new_reduce_state(&mut self, event: LogEvent, discriminant: Discriminant) {
-        match self.reduce_merge_states.entry(discriminant) {
+    /// Flushes the event-time windows the watermark closed since the previous call.
+    fn flush_closed_windows_into(&mut self, emitter: &mut Emitter<Event>) {
+        let Some(event_time) = &mut self.event_time else {
+            return;
+        };
+        let Some(closed_until) = event_time.advance() else {
+            return;
+        };
+
+        let window = event_time.window;
+        let mut closed = self
+            .reduce_merge_states
+            .keys()
+            .filter(|key| {
+                key.window_start
+                    .is_some_and(|window_start| window_start + window <= closed_until)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        closed.sort_by_key(|key| key.window_start);
+        for key in closed {
+            if let Some(state) = self.reduce_merge_states.remove(&key) {
+                emitter.emit(state.flush().into());
+            }
+        }
+    }
+
+    fn push_or_new_reduce_state(&mut self, event: LogEvent, key: StateKey) {
+        match self.reduce_merge_states.entry(key) {
             hash_map::Entry::Vacant(entry) => {
                 let mut state = ReduceState::new();
-                state.add_event(event, &self.merge_strategies);
+                state.add_event(event, &self.merge_strategies, self.merge.as_ref());
                 entry.insert(state);
             }
             hash_map::Entry::Occupied(mut entry) => {
-                entry.get_mut().add_event(event, &self.merge_strategies);
+                entry
+                    .get_mut()
+                    .add_event(event, &self.merge_strategies, self.merge.as_ref());
             }
         };
     }
@@ -268,11 +495,25 @@ impl Reduce {
 
         let event = event.into_log();
         let discriminant = Discriminant::from_log_event(&event, &self.group_by);
+        let window_start = match &mut self.event_time {
+            Some(event_time) => match event_time.window_start(&event) {
+                Some(window_start) => Some(window_start),
+                None => {
+                    emit!(ReduceLateEventDropped);
+                    return;
+                }
+            },
+            None => None,
+        };
+        let key = StateKey {
+            discriminant,
+            window_start,
+        };
 
         if let Some(max_events) = self.max_events {
             if max_events == 1 {
                 ends_here = true;
-            } else if let Some(entry) = self.reduce_merge_states.get(&discriminant) {
+            } else if let Some(entry) = self.reduce_merge_states.get(&key) {
                 // The current event will finish this set
                 if entry.events + 1 == max_events {
                     ends_here = true;
@@ -281,26 +522,28 @@ impl Reduce {
         }
 
         if starts_here {
-            if let Some(state) = self.reduce_merge_states.remove(&discriminant) {
+            if let Some(state) = self.reduce_merge_states.remove(&key) {
                 emitter.emit(state.flush().into());
             }
 
-            self.push_or_new_reduce_state(event, discriminant)
+            self.push_or_new_reduce_state(event, key)
         } else if ends_here {
-            emitter.emit(match self.reduce_merge_states.remove(&discriminant) {
+            emitter.emit(match self.reduce_merge_states.remove(&key) {
                 Some(mut state) => {
-                    state.add_event(event, &self.merge_strategies);
+                    state.add_event(event, &self.merge_strategies, self.merge.as_ref());
                     state.flush().into()
                 }
                 None => {
                     let mut state = ReduceState::new();
-                    state.add_event(event, &self.merge_strategies);
+                    state.add_event(event, &self.merge_strategies, self.merge.as_ref());
                     state.flush().into()
                 }
             });
         } else {
-            self.push_or_new_reduce_state(event, discriminant)
+            self.push_or_new_reduce_state(event, key)
         }
+
+        self.flush_closed_windows_into(emitter);
     }
 }
 
@@ -995,4 +1238,112 @@ merge_strategies.bar = "concat"
         })
         .await
     }
+
+    #[tokio::test]
+    async fn reduce_with_merge_program() {
+        let config = toml::from_str::<ReduceConfig>(indoc!(
+            r#"
+            group_by = [ "id" ]
+            merge = """
+            latency_ms = int!(.event.request.latency_ms)
+            if latency_ms > int!(.state.request.latency_ms) {
+              .state.request.latency_ms = latency_ms
+            }
+            """
+
+            [ends_when]
+              type = "vrl"
+              source = "exists(.done)"
+            "#,
+        ))
+        .unwrap();
+
+        assert_transform_compliance(async move {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            for (latency_ms, done) in [(20, false), (50, false), (30, true)] {
+                let mut event = LogEvent::from(Value::from(btreemap! {
+                    "id" => 1,
+                    "request" => btreemap! { "latency_ms" => latency_ms },
+                }));
+                if done {
+                    event.insert("done", true);
+                }
+                tx.send(event.into()).await.unwrap();
+            }
+
+            let output = out.recv().await.unwrap().into_log();
+            let expected_value = Value::from(btreemap! {
+                "id" => 1,
+                "request" => btreemap! { "latency_ms" => 50 },
+            });
+            assert_eq!(*output.value(), expected_value);
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await
+    }
+
+    #[test]
+    fn merge_program_excludes_merge_strategies() {
+        let config = toml::from_str::<ReduceConfig>(indoc!(
+            r#"
+            merge = ".state = .event"
+            merge_strategies.message = "array"
+            "#,
+        ))
+        .unwrap();
+        let error = Reduce::new(&config, &TableRegistry::default()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "only one of `merge` and `merge_strategies` can be provided"
+        );
+    }
+
+    #[tokio::test]
+    async fn reduce_event_time_windows() {
+        let reduce_config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "id" ]
+merge_strategies.message = "array"
+
+[event_time]
+  window_ms = 10000
+  allowed_lateness_ms = 5000
+"#,
+        )
+        .unwrap();
+
+        assert_transform_compliance(async move {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), reduce_config).await;
+
+            // The third event is out of order, but within the allowed lateness, while the last one
+            // arrives once its window was flushed.
+            for (message, timestamp) in [("a", 100), ("b", 108), ("c", 103), ("d", 116), ("e", 104)]
+            {
+                let mut event = LogEvent::from(message);
+                event.insert("id", "1");
+                event.insert(
+                    "timestamp",
+                    chrono::DateTime::from_timestamp(timestamp, 0).unwrap(),
+                );
+                tx.send(event.into()).await.unwrap();
+            }
+
+            let output_1 = out.recv().await.unwrap().into_log();
+            assert_eq!(output_1["message"], vec!["a", "b", "c"].into());
+
+            drop(tx);
+            topology.stop().await;
+
+            let output_2 = out.recv().await.unwrap().into_log();
+            assert_eq!(output_2["message"], vec!["d"].into());
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
 }
@@ -1,28 +1,32 @@
-use std::collections::HashMap;
-use std::sync::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use std::{
     collections::BTreeMap,
     fs::File,
     io::{self, Read},
-    path::PathBuf,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
 };
 
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
 use snafu::{ResultExt, Snafu};
 use vector_lib::codecs::MetricTagValues;
 use vector_lib::compile_vrl;
 use vector_lib::config::LogNamespace;
 use vector_lib::configurable::configurable_component;
 use vector_lib::enrichment::TableRegistry;
-use vector_lib::lookup::{metadata_path, owned_value_path, PathPrefix};
+use vector_lib::lookup::{owned_value_path, PathPrefix};
 use vector_lib::schema::Definition;
 use vector_lib::TimeZone;
+use vector_vrl_functions::set_abort_reason::take_abort_reason;
 use vector_vrl_functions::set_semantic_meaning::MeaningList;
+use vector_vrl_functions::set_state::{Keyspaces, StateStore};
+use vector_vrl_functions::user_function::UserFunction;
 use vrl::compiler::runtime::{Runtime, Terminate};
 use vrl::compiler::state::ExternalEnv;
-use vrl::compiler::{CompileConfig, ExpressionError, Program, TypeState, VrlRuntime};
-use vrl::diagnostic::{DiagnosticMessage, Formatter, Note};
-use vrl::path;
-use vrl::path::ValuePath;
+use vrl::compiler::{CompileConfig, ExpressionError, Function, Program, TypeState, VrlRuntime};
+use vrl::diagnostic::{DiagnosticMessage, Formatter, Note, Span};
 use vrl::value::{Kind, Value};
 
 use crate::config::OutputId;
@@ -32,15 +36,26 @@ use crate::{
         TransformOutput,
     },
     event::{Event, TargetEvents, VrlTarget},
-    internal_events::{RemapMappingAbort, RemapMappingError},
+    internal_events::{
+        RemapExecutionTimeExceeded, RemapMappingAbort, RemapMappingError, RemapStatePersistError,
+    },
     schema,
     transforms::{SyncTransform, Transform, TransformOutputsBuf},
     Result,
 };
 
 const DROPPED: &str = "dropped";
-type CacheKey = (TableRegistry, schema::Definition);
-type CacheValue = (Program, String, MeaningList);
+const STATE_FILE: &str = "state.json";
+type CacheKey = (TableRegistry, schema::Definition, Keyspaces);
+type CacheValue = (Program, String, MeaningList, Arc<SourceMap>);
+
+// Matches the span that runtime errors of function calls are reported at, as in
+// `function call error for "string" at (160:174): expected string, got integer`.
+static ERROR_SPAN: Lazy<Regex> = Lazy::new(|| Regex::new(r" at \((\d+):(\d+)\)").unwrap());
+
+// Matches an `import "<path>"` statement, which may only be preceded by comments, blank lines, and
+// other imports.
+static IMPORT: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^\s*import\s+"([^"]+)"\s*$"#).unwrap());
 
 /// Configuration for the `remap` transform.
 #[configurable_component(transform(
@@ -72,6 +87,26 @@ pub struct RemapConfig {
     #[configurable(metadata(docs::examples = "./my/program.vrl"))]
     pub file: Option<PathBuf>,
 
+    /// File paths to [Vector Remap Language][vrl] (VRL) programs that are concatenated, in the
+    /// given order, into the program to execute for each event.
+    ///
+    /// Runtime errors and dropped event annotations refer to the file, line, and column they
+    /// originate from, rather than to offsets in the concatenated program.
+    ///
+    /// Can't be used along with `source` or `file`.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[configurable(metadata(docs::examples = "./my/functions.vrl"))]
+    #[configurable(metadata(docs::examples = "./my/program.vrl"))]
+    pub files: Vec<PathBuf>,
+
+    /// Functions defined by VRL programs, which the program of the transform can call.
+    ///
+    /// Functions can call the functions defined before them, but not themselves.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<RemapFunctionConfig>,
+
     /// When set to `single`, metric tag values are exposed as single strings, the
     /// same as they were before this config option. Tags with multiple values show the last assigned value, and null values
     /// are ignored.
@@ -134,6 +169,47 @@ pub struct RemapConfig {
     #[configurable(metadata(docs::human_name = "Reroute Dropped Events"))]
     pub reroute_dropped: bool,
 
+    /// The name of the field that events rerouted to the `dropped` output are annotated under.
+    ///
+    /// For logs using the `Legacy` log namespace, and for metric tags, the annotation is placed
+    /// under the global [`log_schema.metadata_key`][global_metadata_key]. For logs using the
+    /// `Vector` log namespace, it's placed in the `vector` metadata namespace.
+    ///
+    /// Events dropped by `abort` are additionally annotated with the span of the `abort`
+    /// expression in the program, as well as the line and column it starts at. If the program
+    /// was read from `file` or `files`, the annotation also contains the path of the file, and
+    /// the line and column are relative to it.
+    ///
+    /// [global_metadata_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.metadata_key
+    #[serde(default = "default_dropped_metadata_key")]
+    #[configurable(metadata(docs::advanced))]
+    #[derivative(Default(value = "default_dropped_metadata_key()"))]
+    pub dropped_metadata_key: String,
+
+    /// The maximum time, in milliseconds, that the program can take to process an event.
+    ///
+    /// The program isn't interrupted, but events taking longer are handled as if the program had
+    /// failed, according to `drop_on_error` and `reroute_dropped`, and are counted in the
+    /// `component_errors_total` metric with the `execution_time_exceeded` error code.
+    ///
+    /// As any event can then fail, events are copied before running the program, unless they're
+    /// dropped on errors without being rerouted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::type_unit = "milliseconds", docs::advanced))]
+    pub max_execution_time_ms: Option<u64>,
+
+    /// The maximum depth of nested calls of the functions defined in `functions`.
+    ///
+    /// Calls are nested as deep when processing events as they are in the program, so programs
+    /// nesting calls deeper are rejected when the transform is built.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::advanced))]
+    pub max_recursion_depth: Option<usize>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<RemapStateConfig>,
+
     #[configurable(derived, metadata(docs::hidden))]
     #[serde(default)]
     pub runtime: VrlRuntime,
@@ -152,52 +228,194 @@ impl Clone for RemapConfig {
         Self {
             source: self.source.clone(),
             file: self.file.clone(),
+            files: self.files.clone(),
+            functions: self.functions.clone(),
             metric_tag_values: self.metric_tag_values,
             timezone: self.timezone,
             drop_on_error: self.drop_on_error,
             drop_on_abort: self.drop_on_abort,
             reroute_dropped: self.reroute_dropped,
+            dropped_metadata_key: self.dropped_metadata_key.clone(),
+            max_execution_time_ms: self.max_execution_time_ms,
+            max_recursion_depth: self.max_recursion_depth,
+            state: self.state.clone(),
             runtime: self.runtime,
             cache: Mutex::new(Default::default()),
         }
     }
 }
 
+/// A function defined by a VRL program.
+///
+/// The arguments of a call are available to the program as the fields of the event, such as `.name`
+/// for the `name` parameter, and the value of its last expression is returned. The event being
+/// processed is out of reach of the program, and so are enrichment tables.
+///
+/// The program is type checked at each call with the types of the arguments. Calls must handle
+/// errors if the program can fail or abort with these arguments.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RemapFunctionConfig {
+    /// The name of the function.
+    ///
+    /// Can't be the name of a function provided by VRL or Vector.
+    #[configurable(metadata(docs::examples = "normalize_status"))]
+    pub name: String,
+
+    /// The names of the parameters of the function, which are all required.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "status"))]
+    pub parameters: Vec<String>,
+
+    /// The VRL program computing the return value of the function.
+    #[configurable(metadata(
+        docs::examples = "if is_integer(.status) { .status } else { to_int(.status) ?? 0 }",
+        docs::syntax_override = "remap_program"
+    ))]
+    pub source: String,
+}
+
+fn default_dropped_metadata_key() -> String {
+    DROPPED.to_owned()
+}
+
+/// Key/value state shared by the runs of the program, available through the `get_state` and
+/// `set_state` functions.
+///
+/// Programs can use it to count events, track the first time a value was seen, or group events
+/// into sessions. The state is shared by all the events processed by the transform, but not across
+/// transforms.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RemapStateConfig {
+    /// The maximum number of keys held in the state.
+    ///
+    /// Setting a new key once the state is full evicts the expired keys, or else the key set the
+    /// longest ago.
+    #[serde(default = "default_state_max_keys")]
+    pub max_keys: usize,
+
+    /// Whether to persist the state in the data directory, so that it's restored when Vector
+    /// restarts.
+    ///
+    /// Values are restored with their types, except for regular expressions, which are restored as
+    /// strings.
+    #[serde(default)]
+    pub persist: bool,
+
+    /// The interval, in seconds, at which the state is written to disk when persisted.
+    #[serde(default = "default_state_flush_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub flush_interval_secs: u64,
+}
+
+const fn default_state_max_keys() -> usize {
+    100_000
+}
+
+const fn default_state_flush_interval_secs() -> u64 {
+    10
+}
+
+/// The state of a `remap` transform persisted on disk.
+#[derive(Clone, Debug)]
+struct PersistedState {
+    store: StateStore,
+    path: PathBuf,
+    flush_interval: Duration,
+    last_flush: Instant,
+}
+
+impl PersistedState {
+    fn flush(&mut self) {
+        if let Err(error) = self.store.persist(&self.path) {
+            emit!(RemapStatePersistError {
+                error,
+                path: &self.path,
+            });
+        }
+        self.last_flush = Instant::now();
+    }
+}
+
+impl Drop for PersistedState {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
 impl RemapConfig {
     fn compile_vrl_program(
         &self,
         enrichment_tables: TableRegistry,
         merged_schema_definition: schema::Definition,
-    ) -> Result<(Program, String, MeaningList)> {
+        keyspaces: Keyspaces,
+        state_store: Option<StateStore>,
+    ) -> Result<CacheValue> {
+        // Programs using state are bound to the store they're compiled with, so they're never
+        // shared through the cache, which would give them the store of an earlier compilation.
+        let cacheable = state_store.is_none();
         if let Some((_, res)) = self
             .cache
             .lock()
             .expect("Data poisoned")
             .iter()
-            .find(|v| v.0 .0 == enrichment_tables && v.0 .1 == merged_schema_definition)
+            .find(|v| {
+                v.0 .0 == enrichment_tables
+                    && v.0 .1 == merged_schema_definition
+                    && v.0 .2 == keyspaces
+            })
+            .filter(|_| cacheable)
         {
             return res.clone().map_err(Into::into);
         }
 
-        let source = match (&self.source, &self.file) {
-            (Some(source), None) => source.to_owned(),
-            (None, Some(path)) => {
-                let mut buffer = String::new();
-
-                File::open(path)
-                    .with_context(|_| FileOpenFailedSnafu { path })?
-                    .read_to_string(&mut buffer)
-                    .with_context(|_| FileReadFailedSnafu { path })?;
-
-                buffer
-            }
+        let source_map = match (&self.source, &self.file, self.files.as_slice()) {
+            (Some(source), None, []) => SourceMap::inline(source.to_owned())?,
+            (None, Some(path), []) => SourceMap::read(std::slice::from_ref(path))?,
+            (None, None, paths) if !paths.is_empty() => SourceMap::read(paths)?,
             _ => return Err(Box::new(BuildError::SourceAndOrFile)),
         };
+        let source = source_map.source.as_str();
 
         let mut functions = vrl::stdlib::all();
         functions.append(&mut vector_lib::enrichment::vrl_functions());
         functions.append(&mut vector_vrl_functions::all());
 
+        let mut user_functions = Vec::<UserFunction>::with_capacity(self.functions.len());
+        for function in &self.functions {
+            if functions
+                .iter()
+                .any(|existing| existing.identifier() == function.name)
+            {
+                return Err(Box::new(BuildError::FunctionConflict {
+                    name: function.name.clone(),
+                }));
+            }
+
+            let mut body_functions = vrl::stdlib::all();
+            body_functions.append(&mut vector_vrl_functions::all());
+            body_functions.extend(
+                user_functions
+                    .iter()
+                    .cloned()
+                    .map(|function| Box::new(function) as Box<dyn Function>),
+            );
+            let mut user_function = UserFunction::new(
+                &function.name,
+                &function.parameters,
+                function.source.clone(),
+                body_functions,
+            );
+            if let Some(max_depth) = self.max_recursion_depth {
+                user_function = user_function.with_max_depth(max_depth);
+            }
+            functions.push(Box::new(user_function.clone()));
+            user_functions.push(user_function);
+        }
+
         let state = TypeState {
             local: Default::default(),
             external: ExternalEnv::new_with_kind(
@@ -209,21 +427,28 @@ impl RemapConfig {
 
         config.set_custom(enrichment_tables.clone());
         config.set_custom(MeaningList::default());
+        config.set_custom(keyspaces.clone());
+        if let Some(store) = state_store {
+            config.set_custom(store);
+        }
 
-        let res = compile_vrl(&source, &functions, &state, config)
-            .map_err(|diagnostics| Formatter::new(&source, diagnostics).colored().to_string())
+        let res = compile_vrl(source, &functions, &state, config)
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())
             .map(|result| {
                 (
                     result.program,
-                    Formatter::new(&source, result.warnings).to_string(),
+                    Formatter::new(source, result.warnings).to_string(),
                     result.config.get_custom::<MeaningList>().unwrap().clone(),
+                    Arc::new(source_map.clone()),
                 )
             });
 
-        self.cache
-            .lock()
-            .expect("Data poisoned")
-            .push(((enrichment_tables, merged_schema_definition), res.clone()));
+        if cacheable {
+            self.cache.lock().expect("Data poisoned").push((
+                (enrichment_tables, merged_schema_definition, keyspaces),
+                res.clone(),
+            ));
+        }
 
         res.map_err(Into::into)
     }
@@ -271,10 +496,19 @@ impl TransformConfig for RemapConfig {
 
         // We need to compile the VRL program in order to know the schema definition output of this
         // transform. We ignore any compilation errors, as those are caught by the transform build
-        // step.
+        // step, which also checks that the keyspaces used by the program are configured.
+        let state_store = self
+            .state
+            .as_ref()
+            .map(|state| StateStore::new(state.max_keys));
         let compiled = self
-            .compile_vrl_program(enrichment_tables, merged_definition)
-            .map(|(program, _, meaning_list)| (program.final_type_info().state, meaning_list.0))
+            .compile_vrl_program(
+                enrichment_tables,
+                merged_definition,
+                Keyspaces::placeholder(),
+                state_store,
+            )
+            .map(|(program, _, meaning_list, _)| (program.final_type_info().state, meaning_list.0))
             .map_err(|_| ());
 
         let mut dropped_definitions = HashMap::new();
@@ -324,6 +558,10 @@ impl TransformConfig for RemapConfig {
                         ("component_id".into(), Kind::bytes()),
                         ("component_type".into(), Kind::bytes()),
                         ("component_kind".into(), Kind::bytes()),
+                        ("span".into(), span_kind()),
+                        ("file".into(), Kind::bytes().or_undefined()),
+                        ("line".into(), Kind::integer().or_undefined()),
+                        ("column".into(), Kind::integer().or_undefined()),
                     ])),
                     Some("metadata"),
                 ),
@@ -333,7 +571,23 @@ impl TransformConfig for RemapConfig {
                     .with_metadata_field(&owned_value_path!("message"), Kind::bytes(), None)
                     .with_metadata_field(&owned_value_path!("component_id"), Kind::bytes(), None)
                     .with_metadata_field(&owned_value_path!("component_type"), Kind::bytes(), None)
-                    .with_metadata_field(&owned_value_path!("component_kind"), Kind::bytes(), None),
+                    .with_metadata_field(&owned_value_path!("component_kind"), Kind::bytes(), None)
+                    .with_metadata_field(&owned_value_path!("span"), span_kind(), None)
+                    .with_metadata_field(
+                        &owned_value_path!("file"),
+                        Kind::bytes().or_undefined(),
+                        None,
+                    )
+                    .with_metadata_field(
+                        &owned_value_path!("line"),
+                        Kind::integer().or_undefined(),
+                        None,
+                    )
+                    .with_metadata_field(
+                        &owned_value_path!("column"),
+                        Kind::integer().or_undefined(),
+                        None,
+                    ),
             );
 
             default_definitions.insert(
@@ -363,6 +617,182 @@ impl TransformConfig for RemapConfig {
     }
 }
 
+/// The kind of the `span` annotation, which is only set for aborted events.
+fn span_kind() -> Kind {
+    Kind::object(BTreeMap::from([
+        ("start".into(), Kind::integer()),
+        ("end".into(), Kind::integer()),
+    ]))
+    .or_undefined()
+}
+
+/// The source of a VRL program, along with the files it was assembled from, which is used to map
+/// the spans of runtime errors back to the file they originate from.
+#[derive(Clone, Debug)]
+pub struct SourceMap {
+    source: String,
+    /// Each segment of the program, along with the offset it starts at. Segments that were read
+    /// from a file carry its path.
+    segments: Vec<(Option<PathBuf>, usize)>,
+}
+
+/// A position in the source of a program.
+#[derive(Debug, PartialEq)]
+struct SourceLocation<'a> {
+    /// The file containing the position, unless it's part of a program configured inline.
+    file: Option<&'a Path>,
+    /// The one-based line of the position in its segment.
+    line: usize,
+    /// The one-based column of the position in its line.
+    column: usize,
+}
+
+impl SourceMap {
+    fn inline(source: String) -> Result<Self> {
+        let mut builder = SourceMapBuilder::default();
+        builder.add(None, source, false)?;
+        Ok(builder.finish())
+    }
+
+    /// Reads and concatenates the given files, each of them starting on a new line.
+    fn read(paths: &[PathBuf]) -> Result<Self> {
+        let mut builder = SourceMapBuilder::default();
+        for path in paths {
+            builder.read(path, false)?;
+        }
+        Ok(builder.finish())
+    }
+
+    fn locate(&self, offset: usize) -> Option<SourceLocation<'_>> {
+        let (file, start) = self
+            .segments
+            .iter()
+            .rev()
+            .find(|(_, start)| *start <= offset)
+            .map_or((None, 0), |(path, start)| (path.as_deref(), *start));
+        let before = self.source.get(start..offset)?;
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+
+        Some(SourceLocation {
+            file,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        })
+    }
+
+    /// Returns the message of a runtime error, with the spans of failed function calls replaced
+    /// by the file, line, and column they start at if the program was assembled from several
+    /// segments.
+    fn error_message(&self, error: &ExpressionError) -> String {
+        let message = error.to_string();
+        if self.segments.iter().all(|(path, _)| path.is_none()) {
+            return message;
+        }
+
+        ERROR_SPAN
+            .replace_all(&message, |captures: &Captures<'_>| {
+                let location = captures[1]
+                    .parse()
+                    .ok()
+                    .and_then(|offset| self.locate(offset));
+                match location {
+                    Some(SourceLocation {
+                        file: Some(file),
+                        line,
+                        column,
+                    }) => format!(" at {}:{line}:{column}", file.display()),
+                    Some(SourceLocation { line, column, .. }) => format!(" at {line}:{column}"),
+                    None => captures[0].to_string(),
+                }
+            })
+            .into_owned()
+    }
+}
+
+#[derive(Default)]
+struct SourceMapBuilder {
+    source: String,
+    segments: Vec<(Option<PathBuf>, usize)>,
+    /// The files that are already part of the program, which are only included once.
+    included: HashSet<PathBuf>,
+    /// The files that are being read, used to detect import cycles.
+    reading: Vec<PathBuf>,
+}
+
+impl SourceMapBuilder {
+    fn read(&mut self, path: &Path, scoped: bool) -> Result<()> {
+        let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if self.reading.contains(&key) {
+            return Err(Box::new(BuildError::ImportCycle {
+                path: path.to_path_buf(),
+            }));
+        }
+        if !self.included.insert(key.clone()) {
+            return Ok(());
+        }
+
+        let mut content = String::new();
+        File::open(path)
+            .with_context(|_| FileOpenFailedSnafu { path })?
+            .read_to_string(&mut content)
+            .with_context(|_| FileReadFailedSnafu { path })?;
+
+        self.reading.push(key);
+        let result = self.add(Some(path), content, scoped);
+        self.reading.pop();
+        result
+    }
+
+    /// Adds a segment to the program, preceded by the files it imports.
+    ///
+    /// Imports are resolved relative to the directory of the importing file, or to the current
+    /// working directory for programs configured inline. They are blanked out rather than removed
+    /// so that the lines and columns of the segment are kept. Imported files are wrapped in a
+    /// block, which scopes the variables they assign to them.
+    fn add(&mut self, path: Option<&Path>, mut content: String, scoped: bool) -> Result<()> {
+        let dir = path.and_then(Path::parent).unwrap_or_else(|| Path::new(""));
+
+        let mut imports = Vec::new();
+        let mut start = 0;
+        for line in content.split_inclusive('\n') {
+            let statement = line.trim_end_matches(['\r', '\n']);
+            if let Some(captures) = IMPORT.captures(statement) {
+                imports.push((dir.join(&captures[1]), start..start + statement.len()));
+            } else if !statement.trim().is_empty() && !statement.trim_start().starts_with('#') {
+                break;
+            }
+            start += line.len();
+        }
+
+        for (import, range) in imports {
+            self.read(&import, true)?;
+            let blank = " ".repeat(range.len());
+            content.replace_range(range, &blank);
+        }
+
+        if !self.source.is_empty() && !self.source.ends_with('\n') {
+            self.source.push('\n');
+        }
+        if scoped {
+            self.source.push_str("{\n");
+        }
+        self.segments
+            .push((path.map(Path::to_path_buf), self.source.len()));
+        self.source.push_str(&content);
+        if scoped {
+            self.source.push_str("\n}\n");
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> SourceMap {
+        SourceMap {
+            source: self.source,
+            segments: self.segments,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Remap<Runner>
 where
@@ -370,12 +800,16 @@ where
 {
     component_key: Option<ComponentKey>,
     program: Program,
+    source: Arc<SourceMap>,
     timezone: TimeZone,
     drop_on_error: bool,
     drop_on_abort: bool,
     reroute_dropped: bool,
+    dropped_metadata_key: String,
     runner: Runner,
     metric_tag_values: MetricTagValues,
+    max_execution_time: Option<Duration>,
+    state: Option<PersistedState>,
 }
 
 pub trait VrlRunner {
@@ -418,15 +852,37 @@ impl Remap<AstRunner> {
         config: RemapConfig,
         context: &TransformContext,
     ) -> crate::Result<(Self, String)> {
-        let (program, warnings, _) = config.compile_vrl_program(
+        let (store, state) = match &config.state {
+            Some(state_config) if state_config.persist => {
+                let component_id = context.key.as_ref().map_or("remap", ComponentKey::id);
+                let path = context
+                    .globals
+                    .resolve_and_make_data_subdir(None, component_id)?
+                    .join(STATE_FILE);
+                let store = StateStore::load(&path, state_config.max_keys)
+                    .with_context(|_| StateLoadFailedSnafu { path: path.clone() })?;
+                let state = PersistedState {
+                    store: store.clone(),
+                    path,
+                    flush_interval: Duration::from_secs(state_config.flush_interval_secs),
+                    last_flush: Instant::now(),
+                };
+                (Some(store), Some(state))
+            }
+            Some(state_config) => (Some(StateStore::new(state_config.max_keys)), None),
+            None => (None, None),
+        };
+        let (program, warnings, _, source) = config.compile_vrl_program(
             context.enrichment_tables.clone(),
             context.merged_schema_definition.clone(),
+            context.keyspaces.clone(),
+            store,
         )?;
 
         let runtime = Runtime::default();
         let runner = AstRunner { runtime };
 
-        Self::new(config, context, program, runner).map(|remap| (remap, warnings))
+        Self::new(config, context, program, source, runner, state).map(|remap| (remap, warnings))
     }
 }
 
@@ -438,19 +894,25 @@ where
         config: RemapConfig,
         context: &TransformContext,
         program: Program,
+        source: Arc<SourceMap>,
         runner: Runner,
+        state: Option<PersistedState>,
     ) -> crate::Result<Self> {
         Ok(Remap {
             component_key: context.key.clone(),
             program,
+            source,
             timezone: config
                 .timezone
                 .unwrap_or_else(|| context.globals.timezone()),
             drop_on_error: config.drop_on_error,
             drop_on_abort: config.drop_on_abort,
             reroute_dropped: config.reroute_dropped,
+            dropped_metadata_key: config.dropped_metadata_key,
             runner,
             metric_tag_values: config.metric_tag_values,
+            max_execution_time: config.max_execution_time_ms.map(Duration::from_millis),
+            state,
         })
     }
 
@@ -459,72 +921,136 @@ where
         &self.runner
     }
 
-    fn dropped_data(&self, reason: &str, error: ExpressionError) -> serde_json::Value {
+    /// Returns the span of an `abort` expression, along with the location it starts at in the
+    /// program source.
+    fn abort_location(&self, error: &ExpressionError) -> Option<(Span, SourceLocation<'_>)> {
+        let ExpressionError::Abort { span, .. } = error else {
+            return None;
+        };
+        Some((*span, self.source.locate(span.start())?))
+    }
+
+    fn dropped_data(
+        &self,
+        reason: &str,
+        reason_code: Option<&Value>,
+        error: ExpressionError,
+    ) -> serde_json::Value {
         let message = error
             .notes()
             .iter()
             .filter(|note| matches!(note, Note::UserErrorMessage(_)))
             .last()
             .map(|note| note.to_string())
-            .unwrap_or_else(|| error.to_string());
-        serde_json::json!({
+            .unwrap_or_else(|| self.source.error_message(&error));
+        let mut data = serde_json::json!({
                 "reason": reason,
                 "message": message,
                 "component_id": self.component_key,
                 "component_type": "remap",
                 "component_kind": "transform",
-        })
+        });
+        if let Some(code) = reason_code {
+            data["reason_code"] = match code {
+                Value::Integer(code) => (*code).into(),
+                code => code.to_string_lossy().into_owned().into(),
+            };
+        }
+        if let Some((span, location)) = self.abort_location(&error) {
+            data["span"] = serde_json::json!({ "start": span.start(), "end": span.end() });
+            if let Some(file) = location.file {
+                data["file"] = file.display().to_string().into();
+            }
+            data["line"] = location.line.into();
+            data["column"] = location.column.into();
+        }
+        data
     }
 
-    fn annotate_dropped(&self, event: &mut Event, reason: &str, error: ExpressionError) {
+    fn annotate_dropped(
+        &self,
+        event: &mut Event,
+        reason: &str,
+        reason_code: Option<&Value>,
+        error: ExpressionError,
+    ) {
         match event {
             Event::Log(ref mut log) => match log.namespace() {
                 LogNamespace::Legacy => {
                     if let Some(metadata_key) = log_schema().metadata_key() {
                         log.insert(
-                            (PathPrefix::Event, metadata_key.concat(path!("dropped"))),
-                            self.dropped_data(reason, error),
+                            (
+                                PathPrefix::Event,
+                                &metadata_key.with_field_appended(&self.dropped_metadata_key),
+                            ),
+                            self.dropped_data(reason, reason_code, error),
                         );
                     }
                 }
                 LogNamespace::Vector => {
                     log.insert(
-                        metadata_path!("vector", "dropped"),
-                        self.dropped_data(reason, error),
+                        (
+                            PathPrefix::Metadata,
+                            &owned_value_path!("vector")
+                                .with_field_appended(&self.dropped_metadata_key),
+                        ),
+                        self.dropped_data(reason, reason_code, error),
                     );
                 }
             },
             Event::Metric(ref mut metric) => {
                 if let Some(metadata_key) = log_schema().metadata_key() {
-                    metric.replace_tag(format!("{}.dropped.reason", metadata_key), reason.into());
+                    let prefix = format!("{}.{}", metadata_key, self.dropped_metadata_key);
+                    metric.replace_tag(format!("{prefix}.reason"), reason.into());
+                    if let Some(code) = reason_code {
+                        metric.replace_tag(
+                            format!("{prefix}.reason_code"),
+                            code.to_string_lossy().into_owned(),
+                        );
+                    }
                     metric.replace_tag(
-                        format!("{}.dropped.component_id", metadata_key),
+                        format!("{prefix}.component_id"),
                         self.component_key
                             .as_ref()
                             .map(ToString::to_string)
                             .unwrap_or_default(),
                     );
-                    metric.replace_tag(
-                        format!("{}.dropped.component_type", metadata_key),
-                        "remap".into(),
-                    );
-                    metric.replace_tag(
-                        format!("{}.dropped.component_kind", metadata_key),
-                        "transform".into(),
-                    );
+                    metric.replace_tag(format!("{prefix}.component_type"), "remap".into());
+                    metric.replace_tag(format!("{prefix}.component_kind"), "transform".into());
+                    if let Some((span, location)) = self.abort_location(&error) {
+                        metric
+                            .replace_tag(format!("{prefix}.span.start"), span.start().to_string());
+                        metric.replace_tag(format!("{prefix}.span.end"), span.end().to_string());
+                        if let Some(file) = location.file {
+                            metric
+                                .replace_tag(format!("{prefix}.file"), file.display().to_string());
+                        }
+                        metric.replace_tag(format!("{prefix}.line"), location.line.to_string());
+                        metric.replace_tag(format!("{prefix}.column"), location.column.to_string());
+                    }
                 }
             }
             Event::Trace(ref mut trace) => {
                 trace.maybe_insert(log_schema().metadata_key_target_path(), || {
-                    self.dropped_data(reason, error).into()
+                    self.dropped_data(reason, reason_code, error).into()
                 });
             }
         }
     }
 
     fn run_vrl(&mut self, target: &mut VrlTarget) -> std::result::Result<Value, Terminate> {
+        // Discards the reason code set by an earlier program run on this thread.
+        take_abort_reason();
         self.runner.run(target, &self.program, &self.timezone)
     }
+
+    fn flush_state(&mut self) {
+        if let Some(state) = &mut self.state {
+            if state.last_flush.elapsed() >= state.flush_interval {
+                state.flush();
+            }
+        }
+    }
 }
 
 impl<Runner> SyncTransform for Remap<Runner>
@@ -544,7 +1070,8 @@ where
         // the event to the `dropped` output.
         let forward_on_error = !self.drop_on_error || self.reroute_dropped;
         let forward_on_abort = !self.drop_on_abort || self.reroute_dropped;
-        let original_event = if (self.program.info().fallible && forward_on_error)
+        let fallible = self.program.info().fallible || self.max_execution_time.is_some();
+        let original_event = if (fallible && forward_on_error)
             || (self.program.info().abortable && forward_on_abort)
         {
             Some(event.clone())
@@ -565,7 +1092,28 @@ where
                 MetricTagValues::Full => true,
             },
         );
-        let result = self.run_vrl(&mut target);
+        let start = Instant::now();
+        let mut result = self.run_vrl(&mut target);
+        let elapsed = start.elapsed();
+        let exceeded = self
+            .max_execution_time
+            .filter(|max_execution_time| elapsed > *max_execution_time);
+        if let Some(max_execution_time) = exceeded {
+            result = Err(Terminate::Error(
+                format!(
+                    "execution time of {}ms exceeded the budget of {}ms",
+                    elapsed.as_millis(),
+                    max_execution_time.as_millis()
+                )
+                .into(),
+            ));
+            emit!(RemapExecutionTimeExceeded {
+                event_dropped: self.drop_on_error && !self.reroute_dropped,
+                elapsed,
+                max_execution_time,
+            });
+        }
+        self.flush_state();
 
         match result {
             Ok(_) => match target.into_events(log_namespace) {
@@ -576,23 +1124,24 @@ where
                 }
             },
             Err(reason) => {
-                let (reason, error, drop) = match reason {
+                let (reason, reason_code, error, drop) = match reason {
                     Terminate::Abort(error) => {
                         if !self.reroute_dropped {
                             emit!(RemapMappingAbort {
                                 event_dropped: self.drop_on_abort,
                             });
                         }
-                        ("abort", error, self.drop_on_abort)
+                        ("abort", take_abort_reason(), error, self.drop_on_abort)
                     }
                     Terminate::Error(error) => {
-                        if !self.reroute_dropped {
+                        // Exceeding the budget is already reported on its own.
+                        if !self.reroute_dropped && exceeded.is_none() {
                             emit!(RemapMappingError {
-                                error: error.to_string(),
+                                error: self.source.error_message(&error),
                                 event_dropped: self.drop_on_error,
                             });
                         }
-                        ("error", error, self.drop_on_error)
+                        ("error", None, error, self.drop_on_error)
                     }
                 };
 
@@ -603,7 +1152,7 @@ where
                 } else if self.reroute_dropped {
                     let mut event = original_event.expect("event will be set");
 
-                    self.annotate_dropped(&mut event, reason, error);
+                    self.annotate_dropped(&mut event, reason, reason_code.as_ref(), error);
                     push_dropped(event, output);
                 }
             }
@@ -623,13 +1172,19 @@ fn push_dropped(event: Event, output: &mut TransformOutputsBuf) {
 
 #[derive(Debug, Snafu)]
 pub enum BuildError {
-    #[snafu(display("must provide exactly one of `source` or `file` configuration"))]
+    #[snafu(display("must provide exactly one of `source`, `file` or `files` configuration"))]
     SourceAndOrFile,
 
     #[snafu(display("Could not open vrl program {:?}: {}", path, source))]
     FileOpenFailed { path: PathBuf, source: io::Error },
     #[snafu(display("Could not read vrl program {:?}: {}", path, source))]
     FileReadFailed { path: PathBuf, source: io::Error },
+    #[snafu(display("vrl program {:?} is imported by a program it imports", path))]
+    ImportCycle { path: PathBuf },
+    #[snafu(display("Could not load the state persisted in {:?}: {}", path, source))]
+    StateLoadFailed { path: PathBuf, source: io::Error },
+    #[snafu(display("function {:?} is already defined", name))]
+    FunctionConflict { name: String },
 }
 
 #[cfg(test)]
@@ -710,7 +1265,7 @@ mod tests {
         let err = remap(config).unwrap_err().to_string();
         assert_eq!(
             &err,
-            "must provide exactly one of `source` or `file` configuration"
+            "must provide exactly one of `source`, `file` or `files` configuration"
         )
     }
 
@@ -725,10 +1280,122 @@ mod tests {
         let err = remap(config).unwrap_err().to_string();
         assert_eq!(
             &err,
-            "must provide exactly one of `source` or `file` configuration"
+            "must provide exactly one of `source`, `file` or `files` configuration"
         )
     }
 
+    #[test]
+    fn check_remap_calls_user_functions() {
+        let conf = RemapConfig {
+            source: Some(r#".status = normalize_status(.status)"#.to_owned()),
+            functions: vec![
+                RemapFunctionConfig {
+                    name: "clamp".to_owned(),
+                    parameters: vec!["value".to_owned(), "min".to_owned(), "max".to_owned()],
+                    source:
+                        "if .value < .min { .min } else if .value > .max { .max } else { .value }"
+                            .to_owned(),
+                },
+                RemapFunctionConfig {
+                    name: "normalize_status".to_owned(),
+                    parameters: vec!["status".to_owned()],
+                    source: "clamp(to_int(.status) ?? 0, 100, 599)".to_owned(),
+                },
+            ],
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+
+        for (status, expected) in [(Value::from("404"), 404), (Value::from(1000), 599)] {
+            let mut event = LogEvent::default();
+            event.insert("status", status);
+            let result = transform_one(&mut tform, Event::from(event)).unwrap();
+            assert_eq!(result.as_log()["status"], Value::from(expected));
+        }
+    }
+
+    #[test]
+    fn config_user_function_conflict() {
+        let conf = RemapConfig {
+            source: Some(".".to_owned()),
+            functions: vec![RemapFunctionConfig {
+                name: "upcase".to_owned(),
+                parameters: vec!["value".to_owned()],
+                source: ".value".to_owned(),
+            }],
+            ..Default::default()
+        };
+
+        let err = remap(conf).unwrap_err().to_string();
+        assert_eq!(&err, r#"function "upcase" is already defined"#);
+    }
+
+    #[test]
+    fn config_user_function_invalid_call() {
+        let conf = RemapConfig {
+            source: Some(".message = shout(.message)".to_owned()),
+            functions: vec![RemapFunctionConfig {
+                name: "shout".to_owned(),
+                parameters: vec!["value".to_owned()],
+                source: "upcase(.value)".to_owned(),
+            }],
+            ..Default::default()
+        };
+
+        // `.message` could be any type, so it's rejected by `upcase` in the body.
+        let err = remap(conf).unwrap_err().to_string();
+        assert!(err.contains(r#"function "shout" doesn't compile with these arguments"#));
+    }
+
+    #[test]
+    fn config_user_functions_too_deep() {
+        let functions = vec![
+            RemapFunctionConfig {
+                name: "inner".to_owned(),
+                parameters: vec!["value".to_owned()],
+                source: "upcase(.value)".to_owned(),
+            },
+            RemapFunctionConfig {
+                name: "outer".to_owned(),
+                parameters: vec!["value".to_owned()],
+                source: "inner(.value)".to_owned(),
+            },
+        ];
+        let conf = |max_recursion_depth| RemapConfig {
+            source: Some(r#".message = outer("hello")"#.to_owned()),
+            functions: functions.clone(),
+            max_recursion_depth: Some(max_recursion_depth),
+            ..Default::default()
+        };
+
+        assert!(remap(conf(2)).is_ok());
+        let err = remap(conf(1)).unwrap_err().to_string();
+        assert!(
+            err.contains(r#"calling function "inner" exceeds the maximum depth of 1 nested calls"#)
+        );
+    }
+
+    #[test]
+    fn check_remap_execution_time_exceeded() {
+        let conf = RemapConfig {
+            source: Some(".message = upcase(string!(.message))".to_owned()),
+            max_execution_time_ms: Some(0),
+            drop_on_error: true,
+            reroute_dropped: true,
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+
+        let output =
+            transform_one_fallible(&mut tform, Event::from(LogEvent::from("hello"))).unwrap_err();
+        let log = output.as_log();
+        assert_eq!(log["message"], "hello".into());
+        assert_eq!(log["metadata.dropped.reason"], "error".into());
+        assert!(log["metadata.dropped.message"]
+            .to_string_lossy()
+            .contains("exceeded the budget of 0ms"));
+    }
+
     fn get_field_string(event: &Event, field: &str) -> String {
         event
             .as_log()
@@ -770,6 +1437,214 @@ mod tests {
         assert!(tform.runner().runtime.is_empty());
     }
 
+    fn count_events_config(state: RemapStateConfig) -> RemapConfig {
+        RemapConfig {
+            source: Some(
+                indoc! {r#"
+                key = string!(.message)
+                .count = (int(get_state(key)) ?? 0) + 1
+                set_state!(key, .count)
+            "#}
+                .to_owned(),
+            ),
+            state: Some(state),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn remap_state_is_shared_between_events() {
+        let state = RemapStateConfig {
+            max_keys: 2,
+            persist: false,
+            flush_interval_secs: default_state_flush_interval_secs(),
+        };
+        let mut tform = remap(count_events_config(state)).unwrap();
+
+        // Once the state is full, new keys evict the key set the longest ago, here `bar`.
+        for (message, count) in [
+            ("foo", 1_i64),
+            ("bar", 1),
+            ("foo", 2),
+            ("baz", 1),
+            ("bar", 1),
+        ] {
+            let result = transform_one(&mut tform, Event::from(LogEvent::from(message))).unwrap();
+            assert_eq!(result.as_log()["count"], count.into());
+        }
+    }
+
+    #[test]
+    fn remap_state_is_persisted() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let context = TransformContext {
+            key: Some(ComponentKey::from("counter")),
+            globals: GlobalOptions {
+                data_dir: Some(data_dir.path().to_path_buf()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let state = RemapStateConfig {
+            max_keys: default_state_max_keys(),
+            persist: true,
+            flush_interval_secs: 0,
+        };
+
+        for count in 1..=2_i64 {
+            let config = count_events_config(state.clone());
+            let mut tform = Remap::new_ast(config, &context).unwrap().0;
+            let result = transform_one(&mut tform, Event::from(LogEvent::from("foo"))).unwrap();
+            assert_eq!(result.as_log()["count"], count.into());
+        }
+        assert!(data_dir.path().join("counter").join(STATE_FILE).exists());
+    }
+
+    #[tokio::test]
+    async fn remap_state_is_persisted_after_outputs() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let context = TransformContext {
+            key: Some(ComponentKey::from("counter")),
+            globals: GlobalOptions {
+                data_dir: Some(data_dir.path().to_path_buf()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let config = count_events_config(RemapStateConfig {
+            max_keys: default_state_max_keys(),
+            persist: true,
+            flush_interval_secs: 0,
+        });
+
+        // The topology computes the outputs of the transform before building it.
+        config.outputs(
+            TableRegistry::default(),
+            &[(OutputId::dummy(), Definition::any())],
+            LogNamespace::Legacy,
+        );
+        let Transform::Synchronous(mut tform) = config.build(&context).await.unwrap() else {
+            panic!("remap is a synchronous transform");
+        };
+        let result = transform_one(tform.as_mut(), Event::from(LogEvent::from("foo"))).unwrap();
+        assert_eq!(result.as_log()["count"], 1_i64.into());
+        drop(tform);
+
+        let persisted = StateStore::load(
+            &data_dir.path().join("counter").join(STATE_FILE),
+            default_state_max_keys(),
+        )
+        .unwrap();
+        assert_eq!(persisted.get("foo"), 1_i64.into());
+    }
+
+    #[test]
+    fn remap_state_requires_config() {
+        let config = RemapConfig {
+            source: Some(r#"get_state("foo")"#.to_owned()),
+            ..Default::default()
+        };
+        let error = remap(config).unwrap_err().to_string();
+        assert!(error.contains("state is not enabled"), "{error}");
+    }
+
+    fn remap_with_keyspaces(
+        config: RemapConfig,
+        keyspaces: &Keyspaces,
+    ) -> Result<Remap<AstRunner>> {
+        let context = TransformContext {
+            keyspaces: keyspaces.clone(),
+            ..Default::default()
+        };
+        Remap::new_ast(config, &context).map(|(remap, _)| remap)
+    }
+
+    #[test]
+    fn remap_keyspace_is_shared_between_transforms() {
+        let keyspaces = Keyspaces::default();
+        keyspaces.load([("sessions", 10, None)]);
+        let login = RemapConfig {
+            source: Some(
+                r#"set_state!(string!(.message), "alice", keyspace: "sessions")"#.to_owned(),
+            ),
+            ..Default::default()
+        };
+        let activity = RemapConfig {
+            source: Some(
+                r#".user = get_state(string!(.message), keyspace: "sessions")"#.to_owned(),
+            ),
+            ..Default::default()
+        };
+        let mut login = remap_with_keyspaces(login, &keyspaces).unwrap();
+        let mut activity = remap_with_keyspaces(activity, &keyspaces).unwrap();
+
+        transform_one(&mut login, Event::from(LogEvent::from("session-1"))).unwrap();
+        let result =
+            transform_one(&mut activity, Event::from(LogEvent::from("session-1"))).unwrap();
+        assert_eq!(result.as_log()["user"], "alice".into());
+    }
+
+    #[test]
+    fn remap_keyspace_must_be_configured() {
+        let config = RemapConfig {
+            source: Some(r#"get_state("foo", keyspace: "missing")"#.to_owned()),
+            ..Default::default()
+        };
+        let error = remap(config).unwrap_err().to_string();
+        assert!(error.contains("unknown keyspace"), "{error}");
+    }
+
+    #[test]
+    fn remap_keyspaces_are_dropped_on_reload() {
+        let keyspaces = Keyspaces::default();
+        keyspaces.load([("sessions", 10, None), ("logins", 10, None)]);
+        let login = RemapConfig {
+            source: Some(r#"set_state!("session-1", "alice", keyspace: "sessions")"#.to_owned()),
+            ..Default::default()
+        };
+        let mut login = remap_with_keyspaces(login, &keyspaces).unwrap();
+        transform_one(&mut login, Event::from(LogEvent::from("login"))).unwrap();
+
+        // Reloading keeps the entries of the keyspaces still configured.
+        keyspaces.load([("sessions", 10, None)]);
+        assert_eq!(
+            keyspaces.get("sessions").unwrap().get("session-1"),
+            "alice".into()
+        );
+        let config = RemapConfig {
+            source: Some(r#"get_state("foo", keyspace: "logins")"#.to_owned()),
+            ..Default::default()
+        };
+        let error = remap_with_keyspaces(config, &keyspaces)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("unknown keyspace"), "{error}");
+    }
+
+    #[test]
+    fn remap_program_type_checked_with_keyspaces_isnt_reused() {
+        let keyspaces = Keyspaces::default();
+        keyspaces.load([("sessions", 10, None)]);
+        let config = RemapConfig {
+            source: Some(
+                r#"set_state!(string!(.message), "alice", keyspace: "sessions")"#.to_owned(),
+            ),
+            ..Default::default()
+        };
+
+        config.outputs(
+            TableRegistry::default(),
+            &[(OutputId::dummy(), Definition::any())],
+            LogNamespace::Legacy,
+        );
+        let mut tform = remap_with_keyspaces(config, &keyspaces).unwrap();
+        transform_one(&mut tform, Event::from(LogEvent::from("session-1"))).unwrap();
+        assert_eq!(
+            keyspaces.get("sessions").unwrap().get("session-1"),
+            "alice".into()
+        );
+    }
+
     #[test]
     fn remap_return_raw_string_vector_namespace() {
         let initial_definition = Definition::default_for_namespace(&[LogNamespace::Vector].into());
@@ -1233,6 +2108,9 @@ mod tests {
                     "component_id": "remapper",
                     "component_type": "remap",
                     "component_kind": "transform",
+                    "span": { "start": 196, "end": 201 },
+                    "line": 11,
+                    "column": 7,
                 }
             })
             .try_into()
@@ -1297,6 +2175,10 @@ mod tests {
                     "metadata.dropped.component_id" => "remapper",
                     "metadata.dropped.component_type" => "remap",
                     "metadata.dropped.component_kind" => "transform",
+                    "metadata.dropped.span.start" => "104",
+                    "metadata.dropped.span.end" => "109",
+                    "metadata.dropped.line" => "5",
+                    "metadata.dropped.column" => "7",
                 }))
             )
         );
@@ -1420,6 +2302,9 @@ mod tests {
                     "component_id": "remapper",
                     "component_type": "remap",
                     "component_kind": "transform",
+                    "span": { "start": 0, "end": 27 },
+                    "line": 1,
+                    "column": 1,
                 }
             })
             .try_into()
@@ -1427,6 +2312,276 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_remap_branching_abort_with_reason_code() {
+        let conf = RemapConfig {
+            source: Some(formatdoc! {r#"
+                if .hello == "validation" {{
+                  set_abort_reason("invalid_hello")
+                  abort "invalid hello"
+                }} else if .hello == "rate" {{
+                  set_abort_reason(429)
+                }}
+                abort
+            "#}),
+            drop_on_abort: true,
+            reroute_dropped: true,
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+
+        for (hello, code) in [
+            ("validation", Some(Value::from("invalid_hello"))),
+            ("rate", Some(Value::from(429))),
+            // Codes set by earlier runs aren't reported.
+            ("other", None),
+        ] {
+            let event =
+                Event::from_json_value(serde_json::json!({ "hello": hello }), LogNamespace::Legacy)
+                    .unwrap();
+            let output = transform_one_fallible(&mut tform, event).unwrap_err();
+            let log = output.as_log();
+            assert_eq!(log["metadata.dropped.reason"], "abort".into());
+            assert_eq!(log.get("metadata.dropped.reason_code"), code.as_ref());
+        }
+    }
+
+    #[test]
+    fn check_remap_branching_custom_dropped_metadata_key() {
+        let abort =
+            Event::from_json_value(serde_json::json!({"hello": 42}), LogNamespace::Legacy).unwrap();
+        let conf = RemapConfig {
+            source: Some(formatdoc! {r#"
+                .foo = "bar"
+                  abort "custom message here"
+            "#}),
+            drop_on_abort: true,
+            reroute_dropped: true,
+            dropped_metadata_key: "dlq".to_owned(),
+            ..Default::default()
+        };
+        let context = TransformContext {
+            key: Some(ComponentKey::from("remapper")),
+            ..Default::default()
+        };
+        let mut tform = Remap::new_ast(conf, &context).unwrap().0;
+
+        let output = transform_one_fallible(&mut tform, abort).unwrap_err();
+        let log = output.as_log();
+        assert_eq!(log["hello"], 42.into());
+        assert!(!log.contains(event_path!("foo")));
+        assert_eq!(
+            log["metadata"],
+            serde_json::json!({
+                "dlq": {
+                    "reason": "abort",
+                    "message": "custom message here",
+                    "component_id": "remapper",
+                    "component_type": "remap",
+                    "component_kind": "transform",
+                    "span": { "start": 15, "end": 42 },
+                    "line": 2,
+                    "column": 3,
+                }
+            })
+            .try_into()
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn check_remap_branching_files_source_map() {
+        let dir = tempfile::tempdir().unwrap();
+        let functions = dir.path().join("functions.vrl");
+        let program = dir.path().join("program.vrl");
+        std::fs::write(&functions, ".foo = \"bar\"\n").unwrap();
+        std::fs::write(
+            &program,
+            indoc! {r#"
+                .len = strlen(string!(.hello))
+                if .hello == "goodbye" {
+                  abort
+                }
+            "#},
+        )
+        .unwrap();
+
+        let abort = Event::from_json_value(
+            serde_json::json!({"hello": "goodbye"}),
+            LogNamespace::Legacy,
+        )
+        .unwrap();
+        let error =
+            Event::from_json_value(serde_json::json!({"hello": 42}), LogNamespace::Legacy).unwrap();
+        let conf = RemapConfig {
+            files: vec![functions, program.clone()],
+            drop_on_error: true,
+            drop_on_abort: true,
+            reroute_dropped: true,
+            ..Default::default()
+        };
+        let context = TransformContext {
+            key: Some(ComponentKey::from("remapper")),
+            ..Default::default()
+        };
+        let mut tform = Remap::new_ast(conf, &context).unwrap().0;
+
+        let output = transform_one_fallible(&mut tform, abort).unwrap_err();
+        assert_eq!(
+            output.as_log()["metadata"],
+            serde_json::json!({
+                "dropped": {
+                    "reason": "abort",
+                    "message": "aborted",
+                    "component_id": "remapper",
+                    "component_type": "remap",
+                    "component_kind": "transform",
+                    "span": { "start": 71, "end": 76 },
+                    "file": program.display().to_string(),
+                    "line": 3,
+                    "column": 3,
+                }
+            })
+            .try_into()
+            .unwrap()
+        );
+
+        let output = transform_one_fallible(&mut tform, error).unwrap_err();
+        assert_eq!(
+            output.as_log()["metadata.dropped.message"],
+            format!(
+                "function call error for \"string\" at {}:1:15: expected string, got integer",
+                program.display()
+            )
+            .into()
+        );
+    }
+
+    #[test]
+    fn check_remap_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let lib = dir.path().join("lib");
+        std::fs::create_dir(&lib).unwrap();
+        std::fs::write(
+            lib.join("parse.vrl"),
+            indoc! {r#"
+                import "common.vrl"
+                parts = split!(.message, " ")
+                .level = parts[0]
+            "#},
+        )
+        .unwrap();
+        std::fs::write(lib.join("common.vrl"), ".parsed = true\n").unwrap();
+        let program = dir.path().join("program.vrl");
+        std::fs::write(
+            &program,
+            indoc! {r#"
+                # Shared parsing.
+                import "lib/parse.vrl"
+                import "lib/common.vrl"
+
+                if .level == "debug" {
+                  abort
+                }
+            "#},
+        )
+        .unwrap();
+
+        let conf = RemapConfig {
+            file: Some(program.clone()),
+            drop_on_abort: true,
+            reroute_dropped: true,
+            ..Default::default()
+        };
+        let context = TransformContext {
+            key: Some(ComponentKey::from("remapper")),
+            ..Default::default()
+        };
+        let mut tform = Remap::new_ast(conf, &context).unwrap().0;
+
+        let event = LogEvent::from("info started");
+        let output = transform_one(&mut tform, event.into()).unwrap();
+        let log = output.as_log();
+        assert_eq!(log["level"], "info".into());
+        assert_eq!(log["parsed"], true.into());
+
+        let event = LogEvent::from("debug details");
+        let output = transform_one_fallible(&mut tform, event.into()).unwrap_err();
+        let log = output.as_log();
+        assert_eq!(
+            log["metadata.dropped.file"],
+            program.display().to_string().into()
+        );
+        assert_eq!(log["metadata.dropped.line"], 6.into());
+        assert_eq!(log["metadata.dropped.column"], 3.into());
+    }
+
+    #[test]
+    fn check_remap_import_scopes_variables() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.vrl"), "parts = [1, 2]\n").unwrap();
+        let program = dir.path().join("program.vrl");
+        std::fs::write(&program, "import \"lib.vrl\"\n.parts = parts\n").unwrap();
+
+        let conf = RemapConfig {
+            file: Some(program),
+            ..Default::default()
+        };
+
+        let err = remap(conf).unwrap_err().to_string();
+        assert!(err.contains("undefined variable"), "{err}");
+    }
+
+    #[test]
+    fn check_remap_import_cycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.vrl");
+        std::fs::write(&a, "import \"b.vrl\"\n.a = 1\n").unwrap();
+        std::fs::write(dir.path().join("b.vrl"), "import \"a.vrl\"\n.b = 1\n").unwrap();
+
+        let conf = RemapConfig {
+            file: Some(a),
+            ..Default::default()
+        };
+
+        let err = remap(conf).unwrap_err().to_string();
+        assert!(err.contains("is imported by a program it imports"), "{err}");
+    }
+
+    #[test]
+    fn source_map_locates_offsets() {
+        let source_map = SourceMap {
+            source: "first\nfile\nsecond\nfile".to_owned(),
+            segments: vec![(Some("a.vrl".into()), 0), (Some("b.vrl".into()), 11)],
+        };
+
+        assert_eq!(
+            source_map.locate(7),
+            Some(SourceLocation {
+                file: Some(Path::new("a.vrl")),
+                line: 2,
+                column: 2,
+            })
+        );
+        assert_eq!(
+            source_map.locate(20),
+            Some(SourceLocation {
+                file: Some(Path::new("b.vrl")),
+                line: 2,
+                column: 3,
+            })
+        );
+        assert_eq!(source_map.locate(100), None);
+        assert_eq!(
+            SourceMap::inline("abc".to_owned()).unwrap().locate(1),
+            Some(SourceLocation {
+                file: None,
+                line: 1,
+                column: 2,
+            })
+        );
+    }
+
     #[test]
     fn check_remap_branching_disabled() {
         let happy =
@@ -1,7 +1,13 @@
+use std::ops::Range;
+
 use indexmap::IndexMap;
 use vector_lib::config::{clone_input_definitions, LogNamespace};
 use vector_lib::configurable::configurable_component;
 use vector_lib::transform::SyncTransform;
+use vector_lib::{compile_vrl, TimeZone};
+use vrl::compiler::{runtime::Runtime, CompilationResult, CompileConfig, Program, TypeState};
+use vrl::diagnostic::Formatter;
+use vrl::value::Value;
 
 use crate::{
     conditions::{AnyCondition, Condition},
@@ -9,45 +15,213 @@ use crate::{
         DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
         TransformOutput,
     },
-    event::Event,
+    event::{Event, TargetEvents, VrlTarget},
+    internal_events::{RouteDynamicOutputError, TemplateRenderingError},
     schema,
+    template::Template,
     transforms::Transform,
 };
 
 pub(crate) const UNMATCHED_ROUTE: &str = "_unmatched";
 
+// The fractional part of the golden ratio, used to spread the events without a weight key evenly
+// across the weighted routes, as its multiples are evenly distributed modulo 1.
+const GOLDEN_RATIO_FRACTION: f64 = 0.618_033_988_749_894_9;
+
 #[derive(Clone)]
 pub struct Route {
-    conditions: Vec<(String, Condition)>,
+    conditions: Vec<(String, Condition, Option<Range<f64>>)>,
     reroute_unmatched: bool,
+    weighted: bool,
+    weight_key: Option<Template>,
+    count: u64,
+    dynamic: Option<DynamicRoute>,
+}
+
+/// The compiled expression resolving the dynamic output of events.
+#[derive(Clone)]
+struct DynamicRoute {
+    program: Program,
+    outputs: Vec<String>,
+}
+
+impl DynamicRoute {
+    fn new(
+        config: &DynamicRouteConfig,
+        enrichment_tables: &vector_lib::enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        let functions = vrl::stdlib::all()
+            .into_iter()
+            .chain(vector_lib::enrichment::vrl_functions())
+            .chain(vector_vrl_functions::all())
+            .collect::<Vec<_>>();
+
+        let mut compile_config = CompileConfig::default();
+        compile_config.set_custom(enrichment_tables.clone());
+        compile_config.set_read_only();
+
+        let CompilationResult {
+            program, warnings, ..
+        } = compile_vrl(
+            &config.source,
+            &functions,
+            &TypeState::default(),
+            compile_config,
+        )
+        .map_err(|diagnostics| {
+            Formatter::new(&config.source, diagnostics)
+                .colored()
+                .to_string()
+        })?;
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(&config.source, warnings)
+                .colored()
+                .to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(Self {
+            program,
+            outputs: config.outputs.clone(),
+        })
+    }
+
+    /// Resolves the output of the event, returning it along with the event.
+    fn resolve(&self, event: Event) -> (Option<&str>, Event) {
+        let log_namespace = event
+            .maybe_as_log()
+            .map(|log| log.namespace())
+            .unwrap_or(LogNamespace::Legacy);
+        let mut target = VrlTarget::new(event, self.program.info(), false);
+        let result = Runtime::default().resolve(&mut target, &self.program, &TimeZone::default());
+        let event = match target.into_events(log_namespace) {
+            TargetEvents::One(event) => event,
+            _ => panic!("Event was modified in a route. This is an internal compiler error."),
+        };
+
+        let output = match result {
+            Ok(Value::Bytes(name)) => {
+                let output = self
+                    .outputs
+                    .iter()
+                    .find(|output| output.as_bytes() == name.as_ref());
+                if output.is_none() {
+                    emit!(RouteDynamicOutputError {
+                        error: &format!(
+                            "expression resolved to undeclared output {:?}",
+                            String::from_utf8_lossy(&name)
+                        ),
+                    });
+                }
+                output.map(String::as_str)
+            }
+            // Events without an output for them aren't an error.
+            Ok(Value::Null) => None,
+            Ok(value) => {
+                emit!(RouteDynamicOutputError {
+                    error: &format!(
+                        "expression resolved to {}, not to an output name",
+                        value.kind_str()
+                    ),
+                });
+                None
+            }
+            Err(error) => {
+                emit!(RouteDynamicOutputError {
+                    error: &error.to_string(),
+                });
+                None
+            }
+        };
+        (output, event)
+    }
 }
 
 impl Route {
     pub fn new(config: &RouteConfig, context: &TransformContext) -> crate::Result<Self> {
         let mut conditions = Vec::with_capacity(config.route.len());
+        let mut weights_start = 0.0;
         for (output_name, condition) in config.route.iter() {
             let condition = condition.build(&context.enrichment_tables)?;
-            conditions.push((output_name.clone(), condition));
+            // Weighted routes are assigned consecutive ranges of percentages.
+            let range = config.weights.get(output_name).map(|weight| {
+                let range = weights_start..weights_start + weight;
+                weights_start = range.end;
+                range
+            });
+            conditions.push((output_name.clone(), condition, range));
         }
         Ok(Self {
             conditions,
             reroute_unmatched: config.reroute_unmatched,
+            weighted: !config.weights.is_empty(),
+            weight_key: config.weight_key.clone(),
+            count: 0,
+            dynamic: config
+                .dynamic
+                .as_ref()
+                .map(|dynamic| DynamicRoute::new(dynamic, &context.enrichment_tables))
+                .transpose()?,
         })
     }
+
+    /// Returns the percentage determining which weighted route the event is eligible to.
+    fn weight_bucket(&mut self, event: &Event) -> f64 {
+        let key = self.weight_key.as_ref().and_then(|template| {
+            template
+                .render_string(event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("weight_key"),
+                        drop_event: false,
+                    })
+                })
+                .ok()
+        });
+
+        let fraction = match key {
+            // Keep the 53 most significant bits of the hash, which are exactly representable.
+            Some(key) => (seahash::hash(key.as_bytes()) >> 11) as f64 / (1u64 << 53) as f64,
+            None => {
+                self.count = self.count.wrapping_add(1);
+                (self.count as f64 * GOLDEN_RATIO_FRACTION).fract()
+            }
+        };
+        fraction * 100.0
+    }
 }
 
 impl SyncTransform for Route {
     fn transform(&mut self, event: Event, output: &mut vector_lib::transform::TransformOutputsBuf) {
-        let mut check_failed: usize = 0;
-        for (output_name, condition) in &self.conditions {
+        let bucket = self.weighted.then(|| self.weight_bucket(&event));
+        let mut matched = false;
+        for (output_name, condition, range) in &self.conditions {
+            if let Some((range, bucket)) = range.as_ref().zip(bucket) {
+                if !range.contains(&bucket) {
+                    continue;
+                }
+            }
             let (result, event) = condition.check(event.clone());
             if result {
                 output.push(Some(output_name), event);
-            } else {
-                check_failed += 1;
+                matched = true;
             }
         }
-        if self.reroute_unmatched && check_failed == self.conditions.len() {
+
+        let event = match &self.dynamic {
+            Some(dynamic) => match dynamic.resolve(event) {
+                (Some(output_name), event) => {
+                    output.push(Some(output_name), event);
+                    return;
+                }
+                (None, event) => event,
+            },
+            None => event,
+        };
+
+        if self.reroute_unmatched && !matched {
             output.push(Some(UNMATCHED_ROUTE), event);
         }
     }
@@ -84,6 +258,75 @@ pub struct RouteConfig {
     /// as a route name.
     #[configurable(metadata(docs::additional_props_description = "An individual route."))]
     route: IndexMap<String, AnyCondition>,
+
+    /// A table of route identifiers to the percentage of the events that are eligible to the route.
+    ///
+    /// Weighted routes are assigned consecutive ranges of percentages, in the order they are
+    /// declared in `route`: with a weight of `5` for a `canary` route declared before a `stable`
+    /// route with a weight of `95`, 5% of the events are eligible to `canary`, and the other 95% to
+    /// `stable`. Eligible events are still only sent to a route if they match its condition, so
+    /// weighted routes with the same condition split the matching events between them. Routes
+    /// without a weight receive all the events matching their condition.
+    ///
+    /// The weights must add up to at most 100.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    #[configurable(metadata(
+        docs::additional_props_description = "The weight of a route, as a percentage."
+    ))]
+    #[configurable(metadata(docs::examples = "weights_examples()"))]
+    weights: IndexMap<String, f64>,
+
+    /// The key determining which weighted route events are eligible to.
+    ///
+    /// Events with the same key are consistently eligible to the same weighted route, for example
+    /// to always send the requests of a given user to the same backend. If unspecified, or if the
+    /// key can't be rendered for an event, events are spread evenly across the weighted routes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::examples = "{{ user_id }}"))]
+    weight_key: Option<Template>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dynamic: Option<DynamicRouteConfig>,
+}
+
+/// Configuration for resolving the output of events at runtime.
+///
+/// Events are sent to the output the expression resolves to, in addition to the routes they
+/// match. Events for which the expression resolves to `null`, or to an output not declared in
+/// `outputs`, are only sent to the routes they match, if any.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DynamicRouteConfig {
+    /// The [Vector Remap Language][vrl] (VRL) expression resolving the name of the output of an
+    /// event.
+    ///
+    /// The expression can look the output up in an enrichment table, so that the mapping of
+    /// events to outputs can be changed by updating the table, without reloading the
+    /// configuration.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[configurable(metadata(docs::examples = ".tenant"))]
+    #[configurable(metadata(
+        docs::examples = "get_enrichment_table_record!(\"tenants\", { \"id\": .tenant_id }).output"
+    ))]
+    source: String,
+
+    /// The outputs the expression can resolve to.
+    ///
+    /// Each output can be referenced as an input by other components with the name
+    /// `<transform_name>.<output>`, just like routes.
+    #[configurable(metadata(docs::examples = "dynamic_outputs_examples()"))]
+    outputs: Vec<String>,
+}
+
+fn dynamic_outputs_examples() -> Vec<String> {
+    vec!["tenant_a".to_owned(), "tenant_b".to_owned()]
+}
+
+fn weights_examples() -> IndexMap<String, f64> {
+    IndexMap::from([("canary".to_owned(), 5.0), ("stable".to_owned(), 95.0)])
 }
 
 impl GenerateConfig for RouteConfig {
@@ -91,6 +334,9 @@ impl GenerateConfig for RouteConfig {
         toml::Value::try_from(Self {
             reroute_unmatched: true,
             route: IndexMap::new(),
+            weights: IndexMap::new(),
+            weight_key: None,
+            dynamic: None,
         })
         .unwrap()
     }
@@ -109,12 +355,52 @@ impl TransformConfig for RouteConfig {
     }
 
     fn validate(&self, _: &schema::Definition) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
         if self.route.contains_key(UNMATCHED_ROUTE) {
-            Err(vec![format!(
+            errors.push(format!(
                 "cannot have a named output with reserved name: `{UNMATCHED_ROUTE}`"
-            )])
-        } else {
+            ));
+        }
+        for (output_name, weight) in &self.weights {
+            if !self.route.contains_key(output_name) {
+                errors.push(format!(
+                    "cannot set the weight of unknown route `{output_name}`"
+                ));
+            }
+            if !(*weight > 0.0 && *weight <= 100.0) {
+                errors.push(format!(
+                    "the weight of route `{output_name}` must be greater than 0 and at most 100"
+                ));
+            }
+        }
+        if self.weights.values().sum::<f64>() > 100.0 {
+            errors.push("the weights of the routes must add up to at most 100".to_owned());
+        }
+        if let Some(dynamic) = &self.dynamic {
+            if dynamic.outputs.is_empty() {
+                errors.push("the dynamic route must declare at least one output".to_owned());
+            }
+            for (index, output_name) in dynamic.outputs.iter().enumerate() {
+                if output_name == UNMATCHED_ROUTE {
+                    errors.push(format!(
+                        "cannot have a named output with reserved name: `{UNMATCHED_ROUTE}`"
+                    ));
+                } else if self.route.contains_key(output_name) {
+                    errors.push(format!(
+                        "dynamic output `{output_name}` has the same name as a route"
+                    ));
+                } else if dynamic.outputs[..index].contains(output_name) {
+                    errors.push(format!(
+                        "dynamic output `{output_name}` is declared more than once"
+                    ));
+                }
+            }
+        }
+
+        if errors.is_empty() {
             Ok(())
+        } else {
+            Err(errors)
         }
     }
 
@@ -127,6 +413,7 @@ impl TransformConfig for RouteConfig {
         let mut result: Vec<TransformOutput> = self
             .route
             .keys()
+            .chain(self.dynamic.iter().flat_map(|dynamic| &dynamic.outputs))
             .map(|output_name| {
                 TransformOutput::new(
                     DataType::all_bits(),
@@ -162,6 +449,7 @@ mod test {
     use super::*;
     use crate::{
         config::{build_unit_tests, ConfigBuilder},
+        event::LogEvent,
         test_util::components::{init_test, COMPONENT_MULTIPLE_OUTPUTS_TESTS},
     };
 
@@ -363,6 +651,184 @@ mod test {
         }
     }
 
+    #[test]
+    fn route_weighted_routes() {
+        let output_names = vec!["canary", "stable", "all", UNMATCHED_ROUTE];
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.canary = 'exists(.user)'
+            route.stable = 'exists(.user)'
+            route.all = 'exists(.user)'
+
+            weights.canary = 10
+            weights.stable = 90
+            weight_key = "{{ user }}"
+        "#,
+        )
+        .unwrap();
+
+        let mut transform = Route::new(&config, &Default::default()).unwrap();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            output_names
+                .iter()
+                .map(|output_name| {
+                    TransformOutput::new(DataType::all_bits(), HashMap::new())
+                        .with_port(output_name.to_owned())
+                })
+                .collect(),
+            1,
+        );
+
+        let mut canary_users = Vec::new();
+        for user in 0..1000 {
+            let event = Event::from_json_value(
+                serde_json::json!({ "user": user.to_string() }),
+                LogNamespace::Legacy,
+            )
+            .unwrap();
+            // Events with the same key are always sent to the same weighted route.
+            for _ in 0..3 {
+                transform.transform(event.clone(), &mut outputs);
+            }
+
+            let canary = outputs.drain_named("canary").count();
+            let stable = outputs.drain_named("stable").count();
+            assert!((canary, stable) == (3, 0) || (canary, stable) == (0, 3));
+            assert_eq!(outputs.drain_named("all").count(), 3);
+            assert_eq!(outputs.drain_named(UNMATCHED_ROUTE).count(), 0);
+            if canary > 0 {
+                canary_users.push(user);
+            }
+        }
+        assert!(
+            (50..150).contains(&canary_users.len()),
+            "{} canary users",
+            canary_users.len()
+        );
+    }
+
+    #[test]
+    fn route_weighted_routes_without_key() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.canary = 'true'
+            weights.canary = 25
+        "#,
+        )
+        .unwrap();
+
+        let mut transform = Route::new(&config, &Default::default()).unwrap();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            ["canary", UNMATCHED_ROUTE]
+                .into_iter()
+                .map(|output_name| {
+                    TransformOutput::new(DataType::all_bits(), HashMap::new())
+                        .with_port(output_name)
+                })
+                .collect(),
+            1,
+        );
+
+        for _ in 0..1000 {
+            let event =
+                Event::from_json_value(serde_json::json!({}), LogNamespace::Legacy).unwrap();
+            transform.transform(event, &mut outputs);
+        }
+        // Events not eligible to the weighted route are unmatched.
+        let canary = outputs.drain_named("canary").count();
+        assert!((240..=260).contains(&canary), "{canary} canary events");
+        assert_eq!(outputs.drain_named(UNMATCHED_ROUTE).count(), 1000 - canary);
+    }
+
+    #[test]
+    fn route_rejects_invalid_weights() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.canary = 'true'
+            route.stable = 'true'
+            weights.canary = 20
+            weights.stable = 90
+            weights.missing = 0
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.validate(&schema::Definition::any()),
+            Err(vec![
+                "cannot set the weight of unknown route `missing`".to_owned(),
+                "the weight of route `missing` must be greater than 0 and at most 100".to_owned(),
+                "the weights of the routes must add up to at most 100".to_owned(),
+            ])
+        );
+    }
+
+    #[test]
+    fn route_dynamic_outputs() {
+        let output_names = vec!["errors", "tenant_a", "tenant_b", UNMATCHED_ROUTE];
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.errors = '.level == "error"'
+            dynamic.source = '.tenant'
+            dynamic.outputs = ["tenant_a", "tenant_b"]
+        "#,
+        )
+        .unwrap();
+        let mut transform = Route::new(&config, &Default::default()).unwrap();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            output_names
+                .iter()
+                .map(|output_name| {
+                    TransformOutput::new(DataType::all_bits(), HashMap::new())
+                        .with_port(output_name.to_owned())
+                })
+                .collect(),
+            1,
+        );
+
+        for (tenant, level, expected) in [
+            ("tenant_a", "info", vec!["tenant_a"]),
+            ("tenant_b", "error", vec!["errors", "tenant_b"]),
+            ("tenant_c", "info", vec![UNMATCHED_ROUTE]),
+            ("tenant_c", "error", vec!["errors"]),
+        ] {
+            let mut event = Event::Log(LogEvent::from("hello world"));
+            event.as_mut_log().insert("tenant", tenant);
+            event.as_mut_log().insert("level", level);
+            transform.transform(event.clone(), &mut outputs);
+            for output_name in &output_names {
+                let mut events: Vec<_> = outputs.drain_named(output_name).collect();
+                if expected.contains(output_name) {
+                    assert_eq!(events.len(), 1);
+                    assert_eq!(events.pop().unwrap(), event);
+                } else {
+                    assert!(events.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn route_rejects_invalid_dynamic_outputs() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.errors = '.level == "error"'
+            dynamic.source = '.tenant'
+            dynamic.outputs = ["errors", "tenant_a", "tenant_a", "_unmatched"]
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.validate(&schema::Definition::any()),
+            Err(vec![
+                "dynamic output `errors` has the same name as a route".to_owned(),
+                "dynamic output `tenant_a` is declared more than once".to_owned(),
+                "cannot have a named output with reserved name: `_unmatched`".to_owned(),
+            ])
+        );
+    }
+
     #[tokio::test]
     async fn route_metrics_with_output_tag() {
         init_test();
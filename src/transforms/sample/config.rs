@@ -44,15 +44,40 @@ pub struct SampleConfig {
     #[configurable(metadata(docs::examples = "message"))]
     pub key_field: Option<String>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: SampleMode,
+
     /// A logical condition used to exclude events from sampling.
     pub exclude: Option<AnyCondition>,
 }
 
+/// Controls how the value of `key_field` determines whether an event is sampled.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleMode {
+    /// Samples the events whose hashed key is a multiple of `rate`.
+    #[default]
+    Modulo,
+
+    /// Samples events using [rendezvous hashing][rendezvous] of the key over `rate` buckets.
+    ///
+    /// An event is sampled when its key ranks the first bucket highest. Like `modulo`, the same
+    /// keys are sampled by every Vector instance, but the keys sampled at a given `rate` are also
+    /// sampled at any lower `rate`. This keeps traces or sessions whole when they are sampled
+    /// again further down a pipeline, or when the `rate` is changed.
+    ///
+    /// [rendezvous]: https://en.wikipedia.org/wiki/Rendezvous_hashing
+    Rendezvous,
+}
+
 impl GenerateConfig for SampleConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             rate: 10,
             key_field: None,
+            mode: SampleMode::default(),
             exclude: None::<AnyCondition>,
         })
         .unwrap()
@@ -63,15 +88,18 @@ impl GenerateConfig for SampleConfig {
 #[typetag::serde(name = "sample")]
 impl TransformConfig for SampleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Sample::new(
-            Self::NAME.to_string(),
-            self.rate,
-            self.key_field.clone(),
-            self.exclude
-                .as_ref()
-                .map(|condition| condition.build(&context.enrichment_tables))
-                .transpose()?,
-        )))
+        Ok(Transform::function(
+            Sample::new(
+                Self::NAME.to_string(),
+                self.rate,
+                self.key_field.clone(),
+                self.exclude
+                    .as_ref()
+                    .map(|condition| condition.build(&context.enrichment_tables))
+                    .transpose()?,
+            )
+            .with_rendezvous_hashing(self.mode == SampleMode::Rendezvous),
+        ))
     }
 
     fn input(&self) -> Input {
@@ -126,6 +154,7 @@ mod tests {
             let config = SampleConfig {
                 rate: 1,
                 key_field: None,
+                mode: SampleMode::default(),
                 exclude: None,
             };
             let (tx, rx) = mpsc::channel(1);
@@ -1,3 +1,5 @@
+use std::hash::Hasher;
+
 use vector_lib::config::LegacyKey;
 use vrl::event_path;
 
@@ -14,6 +16,7 @@ pub struct Sample {
     rate: u64,
     key_field: Option<String>,
     exclude: Option<Condition>,
+    rendezvous_hashing: bool,
     count: u64,
 }
 
@@ -32,9 +35,34 @@ impl Sample {
             rate,
             key_field,
             exclude,
+            rendezvous_hashing: false,
             count: 0,
         }
     }
+
+    /// Samples keyed events by rendezvous hashing of their key, rather than by their hash modulo
+    /// the rate.
+    pub const fn with_rendezvous_hashing(mut self, rendezvous_hashing: bool) -> Self {
+        self.rendezvous_hashing = rendezvous_hashing;
+        self
+    }
+}
+
+/// Returns whether the first of `rate` buckets ranks highest for `key`, which is the case for
+/// `1/rate` keys. Raising the rate only adds buckets which may outrank the first one, so the keys
+/// sampled at a rate are a subset of those sampled at any lower rate.
+fn rendezvous_sampled(key: &[u8], rate: u64) -> bool {
+    let weight = |bucket: u64| {
+        let mut hasher = seahash::SeaHasher::new();
+        hasher.write(key);
+        hasher.write_u64(bucket);
+        hasher.finish()
+    };
+
+    // Most keys are outranked after a few buckets, so only sampled keys are weighted against all
+    // of them.
+    let first = weight(0);
+    (1..rate).all(|bucket| weight(bucket) < first)
 }
 
 impl FunctionTransform for Sample {
@@ -69,15 +97,17 @@ impl FunctionTransform for Sample {
             })
             .map(|v| v.to_string_lossy());
 
-        let num = if let Some(value) = value {
-            seahash::hash(value.as_bytes())
-        } else {
-            self.count
+        let sampled = match value {
+            Some(value) if self.rendezvous_hashing => {
+                rendezvous_sampled(value.as_bytes(), self.rate)
+            }
+            Some(value) => seahash::hash(value.as_bytes()) % self.rate == 0,
+            None => self.count % self.rate == 0,
         };
 
         self.count = (self.count + 1) % self.rate;
 
-        if num % self.rate == 0 {
+        if sampled {
             match event {
                 Event::Log(ref mut event) => {
                     event.namespace().insert_source_metadata(
@@ -208,6 +238,40 @@ mod tests {
         assert_eq!(first_run, second_run);
     }
 
+    #[test]
+    fn rendezvous_samples_at_roughly_the_configured_rate() {
+        for rate in [2, 25] {
+            let num_events = 10000;
+            let events = random_events(num_events);
+            let mut sampler = Sample::new(
+                "sample".to_string(),
+                rate,
+                log_schema().message_key().map(ToString::to_string),
+                None,
+            )
+            .with_rendezvous_hashing(true);
+            let total_passed = events
+                .into_iter()
+                .filter_map(|event| transform_one(&mut sampler, event))
+                .count();
+            let ideal = 1.0f64 / rate as f64;
+            let actual = total_passed as f64 / num_events as f64;
+            assert_relative_eq!(ideal, actual, epsilon = ideal * 0.5);
+        }
+    }
+
+    #[test]
+    fn rendezvous_keys_sampled_at_higher_rates_are_sampled_at_lower_rates() {
+        let keys = random_lines(10).take(10000).collect::<Vec<_>>();
+        for rate in 2..20 {
+            for key in &keys {
+                if rendezvous_sampled(key.as_bytes(), rate + 1) {
+                    assert!(rendezvous_sampled(key.as_bytes(), rate));
+                }
+            }
+        }
+    }
+
     #[test]
     fn always_passes_events_matching_pass_list() {
         for key_field in &[None, log_schema().message_key().map(ToString::to_string)] {
@@ -0,0 +1,341 @@
+//! The `schema_enforce` transform. See [SchemaEnforceConfig].
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use snafu::{ResultExt, Snafu};
+use vector_lib::config::{clone_input_definitions, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::{owned_value_path, PathPrefix};
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, LogEvent},
+    internal_events::SchemaEnforceViolation,
+    schema,
+    transforms::{SyncTransform, Transform, TransformOutputsBuf},
+};
+
+mod validator;
+
+use self::validator::{Schema, SchemaError, Violation};
+
+const FAILED: &str = "failed";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("One of `schema` or `schema_file` must be provided"))]
+    SchemaMissing,
+    #[snafu(display("Only one of `schema` or `schema_file` can be provided"))]
+    SchemaConflict,
+    #[snafu(display("Could not read schema file {:?}: {}", path, source))]
+    ReadSchemaFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Invalid schema: {}", source))]
+    InvalidSchema { source: SchemaError },
+}
+
+/// Configuration for the `schema_enforce` transform.
+#[configurable_component(transform(
+    "schema_enforce",
+    "Validate events against a JSON Schema, routing the events violating it to a separate output."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaEnforceConfig {
+    /// The [JSON Schema][json_schema] to validate events against, as JSON.
+    ///
+    /// Required if `schema_file` is missing.
+    ///
+    /// [json_schema]: https://json-schema.org/draft/2020-12/json-schema-core
+    #[configurable(metadata(
+        docs::examples = r#"{"type": "object", "required": ["message"], "properties": {"status": {"type": "integer"}}}"#
+    ))]
+    schema: Option<String>,
+
+    /// The path of the file holding the JSON Schema to validate events against.
+    ///
+    /// Required if `schema` is missing.
+    #[configurable(metadata(docs::examples = "/etc/vector/schemas/app.json"))]
+    schema_file: Option<PathBuf>,
+
+    /// Converts values not matching the type required by the schema to it, where possible.
+    ///
+    /// For example, the string `"200"` is converted to the integer `200` where the schema requires
+    /// an integer, and the number `42` to the string `"42"` where it requires a string. Events are
+    /// only converted if they then match the schema, and are otherwise routed unchanged to the
+    /// `failed` output.
+    #[serde(default)]
+    coerce_types: bool,
+
+    /// The name of the field that events routed to the `failed` output are annotated under.
+    ///
+    /// The annotation lists the violations of the schema by the event, each with the location of
+    /// the violating value as a JSON pointer in `path`, the violated `keyword` of the schema, and a
+    /// `message` describing the violation.
+    ///
+    /// For logs using the `Legacy` log namespace, the annotation is placed under the global
+    /// [`log_schema.metadata_key`][global_metadata_key]. For logs using the `Vector` log namespace,
+    /// it's placed in the `vector` metadata namespace.
+    ///
+    /// [global_metadata_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.metadata_key
+    #[serde(default = "default_violations_metadata_key")]
+    #[configurable(metadata(docs::advanced))]
+    violations_metadata_key: String,
+}
+
+fn default_violations_metadata_key() -> String {
+    "schema_violations".to_owned()
+}
+
+impl GenerateConfig for SchemaEnforceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"schema = '{"type": "object", "required": ["message"]}'"#).unwrap()
+    }
+}
+
+impl SchemaEnforceConfig {
+    fn load_schema(&self) -> Result<Schema, BuildError> {
+        let json = match (&self.schema, &self.schema_file) {
+            (Some(schema), None) => schema.clone(),
+            (None, Some(path)) => read_schema_file(path)?,
+            (None, None) => return Err(BuildError::SchemaMissing),
+            (Some(_), Some(_)) => return Err(BuildError::SchemaConflict),
+        };
+        Schema::parse(&json).context(InvalidSchemaSnafu)
+    }
+}
+
+fn read_schema_file(path: &Path) -> Result<String, BuildError> {
+    std::fs::read_to_string(path).context(ReadSchemaFileSnafu { path })
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "schema_enforce")]
+impl TransformConfig for SchemaEnforceConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::synchronous(SchemaEnforce::new(self)?))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        vec![
+            TransformOutput::new(DataType::Log, clone_input_definitions(input_definitions)),
+            TransformOutput::new(DataType::Log, clone_input_definitions(input_definitions))
+                .with_port(FAILED),
+        ]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SchemaEnforce {
+    schema: Arc<Schema>,
+    coerce_types: bool,
+    violations_metadata_key: String,
+}
+
+impl SchemaEnforce {
+    fn new(config: &SchemaEnforceConfig) -> Result<Self, BuildError> {
+        Ok(Self {
+            schema: Arc::new(config.load_schema()?),
+            coerce_types: config.coerce_types,
+            violations_metadata_key: config.violations_metadata_key.clone(),
+        })
+    }
+
+    fn validate(&self, log: &mut LogEvent) -> Vec<Violation> {
+        if !self.coerce_types {
+            return self.schema.validate(log.value_mut(), false);
+        }
+
+        // The event is left unchanged when it doesn't match the schema even once coerced.
+        let mut value = log.value().clone();
+        let violations = self.schema.validate(&mut value, true);
+        if violations.is_empty() {
+            *log.value_mut() = value;
+        }
+        violations
+    }
+
+    fn annotate(&self, log: &mut LogEvent, violations: &[Violation]) {
+        let violations =
+            serde_json::Value::Array(violations.iter().map(Violation::to_json).collect());
+        match log.namespace() {
+            LogNamespace::Legacy => {
+                if let Some(metadata_key) = log_schema().metadata_key() {
+                    log.insert(
+                        (
+                            PathPrefix::Event,
+                            &metadata_key.with_field_appended(&self.violations_metadata_key),
+                        ),
+                        violations,
+                    );
+                }
+            }
+            LogNamespace::Vector => {
+                log.insert(
+                    (
+                        PathPrefix::Metadata,
+                        &owned_value_path!("vector")
+                            .with_field_appended(&self.violations_metadata_key),
+                    ),
+                    violations,
+                );
+            }
+        }
+    }
+}
+
+impl SyncTransform for SchemaEnforce {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let mut log = event.into_log();
+        let violations = self.validate(&mut log);
+        if violations.is_empty() {
+            output.push(None, Event::Log(log));
+            return;
+        }
+
+        emit!(SchemaEnforceViolation {
+            violations: violations.len(),
+            error: &violations[0].message,
+        });
+        self.annotate(&mut log, &violations);
+        output.push(Some(FAILED), Event::Log(log));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use vector_lib::lookup::event_path;
+    use vrl::value;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SchemaEnforceConfig>();
+    }
+
+    fn transform(config: &str, events: Vec<LogEvent>) -> TransformOutputsBuf {
+        let config = toml::from_str::<SchemaEnforceConfig>(config).unwrap();
+        let mut transform = SchemaEnforce::new(&config).unwrap();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                TransformOutput::new(DataType::Log, HashMap::new()),
+                TransformOutput::new(DataType::Log, HashMap::new()).with_port(FAILED),
+            ],
+            events.len(),
+        );
+        for event in events {
+            transform.transform(Event::Log(event), &mut outputs);
+        }
+        outputs
+    }
+
+    #[test]
+    fn routes_violations_to_failed_output() {
+        let config = r#"
+            schema = '''
+            {
+                "type": "object",
+                "required": ["message"],
+                "properties": { "status": { "type": "integer" } }
+            }
+            '''
+        "#;
+        let valid = LogEvent::from(value!({ "message": "hello", "status": 200 }));
+        let invalid = LogEvent::from(value!({ "status": "200" }));
+
+        let mut outputs = transform(config, vec![valid.clone(), invalid]);
+
+        let passed = outputs.drain().collect::<Vec<_>>();
+        assert_eq!(passed, vec![Event::Log(valid)]);
+        let failed = outputs.drain_named(FAILED).collect::<Vec<_>>();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(
+            failed[0]
+                .as_log()
+                .get(event_path!("metadata", "schema_violations")),
+            Some(&value!([
+                {
+                    "path": "",
+                    "keyword": "required",
+                    "message": "required property \"message\" is missing",
+                },
+                {
+                    "path": "/status",
+                    "keyword": "type",
+                    "message": "expected integer, got string",
+                },
+            ]))
+        );
+    }
+
+    #[test]
+    fn coerces_types() {
+        let config = r#"
+            schema = '{"properties": {"status": {"type": "integer"}}, "required": ["message"]}'
+            coerce_types = true
+        "#;
+        let coercible = LogEvent::from(value!({ "message": "hello", "status": "200" }));
+        let invalid = LogEvent::from(value!({ "status": "200" }));
+
+        let mut outputs = transform(config, vec![coercible, invalid]);
+
+        let passed = outputs.drain().collect::<Vec<_>>();
+        assert_eq!(
+            passed,
+            vec![Event::Log(LogEvent::from(value!({
+                "message": "hello",
+                "status": 200,
+            })))]
+        );
+        let failed = outputs.drain_named(FAILED).collect::<Vec<_>>();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(
+            failed[0].as_log().get(event_path!("status")),
+            Some(&value!("200"))
+        );
+    }
+
+    #[test]
+    fn requires_a_single_schema() {
+        let config = toml::from_str::<SchemaEnforceConfig>("coerce_types = true").unwrap();
+        assert!(matches!(
+            config.load_schema(),
+            Err(BuildError::SchemaMissing)
+        ));
+
+        let config = toml::from_str::<SchemaEnforceConfig>(
+            r#"
+            schema = "true"
+            schema_file = "/etc/vector/schemas/app.json"
+        "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            config.load_schema(),
+            Err(BuildError::SchemaConflict)
+        ));
+    }
+}
@@ -0,0 +1,988 @@
+//! A validator for the subset of JSON Schema (draft 2020-12) supported by the `schema_enforce`
+//! transform.
+//!
+//! Keywords outside of this subset are ignored, as the specification requires of unknown
+//! keywords, except for references, which are rejected unless they point to the root of the
+//! schema or to one of its `$defs`.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use ordered_float::NotNan;
+use regex::Regex;
+use serde_json::{Map, Value as JsonValue};
+use snafu::{ResultExt, Snafu};
+use vrl::value::Value;
+
+#[derive(Debug, Snafu)]
+pub enum SchemaError {
+    #[snafu(display("Invalid JSON in schema: {}", source))]
+    InvalidJson { source: serde_json::Error },
+    #[snafu(display("The schema must be an object or a boolean"))]
+    InvalidSchema,
+    #[snafu(display("Invalid value for schema keyword `{}`", keyword))]
+    InvalidKeyword { keyword: String },
+    #[snafu(display("Invalid pattern {:?} in schema: {}", pattern, source))]
+    InvalidPattern {
+        pattern: String,
+        source: regex::Error,
+    },
+    #[snafu(display(
+        "Unsupported reference {:?}, only references to `#` and `#/$defs/<name>` are supported",
+        reference
+    ))]
+    UnsupportedReference { reference: String },
+    #[snafu(display("Reference to undefined schema {:?}", reference))]
+    UndefinedReference { reference: String },
+}
+
+/// A violation of the schema by an event.
+#[derive(Debug, PartialEq)]
+pub(super) struct Violation {
+    /// The location of the violating value, as a JSON pointer.
+    pub(super) path: String,
+    /// The keyword of the schema violated.
+    pub(super) keyword: &'static str,
+    pub(super) message: String,
+}
+
+impl Violation {
+    pub(super) fn to_json(&self) -> JsonValue {
+        serde_json::json!({
+            "path": self.path,
+            "keyword": self.keyword,
+            "message": self.message,
+        })
+    }
+}
+
+/// A compiled JSON Schema.
+#[derive(Debug)]
+pub(super) struct Schema {
+    root: Node,
+    definitions: HashMap<String, Node>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Boolean(bool),
+    Keywords(Box<Keywords>),
+}
+
+#[derive(Debug)]
+enum Reference {
+    Root,
+    Definition(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Type {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl Type {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "null" => Self::Null,
+            "boolean" => Self::Boolean,
+            "integer" => Self::Integer,
+            "number" => Self::Number,
+            "string" => Self::String,
+            "array" => Self::Array,
+            "object" => Self::Object,
+            _ => return None,
+        })
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+        }
+    }
+
+    fn matches(self, value: &Value) -> bool {
+        match (self, value) {
+            (Self::Null, Value::Null) => true,
+            (Self::Boolean, Value::Boolean(_)) => true,
+            (Self::Integer, Value::Integer(_)) => true,
+            (Self::Integer, Value::Float(float)) => float.fract() == 0.0,
+            (Self::Number, Value::Integer(_) | Value::Float(_)) => true,
+            // Timestamps and regular expressions are encoded as strings.
+            (Self::String, Value::Bytes(_) | Value::Timestamp(_) | Value::Regex(_)) => true,
+            (Self::Array, Value::Array(_)) => true,
+            (Self::Object, Value::Object(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Converts the value to this type, if it has an unambiguous representation in it.
+    fn coerce(self, value: &Value) -> Option<Value> {
+        match (self, value) {
+            (Self::Boolean, Value::Bytes(bytes)) => match bytes.as_ref() {
+                b"true" => Some(Value::Boolean(true)),
+                b"false" => Some(Value::Boolean(false)),
+                _ => None,
+            },
+            (Self::Integer, Value::Bytes(bytes)) => std::str::from_utf8(bytes)
+                .ok()?
+                .parse::<i64>()
+                .ok()
+                .map(Value::Integer),
+            (Self::Number, Value::Bytes(bytes)) => {
+                let string = std::str::from_utf8(bytes).ok()?;
+                match string.parse::<i64>() {
+                    Ok(integer) => Some(Value::Integer(integer)),
+                    Err(_) => string
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|float| float.is_finite())
+                        .and_then(|float| NotNan::new(float).ok())
+                        .map(Value::Float),
+                }
+            }
+            (Self::String, Value::Integer(_) | Value::Float(_) | Value::Boolean(_)) => {
+                Some(Value::from(value.to_string_lossy().into_owned()))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Keywords {
+    reference: Option<Reference>,
+    types: Vec<Type>,
+    enumeration: Option<Vec<Value>>,
+    constant: Option<Value>,
+
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    exclusive_minimum: Option<f64>,
+    exclusive_maximum: Option<f64>,
+    multiple_of: Option<f64>,
+
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    pattern: Option<Regex>,
+
+    prefix_items: Vec<Node>,
+    items: Option<Node>,
+    contains: Option<Node>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    unique_items: bool,
+
+    properties: Vec<(String, Node)>,
+    pattern_properties: Vec<(Regex, Node)>,
+    additional_properties: Option<Node>,
+    required: Vec<String>,
+    dependent_required: Vec<(String, Vec<String>)>,
+    min_properties: Option<usize>,
+    max_properties: Option<usize>,
+
+    all_of: Vec<Node>,
+    any_of: Vec<Node>,
+    one_of: Vec<Node>,
+    not: Option<Node>,
+    if_then_else: Option<(Node, Option<Node>, Option<Node>)>,
+}
+
+/// The location of a value in the event, only rendered on violations.
+#[derive(Clone, Copy)]
+enum Location<'a> {
+    Root,
+    Property(&'a Location<'a>, &'a str),
+    Index(&'a Location<'a>, usize),
+}
+
+impl fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Root => Ok(()),
+            Self::Property(parent, name) => {
+                write!(f, "{parent}/{}", name.replace('~', "~0").replace('/', "~1"))
+            }
+            Self::Index(parent, index) => write!(f, "{parent}/{index}"),
+        }
+    }
+}
+
+impl Schema {
+    pub(super) fn parse(json: &str) -> Result<Self, SchemaError> {
+        let value: JsonValue = serde_json::from_str(json).context(InvalidJsonSnafu)?;
+        Self::compile(&value)
+    }
+
+    pub(super) fn compile(value: &JsonValue) -> Result<Self, SchemaError> {
+        let mut compiler = Compiler::default();
+        let root = compiler.compile(value)?;
+        let definitions = match value.get("$defs") {
+            Some(JsonValue::Object(definitions)) => definitions
+                .iter()
+                .map(|(name, definition)| Ok((name.clone(), compiler.compile(definition)?)))
+                .collect::<Result<_, SchemaError>>()?,
+            Some(_) => return Err(invalid("$defs")),
+            None => HashMap::new(),
+        };
+
+        for name in compiler.referenced_definitions {
+            if !definitions.contains_key(&name) {
+                return Err(SchemaError::UndefinedReference {
+                    reference: format!("#/$defs/{name}"),
+                });
+            }
+        }
+
+        Ok(Self { root, definitions })
+    }
+
+    /// Validates the value against the schema, returning the violations found.
+    ///
+    /// When `coerce` is set, values not matching the `type` of their schema are converted to it
+    /// where possible, for example numeric strings to numbers. Values are only coerced by the
+    /// schemas they must match, that is not by the subschemas of `anyOf`, `oneOf`, `not`, or `if`.
+    pub(super) fn validate(&self, value: &mut Value, coerce: bool) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        self.validate_node(&self.root, value, coerce, Location::Root, &mut violations);
+        violations
+    }
+
+    fn is_valid(&self, node: &Node, value: &mut Value) -> bool {
+        let mut violations = Vec::new();
+        self.validate_node(node, value, false, Location::Root, &mut violations);
+        violations.is_empty()
+    }
+
+    fn validate_node(
+        &self,
+        node: &Node,
+        value: &mut Value,
+        coerce: bool,
+        location: Location<'_>,
+        violations: &mut Vec<Violation>,
+    ) {
+        let keywords = match node {
+            Node::Boolean(true) => return,
+            Node::Boolean(false) => {
+                violations.push(violation(
+                    location,
+                    "false",
+                    "no value is allowed".to_owned(),
+                ));
+                return;
+            }
+            Node::Keywords(keywords) => keywords,
+        };
+        let mut report = |keyword, message| violations.push(violation(location, keyword, message));
+
+        if !keywords.types.is_empty() && !keywords.types.iter().any(|ty| ty.matches(value)) {
+            let coerced = coerce
+                .then(|| keywords.types.iter().find_map(|ty| ty.coerce(value)))
+                .flatten();
+            match coerced {
+                Some(coerced) => *value = coerced,
+                None => {
+                    let expected = keywords
+                        .types
+                        .iter()
+                        .map(|ty| ty.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" or ");
+                    report(
+                        "type",
+                        format!("expected {expected}, got {}", value.kind_str()),
+                    );
+                }
+            }
+        }
+
+        if let Some(allowed) = &keywords.enumeration {
+            if !allowed.iter().any(|allowed| equals(allowed, value)) {
+                report("enum", "value is not one of the allowed values".to_owned());
+            }
+        }
+        if let Some(constant) = &keywords.constant {
+            if !equals(constant, value) {
+                report("const", "value is not the expected constant".to_owned());
+            }
+        }
+
+        if let Some(number) = as_f64(value) {
+            validate_number(keywords, number, &mut report);
+        }
+
+        if let Value::Bytes(bytes) = &*value {
+            let string = String::from_utf8_lossy(bytes);
+            let length = string.chars().count();
+            if let Some(min_length) = keywords.min_length.filter(|min| length < *min) {
+                report(
+                    "minLength",
+                    format!("string is shorter than {min_length} characters"),
+                );
+            }
+            if let Some(max_length) = keywords.max_length.filter(|max| length > *max) {
+                report(
+                    "maxLength",
+                    format!("string is longer than {max_length} characters"),
+                );
+            }
+            if let Some(pattern) = &keywords.pattern {
+                if !pattern.is_match(&string) {
+                    report(
+                        "pattern",
+                        format!("string does not match pattern {:?}", pattern.as_str()),
+                    );
+                }
+            }
+        }
+
+        match value {
+            Value::Array(array) => {
+                self.validate_array(keywords, array, coerce, location, violations)
+            }
+            Value::Object(object) => {
+                self.validate_object(keywords, object, coerce, location, violations)
+            }
+            _ => {}
+        }
+
+        self.validate_applicators(keywords, value, coerce, location, violations);
+    }
+
+    fn validate_array(
+        &self,
+        keywords: &Keywords,
+        array: &mut [Value],
+        coerce: bool,
+        location: Location<'_>,
+        violations: &mut Vec<Violation>,
+    ) {
+        if let Some(min_items) = keywords.min_items.filter(|min| array.len() < *min) {
+            violations.push(violation(
+                location,
+                "minItems",
+                format!("array has fewer than {min_items} items"),
+            ));
+        }
+        if let Some(max_items) = keywords.max_items.filter(|max| array.len() > *max) {
+            violations.push(violation(
+                location,
+                "maxItems",
+                format!("array has more than {max_items} items"),
+            ));
+        }
+        if keywords.unique_items
+            && array
+                .iter()
+                .enumerate()
+                .any(|(index, item)| array[..index].iter().any(|other| equals(other, item)))
+        {
+            violations.push(violation(
+                location,
+                "uniqueItems",
+                "array items are not unique".to_owned(),
+            ));
+        }
+
+        for (index, item) in array.iter_mut().enumerate() {
+            let node = keywords.prefix_items.get(index).or(keywords.items.as_ref());
+            if let Some(node) = node {
+                let location = Location::Index(&location, index);
+                self.validate_node(node, item, coerce, location, violations);
+            }
+        }
+
+        if let Some(contains) = &keywords.contains {
+            if !array.iter_mut().any(|item| self.is_valid(contains, item)) {
+                violations.push(violation(
+                    location,
+                    "contains",
+                    "array does not contain a matching item".to_owned(),
+                ));
+            }
+        }
+    }
+
+    fn validate_object(
+        &self,
+        keywords: &Keywords,
+        object: &mut vrl::value::ObjectMap,
+        coerce: bool,
+        location: Location<'_>,
+        violations: &mut Vec<Violation>,
+    ) {
+        for name in &keywords.required {
+            if !object.contains_key(name.as_str()) {
+                violations.push(violation(
+                    location,
+                    "required",
+                    format!("required property {name:?} is missing"),
+                ));
+            }
+        }
+        for (name, dependencies) in &keywords.dependent_required {
+            if object.contains_key(name.as_str()) {
+                for dependency in dependencies {
+                    if !object.contains_key(dependency.as_str()) {
+                        violations.push(violation(
+                            location,
+                            "dependentRequired",
+                            format!("property {name:?} requires property {dependency:?}"),
+                        ));
+                    }
+                }
+            }
+        }
+        if let Some(min_properties) = keywords.min_properties.filter(|min| object.len() < *min) {
+            violations.push(violation(
+                location,
+                "minProperties",
+                format!("object has fewer than {min_properties} properties"),
+            ));
+        }
+        if let Some(max_properties) = keywords.max_properties.filter(|max| object.len() > *max) {
+            violations.push(violation(
+                location,
+                "maxProperties",
+                format!("object has more than {max_properties} properties"),
+            ));
+        }
+
+        for (name, value) in object.iter_mut() {
+            let name = name.as_str();
+            let location = Location::Property(&location, name);
+            let mut evaluated = false;
+            if let Some((_, node)) = keywords
+                .properties
+                .iter()
+                .find(|(property, _)| property == name)
+            {
+                self.validate_node(node, value, coerce, location, violations);
+                evaluated = true;
+            }
+            for (pattern, node) in &keywords.pattern_properties {
+                if pattern.is_match(name) {
+                    self.validate_node(node, value, coerce, location, violations);
+                    evaluated = true;
+                }
+            }
+            if !evaluated {
+                match &keywords.additional_properties {
+                    Some(Node::Boolean(false)) => violations.push(violation(
+                        location,
+                        "additionalProperties",
+                        format!("property {name:?} is not allowed"),
+                    )),
+                    Some(node) => self.validate_node(node, value, coerce, location, violations),
+                    None => {}
+                }
+            }
+        }
+    }
+
+    fn validate_applicators(
+        &self,
+        keywords: &Keywords,
+        value: &mut Value,
+        coerce: bool,
+        location: Location<'_>,
+        violations: &mut Vec<Violation>,
+    ) {
+        if let Some(reference) = &keywords.reference {
+            let node = match reference {
+                Reference::Root => &self.root,
+                Reference::Definition(name) => &self.definitions[name],
+            };
+            self.validate_node(node, value, coerce, location, violations);
+        }
+
+        for node in &keywords.all_of {
+            self.validate_node(node, value, coerce, location, violations);
+        }
+        if !keywords.any_of.is_empty()
+            && !keywords
+                .any_of
+                .iter()
+                .any(|node| self.is_valid(node, value))
+        {
+            violations.push(violation(
+                location,
+                "anyOf",
+                "value does not match any of the schemas".to_owned(),
+            ));
+        }
+        if !keywords.one_of.is_empty() {
+            let matches = keywords
+                .one_of
+                .iter()
+                .filter(|node| self.is_valid(node, value))
+                .count();
+            if matches != 1 {
+                violations.push(violation(
+                    location,
+                    "oneOf",
+                    format!("value matches {matches} of the schemas, instead of exactly one"),
+                ));
+            }
+        }
+        if let Some(not) = &keywords.not {
+            if self.is_valid(not, value) {
+                violations.push(violation(
+                    location,
+                    "not",
+                    "value matches a schema it must not match".to_owned(),
+                ));
+            }
+        }
+        if let Some((condition, then, otherwise)) = &keywords.if_then_else {
+            let node = if self.is_valid(condition, value) {
+                then
+            } else {
+                otherwise
+            };
+            if let Some(node) = node {
+                self.validate_node(node, value, coerce, location, violations);
+            }
+        }
+    }
+}
+
+fn validate_number(
+    keywords: &Keywords,
+    number: f64,
+    report: &mut impl FnMut(&'static str, String),
+) {
+    if let Some(minimum) = keywords.minimum.filter(|minimum| number < *minimum) {
+        report("minimum", format!("{number} is less than {minimum}"));
+    }
+    if let Some(maximum) = keywords.maximum.filter(|maximum| number > *maximum) {
+        report("maximum", format!("{number} is greater than {maximum}"));
+    }
+    if let Some(minimum) = keywords
+        .exclusive_minimum
+        .filter(|minimum| number <= *minimum)
+    {
+        report(
+            "exclusiveMinimum",
+            format!("{number} is not greater than {minimum}"),
+        );
+    }
+    if let Some(maximum) = keywords
+        .exclusive_maximum
+        .filter(|maximum| number >= *maximum)
+    {
+        report(
+            "exclusiveMaximum",
+            format!("{number} is not less than {maximum}"),
+        );
+    }
+    if let Some(divisor) = keywords.multiple_of {
+        if (number / divisor).fract() != 0.0 {
+            report(
+                "multipleOf",
+                format!("{number} is not a multiple of {divisor}"),
+            );
+        }
+    }
+}
+
+fn violation(location: Location<'_>, keyword: &'static str, message: String) -> Violation {
+    Violation {
+        path: location.to_string(),
+        keyword,
+        message,
+    }
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(integer) => Some(*integer as f64),
+        Value::Float(float) => Some(float.into_inner()),
+        _ => None,
+    }
+}
+
+/// Compares values the way JSON Schema does, that is numbers by their mathematical value.
+fn equals(expected: &Value, value: &Value) -> bool {
+    match (expected, value) {
+        (Value::Integer(a), Value::Integer(b)) => a == b,
+        (Value::Integer(_) | Value::Float(_), Value::Integer(_) | Value::Float(_)) => {
+            as_f64(expected) == as_f64(value)
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| equals(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| equals(a, b)))
+        }
+        _ => expected == value,
+    }
+}
+
+fn invalid(keyword: &str) -> SchemaError {
+    SchemaError::InvalidKeyword {
+        keyword: keyword.to_owned(),
+    }
+}
+
+#[derive(Default)]
+struct Compiler {
+    referenced_definitions: Vec<String>,
+}
+
+impl Compiler {
+    fn compile(&mut self, value: &JsonValue) -> Result<Node, SchemaError> {
+        let object = match value {
+            JsonValue::Bool(boolean) => return Ok(Node::Boolean(*boolean)),
+            JsonValue::Object(object) => object,
+            _ => return Err(SchemaError::InvalidSchema),
+        };
+
+        let mut keywords = Keywords {
+            reference: object
+                .get("$ref")
+                .map(|reference| self.reference(reference))
+                .transpose()?,
+            types: match object.get("type") {
+                None => Vec::new(),
+                Some(JsonValue::Array(types)) => types
+                    .iter()
+                    .map(|ty| {
+                        ty.as_str()
+                            .and_then(Type::parse)
+                            .ok_or_else(|| invalid("type"))
+                    })
+                    .collect::<Result<_, _>>()?,
+                Some(ty) => vec![ty
+                    .as_str()
+                    .and_then(Type::parse)
+                    .ok_or_else(|| invalid("type"))?],
+            },
+            enumeration: match object.get("enum") {
+                None => None,
+                Some(JsonValue::Array(values)) => {
+                    Some(values.iter().cloned().map(Value::from).collect())
+                }
+                Some(_) => return Err(invalid("enum")),
+            },
+            constant: object.get("const").cloned().map(Value::from),
+
+            minimum: number(object, "minimum")?,
+            maximum: number(object, "maximum")?,
+            exclusive_minimum: number(object, "exclusiveMinimum")?,
+            exclusive_maximum: number(object, "exclusiveMaximum")?,
+            multiple_of: number(object, "multipleOf")?,
+
+            min_length: count(object, "minLength")?,
+            max_length: count(object, "maxLength")?,
+            pattern: object
+                .get("pattern")
+                .map(|pattern| regex(pattern.as_str().ok_or_else(|| invalid("pattern"))?))
+                .transpose()?,
+
+            prefix_items: self.nodes(object, "prefixItems")?,
+            items: self.node(object, "items")?,
+            contains: self.node(object, "contains")?,
+            min_items: count(object, "minItems")?,
+            max_items: count(object, "maxItems")?,
+            unique_items: match object.get("uniqueItems") {
+                None => false,
+                Some(unique) => unique.as_bool().ok_or_else(|| invalid("uniqueItems"))?,
+            },
+
+            additional_properties: self.node(object, "additionalProperties")?,
+            required: strings(object.get("required"), "required")?,
+            min_properties: count(object, "minProperties")?,
+            max_properties: count(object, "maxProperties")?,
+
+            all_of: self.nodes(object, "allOf")?,
+            any_of: self.nodes(object, "anyOf")?,
+            one_of: self.nodes(object, "oneOf")?,
+            not: self.node(object, "not")?,
+            ..Default::default()
+        };
+
+        if let Some(properties) = map(object, "properties")? {
+            keywords.properties = properties
+                .iter()
+                .map(|(name, node)| Ok((name.clone(), self.compile(node)?)))
+                .collect::<Result<_, SchemaError>>()?;
+        }
+        if let Some(properties) = map(object, "patternProperties")? {
+            keywords.pattern_properties = properties
+                .iter()
+                .map(|(pattern, node)| Ok((regex(pattern)?, self.compile(node)?)))
+                .collect::<Result<_, SchemaError>>()?;
+        }
+        if let Some(dependencies) = map(object, "dependentRequired")? {
+            keywords.dependent_required = dependencies
+                .iter()
+                .map(|(name, required)| {
+                    Ok((name.clone(), strings(Some(required), "dependentRequired")?))
+                })
+                .collect::<Result<_, SchemaError>>()?;
+        }
+        if let Some(condition) = self.node(object, "if")? {
+            keywords.if_then_else = Some((
+                condition,
+                self.node(object, "then")?,
+                self.node(object, "else")?,
+            ));
+        }
+
+        Ok(Node::Keywords(Box::new(keywords)))
+    }
+
+    fn reference(&mut self, reference: &JsonValue) -> Result<Reference, SchemaError> {
+        let reference = reference.as_str().ok_or_else(|| invalid("$ref"))?;
+        if reference == "#" {
+            return Ok(Reference::Root);
+        }
+        match reference.strip_prefix("#/$defs/") {
+            Some(name) if !name.contains('/') => {
+                let name = name.replace("~1", "/").replace("~0", "~");
+                self.referenced_definitions.push(name.clone());
+                Ok(Reference::Definition(name))
+            }
+            _ => Err(SchemaError::UnsupportedReference {
+                reference: reference.to_owned(),
+            }),
+        }
+    }
+
+    fn node(
+        &mut self,
+        object: &Map<String, JsonValue>,
+        keyword: &str,
+    ) -> Result<Option<Node>, SchemaError> {
+        object
+            .get(keyword)
+            .map(|node| self.compile(node))
+            .transpose()
+    }
+
+    fn nodes(
+        &mut self,
+        object: &Map<String, JsonValue>,
+        keyword: &str,
+    ) -> Result<Vec<Node>, SchemaError> {
+        match object.get(keyword) {
+            None => Ok(Vec::new()),
+            Some(JsonValue::Array(nodes)) => nodes.iter().map(|node| self.compile(node)).collect(),
+            Some(_) => Err(invalid(keyword)),
+        }
+    }
+}
+
+fn map<'a>(
+    object: &'a Map<String, JsonValue>,
+    keyword: &str,
+) -> Result<Option<&'a Map<String, JsonValue>>, SchemaError> {
+    match object.get(keyword) {
+        None => Ok(None),
+        Some(JsonValue::Object(map)) => Ok(Some(map)),
+        Some(_) => Err(invalid(keyword)),
+    }
+}
+
+fn number(object: &Map<String, JsonValue>, keyword: &str) -> Result<Option<f64>, SchemaError> {
+    object
+        .get(keyword)
+        .map(|number| number.as_f64().ok_or_else(|| invalid(keyword)))
+        .transpose()
+}
+
+fn count(object: &Map<String, JsonValue>, keyword: &str) -> Result<Option<usize>, SchemaError> {
+    object
+        .get(keyword)
+        .map(|count| {
+            count
+                .as_u64()
+                .and_then(|count| usize::try_from(count).ok())
+                .ok_or_else(|| invalid(keyword))
+        })
+        .transpose()
+}
+
+fn strings(value: Option<&JsonValue>, keyword: &str) -> Result<Vec<String>, SchemaError> {
+    match value {
+        None => Ok(Vec::new()),
+        Some(JsonValue::Array(strings)) => strings
+            .iter()
+            .map(|string| {
+                string
+                    .as_str()
+                    .map(ToOwned::to_owned)
+                    .ok_or_else(|| invalid(keyword))
+            })
+            .collect(),
+        Some(_) => Err(invalid(keyword)),
+    }
+}
+
+fn regex(pattern: &str) -> Result<Regex, SchemaError> {
+    Regex::new(pattern).context(InvalidPatternSnafu { pattern })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use vrl::value;
+
+    use super::*;
+
+    fn violations(
+        schema: JsonValue,
+        mut value: Value,
+        coerce: bool,
+    ) -> Vec<(String, &'static str)> {
+        Schema::compile(&schema)
+            .unwrap()
+            .validate(&mut value, coerce)
+            .into_iter()
+            .map(|violation| (violation.path, violation.keyword))
+            .collect()
+    }
+
+    #[test]
+    fn validates_objects() {
+        let schema = json!({
+            "type": "object",
+            "required": ["id", "message"],
+            "properties": {
+                "id": { "type": "integer", "minimum": 1 },
+                "message": { "type": "string", "maxLength": 5 },
+                "tags": { "type": "array", "items": { "type": "string" } },
+            },
+            "additionalProperties": false,
+        });
+
+        assert!(violations(
+            schema.clone(),
+            value!({ "id": 1, "message": "hello", "tags": ["a"] }),
+            false
+        )
+        .is_empty());
+        assert_eq!(
+            violations(
+                schema,
+                value!({ "id": 0, "tags": ["a", 1], "extra/field": true }),
+                false
+            ),
+            vec![
+                (String::new(), "required"),
+                ("/extra~1field".to_owned(), "additionalProperties"),
+                ("/id".to_owned(), "minimum"),
+                ("/tags/1".to_owned(), "type"),
+            ]
+        );
+    }
+
+    #[test]
+    fn validates_applicators_and_references() {
+        let schema = json!({
+            "$defs": {
+                "level": { "enum": ["info", "error"] },
+            },
+            "properties": {
+                "level": { "$ref": "#/$defs/level" },
+                "code": { "oneOf": [{ "type": "integer" }, { "type": "number" }] },
+                "status": { "anyOf": [{ "const": 200 }, { "const": "ok" }] },
+            },
+        });
+
+        assert!(violations(
+            schema.clone(),
+            value!({ "level": "info", "code": 1.5, "status": 200.0 }),
+            false
+        )
+        .is_empty());
+        assert_eq!(
+            violations(
+                schema,
+                value!({ "level": "debug", "code": 1, "status": 404 }),
+                false
+            ),
+            vec![
+                ("/code".to_owned(), "oneOf"),
+                ("/level".to_owned(), "enum"),
+                ("/status".to_owned(), "anyOf"),
+            ]
+        );
+    }
+
+    #[test]
+    fn coerces_types() {
+        let schema = Schema::compile(&json!({
+            "properties": {
+                "status": { "type": "integer" },
+                "ratio": { "type": "number" },
+                "enabled": { "type": "boolean" },
+                "id": { "type": "string" },
+                "name": { "type": "integer" },
+            },
+        }))
+        .unwrap();
+        let mut value = value!({
+            "status": "200",
+            "ratio": "0.5",
+            "enabled": "true",
+            "id": 42,
+            "name": "vector",
+        });
+
+        let violations = schema.validate(&mut value, true);
+        assert_eq!(
+            violations,
+            vec![Violation {
+                path: "/name".to_owned(),
+                keyword: "type",
+                message: "expected integer, got string".to_owned(),
+            }]
+        );
+        assert_eq!(
+            value,
+            value!({
+                "status": 200,
+                "ratio": 0.5,
+                "enabled": true,
+                "id": "42",
+                "name": "vector",
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_schemas() {
+        assert!(matches!(
+            Schema::compile(&json!({ "type": "text" })),
+            Err(SchemaError::InvalidKeyword { .. })
+        ));
+        assert!(matches!(
+            Schema::compile(&json!({ "pattern": "(" })),
+            Err(SchemaError::InvalidPattern { .. })
+        ));
+        assert!(matches!(
+            Schema::compile(&json!({ "$ref": "https://example.com/schema.json" })),
+            Err(SchemaError::UnsupportedReference { .. })
+        ));
+        assert!(matches!(
+            Schema::compile(&json!({ "$ref": "#/$defs/missing" })),
+            Err(SchemaError::UndefinedReference { .. })
+        ));
+    }
+}
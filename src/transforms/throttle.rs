@@ -1,4 +1,4 @@
-use std::{num::NonZeroU32, pin::Pin, time::Duration};
+use std::{collections::HashMap, num::NonZeroU32, pin::Pin, time::Duration};
 
 use async_stream::stream;
 use futures::{Stream, StreamExt};
@@ -7,12 +7,18 @@ use serde_with::serde_as;
 use snafu::Snafu;
 use vector_lib::config::{clone_input_definitions, LogNamespace};
 use vector_lib::configurable::configurable_component;
+use vector_lib::{compile_vrl, TimeZone};
+use vrl::compiler::{runtime::Runtime, CompilationResult, CompileConfig, Program, TypeState};
+use vrl::diagnostic::Formatter;
+use vrl::value::Value;
 
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
-    event::Event,
-    internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
+    event::{Event, TargetEvents, VrlTarget},
+    internal_events::{
+        TemplateRenderingError, ThrottleEventDiscarded, ThrottleThresholdResolveError,
+    },
     schema,
     template::Template,
     transforms::{TaskTransform, Transform},
@@ -44,9 +50,26 @@ pub struct ThrottleInternalMetricsConfig {
 pub struct ThrottleConfig {
     /// The number of events allowed for a given bucket per configured `window_secs`.
     ///
-    /// Each unique key has its own `threshold`.
+    /// Each unique key has its own `threshold`. When `threshold_expression` is set, this is only
+    /// used for the events for which the expression fails to resolve a threshold.
     threshold: u32,
 
+    /// A [Vector Remap Language][vrl] (VRL) expression resolving the `threshold` of each event.
+    ///
+    /// This allows the rate limit to be set per key, for example by looking up the quota of a
+    /// tenant in an enrichment table. The expression must resolve to a positive integer, or the
+    /// static `threshold` is used instead. The expression cannot modify the event.
+    ///
+    /// Keys are rate limited independently for each distinct threshold, so a key whose threshold
+    /// changes starts with a full bucket.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[configurable(metadata(
+        docs::examples = "to_int(.quota) ?? 100",
+        docs::examples = "get_enrichment_table_record!(\"quotas\", {\"tenant\": .tenant}).limit",
+    ))]
+    threshold_expression: Option<String>,
+
     /// The time window in which the configured `threshold` is applied, in seconds.
     #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
     #[configurable(metadata(docs::human_name = "Time Window"))]
@@ -94,9 +117,89 @@ impl TransformConfig for ThrottleConfig {
     }
 }
 
+fn quota(window: Duration, threshold: NonZeroU32) -> Option<Quota> {
+    Quota::with_period(Duration::from_secs_f64(
+        window.as_secs_f64() / f64::from(threshold.get()),
+    ))
+    .map(|quota| quota.allow_burst(threshold))
+}
+
+/// A VRL expression resolving the threshold of each event.
+#[derive(Clone, Debug)]
+struct ThresholdExpression {
+    program: Program,
+    source: String,
+}
+
+impl ThresholdExpression {
+    fn build(
+        source: &str,
+        enrichment_tables: &vector_lib::enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        let functions = vrl::stdlib::all()
+            .into_iter()
+            .chain(vector_lib::enrichment::vrl_functions())
+            .chain(vector_vrl_functions::all())
+            .collect::<Vec<_>>();
+
+        let mut config = CompileConfig::default();
+        config.set_custom(enrichment_tables.clone());
+        config.set_read_only();
+
+        let CompilationResult {
+            program,
+            warnings,
+            config: _,
+        } = compile_vrl(source, &functions, &TypeState::default(), config)
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+        if !program.final_type_info().result.contains_integer() {
+            return Err("`threshold_expression` must resolve to an integer.".into());
+        }
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(source, warnings).colored().to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(Self {
+            program,
+            source: source.to_owned(),
+        })
+    }
+
+    fn resolve(&self, event: Event) -> (Result<NonZeroU32, String>, Event) {
+        let log_namespace = event
+            .maybe_as_log()
+            .map(|log| log.namespace())
+            .unwrap_or(LogNamespace::Legacy);
+        let mut target = VrlTarget::new(event, self.program.info(), false);
+
+        let result = Runtime::default()
+            .resolve(&mut target, &self.program, &TimeZone::default())
+            .map_err(|error| error.to_string())
+            .and_then(|value| match value {
+                Value::Integer(threshold) => u32::try_from(threshold)
+                    .ok()
+                    .and_then(NonZeroU32::new)
+                    .ok_or_else(|| format!("threshold {threshold} is not a positive integer")),
+                value => Err(format!("threshold {value} is not an integer")),
+            });
+        let event = match target.into_events(log_namespace) {
+            TargetEvents::One(event) => event,
+            _ => panic!(
+                "Event was modified by `threshold_expression` ({}). This is an internal compiler error.",
+                self.source
+            ),
+        };
+        (result, event)
+    }
+}
+
 #[derive(Clone)]
 pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
-    quota: Quota,
+    threshold: NonZeroU32,
+    threshold_expression: Option<ThresholdExpression>,
     flush_keys_interval: Duration,
     key_field: Option<Template>,
     exclude: Option<Condition>,
@@ -109,6 +212,27 @@ where
     C: clock::Clock<Instant = I>,
     I: clock::Reference,
 {
+    /// Resolves the threshold of the event, falling back to the static threshold if the expression
+    /// fails or resolves to a threshold which can't be used over the configured window.
+    fn resolve_threshold(&self, event: Event) -> (NonZeroU32, Event) {
+        let Some(expression) = self.threshold_expression.as_ref() else {
+            return (self.threshold, event);
+        };
+
+        let (result, event) = expression.resolve(event);
+        let threshold = result
+            .and_then(|threshold| {
+                quota(self.flush_keys_interval, threshold)
+                    .map(|_| threshold)
+                    .ok_or_else(|| format!("threshold {threshold} is too large for `window_secs`"))
+            })
+            .unwrap_or_else(|error| {
+                emit!(ThrottleThresholdResolveError { error });
+                self.threshold
+            });
+        (threshold, event)
+    }
+
     pub fn new(
         config: &ThrottleConfig,
         context: &TransformContext,
@@ -121,12 +245,14 @@ where
             None => return Err(Box::new(ConfigError::NonZero)),
         };
 
-        let quota = match Quota::with_period(Duration::from_secs_f64(
-            flush_keys_interval.as_secs_f64() / f64::from(threshold.get()),
-        )) {
-            Some(quota) => quota.allow_burst(threshold),
-            None => return Err(Box::new(ConfigError::NonZero)),
-        };
+        if quota(flush_keys_interval, threshold).is_none() {
+            return Err(Box::new(ConfigError::NonZero));
+        }
+        let threshold_expression = config
+            .threshold_expression
+            .as_deref()
+            .map(|source| ThresholdExpression::build(source, &context.enrichment_tables))
+            .transpose()?;
         let exclude = config
             .exclude
             .as_ref()
@@ -134,7 +260,8 @@ where
             .transpose()?;
 
         Ok(Self {
-            quota,
+            threshold,
+            threshold_expression,
             clock,
             flush_keys_interval,
             key_field: config.key_field.clone(),
@@ -158,7 +285,9 @@ where
     {
         let mut flush_keys = tokio::time::interval(self.flush_keys_interval * 2);
 
-        let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
+        // Keys are rate limited by a separate limiter for each distinct threshold, as a limiter
+        // applies the same quota to all of its keys.
+        let mut limiters = HashMap::new();
 
         Box::pin(stream! {
           loop {
@@ -177,6 +306,12 @@ where
                                 _ => (true, event)
                             };
                             let output = if throttle {
+                                let (threshold, event) = self.resolve_threshold(event);
+                                let limiter = limiters.entry(threshold).or_insert_with(|| {
+                                    let quota = quota(self.flush_keys_interval, threshold)
+                                        .expect("threshold quota was validated");
+                                    RateLimiter::dashmap_with_clock(quota, &self.clock)
+                                });
                                 let key = self.key_field.as_ref().and_then(|t| {
                                     t.render_string(&event)
                                         .map_err(|error| {
@@ -212,7 +347,10 @@ where
                     }
                 }
                 _ = flush_keys.tick() => {
-                    limiter.retain_recent();
+                    limiters.retain(|threshold, limiter| {
+                        limiter.retain_recent();
+                        *threshold == self.threshold || !limiter.is_empty()
+                    });
                     false
                 }
             };
@@ -436,11 +574,65 @@ key_field = "{{ bucket }}"
         assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
     }
 
+    #[tokio::test]
+    async fn throttle_threshold_expression() {
+        let clock = clock::FakeRelativeClock::default();
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 1
+threshold_expression = "to_int(.quota) ?? 0"
+window_secs = 5
+key_field = "{{ bucket }}"
+"#,
+        )
+        .unwrap();
+
+        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
+            .map(Transform::event_task)
+            .unwrap();
+
+        let throttle = throttle.into_task();
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        let mut out_stream = throttle.transform_events(Box::pin(rx));
+
+        // tokio interval is always immediately ready, so we poll once to make sure
+        // we trip it/set the interval in the future
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        let mut log_a = LogEvent::default();
+        log_a.insert("bucket", "a");
+        log_a.insert("quota", 2);
+        let mut log_b = LogEvent::default();
+        log_b.insert("bucket", "b");
+        for _ in 0..3 {
+            tx.send(log_a.clone().into()).await.unwrap();
+            tx.send(log_b.clone().into()).await.unwrap();
+        }
+
+        // Bucket `a` has a quota of 2, while bucket `b` falls back to the static threshold.
+        let mut buckets = Vec::new();
+        for _ in 0..3 {
+            let event = out_stream.next().await.unwrap();
+            buckets.push(event.as_log()["bucket"].to_string_lossy().into_owned());
+        }
+        assert_eq!(buckets, ["a", "b", "a"]);
+
+        // We should be back to pending, having the other events dropped
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        tx.disconnect();
+
+        // And still nothing there
+        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+    }
+
     #[tokio::test]
     async fn emits_internal_events() {
         assert_transform_compliance(async move {
             let config = ThrottleConfig {
                 threshold: 1,
+                threshold_expression: None,
                 window_secs: Duration::from_secs_f64(1.0),
                 key_field: None,
                 exclude: None,
@@ -0,0 +1,493 @@
+use std::{
+    path::{Path, PathBuf},
+    pin::Pin,
+    time::{Duration, SystemTime},
+};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use serde_with::serde_as;
+use snafu::Snafu;
+use tokio::time::{interval_at, Instant, Interval};
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vrl::value::Value;
+use wasmtime::{
+    component::{Component, Linker, ResourceTable},
+    Config, Engine, Store, Trap,
+};
+use wasmtime_wasi::{WasiCtx, WasiCtxBuilder, WasiView};
+
+use crate::{
+    config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
+    event::{Event, EventMetadata, LogEvent},
+    internal_events::{WasmComponentReloadError, WasmComponentReloaded, WasmProcessingError},
+    schema::Definition,
+    transforms::{TaskTransform, Transform},
+};
+
+mod bindings {
+    wasmtime::component::bindgen!({
+        path: "src/transforms/wasm/wit",
+        world: "plugin",
+        async: true,
+    });
+}
+
+use bindings::Plugin;
+
+/// Configuration for the `wasm` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "wasm",
+    "Modify event data with a WebAssembly component written in any language."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WasmConfig {
+    /// The path of the WebAssembly component to process the events with.
+    ///
+    /// The component must target WASI 0.2 and export the `vector:transform/processor` interface,
+    /// which processes each event encoded as a JSON object and returns the events to emit in its
+    /// place.
+    #[configurable(metadata(docs::examples = "/etc/vector/transform.wasm"))]
+    module: PathBuf,
+
+    /// The amount of fuel the component can consume to process each event.
+    ///
+    /// Fuel is roughly the number of WebAssembly instructions executed. An event whose processing
+    /// runs out of fuel is dropped, and the component is instantiated anew for the next event.
+    #[serde(default = "default_fuel")]
+    fuel: u64,
+
+    /// The interval between checks of the component file for changes, in seconds.
+    ///
+    /// When the file changes, the component is compiled again and processes the next events. If
+    /// the new component fails to load, the previous one is kept. Set to `0` to never reload the
+    /// component.
+    #[serde(default = "default_reload_interval_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Reload Interval"))]
+    reload_interval_secs: Duration,
+}
+
+/// The amount of fuel after which a call into the component yields back to the runtime, so that
+/// processing an event doesn't block the other tasks of its worker thread.
+const FUEL_YIELD_INTERVAL: u64 = 10_000;
+
+const fn default_fuel() -> u64 {
+    10_000_000
+}
+
+const fn default_reload_interval_secs() -> Duration {
+    Duration::from_secs(5)
+}
+
+impl crate::config::GenerateConfig for WasmConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"module = "/etc/vector/transform.wasm""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "wasm")]
+impl TransformConfig for WasmConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Wasm::new(self).await.map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // The component can emit arbitrary events, so the type definition is reset
+        let namespaces = input_definitions
+            .iter()
+            .flat_map(|(_output, definition)| definition.log_namespaces().clone())
+            .collect();
+
+        let definition = input_definitions
+            .iter()
+            .map(|(output, _definition)| {
+                (
+                    output.clone(),
+                    Definition::default_for_namespace(&namespaces),
+                )
+            })
+            .collect();
+
+        vec![TransformOutput::new(DataType::Log, definition)]
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Couldn't create the WebAssembly engine: {}", message))]
+    Engine { message: String },
+    #[snafu(display("Couldn't load the WebAssembly component {:?}: {}", path, message))]
+    LoadComponent { path: PathBuf, message: String },
+    #[snafu(display(
+        "Couldn't instantiate the WebAssembly component {:?}: {}",
+        path,
+        message
+    ))]
+    Instantiate { path: PathBuf, message: String },
+}
+
+/// The state of a component instance, giving it access to WASI.
+///
+/// The component doesn't get any access to the filesystem, network, or environment of Vector. Its
+/// standard error is inherited, so it can be used for debugging.
+struct PluginState {
+    ctx: WasiCtx,
+    table: ResourceTable,
+}
+
+impl PluginState {
+    fn new() -> Self {
+        Self {
+            ctx: WasiCtxBuilder::new().inherit_stderr().build(),
+            table: ResourceTable::new(),
+        }
+    }
+}
+
+impl WasiView for PluginState {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.ctx
+    }
+}
+
+struct Instance {
+    store: Store<PluginState>,
+    plugin: Plugin,
+}
+
+/// The modification time of the component file, if it can be read.
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn load_component(engine: &Engine, path: &Path) -> Result<Component, BuildError> {
+    Component::from_file(engine, path).map_err(|error| BuildError::LoadComponent {
+        path: path.to_owned(),
+        message: format!("{error:#}"),
+    })
+}
+
+/// Encodes the value of a log event as the JSON object passed to the component.
+fn encode_event(value: &Value) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|error| error.to_string())
+}
+
+/// Decodes an event returned by the component, carrying over the metadata of the processed event.
+fn decode_event(output: &str, metadata: &EventMetadata) -> Result<Event, String> {
+    let value = serde_json::from_str::<serde_json::Value>(output)
+        .map_err(|error| format!("component returned an event which isn't valid JSON: {error}"))?;
+    if !value.is_object() {
+        return Err(format!(
+            "component returned an event which isn't a JSON object: {output}"
+        ));
+    }
+    Ok(LogEvent::from_parts(Value::from(value), metadata.clone()).into())
+}
+
+pub struct Wasm {
+    path: PathBuf,
+    fuel: u64,
+    reload_interval: Option<Duration>,
+    engine: Engine,
+    linker: Linker<PluginState>,
+    component: Component,
+    modified: Option<SystemTime>,
+    instance: Option<Instance>,
+}
+
+impl Wasm {
+    async fn new(config: &WasmConfig) -> crate::Result<Self> {
+        let mut engine_config = Config::new();
+        engine_config
+            .wasm_component_model(true)
+            .async_support(true)
+            .consume_fuel(true)
+            .fuel_async_yield_interval(Some(FUEL_YIELD_INTERVAL))
+            .map_err(|error| BuildError::Engine {
+                message: format!("{error:#}"),
+            })?;
+        let engine = Engine::new(&engine_config).map_err(|error| BuildError::Engine {
+            message: format!("{error:#}"),
+        })?;
+
+        let mut linker = Linker::new(&engine);
+        wasmtime_wasi::add_to_linker_async(&mut linker).map_err(|error| BuildError::Engine {
+            message: format!("{error:#}"),
+        })?;
+
+        let path = config.module.clone();
+        let modified = modified(&path);
+        let component = {
+            let engine = engine.clone();
+            let path = path.clone();
+            tokio::task::spawn_blocking(move || load_component(&engine, &path)).await??
+        };
+
+        let mut wasm = Self {
+            path,
+            fuel: config.fuel,
+            reload_interval: Some(config.reload_interval_secs)
+                .filter(|interval| !interval.is_zero()),
+            engine,
+            linker,
+            component,
+            modified,
+            instance: None,
+        };
+
+        // Instantiate the component up front, to report missing imports or exports at startup.
+        let instance = wasm
+            .instantiate()
+            .await
+            .map_err(|error| BuildError::Instantiate {
+                path: wasm.path.clone(),
+                message: format!("{error:#}"),
+            })?;
+        wasm.instance = Some(instance);
+
+        Ok(wasm)
+    }
+
+    async fn instantiate(&self) -> wasmtime::Result<Instance> {
+        let mut store = Store::new(&self.engine, PluginState::new());
+        let plugin = Plugin::instantiate_async(&mut store, &self.component, &self.linker).await?;
+        Ok(Instance { store, plugin })
+    }
+
+    /// Compiles the component again if its file changed since it was last loaded.
+    async fn reload(&mut self) {
+        let modified = modified(&self.path);
+        if modified.is_none() || modified == self.modified {
+            return;
+        }
+        // The file is only loaded again once it changes again, even if this load fails.
+        self.modified = modified;
+
+        let engine = self.engine.clone();
+        let path = self.path.clone();
+        let component = tokio::task::spawn_blocking(move || load_component(&engine, &path))
+            .await
+            .map_err(|error| error.to_string())
+            .and_then(|result| result.map_err(|error| error.to_string()));
+
+        match component {
+            Ok(component) => {
+                let previous = std::mem::replace(&mut self.component, component);
+                match self.instantiate().await {
+                    Ok(instance) => {
+                        self.instance = Some(instance);
+                        emit!(WasmComponentReloaded { path: &self.path });
+                    }
+                    Err(error) => {
+                        self.component = previous;
+                        emit!(WasmComponentReloadError {
+                            path: &self.path,
+                            error: &format!("{error:#}"),
+                        });
+                    }
+                }
+            }
+            Err(error) => emit!(WasmComponentReloadError {
+                path: &self.path,
+                error: &error,
+            }),
+        }
+    }
+
+    async fn process(&mut self, event: Event) -> Result<Vec<Event>, (&'static str, String)> {
+        let (value, metadata) = event.into_log().into_parts();
+        let input = encode_event(&value).map_err(|error| ("encoding_failed", error))?;
+
+        let mut instance = match self.instance.take() {
+            Some(instance) => instance,
+            None => self
+                .instantiate()
+                .await
+                .map_err(|error| ("instantiation_failed", format!("{error:#}")))?,
+        };
+        instance
+            .store
+            .set_fuel(self.fuel)
+            .map_err(|error| ("instantiation_failed", format!("{error:#}")))?;
+
+        let result = instance
+            .plugin
+            .vector_transform_processor()
+            .call_process(&mut instance.store, &input)
+            .await;
+        let outputs = match result {
+            Ok(result) => {
+                // The instance can only be reused if it didn't trap.
+                self.instance = Some(instance);
+                result.map_err(|error| ("component_error", error))?
+            }
+            Err(error) if error.downcast_ref::<Trap>() == Some(&Trap::OutOfFuel) => {
+                return Err(("out_of_fuel", "component ran out of fuel".to_owned()));
+            }
+            Err(error) => return Err(("trap", format!("{error:#}"))),
+        };
+
+        outputs
+            .iter()
+            .map(|output| decode_event(output, &metadata))
+            .collect::<Result<_, _>>()
+            .map_err(|error| ("invalid_output", error))
+    }
+}
+
+async fn tick(interval: &mut Option<Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+impl TaskTransform<Event> for Wasm {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut wasm = self;
+        let mut reload = wasm
+            .reload_interval
+            .map(|period| interval_at(Instant::now() + period, period));
+
+        Box::pin(stream! {
+            loop {
+                let events = tokio::select! {
+                    _ = tick(&mut reload) => {
+                        wasm.reload().await;
+                        Vec::new()
+                    }
+                    maybe_event = input_rx.next() => match maybe_event {
+                        None => break,
+                        Some(event) => wasm.process(event).await.unwrap_or_else(|(error_code, error)| {
+                            emit!(WasmProcessingError {
+                                error: &error,
+                                error_code,
+                            });
+                            Vec::new()
+                        }),
+                    },
+                };
+                for event in events {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use similar_asserts::assert_eq;
+    use vrl::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WasmConfig>();
+    }
+
+    #[test]
+    fn parses_config_with_defaults() {
+        let config = toml::from_str::<WasmConfig>(r#"module = "transform.wasm""#).unwrap();
+
+        assert_eq!(config.module, PathBuf::from("transform.wasm"));
+        assert_eq!(config.fuel, 10_000_000);
+        assert_eq!(config.reload_interval_secs, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn encodes_events_as_json_objects() {
+        let value = Value::from(btreemap! {
+            "message" => "hello",
+            "count" => 3,
+        });
+
+        assert_eq!(
+            encode_event(&value).unwrap(),
+            r#"{"count":3,"message":"hello"}"#
+        );
+    }
+
+    #[test]
+    fn decodes_events_with_the_processed_event_metadata() {
+        let metadata = EventMetadata::default();
+        let event = decode_event(r#"{"message":"hello","tags":["a"]}"#, &metadata).unwrap();
+
+        assert_eq!(
+            event.as_log().value(),
+            &Value::from(btreemap! {
+                "message" => "hello",
+                "tags" => vec!["a"],
+            })
+        );
+        assert_eq!(event.metadata(), &metadata);
+    }
+
+    #[test]
+    fn rejects_events_which_arent_json_objects() {
+        let metadata = EventMetadata::default();
+
+        assert!(decode_event("not json", &metadata).is_err());
+        assert!(decode_event(r#"["message"]"#, &metadata).is_err());
+    }
+
+    #[tokio::test]
+    async fn fails_to_build_with_a_missing_component() {
+        let config =
+            toml::from_str::<WasmConfig>(r#"module = "/nonexistent/transform.wasm""#).unwrap();
+
+        let error = Wasm::new(&config).await.err().unwrap().to_string();
+        assert!(
+            error.starts_with("Couldn't load the WebAssembly component"),
+            "{error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_to_build_with_an_invalid_component() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"not a component").unwrap();
+        let config = WasmConfig {
+            module: file.path().to_owned(),
+            fuel: default_fuel(),
+            reload_interval_secs: default_reload_interval_secs(),
+        };
+
+        let error = Wasm::new(&config).await.err().unwrap().to_string();
+        assert!(
+            error.starts_with("Couldn't load the WebAssembly component"),
+            "{error}"
+        );
+    }
+}
@@ -39,6 +39,11 @@ pub struct Opts {
         value_delimiter(',')
     )]
     pub config_dirs: Vec<PathBuf>,
+
+    /// Write the events emitted by the outputs of the tests to their golden files, instead of
+    /// comparing them.
+    #[arg(long)]
+    pub update_golden: bool,
 }
 
 impl Opts {
@@ -72,7 +77,13 @@ pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exi
     {
         println!("Running tests");
     }
-    match config::build_unit_tests_main(&paths, signal_handler).await {
+    let golden_mode = if opts.update_golden {
+        config::GoldenMode::Update
+    } else {
+        config::GoldenMode::Compare
+    };
+
+    match config::build_unit_tests_main(&paths, signal_handler, golden_mode).await {
         Ok(tests) => {
             if tests.is_empty() {
                 #[allow(clippy::print_stdout)]
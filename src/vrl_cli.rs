@@ -0,0 +1,438 @@
+#![allow(missing_docs)]
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use vrl::{
+    compiler::{
+        compile_with_state,
+        state::{ExternalEnv, RuntimeState},
+        CompileConfig, Context, Function, Program, TargetValue, TimeZone, TypeState,
+    },
+    diagnostic::Formatter,
+    parser::ast::{Expr, RootExpr},
+    path::{parse_target_path, PathPrefix},
+    value::{kind::Collection, Kind, Secrets, Value},
+};
+
+const HELP: &str = "\
+Commands:
+  next, prev     select the next or previous sample event
+  event          print the selected sample event
+  load <file>    replace the program with the contents of a file
+  program        print the statements of the program
+  step           run the program statement by statement on the selected event
+  type <path>    print the type of an event (`.foo`) or metadata (`%foo`) path
+  clear          remove all statements from the program
+  help           print this message
+  exit, quit     leave the debugger
+
+Any other input is appended to the program, which is then run on the selected event.";
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Load sample events from an NDJSON file and start the VRL debugger instead of the REPL.
+    ///
+    /// The debugger steps through a program statement by statement, printing the event after each
+    /// assignment, and prints the type of event and metadata paths.
+    #[arg(long, value_name = "EVENTS_FILE")]
+    debug: Option<PathBuf>,
+
+    #[command(flatten)]
+    vrl: vrl::cli::Opts,
+}
+
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let mut functions = vrl::stdlib::all();
+    functions.extend(vector_vrl_functions::all());
+
+    let Some(path) = &opts.debug else {
+        return vrl::cli::cmd::cmd(&opts.vrl, functions);
+    };
+
+    let events = match load_events(path) {
+        Ok(events) => events,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("Failed to load sample events: {error}");
+            }
+            return exitcode::DATAERR;
+        }
+    };
+
+    let mut debugger = Debugger::new(events, functions);
+    match debugger.run(io::stdin().lock(), io::stdout().lock()) {
+        Ok(()) => exitcode::OK,
+        Err(_) => exitcode::IOERR,
+    }
+}
+
+fn load_events(path: &Path) -> Result<Vec<Value>, String> {
+    let content =
+        fs::read_to_string(path).map_err(|error| format!("{}: {error}", path.display()))?;
+    let events = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str::<serde_json::Value>(line)
+                .map(Value::from)
+                .map_err(|error| format!("{}:{}: {error}", path.display(), index + 1))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if events.is_empty() {
+        Err(format!("{}: no events found", path.display()))
+    } else {
+        Ok(events)
+    }
+}
+
+/// A top-level statement of a program.
+#[derive(Debug, PartialEq)]
+struct Statement {
+    source: String,
+    assignment: bool,
+}
+
+fn parse_statements(source: &str) -> Result<Vec<Statement>, String> {
+    let program = vrl::parser::parse(source).map_err(|error| error.to_string())?;
+
+    Ok(program
+        .0
+        .iter()
+        .map(|node| {
+            let span = node.span();
+            Statement {
+                source: source[span.start()..span.end()].to_owned(),
+                assignment: matches!(
+                    node.inner(),
+                    RootExpr::Expr(expr) if matches!(expr.inner(), Expr::Assignment(_))
+                ),
+            }
+        })
+        .collect())
+}
+
+/// Compiles a program for events of the given kind.
+fn compile(source: &str, kind: Kind, functions: &[Box<dyn Function>]) -> Result<Program, String> {
+    let state = TypeState {
+        local: Default::default(),
+        external: ExternalEnv::new_with_kind(kind, Kind::object(Collection::any())),
+    };
+
+    compile_with_state(source, functions, &state, CompileConfig::default())
+        .map(|result| result.program)
+        .map_err(|diagnostics| Formatter::new(source, diagnostics).to_string())
+}
+
+/// Runs a program on a copy of the given event, returning the result of the program and the
+/// modified event.
+///
+/// Like the `remap` transform without a schema, the program is compiled without any knowledge of
+/// the event, so that it is checked the same way it would be in a pipeline.
+fn resolve(
+    source: &str,
+    event: &Value,
+    functions: &[Box<dyn Function>],
+) -> Result<(Value, Value), String> {
+    let program = compile(source, Kind::any_object(), functions)?;
+
+    let mut target = TargetValue {
+        value: event.clone(),
+        metadata: Value::Object(BTreeMap::new()),
+        secrets: Secrets::default(),
+    };
+    let mut state = RuntimeState::default();
+    let timezone = TimeZone::default();
+    let mut ctx = Context::new(&mut target, &mut state, &timezone);
+
+    let result = program
+        .resolve(&mut ctx)
+        .map_err(|error| error.to_string())?;
+    Ok((result, target.value))
+}
+
+struct Debugger {
+    events: Vec<Value>,
+    index: usize,
+    statements: Vec<Statement>,
+    functions: Vec<Box<dyn Function>>,
+}
+
+impl Debugger {
+    fn new(events: Vec<Value>, functions: Vec<Box<dyn Function>>) -> Self {
+        Self {
+            events,
+            index: 0,
+            statements: Vec::new(),
+            functions,
+        }
+    }
+
+    fn run(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        writeln!(
+            output,
+            "Loaded {} sample event(s). Type `help` for a list of commands.",
+            self.events.len()
+        )?;
+        self.prompt(&mut output)?;
+
+        for line in input.lines() {
+            let line = line?;
+            let line = line.trim();
+            match line.split_once(char::is_whitespace) {
+                _ if line.is_empty() => {}
+                _ if line == "exit" || line == "quit" => return Ok(()),
+                None if line == "help" => writeln!(output, "{HELP}")?,
+                None if line == "next" => self.select(self.index + 1, &mut output)?,
+                None if line == "prev" => self.select(self.index.wrapping_sub(1), &mut output)?,
+                None if line == "event" => writeln!(output, "{}", pretty(self.event()))?,
+                None if line == "program" => {
+                    for (index, statement) in self.statements.iter().enumerate() {
+                        writeln!(output, "[{}] {}", index + 1, statement.source)?;
+                    }
+                }
+                None if line == "step" => self.step(&mut output)?,
+                None if line == "clear" => self.statements.clear(),
+                Some(("load", path)) => self.load(path.trim(), &mut output)?,
+                Some(("type", path)) => self.print_type(path.trim(), &mut output)?,
+                _ => self.append(line, &mut output)?,
+            }
+            self.prompt(&mut output)?;
+        }
+
+        Ok(())
+    }
+
+    fn prompt(&self, output: &mut impl Write) -> io::Result<()> {
+        write!(output, "[{}/{}] $ ", self.index + 1, self.events.len())?;
+        output.flush()
+    }
+
+    fn event(&self) -> &Value {
+        &self.events[self.index]
+    }
+
+    fn source(&self, statements: &[Statement]) -> String {
+        statements
+            .iter()
+            .map(|statement| statement.source.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn select(&mut self, index: usize, output: &mut impl Write) -> io::Result<()> {
+        if index < self.events.len() {
+            self.index = index;
+            writeln!(output, "{}", pretty(self.event()))
+        } else {
+            writeln!(output, "No more sample events.")
+        }
+    }
+
+    fn load(&mut self, path: &str, output: &mut impl Write) -> io::Result<()> {
+        let statements = fs::read_to_string(path)
+            .map_err(|error| format!("{path}: {error}"))
+            .and_then(|source| parse_statements(&source));
+        match statements {
+            Ok(statements) => {
+                self.statements = statements;
+                writeln!(output, "Loaded {} statement(s).", self.statements.len())
+            }
+            Err(error) => writeln!(output, "{error}"),
+        }
+    }
+
+    fn append(&mut self, line: &str, output: &mut impl Write) -> io::Result<()> {
+        let statements = match parse_statements(line) {
+            Ok(statements) => statements,
+            Err(error) => return writeln!(output, "{error}"),
+        };
+        let count = self.statements.len();
+        self.statements.extend(statements);
+
+        match resolve(
+            &self.source(&self.statements),
+            self.event(),
+            &self.functions,
+        ) {
+            Ok((result, _)) => writeln!(output, "{}", pretty(&result)),
+            Err(error) => {
+                // Keep the program runnable by dropping the statements that broke it.
+                self.statements.truncate(count);
+                writeln!(output, "{error}")
+            }
+        }
+    }
+
+    /// Runs every prefix of the program on the selected event, so that each step starts from the
+    /// event as the sample defines it and shows the state after the statement.
+    fn step(&self, output: &mut impl Write) -> io::Result<()> {
+        for end in 1..=self.statements.len() {
+            let statement = &self.statements[end - 1];
+            writeln!(output, "[{end}] {}", statement.source)?;
+
+            match resolve(
+                &self.source(&self.statements[..end]),
+                self.event(),
+                &self.functions,
+            ) {
+                Ok((_, event)) if statement.assignment => writeln!(output, "{}", pretty(&event))?,
+                Ok((result, _)) => writeln!(output, "=> {}", pretty(&result))?,
+                Err(error) => return writeln!(output, "{error}"),
+            }
+        }
+        Ok(())
+    }
+
+    fn print_type(&self, path: &str, output: &mut impl Write) -> io::Result<()> {
+        let path = match parse_target_path(path) {
+            Ok(path) => path,
+            Err(error) => return writeln!(output, "{error}"),
+        };
+        // Type the event after the selected sample, unless the program relies on not knowing
+        // its type (for example by aborting on infallible calls).
+        let source = self.source(&self.statements);
+        let program = match compile(&source, Kind::from(self.event()), &self.functions)
+            .or_else(|_| compile(&source, Kind::any_object(), &self.functions))
+        {
+            Ok(program) => program,
+            Err(error) => return writeln!(output, "{error}"),
+        };
+
+        let state = program.final_type_info().state;
+        let kind = match path.prefix {
+            PathPrefix::Event => state.external.target_kind(),
+            PathPrefix::Metadata => state.external.metadata_kind(),
+        };
+        writeln!(output, "{}", kind.at_path(&path.path))
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use vrl::value;
+
+    use super::*;
+
+    fn debug(events: Vec<Value>, input: &str) -> String {
+        let mut functions = vrl::stdlib::all();
+        functions.extend(vector_vrl_functions::all());
+        let mut output = Vec::new();
+
+        Debugger::new(events, functions)
+            .run(input.as_bytes(), &mut output)
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn splits_statements() {
+        let statements = parse_statements(indoc! {r#"
+            .a = 1
+            if .b == null { .b = "x"; .c = 2 }
+            .a + 1
+        "#})
+        .unwrap();
+
+        assert_eq!(
+            statements,
+            vec![
+                Statement {
+                    source: ".a = 1".to_owned(),
+                    assignment: true,
+                },
+                Statement {
+                    source: r#"if .b == null { .b = "x"; .c = 2 }"#.to_owned(),
+                    assignment: false,
+                },
+                Statement {
+                    source: ".a + 1".to_owned(),
+                    assignment: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn steps_through_statements() {
+        let output = debug(
+            vec![value!({"message": "hello"})],
+            indoc! {r#"
+                .upper = upcase!(.message)
+                strlen(.upper)
+                step
+            "#},
+        );
+
+        assert!(
+            output.contains(indoc! {r#"
+                [1] .upper = upcase!(.message)
+                {
+                  "message": "hello",
+                  "upper": "HELLO"
+                }
+                [2] strlen(.upper)
+                => 5
+            "#}),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn selects_sample_events() {
+        let output = debug(
+            vec![value!({"n": 1}), value!({"n": 2})],
+            indoc! {"
+                .n
+                next
+                to_int!(.n) * 10
+                next
+            "},
+        );
+
+        assert!(output.contains("[2/2] $ 20\n"), "{output}");
+        assert!(output.contains("No more sample events."), "{output}");
+    }
+
+    #[test]
+    fn prints_path_types() {
+        let output = debug(
+            vec![value!({"message": "hello"})],
+            indoc! {r#"
+                .count = 1
+                type .message
+                type .count
+            "#},
+        );
+
+        assert!(output.contains("$ string\n"), "{output}");
+        assert!(output.contains("$ integer\n"), "{output}");
+    }
+
+    #[test]
+    fn drops_failing_statements() {
+        let output = debug(
+            vec![value!({})],
+            indoc! {r#"
+                .a = 1
+                .b = parse_json!("{")
+                program
+            "#},
+        );
+
+        assert!(output.ends_with("[1] .a = 1\n[1/1] $ "), "{output}");
+    }
+}
@@ -10,6 +10,7 @@ cp /certs/intermediate_server/certs/postgres-chain.cert.pem /ssl/postgres.crt
 
 /docker-entrypoint.sh postgres \
     -c ssl=on \
+    -c wal_level=logical \
     -c ssl_key_file=/ssl/postgres.key \
     -c ssl_cert_file=/ssl/postgres.crt \
     -c ssl_ca_file=/ssl/postgres.crt
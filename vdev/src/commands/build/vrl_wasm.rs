@@ -1,24 +1,24 @@
-use std::path::Path;
 use anyhow::Result;
 use clap::Args;
+use std::path::Path;
 
 use crate::app;
 
-/// Compiles VRL crates to wasm32-unknown-unknown
+/// Compiles the VRL web playground, which compiles and runs VRL programs, to wasm32-unknown-unknown
 #[derive(Args, Debug)]
 #[command()]
 pub struct Cli {}
 
 impl Cli {
     pub fn exec(self) -> Result<()> {
-        let vrl_path = Path::new(app::path()).join("lib").join("vrl");
+        let playground_path = Path::new(app::path())
+            .join("lib")
+            .join("vector-vrl")
+            .join("web-playground");
         let args = &["build", "--release", "--target", "wasm32-unknown-unknown"];
 
-        for crate_name in  ["compiler", "core", "diagnostic", "parser"] {
-            println!("Compiling lib/vrl/{crate_name} to wasm32-unknown-unknown");
-            std::env::set_current_dir(vrl_path.join(crate_name))?;
-            app::exec("cargo", *args, false)?;
-        }
-        Ok(())
+        println!("Compiling lib/vector-vrl/web-playground to wasm32-unknown-unknown");
+        std::env::set_current_dir(playground_path)?;
+        app::exec("cargo", *args, false)
     }
 }
@@ -62,6 +62,7 @@ pub fn exec(integration: &Option<String>, path: &str) -> Result<()> {
                 "  Needs docker socket: {}",
                 config.runner.needs_docker_socket
             );
+            println!("  Privileged: {}", config.runner.privileged);
 
             println!("Environments:");
             for environment in config.environments().keys() {
@@ -135,6 +135,9 @@ pub struct IntegrationRunnerConfig {
     /// Does the test runner need access to the host's docker socket?
     #[serde(default)]
     pub needs_docker_socket: bool,
+    /// Does the test runner need to run as a privileged container, such as to load eBPF programs?
+    #[serde(default)]
+    pub privileged: bool,
 }
 
 impl ComposeTestConfig {
@@ -99,6 +99,8 @@ pub trait ContainerTestRunner: TestRunner {
 
     fn needs_docker_socket(&self) -> bool;
 
+    fn privileged(&self) -> bool;
+
     fn volumes(&self) -> Vec<String>;
 
     fn state(&self) -> Result<RunnerState> {
@@ -224,6 +226,10 @@ pub trait ContainerTestRunner: TestRunner {
             .needs_docker_socket()
             .then(|| vec!["--volume", &docker_socket])
             .unwrap_or_default();
+        let privileged_args = self
+            .privileged()
+            .then(|| vec!["--privileged"])
+            .unwrap_or_default();
 
         let volumes = self.volumes();
         let volumes: Vec<_> = volumes
@@ -253,6 +259,7 @@ pub trait ContainerTestRunner: TestRunner {
             ]
             .chain_args(volumes)
             .chain_args(docker_args)
+            .chain_args(privileged_args)
             .chain_args([&self.image_name(), "/bin/sleep", "infinity"]),
         )
         .wait(format!("Creating container {}", self.container_name()))
@@ -306,6 +313,7 @@ pub(super) struct IntegrationTestRunner {
     // The integration is None when compiling the runner image with the `all-integration-tests` feature.
     integration: Option<String>,
     needs_docker_socket: bool,
+    privileged: bool,
     network: Option<String>,
     volumes: Vec<String>,
 }
@@ -319,6 +327,7 @@ impl IntegrationTestRunner {
         Ok(Self {
             integration,
             needs_docker_socket: config.needs_docker_socket,
+            privileged: config.privileged,
             network,
             volumes: config
                 .volumes
@@ -368,6 +377,10 @@ impl ContainerTestRunner for IntegrationTestRunner {
         self.needs_docker_socket
     }
 
+    fn privileged(&self) -> bool {
+        self.privileged
+    }
+
     fn volumes(&self) -> Vec<String> {
         self.volumes.clone()
     }
@@ -392,6 +405,10 @@ impl ContainerTestRunner for DockerTestRunner {
         false
     }
 
+    fn privileged(&self) -> bool {
+        false
+    }
+
     fn volumes(&self) -> Vec<String> {
         Vec::default()
     }
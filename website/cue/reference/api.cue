@@ -58,6 +58,29 @@ api: {
 				endpoint of the address set using the `bind` parameter.
 				"""
 		}
+		reload: {
+			common:   false
+			required: false
+			type: bool: default: false
+			description: """
+				Whether the configuration can be reloaded through the `reloadConfig` GraphQL
+				mutation. The mutation validates the given configuration, returns the components
+				it adds, changes, and removes, and unless `dryRun` is set, reloads the running
+				topology with it, as when the configuration is reloaded from disk. Anyone able
+				to reach the API endpoint is then able to replace the running configuration.
+				"""
+		}
+		kafka_partition_control: {
+			common:   false
+			required: false
+			type: bool: default: false
+			description: """
+				Whether the partitions consumed by `kafka` sources can be paused and resumed
+				through the `pauseKafkaPartitions` and `resumeKafkaPartitions` GraphQL mutations.
+				Anyone able to reach the API endpoint is then able to stop the consumption of
+				partitions.
+				"""
+		}
 	}
 
 	endpoints: {
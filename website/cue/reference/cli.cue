@@ -259,6 +259,15 @@ cli: {
 				out the [unit testing documentation](\(urls.vector_unit_tests)).
 				"""
 
+			flags: _default_flags & {
+				"update-golden": {
+					description: """
+						Write the events emitted by the outputs of the tests to their golden
+						files, instead of comparing them
+						"""
+				}
+			}
+
 			options: {
 				"config-toml": {
 					description: env_vars.VECTOR_CONFIG_TOML.description
@@ -350,6 +359,14 @@ cli: {
 					description: "Components (sources, transforms) to observe for their inputs (comma-separated; accepts glob patterns)"
 					type:        "list"
 				}
+				"around": {
+					description: "Components (transforms) to observe for both their inputs and outputs, to compare the events a transform receives with the events it emits (comma-separated; accepts glob patterns)"
+					type:        "list"
+				}
+				"filter": {
+					description: "VRL boolean expression that events must match to be observed, for example `.status >= 500`. Events for which the expression fails are not observed."
+					type:        "string"
+				}
 			}
 
 			args: {
@@ -359,7 +376,7 @@ cli: {
 						Components (sources, transforms) to observe for their
 						outputs (comma-separated; accepts glob patterns). The
 						default value is `*` only if no other patterns are specified
-						(i.e. via `--outputs-of` or `--inputs-of`); otherwise the
+						(i.e. via `--outputs-of`, `--inputs-of` or `--around`); otherwise the
 						default value is empty.
 						"""
 					default: "*"
@@ -475,6 +492,16 @@ cli: {
 			}
 
 			options: {
+				"debug": {
+					description: """
+						NDJSON file of sample events to load into the VRL debugger, which is started
+						instead of the REPL. The debugger steps through a program statement by
+						statement, printing the event after each assignment, and prints the type of
+						event and metadata paths. Type `help` in the debugger for a list of commands.
+						"""
+					type: "string"
+				}
+
 				"input": {
 					_short: "i"
 					description: """
@@ -8,6 +8,10 @@ base: components: sinks: configuration: {
 			More information about the individual buffer types, and buffer behavior, can be found in the
 			[Buffering Model][buffering_model] section.
 
+			A memory buffer can spill to a disk buffer by configuring a list of two buffers: the memory buffer,
+			with `when_full` set to `overflow`, followed by the disk buffer. Events are then only written to
+			disk when the memory buffer is full.
+
 			[buffering_model]: /docs/about/under-the-hood/architecture/buffering-model/
 			"""
 		required: false
@@ -23,9 +27,11 @@ base: components: sinks: configuration: {
 					The maximum size of the buffer on disk.
 
 					Must be at least ~256 megabytes (268435488 bytes).
+
+					Required unless `weight` is set.
 					"""
 				relevant_when: "type = \"disk\""
-				required:      true
+				required:      false
 				type: uint: unit: "bytes"
 			}
 			type: {
@@ -51,6 +57,21 @@ base: components: sinks: configuration: {
 					}
 				}
 			}
+			weight: {
+				description: """
+					The weight of this buffer's share of the global disk buffer budget.
+
+					Instead of a fixed `max_size`, the buffer is allocated a share of the
+					`disk_buffer_budget` left over once buffers with a fixed `max_size` are accounted for,
+					proportional to its weight among all weighted disk buffers. The share must be at least the
+					minimum `max_size` of ~256 megabytes (268435488 bytes).
+
+					Cannot be set together with `max_size`.
+					"""
+				relevant_when: "type = \"disk\""
+				required:      false
+				type: uint: examples: [1]
+			}
 			when_full: {
 				description: "Event handling behavior when a buffer is full."
 				required:    false
@@ -71,6 +92,17 @@ base: components: sinks: configuration: {
 														highest priority, and it is preferable to temporarily lose events rather than cause a
 														slowdown in the acceptance/consumption of events.
 														"""
+						overflow: """
+														Overflows to the next stage in the buffer topology.
+
+														If the current buffer stage is full, attempt to send this event to the next buffer stage.
+														The last stage in a buffer topology must use one of the other handling behaviors, so the
+														next stage may potentially be able to buffer the event, but it may also block or drop the
+														event.
+
+														This mode can only be used by a memory buffer stage in front of a disk buffer stage, so that
+														events are only written to disk when the memory buffer is full.
+														"""
 					}
 				}
 			}
@@ -199,4 +231,36 @@ base: components: sinks: configuration: {
 			}
 		}
 	}
+	reroute_rejected: {
+		description: """
+			Reroutes the events the sink fails to deliver to a named output instead of dropping them.
+
+			Normally, events that are rejected by the downstream service, or that still fail once all
+			retries are exhausted, are dropped. Setting `reroute_rejected` to `true` forwards a copy of
+			each of these events, as it was received by the sink, to a specially-named output,
+			`rejected`, which other components can consume as `<sink_id>.rejected`: for example, a
+			sink writing them to object storage as a dead-letter queue.
+
+			Events sent to the `rejected` output are only acknowledged once they are delivered by the
+			components consuming it.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	required_meanings: {
+		description: """
+			The semantic meanings the events sent to this sink must have, such as `timestamp`,
+			`severity`, or `host`, in addition to the ones required by the sink itself.
+
+			Vector fails to load the configuration when the schema of the events of one of the inputs
+			of the sink doesn't define one of these meanings. Only events using the `Vector` log
+			namespace are validated, and their types are only checked when `schema.validation` is
+			enabled.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["timestamp", "severity"]
+		}
+	}
 }
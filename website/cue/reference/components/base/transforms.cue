@@ -1,6 +1,17 @@
 package metadata
 
 base: components: transforms: configuration: {
+	concurrency: {
+		description: """
+			The maximum number of tasks processing events for this transform concurrently.
+
+			Only transforms that can safely process events concurrently, such as `remap`, `filter`, and
+			`route`, support this option. They default to using as many tasks as there are worker
+			threads. Setting this to `1` processes all events in a single task.
+			"""
+		required: false
+		type: uint: {}
+	}
 	graph: {
 		description: """
 			Extra graph configuration
@@ -44,4 +55,17 @@ base: components: transforms: configuration: {
 		required: true
 		type: array: items: type: string: examples: ["my-source-or-transform-id", "prefix-*"]
 	}
+	ordering_key: {
+		description: """
+			The field used to assign events to tasks when processing events concurrently.
+
+			Events with the same value for this field are processed by the same task and output in the
+			order they were received, while events with different values may be reordered. Metrics are
+			assigned to tasks by series.
+
+			If unset, all events are output in the order they were received.
+			"""
+		required: false
+		type: string: examples: [".tenant"]
+	}
 }
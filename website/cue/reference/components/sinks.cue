@@ -30,6 +30,10 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		reroute_rejected: base.components.sinks.configuration.reroute_rejected
+
+		required_meanings: base.components.sinks.configuration.required_meanings
+
 		if !features.auto_generated {
 			if features.acknowledgements {
 				acknowledgements: {
@@ -546,6 +550,60 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		rejected_events: {
+			title: "Rerouting rejected events"
+			body: """
+				Events this sink fails to deliver, because the downstream service rejected them or retries
+				were exhausted, are dropped by default. When `reroute_rejected` is set to `true`, they
+				are sent to the `rejected` output of the sink instead, as they were received. For a
+				sink named `foo`, this output can be consumed by specifying `foo.rejected` as the
+				input of another component, such as a sink writing them to object storage as a
+				dead-letter queue:
+
+				```yaml title="vector.yaml"
+				sinks:
+					elasticsearch:
+						type: elasticsearch
+						inputs: ["my-source"]
+						reroute_rejected: true
+					dead_letters:
+						type: aws_s3
+						inputs: ["elasticsearch.rejected"]
+				```
+
+				When acknowledgements are enabled, the sources of rerouted events are acknowledged once
+				the components consuming the `rejected` output deliver them.
+				"""
+		}
+
+		required_meanings: {
+			title: "Required semantic meanings"
+			body: """
+				With the `Vector` log namespace, the fields of events are located by their semantic
+				meanings, such as `timestamp` or `host`, which sources and transforms declare in the
+				schema of their events. Listing meanings in `required_meanings` makes Vector fail to
+				load the configuration, for example when running `vector validate`, if the schema of one
+				of the inputs of the sink doesn't define them, instead of the sink silently missing
+				them at runtime:
+
+				```yaml title="vector.yaml"
+				schema:
+					log_namespace: true
+				sinks:
+					archive:
+						type: aws_s3
+						inputs: ["parse_logs"]
+						required_meanings: ["timestamp", "severity", "host"]
+				```
+
+				When global schema support is enabled with `schema.enabled`, the top-level fields
+				named `host`, `message`, `service`, `severity`, `timestamp`, or `trace_id`, declared
+				with the type expected of their meaning, are automatically assigned that meaning when
+				no other field has it. Otherwise, meanings can be assigned with the
+				`set_semantic_meaning` function of the `remap` transform.
+				"""
+		}
+
 		if features.healthcheck.enabled {
 			healthchecks: {
 				title: "Health checks"
@@ -84,6 +84,21 @@ components: sinks: aws_kinesis_firehose: components._aws & {
 		traces:  false
 	}
 
+	how_it_works: {
+		dynamic_delivery_streams: {
+			title: "Dynamic delivery streams"
+			body: """
+				The `stream_name` option is a template, rendered for each event, so a single sink can
+				route the events of several tenants or applications to their own delivery streams.
+				Events are batched separately for each delivery stream, and events whose stream name
+				can't be rendered are dropped.
+
+				The healthcheck is skipped when `stream_name` contains template fields, as the
+				delivery streams are only known once events are received.
+				"""
+		}
+	}
+
 	permissions: iam: [
 		{
 			platform: "aws"
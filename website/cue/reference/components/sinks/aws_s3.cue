@@ -96,6 +96,27 @@ components: sinks: aws_s3: components._aws & {
 				"""
 		}
 
+		hive_partitioning: {
+			title: "Hive-style partitioning"
+			body:  """
+				Query engines such as Amazon Athena read the partitions of a table from the keys of
+				its objects, such as `logs/dt=2024-01-01/hour=00/`. Setting `hive_partitioning` to
+				`true` validates the [`key_prefix`](#key_prefix) template as such partitions when
+				Vector starts, and escapes the values rendered from event fields so that they always
+				stay within their partition:
+
+				```text
+				key_prefix = "logs/dt=%F/hour=%H/service={{ service }}/"
+				hive_partitioning = true
+				```
+
+				Events are batched separately for each partition, and each batch is written as one
+				object. Raise `batch.max_bytes` and `batch.timeout_secs` to write fewer, larger
+				objects per partition, and combine them with `compression = "zstd"` or with
+				`batch_encoding.codec = "parquet"` to keep the scanned data small.
+				"""
+		}
+
 		object_acl: {
 			title: "Object Access Control List (ACL)"
 			body:  """
@@ -0,0 +1,107 @@
+package metadata
+
+components: sinks: azure_event_hubs: {
+	title: "Azure Event Hubs"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			proxy: enabled: false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.azure_event_hubs
+
+				interface: {
+					socket: {
+						api: {
+							title: "AMQP 1.0"
+							url:   urls.azure_event_hubs_amqp
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.azure_event_hubs.configuration
+
+	how_it_works: {
+		authentication: {
+			title: "Authentication"
+			body: """
+				With a [connection string](\(urls.azure_event_hubs_connection_string)), connections are
+				authorized with shared access signatures, generated from the connection string's shared
+				access key and renewed before they expire. When `namespace` is set instead, connections
+				are authorized with Microsoft Entra ID tokens, obtained from the environment, a managed
+				identity or the Azure CLI.
+				"""
+		}
+		batching: {
+			title: "Batching"
+			body: """
+				Each event is sent as a separate Event Hubs event, but events sharing a partition key
+				are sent together in batch messages. The maximum message size depends on the
+				[tier](\(urls.azure_event_hubs_tiers)) of the namespace, and is announced by the Event
+				Hub when the sink connects to it. Batches that don't fit are split across several
+				messages, while events larger than the maximum message size are rejected.
+				"""
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+}
@@ -206,10 +206,58 @@ base: components: sinks: aws_kinesis_streams: configuration: {
 				description:   "Apache Avro-specific encoder options."
 				relevant_when: "codec = \"avro\""
 				required:      true
-				type: object: options: schema: {
-					description: "The Avro schema."
-					required:    true
-					type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+				type: object: options: {
+					schema: {
+						description: "The Avro schema."
+						required:    true
+						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to register the schema in.
+
+							When set, the schema is registered under the `subject` of the registry before the first
+							event is encoded, and messages are prefixed with its ID, following the Confluent wire format.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
@@ -608,6 +656,36 @@ base: components: sinks: aws_kinesis_streams: configuration: {
 					unit:    "retries"
 				}
 			}
+			retry_budget: {
+				description: """
+					A budget limiting the fraction of requests that are retries.
+
+					By default, retries are only limited by `retry_attempts`.
+					"""
+				required: false
+				type: object: options: {
+					min_retries_per_second: {
+						description: """
+																The number of retries per second allowed regardless of `ratio`, so that sinks sending few
+																requests are still able to retry them.
+																"""
+						required: false
+						type: uint: {
+							default: 10
+							unit:    "retries"
+						}
+					}
+					ratio: {
+						description: """
+																The number of retries allowed per request, over the last 10 seconds.
+
+																For example, `0.2` allows one retry for every five requests.
+																"""
+						required: false
+						type: float: default: 0.2
+					}
+				}
+			}
 			retry_initial_backoff_secs: {
 				description: """
 					The amount of time to wait before attempting the first retry for a failed request.
@@ -672,10 +750,16 @@ base: components: sinks: aws_kinesis_streams: configuration: {
 		description: """
 			The [stream name][stream_name] of the target Kinesis Firehose delivery stream.
 
+			Events are batched by the stream name rendered for them, so that a single sink can send
+			events to several streams.
+
 			[stream_name]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/logs/Working-with-log-groups-and-streams.html
 			"""
 		required: true
-		type: string: examples: ["my-stream"]
+		type: string: {
+			examples: ["my-stream", "logs-{{ tenant }}"]
+			syntax: "template"
+		}
 	}
 	tls: {
 		description: "TLS configuration."
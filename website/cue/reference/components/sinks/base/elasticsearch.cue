@@ -709,6 +709,36 @@ base: components: sinks: elasticsearch: configuration: {
 					unit:    "retries"
 				}
 			}
+			retry_budget: {
+				description: """
+					A budget limiting the fraction of requests that are retries.
+
+					By default, retries are only limited by `retry_attempts`.
+					"""
+				required: false
+				type: object: options: {
+					min_retries_per_second: {
+						description: """
+																The number of retries per second allowed regardless of `ratio`, so that sinks sending few
+																requests are still able to retry them.
+																"""
+						required: false
+						type: uint: {
+							default: 10
+							unit:    "retries"
+						}
+					}
+					ratio: {
+						description: """
+																The number of retries allowed per request, over the last 10 seconds.
+
+																For example, `0.2` allows one retry for every five requests.
+																"""
+						required: false
+						type: float: default: 0.2
+					}
+				}
+			}
 			retry_initial_backoff_secs: {
 				description: """
 					The amount of time to wait before attempting the first retry for a failed request.
@@ -768,6 +798,9 @@ base: components: sinks: elasticsearch: configuration: {
 		description: """
 			Whether or not to retry successful requests containing partial failures.
 
+			Only the documents which failed with a retriable error, such as `429 Too Many Requests`, are
+			sent again. Documents rejected by Elasticsearch are never retried.
+
 			To avoid duplicates in Elasticsearch, please use option `id_key`.
 			"""
 		required: false
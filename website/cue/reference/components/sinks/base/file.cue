@@ -27,6 +27,108 @@ base: components: sinks: file: configuration: {
 			type: bool: {}
 		}
 	}
+	batch_encoding: {
+		description: """
+			Encodes all the events written to a file at once, such as into a Parquet file, instead of
+			encoding events one by one.
+
+			Events are buffered until the file is closed, after being idle for `idle_timeout_secs` or
+			when Vector shuts down. A file receiving events again after being closed is overwritten.
+
+			When set, the `encoding.codec` and `framing` options are ignored, while the other
+			`encoding` options still apply. Batch codecs compress their output themselves, so
+			`compression` must be set to `none`.
+			"""
+		required: false
+		type: object: options: {
+			codec: {
+				description: "The codec to use for encoding batches of events."
+				required:    true
+				type: string: enum: parquet: """
+					Encodes a batch of events as an [Apache Parquet][apache_parquet] file.
+
+					Only log events are supported.
+
+					[apache_parquet]: https://parquet.apache.org/
+					"""
+			}
+			parquet: {
+				description:   "Apache Parquet-specific encoder options."
+				relevant_when: "codec = \"parquet\""
+				required:      false
+				type: object: options: {
+					compression: {
+						description: "The compression codec applied to the pages of the written files."
+						required:    false
+						type: string: {
+							default: "snappy"
+							enum: {
+								gzip: """
+									[Gzip][gzip] compression.
+
+									[gzip]: https://www.gzip.org/
+									"""
+								none: "No compression."
+								snappy: """
+									[Snappy][snappy] compression.
+
+									[snappy]: https://google.github.io/snappy/
+									"""
+								zstd: """
+									[Zstandard][zstd] compression.
+
+									[zstd]: https://facebook.github.io/zstd/
+									"""
+							}
+						}
+					}
+					row_group_size: {
+						description: "The maximum number of events in each row group of the written files."
+						required:    false
+						type: uint: {
+							default: 10000
+							unit:    "events"
+						}
+					}
+					schema: {
+						description: """
+							The columns of the written files, in order.
+
+							Each column is read from the top-level event field of the same name. Fields missing from an
+							event are written as null.
+
+							If not set, the columns are derived from the top-level fields of the events in each batch,
+							in alphabetical order, with their types inferred from the values. Fields holding values of
+							conflicting types are written as strings.
+							"""
+						required: false
+						type: array: items: type: object: options: {
+							name: {
+								description: "The name of the column, and of the top-level event field it is read from."
+								required:    true
+								type: string: examples: ["message"]
+							}
+							type: {
+								description: "The type of the column."
+								required:    true
+								type: string: enum: {
+									boolean: "A boolean."
+									double:  "A double-precision floating point number. Integers are converted."
+									int64:   "A signed 64-bit integer."
+									string: """
+										A UTF-8 string.
+
+										Timestamps are written in RFC 3339 format, and other non-string values as JSON.
+										"""
+									timestamp: "A timestamp with microsecond precision, adjusted to UTC."
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
 	compression: {
 		description: "Compression configuration."
 		required:    false
@@ -55,10 +157,58 @@ base: components: sinks: file: configuration: {
 				description:   "Apache Avro-specific encoder options."
 				relevant_when: "codec = \"avro\""
 				required:      true
-				type: object: options: schema: {
-					description: "The Avro schema."
-					required:    true
-					type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+				type: object: options: {
+					schema: {
+						description: "The Avro schema."
+						required:    true
+						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to register the schema in.
+
+							When set, the schema is registered under the `subject` of the registry before the first
+							event is encoded, and messages are prefixed with its ID, following the Confluent wire format.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
@@ -401,6 +551,85 @@ base: components: sinks: file: configuration: {
 			syntax: "template"
 		}
 	}
+	rotation: {
+		description: """
+			Rotates the files once they grow too large or too old, optionally compressing and
+			deleting the rotated files.
+
+			Rotated files are renamed by appending the time of their rotation to their path, such as
+			`/tmp/vector.log.20240101T000000.000000Z`. Rotation is not supported with `batch_encoding`.
+			"""
+		required: false
+		type: object: options: {
+			compression: {
+				description: """
+					The compression applied to the rotated files.
+
+					Must be `none` when the sink already compresses the files it writes.
+					"""
+				required: false
+				type: string: {
+					default: "none"
+					enum: {
+						gzip: """
+							[Gzip][gzip] compression.
+
+							[gzip]: https://www.gzip.org/
+							"""
+						none: "No compression."
+						zstd: """
+							[Zstandard][zstd] compression.
+
+							[zstd]: https://facebook.github.io/zstd/
+							"""
+					}
+				}
+			}
+			max_age_secs: {
+				description: """
+					The amount of time after which a file opened by Vector is rotated.
+
+					The age of the file is checked whenever an event is written to it.
+					"""
+				required: false
+				type: uint: {
+					examples: [3600]
+					unit: "seconds"
+				}
+			}
+			max_bytes: {
+				description: """
+					The number of bytes written to a file after which it is rotated.
+
+					With `compression` set, the bytes are counted before being compressed.
+					"""
+				required: false
+				type: uint: {
+					examples: [104857600]
+					unit: "bytes"
+				}
+			}
+			max_files: {
+				description: """
+					The maximum number of rotated files kept for each file, after which the oldest ones are
+					deleted.
+					"""
+				required: false
+				type: uint: {
+					examples: [10]
+					unit: "files"
+				}
+			}
+			retention_secs: {
+				description: "The amount of time after which a rotated file is deleted."
+				required:    false
+				type: uint: {
+					examples: [604800]
+					unit: "seconds"
+				}
+			}
+		}
+	}
 	timezone: {
 		description: """
 			Timezone to use for any date specifiers in template strings.
@@ -130,6 +130,105 @@ base: components: sinks: gcp_cloud_storage: configuration: {
 			}
 		}
 	}
+	batch_encoding: {
+		description: """
+			Encodes each batch of events as a single object, such as a Parquet file, instead of
+			encoding events one by one.
+
+			When set, the `encoding.codec` and `framing` options are ignored, while the other
+			`encoding` options still apply. Batch codecs compress their output themselves, so
+			`compression` must be set to `none`.
+			"""
+		required: false
+		type: object: options: {
+			codec: {
+				description: "The codec to use for encoding batches of events."
+				required:    true
+				type: string: enum: parquet: """
+					Encodes a batch of events as an [Apache Parquet][apache_parquet] file.
+
+					Only log events are supported.
+
+					[apache_parquet]: https://parquet.apache.org/
+					"""
+			}
+			parquet: {
+				description:   "Apache Parquet-specific encoder options."
+				relevant_when: "codec = \"parquet\""
+				required:      false
+				type: object: options: {
+					compression: {
+						description: "The compression codec applied to the pages of the written files."
+						required:    false
+						type: string: {
+							default: "snappy"
+							enum: {
+								gzip: """
+									[Gzip][gzip] compression.
+
+									[gzip]: https://www.gzip.org/
+									"""
+								none: "No compression."
+								snappy: """
+									[Snappy][snappy] compression.
+
+									[snappy]: https://google.github.io/snappy/
+									"""
+								zstd: """
+									[Zstandard][zstd] compression.
+
+									[zstd]: https://facebook.github.io/zstd/
+									"""
+							}
+						}
+					}
+					row_group_size: {
+						description: "The maximum number of events in each row group of the written files."
+						required:    false
+						type: uint: {
+							default: 10000
+							unit:    "events"
+						}
+					}
+					schema: {
+						description: """
+							The columns of the written files, in order.
+
+							Each column is read from the top-level event field of the same name. Fields missing from an
+							event are written as null.
+
+							If not set, the columns are derived from the top-level fields of the events in each batch,
+							in alphabetical order, with their types inferred from the values. Fields holding values of
+							conflicting types are written as strings.
+							"""
+						required: false
+						type: array: items: type: object: options: {
+							name: {
+								description: "The name of the column, and of the top-level event field it is read from."
+								required:    true
+								type: string: examples: ["message"]
+							}
+							type: {
+								description: "The type of the column."
+								required:    true
+								type: string: enum: {
+									boolean: "A boolean."
+									double:  "A double-precision floating point number. Integers are converted."
+									int64:   "A signed 64-bit integer."
+									string: """
+										A UTF-8 string.
+
+										Timestamps are written in RFC 3339 format, and other non-string values as JSON.
+										"""
+									timestamp: "A timestamp with microsecond precision, adjusted to UTC."
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
 	bucket: {
 		description: "The GCS bucket name."
 		required:    true
@@ -193,10 +292,58 @@ base: components: sinks: gcp_cloud_storage: configuration: {
 				description:   "Apache Avro-specific encoder options."
 				relevant_when: "codec = \"avro\""
 				required:      true
-				type: object: options: schema: {
-					description: "The Avro schema."
-					required:    true
-					type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+				type: object: options: {
+					schema: {
+						description: "The Avro schema."
+						required:    true
+						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to register the schema in.
+
+							When set, the schema is registered under the `subject` of the registry before the first
+							event is encoded, and messages are prefixed with its ID, following the Confluent wire format.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
@@ -467,7 +614,8 @@ base: components: sinks: gcp_cloud_storage: configuration: {
 		description: """
 			The filename extension to use in the object key.
 
-			If not specified, the extension is determined by the compression scheme used.
+			If not specified, the extension is determined by the batch codec or the compression
+			scheme used.
 			"""
 		required: false
 		type: string: {}
@@ -711,6 +859,36 @@ base: components: sinks: gcp_cloud_storage: configuration: {
 					unit:    "retries"
 				}
 			}
+			retry_budget: {
+				description: """
+					A budget limiting the fraction of requests that are retries.
+
+					By default, retries are only limited by `retry_attempts`.
+					"""
+				required: false
+				type: object: options: {
+					min_retries_per_second: {
+						description: """
+																The number of retries per second allowed regardless of `ratio`, so that sinks sending few
+																requests are still able to retry them.
+																"""
+						required: false
+						type: uint: {
+							default: 10
+							unit:    "retries"
+						}
+					}
+					ratio: {
+						description: """
+																The number of retries allowed per request, over the last 10 seconds.
+
+																For example, `0.2` allows one retry for every five requests.
+																"""
+						required: false
+						type: float: default: 0.2
+					}
+				}
+			}
 			retry_initial_backoff_secs: {
 				description: """
 					The amount of time to wait before attempting the first retry for a failed request.
@@ -90,10 +90,58 @@ base: components: sinks: kafka: configuration: {
 				description:   "Apache Avro-specific encoder options."
 				relevant_when: "codec = \"avro\""
 				required:      true
-				type: object: options: schema: {
-					description: "The Avro schema."
-					required:    true
-					type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+				type: object: options: {
+					schema: {
+						description: "The Avro schema."
+						required:    true
+						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to register the schema in.
+
+							When set, the schema is registered under the `subject` of the registry before the first
+							event is encoded, and messages are prefixed with its ID, following the Confluent wire format.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
@@ -357,6 +405,16 @@ base: components: sinks: kafka: configuration: {
 		required: false
 		type: string: {}
 	}
+	idempotence: {
+		description: """
+			Enables the idempotent producer, which ensures that the retries of the producer don't
+			write duplicates of the events, and keeps their order within each partition.
+
+			This is always enabled when `transaction` is set.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	key_field: {
 		description: """
 			The log field name or tag key to use for the topic key.
@@ -560,4 +618,63 @@ base: components: sinks: kafka: configuration: {
 			syntax: "template"
 		}
 	}
+	transaction: {
+		description: """
+			Settings of the transactional producer, which writes the events in transactions.
+
+			A transaction is only committed once all its events are written, and the events are only
+			acknowledged once their transaction is committed, so consumers reading with the
+			`read_committed` isolation level never see the events of aborted transactions.
+
+			The events read by a `kafka` source have their offsets committed within the transaction they
+			are written in, so that they're written exactly once, provided that the source has
+			acknowledgements enabled and that the events keep their topic, partition and offset fields.
+			"""
+		required: false
+		type: object: options: {
+			commit_interval_ms: {
+				description: """
+					The maximum time, in milliseconds, events are gathered for before their transaction is
+					committed.
+					"""
+				required: false
+				type: uint: {
+					default: 1000
+					unit:    "milliseconds"
+				}
+			}
+			id: {
+				description: """
+					The transactional ID of the producer.
+
+					It must be unique to each instance of the sink, and stay the same across restarts, so that
+					Kafka fences off the transactions left in progress by previous instances.
+					"""
+				required: true
+				type: string: examples: ["vector-${HOSTNAME}"]
+			}
+			max_events: {
+				description: "The maximum number of events written in a transaction."
+				required:    false
+				type: uint: {
+					default: 1000
+					unit:    "events"
+				}
+			}
+			timeout_ms: {
+				description: """
+					The maximum time, in milliseconds, a transaction can be in progress for before Kafka aborts
+					it.
+
+					The `message_timeout_ms` option is lowered to this timeout if it's greater, as required by
+					`librdkafka`.
+					"""
+				required: false
+				type: uint: {
+					default: 60000
+					unit:    "milliseconds"
+				}
+			}
+		}
+	}
 }
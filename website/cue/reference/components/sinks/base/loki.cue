@@ -27,6 +27,28 @@ base: components: sinks: loki: configuration: {
 			type: bool: {}
 		}
 	}
+	api: {
+		description: "The Loki API that events are sent to."
+		required:    false
+		type: string: {
+			default: "push"
+			enum: {
+				otlp: """
+					The native OTLP endpoint of Loki.
+
+					Events are sent as OpenTelemetry log records encoded as JSON. Labels are sent as resource
+					attributes, and structured metadata as attributes of the log records. Loki indexes the
+					resource attributes configured in its `otlp_config` as labels, and stores the others as
+					structured metadata.
+
+					Snappy compression is not supported by this endpoint.
+
+					Requires Loki 3.0.0 or newer.
+					"""
+				push: "The push API of Loki."
+			}
+		}
+	}
 	auth: {
 		description: """
 			Configuration of the authentication strategy for HTTP requests.
@@ -150,10 +172,58 @@ base: components: sinks: loki: configuration: {
 				description:   "Apache Avro-specific encoder options."
 				relevant_when: "codec = \"avro\""
 				required:      true
-				type: object: options: schema: {
-					description: "The Avro schema."
-					required:    true
-					type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+				type: object: options: {
+					schema: {
+						description: "The Avro schema."
+						required:    true
+						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to register the schema in.
+
+							When set, the schema is registered under the `subject` of the registry before the first
+							event is encoded, and messages are prefixed with its ID, following the Confluent wire format.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
@@ -467,8 +537,13 @@ base: components: sinks: loki: configuration: {
 		}
 	}
 	path: {
-		description: "The path to use in the URL of the Loki instance."
-		required:    false
+		description: """
+			The path to use in the URL of the Loki instance.
+
+			When `api` is set to `otlp` and this is left to its default, `/otlp/v1/logs` is used
+			instead.
+			"""
+		required: false
 		type: string: default: "/loki/api/v1/push"
 	}
 	remove_label_fields: {
@@ -621,6 +696,36 @@ base: components: sinks: loki: configuration: {
 					unit:    "retries"
 				}
 			}
+			retry_budget: {
+				description: """
+					A budget limiting the fraction of requests that are retries.
+
+					By default, retries are only limited by `retry_attempts`.
+					"""
+				required: false
+				type: object: options: {
+					min_retries_per_second: {
+						description: """
+																The number of retries per second allowed regardless of `ratio`, so that sinks sending few
+																requests are still able to retry them.
+																"""
+						required: false
+						type: uint: {
+							default: 10
+							unit:    "retries"
+						}
+					}
+					ratio: {
+						description: """
+																The number of retries allowed per request, over the last 10 seconds.
+
+																For example, `0.2` allows one retry for every five requests.
+																"""
+						required: false
+						type: float: default: 0.2
+					}
+				}
+			}
 			retry_initial_backoff_secs: {
 				description: """
 					The amount of time to wait before attempting the first retry for a failed request.
@@ -33,8 +33,13 @@ base: components: sinks: mqtt: configuration: {
 		type: bool: default: false
 	}
 	client_id: {
-		description: "MQTT client ID."
-		required:    false
+		description: """
+			MQTT client ID.
+
+			If unset, a random client ID is generated. Set a fixed client ID, together with
+			`clean_session` disabled, to resume the session with the broker after a restart.
+			"""
+		required: false
 		type: string: {}
 	}
 	encoding: {
@@ -45,10 +50,58 @@ base: components: sinks: mqtt: configuration: {
 				description:   "Apache Avro-specific encoder options."
 				relevant_when: "codec = \"avro\""
 				required:      true
-				type: object: options: schema: {
-					description: "The Avro schema."
-					required:    true
-					type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+				type: object: options: {
+					schema: {
+						description: "The Avro schema."
+						required:    true
+						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to register the schema in.
+
+							When set, the schema is registered under the `subject` of the registry before the first
+							event is encoded, and messages are prefixed with its ID, following the Confluent wire format.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
@@ -313,6 +366,17 @@ base: components: sinks: mqtt: configuration: {
 		required:    false
 		type: uint: default: 1883
 	}
+	protocol_version: {
+		description: "Supported versions of the MQTT protocol."
+		required:    false
+		type: string: {
+			default: "v3_1_1"
+			enum: {
+				v3_1_1: "MQTT 3.1.1."
+				v5:     "MQTT 5.0."
+			}
+		}
+	}
 	quality_of_service: {
 		description: "Supported Quality of Service types for MQTT."
 		required:    false
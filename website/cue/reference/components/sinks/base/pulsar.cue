@@ -129,10 +129,58 @@ base: components: sinks: pulsar: configuration: {
 				description:   "Apache Avro-specific encoder options."
 				relevant_when: "codec = \"avro\""
 				required:      true
-				type: object: options: schema: {
-					description: "The Avro schema."
-					required:    true
-					type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+				type: object: options: {
+					schema: {
+						description: "The Avro schema."
+						required:    true
+						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to register the schema in.
+
+							When set, the schema is registered under the `subject` of the registry before the first
+							event is encoded, and messages are prefixed with its ID, following the Confluent wire format.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
@@ -386,6 +434,31 @@ base: components: sinks: pulsar: configuration: {
 		required: true
 		type: string: examples: ["pulsar://127.0.0.1:6650"]
 	}
+	key_shared: {
+		description: """
+			Whether to route events for consumers of `Key_Shared` subscriptions.
+
+			When enabled, the partition key is also set as the ordering key of each message, so that
+			the broker dispatches all messages sharing a key to the same consumer. As the producer
+			batches messages regardless of their key, which would dispatch a whole batch to the
+			consumer of its first key, this can't be combined with `batch` settings.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	partition_key: {
+		description: """
+			The partition key to use for each event, rendered from a template.
+
+			Takes the place of `partition_key_field` when the key is built from multiple fields or
+			static text. Events for which the template fails to render are dropped.
+			"""
+		required: false
+		type: string: {
+			examples: ["{{ tenant }}-{{ host }}"]
+			syntax: "template"
+		}
+	}
 	partition_key_field: {
 		description: """
 			The log field name or tags key to use for the partition key.
@@ -413,6 +486,35 @@ base: components: sinks: pulsar: configuration: {
 		required: false
 		type: string: {}
 	}
+	schema: {
+		description: """
+			Schema registered with the topics written to.
+
+			Pulsar rejects messages from producers whose schema is incompatible with the schema of the
+			topic, so registering it lets the broker enforce it and consumers decode messages with it.
+			"""
+		required: false
+		type: object: options: {
+			definition: {
+				description: """
+					The schema definition, in the Avro schema JSON format.
+
+					Pulsar also uses this format to describe the structure of JSON messages. Defaults to the
+					schema of the `avro` encoding for Avro schemas, and is required for JSON schemas.
+					"""
+				required: false
+				type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+			}
+			type: {
+				description: "Supported schema types for Pulsar."
+				required:    true
+				type: string: enum: {
+					avro: "Avro messages, requiring the `avro` encoding."
+					json: "JSON messages, requiring the `json` encoding."
+				}
+			}
+		}
+	}
 	topic: {
 		description: "The Pulsar topic name to write events to."
 		required:    true
@@ -0,0 +1,87 @@
+package metadata
+
+components: sinks: clickhouse_native: {
+	title: "ClickHouse (native)"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Yandex"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.clickhouse
+
+				interface: {
+					socket: {
+						api: {
+							title: "ClickHouse native protocol"
+							url:   urls.clickhouse_native
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.clickhouse_native.configuration
+
+	how_it_works: {
+		column_types: {
+			title: "Column types"
+			body: """
+				Before each insert the sink reads the table structure sent by the server and encodes
+				event fields into the matching column types. Integer, floating point, `Bool`, `String`,
+				`FixedString`, `Date`, `Date32`, `DateTime`, `DateTime64`, `UUID`, `IPv4`, `IPv6`,
+				`Enum8` and `Enum16` columns are supported, as well as `Nullable` and `Array` of those.
+				Inserting into a table with any other column type is rejected.
+				"""
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+}
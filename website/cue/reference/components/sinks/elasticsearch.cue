@@ -127,11 +127,22 @@ components: sinks: elasticsearch: {
 				typed. To change this behavior, refer to the Elasticsearch [`ignore_malformed`
 				setting](\(urls.elasticsearch_ignore_malformed)).
 
+				Each document of a bulk request is acknowledged on its own, according to the status Elasticsearch
+				reports for it. Documents rejected by Elasticsearch, such as those failing to match the index
+				mapping, are rejected, and so can be sent to the `rejected` output of the sink when
+				`reroute_rejected` is enabled. Each failed document is counted by the
+				`elasticsearch_bulk_item_errors_total` metric, tagged with its error type and status.
+
 				By default, partial failures are not retried. To enable retries, set `request_retry_partial`. Once enabled it will
-				retry whole partially failed requests. As such it is advised to use `id_key` to avoid duplicates.
+				retry only the documents which failed with a retriable error, such as `429 Too Many Requests`. As
+				a retried request may have been partially written, it is advised to use `id_key` to avoid duplicates.
 				"""
 		}
 
 		aws_authentication: components._aws.how_it_works.aws_authentication
 	}
+
+	telemetry: metrics: {
+		elasticsearch_bulk_item_errors_total: components.sources.internal_metrics.output.metrics.elasticsearch_bulk_item_errors_total
+	}
 }
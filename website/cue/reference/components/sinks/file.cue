@@ -73,5 +73,22 @@ components: sinks: file: {
 				disk before acknowledging the events.
 				"""
 		}
+
+		rotation: {
+			title: "File Rotation & Retention"
+			body: """
+				With `rotation` set, a file is closed and renamed once the bytes
+				written to it exceed `rotation.max_bytes`, or once it has been open
+				for longer than `rotation.max_age_secs`. The time of the rotation is
+				appended to the name of the rotated file, and the next events are
+				written to a new file at the original path.
+
+				Rotated files can be compressed with `rotation.compression`, and are
+				deleted once more than `rotation.max_files` of them are kept for a
+				file, or once they are older than `rotation.retention_secs`. This
+				lets Vector archive events locally without an external tool such as
+				`logrotate`.
+				"""
+		}
 	}
 }
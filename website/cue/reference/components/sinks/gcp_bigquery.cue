@@ -0,0 +1,121 @@
+package metadata
+
+components: sinks: gcp_bigquery: {
+	title: "GCP BigQuery"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["GCP"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    9_000_000
+				max_events:   10_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.gcp_bigquery
+
+				interface: {
+					socket: {
+						api: {
+							title: "BigQuery Storage Write API"
+							url:   urls.gcp_bigquery_storage_write_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.gcp_bigquery.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		row_serialization: {
+			title: "Row serialization"
+			body: """
+				Each event is serialized as the Protobuf message configured with `schema`, whose
+				fields are mapped to the columns of the table with the same names. The descriptor
+				of the message is sent along with the rows, so it must be self-contained: messages
+				of nested fields must be declared inside of it. Events that can't be serialized as
+				the message are rejected.
+				"""
+		}
+		write_modes: {
+			title: "Write modes"
+			body: """
+				By default, rows are appended to the default stream of the table, and can be
+				queried as soon as they are written. Rows of requests that are retried after a
+				failure whose outcome is unknown, such as a timeout, may be written more than once.
+
+				With `write_mode` set to `committed`, Vector creates its own committed stream and
+				appends each batch at an explicit offset. A retried batch whose rows were already
+				written is recognized by its offset and not written again. When a batch can't be
+				appended because an earlier batch was never written, Vector finalizes the stream
+				and continues in a new one.
+
+				With `write_mode` set to `pending`, each batch is appended to its own pending
+				stream, which is committed once the batch is written, so the rows of a batch
+				become visible at once, and only once.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "gcp"
+			_service: "bigquery"
+
+			policies: [
+				{
+					_action: "tables.get"
+					required_for: ["healthcheck"]
+				},
+				{
+					_action: "tables.updateData"
+					required_for: ["operation"]
+				},
+			]
+		},
+	]
+}
@@ -66,7 +66,35 @@ components: sinks: kafka: {
 		traces: false
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		idempotent_and_transactional_writes: {
+			title: "Idempotent and transactional writes"
+			body: """
+				By default, the events written by the sink can be duplicated when the producer retries
+				them, or when they are sent again after Vector restarts. Setting `idempotence` prevents
+				the duplicates caused by the retries of the producer.
+
+				Setting `transaction` enables the transactional producer, which writes the events in
+				transactions of up to `transaction.max_events` events. Transactions are committed every
+				`transaction.commit_interval_ms` milliseconds, and the events are only acknowledged once
+				their transaction is committed. Consumers reading the topic with the `read_committed`
+				isolation level never see the events of transactions that failed, or that were in
+				progress when Vector stopped.
+
+				When the events are read by a `kafka` source, the offsets they were consumed at are
+				committed within the transaction they are written in, with the consumer group of the
+				source. Either both the events and their offsets are committed, or neither is and the
+				events are consumed again, so the events are written exactly once. This requires the
+				source to have acknowledgements enabled, so that it doesn't commit the offsets before the
+				events are written, and the events to keep the topic, partition and offset fields set by
+				the source. Events whose fields were removed are delivered at-least-once.
+
+				The transactional ID set with `transaction.id` must be unique to each instance of the sink,
+				and remain the same across restarts, so that Kafka aborts the transactions left in progress
+				by the previous instance.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		kafka_queue_messages:                components.sources.internal_metrics.output.metrics.kafka_queue_messages
@@ -156,6 +156,30 @@ components: sinks: loki: {
 				on the configured `compression`.
 				"""
 		}
+
+		otlp_endpoint: {
+			title: "OTLP Endpoint"
+			body: """
+				Loki 3.0 and newer can also receive logs on its native OTLP endpoint. With `api` set
+				to `otlp`, the sink sends each batch to `/otlp/v1/logs` as an OTLP JSON request:
+				each stream becomes an OpenTelemetry resource whose attributes are the labels of
+				the stream, and each event becomes a log record whose attributes are its
+				`structured_metadata`.
+
+				Loki decides which resource attributes are indexed as labels following its
+				`otlp_config`, storing the others as structured metadata. Out-of-order events must
+				be accepted by Loki for this endpoint, which is the default since Loki 2.4.0.
+				"""
+		}
+
+		multi_tenancy: {
+			title: "Multi-tenancy"
+			body: """
+				Events are batched separately for each tenant when `tenant_id` is templated, so that
+				every request carries the `X-Scope-OrgID` header of the tenant of its events.
+				Events whose `tenant_id` fails to render are sent without a tenant.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
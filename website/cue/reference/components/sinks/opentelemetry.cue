@@ -0,0 +1,124 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Exports logs, metrics, and traces to an [OpenTelemetry](\(urls.opentelemetry)) collector or
+		any endpoint accepting the [OpenTelemetry protocol](\(urls.opentelemetry_protocol)) over gRPC.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          false
+		}
+		traces: true
+	}
+
+	configuration: base.components.sinks.opentelemetry.configuration
+
+	how_it_works: {
+		logs: {
+			title: "Log mapping"
+			body: """
+				Logs are exported as OTLP log records, whose fields are read from the fields of the logs
+				set by the `logs.*` options. The defaults match the layout of the logs emitted by the
+				`opentelemetry` source, and the `logs.resource_attributes` option allows setting resource
+				attributes from the [semantic conventions](\(urls.opentelemetry)/docs/specs/semconv/),
+				such as `service.name`, from arbitrary fields.
+				"""
+		}
+		metrics: {
+			title: "Metric mapping"
+			body: """
+				Metrics are exported according to their type:
+
+				* Counters become monotonic sums.
+				* Absolute gauges become gauges, while incremental gauges become non-monotonic sums.
+				* Aggregated histograms become histograms.
+				* Aggregated summaries become summaries.
+				* Sketches and distributions become exponential histograms.
+
+				Incremental metrics have a delta aggregation temporality, while absolute metrics have a
+				cumulative one. Other metric types are dropped. Tags prefixed with `resource.` become
+				resource attributes, the `scope.name` and `scope.version` tags, and the tags prefixed with
+				`scope.attributes.`, set the instrumentation scope, and all other tags become attributes of
+				the data points.
+				"""
+		}
+		traces: {
+			title: "Trace mapping"
+			body: """
+				Traces are expected to have the layout of the traces emitted by the `opentelemetry`
+				source, each trace event holding a single span.
+				"""
+		}
+		retries: {
+			title: "Retries"
+			body: """
+				Requests failing with one of the gRPC status codes the OpenTelemetry protocol defines as
+				retryable, such as `UNAVAILABLE` or `RESOURCE_EXHAUSTED`, are retried with backoff as
+				configured with the `request.*` options. Other failures aren't retried.
+				"""
+		}
+	}
+}
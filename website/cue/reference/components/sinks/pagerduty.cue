@@ -0,0 +1,105 @@
+package metadata
+
+components: sinks: pagerduty: {
+	title: "PagerDuty"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: ["PagerDuty"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.pagerduty
+
+				interface: {
+					socket: {
+						api: {
+							title: "PagerDuty Events API v2"
+							url:   urls.pagerduty_events_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.pagerduty.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		actions: {
+			title: "Actions"
+			body: """
+				Each event is sent as an event of the [Events API](\(urls.pagerduty_events_api)),
+				whose action is rendered with the `action` template: `trigger` raises an alert, or
+				updates the open alert with the same deduplication key, while `acknowledge` and
+				`resolve` update the alert whose deduplication key is rendered with `dedup_key`.
+				For example, setting `action` to `{{ alert.action }}` lets a `remap` transform
+				decide the action of each event. Events rendering an unknown action, or
+				acknowledging or resolving an alert without a `dedup_key`, are dropped.
+				"""
+		}
+		payloads: {
+			title: "Payloads"
+			body: """
+				Triggered alerts carry a payload rendered with the `summary`, `source`, `severity`,
+				`component`, `group`, and `class` templates, along with the timestamp of the event.
+				The event itself is sent as the custom details of the alert, once transformed with
+				the `encoding` options.
+				"""
+		}
+		rate_limits: {
+			title: "Rate limits"
+			body: """
+				PagerDuty [limits](\(urls.pagerduty_rate_limits)) the rate of the events of each
+				integration, so requests are limited to 120 per minute by default, as set with
+				`request.rate_limit_num` and `request.rate_limit_duration_secs`. Requests rejected
+				with a `429 Too Many Requests` status, or a server error, are retried.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -0,0 +1,99 @@
+package metadata
+
+components: sinks: plugin: {
+	title: "Plugin"
+
+	description: """
+		Runs an external plugin process and sends events to it. This lets niche integrations be
+		developed and released on their own, in any language with gRPC support, while Vector
+		handles their supervision, batching, retries, and acknowledgements.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: services.vector_plugin
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "disabled"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          true
+		}
+		traces: true
+	}
+
+	configuration: base.components.sinks.plugin.configuration
+
+	how_it_works: {
+		plugin_protocol: {
+			title: "Plugin protocol"
+			body: """
+				Plugins speak the [gRPC protocol](\(urls.vector_protocol)) of the `vector` source and sink.
+				Sink plugins serve the `vector.Vector` service on the address given to them in the
+				`VECTOR_PLUGIN_ADDRESS` environment variable. Events are sent to them with `PushEvents`
+				requests, which are retried when they fail, and acknowledged once answered successfully.
+				Plugins must also answer `HealthCheck` requests, which are used for the healthcheck of the
+				sink.
+				"""
+		}
+		supervision: {
+			title: "Supervision"
+			body: """
+				Vector runs the plugin `command` when the sink is built, so that it can answer the
+				healthcheck, and stops it along with the sink. Whenever the plugin exits, it is restarted
+				after `restart_delay_secs`, and the restart is counted by the `plugin_restarts_total`
+				metric. Events sent while the plugin restarts are retried until it is available again.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		plugin_restarts_total: components.sources.internal_metrics.output.metrics.plugin_restarts_total
+	}
+}
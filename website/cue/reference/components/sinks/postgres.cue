@@ -0,0 +1,110 @@
+package metadata
+
+components: sinks: postgres: {
+	title: "PostgreSQL"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: services.postgresql
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.postgres.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		inserts: {
+			title: "Inserts"
+			body: """
+				Each batch of events is inserted into the `table` by a single `INSERT` statement. The batch
+				is sent as a JSON array with one object per event, which is expanded into rows of the table
+				with `json_populate_recordset`, so the values are cast to the types of the columns by
+				PostgreSQL. Timestamps are sent in the RFC 3339 format, which can be inserted into
+				`timestamptz` columns.
+
+				As the batch is inserted by a single statement, it's either written completely or not at
+				all.
+				"""
+		}
+		column_mapping: {
+			title: "Column mapping"
+			body: """
+				By default, each top-level field of an event is inserted into the column of the same name,
+				and fields that have no matching column are ignored. This also inserts `NULL` into every
+				column that has no matching field, instead of its default value.
+
+				The `columns` option instead lists the columns to insert, and the event field that each of
+				them is populated from. Columns that aren't listed are left to their default values, such as
+				generated identifiers and insertion times.
+				"""
+		}
+		idempotent_writes: {
+			title: "Idempotent writes"
+			body: """
+				Batches that fail with a connection error, a serialization failure, a deadlock, or any other
+				error that doesn't depend on the inserted rows are retried. A batch can be applied by the
+				server without the response reaching Vector, so to avoid duplicate rows when it's retried,
+				add a unique constraint on an identifier of the events and set `on_conflict` to `ignore`.
+				The rows that were already written are then skipped.
+				"""
+		}
+		connection_pooling: {
+			title: "Connection pooling"
+			body: """
+				Connections to the server are kept open in a pool of up to `pool_size` connections, which
+				are shared by the concurrent requests. Connections that were closed by the server are
+				replaced when they're next needed.
+				"""
+		}
+	}
+}
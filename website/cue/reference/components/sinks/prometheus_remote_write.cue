@@ -109,5 +109,15 @@ components: sinks: prometheus_remote_write: {
 				Vector also supports using Gzip and Zstd.
 				"""
 		}
+		exemplars: {
+			title: "Exemplars"
+			body: """
+				Exemplars of histograms and summaries, such as those scraped by the `prometheus_scrape`
+				source, are sent along with the samples, allowing the receiver to correlate them with the
+				traces identified by their labels. A histogram exemplar is attached to the series of the
+				first bucket counting its value, and a summary exemplar to the `_count` series. The
+				receiver must have exemplar storage enabled to keep them.
+				"""
+		}
 	}
 }
@@ -0,0 +1,115 @@
+package metadata
+
+components: sinks: slack: {
+	title: "Slack"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Slack", "Microsoft"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   10
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.slack
+
+				interface: {
+					socket: {
+						api: {
+							title: "Slack incoming webhooks"
+							url:   urls.slack_webhooks
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.slack.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		messages: {
+			title: "Messages"
+			body: """
+				The events of each batch are posted to the webhook rendered with `uri` as a single
+				message, in which each event is rendered as a block showing its `text`, along with
+				its `fields`. As Slack renders at most 50 blocks per message, `batch.max_events` can't
+				exceed 50. Events are partitioned by their webhook before being batched, so that the
+				alerts of each team can be routed to its own channel.
+				"""
+		}
+		threads: {
+			title: "Threads"
+			body: """
+				When `thread_ts` is set, messages are posted as replies in the
+				[thread](\(urls.slack_threads)) of the message with that timestamp, and the events of
+				each batch all belong to the same thread. This lets the updates of an alert be gathered
+				under the message first posted for it.
+				"""
+		}
+		microsoft_teams: {
+			title: "Microsoft Teams"
+			body: """
+				In the `teams` mode, messages are posted to [Microsoft Teams webhooks](\(urls.microsoft_teams_webhooks))
+				as an Adaptive Card, in which each event is rendered as a text block followed by a fact set
+				of its `fields`. Threads aren't supported by Microsoft Teams webhooks.
+				"""
+		}
+		rate_limits: {
+			title: "Rate limits"
+			body: """
+				Slack [limits](\(urls.slack_rate_limits)) incoming webhooks to around one message per
+				second, so requests are limited to one per second by default, as set with
+				`request.rate_limit_num` and `request.rate_limit_duration_secs`. Requests rejected with
+				a `429 Too Many Requests` status are retried once the delay of their `Retry-After`
+				header has elapsed, up to the maximum backoff.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -0,0 +1,110 @@
+package metadata
+
+components: sinks: smtp: {
+	title: "SMTP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       true
+				max_bytes:    null
+				max_events:   1
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled:     true
+				concurrency: 1
+				headers:     false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.smtp
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.smtp.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		messages: {
+			title: "Messages"
+			body: """
+				Each batch of events is sent as a single plain text message to all of the `to`
+				addresses. The body of each event is rendered with the `body` template, and the
+				subject of the message with the `subject` template from the first event of the
+				batch. Events whose templates can't be rendered are dropped.
+				"""
+		}
+		digests: {
+			title: "Digests"
+			body: """
+				By default, each event is sent as a message of its own. To send digests instead,
+				raise `batch.max_events` and `batch.timeout_secs`, so that the events received
+				within the timeout are sent together, and limit how often messages are sent with
+				`request.rate_limit_num` and `request.rate_limit_duration_secs`. For example,
+				setting `request.rate_limit_num` to `1` and `request.rate_limit_duration_secs` to
+				`300` sends at most one message every five minutes, with events queued in the
+				meantime until the batches are full.
+				"""
+		}
+		security: {
+			title: "Transport security"
+			body: """
+				By default, the connection is upgraded to TLS with the
+				[`STARTTLS`](\(urls.smtp_starttls)) command, as done by submission servers
+				listening on port 587, and sending fails if the server doesn't support it. Set
+				`security` to `tls` for servers expecting TLS from the start, usually on port
+				465, or to `none` to send messages in the clear. The `tls` options apply to both
+				`start_tls` and `tls`.
+				"""
+		}
+		authentication: {
+			title: "Authentication"
+			body: """
+				When `auth` is set, Vector authenticates with the `PLAIN` mechanism once the
+				connection is secured. A message rejected with a transient (`4xx`) reply is
+				retried, while a permanent (`5xx`) rejection fails the events it holds.
+				"""
+		}
+	}
+}
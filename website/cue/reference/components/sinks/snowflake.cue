@@ -0,0 +1,110 @@
+package metadata
+
+components: sinks: snowflake: {
+	title: "Snowflake"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Snowflake"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.snowflake
+
+				interface: {
+					socket: {
+						api: {
+							title: "Snowpipe Streaming REST API"
+							url:   urls.snowflake_snowpipe_streaming
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The user must be assigned the public key of the pair used for
+				[key-pair authentication](\(urls.snowflake_key_pair_auth)), and a role with the
+				`INSERT` privilege on the tables and the `OPERATE` privilege on their pipes.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.snowflake.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		authentication: {
+			title: "Authentication"
+			body: """
+				Vector signs a JWT with the private key configured with `private_key_path`, which it
+				exchanges for a token scoped to the ingest host of the account. The token is
+				exchanged again every 30 minutes, and whenever a request is unauthorized.
+				"""
+		}
+		routing: {
+			title: "Routing"
+			body: """
+				Events are routed to tables with the `database`, `schema`, and `table` options,
+				which are templates, and written as newline-delimited JSON through the default
+				pipe of each table, `<table>-STREAMING`, unless another one is configured with
+				`pipe`. Events whose templates can't be rendered are dropped.
+				"""
+		}
+		channels: {
+			title: "Channels"
+			body: """
+				Rows are appended to a channel of each pipe, named after the host Vector runs on
+				by default, which orders the rows appended to it. Each Vector instance writing to
+				the same pipe must use a channel of its own, since opening a channel invalidates
+				the instances it was previously opened by. When an append fails, the channel is
+				reopened before the rows are retried, resuming from the last offset committed.
+				"""
+		}
+	}
+}
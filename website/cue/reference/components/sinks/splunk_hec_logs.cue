@@ -86,5 +86,20 @@ components: sinks: splunk_hec_logs: {
 		http_client_response_rtt_seconds: components.sources.internal_metrics.output.metrics.http_client_response_rtt_seconds
 	}
 
-	how_it_works: sinks._splunk_hec.how_it_works
+	how_it_works: sinks._splunk_hec.how_it_works & {
+		load_balancing: {
+			title: "Load balancing"
+			body:  """
+				Events can be distributed across several HEC endpoints, such as the indexers of a cluster,
+				without an external load balancer by listing the additional endpoints in
+				[`endpoints`](#endpoints). Requests are balanced across the healthy endpoints. An endpoint
+				that can't be reached or answers with server errors, like the `503` sent by indexers whose
+				queues are full, is set aside and periodically probed again, as configured by
+				[`distribution`](#distribution), while failed requests are retried on the other endpoints.
+
+				Every endpoint gets its own Splunk channel, and the indexer acknowledgements of a request are
+				polled from the endpoint it was sent to.
+				"""
+		}
+	}
 }
@@ -0,0 +1,114 @@
+package metadata
+
+components: sinks: webhook: {
+	title: "Webhook"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: false
+		send: {
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       true
+			request: {
+				enabled: true
+				headers: true
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: {
+					name:     "HTTP"
+					thing:    "an \(name) server"
+					url:      urls.http_server
+					versions: null
+				}
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.webhook.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		requests: {
+			title: "Requests"
+			body: """
+				Each event is delivered with a `POST` request of its own, with a JSON body, to the
+				URI rendered from it with the `uri` template. Unlike the `http` sink, events aren't
+				batched, as webhooks expect a single event per request. Events whose URI can't be
+				rendered into a valid URI are dropped.
+				"""
+		}
+		bodies: {
+			title: "Bodies"
+			body: """
+				By default, the event itself is sent. To send the payload expected by a service
+				instead, set either `template`, which must render valid JSON, or `mapping`, a VRL
+				program whose resulting event is sent. Since templates don't escape the values
+				they render, `mapping` should be preferred for fields that may contain quotes or
+				line breaks. Events whose bodies can't be rendered are dropped.
+				"""
+		}
+		signing: {
+			title: "Signing"
+			body: """
+				When `signing` is set, requests carry the hex encoded HMAC of their body, keyed
+				with `signing.secret` and prefixed with the name of the algorithm, in the
+				`signing.header` header, for example `X-Signature: sha256=9f86d0...`. When
+				`signing.timestamp_header` is set, the Unix time of the request is sent in that
+				header and signed along with the body, as `<timestamp>.<body>`, so that receivers
+				can reject replayed requests. Retried requests are signed again with a new
+				timestamp.
+				"""
+		}
+		rate_limits: {
+			title: "Rate limits"
+			body: """
+				Requests rejected with a `429 Too Many Requests` status, or a server error, are
+				retried. When the response carries a `Retry-After` or `X-RateLimit-Reset` header,
+				the request isn't retried before the delay it asks for has passed, up to the maximum
+				backoff set with `request.retry_max_duration_secs`. Other client errors aren't
+				retried.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -0,0 +1,160 @@
+package metadata
+
+components: sources: aws_kinesis_streams: components._aws & {
+	title: "AWS Kinesis Data Streams"
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			checkpoint: enabled: true
+			proxy: enabled:      true
+			from: service:       services.aws_kinesis_data_streams
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				Every instance of the source reads every shard of the stream, so a stream must only be
+				read by one instance, or the records are received once by each instance.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.aws_kinesis_streams.configuration & {
+		_aws_include: false
+	}
+
+	output: logs: record: {
+		description: "An individual Kinesis record."
+		fields: {
+			message: {
+				description: "The raw data of the record."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["aws_kinesis_streams"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The approximate time the record was added to the stream."
+			}
+			stream_name: {
+				description: "The name of the stream the record was read from."
+				required:    true
+				type: string: {
+					examples: ["my-stream"]
+				}
+			}
+			shard_id: {
+				description: "The ID of the shard the record was read from."
+				required:    true
+				type: string: {
+					examples: ["shardId-000000000000"]
+				}
+			}
+			sequence_number: {
+				description: "The sequence number of the record in its shard."
+				required:    true
+				type: string: {
+					examples: ["49590338271490256608559692538361571095921575989136588898"]
+				}
+			}
+			partition_key: {
+				description: "The partition key the record was added with."
+				required:    true
+				type: string: {
+					examples: ["host-1"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		resharding: {
+			title: "Resharding"
+			body: """
+				The shards of the stream are listed every `shard_discovery_interval_secs`, and each
+				shard is read by its own task with `GetRecords`. When the stream is resharded, the
+				shards a shard was split or merged from are read to their end before it starts being
+				read, from its oldest record, so the records of each partition key are received in
+				order.
+
+				Enhanced fan-out consumers, which receive records with `SubscribeToShard`, are not
+				supported.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The sequence number read up to in each shard is checkpointed every
+				`checkpoint_interval_secs` in the `data_dir`, and when Vector stops. With
+				acknowledgements enabled, only sequence numbers of records whose events were
+				delivered by every sink are checkpointed. Shards without a checkpoint are read from
+				`start_position`.
+
+				As checkpoints are periodic, records read after the last checkpoint may be received
+				again after a restart.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "aws"
+			_service: "kinesis"
+
+			policies: [
+				{
+					_action: "GetRecords"
+				},
+				{
+					_action: "GetShardIterator"
+				},
+				{
+					_action: "ListShards"
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -129,6 +129,32 @@ components: sources: aws_s3: components._aws & {
 				```
 				"""
 		}
+
+		polling: {
+			title: "Polling buckets without SQS notifications"
+			body:  """
+				When the notifications of a bucket can't be sent to an SQS queue, set `strategy`
+				to `poll` for the source to list the objects under `poll.prefix` every
+				`poll.interval_secs` instead:
+
+				```toml
+				[sources.s3]
+				type = "aws_s3"
+				strategy = "poll"
+				poll.bucket = "my-bucket"
+				poll.prefix = "logs/"
+				```
+
+				The keys and entity tags of the objects read are recorded in a checkpoint in the
+				data directory of the source, so that an object is only read again once it is
+				modified, including after Vector restarts. The checkpoint is written after every
+				listing, so objects read right before Vector stops may be read again. Objects
+				whose events fail to be delivered are read again on the next listing.
+
+				Listing a bucket costs one request per thousand objects, so prefer a narrow
+				`poll.prefix` for large buckets.
+				"""
+		}
 	}
 
 	permissions: iam: [
@@ -142,6 +168,10 @@ components: sources: aws_s3: components._aws & {
 				{
 					_action: "GetObject"
 				},
+				{
+					_action:       "ListBucket"
+					required_when: "[`strategy`](#strategy) is set to `poll`"
+				},
 			]
 		},
 		{
@@ -0,0 +1,151 @@
+package metadata
+
+components: sources: azure_event_hubs: {
+	title: "Azure Event Hubs"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.azure_event_hubs
+
+				interface: {
+					socket: {
+						api: {
+							title: "AMQP 1.0"
+							url:   urls.azure_event_hubs_amqp
+						}
+						direction: "outgoing"
+						port:      5671
+						protocols: ["tcp"]
+						ssl: "required"
+					}
+				}
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.azure_event_hubs.configuration
+
+	output: logs: record: {
+		description: "An individual Event Hubs event."
+		fields: {
+			message: {
+				description: "The raw body of the event."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["azure_event_hubs"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the event was enqueued in the Event Hub, or the current time if it is unknown."
+			}
+			partition_id: {
+				description: "The ID of the partition the event was read from."
+				required:    true
+				type: string: {
+					examples: ["0"]
+				}
+			}
+			offset: {
+				description: "The offset of the event in its partition."
+				required:    true
+				type: string: {
+					examples: ["4294971392"]
+				}
+			}
+			sequence_number: {
+				description: "The sequence number of the event in its partition."
+				required:    true
+				type: uint: {
+					examples: [42]
+					unit: null
+				}
+			}
+			partition_key: {
+				description: "The partition key the event was sent with."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["host-1"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		load_balancing: {
+			title: "Load balancing"
+			body: """
+				Vector instances reading the same consumer group share the partitions of the Event Hub
+				through claims kept in the `checkpoint_store`, in the same layout as the
+				[checkpoint stores](\(urls.azure_event_hubs_checkpoint_store)) of the Azure SDKs. Each
+				instance renews its claims every `load_balancing_interval_secs`, and claims unowned
+				partitions up to its share of them. Once every partition is claimed, instances owning
+				fewer partitions than their share take one over from the instance owning the most each
+				interval, until the partitions are evenly split. Partitions of instances that stopped
+				are taken over once their claims are older than `ownership_expiration_secs`.
+
+				Without a `checkpoint_store`, the instance reads every partition on its own.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The offset read up to in each partition is checkpointed every
+				`checkpoint_interval_secs`, and when the partition stops being read. With
+				acknowledgements enabled, only offsets of events delivered by every sink are
+				checkpointed. Partitions without a checkpoint are read from `start_position`.
+
+				As checkpoints are periodic, events read after the last checkpoint may be received
+				again after a restart or when another instance takes a partition over.
+				"""
+		}
+		authentication: components.sinks.azure_event_hubs.how_it_works.authentication
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
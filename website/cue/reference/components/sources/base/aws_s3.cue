@@ -105,7 +105,57 @@ base: components: sources: aws_s3: configuration: {
 					examples: ["develop"]
 				}
 			}
-			region: {
+			poll: {
+		description: "Configuration options for polling, when `strategy` is set to `poll`."
+		required:    false
+		type: object: options: {
+			bucket: {
+				description: "The name of the bucket to list the objects of."
+				required:    true
+				type: string: examples: ["my-bucket"]
+			}
+			data_dir: {
+				description: """
+					The directory used to persist the checkpoint of the objects read.
+
+					By default, the [global `data_dir` option][global_data_dir] is used.
+					Make sure the running user has write permissions to this directory.
+
+					[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+					"""
+				required: false
+				type: string: examples: ["/var/lib/vector"]
+			}
+			interval_secs: {
+				description: "The interval between two listings of the objects, in seconds."
+				required:    false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
+			prefix: {
+				description: """
+					The prefix of the keys of the objects to read.
+
+					By default, all the objects of the bucket are read.
+					"""
+				required: false
+				type: string: examples: ["logs/"]
+			}
+			read_existing_objects: {
+				description: """
+					Whether to read the objects already in the bucket when the source first starts.
+
+					When `false`, only the objects created or modified after the first listing are read. This
+					has no effect once the source has written its checkpoint.
+					"""
+				required: false
+				type: bool: default: true
+			}
+		}
+	}
+	region: {
 				description: """
 					The [AWS region][aws_region] to send STS requests to.
 
@@ -174,6 +224,55 @@ base: components: sources: aws_s3: configuration: {
 						required: true
 						type: bool: {}
 					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to fetch the schemas messages were written
+							with from.
+
+							When set, messages are expected to be prefixed with the ID of the schema they were written
+							with, which is fetched from the registry and cached. Messages are then resolved to `schema`,
+							following the Avro [schema resolution][schema_resolution] rules.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							[schema_resolution]: https://avro.apache.org/docs/current/specification/#schema-resolution
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
@@ -700,7 +799,28 @@ base: components: sources: aws_s3: configuration: {
 					unit: "seconds"
 				}
 			}
-			tls_options: {
+			strategy: {
+		description: "The strategy to use to consume objects from S3."
+		required:    false
+		type: string: {
+			default: "sqs"
+			enum: {
+				poll: """
+					Consumes objects by periodically listing the objects of a bucket, for buckets whose
+					notifications can't be sent to an AWS SQS queue.
+
+					The keys and entity tags of the objects read are recorded in a checkpoint, so that only the
+					objects created or modified since the previous listing are read.
+					"""
+				sqs: """
+					Consumes objects by processing bucket notification events sent to an [AWS SQS queue][aws_sqs].
+
+					[aws_sqs]: https://aws.amazon.com/sqs/
+					"""
+			}
+		}
+	}
+	tls_options: {
 				description: "TLS configuration."
 				required:    false
 				type: object: options: {
@@ -169,6 +169,55 @@ base: components: sources: aws_sqs: configuration: {
 						required: true
 						type: bool: {}
 					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to fetch the schemas messages were written
+							with from.
+
+							When set, messages are expected to be prefixed with the ID of the schema they were written
+							with, which is fetched from the registry and cached. Messages are then resolved to `schema`,
+							following the Avro [schema resolution][schema_resolution] rules.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							[schema_resolution]: https://avro.apache.org/docs/current/specification/#schema-resolution
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
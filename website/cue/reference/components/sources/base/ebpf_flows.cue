@@ -0,0 +1,41 @@
+package metadata
+
+base: components: sources: ebpf_flows: configuration: {
+	interfaces: {
+		description: """
+			The network interfaces to attach TC programs to, counting the TCP packets crossing them.
+
+			When empty, the packets of the flows are counted by kprobes of the TCP stack instead. The
+			interfaces must use Ethernet framing, such as the loopback and Ethernet interfaces.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["eth0", "lo"]
+		}
+	}
+	max_flows: {
+		description: """
+			The maximum number of flows tracked between reports.
+
+			Once reached, the least recently active flows are dropped to track new ones.
+			"""
+		required: false
+		type: uint: {
+			default: 65536
+			unit:    "flows"
+		}
+	}
+	report_interval_secs: {
+		description: """
+			The interval between reports of the flows, in seconds.
+
+			Every report includes the activity of the flows active since the previous report.
+			"""
+		required: false
+		type: uint: {
+			default: 10
+			unit:    "seconds"
+		}
+	}
+}
@@ -107,6 +107,55 @@ base: components: sources: gcp_pubsub: configuration: {
 						required: true
 						type: bool: {}
 					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to fetch the schemas messages were written
+							with from.
+
+							When set, messages are expected to be prefixed with the ID of the schema they were written
+							with, which is fetched from the registry and cached. Messages are then resolved to `schema`,
+							following the Avro [schema resolution][schema_resolution] rules.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							[schema_resolution]: https://avro.apache.org/docs/current/specification/#schema-resolution
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
@@ -472,6 +521,50 @@ base: components: sources: gcp_pubsub: configuration: {
 		required:    false
 		type: uint: default: 10
 	}
+	max_lease_extension_secs: {
+		description: """
+			The maximum amount of time, in seconds, the acknowledgement deadline of a message is
+			extended for while its events are delivered.
+
+			With acknowledgements enabled, the deadlines of messages whose events are not delivered
+			yet are extended before they expire, so that slow sinks don't cause messages to be
+			redelivered. Messages whose events are still not delivered after this time may be
+			redelivered.
+			"""
+		required: false
+		type: uint: {
+			default: 3600
+			unit:    "seconds"
+		}
+	}
+	max_outstanding_bytes: {
+		description: """
+			The maximum size, in bytes, of the messages received on a stream that are not yet
+			acknowledged.
+
+			Once reached, the stream stops receiving messages until some of its messages are
+			acknowledged.
+			"""
+		required: false
+		type: uint: {
+			default: 104857600
+			unit:    "bytes"
+		}
+	}
+	max_outstanding_messages: {
+		description: """
+			The maximum number of messages received on a stream that are not yet acknowledged.
+
+			Once reached, the stream stops receiving messages until some of its messages are
+			acknowledged, so that messages don't wait for slow sinks until their acknowledgement
+			deadline expires.
+			"""
+		required: false
+		type: uint: {
+			default: 1000
+			unit:    "messages"
+		}
+	}
 	poll_time_seconds: {
 		description: """
 			How often to poll the currently active streams to see if they
@@ -87,8 +87,13 @@ base: components: sources: host_metrics: configuration: {
 					load:       "Metrics related to the system load average."
 					memory:     "Metrics related to memory utilization."
 					network:    "Metrics related to network utilization."
+					process: """
+						Metrics related to the CPU, memory, and I/O utilization of processes.
+
+						Only available on Linux.
+						"""
 				}
-				examples: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network"]
+				examples: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network", "process"]
 			}
 		}
 	}
@@ -291,6 +296,74 @@ base: components: sources: host_metrics: configuration: {
 			}
 		}
 	}
+	process: {
+		description: """
+			Options for the process metrics collector.
+
+			This collector is only available on Linux systems. It isn't enabled by default, as it reports
+			metrics for every matching process.
+			"""
+		required: false
+		type: object: options: {
+			cgroups: {
+				description: """
+					Lists of cgroup name patterns of the processes to include or exclude in gathering usage
+					metrics.
+					"""
+				required: false
+				type: object: {
+					examples: [{
+						includes: ["system.slice/*"]
+					}]
+					options: {
+						excludes: {
+							description: """
+																Any patterns which should be excluded.
+
+																The patterns are matched using globbing.
+																"""
+							required: false
+							type: array: items: type: string: {}
+						}
+						includes: {
+							description: """
+																Any patterns which should be included.
+
+																The patterns are matched using globbing.
+																"""
+							required: false
+							type: array: items: type: string: {}
+						}
+					}
+				}
+			}
+			cmdlines: {
+				description: """
+					Regular expressions matched against the command lines of processes, with their arguments
+					separated by spaces.
+
+					If set, only the processes whose command line matches one of the expressions are reported.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["--config /etc/app"]
+				}
+			}
+			names: {
+				description: """
+					Regular expressions matched against the names of processes.
+
+					If set, only the processes whose name matches one of the expressions are reported.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["^nginx$"]
+				}
+			}
+		}
+	}
 	scrape_interval_secs: {
 		description: "The interval between metric gathering, in seconds."
 		required:    false
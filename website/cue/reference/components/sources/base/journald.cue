@@ -129,6 +129,39 @@ base: components: sources: journald: configuration: {
 			}
 		}
 	}
+	include_match_groups: {
+		description: """
+			A list of groups of field/value pairs to monitor, passed to `journalctl` as [matches][matches].
+
+			An entry is included if it matches all the fields of any of the groups, an entry matching a
+			field of a group if its value for the field is one of the values of the group. For example,
+			this includes the entries of a unit of the priorities from emergency to error, along with
+			all the entries of the kernel.
+
+			As the entries are filtered by the journal, this is more efficient than `include_matches`,
+			which is applied to the entries read. The entries also need to match `include_matches` and
+			`include_units`, if specified.
+
+			[matches]: https://www.freedesktop.org/software/systemd/man/latest/journalctl.html#Description
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: {
+				examples: [{
+					"PRIORITY": ["0", "1", "2", "3"]
+					"_SYSTEMD_UNIT": ["sshd.service"]
+				}, {
+					"_TRANSPORT": ["kernel"]
+				}]
+				options: "*": {
+					description: "The set of field values to match in journal entries that are to be included."
+					required:    true
+					type: array: items: type: string: {}
+				}
+			}
+		}
+	}
 	include_units: {
 		description: """
 			A list of unit names to monitor.
@@ -152,12 +185,31 @@ base: components: sources: journald: configuration: {
 		required: false
 		type: string: {}
 	}
+	journal_files: {
+		description: """
+			The journal files to read, such as the archives of `systemd-journal-remote`.
+
+			The paths are passed to `journalctl` through the [`--file` option][journalctl-file-option],
+			supporting glob patterns. The entries of all the files are merged, so a single source can
+			read the journals received from several remote machines. If set, `journal_directory` must
+			not be set.
+
+			[journalctl-file-option]: https://www.freedesktop.org/software/systemd/man/latest/journalctl.html#--file=GLOB
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["/var/log/journal/remote/*.journal"]
+		}
+	}
 	journal_namespace: {
 		description: """
 			The [journal namespace][journal-namespace].
 
 			This value is passed to `journalctl` through the [`--namespace` option][journalctl-namespace-option].
-			If not set, `journalctl` uses the default namespace.
+			If not set, `journalctl` uses the default namespace. The entries of all namespaces are read
+			with `*`, and the entries of a namespace along with the ones of the default namespace with
+			the name of the namespace prefixed with `+`.
 
 			[journal-namespace]: https://www.freedesktop.org/software/systemd/man/systemd-journald.service.html#Journal%20Namespaces
 			[journalctl-namespace-option]: https://www.freedesktop.org/software/systemd/man/journalctl.html#--namespace=NAMESPACE
@@ -85,6 +85,55 @@ base: components: sources: kafka: configuration: {
 						required: true
 						type: bool: {}
 					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to fetch the schemas messages were written
+							with from.
+
+							When set, messages are expected to be prefixed with the ID of the schema they were written
+							with, which is fetched from the registry and cached. Messages are then resolved to `schema`,
+							following the Avro [schema resolution][schema_resolution] rules.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							[schema_resolution]: https://avro.apache.org/docs/current/specification/#schema-resolution
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
@@ -529,6 +578,80 @@ base: components: sources: kafka: configuration: {
 			examples: ["partition"]
 		}
 	}
+	partitions: {
+		description: """
+			The partitions to read events from, rather than those assigned by the consumer group.
+
+			These partitions are consumed regardless of the other consumers of the group, and are
+			never revoked from the source. Offsets are still committed for `group_id`, so that the
+			`committed` start position resumes from them.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				partition: {
+					description: "The number of the partition within its topic."
+					required:    true
+					type: int: examples: [0]
+				}
+				start: {
+					description: """
+						The position the consumption of a partition starts from.
+
+						The position is applied each time the source starts, so only `committed` resumes from the
+						progress of previous runs.
+						"""
+					required: false
+					type: object: options: {
+						from: {
+							description: "Where the consumption starts."
+							required:    false
+							type: string: {
+								default: "committed"
+								enum: {
+									beginning: "The oldest message of the partition."
+									committed: """
+										The offset committed for the consumer group, or as set by `auto_offset_reset` when there
+										is none.
+										"""
+									end: "The next message produced to the partition."
+									offset: "The message at the given offset."
+									timestamp: """
+										The first message whose timestamp is at or after the given time.
+
+										Consumption starts from the end of the partition when no message is that recent.
+										"""
+								}
+							}
+						}
+						offset: {
+							description: """
+								The offset of the message.
+
+								Negative offsets count back from the end of the partition, so that `-100` starts from
+								the last 100 messages.
+								"""
+							relevant_when: "from = \"offset\""
+							required:      true
+							type: int: examples: [1234]
+						}
+						timestamp: {
+							description:   "The time consumption starts from."
+							relevant_when: "from = \"timestamp\""
+							required:      true
+							type: string: examples: ["2024-01-01T00:00:00Z"]
+						}
+					}
+				}
+				topic: {
+					description: "The topic of the partition."
+					required:    true
+					type: string: examples: ["topic-1"]
+				}
+			}
+		}
+	}
 	sasl: {
 		description: "Configuration for SASL authentication when interacting with Kafka."
 		required:    false
@@ -710,8 +833,14 @@ base: components: sources: kafka: configuration: {
 			The Kafka topics names to read events from.
 
 			Regular expression syntax is supported if the topic begins with `^`.
+
+			The partitions of these topics are assigned to the source by the consumer group. Exactly one
+			of `topics` and `partitions` must be set.
 			"""
-		required: true
-		type: array: items: type: string: examples: ["^(prefix1|prefix2)-.+", "topic-1", "topic-2"]
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["^(prefix1|prefix2)-.+", "topic-1", "topic-2"]
+		}
 	}
 }
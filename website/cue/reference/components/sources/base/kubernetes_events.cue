@@ -0,0 +1,59 @@
+package metadata
+
+base: components: sources: kubernetes_events: configuration: {
+	field_selector: {
+		description: """
+			Specifies the [field selector][field_selector] to filter the Events with.
+
+			[field_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/
+			"""
+		required: false
+		type: string: {
+			default: ""
+			examples: ["type=Warning", "involvedObject.kind=Pod,reason!=Scheduled"]
+		}
+	}
+	kube_config_file: {
+		description: """
+			Optional path to a readable [kubeconfig][kubeconfig] file.
+
+			If not set, a connection to Kubernetes is made using the in-cluster configuration.
+
+			[kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+			"""
+		required: false
+		type: string: examples: ["/path/to/.kube/config"]
+	}
+	label_selector: {
+		description: """
+			Specifies the [label selector][label_selector] to filter the Events with.
+
+			[label_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/#label-selectors
+			"""
+		required: false
+		type: string: {
+			default: ""
+			examples: ["my_custom_label!=my_value"]
+		}
+	}
+	namespace: {
+		description: """
+			The [Namespace][namespace] to watch the Events of.
+
+			If not set, the Events of all Namespaces are watched.
+
+			[namespace]: https://kubernetes.io/docs/concepts/overview/working-with-objects/namespaces/
+			"""
+		required: false
+		type: string: examples: ["kube-system"]
+	}
+	read_existing_events: {
+		description: """
+			Whether to emit the Events existing when the source starts.
+
+			By default, only the Events created or updated once the source started are emitted.
+			"""
+		required: false
+		type: bool: default: false
+	}
+}
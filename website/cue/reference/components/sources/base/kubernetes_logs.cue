@@ -448,4 +448,15 @@ base: components: sources: kubernetes_logs: configuration: {
 		required:    false
 		type: bool: default: false
 	}
+	use_pod_parser_annotation: {
+		description: """
+			Whether to parse the messages of the Pods annotated with a parser.
+
+			The messages of the Pods with the `vector.dev/parser` annotation set to `json` are parsed
+			as JSON objects, once the partial events are merged. The messages failing to be parsed are
+			forwarded unparsed.
+			"""
+		required: false
+		type: bool: default: false
+	}
 }
@@ -1,6 +1,27 @@
 package metadata
 
 base: components: sources: nats: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
 	auth: {
 		description: "Configuration of the authentication strategy when interacting with NATS."
 		required:    false
@@ -128,6 +149,55 @@ base: components: sources: nats: configuration: {
 						required: true
 						type: bool: {}
 					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to fetch the schemas messages were written
+							with from.
+
+							When set, messages are expected to be prefixed with the ID of the schema they were written
+							with, which is fetched from the registry and cached. Messages are then resolved to `schema`,
+							following the Avro [schema resolution][schema_resolution] rules.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							[schema_resolution]: https://avro.apache.org/docs/current/specification/#schema-resolution
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
@@ -456,9 +526,70 @@ base: components: sources: nats: configuration: {
 			}
 		}
 	}
+	jetstream: {
+		description: """
+			Configuration for consuming messages from a [JetStream][jetstream] stream.
+
+			Messages are pulled by a durable consumer and explicitly acknowledged. When
+			[end-to-end acknowledgements][e2e_acks] are enabled, a message is only acknowledged once all of
+			its events are delivered by the sinks, and negatively acknowledged if delivery fails so that the
+			server redelivers it.
+
+			[jetstream]: https://docs.nats.io/nats-concepts/jetstream
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: {
+			ack_wait_secs: {
+				description: """
+					The time the server waits for a message to be acknowledged before redelivering it.
+
+					Only used when creating the consumer.
+					"""
+				required: false
+				type: uint: {
+					default: 30
+					unit:    "seconds"
+				}
+			}
+			durable_name: {
+				description: """
+					The name of the durable consumer to pull messages with.
+
+					The consumer is created on the stream if it doesn't exist yet, filtered on `subject`.
+					Vector instances using the same consumer share its messages.
+					"""
+				required: true
+				type: string: examples: ["vector"]
+			}
+			max_ack_pending: {
+				description: """
+					The maximum number of messages delivered to the consumer without being acknowledged.
+
+					Once reached, the server stops delivering messages until some are acknowledged. Only used
+					when creating the consumer.
+					"""
+				required: false
+				type: int: {
+					default: 1000
+					unit:    "messages"
+				}
+			}
+			stream: {
+				description: "The name of the stream to consume messages from."
+				required:    true
+				type: string: examples: ["events"]
+			}
+		}
+	}
 	queue: {
-		description: "The NATS queue group to join."
-		required:    false
+		description: """
+			The NATS queue group to join.
+
+			Not supported with `jetstream`, as the messages of a durable consumer are already shared by
+			all of its clients.
+			"""
+		required: false
 		type: string: {}
 	}
 	subject: {
@@ -0,0 +1,71 @@
+package metadata
+
+base: components: sources: plugin: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	address: {
+		description: """
+			The socket address to listen for the events sent by the plugin on.
+
+			The plugin is given this address in the `VECTOR_PLUGIN_ADDRESS` environment variable. It
+			_must_ include a port.
+			"""
+		required: true
+		type: string: examples: ["127.0.0.1:6100"]
+	}
+	command: {
+		description: "The command running the plugin, plus any arguments required."
+		required:    true
+		type: array: items: type: string: examples: ["/usr/local/bin/vector-plugin-example"]
+	}
+	environment: {
+		description: """
+			Custom environment variables to set when running the plugin.
+
+			This is typically used to pass options to the plugin.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				PLUGIN_LOG_LEVEL: "info"
+			}]
+			options: "*": {
+				description: "An environment variable."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
+	restart_delay_secs: {
+		description: "The amount of time, in seconds, to wait before restarting the plugin when it exits."
+		required:    false
+		type: uint: {
+			default: 5
+			unit:    "seconds"
+		}
+	}
+	working_directory: {
+		description: "The directory in which to run the plugin."
+		required:    false
+		type: string: {}
+	}
+}
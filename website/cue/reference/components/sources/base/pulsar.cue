@@ -31,10 +31,13 @@ base: components: sources: pulsar: configuration: {
 					Basic authentication name/username.
 
 					This can be used either for basic authentication (username/password) or JWT authentication.
-					When used for JWT, the value should be `token`.
+					When used for JWT, the value should be `token`, which is the default.
 					"""
-				required: true
-				type: string: examples: ["${PULSAR_NAME}", "name123"]
+				required: false
+				type: string: {
+					default: "token"
+					examples: ["${PULSAR_NAME}", "name123"]
+				}
 			}
 			oauth2: {
 				description: "OAuth2-specific authentication configuration."
@@ -134,6 +137,55 @@ base: components: sources: pulsar: configuration: {
 						required: true
 						type: bool: {}
 					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to fetch the schemas messages were written
+							with from.
+
+							When set, messages are expected to be prefixed with the ID of the schema they were written
+							with, which is fetched from the registry and cached. Messages are then resolved to `schema`,
+							following the Avro [schema resolution][schema_resolution] rules.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							[schema_resolution]: https://avro.apache.org/docs/current/specification/#schema-resolution
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
@@ -483,6 +535,65 @@ base: components: sources: pulsar: configuration: {
 		required:    false
 		type: string: examples: ["subscription_name"]
 	}
+	subscription_type: {
+		description: """
+			The type of subscription to consume topics with.
+
+			See the [Pulsar documentation][subscription_types] for how messages are dispatched to the
+			consumers of each type.
+
+			[subscription_types]: https://pulsar.apache.org/docs/concepts-messaging/#subscription-types
+			"""
+		required: false
+		type: string: {
+			default: "shared"
+			enum: {
+				exclusive: "Only a single consumer is allowed to attach to the subscription."
+				failover: """
+					Multiple consumers can attach to the subscription, but only one of them receives messages
+					at a time, the others taking over if it disconnects.
+					"""
+				key_shared: """
+					Messages are distributed across all consumers attached to the subscription, with messages
+					sharing a key always dispatched to the same consumer.
+					"""
+				shared: "Messages are distributed across all consumers attached to the subscription."
+			}
+		}
+	}
+	tls: {
+		description: "TLS options for connecting to brokers using the `pulsar+ssl` protocol."
+		required:    false
+		type: object: options: {
+			ca_file: {
+				description: """
+					Absolute path to the certificate authority file, in PEM format, used to verify the broker
+					certificates.
+					"""
+				required: false
+				type: string: examples: ["/etc/certs/ca.pem"]
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of
+					certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the broker must match its TLS certificate.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
 	topics: {
 		description: "The Pulsar topic names to read events from."
 		required:    true
@@ -0,0 +1,131 @@
+package metadata
+
+base: components: sources: snmp_trap: configuration: {
+	address: {
+		description: """
+			The socket address to listen for connections on, or `systemd{#N}` to use the Nth socket passed by
+			systemd socket activation.
+
+			If a socket address is used, it _must_ include a port.
+			"""
+		required: false
+		type: string: {
+			default: "0.0.0.0:162"
+			examples: ["0.0.0.0:9000", "systemd", "systemd#3"]
+		}
+	}
+	communities: {
+		description: """
+			The SNMPv2c communities to accept traps from.
+
+			If empty, SNMPv2c traps are accepted regardless of their community.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["public"]
+		}
+	}
+	host_key: {
+		description: """
+			Overrides the name of the log field used to add the address of the sending device to each
+			event.
+
+			The value is the IP address of the device the trap was received from.
+
+			By default, the [global `log_schema.host_key` option][global_host_key] is used.
+
+			[global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+			"""
+		required: false
+		type: string: {}
+	}
+	mib_paths: {
+		description: """
+			Paths of MIB modules, or of directories containing MIB modules, used to name object
+			identifiers.
+
+			Only the object identifier assignments of the modules are read. The objects of the SMI and
+			SNMPv2 modules, as well as the standard traps, are always known.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["/usr/share/snmp/mibs", "/etc/vector/mibs/IF-MIB.txt"]
+		}
+	}
+	receive_buffer_bytes: {
+		description: """
+			The size of the receive buffer used for the listening socket.
+
+			This should not typically needed to be changed.
+			"""
+		required: false
+		type: uint: unit: "bytes"
+	}
+	users: {
+		description: """
+			The SNMPv3 users to accept traps from.
+
+			SNMPv3 traps from any other user are rejected.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				auth: {
+					description: """
+						Authentication of the traps sent by the user.
+
+						When unset, traps of the user must be sent without authentication.
+						"""
+					required: false
+					type: object: options: {
+						passphrase: {
+							description: "The authentication passphrase of the user."
+							required:    true
+							type: string: examples: ["${SNMP_AUTH_PASSPHRASE}"]
+						}
+						protocol: {
+							description: "The authentication protocol of the user."
+							required:    true
+							type: string: enum: {
+								md5:    "HMAC-MD5-96."
+								sha:    "HMAC-SHA-96."
+								sha224: "HMAC-SHA-224."
+								sha256: "HMAC-SHA-256."
+								sha384: "HMAC-SHA-384."
+								sha512: "HMAC-SHA-512."
+							}
+						}
+					}
+				}
+				name: {
+					description: "The name of the user."
+					required:    true
+					type: string: examples: ["vector"]
+				}
+				privacy: {
+					description: """
+						Encryption of the traps sent by the user.
+
+						When unset, traps of the user must be sent unencrypted. Requires `auth` to be set.
+						"""
+					required: false
+					type: object: options: {
+						passphrase: {
+							description: "The privacy passphrase of the user."
+							required:    true
+							type: string: examples: ["${SNMP_PRIVACY_PASSPHRASE}"]
+						}
+						protocol: {
+							description: "The privacy protocol of the user."
+							required:    true
+							type: string: enum: aes: "AES-128 in CFB mode."
+						}
+					}
+				}
+			}
+		}
+	}
+}
@@ -12,6 +12,36 @@ base: components: sources: socket: configuration: {
 		required:      true
 		type: string: examples: ["0.0.0.0:9000", "systemd", "systemd#3"]
 	}
+	back_channel: {
+		description: """
+			Configuration for acknowledging events back to the sender over the connection they were
+			received on.
+
+			Once the events decoded from a window of frames have been delivered by all sinks, an
+			`ack <count>` message framed with octet counting, as described in [RFC 6587][rfc6587], is
+			written back to the sender. If they could not be delivered, a `nack <count>` message is written
+			instead and the connection is closed. No further frames are read from the connection until the
+			window is acknowledged, so senders waiting for acknowledgements are never ahead of the sinks by
+			more than a window.
+
+			Only enable this for senders that expect these acknowledgements.
+
+			[rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587#section-3.4.1
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: object: options: window: {
+			description: """
+				The maximum number of frames read from a connection before waiting for them to be
+				acknowledged.
+				"""
+			required: false
+			type: uint: {
+				default: 100
+				unit:    "frames"
+			}
+		}
+	}
 	connection_limit: {
 		description:   "The maximum number of TCP connections that are allowed at any given time."
 		relevant_when: "mode = \"tcp\""
@@ -48,6 +78,55 @@ base: components: sources: socket: configuration: {
 						required: true
 						type: bool: {}
 					}
+					schema_registry: {
+						description: """
+							The [Confluent Schema Registry][schema_registry] to fetch the schemas messages were written
+							with from.
+
+							When set, messages are expected to be prefixed with the ID of the schema they were written
+							with, which is fetched from the registry and cached. Messages are then resolved to `schema`,
+							following the Avro [schema resolution][schema_resolution] rules.
+
+							[schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+							[schema_resolution]: https://avro.apache.org/docs/current/specification/#schema-resolution
+							"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									Only used when encoding. With the default subject naming strategy of the Kafka serializers,
+									this is `<topic>-value`.
+									"""
+								required: false
+								type: string: examples: ["logs-value"]
+							}
+							timeout_secs: {
+								description: "The timeout for requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for HTTP basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 				}
 			}
 			codec: {
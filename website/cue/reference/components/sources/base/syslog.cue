@@ -12,6 +12,36 @@ base: components: sources: syslog: configuration: {
 		required:      true
 		type: string: examples: ["0.0.0.0:9000", "systemd", "systemd#3"]
 	}
+	back_channel: {
+		description: """
+			Configuration for acknowledging events back to the sender over the connection they were
+			received on.
+
+			Once the events decoded from a window of frames have been delivered by all sinks, an
+			`ack <count>` message framed with octet counting, as described in [RFC 6587][rfc6587], is
+			written back to the sender. If they could not be delivered, a `nack <count>` message is written
+			instead and the connection is closed. No further frames are read from the connection until the
+			window is acknowledged, so senders waiting for acknowledgements are never ahead of the sinks by
+			more than a window.
+
+			Only enable this for senders that expect these acknowledgements.
+
+			[rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587#section-3.4.1
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: object: options: window: {
+			description: """
+				The maximum number of frames read from a connection before waiting for them to be
+				acknowledged.
+				"""
+			required: false
+			type: uint: {
+				default: 100
+				unit:    "frames"
+			}
+		}
+	}
 	connection_limit: {
 		description:   "The maximum number of TCP connections that are allowed at any given time."
 		relevant_when: "mode = \"tcp\""
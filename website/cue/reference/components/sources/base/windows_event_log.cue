@@ -0,0 +1,96 @@
+package metadata
+
+base: components: sources: windows_event_log: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	batch_size: {
+		description: """
+			The maximum number of events read at once from a channel.
+
+			The bookmark of a channel is updated after each batch of events read.
+			"""
+		required: false
+		type: uint: {
+			default: 10
+			unit:    "events"
+		}
+	}
+	channels: {
+		description: """
+			The channels to read events from.
+
+			The channels of the Event Viewer, such as the `System`, `Application`, and `Security`
+			channels, as well as the operational channels of applications and services, such as
+			`Microsoft-Windows-PowerShell/Operational`, can be read.
+			"""
+		required: true
+		type: array: items: type: string: examples: ["System", "Application", "Microsoft-Windows-PowerShell/Operational"]
+	}
+	data_dir: {
+		description: """
+			The directory used to persist the bookmarks of the channels.
+
+			By default, the [global `data_dir` option][global_data_dir] is used.
+			Make sure the running user has write permissions to this directory.
+
+			If this directory is specified, then Vector will attempt to create it.
+
+			[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+			"""
+		required: false
+		type: string: examples: ["C:\\ProgramData\\vector"]
+	}
+	query: {
+		description: """
+			The [XPath query][xpath_query] selecting the events to read from every channel.
+
+			By default, all events are read.
+
+			[xpath_query]: https://learn.microsoft.com/en-us/windows/win32/wes/consuming-events#xpath-10-limitations
+			"""
+		required: false
+		type: string: {
+			default: "*"
+			examples: ["*[System[(Level=1 or Level=2 or Level=3)]]", "*[System[EventID=4624]]"]
+		}
+	}
+	read_existing_events: {
+		description: """
+			Whether to read the events already logged in the channels.
+
+			Only used for the channels without a bookmark, reading resuming after the last event read
+			from the channels with one. If `false`, only the events logged after Vector starts are read.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	render_message: {
+		description: """
+			Whether to render the message of the events from the message resources of their providers,
+			as displayed by the Event Viewer.
+
+			Events whose providers have no message resources are read without a message.
+			"""
+		required: false
+		type: bool: default: true
+	}
+}
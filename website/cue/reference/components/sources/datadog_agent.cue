@@ -83,6 +83,12 @@ components: sources: datadog_agent: {
 				If [multiple_outputs](#multiple_outputs) is enabled, received trace events will go to this output stream. Use `<component_id>.traces` as an input to downstream transforms and sinks.
 				"""
 		},
+		{
+			name: "profiles"
+			description: """
+				If [multiple_outputs](#multiple_outputs) and [accept_profiles](#accept_profiles) are enabled, received profiles will go to this output stream. Use `<component_id>.profiles` as an input to downstream transforms and sinks.
+				"""
+		},
 	]
 
 	output: {
@@ -206,6 +212,20 @@ components: sources: datadog_agent: {
 				```
 				"""
 		}
+		profile_support: {
+			title: "Profile passthrough"
+			body: """
+				When [`accept_profiles`](#accept_profiles) is enabled, the source accepts the profiles
+				forwarded by the Datadog Agent on `/api/v2/profile`. Profiles aren't decoded: each
+				payload becomes a single log event whose `message` is the raw, decompressed payload.
+				The `content_type` field of the event holds the `Content-Type` header of the request,
+				including the multipart boundary needed to forward the payload to Datadog unchanged.
+
+				Trace payloads on `/api/v0.2/traces` must be protobuf encoded, as sent by the Datadog
+				Agent. Requests with another `Content-Type`, such as the msgpack payloads sent by
+				tracers directly, are rejected with a `415 Unsupported Media Type` response.
+				"""
+		}
 		trace_support: {
 			title: "Trace support caveats"
 			body: """
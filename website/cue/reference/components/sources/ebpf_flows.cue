@@ -0,0 +1,182 @@
+package metadata
+
+components: sources: ebpf_flows: {
+	title: "eBPF Flows"
+
+	description: """
+		Attaches eBPF programs to kprobes of the TCP stack of the Linux kernel, and optionally
+		to the TC hooks of network interfaces, to report the activity of every IPv4 and IPv6
+		TCP connection of the host, along with the process sending and receiving its data,
+		without capturing packets.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		collect: {
+			checkpoint: enabled: false
+			from: service:       services.host
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-apple-darwin":            false
+			"x86_64-pc-windows-msv":          false
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+
+		requirements: [
+			"""
+				This source requires Linux 5.5 or later with BTF type information, exposed at
+				`/sys/kernel/btf/vmlinux` by kernels built with `CONFIG_DEBUG_INFO_BTF`. Vector must
+				run as root or with the `CAP_BPF` and `CAP_PERFMON` capabilities, or `CAP_SYS_ADMIN`
+				before Linux 5.8, along with `CAP_NET_ADMIN` when `interfaces` are configured.
+				""",
+			"""
+				This source isn't included in the default builds of Vector, and requires building Vector
+				with the `sources-ebpf_flows` feature. Building it requires clang and the headers of
+				libbpf, whose directory can be given with the `LIBBPF_INCLUDE_DIR` environment variable.
+				""",
+		]
+		warnings: []
+		notices: [
+			"""
+				The TC programs only count the IPv6 packets without extension headers.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.ebpf_flows.configuration
+
+	output: logs: {
+		flow: {
+			description: "The activity of a TCP connection since the previous report."
+			fields: {
+				protocol: {
+					description: "The transport protocol of the flow."
+					required:    true
+					type: string: enum: tcp: "The Transmission Control Protocol."
+				}
+				local: {
+					description: "The local endpoint of the connection, with its `address` and `port`."
+					required:    true
+					type: object: {}
+				}
+				remote: {
+					description: "The remote endpoint of the connection, with its `address` and `port`."
+					required:    true
+					type: object: {}
+				}
+				bytes_sent: {
+					description: "The bytes sent by the processes through the connection."
+					required:    true
+					type: uint: unit: "bytes"
+				}
+				bytes_received: {
+					description: "The bytes read by the processes from the connection."
+					required:    true
+					type: uint: unit: "bytes"
+				}
+				packets_sent: {
+					description: "The TCP segments transmitted, before segmentation offloading, or the TCP packets sent through the configured `interfaces`."
+					required:    true
+					type: uint: unit: null
+				}
+				packets_received: {
+					description: "The TCP segments received while the connection was established, or the TCP packets received through the configured `interfaces`."
+					required:    true
+					type: uint: unit: null
+				}
+				connect_latency_ns: {
+					description: "The time to establish the connection, for the connections established locally since the previous report."
+					required:    false
+					common:      true
+					type: uint: {
+						default: null
+						unit:    "nanoseconds"
+					}
+				}
+				process: {
+					description: "The process which last sent or read data through the connection, with its `pid` and `name`."
+					required:    false
+					common:      true
+					type: object: {}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["ebpf_flows"]
+					}
+				}
+				timestamp: fields._current_timestamp & {
+					description: "The time at which the flow was reported."
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		probes: {
+			title: "Kernel Probes"
+			body: """
+				Vector attaches eBPF programs to the `tcp_sendmsg`, `tcp_cleanup_rbuf`,
+				`tcp_connect`, and `tcp_finish_connect` functions of the kernel, and also to the
+				`__tcp_transmit_skb` and `tcp_rcv_established` functions to count packets when no
+				`interfaces` are configured. The programs update the counters of the flows in an
+				eBPF map shared with Vector, which reports and resets them at every interval. The
+				programs are detached when Vector stops.
+
+				The programs are compiled with Vector, and their accesses to the structures of the
+				kernel are relocated when loading them from the BTF type information of the running
+				kernel, so that the same programs run on every supported kernel version.
+				"""
+		}
+		traffic_control: {
+			title: "Traffic Control Programs"
+			body: """
+				When `interfaces` are configured, Vector adds a `clsact` queueing discipline to each
+				of them if it's missing, and attaches eBPF programs to their ingress and egress
+				hooks to count the IPv4 and IPv6 TCP packets crossing them, including the packets
+				of connections which don't belong to the host, such as forwarded ones. The
+				queueing discipline is left in place when Vector stops.
+				"""
+		}
+		families: {
+			title: "Address Families"
+			body: """
+				Both IPv4 and IPv6 connections are reported. The connections of IPv6 sockets with
+				IPv4-mapped addresses are reported with their IPv4 addresses, like the connections
+				of IPv4 sockets.
+				"""
+		}
+		attribution: {
+			title: "Process Attribution"
+			body: """
+				The flows are attributed to the processes sending and reading their data, as these
+				operations run in the context of the processes. Flows which only transmitted
+				segments since the previous report, such as retransmissions, have no process.
+				"""
+		}
+	}
+}
@@ -365,6 +365,38 @@ components: sources: file: {
 							a timestamp sequence.
 						"""#
 				},
+				{
+					title: "Example 4: Rules per File"
+					body: #"""
+						When the files of different applications are read by the same source,
+						each of them can be aggregated with its own rule, selected by the path
+						of the file. The files matching none of the rules are aggregated with
+						the `multiline` options, if set:
+
+						```toml
+						[sources.my_file_source]
+							type = "file"
+							include = ["/var/log/**/*.log"]
+
+							[sources.my_file_source.multiline_rules.java]
+								include = ["/var/log/java/**/*.log"]
+								multiline.start_pattern = '^[^\s]'
+								multiline.mode = "continue_through"
+								multiline.condition_pattern = '^[\s]+at '
+								multiline.timeout_ms = 1000
+
+							[sources.my_file_source.multiline]
+								start_pattern = '^\[[0-9]{4}-[0-9]{2}-[0-9]{2}'
+								mode = "halt_before"
+								condition_pattern = '^\[[0-9]{4}-[0-9]{2}-[0-9]{2}'
+								timeout_ms = 1000
+						```
+
+						The messages flushed once `timeout_ms` elapses are checkpointed like
+						the others: with end-to-end acknowledgements enabled, the lines of a
+						message are only checkpointed once the message is delivered.
+						"""#
+				},
 			]
 		}
 
@@ -167,6 +167,24 @@ components: sources: host_metrics: {
 		network_transmit_packets_drop_total: _host & _network_nomac & {description: "The number of packets dropped during transmits on this interface."}
 		network_transmit_packets_total: _host & _network_nomac & {description: "The number of packets transmitted on this interface."}
 
+		// Host processes
+		process_cpu_seconds_total: _host & _process_counter & {
+			description: "The total amount of CPU time used by this process, in seconds."
+			tags: mode: {
+				description: "Which CPU mode the time was spent in."
+				required:    true
+				enum: {
+					system: "The time spent in the kernel on behalf of the process."
+					user:   "The time spent in user space."
+				}
+			}
+		}
+		process_io_read_bytes_total: _host & _process_counter & {description: "The number of bytes read from storage by this process. Only reported for the processes Vector has the privileges to inspect."}
+		process_io_written_bytes_total: _host & _process_counter & {description: "The number of bytes written to storage by this process. Only reported for the processes Vector has the privileges to inspect."}
+		process_memory_resident_bytes: _host & _process_gauge & {description: "The amount of memory held in RAM by this process, in bytes."}
+		process_memory_virtual_bytes: _host & _process_gauge & {description: "The size of the virtual memory of this process, in bytes."}
+		process_threads: _host & _process_gauge & {description: "The number of threads of this process."}
+
 		// Helpers
 		_host: {
 			default_namespace: "host"
@@ -256,5 +274,28 @@ components: sources: host_metrics: {
 			}
 		}
 		_network_nomac: _network_gauge & {relevant_when: "OS is not macOS"}
+		_process_tags: _host_metrics_tags & {
+			collector: examples: ["process"]
+			pid: {
+				description: "The ID of the process."
+				required:    true
+				examples: ["1234"]
+			}
+			name: {
+				description: "The name of the process."
+				required:    true
+				examples: ["nginx"]
+			}
+		}
+		_process_counter: {
+			type:          "counter"
+			tags:          _process_tags
+			relevant_when: "OS is Linux"
+		}
+		_process_gauge: {
+			type:          "gauge"
+			tags:          _process_tags
+			relevant_when: "OS is Linux"
+		}
 	}
 }
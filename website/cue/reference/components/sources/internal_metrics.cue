@@ -127,25 +127,54 @@ components: sources: internal_metrics: {
 			description:       "The average round-trip time (RTT) for the current window."
 			type:              "histogram"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags: _component_tags & {
+				endpoint: {
+					description: "The endpoint the concurrency of requests is limited for, when the sink balances requests between several endpoints, each limited independently."
+					required:    false
+				}
+			}
+		}
+		adaptive_concurrency_back_pressure: {
+			description:       "Whether the current window had back pressure, recorded as `1` if it did and `0` otherwise."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              adaptive_concurrency_averaged_rtt.tags
 		}
 		adaptive_concurrency_in_flight: {
 			description:       "The number of outbound requests currently awaiting a response."
 			type:              "histogram"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags:              adaptive_concurrency_averaged_rtt.tags
 		}
 		adaptive_concurrency_limit: {
 			description:       "The concurrency limit that the adaptive concurrency feature has decided on for this current window."
 			type:              "histogram"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags:              adaptive_concurrency_averaged_rtt.tags
 		}
 		adaptive_concurrency_observed_rtt: {
 			description:       "The observed round-trip time (RTT) for requests."
 			type:              "histogram"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags:              adaptive_concurrency_averaged_rtt.tags
+		}
+		adaptive_concurrency_past_rtt_deviation: {
+			description:       "The deviation of the moving average of past round-trip times (RTT), scaled by `rtt_deviation_scale` to tell anomalous RTT increases apart."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              adaptive_concurrency_averaged_rtt.tags
+		}
+		adaptive_concurrency_past_rtt_mean: {
+			description:       "The moving average of past round-trip times (RTT) the current RTT is compared with."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              adaptive_concurrency_averaged_rtt.tags
+		}
+		adaptive_concurrency_reached_limit: {
+			description:       "Whether the current window reached the concurrency limit, recorded as `1` if it did and `0` otherwise."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              adaptive_concurrency_averaged_rtt.tags
 		}
 		checkpoints_total: {
 			description:       "The total number of files checkpointed."
@@ -217,6 +246,22 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		elasticsearch_bulk_item_errors_total: {
+			description:       "The total number of documents of bulk requests Elasticsearch failed to write."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				document_error_type: {
+					description: "The type of the error Elasticsearch reported for the document, such as `mapper_parsing_exception`."
+					required:    true
+				}
+				status: _status
+				retriable: {
+					description: "Whether the document failed with a retriable error, and so may be sent again."
+					required:    true
+				}
+			}
+		}
 		k8s_format_picker_edge_cases_total: {
 			description:       "The total number of edge cases encountered while picking format of the Kubernetes log message."
 			type:              "counter"
@@ -564,6 +609,28 @@ components: sources: internal_metrics: {
 				file: _file
 			}
 		}
+		filter_condition_events_dropped_total: {
+			description:       "The number of events dropped by a named condition of a `filter` transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				condition: {
+					description: "The name of the condition."
+					required:    true
+				}
+			}
+		}
+		filter_condition_events_matched_total: {
+			description:       "The number of events matched by a named condition of a `filter` transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				condition: {
+					description: "The name of the condition."
+					required:    true
+				}
+			}
+		}
 		grpc_server_messages_received_total: {
 			description:       "The total number of gRPC messages received."
 			type:              "counter"
@@ -688,6 +755,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		plugin_restarts_total: {
+			description:       "The total number of times a plugin was restarted after exiting."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		protobuf_decode_errors_total: {
 			description:       "The total number of [Protocol Buffers](\(urls.protobuf)) errors thrown during communication between Vector instances."
 			type:              "counter"
@@ -148,5 +148,25 @@ components: sources: journald: {
 				are replaced with the Unicode replacement character, `�`.
 				"""
 		}
+		match_groups: {
+			title: "Match Groups"
+			body: """
+				The groups of `include_match_groups` are passed to `journalctl`, which only
+				outputs the entries matching them. Within a group, an entry must match every
+				field, matching a field if its value is any of the values listed for the field.
+				An entry is read if it matches any of the groups. As the matches are applied by
+				the journal, the groups are more efficient than `include_matches` and
+				`exclude_matches`, which are applied to the entries read by Vector.
+				"""
+		}
+		remote_journals: {
+			title: "Remote Journals"
+			body: """
+				The journals received by `systemd-journal-remote` can be read by listing their
+				files, or glob patterns matching them, in `journal_files`. As the boot of these
+				journals is the boot of the remote machines, `current_boot_only` should be
+				disabled to read them.
+				"""
+		}
 	}
 }
@@ -100,5 +100,33 @@ components: sources: kafka: {
 		kafka_consumer_lag:                  components.sources.internal_metrics.output.metrics.kafka_consumer_lag
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		partition_assignment: {
+			title: "Static partition assignment"
+			body: """
+				By default, the partitions of the `topics` are assigned to the source by the consumer
+				group, which spreads them over its consumers and reassigns them as consumers join and
+				leave the group. Setting `partitions` instead assigns the listed partitions to the source,
+				which consumes them regardless of the other consumers of the group, and never has them
+				revoked. This suits deployments in which each instance of Vector owns a fixed set of
+				partitions.
+
+				Each partition starts from the position set with its `start` option: the offset committed
+				for the group, the beginning or end of the partition, a given offset, or the first message
+				at or after a given time, which makes it possible to replay a range of a topic. Positions
+				other than `committed` are applied each time the source starts.
+				"""
+		}
+		pausing_partitions: {
+			title: "Pausing partitions"
+			body: """
+				The consumption of partitions assigned to a running source can be paused and resumed
+				through the `pauseKafkaPartitions` and `resumeKafkaPartitions` mutations of the
+				[GraphQL API](\(urls.vector_api)), given the ID of the source and the partitions, once the
+				`api.kafka_partition_control` option is enabled. Pausing a partition stops fetching its
+				messages, while those already fetched are still processed. Partitions are consumed again
+				when the source restarts.
+				"""
+		}
+	}
 }
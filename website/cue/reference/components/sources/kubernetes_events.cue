@@ -0,0 +1,161 @@
+package metadata
+
+components: sources: kubernetes_events: {
+	title: "Kubernetes Events"
+
+	description: """
+		Watches the Events of the Kubernetes API, such as the scheduling, the image pulls,
+		and the restarts of the Pods, and emits them as structured logs.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service:       services.kubernetes
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				[Kubernetes](\(urls.kubernetes)) version `\(services.kubernetes.versions)` is required.
+				""",
+			"""
+				Vector must be allowed to `list` and `watch` the `events` resources, of the
+				watched Namespace or of the cluster.
+				""",
+		]
+		warnings: [
+			"""
+				Every instance of the source emits all the Events watched, so a single instance
+				should run in a cluster, rather than one instance per Node.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: "kubernetes"
+	}
+
+	configuration: base.components.sources.kubernetes_events.configuration
+
+	output: logs: event: {
+		description: "A Kubernetes Event."
+		fields: {
+			message: {
+				description: "The description of the Event."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["Back-off restarting failed container"]
+				}
+			}
+			reason: {
+				description: "The reason of the Event, in `UpperCamelCase`."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["BackOff", "Scheduled", "Pulled"]
+				}
+			}
+			type: {
+				description: "The type of the Event."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["Normal", "Warning"]
+				}
+			}
+			count: {
+				description: "The number of times the Event occurred."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					unit:    null
+				}
+			}
+			name: {
+				description: "The name of the Event object."
+				required:    false
+				common:      false
+				type: string: default: null
+			}
+			namespace: {
+				description: "The Namespace of the Event object."
+				required:    false
+				common:      true
+				type: string: default: null
+			}
+			uid: {
+				description: "The UID of the Event object."
+				required:    false
+				common:      false
+				type: string: default: null
+			}
+			involved_object: {
+				description: "The object the Event is about, with its `kind`, `name`, `namespace`, `uid`, `api_version`, and `field_path`."
+				required:    true
+				type: object: {}
+			}
+			source: {
+				description: "The component reporting the Event, with its `component`, `host`, and `instance`."
+				required:    false
+				common:      true
+				type: object: {}
+			}
+			first_timestamp: {
+				description: "The time at which the Event first occurred."
+				required:    false
+				common:      true
+				type: timestamp: {}
+			}
+			last_timestamp: {
+				description: "The time at which the Event last occurred."
+				required:    false
+				common:      true
+				type: timestamp: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["kubernetes_events"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time at which the Event last occurred, or the time at which it was received if unknown."
+			}
+		}
+	}
+
+	how_it_works: {
+		watching: {
+			title: "Watching Events"
+			body: """
+				The source lists the Events when starting, then watches their changes from the
+				resource version of the list, which is kept up to date by the bookmarks of the
+				Kubernetes API. When that version expires, the Events are listed again, and only
+				the Events created or updated since the last version received are emitted. An
+				Event occurring repeatedly is updated with an incremented `count`, and is emitted
+				at every update.
+				"""
+		}
+	}
+}
@@ -321,8 +321,8 @@ components: sources: kubernetes_logs: {
 			title: "Pod exclusion"
 			body:  """
 					By default, the [`kubernetes_logs` source](\(urls.vector_kubernetes_logs_source))
-					will skip logs from the Pods that have a `vector.dev/exclude: "true"` **label**.
-					You can configure additional exclusion rules via label or field selectors,
+					will skip logs from the Pods that have a `vector.dev/exclude: "true"` **label**
+					or **annotation**. You can configure additional exclusion rules via label or field selectors,
 					see [the available options](\(urls.vector_kubernetes_logs_source)#configuration).
 					"""
 		}
@@ -346,6 +346,24 @@ components: sources: kubernetes_logs: {
 					"""
 		}
 
+		pod_parser_annotation: {
+			title: "Pod parser annotation"
+			body:  """
+					With the `use_pod_parser_annotation` option enabled, the
+					[`kubernetes_logs` source](\(urls.vector_kubernetes_logs_source)) parses the
+					logs of the Pods selecting a parser with an **annotation**:
+
+					```yaml
+					vector.dev/parser: "json"
+					```
+
+					The messages of these Pods are parsed as JSON objects once the partial
+					messages are merged, the fields of the objects replacing the messages.
+					The messages that are not JSON objects are forwarded unparsed. `json` is
+					the only parser supported.
+					"""
+		}
+
 		kubernetes_api_communication: {
 			title: "Kubernetes API communication"
 			body:  """
@@ -0,0 +1,141 @@
+package metadata
+
+components: sources: loki: {
+	_port: 3100
+
+	title: "Loki"
+
+	description: """
+		Receives logs sent to the [Loki push API](\(urls.loki_push_api)) by clients such as Promtail
+		and Grafana Agent, so that they can forward their logs through Vector to be processed before
+		they are stored.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.loki
+
+				interface: socket: {
+					api: {
+						title: "Loki push API"
+						url:   urls.loki_push_api
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.loki.configuration
+
+	output: logs: entry: {
+		description: "A single entry of a Loki stream."
+		fields: {
+			message: fields._raw_line
+			labels: {
+				description: "The labels of the stream the entry was sent in."
+				required:    true
+				type: object: {
+					examples: [{"job": "nginx", "env": "production"}]
+					options: {}
+				}
+			}
+			structured_metadata: {
+				description: "The structured metadata of the entry, if any."
+				required:    false
+				type: object: {
+					examples: [{"trace_id": "0242ac120002"}]
+					options: {}
+				}
+			}
+			tenant_id: {
+				description: "The tenant of the entry, as set by the client in the `X-Scope-OrgID` header."
+				required:    false
+				type: string: {
+					examples: ["tenant-1"]
+				}
+			}
+			timestamp: {
+				description: "The timestamp of the entry."
+				required:    true
+				type: timestamp: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["loki"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		push_api: {
+			title: "Push API"
+			body: """
+				This source serves the `/loki/api/v1/push` endpoint, and answers successful requests with a
+				`204 No Content` response like Loki does. Requests are accepted either as snappy
+				compressed Protocol Buffers, which is what Promtail and Grafana Agent send, or as JSON
+				when their `Content-Type` is `application/json`. JSON requests can also be compressed
+				following their `Content-Encoding`.
+
+				Each entry becomes an event, carrying the labels of its stream along with its
+				structured metadata. Requests that can't be decoded are rejected with a
+				`400 Bad Request` response.
+				"""
+		}
+
+		client_configuration: {
+			title: "Client configuration"
+			body: """
+				To configure Promtail to send its logs to Vector, point one of its clients to this
+				source:
+
+				```yaml
+				clients:
+				  # update this to point to your vector instance
+				  - url: http://127.0.0.1:3100/loki/api/v1/push
+				    tenant_id: tenant-1
+				```
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		http_server_handler_duration_seconds: components.sources.internal_metrics.output.metrics.http_server_handler_duration_seconds
+		http_server_requests_received_total:  components.sources.internal_metrics.output.metrics.http_server_requests_received_total
+		http_server_responses_sent_total:     components.sources.internal_metrics.output.metrics.http_server_responses_sent_total
+	}
+}
@@ -0,0 +1,114 @@
+package metadata
+
+components: sources: mqtt: {
+	title: "MQTT"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.mqtt
+				interface: {
+					socket: {
+						direction: "outgoing"
+						port:      1883
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.mqtt.configuration
+
+	output: logs: record: {
+		description: "An individual MQTT message."
+		fields: {
+			message: {
+				description: "The raw payload of the MQTT message."
+				required:    true
+				type: string: {
+					examples: ["{\"temperature\": 21.5, \"unit\": \"celsius\"}"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["mqtt"]
+				}
+			}
+			timestamp: fields._current_timestamp
+			topic: {
+				description: "The topic the MQTT message was published to."
+				required:    true
+				type: string: {
+					examples: ["sensors/kitchen/temperature"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		reconnects: {
+			title: "Reconnects"
+			body: """
+				When the connection to the broker is lost, the source reconnects with an exponential
+				backoff of up to 30 seconds and subscribes to all topics again. With
+				`clean_session` disabled, the broker keeps the messages published in the meantime
+				and delivers them once the connection is back.
+				"""
+		}
+		shared_subscriptions: {
+			title: "Shared subscriptions"
+			body: """
+				By default, every client subscribed to a topic receives all of its messages. Setting
+				`shared_group` turns each subscription into a shared subscription, so that the broker
+				distributes messages among all Vector instances in the same group. Shared
+				subscriptions are part of MQTT 5.0, and are also supported for MQTT 3.1.1 by most
+				brokers.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -5,7 +5,7 @@ components: sources: nats: {
 
 	features: {
 		auto_generated:   true
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			from: components._nats.features.collect.from
@@ -68,5 +68,22 @@ components: sources: nats: {
 		}
 	}
 
-	how_it_works: components._nats.how_it_works
+	how_it_works: components._nats.how_it_works & {
+		jetstream: {
+			title: "JetStream"
+			body: """
+				With `jetstream` set, messages are pulled from a JetStream stream by a durable consumer,
+				which is created with an explicit acknowledgement policy if it doesn't exist yet. Since
+				the consumer keeps track of the messages delivered to it, its messages are shared by
+				all the Vector instances using it, and messages delivered while Vector is stopped are
+				received once it starts again.
+
+				Each message is acknowledged once its events are sent to the next components or, with
+				end-to-end acknowledgements enabled, once they are delivered by the sinks. Messages
+				whose delivery failed are negatively acknowledged to be redelivered right away,
+				while messages whose events were rejected by a sink are terminated. Messages that
+				aren't acknowledged within `ack_wait_secs` are redelivered by the server.
+				"""
+		}
+	}
 }
@@ -39,11 +39,7 @@ components: sources: opentelemetry: {
 
 	support: {
 		requirements: []
-		warnings: [
-			"""
-				The `opentelemetry` source only supports log and trace events at this time.
-				""",
-		]
+		warnings: []
 		notices: []
 	}
 
@@ -60,6 +56,12 @@ components: sources: opentelemetry: {
 				Received log events will go to this output stream. Use `<component_id>.logs` as an input to downstream transforms and sinks.
 				"""
 		},
+		{
+			name: "metrics"
+			description: """
+				Received metric events will go to this output stream. Use `<component_id>.metrics` as an input to downstream transforms and sinks.
+				"""
+		},
 		{
 			name: "traces"
 			description: """
@@ -69,6 +71,12 @@ components: sources: opentelemetry: {
 	]
 
 	output: {
+		metrics: {
+			counter:   output._passthrough_counter
+			gauge:     output._passthrough_gauge
+			histogram: output._passthrough_histogram
+			summary:   output._passthrough_summary
+		}
 		logs: event: {
 			description: "An individual log event from a batch of events received through an OTLP request"
 			fields: {
@@ -250,6 +258,30 @@ components: sources: opentelemetry: {
 				`/usr/local/ssl/openssl.cnf` or can be specified with the `OPENSSL_CONF` environment variable.
 				"""
 		}
+		encodings: {
+			title: "Request encodings"
+			body: """
+				Requests are accepted on the HTTP server with both the binary Protobuf encoding, with the
+				`application/x-protobuf` content type, and the JSON Protobuf encoding, with the
+				`application/json` content type. Responses use the encoding of the request.
+				"""
+		}
+		metrics: {
+			title: "Ingest OTLP metrics"
+			body: """
+				OTLP metrics are converted to Vector metrics according to their type:
+
+				* Gauges become gauges.
+				* Monotonic sums become counters, while non-monotonic sums become gauges.
+				* Histograms, including exponential histograms, become aggregated histograms.
+				* Summaries become aggregated summaries.
+
+				Metrics with a delta aggregation temporality are incremental, while all others are
+				absolute. The attributes of the data points become tags, along with the resource attributes,
+				prefixed with `resource.`, the name and version of the instrumentation scope, as `scope.name`
+				and `scope.version`, and the scope attributes, prefixed with `scope.attributes.`.
+				"""
+		}
 		traces: {
 			title: "Ingest OTLP traces"
 			body: """
@@ -0,0 +1,113 @@
+package metadata
+
+components: sources: plugin: {
+	_port: 6100
+
+	title: "Plugin"
+
+	description: """
+		Runs an external plugin process and receives the events it sends. This lets niche
+		integrations be developed and released on their own, in any language with gRPC support,
+		while Vector handles their supervision and acknowledgements.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.vector_plugin
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: false
+			keepalive: enabled:            true
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.plugin.configuration
+
+	output: {
+		logs: event: {
+			description: "An event sent by the plugin."
+			fields: {
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["plugin"]
+					}
+				}
+				"*": {
+					description: "The fields of the events sent by the plugin, which are not modified."
+					required:    true
+					type: "*": {}
+				}
+			}
+		}
+		metrics: {
+			counter:      output._passthrough_counter
+			distribution: output._passthrough_distribution
+			gauge:        output._passthrough_gauge
+			histogram:    output._passthrough_histogram
+			set:          output._passthrough_set
+		}
+	}
+
+	how_it_works: {
+		plugin_protocol: {
+			title: "Plugin protocol"
+			body: """
+				Plugins speak the [gRPC protocol](\(urls.vector_protocol)) of the `vector` source and sink.
+				Source plugins are clients of the `vector.Vector` service: they read the address Vector
+				listens on from the `VECTOR_PLUGIN_ADDRESS` environment variable, and send their events
+				with `PushEvents` requests. Vector answers each request once the events it holds are
+				delivered, when acknowledgements are enabled, so plugins should only treat their events
+				as sent after a successful response, and retry otherwise.
+				"""
+		}
+		supervision: {
+			title: "Supervision"
+			body: """
+				Vector runs the plugin `command` when the source starts, and stops it along with the
+				source. Whenever the plugin exits, it is restarted after `restart_delay_secs`, and the
+				restart is counted by the `plugin_restarts_total` metric. The standard output and error
+				of the plugin are inherited from Vector, so its logs are written along with the logs of
+				Vector.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		grpc_server_handler_duration_seconds: components.sources.internal_metrics.output.metrics.grpc_server_handler_duration_seconds
+		grpc_server_messages_received_total:  components.sources.internal_metrics.output.metrics.grpc_server_messages_received_total
+		grpc_server_messages_sent_total:      components.sources.internal_metrics.output.metrics.grpc_server_messages_sent_total
+		plugin_restarts_total:                components.sources.internal_metrics.output.metrics.plugin_restarts_total
+	}
+}
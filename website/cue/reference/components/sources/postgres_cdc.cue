@@ -0,0 +1,171 @@
+package metadata
+
+components: sources: postgres_cdc: {
+	title: "PostgreSQL CDC"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.postgresql
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The server must run PostgreSQL 11 or later, with the `wal_level` setting set to
+				`logical`.
+				""",
+		]
+		warnings: [
+			"""
+				A replication slot keeps the write-ahead log of the changes that weren't read yet, so
+				the server's disk fills up if the source stops reading from it. Drop slots that are
+				no longer used.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.postgres_cdc.configuration
+
+	output: logs: change: {
+		description: "A change made to a row of a published table."
+		fields: {
+			after: {
+				description: """
+					The values of the row after an insert or an update, keyed by column. Unchanged
+					values that are stored out of line (TOASTed) are left out.
+					"""
+				required: false
+				type: object: examples: [{"id": 1, "name": "alicia", "active": true}]
+			}
+			before: {
+				description: """
+					The values of the row before an update or a delete, keyed by column. Which
+					columns are included depends on the replica identity of the table.
+					"""
+				required: false
+				type: object: examples: [{"id": 1, "name": "alice", "active": true}]
+			}
+			lsn: {
+				description: "The log sequence number of the change."
+				required:    true
+				type: string: examples: ["0/16B3748"]
+			}
+			operation: {
+				description: "The kind of change."
+				required:    true
+				type: string: enum: {
+					insert: "A row was inserted."
+					update: "A row was updated."
+					delete: "A row was deleted."
+				}
+			}
+			schema: {
+				description: "The schema of the table."
+				required:    true
+				type: string: examples: ["public"]
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: examples: ["postgres_cdc"]
+			}
+			table: {
+				description: "The name of the table."
+				required:    true
+				type: string: examples: ["users"]
+			}
+			timestamp: {
+				description: "The time at which the transaction of the change was committed."
+				required:    true
+				type: timestamp: {}
+			}
+			transaction_id: {
+				description: "The identifier of the transaction of the change."
+				required:    true
+				type: uint: {
+					examples: [742]
+					unit: null
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		replication_slots: {
+			title: "Replication slots"
+			body: """
+				Changes are read from a logical replication slot that uses the built-in `pgoutput`
+				plugin, limited to the tables of the `publications`. It can be created with:
+
+				```sql
+				CREATE PUBLICATION vector FOR TABLE users, orders;
+				SELECT pg_create_logical_replication_slot('vector', 'pgoutput');
+				```
+
+				or by setting `create_slot`. The changes are read with
+				`pg_logical_slot_peek_binary_changes`, and the slot is only advanced past them once
+				they're delivered, so changes that fail to be delivered, or that were read when Vector
+				stopped, are read again. When acknowledgements are enabled, changes are only delivered
+				once every sink has acknowledged them.
+				"""
+		}
+		before_images: {
+			title: "Before images"
+			body: """
+				The `before` field of updates and deletes holds the columns of the replica identity of
+				the table. With the default replica identity, this is only the primary key, and it's
+				only set for updates that change it. To capture every column of the previous row, set
+				the replica identity of the table to `FULL`:
+
+				```sql
+				ALTER TABLE users REPLICA IDENTITY FULL;
+				```
+				"""
+		}
+		values: {
+			title: "Values"
+			body: """
+				Values of the `boolean`, integer, floating point, `json`, and `jsonb` types are
+				converted to the matching Vector types, and `NULL` values are kept as `null`. Values
+				of the other types, such as `numeric` and timestamps, are kept in their text
+				representation.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -66,6 +66,15 @@ components: sources: prometheus_scrape: {
 				but will only take the last value for each tag name specified.
 				"""
 		}
+		exemplars: {
+			title: "Exemplars"
+			body: """
+				[OpenMetrics exemplars](\(urls.openmetrics_exemplars)) of histogram buckets and summaries,
+				which typically carry the ID of the trace an observation was made in, are kept on the
+				emitted metrics. They are forwarded by sinks supporting them, such as the
+				`prometheus_remote_write` sink.
+				"""
+		}
 	}
 
 	output: metrics: {
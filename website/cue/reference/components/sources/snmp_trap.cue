@@ -0,0 +1,142 @@
+package metadata
+
+components: sources: snmp_trap: {
+	_port: 162
+
+	title: "SNMP Trap"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.snmp
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.snmp_trap.configuration
+
+	output: logs: trap: {
+		description: "An SNMPv2c or SNMPv3 notification."
+		fields: {
+			community: {
+				description: "The community of an SNMPv2c trap."
+				required:    false
+				type: string: examples: ["public"]
+			}
+			host: {
+				description: "The IP address of the device the trap was received from."
+				required:    true
+				type: string: examples: ["10.0.0.1"]
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: examples: ["snmp_trap"]
+			}
+			timestamp: fields._current_timestamp
+			trap_oid: {
+				description: "The name of the trap, from the value of `snmpTrapOID.0`."
+				required:    false
+				type: string: examples: ["linkDown", "enterprises.9.9.41.2.0.1"]
+			}
+			uptime: {
+				description: "The uptime of the device in hundredths of a second, from the value of `sysUpTime.0`."
+				required:    false
+				type: uint: {
+					examples: [12345678]
+					unit: null
+				}
+			}
+			user: {
+				description: "The user of an SNMPv3 trap."
+				required:    false
+				type: string: examples: ["vector"]
+			}
+			variables: {
+				description: "The other variables of the trap, keyed by the name of their object identifiers."
+				required:    true
+				type: object: {
+					examples: [{"ifIndex.2": 2, "ifDescr.2": "eth0"}]
+				}
+			}
+			version: {
+				description: "The SNMP version of the trap."
+				required:    true
+				type: string: enum: {
+					"2c": "SNMPv2c."
+					"3":  "SNMPv3."
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		object_names: {
+			title: "Object names"
+			body: """
+				Object identifiers are named after the closest object defined by the MIB modules of
+				`mib_paths`, followed by the remaining sub-identifiers, so that the instance
+				`1.3.6.1.2.1.2.2.1.2.2` of `ifDescr` is named `ifDescr.2`. Only the object identifier
+				assignments of modules are read, and imports are ignored, so every module that
+				others depend on needs to be loaded as well. Object identifiers outside of any
+				known object are kept in their numeric form.
+
+				Values that are object identifiers, such as the one of `snmpTrapOID.0`, are named in
+				the same way. Octet strings are kept as bytes, and IP addresses are converted to
+				strings.
+				"""
+		}
+		snmpv3: {
+			title: "SNMPv3"
+			body: """
+				SNMPv3 traps are authenticated and decrypted with the [User-based Security
+				Model](\(urls.snmp_usm)) of the configured `users`, whose security level must
+				match the one of the traps they send. The keys of users are localized with the
+				engine ID of each trap, so devices with different engine IDs can share the same
+				user. The timeliness of traps, based on the engine boots and time of the sending
+				device, isn't checked.
+
+				SNMPv2c informs are acknowledged with a response, but SNMPv3 informs are only
+				received, since acknowledging them requires Vector to act as an authoritative SNMP
+				engine.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -0,0 +1,170 @@
+package metadata
+
+components: sources: splunk_s2s: {
+	_port: 9997
+
+	title: "Splunk S2S"
+
+	description: """
+		Receives events from Splunk universal and heavy forwarders over the Splunk-to-Splunk (S2S)
+		protocol, the protocol forwarders use to send data to Splunk indexers. This lets forwarders send
+		their data to Vector directly, without relaying it through a Splunk HTTP Event Collector.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		receive: {
+			from: {
+				service: services.splunk_forwarder
+
+				interface: socket: {
+					api: {
+						title: "Splunk forwarder outputs"
+						url:   urls.splunk_forwarder_outputs
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: {
+				enabled: true
+			}
+			keepalive: enabled: true
+			tls: sources.socket.features.receive.tls
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				Forwarders must be configured with `useACK = false` and `compressed = false`, as indexer
+				acknowledgements and compression aren't supported by this source.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.splunk_s2s.configuration
+
+	output: logs: event: {
+		description: "A single event sent by a forwarder."
+		fields: {
+			message: fields._raw_line
+			host: {
+				description: """
+					The host the event originates from, as set by the forwarder, or the name of the forwarder
+					if the event has no host.
+					"""
+				required: true
+				type: string: {
+					examples: ["web-1"]
+				}
+			}
+			splunk_index: {
+				description: "The index the forwarder routed the event to."
+				required:    false
+				type: string: {
+					examples: ["main"]
+				}
+			}
+			splunk_source: {
+				description: "The source of the event, such as the file it was read from."
+				required:    false
+				type: string: {
+					examples: ["/var/log/nginx/access.log"]
+				}
+			}
+			splunk_sourcetype: {
+				description: "The sourcetype of the event."
+				required:    false
+				type: string: {
+					examples: ["nginx"]
+				}
+			}
+			fields: {
+				description: "The indexed fields of the event, such as the ones set with `_meta` in `inputs.conf`."
+				required:    false
+				type: object: {
+					examples: [{"datacenter": "eu-west"}]
+					options: {}
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: """
+					The time of the event, as set by the forwarder, or the time the event was received if the
+					forwarder did not set it.
+					"""
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["splunk_s2s"]
+				}
+			}
+			client_metadata: fields._client_metadata
+		}
+	}
+
+	how_it_works: {
+		forwarder_configuration: {
+			title: "Forwarder configuration"
+			body: """
+				To configure a forwarder to send its data to Vector, add a target group pointing to this
+				source in its `outputs.conf`:
+
+				```text
+				[tcpout]
+				defaultGroup = vector
+
+				[tcpout:vector]
+				# update this to point to your vector instance
+				server = 127.0.0.1:9997
+				useACK = false
+				compressed = false
+				```
+
+				Forwarders using SSL can send to this source when `tls` is enabled, with `clientCert` and
+				`sslRootCAPath` matching the TLS settings of the source.
+				"""
+		}
+
+		protocol_support: {
+			title: "Protocol support"
+			body: """
+				This source reads the cooked events sent by forwarders running S2S protocol versions 2 and 3,
+				and accepts the capabilities negotiated by version 3 forwarders. Indexer acknowledgements
+				(`useACK`) and compression aren't supported: forwarders requiring them are not able to send
+				events to this source.
+
+				The `_raw` field of each event becomes the message, while the `MetaData:Host`,
+				`MetaData:Source`, `MetaData:Sourcetype` and `_MetaData:Index` fields are stripped of their
+				prefix and inserted as the host, source, sourcetype and index of the event. Events sent as
+				parsed data by heavy forwarders are received the same way, as their fields are part of the
+				same messages.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		open_connections: components.sources.internal_metrics.output.metrics.open_connections
+	}
+}
@@ -0,0 +1,248 @@
+package metadata
+
+components: sources: windows_event_log: {
+	title: "Windows Event Log"
+
+	classes: {
+		commonly_used: true
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		collect: {
+			checkpoint: enabled: true
+			from: service: services.windows_event_log
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      false
+			"aarch64-unknown-linux-musl":     false
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-apple-darwin":            false
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       false
+			"x86_64-unknown-linux-musl":      false
+		}
+
+		requirements: [
+			"""
+				Reading the `Security` channel requires Vector to run as a user with the
+				`Manage auditing and security log` right, such as the members of the `Administrators` group.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.windows_event_log.configuration
+
+	output: logs: {
+		event: {
+			description: "An event from the Windows Event Log"
+			fields: {
+				message: {
+					description: "The message of the event, rendered from the message resources of its provider."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["The Windows Update service entered the running state."]
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["windows_event_log"]
+					}
+				}
+				timestamp: fields._current_timestamp & {
+					description: "The time at which the event was logged."
+				}
+				provider_name: {
+					description: "The name of the provider which logged the event."
+					required:    true
+					type: string: {
+						examples: ["Service Control Manager", "Microsoft-Windows-Security-Auditing"]
+					}
+				}
+				event_id: {
+					description: "The identifier of the event, specific to its provider."
+					required:    true
+					type: uint: {
+						examples: [7036, 4624]
+						unit: null
+					}
+				}
+				level: {
+					description: "The name of the level of the event."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						enum: {
+							critical:    "Level 1."
+							error:       "Level 2."
+							warning:     "Level 3."
+							information: "Level 4, or 0 for the events logged regardless of the level."
+							verbose:     "Level 5."
+						}
+					}
+				}
+				level_value: {
+					description: "The level of the event."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						examples: [4]
+						unit: null
+					}
+				}
+				channel: {
+					description: "The channel the event was logged to."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["System"]
+					}
+				}
+				computer: {
+					description: "The name of the computer the event was logged on."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["DESKTOP-1"]
+					}
+				}
+				record_id: {
+					description: "The number of the event record in its channel."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						examples: [42]
+						unit: null
+					}
+				}
+				event_data: {
+					description: """
+						The data of the event, as an object of its named values, or as an array
+						of its values for the events of classic providers, which don't name them.
+						"""
+					required: false
+					common:   true
+					type: object: {}
+				}
+				user_data: {
+					description: "The provider-defined data of the event, converted from XML."
+					required:    false
+					common:      false
+					type: object: {}
+				}
+				"*": {
+					common:      false
+					description: """
+						The other fields of the `System` element of the event, such as `task`, `opcode`,
+						`keywords`, `process_id`, `thread_id`, `user_id`, and `activity_id`.
+						"""
+					required: false
+					type: "*": {}
+				}
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "Service state change"
+
+			configuration: {
+				channels: ["System"]
+			}
+			input: """
+				<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'>
+				  <System>
+				    <Provider Name='Service Control Manager'/>
+				    <EventID Qualifiers='16384'>7036</EventID>
+				    <Level>4</Level>
+				    <TimeCreated SystemTime='2024-01-15T10:30:00.000000000Z'/>
+				    <EventRecordID>42</EventRecordID>
+				    <Channel>System</Channel>
+				    <Computer>DESKTOP-1</Computer>
+				  </System>
+				  <EventData>
+				    <Data Name='param1'>Windows Update</Data>
+				    <Data Name='param2'>running</Data>
+				  </EventData>
+				</Event>
+				"""
+			output: [{
+				log: {
+					timestamp:     "2024-01-15T10:30:00Z"
+					source_type:   "windows_event_log"
+					message:       "The Windows Update service entered the running state."
+					provider_name: "Service Control Manager"
+					event_id:      7036
+					qualifiers:    16384
+					level:         "information"
+					level_value:   4
+					record_id:     42
+					channel:       "System"
+					computer:      "DESKTOP-1"
+					event_data: {
+						param1: "Windows Update"
+						param2: "running"
+					}
+				}
+			}]
+		},
+	]
+
+	how_it_works: {
+		subscriptions: {
+			title: "Subscriptions"
+			body: """
+				Vector subscribes to every configured channel with the `EvtSubscribe` API of the
+				Windows Event Log, reading the events matching the configured
+				[XPath query](\(urls.windows_event_log_xpath)) as they're logged.
+				Unknown channels and invalid queries are reported when Vector starts.
+				"""
+		}
+		bookmarks: {
+			title: "Bookmarks"
+			body: """
+				After each batch of events read from a channel, Vector persists a bookmark of the
+				last event of the batch to the `bookmarks.json` file in its data directory. When
+				Vector restarts, reading resumes after the bookmarked events, so events logged
+				while Vector was stopped aren't lost. With end-to-end acknowledgements enabled,
+				the bookmarks are only persisted once the events are delivered.
+				"""
+		}
+		messages: {
+			title: "Message Rendering"
+			body: """
+				Events only record the values of their messages. The messages displayed by the
+				Event Viewer are rendered from the message resources of the providers of the events,
+				which are looked up on the host running Vector. Rendering can be disabled with the
+				`render_message` option, for example to reduce the processing of high volume channels.
+				"""
+		}
+	}
+}
@@ -0,0 +1,105 @@
+package metadata
+
+components: transforms: aggregate_logs: {
+	title: "Aggregate Logs"
+
+	description: """
+		Aggregates log events over time windows, grouped by the values of a set of fields, into
+		metrics or into a single log per group and window. This helps to reduce the volume of logs
+		sent to expensive sinks, at the cost of detail.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		aggregate: {}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: base.components.transforms.aggregate_logs.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	output: metrics: {
+		counter: output._passthrough_counter
+		gauge:   output._passthrough_gauge
+		summary: output._passthrough_summary
+	}
+
+	examples: [
+		{
+			title: "Count requests per service"
+
+			configuration: {
+				group_by: ["service"]
+				window: {
+					type:          "tumbling"
+					duration_secs: 60
+				}
+				aggregates: [
+					{name: "requests_total", type: "count"},
+				]
+			}
+
+			input: [
+				{log: {service: "api", message: "GET /users 200"}},
+				{log: {service: "api", message: "GET /orders 200"}},
+			]
+			output: [{metric: {
+				kind: "incremental"
+				name: "requests_total"
+				tags: {
+					service: "api"
+				}
+				counter: {
+					value: 2.0
+				}
+			}}]
+		},
+	]
+
+	how_it_works: {
+		windows: {
+			title: "Windows"
+			body: """
+				Events are assigned to windows according to the time they are received by the transform,
+				and windows are aligned on the Unix epoch. Once a window ends, the events emitted for it are
+				timestamped with its end.
+
+				With `tumbling` windows, each event is aggregated in a single window. With `sliding`
+				windows, a new window starts every `slide_secs`, so each event is aggregated in every window
+				it falls in. Aggregating in many overlapping windows requires proportionally more memory and
+				processing.
+
+				When Vector stops, the windows which haven't ended yet are flushed.
+				"""
+		}
+
+		grouping: {
+			title: "Grouping"
+			body: """
+				Within a window, events with the same values for the `group_by` fields are aggregated
+				together. In `metrics` mode, each aggregate of a group is emitted as a metric tagged with the
+				values of the `group_by` fields. In `log` mode, a single log is emitted per group, with the
+				`group_by` fields, the `window_start` and `window_end` timestamps, and a field for each
+				aggregate.
+
+				The events of a group are only acknowledged once the events aggregating them are.
+				"""
+		}
+	}
+}
@@ -0,0 +1,129 @@
+package metadata
+
+base: components: transforms: aggregate_logs: configuration: {
+	aggregates: {
+		description: "A list of aggregates to compute over the events of each group and window."
+		required:    true
+		type: array: items: type: object: options: {
+			field: {
+				description:   "The field to aggregate."
+				relevant_when: "type = \"sum\" or type = \"min\" or type = \"max\" or type = \"mean\" or type = \"percentiles\""
+				required:      true
+				type: string: {}
+			}
+			name: {
+				description: """
+					The name of the aggregate.
+
+					This is the name of the metric in `metrics` mode, and the path of the field in `log` mode.
+					"""
+				required: true
+				type: string: examples: ["requests_total"]
+			}
+			quantiles: {
+				description:   "The quantiles to estimate, between `0.0` and `1.0`."
+				relevant_when: "type = \"percentiles\""
+				required:      false
+				type: array: {
+					default: [0.5, 0.9, 0.99]
+					items: type: float: {}
+				}
+			}
+			type: {
+				description: "The function of the aggregate."
+				required:    true
+				type: string: enum: {
+					count: "Counts the events, as an incremental counter."
+					max:   "Keeps the maximum value of a field, as a gauge."
+					mean:  "Averages the values of a field, as a gauge."
+					min:   "Keeps the minimum value of a field, as a gauge."
+					percentiles: """
+						Estimates quantiles of the values of a field, as a summary.
+
+						In `log` mode, each quantile is stored under the aggregate as `p<percentile>`, for example
+						`p99` for the `0.99` quantile.
+						"""
+					sum: "Sums the values of a field, as an incremental counter."
+				}
+			}
+		}
+	}
+	group_by: {
+		description: """
+			An ordered list of fields by which to group events.
+
+			Each group with matching values for the specified fields is aggregated independently. When
+			no fields are specified, all events of a window are aggregated together.
+
+			The values of these fields are added as tags to the aggregated metrics, or as fields to the
+			aggregated logs.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["service", "status"]
+		}
+	}
+	mode: {
+		description: "The type of the events emitted for each group and window."
+		required:    false
+		type: string: {
+			default: "metrics"
+			enum: {
+				log: """
+					Emits a single log, holding the values of the `group_by` fields, the bounds of the window
+					as `window_start` and `window_end`, and a field for each aggregate.
+					"""
+				metrics: "Emits a metric for each aggregate, tagged with the values of the `group_by` fields."
+			}
+		}
+	}
+	namespace: {
+		description: """
+			Sets the namespace of the aggregated metrics.
+
+			Only used in `metrics` mode.
+			"""
+		required: false
+		type: string: examples: ["service"]
+	}
+	window: {
+		description: """
+			The windows over which events are aggregated.
+
+			Windows are aligned on the Unix epoch, and events are assigned to windows according to the time
+			they are received by the transform.
+			"""
+		required: false
+		type: object: options: {
+			duration_secs: {
+				description: "The duration of each window, in seconds."
+				required:    true
+				type: uint: unit: "seconds"
+			}
+			slide_secs: {
+				description: """
+					The interval between the starts of consecutive windows, in seconds.
+
+					Must not be greater than `duration_secs`.
+					"""
+				relevant_when: "type = \"sliding\""
+				required:      true
+				type: uint: unit: "seconds"
+			}
+			type: {
+				description: "The type of the windows."
+				required:    true
+				type: string: enum: {
+					sliding: """
+						Windows which overlap, a new window starting every `slide_secs`.
+
+						Each event is aggregated in every window it falls in, that is in about
+						`duration_secs / slide_secs` windows.
+						"""
+					tumbling: "Consecutive windows which don't overlap, each event being aggregated in a single window."
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,32 @@
+package metadata
+
+base: components: transforms: convert_histograms: configuration: {
+	buckets: {
+		description: """
+			The upper bounds of the buckets of the aggregated histograms converted to.
+
+			Only used when converting to aggregated histograms. The observations above all bounds are
+			only part of the count of the histograms, as for the `+Inf` bucket of Prometheus histograms.
+			"""
+		required: false
+		type: array: {
+			default: [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+			items: type: float: {}
+		}
+	}
+	target: {
+		description: "The representation to convert distributions, aggregated histograms, and sketches to."
+		required:    true
+		type: string: enum: {
+			aggregated_histogram: """
+				Aggregated histograms with the configured buckets, as the classic histograms of Prometheus
+				and the explicit bucket histograms of OpenTelemetry.
+				"""
+			sketch: """
+				Sketches, as the distributions of Datadog.
+
+				The `opentelemetry` sink exports sketches as exponential histograms.
+				"""
+		}
+	}
+}
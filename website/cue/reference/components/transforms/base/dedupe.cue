@@ -4,10 +4,52 @@ base: components: transforms: dedupe: configuration: {
 	cache: {
 		description: "Caching configuration for deduplication."
 		required:    false
-		type: object: options: num_events: {
-			description: "Number of events to cache and use for comparing incoming events to previously seen events."
-			required:    false
-			type: uint: default: 5000
+		type: object: options: {
+			false_positive_rate: {
+				description: """
+					The rate at which new events are falsely considered duplicates in `probabilistic` mode.
+
+					Lower rates require more memory for the same number of events.
+					"""
+				required: false
+				type: float: {
+					default: 0.01
+					examples: [0.001]
+				}
+			}
+			mode: {
+				description: "Controls the approach taken for remembering previously seen events."
+				required:    false
+				type: string: {
+					default: "exact"
+					enum: {
+						exact: """
+							Remembers the most recently seen events exactly, in a least recently used cache.
+
+							This mode never drops events it hasn't seen, but holds a copy of the matched fields of
+							each cached event in memory.
+							"""
+						probabilistic: """
+							Remembers previously seen events in a rotating pair of bloom filters.
+
+							This mode uses a small, fixed amount of memory, which allows deduplicating high
+							cardinality streams over many more events than `exact`, but may drop events it hasn't
+							seen at the configured `false_positive_rate`. Once the newest filter holds `num_events`
+							distinct events, the oldest one is discarded.
+							"""
+					}
+				}
+			}
+			num_events: {
+				description: """
+					Number of events to cache and use for comparing incoming events to previously seen events.
+
+					In `probabilistic` mode, this is the number of distinct events each generation of the
+					filter holds, so between one and two times this many events are remembered.
+					"""
+				required: false
+				type: uint: default: 5000
+			}
 		}
 	}
 	fields: {
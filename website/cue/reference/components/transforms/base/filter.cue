@@ -1,11 +1,42 @@
 package metadata
 
-base: components: transforms: filter: configuration: condition: {
-	description: """
-		The condition that every input event is matched against.
-
-		If an event is matched by the condition, it is forwarded. Otherwise, the event is dropped.
-		"""
-	required: true
-	type: condition: {}
+base: components: transforms: filter: configuration: {
+	condition: {
+		description: """
+			The condition that every input event is matched against.
+
+			If an event is matched by the condition, it is forwarded. Otherwise, the event is dropped.
+
+			Only one of `condition` and `conditions` can be provided.
+			"""
+		required: false
+		type: condition: {}
+	}
+	conditions: {
+		description: """
+			An ordered list of named conditions that every input event is matched against.
+
+			If an event is matched by all of the conditions, it is forwarded. The conditions are checked
+			in order, and the first one that doesn't match an event drops it, skipping the following
+			ones. The events matched and dropped by each condition are counted by the
+			`filter_condition_events_matched_total` and `filter_condition_events_dropped_total` metrics,
+			tagged with the name of the condition.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				condition: {
+					description: "A condition of the `filter` transform, identified by its name in its metrics."
+					required:    true
+					type: condition: {}
+				}
+				name: {
+					description: "The name of the condition."
+					required:    true
+					type: string: examples: ["drop_debug_logs"]
+				}
+			}
+		}
+	}
 }
@@ -0,0 +1,85 @@
+package metadata
+
+base: components: transforms: log_to_trace: configuration: {
+	duration_ms_field: {
+		description: """
+			The field holding the duration of the span, in milliseconds.
+
+			The span starts at the timestamp of its earliest log. If no log of the span has a duration,
+			the span ends at the timestamp of its latest log.
+			"""
+		required: false
+		type: string: examples: ["duration_ms"]
+	}
+	error_field: {
+		description: """
+			The field telling whether the span failed.
+
+			The span is flagged as an error if the field of any of its logs is `true`.
+			"""
+		required: false
+		type: string: default: "error"
+	}
+	expire_after_ms: {
+		description: """
+			The period of time to wait after the last log of a trace is received, in milliseconds,
+			before the trace is considered complete.
+			"""
+		required: false
+		type: uint: {
+			default: 10000
+			unit:    "milliseconds"
+		}
+	}
+	flush_period_ms: {
+		description: "The interval to check for and flush complete traces, in milliseconds."
+		required:    false
+		type: uint: {
+			default: 1000
+			unit:    "milliseconds"
+		}
+	}
+	name_field: {
+		description: "The field holding the name of the operation of the span."
+		required:    false
+		type: string: default: "name"
+	}
+	parent_id_field: {
+		description: "The field holding the ID of the parent span of the span a log belongs to."
+		required:    false
+		type: string: default: "parent_id"
+	}
+	resource_field: {
+		description: """
+			The field holding the resource the span operates on, such as an endpoint or a query.
+
+			Defaults to the name of the operation.
+			"""
+		required: false
+		type: string: default: "resource"
+	}
+	service_field: {
+		description: "The field holding the name of the service emitting the span."
+		required:    false
+		type: string: default: "service"
+	}
+	span_id_field: {
+		description: """
+			The field holding the ID of the span a log belongs to.
+
+			The logs of a trace sharing the same span ID are combined into a single span.
+			"""
+		required: false
+		type: string: default: "span_id"
+	}
+	trace_id_field: {
+		description: """
+			The field holding the ID of the trace a log belongs to.
+
+			IDs are either integers, decimal strings, or hexadecimal strings of 16 or 32 characters,
+			such as OpenTelemetry IDs, of which only the lower 64 bits are kept.
+			"""
+		required: false
+		type: string: default: "trace_id"
+	}
+}
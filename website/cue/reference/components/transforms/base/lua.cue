@@ -86,6 +86,50 @@ base: components: transforms: lua: configuration: {
 			}
 		}
 	}
+	sandbox: {
+		description: """
+			Sandboxing and resource limits.
+
+			These options make it possible to run Lua code which isn't trusted, such as code provided by tenants, without
+			letting it access the host, hang the transform, or exhaust the memory of Vector.
+			"""
+		required: false
+		type: object: options: {
+			max_instructions: {
+				description: """
+					The maximum number of Lua instructions each call of a hook or timer handler can execute.
+
+					Calls exceeding it are aborted with an error, which can't be caught with `pcall` or `xpcall`. The limit is
+					checked every 1000 instructions.
+					"""
+				required: false
+				type: uint: examples: [1000000]
+			}
+			max_memory_bytes: {
+				description: """
+					The maximum amount of memory the Lua runtime can allocate.
+
+					Allocations exceeding it fail with a memory error, aborting the call which made them.
+					"""
+				required: false
+				type: uint: {
+					examples: [67108864]
+					unit:     "bytes"
+				}
+			}
+			restrict_stdlib: {
+				description: """
+					Whether to restrict the Lua standard library to the functions which don't access the host.
+
+					When enabled, only the `coroutine`, `math`, `string`, `table`, and `utf8` libraries are available, along with
+					the `clock`, `date`, `difftime`, and `time` functions of the `os` library. Code can't be loaded with `load`,
+					`loadfile`, `dofile`, or `require`, so `search_dirs` is ignored.
+					"""
+				required: false
+				type: bool: default: false
+			}
+		}
+	}
 	search_dirs: {
 		description: """
 			A list of directories to search when loading a Lua file via the `require` function.
@@ -19,6 +19,51 @@ base: components: transforms: reduce: configuration: {
 		required: false
 		type: condition: {}
 	}
+	event_time: {
+		description: """
+			Reduces events within windows of their own time, rather than of the time they are received.
+
+			Each group is reduced separately within each window. A watermark follows the latest event time
+			seen, minus `allowed_lateness_ms`, and flushes the windows it passes the end of. Events arriving
+			for a window which was already flushed are dropped.
+
+			Groups are still flushed after `expire_after_ms` without events, so that the windows of idle
+			streams are eventually flushed.
+			"""
+		required: false
+		type: object: options: {
+			allowed_lateness_ms: {
+				description: "How late events may arrive, in milliseconds, relative to the latest event time seen."
+				required:    false
+				type: uint: {
+					default: 0
+					unit:    "milliseconds"
+				}
+			}
+			timestamp_field: {
+				description: """
+					The field holding the time of the events.
+
+					Defaults to the timestamp of the events, as configured by the [global log schema][global_log_schema]
+					in the legacy namespace, or as the field with the `timestamp` meaning in the `vector`
+					namespace. Events without a timestamp are assigned the latest event time seen.
+
+					[global_log_schema]: https://vector.dev/docs/reference/configuration/global-options/#log_schema
+					"""
+				required: false
+				type: string: examples: ["timestamp", "event.created"]
+			}
+			window_ms: {
+				description: """
+					The duration of the windows, in milliseconds.
+
+					Windows are aligned on the Unix epoch.
+					"""
+				required: true
+				type: uint: unit: "milliseconds"
+			}
+		}
+	}
 	expire_after_ms: {
 		description: """
 			The maximum period of time to wait after the last event is received, in milliseconds, before
@@ -60,6 +105,23 @@ base: components: transforms: reduce: configuration: {
 		required:    false
 		type: uint: {}
 	}
+	merge: {
+		description: """
+			A [Vector Remap Language][vrl] (VRL) program merging each event into the state of its group,
+			in place of the merge strategies.
+
+			The program is run for every event after the first of a group, with the state at `.state`
+			and the incoming event at `.event`, and updates `.state` in place. The first event of a group
+			becomes its initial state, and the final state is flushed as the reduced event. If the
+			program fails, the incoming event is dropped and the state is left unchanged.
+
+			Only one of `merge` and `merge_strategies` can be provided.
+
+			[vrl]: https://vector.dev/docs/reference/vrl
+			"""
+		required: false
+		type: string: examples: ["if .event.duration_ms > .state.duration_ms { .state.duration_ms = .event.duration_ms }"]
+	}
 	merge_strategies: {
 		description: """
 			A map of field names to custom merge strategies.
@@ -33,6 +33,24 @@ base: components: transforms: remap: configuration: {
 		required: false
 		type: bool: default: false
 	}
+	dropped_metadata_key: {
+		description: """
+			The name of the field that events rerouted to the `dropped` output are annotated under.
+
+			For logs using the `Legacy` log namespace, and for metric tags, the annotation is placed
+			under the global [`log_schema.metadata_key`][global_metadata_key]. For logs using the
+			`Vector` log namespace, it's placed in the `vector` metadata namespace.
+
+			Events dropped by `abort` are additionally annotated with the span of the `abort`
+			expression in the program, as well as the line and column it starts at. If the program
+			was read from `file` or `files`, the annotation also contains the path of the file, and
+			the line and column are relative to it.
+
+			[global_metadata_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.metadata_key
+			"""
+		required: false
+		type: string: default: "dropped"
+	}
 	file: {
 		description: """
 			File path to the [Vector Remap Language][vrl] (VRL) program to execute for each event.
@@ -46,6 +64,86 @@ base: components: transforms: remap: configuration: {
 		required: false
 		type: string: examples: ["./my/program.vrl"]
 	}
+	files: {
+		description: """
+			File paths to [Vector Remap Language][vrl] (VRL) programs that are concatenated, in the
+			given order, into the program to execute for each event.
+
+			Runtime errors and dropped event annotations refer to the file, line, and column they
+			originate from, rather than to offsets in the concatenated program.
+
+			Can't be used along with `source` or `file`.
+
+			[vrl]: https://vector.dev/docs/reference/vrl
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["./my/functions.vrl", "./my/program.vrl"]
+		}
+	}
+	functions: {
+		description: """
+			Functions defined by VRL programs, which the program of the transform can call.
+
+			Functions can call the functions defined before them, but not themselves.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				name: {
+					description: """
+						The name of the function.
+
+						Can't be the name of a function provided by VRL or Vector.
+						"""
+					required: true
+					type: string: examples: ["normalize_status"]
+				}
+				parameters: {
+					description: "The names of the parameters of the function, which are all required."
+					required:    false
+					type: array: {
+						default: []
+						items: type: string: examples: ["status"]
+					}
+				}
+				source: {
+					description: "The VRL program computing the return value of the function."
+					required:    true
+					type: string: {
+						examples: ["if is_integer(.status) { .status } else { to_int(.status) ?? 0 }"]
+						syntax: "remap_program"
+					}
+				}
+			}
+		}
+	}
+	max_execution_time_ms: {
+		description: """
+			The maximum time, in milliseconds, that the program can take to process an event.
+
+			The program isn't interrupted, but events taking longer are handled as if the program had
+			failed, according to `drop_on_error` and `reroute_dropped`, and are counted in the
+			`component_errors_total` metric with the `execution_time_exceeded` error code.
+
+			As any event can then fail, events are copied before running the program, unless they're
+			dropped on errors without being rerouted.
+			"""
+		required: false
+		type: uint: unit: "milliseconds"
+	}
+	max_recursion_depth: {
+		description: """
+			The maximum depth of nested calls of the functions defined in `functions`.
+
+			Calls are nested as deep when processing events as they are in the program, so programs
+			nesting calls deeper are rejected when the transform is built.
+			"""
+		required: false
+		type: uint: {}
+	}
 	metric_tag_values: {
 		description: """
 			When set to `single`, metric tag values are exposed as single strings, the
@@ -103,6 +201,48 @@ base: components: transforms: remap: configuration: {
 			syntax: "remap_program"
 		}
 	}
+	state: {
+		description: """
+			Key/value state shared by the runs of the program, available through the `get_state` and
+			`set_state` functions.
+
+			Programs can use it to count events, track the first time a value was seen, or group events
+			into sessions. The state is shared by all the events processed by the transform, but not across
+			transforms.
+			"""
+		required: false
+		type: object: options: {
+			flush_interval_secs: {
+				description: "The interval, in seconds, at which the state is written to disk when persisted."
+				required:    false
+				type: uint: {
+					default: 10
+					unit:    "seconds"
+				}
+			}
+			max_keys: {
+				description: """
+					The maximum number of keys held in the state.
+
+					Setting a new key once the state is full evicts the expired keys, or else the key set the
+					longest ago.
+					"""
+				required: false
+				type: uint: default: 100000
+			}
+			persist: {
+				description: """
+					Whether to persist the state in the data directory, so that it's restored when Vector
+					restarts.
+
+					Values are restored with their types, except for regular expressions, which are restored as
+					strings.
+					"""
+				required: false
+				type: bool: default: false
+			}
+		}
+	}
 	timezone: {
 		description: """
 			The name of the timezone to apply to timestamp conversions that do not contain an explicit
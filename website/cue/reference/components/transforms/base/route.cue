@@ -1,6 +1,42 @@
 package metadata
 
 base: components: transforms: route: configuration: {
+	dynamic: {
+		description: """
+			Configuration for resolving the output of events at runtime.
+
+			Events are sent to the output the expression resolves to, in addition to the routes they
+			match. Events for which the expression resolves to `null`, or to an output not declared in
+			`outputs`, are only sent to the routes they match, if any.
+			"""
+		required: false
+		type: object: options: {
+			outputs: {
+				description: """
+					The outputs the expression can resolve to.
+
+					Each output can be referenced as an input by other components with the name
+					`<transform_name>.<output>`, just like routes.
+					"""
+				required: true
+				type: array: items: type: string: examples: ["tenant_a", "tenant_b"]
+			}
+			source: {
+				description: """
+					The [Vector Remap Language][vrl] (VRL) expression resolving the name of the output of an
+					event.
+
+					The expression can look the output up in an enrichment table, so that the mapping of
+					events to outputs can be changed by updating the table, without reloading the
+					configuration.
+
+					[vrl]: https://vector.dev/docs/reference/vrl
+					"""
+				required: true
+				type: string: examples: [".tenant", "get_enrichment_table_record!(\"tenants\", { \"id\": .tenant_id }).output"]
+			}
+		}
+	}
 	reroute_unmatched: {
 		description: """
 			Reroutes unmatched events to a named output instead of silently discarding them.
@@ -34,4 +70,44 @@ base: components: transforms: route: configuration: {
 			type: condition: {}
 		}
 	}
+	weight_key: {
+		description: """
+			The key determining which weighted route events are eligible to.
+
+			Events with the same key are consistently eligible to the same weighted route, for example
+			to always send the requests of a given user to the same backend. If unspecified, or if the
+			key can't be rendered for an event, events are spread evenly across the weighted routes.
+			"""
+		required: false
+		type: string: {
+			examples: ["{{ user_id }}"]
+			syntax: "template"
+		}
+	}
+	weights: {
+		description: """
+			A table of route identifiers to the percentage of the events that are eligible to the route.
+
+			Weighted routes are assigned consecutive ranges of percentages, in the order they are
+			declared in `route`: with a weight of `5` for a `canary` route declared before a `stable`
+			route with a weight of `95`, 5% of the events are eligible to `canary`, and the other 95% to
+			`stable`. Eligible events are still only sent to a route if they match its condition, so
+			weighted routes with the same condition split the matching events between them. Routes
+			without a weight receive all the events matching their condition.
+
+			The weights must add up to at most 100.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				canary: 5.0
+				stable: 95.0
+			}]
+			options: "*": {
+				description: "The weight of a route, as a percentage."
+				required:    true
+				type: float: {}
+			}
+		}
+	}
 }
@@ -23,6 +23,26 @@ base: components: transforms: sample: configuration: {
 		required: false
 		type: string: examples: ["message"]
 	}
+	mode: {
+		description: "Controls how the value of `key_field` determines whether an event is sampled."
+		required:    false
+		type: string: {
+			default: "modulo"
+			enum: {
+				modulo: "Samples the events whose hashed key is a multiple of `rate`."
+				rendezvous: """
+					Samples events using [rendezvous hashing][rendezvous] of the key over `rate` buckets.
+
+					An event is sampled when its key ranks the first bucket highest. Like `modulo`, the same
+					keys are sampled by every Vector instance, but the keys sampled at a given `rate` are also
+					sampled at any lower `rate`. This keeps traces or sessions whole when they are sampled
+					again further down a pipeline, or when the `rate` is changed.
+
+					[rendezvous]: https://en.wikipedia.org/wiki/Rendezvous_hashing
+					"""
+			}
+		}
+	}
 	rate: {
 		description: """
 			The rate at which events are forwarded, expressed as `1/N`.
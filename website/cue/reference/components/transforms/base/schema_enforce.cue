@@ -0,0 +1,53 @@
+package metadata
+
+base: components: transforms: schema_enforce: configuration: {
+	coerce_types: {
+		description: """
+			Converts values not matching the type required by the schema to it, where possible.
+
+			For example, the string `"200"` is converted to the integer `200` where the schema requires
+			an integer, and the number `42` to the string `"42"` where it requires a string. Events are
+			only converted if they then match the schema, and are otherwise routed unchanged to the
+			`failed` output.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	schema: {
+		description: """
+			The [JSON Schema][json_schema] to validate events against, as JSON.
+
+			Required if `schema_file` is missing.
+
+			[json_schema]: https://json-schema.org/draft/2020-12/json-schema-core
+			"""
+		required: false
+		type: string: examples: ["{\"type\": \"object\", \"required\": [\"message\"], \"properties\": {\"status\": {\"type\": \"integer\"}}}"]
+	}
+	schema_file: {
+		description: """
+			The path of the file holding the JSON Schema to validate events against.
+
+			Required if `schema` is missing.
+			"""
+		required: false
+		type: string: examples: ["/etc/vector/schemas/app.json"]
+	}
+	violations_metadata_key: {
+		description: """
+			The name of the field that events routed to the `failed` output are annotated under.
+
+			The annotation lists the violations of the schema by the event, each with the location of
+			the violating value as a JSON pointer in `path`, the violated `keyword` of the schema, and a
+			`message` describing the violation.
+
+			For logs using the `Legacy` log namespace, the annotation is placed under the global
+			[`log_schema.metadata_key`][global_metadata_key]. For logs using the `Vector` log namespace,
+			it's placed in the `vector` metadata namespace.
+
+			[global_metadata_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.metadata_key
+			"""
+		required: false
+		type: string: default: "schema_violations"
+	}
+}
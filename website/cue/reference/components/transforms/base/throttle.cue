@@ -42,11 +42,28 @@ base: components: transforms: throttle: configuration: {
 		description: """
 			The number of events allowed for a given bucket per configured `window_secs`.
 
-			Each unique key has its own `threshold`.
+			Each unique key has its own `threshold`. When `threshold_expression` is set, this is only
+			used for the events for which the expression fails to resolve a threshold.
 			"""
 		required: true
 		type: uint: {}
 	}
+	threshold_expression: {
+		description: """
+			A [Vector Remap Language][vrl] (VRL) expression resolving the `threshold` of each event.
+
+			This allows the rate limit to be set per key, for example by looking up the quota of a
+			tenant in an enrichment table. The expression must resolve to a positive integer, or the
+			static `threshold` is used instead. The expression cannot modify the event.
+
+			Keys are rate limited independently for each distinct threshold, so a key whose threshold
+			changes starts with a full bucket.
+
+			[vrl]: https://vector.dev/docs/reference/vrl
+			"""
+		required: false
+		type: string: examples: ["to_int(.quota) ?? 100", "get_enrichment_table_record!(\"quotas\", {\"tenant\": .tenant}).limit"]
+	}
 	window_secs: {
 		description: "The time window in which the configured `threshold` is applied, in seconds."
 		required:    true
@@ -0,0 +1,39 @@
+package metadata
+
+base: components: transforms: wasm: configuration: {
+	fuel: {
+		description: """
+			The amount of fuel the component can consume to process each event.
+
+			Fuel is roughly the number of WebAssembly instructions executed. An event whose processing
+			runs out of fuel is dropped, and the component is instantiated anew for the next event.
+			"""
+		required: false
+		type: uint: default: 10000000
+	}
+	module: {
+		description: """
+			The path of the WebAssembly component to process the events with.
+
+			The component must target WASI 0.2 and export the `vector:transform/processor` interface,
+			which processes each event encoded as a JSON object and returns the events to emit in its
+			place.
+			"""
+		required: true
+		type: string: examples: ["/etc/vector/transform.wasm"]
+	}
+	reload_interval_secs: {
+		description: """
+			The interval between checks of the component file for changes, in seconds.
+
+			When the file changes, the component is compiled again and processes the next events. If
+			the new component fails to load, the previous one is kept. Set to `0` to never reload the
+			component.
+			"""
+		required: false
+		type: uint: {
+			default: 5
+			unit:    "seconds"
+		}
+	}
+}
@@ -0,0 +1,61 @@
+package metadata
+
+components: transforms: convert_histograms: {
+	title: "Convert Histograms"
+
+	description: """
+		Converts distributions, aggregated histograms, and sketches to a single representation, so
+		they can be sent to metric backends which don't support the others. For example, sketches
+		received from Datadog Agents can be converted to aggregated histograms for Prometheus, and
+		Prometheus histograms to sketches for Datadog.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: base.components.transforms.convert_histograms.configuration
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		conversions: {
+			title: "Conversions"
+			body: """
+				Converting to aggregated histograms counts the samples of distributions, and the
+				observations of each bin of sketches, towards the first bucket whose upper bound is
+				greater than or equal to their value. Converting to sketches interpolates the
+				observations of each bucket of aggregated histograms across the bins spanning the
+				bucket, and inserts the samples of distributions.
+
+				Both conversions approximate the values of observations, within the width of the buckets
+				and the relative accuracy of sketches respectively. Metrics of other types are forwarded
+				unmodified, and the `opentelemetry` sink exports sketches as exponential histograms.
+				"""
+		}
+	}
+}
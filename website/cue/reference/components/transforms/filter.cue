@@ -69,4 +69,34 @@ components: transforms: filter: {
 			]
 		},
 	]
+
+	how_it_works: {
+		named_conditions: {
+			title: "Named conditions"
+			body: """
+				Instead of a single `condition`, `conditions` takes an ordered list of named
+				conditions, all of which an event must match to be forwarded:
+
+				```toml
+				[[transforms.my_filter.conditions]]
+				name = "drop_debug_logs"
+				condition = '.level != "debug"'
+
+				[[transforms.my_filter.conditions]]
+				name = "drop_healthchecks"
+				condition = '.path != "/health"'
+				```
+
+				The conditions are checked in order, and checking stops at the first one that doesn't
+				match an event, which drops it. The events matched and dropped by each condition are
+				counted separately, tagged with the name of the condition, showing which rule is
+				discarding traffic.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		filter_condition_events_dropped_total: components.sources.internal_metrics.output.metrics.filter_condition_events_dropped_total
+		filter_condition_events_matched_total: components.sources.internal_metrics.output.metrics.filter_condition_events_matched_total
+	}
 }
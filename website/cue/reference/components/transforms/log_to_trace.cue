@@ -0,0 +1,82 @@
+package metadata
+
+components: transforms: log_to_trace: {
+	title: "Log to Trace"
+
+	description: """
+		Builds trace events from log events sharing trace and span IDs, such as the logs of
+		services instrumented with OpenTelemetry. This allows synthesizing traces from structured
+		logs, to send them to the `datadog_traces` sink for example.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: base.components.transforms.log_to_trace.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	output: traces: {
+		description: "A trace built from the logs sharing its trace ID."
+		fields: {
+			trace_id: {
+				description: "The ID of the trace."
+				required:    true
+				type: uint: examples: [1234]
+			}
+			spans: {
+				description: """
+					The spans of the trace, ordered by start time. Each span holds the `trace_id`,
+					`span_id`, `parent_id`, `service`, `name`, `resource`, `start`, `duration` (in
+					nanoseconds) and `error` fields, along with the other fields of its logs, as strings
+					in `meta` and as numbers in `metrics`.
+					"""
+				required: true
+				type: array: items: type: object: options: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		spans: {
+			title: "Spans"
+			body: """
+				The logs of a trace sharing the same span ID are combined into a single span. The span
+				starts at the timestamp of its earliest log, and ends either after the duration given by
+				the `duration_ms_field` of its logs, or at the timestamp of its latest log.
+
+				Trace and span IDs may be integers, decimal strings, or hexadecimal strings of 16 or 32
+				characters. Only the lower 64 bits of 128-bit IDs are kept. Logs without a valid trace ID
+				or span ID are dropped.
+				"""
+		}
+
+		completion: {
+			title: "Trace completion"
+			body: """
+				A trace is considered complete, and emitted, once no log was received for it for
+				`expire_after_ms`. When Vector stops, the pending traces are flushed.
+
+				The logs of a trace are only acknowledged once the trace is.
+				"""
+		}
+	}
+}
@@ -306,6 +306,24 @@ components: transforms: lua: {
 				set, the directories of the configuration files will be used instead.
 				"""
 		}
+		sandboxing: {
+			title: "Sandboxing"
+			body:  """
+				Lua code which isn't trusted, such as code provided by the tenants of a
+				shared Vector deployment, can be run with the `sandbox` options:
+
+				* `restrict_stdlib` removes the functions giving access to the host, such
+				  as `io`, `os.execute`, or `require`.
+				* `max_instructions` aborts a call of a hook or timer handler once it has
+				  executed the given number of instructions, so that infinite loops can't
+				  hang the transform.
+				* `max_memory_bytes` caps the memory allocated by the Lua runtime.
+
+				Events whose processing is aborted are dropped, and the error is reported
+				with the `component_errors_total` metric. The runtime keeps its state, so
+				the following events are processed normally.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
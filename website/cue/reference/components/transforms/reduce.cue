@@ -144,6 +144,42 @@ components: transforms: reduce: {
 		},
 	]
 
+	how_it_works: {
+		custom_merge: {
+			title: "Custom merge logic"
+			body: """
+				When the merge strategies don't express the aggregation needed, `merge` takes a VRL
+				program merging each event into the state of its group. The first event of a group
+				becomes its state, and the program then runs for each following event, with the state
+				at `.state` and the incoming event at `.event`. For example, to keep the highest
+				latency of a request:
+
+				```coffee
+				latency_ms = int!(.event.request.latency_ms)
+				if latency_ms > int!(.state.request.latency_ms) {
+				  .state.request.latency_ms = latency_ms
+				}
+				```
+
+				Fields the program doesn't copy from `.event` are discarded. When the program fails,
+				the incoming event is dropped and the state is left unchanged.
+				"""
+		}
+		event_time: {
+			title: "Event-time windows"
+			body: """
+				By default, events are reduced in the order they are received, and groups are flushed
+				according to the time they are received, which splits or merges out-of-order streams
+				arbitrarily. With `event_time` set, each group is instead reduced within windows of the
+				time of its events, as read from `event_time.timestamp_field`.
+
+				A window is flushed once the watermark, which trails the latest event time seen by
+				`event_time.allowed_lateness_ms`, passes its end. Out-of-order events arriving before
+				then are reduced into their window, while later ones are dropped.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		stale_events_flushed_total: components.sources.internal_metrics.output.metrics.stale_events_flushed_total
 	}
@@ -136,6 +136,97 @@ components: transforms: "remap": {
 				```
 				"""#
 		}
+		imports: {
+			title: "Importing shared programs"
+			body: #"""
+				A program can include other VRL files with `import` statements, for example to share
+				parsing logic between several `remap` transforms:
+
+				```coffee
+				import "lib/parse_nginx.vrl"
+
+				.environment = "production"
+				```
+
+				Imports are resolved when the transform is built, and must come before any other
+				statement of the program, only preceded by comments and blank lines. Paths are
+				relative to the directory of the importing file, or to the current working directory
+				for programs configured with `source`. Imported files can import other files
+				themselves. Each file is only included once per program, and import cycles are
+				rejected.
+
+				Variables assigned by an imported file are scoped to it, so that they don't clash
+				with the variables of the importing program. Changes made to the event are kept.
+				Runtime errors and dropped event annotations refer to the file they originate from.
+				"""#
+		}
+		functions: {
+			title: "User-defined functions"
+			body: #"""
+				Logic repeated across a program can be defined once as a function in the
+				`functions` option, and called like the functions provided by VRL:
+
+				```toml
+				[[transforms.my_transform.functions]]
+				name = "normalize_status"
+				parameters = ["status"]
+				source = "if is_integer(.status) { .status } else { to_int(.status) ?? 0 }"
+				```
+
+				```coffee
+				.status = normalize_status(.status)
+				```
+
+				The arguments are the fields of the event seen by the program of the function,
+				which only has access to them, and the function returns the value of its last
+				expression. Functions can call the functions defined before them, but not
+				themselves, nor the functions accessing enrichment tables.
+
+				The program of a function is type checked at each call with the types of the
+				arguments given there, so that the type of the value returned is known. Calls
+				must handle errors if the function can fail with these arguments, and aborting
+				in a function fails the call.
+				"""#
+		}
+		state: {
+			title: "State"
+			body: #"""
+				Programs of a transform configured with `state` can keep values across events with
+				the `get_state` and `set_state` functions, for example to count the events of each
+				host:
+
+				```coffee
+				host = string!(.host)
+				.count = (int(get_state(host)) ?? 0) + 1
+				set_state!(host, .count)
+				```
+
+				`get_state` returns `null` for missing or expired keys. `set_state` accepts a
+				`ttl_secs` argument to expire keys, which suits first-seen tracking and
+				sessionization, and setting a key to `null` removes it. The state is held in memory.
+				When `persist` is enabled, it's also written every `flush_interval_secs` to the
+				`state.json` file in the data directory of the transform, and restored from it when
+				Vector restarts.
+
+				To correlate events across transforms, for example to join authentication events to
+				activity events, programs can instead pass the `keyspace` argument to use a named
+				keyspace, shared by all `remap` transforms and configured in the top-level
+				`keyspaces` section:
+
+				```toml
+				[keyspaces.sessions]
+				max_keys = 100000
+				default_ttl_secs = 3600
+				```
+
+				```coffee
+				set_state!(string!(.session_id), .user, keyspace: "sessions")
+				```
+
+				Keyspaces are held in memory and bounded by their `max_keys`. Their keys expire
+				after `default_ttl_secs` unless a `ttl_secs` is given.
+				"""#
+		}
 	}
 
 	outputs: [
@@ -151,6 +242,15 @@ components: transforms: "remap": {
 				output can be accessed by specifying `foo.dropped` as the input to another
 				component. Events sent to this output will be in their original form,
 				omitting any partial modification that took place before the error or abort.
+
+				Each event is annotated with the reason it was dropped, its error or abort
+				message, and the ID of this component, under the field named by
+				`dropped_metadata_key`. Aborted events also record the span of the `abort`
+				expression as well as its file, line, and column in the program, which can be
+				used to tell apart the events of a dead-letter queue by where they were aborted.
+				Programs can also call `set_abort_reason` before aborting, to record a reason
+				code under `reason_code`, such as `"validation"` or `429`, that routes can match
+				on.
 				"""
 		},
 	]
@@ -89,6 +89,77 @@ components: transforms: route: {
 		},
 	]
 
+	how_it_works: {
+		weighted_routes: {
+			title: "Weighted routes"
+			body: """
+				Routes can be given a weight in `weights` to only receive a percentage of the events matching
+				their condition, for example to send a small share of the traffic to a canary
+				deployment, or to progressively migrate it to a new backend:
+
+				```yaml
+				transforms:
+				  split:
+				    type: route
+				    inputs: ["app"]
+				    route:
+				      canary: '.service == "api"'
+				      stable: '.service == "api"'
+				    weights:
+				      canary: 5
+				      stable: 95
+				    weight_key: "{{ user_id }}"
+				```
+
+				Weighted routes are assigned consecutive ranges of percentages in the order they are
+				declared, and each event falls into one of these ranges, so that weighted routes with
+				the same condition receive disjoint shares of the events. When `weight_key` is set,
+				the range of each event is determined by the hash of its key, so that the events of a
+				given user are always sent to the same route, even across restarts. Otherwise, events
+				are spread evenly across the ranges.
+				"""
+		}
+
+		dynamic_routes: {
+			title: "Dynamic routes"
+			body: """
+				Instead of being declared as routes, the outputs of events can be resolved at runtime by
+				a VRL expression in `dynamic.source`, among the outputs declared in `dynamic.outputs`.
+				Looking the output up in an enrichment table allows changing the mapping of tenants to
+				destinations by updating the table, without reloading the configuration:
+
+				```yaml
+				enrichment_tables:
+				  tenants:
+				    type: file
+				    file:
+				      path: /etc/vector/tenants.csv
+				      encoding:
+				        type: csv
+				    schema:
+				      id: string
+				      output: string
+
+				transforms:
+				  by_tenant:
+				    type: route
+				    inputs: ["app"]
+				    route:
+				      errors: '.level == "error"'
+				    dynamic:
+				      source: 'get_enrichment_table_record!("tenants", { "id": .tenant_id }).output'
+				      outputs: ["us", "eu"]
+				```
+
+				Events are sent to the output the expression resolves to in addition to the routes they
+				match. Events for which the expression fails, or resolves to an output that isn't
+				declared, are logged as errors and are only sent to the routes they match, or to the
+				`_unmatched` output if they don't match any. The expression is read-only: it can't
+				modify the events.
+				"""
+		}
+	}
+
 	outputs: [
 		{
 			name:        "<route_id>"
@@ -0,0 +1,85 @@
+package metadata
+
+components: transforms: schema_enforce: {
+	title: "Schema Enforce"
+
+	description: """
+		Validates log events against a [JSON Schema](https://json-schema.org), routing the events
+		violating it to a separate `failed` output annotated with the details of the violations.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.schema_enforce.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		supported_keywords: {
+			title: "Supported keywords"
+			body: """
+				Schemas follow the 2020-12 draft of JSON Schema. The following keywords are
+				supported:
+
+				* `type`, `enum`, and `const`
+				* `minimum`, `maximum`, `exclusiveMinimum`, `exclusiveMaximum`, and `multipleOf`
+				* `minLength`, `maxLength`, and `pattern`
+				* `items`, `prefixItems`, `contains`, `minItems`, `maxItems`, and `uniqueItems`
+				* `properties`, `patternProperties`, `additionalProperties`, `required`,
+				  `dependentRequired`, `minProperties`, and `maxProperties`
+				* `allOf`, `anyOf`, `oneOf`, `not`, and `if`/`then`/`else`
+				* `$ref`, to the root of the schema (`#`) or to its `$defs` (`#/$defs/<name>`)
+
+				Other keywords, such as `format`, are ignored, as are unknown keywords. Schemas with
+				references to other documents are rejected.
+
+				Timestamps are validated as strings. For logs using the `Legacy` log namespace, the
+				schema applies to the whole event, including fields such as `timestamp` and `host`
+				added by sources.
+				"""
+		}
+
+		type_coercion: {
+			title: "Type coercion"
+			body: """
+				When `coerce_types` is enabled, values of the wrong type are converted to the `type`
+				required by their schema where the conversion is unambiguous: numeric strings to
+				integers and numbers, `"true"` and `"false"` to booleans, and numbers and booleans to
+				strings. Values are only coerced by the schemas they must match, not by the
+				subschemas of `anyOf`, `oneOf`, `not`, or `if`.
+				"""
+		}
+	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "failed"
+			description: """
+				Events violating the schema are sent to the `failed` output, unchanged, and annotated
+				with the list of their violations under the field named by
+				`violations_metadata_key`. For a transform component named `foo`, this output can be
+				accessed by specifying `foo.failed` as the input to another component.
+				"""
+		},
+	]
+}
@@ -0,0 +1,95 @@
+package metadata
+
+components: transforms: wasm: {
+	title: "WebAssembly"
+
+	description: """
+		Process events with a [WebAssembly](\(urls.wasm)) component, so that custom logic can be
+		written in [any language compiling to WebAssembly](\(urls.wasm_languages)) and changed
+		without recompiling Vector.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		program: {
+			runtime: {
+				name:    "Wasmtime"
+				url:     urls.wasmtime
+				version: "25"
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.wasm.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		interface: {
+			title: "Component interface"
+			body:  """
+				The `module` must be a [WebAssembly component](\(urls.wasm_component_model))
+				targeting WASI 0.2, and exporting the following interface:
+
+				```wit
+				package vector:transform@0.1.0;
+
+				interface processor {
+				    process: func(event: string) -> result<list<string>, string>;
+				}
+
+				world plugin {
+				    export processor;
+				}
+				```
+
+				Each event is passed to `process` encoded as a JSON object, and is replaced by the
+				events returned, each also encoded as a JSON object. The returned events keep the
+				metadata of the processed event, such as its acknowledgement. Returning an empty list
+				drops the event, and returning an error drops the event and reports the error with
+				the `component_errors_total` metric.
+
+				The component has no access to the filesystem, network, or environment of Vector.
+				Its standard error is forwarded to the one of Vector.
+				"""
+		}
+		fuel_metering: {
+			title: "Fuel metering"
+			body:  """
+				The processing of each event can consume up to `fuel` units of fuel, which roughly
+				correspond to the WebAssembly instructions executed, so that infinite loops can't
+				hang the transform. An event whose processing runs out of fuel is dropped, and the
+				component is instantiated anew, losing any state it held, before the next event is
+				processed. The same happens when the component traps. Calls into the component
+				periodically yield while consuming fuel, so that long computations don't block
+				the other components running on the same thread.
+				"""
+		}
+		hot_reload: {
+			title: "Hot reload"
+			body:  """
+				Every `reload_interval_secs`, the modification time of the `module` file is checked.
+				When it changed, the component is compiled and instantiated again, and processes the
+				following events, without restarting Vector. If the new component fails to load, the
+				error is reported and the previous component keeps processing the events, until the
+				file changes again.
+				"""
+		}
+	}
+}
@@ -37,6 +37,24 @@ configuration: {
 			}
 		}
 
+		disk_buffer_budget: {
+			common: false
+			description: """
+				The total amount of disk space shared between all disk buffers.
+
+				Disk buffers configured with a `weight` instead of a `max_size` are allocated a
+				share of this budget proportional to their weight, once the space used by disk
+				buffers with a fixed `max_size` has been subtracted. Each buffer then applies its
+				own `when_full` behavior when its share is used up.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [53687091200]
+				unit: "bytes"
+			}
+		}
+
 		expire_metrics: {
 			common: false
 			description: """
@@ -97,7 +115,9 @@ configuration: {
 				[`remap`](\(urls.vector_remap_transform)) transform. Currently supported are:
 
 				* [CSV](\(urls.csv)) files
+				* [CSV](\(urls.csv)) or [JSON](\(urls.json)) documents downloaded over HTTP
 				* [MaxMind](\(urls.maxmind)) databases
+				* [Redis](\(urls.redis)) servers
 
 				For the lookup in the enrichment tables to be as performant as possible, the data is indexed according
 				to the fields that are used in the search. Note that indices can only be created for fields for which an
@@ -117,7 +137,9 @@ configuration: {
 						enum: {
 							"file":  "Enrich data from a CSV file."
 							"geoip": "Enrich data from a [GeoIp](\(urls.maxmind_geoip2)) [MaxMind](\(urls.maxmind)) database."
+							"http":  "Enrich data from a CSV or JSON document downloaded from an HTTP endpoint."
 							"mmdb":  "Enrich data from any [MaxMind](\(urls.maxmind)) database."
+							"redis": "Enrich data from a live [Redis](\(urls.redis)) server."
 						}
 					}
 				}
@@ -275,6 +297,180 @@ configuration: {
 						}
 					}
 				}
+				http: {
+					required:    true
+					description: """
+						Configuration options for a table downloaded from an HTTP endpoint.
+
+						The table is downloaded when Vector starts, and then again every
+						`reload_interval_secs` seconds. Each successful download replaces the table as a
+						whole, including its indexes, without restarting Vector. When a download fails,
+						the previous version of the table keeps being used.
+						"""
+					type: object: options: {
+						endpoint: {
+							description: "The URL to download the table from."
+							required:    true
+							type: string: {
+								examples: ["https://example.com/allow-list.csv", "http://127.0.0.1:8080/tenants.json"]
+							}
+						}
+						encoding: {
+							description: "Encoding of the data served by the endpoint."
+							required:    false
+							common:      true
+							type: object: options: {
+								type: {
+									description: """
+										The encoding of the data. Either `csv`, or `json` for an array of objects, each
+										object being a row.
+										"""
+									required: false
+									common:   true
+									type: string: default: "csv"
+								}
+								include_headers: {
+									description: "Whether or not the CSV data contains column headers."
+									required:    false
+									common:      false
+									type: bool: default: true
+								}
+								delimiter: {
+									description: "The delimiter used to separate fields in each row of the CSV data."
+									required:    false
+									common:      false
+									type: string: default: ","
+								}
+							}
+						}
+						reload_interval_secs: {
+							description: "How often to download the table again, in seconds."
+							required:    false
+							common:      true
+							type: uint: {
+								default: 300
+								unit:    "seconds"
+							}
+						}
+						timeout_secs: {
+							description: "The maximum time, in seconds, to wait for a download to complete."
+							required:    false
+							common:      false
+							type: uint: {
+								default: 30
+								unit:    "seconds"
+							}
+						}
+						headers: {
+							description: "HTTP headers to add to the request."
+							required:    false
+							common:      false
+							type: object: {
+								examples: [{"Authorization": "Bearer ${TOKEN}"}]
+								options: {}
+							}
+						}
+						schema: {
+							description: _coercing_fields
+							required:    false
+							common:      false
+							type: object: {
+								examples: [{seats: "int", active: "bool"}]
+								options: {}
+							}
+						}
+					}
+				}
+				redis: {
+					required:    true
+					description: """
+						Configuration options for looking up rows in a live [Redis](\(urls.redis)) server.
+
+						This enrichment table only supports lookup with a single equality condition. The
+						looked up value, prepended with `key_prefix`, is the Redis key of the row. Rows
+						are cached for `cache_ttl_secs` seconds, and lookups missing the cache block
+						event processing until Redis answers.
+						"""
+					type: object: options: {
+						url: {
+							description: """
+								The Redis URL to connect to.
+
+								The URL must take the form of `protocol://server:port/db` where the `protocol` can either be `redis` or `rediss` for connections secured using TLS.
+								"""
+							required: true
+							type: string: {
+								examples: ["redis://127.0.0.1:6379/0"]
+							}
+						}
+						data_type: {
+							description: "How rows are stored in Redis."
+							required:    false
+							common:      true
+							type: string: {
+								default: "hash"
+								enum: {
+									hash: """
+										Each row is stored as a hash, and read with `HGETALL`.
+
+										All values of the row are strings.
+										"""
+									string: "Each row is stored as a string holding a JSON object, and read with `GET`."
+								}
+							}
+						}
+						key_prefix: {
+							description: """
+								A prefix prepended to the looked up value to build the Redis key of a row.
+
+								For example, with a prefix of `tenant:`, looking up `acme` reads the key `tenant:acme`.
+								"""
+							required: false
+							common:   true
+							type: string: {
+								default: ""
+								examples: ["tenant:"]
+							}
+						}
+						cache_ttl_secs: {
+							description: """
+								The time, in seconds, for which a row read from Redis is cached.
+
+								Keys missing from Redis are cached as well. Set to `0` to disable caching, in which case
+								every lookup queries Redis.
+								"""
+							required: false
+							common:   true
+							type: uint: {
+								default: 60
+								unit:    "seconds"
+							}
+						}
+						cache_max_entries: {
+							description: """
+								The maximum number of rows kept in the cache.
+
+								Once reached, the least recently used rows are evicted first.
+								"""
+							required: false
+							common:   false
+							type: uint: default: 10000
+						}
+						request_timeout_secs: {
+							description: """
+								The maximum time, in seconds, to wait for Redis to answer a lookup.
+
+								Lookups missing the cache block event processing for up to this long.
+								"""
+							required: false
+							common:   false
+							type: uint: {
+								default: 1
+								unit:    "seconds"
+							}
+						}
+					}
+				}
 			}
 		}
 		schema: {
@@ -471,6 +667,52 @@ configuration: {
 			}
 		}
 
+		keyspaces: {
+			common: false
+			description: """
+				Named in-memory key/value stores shared by all `remap` transforms. Programs read and
+				write a keyspace by passing its name as the `keyspace` argument of the
+				[`get_state`](\(urls.vrl_functions)/#get_state) and
+				[`set_state`](\(urls.vrl_functions)/#set_state) functions, which allows correlating
+				events across transforms. Keyspaces keep their entries across configuration reloads,
+				but not across restarts, and keyspaces removed from the configuration are dropped on
+				reload.
+				"""
+			required: false
+			type: object: options: {
+				"*": {
+					description: "A keyspace, keyed by its name."
+					required:    true
+					type: object: options: {
+						max_keys: {
+							common: true
+							description: """
+								The maximum number of keys held by the keyspace. Once reached, setting
+								a new key evicts the expired keys, or else the key set the longest ago.
+								"""
+							required: false
+							type: uint: {
+								default: 100000
+								unit:    null
+							}
+						}
+						default_ttl_secs: {
+							common: true
+							description: """
+								The time to live of the keys set without a `ttl_secs`, in seconds. If not
+								set, such keys never expire.
+								"""
+							required: false
+							type: uint: {
+								examples: [3600]
+								unit: "seconds"
+							}
+						}
+					}
+				}
+			}
+		}
+
 		secret: {
 			common: false
 			description: """
@@ -557,6 +799,241 @@ configuration: {
 								examples: ["/secret/foo-bar"]
 							}
 						}
+						refresh_interval_secs: {
+							description: """
+								How often to check the secret for changes. When it changed, Vector reloads its
+								configuration with the new values. If unset, the secret is only retrieved when the
+								configuration is loaded.
+								"""
+							required: false
+							common:   false
+							type: uint: {
+								default: null
+								unit:    "seconds"
+							}
+						}
+					}
+				}
+				aws_ssm_parameter_store: {
+					required: true
+					description: """
+						Retrieve secrets from AWS Systems Manager Parameter Store.
+
+						Each secret key is appended to `prefix` to form the name of the parameter to retrieve, so with a
+						`prefix` of `/vector/production/`, `SECRET[<backend_name>.db_password]` resolves the
+						`/vector/production/db_password` parameter. `SecureString` parameters are decrypted.
+
+						The region and credentials are resolved from the usual AWS provider chains when not configured.
+
+						If an error occurred retrieving the secrets, Vector logs the error and exits.
+
+						Secrets are loaded when Vector starts or if Vector receives a `SIGHUP` signal triggering its
+						configuration reload process, and periodically if `refresh_interval_secs` is set.
+						"""
+					type: object: options: {
+						prefix: {
+							description: "Prefix prepended to each secret key to form the name of the parameter to resolve."
+							required:    false
+							common:      true
+							type: string: {
+								default: ""
+								examples: ["/vector/production/"]
+							}
+						}
+						with_decryption: {
+							description: "Whether to decrypt `SecureString` parameters."
+							required:    false
+							common:      false
+							type: bool: default: true
+						}
+						refresh_interval_secs: {
+							description: """
+								How often to check the parameters for changes. When any of them changed, Vector reloads
+								its configuration with the new values. If unset, the parameters are only retrieved when
+								the configuration is loaded.
+								"""
+							required: false
+							common:   false
+							type: uint: {
+								default: null
+								unit:    "seconds"
+							}
+						}
+					}
+				}
+				hashicorp_vault: {
+					required: true
+					description: """
+						Retrieve secrets from a HashiCorp Vault KV version 2 secrets engine.
+
+						Each key of the secret's data can be referenced as `SECRET[<backend_name>.<key>]`. String,
+						number, and boolean values are supported.
+
+						Vector authenticates with either a Vault token or the AppRole auth method. Renewable tokens are
+						renewed in the background before their lease expires and reused when the configuration is
+						reloaded, until they reach their maximum TTL.
+
+						If an error occurred retrieving the secrets, Vector logs the error and exits.
+
+						Secrets are loaded when Vector starts or if Vector receives a `SIGHUP` signal triggering its
+						configuration reload process.
+						"""
+					type: object: options: {
+						endpoint: {
+							description: "The address of the Vault server."
+							required:    true
+							type: string: {
+								examples: ["https://vault.example.com:8200"]
+							}
+						}
+						namespace: {
+							description: "The Vault Enterprise namespace to send requests to."
+							required:    false
+							common:      false
+							type: string: {
+								default: null
+								examples: ["team-a"]
+							}
+						}
+						mount: {
+							description: "The path at which the KV version 2 secrets engine is mounted."
+							required:    false
+							common:      false
+							type: string: {
+								default: "secret"
+							}
+						}
+						path: {
+							description: "The path of the secret to resolve, relative to `mount`."
+							required:    true
+							type: string: {
+								examples: ["vector/credentials"]
+							}
+						}
+						version: {
+							description: "The version of the secret to resolve. The latest version is resolved if unset."
+							required:    false
+							common:      false
+							type: uint: {
+								default: null
+								unit:    null
+							}
+						}
+						auth: {
+							description: "The method used to authenticate with Vault."
+							required:    true
+							type: object: options: {
+								strategy: {
+									description: "The authentication strategy to use."
+									required:    true
+									type: string: enum: {
+										token:   "Authenticate with a Vault token."
+										approle: "Authenticate with the AppRole auth method."
+									}
+								}
+								token: {
+									description:   "The Vault token."
+									relevant_when: "strategy = \"token\""
+									required:      true
+									type: string: {
+										examples: ["${VAULT_TOKEN}"]
+									}
+								}
+								role_id: {
+									description:   "The role ID of the AppRole."
+									relevant_when: "strategy = \"approle\""
+									required:      true
+									type: string: {
+										examples: ["${VAULT_ROLE_ID}"]
+									}
+								}
+								secret_id: {
+									description:   "The secret ID of the AppRole."
+									relevant_when: "strategy = \"approle\""
+									required:      true
+									type: string: {
+										examples: ["${VAULT_SECRET_ID}"]
+									}
+								}
+								mount: {
+									description:   "The path at which the AppRole auth method is mounted."
+									relevant_when: "strategy = \"approle\""
+									required:      false
+									common:        false
+									type: string: {
+										default: "approle"
+									}
+								}
+							}
+						}
+						renew_token: {
+							description: "Whether to keep renewable Vault tokens alive in the background."
+							required:    false
+							common:      false
+							type: bool: default: true
+						}
+					}
+				}
+			}
+		}
+
+		templates: {
+			common: false
+			description: """
+				Parameterized sets of sources, transforms, and sinks that are declared once and instantiated
+				once per entry of their `instances`. Use `PARAM[<parameter_name>]` in the components of a
+				template to refer to the value of a parameter for the instance being expanded. See
+				[pipeline templates](#pipeline-templates) for more details.
+				"""
+			required: false
+			type: object: options: {
+				"*": {
+					description: "A template, keyed by its name."
+					required:    true
+					type: object: options: {
+						parameters: {
+							common:      true
+							description: "The parameters of the template, keyed by name."
+							required:    false
+							type: object: options: {
+								"*": {
+									description: "A parameter of the template."
+									required:    true
+									type: object: options: {
+										default: {
+											common:      false
+											description: "The value of the parameter for instances that don't set it. The parameter is required if unset."
+											required:    false
+											type: "*": {}
+										}
+									}
+								}
+							}
+						}
+						sources: {
+							common:      true
+							description: "The sources of the template, keyed by ID."
+							required:    false
+							type: object: {}
+						}
+						transforms: {
+							common:      true
+							description: "The transforms of the template, keyed by ID."
+							required:    false
+							type: object: {}
+						}
+						sinks: {
+							common:      true
+							description: "The sinks of the template, keyed by ID."
+							required:    false
+							type: object: {}
+						}
+						instances: {
+							common:      true
+							description: "The instances of the template, keyed by instance ID, each mapping parameter names to values."
+							required:    false
+							type: object: {}
+						}
 					}
 				}
 			}
@@ -813,6 +1290,64 @@ configuration: {
 				```
 				"""
 		}
+		pipeline_templates: {
+			title: "Pipeline templates"
+			body: """
+				When the same pipeline is needed for several tenants, indexes, or tokens, it can be declared once in
+				the `templates` section and instantiated with different parameters. Templates are expanded when the
+				configuration is loaded.
+
+				```yaml title="vector.yaml"
+				sources:
+					kafka:
+						type: "kafka"
+						bootstrap_servers: "kafka:9092"
+						group_id: "vector"
+						topics: ["logs"]
+
+				templates:
+					tenant:
+						parameters:
+							tenant: {}
+							token: {}
+							index:
+								default: "logs"
+						transforms:
+							filter:
+								type: "filter"
+								inputs: ["kafka"]
+								condition: '.tenant == "PARAM[tenant]"'
+						sinks:
+							splunk:
+								type: "splunk_hec_logs"
+								inputs: ["filter"]
+								endpoint: "https://splunk.example.com:8088"
+								default_token: "PARAM[token]"
+								index: "PARAM[index]"
+								encoding:
+									codec: "json"
+						instances:
+							acme:
+								tenant: "acme"
+								token: "SECRET[vault.acme_token]"
+							globex:
+								tenant: "globex"
+								token: "SECRET[vault.globex_token]"
+								index: "globex-logs"
+				```
+
+				Each component of an instance gets the ID of the component in the template prefixed with the instance
+				ID: the example above defines the `acme_filter`, `acme_splunk`, `globex_filter`, and `globex_splunk`
+				components. Inputs that refer to components of the same template, including their named outputs, are
+				prefixed the same way, while other inputs, like `kafka` above, are left as-is. Components of instances
+				can also be used as inputs of regular components through their prefixed IDs.
+
+				A value that consists of a single `PARAM[...]` placeholder takes the type of the parameter, so
+				parameters can also be used for numeric and boolean options. Vector refuses to load a configuration that
+				refers to undeclared parameters, that doesn't set the required parameters of an instance, or whose
+				instances define components with already existing IDs.
+				"""
+		}
 		wildcards: {
 			title: "Wildcards in component names"
 			body: """
@@ -0,0 +1,46 @@
+package metadata
+
+remap: functions: any: {
+	category: "Enumerate"
+	description: """
+		Check whether the closure returns `true` for any element of a collection, stopping at the first
+		element it does. Returns `false` for empty collections.
+
+		This function currently *does not* support recursive iteration.
+
+		The function uses the function closure syntax to allow reading
+		the key-value or index-value combination for each item in the
+		collection. The closure block must return a boolean.
+
+		The same scoping rules apply to closure blocks as they do for
+		regular blocks. This means that any variable defined in parent scopes
+		is accessible, and mutations to those variables are preserved,
+		but any new variables instantiated in the closure block are
+		unavailable outside of the block.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array or object to check."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["boolean"]
+	}
+	examples: [
+		{
+			title: "Check whether any tag matches"
+			input: log: {
+				tags: ["env:production", "team:platform"]
+			}
+			source: #"""
+				any(array!(.tags)) -> |_index, value| { value == "env:production" }
+				"""#
+			return: true
+		},
+	]
+}
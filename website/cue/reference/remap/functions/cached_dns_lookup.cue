@@ -0,0 +1,77 @@
+package metadata
+
+remap: functions: cached_dns_lookup: {
+	category: "Enrichment"
+	description: """
+		Sends a DNS query for the records of type `qtype` of the `value` domain name, and returns the data of the
+		records answering it as an array of strings, for enriching events with resolved hostnames without an
+		external enrichment service.
+		"""
+	notices: [
+		"""
+			The function is only available in Vector builds with the `vrl-functions-cached_dns` feature enabled,
+			which isn't part of the default features.
+			""",
+		"""
+			The answers are cached until the TTL of their records expires, each call to the function caching up to
+			1000 answers. The absence of records is cached for the negative TTL given by the authority of the
+			name, while other failures aren't cached.
+			""",
+		"""
+			The nameservers are read from the system configuration when the program is compiled. The event is
+			processed once the query is answered, so queries slow down the whole pipeline.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The domain name to query."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "qtype"
+			description: "The type of the records to query, such as `A`, `AAAA`, `MX`, or `TXT`. Must be a literal."
+			required:    false
+			type: ["string"]
+			default: "A"
+		},
+		{
+			name:        "timeout_secs"
+			description: "The amount of time, in seconds, to wait for an answer from a nameserver. Must be a literal."
+			required:    false
+			type: ["integer"]
+			default: 2
+		},
+		{
+			name:        "attempts"
+			description: "The number of times a query is sent before failing. Must be a literal."
+			required:    false
+			type: ["integer"]
+			default: 2
+		},
+	]
+	internal_failure_reasons: [
+		"No records of type `qtype` exist for `value`.",
+		"The query fails or times out.",
+	]
+	return: types: ["array"]
+
+	examples: [
+		{
+			title: "Resolve the addresses of a host"
+			source: #"""
+				cached_dns_lookup!("localhost.example.com")
+				"""#
+			return: ["127.0.0.1"]
+		},
+		{
+			title: "Resolve the mail exchangers of a domain"
+			source: #"""
+				cached_dns_lookup!("example.com", qtype: "MX", timeout_secs: 1)
+				"""#
+			return: ["10 mail.example.com."]
+		},
+	]
+}
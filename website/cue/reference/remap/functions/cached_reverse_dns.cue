@@ -0,0 +1,58 @@
+package metadata
+
+remap: functions: cached_reverse_dns: {
+	category: "Enrichment"
+	description: """
+		Sends a reverse DNS query for the `value` IP address, and returns the hostname it resolves to.
+		"""
+	notices: [
+		"""
+			The function is only available in Vector builds with the `vrl-functions-cached_dns` feature enabled,
+			which isn't part of the default features.
+			""",
+		"""
+			The hostnames are cached until the TTL of their records expires, each call to the function caching up
+			to 1000 hostnames. The absence of records is cached for the negative TTL given by the authority of
+			the address, while other failures aren't cached.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The IPv4 or IPv6 address to resolve."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "timeout_secs"
+			description: "The amount of time, in seconds, to wait for an answer from a nameserver. Must be a literal."
+			required:    false
+			type: ["integer"]
+			default: 2
+		},
+		{
+			name:        "attempts"
+			description: "The number of times a query is sent before failing. Must be a literal."
+			required:    false
+			type: ["integer"]
+			default: 2
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid IP address.",
+		"No hostname is registered for `value`.",
+		"The query fails or times out.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Resolve the hostname of an address"
+			source: #"""
+				cached_reverse_dns!("127.0.0.1")
+				"""#
+			return: "localhost"
+		},
+	]
+}
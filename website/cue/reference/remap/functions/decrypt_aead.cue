@@ -0,0 +1,67 @@
+package metadata
+
+remap: functions: decrypt_aead: {
+	category: "Cryptography"
+	description: """
+		Decrypts a string encrypted with an authenticated encryption algorithm, such as by `encrypt_aead`,
+		verifying that neither the ciphertext nor the additional authenticated data (AAD) was tampered with.
+
+		Supported Algorithms:
+
+		* AES-256-GCM (key = 32 bytes, iv = 12 bytes)
+		* AES-128-GCM (key = 16 bytes, iv = 12 bytes)
+		"""
+
+	arguments: [
+		{
+			name:        "ciphertext"
+			description: "The string in raw bytes (not encoded) to decrypt, with the authentication tag appended."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The algorithm to use."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "key"
+			description: "The key in raw bytes (not encoded) for decryption. The length must match the algorithm requested."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "iv"
+			description: "The IV (nonce) in raw bytes (not encoded) the value was encrypted with."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "aad"
+			description: "The additional authenticated data the value was encrypted with."
+			required:    false
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`algorithm` is not a supported algorithm.",
+		"`key` length does not match the key size required for the algorithm specified.",
+		"`iv` length does not match the `iv` size required for the algorithm specified.",
+		"The ciphertext, `iv` or `aad` doesn't match the authentication tag.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decrypt value"
+			source: #"""
+				ciphertext = decode_base64!("ox+pG2aU8rwuD1/APFEo9U53vp6F0nrmAljEzD64N14Hlqk3")
+				iv = "123456789012"
+				key = "01234567890123456789012345678912"
+				decrypt_aead!(ciphertext, "AES-256-GCM", key, iv: iv, aad: "user-1234")
+				"""#
+			return: "super secret message"
+		},
+	]
+}
@@ -0,0 +1,62 @@
+package metadata
+
+remap: functions: encode_avro: {
+	category: "Codec"
+	description: """
+		Encodes the `value` as an [Avro single-object encoded](\(urls.avro_single_object_encoding)) payload, with the
+		given Avro schema.
+		"""
+	notices: [
+		"""
+			Record fields missing from the `value` are encoded as `null`, and values of unions as the first variant
+			of the union they match. Timestamps can only be encoded as the `timestamp-millis` and `timestamp-micros`
+			logical types, other logical types are encoded from their underlying values.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The value to encode."
+			required:    true
+			type: ["any"]
+		},
+		{
+			name: "schema"
+			description: """
+				The Avro schema to encode the value with, as JSON. Must be a literal string.
+
+				Required if `schema_file` is missing.
+				"""
+			required: false
+			type: ["string"]
+		},
+		{
+			name: "schema_file"
+			description: """
+				The path of the file holding the Avro schema to encode the value with. Must be a literal string.
+
+				Required if `schema` is missing.
+				"""
+			required: false
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` doesn't match the schema.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode a record as Avro"
+			source: #"""
+				encode_base64(encode_avro!(
+					{ "message": "hello" },
+					schema: s'{"type": "record", "name": "Message", "fields": [{"name": "message", "type": "string"}]}'
+				))
+				"""#
+			return: "wwEXzGcl6JXfawpoZWxsbw=="
+		},
+	]
+}
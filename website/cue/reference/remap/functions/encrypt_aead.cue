@@ -0,0 +1,71 @@
+package metadata
+
+remap: functions: encrypt_aead: {
+	category: "Cryptography"
+	description: """
+		Encrypts a string with an authenticated encryption algorithm, optionally binding it to additional
+		authenticated data (AAD). The authentication tag is appended to the returned ciphertext, and
+		`decrypt_aead` fails if either the ciphertext or the AAD is tampered with.
+
+		Supported Algorithms:
+
+		* AES-256-GCM (key = 32 bytes, iv = 12 bytes)
+		* AES-128-GCM (key = 16 bytes, iv = 12 bytes)
+		"""
+
+	arguments: [
+		{
+			name:        "plaintext"
+			description: "The string to encrypt."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The algorithm to use."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "key"
+			description: "The key in raw bytes (not encoded) for encryption. The length must match the algorithm requested."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "iv"
+			description: #"""
+				The IV (nonce) in raw bytes (not encoded) for encryption. The length must match the algorithm requested.
+				An IV must never be reused with the same key. You can use `random_bytes` to generate a cryptographically secure random value.
+				"""#
+			required: true
+			type: ["string"]
+		},
+		{
+			name:        "aad"
+			description: "Additional data which isn't encrypted, but must be provided unchanged to decrypt the ciphertext, such as the ID of the record the value belongs to."
+			required:    false
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`algorithm` is not a supported algorithm.",
+		"`key` length does not match the key size required for the algorithm specified.",
+		"`iv` length does not match the `iv` size required for the algorithm specified.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encrypt value bound to additional authenticated data"
+			source: #"""
+				plaintext = "super secret message"
+				iv = "123456789012" # typically you would call random_bytes(12)
+				key = "01234567890123456789012345678912"
+				encrypted_message = encrypt_aead!(plaintext, "AES-256-GCM", key, iv: iv, aad: "user-1234")
+				encode_base64(encrypted_message)
+				"""#
+			return: "ox+pG2aU8rwuD1/APFEo9U53vp6F0nrmAljEzD64N14Hlqk3"
+		},
+	]
+}
@@ -0,0 +1,58 @@
+package metadata
+
+remap: functions: get_state: {
+	category: "Event"
+	description: """
+		Returns the value of the `key` in the state of the `remap` transform, as set by
+		[`set_state`](\(urls.vrl_functions)/#set_state).
+		"""
+	notices: [
+		"""
+			Without a `keyspace`, this function is only available in `remap` transforms configured
+			with `state`.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key to get the value of."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "keyspace"
+			description: """
+				The name of the keyspace to use instead of the state of the transform. Keyspaces are
+				configured in the top-level `keyspaces` section and shared by all `remap` transforms.
+				"""
+			required: false
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["any"]
+		rules: [
+			"Returns `null` if the `key` is missing or expired.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Get a value set by a previous event"
+			source: #"""
+				set_state!("last_user", "alice")
+				get_state("last_user")
+				"""#
+			return: "alice"
+		},
+		{
+			title: "Get a missing key"
+			source: #"""
+				get_state("last_login")
+				"""#
+			return: null
+		},
+	]
+}
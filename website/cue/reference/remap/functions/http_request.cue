@@ -0,0 +1,123 @@
+package metadata
+
+remap: functions: http_request: {
+	category: "Enrichment"
+	description: """
+		Sends an HTTP request to the `url` and returns the response, as an object of its `status`, `headers` and
+		`body`, for enriching events with data that can't be loaded into an enrichment table beforehand.
+		"""
+	notices: [
+		"""
+			The function is only available in Vector builds with the `vrl-functions-http_request` feature enabled,
+			which isn't part of the default features.
+			""",
+		"""
+			The host of the `url` must be one of the `allowed_hosts`, which must be a literal. A host prefixed with
+			`*.` allows all of its subdomains. Literal URLs are checked when the program is compiled.
+			Redirects aren't followed, as they could lead to any host, so redirect responses are returned as is.
+			""",
+		"""
+			The event is processed once the response is received, so requests slow down the whole pipeline. The
+			successful responses to `GET` requests are cached for `cache_ttl_secs`, each call to the function caching
+			up to 1000 responses.
+			""",
+		"""
+			After `failure_threshold` consecutive requests fail, or are answered with a server error, the function
+			fails without sending any request for `cooldown_secs`.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "url"
+			description: "The URL to send the request to, using the `http` or `https` scheme."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "allowed_hosts"
+			description: "The hosts requests can be sent to. Must be a literal array of strings."
+			required:    true
+			type: ["array"]
+		},
+		{
+			name:        "method"
+			description: "The method of the request. Must be a literal string."
+			required:    false
+			type: ["string"]
+			default: "GET"
+			enum: {
+				GET:    "The `GET` method."
+				HEAD:   "The `HEAD` method."
+				POST:   "The `POST` method."
+				PUT:    "The `PUT` method."
+				PATCH:  "The `PATCH` method."
+				DELETE: "The `DELETE` method."
+			}
+		},
+		{
+			name:        "headers"
+			description: "The headers of the request, as an object of string values."
+			required:    false
+			type: ["object"]
+		},
+		{
+			name:        "body"
+			description: "The body of the request."
+			required:    false
+			type: ["string"]
+		},
+		{
+			name:        "timeout_secs"
+			description: "The amount of time, in seconds, to wait for the response. Must be a literal."
+			required:    false
+			type: ["integer"]
+			default: 5
+		},
+		{
+			name: "cache_ttl_secs"
+			description: """
+				The amount of time, in seconds, successful responses to `GET` requests are cached for. Set to `0` to
+				disable caching. Must be a literal.
+				"""
+			required: false
+			type: ["integer"]
+			default: 60
+		},
+		{
+			name:        "failure_threshold"
+			description: "The number of consecutive failed requests opening the circuit breaker. Must be a literal."
+			required:    false
+			type: ["integer"]
+			default: 5
+		},
+		{
+			name: "cooldown_secs"
+			description: """
+				The amount of time, in seconds, the circuit breaker stays open for before requests are sent again.
+				Must be a literal.
+				"""
+			required: false
+			type: ["integer"]
+			default: 30
+		},
+	]
+	internal_failure_reasons: [
+		"`url` isn't a valid `http` or `https` URL.",
+		"The host of `url` isn't one of the `allowed_hosts`.",
+		"The request fails or times out.",
+		"The circuit breaker is open.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Look up a user"
+			source: #"""
+				response = http_request!("https://users.example.com/users/42", allowed_hosts: ["users.example.com"])
+				response.status
+				"""#
+			return: 200
+		},
+	]
+}
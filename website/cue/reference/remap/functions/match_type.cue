@@ -0,0 +1,59 @@
+package metadata
+
+remap: functions: match_type: {
+	category: "Type"
+	description: """
+		Returns the case of `cases` named by the type of `value`, or `default` if there's no
+		case for this type. Only the returned case, or the default, is evaluated.
+
+		The cases are named by the types `array`, `boolean`, `float`, `integer`, `null`,
+		`object`, `regex`, `string`, and `timestamp`. The type of the returned value is
+		inferred from the cases of the types `value` can be. The function can return `null`
+		if these cases aren't exhaustive and `default` is missing, and it can only fail if
+		one of these cases, or the default when it's used, can fail.
+
+		Cases are resolved when compiling the program if `value` is a constant, and the cases
+		of the types `value` can't be are discarded, so they don't cost anything when
+		processing events.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The value to match on the type of."
+			required:    true
+			type: ["any"]
+		},
+		{
+			name:        "cases"
+			description: "An object literal with the expression to return for each type."
+			required:    true
+			type: ["object"]
+		},
+		{
+			name:        "default"
+			description: "The expression to return for the types without a case."
+			required:    false
+			type: ["any"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["any"]
+	}
+	examples: [
+		{
+			title: "Normalize a field by type"
+			input: log: {
+				status: "404"
+			}
+			source: #"""
+				match_type(.status, {
+				    "integer": .status,
+				    "string": to_int(.status) ?? 0,
+				}, default: 0)
+				"""#
+			return: 404
+		},
+	]
+}
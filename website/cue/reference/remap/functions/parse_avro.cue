@@ -0,0 +1,64 @@
+package metadata
+
+remap: functions: parse_avro: {
+	category: "Parse"
+	description: """
+		Parses the `value` as an [Avro single-object encoded](\(urls.avro_single_object_encoding)) payload, given the
+		Avro schema it was written with.
+		"""
+	notices: [
+		"""
+			The `date`, `time-*` and `*timestamp-*` logical types are returned as the integers they are encoded as,
+			`enum` symbols and `uuid` values as strings, and `fixed` values as bytes. The `decimal` and `duration`
+			logical types aren't supported.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The Avro single-object encoded payload to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "schema"
+			description: """
+				The Avro schema of the payload, as JSON. Must be a literal string.
+
+				Required if `schema_file` is missing.
+				"""
+			required: false
+			type: ["string"]
+		},
+		{
+			name: "schema_file"
+			description: """
+				The path of the file holding the Avro schema of the payload. Must be a literal string.
+
+				Required if `schema` is missing.
+				"""
+			required: false
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` doesn't start with the single-object marker followed by the fingerprint of the schema.",
+		"`value` isn't a valid Avro payload for the schema.",
+		"`value` holds `decimal` or `duration` values.",
+	]
+	return: types: ["any"]
+
+	examples: [
+		{
+			title: "Parse an Avro single-object encoded record"
+			source: #"""
+				parse_avro!(
+					decode_base64!("wwEXzGcl6JXfawpoZWxsbw=="),
+					schema: s'{"type": "record", "name": "Message", "fields": [{"name": "message", "type": "string"}]}'
+				)
+				"""#
+			return: message: "hello"
+		},
+	]
+}
@@ -0,0 +1,109 @@
+package metadata
+
+remap: functions: parse_leef: {
+	category: "Parse"
+	description: """
+		Parses the `value` in [LEEF](\(urls.leef)) (Log Event Extended Format) format, versions 1.0 and 2.0.
+		Ignores everything up to the LEEF header, such as a syslog prefix. The header fields are returned at the top
+		level of the object, and the event attributes under `attributes`.
+		"""
+	notices: [
+		"""
+			All values are returned as strings. We recommend manually coercing values to desired types as you see fit.
+			""",
+		"""
+			The attribute delimiter is, in order of precedence: the `delimiter` argument, the delimiter declared in a
+			LEEF 2.0 header (as a single character or as a hexadecimal code point prefixed with `x` or `0x`), a tab if
+			the attributes contain one, and finally the first of `|`, `^`, `;`, `,` and space which only separates
+			`key=value` pairs.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "delimiter"
+			description: """
+				The delimiter of the attributes, overriding the one declared in the header or detected. Either a single
+				character or a hexadecimal code point prefixed with `x` or `0x`.
+				"""
+			required: false
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is not a properly formatted LEEF string.",
+		"`value` uses an unsupported LEEF version.",
+		"`delimiter` is not a valid delimiter.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse a LEEF 1.0 event"
+			source: #"""
+				parse_leef!(
+					"LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=10.50.1.1\tdst=2.10.20.20\tspt=1200"
+				)
+				"""#
+			return: {
+				"leefVersion":    "1.0"
+				"vendor":         "Microsoft"
+				"productName":    "MSExchange"
+				"productVersion": "4.0 SP1"
+				"eventId":        "15345"
+				"attributes": {
+					"src": "10.50.1.1"
+					"dst": "2.10.20.20"
+					"spt": "1200"
+				}
+			}
+		},
+		{
+			title: "Parse a LEEF 2.0 event with a custom delimiter"
+			source: #"""
+				parse_leef!(
+					"LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^sev=5^srcPort=81^dstPort=21"
+				)
+				"""#
+			return: {
+				"leefVersion":    "2.0"
+				"vendor":         "Lancope"
+				"productName":    "StealthWatch"
+				"productVersion": "1.0"
+				"eventId":        "41"
+				"attributes": {
+					"src":     "10.0.1.8"
+					"dst":     "10.0.0.5"
+					"sev":     "5"
+					"srcPort": "81"
+					"dstPort": "21"
+				}
+			}
+		},
+		{
+			title: "Parse a LEEF 2.0 event with a hexadecimal delimiter"
+			source: #"""
+				parse_leef!(
+					"<13>Jan 18 11:07:53 host LEEF:2.0|Vendor|Product|2.1|login|x7C|usrName=admin|src=192.168.0.1"
+				)
+				"""#
+			return: {
+				"leefVersion":    "2.0"
+				"vendor":         "Vendor"
+				"productName":    "Product"
+				"productVersion": "2.1"
+				"eventId":        "login"
+				"attributes": {
+					"usrName": "admin"
+					"src":     "192.168.0.1"
+				}
+			}
+		},
+	]
+}
@@ -0,0 +1,80 @@
+package metadata
+
+remap: functions: parse_named_groks: {
+	category:    "Parse"
+	description: """
+		Parses the `value` using an ordered list of named [`grok`](\(urls.grok)) patterns, returning the
+		captures of the first pattern that matches along with the name of that pattern. All patterns
+		[listed here](\(urls.grok_patterns)) are supported.
+
+		This avoids chaining `parse_grok` calls with `??` to parse the logs of sources emitting several
+		formats, while still telling which format each event was in.
+		"""
+	notices: [
+		"""
+			We recommend using community-maintained Grok patterns when possible, as they're more likely to be properly
+			vetted and improved over time than bespoke patterns.
+			""",
+		"""
+			Only named captures are returned, and captures which didn't match anything are omitted.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "patterns"
+			description: """
+				The patterns to try in order until the first match, each an object with the `name` the
+				pattern is reported under and the Grok `pattern` itself.
+				"""
+			required: true
+			type: ["array"]
+		},
+		{
+			name:        "aliases"
+			description: "The shared set of grok aliases that can be referenced in the patterns to simplify them."
+			required:    false
+			type: ["object"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` fails to parse using any of the provided `patterns`.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse using the first matching pattern"
+			source: #"""
+				parse_named_groks!(
+					"2020-10-02T23:22:12.223222Z info Hello world",
+					patterns: [
+						{ "name": "access", "pattern": "%{_client} %{_request}" },
+						{ "name": "application", "pattern": "%{_timestamp} %{_loglevel} %{_message}" },
+					],
+					aliases: {
+						"_client": "%{IPORHOST:client}",
+						"_request": "%{WORD:method} %{URIPATHPARAM:path}",
+						"_timestamp": "%{TIMESTAMP_ISO8601:timestamp}",
+						"_loglevel": "%{LOGLEVEL:level}",
+						"_message": "%{GREEDYDATA:message}"
+					}
+				)
+				"""#
+			return: {
+				pattern: "application"
+				captures: {
+					timestamp: "2020-10-02T23:22:12.223222Z"
+					level:     "info"
+					message:   "Hello world"
+				}
+			}
+		},
+	]
+}
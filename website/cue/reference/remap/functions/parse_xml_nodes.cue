@@ -0,0 +1,131 @@
+package metadata
+
+remap: functions: parse_xml_nodes: {
+	category: "Parse"
+	description: """
+		Extracts the elements of the XML document in `value` selected by `paths`, parsed the same way as by
+		`parse_xml`. The document is read as a stream and only the selected elements are built, which makes this
+		function much faster and lighter than `parse_xml` when only a small part of a large document, such as a
+		SOAP payload, is of interest.
+
+		The result is an object with an array of the elements selected by each path, keyed by the path.
+		"""
+	notices: [
+		"""
+			Paths are a subset of XPath made of element names separated by `/`. A path starting with `/` selects
+			elements from the root of the document, and any other path selects them at any depth, as if it started
+			with `//`. A `*` segment matches any element. Names are compared to the local name of elements, unless
+			they have a namespace prefix, such as `soap:Body`, in which case they are compared to the qualified name.
+			""",
+		"""
+			A selected element is extracted as a whole, so paths aren't matched against its descendants. Text and
+			attributes outside of the selected elements are ignored.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string representation of the XML document to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "paths"
+			description: "The paths of the elements to extract."
+			required:    true
+			type: ["array"]
+		},
+		{
+			name:        "include_attr"
+			description: "Include XML tag attributes in the returned objects."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+		{
+			name:        "attr_prefix"
+			description: "String prefix to use for XML tag attribute keys."
+			required:    false
+			default:     "@"
+			type: ["string"]
+		},
+		{
+			name:        "text_key"
+			description: "Key name to use for expanded text nodes."
+			required:    false
+			default:     "text"
+			type: ["string"]
+		},
+		{
+			name:        "always_use_text_key"
+			description: "Always return text nodes as `{\"<text_key>\": \"value\"}.`"
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+		{
+			name:        "parse_bool"
+			description: "Parse \"true\" and \"false\" as boolean."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+		{
+			name:        "parse_null"
+			description: "Parse \"null\" as null."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+		{
+			name:        "parse_number"
+			description: "Parse numbers as integers/floats."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is not a valid XML document.",
+		"One of `paths` isn't a valid path.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Extract the items of a SOAP response"
+			source: #"""
+				parse_xml_nodes!(
+					s'<soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope"><soap:Body><GetItemsResponse><Item id="1"><Name>foo</Name></Item><Item id="2"><Name>bar</Name></Item></GetItemsResponse></soap:Body></soap:Envelope>',
+					["/Envelope/Body/GetItemsResponse/Item"]
+				)
+				"""#
+			return: {
+				"/Envelope/Body/GetItemsResponse/Item": [
+					{
+						"@id":  1
+						"Name": "foo"
+					},
+					{
+						"@id":  2
+						"Name": "bar"
+					},
+				]
+			}
+		},
+		{
+			title: "Extract elements at any depth"
+			source: #"""
+				parse_xml_nodes!(
+					s'<root><a><status>ok</status></a><b><status>failed</status></b></root>',
+					["//status", "/root/c"]
+				)
+				"""#
+			return: {
+				"//status": ["ok", "failed"]
+				"/root/c": []
+			}
+		},
+	]
+}
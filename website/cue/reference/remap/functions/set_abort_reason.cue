@@ -0,0 +1,34 @@
+package metadata
+
+remap: functions: set_abort_reason: {
+	category: "Event"
+	description: """
+		Sets the reason code of the next `abort` of the program.
+
+		When the program aborts, the `remap` transform annotates the events rerouted to its
+		`dropped` output with the code, under `reason_code`. Codes set by a program are
+		discarded when the next program runs, and only the last code set before aborting is
+		recorded.
+		"""
+
+	arguments: [
+		{
+			name:        "code"
+			description: "The reason code, such as `\"validation\"` or `429`."
+			required:    true
+			type: ["integer", "string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["null"]
+
+	examples: [
+		{
+			title: "Set the reason code of an abort"
+			source: #"""
+				set_abort_reason("rate_limited")
+				"""#
+			return: null
+		},
+	]
+}
@@ -0,0 +1,78 @@
+package metadata
+
+remap: functions: set_state: {
+	category: "Event"
+	description: """
+		Sets the `key` to the `value` in the state of the `remap` transform, which is shared by all
+		the events it processes. The value can be read back by
+		[`get_state`](\(urls.vrl_functions)/#get_state).
+		"""
+	notices: [
+		"""
+			Without a `keyspace`, this function is only available in `remap` transforms configured
+			with `state`.
+			""",
+		"""
+			Setting a new key once the state or keyspace holds `max_keys` keys evicts the expired
+			keys, or else the key set the longest ago.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key to set."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "value"
+			description: "The value to set the key to. Setting a key to `null` removes it."
+			required:    true
+			type: ["any"]
+		},
+		{
+			name:        "ttl_secs"
+			description: """
+				The amount of time, in seconds, after which the key expires. Defaults to the
+				`default_ttl_secs` of the keyspace, if any.
+				"""
+			required:    false
+			type: ["integer"]
+		},
+		{
+			name:        "keyspace"
+			description: """
+				The name of the keyspace to use instead of the state of the transform. Keyspaces are
+				configured in the top-level `keyspaces` section and shared by all `remap` transforms.
+				"""
+			required: false
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"The state or keyspace is configured with a `max_keys` of zero.",
+		"`ttl_secs` is negative.",
+	]
+	return: types: ["null"]
+
+	examples: [
+		{
+			title: "Count events"
+			source: #"""
+				set_state!("events", (int(get_state("events")) ?? 0) + 1)
+				"""#
+			return: null
+		},
+		{
+			title: "Track the first time a user was seen"
+			source: #"""
+				if get_state("first_seen:alice") == null {
+					set_state!("first_seen:alice", "2024-05-01T12:00:00Z", ttl_secs: 86400)
+				}
+				get_state("first_seen:alice")
+				"""#
+			return: "2024-05-01T12:00:00Z"
+		},
+	]
+}
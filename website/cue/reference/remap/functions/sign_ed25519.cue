@@ -0,0 +1,41 @@
+package metadata
+
+remap: functions: sign_ed25519: {
+	category: "Cryptography"
+	description: """
+		Signs the `value` with the [Ed25519](\(urls.ed25519)) `private_key`, returning the 64 bytes signature.
+
+		For most use cases, the resulting bytestream should be encoded into a hex or base64
+		string using either [encode_base16](\(urls.vrl_functions)/#encode_base16) or
+		[encode_base64](\(urls.vrl_functions)/#encode_base64).
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to sign."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "private_key"
+			description: "The 32 bytes private key (seed) in raw bytes (not encoded)."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`private_key` is not 32 bytes long.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Sign a message"
+			source: #"""
+				encode_base64(sign_ed25519!("Hello there", private_key: "00000000000000000000000000000000"))
+				"""#
+			return: "+hDVC8ZIPy/c3JD+EkBFs3q9xkXOyVSodcqoxW417TCIhL/h7W61+XRjYDZSI9YzqaJQ/dnoDIAx76O0UCNPBQ=="
+		},
+	]
+}
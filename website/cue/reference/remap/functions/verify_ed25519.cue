@@ -0,0 +1,48 @@
+package metadata
+
+remap: functions: verify_ed25519: {
+	category: "Cryptography"
+	description: """
+		Checks that `signature` is a valid [Ed25519](\(urls.ed25519)) signature of the `value` by the owner of
+		the `public_key`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The signed string."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "signature"
+			description: "The signature in raw bytes (not encoded)."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "public_key"
+			description: "The 32 bytes public key in raw bytes (not encoded)."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`public_key` is not 32 bytes long.",
+	]
+	return: types: ["boolean"]
+
+	examples: [
+		{
+			title: "Verify a signature"
+			source: #"""
+				verify_ed25519!(
+					"Hello there",
+					signature: decode_base64!("+hDVC8ZIPy/c3JD+EkBFs3q9xkXOyVSodcqoxW417TCIhL/h7W61+XRjYDZSI9YzqaJQ/dnoDIAx76O0UCNPBQ=="),
+					public_key: decode_base64!("G6QHW3fJ4/s+zeFc2vUiHzwQNz5iP3sOHvdjZrCvcTc=")
+				)
+				"""#
+			return: true
+		},
+	]
+}
@@ -0,0 +1,62 @@
+package metadata
+
+remap: functions: verify_hmac: {
+	category: "Cryptography"
+	description: """
+		Checks that `signature` is the [HMAC](\(urls.hmac)) of the `value` using the given `key`, such as
+		to validate the signature of a webhook payload. Unlike comparing the result of `hmac` with the
+		signature, the comparison takes constant time, so it doesn't leak the expected signature.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The signed string."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "signature"
+			description: "The HMAC in raw bytes (not encoded)."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "key"
+			description: "The string to use as the cryptographic key."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The hashing algorithm to use."
+			enum: {
+				"SHA1":    "SHA1 algorithm"
+				"SHA-256": "SHA-256 algorithm"
+				"SHA-384": "SHA-384 algorithm"
+				"SHA-512": "SHA-512 algorithm"
+			}
+			required: false
+			default:  "SHA-256"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`algorithm` is not a supported algorithm.",
+	]
+	return: types: ["boolean"]
+
+	examples: [
+		{
+			title: "Verify the signature of a webhook payload"
+			source: #"""
+				verify_hmac!(
+					"Hello there",
+					signature: decode_base16!("78b184f1832f8aff3934f5e0212454671b2d04d494e3b25075c5e45167029662"),
+					key: "super-secret-key"
+				)
+				"""#
+			return: true
+		},
+	]
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: gcp_bigquery: {
+	name:     "GCP BigQuery"
+	thing:    "a \(name) table"
+	url:      urls.gcp_bigquery
+	versions: null
+
+	description: "[GCP BigQuery](\(urls.gcp_bigquery)) is a fully-managed, serverless data warehouse that enables scalable analysis over large datasets with SQL."
+}
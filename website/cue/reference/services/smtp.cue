@@ -0,0 +1,10 @@
+package metadata
+
+services: smtp: {
+	name:     "SMTP"
+	thing:    "an \(name) server"
+	url:      urls.smtp
+	versions: null
+
+	description: "[SMTP](\(urls.smtp)) (Simple Mail Transfer Protocol) is the standard protocol for relaying and submitting email."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: snmp: {
+	name:     "SNMP"
+	thing:    "\(name) agents"
+	url:      urls.snmp
+	versions: null
+
+	description: "The [Simple Network Management Protocol (SNMP)](\(urls.snmp)) is a protocol for monitoring and managing network devices, which send traps to notify managers of events."
+}
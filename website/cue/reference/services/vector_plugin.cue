@@ -0,0 +1,8 @@
+package metadata
+
+services: vector_plugin: {
+	name:     "Vector plugin"
+	thing:    "a \(name)"
+	url:      urls.vector_protocol
+	versions: null
+}
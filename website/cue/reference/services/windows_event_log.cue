@@ -0,0 +1,10 @@
+package metadata
+
+services: windows_event_log: {
+	name:     "Windows Event Log"
+	thing:    "the \(name)"
+	url:      urls.windows_event_log
+	versions: null
+
+	description: "The [Windows Event Log](\(urls.windows_event_log)) records the events of the system, security, and applications of Windows hosts, such as service state changes, logons, and application errors."
+}
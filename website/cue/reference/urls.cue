@@ -4,9 +4,11 @@ urls: {
 	appsignal:                                  "https://www.appsignal.com/"
 	azure_blob_storage:                         "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	azure_event_hubs:                           "https://learn.microsoft.com/en-us/azure/event-hubs/"
+	azure_event_hubs_amqp:                      "https://learn.microsoft.com/en-us/azure/service-bus-messaging/service-bus-amqp-overview"
 	azure_event_hubs_kafka:                     "https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-for-kafka-ecosystem-overview"
 	azure_event_hubs_connection_string:         "https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-get-connection-string"
 	azure_event_hubs_tiers:                     "https://learn.microsoft.com/en-us/azure/event-hubs/compare-tiers"
+	azure_event_hubs_checkpoint_store:          "https://learn.microsoft.com/en-us/azure/event-hubs/event-processor-balance-partition-load"
 	affine_type_system:                         "\(wikipedia)/wiki/Substructural_type_system#Affine_type_systems"
 	adaptive_request_concurrency_post:          "/blog/adaptive-request-concurrency/"
 	amazon_linux:                               "https://aws.amazon.com/amazon-linux-ami/"
@@ -21,6 +23,7 @@ urls: {
 	apache_mod_status:                          "http://httpd.apache.org/docs/current/mod/mod_status.html"
 	apt:                                        "\(wikipedia)/wiki/APT_(software)"
 	arm:                                        "\(wikipedia)/wiki/ARM_architecture"
+	avro_single_object_encoding:                "https://avro.apache.org/docs/current/specification/#single-object-encoding"
 	aws_access_keys:                            "\(aws_docs)/IAM/latest/UserGuide/id_credentials_access-keys.html"
 	aws_arm_g2_announcement:                    "https://aws.amazon.com/about-aws/whats-new/2019/12/announcing-new-amazon-ec2-m6g-c6g-and-r6g-instances-powered-by-next-generation-arm-based-aws-graviton2-processors/"
 	aws_athena:                                 "https://aws.amazon.com/athena/"
@@ -110,6 +113,7 @@ urls: {
 	cgroups_limit_resources:                    "https://the.binbashtheory.com/control-resources-cgroups/"
 	clickhouse:                                 "https://clickhouse.com/"
 	clickhouse_http:                            "https://clickhouse.com/docs/en/interfaces/http/"
+	clickhouse_native:                          "https://clickhouse.com/docs/en/interfaces/tcp"
 	community_id_spec:                          "https://github.com/corelight/community-id-spec"
 	console:                                    "\(wikipedia)/wiki/System_console"
 	contributing:                               "\(vector_repo)/blob/master/CONTRIBUTING.md#setup"
@@ -173,6 +177,7 @@ urls: {
 	dpkg:                                       "https://wiki.debian.org/dpkg"
 	dry_code:                                   "\(wikipedia)/wiki/Don%27t_repeat_yourself"
 	cidr:                                       "\(wikipedia)/wiki/Classless_Inter-Domain_Routing"
+	ed25519:                                    "https://ed25519.cr.yp.to/"
 	elastic_beats:                              "https://www.elastic.co/beats/"
 	elasticsearch:                              "https://www.elastic.co/products/elasticsearch"
 	elasticsearch_bulk:                         "https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html"
@@ -203,6 +208,8 @@ urls: {
 	fluentbit:                                  "https://fluentbit.io/"
 	freebsd:                                    "https://www.freebsd.org/"
 	gcp:                                        "https://cloud.google.com"
+	gcp_bigquery:                               "\(gcp)/bigquery"
+	gcp_bigquery_storage_write_api:             "\(gcp)/bigquery/docs/write-api"
 	gcp_authentication:                         "\(gcp)/docs/authentication/"
 	gcp_authentication_api_key:                 "\(gcp)/docs/authentication/api-keys"
 	gcp_authentication_server_to_server:        "\(gcp)/docs/authentication/production"
@@ -307,6 +314,7 @@ urls: {
 	kubernetes_request_verbs:                   "\(kubernetes)/docs/reference/access-authn-authz/authorization/#determine-the-request-verb"
 	kubernetes_watch_api:                       "\(kubernetes)/docs/reference/generated/kubernetes-api/v1.10/#watch-30"
 	lapin:                                      "https://crates.io/crates/lapin"
+	leef:                                       "https://www.ibm.com/docs/en/dsm?topic=overview-leef-event-components"
 	leveldb:                                    "\(github)/google/leveldb"
 	leveldb_sys_2:                              "https://crates.io/crates/leveldb-sys"
 	leveldb_sys_3:                              "\(github)/vectordotdev/leveldb-sys/tree/v3.0.0"
@@ -321,6 +329,7 @@ urls: {
 	log_namespacing_blog:                       "/blog/log-namespacing/"
 	loki:                                       "https://grafana.com/oss/loki/"
 	loki_multi_tenancy:                         "\(github)/grafana/loki/blob/master/docs/operations/multi-tenancy.md"
+	loki_push_api:                              "https://grafana.com/docs/loki/latest/reference/loki-http-api/#ingest-logs"
 	log_event_source:                           "\(vector_repo)/blob/master/src/event/"
 	logplex:                                    "https://devcenter.heroku.com/articles/logplex"
 	logplex_protocol:                           "\(github)/heroku/logplex/blob/master/doc/README.http_drains.md"
@@ -358,6 +367,8 @@ urls: {
 	memory_safety:                              "\(wikipedia)/wiki/Memory_safety"
 	memory_safety_bugs:                         "https://thenewstack.io/microsoft-rust-is-the-industrys-best-chance-at-safe-systems-programming/"
 	metric_event_source:                        "\(vector_repo)/blob/master/src/event/metric.rs"
+	microsoft_teams:                            "https://www.microsoft.com/en-us/microsoft-teams/"
+	microsoft_teams_webhooks:                   "https://learn.microsoft.com/en-us/microsoftteams/platform/webhooks-and-connectors/how-to/add-incoming-webhook"
 	mlua:                                       "\(github)/khvzak/mlua"
 	mongodb:                                    "https://www.mongodb.com"
 	mongodb_command_server_status:              "https://docs.mongodb.com/manual/reference/command/serverStatus/"
@@ -386,11 +397,15 @@ urls: {
 	nix:                                        "https://nixos.org/nix/"
 	nixos:                                      "https://nixos.org/"
 	nixpkgs_9682:                               "\(github)/NixOS/nixpkgs/issues/9682"
+	openmetrics_exemplars:                      "https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md#exemplars"
 	openssl:                                    "https://www.openssl.org/"
 	openssl_conf:                               "https://www.openssl.org/docs/man3.1/man5/config.html"
 	opentelemetry:                              "https://opentelemetry.io"
 	opentelemetry_protocol:                     "\(opentelemetry)/docs/reference/specification/protocol/otlp/"
 	order_of_ops:                               "\(wikipedia)/wiki/Order_of_operations"
+	pagerduty:                                  "https://www.pagerduty.com/"
+	pagerduty_events_api:                       "https://developer.pagerduty.com/docs/events-api-v2/overview/"
+	pagerduty_rate_limits:                      "https://developer.pagerduty.com/docs/events-api-v2-overview#api-limits"
 	papertrail:                                 "https://www.papertrail.com/"
 	papertrail_syslog:                          "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
 	perl_windows:                               "https://www.perl.org/get.html#win32"
@@ -474,10 +489,23 @@ urls: {
 	sha2:                                       "\(wikipedia)/wiki/SHA-2"
 	sha3:                                       "\(wikipedia)/wiki/SHA-3"
 	signal:                                     "\(wikipedia)/wiki/Signal_(IPC)"
+	slack:                                      "https://slack.com/"
+	slack_rate_limits:                          "https://api.slack.com/apis/rate-limits"
+	slack_threads:                              "https://api.slack.com/messaging/retrieving#finding_threads"
+	slack_webhooks:                             "https://api.slack.com/messaging/webhooks"
+	smtp:                                       "https://datatracker.ietf.org/doc/html/rfc5321"
+	smtp_starttls:                              "https://datatracker.ietf.org/doc/html/rfc3207"
 	snake_case:                                 "\(wikipedia)/wiki/Snake_case"
 	snappy:                                     "https://google.github.io/snappy/"
+	snmp:                                       "\(wikipedia)/wiki/Simple_Network_Management_Protocol"
+	snmp_usm:                                   "https://datatracker.ietf.org/doc/html/rfc3414"
+	snowflake:                                  "https://www.snowflake.com"
+	snowflake_key_pair_auth:                    "https://docs.snowflake.com/en/user-guide/key-pair-auth"
+	snowflake_snowpipe_streaming:               "https://docs.snowflake.com/en/user-guide/snowpipe-streaming/snowpipe-streaming-high-performance-overview"
 	socket:                                     "\(wikipedia)/wiki/Network_socket"
 	splunk:                                     "https://www.splunk.com"
+	splunk_forwarder:                           "https://docs.splunk.com/Documentation/Forwarder/latest/Forwarder/Abouttheuniversalforwarder"
+	splunk_forwarder_outputs:                   "https://docs.splunk.com/Documentation/Forwarder/latest/Forwarder/Configureforwardingwithoutputs.conf"
 	splunk_hec:                                 "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
 	splunk_hec_channel_header:                  "https://docs.splunk.com/Documentation/Splunk/8.2.4/Data/FormateventsforHTTPEventCollector#Channel_identifier_header"
 	splunk_hec_event_endpoint:                  "https://docs.splunk.com/Documentation/Splunk/8.0.0/RESTREF/RESTinput#services.2Fcollector.2Fevent"
@@ -534,6 +562,7 @@ urls: {
 	user_agent:                                 "https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/User-Agent"
 	vector_agent_role:                          "/docs/setup/deployment/roles/#agent"
 	vector_aggregator_role:                     "/docs/setup/deployment/roles/#aggregator"
+	vector_api:                                 "/docs/reference/api/"
 	vector_aws_s3_sink:                         "/docs/reference/configuration/sinks/aws_s3/"
 	vector_aws_s3_sink_auth:                    "/docs/reference/configuration/sinks/aws_s3/#auth"
 	vector_aws_s3_sink_how_it_works:            "/docs/reference/configuration/sinks/aws_s3/#how-it-works"
@@ -590,6 +619,7 @@ urls: {
 	vector_parsing_transforms:                  "/components/?functions%5B%5D=parse"
 	vector_performance:                         "\(vector_repo)/#performance"
 	vector_privacy_policy:                      "\(vector_repo)/blob/master/PRIVACY.md"
+	vector_protocol:                            "\(vector_repo)/blob/master/proto/vector/vector.proto"
 	vector_programmable_transforms:             "/components/?functions%5B%5D=program"
 	vector_pull_requests:                       "\(vector_repo)/pulls"
 	vector_readme:                              "\(vector_repo)/blob/master/README.md"
@@ -654,10 +684,14 @@ urls: {
 	vrl_type_safety:                            "\(vrl_reference)#type-safety"
 	vote_feature:                               "\(vector_repo)/issues?q=is%3Aissue+is%3Aopen+sort%3Areactions-%2B1-desc+label%3A%22Type%3A+New+Feature%22"
 	wasm:                                       "https://webassembly.org/"
+	wasm_component_model:                       "https://component-model.bytecodealliance.org"
 	wasm_languages:                             "\(github)/appcypher/awesome-wasm-langs"
+	wasmtime:                                   "https://wasmtime.dev"
 	websocket:                                  "\(wikipedia)/wiki/WebSocket"
 	wikipedia:                                  "https://en.wikipedia.org"
 	windows:                                    "https://www.microsoft.com/en-us/windows"
+	windows_event_log:                          "https://learn.microsoft.com/en-us/windows/win32/wes/windows-event-log"
+	windows_event_log_xpath:                    "https://learn.microsoft.com/en-us/windows/win32/wes/consuming-events#xpath-10-limitations"
 	windows_installer:                          "\(wikipedia)/wiki/Windows_Installer"
 	windows_service:                            "https://docs.microsoft.com/en-us/powershell/module/microsoft.powershell.management/new-service"
 	woothee:                                    "https://github.com/woothee/woothee"